@@ -0,0 +1,29 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct DamageVignette {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+@group(1) @binding(0) var src_color: texture_2d<f32>;
+@group(1) @binding(1) var src_sampler: sampler;
+
+@group(2) @binding(0) var<uniform> vignette: DamageVignette;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(src_color, src_sampler, in.uv);
+    if (vignette.intensity <= 0.0) {
+        return color;
+    }
+
+    // 0 at the center of the screen, about 1.4 in the corners
+    let offset = (in.uv - vec2<f32>(0.5)) * 2.0;
+    let edge = smoothstep(0.4, 1.4, length(offset));
+    let amount = clamp(edge * vignette.intensity, 0.0, 1.0);
+
+    // Keep the scene's brightness so the tint reads in daylight and at night
+    let luminance = max(dot(color.rgb, vec3<f32>(0.2126, 0.7152, 0.0722)), 0.05);
+    let tinted = mix(color.rgb, vignette.color * luminance * 2.0, amount);
+    return vec4<f32>(tinted, color.a);
+}
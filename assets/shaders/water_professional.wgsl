@@ -13,6 +13,8 @@ struct WaveMaterial {
     shallow_color: vec3<f32>,
     deep_color: vec3<f32>,
     foam_color: vec3<f32>,
+    sky_zenith: vec3<f32>,
+    sky_horizon: vec3<f32>,
     roughness: f32,
     fresnel_bias: f32,
     fresnel_power: f32,
@@ -154,8 +156,9 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     // Base water color
     let water_color = material.base_color.rgb;
     
-    // Simple sky/environment reflection color (will improve with IBL)
-    let sky_color = vec3<f32>(0.35, 0.45, 0.65);
+    // Sky reflection from the same gradient the sky probe is baked from
+    let R = reflect(V, N);
+    let sky_color = mix(material.sky_horizon, material.sky_zenith, sqrt(clamp(R.y, 0.0, 1.0)));
     
     // Mix water color with sky reflection based on Fresnel
     let final_color = mix(water_color, sky_color, fresnel);
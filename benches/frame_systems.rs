@@ -0,0 +1,331 @@
+//! Frame-time regression benchmarks for the per-frame world systems.
+//!
+//! Each benchmark builds a headless scene of 1k, 5k or 20k entities on the
+//! shared test app and times one `app.update()` of the systems under test:
+//! LOD (visibility range rescaling, HLOD swaps), culling (parked car
+//! impostors), spatial queries (index refresh plus radius lookups, distance
+//! cache refresh) and instance batching. Time advances half a second per update so throttled
+//! systems run on every iteration.
+//!
+//! `cargo xtask bench --save <name>` records a baseline and
+//! `cargo xtask bench --baseline <name>` fails if any benchmark got slower.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use gta_game::components::{
+    ActiveEntity, Building, BuildingType, Car, ContentType, CurrentInterior, DynamicContent,
+    HlodClusters, MainCamera, MovementTracker,
+};
+use gta_game::config::{ConfigReloadedEvent, GameConfig, GraphicsQuality};
+use gta_game::systems::distance_cache::{DistanceBand, DistanceCache, update_distance_cache};
+use gta_game::systems::performance::frame_budget::FrameBudgetGovernor;
+use gta_game::systems::rendering::car_impostors::parked_car_impostor_system;
+use gta_game::systems::rendering::graphics_quality::apply_lod_bias;
+use gta_game::systems::rendering::{CarImpostor, InstancedBatcher, Streetlight, upload_instances};
+use gta_game::systems::spatial_index::{SpatialIndex, update_spatial_index};
+use gta_game::systems::world::hlod::{bake_hlod_proxies, hlod_swap_system, register_hlod_members};
+use gta_game::systems::world::unified_world::ChunkCoord;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const SCENE_SIZES: [usize; 3] = [1_000, 5_000, 20_000];
+/// Side of the square the scene is spread over, in metres
+const SCENE_EXTENT: f32 = 3000.0;
+const CHUNK_SIZE: f32 = 200.0;
+
+/// Position of entity `i` of `count`, on a grid filling the scene square
+fn grid_position(i: usize, count: usize) -> Vec3 {
+    let side = (count as f32).sqrt().ceil() as usize;
+    let spacing = SCENE_EXTENT / side as f32;
+    Vec3::new(
+        (i % side) as f32 * spacing - SCENE_EXTENT * 0.5,
+        0.0,
+        (i / side) as f32 * spacing - SCENE_EXTENT * 0.5,
+    )
+}
+
+/// Test app with the game config and a camera/player focus at the origin
+fn scene_app() -> App {
+    let mut app = common::setup_test_app();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        0.5,
+    )))
+    .init_resource::<GameConfig>()
+    .init_resource::<FrameBudgetGovernor>()
+    .add_event::<ConfigReloadedEvent>();
+    // Virtual time caps each step at 250 ms by default
+    app.world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .set_max_delta(Duration::from_secs(1));
+    app.world_mut().spawn((
+        MainCamera,
+        ActiveEntity,
+        Transform::default(),
+        GlobalTransform::default(),
+    ));
+    app
+}
+
+fn move_focus(app: &mut App, position: Vec3) {
+    let mut focus = app
+        .world_mut()
+        .query_filtered::<(&mut Transform, &mut GlobalTransform), With<MainCamera>>();
+    for (mut transform, mut global) in focus.iter_mut(app.world_mut()) {
+        transform.translation = position;
+        *global = GlobalTransform::from(*transform);
+    }
+}
+
+fn bench_lod(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lod");
+    group.sample_size(20);
+
+    for count in SCENE_SIZES {
+        // Every preset change rescales every range in the scene
+        let mut app = scene_app();
+        app.add_systems(Update, apply_lod_bias);
+        for i in 0..count {
+            app.world_mut().spawn((
+                Transform::from_translation(grid_position(i, count)),
+                bevy::render::view::visibility::VisibilityRange::abrupt(0.0, 300.0),
+            ));
+        }
+        app.update();
+        group.bench_with_input(
+            BenchmarkId::new("visibility_rescale", count),
+            &count,
+            |b, _| {
+                b.iter(|| {
+                    let mut config = app.world_mut().resource_mut::<GameConfig>();
+                    config.graphics.quality = match config.graphics.quality {
+                        GraphicsQuality::High => GraphicsQuality::Low,
+                        _ => GraphicsQuality::High,
+                    };
+                    app.world_mut().send_event(ConfigReloadedEvent);
+                    app.update();
+                })
+            },
+        );
+
+        // The focus jumps across the map, so every cluster swaps each update
+        let mut app = scene_app();
+        app.init_resource::<HlodClusters>()
+            .init_resource::<CurrentInterior>()
+            .add_systems(
+                Update,
+                (register_hlod_members, bake_hlod_proxies, hlod_swap_system).chain(),
+            );
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(Color::WHITE);
+        for i in 0..count {
+            app.world_mut().spawn((
+                Building {
+                    building_type: BuildingType::Generic,
+                    height: 20.0,
+                    scale: Vec3::new(10.0, 20.0, 10.0),
+                },
+                Transform::from_translation(grid_position(i, count)),
+                MeshMaterial3d(material.clone()),
+                Visibility::default(),
+            ));
+        }
+        // Proxies bake a couple of clusters per frame
+        common::run_app_updates(&mut app, 64);
+        let mut far = false;
+        group.bench_with_input(BenchmarkId::new("hlod_swap", count), &count, |b, _| {
+            b.iter(|| {
+                far = !far;
+                move_focus(&mut app, Vec3::splat(if far { 20_000.0 } else { 0.0 }));
+                app.update();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_culling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("culling");
+    group.sample_size(20);
+
+    for count in SCENE_SIZES {
+        let mut app = scene_app();
+        app.init_resource::<InstancedBatcher<CarImpostor>>()
+            .init_resource::<SpatialIndex>()
+            .add_systems(
+                Update,
+                (parked_car_impostor_system, upload_instances::<CarImpostor>).chain(),
+            )
+            .add_systems(PostUpdate, update_spatial_index);
+        for i in 0..count {
+            app.world_mut().spawn((
+                Car,
+                DynamicContent {
+                    content_type: ContentType::Vehicle,
+                },
+                Transform::from_translation(grid_position(i, count)),
+                Visibility::default(),
+            ));
+        }
+        app.update();
+        // Alternating focus flips which half of the cars are impostors
+        let mut side = 1.0;
+        group.bench_with_input(BenchmarkId::new("car_impostors", count), &count, |b, _| {
+            b.iter(|| {
+                side = -side;
+                move_focus(&mut app, Vec3::new(side * SCENE_EXTENT * 0.25, 0.0, 0.0));
+                app.update();
+            })
+        });
+        // Driving speed: only cars near the impostor distance are revisited
+        let mut travelled = 0.0;
+        group.bench_with_input(
+            BenchmarkId::new("car_impostors_driving", count),
+            &count,
+            |b, _| {
+                b.iter(|| {
+                    travelled = (travelled + 10.0) % SCENE_EXTENT;
+                    move_focus(
+                        &mut app,
+                        Vec3::new(travelled - SCENE_EXTENT * 0.5, 0.0, 0.0),
+                    );
+                    app.update();
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_spatial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spatial");
+    group.sample_size(20);
+
+    for count in SCENE_SIZES {
+        let mut app = scene_app();
+        app.init_resource::<SpatialIndex>()
+            .add_systems(Update, update_spatial_index);
+        let entities: Vec<Entity> = (0..count)
+            .map(|i| {
+                app.world_mut()
+                    .spawn((
+                        DynamicContent {
+                            content_type: ContentType::Vehicle,
+                        },
+                        Transform::from_translation(grid_position(i, count)),
+                    ))
+                    .id()
+            })
+            .collect();
+        app.update();
+
+        // A tenth of the entities move each frame, then every tenth one looks around itself
+        let mut frame = 0;
+        group.bench_with_input(
+            BenchmarkId::new("index_update_and_query", count),
+            &count,
+            |b, _| {
+                b.iter(|| {
+                    frame += 1;
+                    for entity in entities.iter().skip(frame % 10).step_by(10) {
+                        if let Some(mut transform) = app.world_mut().get_mut::<Transform>(*entity) {
+                            transform.translation.x += if frame % 2 == 0 { 1.0 } else { -1.0 };
+                        }
+                    }
+                    app.update();
+                    let index = app.world().resource::<SpatialIndex>();
+                    let mut found = 0;
+                    for i in (0..count).step_by(10) {
+                        found += index.query_radius(grid_position(i, count), 50.0).len();
+                    }
+                    found
+                })
+            },
+        );
+
+        // A tenth of the entities move past their tracker threshold each frame
+        let mut app = scene_app();
+        app.init_resource::<DistanceCache>()
+            .add_systems(Update, update_distance_cache);
+        let entities: Vec<Entity> = (0..count)
+            .map(|i| {
+                let position = grid_position(i, count);
+                app.world_mut()
+                    .spawn((
+                        MovementTracker::new(position, 5.0),
+                        Transform::from_translation(position),
+                    ))
+                    .id()
+            })
+            .collect();
+        app.update();
+
+        let mut frame = 0;
+        group.bench_with_input(BenchmarkId::new("distance_cache", count), &count, |b, _| {
+            b.iter(|| {
+                frame += 1;
+                for entity in entities.iter().skip(frame % 10).step_by(10) {
+                    if let Some(mut transform) = app.world_mut().get_mut::<Transform>(*entity) {
+                        transform.translation.x += if frame % 2 == 0 { 6.0 } else { -6.0 };
+                    }
+                }
+                app.update();
+                app.world()
+                    .resource::<DistanceCache>()
+                    .in_band(DistanceBand::Near)
+                    .len()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_batching(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batching");
+    group.sample_size(20);
+
+    for count in SCENE_SIZES {
+        let mut app = scene_app();
+        app.init_resource::<InstancedBatcher<Streetlight>>()
+            .add_systems(Update, upload_instances::<Streetlight>);
+
+        // Every instance shifts each update, so every chunk re-uploads
+        let mut offset = 0.0;
+        group.bench_with_input(BenchmarkId::new("upload", count), &count, |b, _| {
+            b.iter(|| {
+                offset = if offset == 0.0 { 0.5 } else { 0.0 };
+                let mut chunks: HashMap<ChunkCoord, Vec<Transform>> = HashMap::new();
+                for i in 0..count {
+                    let position = grid_position(i, count) + Vec3::X * offset;
+                    let coord = ChunkCoord::new(
+                        (position.x / CHUNK_SIZE).floor() as i32,
+                        (position.z / CHUNK_SIZE).floor() as i32,
+                    );
+                    chunks
+                        .entry(coord)
+                        .or_default()
+                        .push(Transform::from_translation(position));
+                }
+                app.world_mut()
+                    .resource_mut::<InstancedBatcher<Streetlight>>()
+                    .set_all(chunks);
+                app.update();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_lod,
+    bench_culling,
+    bench_spatial,
+    bench_batching
+);
+criterion_main!(benches);
@@ -0,0 +1,169 @@
+//! Config schema export and validation, run through `cargo xtask config-schema`
+//! and `cargo xtask config-validate`.
+//!
+//! `schema` prints a JSON Schema for every layered config file, keyed by file
+//! name; `schema --template` prints each file's defaults as RON instead, ready
+//! to copy into `assets/config` or the user config directory.
+//!
+//! `validate <path>...` checks config files named like the ones in
+//! `assets/config`: the RON must parse, every field must exist (with a
+//! suggestion for near misses), and no value may be one the game would clamp.
+
+use gta_game::config::{CONFIG_FILES, GameConfig};
+use gta_game::util::config_schema::{changed_values, json_schema, unknown_fields};
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: config_tool schema [--template]
+       config_tool validate <path>...";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("schema") => schema(&args[1..]),
+        Some("validate") if args.len() > 1 => validate(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn schema(args: &[String]) -> Result<(), String> {
+    let template = match args {
+        [] => false,
+        [flag] if flag == "--template" => true,
+        _ => return Err(USAGE.to_string()),
+    };
+    let defaults = GameConfig::default();
+
+    if template {
+        for (filename, description) in CONFIG_FILES {
+            let ron = defaults.section_ron(filename).unwrap_or_default();
+            println!("// ---- {filename}: {description} ----\n{ron}\n");
+        }
+        return Ok(());
+    }
+
+    let mut files = serde_json::Map::new();
+    for (filename, description) in CONFIG_FILES {
+        let Some(section) = defaults.section_json(filename) else {
+            continue;
+        };
+        let mut schema = json_schema(&section);
+        schema["title"] = (*filename).into();
+        schema["description"] = (*description).into();
+        files.insert((*filename).to_string(), schema);
+    }
+    let document = serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "gta_game config files",
+        "type": "object",
+        "properties": files,
+    });
+    let text = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+    println!("{text}");
+    Ok(())
+}
+
+fn validate(paths: &[String]) -> Result<(), String> {
+    let mut problems = 0;
+    for path in paths {
+        let issues = check_file(Path::new(path));
+        if issues.is_empty() {
+            println!("{path}: ok");
+        }
+        for issue in &issues {
+            println!("{path}: {issue}");
+        }
+        problems += issues.len();
+    }
+    if problems == 0 {
+        Ok(())
+    } else {
+        Err(format!("{problems} problem(s) found"))
+    }
+}
+
+fn check_file(path: &Path) -> Vec<String> {
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if !CONFIG_FILES.iter().any(|(known, _)| *known == filename) {
+        let known: Vec<&str> = CONFIG_FILES.iter().map(|(name, _)| *name).collect();
+        return vec![format!(
+            "not a config file name; expected one of {}",
+            known.join(", ")
+        )];
+    }
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => return vec![format!("can't read: {e}")],
+    };
+    let user: ron::Value = match ron::from_str(&contents) {
+        Ok(value) => value,
+        Err(e) => return vec![format!("syntax error: {e}")],
+    };
+
+    let defaults = GameConfig::default();
+    let mut issues: Vec<String> = defaults
+        .section_json(&filename)
+        .map(|known| unknown_fields(&user, &known))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|field| match field.suggestion {
+            Some(suggestion) => format!(
+                "unknown field `{}`, did you mean `{suggestion}`?",
+                field.path
+            ),
+            None => format!("unknown field `{}`", field.path),
+        })
+        .collect();
+
+    let mut config = defaults;
+    if let Err(e) = config.apply_file(&filename, &contents) {
+        issues.push(format!("invalid value: {e}"));
+        return issues;
+    }
+    let mut clamped = config.clone();
+    clamped.validate_and_clamp();
+    if let (Some(given), Some(valid)) = (
+        config.section_json(&filename),
+        clamped.section_json(&filename),
+    ) {
+        for value in changed_values(&given, &valid) {
+            issues.push(format!(
+                "`{}` = {} is out of range, the game uses {}",
+                value.path, value.before, value.after
+            ));
+        }
+    }
+    // Streaming distances are clamped where the world reads them
+    if filename == "world_streaming.ron" {
+        for (field, given, valid) in [
+            (
+                "chunk_size",
+                config.world.chunk_size,
+                clamped.world.chunk_size,
+            ),
+            (
+                "streaming_radius",
+                config.world.streaming_radius,
+                clamped.world.streaming_radius,
+            ),
+        ] {
+            if given != valid {
+                issues.push(format!(
+                    "`{field}` = {given} is out of range, the game uses {valid}"
+                ));
+            }
+        }
+    }
+    issues
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+/// Caption currently on screen. `key` is a localization key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subtitle {
+    pub key: String,
+    /// Seconds left before the caption fades out
+    pub remaining: f32,
+}
+
+/// Captions for audio cues, oldest first
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Subtitles {
+    pub lines: Vec<Subtitle>,
+}
+
+impl Subtitles {
+    /// Shows `key` for at least `seconds`; a caption already up is extended
+    /// instead of repeated
+    pub fn show(&mut self, key: &str, seconds: f32) {
+        match self.lines.iter_mut().find(|line| line.key == key) {
+            Some(line) => line.remaining = line.remaining.max(seconds),
+            None => self.lines.push(Subtitle {
+                key: key.to_string(),
+                remaining: seconds,
+            }),
+        }
+    }
+
+    /// Counts captions down and drops the expired ones
+    pub fn tick(&mut self, dt: f32) {
+        for line in &mut self.lines {
+            line.remaining -= dt;
+        }
+        self.lines.retain(|line| line.remaining > 0.0);
+    }
+}
+
+/// Caption line at the bottom of the screen
+#[derive(Component)]
+pub struct SubtitleText;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_cue_extends_caption() {
+        let mut subtitles = Subtitles::default();
+        subtitles.show("subtitle.horn", 2.0);
+        subtitles.tick(1.5);
+        subtitles.show("subtitle.horn", 2.0);
+        subtitles.show("subtitle.siren", 0.5);
+        assert_eq!(subtitles.lines.len(), 2);
+
+        subtitles.tick(1.0);
+        assert_eq!(subtitles.lines.len(), 1);
+        assert_eq!(subtitles.lines[0].key, "subtitle.horn");
+    }
+}
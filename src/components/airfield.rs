@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+
+/// Paved strip aircraft take off from and land on
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Runway {
+    pub length: f32,
+    pub width: f32,
+}
+
+/// Marked ground helipad for helicopter landings (yacht decks use `Helipad`)
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LandingPad {
+    pub radius: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AirfieldKind {
+    Runway,
+    Helipad,
+}
+
+/// A runway or helipad as recorded by the airfield factory
+#[derive(Debug, Clone, Copy)]
+pub struct AirfieldEntry {
+    pub entity: Entity,
+    pub kind: AirfieldKind,
+    /// Center of the landing surface
+    pub position: Vec3,
+    /// Unit direction along the runway centerline (any horizontal direction for helipads)
+    pub heading: Vec3,
+    /// Runway length and width, or helipad diameter for both
+    pub size: Vec2,
+}
+
+impl AirfieldEntry {
+    /// Whether a world position is over the landing surface
+    pub fn contains(&self, position: Vec3) -> bool {
+        let offset = (position - self.position).with_y(0.0);
+        match self.kind {
+            AirfieldKind::Runway => {
+                let along = offset.dot(self.heading);
+                let across = offset.dot(self.heading.cross(Vec3::Y));
+                along.abs() <= self.size.x * 0.5 && across.abs() <= self.size.y * 0.5
+            }
+            AirfieldKind::Helipad => offset.length() <= self.size.x * 0.5,
+        }
+    }
+}
+
+/// Every runway and helipad in the world, for AI routing and the map
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AirfieldRegistry {
+    entries: Vec<AirfieldEntry>,
+}
+
+impl AirfieldRegistry {
+    pub fn register(&mut self, entry: AirfieldEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AirfieldEntry> {
+        self.entries.iter()
+    }
+
+    /// Closest airfield of `kind` to `position`
+    pub fn nearest(&self, kind: AirfieldKind, position: Vec3) -> Option<&AirfieldEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .min_by(|a, b| {
+                a.position
+                    .distance_squared(position)
+                    .total_cmp(&b.position.distance_squared(position))
+            })
+    }
+
+    /// Airfield whose landing surface is under `position`
+    pub fn surface_at(&self, position: Vec3) -> Option<&AirfieldEntry> {
+        self.entries.iter().find(|entry| entry.contains(position))
+    }
+}
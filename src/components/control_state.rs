@@ -49,8 +49,33 @@ pub struct ControlState {
     /// Interaction flag: enter/exit vehicle, interact with objects
     pub interact: bool,
 
+    /// Landing gear toggle flag: raise/lower aircraft gear (one-shot)
+    pub gear_toggle: bool,
+
+    /// Eject/bail-out flag: leave a moving vehicle mid-air (one-shot)
+    pub eject: bool,
+
+    /// Parachute flag: open the canopy while falling (one-shot)
+    pub deploy_parachute: bool,
+
+    /// Gear shift flags for manual transmissions (one-shot)
+    pub shift_up: bool,
+    pub shift_down: bool,
+
+    /// Switch between automatic and manual transmission (one-shot)
+    pub toggle_transmission: bool,
+
+    /// Cycle the headlights between auto, on and off (one-shot)
+    pub toggle_headlights: bool,
+
     /// Running/sprint modifier for walking
     pub run: bool,
+
+    /// Horn flag: sounds the horn while held
+    pub horn: bool,
+
+    /// Camera mode cycle flag: switch to the next camera view (one-shot)
+    pub cycle_camera: bool,
 }
 
 impl ControlState {
@@ -0,0 +1,167 @@
+use crate::components::SimpleCarSpecs;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Paint colors offered by the garage, cycled in order
+pub const PAINT_COLORS: [[f32; 4]; 6] = [
+    [0.8, 0.1, 0.1, 1.0],
+    [0.1, 0.3, 0.8, 1.0],
+    [0.95, 0.95, 0.95, 1.0],
+    [0.05, 0.05, 0.05, 1.0],
+    [0.9, 0.7, 0.1, 1.0],
+    [0.1, 0.6, 0.2, 1.0],
+];
+
+/// Tuning stages offered by the garage: stock, street, sport and race
+pub const TUNING_STAGES: [PerformanceTuning; 4] = [
+    PerformanceTuning {
+        top_speed: 1.0,
+        acceleration: 1.0,
+        grip: 1.0,
+    },
+    PerformanceTuning {
+        top_speed: 1.05,
+        acceleration: 1.1,
+        grip: 1.05,
+    },
+    PerformanceTuning {
+        top_speed: 1.12,
+        acceleration: 1.2,
+        grip: 1.1,
+    },
+    PerformanceTuning {
+        top_speed: 1.2,
+        acceleration: 1.3,
+        grip: 1.2,
+    },
+];
+
+/// Wheel meshes from `MeshFactory` a car can be fitted with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WheelStyle {
+    #[default]
+    Sport,
+    Standard,
+    OffRoad,
+}
+
+impl WheelStyle {
+    pub fn next(self) -> Self {
+        match self {
+            WheelStyle::Sport => WheelStyle::Standard,
+            WheelStyle::Standard => WheelStyle::OffRoad,
+            WheelStyle::OffRoad => WheelStyle::Sport,
+        }
+    }
+
+    /// Radius of the matching `MeshFactory` wheel mesh
+    pub fn radius(self) -> f32 {
+        match self {
+            WheelStyle::Sport => 0.25,
+            WheelStyle::Standard => 0.35,
+            WheelStyle::OffRoad => 0.4,
+        }
+    }
+}
+
+/// Multipliers applied on top of the shared `SimpleCarSpecs`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerformanceTuning {
+    pub top_speed: f32,    // 1.0 - multiplies base_speed
+    pub acceleration: f32, // 1.0 - multiplies accel_lerp
+    pub grip: f32,         // 1.0 - multiplies grip and drift_grip
+}
+
+impl Default for PerformanceTuning {
+    fn default() -> Self {
+        TUNING_STAGES[0]
+    }
+}
+
+impl PerformanceTuning {
+    pub fn validate_and_clamp(&mut self) {
+        self.top_speed = self.top_speed.clamp(0.5, 1.5);
+        self.acceleration = self.acceleration.clamp(0.5, 1.5);
+        self.grip = self.grip.clamp(0.5, 1.5);
+    }
+
+    pub fn is_stock(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Index into `TUNING_STAGES`, or None for hand-edited values
+    pub fn stage(&self) -> Option<usize> {
+        TUNING_STAGES.iter().position(|stage| stage == self)
+    }
+
+    /// Next entry in `TUNING_STAGES`; hand-edited values restart at stock
+    pub fn next_stage(&self) -> Self {
+        self.stage().map_or(TUNING_STAGES[0], |i| {
+            TUNING_STAGES[(i + 1) % TUNING_STAGES.len()]
+        })
+    }
+
+    /// Copy of `specs` with these multipliers applied
+    pub fn apply_to(&self, specs: &SimpleCarSpecs) -> SimpleCarSpecs {
+        let mut tuned = *self;
+        tuned.validate_and_clamp();
+        SimpleCarSpecs {
+            base_speed: specs.base_speed * tuned.top_speed,
+            accel_lerp: specs.accel_lerp * tuned.acceleration,
+            grip: specs.grip * tuned.grip,
+            drift_grip: specs.drift_grip * tuned.grip,
+            ..specs.clone()
+        }
+    }
+}
+
+/// Paint, wheels and tuning of a vehicle; stored with its garage record
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VehicleCustomization {
+    /// None keeps the color the vehicle was spawned with
+    pub paint: Option<[f32; 4]>,
+    pub wheels: WheelStyle,
+    pub tuning: PerformanceTuning,
+}
+
+impl VehicleCustomization {
+    pub fn is_stock(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Next entry in `PAINT_COLORS`; custom colors restart the cycle
+    pub fn next_paint(&self) -> [f32; 4] {
+        self.paint
+            .and_then(|paint| PAINT_COLORS.iter().position(|c| *c == paint))
+            .map_or(PAINT_COLORS[0], |i| {
+                PAINT_COLORS[(i + 1) % PAINT_COLORS.len()]
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tuning_scales_car_specs() {
+        let base = SimpleCarSpecs::default();
+        let race = TUNING_STAGES[3];
+        let tuned = race.apply_to(&base);
+
+        assert_eq!(tuned.base_speed, base.base_speed * race.top_speed);
+        assert_eq!(tuned.grip, base.grip * race.grip);
+        assert_eq!(tuned.rotation_speed, base.rotation_speed);
+        assert_eq!(race.next_stage(), TUNING_STAGES[0]);
+    }
+
+    #[test]
+    fn test_stored_customization_defaults_to_stock() {
+        let custom: VehicleCustomization = ron::from_str("(wheels: OffRoad)").unwrap();
+        assert_eq!(custom.paint, None);
+        assert!(custom.tuning.is_stock());
+        assert_eq!(custom.next_paint(), PAINT_COLORS[0]);
+    }
+}
@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::ops::{Add, Mul, Sub};
+
+fn default_fov() -> f32 {
+    60.0
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Camera pose at `time` seconds into the cutscene. The camera passes through
+/// every keyframe; position, aim point, FOV and roll are splined between them.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CutsceneKeyframe {
+    pub time: f32,
+    pub position: (f32, f32, f32),
+    /// World point the camera looks at
+    pub look_at: (f32, f32, f32),
+    /// Vertical FOV in degrees
+    #[serde(default = "default_fov")]
+    pub fov: f32,
+    /// Roll around the view direction in degrees
+    #[serde(default)]
+    pub roll: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CutsceneDefinition {
+    pub id: String,
+    /// Sorted by `time`
+    pub keyframes: Vec<CutsceneKeyframe>,
+    /// Whether Escape/Enter/Space ends the cutscene early
+    #[serde(default = "default_true")]
+    pub skippable: bool,
+}
+
+/// Where a cutscene puts the camera at one moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub position: Vec3,
+    pub look_at: Vec3,
+    /// Degrees
+    pub fov: f32,
+    /// Degrees
+    pub roll: f32,
+}
+
+impl CameraPose {
+    pub fn rotation(&self) -> Quat {
+        let forward = (self.look_at - self.position).normalize_or(Vec3::NEG_Z);
+        let rotation = Transform::IDENTITY.looking_to(forward, Vec3::Y).rotation;
+        rotation * Quat::from_rotation_z(self.roll.to_radians())
+    }
+}
+
+/// Catmull-Rom segment from `p1` (u = 0) to `p2` (u = 1)
+fn catmull_rom<T>(p0: T, p1: T, p2: T, p3: T, u: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let u2 = u * u;
+    let u3 = u2 * u;
+    (p1 * 2.0
+        + (p2 - p0) * u
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * u2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * u3)
+        * 0.5
+}
+
+impl CutsceneDefinition {
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Camera pose `elapsed` seconds in, held at the first and last keyframe
+    /// outside the cutscene's time range
+    pub fn sample(&self, elapsed: f32) -> Option<CameraPose> {
+        let keys = &self.keyframes;
+        let last = keys.len().checked_sub(1)?;
+        let next = keys
+            .partition_point(|k| k.time <= elapsed)
+            .clamp(1, last.max(1));
+        let i1 = next - 1;
+        let i2 = next.min(last);
+        let span = keys[i2].time - keys[i1].time;
+        let u = if span > f32::EPSILON {
+            ((elapsed - keys[i1].time) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let neighbours = [i1.saturating_sub(1), i1, i2, (i2 + 1).min(last)];
+        let [k0, k1, k2, k3] = neighbours.map(|i| &keys[i]);
+
+        let vec = |v: (f32, f32, f32)| Vec3::new(v.0, v.1, v.2);
+        Some(CameraPose {
+            position: catmull_rom(
+                vec(k0.position),
+                vec(k1.position),
+                vec(k2.position),
+                vec(k3.position),
+                u,
+            ),
+            look_at: catmull_rom(
+                vec(k0.look_at),
+                vec(k1.look_at),
+                vec(k2.look_at),
+                vec(k3.look_at),
+                u,
+            ),
+            fov: catmull_rom(k0.fov, k1.fov, k2.fov, k3.fov, u),
+            roll: catmull_rom(k0.roll, k1.roll, k2.roll, k3.roll, u),
+        })
+    }
+}
+
+/// Cutscenes loaded from `assets/config/cutscenes.ron`
+#[derive(Resource, Debug, Clone, Default, Deserialize)]
+pub struct CutsceneCatalog {
+    pub cutscenes: Vec<CutsceneDefinition>,
+}
+
+impl CutsceneCatalog {
+    pub fn get(&self, id: &str) -> Option<&CutsceneDefinition> {
+        self.cutscenes.iter().find(|c| c.id == id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CutscenePlayback {
+    pub id: String,
+    pub elapsed: f32,
+    /// Set by the skip key; playback ends on the next update
+    pub skipped: bool,
+}
+
+/// The cutscene on screen, if any. Player input is locked while it plays.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ActiveCutscene(pub Option<CutscenePlayback>);
+
+/// Run condition for systems that must wait out cutscenes
+pub fn cutscene_playing(active: Res<ActiveCutscene>) -> bool {
+    active.0.is_some()
+}
+
+/// Asks for a cutscene to play; replaces the one playing, if any
+#[derive(Event, Debug, Clone)]
+pub struct PlayCutscene {
+    pub cutscene_id: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct CutsceneFinished {
+    pub cutscene_id: String,
+    pub skipped: bool,
+}
+
+/// Black bar at the top or bottom of the screen during cutscenes
+#[derive(Component)]
+pub struct LetterboxBar;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(time: f32, x: f32, fov: f32) -> CutsceneKeyframe {
+        CutsceneKeyframe {
+            time,
+            position: (x, 10.0, 0.0),
+            look_at: (x, 0.0, -20.0),
+            fov,
+            roll: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_sample_passes_through_keyframes() {
+        let cutscene = CutsceneDefinition {
+            id: "intro".into(),
+            keyframes: vec![
+                keyframe(0.0, 0.0, 40.0),
+                keyframe(2.0, 10.0, 60.0),
+                keyframe(5.0, 30.0, 50.0),
+            ],
+            skippable: true,
+        };
+        assert_eq!(cutscene.duration(), 5.0);
+        for key in &cutscene.keyframes {
+            let pose = cutscene.sample(key.time).unwrap();
+            assert!((pose.position.x - key.position.0).abs() < 1e-4);
+            assert!((pose.fov - key.fov).abs() < 1e-4);
+        }
+
+        let between = cutscene.sample(1.0).unwrap();
+        assert!(between.position.x > 0.0 && between.position.x < 10.0);
+        assert_eq!(cutscene.sample(9.0).unwrap().position.x, 30.0);
+        assert_eq!(cutscene.sample(-1.0).unwrap().position.x, 0.0);
+    }
+
+    #[test]
+    fn test_single_keyframe_holds_pose() {
+        let mut cutscene = CutsceneDefinition {
+            id: "still".into(),
+            keyframes: vec![keyframe(0.0, 5.0, 45.0)],
+            skippable: false,
+        };
+        assert_eq!(cutscene.sample(3.0).unwrap().position.x, 5.0);
+        cutscene.keyframes.clear();
+        assert!(cutscene.sample(0.0).is_none());
+    }
+}
@@ -0,0 +1,23 @@
+// ShaderType derive emits unused `check` helpers that newer rustc flags as dead code
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use bevy::render::extract_component::ExtractComponent;
+use bevy::render::render_resource::ShaderType;
+
+/// Screen-edge tint on the main camera that deepens as the player is hurt
+#[derive(Component, ExtractComponent, Clone, ShaderType)]
+pub struct DamageVignette {
+    pub color: Vec3,
+    /// 0 leaves the image untouched, 1 tints the edges fully
+    pub intensity: f32,
+}
+
+impl Default for DamageVignette {
+    fn default() -> Self {
+        Self {
+            color: Vec3::new(0.6, 0.0, 0.0),
+            intensity: 0.0,
+        }
+    }
+}
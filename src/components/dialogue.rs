@@ -0,0 +1,111 @@
+use crate::components::ShopKind;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Gameplay a dialogue choice sets off besides moving the conversation on
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum DialogueAction {
+    /// Sends a `StartMissionRequest` for this mission id
+    StartMission(String),
+    /// Opens this shop's buy menu right where the NPC stands
+    OpenShop(ShopKind),
+}
+
+/// Answer the player can pick. `text` is a localization key.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DialogueChoice {
+    pub text: String,
+    /// Node to continue with; None ends the conversation
+    #[serde(default)]
+    pub next: Option<String>,
+    #[serde(default)]
+    pub action: Option<DialogueAction>,
+}
+
+/// One line of a conversation. `speaker` and `text` are localization keys.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DialogueNode {
+    pub id: String,
+    pub speaker: String,
+    pub text: String,
+    pub choices: Vec<DialogueChoice>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DialogueTree {
+    pub id: String,
+    /// Node the conversation opens with
+    pub start: String,
+    pub nodes: Vec<DialogueNode>,
+}
+
+impl DialogueTree {
+    pub fn node(&self, id: &str) -> Option<&DialogueNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+}
+
+/// Dialogue trees loaded from `dialogues.ron`
+#[derive(Resource, Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DialogueCatalog {
+    /// Trees handed out to ambient NPCs, spread evenly over the crowd
+    pub ambient: Vec<String>,
+    pub trees: Vec<DialogueTree>,
+}
+
+impl DialogueCatalog {
+    pub fn tree(&self, id: &str) -> Option<&DialogueTree> {
+        self.trees.iter().find(|tree| tree.id == id)
+    }
+
+    /// Ambient tree for the NPC with this index, if any are configured
+    pub fn ambient_for(&self, index: u32) -> Option<&str> {
+        if self.ambient.is_empty() {
+            return None;
+        }
+        Some(&self.ambient[index as usize % self.ambient.len()])
+    }
+}
+
+/// NPC the player can talk to with the interact key
+#[derive(Component, Debug, Clone)]
+pub struct Talkable {
+    /// Id of the tree in the `DialogueCatalog`
+    pub dialogue: String,
+}
+
+/// Conversation currently on screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueSession {
+    pub npc: Entity,
+    pub tree: String,
+    pub node: String,
+}
+
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ActiveDialogue(pub Option<DialogueSession>);
+
+/// Root node of the dialogue box
+#[derive(Component)]
+pub struct DialogueBox;
+
+/// Picks the choice at this index of the current node
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DialogueChoiceButton(pub usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ambient_trees_wrap_around() {
+        let catalog = DialogueCatalog {
+            ambient: vec!["a".to_string(), "b".to_string()],
+            trees: Vec::new(),
+        };
+        assert_eq!(catalog.ambient_for(0), Some("a"));
+        assert_eq!(catalog.ambient_for(3), Some("b"));
+        assert_eq!(DialogueCatalog::default().ambient_for(7), None);
+    }
+}
@@ -12,12 +12,6 @@ pub struct DirtyVisibility {
     pub frame_marked: u64,
 }
 
-#[derive(Component, Default)]
-pub struct DirtyVegetationInstancing {
-    pub priority: DirtyPriority,
-    pub frame_marked: u64,
-}
-
 #[derive(Default, Clone, Copy)]
 pub enum DirtyPriority {
     High,
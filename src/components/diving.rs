@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+/// Air supply of a swimmer, in seconds of breath-holding
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Breath {
+    pub remaining: f32,
+    pub capacity: f32,
+}
+
+impl Breath {
+    pub fn new(capacity: f32) -> Self {
+        Self {
+            remaining: capacity,
+            capacity,
+        }
+    }
+
+    /// Drains while `submerged`, otherwise refills at `refill_rate` seconds per second
+    pub fn tick(&mut self, delta: f32, submerged: bool, refill_rate: f32) {
+        self.remaining = if submerged {
+            self.remaining - delta
+        } else {
+            self.remaining + delta * refill_rate
+        }
+        .clamp(0.0, self.capacity);
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.capacity > 0.0 {
+            self.remaining / self.capacity
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining <= 0.0
+    }
+}
+
+/// HUD container of the oxygen meter, shown while the player's breath isn't full
+#[derive(Component)]
+pub struct OxygenMeter;
+
+/// Bar inside the oxygen meter whose width tracks the remaining breath
+#[derive(Component)]
+pub struct OxygenMeterFill;
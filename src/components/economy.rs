@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+/// The player's cash; saved with the game. `MoneyChanged` follows any change.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Wallet {
+    balance: u32,
+}
+
+impl Wallet {
+    pub fn new(balance: u32) -> Self {
+        Self { balance }
+    }
+
+    pub fn balance(&self) -> u32 {
+        self.balance
+    }
+
+    pub fn set(&mut self, balance: u32) {
+        self.balance = balance;
+    }
+
+    pub fn credit(&mut self, amount: u32) {
+        self.balance = self.balance.saturating_add(amount);
+    }
+
+    pub fn can_afford(&self, amount: u32) -> bool {
+        self.balance >= amount
+    }
+
+    /// Takes `amount` if the balance covers it; otherwise leaves it untouched
+    pub fn try_spend(&mut self, amount: u32) -> bool {
+        if !self.can_afford(amount) {
+            return false;
+        }
+        self.balance -= amount;
+        true
+    }
+}
+
+/// Cash lying in the world, collected by walking or driving over it
+#[derive(Component, Debug, Clone)]
+pub struct MoneyPickup {
+    pub amount: u32,
+    /// Despawns uncollected when this finishes
+    pub lifetime: Timer,
+}
+
+/// HUD balance; counts towards the wallet instead of jumping
+#[derive(Component, Debug, Default)]
+pub struct MoneyCounter {
+    pub shown: f32,
+}
+
+/// "+$500" line under the balance, fading out; changes arriving while it is
+/// still shown add up
+#[derive(Component, Debug, Default)]
+pub struct MoneyDeltaText {
+    pub amount: i64,
+    pub timer: Timer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wallet_never_overdraws() {
+        let mut wallet = Wallet::new(100);
+        assert!(!wallet.try_spend(150));
+        assert_eq!(wallet.balance(), 100);
+        assert!(wallet.try_spend(100));
+        assert_eq!(wallet.balance(), 0);
+
+        wallet.set(u32::MAX - 1);
+        wallet.credit(10);
+        assert_eq!(wallet.balance(), u32::MAX);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::components::VehicleType;
+use crate::resources::District;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Recorded events kept by default
+pub const DEFAULT_HISTORY_CAPACITY: usize = 256;
+
+/// The player got into a vehicle, by any route (door, yacht deck, helipad)
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct VehicleEntered {
+    pub player: Entity,
+    pub vehicle: Entity,
+    pub vehicle_type: VehicleType,
+}
+
+/// A vehicle's health reached zero
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct VehicleDestroyed {
+    pub vehicle: Entity,
+    pub vehicle_type: VehicleType,
+    pub position: Vec3,
+}
+
+/// An NPC was hit hard enough that it won't get up
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct NpcKilled {
+    pub npc: Entity,
+    pub position: Vec3,
+}
+
+/// The active entity crossed into another district
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ZoneEntered {
+    /// `None` for the first district after spawning
+    pub previous: Option<District>,
+    pub district: District,
+}
+
+/// The `Wallet` balance changed, from whatever source
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct MoneyChanged {
+    pub previous: u32,
+    pub current: u32,
+}
+
+impl MoneyChanged {
+    pub fn delta(&self) -> i64 {
+        self.current as i64 - self.previous as i64
+    }
+}
+
+/// Any of the canonical gameplay events, as kept in the history
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameplayEvent {
+    VehicleEntered(VehicleEntered),
+    VehicleDestroyed(VehicleDestroyed),
+    NpcKilled(NpcKilled),
+    ZoneEntered(ZoneEntered),
+    MoneyChanged(MoneyChanged),
+}
+
+impl GameplayEvent {
+    /// Stable snake_case name, also used by scripts
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameplayEvent::VehicleEntered(_) => "vehicle_entered",
+            GameplayEvent::VehicleDestroyed(_) => "vehicle_destroyed",
+            GameplayEvent::NpcKilled(_) => "npc_killed",
+            GameplayEvent::ZoneEntered(_) => "zone_entered",
+            GameplayEvent::MoneyChanged(_) => "money_changed",
+        }
+    }
+}
+
+impl fmt::Display for GameplayEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.name())?;
+        match self {
+            GameplayEvent::VehicleEntered(e) => write!(f, "{:?} {}", e.vehicle_type, e.vehicle),
+            GameplayEvent::VehicleDestroyed(e) => {
+                write!(f, "{:?} {} at {:.0}", e.vehicle_type, e.vehicle, e.position)
+            }
+            GameplayEvent::NpcKilled(e) => write!(f, "{} at {:.0}", e.npc, e.position),
+            GameplayEvent::ZoneEntered(e) => match e.previous {
+                Some(previous) => write!(f, "{previous:?} -> {:?}", e.district),
+                None => write!(f, "{:?}", e.district),
+            },
+            GameplayEvent::MoneyChanged(e) => {
+                write!(f, "${} ({:+})", e.current, e.delta())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordedEvent {
+    /// Increases by one per recorded event, for readers tracking what they've seen
+    pub sequence: u64,
+    /// Seconds since startup
+    pub time: f32,
+    pub event: GameplayEvent,
+}
+
+/// Ring buffer of recent gameplay events, for the `events` console command
+/// and for scripts. Recording can be switched off.
+#[derive(Resource, Debug)]
+pub struct GameplayEventHistory {
+    recording: bool,
+    capacity: usize,
+    events: VecDeque<RecordedEvent>,
+    next_sequence: u64,
+}
+
+impl Default for GameplayEventHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl GameplayEventHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            recording: true,
+            capacity: capacity.max(1),
+            events: VecDeque::new(),
+            next_sequence: 0,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+    }
+
+    pub fn push(&mut self, time: f32, event: GameplayEvent) {
+        if !self.recording {
+            return;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(RecordedEvent {
+            sequence: self.next_sequence,
+            time,
+            event,
+        });
+        self.next_sequence += 1;
+    }
+
+    /// Sequence number the next recorded event will get
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Events recorded at or after `sequence` that are still in the buffer
+    pub fn since(&self, sequence: u64) -> impl Iterator<Item = &RecordedEvent> {
+        self.events
+            .iter()
+            .filter(move |recorded| recorded.sequence >= sequence)
+    }
+
+    /// Up to `count` most recent events, oldest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &RecordedEvent> {
+        self.events
+            .iter()
+            .skip(self.events.len().saturating_sub(count))
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
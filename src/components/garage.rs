@@ -0,0 +1,91 @@
+use crate::components::{VehicleCustomization, VehicleType};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Most vehicles the garage keeps; further vehicles aren't recorded
+pub const MAX_GARAGE_VEHICLES: usize = 12;
+
+/// A vehicle the player owns, restorable from any garage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredVehicle {
+    pub id: u32,
+    pub vehicle_type: VehicleType,
+    pub color: [f32; 4],
+    #[serde(default)]
+    pub customization: VehicleCustomization,
+}
+
+/// Every vehicle the player owns; saved with the game
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Garage {
+    pub vehicles: Vec<StoredVehicle>,
+    pub next_id: u32,
+}
+
+impl Garage {
+    /// Records a newly owned vehicle, returning its id (None when full)
+    pub fn add(&mut self, vehicle_type: VehicleType, color: [f32; 4]) -> Option<u32> {
+        if self.vehicles.len() >= MAX_GARAGE_VEHICLES {
+            return None;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.vehicles.push(StoredVehicle {
+            id,
+            vehicle_type,
+            color,
+            customization: VehicleCustomization::default(),
+        });
+        Some(id)
+    }
+
+    pub fn get(&self, id: u32) -> Option<&StoredVehicle> {
+        self.vehicles.iter().find(|vehicle| vehicle.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut StoredVehicle> {
+        self.vehicles.iter_mut().find(|vehicle| vehicle.id == id)
+    }
+}
+
+/// Live entity of a garage vehicle; recalling the vehicle replaces it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GarageVehicle(pub u32);
+
+/// Garage placed in the world; using its pad opens the garage menu
+#[derive(Component, Debug, Clone)]
+pub struct GarageLocation {
+    pub name: String,
+    pub radius: f32,
+    /// Where recalled vehicles appear, in world space
+    pub spawn_point: Vec3,
+}
+
+/// Root node of the garage menu
+#[derive(Component)]
+pub struct GarageMenu;
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GarageSpawnButton(pub u32);
+
+/// Buys a new vehicle of this type into the garage
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GarageBuyButton(pub VehicleType);
+
+/// Part of a stored vehicle a garage customization button changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomizationPart {
+    Paint,
+    Wheels,
+    Tuning,
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GarageCustomizeButton {
+    pub id: u32,
+    pub part: CustomizationPart,
+}
+
+/// Garage whose menu the player opened; stepping off its pad closes it
+#[derive(Resource, Debug, Default)]
+pub struct NearbyGarage(pub Option<Entity>);
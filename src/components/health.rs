@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+
+/// The player's health. Damage arrives as `PlayerDamaged`; it regenerates
+/// after a while without any, and reaching zero makes the player `Wasted`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PlayerHealth {
+    pub current: f32,
+    pub max: f32,
+    /// Seconds since damage was last taken
+    pub since_damage: f32,
+}
+
+impl PlayerHealth {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            since_damage: f32::INFINITY,
+        }
+    }
+
+    /// Takes `amount` off, returning whether that was fatal
+    pub fn damage(&mut self, amount: f32) -> bool {
+        self.current = (self.current - amount.max(0.0)).max(0.0);
+        self.since_damage = 0.0;
+        self.is_dead()
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.current / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+    /// Landing too fast, with or without a skydive first
+    Fall,
+    /// Hit by a moving vehicle while on foot
+    Vehicle,
+    /// Out of breath underwater
+    Drowning,
+}
+
+/// Health points taken off the player, from any source
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct PlayerDamaged {
+    pub amount: f32,
+    pub source: DamageSource,
+}
+
+/// On the player between dying and getting back on their feet: the screen
+/// fades out, they are moved to the nearest hospital, and it fades back in
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Wasted {
+    pub elapsed: f32,
+    pub respawned: bool,
+}
+
+/// Where the player is patched up after dying
+#[derive(Component, Debug, Clone)]
+pub struct Hospital {
+    pub name: String,
+    /// Spot outside the doors the player respawns at
+    pub entrance: Vec3,
+}
+
+/// HUD health bar frame and the fill inside it
+#[derive(Component)]
+pub struct HealthBar;
+
+#[derive(Component)]
+pub struct HealthBarFill;
+
+/// Full-screen black overlay faded in on death, and its "wasted" caption
+#[derive(Component)]
+pub struct DeathFade;
+
+#[derive(Component)]
+pub struct DeathFadeText;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_clamps_at_zero() {
+        let mut health = PlayerHealth::new(100.0);
+        assert!(!health.damage(60.0));
+        assert_eq!(health.fraction(), 0.4);
+        assert!(health.damage(500.0));
+        assert_eq!(health.current, 0.0);
+    }
+}
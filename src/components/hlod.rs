@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Merged low-poly stand-in drawn for a whole building cluster from far away
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HlodProxy {
+    pub region: IVec2,
+}
+
+/// Building whose mesh, material and textures were taken off the GPU while
+/// its cluster is far away. Holds the assets so they can go back under the
+/// same handles; an asset shared with other entities is never taken.
+#[derive(Component, Debug, Default)]
+pub struct NonResident {
+    pub mesh: Option<Mesh>,
+    pub material: Option<StandardMaterial>,
+    pub textures: Vec<(AssetId<Image>, Image)>,
+}
+
+/// Static buildings grouped into square regions, one cluster per region
+#[derive(Resource, Debug, Default)]
+pub struct HlodClusters {
+    pub clusters: HashMap<IVec2, HlodCluster>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HlodCluster {
+    pub members: Vec<Entity>,
+    pub proxy: Option<Entity>,
+    /// Ground-plane bounds of the members
+    pub min: Vec2,
+    pub max: Vec2,
+    /// Members changed since the proxy was baked
+    pub dirty: bool,
+    /// The proxy is drawn instead of the members
+    pub using_proxy: bool,
+    /// Member and proxy visibility must be reapplied, e.g. after a rebake
+    pub resync: bool,
+}
+
+impl Default for HlodCluster {
+    fn default() -> Self {
+        Self {
+            members: Vec::new(),
+            proxy: None,
+            min: Vec2::splat(f32::INFINITY),
+            max: Vec2::splat(f32::NEG_INFINITY),
+            dirty: false,
+            using_proxy: false,
+            resync: false,
+        }
+    }
+}
+
+impl HlodClusters {
+    pub fn region_of(position: Vec3, region_size: f32) -> IVec2 {
+        (position.xz() / region_size).floor().as_ivec2()
+    }
+}
+
+impl HlodCluster {
+    /// Ground-plane distance from `position` to the cluster bounds; zero inside
+    pub fn distance_to(&self, position: Vec3) -> f32 {
+        let point = position.xz();
+        point.distance(point.clamp(self.min, self.max))
+    }
+}
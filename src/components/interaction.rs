@@ -0,0 +1,90 @@
+use super::control_state::VehicleControlType;
+use bevy::prelude::*;
+
+/// What using an `Interactable` does; the system owning that kind reacts to
+/// the `InteractionEvent`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InteractionKind {
+    /// Get in and drive; a deck walker takes the helm of their own yacht
+    EnterVehicle(VehicleControlType),
+    /// Street door into a building interior
+    Door,
+    /// Door back out of the interior
+    ExitInterior,
+    Shop,
+    /// Buy a property, or save at an owned safehouse
+    Property,
+    /// Open the garage menu on a garage pad
+    Garage,
+    /// Board a stopped train, or get off the one being ridden
+    Train,
+    /// Start or end a conversation with an NPC
+    Talk,
+}
+
+impl InteractionKind {
+    /// Default priority: using a building beats getting into a car parked by
+    /// it, and so do a garage pad, a waiting train or a passer-by; among
+    /// vehicles the one you have to stand closest to wins
+    pub fn priority(self) -> i32 {
+        match self {
+            InteractionKind::Door | InteractionKind::ExitInterior => 100,
+            InteractionKind::Shop => 90,
+            InteractionKind::Property => 80,
+            InteractionKind::Garage => 70,
+            InteractionKind::Train => 60,
+            InteractionKind::Talk => 50,
+            InteractionKind::EnterVehicle(VehicleControlType::Car) => 40,
+            InteractionKind::EnterVehicle(VehicleControlType::Helicopter) => 30,
+            InteractionKind::EnterVehicle(VehicleControlType::F16) => 20,
+            InteractionKind::EnterVehicle(_) => 10,
+        }
+    }
+}
+
+/// Something the walking player can use with the interact key
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Interactable {
+    pub kind: InteractionKind,
+    /// Reach from the entity's origin
+    pub radius: f32,
+    /// Higher wins over nearer
+    pub priority: i32,
+    /// Localized context prompt; `{key}` is replaced with the bound interact input
+    pub prompt: String,
+    /// Reach is measured on the ground plane, for pads the player stands on
+    pub planar: bool,
+}
+
+impl Interactable {
+    pub fn new(kind: InteractionKind, radius: f32, prompt: impl Into<String>) -> Self {
+        Self {
+            kind,
+            radius,
+            priority: kind.priority(),
+            prompt: prompt.into(),
+            planar: false,
+        }
+    }
+
+    pub fn planar(mut self) -> Self {
+        self.planar = true;
+        self
+    }
+}
+
+/// Interactable the interact key would use right now
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InteractionFocus(pub Option<Entity>);
+
+/// The player used `target`
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InteractionEvent {
+    pub actor: Entity,
+    pub target: Entity,
+    pub kind: InteractionKind,
+}
+
+/// The single context prompt for the focused interactable
+#[derive(Component)]
+pub struct InteractionPrompt;
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+/// Kind of room behind an enterable door
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InteriorKind {
+    Apartment,
+    Shop,
+    Office,
+    Warehouse,
+}
+
+impl InteriorKind {
+    /// Inside dimensions (width, height, depth) in meters
+    pub fn room_size(self) -> Vec3 {
+        match self {
+            InteriorKind::Apartment => Vec3::new(10.0, 3.0, 8.0),
+            InteriorKind::Shop => Vec3::new(14.0, 4.0, 10.0),
+            InteriorKind::Office => Vec3::new(16.0, 3.5, 12.0),
+            InteriorKind::Warehouse => Vec3::new(24.0, 8.0, 20.0),
+        }
+    }
+
+    /// Storeys reachable inside a building of `height`; shops and warehouses are single-storey
+    pub fn floors_for_height(self, height: f32) -> u32 {
+        match self {
+            InteriorKind::Apartment | InteriorKind::Office => {
+                ((height / self.room_size().y) as u32).clamp(1, MAX_INTERIOR_FLOORS)
+            }
+            InteriorKind::Shop | InteriorKind::Warehouse => 1,
+        }
+    }
+}
+
+/// Most storeys built for one interior
+pub const MAX_INTERIOR_FLOORS: u32 = 6;
+
+/// Door on a building's front face that leads into an interior
+#[derive(Component, Debug, Clone, Copy)]
+pub struct EnterableDoor {
+    pub kind: InteriorKind,
+    pub floors: u32,
+}
+
+/// Root of a spawned interior scene; despawning it removes the whole room
+#[derive(Component, Debug, Clone, Copy)]
+pub struct InteriorScene {
+    pub kind: InteriorKind,
+    pub floors: u32,
+}
+
+/// Door inside an interior that leads back out
+#[derive(Component, Debug, Clone, Copy)]
+pub struct InteriorExit;
+
+/// Exterior entity hidden while the player is inside; holds its visibility to restore
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ExteriorCulled(pub Visibility);
+
+/// Interior the player is currently in
+#[derive(Resource, Debug, Default)]
+pub struct CurrentInterior(pub Option<ActiveInterior>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveInterior {
+    pub scene: Entity,
+    /// Where the player comes back out, just in front of the street door
+    pub return_position: Vec3,
+    pub return_rotation: Quat,
+}
+
+/// Kinematic car moving between the floors of a multi-storey interior
+#[derive(Component, Debug, Clone)]
+pub struct Elevator {
+    pub floors: u32,
+    pub floor_spacing: f32,
+    /// Car position at the ground floor, relative to the interior root
+    pub base: Vec3,
+    /// Height of the car above `base`
+    pub height: f32,
+    pub velocity: f32,
+    pub target_floor: u32,
+    /// Half size of the car floor
+    pub half_extents: Vec2,
+}
+
+impl Elevator {
+    pub fn current_floor(&self) -> u32 {
+        ((self.height / self.floor_spacing).round() as u32).min(self.floors.saturating_sub(1))
+    }
+}
+
+/// Player or NPC standing in an elevator car and carried with it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ElevatorRider {
+    pub elevator: Entity,
+    /// Car transform when the rider was last moved, as with `DeckWalker`
+    pub last_anchor: GlobalTransform,
+}
+
+/// Floor selection panel shown while the player rides an elevator
+#[derive(Component)]
+pub struct ElevatorPanel;
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ElevatorFloorButton {
+    pub elevator: Entity,
+    pub floor: u32,
+}
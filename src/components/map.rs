@@ -10,6 +10,21 @@ pub struct MinimapUI;
 #[derive(Component)]
 pub struct PlayerMapIcon;
 
+/// Pooled minimap marker for a nearby vehicle or NPC
+#[derive(Component)]
+pub struct MinimapIcon;
+
+/// Pooled dot tracing the GPS route on the minimap
+#[derive(Component)]
+pub struct MinimapRouteDot;
+
+/// Runtime minimap view state (zoom and fullscreen toggle)
+#[derive(Resource, Default)]
+pub struct MapViewState {
+    pub zoom_index: usize,
+    pub fullscreen: bool,
+}
+
 #[derive(Resource, Serialize, Deserialize, Clone)]
 pub struct MapConfig {
     pub map_size: f32,
@@ -22,6 +37,74 @@ pub struct MapConfig {
     pub show_player_icon: bool,
     pub player_icon_size: f32,
     pub player_icon_color: (f32, f32, f32, f32),
+    /// Zoom factors cycled with +/-; visible area is `map_size / zoom`
+    #[serde(default = "default_zoom_levels")]
+    pub zoom_levels: Vec<f32>,
+    #[serde(default = "default_icon_size")]
+    pub icon_size: f32,
+    /// Maximum number of vehicle/NPC markers drawn at once
+    #[serde(default = "default_max_icons")]
+    pub max_icons: usize,
+    #[serde(default = "default_vehicle_icon_color")]
+    pub vehicle_icon_color: (f32, f32, f32, f32),
+    #[serde(default = "default_npc_icon_color")]
+    pub npc_icon_color: (f32, f32, f32, f32),
+    /// Runway and helipad markers, drawn ahead of vehicles and NPCs
+    #[serde(default = "default_airfield_icon_color")]
+    pub airfield_icon_color: (f32, f32, f32, f32),
+    /// GPS route trace: dot color and how many dots span the view
+    #[serde(default = "default_route_color")]
+    pub route_color: (f32, f32, f32, f32),
+    #[serde(default = "default_route_dots")]
+    pub route_dots: usize,
+}
+
+fn default_zoom_levels() -> Vec<f32> {
+    vec![1.0, 3.0, 10.0]
+}
+
+fn default_icon_size() -> f32 {
+    6.0
+}
+
+fn default_max_icons() -> usize {
+    64
+}
+
+fn default_vehicle_icon_color() -> (f32, f32, f32, f32) {
+    (0.2, 0.6, 1.0, 1.0)
+}
+
+fn default_npc_icon_color() -> (f32, f32, f32, f32) {
+    (1.0, 0.9, 0.2, 1.0)
+}
+
+fn default_airfield_icon_color() -> (f32, f32, f32, f32) {
+    (0.9, 0.9, 0.9, 1.0)
+}
+
+fn default_route_color() -> (f32, f32, f32, f32) {
+    (0.8, 0.3, 1.0, 1.0)
+}
+
+fn default_route_dots() -> usize {
+    48
+}
+
+impl MapConfig {
+    /// Zoom factor for the given level index, falling back to `zoom_level`
+    pub fn zoom_at(&self, index: usize) -> f32 {
+        self.zoom_levels
+            .get(index)
+            .copied()
+            .unwrap_or(self.zoom_level)
+            .max(0.01)
+    }
+
+    /// World-space width/height shown by the minimap at the given zoom level
+    pub fn view_size(&self, index: usize) -> f32 {
+        self.map_size / self.zoom_at(index)
+    }
 }
 
 impl Default for MapConfig {
@@ -37,6 +120,143 @@ impl Default for MapConfig {
             show_player_icon: true,
             player_icon_size: 10.0,
             player_icon_color: (1.0, 0.0, 0.0, 1.0),
+            zoom_levels: default_zoom_levels(),
+            icon_size: default_icon_size(),
+            max_icons: default_max_icons(),
+            vehicle_icon_color: default_vehicle_icon_color(),
+            npc_icon_color: default_npc_icon_color(),
+            airfield_icon_color: default_airfield_icon_color(),
+            route_color: default_route_color(),
+            route_dots: default_route_dots(),
+        }
+    }
+}
+
+/// Sets the GPS destination, or clears it with `None`
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SetGpsDestination(pub Option<Vec3>);
+
+/// Route the GPS guides along, recomputed whenever the player strays from it
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GpsRoute {
+    pub destination: Option<Vec3>,
+    /// Polyline to the destination, following roads where there are any
+    pub points: Vec<Vec3>,
+    /// Segment the player was last found on; earlier ones are behind them
+    pub progress: usize,
+}
+
+impl GpsRoute {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Segment at or after `progress` closest to `position` on the ground
+    /// plane, as (segment index, distance)
+    pub fn nearest_segment(&self, position: Vec3) -> Option<(usize, f32)> {
+        (self.progress..self.points.len().saturating_sub(1))
+            .map(|i| {
+                let closest = closest_on_segment(self.points[i], self.points[i + 1], position);
+                (i, closest.xz().distance(position.xz()))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Points every `spacing` meters along the route ahead of `position`,
+    /// with the travel direction there, at most `count` of them
+    pub fn points_ahead(&self, position: Vec3, spacing: f32, count: usize) -> Vec<(Vec3, Vec3)> {
+        let Some((segment, _)) = self.nearest_segment(position) else {
+            return Vec::new();
+        };
+        let spacing = spacing.max(0.1);
+        let mut from = closest_on_segment(self.points[segment], self.points[segment + 1], position);
+        let mut until_next = spacing;
+        let mut points = Vec::new();
+
+        for &to in &self.points[segment + 1..] {
+            let direction = (to - from).normalize_or_zero();
+            let mut length = from.distance(to);
+            while length >= until_next && points.len() < count {
+                from += direction * until_next;
+                length -= until_next;
+                until_next = spacing;
+                points.push((from, direction));
+            }
+            until_next -= length;
+            from = to;
         }
+        points
+    }
+
+    /// Meters left along the route from `position`
+    pub fn remaining_distance(&self, position: Vec3) -> f32 {
+        let Some((segment, _)) = self.nearest_segment(position) else {
+            return 0.0;
+        };
+        let start = closest_on_segment(self.points[segment], self.points[segment + 1], position);
+        let rest: f32 = self.points[segment + 1..]
+            .windows(2)
+            .map(|pair| pair[0].distance(pair[1]))
+            .sum();
+        start.distance(self.points[segment + 1]) + rest
+    }
+}
+
+fn closest_on_segment(a: Vec3, b: Vec3, position: Vec3) -> Vec3 {
+    let ab = b - a;
+    let t = (position - a).dot(ab) / ab.length_squared().max(f32::EPSILON);
+    a + ab * t.clamp(0.0, 1.0)
+}
+
+/// Floating chevron pointing the way along the GPS route
+#[derive(Component)]
+pub struct GpsArrow;
+
+/// Light beam standing on the GPS destination
+#[derive(Component)]
+pub struct GpsDestinationBeacon;
+
+/// Distance left to the GPS destination
+#[derive(Component)]
+pub struct GpsDistanceText;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn l_route() -> GpsRoute {
+        GpsRoute {
+            destination: Some(Vec3::new(100.0, 0.0, 100.0)),
+            points: vec![
+                Vec3::ZERO,
+                Vec3::new(100.0, 0.0, 0.0),
+                Vec3::new(100.0, 0.0, 100.0),
+            ],
+            progress: 0,
+        }
+    }
+
+    #[test]
+    fn test_nearest_segment_and_remaining_distance() {
+        let route = l_route();
+        let position = Vec3::new(95.0, 0.0, 40.0);
+        assert_eq!(route.nearest_segment(position), Some((1, 5.0)));
+        assert_eq!(route.remaining_distance(position), 60.0);
+        assert_eq!(route.remaining_distance(Vec3::new(-10.0, 0.0, 0.0)), 200.0);
+    }
+
+    #[test]
+    fn test_points_ahead_turn_the_corner() {
+        let route = l_route();
+        let points = route.points_ahead(Vec3::new(70.0, 0.0, 0.0), 20.0, 3);
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].0, Vec3::new(90.0, 0.0, 0.0));
+        assert_eq!(points[1], (Vec3::new(100.0, 0.0, 10.0), Vec3::Z));
+        assert_eq!(points[2].0, Vec3::new(100.0, 0.0, 30.0));
+        assert!(
+            route
+                .points_ahead(Vec3::new(100.0, 0.0, 95.0), 20.0, 3)
+                .is_empty()
+        );
     }
 }
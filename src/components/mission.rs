@@ -0,0 +1,133 @@
+use crate::components::VehicleControlType;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A single step the player must complete inside a mission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionObjective {
+    pub description: String,
+    pub kind: ObjectiveKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    /// Active entity must come within `radius` of `position` (XZ plane)
+    ReachLocation {
+        position: (f32, f32, f32),
+        radius: f32,
+    },
+    /// Player must be controlling a vehicle of the given type
+    EnterVehicle { vehicle: VehicleControlType },
+    /// Stay alive (and in the mission) for the given time
+    Survive { seconds: f32 },
+}
+
+/// What starts a mission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MissionTrigger {
+    /// Starts when the active entity enters the marker radius
+    Location {
+        position: (f32, f32, f32),
+        radius: f32,
+    },
+    /// Starts as soon as the previous mission in the catalog is completed
+    AfterMission(String),
+    /// Only starts through a `StartMissionRequest`, e.g. from a dialogue choice
+    Dialogue,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MissionReward {
+    pub money: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionDefinition {
+    pub id: String,
+    pub title: String,
+    pub trigger: MissionTrigger,
+    pub objectives: Vec<MissionObjective>,
+    /// Optional overall time limit in seconds; the mission fails when it runs out
+    #[serde(default)]
+    pub time_limit: Option<f32>,
+    #[serde(default)]
+    pub reward: MissionReward,
+    /// Cutscene played when the mission starts; the clock waits for it
+    #[serde(default)]
+    pub intro_cutscene: Option<String>,
+}
+
+/// All missions loaded from `assets/config/missions.ron`
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MissionCatalog {
+    pub missions: Vec<MissionDefinition>,
+}
+
+impl MissionCatalog {
+    pub fn get(&self, id: &str) -> Option<&MissionDefinition> {
+        self.missions.iter().find(|m| m.id == id)
+    }
+}
+
+/// The mission currently in progress, if any
+#[derive(Debug, Clone)]
+pub struct RunningMission {
+    pub id: String,
+    pub objective_index: usize,
+    pub elapsed: f32,
+    pub objective_elapsed: f32,
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ActiveMission(pub Option<RunningMission>);
+
+/// Completed mission ids, kept so triggers don't fire twice
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MissionProgress {
+    pub completed: Vec<String>,
+}
+
+impl MissionProgress {
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.iter().any(|c| c == id)
+    }
+}
+
+/// Asks for a mission to start; ignored while another one runs or once it
+/// is completed
+#[derive(Event, Debug, Clone)]
+pub struct StartMissionRequest {
+    pub mission_id: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct MissionStarted {
+    pub mission_id: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct ObjectiveCompleted {
+    pub mission_id: String,
+    pub objective_index: usize,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct MissionCompleted {
+    pub mission_id: String,
+    pub reward: MissionReward,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissionFailReason {
+    TimeExpired,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct MissionFailed {
+    pub mission_id: String,
+    pub reason: MissionFailReason,
+}
+
+/// HUD text showing the current mission objective
+#[derive(Component)]
+pub struct MissionHudText;
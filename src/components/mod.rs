@@ -15,13 +15,41 @@
 //! ### Entity Identity
 //! - `player`: Player character markers and state
 //! - `vehicles`: Vehicle types and properties
-//! - `world`: Terrain and world structure markers
+//! - `world`: Terrain and world structure markers, and destructible street props
 //! - `entity_types`: World entity classification for LOD and spawning
+//! - `mission`: Mission definitions, progress and events
+//! - `gameplay_events`: Canonical cross-plugin events and the event history
+//! - `economy`: Player wallet, cash pickups and the HUD money counter
+//! - `traffic`: Ambient traffic agents following the road network
+//! - `pedestrian`: Sidewalk navigation and crowd reactions for NPCs
+//! - `police`: Wanted level, crimes and police units
+//! - `race`: RON race definitions, checkpoints, opponents and the race manager
+//! - `stunt`: Stunt jump and time trial events, trick scoring and best-score tables
+//! - `weather`: Weather presets, transitions and rain emitter
+//! - `customization`: Vehicle paint, wheels and performance tuning
+//! - `garage`: Owned vehicles and world garages
+//! - `shop`: Storefronts, RON shop catalogs, properties for sale and what the player owns
+//! - `dialogue`: RON dialogue trees, talkable NPCs and the open conversation
+//! - `diving`: Breath supply and oxygen meter HUD
+//! - `health`: Player health, damage events, death fade and hospitals
+//! - `airfield`: Runways, helipads and the registry that lists them
+//! - `parachute`: Ejected/bailed-out player freefall and canopy state
+//! - `rail`: Rail tracks, stations, trains and level crossings
+//! - `ragdoll`: NPCs knocked over by vehicles and their detached body parts
+//! - `interior`: Enterable building doors, interior scenes, elevators and culled exterior
+//! - `interaction`: Interactables, the focused one and interaction events
+//! - `schedule`: NPC daily routines from RON and each NPC's home, work and park
+//! - `accessibility`: Subtitles captioning audio cues
+//! - `cutscene`: Keyframed camera cutscenes, playback state and letterbox bars
+//! - `tire`: Surface types, axle slip and the tire state behind skid marks and screech
+//! - `transmission`: Car gearbox state, gear ratios and torque curve sampling
+//! - `vehicle_hud`: Speedometer, rev counter, flight and throttle HUD parts
 //!
 //! ### Visual & Rendering
 //! - `effects`: Visual effect data and parameters
 //! - `water`: Water simulation properties
 //! - `lod`: Level-of-detail rendering data
+//! - `hlod`: Building clusters, their merged far-distance proxies and evicted assets
 //! - `instanced_vegetation`: Efficient vegetation rendering
 //!
 //! ### Optimization
@@ -54,19 +82,50 @@
 //! 4. Add documentation for each field
 //! 5. Export from this mod.rs file
 
+pub mod accessibility;
+pub mod airfield;
+pub mod customization;
+pub mod cutscene;
+pub mod damage_vignette;
+pub mod dialogue;
+pub mod diving;
+pub mod economy;
 pub mod effects;
+pub mod gameplay_events;
+pub mod garage;
+pub mod health;
+pub mod hlod;
+pub mod interaction;
+pub mod interior;
 pub mod map;
+pub mod mission;
 pub mod movement_tracker;
 pub mod navigation_lights;
+pub mod parachute;
+pub mod particles;
+pub mod pedestrian;
 pub mod player;
+pub mod police;
 pub mod propeller;
+pub mod race;
+pub mod rail;
+pub mod ragdoll;
 pub mod rudder;
 pub mod rotor_wash;
+pub mod schedule;
+pub mod shop;
+pub mod stunt;
+pub mod tire;
+pub mod traffic;
+pub mod transmission;
 pub mod underwater_settings;
 pub mod unified_water;
+pub mod vehicle_hud;
+pub mod vehicle_lights;
 pub mod vehicles;
 pub mod water;
 pub mod water_material;
+pub mod weather;
 pub mod world;
 
 pub mod control_state;
@@ -81,18 +140,31 @@ pub mod unified_vehicle;
 // Core entity components
 pub use player::{
     ActiveEntity, BodyPart, HumanAnimation, HumanMovement, InCar, Player, PlayerBody,
-    PlayerBodyMesh, PlayerHead, PlayerLeftArm, PlayerLeftLeg, PlayerRightArm, PlayerRightLeg,
-    PlayerTorso,
+    PlayerBodyMesh, PlayerHead, PlayerLeftArm, PlayerLeftLeg, PlayerOwned, PlayerRightArm,
+    PlayerRightLeg, PlayerTorso,
 };
 
 pub use navigation_lights::{LandingLight, NavigationLight, NavigationLightType};
 pub use propeller::PropellerHub;
+pub use hlod::{HlodCluster, HlodClusters, HlodProxy, NonResident};
+pub use interaction::{
+    Interactable, InteractionEvent, InteractionFocus, InteractionKind, InteractionPrompt,
+};
+pub use interior::{
+    ActiveInterior, CurrentInterior, Elevator, ElevatorFloorButton, ElevatorPanel, ElevatorRider,
+    EnterableDoor, ExteriorCulled, InteriorExit, InteriorKind, InteriorScene,
+};
+pub use rail::{
+    RailCrossingLight, RailLine, RailNetwork, RailStation, RailTrack, Train, TrainCar,
+    TrainPassenger, TrainState,
+};
+pub use ragdoll::{Ragdoll, RagdollBone, RagdollPart};
 pub use rudder::Rudder;
 pub use rotor_wash::RotorWash;
 
 pub use vehicles::{
     AircraftFlight, Car, CarWheelsConfig, F16, Grounded, HeliState, Helicopter, HelicopterRuntime,
-    HelicopterVisualBody, MainRotor, RotorBlurDisk, SimpleCarSpecs, SimpleCarSpecsHandle,
+    HelicopterVisualBody, LandingGear, LandingGearStrut, MainRotor, RotorBlurDisk, SimpleCarSpecs, SimpleCarSpecsHandle,
     SimpleF16Specs, SimpleF16SpecsHandle, SimpleHelicopterSpecs, SimpleHelicopterSpecsHandle,
     TailRotor, VehicleHealth, VehicleLOD, VehicleRendering, VehicleState, VehicleType, VisualRig,
     VisualRigRoot, WheelMesh, WheelPos, WheelSteerPivot, WheelsRoot,
@@ -101,14 +173,14 @@ pub use vehicles::{
 pub use world::{
     BoundaryEffects, Buildable, Building, BuildingType, ContentType, CullingSettings,
     DynamicContent, DynamicTerrain, IntersectionEntity, Landmark, MainCamera, MaterialCache,
-    MovementController, NPC, NPCAppearance, NPCBehaviorComponent, NPCBehaviorType, NPCBodyPart,
-    NPCGender, NPCHead, NPCLOD, NPCLeftArm, NPCLeftFoot, NPCLeftLeg, NPCRendering, NPCRightArm,
-    NPCRightFoot, NPCRightLeg, NPCState, NPCTorso, NPCType, PerformanceCritical, PerformanceStats,
-    RoadEntity, WorldBounds,
+    LooseProp, MovementController, NPC, NPCAppearance, NPCBehaviorComponent, NPCBehaviorType,
+    NPCBodyPart, NPCGender, NPCHead, NPCLOD, NPCLeftArm, NPCLeftFoot, NPCLeftLeg, NPCRendering,
+    NPCRightArm, NPCRightFoot, NPCRightLeg, NPCState, NPCTorso, NPCType, PerformanceCritical,
+    PerformanceStats, RoadEntity, StreetProp, WorldBounds,
 };
 
 pub use unified_water::{
-    CurrentWaterRegion, TideConfig, UnifiedWaterAsset, UnifiedWaterBody, WaterSurface, WaveParams,
+    BuoyancyHull, CurrentWaterRegion, TideConfig, UnifiedWaterAsset, UnifiedWaterBody, WaterSurface, WaveParams,
 };
 pub use water::{Boat, WaterBody, WaterWave, Yacht};
 pub use water_material::WaterMaterial;
@@ -122,15 +194,64 @@ pub use effects::{
 pub use control_state::{
     AIControlled, ControlState, PendingPhysicsEnable, PlayerControlled, VehicleControlType,
 };
+pub use accessibility::{Subtitle, SubtitleText, Subtitles};
+pub use airfield::{AirfieldEntry, AirfieldKind, AirfieldRegistry, LandingPad, Runway};
+pub use customization::{
+    PAINT_COLORS, PerformanceTuning, TUNING_STAGES, VehicleCustomization, WheelStyle,
+};
+pub use cutscene::{
+    ActiveCutscene, CameraPose, CutsceneCatalog, CutsceneDefinition, CutsceneFinished,
+    CutsceneKeyframe, CutscenePlayback, LetterboxBar, PlayCutscene, cutscene_playing,
+};
+pub use damage_vignette::DamageVignette;
 pub use debug::MissingSpecsWarned;
+pub use dialogue::{
+    ActiveDialogue, DialogueAction, DialogueBox, DialogueCatalog, DialogueChoice,
+    DialogueChoiceButton, DialogueNode, DialogueSession, DialogueTree, Talkable,
+};
+pub use diving::{Breath, OxygenMeter, OxygenMeterFill};
 pub use dirty_flags::{DirtyFlagsMetrics, DirtyLOD, DirtyVisibility};
+pub use economy::{MoneyCounter, MoneyDeltaText, MoneyPickup, Wallet};
+pub use garage::{
+    CustomizationPart, Garage, GarageBuyButton, GarageCustomizeButton, GarageLocation, GarageMenu, GarageSpawnButton, GarageVehicle,
+    MAX_GARAGE_VEHICLES, NearbyGarage, StoredVehicle,
+};
+pub use health::{
+    DamageSource, DeathFade, DeathFadeText, HealthBar, HealthBarFill, Hospital, PlayerDamaged,
+    PlayerHealth, Wasted,
+};
 pub use input_smoother::InputSmoother;
-pub use map::{MapCamera, MapConfig, MinimapUI, PlayerMapIcon};
+pub use map::{
+    GpsArrow, GpsDestinationBeacon, GpsDistanceText, GpsRoute, MapCamera, MapConfig, MapViewState,
+    MinimapIcon, MinimapRouteDot, MinimapUI, PlayerMapIcon, SetGpsDestination,
+};
 pub use movement_tracker::MovementTracker;
+pub use parachute::{Parachute, ParachuteCanopy, ParachuteState};
+pub use particles::{EmitterControl, Keyframes, ParticleEmitter, ParticleSimulation};
+pub use pedestrian::{HORN_BARKS, HornHonked, Pedestrian, PedestrianBark, PedestrianState};
+pub use police::{
+    CrimeCommitted, CrimeKind, PoliceUnit, WantedLevel, WantedLevelChanged, WantedStarsText,
+};
+pub use schedule::{Activity, NpcSchedule, Routine, Routines};
+pub use shop::{
+    OpenShopRequest, OwnedProperties, PlayerOutfit, Property, PropertyKind, Shop, ShopBuyButton,
+    ShopCatalogs, ShopGood, ShopItem, ShopKind, ShopMenu,
+};
 pub use swimming_events::SwimmingEvent;
+pub use traffic::{CarAlarm, TrafficAgent, TrafficCandidate};
+pub use weather::{
+    ChangeWeather, RainEmitter, WeatherConfig, WeatherKind, WeatherParams, WeatherState,
+};
 pub use underwater_settings::UnderwaterSettings;
 pub use unified_vehicle::UnifiedVehicleSpecs;
+pub use tire::{SurfaceGrip, SurfaceType, TirePhysics};
+pub use transmission::{Transmission, TransmissionMode};
+pub use vehicle_hud::{
+    AttitudeHorizon, HudGear, RpmNeedle, ThrottleFill, VehicleHud, VehicleHudSection,
+    VehicleHudText,
+};
+pub use vehicle_lights::{HeadlightMode, NightLights, StreetLamp, VehicleLamp, VehicleLights};
 pub use yacht_exit::{
-    DeckWalkAnchor, DeckWalkable, DeckWalker, DockedOnYacht, DockingCooldown, Enterable, ExitPoint,
-    ExitPointKind, Helipad, LandedOnYacht,
+    DeckWalkAnchor, DeckWalkable, DeckWalker, Dock, DockedOnYacht, DockingCooldown, Enterable,
+    ExitPoint, ExitPointKind, Helipad, LandedOnYacht, MooredAtDock,
 };
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParachuteState {
+    /// Falling with the canopy still packed
+    Freefall,
+    /// Canopy out; `Parachute::canopy` tracks how far it has inflated
+    Open,
+}
+
+/// Player falling after ejecting or bailing out of a vehicle.
+/// Removed on touchdown, which hands control back to walking.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Parachute {
+    pub state: ParachuteState,
+    /// Canopy inflation, 0 (packed) to 1 (full)
+    pub canopy: f32,
+    /// Ragdoll-style spin from the launch, in rad/s about the body's right axis
+    pub tumble_rate: f32,
+    /// Current tumble pitch, eased back upright as the spin dies out
+    pub tumble_angle: f32,
+}
+
+impl Parachute {
+    pub fn launched(tumble_rate: f32) -> Self {
+        Self {
+            state: ParachuteState::Freefall,
+            canopy: 0.0,
+            tumble_rate,
+            tumble_angle: 0.0,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state == ParachuteState::Open
+    }
+}
+
+/// Canopy mesh above a parachuting player
+#[derive(Component)]
+pub struct ParachuteCanopy;
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul};
+
+/// Values keyed by normalized particle age (0 at birth, 1 at death), sampled
+/// linearly between keys and held flat past either end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Keyframes<T>(pub Vec<(f32, T)>);
+
+impl<T> Keyframes<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<f32, Output = T>,
+{
+    pub fn constant(value: T) -> Self {
+        Self(vec![(0.0, value)])
+    }
+
+    pub fn sample(&self, t: f32) -> T {
+        let keys = &self.0;
+        let Some(&(first_t, first)) = keys.first() else {
+            return T::default();
+        };
+        if t <= first_t {
+            return first;
+        }
+        for pair in keys.windows(2) {
+            let ((t0, a), (t1, b)) = (pair[0], pair[1]);
+            if t <= t1 {
+                let span = (t1 - t0).max(f32::EPSILON);
+                let s = ((t - t0) / span).clamp(0.0, 1.0);
+                return a * (1.0 - s) + b * s;
+            }
+        }
+        keys[keys.len() - 1].1
+    }
+}
+
+/// Where an emitter's particles are simulated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParticleSimulation {
+    /// GPU when the graphics preset allows it, CPU otherwise
+    #[default]
+    Auto,
+    Cpu,
+    Gpu,
+}
+
+/// Particle emitter definition, usually loaded from a prefab as
+/// `Custom("ParticleEmitter", "(...)")`.
+///
+/// An emitter either streams `rate` particles a second or, with a rate of
+/// zero, fires `burst` particles once each time it is (re)started. Velocity is
+/// in the emitter's frame; acceleration is in world space.
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[require(EmitterControl, Transform, Visibility)]
+#[serde(default)]
+pub struct ParticleEmitter {
+    pub rate: f32,                        // 50.0 - Particles per second at full intensity
+    pub burst: u32,                       // 0 - Particles fired at once when rate is 0
+    pub lifetime: (f32, f32),             // (0.5, 1.0) - Random lifetime range in seconds
+    pub velocity: Vec3,                   // (0, 2, 0) - Initial velocity
+    pub velocity_spread: f32,             // 1.0 - Random per-axis velocity offset
+    pub acceleration: Vec3,               // (0, 0, 0) - Constant acceleration, gravity etc.
+    pub drag: f32,                        // 0.0 - Fraction of velocity lost per second
+    pub spawn_radius: f32,                // 0.1 - Particles start inside this sphere
+    pub local_space: bool,                // false - Particles move with the emitter
+    pub align_to_velocity: bool,          // false - Stretch quads along velocity
+    pub speed_over_life: Keyframes<f32>,  // [1.0] - Speed multiplier; CPU only
+    pub color_over_life: Keyframes<Vec4>, // white fading out - HDR RGBA
+    pub size_over_life: Keyframes<Vec2>,  // [(0.3, 0.3)] - Quad size; x along velocity
+    pub capacity: u32,                    // 256 - Most particles alive at once
+    pub simulation: ParticleSimulation,   // Auto - CPU or GPU simulation
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            rate: 50.0,
+            burst: 0,
+            lifetime: (0.5, 1.0),
+            velocity: Vec3::Y * 2.0,
+            velocity_spread: 1.0,
+            acceleration: Vec3::ZERO,
+            drag: 0.0,
+            spawn_radius: 0.1,
+            local_space: false,
+            align_to_velocity: false,
+            speed_over_life: Keyframes::constant(1.0),
+            color_over_life: Keyframes(vec![
+                (0.0, Vec4::ONE),
+                (1.0, Vec4::new(1.0, 1.0, 1.0, 0.0)),
+            ]),
+            size_over_life: Keyframes::constant(Vec2::splat(0.3)),
+            capacity: 256,
+            simulation: ParticleSimulation::Auto,
+        }
+    }
+}
+
+impl ParticleEmitter {
+    /// One-shot emitter that only fires `burst`
+    pub fn is_burst(&self) -> bool {
+        self.rate <= 0.0
+    }
+
+    /// Whether this emitter runs on the GPU given the preset's preference
+    pub fn uses_gpu(&self, gpu_preferred: bool) -> bool {
+        match self.simulation {
+            ParticleSimulation::Auto => gpu_preferred,
+            ParticleSimulation::Cpu => false,
+            ParticleSimulation::Gpu => true,
+        }
+    }
+}
+
+/// Runtime switch for an emitter, driven by gameplay systems
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct EmitterControl {
+    pub active: bool,
+    /// Multiplier on `rate`
+    pub intensity: f32,
+    /// Set by `restart`; cleared once the burst has been fired
+    pub pending_burst: bool,
+}
+
+impl Default for EmitterControl {
+    fn default() -> Self {
+        Self {
+            active: true,
+            intensity: 1.0,
+            pending_burst: true,
+        }
+    }
+}
+
+impl EmitterControl {
+    /// Re-arms a burst emitter, as when it is taken from the pool
+    pub fn restart(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PedestrianState {
+    /// Walking the sidewalk graph
+    Wander,
+    /// Stopped to chat with another pedestrian
+    Converse,
+    /// Running away from a threat
+    Flee,
+    /// Side-stepping an approaching vehicle
+    Dodge,
+}
+
+/// Sidewalk navigation for NPCs; NPCs without it fall back to free roaming
+#[derive(Component, Debug, Clone)]
+pub struct Pedestrian {
+    pub state: PedestrianState,
+    /// Waypoint being walked towards
+    pub target_node: Option<usize>,
+    /// Waypoint just left, avoided when choosing the next one
+    pub previous_node: Option<usize>,
+    /// Time left in the current non-wander state
+    pub state_timer: f32,
+    /// Direction to move while fleeing or dodging, or to face while conversing
+    pub heading: Vec3,
+}
+
+impl Default for Pedestrian {
+    fn default() -> Self {
+        Self {
+            state: PedestrianState::Wander,
+            target_node: None,
+            previous_node: None,
+            state_timer: 0.0,
+            heading: Vec3::ZERO,
+        }
+    }
+}
+
+impl Pedestrian {
+    pub fn enter(&mut self, state: PedestrianState, duration: f32, heading: Vec3) {
+        self.state = state;
+        self.state_timer = duration;
+        self.heading = heading;
+    }
+}
+
+/// Vehicle horn sounded at `position`
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HornHonked {
+    pub position: Vec3,
+    /// Ground-plane heading of the honking vehicle
+    pub direction: Vec3,
+}
+
+/// Localization keys of the lines pedestrians shout at a honking driver
+pub const HORN_BARKS: [&str; 4] = [
+    "bark.horn_watch_it",
+    "bark.horn_road_hog",
+    "bark.horn_learn_to_drive",
+    "bark.horn_what",
+];
+
+/// Pedestrian at `position` shouting `line`, a localization key
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PedestrianBark {
+    pub position: Vec3,
+    pub line: &'static str,
+}
@@ -9,6 +9,10 @@ pub struct ActiveEntity;
 #[derive(Component)]
 pub struct InCar(#[allow(dead_code)] pub Entity);
 
+/// Vehicle the player has taken control of at least once; persisted in save games
+#[derive(Component, Default)]
+pub struct PlayerOwned;
+
 #[derive(Component)]
 pub struct HumanMovement {
     pub acceleration: f32,
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+/// Player notoriety; stars are derived from accumulated heat
+#[derive(Resource, Debug, Clone, Default)]
+pub struct WantedLevel {
+    pub stars: u8,
+    pub heat: f32,
+    /// Seconds since the last crime, drives decay
+    pub time_since_crime: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrimeKind {
+    HitPedestrian,
+    HitVehicle,
+    /// Set off a car alarm while already wanted
+    CarAlarm,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CrimeCommitted {
+    pub kind: CrimeKind,
+    pub position: Vec3,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct WantedLevelChanged {
+    pub previous: u8,
+    pub stars: u8,
+}
+
+/// Police car pursuing the player
+#[derive(Component, Debug, Default)]
+pub struct PoliceUnit;
+
+/// HUD text showing the wanted stars
+#[derive(Component)]
+pub struct WantedStarsText;
@@ -0,0 +1,327 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+fn default_laps() -> u32 {
+    1
+}
+
+fn default_checkpoint_radius() -> f32 {
+    12.0
+}
+
+fn default_rubber_band() -> f32 {
+    0.25
+}
+
+/// One sample of a recorded racing line: where to drive and how fast
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RacingLinePoint {
+    pub position: (f32, f32, f32),
+    /// Target speed in m/s at this point
+    pub speed: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RaceOpponentDefinition {
+    pub name: String,
+    pub color: (f32, f32, f32),
+    /// Multiplier on the racing line speeds
+    #[serde(default = "default_skill")]
+    pub skill: f32,
+}
+
+fn default_skill() -> f32 {
+    1.0
+}
+
+/// A race from `assets/config/races/*.ron`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RaceDefinition {
+    pub id: String,
+    /// May be a localization key
+    pub name: String,
+    /// Start marker and front of the grid; the car stops here to enter
+    pub start: (f32, f32, f32),
+    /// Direction the grid faces, degrees clockwise from north (-Z)
+    pub heading: f32,
+    /// Passed in order every lap; the last one is the finish line
+    pub checkpoints: Vec<(f32, f32, f32)>,
+    #[serde(default = "default_checkpoint_radius")]
+    pub checkpoint_radius: f32,
+    #[serde(default = "default_laps")]
+    pub laps: u32,
+    pub opponents: Vec<RaceOpponentDefinition>,
+    /// Line the opponents drive, looped every lap. Without one they aim
+    /// straight for the checkpoints.
+    #[serde(default)]
+    pub racing_line: Vec<RacingLinePoint>,
+    /// How strongly opponents slow down when ahead of the player and speed
+    /// up when behind, 0 = not at all
+    #[serde(default = "default_rubber_band")]
+    pub rubber_band: f32,
+    /// Paid for first place
+    #[serde(default)]
+    pub reward: u32,
+}
+
+impl RaceDefinition {
+    pub fn checkpoint(&self, index: usize) -> Vec3 {
+        let (x, y, z) = self.checkpoints[index % self.checkpoints.len()];
+        Vec3::new(x, y, z)
+    }
+
+    /// Checkpoints to pass before the finish
+    pub fn total_checkpoints(&self) -> usize {
+        self.checkpoints.len() * self.laps as usize
+    }
+
+    /// Length of the segment ending at checkpoint `index`. The first segment
+    /// starts at the finish line, which the grid sits behind.
+    fn segment_length(&self, index: usize) -> f32 {
+        let count = self.checkpoints.len();
+        let index = index % count;
+        let previous = (index + count - 1) % count;
+        self.checkpoint(previous)
+            .xz()
+            .distance(self.checkpoint(index).xz())
+    }
+
+    pub fn lap_length(&self) -> f32 {
+        (0..self.checkpoints.len())
+            .map(|i| self.segment_length(i))
+            .sum()
+    }
+
+    /// Meters covered by a racer who has passed `passed` checkpoints and is
+    /// at `position`, used to rank racers between checkpoints
+    pub fn course_distance(&self, passed: usize, position: Vec3) -> f32 {
+        if self.checkpoints.is_empty() {
+            return 0.0;
+        }
+        let count = self.checkpoints.len();
+        let completed_laps = (passed / count) as f32 * self.lap_length();
+        let this_lap: f32 = (0..passed % count).map(|i| self.segment_length(i)).sum();
+        let segment = self.segment_length(passed);
+        let remaining = position.xz().distance(self.checkpoint(passed).xz());
+        completed_laps + this_lap + (segment - remaining).clamp(0.0, segment)
+    }
+
+    /// Grid slot `slot` places, two abreast behind the start
+    pub fn grid_slot(&self, slot: usize) -> Transform {
+        let rotation = Quat::from_rotation_y(-self.heading.to_radians());
+        let row = (slot / 2) as f32;
+        let side = if slot.is_multiple_of(2) { -1.0 } else { 1.0 };
+        let (x, y, z) = self.start;
+        let offset = rotation * Vec3::new(side * 3.5, 0.0, row * 9.0);
+        Transform::from_translation(Vec3::new(x, y, z) + offset).with_rotation(rotation)
+    }
+}
+
+/// Every race found in `assets/config/races`
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RaceCatalog {
+    pub races: Vec<RaceDefinition>,
+}
+
+impl RaceCatalog {
+    pub fn get(&self, id: &str) -> Option<&RaceDefinition> {
+        self.races.iter().find(|r| r.id == id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RacePhase {
+    /// Seconds left until the start
+    Countdown(f32),
+    Racing,
+    /// Seconds left on the results screen
+    Results(f32),
+}
+
+/// Where one car stands in the race
+#[derive(Debug, Clone)]
+pub struct RacerProgress {
+    pub entity: Entity,
+    pub name: String,
+    pub is_player: bool,
+    /// Checkpoints passed so far, over all laps
+    pub passed: usize,
+    /// See `RaceDefinition::course_distance`
+    pub distance: f32,
+    pub lap_times: Vec<f32>,
+    /// Race clock when the racer crossed the finish
+    pub finish_time: Option<f32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RaceSession {
+    pub race_id: String,
+    pub phase: RacePhase,
+    /// Race clock, running from the start signal
+    pub clock: f32,
+    pub racers: Vec<RacerProgress>,
+    /// Set when the player left the car or it was destroyed
+    pub abandoned: bool,
+}
+
+impl RaceSession {
+    /// Racer indices from first to last: finishers by time, then the rest by
+    /// distance covered
+    pub fn standings(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.racers.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.racers[a], &self.racers[b]);
+            match (a.finish_time, b.finish_time) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => b.distance.total_cmp(&a.distance),
+            }
+        });
+        order
+    }
+
+    /// 1-based race position of racer `index`
+    pub fn position_of(&self, index: usize) -> usize {
+        self.standings()
+            .iter()
+            .position(|&i| i == index)
+            .map_or(self.racers.len(), |p| p + 1)
+    }
+
+    pub fn player(&self) -> Option<(usize, &RacerProgress)> {
+        self.racers.iter().enumerate().find(|(_, r)| r.is_player)
+    }
+}
+
+/// The race in progress, if any: countdown, lap times and positions
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RaceManager(pub Option<RaceSession>);
+
+/// Speed multiplier for an opponent `gap` meters ahead of the player
+/// (negative when behind). Full effect at `range` meters either way.
+pub fn rubber_band_factor(gap: f32, range: f32, strength: f32) -> f32 {
+    1.0 - strength * (gap / range.max(1.0)).clamp(-1.0, 1.0)
+}
+
+/// Asks for a race to start with the player's current car
+#[derive(Event, Debug, Clone)]
+pub struct StartRaceRequest {
+    pub race_id: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct RaceFinished {
+    pub race_id: String,
+    /// None when the player abandoned the race
+    pub position: Option<usize>,
+    pub time: f32,
+    /// The race's reward when the player won, otherwise 0
+    pub reward: u32,
+}
+
+/// Marker a car stops in to enter a race
+#[derive(Component, Debug, Clone)]
+pub struct RaceStartMarker {
+    pub race_id: String,
+}
+
+/// Checkpoint ring of the running race
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Checkpoint {
+    pub index: usize,
+}
+
+/// AI car in the running race
+#[derive(Component, Debug, Clone)]
+pub struct RaceOpponent {
+    /// Index into `RaceSession::racers`
+    pub racer: usize,
+    pub skill: f32,
+    /// Racing line point currently driven towards
+    pub line_index: usize,
+}
+
+/// Race HUD texts, empty while no race runs
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaceUiText {
+    /// Lap, position and race clock in the top-right corner
+    Status,
+    /// 3-2-1-GO in the middle of the screen
+    Countdown,
+    /// Final standings once the player finishes
+    Results,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> RaceDefinition {
+        RaceDefinition {
+            id: "square".into(),
+            name: "Square".into(),
+            start: (0.0, 0.0, 0.0),
+            heading: 0.0,
+            checkpoints: vec![
+                (0.0, 0.0, -100.0),
+                (100.0, 0.0, -100.0),
+                (100.0, 0.0, 0.0),
+                (0.0, 0.0, 0.0),
+            ],
+            checkpoint_radius: 12.0,
+            laps: 2,
+            opponents: Vec::new(),
+            racing_line: Vec::new(),
+            rubber_band: 0.25,
+            reward: 0,
+        }
+    }
+
+    fn racer(distance: f32, finish_time: Option<f32>) -> RacerProgress {
+        RacerProgress {
+            entity: Entity::PLACEHOLDER,
+            name: String::new(),
+            is_player: false,
+            passed: 0,
+            distance,
+            lap_times: Vec::new(),
+            finish_time,
+        }
+    }
+
+    #[test]
+    fn test_course_distance_counts_laps_and_segments() {
+        let race = square();
+        assert_eq!(race.lap_length(), 400.0);
+        assert_eq!(race.total_checkpoints(), 8);
+        assert_eq!(race.course_distance(0, Vec3::ZERO), 0.0);
+        assert_eq!(race.course_distance(0, Vec3::new(0.0, 0.0, -40.0)), 40.0);
+        assert_eq!(race.course_distance(5, Vec3::new(50.0, 0.0, -100.0)), 550.0);
+    }
+
+    #[test]
+    fn test_standings_put_finishers_first() {
+        let session = RaceSession {
+            race_id: "square".into(),
+            phase: RacePhase::Racing,
+            clock: 60.0,
+            racers: vec![
+                racer(700.0, None),
+                racer(800.0, Some(58.0)),
+                racer(750.0, None),
+                racer(800.0, Some(55.0)),
+            ],
+            abandoned: false,
+        };
+        assert_eq!(session.standings(), vec![3, 1, 2, 0]);
+        assert_eq!(session.position_of(2), 3);
+    }
+
+    #[test]
+    fn test_rubber_band_slows_leaders_and_helps_stragglers() {
+        assert_eq!(rubber_band_factor(0.0, 150.0, 0.25), 1.0);
+        assert_eq!(rubber_band_factor(300.0, 150.0, 0.25), 0.75);
+        assert_eq!(rubber_band_factor(-75.0, 150.0, 0.25), 1.125);
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+/// NPC knocked over by a vehicle.
+/// Full ragdolls split the body parts into jointed rigid bodies; simplified
+/// ones (far from the camera, or NPCs without body parts) just let the single
+/// capsule tumble.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Ragdoll {
+    /// Seconds until the NPC gets up, or is despawned when `fatal`
+    pub timer: f32,
+    pub fatal: bool,
+    /// Body parts are detached and jointed
+    pub full: bool,
+    /// Horizontal direction the NPC was thrown, fled along after getting up
+    pub heading: Vec3,
+}
+
+/// Body part detached from its NPC while ragdolling
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RagdollPart {
+    pub owner: Entity,
+}
+
+/// Which limb an NPC body part is, set by the NPC factory
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RagdollBone {
+    Torso,
+    Head,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+    LeftFoot,
+    RightFoot,
+}
+
+impl RagdollBone {
+    /// Bone this one hangs from; the torso is the root
+    pub fn parent(self) -> Option<RagdollBone> {
+        match self {
+            RagdollBone::Torso => None,
+            RagdollBone::Head
+            | RagdollBone::LeftArm
+            | RagdollBone::RightArm
+            | RagdollBone::LeftLeg
+            | RagdollBone::RightLeg => Some(RagdollBone::Torso),
+            RagdollBone::LeftFoot => Some(RagdollBone::LeftLeg),
+            RagdollBone::RightFoot => Some(RagdollBone::RightLeg),
+        }
+    }
+
+    /// Rest position relative to the NPC root (matches the NPC factory layout)
+    pub fn rest_position(self) -> Vec3 {
+        match self {
+            RagdollBone::Torso => Vec3::new(0.0, 0.6, 0.0),
+            RagdollBone::Head => Vec3::new(0.0, 1.2, 0.0),
+            RagdollBone::LeftArm => Vec3::new(-0.4, 0.7, 0.0),
+            RagdollBone::RightArm => Vec3::new(0.4, 0.7, 0.0),
+            RagdollBone::LeftLeg => Vec3::new(-0.15, 0.0, 0.0),
+            RagdollBone::RightLeg => Vec3::new(0.15, 0.0, 0.0),
+            RagdollBone::LeftFoot => Vec3::new(-0.15, -0.4, 0.1),
+            RagdollBone::RightFoot => Vec3::new(0.15, -0.4, 0.1),
+        }
+    }
+
+    /// Joint position (neck, shoulder, hip, ankle) relative to the NPC root
+    pub fn pivot(self) -> Vec3 {
+        match self {
+            RagdollBone::Torso => Vec3::new(0.0, 0.6, 0.0),
+            RagdollBone::Head => Vec3::new(0.0, 1.0, 0.0),
+            RagdollBone::LeftArm => Vec3::new(-0.35, 0.95, 0.0),
+            RagdollBone::RightArm => Vec3::new(0.35, 0.95, 0.0),
+            RagdollBone::LeftLeg => Vec3::new(-0.15, 0.25, 0.0),
+            RagdollBone::RightLeg => Vec3::new(0.15, 0.25, 0.0),
+            RagdollBone::LeftFoot => Vec3::new(-0.15, -0.35, 0.0),
+            RagdollBone::RightFoot => Vec3::new(0.15, -0.35, 0.0),
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use bevy::prelude::*;
+
+/// Spacing of the samples a rail curve is broken into (m)
+const TRACK_SAMPLE_SPACING: f32 = 4.0;
+
+/// Rail centerline: a centripetal Catmull-Rom curve through the configured
+/// points, kept as a dense polyline with the distance to every sample
+#[derive(Debug, Clone, Default)]
+pub struct RailTrack {
+    points: Vec<Vec3>,
+    distances: Vec<f32>,
+    looped: bool,
+}
+
+/// Point `u` (0..1) of the way from `p1` to `p2` on a centripetal
+/// Catmull-Rom span, which never loops or overshoots on uneven spacing
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, u: f32) -> Vec3 {
+    let knot = |a: Vec3, b: Vec3| a.distance(b).sqrt().max(1e-4);
+    let t1 = knot(p0, p1);
+    let t2 = t1 + knot(p1, p2);
+    let t3 = t2 + knot(p2, p3);
+    let t = t1 + (t2 - t1) * u;
+
+    let lerp = |a: Vec3, b: Vec3, ta: f32, tb: f32| a + (b - a) * ((t - ta) / (tb - ta));
+    let a1 = lerp(p0, p1, 0.0, t1);
+    let a2 = lerp(p1, p2, t1, t2);
+    let a3 = lerp(p2, p3, t2, t3);
+    let b1 = lerp(a1, a2, 0.0, t2);
+    let b2 = lerp(a2, a3, t1, t3);
+    lerp(b1, b2, t1, t2)
+}
+
+impl RailTrack {
+    /// Curve through `control` points; a looped track joins the last point
+    /// back to the first
+    pub fn new(control: &[Vec3], looped: bool) -> Self {
+        let count = control.len();
+        if count < 2 {
+            return Self {
+                points: control.to_vec(),
+                distances: vec![0.0; count],
+                looped: false,
+            };
+        }
+        let at = |index: isize| -> Vec3 {
+            if looped {
+                control[index.rem_euclid(count as isize) as usize]
+            } else {
+                control[index.clamp(0, count as isize - 1) as usize]
+            }
+        };
+
+        let spans = if looped { count } else { count - 1 };
+        let mut points = Vec::new();
+        for span in 0..spans as isize {
+            let (p0, p1, p2, p3) = (at(span - 1), at(span), at(span + 1), at(span + 2));
+            let steps = (p1.distance(p2) / TRACK_SAMPLE_SPACING).ceil().max(1.0) as usize;
+            points.extend(
+                (0..steps).map(|step| catmull_rom(p0, p1, p2, p3, step as f32 / steps as f32)),
+            );
+        }
+        points.push(if looped {
+            control[0]
+        } else {
+            control[count - 1]
+        });
+
+        let mut distances = Vec::with_capacity(points.len());
+        let mut total = 0.0;
+        for (index, point) in points.iter().enumerate() {
+            if index > 0 {
+                total += points[index - 1].distance(*point);
+            }
+            distances.push(total);
+        }
+        Self {
+            points,
+            distances,
+            looped,
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn is_looped(&self) -> bool {
+        self.looped
+    }
+
+    /// Samples along the centerline, first to last
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    /// Distance folded onto the track: wrapped round a loop, clamped to the
+    /// ends of an open line
+    pub fn wrap(&self, distance: f32) -> f32 {
+        let length = self.length();
+        if length <= 0.0 {
+            0.0
+        } else if self.looped {
+            distance.rem_euclid(length)
+        } else {
+            distance.clamp(0.0, length)
+        }
+    }
+
+    /// Position and unit direction (towards increasing distance) at `distance`
+    pub fn sample(&self, distance: f32) -> (Vec3, Vec3) {
+        match self.points.len() {
+            0 => return (Vec3::ZERO, Vec3::Z),
+            1 => return (self.points[0], Vec3::Z),
+            _ => {}
+        }
+        let distance = self.wrap(distance);
+        let next = self
+            .distances
+            .partition_point(|&d| d <= distance)
+            .clamp(1, self.points.len() - 1);
+        let (a, b) = (self.points[next - 1], self.points[next]);
+        let span = (self.distances[next] - self.distances[next - 1]).max(1e-4);
+        let t = ((distance - self.distances[next - 1]) / span).clamp(0.0, 1.0);
+        (a.lerp(b, t), (b - a).normalize_or(Vec3::Z))
+    }
+
+    /// Distance along the track closest to `position`, and how far off the
+    /// track `position` is
+    pub fn nearest(&self, position: Vec3) -> (f32, f32) {
+        let mut best = (0.0, f32::INFINITY);
+        for (index, pair) in self.points.windows(2).enumerate() {
+            let segment = pair[1] - pair[0];
+            let t = ((position - pair[0]).dot(segment) / segment.length_squared().max(1e-6))
+                .clamp(0.0, 1.0);
+            let gap = position.distance(pair[0] + segment * t);
+            if gap < best.1 {
+                let along = self.distances[index] + segment.length() * t;
+                best = (along, gap);
+            }
+        }
+        best
+    }
+
+    /// Distances along the track where the polyline `path` crosses it,
+    /// seen from above
+    pub fn crossings(&self, path: &[Vec3]) -> Vec<f32> {
+        let Some((min, max)) = path.iter().fold(None, |bounds: Option<(Vec2, Vec2)>, p| {
+            let p = p.xz();
+            Some(bounds.map_or((p, p), |(min, max)| (min.min(p), max.max(p))))
+        }) else {
+            return Vec::new();
+        };
+        // Only track segments overlapping the path's bounds can cross it
+        let candidates: Vec<usize> = (0..self.points.len().saturating_sub(1))
+            .filter(|&i| {
+                let (c, d) = (self.points[i].xz(), self.points[i + 1].xz());
+                !(c.max(d).cmplt(min).any() || c.min(d).cmpgt(max).any())
+            })
+            .collect();
+
+        let mut found = Vec::new();
+        for pair in path.windows(2) {
+            let (a, b) = (pair[0].xz(), pair[1].xz());
+            for &index in &candidates {
+                let (c, d) = (self.points[index].xz(), self.points[index + 1].xz());
+                let (r, s) = (b - a, d - c);
+                let denom = r.perp_dot(s);
+                if denom.abs() < 1e-6 {
+                    continue;
+                }
+                let t = (c - a).perp_dot(s) / denom;
+                let u = (c - a).perp_dot(r) / denom;
+                if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+                    let span = self.distances[index + 1] - self.distances[index];
+                    found.push(self.distances[index] + span * u);
+                }
+            }
+        }
+        found
+    }
+
+    /// Meters a train at `from` has to run in `direction` (+1 with the
+    /// track, -1 against it) to reach `to`; negative when `to` is behind it
+    /// on an open line
+    pub fn distance_ahead(&self, from: f32, to: f32, direction: f32) -> f32 {
+        let ahead = (to - from) * direction.signum();
+        if self.looped {
+            ahead.rem_euclid(self.length().max(f32::EPSILON))
+        } else {
+            ahead
+        }
+    }
+}
+
+/// A stop on a rail line
+#[derive(Debug, Clone)]
+pub struct RailStation {
+    pub name: String,
+    /// Distance along the track of the platform's middle
+    pub distance: f32,
+    /// Where passengers step off, on the platform beside the track
+    pub platform: Vec3,
+}
+
+/// One rail route with its stations, sorted by distance along the track
+#[derive(Debug, Clone)]
+pub struct RailLine {
+    pub name: String,
+    pub track: RailTrack,
+    pub stations: Vec<RailStation>,
+    /// Distances along the track where it crosses a road
+    pub crossings: Vec<f32>,
+    /// Cruise speed between stations (m/s)
+    pub cruise_speed: f32,
+    /// Acceleration and braking (m/s²)
+    pub acceleration: f32,
+    /// Seconds spent stopped at each station
+    pub dwell_time: f32,
+    /// Cruise speed multiplier while the player rides along
+    pub express_multiplier: f32,
+}
+
+impl RailLine {
+    /// Station a train leaving `station` in `direction` stops at next, and
+    /// the direction it gets there in. Open lines turn round at their ends.
+    pub fn next_station(&self, station: usize, direction: f32) -> (usize, f32) {
+        let count = self.stations.len();
+        if count < 2 {
+            return (station, -direction);
+        }
+        if self.track.is_looped() {
+            let next = if direction >= 0.0 {
+                (station + 1) % count
+            } else {
+                (station + count - 1) % count
+            };
+            return (next, direction);
+        }
+        match (station, direction >= 0.0) {
+            (s, true) if s + 1 >= count => (s - 1, -1.0),
+            (0, false) => (1, 1.0),
+            (s, true) => (s + 1, 1.0),
+            (s, false) => (s - 1, -1.0),
+        }
+    }
+}
+
+/// Every rail line in the world, built from `assets/config/rail.ron`
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RailNetwork {
+    pub lines: Vec<RailLine>,
+    /// Length of one train car (m)
+    pub car_length: f32,
+    /// Gap between coupled cars (m)
+    pub car_gap: f32,
+    /// Vehicle damage per m/s of train speed on impact
+    pub hit_damage: f32,
+    /// Trains slower than this (m/s) nudge cars without damaging them
+    pub min_hit_speed: f32,
+    /// How close to a stopped train the player has to be to board (m)
+    pub boarding_radius: f32,
+}
+
+impl RailNetwork {
+    /// Track length a train of `cars` cars covers
+    pub fn train_length(&self, cars: usize) -> f32 {
+        cars as f32 * (self.car_length + self.car_gap) - self.car_gap
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrainState {
+    /// Heading for `station`
+    Running { station: usize },
+    /// Stopped at `station` for another `remaining` seconds
+    Dwelling { station: usize, remaining: f32 },
+}
+
+/// A train running along one rail line. Its cars are separate kinematic
+/// bodies placed behind the front along the curve.
+#[derive(Component, Debug, Clone)]
+pub struct Train {
+    pub line: usize,
+    /// Distance along the track of the front of the train
+    pub distance: f32,
+    pub speed: f32,
+    /// +1 runs towards increasing track distance, -1 back
+    pub direction: f32,
+    pub state: TrainState,
+    /// Cars from the front, locomotive first
+    pub cars: Vec<Entity>,
+}
+
+/// One car of a train
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TrainCar {
+    pub train: Entity,
+    pub index: usize,
+}
+
+/// On the player while riding a train
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TrainPassenger {
+    pub train: Entity,
+    pub car: Entity,
+}
+
+/// Warning light at a level crossing, flashing while a train is near
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RailCrossingLight {
+    pub line: usize,
+    pub distance: f32,
+    /// Lights on either side of the road flash in turn
+    pub phase: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_loop() -> RailTrack {
+        RailTrack::new(
+            &[
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(100.0, 0.0, 0.0),
+                Vec3::new(100.0, 0.0, 100.0),
+                Vec3::new(0.0, 0.0, 100.0),
+            ],
+            true,
+        )
+    }
+
+    #[test]
+    fn test_track_sampling_wraps_round_loops() {
+        let track = square_loop();
+        // The curve passes through each corner and bows out between them
+        assert!(track.length() > 400.0 && track.length() < 480.0, "{}", track.length());
+        let (start, _) = track.sample(0.0);
+        assert!(start.distance(Vec3::ZERO) < 1e-3);
+        let (wrapped, _) = track.sample(track.length() + 10.0);
+        assert!(wrapped.distance(track.sample(10.0).0) < 1e-3);
+
+        let (along, gap) = track.nearest(track.sample(120.0).0 + Vec3::Y * 2.0);
+        assert!((along - 120.0).abs() < 0.5);
+        assert!((gap - 2.0).abs() < 1e-3);
+
+        assert!((track.distance_ahead(300.0, 20.0, 1.0) - (track.length() - 280.0)).abs() < 1e-3);
+        assert!((track.distance_ahead(20.0, 300.0, -1.0) - (track.length() - 280.0)).abs() < 1e-3);
+
+        // A road running straight across the loop crosses it twice
+        let crossings =
+            track.crossings(&[Vec3::new(50.0, 0.0, -50.0), Vec3::new(50.0, 0.0, 150.0)]);
+        assert_eq!(crossings.len(), 2);
+        for along in crossings {
+            assert!((track.sample(along).0.x - 50.0).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn test_open_lines_turn_round_at_the_ends() {
+        let line = RailLine {
+            name: "Shuttle".into(),
+            track: RailTrack::new(&[Vec3::ZERO, Vec3::X * 500.0], false),
+            stations: Vec::new(),
+            crossings: Vec::new(),
+            cruise_speed: 30.0,
+            acceleration: 1.0,
+            dwell_time: 10.0,
+            express_multiplier: 1.0,
+        };
+        let stations = |count| RailLine {
+            stations: (0..count)
+                .map(|i| RailStation {
+                    name: format!("S{i}"),
+                    distance: i as f32 * 100.0,
+                    platform: Vec3::ZERO,
+                })
+                .collect(),
+            ..line.clone()
+        };
+        let line = stations(3);
+        assert_eq!(line.next_station(0, 1.0), (1, 1.0));
+        assert_eq!(line.next_station(2, 1.0), (1, -1.0));
+        assert_eq!(line.next_station(0, -1.0), (1, 1.0));
+        assert_eq!(line.track.distance_ahead(400.0, 100.0, 1.0), -300.0);
+
+        let mut looped = line.clone();
+        looped.track = square_loop();
+        assert_eq!(looped.next_station(2, 1.0), (0, 1.0));
+        assert_eq!(looped.next_station(0, -1.0), (2, -1.0));
+    }
+}
@@ -0,0 +1,169 @@
+use super::world::NPCBehaviorType;
+use crate::resources::District;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Ground distance at which an NPC has reached the place of its activity
+pub const ARRIVAL_RADIUS: f32 = 15.0;
+
+/// Where a scheduled NPC wants to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Activity {
+    Home,
+    Work,
+    Park,
+}
+
+/// One day of an NPC's life, from `routines.ron`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Routine {
+    pub name: String,
+    /// Relative share of NPCs following this routine
+    pub weight: f32,
+    /// District of the workplace; without one, work hours are spent at home
+    #[serde(default)]
+    pub work: Option<District>,
+    /// `(hour, activity)` pairs; each activity lasts until the next entry's hour
+    pub entries: Vec<(f32, Activity)>,
+}
+
+impl Routine {
+    /// Activity at `hour`. Before the day's first entry the last one of the
+    /// previous day still holds.
+    pub fn activity_at(&self, hour: f32) -> Activity {
+        let by_start = |a: &&(f32, Activity), b: &&(f32, Activity)| a.0.total_cmp(&b.0);
+        self.entries
+            .iter()
+            .filter(|(start, _)| *start <= hour)
+            .max_by(by_start)
+            .or_else(|| self.entries.iter().max_by(by_start))
+            .map_or(Activity::Home, |(_, activity)| *activity)
+    }
+}
+
+/// Every routine in `assets/config/routines.ron`
+#[derive(Resource, Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Routines {
+    pub routines: Vec<Routine>,
+}
+
+/// Daily routine of an NPC and the places it moves between
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct NpcSchedule {
+    /// Index into `Routines::routines`
+    pub routine: usize,
+    pub home: Vec3,
+    pub work: Vec3,
+    pub park: Vec3,
+    pub activity: Activity,
+    /// Reached the place of the current activity
+    pub arrived: bool,
+}
+
+impl NpcSchedule {
+    pub fn destination(&self) -> Vec3 {
+        match self.activity {
+            Activity::Home => self.home,
+            Activity::Work => self.work,
+            Activity::Park => self.park,
+        }
+    }
+
+    /// Switches to the routine's activity for `hour`; the NPC sets off for
+    /// the new place if it changed
+    pub fn follow(&mut self, routine: &Routine, hour: f32) {
+        let activity = routine.activity_at(hour);
+        if activity != self.activity {
+            self.activity = activity;
+            self.arrived = false;
+        }
+    }
+
+    /// Marks the NPC arrived once `position` is at its destination
+    pub fn reach(&mut self, position: Vec3) -> bool {
+        if !self.arrived && position.xz().distance(self.destination().xz()) <= ARRIVAL_RADIUS {
+            self.arrived = true;
+        }
+        self.arrived
+    }
+
+    /// Waiting at home or work for the next entry of the routine
+    pub fn idle(&self) -> bool {
+        self.arrived && self.activity != Activity::Park
+    }
+
+    /// Coarse movement for NPCs nobody watches: up to `distance` straight
+    /// towards the destination on the ground plane. Returns the new position.
+    pub fn step(&mut self, position: Vec3, distance: f32) -> Vec3 {
+        if self.arrived {
+            return position;
+        }
+        let offset = (self.destination() - position).with_y(0.0);
+        if offset.length() <= distance {
+            self.arrived = true;
+            return position + offset;
+        }
+        position + offset.normalize() * distance
+    }
+
+    pub fn behavior(&self) -> NPCBehaviorType {
+        match (self.arrived, self.activity) {
+            (false, _) => NPCBehaviorType::Commuting,
+            (true, Activity::Home) => NPCBehaviorType::AtHome,
+            (true, Activity::Work) => NPCBehaviorType::Working,
+            (true, Activity::Park) => NPCBehaviorType::Socializing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_follows_entries_and_wraps_past_midnight() {
+        let routine = Routine {
+            name: "office".to_string(),
+            weight: 1.0,
+            work: Some(District::Downtown),
+            entries: vec![
+                (7.5, Activity::Work),
+                (12.0, Activity::Park),
+                (13.0, Activity::Work),
+                (18.0, Activity::Home),
+            ],
+        };
+        assert_eq!(routine.activity_at(3.0), Activity::Home);
+        assert_eq!(routine.activity_at(7.5), Activity::Work);
+        assert_eq!(routine.activity_at(12.5), Activity::Park);
+        assert_eq!(routine.activity_at(17.9), Activity::Work);
+        assert_eq!(routine.activity_at(23.0), Activity::Home);
+
+        let empty = Routine {
+            entries: Vec::new(),
+            ..routine
+        };
+        assert_eq!(empty.activity_at(12.0), Activity::Home);
+    }
+
+    #[test]
+    fn test_coarse_steps_arrive_at_the_destination() {
+        let mut schedule = NpcSchedule {
+            routine: 0,
+            home: Vec3::ZERO,
+            work: Vec3::new(100.0, 0.0, 0.0),
+            park: Vec3::ZERO,
+            activity: Activity::Work,
+            arrived: false,
+        };
+        let position = schedule.step(Vec3::new(0.0, 2.0, 0.0), 60.0);
+        assert_eq!(position, Vec3::new(60.0, 2.0, 0.0));
+        assert_eq!(schedule.behavior(), NPCBehaviorType::Commuting);
+
+        let position = schedule.step(position, 60.0);
+        assert_eq!(position, Vec3::new(100.0, 2.0, 0.0));
+        assert!(schedule.idle());
+        assert_eq!(schedule.behavior(), NPCBehaviorType::Working);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::components::VehicleType;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+/// What a storefront sells; each kind has its own catalog in `shops.ron`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum ShopKind {
+    VehicleDealer,
+    Clothes,
+}
+
+impl ShopKind {
+    pub const ALL: [ShopKind; 2] = [ShopKind::VehicleDealer, ShopKind::Clothes];
+
+    /// Display name, translated with the current localization
+    pub fn label(self) -> String {
+        crate::t!(match self {
+            ShopKind::VehicleDealer => "shop.vehicle_dealer",
+            ShopKind::Clothes => "shop.clothes",
+        })
+    }
+}
+
+/// Storefront on a building's street face; interacting opens its buy menu
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Shop {
+    pub kind: ShopKind,
+}
+
+/// What buying a catalog entry gives the player
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum ShopGood {
+    /// Delivered in front of the shop and recorded in the garage
+    Vehicle(VehicleType),
+    /// Torso color of the player model
+    Outfit([f32; 4]),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ShopItem {
+    pub name: String,
+    pub price: u32,
+    pub good: ShopGood,
+}
+
+/// Items per shop kind, loaded from `shops.ron`
+#[derive(Resource, Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ShopCatalogs {
+    pub vehicle_dealer: Vec<ShopItem>,
+    pub clothes: Vec<ShopItem>,
+}
+
+impl ShopCatalogs {
+    pub fn items(&self, kind: ShopKind) -> &[ShopItem] {
+        match kind {
+            ShopKind::VehicleDealer => &self.vehicle_dealer,
+            ShopKind::Clothes => &self.clothes,
+        }
+    }
+}
+
+/// What owning a property gives the player
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum PropertyKind {
+    /// Interacting while on it saves the game
+    Safehouse,
+    /// Works as a garage once owned; recalled vehicles appear at `spawn_point`
+    Garage { spawn_point: Vec3 },
+}
+
+/// Property for sale, placed from `properties.ron`
+#[derive(Component, Debug, Clone)]
+pub struct Property {
+    /// Stable key stored in saves
+    pub id: String,
+    pub name: String,
+    pub price: u32,
+    pub radius: f32,
+    pub kind: PropertyKind,
+}
+
+/// Ids of the properties the player bought; saved with the game
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct OwnedProperties(pub BTreeSet<String>);
+
+impl OwnedProperties {
+    pub fn owns(&self, id: &str) -> bool {
+        self.0.contains(id)
+    }
+}
+
+/// Torso color bought at a clothes shop; None keeps the default look
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerOutfit(pub Option<[f32; 4]>);
+
+/// Root node of a shop's buy menu; closes once the player walks away from
+/// `anchor`
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShopMenu {
+    pub kind: ShopKind,
+    pub anchor: Vec3,
+    /// Where bought vehicles are delivered
+    pub delivery: Vec3,
+}
+
+/// Opens a shop's buy menu away from a storefront, e.g. from a dialogue choice
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OpenShopRequest {
+    pub kind: ShopKind,
+    pub anchor: Vec3,
+    pub delivery: Vec3,
+}
+
+/// Buys the catalog entry at this index of the open shop
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ShopBuyButton(pub usize);
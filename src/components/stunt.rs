@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::f32::consts::TAU;
+
+/// Scores kept per event in the best-score table
+pub const BEST_SCORES_KEPT: usize = 5;
+
+/// Share of a full turn that still counts as a flip, so a landing a little
+/// short of upright isn't robbed of it
+const FLIP_SLACK: f32 = 0.15;
+
+fn default_zone_radius() -> f32 {
+    8.0
+}
+
+fn default_checkpoint_radius() -> f32 {
+    15.0
+}
+
+fn default_ramp_width() -> f32 {
+    6.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum StuntEventKind {
+    /// Launch off the ramp; the session ends once the car is back down
+    Jump,
+    /// Drive the checkpoints before the clock runs out
+    TimeTrial,
+}
+
+/// Wedge the car launches off, starting at the far edge of the start zone
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct StuntRampDefinition {
+    /// Ground length of the slope
+    pub length: f32,
+    /// Height of the lip
+    pub height: f32,
+    #[serde(default = "default_ramp_width")]
+    pub width: f32,
+}
+
+/// A scoring event from `assets/config/stunts.ron`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StuntEventDefinition {
+    pub id: String,
+    /// May be a localization key
+    pub name: String,
+    pub kind: StuntEventKind,
+    /// Center of the start zone, on the ground
+    pub start: (f32, f32, f32),
+    #[serde(default = "default_zone_radius")]
+    pub radius: f32,
+    /// Direction of the ramp or first leg, degrees clockwise from north (-Z)
+    pub heading: f32,
+    /// Seconds before the session ends on its own
+    pub time_limit: f32,
+    #[serde(default)]
+    pub ramp: Option<StuntRampDefinition>,
+    /// Time trial route, passed in order; the last one is the finish
+    #[serde(default)]
+    pub checkpoints: Vec<(f32, f32, f32)>,
+    #[serde(default = "default_checkpoint_radius")]
+    pub checkpoint_radius: f32,
+}
+
+impl StuntEventDefinition {
+    pub fn start_position(&self) -> Vec3 {
+        let (x, y, z) = self.start;
+        Vec3::new(x, y, z)
+    }
+
+    /// Horizontal unit vector along `heading`
+    pub fn direction(&self) -> Vec3 {
+        Quat::from_rotation_y(-self.heading.to_radians()) * Vec3::NEG_Z
+    }
+
+    pub fn checkpoint(&self, index: usize) -> Option<Vec3> {
+        self.checkpoints
+            .get(index)
+            .map(|&(x, y, z)| Vec3::new(x, y, z))
+    }
+}
+
+/// Every stunt event in `assets/config/stunts.ron`
+#[derive(Resource, Debug, Clone, Default, Deserialize)]
+pub struct StuntCatalog {
+    #[serde(default)]
+    pub events: Vec<StuntEventDefinition>,
+}
+
+impl StuntCatalog {
+    pub fn get(&self, id: &str) -> Option<&StuntEventDefinition> {
+        self.events.iter().find(|e| e.id == id)
+    }
+}
+
+/// Something the player scored points for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StuntTrick {
+    /// Seconds off the ground
+    Airtime(f32),
+    /// Full turns end over end or barrel-rolling
+    Flips(u32),
+    /// Horizontal meters covered in one jump
+    JumpDistance(f32),
+    /// Passed another car at speed without touching it
+    NearMiss,
+    Checkpoint,
+    /// Seconds left on the clock at the finish
+    TimeBonus(f32),
+}
+
+impl StuntTrick {
+    pub fn points(&self) -> u32 {
+        match *self {
+            StuntTrick::Airtime(seconds) => (seconds * 100.0).round() as u32,
+            StuntTrick::Flips(count) => count * count * 500,
+            StuntTrick::JumpDistance(meters) => (meters * 10.0).round() as u32,
+            StuntTrick::NearMiss => 250,
+            StuntTrick::Checkpoint => 200,
+            StuntTrick::TimeBonus(seconds) => (seconds * 50.0).round() as u32,
+        }
+    }
+}
+
+/// Whole flips in `angle` radians of rotation about one axis
+pub fn count_flips(angle: f32) -> u32 {
+    (angle.abs() / TAU + FLIP_SLACK).floor() as u32
+}
+
+/// One jump in progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StuntAirborne {
+    pub takeoff: Vec3,
+    pub time: f32,
+    /// Signed rotation (radians) about the car's right and forward axes
+    pub pitch: f32,
+    pub roll: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StuntPhase {
+    Running,
+    /// Seconds left on the summary screen
+    Summary(f32),
+}
+
+#[derive(Debug, Clone)]
+pub struct StuntSession {
+    pub event_id: String,
+    pub phase: StuntPhase,
+    pub elapsed: f32,
+    pub score: u32,
+    /// Time trial checkpoints passed
+    pub passed: usize,
+    pub airborne: Option<StuntAirborne>,
+    /// Jump events: the car has left the ramp at least once
+    pub launched: bool,
+    /// Seconds the car has been back on its wheels after a jump
+    pub landed_for: f32,
+    /// Cars currently close by, with the nearest they've come
+    pub close_calls: HashMap<Entity, f32>,
+    /// Place in the best-score table, once the session is over
+    pub rank: Option<usize>,
+    /// Time trial finished before the clock ran out
+    pub completed: bool,
+}
+
+impl StuntSession {
+    pub fn new(event_id: String) -> Self {
+        Self {
+            event_id,
+            phase: StuntPhase::Running,
+            elapsed: 0.0,
+            score: 0,
+            passed: 0,
+            airborne: None,
+            launched: false,
+            landed_for: 0.0,
+            close_calls: HashMap::new(),
+            rank: None,
+            completed: false,
+        }
+    }
+}
+
+/// The stunt event being scored, if any
+#[derive(Resource, Debug, Clone, Default)]
+pub struct StuntManager(pub Option<StuntSession>);
+
+/// Best scores per event id, highest first, kept between launches
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StuntScores {
+    pub tables: BTreeMap<String, Vec<u32>>,
+}
+
+impl StuntScores {
+    /// Adds `score` to the event's table, returning its 0-based place if it
+    /// made the cut
+    pub fn record(&mut self, event_id: &str, score: u32) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+        let table = self.tables.entry(event_id.to_string()).or_default();
+        let place = table
+            .iter()
+            .position(|&best| score > best)
+            .or((table.len() < BEST_SCORES_KEPT).then_some(table.len()))?;
+        table.insert(place, score);
+        table.truncate(BEST_SCORES_KEPT);
+        Some(place)
+    }
+
+    pub fn table(&self, event_id: &str) -> &[u32] {
+        self.tables.get(event_id).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Sent when a stunt session ends, whether or not it made the table
+#[derive(Event, Debug, Clone)]
+pub struct StuntEventFinished {
+    pub event_id: String,
+    pub score: u32,
+    pub rank: Option<usize>,
+}
+
+/// Sent for every trick scored, to pop it up on screen
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StuntTrickScored {
+    pub trick: StuntTrick,
+}
+
+/// Start zone of a stunt event
+#[derive(Component, Debug, Clone)]
+pub struct StuntZone {
+    pub event_id: String,
+}
+
+/// Checkpoint ring of the running time trial
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StuntCheckpoint {
+    pub index: usize,
+}
+
+/// Score popup rising out of the middle of the screen
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StuntPopup {
+    pub age: f32,
+}
+
+/// Column the popups are stacked in
+#[derive(Component)]
+pub struct StuntPopupColumn;
+
+/// Stunt HUD texts, empty while no event runs
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StuntUiText {
+    /// Event name, score and clock in the top-right corner
+    Status,
+    /// Final score and the best-score table
+    Summary,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flips_count_near_complete_turns() {
+        assert_eq!(count_flips(0.8 * TAU), 0);
+        assert_eq!(count_flips(-0.9 * TAU), 1);
+        assert_eq!(count_flips(2.1 * TAU), 2);
+        assert_eq!(StuntTrick::Flips(2).points(), 2000);
+    }
+
+    #[test]
+    fn test_best_scores_keep_the_top_entries() {
+        let mut scores = StuntScores::default();
+        assert_eq!(scores.record("ramp", 0), None);
+        for score in [300, 100, 500, 200, 400] {
+            scores.record("ramp", score);
+        }
+        assert_eq!(scores.record("ramp", 350), Some(2));
+        assert_eq!(scores.record("ramp", 50), None);
+        assert_eq!(scores.table("ramp"), &[500, 400, 350, 300, 200]);
+        assert!(scores.table("loop").is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Rain intensity above which paved ground counts as wet
+const WET_RAIN: f32 = 0.5;
+
+/// Ground a car's tires are rolling on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurfaceType {
+    #[default]
+    Asphalt,
+    Grass,
+    Sand,
+    Wet,
+}
+
+impl SurfaceType {
+    /// Roads and airfields are paved (wet in heavy rain), plateaus off the
+    /// road are grass and the beach slopes around them sand
+    pub fn classify(paved: bool, on_plateau: bool, rain_intensity: f32) -> Self {
+        match (paved, on_plateau) {
+            (true, _) if rain_intensity >= WET_RAIN => SurfaceType::Wet,
+            (true, _) => SurfaceType::Asphalt,
+            (false, true) => SurfaceType::Grass,
+            (false, false) => SurfaceType::Sand,
+        }
+    }
+}
+
+/// Grip multiplier per surface, from the car specs
+#[derive(Debug, Clone, Deserialize)]
+pub struct SurfaceGrip {
+    pub asphalt: f32,
+    pub grass: f32,
+    pub sand: f32,
+    pub wet: f32,
+}
+
+impl Default for SurfaceGrip {
+    fn default() -> Self {
+        Self {
+            asphalt: 1.0,
+            grass: 0.6,
+            sand: 0.45,
+            wet: 0.75,
+        }
+    }
+}
+
+impl SurfaceGrip {
+    pub fn get(&self, surface: SurfaceType) -> f32 {
+        match surface {
+            SurfaceType::Asphalt => self.asphalt,
+            SurfaceType::Grass => self.grass,
+            SurfaceType::Sand => self.sand,
+            SurfaceType::Wet => self.wet,
+        }
+        .clamp(0.05, 2.0)
+    }
+}
+
+/// Tire state of a car: the surface under it and how far each axle is
+/// sliding. Written by the ground probe and `car_movement`, read by skid
+/// marks and tire screech.
+#[derive(Component, Debug, Clone, Default)]
+pub struct TirePhysics {
+    pub surface: SurfaceType,
+    /// Sideways sliding speed at the front axle (m/s)
+    pub front_slip: f32,
+    /// Sideways sliding speed at the rear axle (m/s)
+    pub rear_slip: f32,
+    pub skidding: bool,
+    /// Where each rear wheel last laid down a skid mark
+    pub last_marks: [Option<Vec3>; 2],
+}
+
+/// Sideways slide at the front and rear axles of a car moving at `v_local`
+/// (car space). Cars pivot about their middle, so rotating at the
+/// `commanded_yaw` the steering asks for slides neither axle; turning slower
+/// pushes the front wide (understeer), faster swings the rear out
+/// (oversteer). Axles sit at `front_z` and `rear_z` (forward is -Z).
+pub fn axle_slip(
+    v_local: Vec3,
+    yaw_rate: f32,
+    commanded_yaw: f32,
+    front_z: f32,
+    rear_z: f32,
+) -> (f32, f32) {
+    let yaw_error = yaw_rate - commanded_yaw;
+    (
+        v_local.x + yaw_error * front_z,
+        v_local.x + yaw_error * rear_z,
+    )
+}
+
+/// Share of grip an axle keeps while sliding at `slip`: full up to the peak
+/// at `extremum`, then falling off toward 0.2 at `asymptote`
+pub fn slide_grip(slip: f32, extremum: f32, asymptote: f32) -> f32 {
+    let slip = slip.abs();
+    if slip <= extremum {
+        return 1.0;
+    }
+    let t = ((slip - extremum) / (asymptote - extremum).max(0.001)).clamp(0.0, 1.0);
+    0.2 + 0.8 * (-3.0 * t).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_surface_classification() {
+        assert_eq!(SurfaceType::classify(true, true, 0.0), SurfaceType::Asphalt);
+        assert_eq!(SurfaceType::classify(true, false, 0.8), SurfaceType::Wet);
+        assert_eq!(SurfaceType::classify(false, true, 0.8), SurfaceType::Grass);
+        assert_eq!(SurfaceType::classify(false, false, 0.0), SurfaceType::Sand);
+
+        let grip = SurfaceGrip::default();
+        assert!(grip.get(SurfaceType::Sand) < grip.get(SurfaceType::Grass));
+        assert!(grip.get(SurfaceType::Wet) < grip.get(SurfaceType::Asphalt));
+    }
+
+    #[test]
+    fn test_axle_slip_and_slide_grip() {
+        // Turning exactly as steered slides nothing
+        assert_eq!(axle_slip(Vec3::ZERO, 2.0, 2.0, -1.4, 1.4), (0.0, 0.0));
+        // Over-rotating swings the two axles in opposite directions
+        let (front, rear) = axle_slip(Vec3::ZERO, 3.0, 2.0, -1.4, 1.4);
+        assert_eq!((front, rear), (-1.4, 1.4));
+        // A pure sideways slide moves both axles alike
+        assert_eq!(axle_slip(Vec3::X * 3.0, 0.0, 0.0, -1.4, 1.4), (3.0, 3.0));
+
+        assert_eq!(slide_grip(0.5, 1.5, 12.0), 1.0);
+        assert!(slide_grip(6.0, 1.5, 12.0) < 0.5);
+        assert!((slide_grip(50.0, 1.5, 12.0) - (0.2 + 0.8 * (-3.0f32).exp())).abs() < 1e-5);
+    }
+}
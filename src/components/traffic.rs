@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+/// Ambient car that should join traffic once a nearby road is found
+#[derive(Component, Default)]
+pub struct TrafficCandidate;
+
+/// Ambient car following the road network
+#[derive(Component, Debug, Clone)]
+pub struct TrafficAgent {
+    pub road_id: u64,
+    /// Spline parameter along the current road (0.0 = start, 1.0 = end)
+    pub t: f32,
+    /// Travelling from start to end of the spline
+    pub forward: bool,
+    pub road_length: f32,
+    pub speed_limit: f32,
+    pub current_speed: f32,
+    /// Road end in travel direction sits inside an intersection
+    pub stop_at_end: bool,
+    /// Remaining wait time at an intersection stop line
+    pub stop_timer: f32,
+}
+
+impl TrafficAgent {
+    /// Parameter reached when travelling `distance` meters further along the road
+    pub fn t_ahead(&self, distance: f32) -> f32 {
+        let step = distance / self.road_length.max(1.0);
+        if self.forward {
+            (self.t + step).min(1.0)
+        } else {
+            (self.t - step).max(0.0)
+        }
+    }
+
+    /// Meters left until the end of the current road in travel direction
+    pub fn distance_to_end(&self) -> f32 {
+        let remaining = if self.forward { 1.0 - self.t } else { self.t };
+        remaining * self.road_length
+    }
+}
+
+/// Armed alarm on a parked car. Goes off when the car is knocked about and
+/// sounds for `remaining` seconds.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct CarAlarm {
+    pub remaining: f32,
+}
+
+/// Lamp flashes per second while an alarm sounds
+const ALARM_FLASH_RATE: f32 = 2.0;
+
+impl CarAlarm {
+    pub fn sounding(&self) -> bool {
+        self.remaining > 0.0
+    }
+
+    /// A quiet alarm goes off once a bump moves the car faster than
+    /// `trigger_speed` across the ground
+    pub fn tripped(&self, velocity: Vec3, trigger_speed: f32) -> bool {
+        !self.sounding() && velocity.with_y(0.0).length() > trigger_speed
+    }
+
+    /// Lamps on for the first half of every flash
+    pub fn flash_on(&self) -> bool {
+        self.sounding() && (self.remaining * ALARM_FLASH_RATE).fract() >= 0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alarm_trips_on_a_bump_and_flashes_while_sounding() {
+        let mut alarm = CarAlarm::default();
+        // Settling onto its wheels is not a bump
+        assert!(!alarm.tripped(Vec3::new(0.0, -4.0, 0.0), 1.5));
+        assert!(alarm.tripped(Vec3::new(2.0, 0.0, 0.5), 1.5));
+
+        alarm.remaining = 10.0;
+        assert!(!alarm.tripped(Vec3::new(5.0, 0.0, 0.0), 1.5));
+        alarm.remaining = 9.9;
+        assert!(alarm.flash_on());
+        alarm.remaining = 9.6;
+        assert!(!alarm.flash_on());
+        alarm.remaining = 0.0;
+        assert!(!alarm.flash_on());
+    }
+}
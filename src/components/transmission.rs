@@ -0,0 +1,282 @@
+use crate::components::SimpleCarSpecs;
+use bevy::prelude::*;
+
+/// Rate at which the revs chase their target while the clutch slips or is open
+const FREE_REV_RATE: f32 = 8.0;
+
+/// Road speed below which the automatic box picks reverse or first
+const STANDSTILL_SPEED: f32 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransmissionMode {
+    #[default]
+    Automatic,
+    Manual,
+}
+
+/// Gearbox and engine state of a car. `car_movement` scales its drive by
+/// what `update` returns; the HUD and engine audio read the gear and revs.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Transmission {
+    pub mode: TransmissionMode,
+    /// -1 reverse, 0 neutral, 1.. forward gears
+    pub gear: i32,
+    /// Engine speed in revolutions per minute
+    pub rpm: f32,
+    /// 0 fully slipping to 1 locked
+    pub clutch: f32,
+    /// Seconds left of the current shift, during which no drive reaches the wheels
+    pub shift_timer: f32,
+}
+
+impl Default for Transmission {
+    fn default() -> Self {
+        Self {
+            mode: TransmissionMode::Automatic,
+            gear: 1,
+            rpm: 0.0,
+            clutch: 1.0,
+            shift_timer: 0.0,
+        }
+    }
+}
+
+/// Combined gear and final drive ratio, signed like the gear (0 in neutral)
+pub fn total_ratio(specs: &SimpleCarSpecs, gear: i32) -> f32 {
+    match gear {
+        0 => 0.0,
+        g if g < 0 => -specs.reverse_ratio * specs.final_drive,
+        g => {
+            let index = (g as usize - 1).min(specs.gear_ratios.len().saturating_sub(1));
+            specs.gear_ratios.get(index).copied().unwrap_or(1.0) * specs.final_drive
+        }
+    }
+}
+
+/// Engine rpm that turns the wheels at `road_speed` in `gear`
+pub fn engine_rpm_at(specs: &SimpleCarSpecs, road_speed: f32, gear: i32) -> f32 {
+    let wheel_rad_per_sec = road_speed.abs() / specs.wheel_radius.max(0.05);
+    wheel_rad_per_sec * total_ratio(specs, gear).abs() * 60.0 / std::f32::consts::TAU
+}
+
+/// Fastest road speed `gear` reaches before the redline
+pub fn gear_top_speed(specs: &SimpleCarSpecs, gear: i32) -> f32 {
+    let ratio = total_ratio(specs, gear).abs();
+    if ratio <= f32::EPSILON {
+        return 0.0;
+    }
+    specs.redline_rpm * std::f32::consts::TAU / 60.0 / ratio * specs.wheel_radius
+}
+
+/// Share of peak torque at `rpm`, interpolated along the torque curve
+pub fn torque_at(specs: &SimpleCarSpecs, rpm: f32) -> f32 {
+    let curve = &specs.torque_curve;
+    let (Some(first), Some(last)) = (curve.first(), curve.last()) else {
+        return 1.0;
+    };
+    if rpm <= first.0 {
+        return first.1;
+    }
+    if rpm >= last.0 {
+        return last.1;
+    }
+    let next = curve.partition_point(|point| point.0 <= rpm);
+    let ((rpm_a, torque_a), (rpm_b, torque_b)) = (curve[next - 1], curve[next]);
+    let t = (rpm - rpm_a) / (rpm_b - rpm_a).max(f32::EPSILON);
+    torque_a + (torque_b - torque_a) * t
+}
+
+impl Transmission {
+    pub fn top_gear(specs: &SimpleCarSpecs) -> i32 {
+        specs.gear_ratios.len().max(1) as i32
+    }
+
+    /// Engine speed as a share of the range from idle to the redline
+    pub fn rev_share(&self, specs: &SimpleCarSpecs) -> f32 {
+        ((self.rpm - specs.idle_rpm) / (specs.redline_rpm - specs.idle_rpm).max(1.0))
+            .clamp(0.0, 1.0)
+    }
+
+    fn start_shift(&mut self, gear: i32, specs: &SimpleCarSpecs) {
+        if gear != self.gear {
+            self.gear = gear;
+            self.shift_timer = specs.shift_time;
+        }
+    }
+
+    /// Manual shift by `delta` gears; going below first passes through
+    /// neutral into reverse, which only engages near a standstill
+    pub fn shift(&mut self, delta: i32, forward_speed: f32, specs: &SimpleCarSpecs) {
+        let target = (self.gear + delta).clamp(-1, Self::top_gear(specs));
+        if target < 0 && forward_speed > STANDSTILL_SPEED {
+            return;
+        }
+        self.start_shift(target, specs);
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            TransmissionMode::Automatic => TransmissionMode::Manual,
+            TransmissionMode::Manual => TransmissionMode::Automatic,
+        };
+    }
+
+    /// Advances the gearbox by `dt` and returns how much of the engine's
+    /// pull reaches the wheels: torque at the current revs times gearing,
+    /// zero mid-shift, in neutral or on the rev limiter
+    pub fn update(
+        &mut self,
+        specs: &SimpleCarSpecs,
+        forward_speed: f32,
+        throttle: f32,
+        reverse: f32,
+        dt: f32,
+    ) -> f32 {
+        self.shift_timer = (self.shift_timer - dt).max(0.0);
+
+        if self.mode == TransmissionMode::Automatic {
+            self.auto_shift(specs, forward_speed, throttle, reverse);
+        }
+
+        let pedal = if self.gear < 0 { reverse } else { throttle }.clamp(0.0, 1.0);
+        let wheel_rpm = engine_rpm_at(specs, forward_speed, self.gear);
+        let free_rpm = specs.idle_rpm + (specs.redline_rpm - specs.idle_rpm) * pedal;
+
+        self.clutch = if self.gear == 0 {
+            0.0
+        } else {
+            (forward_speed.abs() / specs.clutch_slip_speed.max(0.1)).clamp(0.0, 1.0)
+        };
+        let target_rpm = if self.gear == 0 {
+            free_rpm
+        } else {
+            // A slipping clutch lets the engine hold launch revs above the wheels
+            let launch = specs.idle_rpm + (specs.launch_rpm - specs.idle_rpm) * pedal;
+            wheel_rpm.max(launch * (1.0 - self.clutch))
+        };
+        self.rpm = if self.gear == 0 || self.clutch < 1.0 {
+            self.rpm + (target_rpm - self.rpm) * (dt * FREE_REV_RATE).min(1.0)
+        } else {
+            target_rpm
+        }
+        .clamp(specs.idle_rpm, specs.redline_rpm);
+
+        if self.gear == 0 || self.shift_timer > 0.0 || wheel_rpm >= specs.redline_rpm {
+            return 0.0;
+        }
+        torque_at(specs, self.rpm) * self.gearing(specs)
+    }
+
+    /// Low gears pull harder than high ones, relative to a mid gear
+    fn gearing(&self, specs: &SimpleCarSpecs) -> f32 {
+        let first = total_ratio(specs, 1);
+        let top = total_ratio(specs, Self::top_gear(specs));
+        let reference = (first * top).sqrt().max(f32::EPSILON);
+        (total_ratio(specs, self.gear).abs() / reference).clamp(0.4, 1.6)
+    }
+
+    fn auto_shift(
+        &mut self,
+        specs: &SimpleCarSpecs,
+        forward_speed: f32,
+        throttle: f32,
+        reverse: f32,
+    ) {
+        if forward_speed.abs() < STANDSTILL_SPEED {
+            if reverse > 0.0 && self.gear >= 0 {
+                self.start_shift(-1, specs);
+            } else if throttle > 0.0 && self.gear <= 0 {
+                self.start_shift(1, specs);
+            }
+        }
+        if self.gear <= 0 || self.shift_timer > 0.0 {
+            return;
+        }
+        let rpm = engine_rpm_at(specs, forward_speed, self.gear);
+        if rpm > specs.upshift_rpm && self.gear < Self::top_gear(specs) {
+            self.start_shift(self.gear + 1, specs);
+        } else if self.gear > 1 && rpm < specs.downshift_rpm {
+            // Only drop a gear if the revs there stay under the upshift point
+            let lower = engine_rpm_at(specs, forward_speed, self.gear - 1);
+            if lower < specs.upshift_rpm {
+                self.start_shift(self.gear - 1, specs);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gearing_and_torque_curve() {
+        let specs = SimpleCarSpecs::default();
+        let first = gear_top_speed(&specs, 1);
+        let sixth = gear_top_speed(&specs, 6);
+        assert!(
+            first > 18.0 && first < 26.0,
+            "first gear tops out at {first}"
+        );
+        assert!(sixth > specs.base_speed);
+        assert!((engine_rpm_at(&specs, first, 1) - specs.redline_rpm).abs() < 1.0);
+        assert_eq!(total_ratio(&specs, 0), 0.0);
+        assert!(total_ratio(&specs, -1) < 0.0);
+
+        assert_eq!(torque_at(&specs, 0.0), 0.55);
+        assert_eq!(torque_at(&specs, 5000.0), 1.0);
+        assert!((torque_at(&specs, 4000.0) - 0.925).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_automatic_shifts_and_launch_slip() {
+        let specs = SimpleCarSpecs::default();
+        let mut gearbox = Transmission::default();
+
+        // Launch: the clutch slips and the engine holds revs above the wheels
+        for _ in 0..30 {
+            gearbox.update(&specs, 0.5, 1.0, 0.0, 1.0 / 60.0);
+        }
+        assert_eq!(gearbox.gear, 1);
+        assert!(gearbox.clutch < 0.2);
+        assert!(gearbox.rpm > engine_rpm_at(&specs, 0.5, 1) + 1000.0);
+
+        // Near the redline in first it shifts up and cuts drive for the shift
+        let drive = gearbox.update(&specs, gear_top_speed(&specs, 1) * 0.95, 1.0, 0.0, 0.01);
+        assert_eq!(gearbox.gear, 2);
+        assert_eq!(drive, 0.0);
+
+        // Slowing down drops back a gear, stopping and reversing selects R
+        gearbox.shift_timer = 0.0;
+        gearbox.update(&specs, 5.0, 0.0, 0.0, 0.01);
+        assert_eq!(gearbox.gear, 1);
+        gearbox.shift_timer = 0.0;
+        gearbox.update(&specs, 0.0, 0.0, 1.0, 0.01);
+        assert_eq!(gearbox.gear, -1);
+    }
+
+    #[test]
+    fn test_manual_shifting_limits() {
+        let specs = SimpleCarSpecs::default();
+        let mut gearbox = Transmission {
+            mode: TransmissionMode::Manual,
+            ..default()
+        };
+        gearbox.shift(-1, 20.0, &specs);
+        assert_eq!(gearbox.gear, 0);
+        // No reverse while rolling forward
+        gearbox.shift(-1, 20.0, &specs);
+        assert_eq!(gearbox.gear, 0);
+        gearbox.shift(-1, 0.0, &specs);
+        assert_eq!(gearbox.gear, -1);
+        gearbox.shift(10, 0.0, &specs);
+        assert_eq!(gearbox.gear, 6);
+
+        // Manual boxes hold the gear, bouncing off the limiter
+        gearbox.gear = 1;
+        gearbox.shift_timer = 0.0;
+        let drive = gearbox.update(&specs, gear_top_speed(&specs, 1) + 1.0, 1.0, 0.0, 0.01);
+        assert_eq!(gearbox.gear, 1);
+        assert_eq!(drive, 0.0);
+    }
+}
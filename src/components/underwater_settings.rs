@@ -2,14 +2,23 @@ use bevy::prelude::*;
 use bevy::render::extract_component::ExtractComponent;
 use bevy::render::render_resource::ShaderType;
 
-#[derive(Component, ExtractComponent, Clone, ShaderType)]
-pub struct UnderwaterSettings {
-    pub sea_level: f32,
-    pub fog_density: f32,
-    #[align(16)]
-    pub absorption: Vec3,
-    pub scatter_color: Vec3,
-    pub enabled: u32,
+pub use uniform::UnderwaterSettings;
+
+// ShaderType derive emits unused `check` helpers that newer rustc flags as
+// dead code; the module keeps the allow to the derive alone
+#[allow(dead_code)]
+mod uniform {
+    use super::*;
+
+    #[derive(Component, ExtractComponent, Clone, ShaderType)]
+    pub struct UnderwaterSettings {
+        pub sea_level: f32,
+        pub fog_density: f32,
+        #[align(16)]
+        pub absorption: Vec3,
+        pub scatter_color: Vec3,
+        pub enabled: u32,
+    }
 }
 
 impl Default for UnderwaterSettings {
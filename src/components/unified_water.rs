@@ -24,6 +24,22 @@ impl UnifiedWaterBody {
         self.surface_level + self.tide.offset(time)
     }
 
+    /// Horizontal center of the region; wave phases are measured from here,
+    /// matching the surface mesh which is spawned at the center
+    pub fn center(&self) -> Vec2 {
+        let (min_x, min_z, max_x, max_z) = self.bounds;
+        Vec2::new(min_x + max_x, min_z + max_z) * 0.5
+    }
+
+    /// Water height at a point including wave displacement, for hull buoyancy.
+    /// Swimming and other gameplay checks keep using `get_base_water_level`.
+    pub fn sample_surface_height(&self, x: f32, z: f32, time: f32) -> f32 {
+        let waves = self.wave_params.as_ref().map_or(0.0, |waves| {
+            waves.height_at(Vec2::new(x, z) - self.center(), time)
+        });
+        self.get_base_water_level(time) + waves
+    }
+
     pub fn get_bed_level(&self) -> f32 {
         self.surface_level - self.depth
     }
@@ -166,6 +182,15 @@ impl TideConfig {
     }
 }
 
+/// Gerstner swells rendered on every water surface.
+/// Format: (dir.x, dir.y, amplitude, wavelength); directions are normalized on use
+pub const GERSTNER_SWELLS: [Vec4; 4] = [
+    Vec4::new(1.0, 0.2, 0.8, 120.0), // Large ocean swells (visible at horizon)
+    Vec4::new(-0.6, 1.0, 0.5, 80.0), // Medium swells
+    Vec4::new(0.2, -1.0, 0.3, 50.0), // Smaller waves
+    Vec4::new(-1.0, -0.3, 0.15, 25.0), // Detail waves
+];
+
 /// Wave parameters for visual surface displacement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WaveParams {
@@ -174,10 +199,78 @@ pub struct WaveParams {
     pub speed: f32,
 }
 
+impl WaveParams {
+    /// Swell amplitude multiplier applied to `GERSTNER_SWELLS` (0.25 is the reference)
+    pub fn amplitude_scale(&self) -> f32 {
+        (self.amplitude / 0.25).clamp(0.5, 2.0)
+    }
+
+    /// Vertical wave displacement at `local` (relative to the region center),
+    /// using the same swells, scale and dispersion as the water shader
+    pub fn height_at(&self, local: Vec2, time: f32) -> f32 {
+        let scale = self.amplitude_scale();
+        GERSTNER_SWELLS
+            .iter()
+            .map(|swell| {
+                let direction = swell.truncate().truncate().normalize_or_zero();
+                let k = std::f32::consts::TAU / swell.w.max(1.0);
+                // speed 0.0 = deep-water dispersion w = sqrt(g*k)
+                let w = if self.speed > 0.0 {
+                    self.speed
+                } else {
+                    (9.81 * k).sqrt()
+                };
+                swell.z * scale * (k * direction.dot(local) - w * time).sin()
+            })
+            .sum()
+    }
+}
+
 /// Marker component for entities that should experience water physics
 #[derive(Component, Default)]
 pub struct WaterBodyId;
 
+/// Floats a rigid body on hull sample points instead of its collider box.
+/// Each submerged point pushes up with its share of the body's weight, scaled
+/// by depth, so the body settles with its points `rest_depth` below the
+/// surface and rolls back upright when one side dips.
+#[derive(Component, Debug, Clone)]
+pub struct BuoyancyHull {
+    /// Sample points in the body's local space, usually along the keel
+    pub sample_points: Vec<Vec3>,
+    /// Submersion depth at which a point carries exactly its share of the weight
+    pub rest_depth: f32,
+    /// Upper bound on a point's lift, as a multiple of its share of the weight
+    pub max_lift: f32,
+    /// Vertical drag per submerged point (1/s, scaled by its share of the mass)
+    pub vertical_drag: f32,
+    /// Horizontal drag per submerged point (1/s, scaled by its share of the mass)
+    pub horizontal_drag: f32,
+}
+
+impl BuoyancyHull {
+    /// 3x3 grid of points across the bottom face of a box hull
+    pub fn box_keel(half_extents: Vec3, rest_depth: f32) -> Self {
+        let mut sample_points = Vec::with_capacity(9);
+        for x in [-1.0, 0.0, 1.0] {
+            for z in [-1.0, 0.0, 1.0] {
+                sample_points.push(Vec3::new(
+                    x * half_extents.x,
+                    -half_extents.y,
+                    z * half_extents.z,
+                ));
+            }
+        }
+        Self {
+            sample_points,
+            rest_depth: rest_depth.max(0.05),
+            max_lift: 2.5,
+            vertical_drag: 1.5,
+            horizontal_drag: 0.1,
+        }
+    }
+}
+
 /// Links a water surface mesh to its parent water region
 /// Enables O(1) updates instead of O(N) name-based scanning
 #[derive(Component)]
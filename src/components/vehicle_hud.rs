@@ -0,0 +1,145 @@
+use crate::components::VehicleType;
+use bevy::prelude::*;
+
+/// Top speed share where each gear of the HUD's simulated gearbox runs out.
+/// Low gears are short, like a real box.
+const GEAR_TOPS: [f32; 6] = [0.12, 0.25, 0.4, 0.56, 0.75, 1.0];
+
+/// Engine speed shown at a standstill, as a share of the redline
+pub const IDLE_RPM: f32 = 0.15;
+
+/// Gear shown on the vehicle HUD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudGear {
+    Reverse,
+    Neutral,
+    Drive(usize),
+}
+
+impl HudGear {
+    /// Gear of a `Transmission` (-1 reverse, 0 neutral)
+    pub fn from_gear(gear: i32) -> Self {
+        match gear {
+            0 => HudGear::Neutral,
+            g if g < 0 => HudGear::Reverse,
+            g => HudGear::Drive(g as usize),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            HudGear::Reverse => "R".into(),
+            HudGear::Neutral => "N".into(),
+            HudGear::Drive(gear) => gear.to_string(),
+        }
+    }
+}
+
+/// Gear and engine speed (share of the redline) an automatic gearbox would
+/// show at `forward_speed` (negative when reversing) in a vehicle topping
+/// out at `max_speed`. Display-only fallback for vehicles without a `Transmission`.
+pub fn simulated_gearbox(forward_speed: f32, max_speed: f32) -> (HudGear, f32) {
+    let share = forward_speed / max_speed.max(1.0);
+    if share.abs() < 0.01 {
+        return (HudGear::Neutral, IDLE_RPM);
+    }
+    if share < 0.0 {
+        let rpm = (-share / GEAR_TOPS[0]).clamp(IDLE_RPM, 1.0);
+        return (HudGear::Reverse, rpm);
+    }
+    let gear = GEAR_TOPS
+        .iter()
+        .position(|&top| share <= top)
+        .unwrap_or(GEAR_TOPS.len() - 1);
+    let rpm = (share / GEAR_TOPS[gear]).clamp(IDLE_RPM, 1.0);
+    (HudGear::Drive(gear + 1), rpm)
+}
+
+/// Root of the vehicle HUD, hidden on foot
+#[derive(Component)]
+pub struct VehicleHud;
+
+/// Part of the vehicle HUD shown only for some vehicles; the layout
+/// switches with the vehicle the player is in
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VehicleHudSection {
+    /// Gear and rev counter
+    Drivetrain,
+    /// Altitude and attitude indicator
+    Flight,
+    /// Throttle setting bar
+    Throttle,
+}
+
+impl VehicleHudSection {
+    pub fn shown_for(self, vehicle: VehicleType) -> bool {
+        match self {
+            VehicleHudSection::Drivetrain => vehicle == VehicleType::SuperCar,
+            VehicleHudSection::Flight => {
+                matches!(vehicle, VehicleType::Helicopter | VehicleType::F16)
+            }
+            VehicleHudSection::Throttle => matches!(vehicle, VehicleType::F16 | VehicleType::Yacht),
+        }
+    }
+}
+
+/// Vehicle HUD readouts
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VehicleHudText {
+    Speed,
+    Gear,
+    Altitude,
+    Attitude,
+    Throttle,
+}
+
+/// Rev counter needle, rotated about the dial's center
+#[derive(Component)]
+pub struct RpmNeedle;
+
+/// Sky/ground card of the attitude indicator, shifted by pitch and turned by roll
+#[derive(Component)]
+pub struct AttitudeHorizon;
+
+/// Filled part of the throttle bar
+#[derive(Component)]
+pub struct ThrottleFill;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_gearbox_shifts_up_with_speed() {
+        assert_eq!(simulated_gearbox(0.0, 70.0), (HudGear::Neutral, IDLE_RPM));
+        assert_eq!(simulated_gearbox(-5.0, 70.0).0, HudGear::Reverse);
+
+        let (gear, rpm) = simulated_gearbox(8.0, 70.0);
+        assert_eq!(gear, HudGear::Drive(1));
+        assert!(rpm > 0.9);
+        // Revs drop after the shift into second
+        let (gear, rpm) = simulated_gearbox(9.0, 70.0);
+        assert_eq!(gear, HudGear::Drive(2));
+        assert!(rpm < 0.6);
+        assert_eq!(simulated_gearbox(90.0, 70.0), (HudGear::Drive(6), 1.0));
+
+        assert_eq!(HudGear::from_gear(-1).label(), "R");
+        assert_eq!(HudGear::from_gear(0).label(), "N");
+        assert_eq!(HudGear::from_gear(3).label(), "3");
+    }
+
+    #[test]
+    fn test_sections_follow_vehicle_type() {
+        use VehicleHudSection::*;
+        let shown = |vehicle| {
+            [Drivetrain, Flight, Throttle]
+                .into_iter()
+                .filter(|section| section.shown_for(vehicle))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shown(VehicleType::SuperCar), vec![Drivetrain]);
+        assert_eq!(shown(VehicleType::Helicopter), vec![Flight]);
+        assert_eq!(shown(VehicleType::F16), vec![Flight, Throttle]);
+        assert_eq!(shown(VehicleType::Yacht), vec![Throttle]);
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+/// How a car's headlights are switched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeadlightMode {
+    /// On at night, off by day
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl HeadlightMode {
+    pub fn next(self) -> Self {
+        match self {
+            HeadlightMode::Auto => HeadlightMode::On,
+            HeadlightMode::On => HeadlightMode::Off,
+            HeadlightMode::Off => HeadlightMode::Auto,
+        }
+    }
+
+    pub fn is_on(self, night: bool) -> bool {
+        match self {
+            HeadlightMode::Auto => night,
+            HeadlightMode::On => true,
+            HeadlightMode::Off => false,
+        }
+    }
+}
+
+/// Headlight switch of a car whose children carry `VehicleLamp`s
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct VehicleLights {
+    pub mode: HeadlightMode,
+}
+
+/// Light on a car: a `SpotLight` for `Head`, a `PointLight` for `Tail`
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VehicleLamp {
+    Head,
+    Tail,
+}
+
+/// Pooled point light placed on one of the streetlights nearest the camera
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct StreetLamp;
+
+/// Whether the sun is low enough for lamps to be on
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct NightLights {
+    pub night: bool,
+}
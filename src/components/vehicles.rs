@@ -21,6 +21,7 @@
 //! - [`VehicleRendering`] - LOD rendering management
 //! - [`AircraftFlight`] - Minimal flight state for aircraft
 
+use crate::components::tire::SurfaceGrip;
 use bevy::prelude::*;
 use bevy::reflect::TypePath;
 
@@ -102,8 +103,49 @@ pub struct AircraftFlight {
     pub throttle: f32, // 0.0-1.0, processed from controls
     pub airspeed: f32, // For UI/debugging only
     pub afterburner_active: bool,
+
+    // Flight envelope state
+    pub angle_of_attack: f32, // Radians, positive when the nose is above the flight path
+    pub stalled: bool,
+    pub g_load: f32, // Load factor along the aircraft's up axis (1.0 in level flight)
+    pub blackout: f32, // 0.0-1.0 pilot blackout from sustained high G
+    pub fuel_used: f32, // kg burned since spawn
+}
+
+impl AircraftFlight {
+    pub fn fuel_remaining(&self, specs: &SimpleF16Specs) -> f32 {
+        (specs.fuel_capacity - self.fuel_used).max(0.0)
+    }
+}
+
+/// Retractable landing gear on fixed-wing aircraft
+#[derive(Component, Debug, Clone)]
+pub struct LandingGear {
+    pub deployed: bool, // Commanded position
+    pub extension: f32, // 0.0 retracted - 1.0 down and locked
+    pub airborne: bool, // Set once properly flying, cleared at touchdown
+}
+
+impl Default for LandingGear {
+    fn default() -> Self {
+        Self {
+            deployed: true,
+            extension: 1.0,
+            airborne: false,
+        }
+    }
+}
+
+impl LandingGear {
+    pub fn is_down(&self) -> bool {
+        self.extension >= 1.0
+    }
 }
 
+/// Visual gear leg, scaled with its aircraft's `LandingGear` extension
+#[derive(Component)]
+pub struct LandingGearStrut;
+
 // Simplified F16 specifications - all tuning constants data-driven
 // Asset-driven configuration following YachtSpecs pattern
 #[derive(Asset, TypePath, Component, Clone, serde::Deserialize)]
@@ -116,14 +158,13 @@ pub struct SimpleF16Specs {
     pub throttle_decrease_rate: f32,
     pub linear_damping: f32,
     pub angular_damping: f32,
-    pub lift_per_throttle: f32,
     pub drag_factor: f32, // Momentum decay when engine off
 
     // Previously magic numbers in code
     pub afterburner_multiplier: f32, // Speed multiplier for afterburner
     pub linear_lerp_factor: f32,     // Linear velocity smoothing rate
     pub angular_lerp_factor: f32,    // Angular velocity smoothing rate
-    pub throttle_deadzone: f32,      // Minimum throttle for lift activation
+    pub throttle_deadzone: f32,      // Minimum throttle for thrust
 
     // GTA-style input shaping and discrete step rates
     pub input_deadzone: f32, // Input threshold below which it's treated as zero
@@ -148,8 +189,30 @@ pub struct SimpleF16Specs {
     pub auto_bank_gain: f32,     // Roll rate per m/s lateral velocity
     pub auto_bank_max_rate: f32, // Maximum auto-bank contribution
 
-    // Optional banked-lift feedback
-    pub bank_lift_scale: f32, // How much lift reduces when banked (0-1)
+    // Flight envelope
+    pub stall_speed: f32, // Speed where lift at the critical AoA equals weight (m/s)
+    pub stall_aoa_deg: f32, // Critical angle of attack (degrees)
+    pub post_stall_lift: f32, // Lift fraction left past the critical AoA
+    pub post_stall_grip: f32, // How well velocity follows the nose while stalled (0-1)
+    pub stall_pitch_down_rate: f32, // Nose-drop rate while stalled (rad/s)
+    pub max_g: f32,       // Positive load limit enforced on pitch input
+    pub min_g: f32,       // Negative load limit enforced on pitch input
+    pub blackout_onset_g: f32, // Load factor above which blackout builds
+    pub blackout_rate: f32, // Blackout gained per second per G above onset
+    pub blackout_recovery_rate: f32, // Blackout recovered per second below onset
+
+    // Fuel
+    pub fuel_capacity: f32,              // Internal fuel (kg)
+    pub fuel_burn_rate: f32,             // Burn at full dry throttle (kg/s)
+    pub afterburner_fuel_burn_rate: f32, // Extra burn while the afterburner is lit (kg/s)
+
+    // Landing gear and touchdown
+    pub gear_cycle_time: f32, // Seconds to fully raise or lower the gear
+    pub touchdown_probe_length: f32, // Ground ray length that counts as on the ground (m)
+    pub max_touchdown_sink_rate: f32, // Sink rate above which a landing is hard (m/s)
+    pub max_touchdown_speed: f32, // Ground speed above which a landing is hard (m/s)
+    pub hard_landing_damage: f32, // Damage per m/s over either touchdown limit
+    pub belly_landing_damage: f32, // Damage for touching down with the gear up
 }
 
 impl Default for AircraftFlight {
@@ -158,6 +221,11 @@ impl Default for AircraftFlight {
             throttle: 0.0,
             airspeed: 0.0,
             afterburner_active: false,
+            angle_of_attack: 0.0,
+            stalled: false,
+            g_load: 1.0,
+            blackout: 0.0,
+            fuel_used: 0.0,
         }
     }
 }
@@ -173,14 +241,13 @@ impl Default for SimpleF16Specs {
             throttle_decrease_rate: 3.0_f32.clamp(0.1, 10.0),
             linear_damping: 0.15_f32.clamp(0.01, 5.0),
             angular_damping: 0.05_f32.clamp(0.01, 5.0),
-            lift_per_throttle: 3.0_f32.clamp(0.1, 50.0),
             drag_factor: 0.995_f32.clamp(0.9, 1.0), // Momentum decay per second when engine off
 
             // Formerly magic numbers - with safety limits
             afterburner_multiplier: 1.5_f32.clamp(1.0, 3.0), // Speed multiplier for afterburner
             linear_lerp_factor: 4.0_f32.clamp(1.0, 20.0),    // Linear velocity smoothing
             angular_lerp_factor: 8.0_f32.clamp(1.0, 20.0),   // Angular velocity smoothing
-            throttle_deadzone: 0.1_f32.clamp(0.0, 0.5),      // Minimum throttle for lift
+            throttle_deadzone: 0.1_f32.clamp(0.0, 0.5),      // Minimum throttle for thrust
 
             // GTA-style input shaping
             input_deadzone: 0.10_f32.clamp(0.0, 0.3),
@@ -205,27 +272,75 @@ impl Default for SimpleF16Specs {
             auto_bank_gain: 0.02_f32.clamp(0.0, 0.1),
             auto_bank_max_rate: 2.0_f32.clamp(0.0, 10.0),
 
-            // Banked-lift feedback
-            bank_lift_scale: 0.7_f32.clamp(0.0, 1.0),
+            // Flight envelope
+            stall_speed: 55.0,
+            stall_aoa_deg: 20.0,
+            post_stall_lift: 0.4,
+            post_stall_grip: 0.15,
+            stall_pitch_down_rate: 0.8,
+            max_g: 9.0,
+            min_g: -3.0,
+            blackout_onset_g: 7.0,
+            blackout_rate: 0.25,
+            blackout_recovery_rate: 0.5,
+
+            // Fuel
+            fuel_capacity: 3200.0,
+            fuel_burn_rate: 0.8,
+            afterburner_fuel_burn_rate: 10.0,
+
+            // Landing gear and touchdown
+            gear_cycle_time: 3.0,
+            touchdown_probe_length: 2.5,
+            max_touchdown_sink_rate: 5.0,
+            max_touchdown_speed: 100.0,
+            hard_landing_damage: 6.0,
+            belly_landing_damage: 60.0,
         }
     }
 }
 
 impl SimpleF16Specs {
+    /// Lift coefficient normalized to 1.0 at the critical angle of attack;
+    /// past it lift collapses to `post_stall_lift`
+    pub fn lift_coefficient(&self, angle_of_attack: f32) -> f32 {
+        let stall_aoa = self.stall_aoa_deg.to_radians();
+        if angle_of_attack.abs() <= stall_aoa {
+            angle_of_attack / stall_aoa
+        } else {
+            angle_of_attack.signum() * self.post_stall_lift
+        }
+    }
+
     pub fn validate(&mut self) {
         self.max_forward_speed = self.max_forward_speed.clamp(50.0, 500.0);
         self.roll_rate_max = self.roll_rate_max.clamp(0.1, 10.0);
         self.pitch_rate_max = self.pitch_rate_max.clamp(0.1, 10.0);
         self.yaw_rate_max = self.yaw_rate_max.clamp(0.1, 5.0);
+        self.stall_speed = self.stall_speed.clamp(10.0, 200.0);
+        self.stall_aoa_deg = self.stall_aoa_deg.clamp(5.0, 60.0);
+        self.post_stall_lift = self.post_stall_lift.clamp(0.0, 1.0);
+        self.post_stall_grip = self.post_stall_grip.clamp(0.0, 1.0);
+        self.stall_pitch_down_rate = self.stall_pitch_down_rate.clamp(0.0, 5.0);
+        self.max_g = self.max_g.clamp(2.0, 15.0);
+        self.min_g = self.min_g.clamp(-10.0, 0.0);
+        self.blackout_onset_g = self.blackout_onset_g.clamp(1.0, self.max_g);
+        self.blackout_rate = self.blackout_rate.clamp(0.0, 5.0);
+        self.blackout_recovery_rate = self.blackout_recovery_rate.clamp(0.01, 5.0);
+        self.fuel_capacity = self.fuel_capacity.clamp(1.0, 20000.0);
+        self.fuel_burn_rate = self.fuel_burn_rate.clamp(0.0, 100.0);
+        self.afterburner_fuel_burn_rate = self.afterburner_fuel_burn_rate.clamp(0.0, 100.0);
+        self.gear_cycle_time = self.gear_cycle_time.clamp(0.1, 20.0);
+        self.touchdown_probe_length = self.touchdown_probe_length.clamp(0.5, 20.0);
+        self.max_touchdown_sink_rate = self.max_touchdown_sink_rate.clamp(0.5, 50.0);
+        self.max_touchdown_speed = self.max_touchdown_speed.clamp(10.0, 500.0);
+        self.hard_landing_damage = self.hard_landing_damage.clamp(0.0, 100.0);
+        self.belly_landing_damage = self.belly_landing_damage.clamp(0.0, 1000.0);
 
         if !self.max_forward_speed.is_finite() {
             error!("Invalid max_forward_speed, using default");
             self.max_forward_speed = 200.0;
         }
-        if !self.lift_per_throttle.is_finite() {
-            error!("Invalid lift_per_throttle, using default");
-            self.lift_per_throttle = 3.0;
-        }
     }
 }
 
@@ -279,7 +394,7 @@ impl Default for RotorBlurDisk {
 
 // NEW LOD SYSTEM
 
-#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum VehicleType {
     SuperCar,
     Helicopter,
@@ -418,6 +533,30 @@ pub struct SimpleCarSpecs {
     pub max_steer_deg: f32,
     pub wheel_radius: f32,
     pub wheel_positions: [(f32, f32, f32); 4], // FL, FR, RL, RR positions
+
+    // Transmission (see `Transmission`)
+    pub gear_ratios: Vec<f32>, // Forward gears, first to top
+    pub reverse_ratio: f32,
+    pub final_drive: f32,
+    pub idle_rpm: f32,
+    pub redline_rpm: f32,
+    pub torque_curve: Vec<(f32, f32)>, // (rpm, share of peak torque), ascending rpm
+    pub upshift_rpm: f32,              // Automatic shifts up above this
+    pub downshift_rpm: f32,            // Automatic shifts down below this
+    pub shift_time: f32,               // Seconds of cut drive per shift
+    pub clutch_slip_speed: f32,        // Clutch slips below this road speed (m/s)
+    pub launch_rpm: f32,               // Revs held while the clutch slips at full throttle
+
+    // Tires (see `TirePhysics`)
+    pub surface_grip: SurfaceGrip,
+    pub cornering_limit: f32, // Sideways acceleration the front tires hold on dry asphalt (m/s²)
+    pub rear_grip_bias: f32,  // Rear breakaway slip relative to slip_extremum (lower = oversteer)
+    pub oversteer_yaw: f32,   // Extra yaw rate once the rear fully lets go (rad/s)
+    pub skid_slip: f32,       // Axle slip above which tires skid, mark and screech (m/s)
+    pub skid_mark_width: f32,
+    pub skid_mark_spacing: f32,  // Distance between skid mark segments (m)
+    pub skid_mark_lifetime: f32, // Seconds before a skid mark is removed
+    pub screech_volume: f32,
 }
 
 impl Default for SimpleCarSpecs {
@@ -474,6 +613,35 @@ impl Default for SimpleCarSpecs {
                 (0.85, -0.32, 1.40),   // RL (rear = positive Z)
                 (-0.85, -0.32, 1.40),  // RR
             ],
+
+            // Six-speed box: ~80 km/h in first, top speed near the redline in sixth
+            gear_ratios: vec![3.2, 2.1, 1.5, 1.15, 0.92, 0.75],
+            reverse_ratio: 3.0,
+            final_drive: 3.4,
+            idle_rpm: 900.0,
+            redline_rpm: 7000.0,
+            torque_curve: vec![
+                (1000.0, 0.55),
+                (3000.0, 0.85),
+                (5000.0, 1.0),
+                (6500.0, 0.9),
+                (7000.0, 0.75),
+            ],
+            upshift_rpm: 6300.0,
+            downshift_rpm: 2800.0,
+            shift_time: 0.2,
+            clutch_slip_speed: 4.0,
+            launch_rpm: 3500.0,
+
+            surface_grip: SurfaceGrip::default(),
+            cornering_limit: 120.0,
+            rear_grip_bias: 1.2,
+            oversteer_yaw: 1.5,
+            skid_slip: 4.0,
+            skid_mark_width: 0.25,
+            skid_mark_spacing: 0.6,
+            skid_mark_lifetime: 15.0,
+            screech_volume: 0.6,
         }
     }
 }
@@ -537,6 +705,12 @@ pub struct SimpleHelicopterSpecs {
 
     // Ground detection
     pub ground_ray_length: f32, // Raycast length for ground detection (m)
+
+    // Rotor wash interaction
+    pub rotor_wash_radius: f32,       // Downwash footprint radius (m)
+    pub rotor_wash_max_altitude: f32, // Wash fades out above this height (m)
+    pub rotor_wash_push: f32,         // Outward push at full intensity (m/s^2)
+    pub rotor_wash_max_mass: f32,     // Bodies heavier than this are not pushed (kg)
 }
 
 impl Default for SimpleHelicopterSpecs {
@@ -573,6 +747,12 @@ impl Default for SimpleHelicopterSpecs {
 
             // Ground detection
             ground_ray_length: 5.0,
+
+            // Rotor wash interaction
+            rotor_wash_radius: 12.0,
+            rotor_wash_max_altitude: 10.0,
+            rotor_wash_push: 18.0,
+            rotor_wash_max_mass: 400.0,
         }
     }
 }
@@ -584,6 +764,10 @@ impl SimpleHelicopterSpecs {
         self.pitch_rate = self.pitch_rate.clamp(0.1, 5.0);
         self.roll_rate = self.roll_rate.clamp(0.1, 5.0);
         self.angular_lerp_factor = self.angular_lerp_factor.clamp(1.0, 20.0);
+        self.rotor_wash_radius = self.rotor_wash_radius.clamp(1.0, 50.0);
+        self.rotor_wash_max_altitude = self.rotor_wash_max_altitude.clamp(1.0, 100.0);
+        self.rotor_wash_push = self.rotor_wash_push.clamp(0.0, 100.0);
+        self.rotor_wash_max_mass = self.rotor_wash_max_mass.clamp(0.0, 5000.0);
 
         if !self.vertical_speed.is_finite() {
             error!("Invalid vertical_speed, using default");
@@ -3,6 +3,7 @@ use bevy::reflect::TypePath;
 use serde::{Deserialize, Serialize};
 
 #[derive(Asset, TypePath, Serialize, Deserialize, Clone, Component)]
+#[serde(default)]
 pub struct YachtSpecs {
     pub max_speed: f32,
     pub throttle_ramp: f32,
@@ -12,6 +13,20 @@ pub struct YachtSpecs {
     pub buoyancy_strength: f32,
     pub boat_grip: f32,
     pub drag_factor: f32,
+    /// Astern top speed as a share of `max_speed`
+    pub reverse_speed_share: f32,
+    /// Yaw rate at full rudder and full rudder authority (rad/s)
+    pub turn_rate: f32,
+    /// Rudder authority at a standstill, from propeller wash alone
+    pub rudder_idle_authority: f32,
+    /// Share of `max_speed` where the hull turns tightest
+    pub turn_peak_speed: f32,
+    /// Rudder authority left at top speed, where the turning circle widens
+    pub top_speed_authority: f32,
+    /// Share of forward speed lost per second at full rudder
+    pub turn_speed_bleed: f32,
+    /// Propeller position in hull space; no thrust while it is out of the water
+    pub propeller_offset: Vec3,
 }
 
 impl Default for YachtSpecs {
@@ -25,6 +40,13 @@ impl Default for YachtSpecs {
             buoyancy_strength: 3.0,
             boat_grip: 2.0,
             drag_factor: 0.992,
+            reverse_speed_share: 0.4,
+            turn_rate: 0.5,
+            rudder_idle_authority: 0.25,
+            turn_peak_speed: 0.35,
+            top_speed_authority: 0.55,
+            turn_speed_bleed: 0.2,
+            propeller_offset: Vec3::new(0.0, -2.5, 31.0),
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::components::unified_water::GERSTNER_SWELLS;
 use bevy::prelude::*;
 use bevy::render::render_resource::{AsBindGroup, ShaderRef};
 
@@ -16,6 +17,12 @@ pub struct WaterMaterial {
     #[uniform(0)]
     pub foam_color: Vec3,
 
+    // Sky seen in reflections, kept in step with the sky probe
+    #[uniform(0)]
+    pub sky_zenith: Vec3,
+    #[uniform(0)]
+    pub sky_horizon: Vec3,
+
     // Material properties
     #[uniform(0)]
     pub roughness: f32,
@@ -63,6 +70,8 @@ impl Default for WaterMaterial {
             shallow_color: Vec3::new(0.10, 0.60, 0.70),
             deep_color: Vec3::new(0.02, 0.08, 0.18),
             foam_color: Vec3::new(0.95, 0.97, 0.98),
+            sky_zenith: Vec3::new(0.35, 0.45, 0.65),
+            sky_horizon: Vec3::new(0.35, 0.45, 0.65),
 
             roughness: 0.05,
             fresnel_bias: 0.02,
@@ -72,16 +81,9 @@ impl Default for WaterMaterial {
             wave_count: 4,
             _pad: Vec2::ZERO,
 
-            // 4 Gerstner wave octaves for horizon-scale ocean
-            // Larger wavelengths and amplitudes for visibility at distance
-            // Format: (dir.x, dir.y, amplitude, wavelength)
-            // NOTE: Directions are normalized in shader
-            wave_data0: [
-                Vec4::new(1.0, 0.2, 0.8, 120.0), // Large ocean swells (visible at horizon)
-                Vec4::new(-0.6, 1.0, 0.5, 80.0), // Medium swells
-                Vec4::new(0.2, -1.0, 0.3, 50.0), // Smaller waves
-                Vec4::new(-1.0, -0.3, 0.15, 25.0), // Detail waves
-            ],
+            // 4 Gerstner wave octaves for horizon-scale ocean, shared with
+            // hull buoyancy so boats ride the waves that are drawn
+            wave_data0: GERSTNER_SWELLS,
 
             // Format: (speed_override, steepness, _pad, _pad)
             // Moderate steepness for visible rolling swells
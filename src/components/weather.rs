@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Rain,
+    Fog,
+    Storm,
+}
+
+impl WeatherKind {
+    pub const ALL: [WeatherKind; 4] = [
+        WeatherKind::Clear,
+        WeatherKind::Rain,
+        WeatherKind::Fog,
+        WeatherKind::Storm,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|k| *k == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Tunable values for a single weather state; blended during transitions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeatherParams {
+    /// 0.0 = dry, 1.0 = downpour
+    pub rain_intensity: f32,
+    /// Distance in meters at which fog fully hides geometry
+    pub fog_visibility: f32,
+    pub fog_color: (f32, f32, f32),
+    /// Wind velocity on the XZ plane in m/s
+    pub wind: (f32, f32),
+    /// Multiplier applied to car tire grip
+    pub road_friction: f32,
+}
+
+impl Default for WeatherParams {
+    fn default() -> Self {
+        Self {
+            rain_intensity: 0.0,
+            fog_visibility: 6000.0,
+            fog_color: (0.7, 0.8, 0.9),
+            wind: (0.0, 0.0),
+            road_friction: 1.0,
+        }
+    }
+}
+
+impl WeatherParams {
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            rain_intensity: mix(self.rain_intensity, other.rain_intensity),
+            fog_visibility: mix(self.fog_visibility, other.fog_visibility),
+            fog_color: (
+                mix(self.fog_color.0, other.fog_color.0),
+                mix(self.fog_color.1, other.fog_color.1),
+                mix(self.fog_color.2, other.fog_color.2),
+            ),
+            wind: (
+                mix(self.wind.0, other.wind.0),
+                mix(self.wind.1, other.wind.1),
+            ),
+            road_friction: mix(self.road_friction, other.road_friction),
+        }
+    }
+
+    pub fn wind_vector(&self) -> Vec3 {
+        Vec3::new(self.wind.0, 0.0, self.wind.1)
+    }
+}
+
+/// Weather presets and cycling rules loaded from `assets/config/weather.ron`
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherConfig {
+    pub initial: WeatherKind,
+    /// Seconds to blend between two weather states
+    pub transition_secs: f32,
+    /// Automatically switch weather after a random duration
+    pub auto_cycle: bool,
+    pub min_duration: f32,
+    pub max_duration: f32,
+    pub presets: HashMap<WeatherKind, WeatherParams>,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            initial: WeatherKind::Clear,
+            transition_secs: 10.0,
+            auto_cycle: true,
+            min_duration: 120.0,
+            max_duration: 300.0,
+            presets: HashMap::from([(WeatherKind::Clear, WeatherParams::default())]),
+        }
+    }
+}
+
+impl WeatherConfig {
+    pub fn params(&self, kind: WeatherKind) -> WeatherParams {
+        self.presets.get(&kind).copied().unwrap_or_default()
+    }
+}
+
+/// Current weather, including an in-progress transition
+#[derive(Resource, Debug, Clone)]
+pub struct WeatherState {
+    pub current: WeatherKind,
+    pub target: WeatherKind,
+    /// Transition progress towards `target` (1.0 = done)
+    pub transition: f32,
+    /// Parameters at the start of the current transition
+    pub from: WeatherParams,
+    /// Blended parameters in effect this frame
+    pub params: WeatherParams,
+    pub time_until_change: f32,
+}
+
+impl WeatherState {
+    pub fn new(kind: WeatherKind, config: &WeatherConfig) -> Self {
+        let params = config.params(kind);
+        Self {
+            current: kind,
+            target: kind,
+            transition: 1.0,
+            from: params,
+            params,
+            time_until_change: config.max_duration,
+        }
+    }
+}
+
+/// Request a transition to another weather state
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ChangeWeather {
+    pub kind: WeatherKind,
+}
+
+/// Rain particle emitter following the active entity
+#[derive(Component)]
+pub struct RainEmitter;
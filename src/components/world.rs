@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::Deserialize;
 
 /// NPC Behavior Component - replaces old NPCBehavior
 #[derive(Component, Debug, Clone)]
@@ -88,13 +89,17 @@ pub enum NPCBehaviorType {
     Commuting,
     Working,
     Socializing,
+    /// Home for the night
+    AtHome,
 }
 
 impl NPCState {
     pub fn new(npc_type: NPCType) -> Self {
-        use rand::prelude::*;
-        let mut rng = thread_rng();
+        Self::with_rng(npc_type, &mut rand::thread_rng())
+    }
 
+    /// Random state drawn from `rng`, for reproducible worlds
+    pub fn with_rng(npc_type: NPCType, rng: &mut impl rand::Rng) -> Self {
         let speed = match npc_type {
             NPCType::Civilian => rng.gen_range(2.0..4.0),
             NPCType::Worker => rng.gen_range(3.0..5.0),
@@ -104,7 +109,7 @@ impl NPCState {
 
         Self {
             npc_type,
-            appearance: NPCAppearance::random(),
+            appearance: NPCAppearance::random_with(rng),
             behavior: NPCBehaviorType::Wandering,
             target_position: Vec3::new(
                 rng.gen_range(-900.0..900.0),
@@ -120,9 +125,10 @@ impl NPCState {
 
 impl NPCAppearance {
     pub fn random() -> Self {
-        use rand::prelude::*;
-        let mut rng = thread_rng();
+        Self::random_with(&mut rand::thread_rng())
+    }
 
+    pub fn random_with(rng: &mut impl rand::Rng) -> Self {
         let skin_tones = [
             Color::srgb(0.8, 0.6, 0.4),
             Color::srgb(0.6, 0.4, 0.3),
@@ -259,7 +265,7 @@ pub struct DynamicContent {
     pub content_type: ContentType,
 }
 
-#[derive(Clone, PartialEq, Debug, Copy)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Copy)]
 pub enum ContentType {
     Road,
     Building,
@@ -379,6 +385,14 @@ impl MaterialCache {
         }
     }
 
+    /// Approximate memory held by the palette and its materials
+    pub fn memory_bytes(&self) -> usize {
+        self.palette.capacity()
+            * (std::mem::size_of::<Handle<StandardMaterial>>()
+                + std::mem::size_of::<StandardMaterial>())
+            + self.palette_colors.capacity() * std::mem::size_of::<[f32; 3]>()
+    }
+
     pub fn get_material(&self, color: Color) -> Handle<StandardMaterial> {
         let [r, g, b] = color.to_srgba().to_f32_array_no_alpha();
         let mut closest_idx = 0;
@@ -601,3 +615,20 @@ impl Default for BoundaryEffects {
         }
     }
 }
+
+/// Street furniture (trash cans, mailboxes, fences, lamp posts) spawned as a
+/// fixed body. A vehicle driving into it faster than `break_speed` (m/s)
+/// knocks it loose as a dynamic body.
+#[derive(Component, Debug, Clone, Copy, Deserialize)]
+pub struct StreetProp {
+    pub break_speed: f32,
+}
+
+/// Street prop that has been knocked loose
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct LooseProp {
+    /// Seconds since it was knocked loose
+    pub age: f32,
+    /// Seconds it has lain still
+    pub resting: f32,
+}
@@ -53,3 +53,87 @@ pub struct DockingCooldown {
     pub timer: Timer,
 }
 
+/// Pier boats moor alongside; the player steps off onto its deck
+#[derive(Component, Debug, Clone)]
+pub struct Dock {
+    pub name: String,
+    /// Landward end of the deck centerline, at deck height
+    pub start: Vec3,
+    /// Seaward end of the deck centerline
+    pub end: Vec3,
+    pub width: f32,
+}
+
+impl Dock {
+    /// Closest point on the deck centerline to `position`, and how far out
+    /// along the pier it is (0 landward end, 1 seaward end)
+    pub fn closest(&self, position: Vec3) -> (Vec3, f32) {
+        let span = self.end - self.start;
+        let t =
+            ((position - self.start).dot(span) / span.length_squared().max(1e-6)).clamp(0.0, 1.0);
+        (self.start + span * t, t)
+    }
+
+    /// Berth alongside the seaward half of the pier for a hull `half_beam`
+    /// wide, on the side `position` is on, with the hull lying along the
+    /// pier in whichever direction is closer to `forward`. Returns the berth
+    /// at the pier's deck height and the hull's forward direction there.
+    pub fn berth(&self, position: Vec3, half_beam: f32, forward: Vec3) -> (Vec3, Vec3) {
+        let along = (self.end - self.start).with_y(0.0).normalize_or(Vec3::X);
+        let across = Vec3::new(-along.z, 0.0, along.x);
+        let (closest, t) = self.closest(position);
+        let moored_at = self.start.lerp(self.end, t.max(0.5));
+        let side = if (position - closest).dot(across) >= 0.0 {
+            1.0
+        } else {
+            -1.0
+        };
+        let berth = moored_at.with_y(closest.y) + across * side * (self.width * 0.5 + half_beam);
+        let heading = if forward.dot(along) >= 0.0 {
+            along
+        } else {
+            -along
+        };
+        (berth, heading)
+    }
+}
+
+/// On a yacht tied up at a dock, holding it at its berth until someone
+/// takes the helm again
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MooredAtDock {
+    pub dock: Entity,
+    /// Where the hull is held, horizontally
+    pub berth: Vec3,
+    /// Direction the bow points while moored
+    pub heading: Vec3,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_berths_lie_alongside_the_seaward_half() {
+        let dock = Dock {
+            name: "Pier".into(),
+            start: Vec3::new(0.0, 2.0, 0.0),
+            end: Vec3::new(100.0, 2.0, 0.0),
+            width: 6.0,
+        };
+        assert_eq!(
+            dock.closest(Vec3::new(40.0, 0.0, 20.0)),
+            (Vec3::new(40.0, 2.0, 0.0), 0.4)
+        );
+
+        // South of the pier near its root: pulled out to the seaward half
+        let (berth, heading) = dock.berth(Vec3::new(20.0, 0.0, -15.0), 10.0, Vec3::NEG_X);
+        assert_eq!(berth, Vec3::new(50.0, 2.0, -13.0));
+        assert_eq!(heading, Vec3::NEG_X);
+
+        // North side near the end keeps its spot along the pier
+        let (berth, heading) = dock.berth(Vec3::new(80.0, 0.0, 14.0), 10.0, Vec3::X);
+        assert_eq!(berth, Vec3::new(80.0, 2.0, 13.0));
+        assert_eq!(heading, Vec3::X);
+    }
+}
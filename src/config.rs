@@ -1,7 +1,11 @@
+use crate::components::VehicleType;
 use crate::systems::world::unified_world::{ChunkCoord, chunk_coord_to_index};
+use crate::util::config_merge::merge_ron;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Asset loading policy for handling missing assets
 #[derive(Resource, Default)]
@@ -65,6 +69,45 @@ pub struct GameConfig {
 
     // World Environment Configuration (from world_config.ron)
     pub world_env: crate::constants::WorldEnvConfig,
+
+    // Ambient Traffic Configuration
+    pub traffic: TrafficConfig,
+
+    // Wanted Level / Police Configuration
+    pub police: PoliceConfig,
+
+    // Swimming / Diving Configuration
+    pub diving: DivingConfig,
+
+    // Ejection / Parachute Configuration
+    pub parachute: ParachuteConfig,
+
+    // Player Health, Damage and Respawn Configuration
+    pub health: HealthConfig,
+
+    // Money, Prices and Pickups (from economy.ron)
+    pub economy: EconomyConfig,
+
+    // Graphics Settings (edited from the pause menu)
+    pub graphics: GraphicsConfig,
+
+    // Bloom, Exposure, Motion Blur, Damage Vignette and Reflections (from post_processing.ron)
+    pub post_processing: PostProcessingConfig,
+
+    // Time of Day, Headlights, Taillights and Street Lamps (from lighting.ron)
+    pub lighting: LightingConfig,
+
+    // Logging Configuration (from logging.ron)
+    pub logging: LoggingConfig,
+
+    // Telemetry Export (from telemetry.ron)
+    pub telemetry: TelemetryConfig,
+
+    // UI Language and Fonts (from localization.ron)
+    pub localization: LocalizationConfig,
+
+    // Accessibility Settings (from accessibility.ron, saved when edited in the settings menu)
+    pub accessibility: AccessibilityConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -106,6 +149,7 @@ pub struct WorldConfig {
     pub total_chunks_x: usize, // 31 - Total chunks along X axis (4000/128)
     pub total_chunks_z: usize, // 31 - Total chunks along Z axis (4000/128)
     pub streaming_radius: f32, // 800.0 - Object streaming radius
+    pub seed: Option<u64>,     // None - Fixed world seed (a new one each run when unset)
 
     // LOD distances with performance optimization
     pub lod_distances: [f32; 3], // [300.0, 600.0, 1000.0] - LOD transitions
@@ -115,6 +159,10 @@ pub struct WorldConfig {
     pub tree_density: f32,     // 2.0 - Tree spawn density
     pub vehicle_density: f32,  // 0.3 - Vehicle spawn density
     pub npc_density: f32,      // 0.2 - NPC spawn density
+    pub interior_chance: f32,  // 0.2 - Share of off-grid buildings with an enterable door
+    pub door_use_radius: f32,  // 2.5 - How close the player must be to use a door or shop counter
+    pub shop_chance: f32,      // 0.15 - Share of off-grid commercial buildings with a storefront
+    pub npc_talk_radius: f32,  // 3.0 - How close the player must be to talk to an NPC
 
     // Performance parameters
     pub cleanup_delay: f32,   // 30.0 - Entity cleanup delay
@@ -179,6 +227,25 @@ pub struct NPCConfig {
     pub walk_speed: f32,         // 1.5 - Walking speed
     pub run_speed: f32,          // 3.0 - Running speed
     pub avoidance_distance: f32, // 5.0 - Player avoidance distance
+
+    // Pedestrian behavior
+    pub sidewalk_offset: f32, // 2.0 - Sidewalk distance outside the road edge
+    pub waypoint_spacing: f32, // 15.0 - Distance between sidewalk waypoints
+    pub sidewalk_snap_distance: f32, // 40.0 - NPCs farther from a sidewalk roam freely
+    pub converse_chance: f32, // 0.2 - Chance to stop and chat at a waypoint
+    pub converse_duration: f32, // 6.0 - Seconds spent chatting
+    pub flee_duration: f32,   // 4.0 - Seconds spent fleeing after a scare
+    pub honk_radius: f32,     // 25.0 - Horn scares pedestrians within this radius
+    pub bark_chance: f32,     // 0.3 - Chance a honked pedestrian shouts back instead of running
+
+    // Ragdoll on vehicle impact
+    pub ragdoll_impact_energy: f32, // 1500.0 - Impact energy (J) that knocks an NPC over
+    pub ragdoll_fatal_energy: f32,  // 9000.0 - Impact energy (J) the NPC doesn't get up from
+    pub ragdoll_impact_radius: f32, // 3.0 - Vehicle reach when checking for hit NPCs
+    pub ragdoll_mass: f32,          // 70.0 - NPC mass used for impact energy
+    pub ragdoll_recover_time: f32,  // 4.0 - Seconds before a knocked-over NPC stands up
+    pub ragdoll_despawn_time: f32,  // 10.0 - Seconds a fatally hit NPC stays in the world
+    pub ragdoll_full_distance: f32, // 40.0 - Jointed ragdolls only within this camera distance
 }
 
 #[derive(Debug, Clone)]
@@ -190,6 +257,362 @@ pub struct NPCUpdateIntervals {
     pub far_interval: f32,    // 0.5 - Update interval for far NPCs
 }
 
+#[derive(Debug, Clone)]
+pub struct TrafficConfig {
+    pub enabled: bool,
+    pub active_radius: f32, // 400.0 - Traffic only simulated near the player
+    pub max_road_snap_distance: f32, // 30.0 - Cars farther than this from a road stay parked
+    pub lookahead_distance: f32, // 8.0 - Steering target distance along the road
+    pub intersection_stop_time: f32, // 2.0 - Seconds waited at each intersection
+    pub player_avoid_distance: f32, // 12.0 - Brake when the player is this close ahead
+    pub acceleration: f32,  // 4.0 - m/s² speed change rate
+    pub highway_speed: f32, // 25.0 - m/s speed limits per road type
+    pub main_street_speed: f32, // 14.0
+    pub side_street_speed: f32, // 10.0
+    pub alley_speed: f32,   // 6.0
+    pub alarm_chance: f32,  // 0.5 - Share of parked cars with an armed alarm
+    pub alarm_trigger_speed: f32, // 1.5 - A bump moving a parked car this fast sets its alarm off
+    pub alarm_duration: f32, // 20.0 - Seconds an alarm sounds
+}
+
+#[derive(Debug, Clone)]
+pub struct PoliceConfig {
+    pub max_stars: u8,            // 5 - Highest wanted level
+    pub heat_per_star: f32,       // 2.0 - Heat needed for each star
+    pub pedestrian_hit_heat: f32, // 1.0 - Heat added for hitting a pedestrian
+    pub vehicle_hit_heat: f32,    // 0.5 - Heat added for ramming a vehicle
+    pub hit_speed_threshold: f32, // 6.0 - Minimum speed for a hit to count as a crime
+    pub crime_cooldown: f32,      // 1.0 - Seconds before another hit counts
+    pub decay_delay: f32,         // 20.0 - Seconds without crimes before heat decays
+    pub decay_rate: f32,          // 0.25 - Heat lost per second while decaying
+    pub units_per_star: usize,    // 1 - Pursuit cars per star
+    pub spawn_distance: f32,      // 90.0 - Pursuit cars appear this far from the player
+    pub spawn_interval: f32,      // 3.0 - Seconds between pursuit car spawns
+    pub pursuit_speed: f32,       // 24.0 - Pursuit car top speed
+    pub despawn_distance: f32,    // 250.0 - Idle police cars beyond this are removed
+    pub alarm_heat: f32,          // 0.5 - Heat added when the wanted player sets off a car alarm
+    pub alarm_min_stars: u8,      // 3 - Wanted level from which car alarms draw the police
+}
+
+#[derive(Debug, Clone)]
+pub struct DivingConfig {
+    pub breath_capacity: f32, // 30.0 - Seconds of air with the head underwater
+    pub breath_refill_rate: f32, // 5.0 - Seconds of air regained per second above water
+    pub surface_swim_speed: f32, // 2.4 - Base speed swimming at the surface
+    pub dive_swim_speed: f32, // 1.8 - Base speed swimming underwater
+    pub breathless_speed_scale: f32, // 0.5 - Speed multiplier once out of air
+    pub surfacing_speed: f32, // 2.5 - Forced ascent speed once out of air
+    pub underwater_saturation: f32, // 0.55 - Camera saturation below the surface
+    pub underwater_exposure: f32, // -0.5 - Camera exposure offset (EV) below the surface
+    pub grading_blend_rate: f32, // 6.0 - How fast the camera grading follows the surface
+}
+
+#[derive(Debug, Clone)]
+pub struct ParachuteConfig {
+    pub ejection_speed: f32, // 25.0 - Seat launch speed along the aircraft's up axis
+    pub bail_speed: f32,     // 5.0 - Sideways jump speed when bailing from a vehicle
+    pub bail_velocity_scale: f32, // 0.8 - Share of the vehicle's velocity kept when bailing
+    pub launch_tumble_rate: f32, // 8.0 - Initial tumble spin after launch (rad/s)
+    pub tumble_damping: f32, // 1.5 - How fast the tumble spin dies out
+    pub terminal_velocity: f32, // 55.0 - Freefall speed where drag cancels gravity
+    pub min_deploy_height: f32, // 15.0 - Canopy can't open closer to the ground than this
+    pub deploy_time: f32,    // 1.5 - Seconds for the canopy to fully inflate
+    pub canopy_descent_speed: f32, // 5.0 - Sink rate under a full canopy
+    pub canopy_glide_speed: f32, // 9.0 - Forward speed under a full canopy
+    pub canopy_turn_rate: f32, // 1.2 - Yaw rate at full steering input (rad/s)
+    pub canopy_response: f32, // 2.0 - How fast velocity settles to the canopy target
+    pub flare_descent_scale: f32, // 0.4 - Sink rate multiplier while flaring (brake input)
+    pub landing_probe_length: f32, // 1.2 - Ray length below the feet that counts as touchdown
+    pub freefall_entry_height: f32, // 20.0 - Falling with this much air below starts a skydive
+    pub freefall_track_speed: f32, // 20.0 - Horizontal speed when tracking forward in freefall
+    pub freefall_air_control: f32, // 1.0 - How fast freefall drift settles to the steering target
+    pub freefall_turn_rate: f32, // 1.5 - Yaw rate in freefall at full steering input (rad/s)
+    pub dive_terminal_scale: f32, // 1.4 - Terminal velocity multiplier head down (throttle)
+    pub spread_terminal_scale: f32, // 0.7 - Terminal velocity multiplier spread flat (brake)
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    pub max_health: f32,           // 100.0 - Player health when full
+    pub regen_delay: f32,          // 5.0 - Seconds without damage before health comes back
+    pub regen_rate: f32,           // 0.1 - Share of max health regained per second
+    pub fall_safe_speed: f32,      // 15.0 - Landing speed taken without damage
+    pub fall_lethal_speed: f32,    // 32.0 - Landing speed that takes all the player's health
+    pub vehicle_safe_speed: f32,   // 4.0 - Closing speed of a vehicle that only nudges
+    pub vehicle_lethal_speed: f32, // 25.0 - Closing speed of a vehicle that kills outright
+    pub drowning_rate: f32,        // 10.0 - Health lost per second underwater without breath
+    pub death_fade_time: f32,      // 1.5 - Seconds the screen takes to go black on death
+    pub wasted_hold_time: f32,     // 2.0 - Seconds it stays black before the respawn
+    pub respawn_fade_time: f32,    // 1.0 - Seconds it takes to fade back in at the hospital
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EconomyConfig {
+    pub npc_cash_min: u32,      // 5 - Least cash a killed NPC drops
+    pub npc_cash_max: u32,      // 60 - Most cash a killed NPC drops
+    pub pickup_radius: f32,     // 1.5 - Distance at which dropped cash is collected
+    pub pickup_lifetime: f32,   // 60.0 - Seconds dropped cash lies around before vanishing
+    pub supercar_price: u32,    // 2000 - Garage price of a new car
+    pub helicopter_price: u32,  // 8000 - Garage price of a new helicopter
+    pub paint_cost: u32,        // 100 - Each repaint
+    pub wheels_cost: u32,       // 250 - Each wheel swap
+    pub tuning_stage_cost: u32, // 500 - Per tuning stage bought; going back to stock is free
+    pub hospital_fee: u32,      // 500 - Taken from the wallet (what there is of it) on respawn
+}
+
+/// Sent whenever `GameConfig` is loaded or edited at runtime, so systems that
+/// bake config values into the world can re-apply them without a restart
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ConfigReloadedEvent;
+
+/// One field of a config file that changed on hot reload; systems that only
+/// care about one file filter on `file` instead of re-applying everything
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ConfigFieldChanged {
+    /// One of `CONFIG_FILES`
+    pub file: &'static str,
+    /// Dotted path within the file, e.g. `lod_distances.full`
+    pub path: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+/// Per-user config directory; RON files here override fields of the
+/// workspace files in `assets/config` with the same name
+pub fn user_config_directory() -> PathBuf {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("gta_game")
+}
+
+/// Rendering quality presets selectable from the settings menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphicsQuality {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Ultra,
+}
+
+/// Render settings a `GraphicsQuality` preset resolves to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualitySettings {
+    /// Sun shadow range; `None` turns sun shadows off
+    pub shadow_distance: Option<f32>,
+    pub shadow_cascades: usize,
+    /// Resolution of each directional shadow cascade
+    pub shadow_map_size: usize,
+    /// Multiplier on every `VisibilityRange` and the HLOD swap distance
+    pub lod_bias: f32,
+    /// Fraction of palm trees kept
+    pub vegetation_density: f32,
+    /// Glow around the sun, headlights and other bright emissives
+    pub bloom: bool,
+    /// Speed-scaled blur while driving or flying; adds a motion vector prepass
+    pub motion_blur: bool,
+    /// Red screen edges as health drops
+    pub damage_vignette: bool,
+    /// Multiplier on how many cars and streetlights may cast light at once
+    pub light_budget: f32,
+    /// Simulate `Auto` particle emitters in a compute shader rather than on the CPU
+    pub gpu_particles: bool,
+    /// Ray-marched reflections on car paint and wet roads; renders those deferred
+    pub screen_space_reflections: bool,
+}
+
+impl GraphicsQuality {
+    pub fn next(self) -> Self {
+        match self {
+            GraphicsQuality::Low => GraphicsQuality::Medium,
+            GraphicsQuality::Medium => GraphicsQuality::High,
+            GraphicsQuality::High => GraphicsQuality::Ultra,
+            GraphicsQuality::Ultra => GraphicsQuality::Low,
+        }
+    }
+
+    /// Medium matches Bevy's defaults, so it renders as the game did before presets
+    pub fn settings(self) -> QualitySettings {
+        match self {
+            GraphicsQuality::Low => QualitySettings {
+                shadow_distance: None,
+                shadow_cascades: 1,
+                shadow_map_size: 1024,
+                lod_bias: 0.6,
+                vegetation_density: 0.5,
+                bloom: false,
+                motion_blur: false,
+                damage_vignette: true,
+                light_budget: 0.5,
+                gpu_particles: false,
+                screen_space_reflections: false,
+            },
+            GraphicsQuality::Medium => QualitySettings {
+                shadow_distance: Some(150.0),
+                shadow_cascades: 4,
+                shadow_map_size: 2048,
+                lod_bias: 1.0,
+                vegetation_density: 1.0,
+                bloom: true,
+                motion_blur: false,
+                damage_vignette: true,
+                light_budget: 1.0,
+                gpu_particles: true,
+                screen_space_reflections: false,
+            },
+            GraphicsQuality::High => QualitySettings {
+                shadow_distance: Some(400.0),
+                shadow_cascades: 4,
+                shadow_map_size: 2048,
+                lod_bias: 1.3,
+                vegetation_density: 1.0,
+                bloom: true,
+                motion_blur: true,
+                damage_vignette: true,
+                light_budget: 1.5,
+                gpu_particles: true,
+                screen_space_reflections: true,
+            },
+            GraphicsQuality::Ultra => QualitySettings {
+                shadow_distance: Some(800.0),
+                shadow_cascades: 4,
+                shadow_map_size: 4096,
+                lod_bias: 1.6,
+                vegetation_density: 1.0,
+                bloom: true,
+                motion_blur: true,
+                damage_vignette: true,
+                light_budget: 2.0,
+                gpu_particles: true,
+                screen_space_reflections: true,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphicsConfig {
+    pub resolution: (u32, u32), // (1280, 720) - Window size in logical pixels
+    pub vsync: bool,            // true - Sync presentation to the display refresh
+    pub quality: GraphicsQuality, // Medium - Shadows, LOD bias and vegetation preset
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub filter: String, // "info,wgpu=error,naga=warn" - EnvFilter directives, per module
+    pub file_output: bool, // true - Write logs to the data directory
+    pub max_file_bytes: u64, // 4 MiB - Size at which the log file rolls over
+    pub max_files: usize, // 3 - Log files kept, current one included
+    pub buffer_lines: usize, // 500 - Lines kept in memory for the in-game log panel
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalizationConfig {
+    pub language: String, // "en" - Locale under assets/locale loaded at startup
+    pub languages: Vec<String>, // ["en", "es"] - Locales the settings menu cycles through
+    pub fonts: HashMap<String, Vec<String>>, // {} - Per-language font files, tried in order
+    pub fallback_fonts: Vec<String>, // [] - Tried after a language's own fonts; Bevy's built-in font if none exist
+}
+
+/// Color vision deficiency the HUD palette is adjusted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+/// What a HUD color tells the player; colorblind palettes pick a color per role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indicator {
+    /// Low oxygen, money lost
+    Danger,
+    /// Wanted level
+    Warning,
+    /// Healthy oxygen, money earned
+    Good,
+    VehicleIcon,
+    NpcIcon,
+    AirfieldIcon,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    pub ui_scale: f32,                   // 1.0 - Scale of every HUD and menu node
+    pub colorblind_mode: ColorblindMode, // Off - Palette for map icons and warning indicators
+    pub subtitles: bool,                 // false - Caption sirens, horns and other audio cues
+    pub reduced_camera_shake: bool, // false - Cut camera shake from crashes and explosions to a quarter
+}
+
+/// Tonemapping curve, mirroring Bevy's `Tonemapping`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TonemappingMode {
+    None,
+    Reinhard,
+    ReinhardLuminance,
+    #[default]
+    AcesFitted,
+    AgX,
+    TonyMcMapface,
+    BlenderFilmic,
+}
+
+/// Which effects run is up to the quality preset; these tune them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostProcessingConfig {
+    pub tonemapping: TonemappingMode, // AcesFitted - Curve mapping HDR to the display
+    pub exposure_ev100: f32,          // 9.7 - Camera exposure; lower is brighter
+    pub bloom_intensity: f32,         // 0.05 - Strength of the glow around bright pixels
+    pub motion_blur_shutter_angle: f32, // 0.5 - Blur at full speed, as a fraction of a frame
+    pub motion_blur_full_speed: f32,  // 40.0 - Speed (m/s) at which blur reaches full strength
+    pub motion_blur_samples: u32,     // 4 - Samples per pixel along the motion
+    pub damage_vignette: f32,         // 0.7 - Vignette strength at zero health
+    pub damage_flash_seconds: f32,    // 0.4 - Extra flash right after a hit fades over this long
+    pub sky_reflection_intensity: f32, // 1500.0 - Brightness (cd/m²) of the sky probe at noon
+    pub ssr_max_roughness: f32,       // 0.3 - Rougher surfaces skip screen-space reflections
+    pub wet_road_roughness: f32,      // 0.15 - Road roughness in a downpour
+}
+
+/// Lamp budgets are scaled by the quality preset's `light_budget`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LightingConfig {
+    pub night_illuminance: f32, // 3000.0 - Sun lux below which lamps switch on
+    pub headlight_intensity: f32, // 400000.0 - Luminous power (lm) of each headlight
+    pub headlight_range: f32,   // 60.0 - Reach of the headlight beams
+    pub taillight_intensity: f32, // 15000.0 - Taillights with the headlights on
+    pub brake_light_intensity: f32, // 60000.0 - Taillights at full brake
+    pub max_lit_vehicles: usize, // 8 - Cars nearest the camera whose lamps light the scene
+    pub street_lamp_intensity: f32, // 600000.0 - Luminous power (lm) of each street lamp
+    pub street_lamp_range: f32, // 30.0 - Reach of a street lamp's light
+    pub street_lamp_distance: f32, // 180.0 - Street lamps farther than this stay dark
+    pub max_street_lamps: usize, // 24 - Lit street lamps, nearest first
+    pub day_length_minutes: f32, // 48.0 - Real minutes per in-game day; 0 stops the clock
+    pub start_hour: f32,        // 16.5 - Time of day a new session starts at
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    pub sample_interval: f32,   // 1.0 - Seconds between metric samples
+    pub csv_output: bool,       // false - Append samples to metrics.csv in the data directory
+    pub csv_max_bytes: u64,     // 8 MiB - Size at which the CSV file rolls over
+    pub csv_max_files: usize,   // 5 - CSV files kept, current one included
+    pub http_port: Option<u16>, // None - Serve Prometheus text on this port at /metrics
+    pub http_bind: String,      // "127.0.0.1" - Address the metrics endpoint listens on
+}
+
 #[derive(Debug, Clone)]
 pub struct PerformanceConfig {
     // Timing intervals
@@ -219,9 +642,17 @@ pub struct PerformanceConfig {
     pub target_fps: f32,           // 60.0 - Target FPS
     pub frame_time_threshold: f32, // 16.67 - Target frame time (ms)
 
+    // Frame budget governor: sheds load while frames run over frame_time_threshold
+    pub budget_over_frames: u32, // 30 - Consecutive frames over budget before stepping load down
+    pub budget_recover_frames: u32, // 180 - Consecutive frames with headroom before stepping load back up
+    pub budget_headroom: f32, // 0.75 - Frames under this fraction of the budget count as headroom
+    pub budget_step: f32,     // 0.1 - Load scale change per governor step
+    pub budget_min_scale: f32, // 0.5 - Lowest load scale the governor will go to
+
     // Culling parameters
     pub culling_check_interval: f32, // 0.5 - Culling check interval
     pub max_visible_distance: f32, // 1000.0 - Maximum visibility distance (reduced for performance)
+    pub gpu_occlusion_culling: bool, // true - Two-phase Hi-Z occlusion culling on the main camera
 
     // VisibilityRange distances per entity type
     pub npc_visibility_distance: f32, // 125.0 - NPCs visible range
@@ -229,6 +660,30 @@ pub struct PerformanceConfig {
     pub tree_visibility_distance: f32, // 300.0 - Trees visible range
     pub building_visibility_distance: f32, // 1500.0 - High for Manhattan skyline visibility
     pub road_visibility_distance: f32, // 400.0 - Roads visible range
+
+    // Hierarchical LOD: far building clusters drawn as one merged proxy mesh
+    pub hlod_region_size: f32, // 400.0 - Side of the square regions buildings are clustered in
+    pub hlod_distance: f32,    // 700.0 - Clusters farther than this from the camera use their proxy
+
+    // GPU uploads and residency
+    pub upload_budget_ms: f32, // 2.0 - Frame time new meshes and textures may spend uploading (0 disables)
+    pub upload_bytes_per_ms: usize, // 4 MiB - Assumed upload throughput, turns the time into bytes
+    pub gpu_residency_budget_kb: usize, // 262144 - Meshes and textures kept on the GPU before far building clusters give theirs up (0 disables)
+
+    // Entity pooling: despawned entities kept for reuse, per content type
+    pub npc_pool_size: usize, // 32 - Pooled NPC entities (0 disables pooling)
+    pub vehicle_pool_size: usize, // 16 - Pooled traffic cars (0 disables pooling)
+    pub particle_pool_size: usize, // 16 - Finished emitters kept per particle prefab (0 disables pooling)
+
+    // NPC persistence beyond the streaming radius
+    pub dormant_npc_budget_kb: usize, // 256 - Memory for NPCs stored while out of range (0 disables)
+
+    // Memory budgets per subsystem; going over logs a warning and flags the F3 overlay (0 disables)
+    pub mesh_cache_budget_kb: usize, // 65536 - Cached road, intersection and NPC body meshes
+    pub material_cache_budget_kb: usize, // 1024 - Shared material palette
+    pub distance_cache_budget_kb: usize, // 4096 - Per-entity camera distances
+    pub spatial_index_budget_kb: usize, // 8192 - Spatial hash buckets
+    pub prefab_registry_budget_kb: usize, // 4096 - Loaded prefab definitions
 }
 
 #[derive(Debug, Clone)]
@@ -241,13 +696,19 @@ pub struct AudioConfig {
     pub backfire_volume: f32, // 0.7 - Backfire volume
 
     pub footstep_volume: f32, // 0.5 - Footstep volume
+    pub siren_volume: f32,    // 0.7 - Police siren volume
+    pub ambient_volume: f32,  // 0.4 - City ambience volume
+    pub alarm_volume: f32,    // 0.8 - Car alarm volume
+    pub bark_volume: f32,     // 0.8 - Pedestrian shout volume
 
     // Audio timing
     pub footstep_intervals: FootstepConfig,
 
     // Spatial audio
-    pub fade_distance: f32,      // 100.0 - Audio fade distance
-    pub max_audio_distance: f32, // 250.0 - Maximum audio distance
+    pub fade_distance: f32,       // 100.0 - Audio fade distance
+    pub max_audio_distance: f32,  // 250.0 - Maximum audio distance
+    pub max_engine_voices: usize, // 8 - Nearest vehicles with an audible engine
+    pub doppler_scale: f32,       // 1.0 - Doppler strength (0 disables)
 }
 
 #[derive(Debug, Clone)]
@@ -258,19 +719,38 @@ pub struct FootstepConfig {
     pub variation: f32,       // 0.1 - Timing variation (+/- 0.1)
 }
 
-#[derive(Debug, Clone)]
-pub struct CameraConfig {
-    // Chase camera parameters
-    pub distance: f32,            // 20.0 - Camera distance from target
-    pub height: f32,              // 12.0 - Camera height above target
-    pub lerp_speed: f32,          // 0.05 - Camera smoothing speed
-    pub look_ahead_distance: f32, // 10.0 - Look ahead distance
-    pub look_ahead_height: f32,   // 2.0 - Look ahead height
+/// Where the camera rig sits relative to one kind of target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraOffset {
+    pub distance: f32,         // Chase distance behind the target
+    pub height: f32,           // Chase height above the target
+    pub look_height: f32,      // Height above the target's origin the camera aims at
+    pub look_ahead: f32,       // Aim point moves this far ahead, plus 0.3 s of travel
+    pub hood: (f32, f32, f32), // Hood/first-person camera position in the target's local space
+    pub follow_speed: f32,     // Smoothing rate; doubles by 50 m/s
+    pub fov: f32,              // Field of view at rest, degrees
+    pub fov_kick: f32,         // Degrees added at kick_speed
+    pub kick_speed: f32,       // Speed in m/s at which the full FOV kick applies
+}
 
-    // Swimming camera parameters
-    pub swim_distance: f32,   // 2.5 - Distance behind swimmer
-    pub swim_height: f32,     // 0.6 - Height above swimmer's back
-    pub swim_look_ahead: f32, // 0.5 - Look ahead distance for targeting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraConfig {
+    // Per-target offsets (from camera.ron)
+    pub on_foot: CameraOffset,
+    pub swimming: CameraOffset,
+    pub car: CameraOffset,
+    pub helicopter: CameraOffset,
+    pub f16: CameraOffset,
+    pub yacht: CameraOffset,
+    pub freefall: CameraOffset,
+
+    // Camera rig
+    pub collision_margin: f32, // 0.3 - Gap the spring arm keeps from walls and terrain
+    pub arm_recover_speed: f32, // 6.0 - Meters per second a shortened spring arm grows back
+    pub close_scale: f32,      // 0.55 - Close mode's share of the chase distance and height
+    pub cinematic_distance: f32, // 35.0 - How far ahead cinematic shots are set up
+    pub orbit_sensitivity: f32, // 0.005 - Radians per pixel of mouse motion in orbit mode
 }
 
 #[derive(Debug, Clone)]
@@ -299,7 +779,74 @@ pub struct WorldStreamingConfig {
     pub vehicle_lod: LodConfig,
     pub npc_lod: LodConfig,
     pub vegetation_cull_distance: f32,
+    /// Distance past which streetlights and fences stop drawing; cones go at half of it
+    #[serde(default = "default_prop_cull_distance")]
+    pub prop_cull_distance: f32,
+    /// Parked cars farther than this are drawn as instanced impostors
+    #[serde(default = "default_car_impostor_distance")]
+    pub car_impostor_distance: f32,
     pub road_cell_size: f32,
+    /// Chunks generated per frame during the loading screen
+    #[serde(default = "default_startup_chunks_per_frame")]
+    pub startup_chunks_per_frame: usize,
+    /// Chunks generated per frame once in-game
+    #[serde(default = "default_chunks_per_frame")]
+    pub chunks_per_frame: usize,
+    /// Radius around the camera that must be generated before gameplay starts
+    #[serde(default = "default_startup_radius")]
+    pub startup_radius: f32,
+    /// Seconds of camera motion to look ahead when prioritizing chunks
+    #[serde(default = "default_velocity_lookahead")]
+    pub velocity_lookahead: f32,
+    /// Band around region LOD distances: a region drops detail once it is
+    /// this far past the distance and regains it this far inside, so a
+    /// camera hovering at the threshold doesn't flicker
+    #[serde(default = "default_lod_hysteresis")]
+    pub lod_hysteresis: f32,
+    /// Seconds of camera motion region LOD looks ahead, bringing in detail
+    /// in the direction of travel before the camera arrives
+    #[serde(default = "default_lod_lookahead")]
+    pub lod_lookahead: f32,
+    /// Most NPCs alive at once; past it the ones farthest from the player are
+    /// released first
+    #[serde(default = "default_npc_budget")]
+    pub npc_budget: usize,
+}
+
+fn default_prop_cull_distance() -> f32 {
+    300.0
+}
+
+fn default_car_impostor_distance() -> f32 {
+    150.0
+}
+
+fn default_startup_chunks_per_frame() -> usize {
+    200
+}
+
+fn default_chunks_per_frame() -> usize {
+    4
+}
+
+fn default_startup_radius() -> f32 {
+    1200.0
+}
+
+fn default_velocity_lookahead() -> f32 {
+    2.0
+}
+
+fn default_lod_hysteresis() -> f32 {
+    50.0
+}
+
+fn default_lod_lookahead() -> f32 {
+    1.5
+}
+
+fn default_npc_budget() -> usize {
+    150
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -324,6 +871,8 @@ pub struct WorldPhysicsConfig {
     pub boundaries: BoundariesConfig,
     pub emergency_thresholds: EmergencyThresholdsConfig,
     pub water: WaterPhysicsConfig,
+    #[serde(default)]
+    pub street_props: StreetPropPhysicsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -359,6 +908,33 @@ pub struct WaterPhysicsConfig {
     pub gravity: f32,
 }
 
+/// Budget for street props knocked loose by vehicles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreetPropPhysicsConfig {
+    /// Most props that can be loose at once; the oldest is cleared to make room
+    pub max_loose: usize,
+    /// Most props knocked loose in a single frame
+    pub activations_per_frame: usize,
+    /// Share of the vehicle's velocity a prop is sent off with
+    pub knock_share: f32,
+    /// Below this speed (m/s) a loose prop counts as lying still
+    pub rest_speed: f32,
+    /// Seconds a loose prop lies still before it is cleared away
+    pub cleanup_seconds: f32,
+}
+
+impl Default for StreetPropPhysicsConfig {
+    fn default() -> Self {
+        Self {
+            max_loose: 48,
+            activations_per_frame: 6,
+            knock_share: 0.8,
+            rest_speed: 0.3,
+            cleanup_seconds: 8.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharacterDimensionsConfig {
     pub player: CharacterDimensions,
@@ -458,11 +1034,16 @@ impl Default for WorldConfig {
             total_chunks_x: total_chunks,
             total_chunks_z: total_chunks,
             streaming_radius: 800.0, // Reduced from 1200 for 4km world
+            seed: None,
             lod_distances: [150.0, 300.0, 500.0],
             building_density: 0.5,
             tree_density: 2.0,
             vehicle_density: 0.3,
             npc_density: 0.2,
+            interior_chance: 0.2,
+            door_use_radius: 2.5,
+            shop_chance: 0.15,
+            npc_talk_radius: 3.0,
             cleanup_delay: 30.0,
             update_interval: 0.1,
         }
@@ -538,6 +1119,236 @@ impl Default for NPCConfig {
             walk_speed: 2.5, // Increased from 1.5 to match faster player walking
             run_speed: 5.0,  // Increased from 3.0 to match faster player running
             avoidance_distance: 5.0,
+            sidewalk_offset: 2.0,
+            waypoint_spacing: 15.0,
+            sidewalk_snap_distance: 40.0,
+            converse_chance: 0.2,
+            converse_duration: 6.0,
+            flee_duration: 4.0,
+            honk_radius: 25.0,
+            bark_chance: 0.3,
+            ragdoll_impact_energy: 1500.0,
+            ragdoll_fatal_energy: 9000.0,
+            ragdoll_impact_radius: 3.0,
+            ragdoll_mass: 70.0,
+            ragdoll_recover_time: 4.0,
+            ragdoll_despawn_time: 10.0,
+            ragdoll_full_distance: 40.0,
+        }
+    }
+}
+
+impl Default for TrafficConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            active_radius: 400.0,
+            max_road_snap_distance: 30.0,
+            lookahead_distance: 8.0,
+            intersection_stop_time: 2.0,
+            player_avoid_distance: 12.0,
+            acceleration: 4.0,
+            highway_speed: 25.0,
+            main_street_speed: 14.0,
+            side_street_speed: 10.0,
+            alley_speed: 6.0,
+            alarm_chance: 0.5,
+            alarm_trigger_speed: 1.5,
+            alarm_duration: 20.0,
+        }
+    }
+}
+
+impl Default for PoliceConfig {
+    fn default() -> Self {
+        Self {
+            max_stars: 5,
+            heat_per_star: 2.0,
+            pedestrian_hit_heat: 1.0,
+            vehicle_hit_heat: 0.5,
+            hit_speed_threshold: 6.0,
+            crime_cooldown: 1.0,
+            decay_delay: 20.0,
+            decay_rate: 0.25,
+            units_per_star: 1,
+            spawn_distance: 90.0,
+            spawn_interval: 3.0,
+            pursuit_speed: 24.0,
+            despawn_distance: 250.0,
+            alarm_heat: 0.5,
+            alarm_min_stars: 3,
+        }
+    }
+}
+
+impl Default for DivingConfig {
+    fn default() -> Self {
+        Self {
+            breath_capacity: 30.0,
+            breath_refill_rate: 5.0,
+            surface_swim_speed: 2.4,
+            dive_swim_speed: 1.8,
+            breathless_speed_scale: 0.5,
+            surfacing_speed: 2.5,
+            underwater_saturation: 0.55,
+            underwater_exposure: -0.5,
+            grading_blend_rate: 6.0,
+        }
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            filter: "info,wgpu=error,naga=warn".to_string(),
+            file_output: true,
+            max_file_bytes: 4 * 1024 * 1024,
+            max_files: 3,
+            buffer_lines: 500,
+        }
+    }
+}
+
+impl Default for LocalizationConfig {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            languages: vec!["en".to_string(), "es".to_string()],
+            fonts: HashMap::new(),
+            fallback_fonts: Vec::new(),
+        }
+    }
+}
+
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            colorblind_mode: ColorblindMode::Off,
+            subtitles: false,
+            reduced_camera_shake: false,
+        }
+    }
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval: 1.0,
+            csv_output: false,
+            csv_max_bytes: 8 * 1024 * 1024,
+            csv_max_files: 5,
+            http_port: None,
+            http_bind: "127.0.0.1".to_string(),
+        }
+    }
+}
+
+impl Default for LightingConfig {
+    fn default() -> Self {
+        Self {
+            night_illuminance: 3000.0,
+            headlight_intensity: 400_000.0,
+            headlight_range: 60.0,
+            taillight_intensity: 15_000.0,
+            brake_light_intensity: 60_000.0,
+            max_lit_vehicles: 8,
+            street_lamp_intensity: 600_000.0,
+            street_lamp_range: 30.0,
+            street_lamp_distance: 180.0,
+            max_street_lamps: 24,
+            day_length_minutes: 48.0,
+            start_hour: 16.5,
+        }
+    }
+}
+
+impl Default for PostProcessingConfig {
+    fn default() -> Self {
+        Self {
+            tonemapping: TonemappingMode::AcesFitted,
+            exposure_ev100: 9.7,
+            bloom_intensity: 0.05,
+            motion_blur_shutter_angle: 0.5,
+            motion_blur_full_speed: 40.0,
+            motion_blur_samples: 4,
+            damage_vignette: 0.7,
+            damage_flash_seconds: 0.4,
+            sky_reflection_intensity: 1500.0,
+            ssr_max_roughness: 0.3,
+            wet_road_roughness: 0.15,
+        }
+    }
+}
+
+impl Default for EconomyConfig {
+    fn default() -> Self {
+        Self {
+            npc_cash_min: 5,
+            npc_cash_max: 60,
+            pickup_radius: 1.5,
+            pickup_lifetime: 60.0,
+            supercar_price: 2000,
+            helicopter_price: 8000,
+            paint_cost: 100,
+            wheels_cost: 250,
+            tuning_stage_cost: 500,
+            hospital_fee: 500,
+        }
+    }
+}
+
+impl Default for ParachuteConfig {
+    fn default() -> Self {
+        Self {
+            ejection_speed: 25.0,
+            bail_speed: 5.0,
+            bail_velocity_scale: 0.8,
+            launch_tumble_rate: 8.0,
+            tumble_damping: 1.5,
+            terminal_velocity: 55.0,
+            min_deploy_height: 15.0,
+            deploy_time: 1.5,
+            canopy_descent_speed: 5.0,
+            canopy_glide_speed: 9.0,
+            canopy_turn_rate: 1.2,
+            canopy_response: 2.0,
+            flare_descent_scale: 0.4,
+            landing_probe_length: 1.2,
+            freefall_entry_height: 20.0,
+            freefall_track_speed: 20.0,
+            freefall_air_control: 1.0,
+            freefall_turn_rate: 1.5,
+            dive_terminal_scale: 1.4,
+            spread_terminal_scale: 0.7,
+        }
+    }
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_health: 100.0,
+            regen_delay: 5.0,
+            regen_rate: 0.1,
+            fall_safe_speed: 15.0,
+            fall_lethal_speed: 32.0,
+            vehicle_safe_speed: 4.0,
+            vehicle_lethal_speed: 25.0,
+            drowning_rate: 10.0,
+            death_fade_time: 1.5,
+            wasted_hold_time: 2.0,
+            respawn_fade_time: 1.0,
+        }
+    }
+}
+
+impl Default for GraphicsConfig {
+    fn default() -> Self {
+        Self {
+            resolution: (1280, 720),
+            vsync: true,
+            quality: GraphicsQuality::Medium,
         }
     }
 }
@@ -565,13 +1376,33 @@ impl Default for PerformanceConfig {
             effect_update_interval: 0.05,
             target_fps: 60.0,
             frame_time_threshold: 16.67,
+            budget_over_frames: 30,
+            budget_recover_frames: 180,
+            budget_headroom: 0.75,
+            budget_step: 0.1,
+            budget_min_scale: 0.5,
             culling_check_interval: 0.5,
             max_visible_distance: 1000.0,
+            gpu_occlusion_culling: true,
             npc_visibility_distance: 125.0,
             vehicle_visibility_distance: 250.0,
             tree_visibility_distance: 300.0,
             building_visibility_distance: 1500.0, // High for Manhattan skyline visibility
             road_visibility_distance: 400.0,
+            hlod_region_size: 400.0,
+            hlod_distance: 700.0,
+            upload_budget_ms: 2.0,
+            upload_bytes_per_ms: 4 * 1024 * 1024,
+            gpu_residency_budget_kb: 256 * 1024,
+            npc_pool_size: 32,
+            vehicle_pool_size: 16,
+            particle_pool_size: 16,
+            dormant_npc_budget_kb: 256,
+            mesh_cache_budget_kb: 64 * 1024,
+            material_cache_budget_kb: 1024,
+            distance_cache_budget_kb: 4 * 1024,
+            spatial_index_budget_kb: 8 * 1024,
+            prefab_registry_budget_kb: 4 * 1024,
         }
     }
 }
@@ -586,9 +1417,15 @@ impl Default for AudioConfig {
             backfire_volume: 0.7,
 
             footstep_volume: 0.5,
+            siren_volume: 0.7,
+            ambient_volume: 0.4,
+            alarm_volume: 0.8,
+            bark_volume: 0.8,
             footstep_intervals: FootstepConfig::default(),
             fade_distance: 100.0,
             max_audio_distance: 250.0,
+            max_engine_voices: 8,
+            doppler_scale: 1.0,
         }
     }
 }
@@ -607,16 +1444,88 @@ impl Default for FootstepConfig {
 impl Default for CameraConfig {
     fn default() -> Self {
         Self {
-            distance: 8.0,   // Much closer to player
-            height: 1.5,     // Lower for more parallel to ground
-            lerp_speed: 2.5, // Near-instant camera response
-            look_ahead_distance: 10.0,
-            look_ahead_height: 2.0,
-
-            // Swimming camera parameters
-            swim_distance: 2.5,   // Behind swimmer
-            swim_height: 0.6,     // Above swimmer's back
-            swim_look_ahead: 0.5, // Look ahead targeting
+            on_foot: CameraOffset {
+                distance: 8.0,
+                height: 1.5,
+                look_height: 0.5,
+                look_ahead: 0.0,
+                hood: (0.0, 0.7, -0.3),
+                follow_speed: 2.5,
+                fov: 60.0,
+                fov_kick: 5.0,
+                kick_speed: 10.0,
+            },
+            swimming: CameraOffset {
+                distance: 2.5,
+                height: 0.6,
+                look_height: 0.0,
+                look_ahead: 0.5,
+                hood: (0.0, 0.3, -0.6),
+                follow_speed: 2.5,
+                fov: 60.0,
+                fov_kick: 0.0,
+                kick_speed: 5.0,
+            },
+            car: CameraOffset {
+                distance: 12.0,
+                height: 4.0,
+                look_height: 0.0,
+                look_ahead: 5.0,
+                hood: (0.0, 1.1, -0.6),
+                follow_speed: 5.0,
+                fov: 70.0,
+                fov_kick: 15.0,
+                kick_speed: 50.0,
+            },
+            helicopter: CameraOffset {
+                distance: 20.0,
+                height: 8.0,
+                look_height: 0.0,
+                look_ahead: 10.0,
+                hood: (0.0, 1.0, -1.8),
+                follow_speed: 4.0,
+                fov: 65.0,
+                fov_kick: 15.0,
+                kick_speed: 30.0,
+            },
+            f16: CameraOffset {
+                distance: 30.0,
+                height: 12.0,
+                look_height: 0.0,
+                look_ahead: 20.0,
+                hood: (0.0, 1.3, -4.5),
+                follow_speed: 3.5,
+                fov: 60.0,
+                fov_kick: 30.0,
+                kick_speed: 100.0,
+            },
+            yacht: CameraOffset {
+                distance: 80.0,
+                height: 25.0,
+                look_height: 0.0,
+                look_ahead: 20.0,
+                hood: (0.0, 9.0, -12.0),
+                follow_speed: 3.0,
+                fov: 65.0,
+                fov_kick: 15.0,
+                kick_speed: 18.0,
+            },
+            freefall: CameraOffset {
+                distance: 9.0,
+                height: 5.0,
+                look_height: -3.0,
+                look_ahead: 0.0,
+                hood: (0.0, 0.7, -0.3),
+                follow_speed: 8.0,
+                fov: 70.0,
+                fov_kick: 15.0,
+                kick_speed: 60.0,
+            },
+            collision_margin: 0.3,
+            arm_recover_speed: 6.0,
+            close_scale: 0.55,
+            cinematic_distance: 35.0,
+            orbit_sensitivity: 0.005,
         }
     }
 }
@@ -658,7 +1567,16 @@ impl Default for WorldStreamingConfig {
                 cull: 100.0,
             },
             vegetation_cull_distance: 500.0,
+            prop_cull_distance: default_prop_cull_distance(),
+            car_impostor_distance: default_car_impostor_distance(),
             road_cell_size: 400.0,
+            startup_chunks_per_frame: default_startup_chunks_per_frame(),
+            chunks_per_frame: default_chunks_per_frame(),
+            startup_radius: default_startup_radius(),
+            velocity_lookahead: default_velocity_lookahead(),
+            lod_hysteresis: default_lod_hysteresis(),
+            lod_lookahead: default_lod_lookahead(),
+            npc_budget: default_npc_budget(),
         }
     }
 }
@@ -689,6 +1607,7 @@ impl Default for WorldPhysicsConfig {
                 buoyancy_water_density: 1000.0,
                 gravity: 9.81,
             },
+            street_props: StreetPropPhysicsConfig::default(),
         }
     }
 }
@@ -778,6 +1697,141 @@ impl Default for WorldObjectsConfig {
     }
 }
 
+/// Maps each layered RON file onto the `GameConfig` section it fills
+macro_rules! config_files {
+    ($($file:literal => $field:ident, $description:literal;)*) => {
+        /// Files read from `assets/config` and the user config directory, with
+        /// what they configure
+        pub const CONFIG_FILES: &[(&str, &str)] = &[$(($file, $description)),*];
+
+        impl GameConfig {
+            fn merge_section(&mut self, filename: &str, contents: &str) -> ron::Result<()> {
+                match filename {
+                    $($file => self.$field = merge_ron(&self.$field, contents)?,)*
+                    _ => {
+                        return Err(ron::Error::Message(format!(
+                            "unknown config file `{filename}`"
+                        )))
+                    }
+                }
+                Ok(())
+            }
+
+            /// The section `filename` fills, as JSON, for schema export and diffing
+            pub fn section_json(&self, filename: &str) -> Option<serde_json::Value> {
+                match filename {
+                    $($file => serde_json::to_value(&self.$field).ok(),)*
+                    _ => None,
+                }
+            }
+
+            /// The section `filename` fills, written out as that file would be
+            pub fn section_ron(&self, filename: &str) -> Option<String> {
+                let pretty = ron::ser::PrettyConfig::default();
+                match filename {
+                    $($file => ron::ser::to_string_pretty(&self.$field, pretty).ok(),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+config_files! {
+    "world_config.ron" => world_env, "world environment";
+    "world_streaming.ron" => world_streaming, "world streaming";
+    "world_physics.ron" => world_physics, "world physics";
+    "character_dimensions.ron" => character_dimensions, "character dimensions";
+    "world_bounds.ron" => world_bounds, "world bounds";
+    "logging.ron" => logging, "logging";
+    "telemetry.ron" => telemetry, "telemetry";
+    "post_processing.ron" => post_processing, "post-processing";
+    "lighting.ron" => lighting, "lighting";
+    "economy.ron" => economy, "economy";
+    "localization.ron" => localization, "localization";
+    "accessibility.ron" => accessibility, "accessibility";
+    "camera.ron" => camera, "camera";
+}
+
+impl GameConfig {
+    /// Layers one of `CONFIG_FILES` over its section; fields the file leaves
+    /// out keep their current value. Settings other sections derive from the
+    /// file are updated to match.
+    pub fn apply_file(&mut self, filename: &str, contents: &str) -> ron::Result<()> {
+        self.merge_section(filename, contents)?;
+        match filename {
+            "world_streaming.ron" => {
+                let streaming = &self.world_streaming;
+                self.world.chunk_size = streaming.chunk_size;
+                self.world.streaming_radius = streaming.streaming_radius;
+                self.world.lod_distances = [
+                    streaming.lod_distances.full,
+                    streaming.lod_distances.medium,
+                    streaming.lod_distances.far,
+                ];
+            }
+            "world_bounds.ron" => {
+                self.world.map_size = self.world_bounds.world_half_size * 2.0;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Sets one field of the section `filename` fills to `value`, given as
+    /// RON. Values that don't fit are retried as a string, so `es` works as
+    /// well as `"es"`.
+    pub fn apply_override(
+        &mut self,
+        filename: &str,
+        fields: &[String],
+        value: &str,
+    ) -> ron::Result<()> {
+        let section = self.section_json(filename).unwrap_or_default();
+        if fields
+            .iter()
+            .try_fold(&section, |value, field| value.get(field))
+            .is_none()
+        {
+            return Err(ron::Error::Message(format!(
+                "`{filename}` has no field `{}`",
+                fields.join(".")
+            )));
+        }
+        let document = |value: &str| {
+            fields.iter().rev().fold(value.to_string(), |inner, field| {
+                format!("({field}: {inner})")
+            })
+        };
+        let result = self.apply_file(filename, &document(value));
+        if result.is_ok() || value.starts_with('"') {
+            return result;
+        }
+        let quoted = ron::to_string(value)?;
+        self.apply_file(filename, &document(&quoted)).or(result)
+    }
+}
+
+/// Environment variables starting with this override single config keys
+pub const ENV_OVERRIDE_PREFIX: &str = "GTA_CONFIG__";
+
+/// Splits an override variable such as
+/// `GTA_CONFIG__WORLD_STREAMING__LOD_DISTANCES__FULL` into the config file
+/// it overrides and the field path within it
+pub fn parse_env_override(var: &str) -> Option<(String, Vec<String>)> {
+    let mut segments = var
+        .strip_prefix(ENV_OVERRIDE_PREFIX)?
+        .split("__")
+        .map(str::to_ascii_lowercase);
+    let filename = format!("{}.ron", segments.next()?);
+    let fields: Vec<String> = segments.collect();
+    let is_field = |field: &String| {
+        !field.is_empty() && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    let known = CONFIG_FILES.iter().any(|(file, _)| *file == filename);
+    (known && !fields.is_empty() && fields.iter().all(is_field)).then_some((filename, fields))
+}
+
 /// CRITICAL VALIDATION FUNCTIONS - Prevent configuration errors
 impl GameConfig {
     /// Validates all configuration values and clamps to safe ranges
@@ -791,6 +1845,19 @@ impl GameConfig {
         self.camera.validate_and_clamp();
         self.ui.validate_and_clamp();
         self.world_objects.validate_and_clamp();
+        self.traffic.validate_and_clamp();
+        self.police.validate_and_clamp();
+        self.diving.validate_and_clamp();
+        self.parachute.validate_and_clamp();
+        self.health.validate_and_clamp();
+        self.economy.validate_and_clamp();
+        self.graphics.validate_and_clamp();
+        self.logging.validate_and_clamp();
+        self.telemetry.validate_and_clamp();
+        self.post_processing.validate_and_clamp();
+        self.lighting.validate_and_clamp();
+        self.localization.validate_and_clamp();
+        self.accessibility.validate_and_clamp();
         // Validate additional config sections
         // Note: world_bounds, world_physics, character_dimensions, world_streaming
         // don't have validate_and_clamp yet - add if needed
@@ -850,6 +1917,10 @@ impl WorldConfig {
         self.tree_density = self.tree_density.clamp(0.1, 3.0);
         self.vehicle_density = self.vehicle_density.clamp(0.05, 2.0);
         self.npc_density = self.npc_density.clamp(0.01, 1.0);
+        self.interior_chance = self.interior_chance.clamp(0.0, 1.0);
+        self.door_use_radius = self.door_use_radius.clamp(1.0, 10.0);
+        self.shop_chance = self.shop_chance.clamp(0.0, 1.0);
+        self.npc_talk_radius = self.npc_talk_radius.clamp(1.0, 10.0);
 
         // Clamp timing parameters
         self.cleanup_delay = self.cleanup_delay.clamp(5.0, 300.0);
@@ -889,6 +1960,15 @@ impl VehicleConfig {
         self.f16.validate_and_clamp();
         self.yacht.validate_and_clamp();
     }
+
+    pub fn for_type(&self, vehicle_type: VehicleType) -> &VehicleTypeConfig {
+        match vehicle_type {
+            VehicleType::SuperCar => &self.super_car,
+            VehicleType::Helicopter => &self.helicopter,
+            VehicleType::F16 => &self.f16,
+            VehicleType::Yacht => &self.yacht,
+        }
+    }
 }
 
 impl VehicleTypeConfig {
@@ -938,6 +2018,27 @@ impl NPCConfig {
         self.walk_speed = self.walk_speed.clamp(0.5, 10.0);
         self.run_speed = self.run_speed.clamp(1.0, 20.0);
         self.avoidance_distance = self.avoidance_distance.clamp(1.0, 50.0);
+
+        // Clamp pedestrian behavior
+        self.sidewalk_offset = self.sidewalk_offset.clamp(0.0, 10.0);
+        self.waypoint_spacing = self.waypoint_spacing.clamp(2.0, 100.0);
+        self.sidewalk_snap_distance = self.sidewalk_snap_distance.clamp(1.0, 500.0);
+        self.converse_chance = self.converse_chance.clamp(0.0, 1.0);
+        self.converse_duration = self.converse_duration.clamp(0.0, 60.0);
+        self.flee_duration = self.flee_duration.clamp(0.5, 30.0);
+        self.honk_radius = self.honk_radius.clamp(1.0, 200.0);
+        self.bark_chance = self.bark_chance.clamp(0.0, 1.0);
+
+        // Clamp ragdoll parameters
+        self.ragdoll_impact_energy = self.ragdoll_impact_energy.clamp(100.0, 100000.0);
+        self.ragdoll_fatal_energy = self
+            .ragdoll_fatal_energy
+            .clamp(self.ragdoll_impact_energy, 1000000.0);
+        self.ragdoll_impact_radius = self.ragdoll_impact_radius.clamp(0.5, 20.0);
+        self.ragdoll_mass = self.ragdoll_mass.clamp(10.0, 300.0);
+        self.ragdoll_recover_time = self.ragdoll_recover_time.clamp(0.5, 60.0);
+        self.ragdoll_despawn_time = self.ragdoll_despawn_time.clamp(0.5, 300.0);
+        self.ragdoll_full_distance = self.ragdoll_full_distance.clamp(0.0, 500.0);
     }
 }
 
@@ -976,10 +2077,33 @@ impl PerformanceConfig {
         // Clamp performance targets
         self.target_fps = self.target_fps.clamp(15.0, 240.0);
         self.frame_time_threshold = 1000.0 / self.target_fps;
+        self.budget_over_frames = self.budget_over_frames.clamp(1, 600);
+        self.budget_recover_frames = self.budget_recover_frames.clamp(1, 3600);
+        self.budget_headroom = self.budget_headroom.clamp(0.3, 0.95);
+        self.budget_step = self.budget_step.clamp(0.01, 0.5);
+        self.budget_min_scale = self.budget_min_scale.clamp(0.1, 1.0);
 
         // Clamp culling parameters
         self.culling_check_interval = self.culling_check_interval.clamp(0.1, 5.0);
         self.max_visible_distance = self.max_visible_distance.clamp(500.0, 10000.0);
+        self.hlod_region_size = self.hlod_region_size.clamp(100.0, 2000.0);
+        self.hlod_distance = self.hlod_distance.clamp(200.0, 5000.0);
+        self.upload_budget_ms = self.upload_budget_ms.clamp(0.0, 16.0);
+        self.upload_bytes_per_ms = self.upload_bytes_per_ms.clamp(64 * 1024, 256 * 1024 * 1024);
+
+        // Clamp pool sizes
+        self.npc_pool_size = self.npc_pool_size.min(256);
+        self.vehicle_pool_size = self.vehicle_pool_size.min(128);
+        self.particle_pool_size = self.particle_pool_size.min(64);
+        self.dormant_npc_budget_kb = self.dormant_npc_budget_kb.min(16384);
+
+        // Clamp memory budgets (0 stays unlimited)
+        self.mesh_cache_budget_kb = self.mesh_cache_budget_kb.min(4 * 1024 * 1024);
+        self.material_cache_budget_kb = self.material_cache_budget_kb.min(1024 * 1024);
+        self.distance_cache_budget_kb = self.distance_cache_budget_kb.min(1024 * 1024);
+        self.spatial_index_budget_kb = self.spatial_index_budget_kb.min(1024 * 1024);
+        self.prefab_registry_budget_kb = self.prefab_registry_budget_kb.min(1024 * 1024);
+        self.gpu_residency_budget_kb = self.gpu_residency_budget_kb.min(64 * 1024 * 1024);
     }
 }
 
@@ -990,12 +2114,18 @@ impl AudioConfig {
         self.engine_volume = self.engine_volume.clamp(0.0, 2.0);
 
         self.footstep_volume = self.footstep_volume.clamp(0.0, 2.0);
+        self.siren_volume = self.siren_volume.clamp(0.0, 2.0);
+        self.ambient_volume = self.ambient_volume.clamp(0.0, 2.0);
+        self.alarm_volume = self.alarm_volume.clamp(0.0, 2.0);
+        self.bark_volume = self.bark_volume.clamp(0.0, 2.0);
 
         self.footstep_intervals.validate_and_clamp();
 
         // Clamp spatial audio parameters
         self.fade_distance = self.fade_distance.clamp(10.0, 1000.0);
         self.max_audio_distance = self.max_audio_distance.clamp(50.0, 2000.0);
+        self.max_engine_voices = self.max_engine_voices.clamp(0, 32);
+        self.doppler_scale = self.doppler_scale.clamp(0.0, 3.0);
     }
 }
 
@@ -1008,13 +2138,35 @@ impl FootstepConfig {
     }
 }
 
+impl CameraOffset {
+    pub fn validate_and_clamp(&mut self) {
+        self.distance = self.distance.clamp(1.0, 200.0);
+        self.height = self.height.clamp(-5.0, 100.0);
+        self.follow_speed = self.follow_speed.clamp(0.1, 30.0);
+        self.fov = self.fov.clamp(30.0, 120.0);
+        self.fov_kick = self.fov_kick.clamp(0.0, 120.0 - self.fov);
+        self.kick_speed = self.kick_speed.max(1.0);
+    }
+}
+
 impl CameraConfig {
     pub fn validate_and_clamp(&mut self) {
-        self.distance = self.distance.clamp(5.0, 100.0);
-        self.height = self.height.clamp(1.0, 50.0); // Lowered min from 2.0 to accommodate default 1.5
-        self.lerp_speed = self.lerp_speed.clamp(0.001, 5.0); // Raised max from 0.5 to accommodate default 2.5
-        self.look_ahead_distance = self.look_ahead_distance.clamp(2.0, 50.0);
-        self.look_ahead_height = self.look_ahead_height.clamp(0.5, 20.0);
+        for offset in [
+            &mut self.on_foot,
+            &mut self.swimming,
+            &mut self.car,
+            &mut self.helicopter,
+            &mut self.f16,
+            &mut self.yacht,
+            &mut self.freefall,
+        ] {
+            offset.validate_and_clamp();
+        }
+        self.collision_margin = self.collision_margin.clamp(0.0, 2.0);
+        self.arm_recover_speed = self.arm_recover_speed.clamp(0.5, 100.0);
+        self.close_scale = self.close_scale.clamp(0.2, 1.0);
+        self.cinematic_distance = self.cinematic_distance.clamp(5.0, 200.0);
+        self.orbit_sensitivity = self.orbit_sensitivity.clamp(0.0005, 0.05);
     }
 }
 
@@ -1028,6 +2180,274 @@ impl UIConfig {
     }
 }
 
+impl PoliceConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.max_stars = self.max_stars.clamp(1, 6);
+        self.heat_per_star = self.heat_per_star.clamp(0.1, 100.0);
+        self.pedestrian_hit_heat = self.pedestrian_hit_heat.clamp(0.0, 100.0);
+        self.vehicle_hit_heat = self.vehicle_hit_heat.clamp(0.0, 100.0);
+        self.hit_speed_threshold = self.hit_speed_threshold.clamp(0.5, 50.0);
+        self.crime_cooldown = self.crime_cooldown.clamp(0.0, 10.0);
+        self.decay_delay = self.decay_delay.clamp(0.0, 300.0);
+        self.decay_rate = self.decay_rate.clamp(0.01, 10.0);
+        self.units_per_star = self.units_per_star.clamp(0, 5);
+        self.spawn_distance = self.spawn_distance.clamp(20.0, 500.0);
+        self.spawn_interval = self.spawn_interval.clamp(0.1, 60.0);
+        self.pursuit_speed = self.pursuit_speed.clamp(5.0, 80.0);
+        self.despawn_distance = self.despawn_distance.clamp(50.0, 2000.0);
+        self.alarm_heat = self.alarm_heat.clamp(0.0, 100.0);
+        self.alarm_min_stars = self.alarm_min_stars.clamp(1, self.max_stars);
+    }
+
+    /// Stars earned for the given heat
+    pub fn stars_for_heat(&self, heat: f32) -> u8 {
+        ((heat / self.heat_per_star).ceil().max(0.0) as u8).min(self.max_stars)
+    }
+}
+
+impl DivingConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.breath_capacity = self.breath_capacity.clamp(1.0, 600.0);
+        self.breath_refill_rate = self.breath_refill_rate.clamp(0.1, 100.0);
+        self.surface_swim_speed = self.surface_swim_speed.clamp(0.5, 10.0);
+        self.dive_swim_speed = self.dive_swim_speed.clamp(0.5, 10.0);
+        self.breathless_speed_scale = self.breathless_speed_scale.clamp(0.1, 1.0);
+        self.surfacing_speed = self.surfacing_speed.clamp(0.5, 10.0);
+        self.underwater_saturation = self.underwater_saturation.clamp(0.0, 2.0);
+        self.underwater_exposure = self.underwater_exposure.clamp(-4.0, 4.0);
+        self.grading_blend_rate = self.grading_blend_rate.clamp(0.1, 60.0);
+    }
+}
+
+impl LoggingConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.max_file_bytes = self.max_file_bytes.clamp(64 * 1024, 256 * 1024 * 1024);
+        self.max_files = self.max_files.clamp(1, 20);
+        self.buffer_lines = self.buffer_lines.clamp(50, 10_000);
+    }
+}
+
+impl TelemetryConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.sample_interval = self.sample_interval.clamp(0.1, 60.0);
+        self.csv_max_bytes = self.csv_max_bytes.clamp(64 * 1024, 1024 * 1024 * 1024);
+        self.csv_max_files = self.csv_max_files.clamp(1, 50);
+    }
+}
+
+impl PostProcessingConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.exposure_ev100 = self.exposure_ev100.clamp(-5.0, 20.0);
+        self.bloom_intensity = self.bloom_intensity.clamp(0.0, 1.0);
+        self.motion_blur_shutter_angle = self.motion_blur_shutter_angle.clamp(0.0, 2.0);
+        self.motion_blur_full_speed = self.motion_blur_full_speed.clamp(1.0, 500.0);
+        self.motion_blur_samples = self.motion_blur_samples.clamp(1, 32);
+        self.damage_vignette = self.damage_vignette.clamp(0.0, 1.0);
+        self.damage_flash_seconds = self.damage_flash_seconds.clamp(0.0, 5.0);
+        self.sky_reflection_intensity = self.sky_reflection_intensity.clamp(0.0, 100_000.0);
+        self.ssr_max_roughness = self.ssr_max_roughness.clamp(0.0, 1.0);
+        self.wet_road_roughness = self.wet_road_roughness.clamp(0.0, 1.0);
+    }
+}
+
+impl LightingConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.night_illuminance = self.night_illuminance.clamp(0.0, 100_000.0);
+        self.headlight_intensity = self.headlight_intensity.clamp(0.0, 10_000_000.0);
+        self.headlight_range = self.headlight_range.clamp(5.0, 300.0);
+        self.taillight_intensity = self.taillight_intensity.clamp(0.0, 1_000_000.0);
+        self.brake_light_intensity = self.brake_light_intensity.clamp(0.0, 1_000_000.0);
+        self.max_lit_vehicles = self.max_lit_vehicles.min(64);
+        self.street_lamp_intensity = self.street_lamp_intensity.clamp(0.0, 10_000_000.0);
+        self.street_lamp_range = self.street_lamp_range.clamp(5.0, 200.0);
+        self.street_lamp_distance = self.street_lamp_distance.clamp(0.0, 2000.0);
+        self.max_street_lamps = self.max_street_lamps.min(128);
+        self.day_length_minutes = self.day_length_minutes.clamp(0.0, 1440.0);
+        self.start_hour = self.start_hour.rem_euclid(24.0);
+    }
+
+    /// Lit cars and street lamps allowed under `light_budget`
+    pub fn budgets(&self, light_budget: f32) -> (usize, usize) {
+        let scale = |max: usize| (max as f32 * light_budget).round() as usize;
+        (scale(self.max_lit_vehicles), scale(self.max_street_lamps))
+    }
+}
+
+impl LocalizationConfig {
+    pub fn validate_and_clamp(&mut self) {
+        if self.language.is_empty() {
+            self.language = "en".to_string();
+        }
+        if !self.languages.contains(&self.language) {
+            self.languages.push(self.language.clone());
+        }
+    }
+
+    /// Language after `current` in `languages`, wrapping around
+    pub fn next_language(&self, current: &str) -> String {
+        self.languages
+            .iter()
+            .position(|language| language == current)
+            .and_then(|i| self.languages.get((i + 1) % self.languages.len()))
+            .or(self.languages.first())
+            .cloned()
+            .unwrap_or_else(|| current.to_string())
+    }
+
+    /// Font files to try for `language`, best first
+    pub fn font_candidates<'a>(&'a self, language: &str) -> impl Iterator<Item = &'a String> {
+        self.fonts
+            .get(language)
+            .into_iter()
+            .flatten()
+            .chain(&self.fallback_fonts)
+    }
+}
+
+impl ColorblindMode {
+    pub fn next(self) -> Self {
+        match self {
+            ColorblindMode::Off => ColorblindMode::Protanopia,
+            ColorblindMode::Protanopia => ColorblindMode::Deuteranopia,
+            ColorblindMode::Deuteranopia => ColorblindMode::Tritanopia,
+            ColorblindMode::Tritanopia => ColorblindMode::Off,
+        }
+    }
+
+    /// Replacement for an indicator's normal color, or None to keep it.
+    /// Red-green modes share the Okabe-Ito blue/orange pairs; tritanopia
+    /// swaps blue and yellow for red, teal and pink.
+    pub fn indicator_color(self, indicator: Indicator) -> Option<Color> {
+        let color = match (self, indicator) {
+            (ColorblindMode::Off, _) => return None,
+            (ColorblindMode::Tritanopia, Indicator::Danger) => Color::srgb(0.85, 0.15, 0.2),
+            (ColorblindMode::Tritanopia, Indicator::Warning) => Color::srgb(0.95, 0.5, 0.75),
+            (ColorblindMode::Tritanopia, Indicator::Good) => Color::srgb(0.0, 0.65, 0.55),
+            (ColorblindMode::Tritanopia, Indicator::VehicleIcon) => Color::srgb(0.0, 0.65, 0.55),
+            (ColorblindMode::Tritanopia, Indicator::NpcIcon) => Color::srgb(0.95, 0.5, 0.75),
+            (_, Indicator::Danger) => Color::srgb(0.9, 0.6, 0.0),
+            (_, Indicator::Warning) => Color::srgb(0.95, 0.9, 0.25),
+            (_, Indicator::Good) => Color::srgb(0.35, 0.7, 0.9),
+            (_, Indicator::VehicleIcon) => Color::srgb(0.35, 0.7, 0.9),
+            (_, Indicator::NpcIcon) => Color::srgb(0.95, 0.9, 0.25),
+            (_, Indicator::AirfieldIcon) => Color::srgb(0.9, 0.9, 0.9),
+        };
+        Some(color)
+    }
+}
+
+impl AccessibilityConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.ui_scale = self.ui_scale.clamp(0.5, 2.0);
+    }
+
+    /// `normal`, or its replacement under the selected colorblind palette
+    pub fn indicator(&self, indicator: Indicator, normal: Color) -> Color {
+        self.colorblind_mode
+            .indicator_color(indicator)
+            .unwrap_or(normal)
+    }
+
+    /// Multiplier on camera shake offsets
+    pub fn camera_shake_scale(&self) -> f32 {
+        if self.reduced_camera_shake { 0.25 } else { 1.0 }
+    }
+}
+
+impl EconomyConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.npc_cash_max = self.npc_cash_max.min(100_000);
+        self.npc_cash_min = self.npc_cash_min.min(self.npc_cash_max);
+        self.pickup_radius = self.pickup_radius.clamp(0.5, 10.0);
+        self.pickup_lifetime = self.pickup_lifetime.clamp(1.0, 600.0);
+    }
+
+    /// Garage price, or None for vehicles garages don't sell
+    pub fn vehicle_price(&self, vehicle_type: VehicleType) -> Option<u32> {
+        match vehicle_type {
+            VehicleType::SuperCar => Some(self.supercar_price),
+            VehicleType::Helicopter => Some(self.helicopter_price),
+            VehicleType::Yacht | VehicleType::F16 => None,
+        }
+    }
+}
+
+impl ParachuteConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.ejection_speed = self.ejection_speed.clamp(0.0, 100.0);
+        self.bail_speed = self.bail_speed.clamp(0.0, 30.0);
+        self.bail_velocity_scale = self.bail_velocity_scale.clamp(0.0, 1.0);
+        self.launch_tumble_rate = self.launch_tumble_rate.clamp(0.0, 30.0);
+        self.tumble_damping = self.tumble_damping.clamp(0.1, 20.0);
+        self.terminal_velocity = self.terminal_velocity.clamp(10.0, 150.0);
+        self.min_deploy_height = self.min_deploy_height.clamp(0.0, 500.0);
+        self.deploy_time = self.deploy_time.clamp(0.1, 10.0);
+        self.canopy_descent_speed = self.canopy_descent_speed.clamp(1.0, 20.0);
+        self.canopy_glide_speed = self.canopy_glide_speed.clamp(0.0, 30.0);
+        self.canopy_turn_rate = self.canopy_turn_rate.clamp(0.1, 5.0);
+        self.canopy_response = self.canopy_response.clamp(0.1, 20.0);
+        self.flare_descent_scale = self.flare_descent_scale.clamp(0.1, 1.0);
+        self.landing_probe_length = self.landing_probe_length.clamp(0.1, 5.0);
+        self.freefall_entry_height = self.freefall_entry_height.clamp(5.0, 500.0);
+        self.freefall_track_speed = self.freefall_track_speed.clamp(0.0, 50.0);
+        self.freefall_air_control = self.freefall_air_control.clamp(0.1, 10.0);
+        self.freefall_turn_rate = self.freefall_turn_rate.clamp(0.1, 5.0);
+        self.dive_terminal_scale = self.dive_terminal_scale.clamp(1.0, 3.0);
+        self.spread_terminal_scale = self.spread_terminal_scale.clamp(0.3, 1.0);
+    }
+}
+
+impl HealthConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.max_health = self.max_health.clamp(1.0, 10_000.0);
+        self.regen_delay = self.regen_delay.clamp(0.0, 120.0);
+        self.regen_rate = self.regen_rate.clamp(0.0, 1.0);
+        self.fall_safe_speed = self.fall_safe_speed.clamp(1.0, 50.0);
+        self.fall_lethal_speed = self
+            .fall_lethal_speed
+            .clamp(self.fall_safe_speed + 1.0, 150.0);
+        self.vehicle_safe_speed = self.vehicle_safe_speed.clamp(0.5, 50.0);
+        self.vehicle_lethal_speed = self
+            .vehicle_lethal_speed
+            .clamp(self.vehicle_safe_speed + 1.0, 200.0);
+        self.drowning_rate = self.drowning_rate.clamp(0.0, 1000.0);
+        self.death_fade_time = self.death_fade_time.clamp(0.1, 10.0);
+        self.wasted_hold_time = self.wasted_hold_time.clamp(0.0, 10.0);
+        self.respawn_fade_time = self.respawn_fade_time.clamp(0.1, 10.0);
+    }
+
+    /// Share of max health lost when `speed` sits between a safe and a
+    /// lethal speed
+    pub fn damage_share(speed: f32, safe: f32, lethal: f32) -> f32 {
+        ((speed - safe) / (lethal - safe)).clamp(0.0, 1.0)
+    }
+}
+
+impl GraphicsConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.resolution.0 = self.resolution.0.clamp(640, 7680);
+        self.resolution.1 = self.resolution.1.clamp(360, 4320);
+    }
+}
+
+impl TrafficConfig {
+    pub fn validate_and_clamp(&mut self) {
+        self.active_radius = self.active_radius.clamp(50.0, 2000.0);
+        self.max_road_snap_distance = self.max_road_snap_distance.clamp(1.0, 100.0);
+        self.lookahead_distance = self.lookahead_distance.clamp(1.0, 50.0);
+        self.intersection_stop_time = self.intersection_stop_time.clamp(0.0, 30.0);
+        self.player_avoid_distance = self.player_avoid_distance.clamp(2.0, 100.0);
+        self.acceleration = self.acceleration.clamp(0.5, 20.0);
+        self.highway_speed = self.highway_speed.clamp(1.0, 80.0);
+        self.main_street_speed = self.main_street_speed.clamp(1.0, 80.0);
+        self.side_street_speed = self.side_street_speed.clamp(1.0, 80.0);
+        self.alley_speed = self.alley_speed.clamp(1.0, 80.0);
+        self.alarm_chance = self.alarm_chance.clamp(0.0, 1.0);
+        self.alarm_trigger_speed = self.alarm_trigger_speed.clamp(0.1, 20.0);
+        self.alarm_duration = self.alarm_duration.clamp(1.0, 120.0);
+    }
+}
+
 impl WorldObjectsConfig {
     pub fn validate_and_clamp(&mut self) {
         self.palm_tree.validate_and_clamp();
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 // Collision groups for proper physics separation (implementation constant - not data-driven)
 pub const STATIC_GROUP: Group = Group::GROUP_1; // Buildings, terrain, trees
@@ -9,7 +9,7 @@ pub const CHARACTER_GROUP: Group = Group::GROUP_3; // Player, NPCs
 
 // World environment configuration loaded from assets/config/world_config.ron
 // Renamed to WorldEnvConfig to avoid collision with config::WorldConfig
-#[derive(Debug, Clone, Resource, Deserialize)]
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
 pub struct WorldEnvConfig {
     pub sea_level: f32,
     pub land_elevation: f32,
@@ -20,7 +20,7 @@ pub struct WorldEnvConfig {
     pub max_world_coordinate: f32,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IslandConfig {
     pub left_x: f32,
     pub right_x: f32,
@@ -28,11 +28,25 @@ pub struct IslandConfig {
     pub grid_z: f32,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TerrainConfig {
     pub size: f32,
     pub half_size: f32,
     pub beach_width: f32,
+    #[serde(default)]
+    pub relief: TerrainReliefConfig,
+}
+
+/// Seeded noise hills on the island plateaus. Relief only raises the ground and
+/// fades out towards the beaches; an amplitude of 0 keeps the islands flat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TerrainReliefConfig {
+    pub seed: u64,
+    pub amplitude: f32,
+    pub wavelength: f32,
+    pub octaves: u32,
+    pub edge_blend: f32,
 }
 
 // Manual Default implementation matching assets/config/world_config.ron
@@ -67,6 +81,19 @@ impl Default for TerrainConfig {
             size: 1200.0,
             half_size: 600.0,
             beach_width: 100.0,
+            relief: TerrainReliefConfig::default(),
+        }
+    }
+}
+
+impl Default for TerrainReliefConfig {
+    fn default() -> Self {
+        Self {
+            seed: 1337,
+            amplitude: 0.0,
+            wavelength: 180.0,
+            octaves: 4,
+            edge_blend: 80.0,
         }
     }
 }
@@ -0,0 +1,140 @@
+use crate::components::{AirfieldEntry, AirfieldKind, AirfieldRegistry, LandingPad, Runway};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Paint sits this far above the ground to avoid z-fighting
+const SURFACE_OFFSET: f32 = 0.05;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunwayDefinition {
+    pub name: String,
+    pub center: Vec3,
+    /// Direction of the centerline in degrees (0 = +Z, 90 = +X)
+    pub heading_deg: f32,
+    pub length: f32,
+    pub width: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HelipadDefinition {
+    pub name: String,
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+/// Airfield layout loaded from `assets/config/airfields.ron`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AirfieldList {
+    #[serde(default)]
+    pub runways: Vec<RunwayDefinition>,
+    #[serde(default)]
+    pub helipads: Vec<HelipadDefinition>,
+}
+
+/// Spawns runway and helipad surfaces and records them in the `AirfieldRegistry`
+pub struct AirfieldFactory {
+    asphalt: Handle<StandardMaterial>,
+    marking: Handle<StandardMaterial>,
+}
+
+impl AirfieldFactory {
+    pub fn new(materials: &mut Assets<StandardMaterial>) -> Self {
+        Self {
+            asphalt: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.18, 0.18, 0.2),
+                perceptual_roughness: 0.9,
+                ..default()
+            }),
+            marking: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.95, 0.95, 0.9),
+                perceptual_roughness: 0.8,
+                ..default()
+            }),
+        }
+    }
+
+    pub fn spawn_runway(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        registry: &mut AirfieldRegistry,
+        runway: &RunwayDefinition,
+    ) -> Entity {
+        let RunwayDefinition {
+            center,
+            length,
+            width,
+            ..
+        } = *runway;
+        let rotation = Quat::from_rotation_y(runway.heading_deg.to_radians());
+        let entity = commands
+            .spawn((
+                Name::new(runway.name.clone()),
+                Runway { length, width },
+                Mesh3d(meshes.add(Plane3d::default().mesh().size(width, length))),
+                MeshMaterial3d(self.asphalt.clone()),
+                Transform::from_translation(center + Vec3::Y * SURFACE_OFFSET)
+                    .with_rotation(rotation),
+            ))
+            .id();
+
+        // Dashed centerline
+        let dash = meshes.add(Plane3d::default().mesh().size(1.0, 12.0));
+        let dash_count = (length / 30.0) as i32;
+        for i in 0..dash_count {
+            let along = (i as f32 + 0.5) * 30.0 - length * 0.5;
+            commands.spawn((
+                Mesh3d(dash.clone()),
+                MeshMaterial3d(self.marking.clone()),
+                Transform::from_xyz(0.0, SURFACE_OFFSET, along),
+                ChildOf(entity),
+            ));
+        }
+
+        registry.register(AirfieldEntry {
+            entity,
+            kind: AirfieldKind::Runway,
+            position: center,
+            heading: rotation * Vec3::Z,
+            size: Vec2::new(length, width),
+        });
+        entity
+    }
+
+    pub fn spawn_helipad(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        registry: &mut AirfieldRegistry,
+        helipad: &HelipadDefinition,
+    ) -> Entity {
+        let HelipadDefinition { center, radius, .. } = *helipad;
+        let entity = commands
+            .spawn((
+                Name::new(helipad.name.clone()),
+                LandingPad { radius },
+                Mesh3d(meshes.add(Circle::new(radius))),
+                MeshMaterial3d(self.asphalt.clone()),
+                Transform::from_translation(center + Vec3::Y * SURFACE_OFFSET)
+                    .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+            ))
+            .id();
+
+        // Landing ring; the pad is rotated flat, so children lie in its XY plane
+        commands.spawn((
+            Mesh3d(meshes.add(Annulus::new(radius * 0.6, radius * 0.7))),
+            MeshMaterial3d(self.marking.clone()),
+            Transform::from_xyz(0.0, 0.0, SURFACE_OFFSET),
+            ChildOf(entity),
+        ));
+
+        registry.register(AirfieldEntry {
+            entity,
+            kind: AirfieldKind::Helipad,
+            position: center,
+            heading: Vec3::Z,
+            size: Vec2::splat(radius * 2.0),
+        });
+        entity
+    }
+}
@@ -1,11 +1,16 @@
 use crate::components::world::BuildingType as WorldBuildingType;
-use crate::components::{Building, ContentType, DynamicContent};
+use crate::components::{Building, ContentType, DynamicContent, Shop, ShopKind};
 use crate::config::GameConfig;
 use crate::factories::generic_bundle::BundleError;
 use bevy::prelude::*;
 use bevy::render::view::visibility::VisibilityRange;
 use rand::Rng;
 
+/// Shop counter in front of a storefront, where the player interacts
+const COUNTER_SIZE: Vec3 = Vec3::new(2.0, 1.0, 0.6);
+/// Sign above the counter; its width follows the building
+const SIGN_HEIGHT: f32 = 0.8;
+
 /// Building Factory - Focused factory for building spawning only
 /// Handles various building types with proper physics and visual components
 /// Follows AGENT.md simplicity principles with single responsibility
@@ -128,6 +133,50 @@ impl BuildingFactory {
         Ok(building_entity)
     }
 
+    /// Adds a shop counter and sign to the +Z face of `building`, whose
+    /// transform sits at its center
+    pub fn spawn_storefront(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+        building: Entity,
+        building_size: Vec3,
+        kind: ShopKind,
+    ) -> Entity {
+        let color = match kind {
+            ShopKind::VehicleDealer => Color::srgb(0.15, 0.35, 0.8),
+            ShopKind::Clothes => Color::srgb(0.85, 0.3, 0.55),
+        };
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            emissive: LinearRgba::from(color) * 0.3,
+            ..default()
+        });
+        let ground = -building_size.y * 0.5;
+        let front = building_size.z * 0.5;
+
+        let sign_width = (building_size.x * 0.8).min(8.0);
+        let sign = commands
+            .spawn((
+                Mesh3d(meshes.add(Cuboid::new(sign_width, SIGN_HEIGHT, 0.2))),
+                MeshMaterial3d(material.clone()),
+                Transform::from_xyz(0.0, ground + 3.5, front + 0.1),
+            ))
+            .id();
+        let counter = commands
+            .spawn((
+                Name::new(format!("Shop_{kind:?}")),
+                Shop { kind },
+                Mesh3d(meshes.add(Cuboid::new(COUNTER_SIZE.x, COUNTER_SIZE.y, COUNTER_SIZE.z))),
+                MeshMaterial3d(material),
+                Transform::from_xyz(0.0, ground + COUNTER_SIZE.y * 0.5, front + 1.0),
+            ))
+            .id();
+        commands.entity(building).add_children(&[sign, counter]);
+        counter
+    }
+
     /// Spawn multiple buildings in batch
     pub fn spawn_building_batch(
         &self,
@@ -147,7 +196,7 @@ impl BuildingFactory {
     }
 }
 
-#[derive(Component, Clone, Copy, Debug)]
+#[derive(Component, Clone, Copy, Debug, serde::Deserialize)]
 pub enum BuildingType {
     Generic,
     Residential,
@@ -0,0 +1,155 @@
+use crate::systems::world::terrain_height::TerrainHeightService;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy_rapier3d::prelude::Collider;
+
+/// Quads per side of every clipmap level
+pub const CLIPMAP_GRID: u32 = 64;
+/// Vertex spacing of the finest level; each level doubles it
+pub const CLIPMAP_BASE_SPACING: f32 = 2.0;
+pub const CLIPMAP_LEVELS: u32 = 5;
+/// Fraction of a snap step the focus must pass the halfway point by before
+/// a level recenters, so hovering on a boundary doesn't rebuild every frame
+pub const CLIPMAP_SNAP_HYSTERESIS: f32 = 0.25;
+
+/// Vertex spacing of a clipmap level
+pub fn clipmap_spacing(level: u32) -> f32 {
+    CLIPMAP_BASE_SPACING * (1u32 << level) as f32
+}
+
+/// Center of a level around `focus`, snapped to twice its spacing so the next
+/// finer level's edges always land on this level's vertices
+pub fn clipmap_origin(level: u32, focus: Vec2) -> Vec2 {
+    let snap = clipmap_spacing(level) * 2.0;
+    (focus / snap).round() * snap
+}
+
+/// `clipmap_origin`, except the level stays on `previous` until `focus` is
+/// `CLIPMAP_SNAP_HYSTERESIS` of a snap step past where it would round away
+pub fn clipmap_origin_with_hysteresis(level: u32, focus: Vec2, previous: Option<Vec2>) -> Vec2 {
+    let snap = clipmap_spacing(level) * 2.0;
+    match previous {
+        Some(previous)
+            if (focus - previous).abs().max_element() <= snap * (0.5 + CLIPMAP_SNAP_HYSTERESIS) =>
+        {
+            previous
+        }
+        _ => clipmap_origin(level, focus),
+    }
+}
+
+/// How far a level may be centered ahead of the camera: an eighth of its
+/// width, which keeps the camera well inside the finest level and each level
+/// inside the next
+pub fn clipmap_max_lead(level: u32) -> f32 {
+    clipmap_spacing(level) * CLIPMAP_GRID as f32 / 8.0
+}
+
+/// One clipmap level in world space: a `CLIPMAP_GRID`² grid centered on
+/// `origin`, minus the square covered by the finer level centered on `hole`.
+/// Only quads over a plateau are emitted; beaches and the sea bed keep their
+/// own meshes.
+pub fn create_clipmap_level(
+    level: u32,
+    origin: Vec2,
+    hole: Option<Vec2>,
+    terrain: &TerrainHeightService,
+) -> Mesh {
+    let spacing = clipmap_spacing(level);
+    let extent = CLIPMAP_GRID as f32 * spacing;
+    let corner = origin - Vec2::splat(extent * 0.5);
+    let row = CLIPMAP_GRID + 1;
+
+    let mut positions = Vec::with_capacity((row * row) as usize);
+    let mut normals = Vec::with_capacity((row * row) as usize);
+    let mut uvs = Vec::with_capacity((row * row) as usize);
+    for z in 0..row {
+        for x in 0..row {
+            let point = corner + Vec2::new(x as f32, z as f32) * spacing;
+            positions.push([point.x, terrain.height_at(point.x, point.y), point.y]);
+            normals.push(terrain.normal_at(point.x, point.y, spacing).to_array());
+            // World-scaled UVs so texture density is the same on every level
+            uvs.push([point.x / 16.0, point.y / 16.0]);
+        }
+    }
+
+    // The finer level spans half this level's extent
+    let inner_half = extent * 0.25;
+    let mut indices = Vec::new();
+    for z in 0..CLIPMAP_GRID {
+        for x in 0..CLIPMAP_GRID {
+            let center = corner + (Vec2::new(x as f32, z as f32) + 0.5) * spacing;
+            if let Some(hole) = hole
+                && (center - hole).abs().max_element() < inner_half
+            {
+                continue;
+            }
+            if !terrain.is_on_plateau(center.x, center.y) {
+                continue;
+            }
+
+            let i0 = z * row + x;
+            let i1 = i0 + 1;
+            let i2 = i0 + row;
+            let i3 = i2 + 1;
+            indices.extend_from_slice(&[i0, i2, i1, i1, i2, i3]);
+        }
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Heightfield collider for a square plateau of `size` centered on `center`
+/// (whose Y is the collider's origin), sampled every `size / (samples - 1)` meters
+pub fn create_plateau_heightfield(
+    center: Vec3,
+    size: f32,
+    samples: usize,
+    terrain: &TerrainHeightService,
+) -> Collider {
+    let samples = samples.max(2);
+    let step = size / (samples - 1) as f32;
+    let corner = center.xz() - Vec2::splat(size * 0.5);
+
+    // Column-major: rows run along Z, columns along X
+    let mut heights = Vec::with_capacity(samples * samples);
+    for column in 0..samples {
+        for row in 0..samples {
+            let point = corner + Vec2::new(column as f32, row as f32) * step;
+            heights.push(terrain.height_at(point.x, point.y) - center.y);
+        }
+    }
+    Collider::heightfield(heights, samples, samples, Vec3::new(size, 1.0, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_holds_near_snap_boundary() {
+        // Level 0 snaps every 4 m, so 2 m is the rounding boundary
+        let previous = Some(Vec2::ZERO);
+        assert_eq!(clipmap_origin(0, Vec2::new(2.1, 0.0)), Vec2::new(4.0, 0.0));
+        assert_eq!(
+            clipmap_origin_with_hysteresis(0, Vec2::new(2.9, 0.0), previous),
+            Vec2::ZERO
+        );
+        assert_eq!(
+            clipmap_origin_with_hysteresis(0, Vec2::new(3.1, 0.0), previous),
+            Vec2::new(4.0, 0.0)
+        );
+        assert_eq!(
+            clipmap_origin_with_hysteresis(0, Vec2::new(2.9, 0.0), None),
+            Vec2::new(4.0, 0.0)
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::components::ContentType;
+use crate::systems::spatial_index::SpatialIndex;
 use bevy::prelude::*;
 
 /// Collision detector for entity spawning
@@ -36,6 +37,19 @@ impl CollisionDetector {
         })
     }
 
+    /// `has_collision` against the live `SpatialIndex`, touching only nearby cells
+    pub fn has_indexed_collision(
+        position: Vec3,
+        content_type: ContentType,
+        index: &SpatialIndex,
+    ) -> bool {
+        let min_distance = Self::get_min_distance(content_type);
+        index
+            .query_radius(position, min_distance + index.max_radius() + 2.0)
+            .iter()
+            .any(|nearby| position.distance(nearby.position) < min_distance + nearby.radius + 2.0)
+    }
+
     /// Check collision between two specific entities
     ///
     /// Helper method for more precise collision checking:
@@ -0,0 +1,157 @@
+//! Registry of game components that prefabs may list by type name.
+//!
+//! Built-in prefab components are variants of `PrefabComponent`. Any other
+//! component that implements `Deserialize` can be used through
+//! `Custom("TypeName", "<ron>")` once registered, usually with
+//! `register_components!`:
+//!
+//! ```rust,ignore
+//! let mut registry = PrefabComponentRegistry::default();
+//! register_components!(registry, VehicleCustomization, Health);
+//! ```
+
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// Registers each listed component type under its short type name
+#[macro_export]
+macro_rules! register_components {
+    ($registry:expr, $($component:ty),+ $(,)?) => {
+        $( $registry.register::<$component>(); )+
+    };
+}
+
+#[derive(Clone, Copy)]
+struct RegisteredComponent {
+    check: fn(&str) -> Result<(), String>,
+    insert: fn(&str, &mut EntityWorldMut) -> Result<(), String>,
+    remove: fn(&mut EntityWorldMut),
+}
+
+fn check_ron<T: DeserializeOwned>(data: &str) -> Result<(), String> {
+    ron::from_str::<T>(data)
+        .map(drop)
+        .map_err(|e| e.to_string())
+}
+
+fn insert_from_ron<T: Component + DeserializeOwned>(
+    data: &str,
+    entity: &mut EntityWorldMut,
+) -> Result<(), String> {
+    let component: T = ron::from_str(data).map_err(|e| e.to_string())?;
+    entity.insert(component);
+    Ok(())
+}
+
+fn remove_component<T: Component>(entity: &mut EntityWorldMut) {
+    entity.remove::<T>();
+}
+
+/// Deserializers for `PrefabComponent::Custom`, keyed by type name
+#[derive(Resource, Default)]
+pub struct PrefabComponentRegistry {
+    components: HashMap<String, RegisteredComponent>,
+}
+
+impl PrefabComponentRegistry {
+    /// Registers `T` under its type name without the module path
+    pub fn register<T: Component + DeserializeOwned>(&mut self) -> &mut Self {
+        let name = std::any::type_name::<T>();
+        let name = name.rsplit("::").next().unwrap_or(name);
+        self.register_as::<T>(name)
+    }
+
+    /// Registers `T` under an explicit name, replacing any previous entry
+    pub fn register_as<T: Component + DeserializeOwned>(&mut self, name: &str) -> &mut Self {
+        self.components.insert(
+            name.to_string(),
+            RegisteredComponent {
+                check: check_ron::<T>,
+                insert: insert_from_ron::<T>,
+                remove: remove_component::<T>,
+            },
+        );
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    /// Registered names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.components.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Whether `data` deserializes as the component registered under `name`
+    pub fn check(&self, name: &str, data: &str) -> Result<(), String> {
+        let component = self
+            .components
+            .get(name)
+            .ok_or_else(|| format!("component '{name}' is not registered"))?;
+        (component.check)(data)
+    }
+
+    /// Deserializes `data` as the component registered under `name` and inserts it
+    pub fn insert(
+        &self,
+        name: &str,
+        data: &str,
+        entity: &mut EntityWorldMut,
+    ) -> Result<(), String> {
+        let component = self
+            .components
+            .get(name)
+            .ok_or_else(|| format!("component '{name}' is not registered"))?;
+        (component.insert)(data, entity)
+    }
+
+    /// Removes the component registered under `name`, if any
+    pub fn remove(&self, name: &str, entity: &mut EntityWorldMut) {
+        if let Some(component) = self.components.get(name) {
+            (component.remove)(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{VehicleCustomization, WheelStyle};
+
+    #[test]
+    fn test_macro_registers_short_names() {
+        let mut registry = PrefabComponentRegistry::default();
+        register_components!(
+            registry,
+            VehicleCustomization,
+            crate::components::VehicleType
+        );
+
+        assert_eq!(
+            registry.names(),
+            vec!["VehicleCustomization", "VehicleType"]
+        );
+    }
+
+    #[test]
+    fn test_insert_deserializes_ron_payload() {
+        let mut registry = PrefabComponentRegistry::default();
+        register_components!(registry, VehicleCustomization);
+
+        let mut world = World::new();
+        let mut entity = world.spawn_empty();
+        registry
+            .insert("VehicleCustomization", "(wheels: OffRoad)", &mut entity)
+            .unwrap();
+        assert!(registry.insert("Health", "()", &mut entity).is_err());
+
+        assert_eq!(
+            entity.get::<VehicleCustomization>().unwrap().wheels,
+            WheelStyle::OffRoad
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Recycling of high-churn dynamic entities.
+//!
+//! Instead of despawning, `EntityPool::release` strips an entity back to its
+//! transform, render and physics handles, hides it and disables its body, so
+//! the rapier body and collider survive. `EntityPool::spawn` hands a pooled
+//! entity of the same `ContentType` back out with a fresh bundle, falling back
+//! to a normal spawn when the pool is empty.
+
+use crate::components::ContentType;
+use crate::config::PerformanceConfig;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::collections::HashMap;
+
+/// Marks an entity sitting in the `EntityPool`, hidden and without physics
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pooled(pub ContentType);
+
+/// Components that survive `EntityPool::release`; everything else is removed
+type RetainedOnRelease = (
+    Transform,
+    GlobalTransform,
+    Visibility,
+    InheritedVisibility,
+    ViewVisibility,
+    Mesh3d,
+    MeshMaterial3d<StandardMaterial>,
+    RigidBody,
+    Collider,
+    RapierRigidBodyHandle,
+    RapierColliderHandle,
+    RapierContextEntityLink,
+);
+
+#[derive(Resource, Debug, Default)]
+pub struct EntityPool {
+    free: HashMap<ContentType, Vec<Entity>>,
+    limits: HashMap<ContentType, usize>,
+}
+
+impl EntityPool {
+    pub fn from_config(config: &PerformanceConfig) -> Self {
+        let mut pool = Self::default();
+        pool.set_limit(ContentType::NPC, config.npc_pool_size);
+        pool.set_limit(ContentType::Vehicle, config.vehicle_pool_size);
+        pool
+    }
+
+    /// Maximum pooled entities of `content_type`; types without a limit aren't pooled
+    pub fn set_limit(&mut self, content_type: ContentType, limit: usize) {
+        self.limits.insert(content_type, limit);
+        if let Some(free) = self.free.get_mut(&content_type) {
+            free.truncate(limit);
+        }
+    }
+
+    pub fn limit(&self, content_type: ContentType) -> usize {
+        self.limits.get(&content_type).copied().unwrap_or(0)
+    }
+
+    /// Entities of `content_type` waiting to be reused
+    pub fn available(&self, content_type: ContentType) -> usize {
+        self.free.get(&content_type).map_or(0, Vec::len)
+    }
+
+    /// Returns `entity` to the pool, resetting it instead of despawning.
+    /// Despawns it when the pool for `content_type` is full; returns whether it
+    /// is pooled. Releasing an entity that is already pooled does nothing.
+    pub fn release(
+        &mut self,
+        commands: &mut Commands,
+        entity: Entity,
+        content_type: ContentType,
+    ) -> bool {
+        let limit = self.limit(content_type);
+        let free = self.free.entry(content_type).or_default();
+        if free.contains(&entity) {
+            return true;
+        }
+        if free.len() >= limit {
+            commands.entity(entity).despawn();
+            return false;
+        }
+
+        commands
+            .entity(entity)
+            .despawn_related::<Children>()
+            .retain::<RetainedOnRelease>()
+            .insert((
+                Pooled(content_type),
+                Visibility::Hidden,
+                RigidBodyDisabled,
+                ColliderDisabled,
+            ));
+        free.push(entity);
+        true
+    }
+
+    /// Spawns `bundle` on a pooled entity of `content_type` if one is free,
+    /// otherwise on a new entity. The bundle's components replace the retained ones.
+    pub fn spawn(
+        &mut self,
+        commands: &mut Commands,
+        content_type: ContentType,
+        bundle: impl Bundle,
+    ) -> Entity {
+        let free = self.free.entry(content_type).or_default();
+        // Something else may have despawned a pooled entity in the meantime
+        while let Some(entity) = free.pop() {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands
+                    .remove::<(Pooled, RigidBodyDisabled, ColliderDisabled)>()
+                    .insert(Visibility::Inherited)
+                    .insert(bundle);
+                return entity;
+            }
+        }
+        commands.spawn(bundle).id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{NPCState, NPCType};
+
+    #[test]
+    fn test_released_entities_are_reset_and_reused() {
+        let mut world = World::new();
+        let mut pool = EntityPool::default();
+        pool.set_limit(ContentType::NPC, 1);
+
+        let first = world
+            .spawn((
+                NPCState::new(NPCType::Civilian),
+                Transform::from_xyz(1.0, 0.0, 0.0),
+            ))
+            .id();
+        let second = world.spawn(NPCState::new(NPCType::Civilian)).id();
+
+        let mut queue = bevy::ecs::world::CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        assert!(pool.release(&mut commands, first, ContentType::NPC));
+        assert!(!pool.release(&mut commands, second, ContentType::NPC));
+        queue.apply(&mut world);
+
+        assert!(world.get_entity(second).is_err());
+        assert_eq!(pool.available(ContentType::NPC), 1);
+        assert!(!world.entity(first).contains::<NPCState>());
+        assert!(world.entity(first).contains::<RigidBodyDisabled>());
+        assert_eq!(world.get::<Transform>(first).unwrap().translation.x, 1.0);
+
+        let mut commands = Commands::new(&mut queue, &world);
+        let reused = pool.spawn(
+            &mut commands,
+            ContentType::NPC,
+            NPCState::new(NPCType::Civilian),
+        );
+        queue.apply(&mut world);
+
+        assert_eq!(reused, first);
+        assert_eq!(pool.available(ContentType::NPC), 0);
+        let reused = world.entity(reused);
+        assert!(reused.contains::<NPCState>());
+        assert!(!reused.contains::<Pooled>());
+        assert_eq!(reused.get::<Visibility>(), Some(&Visibility::Inherited));
+    }
+
+    #[test]
+    fn test_double_release_keeps_the_entity_pooled() {
+        let mut world = World::new();
+        let mut pool = EntityPool::default();
+        pool.set_limit(ContentType::NPC, 2);
+        let npc = world.spawn(NPCState::new(NPCType::Civilian)).id();
+
+        // Budget enforcement and chunk eviction can both release it in one frame
+        let mut queue = bevy::ecs::world::CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        assert!(pool.release(&mut commands, npc, ContentType::NPC));
+        assert!(pool.release(&mut commands, npc, ContentType::NPC));
+        queue.apply(&mut world);
+
+        assert!(world.get_entity(npc).is_ok());
+        assert_eq!(pool.available(ContentType::NPC), 1);
+    }
+}
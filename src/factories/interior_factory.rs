@@ -0,0 +1,380 @@
+//! Building doors and the interior rooms behind them.
+//!
+//! Interiors are separate scenes built on demand high above the door they
+//! belong to, so the street below never intersects the room and streaming
+//! keeps treating the player as standing at the building. Taller apartment
+//! and office blocks stack one room per storey, linked by an elevator in the
+//! back corner.
+
+use crate::components::{
+    DeckWalkAnchor, Elevator, EnterableDoor, InteriorExit, InteriorKind, InteriorScene,
+};
+use crate::constants::STATIC_GROUP;
+use crate::factories::BuildingType;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
+
+/// Floor height of interior scenes
+pub const INTERIOR_ALTITUDE: f32 = 1500.0;
+
+const WALL_THICKNESS: f32 = 0.3;
+const DOOR_SIZE: Vec3 = Vec3::new(1.4, 2.5, 0.1);
+/// Side of the square elevator shaft cut through the upper floors
+const SHAFT_SIZE: f32 = 2.6;
+const CAR_THICKNESS: f32 = 0.2;
+
+/// What `spawn_interior` built
+pub struct InteriorLayout {
+    pub root: Entity,
+    /// Where the player appears, just inside the exit door
+    pub entry: Vec3,
+}
+
+pub struct InteriorFactory {
+    wall: Handle<StandardMaterial>,
+    floor: Handle<StandardMaterial>,
+    furniture: Handle<StandardMaterial>,
+    door: Handle<StandardMaterial>,
+}
+
+impl InteriorFactory {
+    pub fn new(materials: &mut Assets<StandardMaterial>) -> Self {
+        Self {
+            wall: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.82, 0.8, 0.75),
+                perceptual_roughness: 0.9,
+                ..default()
+            }),
+            floor: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.45, 0.35, 0.25),
+                perceptual_roughness: 0.7,
+                ..default()
+            }),
+            furniture: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.35, 0.3, 0.28),
+                perceptual_roughness: 0.8,
+                ..default()
+            }),
+            door: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.3, 0.18, 0.1),
+                perceptual_roughness: 0.6,
+                ..default()
+            }),
+        }
+    }
+
+    /// Room type for a building; commercial blocks are a mix of shops and offices
+    pub fn interior_kind_for(building_type: BuildingType, rng: &mut impl Rng) -> InteriorKind {
+        match building_type {
+            BuildingType::Residential => InteriorKind::Apartment,
+            BuildingType::Industrial => InteriorKind::Warehouse,
+            BuildingType::Commercial if rng.gen_bool(0.5) => InteriorKind::Shop,
+            BuildingType::Commercial | BuildingType::Generic => InteriorKind::Office,
+        }
+    }
+
+    /// Adds a street door to the +Z face of `building`, whose transform sits at its center
+    pub fn spawn_door(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        building: Entity,
+        building_size: Vec3,
+        kind: InteriorKind,
+    ) -> Entity {
+        let local = Vec3::new(
+            0.0,
+            -building_size.y * 0.5 + DOOR_SIZE.y * 0.5,
+            building_size.z * 0.5 + DOOR_SIZE.z * 0.5,
+        );
+        let door = commands
+            .spawn((
+                Name::new("Door"),
+                EnterableDoor {
+                    kind,
+                    floors: kind.floors_for_height(building_size.y),
+                },
+                Mesh3d(meshes.add(Cuboid::new(DOOR_SIZE.x, DOOR_SIZE.y, DOOR_SIZE.z))),
+                MeshMaterial3d(self.door.clone()),
+                Transform::from_translation(local),
+            ))
+            .id();
+        commands.entity(building).add_child(door);
+        door
+    }
+
+    /// Builds `floors` closed, lit rooms of `kind` stacked above `origin`, the
+    /// ground floor centered on it
+    pub fn spawn_interior(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        kind: InteriorKind,
+        floors: u32,
+        origin: Vec3,
+    ) -> InteriorLayout {
+        let floors = floors.max(1);
+        let size = kind.room_size();
+        let half = size * 0.5;
+        let t = WALL_THICKNESS;
+        let spacing = floor_spacing(kind);
+
+        let root = commands
+            .spawn((
+                Name::new(format!("Interior_{kind:?}")),
+                InteriorScene { kind, floors },
+                RigidBody::Fixed,
+                Transform::from_translation(origin),
+                Visibility::default(),
+            ))
+            .id();
+
+        for storey in 0..floors {
+            let base = Vec3::Y * (storey as f32 * spacing);
+            let (slabs, walls) = storey_shell(kind, storey);
+            for (center, extent) in slabs {
+                spawn_block(commands, meshes, root, &self.floor, base + center, extent);
+            }
+            for (center, extent) in walls {
+                spawn_block(commands, meshes, root, &self.wall, base + center, extent);
+            }
+            for (center, extent) in furniture_layout(kind) {
+                spawn_block(
+                    commands,
+                    meshes,
+                    root,
+                    &self.furniture,
+                    base + center,
+                    extent,
+                );
+            }
+            let light = commands
+                .spawn((
+                    PointLight {
+                        intensity: 400_000.0,
+                        range: size.max_element() * 2.0,
+                        shadows_enabled: false,
+                        ..default()
+                    },
+                    Transform::from_translation(base + Vec3::Y * (size.y - 0.5)),
+                ))
+                .id();
+            commands.entity(root).add_child(light);
+        }
+        let roof = (floors - 1) as f32 * spacing + size.y + t * 0.5;
+        spawn_block(
+            commands,
+            meshes,
+            root,
+            &self.wall,
+            Vec3::new(0.0, roof, 0.0),
+            Vec3::new(size.x, t, size.z),
+        );
+
+        if floors > 1 {
+            let car = SHAFT_SIZE - 0.2;
+            let base = Vec3::new(
+                half.x - SHAFT_SIZE * 0.5,
+                -CAR_THICKNESS * 0.5,
+                -half.z + SHAFT_SIZE * 0.5,
+            );
+            let elevator = commands
+                .spawn((
+                    Name::new("Elevator"),
+                    Elevator {
+                        floors,
+                        floor_spacing: spacing,
+                        base,
+                        height: 0.0,
+                        velocity: 0.0,
+                        target_floor: 0,
+                        half_extents: Vec2::splat(car * 0.5),
+                    },
+                    DeckWalkAnchor,
+                    RigidBody::KinematicPositionBased,
+                    Collider::cuboid(car * 0.5, CAR_THICKNESS * 0.5, car * 0.5),
+                    CollisionGroups::new(STATIC_GROUP, Group::ALL),
+                    Mesh3d(meshes.add(Cuboid::new(car, CAR_THICKNESS, car))),
+                    MeshMaterial3d(self.door.clone()),
+                    Transform::from_translation(base),
+                ))
+                .id();
+            commands.entity(root).add_child(elevator);
+        }
+
+        let exit = commands
+            .spawn((
+                Name::new("InteriorExit"),
+                InteriorExit,
+                Mesh3d(meshes.add(Cuboid::new(DOOR_SIZE.x, DOOR_SIZE.y, DOOR_SIZE.z))),
+                MeshMaterial3d(self.door.clone()),
+                Transform::from_xyz(0.0, DOOR_SIZE.y * 0.5, half.z - DOOR_SIZE.z * 0.5),
+            ))
+            .id();
+        commands.entity(root).add_child(exit);
+
+        InteriorLayout {
+            root,
+            entry: origin + Vec3::new(0.0, 1.0, half.z - 2.0),
+        }
+    }
+}
+
+/// (center, full size) of one box in a room
+type Block = (Vec3, Vec3);
+
+/// Vertical distance between the floors of a multi-storey interior
+fn floor_spacing(kind: InteriorKind) -> f32 {
+    kind.room_size().y + WALL_THICKNESS
+}
+
+/// Floor slabs and four walls of one storey,
+/// relative to that storey's floor. Upper storeys leave the shaft open.
+fn storey_shell(kind: InteriorKind, storey: u32) -> (Vec<Block>, Vec<Block>) {
+    let size = kind.room_size();
+    let half = size * 0.5;
+    let t = WALL_THICKNESS;
+    let s = SHAFT_SIZE;
+
+    let slabs = if storey == 0 {
+        vec![(Vec3::new(0.0, -t * 0.5, 0.0), Vec3::new(size.x, t, size.z))]
+    } else {
+        vec![
+            (
+                Vec3::new(-s * 0.5, -t * 0.5, 0.0),
+                Vec3::new(size.x - s, t, size.z),
+            ),
+            (
+                Vec3::new(half.x - s * 0.5, -t * 0.5, s * 0.5),
+                Vec3::new(s, t, size.z - s),
+            ),
+        ]
+    };
+    let walls = vec![
+        (
+            Vec3::new(0.0, half.y, -half.z - t * 0.5),
+            Vec3::new(size.x, size.y, t),
+        ),
+        (
+            Vec3::new(0.0, half.y, half.z + t * 0.5),
+            Vec3::new(size.x, size.y, t),
+        ),
+        (
+            Vec3::new(-half.x - t * 0.5, half.y, 0.0),
+            Vec3::new(t, size.y, size.z),
+        ),
+        (
+            Vec3::new(half.x + t * 0.5, half.y, 0.0),
+            Vec3::new(t, size.y, size.z),
+        ),
+    ];
+    (slabs, walls)
+}
+
+fn spawn_block(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    root: Entity,
+    material: &Handle<StandardMaterial>,
+    center: Vec3,
+    extent: Vec3,
+) {
+    let block = commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::new(extent.x, extent.y, extent.z))),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(center),
+            Collider::cuboid(extent.x * 0.5, extent.y * 0.5, extent.z * 0.5),
+            CollisionGroups::new(STATIC_GROUP, Group::ALL),
+        ))
+        .id();
+    commands.entity(root).add_child(block);
+}
+
+/// Furniture blocks, kept clear of the entrance
+fn furniture_layout(kind: InteriorKind) -> Vec<Block> {
+    let half = kind.room_size() * 0.5;
+    let back = -half.z + 1.0;
+    match kind {
+        InteriorKind::Apartment => vec![
+            (Vec3::new(-2.5, 0.4, back + 0.5), Vec3::new(3.0, 0.8, 1.0)),
+            (Vec3::new(2.5, 0.375, -0.5), Vec3::new(1.5, 0.75, 1.0)),
+        ],
+        InteriorKind::Shop => vec![
+            (Vec3::new(0.0, 0.5, back + 1.0), Vec3::new(6.0, 1.0, 0.8)),
+            (Vec3::new(-half.x + 0.5, 1.0, 0.0), Vec3::new(0.8, 2.0, 6.0)),
+            (Vec3::new(half.x - 0.5, 1.0, 0.0), Vec3::new(0.8, 2.0, 6.0)),
+        ],
+        InteriorKind::Office => vec![
+            (Vec3::new(0.0, 0.5, back + 1.0), Vec3::new(3.0, 1.0, 1.0)),
+            (Vec3::new(-4.0, 0.375, -1.0), Vec3::new(2.0, 0.75, 1.0)),
+            (Vec3::new(4.0, 0.375, -1.0), Vec3::new(2.0, 0.75, 1.0)),
+        ],
+        InteriorKind::Warehouse => vec![
+            (Vec3::new(-6.0, 1.0, -5.0), Vec3::new(2.0, 2.0, 2.0)),
+            (Vec3::new(-6.0, 3.0, -5.0), Vec3::new(2.0, 2.0, 2.0)),
+            (Vec3::new(5.0, 1.5, -4.0), Vec3::new(3.0, 3.0, 6.0)),
+            (Vec3::new(0.0, 1.0, back + 1.0), Vec3::new(8.0, 2.0, 1.2)),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_furniture_stays_inside_and_clear_of_entrance() {
+        for kind in [
+            InteriorKind::Apartment,
+            InteriorKind::Shop,
+            InteriorKind::Office,
+            InteriorKind::Warehouse,
+        ] {
+            let half = kind.room_size() * 0.5;
+            let entry = Vec3::new(0.0, 1.0, half.z - 2.0);
+            for (center, extent) in furniture_layout(kind) {
+                let min = center - extent * 0.5;
+                let max = center + extent * 0.5;
+                assert!(
+                    min.x >= -half.x && max.x <= half.x,
+                    "{kind:?} furniture in wall"
+                );
+                assert!(
+                    min.z >= -half.z && max.z <= half.z,
+                    "{kind:?} furniture in wall"
+                );
+                assert!(max.y <= kind.room_size().y, "{kind:?} furniture in ceiling");
+                assert!(
+                    (entry.z - max.z) > 1.0 || entry.x < min.x - 1.0 || entry.x > max.x + 1.0,
+                    "{kind:?} furniture blocks the entrance"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_upper_floors_leave_elevator_shaft_clear() {
+        for kind in [InteriorKind::Apartment, InteriorKind::Office] {
+            let half = kind.room_size() * 0.5;
+            let shaft_min = Vec2::new(half.x - SHAFT_SIZE, -half.z);
+            let shaft_max = Vec2::new(half.x, -half.z + SHAFT_SIZE);
+            let overlaps_shaft = |center: Vec3, extent: Vec3| {
+                let min = center - extent * 0.5;
+                let max = center + extent * 0.5;
+                min.x < shaft_max.x - 0.01
+                    && max.x > shaft_min.x + 0.01
+                    && min.z < shaft_max.y - 0.01
+                    && max.z > shaft_min.y + 0.01
+            };
+
+            let (slabs, _) = storey_shell(kind, 1);
+            let area: f32 = slabs.iter().map(|(_, e)| e.x * e.z).sum();
+            assert!((area + SHAFT_SIZE * SHAFT_SIZE - 4.0 * half.x * half.z).abs() < 0.01);
+            for (center, extent) in slabs.into_iter().chain(furniture_layout(kind)) {
+                assert!(!overlaps_shaft(center, extent), "{kind:?} blocks the shaft");
+            }
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use bevy::prelude::*;
 use bevy::render::render_resource::Face;
+use std::collections::HashMap;
+
+/// Roughness of the clear coat `ReflectionHook::ClearCoat` lays over paint
+const CLEARCOAT_ROUGHNESS: f32 = 0.08;
 
 /// Unified material factory that eliminates duplicate StandardMaterial creation
 /// CRITICAL: This replaces 53+ duplicate material patterns across the codebase
@@ -350,6 +354,81 @@ impl MaterialFactory {
     }
 }
 
+/// How a material takes part in reflections
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReflectionHook {
+    /// Glossy clear coat over the base color, like car paint
+    ClearCoat,
+    /// Keeps `dry_roughness` in dry weather and turns glossy in rain, like asphalt
+    Wet { dry_roughness: f32 },
+}
+
+/// Materials opted into reflections through `MaterialFactory::opt_into_reflections`.
+/// While screen-space reflections are on they render deferred so the SSR pass can
+/// trace them; `Wet` ones also follow the rain.
+#[derive(Resource, Debug, Default)]
+pub struct ReflectiveMaterials {
+    materials: HashMap<AssetId<StandardMaterial>, ReflectionHook>,
+}
+
+impl ReflectiveMaterials {
+    pub fn hook(&self, id: AssetId<StandardMaterial>) -> Option<ReflectionHook> {
+        self.materials.get(&id).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (AssetId<StandardMaterial>, ReflectionHook)> + '_ {
+        self.materials.iter().map(|(id, hook)| (*id, *hook))
+    }
+
+    /// Forgets materials that have been dropped, such as old paint jobs
+    pub fn retain_existing(&mut self, materials: &Assets<StandardMaterial>) {
+        self.materials.retain(|id, _| materials.contains(*id));
+    }
+
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+}
+
+/// Reflection hooks: opt materials in once they exist
+impl MaterialFactory {
+    /// Opts a material into reflections. The clear coat is applied right away;
+    /// blended materials are left out because only opaque ones can render deferred.
+    pub fn opt_into_reflections(
+        materials: &mut Assets<StandardMaterial>,
+        reflective: &mut ReflectiveMaterials,
+        handle: &Handle<StandardMaterial>,
+        hook: ReflectionHook,
+    ) {
+        let Some(material) = materials.get_mut(handle) else {
+            return;
+        };
+        if !matches!(material.alpha_mode, AlphaMode::Opaque | AlphaMode::Mask(_)) {
+            return;
+        }
+        if hook == ReflectionHook::ClearCoat {
+            material.clearcoat = 1.0;
+            material.clearcoat_perceptual_roughness = CLEARCOAT_ROUGHNESS;
+        }
+        reflective.materials.insert(handle.id(), hook);
+    }
+
+    /// Vehicle metallic paint under a reflective clear coat
+    pub fn create_reflective_paint(
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+        reflective: &mut ReflectiveMaterials,
+        color: Color,
+    ) -> Handle<StandardMaterial> {
+        let handle = Self::create_vehicle_metallic(materials, color);
+        Self::opt_into_reflections(materials, reflective, &handle, ReflectionHook::ClearCoat);
+        handle
+    }
+}
+
 /// System to initialize the material factory during startup
 /// CRITICAL: This must run before any systems that create materials
 pub fn initialize_material_factory(
@@ -17,6 +17,7 @@
 //! - `entity_limits`: Focused entity count management and FIFO cleanup
 //! - `position_validator`: Spawn position validation and ground height detection
 //! - `collision_detector`: Entity collision detection for spawning
+//! - `entity_pool`: Reuses despawned dynamic entities per `ContentType` instead of respawning
 //!
 //!
 //! ### Specialized Factories
@@ -25,6 +26,17 @@
 //! - `transform_factory`: Transform and positioning utilities
 //! - `rendering_factory`: Rendering component setup
 //! - `generic_bundle`: Reusable component bundles
+//! - `clipmap_terrain`: Clipmap level meshes and heightfield colliders for terrain relief
+//! - `airfield_factory`: Runway and helipad surfaces, recorded in the `AirfieldRegistry`
+//! - `rail_factory`: Track ribbons, station platforms, train cars and crossing lights
+//! - `interior_factory`: Building street doors and the interior rooms behind them
+//! - `prefab_factory`: RON-defined prefabs spawned with position/rotation overrides
+//! - `prefab_format`: RON, JSON and TOML readers for prefab files, chosen by extension
+//! - `prefab_validation`: Line/column diagnostics and did-you-mean hints for prefab files
+//! - `prefab_cache`: Bincode bundle of resolved prefabs, rebuilt when sources change
+//! - `component_registry`: Game components prefabs can reference by type name
+//! - `prefab_hot_reload`: Patches live prefab instances when their files change
+//!   (`prefab-hot-reload` feature)
 //!
 //! ## Usage Patterns
 //!
@@ -66,15 +78,27 @@ pub mod collision_detector;
 pub mod position_validator;
 
 // Domain-specific factories with single responsibilities (following AGENT.md principles)
+pub mod airfield_factory;
 pub mod bridge_factory;
 pub mod building_factory;
+pub mod component_registry;
 pub mod effect_factory;
+pub mod entity_pool;
+pub mod interior_factory;
 pub mod npc_factory;
+pub mod prefab_cache;
+pub mod prefab_factory;
+pub mod prefab_format;
+pub mod prefab_validation;
+#[cfg(feature = "prefab-hot-reload")]
+pub mod prefab_hot_reload;
+pub mod rail_factory;
 
 pub mod vehicle_factory;
 
 // Specialized utility factories
 pub mod beach_terrain;
+pub mod clipmap_terrain;
 pub mod generic_bundle;
 pub mod material_factory;
 pub mod mesh_factory;
@@ -87,10 +111,19 @@ pub use collision_detector::CollisionDetector;
 pub use position_validator::PositionValidator;
 
 // Domain-specific factory exports with explicit imports (no wildcards)
+pub use airfield_factory::AirfieldFactory;
 pub use bridge_factory::spawn_bridge;
 pub use building_factory::{BuildingFactory, BuildingType};
+pub use component_registry::PrefabComponentRegistry;
 pub use effect_factory::{EffectFactory, ParticleEffect};
+pub use entity_pool::{EntityPool, Pooled};
+pub use interior_factory::{INTERIOR_ALTITUDE, InteriorFactory};
 pub use npc_factory::{NPCFactory, NPCType};
+pub use prefab_factory::{
+    PrefabAssetCache, PrefabDefinition, PrefabError, PrefabFactory, PrefabId, PrefabInstance,
+    PrefabOverrides, PrefabRegistry,
+};
+pub use rail_factory::RailFactory;
 pub use vehicle_factory::VehicleFactory;
 
 // Specialized factory exports (selective imports)
@@ -99,7 +132,9 @@ pub use beach_terrain::{
     create_corner_beach_slope, create_corner_beach_slope_collider,
 };
 pub use generic_bundle::{BundleError, GenericBundleFactory};
-pub use material_factory::{MaterialFactory, initialize_material_factory};
+pub use material_factory::{
+    MaterialFactory, ReflectionHook, ReflectiveMaterials, initialize_material_factory,
+};
 pub use mesh_factory::MeshFactory;
 pub use rendering_factory::{
     RenderingBundleType, RenderingFactory, StandardRenderingPattern, VehicleBodyType,
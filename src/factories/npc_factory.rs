@@ -2,7 +2,7 @@ use crate::bundles::VisibleChildBundle;
 use crate::components::world::NPCGender;
 use crate::components::{
     BodyPart, HumanAnimation, HumanMovement, NPC, NPCAppearance, NPCHead, NPCLeftArm, NPCLeftFoot,
-    NPCLeftLeg, NPCRightArm, NPCRightFoot, NPCRightLeg, NPCState, NPCTorso,
+    NPCLeftLeg, NPCRightArm, NPCRightFoot, NPCRightLeg, NPCState, NPCTorso, RagdollBone,
 };
 use crate::config::GameConfig;
 use crate::factories::generic_bundle::BundleError;
@@ -51,6 +51,7 @@ impl NPCFactory {
     }
 
     /// Spawn NPC with automatic appearance generation
+    #[allow(clippy::too_many_arguments)]
     pub fn spawn_npc(
         &self,
         commands: &mut Commands,
@@ -59,9 +60,10 @@ impl NPCFactory {
         cache: &mut ResMut<crate::resources::NPCAssetCache>,
         position: Vec3,
         npc_type: Option<NPCType>,
+        rng: &mut impl Rng,
     ) -> Result<Entity, BundleError> {
-        let npc_type = npc_type.unwrap_or_else(|| self.random_npc_type());
-        let appearance = self.generate_npc_appearance(npc_type);
+        let npc_type = npc_type.unwrap_or_else(|| self.random_npc_type(rng));
+        let appearance = self.generate_npc_appearance(npc_type, rng);
 
         // Position NPC on ground with proper height
         let final_position = Vec3::new(position.x, position.y + 0.45, position.z);
@@ -79,14 +81,14 @@ impl NPCFactory {
             ViewVisibility::default(),
             NPC {
                 target_position: position + Vec3::new(5.0, 0.0, 0.0),
-                speed: self.get_npc_speed(npc_type),
+                speed: self.get_npc_speed(npc_type, rng),
                 last_update: 0.0,
                 update_interval: 0.5,
             },
         ));
 
         entity.insert((
-            NPCState::new(npc_type.to_world_npc_type()),
+            NPCState::with_rng(npc_type.to_world_npc_type(), rng),
             npc_type,
             appearance,
             RigidBody::Dynamic,
@@ -138,6 +140,7 @@ impl NPCFactory {
         position: Vec3,
         npc_type: NPCType,
         appearance: NPCAppearance,
+        rng: &mut impl Rng,
     ) -> Result<Entity, BundleError> {
         let final_position = Vec3::new(position.x, position.y + 0.45, position.z);
 
@@ -154,14 +157,14 @@ impl NPCFactory {
             ViewVisibility::default(),
             NPC {
                 target_position: position + Vec3::new(5.0, 0.0, 0.0),
-                speed: self.get_npc_speed(npc_type),
+                speed: self.get_npc_speed(npc_type, rng),
                 last_update: 0.0,
                 update_interval: 0.5,
             },
         ));
 
         entity.insert((
-            NPCState::new(npc_type.to_world_npc_type()),
+            NPCState::with_rng(npc_type.to_world_npc_type(), rng),
             npc_type,
             appearance,
             RigidBody::Dynamic,
@@ -203,6 +206,7 @@ impl NPCFactory {
     }
 
     /// Spawn multiple NPCs in batch
+    #[allow(clippy::too_many_arguments)]
     pub fn spawn_npc_batch(
         &self,
         commands: &mut Commands,
@@ -211,11 +215,13 @@ impl NPCFactory {
         cache: &mut ResMut<crate::resources::NPCAssetCache>,
         positions: Vec<Vec3>,
         npc_type: Option<NPCType>,
+        rng: &mut impl Rng,
     ) -> Result<Vec<Entity>, BundleError> {
         let mut entities = Vec::new();
 
         for position in positions {
-            let entity = self.spawn_npc(commands, meshes, materials, cache, position, npc_type)?;
+            let entity =
+                self.spawn_npc(commands, meshes, materials, cache, position, npc_type, rng)?;
             entities.push(entity);
         }
 
@@ -223,9 +229,7 @@ impl NPCFactory {
     }
 
     /// Generate random NPC appearance
-    fn generate_npc_appearance(&self, _npc_type: NPCType) -> NPCAppearance {
-        let mut rng = rand::thread_rng();
-
+    fn generate_npc_appearance(&self, _npc_type: NPCType, rng: &mut impl Rng) -> NPCAppearance {
         let skin_tones = [
             Color::srgb(0.8, 0.6, 0.4),
             Color::srgb(0.6, 0.4, 0.3),
@@ -271,8 +275,7 @@ impl NPCFactory {
     }
 
     /// Get NPC speed based on type
-    fn get_npc_speed(&self, npc_type: NPCType) -> f32 {
-        let mut rng = rand::thread_rng();
+    fn get_npc_speed(&self, npc_type: NPCType, rng: &mut impl Rng) -> f32 {
         match npc_type {
             NPCType::Pedestrian => rng.gen_range(1.5..2.5),
             NPCType::Worker => rng.gen_range(2.0..3.0),
@@ -281,8 +284,7 @@ impl NPCFactory {
     }
 
     /// Generate random NPC type
-    fn random_npc_type(&self) -> NPCType {
-        let mut rng = rand::thread_rng();
+    fn random_npc_type(&self, rng: &mut impl Rng) -> NPCType {
         let npc_types = [NPCType::Pedestrian, NPCType::Worker, NPCType::Police];
         npc_types[rng.gen_range(0..npc_types.len())]
     }
@@ -307,6 +309,7 @@ impl NPCFactory {
             Transform::from_xyz(0.0, 0.6, 0.0),
             ChildOf(parent),
             NPCTorso,
+            RagdollBone::Torso,
             BodyPart {
                 rest_position: Vec3::new(0.0, 0.6, 0.0),
                 rest_rotation: Quat::IDENTITY,
@@ -323,6 +326,7 @@ impl NPCFactory {
             Transform::from_xyz(0.0, 1.2, 0.0),
             ChildOf(parent),
             NPCHead,
+            RagdollBone::Head,
             BodyPart {
                 rest_position: Vec3::new(0.0, 1.2, 0.0),
                 rest_rotation: Quat::IDENTITY,
@@ -341,6 +345,7 @@ impl NPCFactory {
             Transform::from_xyz(-0.4, 0.7, 0.0),
             ChildOf(parent),
             NPCLeftArm,
+            RagdollBone::LeftArm,
             BodyPart {
                 rest_position: Vec3::new(-0.4, 0.7, 0.0),
                 rest_rotation: Quat::IDENTITY,
@@ -359,6 +364,7 @@ impl NPCFactory {
             Transform::from_xyz(0.4, 0.7, 0.0),
             ChildOf(parent),
             NPCRightArm,
+            RagdollBone::RightArm,
             BodyPart {
                 rest_position: Vec3::new(0.4, 0.7, 0.0),
                 rest_rotation: Quat::IDENTITY,
@@ -377,6 +383,7 @@ impl NPCFactory {
             Transform::from_xyz(-0.15, 0.0, 0.0),
             ChildOf(parent),
             NPCLeftLeg,
+            RagdollBone::LeftLeg,
             BodyPart {
                 rest_position: Vec3::new(-0.15, 0.0, 0.0),
                 rest_rotation: Quat::IDENTITY,
@@ -395,6 +402,7 @@ impl NPCFactory {
             Transform::from_xyz(0.15, 0.0, 0.0),
             ChildOf(parent),
             NPCRightLeg,
+            RagdollBone::RightLeg,
             BodyPart {
                 rest_position: Vec3::new(0.15, 0.0, 0.0),
                 rest_rotation: Quat::IDENTITY,
@@ -416,6 +424,7 @@ impl NPCFactory {
             Transform::from_xyz(-0.15, -0.4, 0.1),
             ChildOf(parent),
             NPCLeftFoot,
+            RagdollBone::LeftFoot,
             BodyPart {
                 rest_position: Vec3::new(-0.15, -0.4, 0.1),
                 rest_rotation: Quat::IDENTITY,
@@ -432,6 +441,7 @@ impl NPCFactory {
             Transform::from_xyz(0.15, -0.4, 0.1),
             ChildOf(parent),
             NPCRightFoot,
+            RagdollBone::RightFoot,
             BodyPart {
                 rest_position: Vec3::new(0.15, -0.4, 0.1),
                 rest_rotation: Quat::IDENTITY,
@@ -1,10 +1,10 @@
 use crate::components::ContentType;
 use crate::config::GameConfig;
-use crate::constants::WorldEnvConfig;
 
 use crate::factories::generic_bundle::BundleError;
 use crate::systems::world::road_generation::is_on_road_spline;
 use crate::systems::world::road_network::RoadNetwork;
+use crate::systems::world::terrain_height::TerrainHeightService;
 use crate::systems::world::unified_world::UnifiedWorldManager;
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -60,12 +60,7 @@ impl PositionValidator {
     /// - Clear caching strategy: 10m grid resolution
     /// - Explicit behavior: Shows exactly how height is calculated
     /// - Matches island terrain curve: plateau, beach slope, ocean floor
-    pub fn get_ground_height(
-        &mut self,
-        position: Vec2,
-        env: &WorldEnvConfig,
-        world: &UnifiedWorldManager,
-    ) -> f32 {
+    pub fn get_ground_height(&mut self, position: Vec2, terrain: &TerrainHeightService) -> f32 {
         let grid_x = (position.x / 10.0).floor() as i32; // 10m grid resolution
         let grid_z = (position.y / 10.0).floor() as i32;
 
@@ -73,14 +68,7 @@ impl PositionValidator {
             return cached_height;
         }
 
-        // Simplified: terrain islands are at land_elevation, ocean at ocean_floor_depth
-        // Beach slopes handled by visual meshes only
-        let ground_height =
-            if world.is_on_terrain_island(Vec3::new(position.x, env.land_elevation, position.y)) {
-                env.land_elevation
-            } else {
-                env.ocean_floor_depth
-            };
+        let ground_height = terrain.height_at(position.x, position.y);
 
         // Cache for future use with LRU eviction to prevent performance spikes
         if self.position_cache.len() >= self.max_cache_size {
@@ -0,0 +1,169 @@
+//! Compiled prefab cache.
+//!
+//! Resolved prefab definitions are written to a single bincode bundle together
+//! with an FNV-1a hash of every source file. On startup the sources are hashed
+//! (cheap) and, if every hash matches, the bundle is loaded instead of parsing
+//! the sources. Any added, removed or edited file rebuilds the bundle on that load.
+//!
+//! Only files in the prefab directory are hashed; prefabs registered from code
+//! that a cached prefab extends are baked into the bundle at build time.
+
+use crate::factories::prefab_factory::fnv1a;
+use crate::factories::{PrefabDefinition, PrefabError, PrefabRegistry};
+use bevy::log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Bump when `PrefabDefinition` or this layout changes so old bundles are rebuilt
+pub const PREFAB_CACHE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrefabCache {
+    pub version: u32,
+    /// File name -> content hash of every source
+    pub sources: BTreeMap<String, u64>,
+    /// Resolved definitions keyed by file stem
+    pub prefabs: Vec<(String, PrefabDefinition)>,
+}
+
+/// Cache file location, following the XDG base directory spec
+pub fn prefab_cache_path() -> PathBuf {
+    let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    cache_dir.join("gta_game").join("prefabs.bin")
+}
+
+/// Content hash of every prefab file in `dir` that `registry` can read, keyed
+/// by file name so a format change also invalidates the bundle
+pub fn hash_sources(
+    registry: &PrefabRegistry,
+    dir: &Path,
+) -> Result<BTreeMap<String, u64>, PrefabError> {
+    let io_error = |path: &Path, e: std::io::Error| PrefabError::Io {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    };
+
+    let mut sources = BTreeMap::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+        let path = entry.map_err(|e| io_error(dir, e))?.path();
+        if registry.format_for(&path).is_none() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let contents = std::fs::read(&path).map_err(|e| io_error(&path, e))?;
+        sources.insert(name.to_string(), fnv1a(&contents));
+    }
+    Ok(sources)
+}
+
+impl PrefabCache {
+    /// Reads a bundle; missing or unreadable bundles are treated as absent
+    pub fn read(path: &Path) -> Option<Self> {
+        let bytes = std::fs::read(path).ok()?;
+        bincode::deserialize(&bytes).ok()
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let bytes = bincode::serialize(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, bytes).map_err(|e| e.to_string())
+    }
+
+    pub fn is_fresh(&self, sources: &BTreeMap<String, u64>) -> bool {
+        self.version == PREFAB_CACHE_VERSION && self.sources == *sources
+    }
+}
+
+impl PrefabRegistry {
+    /// `load_directory` backed by the bundle at `cache_path`: a fresh bundle is
+    /// loaded without parsing, a stale or missing one is rebuilt from `dir`.
+    /// Failing to write the bundle only costs the next startup a full parse.
+    pub fn load_directory_cached(
+        &mut self,
+        dir: &Path,
+        cache_path: &Path,
+    ) -> Result<usize, PrefabError> {
+        let sources = hash_sources(self, dir)?;
+
+        if let Some(cache) = PrefabCache::read(cache_path).filter(|c| c.is_fresh(&sources)) {
+            let loaded = cache.prefabs.len();
+            for (name, definition) in cache.prefabs {
+                self.register_named(&name, definition)?;
+            }
+            return Ok(loaded);
+        }
+
+        let loaded = self.load_directory(dir)?;
+        let prefabs = sources
+            .keys()
+            .filter_map(|file| {
+                let name = Path::new(file).file_stem()?.to_str()?;
+                let definition = self.get(self.id_of(name)?)?;
+                Some((name.to_string(), definition.clone()))
+            })
+            .collect();
+        let cache = PrefabCache {
+            version: PREFAB_CACHE_VERSION,
+            sources,
+            prefabs,
+        };
+        if let Err(e) = cache.write(cache_path) {
+            warn!(
+                "⚠️ Failed to write prefab cache '{}': {}",
+                cache_path.display(),
+                e
+            );
+        }
+        Ok(loaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factories::PrefabId;
+
+    #[test]
+    fn test_cache_is_used_until_a_source_changes() {
+        let dir = std::env::temp_dir().join(format!("prefab_cache_{}", std::process::id()));
+        let cache_path = dir.join("cache").join("prefabs.bin");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("cone.ron"), "(components: [Mass(1.0)])").unwrap();
+
+        PrefabRegistry::default()
+            .load_directory_cached(&dir, &cache_path)
+            .unwrap();
+        let cache = PrefabCache::read(&cache_path).unwrap();
+        let sources = hash_sources(&PrefabRegistry::default(), &dir).unwrap();
+        assert!(cache.is_fresh(&sources));
+
+        // A fresh bundle is trusted even if it disagrees with the source
+        let mut doctored = cache.clone();
+        doctored.prefabs[0].1.components.clear();
+        doctored.write(&cache_path).unwrap();
+        let mut registry = PrefabRegistry::default();
+        registry.load_directory_cached(&dir, &cache_path).unwrap();
+        let cone = PrefabId::from_name("cone");
+        assert!(registry.get(cone).unwrap().components.is_empty());
+
+        // Editing the source rebuilds it
+        std::fs::write(dir.join("cone.ron"), "(components: [Mass(2.0)])").unwrap();
+        let mut registry = PrefabRegistry::default();
+        registry.load_directory_cached(&dir, &cache_path).unwrap();
+        let rebuilt = PrefabCache::read(&cache_path);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(registry.get(cone).unwrap().components.len(), 1);
+        assert_ne!(rebuilt, Some(cache));
+    }
+}
@@ -0,0 +1,995 @@
+//! Data-driven prefabs loaded from `assets/prefabs` (RON, JSON or TOML; see
+//! `prefab_format`).
+//!
+//! A prefab is a list of components applied to a freshly spawned entity.
+//! Spawn-time overrides (position/rotation) are applied after the prefab's own
+//! components so the same prefab can be placed anywhere in the world.
+//!
+//! A prefab may name a `parent` prefab (by file stem). Inheritance is resolved
+//! at load time: the child starts from the parent's components, replacing any
+//! of the same kind and appending the rest.
+
+use crate::components::{VehicleState, VehicleType};
+use crate::factories::PrefabComponentRegistry;
+use crate::factories::prefab_format::{PrefabFormat, default_formats};
+use crate::factories::prefab_validation::describe_ron_error;
+use crate::systems::performance::memory::table_bytes;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Stable identifier for a registered prefab (FNV-1a hash of its name)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PrefabId(pub u64);
+
+impl PrefabId {
+    pub fn from_name(name: &str) -> Self {
+        Self(fnv1a(name.as_bytes()))
+    }
+}
+
+/// 64-bit FNV-1a; stable across runs and platforms, unlike `DefaultHasher`
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Prefab references in RON files are written as names and hashed on load
+impl<'de> Deserialize<'de> for PrefabId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|name| PrefabId::from_name(&name))
+    }
+}
+
+#[derive(Debug)]
+pub enum PrefabError {
+    NotFound(PrefabId),
+    UnknownName(String),
+    NameCollision {
+        name: String,
+        existing: String,
+    },
+    Io {
+        path: String,
+        message: String,
+    },
+    Parse {
+        path: String,
+        message: String,
+    },
+    MissingParent {
+        prefab: String,
+        parent: String,
+    },
+    InheritanceCycle(Vec<String>),
+    DuplicateSource {
+        name: String,
+        first: String,
+        second: String,
+    },
+}
+
+impl std::fmt::Display for PrefabError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefabError::NotFound(id) => write!(f, "prefab {:#018x} is not registered", id.0),
+            PrefabError::UnknownName(name) => write!(f, "no prefab named '{name}'"),
+            PrefabError::NameCollision { name, existing } => write!(
+                f,
+                "prefab name '{name}' hashes to the same id as '{existing}'"
+            ),
+            PrefabError::Io { path, message } => write!(f, "failed to read '{path}': {message}"),
+            PrefabError::Parse { path, message } => {
+                write!(f, "failed to parse '{path}': {message}")
+            }
+            PrefabError::MissingParent { prefab, parent } => {
+                write!(f, "prefab '{prefab}' extends unknown parent '{parent}'")
+            }
+            PrefabError::InheritanceCycle(chain) => {
+                write!(f, "prefab inheritance cycle: {}", chain.join(" -> "))
+            }
+            PrefabError::DuplicateSource {
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "prefab '{name}' is defined by both '{first}' and '{second}'"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PrefabError {}
+
+/// Shape shared by the `Mesh` and `Collider` components (full extents, not half)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PrefabShape {
+    Cuboid { x: f32, y: f32, z: f32 },
+    Sphere { radius: f32 },
+    Capsule { radius: f32, height: f32 },
+    Cylinder { radius: f32, height: f32 },
+}
+
+impl PrefabShape {
+    fn mesh(&self) -> Mesh {
+        match *self {
+            PrefabShape::Cuboid { x, y, z } => Cuboid::new(x, y, z).into(),
+            PrefabShape::Sphere { radius } => Sphere::new(radius).into(),
+            PrefabShape::Capsule { radius, height } => Capsule3d::new(radius, height).into(),
+            PrefabShape::Cylinder { radius, height } => Cylinder::new(radius, height).into(),
+        }
+    }
+
+    fn collider(&self) -> Collider {
+        match *self {
+            PrefabShape::Cuboid { x, y, z } => Collider::cuboid(x * 0.5, y * 0.5, z * 0.5),
+            PrefabShape::Sphere { radius } => Collider::ball(radius),
+            PrefabShape::Capsule { radius, height } => Collider::capsule_y(height * 0.5, radius),
+            PrefabShape::Cylinder { radius, height } => Collider::cylinder(height * 0.5, radius),
+        }
+    }
+}
+
+/// Colour, metallic and roughness of a prefab `Material` component
+type MaterialKey = ((f32, f32, f32, f32), f32, f32);
+
+fn prefab_material((color, metallic, roughness): MaterialKey) -> StandardMaterial {
+    StandardMaterial {
+        base_color: Color::srgba(color.0, color.1, color.2, color.3),
+        metallic,
+        perceptual_roughness: roughness,
+        ..default()
+    }
+}
+
+/// Meshes and materials built for prefab components, shared by every instance
+/// with the same shape or material. Filled for all registered prefabs while
+/// the splash screen is up, so spawning doesn't create assets mid-game.
+#[derive(Resource, Debug, Default)]
+pub struct PrefabAssetCache {
+    meshes: Vec<(PrefabShape, Handle<Mesh>)>,
+    materials: Vec<(MaterialKey, Handle<StandardMaterial>)>,
+}
+
+impl PrefabAssetCache {
+    fn mesh(&mut self, shape: PrefabShape, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        if let Some((_, handle)) = self.meshes.iter().find(|(cached, _)| *cached == shape) {
+            return handle.clone();
+        }
+        let handle = meshes.add(shape.mesh());
+        self.meshes.push((shape, handle.clone()));
+        handle
+    }
+
+    fn material(
+        &mut self,
+        key: MaterialKey,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        if let Some((_, handle)) = self.materials.iter().find(|(cached, _)| *cached == key) {
+            return handle.clone();
+        }
+        let handle = materials.add(prefab_material(key));
+        self.materials.push((key, handle.clone()));
+        handle
+    }
+
+    /// Builds every mesh and material `definition` uses
+    pub fn preload(
+        &mut self,
+        definition: &PrefabDefinition,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        for component in &definition.components {
+            match component {
+                PrefabComponent::Mesh(shape) => {
+                    self.mesh(*shape, meshes);
+                }
+                PrefabComponent::Material {
+                    color,
+                    metallic,
+                    roughness,
+                } => {
+                    self.material((*color, *metallic, *roughness), materials);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Distinct meshes and materials built so far
+    pub fn len(&self) -> usize {
+        self.meshes.len() + self.materials.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrefabBody {
+    Dynamic,
+    Fixed,
+    KinematicPositionBased,
+}
+
+fn default_scale() -> (f32, f32, f32) {
+    (1.0, 1.0, 1.0)
+}
+
+fn default_roughness() -> f32 {
+    0.5
+}
+
+/// A single component entry in a prefab file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PrefabComponent {
+    Name(String),
+    Transform {
+        #[serde(default)]
+        translation: (f32, f32, f32),
+        /// Euler angles in degrees (YXZ order)
+        #[serde(default)]
+        rotation: (f32, f32, f32),
+        #[serde(default = "default_scale")]
+        scale: (f32, f32, f32),
+    },
+    Mesh(PrefabShape),
+    Material {
+        color: (f32, f32, f32, f32),
+        #[serde(default)]
+        metallic: f32,
+        #[serde(default = "default_roughness")]
+        roughness: f32,
+    },
+    RigidBody(PrefabBody),
+    Collider(PrefabShape),
+    Mass(f32),
+    Vehicle(VehicleType),
+    /// Component registered in `PrefabComponentRegistry`: type name and RON payload
+    Custom(String, String),
+}
+
+impl PrefabComponent {
+    pub(crate) fn apply(&self, entity: &mut EntityWorldMut) {
+        match self {
+            PrefabComponent::Name(name) => {
+                entity.insert(Name::new(name.clone()));
+            }
+            PrefabComponent::Transform {
+                translation,
+                rotation,
+                scale,
+            } => {
+                entity.insert(
+                    Transform::from_xyz(translation.0, translation.1, translation.2)
+                        .with_rotation(Quat::from_euler(
+                            EulerRot::YXZ,
+                            rotation.1.to_radians(),
+                            rotation.0.to_radians(),
+                            rotation.2.to_radians(),
+                        ))
+                        .with_scale(Vec3::new(scale.0, scale.1, scale.2)),
+                );
+            }
+            PrefabComponent::Mesh(shape) => {
+                let handle = entity.world_scope(|world| {
+                    world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+                        match world.get_resource_mut::<PrefabAssetCache>() {
+                            Some(mut cache) => cache.mesh(*shape, &mut meshes),
+                            None => meshes.add(shape.mesh()),
+                        }
+                    })
+                });
+                entity.insert(Mesh3d(handle));
+            }
+            PrefabComponent::Material {
+                color,
+                metallic,
+                roughness,
+            } => {
+                let key = (*color, *metallic, *roughness);
+                let handle = entity.world_scope(|world| {
+                    world.resource_scope(|world, mut materials: Mut<Assets<StandardMaterial>>| {
+                        match world.get_resource_mut::<PrefabAssetCache>() {
+                            Some(mut cache) => cache.material(key, &mut materials),
+                            None => materials.add(prefab_material(key)),
+                        }
+                    })
+                });
+                entity.insert(MeshMaterial3d(handle));
+            }
+            PrefabComponent::RigidBody(body) => {
+                entity.insert(match body {
+                    PrefabBody::Dynamic => RigidBody::Dynamic,
+                    PrefabBody::Fixed => RigidBody::Fixed,
+                    PrefabBody::KinematicPositionBased => RigidBody::KinematicPositionBased,
+                });
+            }
+            PrefabComponent::Collider(shape) => {
+                entity.insert(shape.collider());
+            }
+            PrefabComponent::Mass(mass) => {
+                entity.insert(AdditionalMassProperties::Mass(*mass));
+            }
+            PrefabComponent::Vehicle(vehicle_type) => {
+                entity.insert(VehicleState::new(*vehicle_type));
+            }
+            PrefabComponent::Custom(name, data) => {
+                let entity_id = entity.id();
+                let result = entity.world_scope(|world| {
+                    world
+                        .try_resource_scope(|world, registry: Mut<PrefabComponentRegistry>| {
+                            registry.insert(name, data, &mut world.entity_mut(entity_id))
+                        })
+                        .unwrap_or_else(|| Err("no PrefabComponentRegistry".to_string()))
+                });
+                if let Err(e) = result {
+                    warn!("⚠️ Skipping prefab component {}: {}", name, e);
+                }
+            }
+        }
+    }
+
+    /// Whether both entries set the same component, so one replaces the other
+    pub(crate) fn same_kind(&self, other: &PrefabComponent) -> bool {
+        match (self, other) {
+            (PrefabComponent::Custom(a, _), PrefabComponent::Custom(b, _)) => a == b,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+
+    /// Removes whatever `apply` inserted for this kind of component
+    #[cfg(feature = "prefab-hot-reload")]
+    pub(crate) fn remove(&self, entity: &mut EntityWorldMut) {
+        match self {
+            PrefabComponent::Name(_) => {
+                entity.remove::<Name>();
+            }
+            PrefabComponent::Transform { .. } => {
+                entity.remove::<Transform>();
+            }
+            PrefabComponent::Mesh(_) => {
+                entity.remove::<Mesh3d>();
+            }
+            PrefabComponent::Material { .. } => {
+                entity.remove::<MeshMaterial3d<StandardMaterial>>();
+            }
+            PrefabComponent::RigidBody(_) => {
+                entity.remove::<RigidBody>();
+            }
+            PrefabComponent::Collider(_) => {
+                entity.remove::<Collider>();
+            }
+            PrefabComponent::Mass(_) => {
+                entity.remove::<AdditionalMassProperties>();
+            }
+            PrefabComponent::Vehicle(_) => {
+                entity.remove::<VehicleState>();
+            }
+            PrefabComponent::Custom(name, _) => {
+                let entity_id = entity.id();
+                entity.world_scope(|world| {
+                    world.try_resource_scope(|world, registry: Mut<PrefabComponentRegistry>| {
+                        registry.remove(name, &mut world.entity_mut(entity_id));
+                    });
+                });
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrefabDefinition {
+    /// Prefab (file stem) this one extends; cleared once inheritance is resolved
+    #[serde(default)]
+    pub parent: Option<String>,
+    pub components: Vec<PrefabComponent>,
+}
+
+impl PrefabDefinition {
+    pub fn from_ron(contents: &str) -> Result<Self, String> {
+        ron::from_str(contents).map_err(|e| describe_ron_error(contents, &e).to_string())
+    }
+
+    /// This prefab layered over `parent`: components of a kind the child also
+    /// defines are replaced in place, the child's other components are appended
+    pub fn extend(&self, parent: &PrefabDefinition) -> PrefabDefinition {
+        let mut components = parent.components.clone();
+        for component in &self.components {
+            match components
+                .iter_mut()
+                .find(|existing| existing.same_kind(component))
+            {
+                Some(existing) => *existing = component.clone(),
+                None => components.push(component.clone()),
+            }
+        }
+        PrefabDefinition {
+            parent: None,
+            components,
+        }
+    }
+}
+
+/// Flattens the inheritance chain of `name`, memoizing results in `resolved`
+fn resolve_prefab(
+    name: &str,
+    raw: &HashMap<String, PrefabDefinition>,
+    existing: &HashMap<PrefabId, PrefabDefinition>,
+    resolved: &mut HashMap<String, PrefabDefinition>,
+    chain: &mut Vec<String>,
+) -> Result<PrefabDefinition, PrefabError> {
+    if let Some(definition) = resolved.get(name) {
+        return Ok(definition.clone());
+    }
+    if chain.iter().any(|n| n == name) {
+        let mut cycle = chain.clone();
+        cycle.push(name.to_string());
+        return Err(PrefabError::InheritanceCycle(cycle));
+    }
+
+    let definition = match raw.get(name) {
+        Some(definition) => definition,
+        // Parents may also come from prefabs registered before this load
+        None => match existing.get(&PrefabId::from_name(name)) {
+            Some(definition) => return Ok(definition.clone()),
+            None => {
+                return Err(PrefabError::MissingParent {
+                    prefab: chain.last().cloned().unwrap_or_default(),
+                    parent: name.to_string(),
+                });
+            }
+        },
+    };
+
+    let flattened = match &definition.parent {
+        Some(parent) => {
+            chain.push(name.to_string());
+            let parent_definition = resolve_prefab(parent, raw, existing, resolved, chain)?;
+            chain.pop();
+            definition.extend(&parent_definition)
+        }
+        None => definition.clone(),
+    };
+    resolved.insert(name.to_string(), flattened.clone());
+    Ok(flattened)
+}
+
+/// Spawn-time overrides applied on top of the prefab's own components
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrefabOverrides {
+    pub position: Option<Vec3>,
+    pub rotation: Option<Quat>,
+}
+
+impl PrefabOverrides {
+    pub fn at(position: Vec3) -> Self {
+        Self {
+            position: Some(position),
+            rotation: None,
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+}
+
+/// Marks an entity as spawned from a prefab
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PrefabInstance(pub PrefabId);
+
+/// All prefabs available for spawning, keyed by id, with a name <-> id map
+/// for prefabs registered by name
+#[derive(Resource, Debug)]
+pub struct PrefabRegistry {
+    prefabs: HashMap<PrefabId, PrefabDefinition>,
+    names: HashMap<PrefabId, String>,
+    formats: Vec<Box<dyn PrefabFormat>>,
+}
+
+impl Default for PrefabRegistry {
+    fn default() -> Self {
+        Self {
+            prefabs: HashMap::new(),
+            names: HashMap::new(),
+            formats: default_formats(),
+        }
+    }
+}
+
+impl PrefabRegistry {
+    /// Adds a file format for `load_directory`; it takes precedence over
+    /// formats already registered for the same extension
+    pub fn add_format(&mut self, format: impl PrefabFormat + 'static) {
+        self.formats.insert(0, Box::new(format));
+    }
+
+    /// Format that reads `path`, chosen by its extension
+    pub fn format_for(&self, path: &Path) -> Option<&dyn PrefabFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.formats
+            .iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+            .map(|format| format.as_ref())
+    }
+
+    /// Approximate memory held by the definitions and names
+    pub fn memory_bytes(&self) -> usize {
+        table_bytes::<(PrefabId, PrefabDefinition)>(self.prefabs.capacity())
+            + table_bytes::<(PrefabId, String)>(self.names.capacity())
+            + self
+                .prefabs
+                .values()
+                .map(|definition| {
+                    definition.components.capacity() * std::mem::size_of::<PrefabComponent>()
+                        + definition.parent.as_ref().map_or(0, String::capacity)
+                })
+                .sum::<usize>()
+            + self.names.values().map(String::capacity).sum::<usize>()
+    }
+
+    pub fn register(&mut self, id: PrefabId, definition: PrefabDefinition) {
+        self.prefabs.insert(id, definition);
+    }
+
+    /// Registers `definition` under `name`. Re-registering a name replaces its
+    /// definition; a different name hashing to an existing id is rejected.
+    pub fn register_named(
+        &mut self,
+        name: &str,
+        definition: PrefabDefinition,
+    ) -> Result<PrefabId, PrefabError> {
+        let id = PrefabId::from_name(name);
+        if let Some(existing) = self.names.get(&id)
+            && existing != name
+        {
+            return Err(PrefabError::NameCollision {
+                name: name.to_string(),
+                existing: existing.clone(),
+            });
+        }
+        self.names.insert(id, name.to_string());
+        self.register(id, definition);
+        Ok(id)
+    }
+
+    pub fn get(&self, id: PrefabId) -> Option<&PrefabDefinition> {
+        self.prefabs.get(&id)
+    }
+
+    /// Id of a prefab registered by name
+    pub fn id_of(&self, name: &str) -> Option<PrefabId> {
+        let id = PrefabId::from_name(name);
+        (self.names.get(&id).map(String::as_str) == Some(name)).then_some(id)
+    }
+
+    /// Name a prefab was registered under, if any
+    pub fn name_of(&self, id: PrefabId) -> Option<&str> {
+        self.names.get(&id).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (PrefabId, &PrefabDefinition)> {
+        self.prefabs
+            .iter()
+            .map(|(id, definition)| (*id, definition))
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefabs.is_empty()
+    }
+
+    /// Loads every prefab file in `dir` whose extension has a registered
+    /// `PrefabFormat`, registering each under its file stem.
+    /// Parents are resolved once all files are read, so declaration order doesn't matter.
+    /// Returns the number of prefabs loaded; any failing file or broken chain aborts the load.
+    pub fn load_directory(&mut self, dir: &Path) -> Result<usize, PrefabError> {
+        let io_error = |path: &Path, e: std::io::Error| PrefabError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        };
+
+        let mut raw = HashMap::new();
+        let mut sources: HashMap<String, PathBuf> = HashMap::new();
+        for entry in std::fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+            let path = entry.map_err(|e| io_error(dir, e))?.path();
+            let Some(format) = self.format_for(&path) else {
+                continue;
+            };
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if let Some(first) = sources.get(stem) {
+                return Err(PrefabError::DuplicateSource {
+                    name: stem.to_string(),
+                    first: first.display().to_string(),
+                    second: path.display().to_string(),
+                });
+            }
+
+            let contents = std::fs::read_to_string(&path).map_err(|e| io_error(&path, e))?;
+            let definition = format
+                .parse(&contents)
+                .map_err(|message| PrefabError::Parse {
+                    path: path.display().to_string(),
+                    message,
+                })?;
+            raw.insert(stem.to_string(), definition);
+            sources.insert(stem.to_string(), path);
+        }
+
+        let mut resolved = HashMap::new();
+        for name in raw.keys() {
+            resolve_prefab(name, &raw, &self.prefabs, &mut resolved, &mut Vec::new())?;
+        }
+
+        let loaded = resolved.len();
+        for (name, definition) in resolved {
+            self.register_named(&name, definition)?;
+        }
+        Ok(loaded)
+    }
+
+    /// Re-reads `dir` like `load_directory` and returns the ids whose resolved
+    /// definition changed, including children of an edited parent.
+    pub fn reload_directory(&mut self, dir: &Path) -> Result<Vec<PrefabId>, PrefabError> {
+        let before = self.prefabs.clone();
+        self.load_directory(dir)?;
+        let mut changed: Vec<PrefabId> = self
+            .prefabs
+            .iter()
+            .filter(|(id, definition)| before.get(id) != Some(*definition))
+            .map(|(id, _)| *id)
+            .collect();
+        changed.sort();
+        Ok(changed)
+    }
+}
+
+/// Spawns entities from registered prefabs
+pub struct PrefabFactory;
+
+impl PrefabFactory {
+    /// Spawn a prefab with the given overrides. Components are applied when
+    /// commands are flushed, so the entity is fully populated by the next system.
+    pub fn spawn(
+        commands: &mut Commands,
+        registry: &PrefabRegistry,
+        id: PrefabId,
+        overrides: PrefabOverrides,
+    ) -> Result<Entity, PrefabError> {
+        let definition = registry.get(id).ok_or(PrefabError::NotFound(id))?.clone();
+        let entity = commands
+            .spawn((PrefabInstance(id), Transform::default()))
+            .id();
+
+        commands.queue(move |world: &mut World| {
+            let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+                return;
+            };
+            for component in &definition.components {
+                component.apply(&mut entity_mut);
+            }
+            if let Some(mut transform) = entity_mut.get_mut::<Transform>() {
+                if let Some(position) = overrides.position {
+                    transform.translation = position;
+                }
+                if let Some(rotation) = overrides.rotation {
+                    transform.rotation = rotation;
+                }
+            }
+        });
+
+        Ok(entity)
+    }
+
+    /// Spawn a prefab registered under `name`
+    pub fn spawn_by_name(
+        commands: &mut Commands,
+        registry: &PrefabRegistry,
+        name: &str,
+        overrides: PrefabOverrides,
+    ) -> Result<Entity, PrefabError> {
+        let id = registry
+            .id_of(name)
+            .ok_or_else(|| PrefabError::UnknownName(name.to_string()))?;
+        Self::spawn(commands, registry, id, overrides)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefab_id_is_stable() {
+        assert_eq!(
+            PrefabId::from_name("traffic_cone"),
+            PrefabId::from_name("traffic_cone")
+        );
+        assert_ne!(
+            PrefabId::from_name("traffic_cone"),
+            PrefabId::from_name("barrier")
+        );
+    }
+
+    #[test]
+    fn test_parse_prefab_components() {
+        let definition = PrefabDefinition::from_ron(
+            r#"(
+                components: [
+                    Name("Cone"),
+                    Transform(translation: (1.0, 2.0, 3.0)),
+                    Mesh(Cuboid(x: 0.4, y: 0.7, z: 0.4)),
+                    RigidBody(Dynamic),
+                ],
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(definition.components.len(), 4);
+        assert_eq!(
+            definition.components[1],
+            PrefabComponent::Transform {
+                translation: (1.0, 2.0, 3.0),
+                rotation: (0.0, 0.0, 0.0),
+                scale: (1.0, 1.0, 1.0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_spawn_applies_components_and_overrides() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+
+        let id = PrefabId::from_name("cone");
+        let mut registry = PrefabRegistry::default();
+        registry.register(
+            id,
+            PrefabDefinition {
+                parent: None,
+                components: vec![
+                    PrefabComponent::Name("Cone".to_string()),
+                    PrefabComponent::Transform {
+                        translation: (1.0, 0.0, 0.0),
+                        rotation: (0.0, 0.0, 0.0),
+                        scale: (1.0, 1.0, 1.0),
+                    },
+                    PrefabComponent::Collider(PrefabShape::Sphere { radius: 0.5 }),
+                ],
+            },
+        );
+
+        let world = app.world_mut();
+        let mut commands = world.commands();
+        let entity = PrefabFactory::spawn(
+            &mut commands,
+            &registry,
+            id,
+            PrefabOverrides::at(Vec3::new(5.0, 1.0, -2.0)),
+        )
+        .unwrap();
+        world.flush();
+
+        let entity_ref = world.entity(entity);
+        assert_eq!(entity_ref.get::<Name>().unwrap().as_str(), "Cone");
+        assert!(entity_ref.contains::<Collider>());
+        assert_eq!(
+            entity_ref.get::<Transform>().unwrap().translation,
+            Vec3::new(5.0, 1.0, -2.0)
+        );
+    }
+
+    #[test]
+    fn test_spawns_share_preloaded_meshes_and_materials() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>()
+            .init_resource::<PrefabAssetCache>();
+
+        let id = PrefabId::from_name("cone");
+        let mut registry = PrefabRegistry::default();
+        registry.register(
+            id,
+            PrefabDefinition::from_ron(
+                "(components: [Mesh(Cylinder(radius: 0.2, height: 0.7)), Material(color: (1.0, 0.35, 0.0, 1.0))])",
+            )
+            .unwrap(),
+        );
+        let world = app.world_mut();
+        world.resource_scope(|world, mut cache: Mut<PrefabAssetCache>| {
+            world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+                let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+                cache.preload(registry.get(id).unwrap(), &mut meshes, &mut materials);
+            });
+        });
+        assert_eq!(world.resource::<PrefabAssetCache>().len(), 2);
+
+        let mut commands = world.commands();
+        let first = PrefabFactory::spawn(&mut commands, &registry, id, default()).unwrap();
+        let second = PrefabFactory::spawn(&mut commands, &registry, id, default()).unwrap();
+        world.flush();
+
+        assert_eq!(
+            world.get::<Mesh3d>(first).unwrap().0,
+            world.get::<Mesh3d>(second).unwrap().0
+        );
+        assert_eq!(
+            world
+                .get::<MeshMaterial3d<StandardMaterial>>(first)
+                .unwrap()
+                .0,
+            world
+                .get::<MeshMaterial3d<StandardMaterial>>(second)
+                .unwrap()
+                .0
+        );
+        assert_eq!(world.resource::<Assets<Mesh>>().len(), 1);
+        assert_eq!(world.resource::<PrefabAssetCache>().len(), 2);
+    }
+
+    fn parse_all(files: &[(&str, &str)]) -> HashMap<String, PrefabDefinition> {
+        files
+            .iter()
+            .map(|(name, ron)| (name.to_string(), PrefabDefinition::from_ron(ron).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_child_overrides_and_extends_parent() {
+        let raw = parse_all(&[
+            (
+                "base_car",
+                "(components: [Name(\"Car\"), Mass(1200.0), RigidBody(Dynamic)])",
+            ),
+            (
+                "sports_car",
+                "(parent: Some(\"base_car\"), components: [Mass(1000.0), Vehicle(SuperCar)])",
+            ),
+        ]);
+
+        let mut resolved = HashMap::new();
+        let sports = resolve_prefab(
+            "sports_car",
+            &raw,
+            &HashMap::new(),
+            &mut resolved,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            sports.components,
+            vec![
+                PrefabComponent::Name("Car".to_string()),
+                PrefabComponent::Mass(1000.0),
+                PrefabComponent::RigidBody(PrefabBody::Dynamic),
+                PrefabComponent::Vehicle(VehicleType::SuperCar),
+            ]
+        );
+        assert_eq!(sports.parent, None);
+    }
+
+    #[test]
+    fn test_inheritance_cycle_is_reported() {
+        let raw = parse_all(&[
+            ("a", "(parent: Some(\"b\"), components: [])"),
+            ("b", "(parent: Some(\"a\"), components: [])"),
+        ]);
+
+        let err = resolve_prefab(
+            "a",
+            &raw,
+            &HashMap::new(),
+            &mut HashMap::new(),
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "prefab inheritance cycle: a -> b -> a");
+    }
+
+    #[test]
+    fn test_reload_reports_edited_prefab_and_its_children() {
+        let dir = std::env::temp_dir().join(format!("prefab_reload_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("base.ron"), "(components: [Mass(1.0)])").unwrap();
+        std::fs::write(
+            dir.join("child.ron"),
+            "(parent: Some(\"base\"), components: [Name(\"Child\")])",
+        )
+        .unwrap();
+        std::fs::write(dir.join("other.ron"), "(components: [Name(\"Other\")])").unwrap();
+
+        let mut registry = PrefabRegistry::default();
+        registry.load_directory(&dir).unwrap();
+        std::fs::write(dir.join("base.ron"), "(components: [Mass(2.0)])").unwrap();
+        let changed = registry.reload_directory(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let mut expected = vec![PrefabId::from_name("base"), PrefabId::from_name("child")];
+        expected.sort();
+        assert_eq!(changed.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_custom_components_replace_only_same_type() {
+        let parent =
+            PrefabDefinition::from_ron(r#"(components: [Custom("A", "(1)"), Custom("B", "(2)")])"#)
+                .unwrap();
+        let child = PrefabDefinition::from_ron(r#"(components: [Custom("B", "(3)")])"#).unwrap();
+
+        assert_eq!(
+            child.extend(&parent).components,
+            vec![
+                PrefabComponent::Custom("A".to_string(), "(1)".to_string()),
+                PrefabComponent::Custom("B".to_string(), "(3)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_stem_in_two_formats_is_rejected() {
+        let dir = std::env::temp_dir().join(format!("prefab_formats_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("cone.ron"), "(components: [Mass(1.0)])").unwrap();
+        std::fs::write(dir.join("cone.json"), r#"{"components": [{"Mass": 2.0}]}"#).unwrap();
+        let result = PrefabRegistry::default().load_directory(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(result, Err(PrefabError::DuplicateSource { name, .. }) if name == "cone"));
+    }
+
+    #[test]
+    fn test_shipped_prefabs_load() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/prefabs");
+        let mut registry = PrefabRegistry::default();
+        registry.load_directory(&dir).unwrap();
+
+        assert!(registry.id_of("bollard").is_some());
+        assert!(registry.id_of("sports_car").is_some());
+    }
+
+    #[test]
+    fn test_named_registration_and_collisions() {
+        let mut registry = PrefabRegistry::default();
+        let id = registry
+            .register_named("police_car", PrefabDefinition::default())
+            .unwrap();
+
+        assert_eq!(registry.id_of("police_car"), Some(id));
+        assert_eq!(registry.name_of(id), Some("police_car"));
+        assert_eq!(registry.id_of("taxi"), None);
+        assert!(
+            registry
+                .register_named("police_car", PrefabDefinition::default())
+                .is_ok()
+        );
+
+        // Simulate a hash collision: another name already owns this id
+        registry.names.insert(id, "other".to_string());
+        assert!(matches!(
+            registry.register_named("police_car", PrefabDefinition::default()),
+            Err(PrefabError::NameCollision { .. })
+        ));
+
+        let parsed: PrefabId = ron::from_str("\"police_car\"").unwrap();
+        assert_eq!(parsed, id);
+    }
+}
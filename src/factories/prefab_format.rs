@@ -0,0 +1,97 @@
+//! File formats prefabs can be authored in.
+//!
+//! `PrefabRegistry::load_directory` picks a format by file extension. RON,
+//! JSON and TOML are registered by default; all three map onto the same
+//! `PrefabDefinition` through serde, so enum components are written in each
+//! format's externally tagged form, e.g. `{ "Mass": 80.0 }` in JSON or
+//! `{ RigidBody = "Fixed" }` in TOML.
+
+use crate::factories::PrefabDefinition;
+
+pub trait PrefabFormat: Send + Sync + std::fmt::Debug {
+    /// Lower-case file extensions handled by this format, without the dot
+    fn extensions(&self) -> &[&str];
+
+    fn parse(&self, contents: &str) -> Result<PrefabDefinition, String>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RonFormat;
+
+impl PrefabFormat for RonFormat {
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+
+    fn parse(&self, contents: &str) -> Result<PrefabDefinition, String> {
+        PrefabDefinition::from_ron(contents)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormat;
+
+impl PrefabFormat for JsonFormat {
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+
+    fn parse(&self, contents: &str) -> Result<PrefabDefinition, String> {
+        serde_json::from_str(contents).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TomlFormat;
+
+impl PrefabFormat for TomlFormat {
+    fn extensions(&self) -> &[&str] {
+        &["toml"]
+    }
+
+    fn parse(&self, contents: &str) -> Result<PrefabDefinition, String> {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    }
+}
+
+/// RON, JSON and TOML
+pub fn default_formats() -> Vec<Box<dyn PrefabFormat>> {
+    vec![
+        Box::new(RonFormat),
+        Box::new(JsonFormat),
+        Box::new(TomlFormat),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factories::prefab_factory::{PrefabBody, PrefabComponent};
+
+    #[test]
+    fn test_formats_parse_the_same_prefab() {
+        let ron = RonFormat
+            .parse(r#"(parent: Some("base"), components: [Name("Cone"), RigidBody(Fixed), Mass(2.0)])"#)
+            .unwrap();
+        let json = JsonFormat
+            .parse(
+                r#"{"parent": "base", "components": [{"Name": "Cone"}, {"RigidBody": "Fixed"}, {"Mass": 2.0}]}"#,
+            )
+            .unwrap();
+        let toml = TomlFormat
+            .parse(
+                r#"
+                parent = "base"
+                components = [{ Name = "Cone" }, { RigidBody = "Fixed" }, { Mass = 2.0 }]
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(ron, json);
+        assert_eq!(ron, toml);
+        assert_eq!(
+            ron.components[1],
+            PrefabComponent::RigidBody(PrefabBody::Fixed)
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Live prefab editing (`prefab-hot-reload` feature).
+//!
+//! A file watcher on `assets/prefabs` feeds `HotReloadReceiver`. Each frame the
+//! pending events are drained; if any prefab file changed the directory is
+//! re-parsed and every spawned `PrefabInstance` whose resolved definition
+//! changed is patched in place. Transforms are left alone so patched entities
+//! stay where the game put them.
+
+use crate::factories::prefab_factory::PrefabComponent;
+use crate::factories::{PrefabDefinition, PrefabInstance, PrefabRegistry};
+use bevy::prelude::*;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, channel};
+
+/// File watcher on the prefab directory and the events it has produced
+#[derive(Resource)]
+pub struct HotReloadReceiver {
+    dir: PathBuf,
+    events: Mutex<Receiver<notify::Result<notify::Event>>>,
+    // Dropping the watcher stops the events
+    _watcher: RecommendedWatcher,
+}
+
+impl HotReloadReceiver {
+    pub fn watch(dir: &Path) -> notify::Result<Self> {
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            events: Mutex::new(events),
+            _watcher: watcher,
+        })
+    }
+
+    /// Drains pending events, returning whether any prefab file changed
+    fn prefab_files_changed(&self, registry: &PrefabRegistry) -> bool {
+        let Ok(events) = self.events.lock() else {
+            return false;
+        };
+        let mut changed = false;
+        for event in events.try_iter() {
+            match event {
+                Ok(event) => {
+                    changed |= !matches!(event.kind, EventKind::Access(_))
+                        && event
+                            .paths
+                            .iter()
+                            .any(|path| registry.format_for(path).is_some());
+                }
+                Err(e) => warn!("⚠️ Prefab watcher error: {}", e),
+            }
+        }
+        changed
+    }
+}
+
+pub fn start_prefab_watcher(mut commands: Commands) {
+    let dir = PathBuf::from(crate::util::asset_path::get_assets_base_path()).join("prefabs");
+    match HotReloadReceiver::watch(&dir) {
+        Ok(receiver) => {
+            info!("🔥 Watching {} for prefab changes", dir.display());
+            commands.insert_resource(receiver);
+        }
+        Err(e) => warn!("⚠️ Prefab hot reload disabled: {}", e),
+    }
+}
+
+/// Re-parses the prefab directory after a change and patches live instances
+pub fn reload_changed_prefabs(
+    mut commands: Commands,
+    receiver: Option<Res<HotReloadReceiver>>,
+    mut registry: ResMut<PrefabRegistry>,
+    instances: Query<(Entity, &PrefabInstance)>,
+) {
+    let Some(receiver) = receiver else {
+        return;
+    };
+    if !receiver.prefab_files_changed(&registry) {
+        return;
+    }
+
+    let previous: HashMap<_, _> = instances
+        .iter()
+        .filter_map(|(_, instance)| Some((instance.0, registry.get(instance.0)?.clone())))
+        .collect();
+    let changed = match registry.reload_directory(&receiver.dir) {
+        Ok(changed) => changed,
+        Err(e) => {
+            error!("Prefab reload failed, keeping previous definitions: {}", e);
+            return;
+        }
+    };
+
+    let mut patched = 0;
+    for (entity, instance) in &instances {
+        if !changed.contains(&instance.0) {
+            continue;
+        }
+        let Some(new) = registry.get(instance.0).cloned() else {
+            continue;
+        };
+        let old = previous.get(&instance.0).cloned().unwrap_or_default();
+        commands.queue(move |world: &mut World| {
+            if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                patch_prefab_instance(&mut entity_mut, &old, &new);
+            }
+        });
+        patched += 1;
+    }
+
+    info!(
+        "🔥 Reloaded {} prefab(s), patched {} live entities",
+        changed.len(),
+        patched
+    );
+}
+
+/// Brings an entity spawned from `old` in line with `new`. Only components
+/// that differ are re-applied, so untouched state (vehicle damage, meshes)
+/// survives; kinds dropped from the prefab are removed.
+pub fn patch_prefab_instance(
+    entity: &mut EntityWorldMut,
+    old: &PrefabDefinition,
+    new: &PrefabDefinition,
+) {
+    let is_transform = |c: &_| matches!(c, PrefabComponent::Transform { .. });
+
+    for component in new.components.iter().filter(|c| !is_transform(c)) {
+        if !old.components.contains(component) {
+            component.apply(entity);
+        }
+    }
+    for component in old.components.iter().filter(|c| !is_transform(c)) {
+        if !new.components.iter().any(|c| c.same_kind(component)) {
+            component.remove(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_rapier3d::prelude::{AdditionalMassProperties, RigidBody};
+
+    #[test]
+    fn test_patch_updates_changed_and_removes_dropped_components() {
+        let mut world = World::new();
+        let old = PrefabDefinition::from_ron(
+            "(components: [Name(\"Cone\"), Mass(5.0), RigidBody(Dynamic), Transform(translation: (1.0, 0.0, 0.0))])",
+        )
+        .unwrap();
+        let new = PrefabDefinition::from_ron(
+            "(components: [Name(\"Cone\"), Mass(8.0), Transform(translation: (9.0, 0.0, 0.0))])",
+        )
+        .unwrap();
+
+        let entity = world
+            .spawn((
+                Name::new("Cone"),
+                AdditionalMassProperties::Mass(5.0),
+                RigidBody::Dynamic,
+                Transform::from_xyz(3.0, 0.0, 0.0),
+            ))
+            .id();
+        patch_prefab_instance(&mut world.entity_mut(entity), &old, &new);
+
+        let entity = world.entity(entity);
+        assert!(matches!(
+            entity.get::<AdditionalMassProperties>(),
+            Some(AdditionalMassProperties::Mass(mass)) if *mass == 8.0
+        ));
+        assert!(!entity.contains::<RigidBody>());
+        assert_eq!(
+            entity.get::<Transform>().unwrap().translation,
+            Vec3::new(3.0, 0.0, 0.0)
+        );
+    }
+}
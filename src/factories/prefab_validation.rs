@@ -0,0 +1,167 @@
+//! Readable diagnostics for broken prefab files.
+//!
+//! RON parse failures are turned into a message with the offending line, a
+//! caret under the reported column and, for misspelled components, variants
+//! or fields, the closest valid name. `Custom` components are checked
+//! against the `PrefabComponentRegistry` once it is available.
+
+use crate::factories::prefab_factory::PrefabComponent;
+use crate::factories::{PrefabComponentRegistry, PrefabDefinition};
+use crate::util::suggest::closest_match;
+use ron::error::{Error, SpannedError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefabDiagnostic {
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+    /// Source line the error points at, if it could be found
+    pub source_line: Option<String>,
+}
+
+impl std::fmt::Display for PrefabDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.col, self.message)?;
+        if let Some(source_line) = &self.source_line {
+            let gutter = self.line.to_string().len();
+            write!(
+                f,
+                "\n{:gutter$} |\n{} | {}\n{:gutter$} | {:>col$}",
+                "",
+                self.line,
+                source_line,
+                "",
+                "^",
+                col = self.col.max(1),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Explains a RON error in prefab terms, quoting the line it occurred on
+pub fn describe_ron_error(contents: &str, error: &SpannedError) -> PrefabDiagnostic {
+    let message = match &error.code {
+        Error::NoSuchEnumVariant {
+            expected,
+            found,
+            outer,
+        } => {
+            let what = match outer.as_deref() {
+                Some("PrefabComponent") => "component".to_string(),
+                Some(outer) => format!("`{outer}` variant"),
+                None => "variant".to_string(),
+            };
+            format!(
+                "unknown {what} `{found}`{}",
+                did_you_mean(found, expected.iter().copied())
+            )
+        }
+        Error::NoSuchStructField {
+            expected,
+            found,
+            outer,
+        } => format!(
+            "unknown field `{found}`{}{}",
+            outer_suffix(outer),
+            did_you_mean(found, expected.iter().copied())
+        ),
+        Error::MissingStructField { field, outer } => {
+            format!("missing required field `{field}`{}", outer_suffix(outer))
+        }
+        code => code.to_string(),
+    };
+
+    let line = error.position.line;
+    PrefabDiagnostic {
+        line,
+        col: error.position.col,
+        message,
+        source_line: line
+            .checked_sub(1)
+            .and_then(|index| contents.lines().nth(index))
+            .map(str::to_string),
+    }
+}
+
+/// Problems with the `Custom` components of a parsed prefab: unregistered
+/// names (with suggestions) and payloads that don't deserialize
+pub fn check_custom_components(
+    definition: &PrefabDefinition,
+    registry: &PrefabComponentRegistry,
+) -> Vec<String> {
+    definition
+        .components
+        .iter()
+        .filter_map(|component| match component {
+            PrefabComponent::Custom(name, _) if !registry.contains(name) => Some(format!(
+                "unknown custom component `{name}`{}",
+                did_you_mean(name, registry.names())
+            )),
+            PrefabComponent::Custom(name, data) => registry
+                .check(name, data)
+                .err()
+                .map(|e| format!("invalid `{name}` data: {e}")),
+            _ => None,
+        })
+        .collect()
+}
+
+fn outer_suffix(outer: &Option<String>) -> String {
+    outer
+        .as_ref()
+        .map(|outer| format!(" in `{outer}`"))
+        .unwrap_or_default()
+}
+
+fn did_you_mean<'a>(found: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    match closest_match(found, candidates) {
+        Some(candidate) => format!("; did you mean `{candidate}`?"),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::VehicleCustomization;
+    use crate::register_components;
+
+    #[test]
+    fn test_unknown_component_suggests_closest_name() {
+        let contents = "(\n    components: [\n        Mas(5.0),\n    ],\n)";
+        let error = PrefabDefinition::from_ron(contents).unwrap_err();
+
+        assert!(error.starts_with("3:"), "{error}");
+        assert!(
+            error.contains("unknown component `Mas`; did you mean `Mass`?"),
+            "{error}"
+        );
+        assert!(error.contains("3 |         Mas(5.0),"), "{error}");
+    }
+
+    #[test]
+    fn test_missing_field_is_named() {
+        let error =
+            PrefabDefinition::from_ron("(components: [Mesh(Cuboid(x: 1.0, y: 1.0))])").unwrap_err();
+        assert!(error.contains("missing required field `z`"), "{error}");
+    }
+
+    #[test]
+    fn test_custom_components_are_checked_against_registry() {
+        let mut registry = PrefabComponentRegistry::default();
+        register_components!(registry, VehicleCustomization);
+        let definition = PrefabDefinition::from_ron(
+            r#"(components: [
+                Custom("VehicleCustomisation", "()"),
+                Custom("VehicleCustomization", "(wheels: Square)"),
+            ])"#,
+        )
+        .unwrap();
+
+        let problems = check_custom_components(&definition, &registry);
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].ends_with("did you mean `VehicleCustomization`?"));
+        assert!(problems[1].starts_with("invalid `VehicleCustomization` data"));
+    }
+}
@@ -0,0 +1,388 @@
+use crate::components::{
+    RailCrossingLight, RailLine, RailStation, RailTrack, Train, TrainCar, TrainState,
+};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+
+/// Ballast bed width and height above the ground
+const BALLAST_WIDTH: f32 = 4.2;
+const BALLAST_LIFT: f32 = 0.06;
+/// Rails sit this far either side of the centerline
+const RAIL_OFFSET: f32 = 0.75;
+const RAIL_WIDTH: f32 = 0.12;
+const RAIL_LIFT: f32 = 0.18;
+
+/// Train car body size across and up (m); the length comes from the network
+const CAR_WIDTH: f32 = 3.0;
+pub const CAR_HEIGHT: f32 = 3.6;
+
+/// Platforms run alongside the track this far from the centerline
+const PLATFORM_OFFSET: f32 = 4.0;
+const PLATFORM_WIDTH: f32 = 4.0;
+const PLATFORM_HEIGHT: f32 = 0.9;
+
+/// Crossing light posts stand this far to the side of the track
+const CROSSING_POST_OFFSET: f32 = 3.0;
+const CROSSING_POST_HEIGHT: f32 = 3.5;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StationDefinition {
+    pub name: String,
+    /// Snapped to the nearest point on the track
+    pub position: Vec3,
+}
+
+fn default_trains() -> usize {
+    1
+}
+
+fn default_cars() -> usize {
+    3
+}
+
+fn default_cruise_speed() -> f32 {
+    25.0
+}
+
+fn default_acceleration() -> f32 {
+    1.2
+}
+
+fn default_dwell_time() -> f32 {
+    12.0
+}
+
+fn default_express_multiplier() -> f32 {
+    3.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RailLineDefinition {
+    pub name: String,
+    /// Looped lines run round in one direction, open lines shuttle
+    #[serde(default)]
+    pub looped: bool,
+    /// Control points the track curves through
+    pub points: Vec<Vec3>,
+    #[serde(default)]
+    pub stations: Vec<StationDefinition>,
+    #[serde(default = "default_trains")]
+    pub trains: usize,
+    #[serde(default = "default_cars")]
+    pub cars: usize,
+    #[serde(default = "default_cruise_speed")]
+    pub cruise_speed: f32,
+    #[serde(default = "default_acceleration")]
+    pub acceleration: f32,
+    #[serde(default = "default_dwell_time")]
+    pub dwell_time: f32,
+    #[serde(default = "default_express_multiplier")]
+    pub express_multiplier: f32,
+}
+
+/// Rail layout loaded from `assets/config/rail.ron`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RailList {
+    pub lines: Vec<RailLineDefinition>,
+    pub car_length: f32,
+    pub car_gap: f32,
+    pub hit_damage: f32,
+    pub min_hit_speed: f32,
+    pub boarding_radius: f32,
+}
+
+impl Default for RailList {
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            car_length: 18.0,
+            car_gap: 1.0,
+            hit_damage: 4.0,
+            min_hit_speed: 3.0,
+            boarding_radius: 8.0,
+        }
+    }
+}
+
+impl RailLineDefinition {
+    /// Builds the track and snaps the stations onto it, sorted along the line
+    pub fn build(&self) -> RailLine {
+        let track = RailTrack::new(&self.points, self.looped);
+        let mut stations: Vec<_> = self
+            .stations
+            .iter()
+            .map(|station| {
+                let (distance, _) = track.nearest(station.position);
+                let (position, direction) = track.sample(distance);
+                let side = Vec3::new(-direction.z, 0.0, direction.x).normalize_or(Vec3::X);
+                RailStation {
+                    name: station.name.clone(),
+                    distance,
+                    platform: position + side * PLATFORM_OFFSET + Vec3::Y * (PLATFORM_HEIGHT + 1.0),
+                }
+            })
+            .collect();
+        stations.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        RailLine {
+            name: self.name.clone(),
+            track,
+            stations,
+            crossings: Vec::new(),
+            cruise_speed: self.cruise_speed.max(1.0),
+            acceleration: self.acceleration.max(0.1),
+            dwell_time: self.dwell_time.max(0.0),
+            express_multiplier: self.express_multiplier.max(1.0),
+        }
+    }
+}
+
+/// Flat strip `width` wide following `track`, shifted sideways by `offset`
+/// and raised by `lift`, in coordinates relative to `center`
+fn track_ribbon_mesh(track: &RailTrack, offset: f32, width: f32, lift: f32, center: Vec3) -> Mesh {
+    let points = track.points();
+    let mut vertices = Vec::with_capacity(points.len() * 2);
+    let mut normals = Vec::with_capacity(points.len() * 2);
+    let mut uvs = Vec::with_capacity(points.len() * 2);
+    let mut indices = Vec::new();
+
+    let mut along = 0.0;
+    for (index, point) in points.iter().enumerate() {
+        let ahead = points[(index + 1).min(points.len() - 1)];
+        let behind = points[index.saturating_sub(1)];
+        let tangent = (ahead - behind).normalize_or(Vec3::Z);
+        let right = Vec3::new(tangent.z, 0.0, -tangent.x).normalize_or(Vec3::X);
+        if index > 0 {
+            along += behind.distance(*point);
+        }
+
+        let middle = *point + right * offset + Vec3::Y * lift - center;
+        vertices.push((middle + right * width * 0.5).to_array());
+        vertices.push((middle - right * width * 0.5).to_array());
+        normals.extend([[0.0, 1.0, 0.0]; 2]);
+        uvs.push([0.0, along / width.max(0.01)]);
+        uvs.push([1.0, along / width.max(0.01)]);
+
+        if index + 1 < points.len() {
+            let base = (index * 2) as u32;
+            indices.extend([base, base + 2, base + 1, base + 1, base + 2, base + 3]);
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+    mesh
+}
+
+/// Spawns rail lines: track, station platforms, trains and crossing lights.
+/// Kept as a resource so crossings can be added as roads stream in.
+#[derive(Resource)]
+pub struct RailFactory {
+    ballast: Handle<StandardMaterial>,
+    rail: Handle<StandardMaterial>,
+    platform: Handle<StandardMaterial>,
+    locomotive: Handle<StandardMaterial>,
+    carriage: Handle<StandardMaterial>,
+    post: Handle<StandardMaterial>,
+    pub light_on: Handle<StandardMaterial>,
+    pub light_off: Handle<StandardMaterial>,
+}
+
+impl RailFactory {
+    pub fn new(materials: &mut Assets<StandardMaterial>) -> Self {
+        let solid = |materials: &mut Assets<StandardMaterial>, r, g, b, roughness| {
+            materials.add(StandardMaterial {
+                base_color: Color::srgb(r, g, b),
+                perceptual_roughness: roughness,
+                ..default()
+            })
+        };
+        Self {
+            ballast: solid(materials, 0.32, 0.3, 0.28, 0.95),
+            rail: materials.add(StandardMaterial {
+                base_color: Color::srgb(0.55, 0.55, 0.58),
+                metallic: 0.8,
+                perceptual_roughness: 0.35,
+                ..default()
+            }),
+            platform: solid(materials, 0.62, 0.6, 0.56, 0.85),
+            locomotive: solid(materials, 0.75, 0.12, 0.1, 0.5),
+            carriage: solid(materials, 0.82, 0.82, 0.8, 0.5),
+            post: solid(materials, 0.15, 0.15, 0.15, 0.7),
+            light_on: materials.add(StandardMaterial {
+                base_color: Color::srgb(1.0, 0.1, 0.05),
+                emissive: LinearRgba::rgb(12.0, 0.6, 0.2),
+                ..default()
+            }),
+            light_off: solid(materials, 0.25, 0.04, 0.03, 0.4),
+        }
+    }
+
+    /// Ballast bed and the two rails of `line`
+    pub fn spawn_track(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        line: &RailLine,
+    ) -> Entity {
+        let points = line.track.points();
+        let center = points.iter().copied().sum::<Vec3>() / points.len().max(1) as f32;
+        let entity = commands
+            .spawn((
+                Name::new(line.name.clone()),
+                Mesh3d(meshes.add(track_ribbon_mesh(
+                    &line.track,
+                    0.0,
+                    BALLAST_WIDTH,
+                    BALLAST_LIFT,
+                    center,
+                ))),
+                MeshMaterial3d(self.ballast.clone()),
+                Transform::from_translation(center),
+            ))
+            .id();
+        for offset in [-RAIL_OFFSET, RAIL_OFFSET] {
+            commands.spawn((
+                Mesh3d(meshes.add(track_ribbon_mesh(
+                    &line.track,
+                    offset,
+                    RAIL_WIDTH,
+                    RAIL_LIFT,
+                    center,
+                ))),
+                MeshMaterial3d(self.rail.clone()),
+                Transform::IDENTITY,
+                ChildOf(entity),
+            ));
+        }
+        entity
+    }
+
+    /// Platform beside the track at `station`, long enough for `train_length`
+    pub fn spawn_station(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        line: &RailLine,
+        station: usize,
+        train_length: f32,
+    ) -> Entity {
+        let station = &line.stations[station];
+        let (position, direction) = line.track.sample(station.distance);
+        let side = Vec3::new(-direction.z, 0.0, direction.x).normalize_or(Vec3::X);
+        let length = train_length + 10.0;
+        let half = Vec3::new(PLATFORM_WIDTH, PLATFORM_HEIGHT, length) * 0.5;
+        commands
+            .spawn((
+                Name::new(format!("Station {}", station.name)),
+                Mesh3d(meshes.add(Cuboid::from_size(half * 2.0))),
+                MeshMaterial3d(self.platform.clone()),
+                Transform::from_translation(position + side * PLATFORM_OFFSET + Vec3::Y * half.y)
+                    .looking_to(direction, Vec3::Y),
+                RigidBody::Fixed,
+                Collider::cuboid(half.x, half.y, half.z),
+            ))
+            .id()
+    }
+
+    /// Train of `cars` cars with its front at `distance`, dwelling at `station`
+    #[allow(clippy::too_many_arguments)]
+    pub fn spawn_train(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        groups: CollisionGroups,
+        line_index: usize,
+        line: &RailLine,
+        station: usize,
+        distance: f32,
+        cars: usize,
+        car_length: f32,
+    ) -> Entity {
+        let train = commands.spawn_empty().id();
+        let body = meshes.add(Cuboid::new(CAR_WIDTH, CAR_HEIGHT, car_length));
+        let (position, direction) = line.track.sample(distance);
+        let car_entities: Vec<Entity> = (0..cars.max(1))
+            .map(|index| {
+                let material = if index == 0 {
+                    self.locomotive.clone()
+                } else {
+                    self.carriage.clone()
+                };
+                commands
+                    .spawn((
+                        Name::new(format!("{} car {}", line.name, index + 1)),
+                        TrainCar { train, index },
+                        Mesh3d(body.clone()),
+                        MeshMaterial3d(material),
+                        // Placed along the track on the first movement update
+                        Transform::from_translation(position).looking_to(direction, Vec3::Y),
+                        RigidBody::KinematicPositionBased,
+                        Collider::cuboid(CAR_WIDTH * 0.5, CAR_HEIGHT * 0.5, car_length * 0.5),
+                        groups,
+                    ))
+                    .id()
+            })
+            .collect();
+
+        commands.entity(train).insert((
+            Name::new(format!("{} train", line.name)),
+            Train {
+                line: line_index,
+                distance,
+                speed: 0.0,
+                direction: 1.0,
+                state: TrainState::Dwelling {
+                    station,
+                    remaining: line.dwell_time,
+                },
+                cars: car_entities,
+            },
+        ));
+        train
+    }
+
+    /// Pair of flashing lights on posts at a crossing, one on each side of
+    /// a road `road_width` wide, facing opposite ways like real crossings
+    pub fn spawn_crossing(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        line_index: usize,
+        line: &RailLine,
+        distance: f32,
+        road_width: f32,
+    ) {
+        let post = meshes.add(Cylinder::new(0.1, CROSSING_POST_HEIGHT));
+        let lamp = meshes.add(Sphere::new(0.25));
+        let clearance = road_width * 0.5 + 1.5;
+        for (phase, side_sign) in [(false, 1.0), (true, -1.0)] {
+            let (position, direction) = line.track.sample(distance - side_sign * clearance);
+            let side = Vec3::new(-direction.z, 0.0, direction.x).normalize_or(Vec3::X);
+            let base = position + side * side_sign * CROSSING_POST_OFFSET;
+            commands.spawn((
+                Name::new("Crossing post"),
+                Mesh3d(post.clone()),
+                MeshMaterial3d(self.post.clone()),
+                Transform::from_translation(base + Vec3::Y * CROSSING_POST_HEIGHT * 0.5),
+            ));
+            commands.spawn((
+                Name::new("Crossing light"),
+                RailCrossingLight {
+                    line: line_index,
+                    distance,
+                    phase,
+                },
+                Mesh3d(lamp.clone()),
+                MeshMaterial3d(self.light_off.clone()),
+                Transform::from_translation(base + Vec3::Y * CROSSING_POST_HEIGHT),
+            ));
+        }
+    }
+}
@@ -1,18 +1,20 @@
 use crate::bundles::{DynamicPhysicsBundle, VisibleChildBundle};
 use crate::components::MovementTracker;
-use crate::components::unified_water::WaterBodyId;
+use crate::components::unified_water::{BuoyancyHull, WaterBodyId};
 use crate::components::water::{Yacht, YachtSpecs, YachtState};
 use crate::components::{
     AircraftFlight, Car, CarWheelsConfig, ContentType, DynamicContent, F16, Grounded, Helicopter,
-    HelicopterRuntime, HelicopterVisualBody, LandingLight, MainRotor, NavigationLight,
+    HelicopterRuntime, HelicopterVisualBody, LandingGear, LandingGearStrut, LandingLight,
+    MainRotor, NavigationLight,
     NavigationLightType, RotorBlurDisk, RotorWash, SimpleCarSpecs, SimpleCarSpecsHandle,
     SimpleF16Specs, SimpleF16SpecsHandle, SimpleHelicopterSpecs, SimpleHelicopterSpecsHandle,
-    TailRotor, VehicleState, VehicleType, VisualRig, VisualRigRoot, WheelMesh, WheelPos,
+    TailRotor, TirePhysics, Transmission, VehicleHealth, VehicleLamp, VehicleLights,
+    VehicleState, VehicleType, VisualRig, VisualRigRoot, WheelMesh, WheelPos,
     WheelSteerPivot, WheelsRoot,
 };
 use crate::config::GameConfig;
 use crate::factories::generic_bundle::BundleError;
-use crate::factories::{MaterialFactory, MeshFactory};
+use crate::factories::{EntityPool, MaterialFactory, MeshFactory};
 use crate::systems::movement::simple_yacht::YachtSpecsHandle;
 use bevy::prelude::*;
 use bevy::render::view::visibility::VisibilityRange;
@@ -61,6 +63,54 @@ impl VehicleFactory {
         asset_server: &Res<AssetServer>,
         position: Vec3,
         color: Option<Color>,
+    ) -> Result<Entity, BundleError> {
+        let vehicle_entity = commands.spawn_empty().id();
+        self.build_super_car(
+            commands,
+            vehicle_entity,
+            meshes,
+            materials,
+            asset_server,
+            position,
+            color,
+        )
+    }
+
+    /// Spawn a traffic SuperCar on a car recycled from `pool` if one is free
+    #[allow(clippy::too_many_arguments)]
+    pub fn spawn_pooled_super_car(
+        &self,
+        commands: &mut Commands,
+        pool: &mut EntityPool,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+        asset_server: &Res<AssetServer>,
+        position: Vec3,
+        color: Option<Color>,
+    ) -> Result<Entity, BundleError> {
+        let vehicle_entity = pool.spawn(commands, ContentType::Vehicle, ());
+        self.build_super_car(
+            commands,
+            vehicle_entity,
+            meshes,
+            materials,
+            asset_server,
+            position,
+            color,
+        )
+    }
+
+    /// Fills `vehicle_entity` with the SuperCar's body and spawns its parts
+    #[allow(clippy::too_many_arguments)]
+    fn build_super_car(
+        &self,
+        commands: &mut Commands,
+        vehicle_entity: Entity,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+        asset_server: &Res<AssetServer>,
+        position: Vec3,
+        color: Option<Color>,
     ) -> Result<Entity, BundleError> {
         let color = color.unwrap_or_else(|| self.random_car_color());
 
@@ -68,51 +118,54 @@ impl VehicleFactory {
         // Note: Asset loaded asynchronously - systems check specs_handle validity
         let car_specs_handle: Handle<SimpleCarSpecs> = asset_server.load("config/simple_car.ron");
 
-        let vehicle_entity = commands
-            .spawn((
-                DynamicPhysicsBundle {
-                    dynamic_content: DynamicContent {
-                        content_type: ContentType::Vehicle,
-                    },
-                    transform: Transform::from_translation(position + Vec3::new(0.0, 0.125, 0.0)),
-                    visibility: Visibility::default(),
-                    inherited_visibility: InheritedVisibility::VISIBLE,
-                    view_visibility: ViewVisibility::default(),
-                    rigid_body: RigidBody::Dynamic,
-                    collider: Collider::cuboid(0.72, 0.5, 1.68), // 0.8x of visual bounds: width 0.9, height 0.625, length 2.1
-                    collision_groups: CollisionGroups::new(
-                        self.config.physics.vehicle_group,
-                        self.config.physics.static_group
-                            | self.config.physics.vehicle_group
-                            | self.config.physics.character_group,
-                    ),
-                    velocity: Velocity::default(),
-                    visibility_range: self.visibility_range(),
-                },
-                Car,
-                VehicleState::new(VehicleType::SuperCar),
-                SimpleCarSpecsHandle(car_specs_handle),
-                AdditionalMassProperties::Mass(self.config.vehicles.super_car.mass),
-                Ccd::enabled(), // High-speed cars need continuous collision detection
-                Damping {
-                    linear_damping: self.config.vehicles.super_car.linear_damping,
-                    angular_damping: self.config.vehicles.super_car.angular_damping,
-                },
-                Friction {
-                    coefficient: 0.15, // Phase 0: Reduced friction to prevent interference with custom grip (was 0.2)
-                    combine_rule: CoefficientCombineRule::Min, // Phase 0: Use min to prevent grip conflicts
+        commands.entity(vehicle_entity).insert((
+            DynamicPhysicsBundle {
+                dynamic_content: DynamicContent {
+                    content_type: ContentType::Vehicle,
                 },
-                Restitution {
-                    coefficient: 0.0, // Phase 0: No bounce for cars
-                    combine_rule: CoefficientCombineRule::Min,
-                },
-                MovementTracker::new(position, 10.0),
-                Name::new("SuperCar"),
-                Grounded::default(),      // Phase 2: Ground detection state
-                ExternalForce::default(), // Phase 2: For stability forces and torques
-                VisualRig::default(),     // Phase 3: Visual-only body lean
-            ))
-            .id();
+                transform: Transform::from_translation(position + Vec3::new(0.0, 0.125, 0.0)),
+                visibility: Visibility::default(),
+                inherited_visibility: InheritedVisibility::VISIBLE,
+                view_visibility: ViewVisibility::default(),
+                rigid_body: RigidBody::Dynamic,
+                collider: Collider::cuboid(0.72, 0.5, 1.68), // 0.8x of visual bounds: width 0.9, height 0.625, length 2.1
+                collision_groups: CollisionGroups::new(
+                    self.config.physics.vehicle_group,
+                    self.config.physics.static_group
+                        | self.config.physics.vehicle_group
+                        | self.config.physics.character_group,
+                ),
+                velocity: Velocity::default(),
+                visibility_range: self.visibility_range(),
+            },
+            Car,
+            VehicleState::new(VehicleType::SuperCar),
+            SimpleCarSpecsHandle(car_specs_handle),
+            AdditionalMassProperties::Mass(self.config.vehicles.super_car.mass),
+            Ccd::enabled(), // High-speed cars need continuous collision detection
+            Damping {
+                linear_damping: self.config.vehicles.super_car.linear_damping,
+                angular_damping: self.config.vehicles.super_car.angular_damping,
+            },
+            Friction {
+                coefficient: 0.15, // Phase 0: Reduced friction to prevent interference with custom grip (was 0.2)
+                combine_rule: CoefficientCombineRule::Min, // Phase 0: Use min to prevent grip conflicts
+            },
+            Restitution {
+                coefficient: 0.0, // Phase 0: No bounce for cars
+                combine_rule: CoefficientCombineRule::Min,
+            },
+            MovementTracker::new(position, 10.0),
+            Name::new("SuperCar"),
+            Grounded::default(),      // Phase 2: Ground detection state
+            ExternalForce::default(), // Phase 2: For stability forces and torques
+            VisualRig::default(),     // Phase 3: Visual-only body lean
+            (
+                Transmission::default(),
+                TirePhysics::default(),
+                VehicleLights::default(),
+            ),
+        ));
 
         // Phase 3: Create VisualRigRoot as single child that receives visual rotation
         let rig_root = commands
@@ -264,6 +317,43 @@ impl VehicleFactory {
             ));
         }
 
+        // Lamps stay hidden until update_vehicle_lights switches them on
+        for x in [-0.6, 0.6] {
+            commands.spawn((
+                SpotLight {
+                    color: Color::srgb(1.0, 0.96, 0.88),
+                    intensity: 0.0,
+                    range: self.config.lighting.headlight_range,
+                    radius: 0.1,
+                    shadows_enabled: false,
+                    inner_angle: 0.25,
+                    outer_angle: 0.55,
+                    ..default()
+                },
+                Transform::from_xyz(x, -0.1, -2.1)
+                    .looking_at(Vec3::new(x, -1.5, -20.0), Vec3::Y),
+                ChildOf(vehicle_entity),
+                VehicleLamp::Head,
+                Visibility::Hidden,
+                Name::new("Headlight"),
+            ));
+            commands.spawn((
+                PointLight {
+                    color: Color::srgb(1.0, 0.05, 0.02),
+                    intensity: 0.0,
+                    range: 6.0,
+                    radius: 0.08,
+                    shadows_enabled: false,
+                    ..default()
+                },
+                Transform::from_xyz(x, 0.0, 2.15),
+                ChildOf(vehicle_entity),
+                VehicleLamp::Tail,
+                Visibility::Hidden,
+                Name::new("Taillight"),
+            ));
+        }
+
         Ok(vehicle_entity)
     }
 
@@ -661,6 +751,8 @@ impl VehicleFactory {
                 F16,
                 VehicleState::new(VehicleType::F16),
                 AircraftFlight::default(),
+                LandingGear::default(),
+                VehicleHealth::default(),
                 SimpleF16SpecsHandle(f16_specs_handle),
                 AdditionalMassProperties::Mass(self.config.vehicles.f16.mass),
                 Damping {
@@ -847,6 +939,48 @@ impl VehicleFactory {
         // Particles spawned automatically by spawn_afterburner_particles system
         // See src/systems/effects/afterburner.rs for particle implementation
 
+        // Part 10: Landing gear - nose and two main legs, scaled from their
+        // attach points by update_landing_gear_struts
+        let leg_mesh = meshes.add(Cylinder::new(0.06, 0.3));
+        let wheel_mesh = meshes.add(Cylinder::new(0.2, 0.15));
+        let gear_material = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.12, 0.12, 0.13),
+            perceptual_roughness: 0.8,
+            ..default()
+        });
+
+        for attach in [
+            Vec3::new(0.0, -0.5, -6.0),
+            Vec3::new(-1.4, -0.5, 0.5),
+            Vec3::new(1.4, -0.5, 0.5),
+        ] {
+            commands
+                .spawn((
+                    LandingGearStrut,
+                    Transform::from_translation(attach),
+                    Visibility::default(),
+                    VisibleChildBundle::default(),
+                    ChildOf(vehicle_entity),
+                ))
+                .with_children(|strut| {
+                    strut.spawn((
+                        Mesh3d(leg_mesh.clone()),
+                        MeshMaterial3d(gear_material.clone()),
+                        Transform::from_xyz(0.0, -0.15, 0.0),
+                        VisibleChildBundle::default(),
+                        self.visibility_range(),
+                    ));
+                    strut.spawn((
+                        Mesh3d(wheel_mesh.clone()),
+                        MeshMaterial3d(gear_material.clone()),
+                        Transform::from_xyz(0.0, -0.3, 0.0)
+                            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+                        VisibleChildBundle::default(),
+                        self.visibility_range(),
+                    ));
+                });
+        }
+
         Ok(vehicle_entity)
     }
 
@@ -901,7 +1035,15 @@ impl VehicleFactory {
                 VehicleState::new(VehicleType::Yacht),
                 WaterBodyId,
                 YachtSpecsHandle(yacht_specs_handle),
-                ExternalForce::default(),
+                // Float on the keel, settling 80% of the hull's half height below the surface
+                (
+                    ExternalForce::default(),
+                    BuoyancyHull::box_keel(
+                        yacht_config.collider_size,
+                        yacht_config.collider_size.y * 0.8,
+                    ),
+                    ReadMassProperties::default(),
+                ),
                 crate::components::unified_water::CurrentWaterRegion::default(),
                 Ccd::enabled(),
                 Damping {
@@ -1447,7 +1589,11 @@ impl VehicleFactory {
 
     /// Generate random car color
     fn random_car_color(&self) -> Color {
-        let mut rng = rand::thread_rng();
+        Self::car_color_from(&mut rand::thread_rng())
+    }
+
+    /// Car paint drawn from `rng`, for reproducible world generation
+    pub fn car_color_from(rng: &mut impl Rng) -> Color {
         let car_colors = [
             Color::srgb(1.0, 0.0, 0.0), // Red
             Color::srgb(0.0, 0.0, 1.0), // Blue
@@ -1,6 +1,23 @@
+//! What the player is doing, and the one way to change it.
+//!
+//! Gameplay changes `GameState` through `GameStateTransitions` rather than
+//! `NextState` directly. The guard checks each move against
+//! `GameState::can_switch_to`, refuses a second, different request in the same
+//! frame instead of letting the last writer win, and keeps a pushdown stack so
+//! an overlay like `Paused` can go over `Driving` and hand it back untouched.
+//!
+//! Every applied transition is reported as `GameStateExited` then
+//! `GameStateEntered`, tagged with how it happened: pushing an overlay exits
+//! the state underneath with `TransitionKind::Push`, and popping re-enters it
+//! with `TransitionKind::Pop`. `OnExit`/`OnEnter` systems that should ignore
+//! an overlay coming and going run under the `switching` condition.
+
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::state::state::StateTransitionEvent;
+use std::fmt;
 
-#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum GameState {
     #[default]
     Walking,
@@ -8,4 +25,361 @@ pub enum GameState {
     Driving,
     Flying,
     Jetting, // New state for F16 flying
+    /// Pause menu over whatever the player was doing; pushed, never set
+    Paused,
+}
+
+impl GameState {
+    /// Overlays go on the stack over another state instead of replacing it
+    pub fn is_overlay(self) -> bool {
+        matches!(self, GameState::Paused)
+    }
+
+    /// Whether gameplay may go straight from this state to `to`
+    pub fn can_switch_to(self, to: GameState) -> bool {
+        use GameState::*;
+        match (self, to) {
+            (from, to) if from == to => true,
+            (Paused, _) | (_, Paused) => false,
+            // On foot the player can get into anything, and everything can
+            // be left on foot
+            (Walking, _) | (_, Walking) => true,
+            // Yacht to the helicopter on its helipad
+            (Driving, Flying) => true,
+            // Climbing aboard a boat from the water
+            (Swimming, Driving) => true,
+            // Bailing out over water
+            (Driving | Flying | Jetting, Swimming) => true,
+            _ => false,
+        }
+    }
+}
+
+/// How a transition was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// One gameplay state replaced another
+    Switch,
+    /// An overlay went on top; the state underneath is only suspended
+    Push,
+    /// An overlay came off and the state underneath resumed
+    Pop,
+}
+
+/// Sent after `state` was left
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameStateExited {
+    pub state: GameState,
+    pub kind: TransitionKind,
+}
+
+/// Sent after `state` was entered, following the matching `GameStateExited`
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameStateEntered {
+    pub state: GameState,
+    pub kind: TransitionKind,
+}
+
+/// Why the guard refused a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionError {
+    /// Not a move gameplay can make, e.g. straight from Jetting to Driving
+    NotAllowed {
+        from: GameState,
+        to: GameState,
+    },
+    /// Another system already asked for a different state this frame
+    Conflict {
+        requested: GameState,
+        pending: GameState,
+    },
+    /// An overlay is on top and has to come off first
+    Overlaid(GameState),
+    /// Only overlays can be pushed
+    NotAnOverlay(GameState),
+    NothingToPop,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransitionError::NotAllowed { from, to } => {
+                write!(f, "can't go from {from:?} to {to:?}")
+            }
+            TransitionError::Conflict { requested, pending } => write!(
+                f,
+                "{requested:?} requested while {pending:?} is already pending"
+            ),
+            TransitionError::Overlaid(overlay) => write!(f, "{overlay:?} is on top"),
+            TransitionError::NotAnOverlay(state) => write!(f, "{state:?} is not an overlay"),
+            TransitionError::NothingToPop => write!(f, "no overlay to pop"),
+        }
+    }
+}
+
+/// States suspended under overlays, bottom first, and how the pending
+/// transition was requested
+#[derive(Resource, Debug, Default)]
+pub struct GameStateStack {
+    suspended: Vec<GameState>,
+    pending: Option<TransitionKind>,
+}
+
+impl GameStateStack {
+    /// The gameplay state under any overlays
+    pub fn gameplay(&self, current: GameState) -> GameState {
+        self.suspended.first().copied().unwrap_or(current)
+    }
+
+    pub fn depth(&self) -> usize {
+        self.suspended.len()
+    }
+
+    /// Queues `to`; false when the same request is already pending
+    fn request(
+        &mut self,
+        next: &mut NextState<GameState>,
+        to: GameState,
+        kind: TransitionKind,
+    ) -> Result<bool, TransitionError> {
+        if let NextState::Pending(pending) = *next {
+            return if pending == to {
+                Ok(false)
+            } else {
+                Err(TransitionError::Conflict {
+                    requested: to,
+                    pending,
+                })
+            };
+        }
+        next.set(to);
+        self.pending = Some(kind);
+        Ok(true)
+    }
+
+    /// Switches gameplay state if the rules allow it
+    pub fn set(
+        &mut self,
+        current: GameState,
+        next: &mut NextState<GameState>,
+        to: GameState,
+    ) -> Result<(), TransitionError> {
+        if current.is_overlay() {
+            return Err(TransitionError::Overlaid(current));
+        }
+        if !current.can_switch_to(to) {
+            return Err(TransitionError::NotAllowed { from: current, to });
+        }
+        if current == to && matches!(next, NextState::Unchanged) {
+            return Ok(());
+        }
+        self.request(next, to, TransitionKind::Switch)?;
+        Ok(())
+    }
+
+    /// Lays `overlay` over the current state
+    pub fn push(
+        &mut self,
+        current: GameState,
+        next: &mut NextState<GameState>,
+        overlay: GameState,
+    ) -> Result<(), TransitionError> {
+        if !overlay.is_overlay() {
+            return Err(TransitionError::NotAnOverlay(overlay));
+        }
+        if current == overlay {
+            return Ok(());
+        }
+        if self.request(next, overlay, TransitionKind::Push)? {
+            self.suspended.push(current);
+        }
+        Ok(())
+    }
+
+    /// Takes the top overlay off, returning the state that resumes
+    pub fn pop(
+        &mut self,
+        current: GameState,
+        next: &mut NextState<GameState>,
+    ) -> Result<GameState, TransitionError> {
+        let Some(&resumed) = self.suspended.last() else {
+            return Err(TransitionError::NothingToPop);
+        };
+        if !current.is_overlay() {
+            return Err(TransitionError::NothingToPop);
+        }
+        if self.request(next, resumed, TransitionKind::Pop)? {
+            self.suspended.pop();
+        }
+        Ok(resumed)
+    }
+
+    /// Drops every overlay and goes to `to` regardless of the rules or
+    /// anything pending; for emergency resets
+    pub fn reset(&mut self, next: &mut NextState<GameState>, to: GameState) {
+        self.suspended.clear();
+        next.set(to);
+        self.pending = Some(TransitionKind::Switch);
+    }
+}
+
+/// Guarded access to `GameState` for gameplay systems
+#[derive(SystemParam)]
+pub struct GameStateTransitions<'w> {
+    current: Res<'w, State<GameState>>,
+    next: ResMut<'w, NextState<GameState>>,
+    stack: ResMut<'w, GameStateStack>,
+}
+
+impl GameStateTransitions<'_> {
+    pub fn current(&self) -> GameState {
+        *self.current.get()
+    }
+
+    /// The gameplay state under any overlays
+    pub fn gameplay(&self) -> GameState {
+        self.stack.gameplay(self.current())
+    }
+
+    pub fn set(&mut self, to: GameState) -> Result<(), TransitionError> {
+        let current = self.current();
+        self.stack.set(current, &mut self.next, to)
+    }
+
+    pub fn push(&mut self, overlay: GameState) -> Result<(), TransitionError> {
+        let current = self.current();
+        self.stack.push(current, &mut self.next, overlay)
+    }
+
+    pub fn pop(&mut self) -> Result<GameState, TransitionError> {
+        let current = self.current();
+        self.stack.pop(current, &mut self.next)
+    }
+
+    pub fn reset(&mut self, to: GameState) {
+        self.stack.reset(&mut self.next, to);
+    }
+}
+
+/// Run condition for `OnExit`/`OnEnter` systems that should ignore an overlay
+/// going on or coming off, such as the game being paused
+pub fn switching(stack: Res<GameStateStack>) -> bool {
+    !matches!(
+        stack.pending,
+        Some(TransitionKind::Push | TransitionKind::Pop)
+    )
+}
+
+/// Turns applied transitions into exit and enter events
+pub fn emit_game_state_events(
+    mut transitions: EventReader<StateTransitionEvent<GameState>>,
+    mut stack: ResMut<GameStateStack>,
+    mut exited: EventWriter<GameStateExited>,
+    mut entered: EventWriter<GameStateEntered>,
+) {
+    for transition in transitions.read() {
+        let kind = stack.pending.take().unwrap_or(TransitionKind::Switch);
+        if let Some(state) = transition.exited {
+            exited.write(GameStateExited { state, kind });
+        }
+        if let Some(state) = transition.entered {
+            entered.write(GameStateEntered { state, kind });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_rules() {
+        use GameState::*;
+        assert!(Walking.can_switch_to(Jetting));
+        assert!(Jetting.can_switch_to(Walking));
+        assert!(Driving.can_switch_to(Flying));
+        assert!(Flying.can_switch_to(Swimming));
+        assert!(!Jetting.can_switch_to(Driving));
+        assert!(!Swimming.can_switch_to(Flying));
+        assert!(!Walking.can_switch_to(Paused));
+
+        let mut stack = GameStateStack::default();
+        let mut next = NextState::Unchanged;
+        assert_eq!(
+            stack.set(Jetting, &mut next, Driving),
+            Err(TransitionError::NotAllowed {
+                from: Jetting,
+                to: Driving
+            })
+        );
+        assert!(matches!(next, NextState::Unchanged));
+        assert_eq!(
+            stack.set(Walking, &mut next, Paused),
+            Err(TransitionError::NotAllowed {
+                from: Walking,
+                to: Paused
+            })
+        );
+    }
+
+    #[test]
+    fn test_second_request_in_a_frame_conflicts_unless_it_agrees() {
+        let mut stack = GameStateStack::default();
+        let mut next = NextState::Unchanged;
+        stack
+            .set(GameState::Walking, &mut next, GameState::Driving)
+            .unwrap();
+        assert_eq!(
+            stack.set(GameState::Walking, &mut next, GameState::Swimming),
+            Err(TransitionError::Conflict {
+                requested: GameState::Swimming,
+                pending: GameState::Driving
+            })
+        );
+        assert_eq!(
+            stack.set(GameState::Walking, &mut next, GameState::Driving),
+            Ok(())
+        );
+        assert!(matches!(next, NextState::Pending(GameState::Driving)));
+    }
+
+    #[test]
+    fn test_pause_overlays_driving_and_restores_it() {
+        let mut stack = GameStateStack::default();
+        let mut next = NextState::Unchanged;
+        stack
+            .push(GameState::Driving, &mut next, GameState::Paused)
+            .unwrap();
+        assert!(matches!(next, NextState::Pending(GameState::Paused)));
+        // Asking twice in a frame doesn't stack it twice
+        stack
+            .push(GameState::Driving, &mut next, GameState::Paused)
+            .unwrap();
+        assert_eq!(stack.depth(), 1);
+        assert_eq!(stack.gameplay(GameState::Paused), GameState::Driving);
+        assert_eq!(stack.pending, Some(TransitionKind::Push));
+
+        // Applied; gameplay can't switch underneath the overlay
+        let mut next = NextState::Unchanged;
+        assert_eq!(
+            stack.set(GameState::Paused, &mut next, GameState::Walking),
+            Err(TransitionError::Overlaid(GameState::Paused))
+        );
+        assert_eq!(
+            stack.push(GameState::Paused, &mut next, GameState::Driving),
+            Err(TransitionError::NotAnOverlay(GameState::Driving))
+        );
+        assert_eq!(
+            stack.pop(GameState::Paused, &mut next),
+            Ok(GameState::Driving)
+        );
+        assert!(matches!(next, NextState::Pending(GameState::Driving)));
+        assert_eq!(stack.depth(), 0);
+
+        let mut next = NextState::Unchanged;
+        assert_eq!(
+            stack.pop(GameState::Driving, &mut next),
+            Err(TransitionError::NothingToPop)
+        );
+    }
 }
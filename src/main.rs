@@ -6,9 +6,13 @@ use gta_game::states::AppState;
 /// High-level application flow:
 /// 1. Initialize core systems (GameCorePlugin) - Window loads
 /// 2. AssetLoading state - Show splash screen and load essential assets with progress tracking
-/// 3. WorldGeneration state - Generate static world (8,836 chunks)
-/// 4. InGame state - Run gameplay systems
+/// 3. MainMenu state - New game with a chosen seed, continue the latest save, settings;
+///    opens on the crash report page if the last run panicked
+/// 4. WorldGeneration state - Generate static world (8,836 chunks); Esc cancels to the menu
+/// 5. InGame state - Run gameplay systems
 fn main() {
+    // Before anything else so startup panics get a crash report too
+    gta_game::systems::crash_report::install_panic_hook();
     App::new()
         .add_plugins(GameCorePlugin)
         .init_state::<AppState>()
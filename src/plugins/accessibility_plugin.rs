@@ -0,0 +1,38 @@
+use crate::components::Subtitles;
+use crate::states::AppState;
+use crate::systems::accessibility::{apply_ui_scale, caption_audio_cues, load_saved_accessibility};
+use crate::systems::camera_shake::{
+    CameraShake, apply_camera_shake, remove_camera_shake, shake_on_impacts,
+};
+use crate::systems::ui::gameplay_ui::{setup_subtitle_text, update_subtitle_text};
+use bevy::prelude::*;
+
+/// Saved accessibility settings and what they drive: UI scale, subtitles for
+/// audio cues and camera shake. Colorblind palettes are read by the HUD
+/// systems through `GameConfig::accessibility`.
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Subtitles>()
+            .init_resource::<CameraShake>()
+            .add_systems(Startup, (load_saved_accessibility, setup_subtitle_text))
+            .add_systems(PreUpdate, remove_camera_shake)
+            .add_systems(
+                Update,
+                (
+                    apply_ui_scale,
+                    (shake_on_impacts, caption_audio_cues, update_subtitle_text)
+                        .chain()
+                        .run_if(in_state(AppState::InGame)),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                apply_camera_shake.before(TransformSystem::TransformPropagate),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Accessibility Plugin loaded");
+    }
+}
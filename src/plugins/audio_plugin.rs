@@ -0,0 +1,50 @@
+use crate::states::AppState;
+use crate::systems::audio::{
+    assign_engine_voices, attach_police_sirens, attach_spatial_listener, load_audio_clips,
+    play_pedestrian_barks, spawn_ambient_city_sound, spawn_district_ambience,
+    update_ambient_city_sound, update_car_alarm_sounds, update_horn_sounds, update_tire_screech,
+    update_vehicle_sounds,
+};
+use crate::systems::music::{load_music_playlist, music_crossfade_system, music_director_system};
+use bevy::prelude::*;
+
+/// Spatial vehicle engines, sirens, horns, car alarms and tire screech with doppler, pedestrian
+/// shouts, district ambience and dynamic music
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (load_audio_clips, load_music_playlist))
+            .add_systems(
+                OnEnter(AppState::InGame),
+                (spawn_ambient_city_sound, spawn_district_ambience),
+            )
+            .add_systems(
+                Update,
+                (
+                    attach_spatial_listener,
+                    assign_engine_voices,
+                    attach_police_sirens,
+                    update_tire_screech,
+                    update_horn_sounds,
+                    update_car_alarm_sounds,
+                    play_pedestrian_barks,
+                    update_vehicle_sounds,
+                    update_ambient_city_sound,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    music_director_system.run_if(in_state(AppState::InGame)),
+                    music_crossfade_system,
+                )
+                    .chain(),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Audio Plugin loaded");
+    }
+}
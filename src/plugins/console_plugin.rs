@@ -0,0 +1,36 @@
+use crate::states::AppState;
+use crate::systems::ui::console::{
+    ConsoleCommands, ConsoleState, GodMode, apply_god_mode, console_input,
+    register_builtin_commands, run_console_commands, setup_console, update_console_ui,
+};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+/// `~` drop-down console with a command registry and cheat built-ins.
+/// Other plugins add commands through `ResMut<ConsoleCommands>`.
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleCommands>()
+            .init_resource::<ConsoleState>()
+            .init_resource::<GodMode>()
+            .add_systems(Startup, (setup_console, register_builtin_commands))
+            // Before any gameplay system reads the keys this frame
+            .add_systems(
+                PreUpdate,
+                console_input
+                    .after(InputSystem)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (run_console_commands, update_console_ui, apply_god_mode)
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Console Plugin loaded");
+    }
+}
@@ -0,0 +1,35 @@
+use crate::states::{AppState, MainMenuPage};
+use crate::systems::crash_report::{register_crash_log_sink, update_crash_snapshot};
+use crate::systems::ui::crash_dialog::{
+    crash_report_actions, load_pending_crash_report, show_pending_crash_report,
+    spawn_crash_report_page,
+};
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// Keeps the world summary the panic hook writes into crash reports, and
+/// offers the previous run's report from the main menu. The hook itself is
+/// installed by `main` via `install_panic_hook`.
+pub struct CrashReportPlugin;
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (load_pending_crash_report, register_crash_log_sink),
+        )
+        .add_systems(
+            Update,
+            (
+                update_crash_snapshot.run_if(on_timer(Duration::from_secs(1))),
+                crash_report_actions.run_if(in_state(MainMenuPage::CrashReport)),
+            ),
+        )
+        .add_systems(OnEnter(AppState::MainMenu), show_pending_crash_report)
+        .add_systems(OnEnter(MainMenuPage::CrashReport), spawn_crash_report_page);
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Crash Report Plugin loaded");
+    }
+}
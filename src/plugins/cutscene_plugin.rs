@@ -0,0 +1,43 @@
+use crate::components::cutscene::{
+    ActiveCutscene, CutsceneFinished, PlayCutscene, cutscene_playing,
+};
+use crate::states::AppState;
+use crate::systems::camera_rig::camera_rig_system;
+use crate::systems::cutscene::{
+    load_cutscene_catalog, lock_input_during_cutscene, play_cutscene_camera, setup_letterbox,
+    start_cutscenes, update_letterbox,
+};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+/// Keyframed camera cutscenes from `assets/config/cutscenes.ron`, started with
+/// `PlayCutscene` and announced with `CutsceneFinished` once they end.
+pub struct CutscenePlugin;
+
+impl Plugin for CutscenePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveCutscene>()
+            .add_event::<PlayCutscene>()
+            .add_event::<CutsceneFinished>()
+            .add_systems(Startup, (load_cutscene_catalog, setup_letterbox))
+            .add_systems(
+                PreUpdate,
+                lock_input_during_cutscene
+                    .after(InputSystem)
+                    .run_if(cutscene_playing),
+            )
+            .add_systems(
+                Update,
+                (
+                    start_cutscenes,
+                    play_cutscene_camera.after(camera_rig_system),
+                    update_letterbox,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Cutscene Plugin loaded");
+    }
+}
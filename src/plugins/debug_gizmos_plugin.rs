@@ -0,0 +1,55 @@
+use crate::resources::CrowdDensity;
+use crate::states::AppState;
+use crate::systems::debug_gizmos::{
+    DebugGizmoLayers, GizmoLayer, draw_collider_wireframes, draw_crowd_density, draw_culling_rings,
+    draw_lod_tiers, draw_road_network, draw_spatial_cells, gizmo_layer_enabled,
+    toggle_debug_gizmo_layers,
+};
+use crate::systems::debug_window::{
+    despawn_orphaned_debug_cameras, follow_main_camera, toggle_debug_window,
+};
+use crate::systems::world::unified_world::UnifiedWorldManager;
+use bevy::prelude::*;
+
+/// F6 chord-toggled gizmo layers for colliders, culling rings, LOD tiers,
+/// the road graph, spatial index cells and crowd density, and the F11
+/// top-down window to watch them from
+pub struct DebugGizmosPlugin;
+
+impl Plugin for DebugGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugGizmoLayers>()
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_gizmo_layers,
+                    draw_collider_wireframes.run_if(gizmo_layer_enabled(GizmoLayer::Colliders)),
+                    draw_culling_rings.run_if(gizmo_layer_enabled(GizmoLayer::Culling)),
+                    draw_lod_tiers.run_if(gizmo_layer_enabled(GizmoLayer::Lod)),
+                    draw_road_network.run_if(
+                        gizmo_layer_enabled(GizmoLayer::Roads)
+                            .and(resource_exists::<UnifiedWorldManager>),
+                    ),
+                    draw_spatial_cells.run_if(gizmo_layer_enabled(GizmoLayer::SpatialCells)),
+                    draw_crowd_density.run_if(
+                        gizmo_layer_enabled(GizmoLayer::CrowdDensity)
+                            .and(resource_exists::<CrowdDensity>)
+                            .and(resource_exists::<UnifiedWorldManager>),
+                    ),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_window,
+                    despawn_orphaned_debug_cameras,
+                    follow_main_camera,
+                )
+                    .chain(),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Debug Gizmos Plugin loaded");
+    }
+}
@@ -0,0 +1,20 @@
+use crate::states::AppState;
+use crate::systems::rendering::decals::{
+    Decals, clear_decals, setup_decal_batch, spawn_impact_decals, update_decal_batch,
+};
+use bevy::prelude::*;
+
+/// Skid marks, scorch marks, blood splats and road paint, drawn as one batch
+pub struct DecalPlugin;
+
+impl Plugin for DecalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Decals>()
+            .add_systems(Startup, setup_decal_batch)
+            .add_systems(Update, (spawn_impact_decals, update_decal_batch).chain())
+            .add_systems(OnExit(AppState::InGame), clear_decals);
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Decal Plugin loaded");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::components::{ActiveDialogue, DialogueCatalog};
+use crate::game_state::{GameState, switching};
+use crate::plugins::interaction_plugin::InteractionSet;
+use crate::states::AppState;
+use crate::systems::dialogue::{
+    assign_npc_dialogue, dialogue_choice_system, dialogue_interaction_system,
+    end_abandoned_dialogue, end_dialogue, load_dialogues, sync_talk_interactables,
+};
+use crate::systems::ui::refresh_dialogue_box;
+use bevy::prelude::*;
+
+/// NPC conversations driven by RON dialogue trees, with localized text
+pub struct DialoguePlugin;
+
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DialogueCatalog>()
+            .init_resource::<ActiveDialogue>()
+            .add_systems(Startup, load_dialogues)
+            .add_systems(
+                Update,
+                (
+                    end_abandoned_dialogue,
+                    dialogue_interaction_system,
+                    dialogue_choice_system,
+                )
+                    .chain()
+                    .after(InteractionSet)
+                    .run_if(in_state(GameState::Walking))
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    assign_npc_dialogue,
+                    sync_talk_interactables,
+                    refresh_dialogue_box,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            )
+            // Pausing mid-conversation picks it back up on resume
+            .add_systems(OnExit(GameState::Walking), end_dialogue.run_if(switching));
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Dialogue Plugin loaded");
+    }
+}
@@ -0,0 +1,34 @@
+use crate::components::Wallet;
+use crate::states::AppState;
+use crate::systems::economy::{
+    collect_money_pickups, credit_mission_rewards, credit_race_rewards, drop_npc_cash,
+    register_economy_commands,
+};
+use crate::systems::ui::gameplay_ui::{setup_money_counter, update_money_counter};
+use bevy::prelude::*;
+
+/// Player wallet, cash dropped by NPCs, mission and race payouts and the HUD money
+/// counter. Garage purchases spend from the same wallet.
+pub struct EconomyPlugin;
+
+impl Plugin for EconomyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Wallet>()
+            .add_systems(Startup, (register_economy_commands, setup_money_counter))
+            .add_systems(
+                Update,
+                (
+                    drop_npc_cash,
+                    collect_money_pickups,
+                    credit_mission_rewards,
+                    credit_race_rewards,
+                    update_money_counter,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Economy Plugin loaded");
+    }
+}
@@ -1,29 +1,49 @@
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::log::{Level, LogPlugin};
 use bevy::prelude::*;
 use bevy::time::common_conditions::on_timer;
 use bevy_hanabi::HanabiPlugin;
 use bevy_rapier3d::prelude::*;
+use std::path::Path;
 use std::time::Duration;
 
 use crate::GameState;
 use crate::components::world::{EntityLimits, MaterialCache, MeshCache, WorldBounds};
 use crate::components::{CullingSettings, DirtyFlagsMetrics, PerformanceStats};
-use crate::config::GameConfig;
+use crate::config::{CONFIG_FILES, ConfigFieldChanged, ConfigReloadedEvent, GameConfig};
+use crate::factories::EntityPool;
+use crate::game_state::{
+    GameStateEntered, GameStateExited, GameStateStack, emit_game_state_events,
+};
 use crate::plugins::{
-    InputPlugin, MapPlugin, PlayerPlugin, SkyboxPlugin, UIPlugin, UnderwaterPlugin,
-    UnifiedWorldPlugin, VehiclePlugin, WaterPlugin,
+    AccessibilityPlugin, AudioPlugin, ConsolePlugin, CrashReportPlugin, CutscenePlugin,
+    DebugGizmosPlugin, DecalPlugin, DialoguePlugin, EconomyPlugin, GameplayEventsPlugin,
+    GaragePlugin, GpsPlugin, GpuStreamingPlugin, HealthPlugin, InputPlugin, InspectorPlugin,
+    InstancingPlugin, InteractionPlugin, InteriorPlugin, LightingPlugin, LoggingPlugin, MapPlugin,
+    MenuPlugin, MissionPlugin, ParticlePlugin, PersistencePlugin, PlayerPlugin, PolicePlugin,
+    PostProcessingPlugin, PrefabPlugin, ProfilePlugin, RacePlugin, RailPlugin, ReflectionPlugin,
+    ShopPlugin, SkyboxPlugin, StuntPlugin, TelemetryPlugin, TrafficPlugin, UIPlugin,
+    UnderwaterPlugin, UnifiedWorldPlugin, VehiclePlugin, WaterPlugin, WeatherPlugin,
 };
-use crate::resources::WorldRng;
+use crate::resources::{DistrictMap, WorldRng, WorldSeed};
 
+use crate::systems::config_reload::{
+    ConfigReloadRequest, hot_reload_config, layer_config_files, register_config_command,
+    request_config_reload,
+};
+use crate::systems::effects::ParticleEmitterPool;
+use crate::systems::logging::logging_layer;
 use crate::systems::performance::{DebugUIPlugin, PerformancePlugin, UnifiedPerformancePlugin};
 use crate::systems::physics::apply_universal_physics_safeguards;
 use crate::systems::player_physics_enable::enable_player_physics_next_frame;
+use crate::systems::profiles::ActiveProfile;
 use crate::systems::safe_active_entity::{
     active_entity_integrity_check, active_transfer_executor_system,
 };
 use crate::systems::world::boundaries::{aircraft_boundary_system, world_boundary_system};
 use crate::systems::world::entity_limit_enforcement::enforce_entity_limits;
-use crate::systems::{SpawnValidationPlugin, TransformSyncPlugin};
+use crate::systems::world::terrain_height::TerrainHeightService;
+use crate::systems::{DistanceCachePlugin, SpawnValidationPlugin, TransformSyncPlugin};
 
 /// Core plugin that groups all essential game plugins and resources
 /// Simplifies main.rs by organizing plugins into logical groups
@@ -38,13 +58,28 @@ impl Plugin for GameCorePlugin {
                     .set(WindowPlugin {
                         primary_window: Some(Window {
                             present_mode: bevy::window::PresentMode::AutoVsync,
+                            // Matches the smallest resolution the graphics config allows
+                            resize_constraints: bevy::window::WindowResizeConstraints {
+                                min_width: 640.0,
+                                min_height: 360.0,
+                                ..default()
+                            },
                             ..default()
                         }),
+                        // The F11 debug window must not keep the game alive
+                        exit_condition: bevy::window::ExitCondition::OnPrimaryClosed,
                         ..default()
                     })
                     .set(AssetPlugin {
                         file_path: crate::util::asset_path::get_assets_base_path(),
                         ..default()
+                    })
+                    // Everything is let through here; the reloadable filter in
+                    // `logging_layer` decides what is actually logged
+                    .set(LogPlugin {
+                        level: Level::TRACE,
+                        filter: String::new(),
+                        custom_layer: logging_layer,
                     }),
             )
             // Performance optimizations: Lock physics to 60Hz fixed timestep
@@ -54,13 +89,24 @@ impl Plugin for GameCorePlugin {
             .add_plugins(FrameTimeDiagnosticsPlugin::default())
             // Game State and Resources
             .init_state::<GameState>()
+            .init_resource::<GameStateStack>()
+            .add_event::<GameStateExited>()
+            .add_event::<GameStateEntered>()
+            .add_systems(Update, emit_game_state_events)
             .init_resource::<GameConfig>()
+            .add_event::<ConfigReloadedEvent>()
+            .add_event::<ConfigFieldChanged>()
+            .add_event::<ConfigReloadRequest>()
+            .add_systems(Startup, register_config_command)
+            .add_systems(Update, (request_config_reload, hot_reload_config).chain())
             .init_resource::<CullingSettings>()
             .init_resource::<PerformanceStats>()
             .init_resource::<DirtyFlagsMetrics>()
             .init_resource::<MeshCache>()
             .init_resource::<EntityLimits>()
+            .init_resource::<WorldSeed>()
             .init_resource::<WorldRng>()
+            .init_resource::<DistrictMap>()
             // Coordinate safety resources
             // World boundary system - initialize from config (runs before validation)
             // Chain load_world_configs BEFORE systems that depend on it
@@ -72,6 +118,25 @@ impl Plugin for GameCorePlugin {
                         let bounds = WorldBounds::from_config(&config.world);
                         commands.insert_resource(bounds);
                     },
+                    |mut commands: Commands, config: Res<GameConfig>| {
+                        commands.insert_resource(EntityPool::from_config(&config.performance));
+                        commands
+                            .insert_resource(ParticleEmitterPool::from_config(&config.performance));
+                    },
+                    |mut commands: Commands, config: Res<GameConfig>| {
+                        // Fixed seed from config reproduces the same world every run
+                        let seed = config
+                            .world
+                            .seed
+                            .map_or_else(WorldSeed::from_time, WorldSeed);
+                        info!("🌱 World seed: {}", seed.0);
+                        commands.insert_resource(seed);
+                        commands.insert_resource(WorldRng::new(seed.0));
+                    },
+                    |mut commands: Commands, config: Res<GameConfig>| {
+                        let terrain = TerrainHeightService::from_env(&config.world_env);
+                        commands.insert_resource(terrain);
+                    },
                     |mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>| {
                         let material_cache = MaterialCache::new(&mut materials);
                         commands.insert_resource(material_cache);
@@ -86,27 +151,70 @@ impl Plugin for GameCorePlugin {
                 brightness: 1800.0,
                 affects_lightmapped_meshes: true,
             })
+            // Data-driven prefabs
+            .add_plugins(PrefabPlugin)
             // Input and Player Systems
-            .add_plugins((InputPlugin, PlayerPlugin))
+            .add_plugins((InputPlugin, PlayerPlugin, InteractionPlugin))
             // Vehicle Systems
             .add_plugins(VehiclePlugin)
+            // Gameplay Systems
+            .add_plugins((
+                MissionPlugin,
+                CutscenePlugin,
+                GameplayEventsPlugin,
+                EconomyPlugin,
+                HealthPlugin,
+                PersistencePlugin,
+                GaragePlugin,
+                ShopPlugin,
+                DialoguePlugin,
+                InteriorPlugin,
+                TrafficPlugin,
+                PolicePlugin,
+                RacePlugin,
+                RailPlugin,
+                StuntPlugin,
+            ))
             // World and Environment Systems
             .add_plugins((
                 WaterPlugin,
                 UnifiedWorldPlugin,
                 UnderwaterPlugin,
+                PostProcessingPlugin,
+                ReflectionPlugin,
+                LightingPlugin,
+                DecalPlugin,
+                ParticlePlugin,
                 SkyboxPlugin,
+                WeatherPlugin,
+                AudioPlugin,
+                InstancingPlugin,
+                GpuStreamingPlugin,
             ))
             // Performance and Validation Systems
             .add_plugins((
                 SpawnValidationPlugin,
+                DistanceCachePlugin,
                 TransformSyncPlugin,
                 PerformancePlugin,
                 UnifiedPerformancePlugin,
                 DebugUIPlugin,
+                DebugGizmosPlugin,
+                TelemetryPlugin,
             ))
             // UI Systems
-            .add_plugins((UIPlugin, MapPlugin))
+            .add_plugins((
+                UIPlugin,
+                MapPlugin,
+                GpsPlugin,
+                MenuPlugin,
+                ConsolePlugin,
+                InspectorPlugin,
+                LoggingPlugin,
+                CrashReportPlugin,
+                AccessibilityPlugin,
+                ProfilePlugin,
+            ))
             // Setup world root entity at startup
             // No longer need WorldRoot setup
             // Re-enable player physics before Rapier reads poses (safe vehicle exit)
@@ -173,6 +281,9 @@ impl Plugin for GameCorePlugin {
                 (enforce_entity_limits.run_if(on_timer(Duration::from_millis(500))),).chain(),
             );
 
+        #[cfg(feature = "scripting")]
+        app.add_plugins(crate::plugins::ScriptingPlugin);
+
         #[cfg(feature = "debug-ui")]
         info!("✅ Game Core Plugin loaded with physics ordering and coordinate safety");
     }
@@ -216,10 +327,12 @@ fn validate_world_env_config(env: &crate::constants::WorldEnvConfig) {
     info!("✅ WorldEnvConfig validation passed");
 }
 
-fn load_world_configs(mut commands: Commands, mut config: ResMut<GameConfig>) {
-    use std::fs;
-    use std::path::Path;
-
+fn load_world_configs(
+    mut commands: Commands,
+    profile: Res<ActiveProfile>,
+    mut config: ResMut<GameConfig>,
+    mut reloaded: EventWriter<ConfigReloadedEvent>,
+) {
     let assets_base = crate::util::asset_path::get_assets_base_path();
     #[cfg(feature = "debug-ui")]
     info!("📁 Assets base path: {}", assets_base);
@@ -234,93 +347,24 @@ fn load_world_configs(mut commands: Commands, mut config: ResMut<GameConfig>) {
         warn!("⚠️ Config directory does not exist: {assets_base}/config");
     }
 
-    let configs = [
-        ("world_config.ron", "world environment"),
-        ("world_streaming.ron", "world streaming"),
-        ("world_physics.ron", "world physics"),
-        ("character_dimensions.ron", "character dimensions"),
-        ("world_bounds.ron", "world bounds"),
-    ];
-
-    for (filename, description) in configs.iter() {
-        let path = format!("{assets_base}/config/{filename}");
-        match fs::read_to_string(&path) {
-            Ok(contents) => match *filename {
-                "world_config.ron" => {
-                    match ron::from_str::<crate::constants::WorldEnvConfig>(&contents) {
-                        Ok(world_env_config) => {
-                            validate_world_env_config(&world_env_config);
-                            config.world_env = world_env_config.clone();
-                            commands.insert_resource(world_env_config);
-                            #[cfg(feature = "debug-ui")]
-                            info!("✅ Loaded {} config", description);
-                        }
-                        Err(e) => warn!("⚠️ Failed to parse {}: {}", description, e),
-                    }
-                }
-                "world_streaming.ron" => {
-                    match ron::from_str::<crate::config::WorldStreamingConfig>(&contents) {
-                        Ok(streaming_config) => {
-                            config.world.chunk_size = streaming_config.chunk_size;
-                            config.world.streaming_radius = streaming_config.streaming_radius;
-                            config.world.lod_distances = [
-                                streaming_config.lod_distances.full,
-                                streaming_config.lod_distances.medium,
-                                streaming_config.lod_distances.far,
-                            ];
-                            config.world_streaming = streaming_config;
-                            #[cfg(feature = "debug-ui")]
-                            info!("✅ Loaded {} config", description);
-                        }
-                        Err(e) => warn!("⚠️ Failed to parse {}: {}", description, e),
-                    }
-                }
-                "world_physics.ron" => {
-                    match ron::from_str::<crate::config::WorldPhysicsConfig>(&contents) {
-                        Ok(physics_config) => {
-                            config.world_physics = physics_config;
-                            #[cfg(feature = "debug-ui")]
-                            info!("✅ Loaded {} config", description);
-                        }
-                        Err(e) => warn!("⚠️ Failed to parse {}: {}", description, e),
-                    }
-                }
-                "world_bounds.ron" => {
-                    match ron::from_str::<crate::config::WorldBoundsConfig>(&contents) {
-                        Ok(bounds_config) => {
-                            config.world_bounds = bounds_config;
-                            config.world.map_size = config.world_bounds.world_half_size * 2.0;
-                            #[cfg(feature = "debug-ui")]
-                            info!("✅ Loaded {} config", description);
-                            #[cfg(feature = "debug-ui")]
-                            info!(
-                                "🔗 Unified world bounds: map_size = {}",
-                                config.world.map_size
-                            );
-                        }
-                        Err(e) => warn!("⚠️ Failed to parse {}: {}", description, e),
-                    }
-                }
-                "character_dimensions.ron" => {
-                    match ron::from_str::<crate::config::CharacterDimensionsConfig>(&contents) {
-                        Ok(char_config) => {
-                            config.character_dimensions = char_config;
-                            #[cfg(feature = "debug-ui")]
-                            info!("✅ Loaded {} config", description);
-                        }
-                        Err(e) => warn!("⚠️ Failed to parse {}: {}", description, e),
-                    }
-                }
-                _ => {}
-            },
-            Err(e) => {
-                info!("ℹ️ No {} config found, using defaults: {}", description, e);
-            }
+    let loaded = layer_config_files(&mut config, profile.config_directory().as_deref());
+    for (filename, description) in CONFIG_FILES {
+        if !loaded.contains(filename) {
+            info!("ℹ️ No {} config found, using defaults", description);
+        } else if *filename == "world_config.ron" {
+            validate_world_env_config(&config.world_env);
+            commands.insert_resource(config.world_env.clone());
         }
     }
+    #[cfg(feature = "debug-ui")]
+    info!(
+        "🔗 Unified world bounds: map_size = {}",
+        config.world.map_size
+    );
 
     // Validate and clamp all loaded config values
     config.validate_and_clamp();
+    reloaded.write(ConfigReloadedEvent);
     #[cfg(feature = "debug-ui")]
     info!("✅ Validated and clamped all configuration values");
 
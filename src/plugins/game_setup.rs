@@ -9,8 +9,10 @@ use crate::setup::{
 use crate::states::AppState;
 use crate::system_sets::GameSystemSets;
 use crate::systems::loading::{
-    advance_to_ingame, check_vehicle_specs_loaded, start_loading_vehicle_specs,
+    check_vehicle_specs_loaded, start_asset_preload, start_loading_vehicle_specs,
+    track_startup_assets,
 };
+use crate::systems::ui::splash_screen::AssetLoadingState;
 
 /// Plugin for organizing all startup and runtime systems with proper ordering
 pub struct GameSetupPlugin;
@@ -18,14 +20,15 @@ pub struct GameSetupPlugin;
 impl Plugin for GameSetupPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(AssetLoadingPolicy::for_build())
-            .add_systems(OnEnter(AppState::AssetLoading), start_loading_vehicle_specs)
+            .init_resource::<AssetLoadingState>()
             .add_systems(
-                Update,
-                check_vehicle_specs_loaded.run_if(in_state(AppState::AssetLoading)),
+                OnEnter(AppState::AssetLoading),
+                (start_loading_vehicle_specs, start_asset_preload),
             )
             .add_systems(
                 Update,
-                advance_to_ingame.run_if(in_state(AppState::WorldGeneration)),
+                (track_startup_assets, check_vehicle_specs_loaded)
+                    .run_if(in_state(AppState::AssetLoading)),
             )
             .configure_sets(
                 Startup,
@@ -36,7 +39,10 @@ impl Plugin for GameSetupPlugin {
             )
             .add_systems(
                 OnEnter(AppState::WorldGeneration),
-                (setup_basic_world, setup_dubai_noon_lighting).in_set(GameSystemSets::WorldSetup),
+                // Generation can be cancelled and retried; the base world is only built once
+                (setup_basic_world, setup_dubai_noon_lighting)
+                    .run_if(run_once)
+                    .in_set(GameSystemSets::WorldSetup),
             )
             .add_systems(
                 OnEnter(AppState::InGame),
@@ -0,0 +1,45 @@
+use crate::components::gameplay_events::{
+    GameplayEventHistory, MoneyChanged, NpcKilled, VehicleDestroyed, VehicleEntered, ZoneEntered,
+};
+use crate::states::AppState;
+use crate::systems::gameplay_events::{
+    emit_money_changed, emit_npc_killed, emit_vehicle_destroyed, emit_vehicle_entered,
+    emit_zone_entered, record_gameplay_events, register_gameplay_event_commands,
+};
+use bevy::prelude::*;
+
+/// Canonical gameplay events (`VehicleEntered`, `VehicleDestroyed`,
+/// `NpcKilled`, `ZoneEntered`, `MoneyChanged`) for plugins to react to
+/// without reaching into each other, plus the `GameplayEventHistory` ring
+/// buffer behind the `events` console command and script hooks
+pub struct GameplayEventsPlugin;
+
+impl Plugin for GameplayEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameplayEventHistory>()
+            .add_event::<VehicleEntered>()
+            .add_event::<VehicleDestroyed>()
+            .add_event::<NpcKilled>()
+            .add_event::<ZoneEntered>()
+            .add_event::<MoneyChanged>()
+            .add_systems(Startup, register_gameplay_event_commands)
+            .add_systems(
+                Update,
+                (
+                    (
+                        emit_vehicle_entered,
+                        emit_vehicle_destroyed,
+                        emit_npc_killed,
+                        emit_zone_entered,
+                    ),
+                    emit_money_changed,
+                    record_gameplay_events,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Gameplay Events Plugin loaded");
+    }
+}
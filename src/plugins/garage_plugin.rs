@@ -0,0 +1,46 @@
+use crate::components::{Garage, NearbyGarage};
+use crate::plugins::interaction_plugin::InteractionSet;
+use crate::states::AppState;
+use crate::systems::customization::apply_vehicle_customization;
+use crate::systems::garage::{
+    attach_garage_customization, close_distant_garage_menus, garage_buy_buttons,
+    garage_customize_buttons, garage_interaction_system, garage_menu_buttons,
+    register_owned_vehicles, spawn_garages, sync_garage_interactables,
+};
+use bevy::prelude::*;
+
+/// Owned vehicle records, world garages, vehicle recall and customization
+pub struct GaragePlugin;
+
+impl Plugin for GaragePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Garage>()
+            .init_resource::<NearbyGarage>()
+            .add_systems(OnEnter(AppState::InGame), spawn_garages)
+            .add_systems(
+                Update,
+                (close_distant_garage_menus, garage_interaction_system)
+                    .chain()
+                    .after(InteractionSet)
+                    .before(garage_menu_buttons)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    register_owned_vehicles,
+                    sync_garage_interactables,
+                    garage_menu_buttons,
+                    garage_buy_buttons,
+                    garage_customize_buttons,
+                    attach_garage_customization,
+                    apply_vehicle_customization,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Garage Plugin loaded");
+    }
+}
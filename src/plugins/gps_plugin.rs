@@ -0,0 +1,32 @@
+use crate::components::{GpsRoute, SetGpsDestination};
+use crate::states::AppState;
+use crate::systems::gps::{
+    follow_gps_route, set_gps_destination, spawn_gps_markers, update_gps_hud, update_gps_markers,
+};
+use bevy::prelude::*;
+
+/// GPS routes over the road network to a destination picked on the map,
+/// shown on the minimap and as arrows along the road
+pub struct GpsPlugin;
+
+impl Plugin for GpsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GpsRoute>()
+            .add_event::<SetGpsDestination>()
+            .add_systems(Startup, spawn_gps_markers)
+            .add_systems(
+                Update,
+                (
+                    set_gps_destination,
+                    follow_gps_route,
+                    update_gps_markers,
+                    update_gps_hud,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ GPS Plugin loaded");
+    }
+}
@@ -0,0 +1,30 @@
+use crate::config::GameConfig;
+use crate::systems::rendering::residency::{evict_far_clusters, restore_near_clusters};
+use crate::systems::rendering::upload_queue::{
+    GpuUploadQueue, configure_upload_budget, track_gpu_uploads,
+};
+use crate::systems::world::hlod::hlod_swap_system;
+use bevy::prelude::*;
+
+/// Mesh and texture uploads held to a per-frame time budget, and far
+/// building clusters' assets moved off the GPU while memory is over budget
+pub struct GpuStreamingPlugin;
+
+impl Plugin for GpuStreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GpuUploadQueue>().add_systems(
+            Update,
+            (
+                configure_upload_budget.run_if(resource_changed::<GameConfig>),
+                track_gpu_uploads,
+                restore_near_clusters,
+                evict_far_clusters,
+            )
+                .chain()
+                .after(hlod_swap_system),
+        );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ GPU Streaming Plugin loaded");
+    }
+}
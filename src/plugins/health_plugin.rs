@@ -0,0 +1,46 @@
+use crate::components::PlayerDamaged;
+use crate::plugins::input_plugin::InputProcessingSet;
+use crate::states::AppState;
+use crate::systems::movement::velocity_apply_system;
+use crate::systems::parachute::parachute_flight_system;
+use crate::systems::player_health::{
+    apply_player_damage, drowning_damage_system, fall_damage_system, player_health_regen_system,
+    spawn_hospitals, vehicle_impact_damage_system, wasted_system,
+};
+use crate::systems::ui::gameplay_ui::{
+    setup_death_fade, setup_health_bar, update_death_fade, update_health_bar,
+};
+use bevy::prelude::*;
+
+/// Player health: fall, vehicle and drowning damage, regeneration, the HUD
+/// health bar, and the death fade and hospital respawn
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayerDamaged>()
+            .add_systems(Startup, (setup_health_bar, setup_death_fade))
+            .add_systems(OnEnter(AppState::InGame), spawn_hospitals)
+            .add_systems(
+                Update,
+                (
+                    (
+                        fall_damage_system.after(parachute_flight_system),
+                        vehicle_impact_damage_system,
+                        drowning_damage_system,
+                    ),
+                    apply_player_damage,
+                    player_health_regen_system,
+                    wasted_system
+                        .after(InputProcessingSet)
+                        .before(velocity_apply_system),
+                    (update_health_bar, update_death_fade),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Health Plugin loaded");
+    }
+}
@@ -1,6 +1,6 @@
 use crate::systems::input::{
     LoadedVehicleControls, VehicleControlsConfig, asset_based_input_mapping_system,
-    controls_loaded, load_vehicle_controls_system, process_loaded_controls_system,
+    controls_loaded, load_input_map, load_vehicle_controls_system, process_loaded_controls_system,
 };
 use bevy::prelude::*;
 use bevy_common_assets::ron::RonAssetPlugin;
@@ -24,7 +24,7 @@ impl Plugin for InputPlugin {
 
         // Asset-based input systems - process assets then map input to ControlState
         // CRITICAL: Label this system so interaction systems can run after it
-        app.add_systems(Startup, load_vehicle_controls_system)
+        app.add_systems(Startup, (load_vehicle_controls_system, load_input_map))
             .add_systems(
                 Update,
                 (
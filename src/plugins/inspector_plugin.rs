@@ -1,9 +1,31 @@
+use crate::states::AppState;
+use crate::systems::ui::inspector::{
+    InspectorState, draw_inspector_selection, inspector_keys, pick_inspected_entities,
+    refresh_inspector, setup_inspector, update_inspector_ui,
+};
 use bevy::prelude::*;
 
+/// F4 entity inspector: pick entities, read and nudge their reflected
+/// components. With `debug-ui` the egui world inspector is added as well.
 pub struct InspectorPlugin;
 
 impl Plugin for InspectorPlugin {
-    fn build(&self, #[allow(unused_variables)] app: &mut App) {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InspectorState>()
+            .add_systems(Startup, setup_inspector)
+            .add_systems(
+                Update,
+                (
+                    inspector_keys,
+                    pick_inspected_entities,
+                    refresh_inspector,
+                    update_inspector_ui,
+                    draw_inspector_selection,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
         #[cfg(feature = "debug-ui")]
         {
             use bevy_inspector_egui::quick::WorldInspectorPlugin;
@@ -0,0 +1,46 @@
+use crate::states::AppState;
+use crate::systems::rendering::car_impostors::parked_car_impostor_system;
+use crate::systems::rendering::graphics_quality::{apply_lod_bias, apply_vegetation_density};
+use crate::systems::rendering::{
+    CarImpostor, Fence, InstanceKind, InstancedBatcher, PalmTree, Streetlight, TrafficCone,
+    upload_instances,
+};
+use bevy::prelude::*;
+
+/// Instanced batches for palm trees, street props and far parked car impostors,
+/// and the quality preset's LOD bias and vegetation density
+pub struct InstancingPlugin;
+
+impl Plugin for InstancingPlugin {
+    fn build(&self, app: &mut App) {
+        add_batch::<PalmTree>(app);
+        add_batch::<Streetlight>(app);
+        add_batch::<TrafficCone>(app);
+        add_batch::<Fence>(app);
+        add_batch::<CarImpostor>(app);
+
+        app.add_systems(
+            Update,
+            parked_car_impostor_system
+                .before(upload_instances::<CarImpostor>)
+                .run_if(in_state(AppState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                apply_lod_bias,
+                apply_vegetation_density.before(upload_instances::<PalmTree>),
+            ),
+        );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Instancing Plugin loaded");
+    }
+}
+
+/// Buffer resource plus its upload, run every frame so chunks generated
+/// during loading are drawn as soon as they exist
+fn add_batch<T: InstanceKind>(app: &mut App) {
+    app.init_resource::<InstancedBatcher<T>>()
+        .add_systems(Update, upload_instances::<T>);
+}
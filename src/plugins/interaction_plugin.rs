@@ -0,0 +1,41 @@
+use crate::components::{InteractionEvent, InteractionFocus};
+use crate::game_state::GameState;
+use crate::plugins::input_plugin::InputProcessingSet;
+use crate::states::AppState;
+use crate::systems::interaction_manager::{
+    clear_interaction_focus, dispatch_interaction_system, interaction_focus_system,
+};
+use crate::systems::ui::gameplay_ui::{setup_interaction_prompt, update_interaction_prompt};
+use bevy::prelude::*;
+
+/// Picks the focus and sends this frame's `InteractionEvent`; systems that
+/// react to interactions run after it
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InteractionSet;
+
+/// Prioritized interactables, the single context prompt and the events sent
+/// when one is used
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionFocus>()
+            .add_event::<InteractionEvent>()
+            .add_systems(Startup, setup_interaction_prompt)
+            .add_systems(
+                Update,
+                (interaction_focus_system, dispatch_interaction_system)
+                    .chain()
+                    .in_set(InteractionSet)
+                    .after(InputProcessingSet)
+                    .run_if(in_state(GameState::Walking))
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(Update, update_interaction_prompt.after(InteractionSet))
+            .add_systems(OnExit(GameState::Walking), clear_interaction_focus)
+            .add_systems(OnExit(AppState::InGame), clear_interaction_focus);
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Interaction Plugin loaded");
+    }
+}
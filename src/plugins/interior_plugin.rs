@@ -0,0 +1,49 @@
+use crate::components::CurrentInterior;
+use crate::plugins::input_plugin::InputProcessingSet;
+use crate::plugins::interaction_plugin::InteractionSet;
+use crate::states::AppState;
+use crate::systems::elevators::{
+    elevator_boarding_system, elevator_carry_system, elevator_floor_keys_system,
+    elevator_movement_system,
+};
+use crate::systems::interiors::{
+    exterior_culling_system, interior_door_system, sync_door_interactables,
+};
+use crate::systems::ui::gameplay_ui::{elevator_floor_buttons, elevator_panel_system};
+use bevy::prelude::*;
+
+/// Enterable building doors, interior scenes, elevators and exterior culling while inside
+pub struct InteriorPlugin;
+
+impl Plugin for InteriorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CurrentInterior>()
+            .add_systems(
+                Update,
+                (
+                    sync_door_interactables,
+                    interior_door_system.after(InteractionSet),
+                    exterior_culling_system,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    elevator_boarding_system,
+                    elevator_floor_keys_system,
+                    elevator_floor_buttons,
+                    elevator_movement_system,
+                    elevator_carry_system,
+                    elevator_panel_system,
+                )
+                    .chain()
+                    .after(InputProcessingSet)
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Interior Plugin loaded");
+    }
+}
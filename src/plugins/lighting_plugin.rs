@@ -0,0 +1,45 @@
+use crate::components::NightLights;
+use crate::plugins::input_plugin::InputProcessingSet;
+use crate::resources::WorldClock;
+use crate::states::AppState;
+use crate::systems::lighting::{
+    advance_world_clock, start_world_clock, sync_sun_to_clock, toggle_headlights,
+    update_night_lights, update_street_lamps, update_vehicle_lights,
+};
+use bevy::prelude::*;
+
+/// The day/night clock and the sun following it, plus headlights, brake
+/// lights and street lamps, switched on at night and kept to the light budget
+/// in `lighting.ron`
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NightLights>()
+            .init_resource::<WorldClock>()
+            .add_systems(Startup, start_world_clock)
+            .add_systems(
+                Update,
+                (
+                    advance_world_clock.run_if(in_state(AppState::InGame)),
+                    sync_sun_to_clock,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_night_lights,
+                    toggle_headlights,
+                    update_vehicle_lights,
+                    update_street_lamps,
+                )
+                    .chain()
+                    .after(InputProcessingSet)
+                    .after(sync_sun_to_clock),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Lighting Plugin loaded");
+    }
+}
@@ -0,0 +1,21 @@
+use crate::systems::logging::{apply_log_config, register_log_command};
+use crate::systems::ui::log_panel::{setup_log_panel, toggle_log_panel, update_log_panel};
+use bevy::prelude::*;
+
+/// Runtime side of the logging layer: applies `logging.ron`, adds the `log`
+/// console command and the F8 log panel. The layer itself is installed on
+/// Bevy's `LogPlugin` in `GameCorePlugin`.
+pub struct LoggingPlugin;
+
+impl Plugin for LoggingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (setup_log_panel, register_log_command))
+            .add_systems(
+                Update,
+                (apply_log_config, toggle_log_panel, update_log_panel).chain(),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Logging Plugin loaded");
+    }
+}
@@ -1,10 +1,17 @@
-use crate::components::{ActiveEntity, MapCamera, MapConfig, MinimapUI, PlayerMapIcon};
+use crate::components::{
+    ActiveEntity, AirfieldRegistry, GpsRoute, MapCamera, MapConfig, MapViewState, MinimapIcon,
+    MinimapRouteDot, MinimapUI, NPCState, PlayerMapIcon, SetGpsDestination, VehicleState,
+};
+use crate::config::{GameConfig, Indicator};
+use crate::systems::ui::LocalizedText;
+use crate::systems::world::terrain_height::TerrainHeightService;
 use bevy::math::{EulerRot, FloatOrd};
 use bevy::prelude::*;
 use bevy::render::camera::{ImageRenderTarget, RenderTarget};
 use bevy::render::render_resource::{
     Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
 };
+use bevy::ui::RelativeCursorPosition;
 
 pub struct MapPlugin;
 
@@ -26,7 +33,21 @@ impl Plugin for MapPlugin {
                 delayed_setup_minimap.run_if(resource_exists::<MapSetupTimer>),
             )
             .add_systems(Update, update_map_camera)
-            .add_systems(Update, update_player_icon);
+            .add_systems(Update, update_player_icon)
+            .add_systems(
+                Update,
+                (map_view_input, apply_map_view, update_minimap_icons)
+                    .chain()
+                    .run_if(resource_exists::<MapViewState>),
+            )
+            .add_systems(
+                Update,
+                (select_map_destination, update_minimap_route)
+                    .chain()
+                    .after(apply_map_view)
+                    .run_if(resource_exists::<MapViewState>)
+                    .run_if(resource_exists::<GpsRoute>),
+            );
     }
 }
 
@@ -64,9 +85,20 @@ fn load_map_config(mut commands: Commands) {
         }
     };
 
+    // Start at the configured zoom level if it's one of the presets
+    let zoom_index = config
+        .zoom_levels
+        .iter()
+        .position(|zoom| (*zoom - config.zoom_level).abs() < f32::EPSILON)
+        .unwrap_or(0);
+    commands.insert_resource(MapViewState {
+        zoom_index,
+        fullscreen: false,
+    });
     commands.insert_resource(config);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn delayed_setup_minimap(
     time: Res<Time>,
     mut timer: ResMut<MapSetupTimer>,
@@ -74,10 +106,11 @@ fn delayed_setup_minimap(
     mut images: ResMut<Assets<Image>>,
     asset_server: Res<AssetServer>,
     config: Res<MapConfig>,
+    view: Res<MapViewState>,
     minimap_query: Query<Entity, With<MinimapUI>>,
 ) {
     if timer.0.tick(time.delta()).just_finished() && minimap_query.is_empty() {
-        setup_minimap(&mut commands, &mut images, &asset_server, &config);
+        setup_minimap(&mut commands, &mut images, &asset_server, &config, &view);
         commands.remove_resource::<MapSetupTimer>();
     }
 }
@@ -87,6 +120,7 @@ fn setup_minimap(
     images: &mut ResMut<Assets<Image>>,
     asset_server: &Res<AssetServer>,
     config: &Res<MapConfig>,
+    view: &MapViewState,
 ) {
     let size = Extent3d {
         width: 512,
@@ -127,7 +161,7 @@ fn setup_minimap(
         },
         Projection::Orthographic(OrthographicProjection {
             scaling_mode: bevy::render::camera::ScalingMode::FixedVertical {
-                viewport_height: config.map_size,
+                viewport_height: config.view_size(view.zoom_index),
             },
             ..OrthographicProjection::default_3d()
         }),
@@ -151,6 +185,7 @@ fn setup_minimap(
             },
             BorderColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, config.background_alpha)),
+            RelativeCursorPosition::default(),
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -192,10 +227,59 @@ fn setup_minimap(
                 ));
             }
 
+            // Pooled GPS route trace, under the other markers
+            let route_color = config.route_color;
+            let half_dot = config.icon_size / 3.0;
+            for _ in 0..config.route_dots {
+                parent.spawn((
+                    MinimapRouteDot,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(half_dot * 2.0),
+                        height: Val::Px(half_dot * 2.0),
+                        margin: UiRect {
+                            left: Val::Px(-half_dot),
+                            top: Val::Px(-half_dot),
+                            ..default()
+                        },
+                        display: Display::None,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(
+                        route_color.0,
+                        route_color.1,
+                        route_color.2,
+                        route_color.3,
+                    )),
+                ));
+            }
+
+            // Pooled vehicle/NPC markers, positioned each frame by update_minimap_icons
+            let half_icon = config.icon_size / 2.0;
+            for _ in 0..config.max_icons {
+                parent.spawn((
+                    MinimapIcon,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(config.icon_size),
+                        height: Val::Px(config.icon_size),
+                        margin: UiRect {
+                            left: Val::Px(-half_icon),
+                            top: Val::Px(-half_icon),
+                            ..default()
+                        },
+                        display: Display::None,
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                ));
+            }
+
             // Cardinal direction labels - match actual minimap orientation
             // North at top
             parent.spawn((
-                Text::new("N"),
+                Text::default(),
+                LocalizedText::new("map.north"),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -215,7 +299,8 @@ fn setup_minimap(
 
             // West on right (camera orientation)
             parent.spawn((
-                Text::new("W"),
+                Text::default(),
+                LocalizedText::new("map.west"),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -235,7 +320,8 @@ fn setup_minimap(
 
             // South (bottom)
             parent.spawn((
-                Text::new("S"),
+                Text::default(),
+                LocalizedText::new("map.south"),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -255,7 +341,8 @@ fn setup_minimap(
 
             // East on left (camera orientation)
             parent.spawn((
-                Text::new("E"),
+                Text::default(),
+                LocalizedText::new("map.east"),
                 TextFont {
                     font_size: 14.0,
                     ..default()
@@ -307,3 +394,199 @@ fn update_player_icon(
     let (yaw, _pitch, _roll) = active_transform.rotation.to_euler(EulerRot::YXZ);
     icon_transform.rotation = Quat::from_rotation_z(-yaw + std::f32::consts::PI);
 }
+
+fn map_view_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<MapConfig>,
+    mut view: ResMut<MapViewState>,
+) {
+    if keys.just_pressed(KeyCode::KeyM) {
+        view.fullscreen = !view.fullscreen;
+    }
+
+    let max_index = config.zoom_levels.len().saturating_sub(1);
+    if keys.just_pressed(KeyCode::Equal) && view.zoom_index < max_index {
+        view.zoom_index += 1;
+    }
+    if keys.just_pressed(KeyCode::Minus) && view.zoom_index > 0 {
+        view.zoom_index -= 1;
+    }
+}
+
+fn apply_map_view(
+    view: Res<MapViewState>,
+    config: Res<MapConfig>,
+    mut camera_query: Query<&mut Projection, With<MapCamera>>,
+    mut ui_query: Query<&mut Node, With<MinimapUI>>,
+    added_ui: Query<(), Added<MinimapUI>>,
+) {
+    if !view.is_changed() && added_ui.is_empty() {
+        return;
+    }
+
+    if let Ok(mut projection) = camera_query.single_mut()
+        && let Projection::Orthographic(ortho) = projection.as_mut()
+    {
+        ortho.scaling_mode = bevy::render::camera::ScalingMode::FixedVertical {
+            viewport_height: config.view_size(view.zoom_index),
+        };
+    }
+
+    let Ok(mut node) = ui_query.single_mut() else {
+        return;
+    };
+    if view.fullscreen {
+        // Square map centered on screen, sized to 80% of the window height
+        node.width = Val::Vh(80.0);
+        node.height = Val::Vh(80.0);
+        node.bottom = Val::Vh(10.0);
+        node.right = Val::Percent(50.0);
+        node.margin.right = Val::Vh(-40.0);
+    } else {
+        node.width = Val::Px(config.ui_size.0);
+        node.height = Val::Px(config.ui_size.1);
+        node.bottom = Val::Px(config.ui_position.1);
+        node.right = Val::Px(config.ui_position.0);
+        node.margin.right = Val::Px(0.0);
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_minimap_icons(
+    view: Res<MapViewState>,
+    config: Res<MapConfig>,
+    game_config: Res<GameConfig>,
+    airfields: Res<AirfieldRegistry>,
+    active_query: Query<(Entity, &Transform), With<ActiveEntity>>,
+    vehicle_query: Query<(Entity, &GlobalTransform), With<VehicleState>>,
+    npc_query: Query<&GlobalTransform, With<NPCState>>,
+    mut icon_query: Query<(&mut Node, &mut BackgroundColor), With<MinimapIcon>>,
+) {
+    let Ok((active_entity, active_transform)) = active_query.single() else {
+        return;
+    };
+
+    let center = active_transform.translation;
+    let half_view = config.view_size(view.zoom_index) / 2.0;
+    let color = |indicator, c: (f32, f32, f32, f32)| {
+        game_config
+            .accessibility
+            .indicator(indicator, Color::srgba(c.0, c.1, c.2, c.3))
+    };
+    let airfield_color = color(Indicator::AirfieldIcon, config.airfield_icon_color);
+    let vehicle_color = color(Indicator::VehicleIcon, config.vehicle_icon_color);
+    let npc_color = color(Indicator::NpcIcon, config.npc_icon_color);
+
+    let airfield_icons = airfields
+        .iter()
+        .map(|entry| (entry.position, airfield_color));
+    let vehicles = vehicle_query
+        .iter()
+        .filter(|(entity, _)| *entity != active_entity)
+        .map(|(_, transform)| (transform.translation(), vehicle_color));
+    let npcs = npc_query
+        .iter()
+        .map(|transform| (transform.translation(), npc_color));
+
+    // Camera looks straight down with +Z up on screen, so world -X maps to screen right
+    let mut visible =
+        airfield_icons
+            .chain(vehicles)
+            .chain(npcs)
+            .filter_map(|(position, icon_color)| {
+                let offset = position - center;
+                (offset.x.abs() < half_view && offset.z.abs() < half_view).then(|| {
+                    let left = 50.0 - offset.x / half_view * 50.0;
+                    let top = 50.0 - offset.z / half_view * 50.0;
+                    (left, top, icon_color)
+                })
+            });
+
+    for (mut node, mut background) in icon_query.iter_mut() {
+        match visible.next() {
+            Some((left, top, icon_color)) => {
+                node.display = Display::Flex;
+                node.left = Val::Percent(left);
+                node.top = Val::Percent(top);
+                background.0 = icon_color;
+            }
+            None => node.display = Display::None,
+        }
+    }
+}
+
+/// Left click on the fullscreen map sets the GPS destination there, right
+/// click clears it
+fn select_map_destination(
+    mouse: Res<ButtonInput<MouseButton>>,
+    view: Res<MapViewState>,
+    config: Res<MapConfig>,
+    terrain: Option<Res<TerrainHeightService>>,
+    active_query: Query<&Transform, With<ActiveEntity>>,
+    ui_query: Query<&RelativeCursorPosition, With<MinimapUI>>,
+    mut destinations: EventWriter<SetGpsDestination>,
+) {
+    if !view.fullscreen {
+        return;
+    }
+    if mouse.just_pressed(MouseButton::Right) {
+        destinations.write(SetGpsDestination(None));
+        return;
+    }
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let (Ok(active_transform), Ok(cursor)) = (active_query.single(), ui_query.single()) else {
+        return;
+    };
+    let Some(normalized) = cursor.normalized.filter(|_| cursor.mouse_over()) else {
+        return;
+    };
+
+    // Inverse of the icon placement in update_minimap_icons
+    let half_view = config.view_size(view.zoom_index) / 2.0;
+    let center = active_transform.translation;
+    let x = center.x + (0.5 - normalized.x) * 2.0 * half_view;
+    let z = center.z + (0.5 - normalized.y) * 2.0 * half_view;
+    let y = terrain.map_or(center.y, |terrain| terrain.height_at(x, z));
+    destinations.write(SetGpsDestination(Some(Vec3::new(x, y, z))));
+}
+
+/// Spreads the route dots evenly along the part of the GPS route inside the
+/// minimap's view
+fn update_minimap_route(
+    view: Res<MapViewState>,
+    config: Res<MapConfig>,
+    route: Res<GpsRoute>,
+    active_query: Query<&Transform, With<ActiveEntity>>,
+    mut dot_query: Query<&mut Node, With<MinimapRouteDot>>,
+) {
+    let Ok(active_transform) = active_query.single() else {
+        return;
+    };
+    let center = active_transform.translation;
+    let half_view = config.view_size(view.zoom_index) / 2.0;
+    let spacing = half_view * 2.0 / config.route_dots.max(1) as f32;
+    let mut dots = route
+        .points_ahead(center, spacing, usize::MAX)
+        .into_iter()
+        .map(|(position, _)| position - center)
+        .filter(|offset| offset.x.abs() < half_view && offset.z.abs() < half_view)
+        .map(|offset| {
+            (
+                50.0 - offset.x / half_view * 50.0,
+                50.0 - offset.z / half_view * 50.0,
+            )
+        });
+
+    for mut node in dot_query.iter_mut() {
+        match dots.next() {
+            Some((left, top)) => {
+                node.display = Display::Flex;
+                node.left = Val::Percent(left);
+                node.top = Val::Percent(top);
+            }
+            None => node.display = Display::None,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::states::{AppState, MainMenuPage, MenuState};
+use crate::systems::ui::main_menu::{
+    close_main_menu, main_menu_actions, main_menu_keys, open_main_menu, spawn_new_game_page,
+    spawn_settings_page, spawn_title_page,
+};
+use crate::systems::ui::pause_menu::{
+    PendingRebind, apply_graphics_settings, capture_rebind_key, menu_button_system, pause_time,
+    pop_paused_state, push_paused_state, resume_time, spawn_controls_menu, spawn_pause_menu,
+    spawn_settings_menu, toggle_pause_menu, track_window_resize, update_menu_labels,
+};
+use bevy::prelude::*;
+
+/// Main menu (new game, continue, settings, quit) and the Esc pause menu
+/// with graphics settings and control remapping
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_sub_state::<MenuState>()
+            .enable_state_scoped_entities::<MenuState>()
+            .add_sub_state::<MainMenuPage>()
+            .enable_state_scoped_entities::<MainMenuPage>()
+            .init_resource::<PendingRebind>()
+            // Virtual time drives Update and FixedUpdate (physics), so pausing it freezes the world
+            .add_systems(OnExit(MenuState::Closed), (pause_time, push_paused_state))
+            .add_systems(OnEnter(MenuState::Closed), (resume_time, pop_paused_state))
+            // Quitting to the main menu from the pause menu never closes it
+            .add_systems(OnExit(AppState::InGame), pop_paused_state)
+            .add_systems(OnEnter(MenuState::Paused), spawn_pause_menu)
+            .add_systems(OnEnter(MenuState::Settings), spawn_settings_menu)
+            .add_systems(OnEnter(MenuState::Controls), spawn_controls_menu)
+            .add_systems(OnEnter(AppState::MainMenu), open_main_menu)
+            .add_systems(OnExit(AppState::MainMenu), close_main_menu)
+            .add_systems(OnEnter(MainMenuPage::Title), spawn_title_page)
+            .add_systems(OnEnter(MainMenuPage::NewGame), spawn_new_game_page)
+            .add_systems(OnEnter(MainMenuPage::Settings), spawn_settings_page)
+            .add_systems(
+                Update,
+                (
+                    (capture_rebind_key, toggle_pause_menu).run_if(in_state(AppState::InGame)),
+                    (main_menu_keys, main_menu_actions).run_if(in_state(AppState::MainMenu)),
+                    menu_button_system,
+                    update_menu_labels,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame).or(in_state(AppState::MainMenu))),
+            )
+            .add_systems(
+                Update,
+                (track_window_resize, apply_graphics_settings).chain(),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Menu Plugin loaded");
+    }
+}
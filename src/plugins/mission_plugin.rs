@@ -0,0 +1,43 @@
+use crate::components::cutscene_playing;
+use crate::components::mission::{
+    ActiveMission, MissionCompleted, MissionFailed, MissionProgress, MissionStarted,
+    ObjectiveCompleted, StartMissionRequest,
+};
+use crate::states::AppState;
+use crate::systems::missions::{
+    load_mission_catalog, mission_objective_system, mission_trigger_system, setup_mission_hud,
+    start_requested_missions, update_mission_hud,
+};
+use bevy::prelude::*;
+
+/// Data-driven missions loaded from `assets/config/missions.ron`.
+/// Emits `MissionStarted`, `ObjectiveCompleted`, `MissionCompleted` and `MissionFailed`
+/// so UI and other gameplay systems can react without touching mission state.
+pub struct MissionPlugin;
+
+impl Plugin for MissionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveMission>()
+            .init_resource::<MissionProgress>()
+            .add_event::<StartMissionRequest>()
+            .add_event::<MissionStarted>()
+            .add_event::<ObjectiveCompleted>()
+            .add_event::<MissionCompleted>()
+            .add_event::<MissionFailed>()
+            .add_systems(Startup, (load_mission_catalog, setup_mission_hud))
+            .add_systems(
+                Update,
+                (
+                    start_requested_missions,
+                    mission_trigger_system,
+                    mission_objective_system.run_if(not(cutscene_playing)),
+                    update_mission_hud,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Mission Plugin loaded");
+    }
+}
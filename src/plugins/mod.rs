@@ -15,17 +15,47 @@
 //! ### Core Plugins
 //! - `game_core`: Essential game systems and state management
 //! - `game_setup`: Initial world setup and configuration
+//! - `prefab_plugin`: Loads RON/JSON/TOML prefabs for `PrefabFactory`
 //!
 //! ### Gameplay Plugins
 //! - `player_plugin`: Player character control and state
 //! - `vehicle_plugin`: Vehicle physics and spawning
 //! - `unified_world_plugin`: World generation and terrain
-//! - `vegetation_lod_plugin`: Vegetation rendering and LOD
+//! - `instancing_plugin`: Instanced palm trees, street props and far parked car impostors
 //! - `water_plugin`: Water simulation and rendering
+//! - `mission_plugin`: Data-driven missions and objectives
+//! - `cutscene_plugin`: Keyframed camera cutscenes with letterbox and input lockout
+//! - `gameplay_events_plugin`: Vehicle, NPC, zone and money events plus their history
+//! - `economy_plugin`: Wallet, cash pickups, mission payouts and the money HUD
+//! - `persistence_plugin`: Save/load game slots
+//! - `garage_plugin`: Owned vehicles, world garages and vehicle recall
+//! - `health_plugin`: Player health, damage, death fade and hospital respawn
+//! - `shop_plugin`: Shops, properties for sale and their interaction prompts
+//! - `dialogue_plugin`: Talkable NPCs, RON dialogue trees and localized text
+//! - `interaction_plugin`: Prioritized interactables, the context prompt and interaction events
+//! - `interior_plugin`: Enterable buildings and exterior culling while inside
+//! - `traffic_plugin`: Ambient traffic AI on the road network
+//! - `police_plugin`: Wanted level and police pursuit
+//! - `race_plugin`: Checkpoint races against rubber-banded AI opponents
+//! - `rail_plugin`: Trains between stations, boarding and level crossings
+//! - `stunt_plugin`: Ramp jumps and time trials with trick scoring and best scores
+//! - `scripting_plugin`: Hot-reloaded Rhai hooks for mission and event logic (`scripting` feature)
+//! - `weather_plugin`: Data-driven rain, fog and wind
+//! - `audio_plugin`: Spatial engine/siren audio, city ambience and dynamic music
 //!
 //! ### Interface Plugins
 //! - `ui_plugin`: User interface and HUD
 //! - `input_plugin`: Input handling and mapping
+//! - `menu_plugin`: Main menu, pause menu, graphics settings and control remapping
+//! - `gps_plugin`: Road-following GPS route, minimap trace and world arrows
+//! - `console_plugin`: `~` cheat/debug console and its command registry
+//! - `inspector_plugin`: F4 entity picker with live component editing
+//! - `debug_gizmos_plugin`: F6+number gizmo layers for physics, LOD, culling and roads
+//! - `logging_plugin`: Runtime log levels, rolling log file and the F8 log panel
+//! - `crash_report_plugin`: World summary for crash reports and the next-launch report dialog
+//! - `telemetry_plugin`: Metrics export to a rolling CSV and a Prometheus endpoint
+//! - `accessibility_plugin`: Saved accessibility settings, subtitles and camera shake
+//! - `profile_plugin`: Player profiles with their own saves, settings and key bindings
 //!
 //! ### Utility Plugins
 //!
@@ -47,19 +77,55 @@
 //! 4. Communicate via events, not direct calls
 //! 5. Add to this mod.rs file
 
+pub mod accessibility_plugin;
+pub mod audio_plugin;
+pub mod console_plugin;
+pub mod crash_report_plugin;
+pub mod cutscene_plugin;
+pub mod debug_gizmos_plugin;
+pub mod decal_plugin;
+pub mod dialogue_plugin;
+pub mod economy_plugin;
 pub mod game_core;
 pub mod game_setup;
+pub mod gameplay_events_plugin;
+pub mod garage_plugin;
+pub mod gps_plugin;
+pub mod gpu_streaming_plugin;
+pub mod health_plugin;
 pub mod input_plugin;
+pub mod instancing_plugin;
+pub mod interaction_plugin;
+pub mod logging_plugin;
+pub mod interior_plugin;
 pub mod inspector_plugin;
+pub mod lighting_plugin;
 pub mod map_plugin;
+pub mod menu_plugin;
+pub mod mission_plugin;
 pub mod particle_plugin;
+pub mod persistence_plugin;
 pub mod player_plugin;
+pub mod police_plugin;
+pub mod post_processing_plugin;
+pub mod prefab_plugin;
+pub mod profile_plugin;
+pub mod race_plugin;
+pub mod rail_plugin;
+pub mod reflection_plugin;
+#[cfg(feature = "scripting")]
+pub mod scripting_plugin;
+pub mod shop_plugin;
 pub mod skybox_plugin;
+pub mod stunt_plugin;
+pub mod telemetry_plugin;
+pub mod traffic_plugin;
 pub mod ui_plugin;
 pub mod underwater_plugin;
 pub mod unified_world_plugin;
 pub mod vehicle_plugin;
 pub mod water_plugin;
+pub mod weather_plugin;
 
 // New focused world plugins
 pub mod physics_activation_plugin;
@@ -68,14 +134,49 @@ pub mod world_debug_plugin;
 pub mod world_npc_plugin;
 
 // Core game plugins
+pub use accessibility_plugin::AccessibilityPlugin;
+pub use audio_plugin::AudioPlugin;
+pub use console_plugin::ConsolePlugin;
+pub use cutscene_plugin::CutscenePlugin;
+pub use economy_plugin::EconomyPlugin;
 pub use game_core::GameCorePlugin;
 pub use game_setup::GameSetupPlugin;
+pub use gameplay_events_plugin::GameplayEventsPlugin;
+pub use garage_plugin::GaragePlugin;
+pub use gps_plugin::GpsPlugin;
+pub use gpu_streaming_plugin::GpuStreamingPlugin;
+pub use health_plugin::HealthPlugin;
+pub use shop_plugin::ShopPlugin;
+pub use dialogue_plugin::DialoguePlugin;
 pub use input_plugin::InputPlugin;
+pub use instancing_plugin::InstancingPlugin;
+pub use interaction_plugin::InteractionPlugin;
+pub use interior_plugin::InteriorPlugin;
+pub use crash_report_plugin::CrashReportPlugin;
+pub use debug_gizmos_plugin::DebugGizmosPlugin;
+pub use decal_plugin::DecalPlugin;
 pub use inspector_plugin::InspectorPlugin;
+pub use lighting_plugin::LightingPlugin;
+pub use logging_plugin::LoggingPlugin;
 pub use map_plugin::MapPlugin;
+pub use menu_plugin::MenuPlugin;
+pub use mission_plugin::MissionPlugin;
 pub use particle_plugin::ParticlePlugin;
+pub use persistence_plugin::PersistencePlugin;
 pub use player_plugin::PlayerPlugin;
+pub use police_plugin::PolicePlugin;
+pub use post_processing_plugin::PostProcessingPlugin;
+pub use prefab_plugin::PrefabPlugin;
+pub use profile_plugin::ProfilePlugin;
+pub use race_plugin::RacePlugin;
+pub use rail_plugin::RailPlugin;
+pub use reflection_plugin::ReflectionPlugin;
+#[cfg(feature = "scripting")]
+pub use scripting_plugin::ScriptingPlugin;
 pub use skybox_plugin::SkyboxPlugin;
+pub use stunt_plugin::StuntPlugin;
+pub use telemetry_plugin::TelemetryPlugin;
+pub use traffic_plugin::TrafficPlugin;
 pub use ui_plugin::UIPlugin;
 pub use underwater_plugin::UnderwaterPlugin;
 
@@ -84,6 +185,7 @@ pub use unified_world_plugin::UnifiedWorldPlugin;
 
 pub use vehicle_plugin::VehiclePlugin;
 pub use water_plugin::WaterPlugin;
+pub use weather_plugin::WeatherPlugin;
 
 // Specialized world plugins
 pub use physics_activation_plugin::PhysicsActivationPlugin;
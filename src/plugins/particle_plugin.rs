@@ -1,10 +1,37 @@
+use crate::states::AppState;
+use crate::systems::effects::particles::{
+    CpuParticleMaterial, GpuParticleEffects, ParticleEmitterPool, SpawnParticleBurst,
+    burst_on_vehicle_destroyed, clear_particle_pool, drive_gpu_emitters, recycle_pooled_emitters,
+    select_particle_backend, simulate_cpu_particles, spawn_particle_bursts,
+};
 use bevy::prelude::*;
 
+/// Runs prefab-defined `ParticleEmitter`s on the GPU through hanabi or on the
+/// CPU, and pools one-shot bursts. `HanabiPlugin` itself is added by
+/// `GameCorePlugin`.
 pub struct ParticlePlugin;
 
 impl Plugin for ParticlePlugin {
-    fn build(&self, _app: &mut App) {
-        // REMOVED: Duplicate HanabiPlugin - already in GameCorePlugin
-        // All particle systems are registered in VehiclePlugin
+    fn build(&self, app: &mut App) {
+        app.add_event::<SpawnParticleBurst>()
+            .init_resource::<GpuParticleEffects>()
+            .init_resource::<CpuParticleMaterial>()
+            .init_resource::<ParticleEmitterPool>()
+            .add_systems(
+                Update,
+                (
+                    burst_on_vehicle_destroyed,
+                    spawn_particle_bursts,
+                    select_particle_backend,
+                    drive_gpu_emitters,
+                    simulate_cpu_particles,
+                    recycle_pooled_emitters,
+                )
+                    .chain(),
+            )
+            .add_systems(OnExit(AppState::InGame), clear_particle_pool);
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Particle Plugin loaded");
     }
 }
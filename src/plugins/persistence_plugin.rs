@@ -0,0 +1,29 @@
+use crate::states::AppState;
+use crate::systems::persistence::{
+    LoadGameRequest, PendingLoad, SaveGameRequest, load_game_system, quick_save_input_system,
+    request_pending_load, save_game_system,
+};
+use bevy::prelude::*;
+
+/// Slot-based save/load driven by `SaveGameRequest` and `LoadGameRequest` events,
+/// plus the main menu's Continue, which loads its slot on entering the game.
+/// Requires `MissionPlugin` for mission progress resources.
+pub struct PersistencePlugin;
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveGameRequest>()
+            .add_event::<LoadGameRequest>()
+            .init_resource::<PendingLoad>()
+            .add_systems(OnEnter(AppState::InGame), request_pending_load)
+            .add_systems(
+                Update,
+                (quick_save_input_system, save_game_system, load_game_system)
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Persistence Plugin loaded");
+    }
+}
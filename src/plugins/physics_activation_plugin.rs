@@ -4,12 +4,13 @@ use std::time::Duration;
 
 use crate::states::AppState;
 use crate::systems::world::physics_activation::{
-    activate_nearby_building_physics, deactivate_distant_building_physics,
-    disable_distant_dynamic_physics, enable_nearby_dynamic_physics,
+    activate_nearby_building_physics, clean_up_resting_props, deactivate_distant_building_physics,
+    disable_distant_dynamic_physics, enable_nearby_dynamic_physics, knock_loose_struck_props,
 };
 
 /// Physics activation plugin - GTA-style dynamic physics
-/// Only activates physics for buildings near the player
+/// Only activates physics for buildings near the player, and turns street
+/// props into dynamic bodies only when a vehicle hits them
 pub struct PhysicsActivationPlugin;
 
 impl Plugin for PhysicsActivationPlugin {
@@ -25,6 +26,13 @@ impl Plugin for PhysicsActivationPlugin {
                 .chain()
                 .run_if(in_state(AppState::InGame))
                 .run_if(on_timer(Duration::from_millis(200))),
+        )
+        // Every frame: a prop has to be loose before the car reaches it
+        .add_systems(
+            Update,
+            (knock_loose_struck_props, clean_up_resting_props)
+                .chain()
+                .run_if(in_state(AppState::InGame)),
         );
 
         #[cfg(feature = "debug-ui")]
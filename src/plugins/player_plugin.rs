@@ -1,11 +1,15 @@
 use crate::plugins::input_plugin::InputProcessingSet;
+use crate::plugins::interaction_plugin::InteractionSet;
 use crate::systems::audio::{cleanup_footstep_sounds, footstep_system};
-use crate::systems::camera::camera_follow_system;
-use crate::systems::interaction::interaction_system;
+use crate::systems::camera_rig::{CameraRig, camera_rig_system, cycle_camera_mode};
+use crate::systems::interaction::{
+    interaction_system, sync_vehicle_interactables, vehicle_entry_system,
+};
 use crate::systems::movement::{
     PlayerInputData, animation_flag_system, human_player_animation, read_input_system,
     velocity_apply_system,
 };
+use crate::systems::parachute::{ejection_system, freefall_entry_system, parachute_flight_system};
 use bevy::prelude::*;
 
 use crate::game_state::GameState;
@@ -18,6 +22,13 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<CameraRig>().add_systems(
+            Update,
+            (
+                sync_vehicle_interactables.before(InteractionSet),
+                vehicle_entry_system.after(InteractionSet),
+            ),
+        );
         app.init_resource::<PlayerInputData>().add_systems(
             Update,
             (
@@ -34,9 +45,16 @@ impl Plugin for PlayerPlugin {
                     .run_if(in_state(GameState::Walking).or(in_state(GameState::Swimming))),
                 footstep_system.run_if(in_state(GameState::Walking)),
                 cleanup_footstep_sounds,
-                camera_follow_system,
+                (
+                    cycle_camera_mode.after(InputProcessingSet),
+                    camera_rig_system,
+                )
+                    .chain(),
                 // CRITICAL: Run interaction_system AFTER input processing
                 interaction_system.after(InputProcessingSet),
+                ejection_system.after(InputProcessingSet),
+                freefall_entry_system,
+                parachute_flight_system.after(InputProcessingSet),
                 debug_game_state,
                 (
                     player_collision_resolution_system,
@@ -0,0 +1,37 @@
+use crate::components::{CrimeCommitted, WantedLevel, WantedLevelChanged};
+use crate::states::AppState;
+use crate::systems::car_alarms::update_car_alarms;
+use crate::systems::police::{
+    detect_player_crimes, police_pursuit_system, setup_wanted_hud, spawn_police_units,
+    update_wanted_hud, update_wanted_level,
+};
+use bevy::prelude::*;
+
+/// Wanted level escalation, pursuit cars, the HUD star indicator and the car
+/// alarms that give a wanted player away. Tuned through `GameConfig::police`.
+pub struct PolicePlugin;
+
+impl Plugin for PolicePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WantedLevel>()
+            .add_event::<CrimeCommitted>()
+            .add_event::<WantedLevelChanged>()
+            .add_systems(Startup, setup_wanted_hud)
+            .add_systems(
+                Update,
+                (
+                    detect_player_crimes,
+                    update_car_alarms,
+                    update_wanted_level,
+                    spawn_police_units,
+                    police_pursuit_system,
+                    update_wanted_hud,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Police Plugin loaded");
+    }
+}
@@ -0,0 +1,43 @@
+use crate::components::{DamageVignette, UnderwaterSettings};
+use crate::systems::rendering::post_pass::{PostPass, PostPassLabel, PostPassPlugin};
+use crate::systems::rendering::post_processing::{
+    apply_post_processing, update_damage_vignette, update_motion_blur,
+};
+use bevy::core_pipeline::core_3d::graph::Node3d;
+use bevy::prelude::*;
+use bevy::render::render_graph::{InternedRenderLabel, RenderLabel};
+
+impl PostPass for DamageVignette {
+    const NAME: &'static str = "damage_vignette";
+    const SHADER: &'static str = "shaders/damage_vignette.wgsl";
+
+    // Tint what the underwater fog left, not the other way round
+    fn between() -> (InternedRenderLabel, InternedRenderLabel) {
+        (
+            PostPassLabel(UnderwaterSettings::NAME).intern(),
+            Node3d::Tonemapping.intern(),
+        )
+    }
+}
+
+/// Tonemapping, exposure, bloom and motion blur on the main camera from
+/// `post_processing.ron` and the quality preset, plus the damage vignette
+pub struct PostProcessingPlugin;
+
+impl Plugin for PostProcessingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PostPassPlugin::<DamageVignette>::default())
+            .add_systems(
+                Update,
+                (
+                    apply_post_processing,
+                    update_motion_blur,
+                    update_damage_vignette,
+                )
+                    .chain(),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Post-Processing Plugin loaded");
+    }
+}
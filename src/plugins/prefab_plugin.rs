@@ -0,0 +1,54 @@
+use crate::components::{ParticleEmitter, StreetProp, VehicleCustomization};
+use crate::factories::prefab_cache::prefab_cache_path;
+use crate::factories::prefab_validation::check_custom_components;
+use crate::factories::{PrefabAssetCache, PrefabComponentRegistry, PrefabRegistry};
+use crate::register_components;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Loads RON/JSON/TOML prefabs from `assets/prefabs` into the `PrefabRegistry` and shares
+/// their meshes and materials through `PrefabAssetCache`; with the `prefab-hot-reload`
+/// feature, edits to those files patch live entities
+pub struct PrefabPlugin;
+
+impl Plugin for PrefabPlugin {
+    fn build(&self, app: &mut App) {
+        let mut components = PrefabComponentRegistry::default();
+        register_components!(
+            components,
+            VehicleCustomization,
+            StreetProp,
+            ParticleEmitter
+        );
+
+        app.init_resource::<PrefabRegistry>()
+            .init_resource::<PrefabAssetCache>()
+            .insert_resource(components)
+            .add_systems(Startup, load_prefabs);
+
+        #[cfg(feature = "prefab-hot-reload")]
+        app.add_systems(
+            Startup,
+            crate::factories::prefab_hot_reload::start_prefab_watcher,
+        )
+        .add_systems(
+            Update,
+            crate::factories::prefab_hot_reload::reload_changed_prefabs,
+        );
+    }
+}
+
+fn load_prefabs(mut registry: ResMut<PrefabRegistry>, components: Res<PrefabComponentRegistry>) {
+    let dir = PathBuf::from(crate::util::asset_path::get_assets_base_path()).join("prefabs");
+    match registry.load_directory_cached(&dir, &prefab_cache_path()) {
+        Ok(count) => info!("📦 Loaded {} prefabs from {}", count, dir.display()),
+        Err(e) => error!("Failed to load prefabs: {}", e),
+    }
+
+    for (id, definition) in registry.iter() {
+        for problem in check_custom_components(definition, &components) {
+            let name = registry.name_of(id).unwrap_or("<unnamed>");
+            warn!("⚠️ Prefab '{}': {}", name, problem);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::states::AppState;
+use crate::systems::accessibility::reload_profile_accessibility;
+use crate::systems::input::reload_profile_input_map;
+use crate::systems::profiles::{ActiveProfile, ProfileChangedEvent, refresh_latest_save};
+use crate::systems::stunts::reload_profile_stunt_scores;
+use crate::systems::ui::main_menu::switch_profile;
+use bevy::prelude::*;
+
+/// Player profiles picked from the main menu. Switching reloads the
+/// profile's accessibility settings, key bindings, stunt scores and save
+/// list; see `systems::profiles` for where each profile keeps its files.
+/// Inserts the `ActiveProfile` that saves, settings and config are read from.
+/// Requires `AccessibilityPlugin` and `StuntPlugin`.
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ActiveProfile::from_environment())
+            .add_event::<ProfileChangedEvent>()
+            .add_systems(
+                Update,
+                (
+                    switch_profile.run_if(in_state(AppState::MainMenu)),
+                    (
+                        reload_profile_accessibility,
+                        reload_profile_input_map,
+                        reload_profile_stunt_scores,
+                        refresh_latest_save,
+                    ),
+                )
+                    .chain(),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Profile Plugin loaded");
+    }
+}
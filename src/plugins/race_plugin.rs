@@ -0,0 +1,50 @@
+use crate::components::race::{RaceFinished, RaceManager, StartRaceRequest};
+use crate::plugins::input_plugin::InputProcessingSet;
+use crate::states::AppState;
+use crate::systems::racing::{
+    RacingLineRecorder, clear_race_entities, enter_races_at_markers, hold_player_at_start,
+    load_race_catalog, race_opponent_ai, race_progress_system, record_racing_line,
+    register_race_commands, setup_race_hud, spawn_race_start_markers, start_requested_races,
+    update_checkpoint_visibility, update_race_hud,
+};
+use bevy::prelude::*;
+
+/// Checkpoint races against AI opponents, defined in `assets/config/races`.
+/// Emits `RaceFinished` when the player finishes or abandons a race.
+pub struct RacePlugin;
+
+impl Plugin for RacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RaceManager>()
+            .init_resource::<RacingLineRecorder>()
+            .add_event::<StartRaceRequest>()
+            .add_event::<RaceFinished>()
+            .add_systems(
+                Startup,
+                (
+                    (load_race_catalog, spawn_race_start_markers).chain(),
+                    setup_race_hud,
+                    register_race_commands,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    enter_races_at_markers,
+                    start_requested_races,
+                    hold_player_at_start.after(InputProcessingSet),
+                    race_progress_system,
+                    race_opponent_ai,
+                    update_checkpoint_visibility,
+                    clear_race_entities,
+                    update_race_hud,
+                    record_racing_line,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Race Plugin loaded");
+    }
+}
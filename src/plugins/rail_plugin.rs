@@ -0,0 +1,44 @@
+use crate::components::RailNetwork;
+use crate::game_state::GameState;
+use crate::plugins::interaction_plugin::InteractionSet;
+use crate::states::AppState;
+use crate::systems::camera_rig::camera_rig_system;
+use crate::systems::rail::{
+    carry_train_passengers, detect_level_crossings, spawn_rail_network, sync_train_interactables,
+    train_boarding_system, train_car_collisions, train_movement_system, update_crossing_lights,
+};
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+
+/// Trains on the rail lines in `assets/config/rail.ron`: stations, boarding
+/// for fast travel, level crossings and cars struck on the track
+pub struct RailPlugin;
+
+impl Plugin for RailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RailNetwork>()
+            .add_systems(OnEnter(AppState::InGame), spawn_rail_network)
+            .add_systems(
+                Update,
+                (
+                    detect_level_crossings.run_if(on_timer(Duration::from_secs(1))),
+                    train_boarding_system
+                        .after(InteractionSet)
+                        .run_if(in_state(GameState::Walking)),
+                    (
+                        train_movement_system,
+                        carry_train_passengers.before(camera_rig_system),
+                    )
+                        .chain(),
+                    train_car_collisions.after(train_movement_system),
+                    update_crossing_lights,
+                    sync_train_interactables.after(train_movement_system),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Rail Plugin loaded");
+    }
+}
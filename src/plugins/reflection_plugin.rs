@@ -0,0 +1,29 @@
+use crate::factories::ReflectiveMaterials;
+use crate::systems::rendering::reflections::{
+    opt_in_car_paint, setup_sky_probe, update_reflective_materials, update_sky_probe,
+};
+use bevy::prelude::*;
+
+/// Time-of-day sky probe on the main camera and the material side of
+/// screen-space reflections: clear-coated car paint and roads that get
+/// glossier in the rain
+pub struct ReflectionPlugin;
+
+impl Plugin for ReflectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReflectiveMaterials>()
+            .add_systems(Startup, setup_sky_probe)
+            .add_systems(
+                Update,
+                (
+                    opt_in_car_paint,
+                    update_reflective_materials,
+                    update_sky_probe,
+                )
+                    .chain(),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Reflection Plugin loaded");
+    }
+}
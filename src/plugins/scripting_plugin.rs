@@ -0,0 +1,32 @@
+use crate::states::AppState;
+use crate::systems::scripting::{
+    ScriptHud, apply_script_actions, load_scripts, reload_changed_scripts, run_script_hooks,
+    setup_script_hud, update_script_hud,
+};
+use bevy::prelude::*;
+
+/// Rhai scripts from `assets/prefabs/*.rhai`, hot-reloaded on save, with hooks
+/// for mission events and a small API for spawning prefabs, objectives, sounds
+/// and on-screen text
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScriptHud>()
+            .add_systems(Startup, (load_scripts, setup_script_hud))
+            .add_systems(
+                Update,
+                (
+                    reload_changed_scripts,
+                    run_script_hooks,
+                    apply_script_actions,
+                    update_script_hud,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Scripting Plugin loaded");
+    }
+}
@@ -0,0 +1,46 @@
+use crate::components::{OpenShopRequest, OwnedProperties, PlayerOutfit, ShopCatalogs};
+use crate::plugins::interaction_plugin::InteractionSet;
+use crate::states::AppState;
+use crate::systems::shops::{
+    apply_player_outfit, close_distant_shop_menus, load_shop_catalogs, open_requested_shops,
+    shop_interaction_system, shop_menu_buttons, spawn_properties, sync_property_garages,
+    sync_property_interactables, sync_shop_interactables,
+};
+use bevy::prelude::*;
+
+/// Storefronts with RON catalogs, properties for sale and the player's outfit
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShopCatalogs>()
+            .init_resource::<OwnedProperties>()
+            .init_resource::<PlayerOutfit>()
+            .add_event::<OpenShopRequest>()
+            .add_systems(Startup, load_shop_catalogs)
+            .add_systems(OnEnter(AppState::InGame), spawn_properties)
+            .add_systems(
+                Update,
+                (close_distant_shop_menus, shop_interaction_system)
+                    .chain()
+                    .after(InteractionSet)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    open_requested_shops,
+                    shop_menu_buttons,
+                    sync_property_garages,
+                    sync_shop_interactables,
+                    sync_property_interactables,
+                    apply_player_outfit,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Shop Plugin loaded");
+    }
+}
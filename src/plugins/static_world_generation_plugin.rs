@@ -1,22 +1,32 @@
 use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
 
-use crate::components::unified_water::UnifiedWaterBody;
+use crate::components::{HlodClusters, HlodProxy};
 use crate::config::GameConfig;
-use crate::constants::WorldEnvConfig;
-use crate::resources::{MaterialRegistry, WorldRng};
 use crate::states::AppState;
 
-use crate::systems::spawn_validation::SpawnRegistry;
-// use crate::systems::ui::loading_screen::{
-//     cleanup_loading_screen, setup_loading_screen, update_loading_progress,
-// };
-use crate::systems::world::generators::{
-    BuildingGenerator, RoadGenerator, VegetationGenerator, VehicleGenerator,
+use crate::systems::rendering::{InstancedBatcher, PalmTree};
+use crate::systems::ui::loading_screen::{
+    cancel_world_generation, cleanup_loading_screen, setup_loading_screen, update_loading_progress,
 };
-use crate::systems::world::unified_world::{ChunkCoord, ChunkState, UnifiedWorldManager};
+use crate::systems::world::chunk_eviction::{
+    evict_streamed_out_chunks, restore_streamed_in_chunks,
+};
+use crate::systems::world::chunk_streaming::{
+    ChunkGenerationContext, ChunkStreamingProvider, StreamingFocus, prioritize_pending_chunks,
+    stream_remaining_chunks, update_streaming_focus,
+};
+use crate::systems::world::districts::load_district_map;
+use crate::systems::world::generators::StreetPropBatchers;
+use crate::systems::world::unified_world::{
+    ChunkCoord, ChunkState, UnifiedChunkEntity, UnifiedWorldManager,
+};
+use crate::util::morton::ZOrderCells;
 
-/// Static world generation plugin - generates all chunks at startup
-/// Uses Loading state to parallelize generation without blocking window
+/// World generation plugin - generates the chunks around the camera at startup,
+/// then streams the remainder in-game with a per-frame budget, tearing down
+/// chunk-owned dynamic content as its chunk streams out
 pub struct StaticWorldGenerationPlugin;
 
 impl Plugin for StaticWorldGenerationPlugin {
@@ -24,10 +34,26 @@ impl Plugin for StaticWorldGenerationPlugin {
         app
             // Note: SpawnRegistry is already initialized by SpawnValidationPlugin
             // World generation screen UI (camera stays active for UI rendering)
-            // .add_systems(OnEnter(AppState::WorldGeneration), setup_loading_screen)
+            .add_systems(OnEnter(AppState::WorldGeneration), setup_loading_screen)
             .add_systems(
                 OnExit(AppState::WorldGeneration),
-                cleanup_generation_resources,
+                (cleanup_loading_screen, cleanup_generation_resources),
+            )
+            .add_systems(
+                Update,
+                (
+                    cancel_world_generation,
+                    update_loading_progress.after(apply_generated_chunks),
+                )
+                    .run_if(in_state(AppState::WorldGeneration)),
+            )
+            // Cancelling drops the partial world so the next attempt starts clean
+            .add_systems(
+                OnTransition {
+                    exited: AppState::WorldGeneration,
+                    entered: AppState::MainMenu,
+                },
+                discard_generated_world,
             )
             .add_systems(OnExit(AppState::AssetLoading), cleanup_generation_resources)
             // World generation systems
@@ -35,30 +61,91 @@ impl Plugin for StaticWorldGenerationPlugin {
                 OnEnter(AppState::WorldGeneration),
                 queue_all_chunks_for_generation,
             )
+            .add_systems(Startup, load_district_map)
+            .init_resource::<ChunkStreamingProvider>()
+            .init_resource::<StreamingFocus>()
+            .add_systems(
+                Update,
+                (update_streaming_focus, prioritize_pending_chunks)
+                    .chain()
+                    .before(apply_generated_chunks)
+                    .before(stream_remaining_chunks),
+            )
             .add_systems(
                 Update,
                 apply_generated_chunks.run_if(in_state(AppState::WorldGeneration)),
+            )
+            .add_systems(
+                Update,
+                stream_remaining_chunks.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (evict_streamed_out_chunks, restore_streamed_in_chunks)
+                    .chain()
+                    .after(update_streaming_focus)
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(on_timer(Duration::from_millis(500))),
             );
     }
 }
 
-/// Temporary resource to track static generation progress
-#[derive(Resource)]
-pub struct StaticGenerationQueue {
-    /// Total chunks to generate
+/// Startup generation progress, updated after every batch of chunks
+#[derive(Resource, Debug)]
+pub struct WorldGenProgress {
+    /// Island chunks queued for generation, including those left to stream in-game
     pub total_chunks: usize,
-    /// Chunks completed
+    /// Chunks generated so far
     pub completed_chunks: usize,
-    /// Start time for progress reporting
+    /// Chunks still pending inside the startup radius, counted after each
+    /// batch (every queued chunk before the first); play starts at zero
+    pub startup_remaining: usize,
     pub start_time: std::time::Instant,
 }
 
+impl WorldGenProgress {
+    pub fn new(total_chunks: usize) -> Self {
+        Self {
+            total_chunks,
+            completed_chunks: 0,
+            startup_remaining: total_chunks,
+            start_time: std::time::Instant::now(),
+        }
+    }
+
+    /// Share of the startup area generated, 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        let needed = self.completed_chunks + self.startup_remaining;
+        if needed == 0 {
+            1.0
+        } else {
+            self.completed_chunks as f32 / needed as f32
+        }
+    }
+
+    pub fn chunks_per_second(&self) -> f32 {
+        let elapsed = self.start_time.elapsed().as_secs_f32();
+        if elapsed > 0.0 {
+            self.completed_chunks as f32 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Seconds until the startup area is done at the current rate
+    pub fn eta_secs(&self) -> Option<f32> {
+        let rate = self.chunks_per_second();
+        (rate > 0.0).then(|| self.startup_remaining as f32 / rate)
+    }
+}
+
 /// Queue all chunks for generation at startup
-/// Creates bounded parallel generation (max 16 concurrent tasks)
+/// Island chunks are marked Loading and handed to the streaming provider
 fn queue_all_chunks_for_generation(
     mut commands: Commands,
     config: Res<GameConfig>,
     mut world_manager: ResMut<UnifiedWorldManager>,
+    mut provider: ResMut<ChunkStreamingProvider>,
 ) {
     info!(
         "Starting static world generation for {}x{} chunks",
@@ -71,19 +158,22 @@ fn queue_all_chunks_for_generation(
     let margin = 200.0; // 1 chunk margin for beaches and nearshore content
 
     let mut total_count = 0;
-    for array_z in 0..config.world.total_chunks_z {
-        for array_x in 0..config.world.total_chunks_x {
-            let chunk_x = array_x as i32 - half_x;
-            let chunk_z = array_z as i32 - half_z;
-            let coord = ChunkCoord::new(chunk_x, chunk_z);
-
-            // Check if chunk is on a terrain island (skip ocean chunks)
-            let chunk_center = coord.to_world_pos_with_size(world_manager.chunk_size);
-            if world_manager.is_on_terrain_island_with_margin(chunk_center, margin) {
-                if let Some(chunk) = world_manager.get_chunk_mut(coord) {
-                    chunk.state = ChunkState::Loading;
-                    total_count += 1;
-                }
+    let min = IVec2::new(-half_x, -half_z);
+    let max = IVec2::new(
+        config.world.total_chunks_x as i32 - 1 - half_x,
+        config.world.total_chunks_z as i32 - 1 - half_z,
+    );
+    // Z-order keeps neighbouring chunks together, the order they are keyed in
+    for cell in ZOrderCells::new(min, max) {
+        let coord = ChunkCoord::new(cell.x, cell.y);
+
+        // Check if chunk is on a terrain island (skip ocean chunks)
+        let chunk_center = coord.to_world_pos_with_size(world_manager.chunk_size);
+        if world_manager.is_on_terrain_island_with_margin(chunk_center, margin) {
+            if let Some(chunk) = world_manager.get_chunk_mut(coord) {
+                chunk.state = ChunkState::Loading;
+                provider.queue(coord);
+                total_count += 1;
             }
         }
     }
@@ -93,138 +183,103 @@ fn queue_all_chunks_for_generation(
         total_count
     );
 
-    // Create generation queue
-    commands.insert_resource(StaticGenerationQueue {
-        total_chunks: total_count,
-        completed_chunks: 0,
-        start_time: std::time::Instant::now(),
-    });
+    commands.insert_resource(WorldGenProgress::new(total_count));
 }
 
-/// Apply generated chunks - generates chunks synchronously with frame budget
-/// Processes up to 10 chunks per frame to maintain responsiveness
-#[allow(clippy::too_many_arguments)]
+/// Generate chunks around the camera first, within the loading budget
+/// Switches to InGame once everything inside `startup_radius` exists; the rest streams in-game
 fn apply_generated_chunks(
-    mut commands: Commands,
-    mut world_manager: ResMut<UnifiedWorldManager>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut material_registry: ResMut<MaterialRegistry>,
-    mut world_rng: ResMut<WorldRng>,
-    _spawn_registry: ResMut<SpawnRegistry>,
-    mut queue: ResMut<StaticGenerationQueue>,
+    mut provider: ResMut<ChunkStreamingProvider>,
+    mut context: ChunkGenerationContext,
+    mut progress: ResMut<WorldGenProgress>,
     mut next_state: ResMut<NextState<AppState>>,
-    water_bodies: Query<&UnifiedWaterBody>,
-    asset_server: Res<AssetServer>,
-    config: Res<GameConfig>,
-    env: Res<WorldEnvConfig>,
+    focus: Res<StreamingFocus>,
 ) {
-    // Increased from 10 to 200 - no need to maintain 60 FPS during loading
-    const CHUNKS_PER_FRAME: usize = 200;
-
-    // Find chunks that need generation
-    let chunks_to_process: Vec<ChunkCoord> = world_manager
-        .chunks
-        .iter()
-        .flatten()
-        .filter(|chunk| matches!(chunk.state, ChunkState::Loading))
-        .map(|chunk| chunk.coord)
-        .take(CHUNKS_PER_FRAME)
-        .collect();
-
-    if chunks_to_process.is_empty() {
-        // All chunks generated - transition to InGame
-        let elapsed = queue.start_time.elapsed();
+    let streaming = &context.config.world_streaming;
+    let (budget, startup_radius) = (streaming.startup_chunks_per_frame, streaming.startup_radius);
+    let chunk_size = context.world_manager.chunk_size;
+
+    if !provider.has_pending_within(focus.position, startup_radius, chunk_size) {
+        progress.startup_remaining = 0;
         info!(
-            "Static world generation complete! {} chunks in {:.2}s ({:.0} chunks/s)",
-            queue.total_chunks,
-            elapsed.as_secs_f32(),
-            queue.total_chunks as f32 / elapsed.as_secs_f32()
+            "Startup world generation complete! {} chunks in {:.2}s, {} left to stream in-game",
+            progress.completed_chunks,
+            progress.start_time.elapsed().as_secs_f32(),
+            provider.len()
         );
         next_state.set(AppState::InGame);
         return;
     }
 
-    // Generate chunks using existing generators
-    let road_generator = RoadGenerator;
-    let building_generator = BuildingGenerator;
-    let vehicle_generator = VehicleGenerator;
-    let vegetation_generator = VegetationGenerator;
-
-    for coord in chunks_to_process {
-        // Generate all content layers
-        road_generator.generate_roads(
-            &mut commands,
-            &mut world_manager,
-            coord,
-            &mut meshes,
-            &mut materials,
-            &mut material_registry,
-            &mut world_rng,
-            &water_bodies,
-            &config,
-            &env,
-        );
-
-        building_generator.generate_buildings(
-            &mut commands,
-            &mut world_manager,
-            coord,
-            &mut meshes,
-            &mut materials,
-            &mut world_rng,
-            &water_bodies,
-            &config,
-            &env,
-        );
-
-        vehicle_generator.generate_vehicles(
-            &mut commands,
-            &mut world_manager,
-            coord,
-            &mut meshes,
-            &mut materials,
-            &asset_server,
-            &mut world_rng,
-            &config,
-        );
-
-        vegetation_generator.generate_vegetation(
-            &mut commands,
-            &mut world_manager,
-            coord,
-            &mut meshes,
-            &mut materials,
-            &mut world_rng,
-            &water_bodies,
-            &config,
-            &env,
-        );
-
-        // Mark chunk as complete
-        if let Some(chunk) = world_manager.get_chunk_mut(coord) {
-            chunk.state = ChunkState::Loaded { lod_level: 0 };
-        }
-
-        queue.completed_chunks += 1;
-    }
+    let generated = context.stream(&mut provider, budget);
+    progress.completed_chunks += generated;
+    progress.startup_remaining =
+        provider.count_pending_within(focus.position, startup_radius, chunk_size);
 
     // Progress logging every 100 chunks
-    if queue.completed_chunks % 100 == 0 {
-        let progress = (queue.completed_chunks as f32 / queue.total_chunks as f32) * 100.0;
-        let elapsed = queue.start_time.elapsed().as_secs_f32();
-        let rate = queue.completed_chunks as f32 / elapsed;
-        let eta = (queue.total_chunks - queue.completed_chunks) as f32 / rate;
-
+    if generated > 0 && progress.completed_chunks.is_multiple_of(100) {
         info!(
-            "Generation progress: {}/{} ({:.1}%) - {:.0} chunks/s - ETA: {:.1}s",
-            queue.completed_chunks, queue.total_chunks, progress, rate, eta
+            "Generation progress: {}/{} ({:.1}% of startup area) - {:.0} chunks/s",
+            progress.completed_chunks,
+            progress.total_chunks,
+            progress.fraction() * 100.0,
+            progress.chunks_per_second()
         );
     }
 }
 
 /// Cleanup generation resources after loading completes
 fn cleanup_generation_resources(mut commands: Commands) {
-    commands.remove_resource::<StaticGenerationQueue>();
+    commands.remove_resource::<WorldGenProgress>();
     info!("Static generation resources cleaned up");
 }
+
+/// Chunk content and the HLOD proxies built over it
+type GeneratedContent = Or<(With<UnifiedChunkEntity>, With<HlodProxy>)>;
+
+/// Despawns everything a cancelled generation produced and forgets the queued chunks
+#[allow(clippy::too_many_arguments)]
+fn discard_generated_world(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut world_manager: ResMut<UnifiedWorldManager>,
+    mut provider: ResMut<ChunkStreamingProvider>,
+    mut street_props: StreetPropBatchers,
+    mut palms: ResMut<InstancedBatcher<PalmTree>>,
+    mut clusters: ResMut<HlodClusters>,
+    generated: Query<Entity, GeneratedContent>,
+) {
+    let mut count = 0;
+    for entity in &generated {
+        commands.entity(entity).despawn();
+        count += 1;
+    }
+    *world_manager = UnifiedWorldManager::from_config(&config);
+    *provider = ChunkStreamingProvider::default();
+    *clusters = HlodClusters::default();
+    street_props.streetlights.clear();
+    street_props.cones.clear();
+    street_props.fences.clear();
+    palms.clear();
+    info!("World generation cancelled, discarded {count} entities");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_tracks_startup_area() {
+        let mut progress = WorldGenProgress::new(8836);
+        assert_eq!(progress.fraction(), 0.0);
+        assert_eq!(progress.eta_secs(), None);
+
+        progress.completed_chunks = 30;
+        progress.startup_remaining = 90;
+        assert_eq!(progress.fraction(), 0.25);
+
+        progress.startup_remaining = 0;
+        assert_eq!(progress.fraction(), 1.0);
+        assert_eq!(WorldGenProgress::new(0).fraction(), 1.0);
+    }
+}
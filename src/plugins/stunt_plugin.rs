@@ -0,0 +1,48 @@
+use crate::components::stunt::{StuntEventFinished, StuntManager, StuntScores, StuntTrickScored};
+use crate::states::AppState;
+use crate::systems::racing::enter_races_at_markers;
+use crate::systems::stunts::{
+    clear_stunt_checkpoints, enter_stunt_zones, fade_stunt_popups, load_stunt_catalog,
+    score_stunt_tricks, setup_stunt_hud, spawn_stunt_popups, spawn_stunt_zones,
+    stunt_session_system, update_stunt_checkpoints, update_stunt_hud,
+};
+use bevy::prelude::*;
+
+/// Ramp jumps and time trials from `assets/config/stunts.ron`, scored for
+/// airtime, flips, jump distance and near misses. Emits `StuntEventFinished`
+/// when a session ends; best scores are saved per event.
+pub struct StuntPlugin;
+
+impl Plugin for StuntPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StuntManager>()
+            .init_resource::<StuntScores>()
+            .add_event::<StuntTrickScored>()
+            .add_event::<StuntEventFinished>()
+            .add_systems(
+                Startup,
+                (
+                    (load_stunt_catalog, spawn_stunt_zones).chain(),
+                    setup_stunt_hud,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    enter_stunt_zones.after(enter_races_at_markers),
+                    score_stunt_tricks,
+                    stunt_session_system,
+                    update_stunt_checkpoints,
+                    clear_stunt_checkpoints,
+                    update_stunt_hud,
+                    spawn_stunt_popups,
+                    fade_stunt_popups,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Stunt Plugin loaded");
+    }
+}
@@ -0,0 +1,19 @@
+use crate::systems::performance::telemetry::{
+    TelemetryExporter, apply_telemetry_config, sample_telemetry,
+};
+use bevy::prelude::*;
+
+/// Samples performance metrics for soak tests and CI runs and exports them to
+/// a rolling CSV file and/or a Prometheus `/metrics` endpoint, as configured
+/// in `telemetry.ron`
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TelemetryExporter>()
+            .add_systems(Update, (apply_telemetry_config, sample_telemetry).chain());
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Telemetry Plugin loaded");
+    }
+}
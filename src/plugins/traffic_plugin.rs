@@ -0,0 +1,21 @@
+use crate::states::AppState;
+use crate::systems::traffic::{assign_traffic_agents, traffic_ai_system};
+use bevy::prelude::*;
+
+/// Ambient traffic driving along the road network.
+/// Tuned through `GameConfig::traffic`.
+pub struct TrafficPlugin;
+
+impl Plugin for TrafficPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (assign_traffic_agents, traffic_ai_system)
+                .chain()
+                .run_if(in_state(AppState::InGame)),
+        );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Traffic Plugin loaded");
+    }
+}
@@ -1,8 +1,14 @@
+use crate::resources::Localization;
 use crate::states::AppState;
 use crate::systems::effects::update_waypoint_system;
+use crate::systems::ui::localization::{
+    ConfiguredLanguage, UiFont, apply_reloaded_localization, apply_ui_font, load_localization,
+    select_ui_font, update_localized_text,
+};
 use crate::systems::ui::{
-    controls_ui_system, load_initial_assets, setup_fps_display, update_asset_loading,
-    update_fps_display,
+    cleanup_splash_screen, controls_ui_system, setup_blackout_vignette, setup_fps_display,
+    setup_splash_screen, setup_vehicle_hud, update_asset_loading, update_blackout_vignette,
+    update_fps_display, update_vehicle_hud,
 };
 use bevy::prelude::*;
 
@@ -10,20 +16,44 @@ pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<crate::systems::ui::splash_screen::AssetLoadingState>()
-            .add_systems(OnEnter(AppState::AssetLoading), load_initial_assets)
+        app.init_resource::<Localization>()
+            .init_resource::<UiFont>()
+            .init_resource::<ConfiguredLanguage>()
+            .add_systems(Startup, load_localization)
+            .add_systems(OnEnter(AppState::AssetLoading), setup_splash_screen)
+            .add_systems(OnExit(AppState::AssetLoading), cleanup_splash_screen)
             .add_systems(
                 Update,
                 update_asset_loading.run_if(in_state(AppState::AssetLoading)),
             )
-            .add_systems(Startup, setup_fps_display)
+            .add_systems(
+                Startup,
+                (
+                    setup_fps_display,
+                    setup_blackout_vignette,
+                    setup_vehicle_hud,
+                ),
+            )
             .add_systems(
                 Update,
                 (
                     controls_ui_system,
                     update_waypoint_system,
                     update_fps_display,
+                    update_blackout_vignette,
+                    update_vehicle_hud,
                 ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    apply_reloaded_localization,
+                    update_localized_text,
+                    select_ui_font,
+                    apply_ui_font,
+                )
+                    .chain()
+                    .before(bevy::ui::UiSystem::Prepare),
             );
     }
 }
@@ -1,3 +1,4 @@
+use crate::components::HlodClusters;
 use crate::config::GameConfig;
 use crate::factories::material_factory::initialize_material_factory;
 use crate::plugins::{
@@ -5,6 +6,11 @@ use crate::plugins::{
 };
 use crate::resources::MaterialRegistry;
 use crate::states::AppState;
+use crate::systems::world::chunk_streaming::update_streaming_focus;
+use crate::systems::world::hlod::{bake_hlod_proxies, hlod_swap_system, register_hlod_members};
+use crate::systems::world::terrain_clipmap::{
+    spawn_terrain_clipmap, terrain_has_relief, update_terrain_clipmap,
+};
 use crate::systems::world::unified_world::UnifiedWorldManager;
 use bevy::prelude::*;
 
@@ -27,6 +33,22 @@ impl Plugin for UnifiedWorldPlugin {
             .add_plugins(WorldDebugPlugin)
             // Initialize material factory
             .add_systems(Startup, initialize_material_factory)
+            // Clipmap meshes for plateau relief (flat islands need none)
+            .add_systems(Startup, spawn_terrain_clipmap.run_if(terrain_has_relief))
+            .add_systems(
+                Update,
+                update_terrain_clipmap
+                    .after(update_streaming_focus)
+                    .run_if(terrain_has_relief),
+            )
+            // Building clusters swap to merged proxies in the distance
+            .init_resource::<HlodClusters>()
+            .add_systems(
+                Update,
+                (register_hlod_members, bake_hlod_proxies, hlod_swap_system)
+                    .chain()
+                    .after(update_streaming_focus),
+            )
             // Cleanup resources on game exit
             .add_systems(OnExit(AppState::InGame), cleanup_world_resources);
     }
@@ -1,25 +1,26 @@
+use crate::components::AirfieldRegistry;
 use crate::components::vehicles::{
     SimpleCarSpecs, SimpleF16Specs, SimpleHelicopterSpecs, VehiclePhysicsConfig,
 };
 use crate::states::AppState;
-use crate::systems::camera_car::car_camera_system;
-use crate::systems::camera_f16::f16_camera_system;
-use crate::systems::camera_helicopter::helicopter_camera_system;
-use crate::systems::camera_yacht::yacht_camera_system;
-use crate::systems::movement::rotate_helicopter_rotors;
+use crate::systems::airfields::{landing_gear_system, spawn_airfields, update_landing_gear_struts};
+use crate::systems::movement::{rotate_helicopter_rotors, transmission_shift_input};
 use crate::systems::setup::on_f16_spawned;
+use crate::systems::tires::{detect_tire_surface, spawn_skid_marks};
 use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
 use bevy_common_assets::ron::RonAssetPlugin;
+use std::time::Duration;
 // Complex aircraft systems moved to examples/complex_aircraft_physics.rs
 use crate::systems::effects::{
-    AfterburnerFlameEffect, RotorWashEffect, cleanup_afterburner_on_f16_despawn,
-    cleanup_afterburner_particle_entities, cleanup_rotor_wash_on_helicopter_despawn,
-    cleanup_rotor_wash_particle_entities, create_afterburner_flame_effect,
-    create_rotor_wash_effect, ensure_afterburner_for_existing_f16s,
-    ensure_rotor_wash_for_existing_helicopters, spawn_afterburner_particles,
-    spawn_rotor_wash_particles, update_afterburner_position_and_intensity,
-    update_jet_flames_unified, update_landing_lights, update_navigation_lights,
-    update_rotor_blur_visibility, update_rotor_wash_position_and_intensity,
+    RotorWashEffect, cleanup_afterburner_on_f16_despawn, cleanup_afterburner_particle_entities,
+    cleanup_rotor_wash_on_helicopter_despawn, cleanup_rotor_wash_particle_entities,
+    create_rotor_spray_effect, create_rotor_wash_effect, ensure_afterburner_for_existing_f16s,
+    ensure_rotor_wash_for_existing_helicopters, rotor_wash_push_system,
+    spawn_afterburner_particles, spawn_rotor_wash_particles,
+    update_afterburner_position_and_intensity, update_jet_flames_unified, update_landing_lights,
+    update_navigation_lights, update_rotor_blur_visibility,
+    update_rotor_wash_position_and_intensity,
 };
 use crate::systems::safety::validate_physics_config;
 use bevy_hanabi::prelude::*;
@@ -40,6 +41,7 @@ impl Plugin for VehiclePlugin {
             .init_asset::<SimpleHelicopterSpecs>()
             .init_asset::<SimpleF16Specs>()
             .init_asset::<VehiclePhysicsConfig>()
+            .init_resource::<AirfieldRegistry>()
             // CRITICAL SAFEGUARDS: Run configuration validation at startup
             .add_systems(Startup, validate_physics_config)
             .add_systems(
@@ -47,8 +49,8 @@ impl Plugin for VehiclePlugin {
                 (
                     init_rotor_wash_effect,
                     ensure_rotor_wash_for_existing_helicopters,
-                    init_afterburner_effect,
                     ensure_afterburner_for_existing_f16s,
+                    spawn_airfields,
                 )
                     .chain(),
             )
@@ -65,25 +67,26 @@ impl Plugin for VehiclePlugin {
                     // car_movement.run_if(in_state(GameState::Driving)),
                     // simple_helicopter_movement.run_if(in_state(GameState::Flying)),
                     // simple_f16_movement.run_if(in_state(GameState::Jetting)),
-                    // Camera systems for smooth vehicle following
-                    car_camera_system,
-                    helicopter_camera_system,
-                    f16_camera_system,
-                    yacht_camera_system,
                     // Visual rotor animation for helicopters
                     rotate_helicopter_rotors,
                     // Helicopter visual enhancements
                     update_rotor_blur_visibility,
                     update_navigation_lights,
                     update_landing_lights,
-                    // Rotor wash dust particles (only when resource exists)
+                    // Rotor wash dust/spray particles (only when resource exists)
                     spawn_rotor_wash_particles.run_if(resource_exists::<RotorWashEffect>),
                     update_rotor_wash_position_and_intensity
                         .run_if(resource_exists::<RotorWashEffect>),
-                    // Afterburner flame particles (only when resource exists)
-                    spawn_afterburner_particles.run_if(resource_exists::<AfterburnerFlameEffect>),
-                    update_afterburner_position_and_intensity
-                        .run_if(resource_exists::<AfterburnerFlameEffect>),
+                    rotor_wash_push_system,
+                    // Afterburner flame emitters, simulated by the ParticlePlugin
+                    spawn_afterburner_particles,
+                    update_afterburner_position_and_intensity,
+                    // Gear toggle is consumed here, before input resets it next frame
+                    (landing_gear_system, update_landing_gear_struts).chain(),
+                    transmission_shift_input,
+                    // Surface lookups walk the road network, so they run at 10 Hz
+                    detect_tire_surface.run_if(on_timer(Duration::from_millis(100))),
+                    spawn_skid_marks,
                 ),
             )
             .add_systems(
@@ -109,7 +112,6 @@ impl Plugin for VehiclePlugin {
                     cleanup_rotor_wash_particle_entities,
                     cleanup_rotor_wash_effect,
                     cleanup_afterburner_particle_entities,
-                    cleanup_afterburner_effect,
                 )
                     .chain(),
             );
@@ -120,7 +122,8 @@ impl Plugin for VehiclePlugin {
 /// Creates the effect once and caches the handle for reuse across all helicopters.
 fn init_rotor_wash_effect(mut commands: Commands, mut effects: ResMut<Assets<EffectAsset>>) {
     let handle = create_rotor_wash_effect(&mut effects);
-    commands.insert_resource(RotorWashEffect { handle });
+    let spray = create_rotor_spray_effect(&mut effects);
+    commands.insert_resource(RotorWashEffect { handle, spray });
 }
 
 fn cleanup_rotor_wash_effect(
@@ -131,28 +134,9 @@ fn cleanup_rotor_wash_effect(
     if let Some(rotor) = rotor {
         // Remove asset to prevent CPU-side asset accumulation across re-entries
         effects.remove(rotor.handle.id());
+        effects.remove(rotor.spray.id());
     }
     commands.remove_resource::<RotorWashEffect>();
     #[cfg(feature = "debug-ui")]
     info!("Rotor wash effect cleaned up");
 }
-
-/// Initializes the afterburner flame effect resource at startup.
-/// Creates the effect once and caches the handle for reuse across all F16s.
-fn init_afterburner_effect(mut commands: Commands, mut effects: ResMut<Assets<EffectAsset>>) {
-    let handle = create_afterburner_flame_effect(&mut effects);
-    commands.insert_resource(AfterburnerFlameEffect { handle });
-}
-
-fn cleanup_afterburner_effect(
-    mut commands: Commands,
-    afterburner: Option<Res<AfterburnerFlameEffect>>,
-    mut effects: ResMut<Assets<EffectAsset>>,
-) {
-    if let Some(afterburner) = afterburner {
-        effects.remove(afterburner.handle.id());
-    }
-    commands.remove_resource::<AfterburnerFlameEffect>();
-    #[cfg(feature = "debug-ui")]
-    info!("Afterburner effect cleaned up");
-}
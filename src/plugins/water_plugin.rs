@@ -7,17 +7,20 @@ use crate::states::AppState;
 use crate::systems::debug_docked_heli::audit_docked_helicopter_movement;
 use crate::systems::movement::{boat_animation_system, simple_yacht_movement, spool_docked_helicopter_rpm};
 use crate::systems::effects::boat_wake::{
-    create_boat_wake_effect, cleanup_boat_wake_on_despawn, spawn_boat_wake_particles,
-    update_boat_wake_intensity, BoatWakeEffect,
+    create_boat_wake_effect, cleanup_boat_wake_on_despawn, cleanup_wake_foam,
+    spawn_boat_wake_particles, update_boat_wake_intensity, update_wake_foam, BoatWakeEffect,
 };
 use bevy_hanabi::prelude::*;
+use crate::systems::diving::{
+    setup_oxygen_meter, update_breath, update_oxygen_meter, update_underwater_camera,
+};
 use crate::systems::swimming::{
     apply_prone_rotation_system, apply_swimming_state, detect_swimming_conditions,
     emergency_swim_exit_system, reset_animation_on_land_system, swim_animation_flag_system,
     swim_velocity_apply_system,
 };
 use crate::systems::water::{
-    load_unified_water_assets, process_loaded_unified_water_assets, simple_yacht_buoyancy,
+    cast_off_moored_yachts, hull_buoyancy_system, moor_yachts, spawn_docks, load_unified_water_assets, process_loaded_unified_water_assets,
     spawn_test_yacht, surface_render_system, update_water_material_time_system,
     update_water_region_cache, update_water_surface_system, water_physics_system,
 };
@@ -57,8 +60,11 @@ impl Plugin for WaterPlugin {
                     .chain()
                     .before(PhysicsSet::StepSimulation),
             )
-            .add_systems(Startup, (load_unified_water_assets, spawn_test_yacht))
-            .add_systems(OnEnter(AppState::InGame), init_boat_wake_effect)
+            .add_systems(
+                Startup,
+                (load_unified_water_assets, spawn_test_yacht, setup_oxygen_meter),
+            )
+            .add_systems(OnEnter(AppState::InGame), (init_boat_wake_effect, spawn_docks))
             .add_systems(Update, process_loaded_unified_water_assets)
             .add_systems(
                 FixedUpdate,
@@ -66,7 +72,7 @@ impl Plugin for WaterPlugin {
             )
             .add_systems(
                 FixedUpdate,
-                (water_physics_system, simple_yacht_buoyancy)
+                (water_physics_system, hull_buoyancy_system)
                     .chain()
                     .in_set(WaterSystemSet::Physics),
             )
@@ -74,8 +80,10 @@ impl Plugin for WaterPlugin {
                 FixedUpdate,
                 (
                     simple_yacht_movement,
+                    moor_yachts,
                     detect_swimming_conditions,
                     apply_swimming_state,
+                    update_breath,
                     swim_velocity_apply_system.run_if(in_state(GameState::Swimming)),
                 )
                     .chain()
@@ -91,6 +99,8 @@ impl Plugin for WaterPlugin {
                     apply_prone_rotation_system,
                     reset_animation_on_land_system,
                     emergency_swim_exit_system,
+                    update_underwater_camera,
+                    update_oxygen_meter,
                 ),
             )
             .add_systems(Update, boat_animation_system)
@@ -99,25 +109,25 @@ impl Plugin for WaterPlugin {
                 (
                     spawn_boat_wake_particles.run_if(resource_exists::<BoatWakeEffect>),
                     update_boat_wake_intensity.run_if(resource_exists::<BoatWakeEffect>),
+                    update_wake_foam,
                 )
             )
-            .add_systems(
-                FixedUpdate,
-                yacht_board_from_deck_system.before(PhysicsSet::SyncBackend),
-            )
             .add_systems(
                 Update,
                 (
                     yacht_exit_system.after(crate::plugins::input_plugin::InputProcessingSet),
+                    yacht_board_from_deck_system
+                        .after(crate::plugins::interaction_plugin::InteractionSet),
                     deck_walk_movement_system,
                     heli_landing_detection_system,
                     helicopter_undock_trigger_system,
                     tick_docking_cooldown_system,
                     spool_docked_helicopter_rpm,
+                    cast_off_moored_yachts,
                 ),
             )
             .add_systems(PostUpdate, (audit_docked_helicopter_movement, cleanup_boat_wake_on_despawn))
-            .add_systems(OnExit(AppState::InGame), cleanup_boat_wake_resource);
+            .add_systems(OnExit(AppState::InGame), (cleanup_boat_wake_resource, cleanup_wake_foam));
     }
 }
 
@@ -0,0 +1,47 @@
+use crate::components::ChangeWeather;
+use crate::states::AppState;
+use crate::systems::effects::{RainEffect, create_rain_effect, spawn_rain_emitter};
+use crate::systems::weather::{
+    apply_weather_fog, load_weather_config, update_rain_emitter, weather_cycle_system,
+    weather_transition_system, weather_wind_system,
+};
+use bevy::prelude::*;
+use bevy_hanabi::prelude::EffectAsset;
+
+/// Rain, fog and wind driven by `assets/config/weather.ron`.
+/// Other systems read the blended `WeatherState`; send `ChangeWeather` to switch presets.
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ChangeWeather>()
+            .add_systems(
+                Startup,
+                (
+                    load_weather_config,
+                    init_rain_effect,
+                    spawn_rain_emitter.after(init_rain_effect),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    weather_cycle_system,
+                    weather_transition_system,
+                    apply_weather_fog,
+                    update_rain_emitter,
+                    weather_wind_system,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+
+        #[cfg(feature = "debug-ui")]
+        info!("✅ Weather Plugin loaded");
+    }
+}
+
+fn init_rain_effect(mut commands: Commands, mut effects: ResMut<Assets<EffectAsset>>) {
+    let handle = create_rain_effect(&mut effects);
+    commands.insert_resource(RainEffect { handle });
+}
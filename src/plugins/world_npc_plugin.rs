@@ -1,19 +1,66 @@
-use crate::resources::NPCAssetCache;
+use crate::components::{HornHonked, PedestrianBark};
+use crate::plugins::input_plugin::InputProcessingSet;
+use crate::resources::{CrowdDensity, NPCAssetCache, SidewalkGraph};
 use crate::systems::world::{
-    npc::simple_npc_movement, npc_animation::npc_animation_system, npc_spawn::spawn_new_npc_system,
+    npc::simple_npc_movement,
+    npc_animation::npc_animation_system,
+    npc_persistence::dormant_npc_system,
+    npc_spawn::{enforce_npc_budget, spawn_new_npc_system, update_crowd_density},
+    pedestrians::{build_sidewalk_graph, pedestrian_behavior_system, vehicle_horn_input},
+    ragdoll::{ragdoll_activation_system, ragdoll_update_system},
+    schedules::{
+        assign_npc_schedules, coarse_schedule_system, load_routines, update_npc_schedules,
+    },
 };
 use bevy::prelude::*;
 
-/// Plugin responsible for NPC spawning, behavior, and management
+/// Plugin responsible for NPC spawning, behavior, daily routines and management
 pub struct WorldNpcPlugin;
 
 impl Plugin for WorldNpcPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(NPCAssetCache::new())
-            .add_systems(Startup, initialize_npc_assets)
-            .add_systems(Update, spawn_new_npc_system)
+            .init_resource::<SidewalkGraph>()
+            .init_resource::<CrowdDensity>()
+            .add_event::<HornHonked>()
+            .add_event::<PedestrianBark>()
+            .add_systems(Startup, (initialize_npc_assets, load_routines))
+            .add_systems(
+                Update,
+                (
+                    dormant_npc_system,
+                    update_crowd_density,
+                    spawn_new_npc_system,
+                    enforce_npc_budget,
+                    assign_npc_schedules,
+                    update_npc_schedules,
+                    coarse_schedule_system,
+                )
+                    .chain(),
+            )
             .add_systems(Update, simple_npc_movement)
-            .add_systems(Update, npc_animation_system.after(simple_npc_movement));
+            .add_systems(
+                Update,
+                (
+                    build_sidewalk_graph,
+                    vehicle_horn_input.after(InputProcessingSet),
+                    pedestrian_behavior_system,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (ragdoll_activation_system, ragdoll_update_system)
+                    .chain()
+                    .before(simple_npc_movement)
+                    .before(pedestrian_behavior_system),
+            )
+            .add_systems(
+                Update,
+                npc_animation_system
+                    .after(simple_npc_movement)
+                    .after(pedestrian_behavior_system),
+            );
 
         #[cfg(feature = "debug-ui")]
         app.add_systems(Update, log_cache_stats);
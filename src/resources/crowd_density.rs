@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Side of a crowd density cell; one cell is a hectare
+pub const CROWD_CELL_SIZE: f32 = 100.0;
+
+/// How many NPCs stand in each hectare of the world, live and dormant, for
+/// the spawner to compare against the district targets
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CrowdDensity {
+    counts: HashMap<IVec2, u32>,
+}
+
+impl CrowdDensity {
+    pub fn cell(position: Vec3) -> IVec2 {
+        IVec2::new(
+            (position.x / CROWD_CELL_SIZE).floor() as i32,
+            (position.z / CROWD_CELL_SIZE).floor() as i32,
+        )
+    }
+
+    /// Ground-level center of `cell`
+    pub fn cell_center(cell: IVec2) -> Vec3 {
+        Vec3::new(
+            (cell.x as f32 + 0.5) * CROWD_CELL_SIZE,
+            0.0,
+            (cell.y as f32 + 0.5) * CROWD_CELL_SIZE,
+        )
+    }
+
+    /// Recounts from scratch
+    pub fn rebuild(&mut self, positions: impl IntoIterator<Item = Vec3>) {
+        self.counts.clear();
+        for position in positions {
+            self.record(position);
+        }
+    }
+
+    /// Counts one more NPC at `position`, so a batch of spawns sees the ones
+    /// before it
+    pub fn record(&mut self, position: Vec3) {
+        *self.counts.entry(Self::cell(position)).or_default() += 1;
+    }
+
+    pub fn count(&self, cell: IVec2) -> u32 {
+        self.counts.get(&cell).copied().unwrap_or(0)
+    }
+
+    /// NPCs missing at `position` for a target of `target` per hectare;
+    /// negative when it is already crowded
+    pub fn deficit(&self, position: Vec3, target: f32) -> f32 {
+        target - self.count(Self::cell(position)) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_per_hectare_and_deficit() {
+        let mut density = CrowdDensity::default();
+        density.rebuild([
+            Vec3::new(10.0, 0.0, 10.0),
+            Vec3::new(90.0, 5.0, 60.0),
+            Vec3::new(-10.0, 0.0, 10.0),
+        ]);
+        assert_eq!(density.count(IVec2::ZERO), 2);
+        assert_eq!(density.count(IVec2::new(-1, 0)), 1);
+        assert_eq!(density.deficit(Vec3::new(50.0, 0.0, 50.0), 1.5), -0.5);
+
+        density.record(Vec3::new(150.0, 0.0, 0.0));
+        assert_eq!(density.deficit(Vec3::new(110.0, 0.0, 20.0), 2.0), 1.0);
+        assert_eq!(
+            CrowdDensity::cell_center(IVec2::new(-1, 0)),
+            Vec3::new(-50.0, 0.0, 50.0)
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use crate::components::VehicleType;
+use crate::factories::BuildingType;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Kind of neighbourhood a point of the world belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+pub enum District {
+    Downtown,
+    #[default]
+    Suburbs,
+    Industrial,
+    Beach,
+    /// Open green space with no buildings, where NPCs spend their breaks
+    Park,
+}
+
+/// One building archetype a district can place
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuildingTemplate {
+    pub kind: BuildingType,
+    pub weight: f32,
+    /// Footprint side length range in meters
+    pub footprint: (f32, f32),
+    pub height: (f32, f32),
+    /// sRGB facade colours; one is picked per building
+    pub colors: Vec<(f32, f32, f32)>,
+}
+
+/// Ground vehicle entry in a district's spawn table
+#[derive(Debug, Clone, Deserialize)]
+pub struct VehicleSpawn {
+    pub vehicle: VehicleType,
+    pub weight: f32,
+}
+
+/// Content palette for one district
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DistrictProfile {
+    pub district: District,
+    /// Multiplier on building placement attempts
+    pub building_density: f32,
+    pub buildings: Vec<BuildingTemplate>,
+    /// Target NPCs per hectare (100 m square) the spawner fills up to
+    pub npc_density: f32,
+    /// Multiplier on road vehicle placement attempts
+    pub vehicle_density: f32,
+    pub vehicles: Vec<VehicleSpawn>,
+    /// sRGB paint colours; empty uses the factory's car palette
+    pub vehicle_colors: Vec<(f32, f32, f32)>,
+    /// Looping ambience clip, relative to the assets folder
+    pub ambient: Option<String>,
+}
+
+impl Default for DistrictProfile {
+    fn default() -> Self {
+        Self {
+            district: District::default(),
+            building_density: 1.0,
+            buildings: Vec::new(),
+            npc_density: 1.0,
+            vehicle_density: 1.0,
+            vehicles: vec![VehicleSpawn {
+                vehicle: VehicleType::SuperCar,
+                weight: 1.0,
+            }],
+            vehicle_colors: Vec::new(),
+            ambient: None,
+        }
+    }
+}
+
+/// Axis-aligned area of the ground plane assigned to a district
+#[derive(Debug, Clone, Deserialize)]
+pub struct Zone {
+    pub district: District,
+    pub min: (f32, f32),
+    pub max: (f32, f32),
+    /// Target NPCs per hectare here instead of the district's
+    #[serde(default)]
+    pub npc_density: Option<f32>,
+}
+
+impl Zone {
+    pub fn contains(&self, position: Vec3) -> bool {
+        (self.min.0..=self.max.0).contains(&position.x)
+            && (self.min.1..=self.max.1).contains(&position.z)
+    }
+
+    /// Ground-plane distance from `position` to the nearest point of the zone
+    pub fn distance_to(&self, position: Vec3) -> f32 {
+        let min = Vec2::new(self.min.0, self.min.1);
+        let max = Vec2::new(self.max.0, self.max.1);
+        position
+            .xz()
+            .clamp(min, max.max(min))
+            .distance(position.xz())
+    }
+}
+
+/// Contents of `assets/config/zones.ron`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ZoneList {
+    /// District for everything outside the listed zones
+    pub default_district: District,
+    /// Checked in order; the first zone containing a point wins
+    pub zones: Vec<Zone>,
+    pub profiles: Vec<DistrictProfile>,
+}
+
+/// District layout and content palettes used by world generation, NPC spawning and ambience
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DistrictMap {
+    pub default_district: District,
+    pub zones: Vec<Zone>,
+    profiles: HashMap<District, DistrictProfile>,
+    fallback: DistrictProfile,
+    /// Loaded ambience loops, for districts whose clip exists
+    pub ambient_clips: HashMap<District, Handle<AudioSource>>,
+}
+
+impl DistrictMap {
+    pub fn from_list(list: ZoneList) -> Self {
+        Self {
+            default_district: list.default_district,
+            zones: list.zones,
+            profiles: list
+                .profiles
+                .into_iter()
+                .map(|profile| (profile.district, profile))
+                .collect(),
+            fallback: DistrictProfile::default(),
+            ambient_clips: HashMap::new(),
+        }
+    }
+
+    pub fn district_at(&self, position: Vec3) -> District {
+        self.zones
+            .iter()
+            .find(|zone| zone.contains(position))
+            .map_or(self.default_district, |zone| zone.district)
+    }
+
+    /// Palette for `district`; districts without one use neutral defaults
+    pub fn profile(&self, district: District) -> &DistrictProfile {
+        self.profiles.get(&district).unwrap_or(&self.fallback)
+    }
+
+    pub fn profile_at(&self, position: Vec3) -> &DistrictProfile {
+        self.profile(self.district_at(position))
+    }
+
+    /// Target NPCs per hectare at `position`, from its zone or else its district
+    pub fn npc_density_at(&self, position: Vec3) -> f32 {
+        match self.zones.iter().find(|zone| zone.contains(position)) {
+            Some(zone) => zone
+                .npc_density
+                .unwrap_or_else(|| self.profile(zone.district).npc_density),
+            None => self.profile(self.default_district).npc_density,
+        }
+    }
+
+    pub fn profiles(&self) -> impl Iterator<Item = &DistrictProfile> {
+        self.profiles.values()
+    }
+
+    /// Random point in the zone of `district` nearest `near`, if one lies
+    /// within `max_distance`
+    pub fn random_point_near(
+        &self,
+        district: District,
+        near: Vec3,
+        max_distance: f32,
+        rng: &mut impl Rng,
+    ) -> Option<Vec3> {
+        let zone = self
+            .zones
+            .iter()
+            .filter(|zone| zone.district == district)
+            .map(|zone| (zone.distance_to(near), zone))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by(|a, b| a.0.total_cmp(&b.0))?
+            .1;
+        let x = rng.gen_range(zone.min.0..=zone.max.0.max(zone.min.0));
+        let z = rng.gen_range(zone.min.1..=zone.max.1.max(zone.min.1));
+        Some(Vec3::new(x, near.y, z))
+    }
+}
+
+impl DistrictProfile {
+    /// Weighted random building archetype, if the district lists any
+    pub fn pick_building(&self, rng: &mut impl Rng) -> Option<&BuildingTemplate> {
+        pick_weighted(&self.buildings, |b| b.weight, rng)
+    }
+
+    /// Weighted random vehicle type, if the district lists any
+    pub fn pick_vehicle(&self, rng: &mut impl Rng) -> Option<VehicleType> {
+        pick_weighted(&self.vehicles, |v| v.weight, rng).map(|v| v.vehicle)
+    }
+
+    pub fn pick_vehicle_color(&self, rng: &mut impl Rng) -> Option<Color> {
+        pick_color(&self.vehicle_colors, rng)
+    }
+}
+
+impl BuildingTemplate {
+    /// Footprint, height and facade colour for one building
+    pub fn roll(&self, rng: &mut impl Rng) -> (f32, f32, Color) {
+        let footprint = rng.gen_range(self.footprint.0..=self.footprint.1.max(self.footprint.0));
+        let height = rng.gen_range(self.height.0..=self.height.1.max(self.height.0));
+        let color = pick_color(&self.colors, rng).unwrap_or(Color::srgb(0.7, 0.7, 0.7));
+        (footprint, height, color)
+    }
+}
+
+fn pick_color(colors: &[(f32, f32, f32)], rng: &mut impl Rng) -> Option<Color> {
+    if colors.is_empty() {
+        return None;
+    }
+    let (r, g, b) = colors[rng.gen_range(0..colors.len())];
+    Some(Color::srgb(r, g, b))
+}
+
+pub(crate) fn pick_weighted<'a, T>(
+    items: &'a [T],
+    weight: impl Fn(&T) -> f32,
+    rng: &mut impl Rng,
+) -> Option<&'a T> {
+    let total: f32 = items.iter().map(|item| weight(item).max(0.0)).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut roll = rng.gen_range(0.0..total);
+    for item in items {
+        let w = weight(item).max(0.0);
+        if roll < w {
+            return Some(item);
+        }
+        roll -= w;
+    }
+    items.last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_zone_list_parses_and_resolves_districts() {
+        let content = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/config/zones.ron"
+        ))
+        .unwrap();
+        let map = DistrictMap::from_list(ron::from_str(&content).unwrap());
+
+        // Grid island is downtown, and every district has a palette
+        assert_eq!(
+            map.district_at(Vec3::new(0.0, 0.0, 1800.0)),
+            District::Downtown
+        );
+        assert_eq!(
+            map.district_at(Vec3::new(9000.0, 0.0, 9000.0)),
+            map.default_district
+        );
+        for district in [
+            District::Downtown,
+            District::Suburbs,
+            District::Industrial,
+            District::Beach,
+            District::Park,
+        ] {
+            assert_eq!(map.profile(district).district, district);
+        }
+    }
+
+    #[test]
+    fn test_weighted_picks_skip_zero_weights() {
+        let profile = DistrictProfile {
+            vehicles: vec![
+                VehicleSpawn {
+                    vehicle: VehicleType::Helicopter,
+                    weight: 0.0,
+                },
+                VehicleSpawn {
+                    vehicle: VehicleType::SuperCar,
+                    weight: 2.0,
+                },
+            ],
+            ..default()
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..32 {
+            assert_eq!(profile.pick_vehicle(&mut rng), Some(VehicleType::SuperCar));
+        }
+        assert!(profile.pick_building(&mut rng).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Language used when nothing else is configured, and for keys a translation lacks
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Strings `t!` reads from; set by `Localization::make_current`
+static CURRENT: RwLock<Option<Arc<HashMap<String, String>>>> = RwLock::new(None);
+
+/// Translated UI strings from `assets/locale/<language>.ron`, keyed like
+/// `menu.settings`
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Localization {
+    pub language: String,
+    strings: Arc<HashMap<String, String>>,
+}
+
+impl Localization {
+    pub fn new(language: impl Into<String>, strings: HashMap<String, String>) -> Self {
+        Self {
+            language: language.into(),
+            strings: Arc::new(strings),
+        }
+    }
+
+    /// Loads `language` on top of the default language so missing keys fall
+    /// back to English
+    pub fn load(language: &str) -> Self {
+        let mut strings = read_locale(DEFAULT_LANGUAGE);
+        if language != DEFAULT_LANGUAGE {
+            strings.extend(read_locale(language));
+        }
+        Self::new(language, strings)
+    }
+
+    /// Translation of `key`; unknown keys come back unchanged so plain text
+    /// also works
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map_or(key, String::as_str)
+    }
+
+    /// Makes these strings the ones `t!` translates with
+    pub fn make_current(&self) {
+        if let Ok(mut current) = CURRENT.write() {
+            *current = Some(Arc::clone(&self.strings));
+        }
+    }
+}
+
+/// Translates `key` with the current localization; see `t!`
+pub fn translate(key: &str) -> String {
+    let current = CURRENT.read().ok();
+    current
+        .as_ref()
+        .and_then(|current| current.as_ref()?.get(key).cloned())
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Translates `key` and fills its `{name}` placeholders from `args`
+pub fn translate_with(key: &str, args: &[(&str, String)]) -> String {
+    fill_placeholders(&translate(key), args)
+}
+
+fn fill_placeholders(template: &str, args: &[(&str, String)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// Translates a UI string key, e.g. `t!("menu.resume")`, or with placeholders
+/// `t!("hud.wanted", stars = "**")` for a string like `"WANTED {stars}"`.
+/// Keys without a translation come back as-is.
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::resources::localization::translate($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::resources::localization::translate_with(
+            $key,
+            &[$((stringify!($name), $value.to_string())),+],
+        )
+    };
+}
+
+fn read_locale(language: &str) -> HashMap<String, String> {
+    let path = format!(
+        "{}/locale/{language}.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+            error!("Failed to parse '{}': {}", path, e);
+            HashMap::new()
+        }),
+        Err(e) => {
+            warn!("⚠️ No locale '{}': {}", language, e);
+            HashMap::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys_fall_back_to_key() {
+        let strings = HashMap::from([("ui.talk".to_string(), "Talk".to_string())]);
+        let locale = Localization::new("en", strings);
+        assert_eq!(locale.get("ui.talk"), "Talk");
+        assert_eq!(locale.get("Plain text"), "Plain text");
+    }
+
+    #[test]
+    fn test_placeholders_are_filled_by_name() {
+        let text = fill_placeholders(
+            "{width}x{height} ({width})",
+            &[("width", "1280".to_string()), ("height", "720".to_string())],
+        );
+        assert_eq!(text, "1280x720 (1280)");
+    }
+
+    #[test]
+    fn test_locales_share_keys() {
+        let english = read_locale("en");
+        let spanish = read_locale("es");
+        assert!(!english.is_empty());
+        for key in spanish.keys() {
+            assert!(english.contains_key(key), "'{key}' missing from en.ron");
+        }
+        for key in english.keys() {
+            assert!(spanish.contains_key(key), "'{key}' missing from es.ron");
+        }
+    }
+}
@@ -1,9 +1,19 @@
+pub mod crowd_density;
+pub mod district_map;
+pub mod localization;
 pub mod material_registry;
 pub mod npc_asset_cache;
+pub mod sidewalk_graph;
 pub mod vehicle_specs_assets;
+pub mod world_clock;
 pub mod world_rng;
 
+pub use crowd_density::CrowdDensity;
+pub use district_map::{District, DistrictMap, DistrictProfile, ZoneList};
+pub use localization::Localization;
 pub use material_registry::{MaterialKey, MaterialRegistry};
 pub use npc_asset_cache::{MeshShape, NPCAssetCache};
+pub use sidewalk_graph::SidewalkGraph;
 pub use vehicle_specs_assets::VehicleSpecsAssets;
-pub use world_rng::WorldRng;
+pub use world_clock::WorldClock;
+pub use world_rng::{WorldRng, WorldSeed};
@@ -0,0 +1,150 @@
+use crate::systems::world::road_network::RoadNetwork;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Spatial hash cell size for waypoint lookups
+const GRID_CELL_SIZE: f32 = 20.0;
+
+/// Pedestrian waypoint graph running along both sides of every road
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SidewalkGraph {
+    pub nodes: Vec<Vec3>,
+    pub edges: Vec<Vec<usize>>,
+    /// Road count the graph was built from, used to detect newly generated roads
+    pub source_road_count: usize,
+    grid: HashMap<IVec2, Vec<usize>>,
+}
+
+fn grid_cell(position: Vec3) -> IVec2 {
+    IVec2::new(
+        (position.x / GRID_CELL_SIZE).floor() as i32,
+        (position.z / GRID_CELL_SIZE).floor() as i32,
+    )
+}
+
+impl SidewalkGraph {
+    /// Builds sidewalks `offset` meters outside each road edge, with a waypoint every `spacing`
+    /// meters. Sidewalk ends meeting at junctions are linked so pedestrians can cross.
+    pub fn build(network: &RoadNetwork, offset: f32, spacing: f32) -> Self {
+        let mut graph = Self {
+            source_road_count: network.roads.len(),
+            ..default()
+        };
+        let mut endpoints = Vec::new();
+
+        // Sort for deterministic node order
+        let mut road_ids: Vec<u64> = network.roads.keys().copied().collect();
+        road_ids.sort_unstable();
+
+        for id in road_ids {
+            let road = &network.roads[&id];
+            let samples = ((road.length() / spacing).ceil() as usize).max(1);
+            let side_distance = road.road_type.width() * 0.5 + offset;
+
+            for side in [-1.0, 1.0] {
+                let mut previous = None;
+                for i in 0..=samples {
+                    let t = i as f32 / samples as f32;
+                    let a = road.evaluate((t - 0.01).max(0.0));
+                    let b = road.evaluate((t + 0.01).min(1.0));
+                    let tangent = Vec3::new(b.x - a.x, 0.0, b.z - a.z).normalize_or_zero();
+                    let right = tangent.cross(Vec3::Y);
+                    let node = graph.add_node(road.evaluate(t) + right * side_distance * side);
+
+                    if let Some(prev) = previous {
+                        graph.link(prev, node);
+                    }
+                    if i == 0 || i == samples {
+                        endpoints.push(node);
+                    }
+                    previous = Some(node);
+                }
+            }
+        }
+
+        // Connect sidewalk ends that meet at corners and crossings
+        let link_distance = spacing.max(GRID_CELL_SIZE);
+        for &node in &endpoints {
+            for other in graph.nodes_near(graph.nodes[node], link_distance) {
+                if other != node && endpoints.contains(&other) {
+                    graph.link(node, other);
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn add_node(&mut self, position: Vec3) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(position);
+        self.edges.push(Vec::new());
+        self.grid
+            .entry(grid_cell(position))
+            .or_default()
+            .push(index);
+        index
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        if !self.edges[a].contains(&b) {
+            self.edges[a].push(b);
+            self.edges[b].push(a);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Waypoints within `radius` of `position` on the XZ plane
+    pub fn nodes_near(&self, position: Vec3, radius: f32) -> Vec<usize> {
+        let min = grid_cell(position - Vec3::splat(radius));
+        let max = grid_cell(position + Vec3::splat(radius));
+        let mut result = Vec::new();
+        for x in min.x..=max.x {
+            for z in min.y..=max.y {
+                let Some(cell) = self.grid.get(&IVec2::new(x, z)) else {
+                    continue;
+                };
+                result.extend(cell.iter().copied().filter(|&i| {
+                    self.nodes[i].xz().distance_squared(position.xz()) <= radius * radius
+                }));
+            }
+        }
+        result
+    }
+
+    /// Closest waypoint within `max_distance`
+    pub fn nearest_node(&self, position: Vec3, max_distance: f32) -> Option<usize> {
+        self.nodes_near(position, max_distance)
+            .into_iter()
+            .min_by(|&a, &b| {
+                let da = self.nodes[a].xz().distance_squared(position.xz());
+                let db = self.nodes[b].xz().distance_squared(position.xz());
+                da.total_cmp(&db)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::road_network::RoadType;
+
+    #[test]
+    fn test_sidewalks_flank_road() {
+        let mut network = RoadNetwork::default();
+        network.add_road(Vec3::ZERO, Vec3::new(100.0, 0.0, 0.0), RoadType::Alley);
+
+        let graph = SidewalkGraph::build(&network, 2.0, 10.0);
+
+        // Two sides, 11 waypoints each
+        assert_eq!(graph.nodes.len(), 22);
+        let half = RoadType::Alley.width() * 0.5 + 2.0;
+        assert!(graph.nodes.iter().all(|n| (n.z.abs() - half).abs() < 1e-3));
+
+        let start = graph.nearest_node(Vec3::new(0.0, 0.0, half), 1.0).unwrap();
+        assert!(!graph.edges[start].is_empty());
+    }
+}
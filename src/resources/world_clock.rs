@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+const HOURS_PER_DAY: f32 = 24.0;
+
+/// In-game time of day. Advanced by `lighting.day_length_minutes`; the sun,
+/// street lamps and NPC routines all follow it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct WorldClock {
+    /// Hour of the day in 0..24
+    pub hour: f32,
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self { hour: 12.0 }
+    }
+}
+
+impl WorldClock {
+    pub fn at(hour: f32) -> Self {
+        Self {
+            hour: hour.rem_euclid(HOURS_PER_DAY),
+        }
+    }
+
+    /// Moves the clock on by `seconds` of real time for a day lasting
+    /// `day_length_minutes`; a zero day length stops the clock
+    pub fn advance(&mut self, seconds: f32, day_length_minutes: f32) {
+        self.hour =
+            (self.hour + Self::hours_in(seconds, day_length_minutes)).rem_euclid(HOURS_PER_DAY);
+    }
+
+    /// In-game hours that `seconds` of real time stand for
+    pub fn hours_in(seconds: f32, day_length_minutes: f32) -> f32 {
+        if day_length_minutes <= 0.0 {
+            0.0
+        } else {
+            seconds * HOURS_PER_DAY / (day_length_minutes * 60.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_wraps_past_midnight_and_stops_without_day_length() {
+        let mut clock = WorldClock::at(23.0);
+        // A 24 minute day runs an in-game hour per real minute
+        clock.advance(90.0, 24.0);
+        assert!((clock.hour - 0.5).abs() < 1e-4);
+
+        clock.advance(600.0, 0.0);
+        assert!((clock.hour - 0.5).abs() < 1e-4);
+        assert_eq!(WorldClock::at(-1.0).hour, 23.0);
+    }
+}
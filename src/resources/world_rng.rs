@@ -1,11 +1,51 @@
+use crate::systems::world::unified_world::ContentLayer;
 use bevy::prelude::*;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 
+/// Seed every piece of generated world content derives from.
+/// Set from `WorldConfig::seed` (or the clock) and restored from save games.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldSeed(pub u64);
+
+/// SplitMix64 finalizer, spreads nearby keys across the whole seed space
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+impl WorldSeed {
+    /// Seed from the current time, so unseeded worlds differ every run
+    pub fn from_time() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(12345);
+        Self(seed)
+    }
+
+    /// RNG for one content layer of one chunk, keyed by
+    /// `chunk_streaming::chunk_content_key`.
+    /// Independent of the order chunks are generated in.
+    pub fn chunk_rng(&self, chunk_key: u64, layer: ContentLayer) -> StdRng {
+        StdRng::seed_from_u64(mix(mix(self.0 ^ mix(chunk_key)) ^ layer as u64))
+    }
+}
+
+impl Default for WorldSeed {
+    fn default() -> Self {
+        Self::from_time()
+    }
+}
+
 /// Deterministic RNG resource for world generation
 /// Ensures reproducible worlds given the same seed
 #[derive(Resource)]
 pub struct WorldRng {
+    /// Seed the RNG was created from (persisted in save games)
+    seed: u64,
     /// Global RNG for world-level decisions
     global_rng: StdRng,
 }
@@ -14,10 +54,16 @@ impl WorldRng {
     /// Create new WorldRng with given seed
     pub fn new(seed: u64) -> Self {
         Self {
+            seed,
             global_rng: StdRng::seed_from_u64(seed),
         }
     }
 
+    /// Seed this RNG was created from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     /// Get global RNG for world-level decisions
     pub fn global(&mut self) -> &mut StdRng {
         &mut self.global_rng
@@ -27,10 +73,40 @@ impl WorldRng {
 impl Default for WorldRng {
     fn default() -> Self {
         // Use current timestamp as default seed for variety
-        let seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(12345);
-        Self::new(seed)
+        Self::new(WorldSeed::from_time().0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_chunk_rng_is_reproducible_per_chunk_and_layer() {
+        let seed = WorldSeed(42);
+        let draw = |key: u64, layer: ContentLayer| -> Vec<u32> {
+            let mut rng = seed.chunk_rng(key, layer);
+            (0..4).map(|_| rng.r#gen()).collect()
+        };
+
+        assert_eq!(
+            draw(7, ContentLayer::Buildings),
+            draw(7, ContentLayer::Buildings)
+        );
+        assert_ne!(
+            draw(7, ContentLayer::Buildings),
+            draw(8, ContentLayer::Buildings)
+        );
+        assert_ne!(
+            draw(7, ContentLayer::Buildings),
+            draw(7, ContentLayer::Vehicles)
+        );
+        assert_ne!(
+            WorldSeed(43)
+                .chunk_rng(7, ContentLayer::Buildings)
+                .r#gen::<u64>(),
+            seed.chunk_rng(7, ContentLayer::Buildings).r#gen::<u64>()
+        );
     }
 }
@@ -4,7 +4,7 @@ use rand::prelude::*;
 use crate::GameConfig;
 use crate::constants::WorldEnvConfig;
 use crate::factories::NPCFactory;
-use crate::resources::NPCAssetCache;
+use crate::resources::{NPCAssetCache, WorldRng};
 
 /// UNIFIED NPC SETUP SYSTEM
 /// Consolidates setup_new_npcs (good patterns) and setup_npcs (bad patterns)
@@ -19,13 +19,15 @@ pub fn setup_initial_npcs_unified(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut cache: ResMut<NPCAssetCache>,
+    mut world_rng: ResMut<WorldRng>,
     env: Res<WorldEnvConfig>,
     _game_config: Res<GameConfig>,
 ) {
     // Initialize focused NPCFactory for consistent spawning following AGENT.md principles
     let npc_factory = NPCFactory::new();
 
-    let mut rng = thread_rng();
+    // Seeded so the starting population is the same for the same world seed
+    let rng = world_rng.global();
     let mut spawned_count = 0;
     let max_attempts = 500; // Increased for higher spawn count
     let mut attempts = 0;
@@ -61,6 +63,7 @@ pub fn setup_initial_npcs_unified(
             &mut cache,
             spawn_position,
             None, // Auto-select NPC type
+            rng,
         ) {
             Ok(_entity) => {
                 spawned_count += 1;
@@ -3,19 +3,22 @@ use crate::components::MovementTracker;
 use crate::components::{
     ActiveEntity, BodyPart, ControlState, ControlsDisplay, ControlsText, DynamicTerrain,
     HumanAnimation, HumanMovement, MainCamera, Player, PlayerBody, PlayerControlled, PlayerHead,
-    PlayerLeftArm, PlayerLeftLeg, PlayerRightArm, PlayerRightLeg, PlayerTorso, UnderwaterSettings,
-    VehicleControlType,
+    PlayerHealth, PlayerLeftArm, PlayerLeftLeg, PlayerRightArm, PlayerRightLeg, PlayerTorso,
+    UnderwaterSettings, VehicleControlType,
 };
 use crate::config::GameConfig;
 use crate::constants::WorldEnvConfig;
+use crate::factories::clipmap_terrain::create_plateau_heightfield;
 use crate::factories::spawn_bridge;
 use crate::systems::audio::FootstepTimer;
 
 use crate::systems::spawn_validation::{SpawnRegistry, SpawnableType};
+use crate::systems::world::terrain_height::TerrainHeightService;
 use bevy::core_pipeline::bloom::Bloom;
 use bevy::core_pipeline::prepass::DepthPrepass;
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::prelude::*;
+use bevy::render::experimental::occlusion_culling::OcclusionCulling;
 use bevy_rapier3d::prelude::*;
 
 pub fn setup_basic_world(
@@ -25,6 +28,7 @@ pub fn setup_basic_world(
     mut spawn_registry: ResMut<SpawnRegistry>,
     config: Res<GameConfig>,
     env: Res<WorldEnvConfig>,
+    terrain: Res<TerrainHeightService>,
 ) {
     // Config drift guards: Verify consistency between WorldEnvConfig and GameConfig
     // These assertions prevent bugs where road_network.rs and world.rs disagree on island boundaries
@@ -69,42 +73,50 @@ pub fn setup_basic_world(
     // No longer need WorldRoot - spawn entities directly in world space
 
     // Camera (stays outside WorldRoot - doesn't move with world shifts)
-    commands.spawn((
-        MainCamera,
-        Camera3d::default(),
-        Msaa::Off,
-        DepthPrepass,
-        Camera {
-            hdr: true, // Enable HDR for particle bloom effects
-            ..default()
-        },
-        Tonemapping::AcesFitted, // Tone mapping for HDR display
-        Bloom {
-            intensity: 0.05, // Very subtle bloom for flame cores only
-            low_frequency_boost: 0.0,
-            low_frequency_boost_curvature: 0.0,
-            high_pass_frequency: 1.0,
-            ..default()
-        },
-        Projection::Perspective(PerspectiveProjection {
-            // Extended for proper horizon/skybox rendering (skybox at 9500 units)
-            far: 10000.0,
-            ..default()
-        }),
-        Transform::from_xyz(0.0, 15.0, 25.0).looking_at(Vec3::ZERO, Vec3::Y),
-        UnderwaterSettings {
-            sea_level: env.sea_level,
-            // Research-based realistic ocean parameters:
-            // - Red light absorbed in top 10m
-            // - Blue/green penetrate deepest
-            // - At 10m depth: only 16% light remains
-            fog_density: 0.25, // Moderate density for clear ocean (0.1-0.5 range)
-            absorption: Vec3::new(0.8, 0.3, 0.15), // RED >> GREEN > BLUE (realistic attenuation)
-            scatter_color: Vec3::new(0.02, 0.35, 0.48), // Deep blue-cyan (clear ocean at depth)
-            enabled: 1,
-        },
-        // Camera in direct world coordinates
-    ));
+    let camera = commands
+        .spawn((
+            MainCamera,
+            Camera3d::default(),
+            Msaa::Off,
+            DepthPrepass,
+            Camera {
+                hdr: true, // Enable HDR for particle bloom effects
+                ..default()
+            },
+            Tonemapping::AcesFitted, // Tone mapping for HDR display
+            Bloom {
+                intensity: 0.05, // Very subtle bloom for flame cores only
+                low_frequency_boost: 0.0,
+                low_frequency_boost_curvature: 0.0,
+                high_pass_frequency: 1.0,
+                ..default()
+            },
+            Projection::Perspective(PerspectiveProjection {
+                // Extended for proper horizon/skybox rendering (skybox at 9500 units)
+                far: 10000.0,
+                ..default()
+            }),
+            Transform::from_xyz(0.0, 15.0, 25.0).looking_at(Vec3::ZERO, Vec3::Y),
+            UnderwaterSettings {
+                sea_level: env.sea_level,
+                // Research-based realistic ocean parameters:
+                // - Red light absorbed in top 10m
+                // - Blue/green penetrate deepest
+                // - At 10m depth: only 16% light remains
+                fog_density: 0.25, // Moderate density for clear ocean (0.1-0.5 range)
+                absorption: Vec3::new(0.8, 0.3, 0.15), // RED >> GREEN > BLUE (realistic attenuation)
+                scatter_color: Vec3::new(0.02, 0.35, 0.48), // Deep blue-cyan (clear ocean at depth)
+                enabled: 1,
+            },
+            // Camera in direct world coordinates
+        ))
+        .id();
+
+    // GPU frustum culling runs in Bevy's mesh preprocessing; this adds the Hi-Z
+    // occlusion test on top, reusing the depth prepass above
+    if config.performance.gpu_occlusion_culling {
+        commands.entity(camera).insert(OcclusionCulling);
+    }
 
     // Controls UI (stays outside WorldRoot - UI doesn't move with world shifts)
     commands
@@ -152,6 +164,7 @@ pub fn setup_basic_world(
         env.terrain.size,
         "Left",
         &config,
+        &terrain,
     );
 
     // RIGHT TERRAIN ISLAND
@@ -163,6 +176,7 @@ pub fn setup_basic_world(
         env.terrain.size,
         "Right",
         &config,
+        &terrain,
     );
 
     // OCEAN FLOOR - Extended to horizon for proper water depth rendering
@@ -270,6 +284,7 @@ pub fn setup_basic_world(
         env.terrain.size,
         "Grid",
         &config,
+        &terrain,
     );
 
     // GRID TERRAIN BEACHES (all 4 edges with corners filled)
@@ -330,6 +345,7 @@ pub fn setup_basic_world(
         ControlState::default(),
         PlayerControlled,
         VehicleControlType::Walking,
+        PlayerHealth::new(config.health.max_health),
         crate::components::unified_water::CurrentWaterRegion {
             region_entity: None,
         },
@@ -461,18 +477,27 @@ fn spawn_terrain_island(
     size: f32,
     name: &str,
     config: &GameConfig,
+    terrain: &TerrainHeightService,
 ) {
     let half_size = size / 2.0;
     let collider_half_height = 0.05;
+    let origin = position - Vec3::Y * collider_half_height;
+
+    // Relief gets a heightfield (rendered by the terrain clipmap); flat islands keep the slab
+    let collider = if terrain.has_relief() {
+        create_plateau_heightfield(origin, size, 257, terrain)
+    } else {
+        Collider::cuboid(half_size, collider_half_height, half_size)
+    };
 
     commands.spawn((
         DynamicTerrain,
         Mesh3d(meshes.add(Plane3d::default().mesh().size(size, size))),
         MeshMaterial3d(materials.add(Color::srgb(0.85, 0.75, 0.6))),
         // Lower visual mesh by half collider height to align top surface with physics
-        Transform::from_translation(position - Vec3::Y * collider_half_height),
+        Transform::from_translation(origin),
         RigidBody::Fixed,
-        Collider::cuboid(half_size, collider_half_height, half_size),
+        collider,
         CollisionGroups::new(
             config.physics.static_group,
             config.physics.vehicle_group | config.physics.character_group,
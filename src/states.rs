@@ -1,11 +1,38 @@
 use bevy::prelude::*;
 
 /// Application state machine for world generation and gameplay
-/// AssetLoading (with splash screen) -> WorldGeneration -> InGame
+/// AssetLoading (with splash screen) -> MainMenu -> WorldGeneration -> InGame
+/// Esc during WorldGeneration returns to MainMenu
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum AppState {
     #[default]
     AssetLoading,
     WorldGeneration,
     InGame,
+    MainMenu,
+}
+
+/// Pause menu screens; only exists while `AppState::InGame`
+/// Closed -> Paused (Esc) -> Settings / Controls -> back to Paused
+#[derive(SubStates, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[source(AppState = AppState::InGame)]
+pub enum MenuState {
+    #[default]
+    Closed,
+    Paused,
+    Settings,
+    Controls,
+}
+
+/// Main menu screens; only exists while `AppState::MainMenu`
+/// Title -> NewGame (seed entry) / Settings -> back to Title; CrashReport -> Title
+#[derive(SubStates, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[source(AppState = AppState::MainMenu)]
+pub enum MainMenuPage {
+    #[default]
+    Title,
+    NewGame,
+    Settings,
+    /// Offered on launch when the previous run left a crash report
+    CrashReport,
 }
@@ -0,0 +1,194 @@
+//! Accessibility settings.
+//!
+//! `GameConfig::accessibility` starts from `assets/config/accessibility.ron`;
+//! whatever the player changes in the settings menu is written to the active
+//! profile's data directory and loaded over it on the next launch, or as soon
+//! as that profile is picked again. UI scale goes straight to
+//! Bevy's `UiScale`, colorblind palettes are looked up by the HUD systems that
+//! color icons and warnings, and audio cues are captioned here while subtitles
+//! are on.
+
+use crate::components::{CarAlarm, HornHonked, PedestrianBark, Subtitles};
+use crate::config::{AccessibilityConfig, ConfigFieldChanged, ConfigReloadedEvent, GameConfig};
+use crate::systems::audio::HasSiren;
+use crate::systems::profiles::{ActiveProfile, ProfileChangedEvent};
+use bevy::audio::SpatialListener;
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Seconds a horn caption stays up
+const HORN_CAPTION_SECONDS: f32 = 2.0;
+
+/// Seconds a pedestrian's shout stays up
+const BARK_CAPTION_SECONDS: f32 = 2.5;
+
+/// Siren captions are refreshed every frame a siren is in earshot, so they
+/// only linger this long once it's gone
+const SIREN_CAPTION_SECONDS: f32 = 1.0;
+
+/// Where settings edited in the menu are kept for the profile in `profile_dir`
+pub fn settings_path(profile_dir: &Path) -> PathBuf {
+    profile_dir.join("accessibility.ron")
+}
+
+/// Settings from the config files, before any saved edits; what a profile
+/// without saved settings falls back to
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ConfiguredAccessibility(pub AccessibilityConfig);
+
+pub fn read_settings(path: &Path) -> Result<AccessibilityConfig, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut settings: AccessibilityConfig = ron::from_str(&contents).map_err(|e| e.to_string())?;
+    settings.validate_and_clamp();
+    Ok(settings)
+}
+
+pub fn write_settings(path: &Path, settings: &AccessibilityConfig) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let contents = ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+/// Saves the settings after an edit from the menu
+pub fn save_accessibility(profile_dir: &Path, settings: &AccessibilityConfig) {
+    let path = settings_path(profile_dir);
+    if let Err(e) = write_settings(&path, settings) {
+        warn!(
+            "⚠️ Failed to save accessibility settings to {:?}: {}",
+            path, e
+        );
+    }
+}
+
+fn saved_settings(profile_dir: &Path) -> Option<AccessibilityConfig> {
+    let path = settings_path(profile_dir);
+    if !path.is_file() {
+        return None;
+    }
+    read_settings(&path)
+        .inspect_err(|e| warn!("⚠️ Failed to read accessibility settings {:?}: {}", path, e))
+        .ok()
+}
+
+/// Replaces the shipped defaults with the player's saved settings, if any
+pub fn load_saved_accessibility(
+    mut commands: Commands,
+    profile: Res<ActiveProfile>,
+    mut config: ResMut<GameConfig>,
+    mut reloaded: EventWriter<ConfigReloadedEvent>,
+) {
+    commands.insert_resource(ConfiguredAccessibility(config.accessibility.clone()));
+    if let Some(settings) = saved_settings(&profile.data_directory()) {
+        config.accessibility = settings;
+        reloaded.write(ConfigReloadedEvent);
+    }
+}
+
+/// Swaps in the settings of the profile picked in the main menu
+pub fn reload_profile_accessibility(
+    mut changed: EventReader<ProfileChangedEvent>,
+    profile: Res<ActiveProfile>,
+    configured: Res<ConfiguredAccessibility>,
+    mut config: ResMut<GameConfig>,
+    mut reloaded: EventWriter<ConfigReloadedEvent>,
+) {
+    if changed.read().last().is_none() {
+        return;
+    }
+    config.accessibility =
+        saved_settings(&profile.data_directory()).unwrap_or_else(|| configured.0.clone());
+    reloaded.write(ConfigReloadedEvent);
+}
+
+/// Applies `ui_scale` on the first frame, on every `ConfigReloadedEvent`
+/// and when a hot reload changes it
+pub fn apply_ui_scale(
+    mut reloaded: EventReader<ConfigReloadedEvent>,
+    mut changed: EventReader<ConfigFieldChanged>,
+    config: Res<GameConfig>,
+    mut applied: Local<bool>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let rescaled = changed
+        .read()
+        .filter(|change| change.file == "accessibility.ron" && change.path == "ui_scale")
+        .count()
+        > 0;
+    if reloaded.read().count() == 0 && !rescaled && *applied {
+        return;
+    }
+    if ui_scale.0 != config.accessibility.ui_scale {
+        ui_scale.0 = config.accessibility.ui_scale;
+    }
+    *applied = true;
+}
+
+/// Captions horns, pedestrians shouting, and the sirens and car alarms within
+/// earshot
+#[allow(clippy::too_many_arguments)]
+pub fn caption_audio_cues(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut subtitles: ResMut<Subtitles>,
+    mut horns: EventReader<HornHonked>,
+    mut barks: EventReader<PedestrianBark>,
+    listener: Query<&GlobalTransform, With<SpatialListener>>,
+    sirens: Query<&GlobalTransform, With<HasSiren>>,
+    alarms: Query<(&GlobalTransform, &CarAlarm)>,
+) {
+    subtitles.tick(time.delta_secs());
+    if !config.accessibility.subtitles {
+        horns.clear();
+        barks.clear();
+        subtitles.lines.clear();
+        return;
+    }
+    let Ok(listener) = listener.single() else {
+        horns.clear();
+        barks.clear();
+        return;
+    };
+    let listener = listener.translation();
+    let audible = |position: Vec3| position.distance(listener) <= config.audio.max_audio_distance;
+
+    if horns.read().filter(|horn| audible(horn.position)).count() > 0 {
+        subtitles.show("subtitle.horn", HORN_CAPTION_SECONDS);
+    }
+    for bark in barks.read().filter(|bark| audible(bark.position)) {
+        subtitles.show(bark.line, BARK_CAPTION_SECONDS);
+    }
+    if sirens.iter().any(|siren| audible(siren.translation())) {
+        subtitles.show("subtitle.siren", SIREN_CAPTION_SECONDS);
+    }
+    if alarms
+        .iter()
+        .any(|(alarm, state)| state.sounding() && audible(alarm.translation()))
+    {
+        subtitles.show("subtitle.car_alarm", SIREN_CAPTION_SECONDS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ColorblindMode;
+
+    #[test]
+    fn test_settings_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("gta_game_accessibility_{}", std::process::id()))
+            .join("accessibility.ron");
+        let settings = AccessibilityConfig {
+            ui_scale: 1.5,
+            colorblind_mode: ColorblindMode::Tritanopia,
+            subtitles: true,
+            reduced_camera_shake: true,
+        };
+        write_settings(&path, &settings).unwrap();
+        assert_eq!(read_settings(&path).unwrap(), settings);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
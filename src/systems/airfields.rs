@@ -0,0 +1,186 @@
+//! Runways, helipads and aircraft landing gear.
+//!
+//! Airfields are laid out in `assets/config/airfields.ron` and spawned through
+//! the `AirfieldFactory`, which records each one in the `AirfieldRegistry` for
+//! AI and the map. F16 landing gear is toggled with its own key; touching down
+//! with the gear up, sinking too fast or landing too fast damages the aircraft.
+
+use crate::components::{
+    AirfieldRegistry, ControlState, F16, LandingGear, LandingGearStrut, SimpleF16Specs,
+    SimpleF16SpecsHandle, VehicleHealth,
+};
+use crate::factories::airfield_factory::{AirfieldFactory, AirfieldList};
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+/// Horizontal speed lost per second while sliding on the belly (fraction kept)
+const BELLY_SLIDE_DECAY: f32 = 0.4;
+
+pub fn spawn_airfields(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut registry: ResMut<AirfieldRegistry>,
+) {
+    // Airfields persist across state re-entry
+    if !registry.is_empty() {
+        return;
+    }
+
+    let path = format!(
+        "{}/config/airfields.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    let list = match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str::<AirfieldList>(&content).unwrap_or_else(|e| {
+            error!("Failed to parse airfields at '{}': {}", path, e);
+            AirfieldList::default()
+        }),
+        Err(e) => {
+            info!("ℹ️ No airfield config found: {}", e);
+            AirfieldList::default()
+        }
+    };
+
+    let factory = AirfieldFactory::new(&mut materials);
+    for runway in &list.runways {
+        factory.spawn_runway(&mut commands, &mut meshes, &mut registry, runway);
+    }
+    for helipad in &list.helipads {
+        factory.spawn_helipad(&mut commands, &mut meshes, &mut registry, helipad);
+    }
+}
+
+/// Damage for a touchdown at the given sink rate and ground speed
+pub fn touchdown_damage(
+    specs: &SimpleF16Specs,
+    gear_down: bool,
+    sink_rate: f32,
+    ground_speed: f32,
+) -> f32 {
+    let excess = (sink_rate - specs.max_touchdown_sink_rate).max(0.0)
+        + (ground_speed - specs.max_touchdown_speed).max(0.0);
+    let belly = if gear_down {
+        0.0
+    } else {
+        specs.belly_landing_damage
+    };
+    belly + excess * specs.hard_landing_damage
+}
+
+/// Gear toggling, extension and touchdown checks for F16s
+#[allow(clippy::type_complexity)]
+pub fn landing_gear_system(
+    time: Res<Time>,
+    rapier_context: ReadRapierContext,
+    specs_assets: Res<Assets<SimpleF16Specs>>,
+    mut aircraft: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut LandingGear,
+            &SimpleF16SpecsHandle,
+            Option<&ControlState>,
+            Option<&mut VehicleHealth>,
+        ),
+        With<F16>,
+    >,
+) {
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+    let dt = time.delta_secs();
+
+    for (entity, transform, mut velocity, mut gear, specs_handle, control_state, health) in
+        &mut aircraft
+    {
+        let Some(specs) = specs_assets.get(&specs_handle.0) else {
+            continue;
+        };
+
+        let on_ground = context
+            .cast_ray(
+                transform.translation,
+                Vec3::NEG_Y,
+                specs.touchdown_probe_length,
+                true,
+                QueryFilter::default().exclude_rigid_body(entity),
+            )
+            .is_some();
+
+        // The gear can't be raised while it carries the aircraft
+        if control_state.is_some_and(|control| control.gear_toggle)
+            && (gear.airborne || !gear.deployed)
+        {
+            gear.deployed = !gear.deployed;
+        }
+        let target = if gear.deployed { 1.0 } else { 0.0 };
+        let step = dt / specs.gear_cycle_time;
+        gear.extension += (target - gear.extension).clamp(-step, step);
+
+        let ground_speed = velocity.linvel.with_y(0.0).length();
+        if !on_ground {
+            // Only count real flight, so spawn drops and bounces aren't landings
+            if ground_speed > specs.stall_speed * 0.5 {
+                gear.airborne = true;
+            }
+            continue;
+        }
+
+        if gear.airborne {
+            gear.airborne = false;
+            let damage = touchdown_damage(specs, gear.is_down(), -velocity.linvel.y, ground_speed);
+            if damage > 0.0
+                && let Some(mut health) = health
+            {
+                health.current = (health.current - damage).max(0.0);
+                #[cfg(feature = "debug-ui")]
+                info!("F16 {:?} hard landing: {:.0} damage", entity, damage);
+            }
+        }
+
+        // Without wheels the fuselage scrapes to a stop
+        if !gear.is_down() {
+            let keep = BELLY_SLIDE_DECAY.powf(dt);
+            velocity.linvel.x *= keep;
+            velocity.linvel.z *= keep;
+        }
+    }
+}
+
+/// Scales gear legs with their aircraft's extension and hides them when stowed
+pub fn update_landing_gear_struts(
+    gear_query: Query<&LandingGear, Changed<LandingGear>>,
+    mut struts: Query<(&ChildOf, &mut Transform, &mut Visibility), With<LandingGearStrut>>,
+) {
+    for (child_of, mut transform, mut visibility) in &mut struts {
+        let Ok(gear) = gear_query.get(child_of.parent()) else {
+            continue;
+        };
+        transform.scale = Vec3::splat(gear.extension.max(0.01));
+        *visibility = if gear.extension > 0.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touchdown_damage() {
+        let specs = SimpleF16Specs::default();
+
+        assert_eq!(touchdown_damage(&specs, true, 2.0, 70.0), 0.0);
+        assert_eq!(
+            touchdown_damage(&specs, false, 2.0, 70.0),
+            specs.belly_landing_damage
+        );
+        let hard = touchdown_damage(&specs, true, specs.max_touchdown_sink_rate + 3.0, 70.0);
+        assert!((hard - 3.0 * specs.hard_landing_damage).abs() < 1e-4);
+    }
+}
@@ -1,8 +1,121 @@
 #![allow(clippy::type_complexity)]
-use crate::components::{ActiveEntity, HumanAnimation, HumanMovement, Player};
+use crate::components::{
+    ActiveEntity, CarAlarm, ControlState, HumanAnimation, HumanMovement, MainCamera,
+    PedestrianBark, Player, PoliceUnit, SimpleCarSpecs, SimpleCarSpecsHandle, TirePhysics,
+    Transmission, VehicleState,
+};
+use crate::config::GameConfig;
+use crate::resources::{District, DistrictMap};
 
+use bevy::audio::{DefaultSpatialScale, SpatialScale, Volume};
 use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
 use rand::Rng;
+use serde::Deserialize;
+
+/// Speed of sound used for the doppler shift (m/s)
+const SPEED_OF_SOUND: f32 = 343.0;
+
+/// Distance between the listener's ears; only affects panning
+const LISTENER_EAR_GAP: f32 = 2.0;
+
+/// Seconds between engine voice reassignments
+const ENGINE_VOICE_INTERVAL: f32 = 0.25;
+
+/// City ambience fades out between these camera heights
+const AMBIENT_FADE_HEIGHTS: (f32, f32) = (60.0, 250.0);
+
+/// Gain change per second when crossfading between district ambiences
+const AMBIENT_CROSSFADE_RATE: f32 = 0.5;
+
+/// Clip paths from `assets/config/audio.ron`, relative to the assets folder
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AudioClipPaths {
+    pub engine: Option<String>,
+    pub siren: Option<String>,
+    pub ambient_city: Option<String>,
+    pub footstep: Option<String>,
+    pub tire_screech: Option<String>,
+    pub horn: Option<String>,
+    pub car_alarm: Option<String>,
+    pub bark: Option<String>,
+}
+
+/// Loaded clips; a missing file leaves its slot empty and that sound silent
+#[derive(Resource, Debug, Default)]
+pub struct AudioClips {
+    pub engine: Option<Handle<AudioSource>>,
+    pub siren: Option<Handle<AudioSource>>,
+    pub ambient_city: Option<Handle<AudioSource>>,
+    pub footstep: Option<Handle<AudioSource>>,
+    pub tire_screech: Option<Handle<AudioSource>>,
+    pub horn: Option<Handle<AudioSource>>,
+    pub car_alarm: Option<Handle<AudioSource>>,
+    pub bark: Option<Handle<AudioSource>>,
+}
+
+impl AudioClips {
+    pub fn handles(&self) -> impl Iterator<Item = &Handle<AudioSource>> {
+        [
+            &self.engine,
+            &self.siren,
+            &self.ambient_city,
+            &self.footstep,
+            &self.tire_screech,
+            &self.horn,
+            &self.car_alarm,
+            &self.bark,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VehicleSoundKind {
+    Engine,
+    Siren,
+    TireScreech,
+    Horn,
+    Alarm,
+}
+
+/// Looping spatial sound attached as a child of a vehicle
+#[derive(Component, Debug)]
+pub struct VehicleSound {
+    pub kind: VehicleSoundKind,
+}
+
+/// On a vehicle: its engine sound child, while it holds an engine voice
+#[derive(Component, Debug)]
+pub struct EngineVoice(pub Entity);
+
+/// On a car: its looping tire screech, while the tires skid
+#[derive(Component, Debug)]
+pub struct TireScreech(pub Entity);
+
+/// On the player's car: its horn sound, while the horn is held
+#[derive(Component, Debug)]
+pub struct HornSound(pub Entity);
+
+/// On a parked car: its alarm sound, while the alarm goes off
+#[derive(Component, Debug)]
+pub struct AlarmSound(pub Entity);
+
+/// On a police unit: its siren has been spawned
+#[derive(Component, Debug)]
+pub struct HasSiren;
+
+#[derive(Component, Debug)]
+pub struct AmbientCitySound;
+
+/// Looping ambience for one district, faded in while the listener is inside it
+#[derive(Component, Debug)]
+pub struct DistrictAmbientSound {
+    pub district: District,
+    pub gain: f32,
+}
 
 #[derive(Component)]
 pub struct FootstepTimer {
@@ -36,6 +149,8 @@ impl Default for FootstepSound {
 pub fn footstep_system(
     mut commands: Commands,
     time: Res<Time>,
+    clips: Option<Res<AudioClips>>,
+    config: Res<GameConfig>,
     mut player_query: Query<
         (
             Entity,
@@ -62,10 +177,20 @@ pub fn footstep_system(
         timer.timer.tick(time.delta());
         if timer.timer.just_finished() {
             // Spawn footstep sound
-            commands.spawn((
-                Transform::from_translation(transform.translation),
-                FootstepSound::default(),
-            ));
+            let footstep = commands
+                .spawn((
+                    Transform::from_translation(transform.translation),
+                    FootstepSound::default(),
+                ))
+                .id();
+            if let Some(clip) = clips.as_ref().and_then(|clips| clips.footstep.clone()) {
+                commands.entity(footstep).insert((
+                    AudioPlayer(clip),
+                    PlaybackSettings::ONCE
+                        .with_spatial(true)
+                        .with_volume(Volume::Linear(config.audio.footstep_volume)),
+                ));
+            }
 
             // Add variation to next step interval
             let new_interval = rand::thread_rng().gen_range(0.45..0.55);
@@ -90,3 +215,494 @@ pub fn cleanup_footstep_sounds(
         }
     }
 }
+
+/// Loads the clip list and sets up global volume and distance attenuation.
+/// Inside `fade_distance` sounds play at full volume; beyond it they fall off
+/// with the inverse square of the distance.
+pub fn load_audio_clips(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    config: Res<GameConfig>,
+) {
+    let base = crate::util::asset_path::get_assets_base_path();
+    let path = format!("{base}/config/audio.ron");
+    let paths = match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str::<AudioClipPaths>(&content).unwrap_or_else(|e| {
+            error!("Failed to parse audio config at '{}': {}", path, e);
+            AudioClipPaths::default()
+        }),
+        Err(e) => {
+            info!("ℹ️ No audio config found, running silent: {}", e);
+            AudioClipPaths::default()
+        }
+    };
+
+    // Only load clips that exist so missing audio doesn't spam asset errors
+    let load = |clip: &Option<String>| {
+        let clip = clip.as_ref()?;
+        if std::path::Path::new(&base).join(clip).exists() {
+            Some(asset_server.load(clip.clone()))
+        } else {
+            info!("🔇 Audio clip '{}' not found, skipping", clip);
+            None
+        }
+    };
+
+    commands.insert_resource(AudioClips {
+        engine: load(&paths.engine),
+        siren: load(&paths.siren),
+        ambient_city: load(&paths.ambient_city),
+        footstep: load(&paths.footstep),
+        tire_screech: load(&paths.tire_screech),
+        horn: load(&paths.horn),
+        car_alarm: load(&paths.car_alarm),
+        bark: load(&paths.bark),
+    });
+    commands.insert_resource(GlobalVolume::new(Volume::Linear(
+        config.audio.master_volume,
+    )));
+    commands.insert_resource(DefaultSpatialScale(SpatialScale::new(
+        1.0 / config.audio.fade_distance,
+    )));
+}
+
+pub fn attach_spatial_listener(
+    mut commands: Commands,
+    cameras: Query<Entity, (With<MainCamera>, Without<SpatialListener>)>,
+) {
+    for camera in &cameras {
+        commands
+            .entity(camera)
+            .insert(SpatialListener::new(LISTENER_EAR_GAP));
+    }
+}
+
+pub fn spawn_ambient_city_sound(
+    mut commands: Commands,
+    clips: Res<AudioClips>,
+    config: Res<GameConfig>,
+) {
+    let Some(clip) = clips.ambient_city.clone() else {
+        return;
+    };
+    commands.spawn((
+        AmbientCitySound,
+        AudioPlayer(clip),
+        PlaybackSettings::LOOP.with_volume(Volume::Linear(config.audio.ambient_volume)),
+    ));
+}
+
+/// One silent loop per district with an ambience clip; the update system fades them in
+pub fn spawn_district_ambience(mut commands: Commands, districts: Res<DistrictMap>) {
+    for (district, clip) in &districts.ambient_clips {
+        commands.spawn((
+            DistrictAmbientSound {
+                district: *district,
+                gain: 0.0,
+            },
+            AudioPlayer(clip.clone()),
+            PlaybackSettings::LOOP.with_volume(Volume::Linear(0.0)),
+        ));
+    }
+}
+
+fn approach(current: f32, target: f32, step: f32) -> f32 {
+    current + (target - current).clamp(-step, step)
+}
+
+/// Street noise fades as the camera climbs away from the city. The listener's
+/// district ambience crossfades in over the generic city loop, which only
+/// plays in districts without their own clip.
+pub fn update_ambient_city_sound(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    districts: Res<DistrictMap>,
+    mut city_gain: Local<f32>,
+    listener: Query<&GlobalTransform, With<SpatialListener>>,
+    mut ambient: Query<&mut AudioSink, (With<AmbientCitySound>, Without<DistrictAmbientSound>)>,
+    mut district_ambient: Query<(&mut DistrictAmbientSound, &mut AudioSink)>,
+) {
+    let Ok(listener) = listener.single() else {
+        return;
+    };
+    let (low, high) = AMBIENT_FADE_HEIGHTS;
+    let fade = 1.0 - ((listener.translation().y - low) / (high - low)).clamp(0.0, 1.0);
+    let current = districts.district_at(listener.translation());
+    let step = AMBIENT_CROSSFADE_RATE * time.delta_secs();
+
+    let city_target = if districts.ambient_clips.contains_key(&current) {
+        0.0
+    } else {
+        1.0
+    };
+    *city_gain = approach(*city_gain, city_target, step);
+    for mut sink in &mut ambient {
+        sink.set_volume(Volume::Linear(
+            config.audio.ambient_volume * fade * *city_gain,
+        ));
+    }
+
+    for (mut sound, mut sink) in &mut district_ambient {
+        let target = if sound.district == current { 1.0 } else { 0.0 };
+        sound.gain = approach(sound.gain, target, step);
+        sink.set_volume(Volume::Linear(
+            config.audio.ambient_volume * fade * sound.gain,
+        ));
+    }
+}
+
+/// Gives the nearest vehicles within `max_audio_distance` an engine sound and
+/// takes it away from the rest, capping the number of live engine voices
+pub fn assign_engine_voices(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut since_update: Local<f32>,
+    clips: Res<AudioClips>,
+    config: Res<GameConfig>,
+    listener: Query<&GlobalTransform, With<SpatialListener>>,
+    vehicles: Query<(Entity, &GlobalTransform, Option<&EngineVoice>), With<VehicleState>>,
+) {
+    let Some(clip) = clips.engine.clone() else {
+        return;
+    };
+    *since_update += time.delta_secs();
+    if *since_update < ENGINE_VOICE_INTERVAL {
+        return;
+    }
+    *since_update = 0.0;
+
+    let Ok(listener) = listener.single() else {
+        return;
+    };
+    let listener = listener.translation();
+    let max_distance_sq = config.audio.max_audio_distance * config.audio.max_audio_distance;
+
+    let mut audible: Vec<(Entity, f32)> = vehicles
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation().distance_squared(listener)))
+        .filter(|(_, distance_sq)| *distance_sq <= max_distance_sq)
+        .collect();
+    audible.sort_by(|a, b| a.1.total_cmp(&b.1));
+    audible.truncate(config.audio.max_engine_voices);
+
+    for (entity, _, voice) in &vehicles {
+        let keep = audible.iter().any(|(audible, _)| *audible == entity);
+        match (voice, keep) {
+            (Some(voice), false) => {
+                commands.entity(voice.0).despawn();
+                commands.entity(entity).remove::<EngineVoice>();
+            }
+            (None, true) => {
+                let sound = commands
+                    .spawn((
+                        VehicleSound {
+                            kind: VehicleSoundKind::Engine,
+                        },
+                        AudioPlayer(clip.clone()),
+                        PlaybackSettings::LOOP
+                            .with_spatial(true)
+                            .with_volume(Volume::Linear(config.audio.engine_volume)),
+                        Transform::default(),
+                        ChildOf(entity),
+                    ))
+                    .id();
+                commands.entity(entity).insert(EngineVoice(sound));
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn attach_police_sirens(
+    mut commands: Commands,
+    clips: Res<AudioClips>,
+    config: Res<GameConfig>,
+    units: Query<Entity, (With<PoliceUnit>, Without<HasSiren>)>,
+) {
+    let Some(clip) = clips.siren.clone() else {
+        return;
+    };
+    for unit in &units {
+        commands.spawn((
+            VehicleSound {
+                kind: VehicleSoundKind::Siren,
+            },
+            AudioPlayer(clip.clone()),
+            PlaybackSettings::LOOP
+                .with_spatial(true)
+                .with_volume(Volume::Linear(config.audio.siren_volume)),
+            Transform::default(),
+            ChildOf(unit),
+        ));
+        commands.entity(unit).insert(HasSiren);
+    }
+}
+
+/// Starts a looping screech on cars whose tires begin to skid and stops it
+/// once they grip again
+pub fn update_tire_screech(
+    mut commands: Commands,
+    clips: Res<AudioClips>,
+    car_specs: Res<Assets<SimpleCarSpecs>>,
+    cars: Query<(
+        Entity,
+        &TirePhysics,
+        &SimpleCarSpecsHandle,
+        Option<&TireScreech>,
+    )>,
+) {
+    let Some(clip) = clips.tire_screech.clone() else {
+        return;
+    };
+    for (entity, tires, specs_handle, screech) in &cars {
+        match (screech, tires.skidding) {
+            (Some(screech), false) => {
+                commands.entity(screech.0).despawn();
+                commands.entity(entity).remove::<TireScreech>();
+            }
+            (None, true) => {
+                let volume = car_specs
+                    .get(&specs_handle.0)
+                    .map_or(0.6, |specs| specs.screech_volume);
+                let sound = commands
+                    .spawn((
+                        VehicleSound {
+                            kind: VehicleSoundKind::TireScreech,
+                        },
+                        AudioPlayer(clip.clone()),
+                        PlaybackSettings::LOOP
+                            .with_spatial(true)
+                            .with_volume(Volume::Linear(volume)),
+                        Transform::default(),
+                        ChildOf(entity),
+                    ))
+                    .id();
+                commands.entity(entity).insert(TireScreech(sound));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Looping spatial `kind` sound on `vehicle`
+fn spawn_vehicle_sound(
+    commands: &mut Commands,
+    vehicle: Entity,
+    kind: VehicleSoundKind,
+    clip: Handle<AudioSource>,
+    volume: f32,
+) -> Entity {
+    commands
+        .spawn((
+            VehicleSound { kind },
+            AudioPlayer(clip),
+            PlaybackSettings::LOOP
+                .with_spatial(true)
+                .with_volume(Volume::Linear(volume)),
+            Transform::default(),
+            ChildOf(vehicle),
+        ))
+        .id()
+}
+
+/// Sounds the horn of the player's vehicle for as long as it is held
+pub fn update_horn_sounds(
+    mut commands: Commands,
+    clips: Res<AudioClips>,
+    config: Res<GameConfig>,
+    vehicles: Query<(
+        Entity,
+        &VehicleState,
+        &ControlState,
+        Has<ActiveEntity>,
+        Option<&HornSound>,
+    )>,
+) {
+    let Some(clip) = clips.horn.clone() else {
+        return;
+    };
+    for (entity, state, control, active, horn) in &vehicles {
+        match (horn, active && control.horn) {
+            (Some(horn), false) => {
+                commands.entity(horn.0).despawn();
+                commands.entity(entity).remove::<HornSound>();
+            }
+            (None, true) => {
+                let volume = config.vehicles.for_type(state.vehicle_type).horn_volume;
+                let sound = spawn_vehicle_sound(
+                    &mut commands,
+                    entity,
+                    VehicleSoundKind::Horn,
+                    clip.clone(),
+                    volume,
+                );
+                commands.entity(entity).insert(HornSound(sound));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Starts the alarm loop on parked cars whose alarm went off and stops it
+/// once the alarm runs out
+pub fn update_car_alarm_sounds(
+    mut commands: Commands,
+    clips: Res<AudioClips>,
+    config: Res<GameConfig>,
+    cars: Query<(Entity, &CarAlarm, Option<&AlarmSound>)>,
+) {
+    let Some(clip) = clips.car_alarm.clone() else {
+        return;
+    };
+    for (entity, alarm, sound) in &cars {
+        match (sound, alarm.sounding()) {
+            (Some(sound), false) => {
+                commands.entity(sound.0).despawn();
+                commands.entity(entity).remove::<AlarmSound>();
+            }
+            (None, true) => {
+                let sound = spawn_vehicle_sound(
+                    &mut commands,
+                    entity,
+                    VehicleSoundKind::Alarm,
+                    clip.clone(),
+                    config.audio.alarm_volume,
+                );
+                commands.entity(entity).insert(AlarmSound(sound));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Plays a shout where a pedestrian barks at the player, pitched a little
+/// differently each time so one clip makes several voices
+pub fn play_pedestrian_barks(
+    mut commands: Commands,
+    clips: Res<AudioClips>,
+    config: Res<GameConfig>,
+    mut barks: EventReader<PedestrianBark>,
+) {
+    let Some(clip) = clips.bark.clone() else {
+        barks.clear();
+        return;
+    };
+    for bark in barks.read() {
+        commands.spawn((
+            Transform::from_translation(bark.position),
+            AudioPlayer(clip.clone()),
+            PlaybackSettings::DESPAWN
+                .with_spatial(true)
+                .with_speed(rand::thread_rng().gen_range(0.85..1.2))
+                .with_volume(Volume::Linear(config.audio.bark_volume)),
+        ));
+    }
+}
+
+/// Engine pitch follows vehicle speed; every vehicle sound gets a doppler shift
+/// and is paused beyond `max_audio_distance`
+pub fn update_vehicle_sounds(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut last_listener: Local<Option<Vec3>>,
+    listener: Query<&GlobalTransform, With<SpatialListener>>,
+    vehicles: Query<(
+        &GlobalTransform,
+        Option<&Velocity>,
+        Option<(&Transmission, &SimpleCarSpecsHandle)>,
+    )>,
+    sounds: Query<(&VehicleSound, &ChildOf, &SpatialAudioSink)>,
+    car_specs: Res<Assets<SimpleCarSpecs>>,
+) {
+    let Ok(listener) = listener.single() else {
+        return;
+    };
+    let listener_pos = listener.translation();
+    let dt = time.delta_secs();
+    let listener_vel = match *last_listener {
+        Some(previous) if dt > 0.0 => (listener_pos - previous) / dt,
+        _ => Vec3::ZERO,
+    };
+    *last_listener = Some(listener_pos);
+
+    for (sound, child_of, sink) in &sounds {
+        let Ok((transform, velocity, gearbox)) = vehicles.get(child_of.parent()) else {
+            continue;
+        };
+        let source_pos = transform.translation();
+        let source_vel = velocity.map_or(Vec3::ZERO, |v| v.linvel);
+
+        if source_pos.distance(listener_pos) > config.audio.max_audio_distance {
+            sink.pause();
+            continue;
+        }
+        if sink.is_paused() {
+            sink.play();
+        }
+
+        let base_pitch = match sound.kind {
+            VehicleSoundKind::Engine => {
+                // Cars follow their gearbox revs, everything else its speed
+                let revs = gearbox
+                    .and_then(|(gearbox, handle)| {
+                        car_specs
+                            .get(&handle.0)
+                            .map(|specs| gearbox.rev_share(specs))
+                    })
+                    .unwrap_or((source_vel.length() / 40.0).min(1.0));
+                engine_pitch(revs)
+            }
+            VehicleSoundKind::Siren
+            | VehicleSoundKind::TireScreech
+            | VehicleSoundKind::Horn
+            | VehicleSoundKind::Alarm => 1.0,
+        };
+        let doppler = doppler_factor(
+            source_pos,
+            source_vel * config.audio.doppler_scale,
+            listener_pos,
+            listener_vel * config.audio.doppler_scale,
+        );
+        sink.set_speed(base_pitch * doppler);
+    }
+}
+
+/// Idle engine plays slightly low, rising with the revs (0 idle to 1 redline)
+pub fn engine_pitch(revs: f32) -> f32 {
+    0.8 + revs.clamp(0.0, 1.0) * 0.9
+}
+
+/// Pitch multiplier heard at the listener: above 1 while the source and
+/// listener close in, below 1 while they separate
+pub fn doppler_factor(
+    source_pos: Vec3,
+    source_vel: Vec3,
+    listener_pos: Vec3,
+    listener_vel: Vec3,
+) -> f32 {
+    let Some(to_listener) = (listener_pos - source_pos).try_normalize() else {
+        return 1.0;
+    };
+    let source_closing = source_vel.dot(to_listener);
+    let listener_closing = -listener_vel.dot(to_listener);
+    let factor = (SPEED_OF_SOUND + listener_closing) / (SPEED_OF_SOUND - source_closing);
+    factor.clamp(0.5, 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doppler_raises_pitch_when_approaching() {
+        let listener = Vec3::ZERO;
+        let source = Vec3::new(100.0, 0.0, 0.0);
+        let approaching = doppler_factor(source, Vec3::new(-30.0, 0.0, 0.0), listener, Vec3::ZERO);
+        let receding = doppler_factor(source, Vec3::new(30.0, 0.0, 0.0), listener, Vec3::ZERO);
+        let passing = doppler_factor(source, Vec3::new(0.0, 0.0, 30.0), listener, Vec3::ZERO);
+
+        assert!(approaching > 1.0);
+        assert!(receding < 1.0);
+        assert!((passing - 1.0).abs() < 1e-6);
+    }
+}
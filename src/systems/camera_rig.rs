@@ -0,0 +1,368 @@
+//! Camera rig: the one camera for every target, in five modes.
+//!
+//! The cycle camera control (V by default) steps through Chase, Close, Hood,
+//! Cinematic and Orbit. The rig follows the `ActiveEntity`, and what that is (on foot, swimming, skydiving, car,
+//! helicopter, F16, yacht) picks its `CameraOffset` from `GameConfig::camera`. Apart from the
+//! hood view the camera hangs on a spring arm from the target: a ray cast
+//! against static geometry shortens the arm so the camera stays in front of
+//! walls and terrain, and the arm grows back once the view is clear. The FOV
+//! widens with speed.
+
+use crate::components::vehicles::{Car, F16, Helicopter};
+use crate::components::water::Yacht;
+use crate::components::{ActiveEntity, ControlState, MainCamera, Parachute};
+use crate::config::{CameraConfig, CameraOffset, GameConfig};
+use crate::systems::swimming::ProneRotation;
+use crate::util::safe_math::safe_lerp;
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+/// The aim point leads the target by this many seconds of travel
+const LOOK_AHEAD_SECONDS: f32 = 0.3;
+
+/// Follow smoothing doubles at this speed
+const FOLLOW_BOOST_SPEED: f32 = 50.0;
+
+/// The spring arm never gets shorter than this, even against a wall
+const MIN_ARM_LENGTH: f32 = 0.5;
+
+const FOV_LERP_SPEED: f32 = 2.5;
+
+/// Cinematic shots use a longer lens
+const CINEMATIC_FOV_SCALE: f32 = 0.6;
+
+/// Orbit pitch limits, radians above the horizon
+const ORBIT_MIN_PITCH: f32 = -0.3;
+const ORBIT_MAX_PITCH: f32 = 1.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CameraMode {
+    #[default]
+    Chase,
+    /// Chase, pulled in by `CameraConfig::close_scale`
+    Close,
+    /// First person from the target's `hood` offset
+    Hood,
+    /// Fixed shots set up ahead of the target, re-placed once it passes
+    Cinematic,
+    /// Mouse-controlled orbit around the target
+    Orbit,
+}
+
+impl CameraMode {
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::Chase => CameraMode::Close,
+            CameraMode::Close => CameraMode::Hood,
+            CameraMode::Hood => CameraMode::Cinematic,
+            CameraMode::Cinematic => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::Chase,
+        }
+    }
+}
+
+/// What the camera follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraTarget {
+    OnFoot,
+    Swimming,
+    /// Falling with the canopy still packed
+    Freefall,
+    Car,
+    Helicopter,
+    F16,
+    Yacht,
+}
+
+impl CameraTarget {
+    pub fn offset(self, config: &CameraConfig) -> &CameraOffset {
+        match self {
+            CameraTarget::OnFoot => &config.on_foot,
+            CameraTarget::Swimming => &config.swimming,
+            CameraTarget::Freefall => &config.freefall,
+            CameraTarget::Car => &config.car,
+            CameraTarget::Helicopter => &config.helicopter,
+            CameraTarget::F16 => &config.f16,
+            CameraTarget::Yacht => &config.yacht,
+        }
+    }
+
+    /// Aircraft keep the camera behind their nose when it pitches; everything
+    /// else is followed on the horizontal plane
+    fn follows_pitch(self) -> bool {
+        matches!(self, CameraTarget::Helicopter | CameraTarget::F16)
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct CameraRig {
+    pub mode: CameraMode,
+    /// Entity followed last frame; switching targets restarts the smoothing
+    target: Option<Entity>,
+    /// Smoothed camera position before the spring arm pulls it in
+    follow_position: Option<Vec3>,
+    /// Current spring arm length
+    arm_length: Option<f32>,
+    /// Orbit yaw and pitch, radians; set from the chase view on entering orbit
+    orbit: Option<(f32, f32)>,
+    /// Position the current cinematic shot is filmed from
+    cinematic_anchor: Option<Vec3>,
+}
+
+/// The cycle camera control of the active entity steps to the next mode
+pub fn cycle_camera_mode(
+    controls: Query<&ControlState, With<ActiveEntity>>,
+    mut rig: ResMut<CameraRig>,
+) {
+    if !controls.iter().any(|control| control.cycle_camera) {
+        return;
+    }
+    rig.mode = rig.mode.next();
+    rig.follow_position = None;
+    rig.arm_length = None;
+    rig.orbit = None;
+    rig.cinematic_anchor = None;
+    info!("🎥 Camera mode: {:?}", rig.mode);
+}
+
+/// Length of a spring arm of `full_length` whose ray hit something `hit`
+/// meters out. The arm shortens at once but only grows back at `recover`
+/// meters per second so the camera doesn't pop out from behind corners.
+pub fn spring_arm_length(
+    full_length: f32,
+    hit: Option<f32>,
+    margin: f32,
+    current: Option<f32>,
+    recover: f32,
+    dt: f32,
+) -> f32 {
+    let allowed = hit
+        .map_or(full_length, |distance| distance - margin)
+        .clamp(MIN_ARM_LENGTH.min(full_length), full_length);
+    match current {
+        Some(current) if current < allowed => (current + recover * dt).min(allowed),
+        _ => allowed,
+    }
+}
+
+type CameraTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static GlobalTransform,
+        Option<&'static Velocity>,
+        Has<Car>,
+        Has<Helicopter>,
+        Has<F16>,
+        Has<Yacht>,
+        Has<ProneRotation>,
+        Option<&'static Parachute>,
+    ),
+    (With<ActiveEntity>, Without<MainCamera>),
+>;
+
+#[allow(clippy::type_complexity)]
+pub fn camera_rig_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    rapier_context: ReadRapierContext,
+    mut rig: ResMut<CameraRig>,
+    mut camera_query: Query<
+        (&mut Transform, &mut Projection),
+        (With<MainCamera>, Without<ActiveEntity>),
+    >,
+    target_query: CameraTargetQuery,
+) {
+    let Ok((mut camera_transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let Ok((entity, target_gt, velocity, car, helicopter, f16, yacht, prone, parachute)) =
+        target_query.single()
+    else {
+        return;
+    };
+    let freefall = parachute.is_some_and(|parachute| !parachute.is_open());
+    let target = match (car, helicopter, f16, yacht, prone, freefall) {
+        (true, ..) => CameraTarget::Car,
+        (_, true, ..) => CameraTarget::Helicopter,
+        (_, _, true, ..) => CameraTarget::F16,
+        (_, _, _, true, ..) => CameraTarget::Yacht,
+        (_, _, _, _, true, _) => CameraTarget::Swimming,
+        (.., true) => CameraTarget::Freefall,
+        _ => CameraTarget::OnFoot,
+    };
+    let (_, rotation, position) = target_gt.to_scale_rotation_translation();
+    if !position.is_finite() || !rotation.is_finite() {
+        return;
+    }
+    if rig.target != Some(entity) {
+        *rig = CameraRig {
+            mode: rig.mode,
+            target: Some(entity),
+            ..default()
+        };
+    }
+
+    let camera = &config.camera;
+    let offset = *target.offset(camera);
+    let dt = time.delta_secs();
+    let velocity = velocity.map_or(Vec3::ZERO, |velocity| velocity.linvel);
+    let speed = velocity.length();
+
+    let forward = rotation * Vec3::NEG_Z;
+    let heading = if target.follows_pitch() {
+        forward.normalize_or_zero()
+    } else {
+        Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero()
+    };
+    if heading == Vec3::ZERO {
+        return;
+    }
+    let pivot = position + Vec3::Y * offset.look_height;
+    // A skydiver only leads the camera by their drift, not the fall itself
+    let lead_speed = if target == CameraTarget::Freefall {
+        velocity.with_y(0.0).length()
+    } else {
+        speed
+    };
+    let look_target = pivot + heading * (offset.look_ahead + lead_speed * LOOK_AHEAD_SECONDS);
+
+    let mut fov = offset.fov + offset.fov_kick * (speed / offset.kick_speed).min(1.0);
+
+    if rig.mode == CameraMode::Hood {
+        let (x, y, z) = offset.hood;
+        camera_transform.translation = position + rotation * Vec3::new(x, y, z);
+        camera_transform.rotation = if target.follows_pitch() {
+            rotation
+        } else {
+            Transform::default().looking_to(heading, Vec3::Y).rotation
+        };
+    } else {
+        let follow_boost = 1.0 + (speed / FOLLOW_BOOST_SPEED).min(1.0);
+        let follow = (offset.follow_speed * follow_boost * dt).clamp(0.0, 1.0);
+        let (desired, aim) = match rig.mode {
+            CameraMode::Orbit => {
+                let (yaw, pitch) = rig
+                    .orbit
+                    .unwrap_or((f32::atan2(-heading.x, -heading.z), 0.3));
+                let yaw = yaw - mouse_motion.delta.x * camera.orbit_sensitivity;
+                let pitch = (pitch + mouse_motion.delta.y * camera.orbit_sensitivity)
+                    .clamp(ORBIT_MIN_PITCH, ORBIT_MAX_PITCH);
+                rig.orbit = Some((yaw, pitch));
+                let arm = Quat::from_euler(EulerRot::YXZ, yaw, -pitch, 0.0) * Vec3::Z;
+                (pivot + arm * offset.distance, pivot)
+            }
+            CameraMode::Cinematic => {
+                let anchor = rig
+                    .cinematic_anchor
+                    .filter(|anchor| anchor.distance(position) < camera.cinematic_distance * 1.5)
+                    .unwrap_or_else(|| {
+                        let travel = if speed > 1.0 {
+                            velocity / speed
+                        } else {
+                            heading
+                        };
+                        let side = travel.cross(Vec3::Y).normalize_or_zero();
+                        position
+                            + travel * camera.cinematic_distance
+                            + side * camera.cinematic_distance * 0.3
+                            + Vec3::Y * offset.height.max(2.0)
+                    });
+                rig.cinematic_anchor = Some(anchor);
+                fov *= CINEMATIC_FOV_SCALE;
+                (anchor, pivot)
+            }
+            _ => {
+                let scale = if rig.mode == CameraMode::Close {
+                    camera.close_scale
+                } else {
+                    1.0
+                };
+                let chase =
+                    position - heading * offset.distance * scale + Vec3::Y * offset.height * scale;
+                let smoothed = rig
+                    .follow_position
+                    .map_or(chase, |last| safe_lerp(last, chase, follow));
+                (smoothed, look_target)
+            }
+        };
+        if !desired.is_finite() {
+            return;
+        }
+        rig.follow_position = Some(desired);
+
+        // Spring arm from the pivot out to the wanted camera position
+        let arm = desired - pivot;
+        let full_length = arm.length();
+        let direction = arm.normalize_or_zero();
+        let hit = rapier_context.single().ok().and_then(|context| {
+            context
+                .cast_ray(
+                    pivot,
+                    direction,
+                    full_length,
+                    true,
+                    QueryFilter::default()
+                        .exclude_sensors()
+                        .exclude_rigid_body(entity)
+                        .groups(CollisionGroups::new(
+                            Group::ALL,
+                            config.physics.static_group,
+                        )),
+                )
+                .map(|(_, distance)| distance)
+        });
+        // Cinematic shots cut rather than ease, so their arm never lags
+        let current = rig.arm_length.filter(|_| rig.mode != CameraMode::Cinematic);
+        let length = spring_arm_length(
+            full_length,
+            hit,
+            camera.collision_margin,
+            current,
+            camera.arm_recover_speed,
+            dt,
+        );
+        rig.arm_length = Some(length);
+        camera_transform.translation = pivot + direction * length;
+        if (aim - camera_transform.translation).length_squared() > 0.01 {
+            camera_transform.look_at(aim, Vec3::Y);
+        }
+    }
+
+    if let Projection::Perspective(perspective) = projection.as_mut() {
+        let fov_lerp = (FOV_LERP_SPEED * dt).clamp(0.0, 1.0);
+        perspective.fov = perspective.fov.lerp(fov.to_radians(), fov_lerp);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spring_arm_snaps_in_and_recovers_slowly() {
+        // Wall 4 m out pulls a 10 m arm in at once
+        let length = spring_arm_length(10.0, Some(4.0), 0.3, Some(10.0), 5.0, 0.1);
+        assert!((length - 3.7).abs() < 1e-5);
+        // Once clear it grows back at the recover speed
+        let length = spring_arm_length(10.0, None, 0.3, Some(length), 5.0, 0.1);
+        assert!((length - 4.2).abs() < 1e-5);
+        // Hugging the target never collapses the arm completely
+        assert_eq!(
+            spring_arm_length(10.0, Some(0.1), 0.3, None, 5.0, 0.1),
+            MIN_ARM_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_camera_modes_cycle_back_to_chase() {
+        let mut mode = CameraMode::Chase;
+        for _ in 0..5 {
+            mode = mode.next();
+        }
+        assert_eq!(mode, CameraMode::Chase);
+    }
+}
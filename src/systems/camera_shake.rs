@@ -0,0 +1,133 @@
+//! Camera shake.
+//!
+//! Crashing the player's vehicle and vehicles blowing up near the camera add
+//! trauma, which decays over time; the shake offset grows with trauma squared
+//! and is scaled by the accessibility settings. The offset is added after the
+//! camera rig has placed the camera and taken back off before it runs again,
+//! so nothing else ever sees a shaken camera position.
+
+use crate::components::gameplay_events::VehicleDestroyed;
+use crate::components::{ActiveEntity, MainCamera, VehicleState};
+use crate::config::GameConfig;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+/// Offset in meters at full trauma
+const MAX_OFFSET: f32 = 0.6;
+
+/// Trauma lost per second
+const TRAUMA_DECAY: f32 = 1.5;
+
+/// Speed the player's vehicle has to lose in one frame to count as a crash
+const CRASH_SPEED_DROP: f32 = 8.0;
+const CRASH_TRAUMA_PER_SPEED: f32 = 0.04;
+
+/// Explosions shake the camera with `EXPLOSION_TRAUMA`, falling off to nothing
+/// at `EXPLOSION_RADIUS`
+const EXPLOSION_RADIUS: f32 = 60.0;
+const EXPLOSION_TRAUMA: f32 = 0.8;
+
+#[derive(Resource, Debug, Default)]
+pub struct CameraShake {
+    /// 0 = still, 1 = strongest shake
+    pub trauma: f32,
+    /// Offset currently added to the camera
+    applied: Vec3,
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Shake offset at `elapsed` seconds, before the accessibility scale
+    pub fn offset_at(&self, elapsed: f32) -> Vec3 {
+        let wobble = Vec3::new(
+            (elapsed * 31.0).sin(),
+            (elapsed * 37.0 + 1.3).sin(),
+            (elapsed * 23.0 + 2.1).sin(),
+        );
+        wobble * self.trauma * self.trauma * MAX_OFFSET
+    }
+}
+
+/// Adds trauma for crashes of the player's vehicle and nearby explosions
+#[allow(clippy::type_complexity)]
+pub fn shake_on_impacts(
+    mut shake: ResMut<CameraShake>,
+    mut destroyed: EventReader<VehicleDestroyed>,
+    mut last_speed: Local<Option<(Entity, f32)>>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    vehicle: Query<(Entity, &Velocity), (With<ActiveEntity>, With<VehicleState>)>,
+) {
+    if let Ok(camera) = camera.single() {
+        for explosion in destroyed.read() {
+            let distance = explosion.position.distance(camera.translation());
+            shake.add_trauma(EXPLOSION_TRAUMA * (1.0 - distance / EXPLOSION_RADIUS).max(0.0));
+        }
+    } else {
+        destroyed.clear();
+    }
+
+    let current = vehicle
+        .single()
+        .ok()
+        .map(|(entity, velocity)| (entity, velocity.linvel.length()));
+    if let (Some((entity, speed)), Some((last_entity, last))) = (current, *last_speed)
+        && entity == last_entity
+        && last - speed > CRASH_SPEED_DROP
+    {
+        shake.add_trauma((last - speed) * CRASH_TRAUMA_PER_SPEED);
+    }
+    *last_speed = current;
+}
+
+/// Takes last frame's offset back off so the camera systems see the
+/// unshaken position
+pub fn remove_camera_shake(
+    mut shake: ResMut<CameraShake>,
+    mut camera: Query<&mut Transform, With<MainCamera>>,
+) {
+    if shake.applied == Vec3::ZERO {
+        return;
+    }
+    if let Ok(mut transform) = camera.single_mut() {
+        transform.translation -= shake.applied;
+    }
+    shake.applied = Vec3::ZERO;
+}
+
+pub fn apply_camera_shake(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut shake: ResMut<CameraShake>,
+    mut camera: Query<&mut Transform, With<MainCamera>>,
+) {
+    if shake.trauma <= 0.0 {
+        return;
+    }
+    let offset = shake.offset_at(time.elapsed_secs()) * config.accessibility.camera_shake_scale();
+    shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+    if let Ok(mut transform) = camera.single_mut() {
+        transform.translation += offset;
+        shake.applied = offset;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shake_grows_with_trauma_squared() {
+        let mut shake = CameraShake::default();
+        assert_eq!(shake.offset_at(0.4), Vec3::ZERO);
+
+        shake.add_trauma(0.5);
+        let half = shake.offset_at(0.4).length();
+        shake.add_trauma(5.0);
+        assert_eq!(shake.trauma, 1.0);
+        let full = shake.offset_at(0.4).length();
+        assert!((full - half * 4.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,50 @@
+//! Car alarms on parked cars.
+//!
+//! Some of the ambient cars that stay parked carry an armed `CarAlarm`. A bump
+//! hard enough to shove one across the ground sets it off: it wails and flashes
+//! its lamps for `traffic.alarm_duration` seconds. Once the player is wanted at
+//! `police.alarm_min_stars` or more, an alarm going off next to them gives them
+//! away and counts as a crime of its own.
+
+use crate::components::{ActiveEntity, CarAlarm, CrimeCommitted, CrimeKind, WantedLevel};
+use crate::config::GameConfig;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+/// An alarm this close to the player is blamed on them
+const ALARM_BLAME_RADIUS: f32 = 15.0;
+
+/// Counts sounding alarms down and sets off the parked cars that got bumped
+pub fn update_car_alarms(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    wanted: Res<WantedLevel>,
+    player: Query<&Transform, With<ActiveEntity>>,
+    mut alarms: Query<(&Transform, &Velocity, &mut CarAlarm, Has<ActiveEntity>)>,
+    mut crimes: EventWriter<CrimeCommitted>,
+) {
+    let dt = time.delta_secs();
+    let player = player.single().ok().map(|transform| transform.translation);
+    let draws_police = wanted.stars >= config.police.alarm_min_stars;
+
+    for (transform, velocity, mut alarm, driven) in &mut alarms {
+        if alarm.sounding() {
+            alarm.remaining = (alarm.remaining - dt).max(0.0);
+        }
+        // A stolen car keeps wailing but driving it off doesn't set it off again
+        if driven || !alarm.tripped(velocity.linvel, config.traffic.alarm_trigger_speed) {
+            continue;
+        }
+        alarm.remaining = config.traffic.alarm_duration;
+
+        let position = transform.translation;
+        if draws_police
+            && player.is_some_and(|player| player.distance(position) < ALARM_BLAME_RADIUS)
+        {
+            crimes.write(CrimeCommitted {
+                kind: CrimeKind::CarAlarm,
+                position,
+            });
+        }
+    }
+}
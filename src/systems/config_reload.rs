@@ -0,0 +1,229 @@
+//! Layered config loading and F10 hot reload.
+//!
+//! `layer_config_files` applies every one of `CONFIG_FILES` in order: the
+//! workspace file in `assets/config`, the user's override, the active
+//! profile's override, then `GTA_CONFIG__` environment variables. Startup
+//! layers onto the defaults; F10 (or the `reload_config` console command)
+//! layers a fresh copy, diffs it section by section against the running
+//! config, prints the changes to the log and the console and sends a
+//! `ConfigFieldChanged` for each. Sections the world is built from at
+//! startup (bounds, streaming chunk size) still need a restart to take effect.
+
+use crate::config::{
+    CONFIG_FILES, ConfigFieldChanged, ENV_OVERRIDE_PREFIX, GameConfig, parse_env_override,
+    user_config_directory,
+};
+use crate::systems::accessibility::{ConfiguredAccessibility, settings_path};
+use crate::systems::profiles::ActiveProfile;
+use crate::systems::ui::console::{ConsoleCommands, ConsoleResult, ConsoleState};
+use crate::systems::ui::localization::ConfiguredLanguage;
+use crate::util::config_schema::changed_values;
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Asks `hot_reload_config` to re-read the config files
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ConfigReloadRequest;
+
+/// Layers the config files and environment overrides onto `config`,
+/// returning the files that set anything. `profile_config_dir` holds the
+/// active profile's overrides, if it has its own.
+pub fn layer_config_files(
+    config: &mut GameConfig,
+    profile_config_dir: Option<&Path>,
+) -> Vec<&'static str> {
+    let assets_base = crate::util::asset_path::get_assets_base_path();
+    let mut dirs = vec![
+        PathBuf::from(format!("{assets_base}/config")),
+        user_config_directory(),
+    ];
+    dirs.extend(profile_config_dir.map(Path::to_path_buf));
+
+    // Single keys from the environment go on last, e.g.
+    // GTA_CONFIG__CAMERA__CAR__DISTANCE=12 for CI runs and quick experiments
+    let mut env_overrides: Vec<(String, String)> = std::env::vars()
+        .filter(|(var, _)| var.starts_with(ENV_OVERRIDE_PREFIX))
+        .collect();
+    env_overrides.sort();
+    for (var, _) in &env_overrides {
+        if parse_env_override(var).is_none() {
+            warn!("⚠️ Ignoring {var}: expected {ENV_OVERRIDE_PREFIX}<FILE>__<FIELD>[__<FIELD>...]");
+        }
+    }
+
+    let mut loaded = Vec::new();
+    for (filename, description) in CONFIG_FILES {
+        for dir in &dirs {
+            let path = dir.join(filename);
+            let Ok(contents) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            match config.apply_file(filename, &contents) {
+                Ok(()) => {
+                    loaded.push(*filename);
+                    #[cfg(feature = "debug-ui")]
+                    info!("✅ Loaded {} config from {}", description, path.display());
+                }
+                Err(e) => warn!(
+                    "⚠️ Failed to parse {} ({}): {}",
+                    description,
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        for (var, value) in &env_overrides {
+            let Some((file, fields)) = parse_env_override(var) else {
+                continue;
+            };
+            if file != *filename {
+                continue;
+            }
+            match config.apply_override(filename, &fields, value) {
+                Ok(()) => {
+                    loaded.push(*filename);
+                    info!("🔧 Config override {var}={value}");
+                }
+                Err(e) => warn!("⚠️ Failed to apply {var}={value}: {e}"),
+            }
+        }
+    }
+    loaded.dedup();
+    loaded
+}
+
+pub fn request_config_reload(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut requests: EventWriter<ConfigReloadRequest>,
+) {
+    if keys.just_pressed(KeyCode::F10) {
+        requests.write(ConfigReloadRequest);
+    }
+}
+
+/// Re-reads the config files and applies whatever changed
+#[allow(clippy::too_many_arguments)]
+pub fn hot_reload_config(
+    mut requests: EventReader<ConfigReloadRequest>,
+    mut commands: Commands,
+    profile: Res<ActiveProfile>,
+    mut config: ResMut<GameConfig>,
+    configured_accessibility: Option<ResMut<ConfiguredAccessibility>>,
+    configured_language: Option<ResMut<ConfiguredLanguage>>,
+    console: Option<ResMut<ConsoleState>>,
+    mut changed: EventWriter<ConfigFieldChanged>,
+) {
+    if requests.read().last().is_none() {
+        return;
+    }
+    let mut fresh = GameConfig::default();
+    layer_config_files(&mut fresh, profile.config_directory().as_deref());
+    fresh.validate_and_clamp();
+
+    // Settings saved from the menu win over the file they started from
+    if let Some(mut configured) = configured_accessibility {
+        configured.0 = fresh.accessibility.clone();
+        if settings_path(&profile.data_directory()).is_file() {
+            fresh.accessibility = config.accessibility.clone();
+        }
+    }
+    // ...and so does a language picked there
+    if let Some(mut configured) = configured_language {
+        let picked_in_menu = config.localization.language != configured.0;
+        configured.0 = fresh.localization.language.clone();
+        if picked_in_menu {
+            fresh.localization.language = config.localization.language.clone();
+        }
+    }
+
+    let mut report = Vec::new();
+    for (filename, _) in CONFIG_FILES {
+        let (Some(before), Some(after), Some(contents)) = (
+            config.section_json(filename),
+            fresh.section_json(filename),
+            fresh.section_ron(filename),
+        ) else {
+            continue;
+        };
+        let changes = changed_values(&before, &after);
+        if changes.is_empty() {
+            continue;
+        }
+        if let Err(e) = config.apply_file(filename, &contents) {
+            warn!("⚠️ Failed to reload {filename}: {e}");
+            continue;
+        }
+        for change in changes {
+            report.push(format!(
+                "{filename} {}: {} -> {}",
+                change.path, change.before, change.after
+            ));
+            changed.write(ConfigFieldChanged {
+                file: filename,
+                path: change.path,
+                before: change.before,
+                after: change.after,
+            });
+        }
+        if *filename == "world_config.ron" {
+            commands.insert_resource(config.world_env.clone());
+        }
+    }
+    config.validate_and_clamp();
+
+    if report.is_empty() {
+        report.push("config reloaded, nothing changed".to_string());
+    }
+    for line in &report {
+        info!("🔁 {line}");
+    }
+    if let Some(mut console) = console {
+        console.print(report.join("\n"));
+    }
+}
+
+/// `reload_config`; the changes are printed once the reload has run
+pub fn reload_config_command(world: &mut World, args: &[&str]) -> ConsoleResult {
+    if !args.is_empty() {
+        return Err("usage: reload_config".to_string());
+    }
+    world.send_event(ConfigReloadRequest);
+    Ok("reloading config...".to_string())
+}
+
+pub fn register_config_command(mut registry: ResMut<ConsoleCommands>) {
+    registry.register("reload_config", reload_config_command);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hot_reload_reports_and_reverts_edited_fields() {
+        let mut config = GameConfig::default();
+        layer_config_files(&mut config, None);
+        config.validate_and_clamp();
+        let distance = config.camera.car.distance;
+        config.camera.car.distance = distance + 5.0;
+
+        let mut app = App::new();
+        app.insert_resource(config)
+            .init_resource::<ActiveProfile>()
+            .add_event::<ConfigReloadRequest>()
+            .add_event::<ConfigFieldChanged>()
+            .add_systems(Update, hot_reload_config);
+        app.world_mut().send_event(ConfigReloadRequest);
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<GameConfig>().camera.car.distance,
+            distance
+        );
+        let events = app.world().resource::<Events<ConfigFieldChanged>>();
+        let changes: Vec<&ConfigFieldChanged> = events.iter_current_update_events().collect();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].file, "camera.ron");
+        assert_eq!(changes[0].path, "car.distance");
+    }
+}
@@ -0,0 +1,260 @@
+//! Crash reports written from a panic hook.
+//!
+//! A panic hook can't reach the ECS `World`, so `update_crash_snapshot` keeps
+//! a small summary of it (entity counts, player, active vehicle, app state and
+//! the current `GameConfig`) in a static once a second. When the game panics,
+//! `install_panic_hook` writes that summary together with the backtrace and
+//! the logging layer's recent lines to `<data dir>/crashes/crash-<time>.txt`
+//! and leaves a `pending` marker, which the main menu picks up on the next
+//! launch to offer the report.
+
+use crate::components::{ActiveEntity, NPCState, Player, VehicleType};
+use crate::config::GameConfig;
+use crate::states::AppState;
+use crate::systems::logging::{LogControl, LogLine, LogSink};
+use crate::systems::persistence::data_directory;
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, TryLockError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Log lines copied into a report
+const REPORT_LOG_LINES: usize = 200;
+const PENDING_MARKER: &str = "pending";
+
+/// What the game was doing, as of the last snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldSnapshot {
+    pub app_state: String,
+    pub entity_count: u32,
+    pub vehicle_count: usize,
+    pub npc_count: usize,
+    pub player_position: Option<Vec3>,
+    pub active_vehicle: Option<String>,
+    /// `Debug` dump of `GameConfig`, refreshed when it changes
+    pub config: String,
+}
+
+impl WorldSnapshot {
+    const EMPTY: Self = Self {
+        app_state: String::new(),
+        entity_count: 0,
+        vehicle_count: 0,
+        npc_count: 0,
+        player_position: None,
+        active_vehicle: None,
+        config: String::new(),
+    };
+}
+
+static SNAPSHOT: Mutex<WorldSnapshot> = Mutex::new(WorldSnapshot::EMPTY);
+static LOG_SINK: OnceLock<Arc<Mutex<LogSink>>> = OnceLock::new();
+/// Several threads can panic on the way down; only the first writes a report
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+pub fn crash_directory() -> PathBuf {
+    data_directory().join("crashes")
+}
+
+/// Locks without blocking: the panicking thread may already hold the lock
+fn try_lock<T>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+    match mutex.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+pub fn render_report(
+    panic: &str,
+    backtrace: &str,
+    snapshot: &WorldSnapshot,
+    log: &[LogLine],
+) -> String {
+    let mut report = String::new();
+    let _ = writeln!(
+        report,
+        "gta_game {} crash report",
+        env!("CARGO_PKG_VERSION")
+    );
+    let _ = writeln!(
+        report,
+        "time: {} (unix seconds)",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs())
+    );
+    let _ = writeln!(report, "panic: {panic}");
+
+    let _ = writeln!(report, "\n== World state ==");
+    let _ = writeln!(report, "app state: {}", snapshot.app_state);
+    let _ = writeln!(
+        report,
+        "entities: {} ({} vehicles, {} NPCs)",
+        snapshot.entity_count, snapshot.vehicle_count, snapshot.npc_count
+    );
+    match snapshot.player_position {
+        Some(position) => {
+            let _ = writeln!(report, "player: {position}");
+        }
+        None => report.push_str("player: none\n"),
+    }
+    let _ = writeln!(
+        report,
+        "active vehicle: {}",
+        snapshot.active_vehicle.as_deref().unwrap_or("none")
+    );
+
+    let _ = writeln!(report, "\n== Recent log ({} lines) ==", log.len());
+    for line in log {
+        let _ = writeln!(report, "{line}");
+    }
+    let _ = writeln!(report, "\n== Backtrace ==\n{backtrace}");
+    let _ = writeln!(report, "\n== GameConfig ==\n{}", snapshot.config);
+    report
+}
+
+/// Writes the report and marks it as not yet seen
+pub fn write_crash_report(dir: &Path, report: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis());
+    let path = dir.join(format!("crash-{stamp}.txt"));
+    fs::write(&path, report)?;
+    fs::write(dir.join(PENDING_MARKER), path.to_string_lossy().as_bytes())?;
+    Ok(path)
+}
+
+/// Report left by a crash that hasn't been shown yet
+pub fn pending_report(dir: &Path) -> Option<PathBuf> {
+    let path = PathBuf::from(fs::read_to_string(dir.join(PENDING_MARKER)).ok()?.trim());
+    path.exists().then_some(path)
+}
+
+pub fn dismiss_pending_report(dir: &Path) {
+    let _ = fs::remove_file(dir.join(PENDING_MARKER));
+}
+
+/// Chains a report writer in front of the current panic hook. Call before
+/// building the app so panics during startup are caught too.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        if !REPORTED.swap(true, Ordering::SeqCst) {
+            let thread = std::thread::current();
+            let panic = format!("{info} (thread '{}')", thread.name().unwrap_or("unnamed"));
+            let backtrace = Backtrace::force_capture().to_string();
+            let snapshot = try_lock(&SNAPSHOT).map_or(WorldSnapshot::EMPTY, |s| s.clone());
+            let log: Vec<LogLine> = LOG_SINK
+                .get()
+                .and_then(|sink| try_lock(sink))
+                .map(|sink| {
+                    let skip = sink.lines().count().saturating_sub(REPORT_LOG_LINES);
+                    sink.lines().skip(skip).cloned().collect()
+                })
+                .unwrap_or_default();
+            let report = render_report(&panic, &backtrace, &snapshot, &log);
+            match write_crash_report(&crash_directory(), &report) {
+                Ok(path) => eprintln!("Crash report written to {}", path.display()),
+                Err(err) => eprintln!("Could not write crash report: {err}"),
+            }
+        }
+        previous(info);
+    }));
+}
+
+/// Hands the logging layer's line buffer to the panic hook
+pub fn register_crash_log_sink(control: Option<Res<LogControl>>) {
+    if let Some(control) = control {
+        let _ = LOG_SINK.set(control.sink().clone());
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_crash_snapshot(
+    entities: &Entities,
+    state: Option<Res<State<AppState>>>,
+    config: Res<GameConfig>,
+    vehicles: Query<(), With<VehicleType>>,
+    npcs: Query<(), With<NPCState>>,
+    player: Query<&GlobalTransform, With<Player>>,
+    active_vehicle: Query<(Entity, &VehicleType, &GlobalTransform), With<ActiveEntity>>,
+    mut config_seen: Local<bool>,
+) {
+    let config_dump = (!*config_seen || config.is_changed()).then(|| format!("{:#?}", *config));
+    *config_seen = true;
+
+    let Ok(mut snapshot) = SNAPSHOT.lock() else {
+        return;
+    };
+    snapshot.app_state = state.map_or_else(String::new, |state| format!("{:?}", state.get()));
+    snapshot.entity_count = entities.len();
+    snapshot.vehicle_count = vehicles.iter().count();
+    snapshot.npc_count = npcs.iter().count();
+    snapshot.player_position = player
+        .iter()
+        .next()
+        .map(|transform| transform.translation());
+    snapshot.active_vehicle =
+        active_vehicle
+            .iter()
+            .next()
+            .map(|(entity, vehicle_type, transform)| {
+                format!("{vehicle_type:?} {entity} at {}", transform.translation())
+            });
+    if let Some(config_dump) = config_dump {
+        snapshot.config = config_dump;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_includes_world_state_and_log() {
+        let snapshot = WorldSnapshot {
+            app_state: "InGame".into(),
+            entity_count: 1234,
+            vehicle_count: 12,
+            npc_count: 40,
+            player_position: Some(Vec3::new(1.0, 2.0, 3.0)),
+            active_vehicle: Some("SuperCar 7v1".into()),
+            config: "GameConfig { .. }".into(),
+        };
+        let log = [LogLine {
+            elapsed: 1.5,
+            level: bevy::log::Level::WARN,
+            target: "gta_game::systems::traffic".into(),
+            message: "stuck car".into(),
+        }];
+        let report = render_report("boom", "frame 0", &snapshot, &log);
+        assert!(report.contains("panic: boom"));
+        assert!(report.contains("entities: 1234 (12 vehicles, 40 NPCs)"));
+        assert!(report.contains("active vehicle: SuperCar 7v1"));
+        assert!(report.contains("gta_game::systems::traffic: stuck car"));
+        assert!(report.contains("== Backtrace ==\nframe 0"));
+    }
+
+    #[test]
+    fn test_pending_report_until_dismissed() {
+        let dir = std::env::temp_dir().join(format!("gta_game_crash_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        assert!(pending_report(&dir).is_none());
+
+        let path = write_crash_report(&dir, "report").unwrap();
+        assert_eq!(pending_report(&dir), Some(path.clone()));
+        dismiss_pending_report(&dir);
+        assert!(pending_report(&dir).is_none());
+        assert!(path.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,87 @@
+//! Applies `VehicleCustomization` to live vehicles.
+//!
+//! Paint swaps the body material, wheels swap the `WheelMesh` meshes and
+//! tuning gives the car its own copy of the shared `SimpleCarSpecs` with the
+//! multipliers applied. Stock tuning points back at the shared asset.
+
+use crate::components::{
+    SimpleCarSpecs, SimpleCarSpecsHandle, VehicleCustomization, VehicleState, WheelMesh, WheelStyle,
+};
+use crate::factories::{MaterialFactory, MeshFactory, ReflectiveMaterials};
+use crate::resources::VehicleSpecsAssets;
+use bevy::prelude::*;
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn apply_vehicle_customization(
+    mut vehicles: Query<
+        (
+            Entity,
+            &VehicleCustomization,
+            &mut VehicleState,
+            Option<&mut SimpleCarSpecsHandle>,
+        ),
+        Changed<VehicleCustomization>,
+    >,
+    children: Query<&Children>,
+    mut body_parts: Query<&mut MeshMaterial3d<StandardMaterial>, Without<WheelMesh>>,
+    mut wheels: Query<(&mut Mesh3d, &mut WheelMesh)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut reflective: ResMut<ReflectiveMaterials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut car_specs: ResMut<Assets<SimpleCarSpecs>>,
+    specs: Option<Res<VehicleSpecsAssets>>,
+) {
+    for (entity, customization, mut state, specs_handle) in &mut vehicles {
+        if let Some([r, g, b, a]) = customization.paint {
+            let paint = Color::srgba(r, g, b, a);
+            if paint != state.color {
+                // Body panels share the material created from the spawn color
+                let painted = MaterialFactory::create_reflective_paint(
+                    &mut materials,
+                    &mut reflective,
+                    paint,
+                );
+                for child in children.iter_descendants(entity) {
+                    let Ok(mut material) = body_parts.get_mut(child) else {
+                        continue;
+                    };
+                    if materials
+                        .get(&material.0)
+                        .is_some_and(|m| m.base_color == state.color)
+                    {
+                        material.0 = painted.clone();
+                    }
+                }
+                state.color = paint;
+            }
+        }
+
+        let style = customization.wheels;
+        let mut wheel_mesh = None;
+        for child in children.iter_descendants(entity) {
+            let Ok((mut mesh, mut wheel)) = wheels.get_mut(child) else {
+                continue;
+            };
+            if wheel.radius == style.radius() {
+                continue;
+            }
+            mesh.0 = wheel_mesh
+                .get_or_insert_with(|| match style {
+                    WheelStyle::Sport => MeshFactory::create_sports_wheel(&mut meshes),
+                    WheelStyle::Standard => MeshFactory::create_standard_wheel(&mut meshes),
+                    WheelStyle::OffRoad => MeshFactory::create_large_wheel(&mut meshes),
+                })
+                .clone();
+            wheel.radius = style.radius();
+        }
+
+        if let (Some(mut handle), Some(specs)) = (specs_handle, specs.as_ref()) {
+            if customization.tuning.is_stock() {
+                handle.0 = specs.car.clone();
+            } else if let Some(base) = car_specs.get(&specs.car) {
+                let tuned = customization.tuning.apply_to(base);
+                handle.0 = car_specs.add(tuned);
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Cutscene playback.
+//!
+//! Cutscenes are keyframed camera paths from `assets/config/cutscenes.ron`,
+//! started with a `PlayCutscene` event: missions send one for their
+//! `intro_cutscene`, scripts through `play_cutscene(id)`. While one plays the
+//! camera follows the path instead of the rig, letterbox bars slide in and
+//! keyboard and mouse input is swallowed before gameplay systems see it;
+//! skippable cutscenes end early on Escape, Enter or Space.
+
+use crate::components::MainCamera;
+use crate::components::cutscene::{
+    ActiveCutscene, CutsceneCatalog, CutsceneFinished, CutscenePlayback, LetterboxBar, PlayCutscene,
+};
+use bevy::prelude::*;
+
+/// Height of each letterbox bar, percent of the screen
+const LETTERBOX_HEIGHT: f32 = 12.0;
+
+/// Seconds the bars take to slide in or out
+const LETTERBOX_SLIDE_SECONDS: f32 = 0.4;
+
+const SKIP_KEYS: [KeyCode; 3] = [KeyCode::Escape, KeyCode::Enter, KeyCode::Space];
+
+pub fn load_cutscene_catalog(mut commands: Commands) {
+    let path = format!(
+        "{}/config/cutscenes.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    let catalog = match std::fs::read_to_string(&path) {
+        Ok(content) => match ron::from_str::<CutsceneCatalog>(&content) {
+            Ok(catalog) => {
+                #[cfg(feature = "debug-ui")]
+                info!("✅ Loaded {} cutscenes", catalog.cutscenes.len());
+                catalog
+            }
+            Err(e) => {
+                error!("Failed to parse cutscene config at '{}': {}", path, e);
+                CutsceneCatalog::default()
+            }
+        },
+        Err(e) => {
+            info!("ℹ️ No cutscene config found, cutscenes disabled: {}", e);
+            CutsceneCatalog::default()
+        }
+    };
+
+    commands.insert_resource(catalog);
+}
+
+pub fn setup_letterbox(mut commands: Commands) {
+    for top in [true, false] {
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(0.0),
+                top: if top { Val::Px(0.0) } else { Val::Auto },
+                bottom: if top { Val::Auto } else { Val::Px(0.0) },
+                ..default()
+            },
+            BackgroundColor(Color::BLACK),
+            GlobalZIndex(100),
+            LetterboxBar,
+        ));
+    }
+}
+
+pub fn start_cutscenes(
+    catalog: Res<CutsceneCatalog>,
+    mut requests: EventReader<PlayCutscene>,
+    mut active: ResMut<ActiveCutscene>,
+    mut finished: EventWriter<CutsceneFinished>,
+) {
+    for request in requests.read() {
+        if catalog.get(&request.cutscene_id).is_none() {
+            warn!("⚠️ Unknown cutscene '{}' requested", request.cutscene_id);
+            continue;
+        }
+        if let Some(previous) = active.0.take() {
+            finished.write(CutsceneFinished {
+                cutscene_id: previous.id,
+                skipped: true,
+            });
+        }
+        info!("🎬 Cutscene started: {}", request.cutscene_id);
+        active.0 = Some(CutscenePlayback {
+            id: request.cutscene_id.clone(),
+            elapsed: 0.0,
+            skipped: false,
+        });
+    }
+}
+
+/// Swallows keyboard and mouse button input while a cutscene plays, after
+/// checking it for the skip keys. Runs right after Bevy's input update, so
+/// nothing in `Update` sees a key press.
+pub fn lock_input_during_cutscene(
+    catalog: Res<CutsceneCatalog>,
+    mut active: ResMut<ActiveCutscene>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+) {
+    let Some(playback) = active.0.as_mut() else {
+        return;
+    };
+    let skippable = catalog.get(&playback.id).is_some_and(|c| c.skippable);
+    if skippable && keys.any_just_pressed(SKIP_KEYS) {
+        playback.skipped = true;
+    }
+    keys.reset_all();
+    mouse.reset_all();
+}
+
+/// Moves the camera along the cutscene path; runs after the camera rig so
+/// the cutscene has the last word
+pub fn play_cutscene_camera(
+    time: Res<Time>,
+    catalog: Res<CutsceneCatalog>,
+    mut active: ResMut<ActiveCutscene>,
+    mut finished: EventWriter<CutsceneFinished>,
+    mut camera: Query<(&mut Transform, &mut Projection), With<MainCamera>>,
+) {
+    let Some(playback) = active.0.as_mut() else {
+        return;
+    };
+    playback.elapsed += time.delta_secs();
+    let cutscene = catalog.get(&playback.id);
+    let done = playback.skipped || cutscene.is_none_or(|c| playback.elapsed >= c.duration());
+    let pose = cutscene.and_then(|c| c.sample(playback.elapsed));
+
+    if let (Some(pose), Ok((mut transform, mut projection))) = (pose, camera.single_mut()) {
+        transform.translation = pose.position;
+        transform.rotation = pose.rotation();
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = pose.fov.to_radians();
+        }
+    }
+
+    if done && let Some(playback) = active.0.take() {
+        info!("🎬 Cutscene finished: {}", playback.id);
+        finished.write(CutsceneFinished {
+            cutscene_id: playback.id,
+            skipped: playback.skipped,
+        });
+    }
+}
+
+/// Slides the letterbox bars in while a cutscene plays and out afterwards
+pub fn update_letterbox(
+    time: Res<Time>,
+    active: Res<ActiveCutscene>,
+    mut amount: Local<f32>,
+    mut bars: Query<(&mut Node, &mut Visibility), With<LetterboxBar>>,
+) {
+    let target = if active.0.is_some() { 1.0 } else { 0.0 };
+    if *amount == target {
+        return;
+    }
+    let step = time.delta_secs() / LETTERBOX_SLIDE_SECONDS;
+    *amount = if target > *amount {
+        (*amount + step).min(target)
+    } else {
+        (*amount - step).max(target)
+    };
+    for (mut node, mut visibility) in &mut bars {
+        node.height = Val::Percent(LETTERBOX_HEIGHT * *amount);
+        *visibility = if *amount > 0.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
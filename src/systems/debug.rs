@@ -1,7 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 use crate::bundles::PlayerPhysicsBundle;
 use crate::components::{ActiveEntity, MainCamera, Player};
-use crate::game_state::GameState;
+use crate::game_state::{GameState, GameStateTransitions};
 use bevy::prelude::*;
 // Legacy input removed - use raw F1 for debug toggle
 
@@ -9,7 +9,7 @@ pub fn debug_game_state(
     current_state: Res<State<GameState>>,
     input: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
-    mut state: ResMut<NextState<GameState>>,
+    mut state: GameStateTransitions,
     player_query: Query<Entity, With<Player>>,
     active_player_query: Query<Entity, (With<Player>, With<ActiveEntity>)>,
     active_any_query: Query<Entity, With<ActiveEntity>>,
@@ -59,7 +59,7 @@ pub fn debug_game_state(
                 .insert(Visibility::Visible)
                 .remove::<ChildOf>()
                 .insert(PlayerPhysicsBundle::default()); // Restore clean physics state
-            state.set(GameState::Walking);
+            state.reset(GameState::Walking);
             info!("Restored player ActiveEntity and set to Walking state");
         } else {
             warn!("No player entity found to fix!");
@@ -0,0 +1,496 @@
+//! Toggleable gizmo overlays for the systems that are otherwise invisible.
+//!
+//! Hold F6 and press a number to flip a layer; F6+0 turns them all off.
+//! 1. Rapier collider wireframes near the camera
+//! 2. Culling and streaming distance rings around the camera, already scaled
+//!    by the LOD bias and frame budget the way the live systems see them
+//! 3. LOD tiers: vehicles and NPCs against their configured LOD bands,
+//!    everything else with a `VisibilityRange` by how far into it it sits
+//! 4. The road network graph and its intersections
+//! 5. Occupied `SpatialIndex` cells with their entry counts as height
+//! 6. Crowd density per hectare: the district's target NPC count as a white
+//!    post next to the actual count, over a tile shaded from blue (too few)
+//!    through green to red (too many)
+
+use crate::components::{MainCamera, NPCState, VehicleState};
+use crate::config::{GameConfig, LodConfig};
+use crate::resources::crowd_density::CROWD_CELL_SIZE;
+use crate::resources::{CrowdDensity, DistrictMap};
+use crate::systems::performance::frame_budget::FrameBudgetGovernor;
+use crate::systems::spatial_index::SpatialIndex;
+use crate::systems::world::road_network::RoadType;
+use crate::systems::world::unified_world::UnifiedWorldManager;
+use bevy::prelude::*;
+use bevy::render::view::visibility::VisibilityRange;
+use bevy_rapier3d::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+/// Colliders and LOD markers beyond this distance are skipped
+const DETAIL_RADIUS: f32 = 200.0;
+/// Roads and spatial cells beyond this distance are skipped
+const OVERVIEW_RADIUS: f32 = 1000.0;
+const ROAD_SAMPLES: usize = 8;
+/// Height of one NPC in the crowd density posts
+const CROWD_POST_SCALE: f32 = 4.0;
+/// Full, medium, low, culled
+const TIER_COLORS: [Color; 4] = [
+    Color::srgb(0.2, 1.0, 0.2),
+    Color::srgb(1.0, 1.0, 0.2),
+    Color::srgb(1.0, 0.55, 0.1),
+    Color::srgb(1.0, 0.15, 0.15),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoLayer {
+    Colliders,
+    Culling,
+    Lod,
+    Roads,
+    SpatialCells,
+    CrowdDensity,
+}
+
+impl GizmoLayer {
+    pub const ALL: [GizmoLayer; 6] = [
+        GizmoLayer::Colliders,
+        GizmoLayer::Culling,
+        GizmoLayer::Lod,
+        GizmoLayer::Roads,
+        GizmoLayer::SpatialCells,
+        GizmoLayer::CrowdDensity,
+    ];
+
+    fn key(self) -> KeyCode {
+        match self {
+            GizmoLayer::Colliders => KeyCode::Digit1,
+            GizmoLayer::Culling => KeyCode::Digit2,
+            GizmoLayer::Lod => KeyCode::Digit3,
+            GizmoLayer::Roads => KeyCode::Digit4,
+            GizmoLayer::SpatialCells => KeyCode::Digit5,
+            GizmoLayer::CrowdDensity => KeyCode::Digit6,
+        }
+    }
+}
+
+/// Which gizmo layers are drawn
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct DebugGizmoLayers {
+    enabled: [bool; GizmoLayer::ALL.len()],
+}
+
+impl DebugGizmoLayers {
+    pub fn is_enabled(&self, layer: GizmoLayer) -> bool {
+        self.enabled[layer as usize]
+    }
+
+    /// Flips `layer` and returns its new state
+    pub fn toggle(&mut self, layer: GizmoLayer) -> bool {
+        let enabled = &mut self.enabled[layer as usize];
+        *enabled = !*enabled;
+        *enabled
+    }
+
+    pub fn clear(&mut self) {
+        self.enabled = Default::default();
+    }
+}
+
+/// Run condition for the draw systems of one layer
+pub fn gizmo_layer_enabled(layer: GizmoLayer) -> impl Fn(Res<DebugGizmoLayers>) -> bool {
+    move |layers| layers.is_enabled(layer)
+}
+
+pub fn toggle_debug_gizmo_layers(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut layers: ResMut<DebugGizmoLayers>,
+) {
+    if !keys.pressed(KeyCode::F6) {
+        return;
+    }
+    if keys.just_pressed(KeyCode::Digit0) {
+        layers.clear();
+        info!("Debug gizmos cleared");
+        return;
+    }
+    for layer in GizmoLayer::ALL {
+        if keys.just_pressed(layer.key()) {
+            let enabled = layers.toggle(layer);
+            info!(
+                "Debug gizmo layer {layer:?}: {}",
+                if enabled { "on" } else { "off" }
+            );
+        }
+    }
+}
+
+/// Flat isometry lying on the XZ plane at `position`
+fn ground(position: Vec3) -> Isometry3d {
+    Isometry3d::new(position, Quat::from_rotation_x(FRAC_PI_2))
+}
+
+pub fn draw_collider_wireframes(
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    colliders: Query<(&GlobalTransform, &Collider, Has<RigidBodyDisabled>)>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera = camera.translation();
+    let radius_squared = DETAIL_RADIUS * DETAIL_RADIUS;
+    for (transform, collider, disabled) in &colliders {
+        if transform.translation().distance_squared(camera) > radius_squared {
+            continue;
+        }
+        let color = if disabled {
+            Color::srgb(0.5, 0.5, 0.5)
+        } else {
+            Color::srgb(0.2, 0.8, 1.0)
+        };
+        draw_collider(&mut gizmos, transform, collider, color);
+    }
+}
+
+/// Colliders already carry their entity's scale, so only rotation and
+/// translation are taken from the transform
+fn draw_collider(
+    gizmos: &mut Gizmos,
+    transform: &GlobalTransform,
+    collider: &Collider,
+    color: Color,
+) {
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    let isometry = Isometry3d::new(translation, rotation);
+    match collider.as_typed_shape() {
+        ColliderView::Ball(ball) => {
+            gizmos.sphere(isometry, ball.radius(), color);
+        }
+        ColliderView::Cuboid(cuboid) => {
+            gizmos.cuboid(
+                Transform {
+                    translation,
+                    rotation,
+                    scale: cuboid.half_extents() * 2.0,
+                },
+                color,
+            );
+        }
+        ColliderView::Capsule(capsule) => {
+            let segment = capsule.segment();
+            let axis = segment.b() - segment.a();
+            let center = translation + rotation * (segment.a() + segment.b()) * 0.5;
+            let orientation =
+                rotation * Quat::from_rotation_arc(Vec3::Y, axis.normalize_or(Vec3::Y));
+            gizmos.primitive_3d(
+                &Capsule3d::new(capsule.radius(), axis.length()),
+                Isometry3d::new(center, orientation),
+                color,
+            );
+        }
+        ColliderView::Cylinder(cylinder) => {
+            gizmos.primitive_3d(
+                &Cylinder::new(cylinder.radius(), cylinder.half_height() * 2.0),
+                isometry,
+                color,
+            );
+        }
+        _ => {
+            let aabb = collider.raw.compute_local_aabb();
+            let min = Vec3::new(aabb.mins.x, aabb.mins.y, aabb.mins.z);
+            let max = Vec3::new(aabb.maxs.x, aabb.maxs.y, aabb.maxs.z);
+            gizmos.cuboid(
+                Transform {
+                    translation: translation + rotation * (min + max) * 0.5,
+                    rotation,
+                    scale: max - min,
+                },
+                color,
+            );
+        }
+    }
+}
+
+/// Cull distances as the live systems apply them, with their ring colors
+fn culling_rings(config: &GameConfig, bias: f32) -> [(f32, Color); 6] {
+    let streaming = &config.world_streaming;
+    [
+        (streaming.npc_lod.cull * bias, Color::srgb(1.0, 0.4, 0.8)),
+        (
+            streaming.car_impostor_distance * bias,
+            Color::srgb(1.0, 0.6, 0.2),
+        ),
+        (
+            streaming.prop_cull_distance * bias,
+            Color::srgb(1.0, 1.0, 0.3),
+        ),
+        (
+            streaming.vegetation_cull_distance * bias,
+            Color::srgb(0.3, 1.0, 0.3),
+        ),
+        (
+            config.performance.hlod_distance * bias,
+            Color::srgb(0.3, 0.7, 1.0),
+        ),
+        (streaming.streaming_radius, Color::srgb(0.8, 0.8, 0.8)),
+    ]
+}
+
+pub fn draw_culling_rings(
+    config: Res<GameConfig>,
+    governor: Res<FrameBudgetGovernor>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let bias = config.graphics.quality.settings().lod_bias * governor.scale();
+    let center = ground(camera.translation());
+    for (radius, color) in culling_rings(&config, bias) {
+        gizmos.circle(center, radius, color).resolution(96);
+    }
+}
+
+/// Tier of an entity `distance` away under `lod`: full, medium, low or culled
+pub fn lod_tier(distance: f32, lod: &LodConfig) -> usize {
+    [lod.full, lod.medium, lod.low]
+        .iter()
+        .position(|limit| distance <= *limit)
+        .unwrap_or(3)
+}
+
+/// Tier of an entity with no LOD bands of its own, by how far into its
+/// visibility range it sits
+pub fn range_tier(distance: f32, range: &VisibilityRange) -> usize {
+    let end = range.end_margin.end;
+    if distance > end {
+        3
+    } else if distance > end * 0.75 {
+        2
+    } else if distance > end * 0.5 {
+        1
+    } else {
+        0
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn draw_lod_tiers(
+    config: Res<GameConfig>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    vehicles: Query<&GlobalTransform, With<VehicleState>>,
+    npcs: Query<&GlobalTransform, With<NPCState>>,
+    ranged: Query<(&GlobalTransform, &VisibilityRange), (Without<VehicleState>, Without<NPCState>)>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera = camera.translation();
+    let streaming = &config.world_streaming;
+    let mut mark = |position: Vec3, tier: usize, size: f32| {
+        gizmos.sphere(
+            Isometry3d::from_translation(position),
+            size,
+            TIER_COLORS[tier],
+        );
+    };
+
+    for transform in &vehicles {
+        let distance = transform.translation().distance(camera);
+        mark(
+            transform.translation(),
+            lod_tier(distance, &streaming.vehicle_lod),
+            2.0,
+        );
+    }
+    for transform in &npcs {
+        let distance = transform.translation().distance(camera);
+        mark(
+            transform.translation(),
+            lod_tier(distance, &streaming.npc_lod),
+            1.0,
+        );
+    }
+    for (transform, range) in &ranged {
+        let distance = transform.translation().distance(camera);
+        if distance <= DETAIL_RADIUS {
+            mark(transform.translation(), range_tier(distance, range), 0.5);
+        }
+    }
+}
+
+fn road_color(road_type: RoadType) -> Color {
+    match road_type {
+        RoadType::Highway => Color::srgb(1.0, 0.3, 0.1),
+        RoadType::MainStreet => Color::srgb(1.0, 0.8, 0.1),
+        RoadType::SideStreet => Color::srgb(0.3, 0.9, 1.0),
+        RoadType::Alley => Color::srgb(0.7, 0.7, 0.7),
+    }
+}
+
+pub fn draw_road_network(
+    world_manager: Res<UnifiedWorldManager>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera = camera.translation();
+    let near = |position: Vec3| position.xz().distance(camera.xz()) <= OVERVIEW_RADIUS;
+    let lift = Vec3::Y * 0.5;
+    let network = &world_manager.road_network;
+
+    for road in network.roads.values() {
+        if !road.control_points.iter().any(|point| near(*point)) {
+            continue;
+        }
+        gizmos.linestrip(
+            (0..=ROAD_SAMPLES).map(|i| road.evaluate(i as f32 / ROAD_SAMPLES as f32) + lift),
+            road_color(road.road_type),
+        );
+    }
+    for intersection in network.intersections.values() {
+        if near(intersection.position) {
+            gizmos.circle(
+                ground(intersection.position + lift),
+                intersection.radius,
+                Color::WHITE,
+            );
+        }
+    }
+}
+
+pub fn draw_spatial_cells(
+    index: Res<SpatialIndex>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera = camera.translation();
+    let size = index.cell_size();
+    for (cell, count) in index.occupied_cells() {
+        let center = Vec3::new(
+            (cell.x as f32 + 0.5) * size,
+            camera.y - 2.0,
+            (cell.y as f32 + 0.5) * size,
+        );
+        if center.xz().distance(camera.xz()) > OVERVIEW_RADIUS {
+            continue;
+        }
+        let color = Color::srgb(0.6, 0.3, 1.0);
+        gizmos.rect(ground(center), Vec2::splat(size), color);
+        gizmos.line(center, center + Vec3::Y * count as f32, color);
+    }
+}
+
+/// Tile shade for `actual` NPCs against `target`: blue when empty, green on
+/// target, red at twice the target or more
+pub fn crowd_heat(actual: f32, target: f32) -> Color {
+    let ratio = if target > 0.0 {
+        actual / target
+    } else if actual > 0.0 {
+        2.0
+    } else {
+        1.0
+    };
+    if ratio < 1.0 {
+        Color::srgb(0.0, ratio, 1.0 - ratio)
+    } else {
+        let over = (ratio - 1.0).min(1.0);
+        Color::srgb(over, 1.0 - over, 0.0)
+    }
+}
+
+pub fn draw_crowd_density(
+    density: Res<CrowdDensity>,
+    districts: Res<DistrictMap>,
+    world_manager: Res<UnifiedWorldManager>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera = camera.translation();
+    let center = CrowdDensity::cell(camera);
+    let reach = (OVERVIEW_RADIUS / CROWD_CELL_SIZE).ceil() as i32;
+    for x in -reach..=reach {
+        for z in -reach..=reach {
+            let cell = center + IVec2::new(x, z);
+            let ground_center = CrowdDensity::cell_center(cell).with_y(camera.y - 2.0);
+            if ground_center.xz().distance(camera.xz()) > OVERVIEW_RADIUS
+                || !world_manager.is_on_terrain_island(ground_center)
+            {
+                continue;
+            }
+            let target = districts.npc_density_at(ground_center);
+            let actual = density.count(cell) as f32;
+            let color = crowd_heat(actual, target);
+            gizmos.rect(
+                ground(ground_center),
+                Vec2::splat(CROWD_CELL_SIZE * 0.9),
+                color,
+            );
+            let post = Vec3::X * CROWD_CELL_SIZE * 0.1;
+            gizmos.line(
+                ground_center - post,
+                ground_center - post + Vec3::Y * target * CROWD_POST_SCALE,
+                Color::WHITE,
+            );
+            gizmos.line(
+                ground_center + post,
+                ground_center + post + Vec3::Y * actual * CROWD_POST_SCALE,
+                color,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lod_tiers_follow_config_bands() {
+        let lod = LodConfig {
+            full: 50.0,
+            medium: 100.0,
+            low: 125.0,
+            cull: 150.0,
+        };
+        let tiers: Vec<usize> = [10.0, 50.0, 75.0, 120.0, 140.0]
+            .iter()
+            .map(|d| lod_tier(*d, &lod))
+            .collect();
+        assert_eq!(tiers, vec![0, 0, 1, 2, 3]);
+
+        let range = VisibilityRange::abrupt(0.0, 200.0);
+        assert_eq!(range_tier(20.0, &range), 0);
+        assert_eq!(range_tier(160.0, &range), 2);
+        assert_eq!(range_tier(250.0, &range), 3);
+    }
+
+    #[test]
+    fn test_layers_toggle_independently() {
+        let mut layers = DebugGizmoLayers::default();
+        assert!(layers.toggle(GizmoLayer::Roads));
+        assert!(layers.is_enabled(GizmoLayer::Roads));
+        assert!(!layers.is_enabled(GizmoLayer::Colliders));
+        assert!(!layers.toggle(GizmoLayer::Roads));
+        layers.toggle(GizmoLayer::Lod);
+        layers.clear();
+        assert!(!layers.is_enabled(GizmoLayer::Lod));
+    }
+
+    #[test]
+    fn test_crowd_heat_runs_from_blue_through_green_to_red() {
+        assert_eq!(crowd_heat(0.0, 2.0), Color::srgb(0.0, 0.0, 1.0));
+        assert_eq!(crowd_heat(2.0, 2.0), Color::srgb(0.0, 1.0, 0.0));
+        assert_eq!(crowd_heat(5.0, 2.0), Color::srgb(1.0, 0.0, 0.0));
+        assert_eq!(crowd_heat(1.0, 0.0), Color::srgb(1.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,113 @@
+//! Secondary top-down debug window.
+//!
+//! F11 opens a second OS window with its own camera hovering above the main
+//! camera, looking straight down with north up. Gizmos draw into every
+//! camera, so the F6 layers (culling rings, LOD tiers, road graph) can be
+//! watched from above while playing in the main window. Pressing F11 again
+//! or closing the window tears it down; closing the main window still quits.
+
+use crate::components::MainCamera;
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::window::WindowRef;
+
+/// Height of the debug camera above the main camera
+const VIEW_HEIGHT: f32 = 400.0;
+const WINDOW_SIZE: (f32, f32) = (640.0, 480.0);
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DebugWindow;
+
+/// Camera rendering into the `DebugWindow` entity `window`
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DebugWindowCamera {
+    pub window: Entity,
+}
+
+pub fn toggle_debug_window(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<Entity, With<DebugWindow>>,
+    cameras: Query<Entity, With<DebugWindowCamera>>,
+) {
+    if !keys.just_pressed(KeyCode::F11) {
+        return;
+    }
+    if !windows.is_empty() {
+        for entity in windows.iter().chain(cameras.iter()) {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let window = commands
+        .spawn((
+            DebugWindow,
+            Window {
+                title: "GTA Debug View".to_string(),
+                resolution: WINDOW_SIZE.into(),
+                ..default()
+            },
+            Name::new("Debug Window"),
+        ))
+        .id();
+    commands.spawn((
+        DebugWindowCamera { window },
+        Camera3d::default(),
+        Camera {
+            target: RenderTarget::Window(WindowRef::Entity(window)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, VIEW_HEIGHT, 0.0).looking_to(Vec3::NEG_Y, Vec3::NEG_Z),
+        Name::new("Debug Window Camera"),
+    ));
+    info!("🪟 Debug window opened");
+}
+
+/// Keeps the debug camera above the main camera
+pub fn follow_main_camera(
+    main_camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut cameras: Query<&mut Transform, With<DebugWindowCamera>>,
+) {
+    let Ok(main_camera) = main_camera.single() else {
+        return;
+    };
+    let position = main_camera.translation();
+    for mut transform in &mut cameras {
+        transform.translation = Vec3::new(position.x, position.y + VIEW_HEIGHT, position.z);
+    }
+}
+
+/// Removes the camera of a debug window the OS closed
+pub fn despawn_orphaned_debug_cameras(
+    mut commands: Commands,
+    windows: Query<(), With<DebugWindow>>,
+    cameras: Query<(Entity, &DebugWindowCamera)>,
+) {
+    for (entity, camera) in &cameras {
+        if !windows.contains(camera.window) {
+            commands.entity(entity).despawn();
+            info!("🪟 Debug window closed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_goes_with_its_window() {
+        let mut app = App::new();
+        app.add_systems(Update, despawn_orphaned_debug_cameras);
+        let window = app.world_mut().spawn(DebugWindow).id();
+        let camera = app.world_mut().spawn(DebugWindowCamera { window }).id();
+
+        app.update();
+        assert!(app.world().get_entity(camera).is_ok());
+
+        app.world_mut().despawn(window);
+        app.update();
+        assert!(app.world().get_entity(camera).is_err());
+    }
+}
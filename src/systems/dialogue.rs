@@ -0,0 +1,312 @@
+//! Talking to NPCs.
+//!
+//! Ambient NPCs get a tree from `dialogues.ron` when they spawn. They are
+//! `Interactable`s, so using one opens the dialogue box; choices are picked with
+//! the mouse or the number keys and may start a mission or open a shop. All
+//! text goes through the `Localization` resource.
+
+use crate::components::mission::StartMissionRequest;
+use crate::components::{
+    ActiveDialogue, ActiveEntity, DialogueAction, DialogueCatalog, DialogueChoiceButton,
+    DialogueSession, Interactable, InteractionEvent, InteractionKind, NPCState, OpenShopRequest,
+    Player, Ragdoll, Talkable,
+};
+use crate::config::GameConfig;
+use crate::resources::Localization;
+use crate::systems::garage::{HOVERED_BUTTON, NORMAL_BUTTON};
+use crate::systems::shops::load_config;
+use crate::t;
+use bevy::prelude::*;
+
+const CHOICE_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// The conversation ends once the player is this many talk radii away
+const WALK_AWAY_RADII: f32 = 2.0;
+
+/// Vehicles bought through a dialogue shop are delivered this far in front of
+/// the NPC
+const DELIVERY_DISTANCE: f32 = 6.0;
+
+pub fn load_dialogues(mut commands: Commands) {
+    commands.insert_resource(load_config::<DialogueCatalog>("dialogues.ron"));
+}
+
+/// Hands each new NPC one of the ambient trees
+pub fn assign_npc_dialogue(
+    mut commands: Commands,
+    catalog: Res<DialogueCatalog>,
+    npcs: Query<Entity, (Added<NPCState>, Without<Talkable>)>,
+) {
+    for npc in &npcs {
+        if let Some(dialogue) = catalog.ambient_for(npc.index()) {
+            commands.entity(npc).insert(Talkable {
+                dialogue: dialogue.to_string(),
+            });
+        }
+    }
+}
+
+/// Gives NPCs that can talk their `Interactable`, again when the language or
+/// the talk radius changes, and takes it away while they are knocked over
+#[allow(clippy::type_complexity)]
+pub fn sync_talk_interactables(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    localization: Res<Localization>,
+    npcs: Query<(Entity, Has<Interactable>, Has<Ragdoll>), (With<Talkable>, With<NPCState>)>,
+) {
+    let refresh = config.is_changed() || localization.is_changed();
+    for (npc, interactable, knocked_over) in &npcs {
+        if knocked_over {
+            if interactable {
+                commands.entity(npc).remove::<Interactable>();
+            }
+        } else if refresh || !interactable {
+            commands.entity(npc).insert(Interactable::new(
+                InteractionKind::Talk,
+                config.world.npc_talk_radius,
+                t!("ui.talk_prompt"),
+            ));
+        }
+    }
+}
+
+/// Using an NPC starts talking to them, or ends the open conversation
+pub fn dialogue_interaction_system(
+    catalog: Res<DialogueCatalog>,
+    mut interactions: EventReader<InteractionEvent>,
+    mut active: ResMut<ActiveDialogue>,
+    npcs: Query<&Talkable>,
+) {
+    for interaction in interactions.read() {
+        if interaction.kind != InteractionKind::Talk {
+            continue;
+        }
+        if active.0.is_some() {
+            active.0 = None;
+            continue;
+        }
+        let Ok(talkable) = npcs.get(interaction.target) else {
+            continue;
+        };
+        let Some(tree) = catalog.tree(&talkable.dialogue) else {
+            warn!("⚠️ Unknown dialogue '{}'", talkable.dialogue);
+            continue;
+        };
+        active.0 = Some(DialogueSession {
+            npc: interaction.target,
+            tree: tree.id.clone(),
+            node: tree.start.clone(),
+        });
+    }
+}
+
+/// Applies the clicked or numbered choice: fires its action and moves on to
+/// the next node, or ends the conversation
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn dialogue_choice_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    catalog: Res<DialogueCatalog>,
+    mut active: ResMut<ActiveDialogue>,
+    mut buttons: Query<
+        (&Interaction, &DialogueChoiceButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    npcs: Query<&GlobalTransform>,
+    mut missions: EventWriter<StartMissionRequest>,
+    mut shops: EventWriter<OpenShopRequest>,
+) {
+    let mut picked = CHOICE_KEYS.iter().position(|key| keys.just_pressed(*key));
+    for (interaction, button, mut color) in &mut buttons {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+        if *interaction == Interaction::Pressed {
+            picked = Some(button.0);
+        }
+    }
+    let (Some(index), Some(session)) = (picked, active.0.as_ref()) else {
+        return;
+    };
+    let Some(choice) = catalog
+        .tree(&session.tree)
+        .and_then(|tree| tree.node(&session.node))
+        .and_then(|node| node.choices.get(index))
+    else {
+        return;
+    };
+
+    match &choice.action {
+        Some(DialogueAction::StartMission(mission_id)) => {
+            missions.write(StartMissionRequest {
+                mission_id: mission_id.clone(),
+            });
+        }
+        Some(DialogueAction::OpenShop(kind)) => {
+            if let Ok(npc) = npcs.get(session.npc) {
+                shops.write(OpenShopRequest {
+                    kind: *kind,
+                    anchor: npc.translation(),
+                    delivery: npc.translation() + npc.forward() * DELIVERY_DISTANCE,
+                });
+            }
+        }
+        None => {}
+    }
+    active.0 = choice.next.clone().map(|node| DialogueSession {
+        node,
+        ..session.clone()
+    });
+}
+
+/// Ends the conversation when the NPC is gone, knocked over or left behind
+pub fn end_abandoned_dialogue(
+    config: Res<GameConfig>,
+    mut active: ResMut<ActiveDialogue>,
+    player: Query<&GlobalTransform, (With<Player>, With<ActiveEntity>)>,
+    npcs: Query<&GlobalTransform, (With<Talkable>, Without<Ragdoll>)>,
+) {
+    let Some(session) = &active.0 else {
+        return;
+    };
+    let in_reach = match (player.single(), npcs.get(session.npc)) {
+        (Ok(player), Ok(npc)) => {
+            player.translation().distance(npc.translation())
+                <= config.world.npc_talk_radius * WALK_AWAY_RADII
+        }
+        _ => false,
+    };
+    if !in_reach {
+        active.0 = None;
+    }
+}
+
+/// Closes the conversation when the player stops walking, e.g. gets in a car
+pub fn end_dialogue(mut active: ResMut<ActiveDialogue>) {
+    active.set_if_neq(ActiveDialogue(None));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialogue_config_parses() {
+        let path = format!(
+            "{}/config/dialogues.ron",
+            crate::util::asset_path::get_assets_base_path()
+        );
+        let content = std::fs::read_to_string(path).unwrap();
+        let catalog: DialogueCatalog = ron::from_str(&content).unwrap();
+        for id in &catalog.ambient {
+            assert!(catalog.tree(id).is_some(), "unknown ambient tree '{id}'");
+        }
+        for tree in &catalog.trees {
+            assert!(tree.node(&tree.start).is_some());
+            for next in tree.nodes.iter().flat_map(|node| &node.choices) {
+                if let Some(next) = &next.next {
+                    assert!(
+                        tree.node(next).is_some(),
+                        "'{}' has no node '{next}'",
+                        tree.id
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_choice_fires_action_and_ends_dialogue() {
+        let catalog: DialogueCatalog = ron::from_str(
+            r#"(
+                trees: [(
+                    id: "tip",
+                    start: "hello",
+                    nodes: [(
+                        id: "hello",
+                        speaker: "Stranger",
+                        text: "Want a job?",
+                        choices: [
+                            (text: "Sure", action: Some(StartMission("street_tip"))),
+                            (text: "Tell me more", next: Some("hello")),
+                        ],
+                    )],
+                )],
+            )"#,
+        )
+        .unwrap();
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(catalog)
+            .add_event::<StartMissionRequest>()
+            .add_event::<OpenShopRequest>()
+            .add_systems(Update, dialogue_choice_system);
+        let npc = app.world_mut().spawn(GlobalTransform::default()).id();
+        app.insert_resource(ActiveDialogue(Some(DialogueSession {
+            npc,
+            tree: "tip".to_string(),
+            node: "hello".to_string(),
+        })));
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Digit1);
+        app.update();
+
+        assert_eq!(app.world().resource::<ActiveDialogue>().0, None);
+        let requests = app.world().resource::<Events<StartMissionRequest>>();
+        let mut reader = requests.get_cursor();
+        let started: Vec<_> = reader
+            .read(requests)
+            .map(|r| r.mission_id.clone())
+            .collect();
+        assert_eq!(started, ["street_tip"]);
+    }
+
+    #[test]
+    fn test_using_an_npc_opens_then_closes_the_conversation() {
+        let catalog: DialogueCatalog = ron::from_str(
+            r#"(trees: [(id: "tip", start: "hello", nodes: [(id: "hello", speaker: "Stranger", text: "Hi", choices: [])])])"#,
+        )
+        .unwrap();
+        let mut app = App::new();
+        app.insert_resource(catalog)
+            .init_resource::<ActiveDialogue>()
+            .add_event::<InteractionEvent>()
+            .add_systems(Update, dialogue_interaction_system);
+        let actor = app.world_mut().spawn_empty().id();
+        let npc = app
+            .world_mut()
+            .spawn(Talkable {
+                dialogue: "tip".to_string(),
+            })
+            .id();
+        let talk = InteractionEvent {
+            actor,
+            target: npc,
+            kind: InteractionKind::Talk,
+        };
+
+        app.world_mut().send_event(talk);
+        app.update();
+        let session = app.world().resource::<ActiveDialogue>().0.clone();
+        assert_eq!(
+            session.map(|session| (session.npc, session.node)),
+            Some((npc, "hello".to_string()))
+        );
+
+        app.world_mut().send_event(talk);
+        app.update();
+        assert_eq!(app.world().resource::<ActiveDialogue>().0, None);
+    }
+}
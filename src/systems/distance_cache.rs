@@ -0,0 +1,281 @@
+//! Camera-relative distances of moving entities, bucketed into LOD bands.
+//!
+//! Every entity with a `MovementTracker` gets its squared distance to the
+//! main camera cached together with the `DistanceBand` it falls in, using the
+//! `world.lod_distances` thresholds. Band lists let LOD and culling passes
+//! walk only the entities in the band they care about instead of the whole
+//! world.
+//!
+//! Refreshes are incremental: an entity is re-measured once it has moved past
+//! its tracker's threshold, and everything is re-measured when the camera has
+//! moved `CAMERA_REBUCKET_DISTANCE` from where the cache was last measured.
+//! Distances are computed in parallel batches on the compute task pool.
+
+use crate::components::{MainCamera, MovementTracker};
+use crate::config::GameConfig;
+use crate::systems::performance::memory::table_bytes;
+use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, ParallelSlice, TaskPool};
+use std::collections::HashMap;
+
+/// Camera travel that invalidates every cached distance
+const CAMERA_REBUCKET_DISTANCE: f32 = 10.0;
+/// Entities measured per parallel task; smaller refreshes run inline
+const BATCH_SIZE: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DistanceBand {
+    Near,
+    Mid,
+    Far,
+    Beyond,
+}
+
+impl DistanceBand {
+    pub const ALL: [DistanceBand; 4] = [
+        DistanceBand::Near,
+        DistanceBand::Mid,
+        DistanceBand::Far,
+        DistanceBand::Beyond,
+    ];
+
+    /// Band of a squared distance; `thresholds` are the near, mid and far band edges
+    pub fn from_distance_sq(distance_sq: f32, thresholds: [f32; 3]) -> Self {
+        match thresholds.iter().position(|edge| distance_sq < edge * edge) {
+            Some(0) => DistanceBand::Near,
+            Some(1) => DistanceBand::Mid,
+            Some(_) => DistanceBand::Far,
+            None => DistanceBand::Beyond,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedDistance {
+    distance_sq: f32,
+    band: DistanceBand,
+    /// Position of the entity in its band's list
+    slot: usize,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct DistanceCache {
+    /// Camera position the cached distances were measured from
+    origin: Option<Vec3>,
+    entries: HashMap<Entity, CachedDistance>,
+    bands: [Vec<Entity>; 4],
+}
+
+impl DistanceCache {
+    pub fn origin(&self) -> Option<Vec3> {
+        self.origin
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn distance_sq(&self, entity: Entity) -> Option<f32> {
+        self.entries.get(&entity).map(|entry| entry.distance_sq)
+    }
+
+    pub fn band(&self, entity: Entity) -> Option<DistanceBand> {
+        self.entries.get(&entity).map(|entry| entry.band)
+    }
+
+    /// Approximate memory held by the entries and band lists
+    pub fn memory_bytes(&self) -> usize {
+        table_bytes::<(Entity, CachedDistance)>(self.entries.capacity())
+            + self
+                .bands
+                .iter()
+                .map(|band| band.capacity() * std::mem::size_of::<Entity>())
+                .sum::<usize>()
+    }
+
+    /// Entities currently in `band`, in no particular order
+    pub fn in_band(&self, band: DistanceBand) -> &[Entity] {
+        &self.bands[band.index()]
+    }
+
+    /// Records a measurement, moving the entity between band lists as needed
+    pub fn set(&mut self, entity: Entity, distance_sq: f32, band: DistanceBand) {
+        if let Some(entry) = self.entries.get_mut(&entity) {
+            entry.distance_sq = distance_sq;
+            if entry.band == band {
+                return;
+            }
+        }
+        self.unlink(entity);
+        let list = &mut self.bands[band.index()];
+        list.push(entity);
+        self.entries.insert(
+            entity,
+            CachedDistance {
+                distance_sq,
+                band,
+                slot: list.len() - 1,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        self.unlink(entity);
+        self.entries.remove(&entity).is_some()
+    }
+
+    /// Takes the entity out of its band list, patching the slot of the entry swapped in
+    fn unlink(&mut self, entity: Entity) {
+        let Some(entry) = self.entries.get(&entity).copied() else {
+            return;
+        };
+        let list = &mut self.bands[entry.band.index()];
+        list.swap_remove(entry.slot);
+        if let Some(moved) = list.get(entry.slot).copied()
+            && let Some(moved) = self.entries.get_mut(&moved)
+        {
+            moved.slot = entry.slot;
+        }
+    }
+}
+
+/// Squared distance and band of each position, split into parallel batches when large
+fn measure(
+    positions: &[(Entity, Vec3)],
+    origin: Vec3,
+    thresholds: [f32; 3],
+) -> Vec<(Entity, f32, DistanceBand)> {
+    let measure_batch = |_: usize, batch: &[(Entity, Vec3)]| {
+        batch
+            .iter()
+            .map(|(entity, position)| {
+                let distance_sq = position.distance_squared(origin);
+                (
+                    *entity,
+                    distance_sq,
+                    DistanceBand::from_distance_sq(distance_sq, thresholds),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    if positions.len() <= BATCH_SIZE {
+        return measure_batch(0, positions);
+    }
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    positions
+        .par_chunk_map(pool, BATCH_SIZE, measure_batch)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Re-measures moved entities, or all of them once the camera has moved far enough
+pub fn update_distance_cache(
+    config: Res<GameConfig>,
+    mut cache: ResMut<DistanceCache>,
+    camera: Query<&Transform, With<MainCamera>>,
+    mut tracked: Query<(Entity, &Transform, &mut MovementTracker)>,
+    mut removed: RemovedComponents<MovementTracker>,
+) {
+    for entity in removed.read() {
+        cache.remove(entity);
+    }
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera = camera.translation;
+
+    let rebucket = cache
+        .origin
+        .is_none_or(|origin| origin.distance_squared(camera) > CAMERA_REBUCKET_DISTANCE.powi(2));
+    let origin = if rebucket {
+        cache.origin = Some(camera);
+        camera
+    } else {
+        cache.origin.unwrap_or(camera)
+    };
+
+    let mut dirty = Vec::new();
+    for (entity, transform, mut tracker) in &mut tracked {
+        let position = transform.translation;
+        if rebucket
+            || tracker.has_moved_significantly(position)
+            || !cache.entries.contains_key(&entity)
+        {
+            tracker.update_position(position);
+            dirty.push((entity, position));
+        }
+    }
+    if dirty.is_empty() {
+        return;
+    }
+
+    crate::profile_scope!("distance_cache_refresh", entities = dirty.len());
+    for (entity, distance_sq, band) in measure(&dirty, origin, config.world.lod_distances) {
+        cache.set(entity, distance_sq, band);
+    }
+}
+
+pub struct DistanceCachePlugin;
+
+impl Plugin for DistanceCachePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DistanceCache>()
+            .add_systems(PostUpdate, update_distance_cache);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_lists_follow_moves_and_removals() {
+        let thresholds = [10.0, 20.0, 30.0];
+        let mut cache = DistanceCache::default();
+        let entities: Vec<Entity> = (0..4).map(Entity::from_raw).collect();
+        let positions: Vec<(Entity, Vec3)> = entities
+            .iter()
+            .enumerate()
+            .map(|(i, entity)| (*entity, Vec3::X * (5.0 + i as f32 * 10.0)))
+            .collect();
+        for (entity, distance_sq, band) in measure(&positions, Vec3::ZERO, thresholds) {
+            cache.set(entity, distance_sq, band);
+        }
+        for (band, entity) in DistanceBand::ALL.iter().zip(&entities) {
+            assert_eq!(cache.in_band(*band), [*entity]);
+        }
+
+        cache.set(entities[0], 625.0, DistanceBand::Far);
+        cache.remove(entities[2]);
+        assert!(cache.in_band(DistanceBand::Near).is_empty());
+        assert_eq!(cache.in_band(DistanceBand::Far), [entities[0]]);
+        assert_eq!(cache.distance_sq(entities[0]), Some(625.0));
+        assert_eq!(cache.band(entities[2]), None);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_parallel_batches_match_inline_measurement() {
+        let positions: Vec<(Entity, Vec3)> = (0..BATCH_SIZE as u32 * 3 + 7)
+            .map(|i| (Entity::from_raw(i), Vec3::new(i as f32, 0.0, -(i as f32))))
+            .collect();
+        let origin = Vec3::new(100.0, 5.0, 0.0);
+        let thresholds = [150.0, 300.0, 500.0];
+
+        let parallel = measure(&positions, origin, thresholds);
+        let inline: Vec<_> = positions
+            .chunks(BATCH_SIZE)
+            .flat_map(|batch| measure(batch, origin, thresholds))
+            .collect();
+        assert_eq!(parallel, inline);
+    }
+}
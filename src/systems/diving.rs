@@ -0,0 +1,171 @@
+//! Diving: breath, forced surfacing and the underwater camera look.
+//!
+//! Breath drains while the player swims with their head under the surface
+//! (`SwimState::Diving`) and refills above it; once it runs out
+//! `swim_velocity_apply_system` slows the swimmer and pulls them up. The main
+//! camera's `UnderwaterSettings` follow the water region under it, and its
+//! color grading blends toward a darker, desaturated look while submerged.
+
+use crate::components::unified_water::UnifiedWaterBody;
+use crate::components::{
+    Breath, MainCamera, OxygenMeter, OxygenMeterFill, Player, UnderwaterSettings,
+};
+use crate::config::{GameConfig, Indicator};
+use crate::systems::swimming::{SwimState, Swimming};
+use bevy::prelude::*;
+use bevy::render::view::ColorGrading;
+
+/// Below this fraction of breath the oxygen meter turns red
+const LOW_BREATH_FRACTION: f32 = 0.25;
+
+const OXYGEN_BLUE: Color = Color::srgb(0.3, 0.8, 1.0);
+const OXYGEN_RED: Color = Color::srgb(1.0, 0.3, 0.2);
+
+pub fn update_breath(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut swimmers: Query<(&mut Breath, Option<&Swimming>), With<Player>>,
+) {
+    for (mut breath, swimming) in swimmers.iter_mut() {
+        let submerged = swimming.is_some_and(|swimming| swimming.state == SwimState::Diving);
+        if !submerged && breath.remaining >= breath.capacity {
+            continue;
+        }
+        breath.tick(
+            time.delta_secs(),
+            submerged,
+            config.diving.breath_refill_rate,
+        );
+    }
+}
+
+/// Keeps the underwater post-process at the local water level and grades the
+/// camera while it is below the surface
+pub fn update_underwater_camera(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    water_regions: Query<&UnifiedWaterBody>,
+    mut camera: Query<
+        (&GlobalTransform, &mut UnderwaterSettings, &mut ColorGrading),
+        With<MainCamera>,
+    >,
+) {
+    let Ok((transform, mut settings, mut grading)) = camera.single_mut() else {
+        return;
+    };
+    let position = transform.translation();
+
+    if let Some(region) = water_regions
+        .iter()
+        .find(|region| region.contains_point(position.x, position.z))
+    {
+        let level = region.get_water_surface_level(time.elapsed_secs());
+        if (settings.sea_level - level).abs() > 0.01 {
+            settings.sea_level = level;
+        }
+    }
+
+    let (saturation, exposure) = if position.y < settings.sea_level {
+        (
+            config.diving.underwater_saturation,
+            config.diving.underwater_exposure,
+        )
+    } else {
+        (1.0, 0.0)
+    };
+    let global = &grading.global;
+    if (global.post_saturation - saturation).abs() < 0.001
+        && (global.exposure - exposure).abs() < 0.001
+    {
+        return;
+    }
+
+    let blend = 1.0 - (-config.diving.grading_blend_rate * time.delta_secs()).exp();
+    let global = &mut grading.global;
+    global.post_saturation = global.post_saturation.lerp(saturation, blend);
+    global.exposure = global.exposure.lerp(exposure, blend);
+}
+
+pub fn setup_oxygen_meter(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(60.0),
+                left: Val::Percent(50.0),
+                width: Val::Px(200.0),
+                height: Val::Px(10.0),
+                margin: UiRect::left(Val::Px(-100.0)),
+                padding: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            BorderRadius::all(Val::Px(3.0)),
+            Visibility::Hidden,
+            OxygenMeter,
+        ))
+        .with_children(|meter| {
+            meter.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(OXYGEN_BLUE),
+                BorderRadius::all(Val::Px(2.0)),
+                OxygenMeterFill,
+            ));
+        });
+}
+
+/// Shows the oxygen meter while the player's breath isn't full
+pub fn update_oxygen_meter(
+    config: Res<GameConfig>,
+    players: Query<&Breath, (With<Player>, Changed<Breath>)>,
+    mut meter_query: Query<&mut Visibility, With<OxygenMeter>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<OxygenMeterFill>>,
+) {
+    let Ok(breath) = players.single() else {
+        return;
+    };
+    let fraction = breath.fraction();
+
+    if let Ok(mut visibility) = meter_query.single_mut() {
+        *visibility = if fraction < 1.0 {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if let Ok((mut node, mut color)) = fill_query.single_mut() {
+        node.width = Val::Percent(fraction * 100.0);
+        color.0 = if fraction < LOW_BREATH_FRACTION {
+            config
+                .accessibility
+                .indicator(Indicator::Danger, OXYGEN_RED)
+        } else {
+            config.accessibility.indicator(Indicator::Good, OXYGEN_BLUE)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::Breath;
+
+    #[test]
+    fn test_breath_drains_underwater_and_refills_above() {
+        let mut breath = Breath::new(10.0);
+        breath.tick(4.0, true, 5.0);
+        assert_eq!(breath.remaining, 6.0);
+
+        breath.tick(8.0, true, 5.0);
+        assert!(breath.is_exhausted());
+        assert_eq!(breath.fraction(), 0.0);
+
+        breath.tick(1.0, false, 5.0);
+        assert_eq!(breath.remaining, 5.0);
+        breath.tick(5.0, false, 5.0);
+        assert_eq!(breath.fraction(), 1.0);
+    }
+}
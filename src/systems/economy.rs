@@ -0,0 +1,144 @@
+//! Money sources for the `Wallet`.
+//!
+//! Killed NPCs drop cash that the active entity collects by passing over it,
+//! and completed missions and won races pay their reward. Purchases happen at
+//! garages.
+
+use crate::components::gameplay_events::NpcKilled;
+use crate::components::mission::MissionCompleted;
+use crate::components::race::RaceFinished;
+use crate::components::{ActiveEntity, MoneyPickup, Wallet};
+use crate::config::GameConfig;
+use crate::systems::ui::console::{ConsoleCommands, ConsoleResult};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Dropped cash floats this high above the ground and spins at this rate
+const PICKUP_HEIGHT: f32 = 0.4;
+const PICKUP_SPIN: f32 = 2.0;
+
+pub fn drop_npc_cash(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut killed: EventReader<NpcKilled>,
+    mut assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let economy = &config.economy;
+    for event in killed.read() {
+        let amount = rand::thread_rng().gen_range(economy.npc_cash_min..=economy.npc_cash_max);
+        if amount == 0 {
+            continue;
+        }
+        let (mesh, material) = assets.get_or_insert_with(|| {
+            (
+                meshes.add(Cuboid::new(0.35, 0.1, 0.2)),
+                materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.3, 0.7, 0.25),
+                    emissive: LinearRgba::rgb(0.05, 0.3, 0.05),
+                    ..default()
+                }),
+            )
+        });
+        commands.spawn((
+            Name::new("Cash"),
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(event.position.with_y(event.position.y + PICKUP_HEIGHT)),
+            MoneyPickup {
+                amount,
+                lifetime: Timer::from_seconds(economy.pickup_lifetime, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+/// Spins dropped cash, credits it when the active entity is close and
+/// removes it once it expires
+pub fn collect_money_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    active: Query<&GlobalTransform, With<ActiveEntity>>,
+    mut pickups: Query<(Entity, &mut MoneyPickup, &mut Transform)>,
+    mut wallet: ResMut<Wallet>,
+) {
+    let collector = active.single().ok().map(GlobalTransform::translation);
+    for (entity, mut pickup, mut transform) in &mut pickups {
+        transform.rotate_y(PICKUP_SPIN * time.delta_secs());
+        if collector.is_some_and(|position| {
+            position.distance(transform.translation) <= config.economy.pickup_radius
+        }) {
+            wallet.credit(pickup.amount);
+            commands.entity(entity).despawn();
+        } else if pickup.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn credit_mission_rewards(
+    mut completed: EventReader<MissionCompleted>,
+    mut wallet: ResMut<Wallet>,
+) {
+    for event in completed.read() {
+        wallet.credit(event.reward.money);
+    }
+}
+
+pub fn credit_race_rewards(mut finished: EventReader<RaceFinished>, mut wallet: ResMut<Wallet>) {
+    for event in finished.read() {
+        wallet.credit(event.reward);
+    }
+}
+
+pub fn money_command(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let mut wallet = world.resource_mut::<Wallet>();
+    match args {
+        [] => {}
+        [amount] => wallet.set(
+            amount
+                .parse()
+                .map_err(|_| format!("`{amount}` is not an amount"))?,
+        ),
+        _ => return Err("usage: money [amount]".to_string()),
+    }
+    Ok(format!("${}", wallet.balance()))
+}
+
+pub fn register_economy_commands(mut registry: ResMut<ConsoleCommands>) {
+    registry.register("money", money_command).usage("[amount]");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_entity_collects_nearby_cash() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<GameConfig>()
+            .init_resource::<Wallet>()
+            .add_systems(Update, collect_money_pickups);
+        app.world_mut()
+            .spawn((ActiveEntity, GlobalTransform::default()));
+        let pickup = |x: f32| {
+            (
+                MoneyPickup {
+                    amount: 25,
+                    lifetime: Timer::from_seconds(60.0, TimerMode::Once),
+                },
+                Transform::from_xyz(x, PICKUP_HEIGHT, 0.0),
+            )
+        };
+        let near = app.world_mut().spawn(pickup(0.5)).id();
+        let far = app.world_mut().spawn(pickup(20.0)).id();
+        app.update();
+
+        assert_eq!(app.world().resource::<Wallet>().balance(), 25);
+        assert!(app.world().get_entity(near).is_err());
+        assert!(app.world().get_entity(far).is_ok());
+    }
+}
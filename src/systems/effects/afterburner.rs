@@ -1,15 +1,13 @@
-use crate::components::{ActiveEntity, AircraftFlight, ControlState, F16, SimpleF16SpecsHandle};
+use crate::components::{
+    ActiveEntity, AircraftFlight, ControlState, EmitterControl, F16, SimpleF16SpecsHandle,
+};
+use crate::factories::{PrefabFactory, PrefabOverrides, PrefabRegistry};
 use bevy::prelude::*;
 use bevy::render::view::visibility::VisibilityRange;
-use bevy_hanabi::prelude::*;
 use bevy_rapier3d::prelude::Velocity;
 
-/// Resource that caches the afterburner flame effect handle.
-/// Created once at startup and reused across all F16s for optimal performance.
-#[derive(Resource)]
-pub struct AfterburnerFlameEffect {
-    pub handle: Handle<EffectAsset>,
-}
+/// Emitter prefab for the flame, see `assets/prefabs/afterburner_flame.ron`
+const AFTERBURNER_PREFAB: &str = "afterburner_flame";
 
 /// Component linking an afterburner particle effect to its F16.
 /// Used to maintain O(N) updates and proper cleanup.
@@ -27,7 +25,7 @@ type ParticleQuery<'w, 's> = Query<
         Entity,
         &'static AfterburnerFlameOf,
         &'static mut Transform,
-        &'static mut EffectSpawner,
+        &'static mut EmitterControl,
     ),
     (With<AfterburnerFlame>, Without<F16>),
 >;
@@ -48,78 +46,27 @@ type F16StateQuery<'w, 's> = Query<
 
 type F16ExistsQuery<'w, 's> = Query<'w, 's, (), With<F16>>;
 
-/// Creates the afterburner flame particle effect asset.
-/// Called once at startup to initialize the cached effect handle.
-pub fn create_afterburner_flame_effect(effects: &mut Assets<EffectAsset>) -> Handle<EffectAsset> {
-    let writer = ExprWriter::new();
-
-    // Initialize particle age to 0
-    let age = writer.lit(0.).expr();
-    let init_age = SetAttributeModifier::new(Attribute::AGE, age);
-
-    // Very short lifetime for tight flames without trails (0.2-0.4 seconds)
-    let base_lifetime = writer.lit(0.2);
-    let lifetime_jitter = writer.rand(ScalarType::Float) * writer.lit(0.2);
-    let lifetime = (base_lifetime + lifetime_jitter).expr();
-    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
-
-    // Spawn particles in a cone shape pointing backwards from engine
-    let init_pos = SetPositionCone3dModifier {
-        height: writer.lit(0.5).expr(),
-        base_radius: writer.lit(0.1).expr(),
-        top_radius: writer.lit(0.3).expr(),
-        dimension: ShapeDimension::Volume,
-    };
-
-    // Directional velocity - shoot particles backwards along +Z axis
-    let base_vel = writer.lit(Vec3::new(0.0, 0.0, 25.0));
-    let random_offset =
-        (writer.rand(VectorType::VEC3F) * writer.lit(2.0) - writer.lit(1.0)) * writer.lit(3.0);
-    let vel = (base_vel + random_offset).expr();
-    let init_vel = SetAttributeModifier::new(Attribute::VELOCITY, vel);
-
-    // Add drag to slow particles down quickly (creates flame taper effect)
-    let drag = writer.lit(2.5).expr();
-    let update_drag = LinearDragModifier::new(drag);
-
-    // GTA V style color gradient: Bright core -> Orange -> Red -> Transparent
-    // Reduced HDR values to prevent excessive bloom
-    let mut color_gradient = bevy_hanabi::Gradient::new();
-    color_gradient.add_key(0.0, Vec4::new(3.0, 3.0, 2.5, 1.0)); // Bright white-yellow core
-    color_gradient.add_key(0.2, Vec4::new(3.0, 2.0, 0.8, 1.0)); // Bright orange-yellow
-    color_gradient.add_key(0.5, Vec4::new(2.5, 1.2, 0.3, 1.0)); // Orange-red
-    color_gradient.add_key(0.8, Vec4::new(2.0, 0.5, 0.0, 0.8)); // Deep red
-    color_gradient.add_key(1.0, Vec4::new(1.0, 0.0, 0.0, 0.0)); // Fade to transparent
-
-    // Size over lifetime - starts large, tapers to point
-    let mut size_gradient = bevy_hanabi::Gradient::new();
-    size_gradient.add_key(0.0, Vec3::new(0.6, 0.6, 1.5)); // Large, elongated
-    size_gradient.add_key(0.3, Vec3::new(0.5, 0.5, 1.2));
-    size_gradient.add_key(0.7, Vec3::new(0.3, 0.3, 0.8));
-    size_gradient.add_key(1.0, Vec3::new(0.05, 0.05, 0.2)); // Taper to point
-
-    // Orient particles along velocity for streak effect
-    let orient = OrientModifier::new(OrientMode::AlongVelocity);
-
-    let module = writer.finish();
-    let spawner = SpawnerSettings::rate(2000.0.into());
-
-    effects.add(
-        EffectAsset::new(8192, spawner, module)
-            .with_name("afterburner_flame")
-            .with_simulation_space(SimulationSpace::Local)
-            .init(init_pos)
-            .init(init_vel)
-            .init(init_age)
-            .init(init_lifetime)
-            .update(update_drag)
-            .render(ColorOverLifetimeModifier::new(color_gradient))
-            .render(SizeOverLifetimeModifier {
-                gradient: size_gradient,
-                screen_space_size: false,
-            })
-            .render(orient),
-    )
+/// Spawns the flame emitter at the F16's exhaust nozzle, switched off
+fn spawn_afterburner(commands: &mut Commands, registry: &PrefabRegistry, f16_entity: Entity) {
+    let overrides = PrefabOverrides::default();
+    match PrefabFactory::spawn_by_name(commands, registry, AFTERBURNER_PREFAB, overrides) {
+        Ok(entity) => {
+            commands.entity(entity).insert((
+                Name::new("afterburner_particles"),
+                EmitterControl {
+                    active: false,
+                    ..default()
+                },
+                AfterburnerFlame,
+                AfterburnerFlameOf(f16_entity),
+                ChildOf(f16_entity),
+                // Position at exhaust nozzle exit (aligned with new detailed nozzle)
+                Transform::from_xyz(0.0, 0.0, 11.0),
+                VisibilityRange::abrupt(0.0, 2000.0),
+            ));
+        }
+        Err(e) => warn!("Afterburner flame: {}", e),
+    }
 }
 
 /// Spawns afterburner particle effects for newly created F16s.
@@ -127,25 +74,10 @@ pub fn create_afterburner_flame_effect(effects: &mut Assets<EffectAsset>) -> Han
 pub fn spawn_afterburner_particles(
     mut commands: Commands,
     f16_query: Query<Entity, (With<F16>, Without<Children>)>,
-    afterburner_effect: Res<AfterburnerFlameEffect>,
+    registry: Res<PrefabRegistry>,
 ) {
     for f16_entity in f16_query.iter() {
-        commands.spawn((
-            Name::new("afterburner_particles"),
-            ParticleEffect::new(afterburner_effect.handle.clone()),
-            {
-                let mut spawner = EffectSpawner::new(&SpawnerSettings::rate(2000.0.into()));
-                spawner.active = false;
-                spawner
-            },
-            AfterburnerFlame,
-            AfterburnerFlameOf(f16_entity),
-            ChildOf(f16_entity),
-            // Position at exhaust nozzle exit (aligned with new detailed nozzle)
-            Transform::from_xyz(0.0, 0.0, 11.0),
-            VisibilityRange::abrupt(0.0, 2000.0),
-            InheritedVisibility::default(),
-        ));
+        spawn_afterburner(&mut commands, &registry, f16_entity);
     }
 }
 
@@ -157,7 +89,7 @@ pub fn update_afterburner_position_and_intensity(
     f16_exists: F16ExistsQuery,
     mut commands: Commands,
 ) {
-    for (particle_entity, afterburner_of, mut particle_transform, mut spawner) in
+    for (particle_entity, afterburner_of, mut particle_transform, mut control) in
         particle_query.iter_mut()
     {
         let f16_entity = afterburner_of.0;
@@ -172,13 +104,13 @@ pub fn update_afterburner_position_and_intensity(
         let Ok((_f16_transform, _velocity, flight, _control, _specs, active)) =
             f16_query.get(f16_entity)
         else {
-            spawner.active = false;
+            control.active = false;
             continue;
         };
 
         // Turn off particles immediately if not the active entity
         if active.is_none() {
-            spawner.active = false;
+            control.active = false;
             continue;
         }
 
@@ -189,15 +121,14 @@ pub fn update_afterburner_position_and_intensity(
 
         // Early exit if flames should be off
         if flame_intensity < 0.1 {
-            spawner.active = false;
+            control.active = false;
             continue;
         }
 
         // Modulate spawn rate based on intensity
-        // Base: 2000 particles/sec, afterburner: up to 4000 particles/sec
-        let spawn_rate = 2000.0 + (flame_intensity * 2000.0);
-        *spawner = EffectSpawner::new(&SpawnerSettings::rate(spawn_rate.into()));
-        spawner.active = true;
+        // Base: 2000 particles/sec, afterburner: up to the prefab's 4000 particles/sec
+        control.intensity = 0.5 + flame_intensity * 0.5;
+        control.active = true;
 
         // Update position to follow F16 (particles are already in local space)
         // Transform is already relative to F16 parent, just ensure proper rotation
@@ -211,28 +142,14 @@ pub fn ensure_afterburner_for_existing_f16s(
     mut commands: Commands,
     f16_query: Query<Entity, With<F16>>,
     afterburner_query: Query<&AfterburnerFlameOf>,
-    afterburner_effect: Res<AfterburnerFlameEffect>,
+    registry: Res<PrefabRegistry>,
 ) {
     let f16s_with_afterburners: std::collections::HashSet<Entity> =
         afterburner_query.iter().map(|af| af.0).collect();
 
     for f16_entity in f16_query.iter() {
         if !f16s_with_afterburners.contains(&f16_entity) {
-            commands.spawn((
-                Name::new("afterburner_particles"),
-                ParticleEffect::new(afterburner_effect.handle.clone()),
-                {
-                    let mut spawner = EffectSpawner::new(&SpawnerSettings::rate(2000.0.into()));
-                    spawner.active = false;
-                    spawner
-                },
-                AfterburnerFlame,
-                AfterburnerFlameOf(f16_entity),
-                ChildOf(f16_entity),
-                Transform::from_xyz(0.0, 0.0, 11.0),
-                VisibilityRange::abrupt(0.0, 2000.0),
-                InheritedVisibility::default(),
-            ));
+            spawn_afterburner(&mut commands, &registry, f16_entity);
         }
     }
 }
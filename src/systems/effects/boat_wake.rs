@@ -1,8 +1,37 @@
 use crate::components::control_state::ControlState;
-use crate::components::{ActiveEntity, PropellerHub, Yacht};
+use crate::components::unified_water::UnifiedWaterBody;
+use crate::components::{PropellerHub, Yacht};
 use bevy::prelude::*;
 use bevy::render::view::visibility::VisibilityRange;
 use bevy_hanabi::prelude::*;
+use bevy_rapier3d::prelude::{Collider, Velocity};
+use std::collections::{HashMap, VecDeque};
+
+/// Wake particles spawned per second per m/s of hull speed, and the range
+/// the rate is held to
+const WAKE_RATE_PER_SPEED: f32 = 8.0;
+const WAKE_MIN_RATE: f32 = 20.0;
+const WAKE_MAX_RATE: f32 = 240.0;
+/// Rate changes are rounded to this step so the spawner isn't reset every frame
+const WAKE_RATE_STEP: f32 = 20.0;
+/// Hulls slower than this (m/s) leave no wake unless under power
+const WAKE_MIN_SPEED: f32 = 1.5;
+
+/// Foam patches kept on the water at once; the oldest go first
+const MAX_WAKE_FOAM: usize = 240;
+/// Hulls slower than this (m/s) leave no foam
+const FOAM_MIN_SPEED: f32 = 3.0;
+/// Distance a hull travels between foam patches (m)
+const FOAM_SPACING: f32 = 6.0;
+const FOAM_LIFETIME: f32 = 8.0;
+/// Patches start this share of the hull's beam wide and spread to
+/// `FOAM_SPREAD` times that as the wake opens out
+const FOAM_START_WIDTH: f32 = 0.8;
+const FOAM_SPREAD: f32 = 3.0;
+/// Height above the wave surface, enough to avoid z-fighting
+const FOAM_LIFT: f32 = 0.08;
+/// Opacity steps a patch fades through
+const FOAM_FADE_STEPS: usize = 4;
 
 /// Resource that caches the boat wake effect handle.
 #[derive(Resource)]
@@ -110,15 +139,25 @@ pub fn spawn_boat_wake_particles(
     }
 }
 
-/// Updates boat wake position and intensity based on throttle and propeller position.
+/// Wake spawn rate for a hull moving at `speed`, rounded to `WAKE_RATE_STEP`
+pub fn wake_spawn_rate(speed: f32) -> f32 {
+    let rate = (speed * WAKE_RATE_PER_SPEED).clamp(WAKE_MIN_RATE, WAKE_MAX_RATE);
+    (rate / WAKE_RATE_STEP).round() * WAKE_RATE_STEP
+}
+
+/// Updates boat wake position and intensity from hull speed, throttle and
+/// propeller position. Coasting boats keep trailing a wake until they slow.
 #[allow(clippy::type_complexity)]
 pub fn update_boat_wake_intensity(
     mut particle_query: Query<(&mut EffectSpawner, &mut Transform, &BoatWakeOf)>,
-    yacht_query: Query<(&GlobalTransform, &ControlState, &Children), (With<Yacht>, With<ActiveEntity>)>,
+    yacht_query: Query<
+        (&GlobalTransform, &Velocity, Option<&ControlState>, &Children),
+        With<Yacht>,
+    >,
     prop_query: Query<&GlobalTransform, With<PropellerHub>>,
 ) {
     for (mut spawner, mut particle_transform, wake_of) in particle_query.iter_mut() {
-        if let Ok((yacht_transform, controls, children)) = yacht_query.get(wake_of.0) {
+        if let Ok((yacht_transform, velocity, controls, children)) = yacht_query.get(wake_of.0) {
             // Find propeller position
             let mut prop_pos = yacht_transform.translation(); // Fallback to yacht center
             let mut found_prop = false;
@@ -153,10 +192,17 @@ pub fn update_boat_wake_intensity(
             // The particles spawn and stay (mostly) where they were, creating a trail.
             particle_transform.rotation = yacht_transform.compute_transform().rotation;
 
-            // Intensity based on throttle
-            let throttle_effort = (controls.throttle - controls.brake).abs();
-            
-            spawner.active = throttle_effort > 0.1;
+            // Intensity based on speed through the water, or throttle while
+            // the propeller churns at low speed
+            let throttle_effort =
+                controls.map_or(0.0, |controls| (controls.throttle - controls.brake).abs());
+            let speed = Vec2::new(velocity.linvel.x, velocity.linvel.z).length();
+
+            spawner.active = throttle_effort > 0.1 || speed > WAKE_MIN_SPEED;
+            let settings = SpawnerSettings::rate(wake_spawn_rate(speed).into());
+            if spawner.active && spawner.settings != settings {
+                spawner.settings = settings;
+            }
 
         } else {
             spawner.active = false;
@@ -177,3 +223,154 @@ pub fn cleanup_boat_wake_on_despawn(
         }
     }
 }
+
+/// A patch of foam left on the water behind a moving hull
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WakeFoam {
+    pub born: f32,
+    /// Width the patch started at (m)
+    pub width: f32,
+    /// Length along the hull's track (m)
+    pub length: f32,
+}
+
+/// Live foam patches, oldest first, with the time each one expires; where
+/// each hull last dropped one; and the shared mesh and fade materials
+#[derive(Default)]
+pub struct WakeFoamTrail {
+    patches: VecDeque<(Entity, f32)>,
+    last_drop: HashMap<Entity, Vec3>,
+    assets: Option<(Handle<Mesh>, Vec<Handle<StandardMaterial>>)>,
+}
+
+/// Opacity step of a foam patch `age` seconds old
+pub fn foam_fade_step(age: f32) -> usize {
+    ((age / FOAM_LIFETIME).clamp(0.0, 1.0) * FOAM_FADE_STEPS as f32) as usize
+}
+
+/// Lays foam patches on the water behind moving yachts, spreads and fades
+/// them out and keeps them riding the waves
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_wake_foam(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut trail: Local<WakeFoamTrail>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    water_regions: Query<&UnifiedWaterBody>,
+    yachts: Query<(Entity, &GlobalTransform, &Velocity, Option<&Collider>), With<Yacht>>,
+    mut foam: Query<(
+        &WakeFoam,
+        &mut Transform,
+        &mut MeshMaterial3d<StandardMaterial>,
+    )>,
+) {
+    let now = time.elapsed_secs();
+    let surface = |position: Vec3| {
+        water_regions
+            .iter()
+            .find(|water| water.contains_point(position.x, position.z))
+            .map(|water| water.sample_surface_height(position.x, position.z, now))
+    };
+
+    let (mesh, fade) = trail
+        .assets
+        .get_or_insert_with(|| {
+            let fade = (0..FOAM_FADE_STEPS)
+                .map(|step| {
+                    let alpha = 0.55 * (1.0 - step as f32 / FOAM_FADE_STEPS as f32);
+                    materials.add(StandardMaterial {
+                        base_color: Color::srgba(0.95, 0.97, 1.0, alpha),
+                        alpha_mode: bevy::prelude::AlphaMode::Blend,
+                        unlit: true,
+                        ..default()
+                    })
+                })
+                .collect();
+            (meshes.add(Plane3d::default().mesh().size(1.0, 1.0)), fade)
+        })
+        .clone();
+
+    // Drop new patches behind hulls moving through the water
+    for (yacht, transform, velocity, collider) in &yachts {
+        let speed = Vec2::new(velocity.linvel.x, velocity.linvel.z).length();
+        let half = collider
+            .and_then(|collider| collider.as_cuboid())
+            .map_or(Vec3::new(5.0, 2.0, 15.0), |cuboid| cuboid.half_extents());
+        let stern = transform.transform_point(Vec3::new(0.0, 0.0, half.z));
+        let Some(height) = surface(stern).filter(|_| speed >= FOAM_MIN_SPEED) else {
+            trail.last_drop.remove(&yacht);
+            continue;
+        };
+        let last = *trail.last_drop.entry(yacht).or_insert(stern);
+        if last.xz().distance(stern.xz()) < FOAM_SPACING {
+            continue;
+        }
+        trail.last_drop.insert(yacht, stern);
+
+        let heading = Vec3::new(transform.forward().x, 0.0, transform.forward().z);
+        let patch = WakeFoam {
+            born: now,
+            width: half.x * 2.0 * FOAM_START_WIDTH,
+            length: FOAM_SPACING * 1.4,
+        };
+        let entity = commands
+            .spawn((
+                Name::new("Wake foam"),
+                patch,
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(fade[0].clone()),
+                Transform::from_translation(Vec3::new(stern.x, height + FOAM_LIFT, stern.z))
+                    .looking_to(heading.normalize_or(Vec3::NEG_Z), Vec3::Y)
+                    .with_scale(Vec3::new(patch.width, 1.0, patch.length)),
+            ))
+            .id();
+        trail.patches.push_back((entity, now + FOAM_LIFETIME));
+    }
+    trail.last_drop.retain(|yacht, _| yachts.contains(*yacht));
+
+    // Age, spread and fade the patches; the oldest go once expired or over the cap
+    while let Some(&(oldest, expires)) = trail.patches.front() {
+        if expires > now && trail.patches.len() <= MAX_WAKE_FOAM {
+            break;
+        }
+        commands.entity(oldest).try_despawn();
+        trail.patches.pop_front();
+    }
+    for (patch, mut transform, mut material) in &mut foam {
+        let age = now - patch.born;
+        let spread = 1.0 + (FOAM_SPREAD - 1.0) * (age / FOAM_LIFETIME).clamp(0.0, 1.0);
+        transform.scale.x = patch.width * spread;
+        if let Some(height) = surface(transform.translation) {
+            transform.translation.y = height + FOAM_LIFT;
+        }
+        let step = &fade[foam_fade_step(age).min(FOAM_FADE_STEPS - 1)];
+        if material.0 != *step {
+            material.0 = step.clone();
+        }
+    }
+}
+
+pub fn cleanup_wake_foam(mut commands: Commands, foam: Query<Entity, With<WakeFoam>>) {
+    for patch in &foam {
+        commands.entity(patch).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wake_rate_and_foam_fade() {
+        assert_eq!(wake_spawn_rate(0.0), WAKE_MIN_RATE);
+        assert_eq!(wake_spawn_rate(10.0), 80.0);
+        assert_eq!(wake_spawn_rate(100.0), WAKE_MAX_RATE);
+        // Small speed changes don't change the rate
+        assert_eq!(wake_spawn_rate(10.4), wake_spawn_rate(10.0));
+
+        assert_eq!(foam_fade_step(0.0), 0);
+        assert_eq!(foam_fade_step(FOAM_LIFETIME * 0.5), FOAM_FADE_STEPS / 2);
+        assert_eq!(foam_fade_step(FOAM_LIFETIME * 2.0), FOAM_FADE_STEPS);
+    }
+}
@@ -3,26 +3,33 @@ pub mod beacon_effects;
 pub mod boat_wake;
 pub mod jet_flames;
 pub mod navigation_lights;
+pub mod particles;
+pub mod rain;
 pub mod rotor_blur;
 pub mod rotor_wash;
 
 pub use afterburner::{
-    AfterburnerFlame, AfterburnerFlameEffect, AfterburnerFlameOf,
-    cleanup_afterburner_on_f16_despawn, cleanup_afterburner_particle_entities,
-    create_afterburner_flame_effect, ensure_afterburner_for_existing_f16s,
+    AfterburnerFlame, AfterburnerFlameOf, cleanup_afterburner_on_f16_despawn,
+    cleanup_afterburner_particle_entities, ensure_afterburner_for_existing_f16s,
     spawn_afterburner_particles, update_afterburner_position_and_intensity,
 };
 pub use beacon_effects::*;
 pub use boat_wake::{
-    BoatWakeEffect, BoatWakeOf, cleanup_boat_wake_on_despawn, create_boat_wake_effect,
-    spawn_boat_wake_particles, update_boat_wake_intensity,
+    BoatWakeEffect, BoatWakeOf, WakeFoam, cleanup_boat_wake_on_despawn, cleanup_wake_foam,
+    create_boat_wake_effect, spawn_boat_wake_particles, update_boat_wake_intensity,
+    update_wake_foam,
 };
 pub use jet_flames::*;
 pub use navigation_lights::{update_landing_lights, update_navigation_lights};
+pub use particles::{
+    CpuParticleMaterial, CpuParticles, GpuParticleEffects, ParticleEmitterPool, PooledEmitter,
+    SpawnParticleBurst,
+};
+pub use rain::{RAIN_EMITTER_HEIGHT, RainEffect, create_rain_effect, spawn_rain_emitter};
 pub use rotor_blur::*;
 pub use rotor_wash::{
-    RotorWashEffect, RotorWashOf, cleanup_rotor_wash_on_helicopter_despawn,
-    cleanup_rotor_wash_particle_entities, create_rotor_wash_effect,
-    ensure_rotor_wash_for_existing_helicopters, spawn_rotor_wash_particles,
-    update_rotor_wash_position_and_intensity,
+    RotorWashEffect, RotorWashOf, RotorWashSurface, cleanup_rotor_wash_on_helicopter_despawn,
+    cleanup_rotor_wash_particle_entities, create_rotor_spray_effect, create_rotor_wash_effect,
+    ensure_rotor_wash_for_existing_helicopters, rotor_wash_intensity, rotor_wash_push_system,
+    spawn_rotor_wash_particles, update_rotor_wash_position_and_intensity,
 };
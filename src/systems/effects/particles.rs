@@ -0,0 +1,708 @@
+//! Prefab-defined particle emitters.
+//!
+//! A `ParticleEmitter` describes what to emit; which simulation runs it is
+//! decided per emitter. On the GPU path the definition is compiled into a
+//! `bevy_hanabi` effect, which spawns and integrates particles in compute
+//! shaders; identical definitions share one `EffectAsset`. The CPU fallback
+//! integrates particles in a system and draws each emitter as one
+//! camera-facing quad mesh, for the Low preset and for emitters pinned to
+//! `Cpu`. Both honour the same rate, lifetime, velocity, drag, color and size
+//! curves; `speed_over_life` only exists on the CPU, where hanabi has no
+//! equivalent, so GPU emitters should lean on `drag` instead.
+//!
+//! One-shot effects such as explosion sparks go through
+//! `SpawnParticleBurst`, which reuses finished emitters of the same prefab
+//! instead of spawning a new entity per burst.
+
+use crate::components::gameplay_events::VehicleDestroyed;
+use crate::components::{EmitterControl, MainCamera, ParticleEmitter};
+use crate::config::{GameConfig, PerformanceConfig};
+use crate::factories::{PrefabFactory, PrefabOverrides, PrefabRegistry};
+use bevy::asset::RenderAssetUsages;
+use bevy::math::Affine3A;
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::view::NoFrustumCulling;
+use bevy_hanabi::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Prefab fired where a vehicle is destroyed
+const DESTRUCTION_BURST_PREFAB: &str = "explosion_sparks";
+
+/// Side of the soft round sprite used by CPU particles
+const SPRITE_SIZE: u32 = 32;
+
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    position: Vec3,
+    velocity: Vec3,
+    age: f32,
+    lifetime: f32,
+}
+
+/// Live particles of an emitter on the CPU path, in world space or, for
+/// `local_space` emitters, in the emitter's frame
+#[derive(Component, Debug, Default)]
+pub struct CpuParticles {
+    particles: Vec<Particle>,
+    spawn_debt: f32,
+    /// Particles in the mesh as last built, so empty emitters skip rebuilding
+    drawn: usize,
+}
+
+impl CpuParticles {
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Spawns up to `count` particles, capped by the emitter's capacity;
+    /// `frame` maps the emitter's frame into simulation space
+    pub fn emit(
+        &mut self,
+        emitter: &ParticleEmitter,
+        count: u32,
+        frame: Affine3A,
+        rng: &mut impl Rng,
+    ) {
+        let room = (emitter.capacity as usize).saturating_sub(self.particles.len());
+        let spread = emitter.velocity_spread;
+        for _ in 0..(count as usize).min(room) {
+            let offset = loop {
+                let p = Vec3::new(
+                    rng.gen_range(-1.0..=1.0),
+                    rng.gen_range(-1.0..=1.0),
+                    rng.gen_range(-1.0..=1.0),
+                );
+                if p.length_squared() <= 1.0 {
+                    break p * emitter.spawn_radius;
+                }
+            };
+            let jitter = if spread > 0.0 {
+                Vec3::new(
+                    rng.gen_range(-spread..=spread),
+                    rng.gen_range(-spread..=spread),
+                    rng.gen_range(-spread..=spread),
+                )
+            } else {
+                Vec3::ZERO
+            };
+            let (min, max) = emitter.lifetime;
+            self.particles.push(Particle {
+                position: frame.transform_point3(offset),
+                velocity: frame.transform_vector3(emitter.velocity + jitter),
+                age: 0.0,
+                lifetime: if max > min {
+                    rng.gen_range(min..max)
+                } else {
+                    min
+                },
+            });
+        }
+    }
+
+    /// Ages, drops and moves the particles
+    pub fn step(&mut self, emitter: &ParticleEmitter, delta: f32) {
+        let damping = (1.0 - emitter.drag * delta).max(0.0);
+        self.particles.retain_mut(|particle| {
+            particle.age += delta;
+            if particle.age >= particle.lifetime {
+                return false;
+            }
+            particle.velocity = (particle.velocity + emitter.acceleration * delta) * damping;
+            let speed = emitter
+                .speed_over_life
+                .sample(particle.age / particle.lifetime);
+            particle.position += particle.velocity * speed * delta;
+            true
+        });
+    }
+
+    /// Writes the particles as quads into `mesh`, in the space `to_mesh` maps
+    /// simulation space into; `right`, `up` and `back` are camera axes already
+    /// in that space
+    pub fn write_mesh(
+        &mut self,
+        emitter: &ParticleEmitter,
+        to_mesh: Affine3A,
+        [right, up, back]: [Vec3; 3],
+        mesh: &mut Mesh,
+    ) {
+        let count = self.particles.len();
+        let mut positions = Vec::with_capacity(count * 4);
+        let mut uvs = Vec::with_capacity(count * 4);
+        let mut colors = Vec::with_capacity(count * 4);
+        let mut indices = Vec::with_capacity(count * 6);
+        for particle in &self.particles {
+            let t = particle.age / particle.lifetime;
+            let size = emitter.size_over_life.sample(t) * 0.5;
+            let center = to_mesh.transform_point3(particle.position);
+            let (axis_x, axis_y) = if emitter.align_to_velocity {
+                let along = to_mesh
+                    .transform_vector3(particle.velocity)
+                    .normalize_or(right);
+                (along, back.cross(along).normalize_or(up))
+            } else {
+                (right, up)
+            };
+            let (x, y) = (axis_x * size.x, axis_y * size.y);
+            let base = positions.len() as u32;
+            positions.extend(
+                [
+                    center - x - y,
+                    center + x - y,
+                    center + x + y,
+                    center - x + y,
+                ]
+                .map(|corner| corner.to_array()),
+            );
+            uvs.extend([[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]);
+            colors.extend([emitter.color_over_life.sample(t).to_array(); 4]);
+            indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![back.to_array(); positions.len()],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        mesh.insert_indices(Indices::U32(indices));
+        self.drawn = count;
+    }
+}
+
+/// Spawn rate last handed to an emitter's `EffectSpawner`
+#[derive(Component, Debug, Default)]
+pub struct GpuEmitter {
+    rate: f32,
+}
+
+/// Compiled hanabi effects keyed by the emitter definition they came from
+#[derive(Resource, Default)]
+pub struct GpuParticleEffects {
+    effects: HashMap<String, Handle<EffectAsset>>,
+}
+
+impl GpuParticleEffects {
+    pub fn handle(
+        &mut self,
+        emitter: &ParticleEmitter,
+        assets: &mut Assets<EffectAsset>,
+    ) -> Handle<EffectAsset> {
+        let key = ron::to_string(emitter).unwrap_or_default();
+        self.effects
+            .entry(key)
+            .or_insert_with(|| assets.add(gpu_effect(emitter)))
+            .clone()
+    }
+}
+
+/// Material shared by every CPU emitter: a soft round sprite tinted by the
+/// vertex colors
+#[derive(Resource)]
+pub struct CpuParticleMaterial(pub Handle<StandardMaterial>);
+
+impl FromWorld for CpuParticleMaterial {
+    fn from_world(world: &mut World) -> Self {
+        let sprite = world.resource_mut::<Assets<Image>>().add(particle_sprite());
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color_texture: Some(sprite),
+                alpha_mode: bevy::prelude::AlphaMode::Blend,
+                unlit: true,
+                cull_mode: None,
+                ..default()
+            });
+        Self(material)
+    }
+}
+
+/// Finished one-shot emitters waiting to be reused, by prefab name
+#[derive(Resource, Debug, Default)]
+pub struct ParticleEmitterPool {
+    idle: HashMap<String, Vec<Entity>>,
+    /// Finished emitters kept per prefab; extras are despawned
+    limit: usize,
+}
+
+impl ParticleEmitterPool {
+    pub fn from_config(config: &PerformanceConfig) -> Self {
+        Self {
+            limit: config.particle_pool_size,
+            ..default()
+        }
+    }
+
+    pub fn acquire(&mut self, prefab: &str) -> Option<Entity> {
+        self.idle.get_mut(prefab)?.pop()
+    }
+
+    /// Returns `false` when the prefab's pool is full and the entity should go
+    pub fn release(&mut self, prefab: &str, entity: Entity) -> bool {
+        let idle = self.idle.entry(prefab.to_string()).or_default();
+        if idle.len() >= self.limit {
+            return false;
+        }
+        idle.push(entity);
+        true
+    }
+
+    pub fn idle_count(&self, prefab: &str) -> usize {
+        self.idle.get(prefab).map_or(0, Vec::len)
+    }
+
+    pub fn clear(&mut self) {
+        self.idle.clear();
+    }
+}
+
+/// One-shot emitter owned by the pool
+#[derive(Component, Debug)]
+pub struct PooledEmitter {
+    pub prefab: String,
+    age: f32,
+    idle: bool,
+}
+
+/// Fires a burst emitter prefab once at a point
+#[derive(Event, Debug, Clone)]
+pub struct SpawnParticleBurst {
+    pub prefab: String,
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+impl SpawnParticleBurst {
+    pub fn at(prefab: impl Into<String>, position: Vec3) -> Self {
+        Self {
+            prefab: prefab.into(),
+            position,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+/// Compiles an emitter definition into a hanabi effect
+pub fn gpu_effect(emitter: &ParticleEmitter) -> EffectAsset {
+    let writer = ExprWriter::new();
+
+    let init_age = SetAttributeModifier::new(Attribute::AGE, writer.lit(0.0).expr());
+    let (min, max) = emitter.lifetime;
+    let lifetime = writer.lit(min) + writer.rand(ScalarType::Float) * writer.lit(max - min);
+    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime.expr());
+    let init_pos = SetPositionSphereModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        radius: writer.lit(emitter.spawn_radius).expr(),
+        dimension: ShapeDimension::Volume,
+    };
+    let jitter = (writer.rand(VectorType::VEC3F) * writer.lit(2.0) - writer.lit(1.0))
+        * writer.lit(emitter.velocity_spread);
+    let velocity = writer.lit(emitter.velocity) + jitter;
+    let init_vel = SetAttributeModifier::new(Attribute::VELOCITY, velocity.expr());
+    let accel = AccelModifier::new(writer.lit(emitter.acceleration).expr());
+    let drag = LinearDragModifier::new(writer.lit(emitter.drag).expr());
+
+    let mut color = bevy_hanabi::Gradient::new();
+    for &(t, value) in &emitter.color_over_life.0 {
+        color.add_key(t, value);
+    }
+    let mut size = bevy_hanabi::Gradient::new();
+    for &(t, value) in &emitter.size_over_life.0 {
+        // X runs along the velocity when oriented to it
+        size.add_key(t, Vec3::new(value.x, value.y, value.y));
+    }
+    let orient = OrientModifier::new(if emitter.align_to_velocity {
+        OrientMode::AlongVelocity
+    } else {
+        OrientMode::FaceCameraPosition
+    });
+
+    let spawner = if emitter.is_burst() {
+        SpawnerSettings::once((emitter.burst as f32).into())
+    } else {
+        SpawnerSettings::rate(emitter.rate.into())
+    };
+    let space = if emitter.local_space {
+        SimulationSpace::Local
+    } else {
+        SimulationSpace::Global
+    };
+    EffectAsset::new(emitter.capacity.max(1), spawner, writer.finish())
+        .with_name("particle_emitter")
+        .with_simulation_space(space)
+        .init(init_pos)
+        .init(init_vel)
+        .init(init_age)
+        .init(init_lifetime)
+        .update(accel)
+        .update(drag)
+        .render(ColorOverLifetimeModifier::new(color))
+        .render(SizeOverLifetimeModifier {
+            gradient: size,
+            screen_space_size: false,
+        })
+        .render(orient)
+}
+
+fn spawner_for(emitter: &ParticleEmitter, rate: f32) -> EffectSpawner {
+    if emitter.is_burst() {
+        EffectSpawner::new(&SpawnerSettings::once((emitter.burst as f32).into()))
+    } else {
+        EffectSpawner::new(&SpawnerSettings::rate(rate.into()))
+    }
+}
+
+/// Soft white disc, opaque in the middle and clear at the rim
+pub fn particle_sprite() -> Image {
+    let mut data = Vec::with_capacity((SPRITE_SIZE * SPRITE_SIZE * 4) as usize);
+    for y in 0..SPRITE_SIZE {
+        for x in 0..SPRITE_SIZE {
+            let uv = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) / SPRITE_SIZE as f32;
+            let falloff = (1.0 - uv.distance(Vec2::splat(0.5)) * 2.0).clamp(0.0, 1.0);
+            data.extend([255, 255, 255, (falloff * falloff * 255.0) as u8]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: SPRITE_SIZE,
+            height: SPRITE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Puts new or edited emitters, and every emitter when the graphics preset
+/// changes, on the GPU or CPU path
+#[allow(clippy::type_complexity)]
+pub fn select_particle_backend(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut gpu_effects: ResMut<GpuParticleEffects>,
+    mut effect_assets: ResMut<Assets<EffectAsset>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    cpu_material: Res<CpuParticleMaterial>,
+    emitters: Query<(
+        Entity,
+        Ref<ParticleEmitter>,
+        &EmitterControl,
+        Has<GpuEmitter>,
+    )>,
+) {
+    let gpu_preferred = config.graphics.quality.settings().gpu_particles;
+    for (entity, emitter, control, on_gpu) in &emitters {
+        let wants_gpu = emitter.uses_gpu(gpu_preferred);
+        if !emitter.is_changed() && wants_gpu == on_gpu {
+            continue;
+        }
+        let mut entity = commands.entity(entity);
+        if wants_gpu {
+            let rate = emitter.rate * control.intensity;
+            entity
+                .remove::<(CpuParticles, Mesh3d, MeshMaterial3d<StandardMaterial>)>()
+                .insert((
+                    ParticleEffect::new(gpu_effects.handle(&emitter, &mut effect_assets)),
+                    spawner_for(&emitter, rate).with_active(false),
+                    GpuEmitter { rate },
+                ));
+        } else {
+            let mesh = Mesh::new(
+                PrimitiveTopology::TriangleList,
+                RenderAssetUsages::default(),
+            );
+            entity
+                .remove::<(
+                    ParticleEffect,
+                    CompiledParticleEffect,
+                    EffectSpawner,
+                    GpuEmitter,
+                )>()
+                .insert((
+                    CpuParticles::default(),
+                    Mesh3d(meshes.add(mesh)),
+                    MeshMaterial3d(cpu_material.0.clone()),
+                    // Particles drift away from where the mesh bounds were taken
+                    NoFrustumCulling,
+                    NotShadowCaster,
+                    NotShadowReceiver,
+                ));
+        }
+    }
+}
+
+/// Feeds `EmitterControl` into the hanabi spawners
+pub fn drive_gpu_emitters(
+    mut emitters: Query<(
+        &ParticleEmitter,
+        &mut EmitterControl,
+        &mut EffectSpawner,
+        &mut GpuEmitter,
+    )>,
+) {
+    for (emitter, mut control, mut spawner, mut gpu) in &mut emitters {
+        if emitter.is_burst() {
+            if control.active && control.pending_burst {
+                spawner.reset();
+                control.pending_burst = false;
+            }
+            spawner.active = control.active;
+            continue;
+        }
+        let rate = emitter.rate * control.intensity.max(0.0);
+        if (rate - gpu.rate).abs() > 1.0 {
+            *spawner = spawner_for(emitter, rate);
+            gpu.rate = rate;
+        }
+        spawner.active = control.active && rate > 0.0;
+    }
+}
+
+/// Spawns, integrates and redraws CPU emitters
+#[allow(clippy::type_complexity)]
+pub fn simulate_cpu_particles(
+    time: Res<Time>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut emitters: Query<(
+        &ParticleEmitter,
+        &mut EmitterControl,
+        &mut CpuParticles,
+        &GlobalTransform,
+        &Mesh3d,
+    )>,
+) {
+    let delta = time.delta_secs();
+    let camera = camera.single().ok();
+    let mut rng = rand::thread_rng();
+    for (emitter, mut control, mut particles, transform, mesh) in &mut emitters {
+        let frame = if emitter.local_space {
+            Affine3A::IDENTITY
+        } else {
+            transform.affine()
+        };
+        if control.active {
+            let count = if emitter.is_burst() {
+                if control.pending_burst {
+                    control.pending_burst = false;
+                    emitter.burst
+                } else {
+                    0
+                }
+            } else {
+                particles.spawn_debt += emitter.rate * control.intensity.max(0.0) * delta;
+                let count = particles.spawn_debt.floor();
+                particles.spawn_debt -= count;
+                count as u32
+            };
+            particles.emit(emitter, count, frame, &mut rng);
+        }
+        particles.step(emitter, delta);
+
+        let Some(camera) = camera else {
+            continue;
+        };
+        if particles.is_empty() && particles.drawn == 0 {
+            continue;
+        }
+        let Some(mesh) = meshes.get_mut(&mesh.0) else {
+            continue;
+        };
+        // The mesh hangs off the emitter, so everything is brought into its frame
+        let to_local = transform.affine().inverse();
+        let to_mesh = if emitter.local_space {
+            Affine3A::IDENTITY
+        } else {
+            to_local
+        };
+        let axes = [camera.right(), camera.up(), camera.back()]
+            .map(|axis| to_local.transform_vector3(*axis));
+        particles.write_mesh(emitter, to_mesh, axes, mesh);
+    }
+}
+
+/// Fires bursts from the pool, spawning the prefab when none is idle
+pub fn spawn_particle_bursts(
+    mut commands: Commands,
+    mut bursts: EventReader<SpawnParticleBurst>,
+    registry: Res<PrefabRegistry>,
+    mut pool: ResMut<ParticleEmitterPool>,
+    mut pooled: Query<(
+        &mut Transform,
+        &mut Visibility,
+        &mut EmitterControl,
+        &mut PooledEmitter,
+    )>,
+) {
+    for burst in bursts.read() {
+        if let Some(entity) = pool.acquire(&burst.prefab)
+            && let Ok((mut transform, mut visibility, mut control, mut emitter)) =
+                pooled.get_mut(entity)
+        {
+            transform.translation = burst.position;
+            transform.rotation = burst.rotation;
+            *visibility = Visibility::Inherited;
+            control.restart();
+            emitter.age = 0.0;
+            emitter.idle = false;
+            continue;
+        }
+        let overrides = PrefabOverrides::at(burst.position).with_rotation(burst.rotation);
+        match PrefabFactory::spawn_by_name(&mut commands, &registry, &burst.prefab, overrides) {
+            Ok(entity) => {
+                commands.entity(entity).insert(PooledEmitter {
+                    prefab: burst.prefab.clone(),
+                    age: 0.0,
+                    idle: false,
+                });
+            }
+            Err(e) => warn!("Particle burst '{}': {}", burst.prefab, e),
+        }
+    }
+}
+
+/// Hands pooled emitters back once their last particle has died
+pub fn recycle_pooled_emitters(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pool: ResMut<ParticleEmitterPool>,
+    mut pooled: Query<(
+        Entity,
+        &ParticleEmitter,
+        &mut EmitterControl,
+        &mut Visibility,
+        &mut PooledEmitter,
+    )>,
+) {
+    for (entity, emitter, mut control, mut visibility, mut pooled) in &mut pooled {
+        if pooled.idle {
+            continue;
+        }
+        pooled.age += time.delta_secs();
+        if pooled.age <= emitter.lifetime.1 {
+            continue;
+        }
+        control.active = false;
+        *visibility = Visibility::Hidden;
+        pooled.idle = true;
+        if !pool.release(&pooled.prefab, entity) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn burst_on_vehicle_destroyed(
+    mut destroyed: EventReader<VehicleDestroyed>,
+    mut bursts: EventWriter<SpawnParticleBurst>,
+) {
+    for event in destroyed.read() {
+        bursts.write(SpawnParticleBurst::at(
+            DESTRUCTION_BURST_PREFAB,
+            event.position,
+        ));
+    }
+}
+
+pub fn clear_particle_pool(
+    mut commands: Commands,
+    mut pool: ResMut<ParticleEmitterPool>,
+    pooled: Query<Entity, With<PooledEmitter>>,
+) {
+    for entity in &pooled {
+        commands.entity(entity).despawn();
+    }
+    pool.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Keyframes;
+
+    #[test]
+    fn test_keyframes_sample_linearly_and_hold_at_the_ends() {
+        let curve = Keyframes(vec![(0.25, 1.0), (0.75, 3.0), (1.0, 0.0)]);
+        assert_eq!(curve.sample(0.0), 1.0);
+        assert_eq!(curve.sample(0.5), 2.0);
+        assert_eq!(curve.sample(0.875), 1.5);
+        assert_eq!(curve.sample(2.0), 0.0);
+        assert_eq!(Keyframes::<f32>(Vec::new()).sample(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_cpu_particles_respect_capacity_and_expire() {
+        let emitter = ParticleEmitter {
+            lifetime: (1.0, 1.0),
+            velocity: Vec3::X,
+            velocity_spread: 0.0,
+            spawn_radius: 0.0,
+            capacity: 10,
+            speed_over_life: Keyframes(vec![(0.0, 1.0), (1.0, 3.0)]),
+            ..default()
+        };
+        let mut particles = CpuParticles::default();
+        let mut rng = rand::thread_rng();
+        let frame = Affine3A::from_translation(Vec3::Y);
+        particles.emit(&emitter, 25, frame, &mut rng);
+        assert_eq!(particles.len(), 10);
+
+        // Halfway through life the speed curve reads 2
+        particles.step(&emitter, 0.5);
+        let particle = particles.particles[0];
+        assert_eq!(particle.position, Vec3::new(1.0, 1.0, 0.0));
+
+        particles.step(&emitter, 0.6);
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn test_shipped_emitter_prefabs_parse() {
+        use crate::factories::prefab_factory::PrefabComponent;
+
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/prefabs");
+        let mut registry = PrefabRegistry::default();
+        registry.load_directory(&dir).unwrap();
+
+        for name in ["afterburner_flame", DESTRUCTION_BURST_PREFAB] {
+            let id = registry.id_of(name).expect(name);
+            let emitter = registry
+                .get(id)
+                .unwrap()
+                .components
+                .iter()
+                .find_map(|component| match component {
+                    PrefabComponent::Custom(kind, data) if kind == "ParticleEmitter" => {
+                        Some(ron::from_str::<ParticleEmitter>(data).unwrap())
+                    }
+                    _ => None,
+                });
+            assert!(emitter.is_some(), "{name} should carry an emitter");
+        }
+    }
+
+    #[test]
+    fn test_pool_reuses_and_caps_idle_emitters() {
+        let config = PerformanceConfig::default();
+        let limit = config.particle_pool_size;
+        let mut pool = ParticleEmitterPool::from_config(&config);
+        assert_eq!(pool.acquire("sparks"), None);
+        for index in 0..limit as u32 {
+            assert!(pool.release("sparks", Entity::from_raw(index)));
+        }
+        assert!(!pool.release("sparks", Entity::from_raw(99)));
+        assert_eq!(pool.idle_count("sparks"), limit);
+        assert!(pool.acquire("sparks").is_some());
+        assert_eq!(pool.idle_count("sparks"), limit - 1);
+        assert_eq!(pool.idle_count("smoke"), 0);
+    }
+}
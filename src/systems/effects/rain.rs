@@ -0,0 +1,83 @@
+use crate::components::RainEmitter;
+use bevy::prelude::*;
+use bevy_hanabi::prelude::*;
+
+/// Height above the active entity where rain drops spawn
+pub const RAIN_EMITTER_HEIGHT: f32 = 25.0;
+
+/// Resource that caches the rain effect handle.
+#[derive(Resource)]
+pub struct RainEffect {
+    pub handle: Handle<EffectAsset>,
+}
+
+/// Creates the rain streak particle effect asset.
+pub fn create_rain_effect(effects: &mut Assets<EffectAsset>) -> Handle<EffectAsset> {
+    let writer = ExprWriter::new();
+
+    let age = writer.lit(0.).expr();
+    let init_age = SetAttributeModifier::new(Attribute::AGE, age);
+
+    // Long enough to fall from the emitter to the ground
+    let lifetime = writer.lit(1.4).expr();
+    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
+
+    // Drops spawn across a wide disc above the camera
+    let init_pos = SetPositionCircleModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        axis: writer.lit(Vec3::Y).expr(),
+        radius: writer.lit(45.0).expr(),
+        dimension: ShapeDimension::Volume,
+    };
+
+    let base_vel = writer.lit(Vec3::new(0.0, -20.0, 0.0));
+    let jitter =
+        (writer.rand(VectorType::VEC3F) * writer.lit(2.0) - writer.lit(1.0)) * writer.lit(1.5);
+    let init_vel = SetAttributeModifier::new(Attribute::VELOCITY, (base_vel + jitter).expr());
+
+    let mut color_gradient = bevy_hanabi::Gradient::new();
+    color_gradient.add_key(0.0, Vec4::new(0.7, 0.75, 0.85, 0.0));
+    color_gradient.add_key(0.1, Vec4::new(0.7, 0.75, 0.85, 0.5));
+    color_gradient.add_key(1.0, Vec4::new(0.7, 0.75, 0.85, 0.4));
+
+    let mut size_gradient = bevy_hanabi::Gradient::new();
+    size_gradient.add_key(0.0, Vec3::new(0.03, 0.6, 0.03));
+    size_gradient.add_key(1.0, Vec3::new(0.03, 0.6, 0.03));
+
+    // Streaks stretched along their fall direction
+    let orient = OrientModifier::new(OrientMode::AlongVelocity);
+
+    let module = writer.finish();
+    let spawner = SpawnerSettings::rate(4000.0.into());
+
+    effects.add(
+        EffectAsset::new(8192, spawner, module)
+            .with_name("rain")
+            .with_simulation_space(SimulationSpace::Global)
+            .init(init_pos)
+            .init(init_vel)
+            .init(init_age)
+            .init(init_lifetime)
+            .render(ColorOverLifetimeModifier::new(color_gradient))
+            .render(SizeOverLifetimeModifier {
+                gradient: size_gradient,
+                screen_space_size: false,
+            })
+            .render(orient),
+    )
+}
+
+/// Spawns the single, initially inactive rain emitter.
+pub fn spawn_rain_emitter(mut commands: Commands, rain_effect: Res<RainEffect>) {
+    commands.spawn((
+        Name::new("rain_particles"),
+        ParticleEffect::new(rain_effect.handle.clone()),
+        {
+            let mut spawner = EffectSpawner::new(&SpawnerSettings::rate(4000.0.into()));
+            spawner.active = false;
+            spawner
+        },
+        Transform::default(),
+        RainEmitter,
+    ));
+}
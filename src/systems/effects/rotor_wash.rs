@@ -1,18 +1,25 @@
+use crate::components::unified_water::UnifiedWaterBody;
 use crate::components::{
-    ActiveEntity, ControlState, Helicopter, HelicopterRuntime, RotorWash, SimpleHelicopterSpecs,
-    SimpleHelicopterSpecsHandle,
+    ActiveEntity, ControlState, Helicopter, HelicopterRuntime, Pedestrian, PedestrianState,
+    RotorWash, SimpleHelicopterSpecs, SimpleHelicopterSpecsHandle, VehicleState,
 };
+use crate::config::GameConfig;
 use crate::constants::WorldEnvConfig;
+use crate::systems::spatial_index::SpatialIndex;
+use crate::systems::world::terrain_height::TerrainHeightService;
 use bevy::prelude::*;
 use bevy::render::view::visibility::VisibilityRange;
 use bevy_hanabi::prelude::*;
-use bevy_rapier3d::prelude::Velocity;
+use bevy_rapier3d::prelude::{ReadMassProperties, RigidBody, Velocity};
 
-/// Resource that caches the rotor wash effect handle.
+/// Resource that caches the rotor wash effect handles.
 /// Created once at startup and reused across all helicopters for optimal performance.
 #[derive(Resource)]
 pub struct RotorWashEffect {
+    /// Dust kicked up over land
     pub handle: Handle<EffectAsset>,
+    /// Spray kicked up over water
+    pub spray: Handle<EffectAsset>,
 }
 
 /// Component linking a rotor wash particle effect to its helicopter.
@@ -20,12 +27,26 @@ pub struct RotorWashEffect {
 #[derive(Component, Debug, Clone, Copy)]
 pub struct RotorWashOf(pub Entity);
 
+/// Surface a rotor wash emitter is meant for; each helicopter has one of each
+/// and only the one matching the surface below it spawns particles
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotorWashSurface {
+    Dust,
+    Water,
+}
+
+/// Fraction of upward kick added to the outward rotor wash push
+const ROTOR_WASH_LIFT: f32 = 0.2;
+/// Pedestrians run from wash at least this strong
+const ROTOR_WASH_SCARE_INTENSITY: f32 = 0.3;
+
 type ParticleTransformQuery<'w, 's> = Query<
     'w,
     's,
     (
         Entity,
         &'static RotorWashOf,
+        &'static RotorWashSurface,
         &'static mut Transform,
         &'static mut EffectSpawner,
     ),
@@ -48,6 +69,80 @@ type HelicopterStateQuery<'w, 's> = Query<
 
 type HelicopterExistsQuery<'w, 's> = Query<'w, 's, (), With<Helicopter>>;
 
+type WashingHelicopterQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static HelicopterRuntime,
+        &'static ControlState,
+        &'static SimpleHelicopterSpecsHandle,
+    ),
+    (With<Helicopter>, With<RotorWash>, With<ActiveEntity>),
+>;
+
+type WashedBodyQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static RigidBody,
+        &'static mut Velocity,
+        Option<&'static ReadMassProperties>,
+        Option<&'static mut Pedestrian>,
+    ),
+    (Without<Helicopter>, Without<VehicleState>),
+>;
+
+/// Rotor wash strength in `0..=rotor_wash_scale * 1.6`: rises with rotor RPM and
+/// collective input, and fades to zero at `rotor_wash_max_altitude` above the surface
+pub fn rotor_wash_intensity(
+    specs: &SimpleHelicopterSpecs,
+    runtime: &HelicopterRuntime,
+    control_state: &ControlState,
+    altitude: f32,
+) -> f32 {
+    // Calculate RPM effectiveness (matches movement system)
+    let rpm_eff = if runtime.rpm < specs.min_rpm_for_lift {
+        0.0
+    } else {
+        ((runtime.rpm - specs.min_rpm_for_lift) / (1.0 - specs.min_rpm_for_lift))
+            .clamp(0.0, 1.0)
+            .powf(specs.rpm_to_lift_exp)
+    };
+
+    // Calculate lift scalar from collective input
+    let collective_gain = 0.6;
+    let lift_scalar: f32 = 1.0 + collective_gain * control_state.vertical;
+
+    // Only near the surface
+    let altitude_gate = (1.0 - altitude.max(0.0) / specs.rotor_wash_max_altitude).max(0.0);
+
+    specs.rotor_wash_scale * rpm_eff * lift_scalar.max(0.0) * altitude_gate
+}
+
+/// Height of whatever the downwash hits below `position`: open water when the
+/// point is inside a water region above the ground, otherwise the ground
+fn wash_surface(
+    position: Vec3,
+    terrain: Option<&TerrainHeightService>,
+    env: &WorldEnvConfig,
+    water_regions: &Query<&UnifiedWaterBody>,
+    time: f32,
+) -> (f32, RotorWashSurface) {
+    let ground = terrain.map_or(env.land_elevation, |terrain| {
+        terrain.height_at(position.x, position.z)
+    });
+    water_regions
+        .iter()
+        .filter(|water| water.contains_point(position.x, position.z))
+        .map(|water| water.get_base_water_level(time))
+        .filter(|level| *level > ground)
+        .reduce(f32::max)
+        .map_or((ground, RotorWashSurface::Dust), |level| {
+            (level, RotorWashSurface::Water)
+        })
+}
+
 /// Creates the rotor wash particle effect asset.
 /// Called once at startup to initialize the cached effect handle.
 pub fn create_rotor_wash_effect(effects: &mut Assets<EffectAsset>) -> Handle<EffectAsset> {
@@ -120,25 +215,92 @@ pub fn create_rotor_wash_effect(effects: &mut Assets<EffectAsset>) -> Handle<Eff
     )
 }
 
-/// Spawns rotor wash particles for newly added helicopters.
-/// Uses Added<Helicopter> filter to only spawn once per helicopter.
-/// Clones the cached effect handle instead of creating a new effect asset.
-pub fn spawn_rotor_wash_particles(
-    mut commands: Commands,
-    helicopter_query: Query<Entity, Added<Helicopter>>,
-    rotor_wash_effect: Res<RotorWashEffect>,
-) {
-    for heli_entity in helicopter_query.iter() {
-        #[cfg(feature = "debug-ui")]
-        info!(
-            "Spawning rotor wash particles for helicopter entity: {:?}",
-            heli_entity
-        );
+/// Creates the water spray variant of the rotor wash, used over open water.
+/// Droplets are thrown out low and fall back under gravity instead of drifting.
+pub fn create_rotor_spray_effect(effects: &mut Assets<EffectAsset>) -> Handle<EffectAsset> {
+    let mut color_gradient = bevy_hanabi::Gradient::new();
+    color_gradient.add_key(0.0, Vec4::new(0.92, 0.96, 1.0, 0.0));
+    color_gradient.add_key(0.1, Vec4::new(0.9, 0.95, 1.0, 0.35));
+    color_gradient.add_key(0.6, Vec4::new(0.85, 0.92, 0.98, 0.2));
+    color_gradient.add_key(1.0, Vec4::new(0.8, 0.9, 0.96, 0.0));
+
+    let mut size_gradient = bevy_hanabi::Gradient::new();
+    size_gradient.add_key(0.0, Vec3::splat(0.15));
+    size_gradient.add_key(0.3, Vec3::splat(0.6));
+    size_gradient.add_key(1.0, Vec3::splat(0.9));
+
+    let writer = ExprWriter::new();
+
+    let age = writer.lit(0.).expr();
+    let init_age = SetAttributeModifier::new(Attribute::AGE, age);
+
+    let base_lifetime = writer.lit(1.2);
+    let lifetime_jitter = writer.rand(ScalarType::Float) * writer.lit(0.6);
+    let lifetime = (base_lifetime + lifetime_jitter).expr();
+    let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
+
+    let min_radius = writer.lit(3.0);
+    let radius_range = writer.lit(5.0);
+    let random_radius = min_radius + writer.rand(ScalarType::Float) * radius_range;
+
+    let init_pos = SetPositionCircleModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        axis: writer.lit(Vec3::Y).expr(),
+        radius: random_radius.expr(),
+        dimension: ShapeDimension::Surface,
+    };
+
+    let base_speed = writer.lit(6.0);
+    let random_factor = writer.rand(ScalarType::Float) * writer.lit(4.0);
+    let speed = (base_speed + random_factor).expr();
+
+    let init_vel = SetVelocityCircleModifier {
+        center: writer.lit(Vec3::new(0.0, -1.5, 0.0)).expr(),
+        axis: writer.lit(Vec3::Y).expr(),
+        speed,
+    };
+
+    let accel = writer.lit(Vec3::new(0.0, -6.0, 0.0)).expr();
+    let update_accel = AccelModifier::new(accel);
+
+    let drag = writer.lit(1.5).expr();
+    let update_drag = LinearDragModifier::new(drag);
+
+    let module = writer.finish();
+    let spawner = SpawnerSettings::rate(1200.0.into());
+
+    effects.add(
+        EffectAsset::new(16000, spawner, module)
+            .with_name("rotor_spray")
+            .with_simulation_space(SimulationSpace::Global)
+            .init(init_pos)
+            .init(init_vel)
+            .init(init_age)
+            .init(init_lifetime)
+            .update(update_accel)
+            .update(update_drag)
+            .render(ColorOverLifetimeModifier::new(color_gradient))
+            .render(SizeOverLifetimeModifier {
+                gradient: size_gradient,
+                screen_space_size: false,
+            }),
+    )
+}
 
-        // Spawn as separate entity (not child) to use world coordinates for ground contact
+/// Spawns the dust and spray emitters for one helicopter.
+/// Spawned as separate entities (not children) to use world coordinates for surface contact.
+fn spawn_rotor_wash_emitters(
+    commands: &mut Commands,
+    rotor_wash_effect: &RotorWashEffect,
+    heli_entity: Entity,
+) {
+    for (surface, handle) in [
+        (RotorWashSurface::Dust, &rotor_wash_effect.handle),
+        (RotorWashSurface::Water, &rotor_wash_effect.spray),
+    ] {
         commands.spawn((
             Name::new("rotor_wash_particles"),
-            ParticleEffect::new(rotor_wash_effect.handle.clone()),
+            ParticleEffect::new(handle.clone()),
             {
                 let mut spawner = EffectSpawner::new(&SpawnerSettings::rate(1200.0.into()));
                 spawner.active = false;
@@ -147,6 +309,7 @@ pub fn spawn_rotor_wash_particles(
             Transform::from_xyz(0.0, 0.0, 0.0),
             RotorWash,
             RotorWashOf(heli_entity),
+            surface,
             VisibilityRange {
                 start_margin: 0.0..0.0,
                 end_margin: 900.0..1100.0,
@@ -156,15 +319,38 @@ pub fn spawn_rotor_wash_particles(
     }
 }
 
+/// Spawns rotor wash particles for newly added helicopters.
+/// Uses Added<Helicopter> filter to only spawn once per helicopter.
+/// Clones the cached effect handle instead of creating a new effect asset.
+pub fn spawn_rotor_wash_particles(
+    mut commands: Commands,
+    helicopter_query: Query<Entity, Added<Helicopter>>,
+    rotor_wash_effect: Res<RotorWashEffect>,
+) {
+    for heli_entity in helicopter_query.iter() {
+        #[cfg(feature = "debug-ui")]
+        info!(
+            "Spawning rotor wash particles for helicopter entity: {:?}",
+            heli_entity
+        );
+
+        spawn_rotor_wash_emitters(&mut commands, &rotor_wash_effect, heli_entity);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn update_rotor_wash_position_and_intensity(
     mut commands: Commands,
     helicopter_query: HelicopterStateQuery,
     helicopter_exists: HelicopterExistsQuery,
     mut particle_query: ParticleTransformQuery,
+    water_regions: Query<&UnifiedWaterBody>,
     env: Res<WorldEnvConfig>,
+    terrain: Option<Res<TerrainHeightService>>,
+    time: Res<Time>,
     heli_specs_assets: Res<Assets<SimpleHelicopterSpecs>>,
 ) {
-    for (rotor_wash_entity, rotor_wash_of, mut particle_transform, mut spawner) in
+    for (rotor_wash_entity, rotor_wash_of, emitter_surface, mut particle_transform, mut spawner) in
         particle_query.iter_mut()
     {
         let heli_entity = rotor_wash_of.0;
@@ -190,41 +376,22 @@ pub fn update_rotor_wash_position_and_intensity(
             };
 
             let heli_pos = helicopter_transform.translation;
-            let ground_height = env.land_elevation;
-            let altitude = (heli_pos.y - ground_height).max(0.0);
+            let (surface_height, surface) = wash_surface(
+                heli_pos,
+                terrain.as_deref(),
+                &env,
+                &water_regions,
+                time.elapsed_secs(),
+            );
+            let altitude = (heli_pos.y - surface_height).max(0.0);
 
-            // World coordinates - particles stay on ground
+            // World coordinates - particles stay on the surface
             particle_transform.translation.x = heli_pos.x;
             particle_transform.translation.z = heli_pos.z;
-            particle_transform.translation.y = ground_height + 0.1;
-
-            // Calculate RPM effectiveness (matches movement system)
-            let rpm_eff = if runtime.rpm < specs.min_rpm_for_lift {
-                0.0
-            } else {
-                ((runtime.rpm - specs.min_rpm_for_lift) / (1.0 - specs.min_rpm_for_lift))
-                    .clamp(0.0, 1.0)
-                    .powf(specs.rpm_to_lift_exp)
-            };
-
-            // Calculate lift scalar from collective input
-            let collective_gain = 0.6;
-            let lift_scalar: f32 = 1.0 + collective_gain * control_state.vertical;
-
-            // Calculate intensity using oracle's formula
-            let base_intensity = specs.rotor_wash_scale * rpm_eff * lift_scalar.max(0.0);
-
-            // Apply altitude gating (only show near ground)
-            let altitude_gate: f32 = if altitude < 10.0 {
-                (1.0 - (altitude / 10.0)).max(0.0)
-            } else {
-                0.0
-            };
-
-            let final_intensity = base_intensity * altitude_gate;
+            particle_transform.translation.y = surface_height + 0.1;
 
-            // Apply intensity to particle system - only below 10m altitude
-            spawner.active = altitude < 10.0 && final_intensity > 0.05;
+            let intensity = rotor_wash_intensity(specs, runtime, control_state, altitude);
+            spawner.active = *emitter_surface == surface && intensity > 0.05;
         } else {
             // Query failed - could be temporary (missing ControlState during exit)
             // or permanent (helicopter despawned). Turn off spawning first.
@@ -239,6 +406,74 @@ pub fn update_rotor_wash_position_and_intensity(
     }
 }
 
+/// Pushes light dynamic bodies and NPCs outward from under the active helicopter,
+/// scaled by rotor wash intensity and falling off towards the edge of the footprint.
+/// Walking pedestrians caught in strong wash run away from the helicopter.
+#[allow(clippy::too_many_arguments)]
+pub fn rotor_wash_push_system(
+    helicopters: WashingHelicopterQuery,
+    mut bodies: WashedBodyQuery,
+    spatial_index: Res<SpatialIndex>,
+    water_regions: Query<&UnifiedWaterBody>,
+    env: Res<WorldEnvConfig>,
+    terrain: Option<Res<TerrainHeightService>>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+    heli_specs_assets: Res<Assets<SimpleHelicopterSpecs>>,
+) {
+    let dt = time.delta_secs();
+    for (helicopter_transform, runtime, control_state, specs_handle) in &helicopters {
+        let Some(specs) = heli_specs_assets.get(&specs_handle.0) else {
+            continue;
+        };
+
+        let heli_pos = helicopter_transform.translation;
+        let (surface_height, _) = wash_surface(
+            heli_pos,
+            terrain.as_deref(),
+            &env,
+            &water_regions,
+            time.elapsed_secs(),
+        );
+        let intensity =
+            rotor_wash_intensity(specs, runtime, control_state, heli_pos.y - surface_height);
+        if intensity <= 0.05 {
+            continue;
+        }
+
+        let footprint_center = heli_pos.with_y(surface_height);
+        for entry in spatial_index.query_radius(footprint_center, specs.rotor_wash_radius) {
+            let Ok((rigid_body, mut velocity, mass, pedestrian)) = bodies.get_mut(entry.entity)
+            else {
+                continue;
+            };
+            if *rigid_body != RigidBody::Dynamic {
+                continue;
+            }
+            // Unknown mass (not yet computed) counts as light
+            if mass.is_some_and(|mass| mass.get().mass > specs.rotor_wash_max_mass) {
+                continue;
+            }
+
+            let offset = (entry.position - footprint_center).with_y(0.0);
+            let away = offset.normalize_or(Vec3::X);
+            let falloff = (1.0 - offset.length() / specs.rotor_wash_radius).max(0.0);
+            let push = specs.rotor_wash_push * falloff * intensity * dt;
+            velocity.linvel += (away + Vec3::Y * ROTOR_WASH_LIFT) * push;
+
+            if let Some(mut pedestrian) = pedestrian
+                && intensity * falloff >= ROTOR_WASH_SCARE_INTENSITY
+                && matches!(
+                    pedestrian.state,
+                    PedestrianState::Wander | PedestrianState::Converse
+                )
+            {
+                pedestrian.enter(PedestrianState::Flee, config.npc.flee_duration, away);
+            }
+        }
+    }
+}
+
 pub fn cleanup_rotor_wash_on_helicopter_despawn(
     mut commands: Commands,
     mut removed_helicopters: RemovedComponents<Helicopter>,
@@ -288,23 +523,41 @@ pub fn ensure_rotor_wash_for_existing_helicopters(
                 heli_entity
             );
 
-            commands.spawn((
-                Name::new("rotor_wash_particles"),
-                ParticleEffect::new(rotor_wash_effect.handle.clone()),
-                {
-                    let mut spawner = EffectSpawner::new(&SpawnerSettings::rate(1200.0.into()));
-                    spawner.active = false;
-                    spawner
-                },
-                Transform::from_xyz(0.0, 0.0, 0.0),
-                RotorWash,
-                RotorWashOf(heli_entity),
-                VisibilityRange {
-                    start_margin: 0.0..0.0,
-                    end_margin: 900.0..1100.0,
-                    use_aabb: false,
-                },
-            ));
+            spawn_rotor_wash_emitters(&mut commands, &rotor_wash_effect, heli_entity);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intensity_follows_collective_and_fades_with_altitude() {
+        let specs = SimpleHelicopterSpecs::default();
+        let runtime = HelicopterRuntime {
+            rpm: 1.0,
+            ..default()
+        };
+        let hover = ControlState::default();
+        let climb = ControlState {
+            vertical: 1.0,
+            ..default()
+        };
+
+        let low = rotor_wash_intensity(&specs, &runtime, &hover, 1.0);
+        assert!(low > 0.0);
+        assert!(rotor_wash_intensity(&specs, &runtime, &climb, 1.0) > low);
+        assert!(rotor_wash_intensity(&specs, &runtime, &hover, 5.0) < low);
+        assert_eq!(
+            rotor_wash_intensity(&specs, &runtime, &hover, specs.rotor_wash_max_altitude),
+            0.0
+        );
+
+        let idle = HelicopterRuntime {
+            rpm: 0.0,
+            ..default()
+        };
+        assert_eq!(rotor_wash_intensity(&specs, &idle, &climb, 0.0), 0.0);
+    }
+}
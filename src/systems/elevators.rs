@@ -0,0 +1,177 @@
+//! Elevators linking the floors of multi-storey interiors.
+//!
+//! The car is a kinematic platform driven along its shaft. Anyone standing on
+//! it becomes an `ElevatorRider` and is carried by the car's frame-to-frame
+//! motion, the same anchoring the yacht deck uses for a walking player.
+
+use crate::components::{Elevator, ElevatorRider, NPCState, Player};
+use crate::systems::yacht_exit::anchor_delta;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+/// Cruise speed of a car in m/s
+const ELEVATOR_MAX_SPEED: f32 = 2.5;
+/// Acceleration and braking in m/s²
+const ELEVATOR_ACCELERATION: f32 = 1.5;
+/// Riders count as aboard up to this far above the car floor
+const RIDER_HEADROOM: f32 = 2.5;
+
+const FLOOR_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+type RiderCandidateQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static GlobalTransform,
+        Option<&'static ElevatorRider>,
+    ),
+    Or<(With<Player>, With<NPCState>)>,
+>;
+
+/// Advances a car at `height` towards `target`, easing in and out so it stops
+/// exactly on the floor. Returns the new height and velocity.
+pub fn step_elevator(height: f32, velocity: f32, target: f32, dt: f32) -> (f32, f32) {
+    let offset = target - height;
+    if offset.abs() < 0.005 && velocity.abs() < 0.05 {
+        return (target, 0.0);
+    }
+
+    // Fastest speed from which the car can still brake to a stop at the target
+    let braking_speed = (2.0 * ELEVATOR_ACCELERATION * offset.abs())
+        .sqrt()
+        .min(ELEVATOR_MAX_SPEED);
+    let max_change = ELEVATOR_ACCELERATION * dt;
+    let velocity =
+        velocity + (braking_speed * offset.signum() - velocity).clamp(-max_change, max_change);
+    let height = height + velocity * dt;
+
+    if (target - height).signum() != offset.signum() {
+        (target, 0.0)
+    } else {
+        (height, velocity)
+    }
+}
+
+/// Marks the player and NPCs standing in a car as its riders
+pub fn elevator_boarding_system(
+    mut commands: Commands,
+    cars: Query<(Entity, &GlobalTransform, &Elevator)>,
+    candidates: RiderCandidateQuery,
+) {
+    if cars.is_empty() {
+        return;
+    }
+    for (entity, transform, rider) in &candidates {
+        let position = transform.translation();
+        let aboard = cars.iter().find(|(_, car, elevator)| {
+            let local = car.affine().inverse().transform_point3(position);
+            local.x.abs() <= elevator.half_extents.x
+                && local.z.abs() <= elevator.half_extents.y
+                && (0.0..=RIDER_HEADROOM).contains(&local.y)
+        });
+
+        match (aboard, rider) {
+            (Some((car, car_transform, _)), rider) if rider.is_none_or(|r| r.elevator != car) => {
+                commands.entity(entity).insert(ElevatorRider {
+                    elevator: car,
+                    last_anchor: *car_transform,
+                });
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<ElevatorRider>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Number keys send the car the player is riding to that floor
+pub fn elevator_floor_keys_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    player: Query<&ElevatorRider, With<Player>>,
+    mut cars: Query<&mut Elevator>,
+) {
+    let Ok(rider) = player.single() else {
+        return;
+    };
+    let Ok(mut elevator) = cars.get_mut(rider.elevator) else {
+        return;
+    };
+    if let Some(floor) = FLOOR_KEYS.iter().position(|key| keys.just_pressed(*key))
+        && (floor as u32) < elevator.floors
+    {
+        elevator.target_floor = floor as u32;
+    }
+}
+
+/// Drives each car along its shaft towards the selected floor
+pub fn elevator_movement_system(time: Res<Time>, mut cars: Query<(&mut Elevator, &mut Transform)>) {
+    let dt = time.delta_secs();
+    for (mut elevator, mut transform) in &mut cars {
+        let target = elevator.target_floor as f32 * elevator.floor_spacing;
+        let (height, velocity) = step_elevator(elevator.height, elevator.velocity, target, dt);
+        elevator.height = height;
+        elevator.velocity = velocity;
+        transform.translation = elevator.base + Vec3::Y * height;
+    }
+}
+
+/// Moves riders with their car
+pub fn elevator_carry_system(
+    mut commands: Commands,
+    cars: Query<&GlobalTransform, With<Elevator>>,
+    mut riders: Query<(
+        Entity,
+        &mut ElevatorRider,
+        &mut Transform,
+        Option<&mut Velocity>,
+    )>,
+) {
+    for (entity, mut rider, mut transform, velocity) in &mut riders {
+        let Ok(car) = cars.get(rider.elevator) else {
+            // Car went away with its interior
+            commands.entity(entity).remove::<ElevatorRider>();
+            continue;
+        };
+        transform.translation += anchor_delta(&rider.last_anchor, car);
+        rider.last_anchor = *car;
+        // Gravity would otherwise build up a fall speed against the moving floor
+        if let Some(mut velocity) = velocity {
+            velocity.linvel.y = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_car_eases_onto_target_floor() {
+        let (mut height, mut velocity) = (0.0, 0.0);
+        let target = 3.3 * 4.0;
+        let mut peak: f32 = 0.0;
+        for _ in 0..60 * 30 {
+            let (h, v) = step_elevator(height, velocity, target, 1.0 / 60.0);
+            assert!(h >= height - 1e-4, "car moved backwards");
+            if h != target {
+                assert!((v - velocity).abs() <= ELEVATOR_ACCELERATION / 60.0 + 1e-4);
+            }
+            (height, velocity) = (h, v);
+            peak = peak.max(velocity);
+        }
+        assert_eq!((height, velocity), (target, 0.0));
+        assert!(peak <= ELEVATOR_MAX_SPEED + 1e-4);
+    }
+}
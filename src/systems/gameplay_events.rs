@@ -0,0 +1,233 @@
+//! Emits the canonical gameplay events and records them.
+//!
+//! Each event is derived from state other systems already keep, so nothing
+//! that enters a vehicle, damages one or hands out money has to know about
+//! the bus: `InCar` being added, `VehicleHealth` reaching zero, a fatal
+//! `Ragdoll`, the active entity's district and the `Wallet` are watched here.
+//! `record_gameplay_events` then copies everything into
+//! `GameplayEventHistory`.
+
+use crate::components::gameplay_events::{
+    GameplayEvent, GameplayEventHistory, MoneyChanged, NpcKilled, VehicleDestroyed, VehicleEntered,
+    ZoneEntered,
+};
+use crate::components::{ActiveEntity, InCar, Player, Ragdoll, VehicleHealth, VehicleType, Wallet};
+use crate::resources::{District, DistrictMap};
+use crate::systems::ui::console::{ConsoleCommands, ConsoleResult};
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Lines `events` prints without a count
+const EVENTS_SHOWN: usize = 20;
+
+#[allow(clippy::type_complexity)]
+pub fn emit_vehicle_entered(
+    entered: Query<(Entity, &InCar), (With<Player>, Added<InCar>)>,
+    vehicles: Query<&VehicleType>,
+    mut events: EventWriter<VehicleEntered>,
+) {
+    for (player, in_car) in &entered {
+        if let Ok(vehicle_type) = vehicles.get(in_car.0) {
+            events.write(VehicleEntered {
+                player,
+                vehicle: in_car.0,
+                vehicle_type: *vehicle_type,
+            });
+        }
+    }
+}
+
+/// Fires once per vehicle when its health reaches zero; repairing it re-arms
+/// the event
+#[allow(clippy::type_complexity)]
+pub fn emit_vehicle_destroyed(
+    vehicles: Query<
+        (Entity, &VehicleHealth, &VehicleType, &GlobalTransform),
+        Changed<VehicleHealth>,
+    >,
+    mut removed: RemovedComponents<VehicleHealth>,
+    mut wrecked: Local<HashSet<Entity>>,
+    mut events: EventWriter<VehicleDestroyed>,
+) {
+    for entity in removed.read() {
+        wrecked.remove(&entity);
+    }
+    for (vehicle, health, vehicle_type, transform) in &vehicles {
+        if !health.is_destroyed() {
+            wrecked.remove(&vehicle);
+        } else if wrecked.insert(vehicle) {
+            events.write(VehicleDestroyed {
+                vehicle,
+                vehicle_type: *vehicle_type,
+                position: transform.translation(),
+            });
+        }
+    }
+}
+
+pub fn emit_npc_killed(
+    knocked: Query<(Entity, &Ragdoll, &GlobalTransform), Added<Ragdoll>>,
+    mut events: EventWriter<NpcKilled>,
+) {
+    for (npc, ragdoll, transform) in &knocked {
+        if ragdoll.fatal {
+            events.write(NpcKilled {
+                npc,
+                position: transform.translation(),
+            });
+        }
+    }
+}
+
+pub fn emit_zone_entered(
+    districts: Option<Res<DistrictMap>>,
+    active: Query<&GlobalTransform, With<ActiveEntity>>,
+    mut current: Local<Option<District>>,
+    mut events: EventWriter<ZoneEntered>,
+) {
+    let (Some(districts), Ok(transform)) = (districts, active.single()) else {
+        return;
+    };
+    let district = districts.district_at(transform.translation());
+    if *current != Some(district) {
+        events.write(ZoneEntered {
+            previous: current.replace(district),
+            district,
+        });
+    }
+}
+
+pub fn emit_money_changed(
+    wallet: Res<Wallet>,
+    mut previous: Local<Option<u32>>,
+    mut events: EventWriter<MoneyChanged>,
+) {
+    if !wallet.is_changed() {
+        return;
+    }
+    let current = wallet.balance();
+    if let Some(previous) = previous.replace(current)
+        && previous != current
+    {
+        events.write(MoneyChanged { previous, current });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn record_gameplay_events(
+    time: Res<Time>,
+    mut history: ResMut<GameplayEventHistory>,
+    mut entered: EventReader<VehicleEntered>,
+    mut destroyed: EventReader<VehicleDestroyed>,
+    mut killed: EventReader<NpcKilled>,
+    mut zones: EventReader<ZoneEntered>,
+    mut money: EventReader<MoneyChanged>,
+) {
+    let now = time.elapsed_secs();
+    let events = entered
+        .read()
+        .map(|e| GameplayEvent::VehicleEntered(*e))
+        .chain(
+            destroyed
+                .read()
+                .map(|e| GameplayEvent::VehicleDestroyed(*e)),
+        )
+        .chain(killed.read().map(|e| GameplayEvent::NpcKilled(*e)))
+        .chain(zones.read().map(|e| GameplayEvent::ZoneEntered(*e)))
+        .chain(money.read().map(|e| GameplayEvent::MoneyChanged(*e)));
+    for event in events {
+        history.push(now, event);
+    }
+}
+
+pub fn events_command(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let mut history = world.resource_mut::<GameplayEventHistory>();
+    let count = match args {
+        [] => EVENTS_SHOWN,
+        ["on"] | ["off"] => {
+            history.set_recording(args[0] == "on");
+            return Ok(format!("event recording {}", args[0]));
+        }
+        [count] => count
+            .parse()
+            .map_err(|_| format!("`{count}` is not a count"))?,
+        _ => return Err("usage: events [count] | events on | events off".to_string()),
+    };
+    if history.is_empty() {
+        return Ok("no events recorded".to_string());
+    }
+    Ok(history
+        .recent(count)
+        .map(|recorded| format!("{:>8.1}s {}", recorded.time, recorded.event))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+pub fn register_gameplay_event_commands(mut registry: ResMut<ConsoleCommands>) {
+    registry
+        .register("events", events_command)
+        .usage("[count] | on | off");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_keeps_latest_and_tracks_sequence() {
+        let mut history = GameplayEventHistory::new(2);
+        let money = |current| {
+            GameplayEvent::MoneyChanged(MoneyChanged {
+                previous: 0,
+                current,
+            })
+        };
+        history.push(0.0, money(1));
+        history.push(1.0, money(2));
+        history.push(2.0, money(3));
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.next_sequence(), 3);
+        assert_eq!(history.since(2).count(), 1);
+        assert_eq!(history.since(0).next().unwrap().event, money(2));
+
+        history.set_recording(false);
+        history.push(3.0, money(4));
+        assert_eq!(history.next_sequence(), 3);
+        assert_eq!(money(3).to_string(), "money_changed $3 (+3)");
+    }
+
+    #[test]
+    fn test_destroyed_fires_once_and_money_changes_are_emitted() {
+        let mut app = App::new();
+        app.add_event::<VehicleDestroyed>()
+            .add_event::<MoneyChanged>()
+            .init_resource::<Wallet>()
+            .add_systems(Update, (emit_vehicle_destroyed, emit_money_changed));
+        let vehicle = app
+            .world_mut()
+            .spawn((
+                VehicleHealth::default(),
+                VehicleType::SuperCar,
+                GlobalTransform::default(),
+            ))
+            .id();
+        app.update();
+
+        for _ in 0..2 {
+            app.world_mut()
+                .get_mut::<VehicleHealth>(vehicle)
+                .unwrap()
+                .current = 0.0;
+            app.world_mut().resource_mut::<Wallet>().credit(100);
+            app.update();
+        }
+        let destroyed = app.world().resource::<Events<VehicleDestroyed>>();
+        assert_eq!(destroyed.len(), 1);
+        let money = app.world().resource::<Events<MoneyChanged>>();
+        let changes: Vec<i64> = money
+            .iter_current_update_events()
+            .map(MoneyChanged::delta)
+            .collect();
+        assert_eq!(changes, vec![100]);
+    }
+}
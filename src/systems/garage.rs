@@ -0,0 +1,583 @@
+//! Vehicle ownership and world garages.
+//!
+//! Every vehicle the player takes control of is recorded in the `Garage`
+//! resource. Using a garage pad (an `Interactable`) lists owned vehicles;
+//! picking one recalls it to the garage, replacing any copy left in the world.
+//! Garages also sell new vehicles and charge for customization, paid from the
+//! `Wallet`.
+
+use crate::components::{
+    ActiveEntity, CustomizationPart, Garage, GarageBuyButton, GarageCustomizeButton,
+    GarageLocation, GarageMenu, GarageSpawnButton, GarageVehicle, Interactable, InteractionEvent,
+    InteractionKind, MAX_GARAGE_VEHICLES, NearbyGarage, PAINT_COLORS, Player, PlayerOwned,
+    VehicleState, VehicleType, Wallet,
+};
+use crate::config::{EconomyConfig, GameConfig};
+use crate::factories::VehicleFactory;
+use crate::resources::Localization;
+use crate::t;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub(crate) const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.18);
+pub(crate) const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.3);
+
+/// Vehicles listed under "Buy", if `EconomyConfig` gives them a price
+const FOR_SALE: [VehicleType; 2] = [VehicleType::SuperCar, VehicleType::Helicopter];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GarageDefinition {
+    pub name: String,
+    pub position: Vec3,
+    pub spawn_point: Vec3,
+    pub radius: f32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GarageList {
+    pub garages: Vec<GarageDefinition>,
+}
+
+pub fn spawn_garages(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let path = format!(
+        "{}/config/garages.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    let list = match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str::<GarageList>(&content).unwrap_or_else(|e| {
+            error!("Failed to parse garages at '{}': {}", path, e);
+            GarageList::default()
+        }),
+        Err(e) => {
+            info!("ℹ️ No garage config found: {}", e);
+            GarageList::default()
+        }
+    };
+
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.2, 0.6, 1.0, 0.6),
+        emissive: LinearRgba::rgb(0.1, 0.4, 1.0),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    for garage in list.garages {
+        commands.spawn((
+            Name::new(garage.name.clone()),
+            Mesh3d(meshes.add(Cylinder::new(garage.radius, 0.1))),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(garage.position),
+            GarageLocation {
+                name: garage.name,
+                radius: garage.radius,
+                spawn_point: garage.spawn_point,
+            },
+        ));
+    }
+}
+
+/// Claims vehicles the first time the player takes control of them
+#[allow(clippy::type_complexity)]
+pub fn register_owned_vehicles(
+    mut commands: Commands,
+    mut garage: ResMut<Garage>,
+    vehicles: Query<(Entity, &VehicleState), (Added<ActiveEntity>, Without<GarageVehicle>)>,
+) {
+    for (entity, state) in &vehicles {
+        let color = state.color.to_srgba().to_f32_array();
+        match garage.add(state.vehicle_type, color) {
+            Some(id) => {
+                commands
+                    .entity(entity)
+                    .insert((PlayerOwned, GarageVehicle(id)));
+                info!("🚗 {:?} added to your garage", state.vehicle_type);
+            }
+            None => info!("🚗 Garage full, {:?} not recorded", state.vehicle_type),
+        }
+    }
+}
+
+/// Gives garage pads their `Interactable`, again when the language changes
+pub fn sync_garage_interactables(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    garages: Query<(Entity, Ref<GarageLocation>)>,
+) {
+    for (entity, location) in &garages {
+        if localization.is_changed() || location.is_changed() {
+            commands.entity(entity).insert(
+                Interactable::new(
+                    InteractionKind::Garage,
+                    location.radius,
+                    t!("garage.prompt", name = &location.name),
+                )
+                .planar(),
+            );
+        }
+    }
+}
+
+/// Using a garage pad toggles its menu
+pub fn garage_interaction_system(
+    mut commands: Commands,
+    garage: Res<Garage>,
+    config: Res<GameConfig>,
+    mut interactions: EventReader<InteractionEvent>,
+    mut nearby: ResMut<NearbyGarage>,
+    garages: Query<&GarageLocation>,
+    menus: Query<Entity, With<GarageMenu>>,
+) {
+    for interaction in interactions.read() {
+        if interaction.kind != InteractionKind::Garage {
+            continue;
+        }
+        let Ok(location) = garages.get(interaction.target) else {
+            continue;
+        };
+        if menus.is_empty() {
+            nearby.0 = Some(interaction.target);
+            spawn_garage_menu(&mut commands, &location.name, &garage, &config.economy);
+        } else {
+            for menu in &menus {
+                commands.entity(menu).despawn();
+            }
+        }
+    }
+}
+
+/// Closes the garage menu once the player steps off its pad
+pub fn close_distant_garage_menus(
+    mut commands: Commands,
+    mut nearby: ResMut<NearbyGarage>,
+    player: Query<&GlobalTransform, (With<Player>, With<ActiveEntity>)>,
+    garages: Query<(&GlobalTransform, &GarageLocation)>,
+    menus: Query<Entity, With<GarageMenu>>,
+) {
+    let Some(open) = nearby.0 else {
+        return;
+    };
+    let on_pad = match (player.single(), garages.get(open)) {
+        (Ok(player), Ok((pad, location))) => {
+            pad.translation().xz().distance(player.translation().xz()) <= location.radius
+        }
+        _ => false,
+    };
+    if !on_pad {
+        nearby.0 = None;
+        for menu in &menus {
+            commands.entity(menu).despawn();
+        }
+    }
+}
+
+fn spawn_garage_menu(
+    commands: &mut Commands,
+    name: &str,
+    garage: &Garage,
+    economy: &EconomyConfig,
+) {
+    commands
+        .spawn((
+            GarageMenu,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(120.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(name.to_uppercase()),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            if garage.vehicles.len() < MAX_GARAGE_VEHICLES {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        for vehicle_type in FOR_SALE {
+                            if let Some(price) = economy.vehicle_price(vehicle_type) {
+                                spawn_menu_button(
+                                    row,
+                                    GarageBuyButton(vehicle_type),
+                                    170.0,
+                                    t!(
+                                        "garage.buy",
+                                        vehicle = format!("{vehicle_type:?}"),
+                                        price = price
+                                    ),
+                                );
+                            }
+                        }
+                    });
+            }
+            parent.spawn((
+                Text::new(t!(
+                    "garage.costs",
+                    paint = economy.paint_cost,
+                    wheels = economy.wheels_cost,
+                    tuning = economy.tuning_stage_cost
+                )),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.7, 0.75)),
+            ));
+
+            if garage.vehicles.is_empty() {
+                parent.spawn((
+                    Text::new(t!("garage.empty")),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.7, 0.7, 0.75)),
+                ));
+            }
+
+            for vehicle in &garage.vehicles {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        spawn_menu_button(
+                            row,
+                            GarageSpawnButton(vehicle.id),
+                            200.0,
+                            format!("{:?} #{}", vehicle.vehicle_type, vehicle.id),
+                        );
+
+                        let customize = |part| GarageCustomizeButton {
+                            id: vehicle.id,
+                            part,
+                        };
+                        spawn_menu_button(
+                            row,
+                            customize(CustomizationPart::Paint),
+                            70.0,
+                            t!("garage.paint"),
+                        );
+                        // Wheels and tuning only exist on cars
+                        if vehicle.vehicle_type == VehicleType::SuperCar {
+                            let custom = &vehicle.customization;
+                            spawn_menu_button(
+                                row,
+                                customize(CustomizationPart::Wheels),
+                                120.0,
+                                format!("{:?}", custom.wheels),
+                            );
+                            let stage = custom.tuning.stage().map_or_else(
+                                || t!("garage.custom"),
+                                |i| t!("garage.stage", stage = i),
+                            );
+                            spawn_menu_button(
+                                row,
+                                customize(CustomizationPart::Tuning),
+                                90.0,
+                                stage,
+                            );
+                        }
+                    });
+            }
+        });
+}
+
+pub(crate) fn spawn_menu_button(
+    parent: &mut ChildSpawnerCommands,
+    button: impl Component,
+    width: f32,
+    label: String,
+) {
+    parent
+        .spawn((
+            button,
+            Button,
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(32.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(NORMAL_BUTTON),
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Gives live garage vehicles their stored paint, wheels and tuning
+pub fn attach_garage_customization(
+    mut commands: Commands,
+    garage: Res<Garage>,
+    vehicles: Query<(Entity, &GarageVehicle), Added<GarageVehicle>>,
+) {
+    for (entity, vehicle) in &vehicles {
+        if let Some(stored) = garage.get(vehicle.0)
+            && !stored.customization.is_stock()
+        {
+            commands.entity(entity).insert(stored.customization.clone());
+        }
+    }
+}
+
+/// Cycles paint, wheels or tuning of a stored vehicle and its live copy, if
+/// the wallet covers the step
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn garage_customize_buttons(
+    mut commands: Commands,
+    mut buttons: Query<
+        (&Interaction, &GarageCustomizeButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut garage: ResMut<Garage>,
+    mut wallet: ResMut<Wallet>,
+    config: Res<GameConfig>,
+    nearby: Res<NearbyGarage>,
+    locations: Query<&GarageLocation>,
+    live: Query<(Entity, &GarageVehicle)>,
+    menus: Query<Entity, With<GarageMenu>>,
+) {
+    let mut changed = false;
+    for (interaction, button, mut color) in &mut buttons {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(stored) = garage.get_mut(button.id) else {
+            continue;
+        };
+
+        let mut custom = stored.customization.clone();
+        let economy = &config.economy;
+        let cost = match button.part {
+            CustomizationPart::Paint => {
+                custom.paint = Some(custom.next_paint());
+                economy.paint_cost
+            }
+            CustomizationPart::Wheels => {
+                custom.wheels = custom.wheels.next();
+                economy.wheels_cost
+            }
+            CustomizationPart::Tuning => {
+                custom.tuning = custom.tuning.next_stage();
+                economy.tuning_stage_cost * custom.tuning.stage().unwrap_or(0) as u32
+            }
+        };
+        if !wallet.try_spend(cost) {
+            info!(
+                "💸 {:?} costs ${}, you have ${}",
+                button.part,
+                cost,
+                wallet.balance()
+            );
+            continue;
+        }
+        stored.customization = custom;
+        let custom = &stored.customization;
+
+        for (entity, vehicle) in &live {
+            if vehicle.0 == button.id {
+                commands.entity(entity).insert(custom.clone());
+            }
+        }
+        changed = true;
+    }
+
+    // Rebuild the menu so the labels show the new values
+    if changed && let Some(location) = nearby.0.and_then(|entity| locations.get(entity).ok()) {
+        for menu in &menus {
+            commands.entity(menu).despawn();
+        }
+        spawn_garage_menu(&mut commands, &location.name, &garage, &config.economy);
+    }
+}
+
+/// Buys a new vehicle into the garage; it is then recalled like any other
+#[allow(clippy::too_many_arguments)]
+pub fn garage_buy_buttons(
+    mut commands: Commands,
+    mut buttons: Query<
+        (&Interaction, &GarageBuyButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut garage: ResMut<Garage>,
+    mut wallet: ResMut<Wallet>,
+    config: Res<GameConfig>,
+    nearby: Res<NearbyGarage>,
+    locations: Query<&GarageLocation>,
+    menus: Query<Entity, With<GarageMenu>>,
+) {
+    let mut bought = false;
+    for (interaction, button, mut color) in &mut buttons {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(price) = config.economy.vehicle_price(button.0) else {
+            continue;
+        };
+        if garage.vehicles.len() >= MAX_GARAGE_VEHICLES {
+            info!("🚗 Garage full");
+            continue;
+        }
+        if !wallet.try_spend(price) {
+            info!(
+                "💸 {:?} costs ${}, you have ${}",
+                button.0,
+                price,
+                wallet.balance()
+            );
+            continue;
+        }
+        garage.add(button.0, PAINT_COLORS[0]);
+        info!("🚗 Bought a {:?} for ${}", button.0, price);
+        bought = true;
+    }
+
+    if bought && let Some(location) = nearby.0.and_then(|entity| locations.get(entity).ok()) {
+        for menu in &menus {
+            commands.entity(menu).despawn();
+        }
+        spawn_garage_menu(&mut commands, &location.name, &garage, &config.economy);
+    }
+}
+
+/// Recalls the picked vehicle to the garage, removing any copy left elsewhere
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn garage_menu_buttons(
+    mut commands: Commands,
+    mut buttons: Query<
+        (&Interaction, &GarageSpawnButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    garage: Res<Garage>,
+    nearby: Res<NearbyGarage>,
+    locations: Query<&GarageLocation>,
+    existing: Query<(Entity, &GarageVehicle), Without<ActiveEntity>>,
+    menus: Query<Entity, With<GarageMenu>>,
+    config: Res<GameConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (interaction, button, mut color) in &mut buttons {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Some(location) = nearby.0.and_then(|entity| locations.get(entity).ok()) else {
+            continue;
+        };
+        let Some(stored) = garage.get(button.0) else {
+            continue;
+        };
+
+        for (entity, vehicle) in &existing {
+            if vehicle.0 == stored.id {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        let [r, g, b, a] = stored.color;
+        let factory = VehicleFactory::with_config(config.clone());
+        match factory.spawn_vehicle_by_type(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &asset_server,
+            stored.vehicle_type,
+            location.spawn_point,
+            Some(Color::srgba(r, g, b, a)),
+        ) {
+            Ok(entity) => {
+                commands
+                    .entity(entity)
+                    .insert((PlayerOwned, GarageVehicle(stored.id)));
+                info!(
+                    "🚗 {:?} delivered to {}",
+                    stored.vehicle_type, location.name
+                );
+            }
+            Err(e) => warn!("⚠️ Failed to spawn {:?}: {:?}", stored.vehicle_type, e),
+        }
+
+        for menu in &menus {
+            commands.entity(menu).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::MAX_GARAGE_VEHICLES;
+
+    #[test]
+    fn test_garage_assigns_ids_and_caps_size() {
+        let mut garage = Garage::default();
+        let first = garage.add(VehicleType::SuperCar, [1.0, 0.0, 0.0, 1.0]);
+        let second = garage.add(VehicleType::Helicopter, [0.0, 0.0, 1.0, 1.0]);
+
+        assert_eq!((first, second), (Some(0), Some(1)));
+        assert_eq!(
+            garage.get(1).map(|v| v.vehicle_type),
+            Some(VehicleType::Helicopter)
+        );
+
+        while garage.vehicles.len() < MAX_GARAGE_VEHICLES {
+            garage.add(VehicleType::SuperCar, [1.0; 4]);
+        }
+        assert_eq!(garage.add(VehicleType::SuperCar, [1.0; 4]), None);
+    }
+
+    #[test]
+    fn test_garage_list_parses() {
+        let content = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/config/garages.ron"
+        ))
+        .unwrap();
+        let list: GarageList = ron::from_str(&content).unwrap();
+        assert!(!list.garages.is_empty());
+    }
+}
@@ -0,0 +1,474 @@
+//! GPS guidance.
+//!
+//! Clicking the fullscreen map (or sending `SetGpsDestination`) plans a route
+//! over the road network: an A* search over the points where roads meet, from
+//! the road nearest the player to the road nearest the destination. The route
+//! is traced on the minimap and marked in the world by floating arrows along
+//! the road ahead and a beam on the destination. Straying too far from it
+//! plans a new one; arriving clears it.
+
+use crate::components::{
+    ActiveEntity, GpsArrow, GpsDestinationBeacon, GpsDistanceText, GpsRoute, SetGpsDestination,
+};
+use crate::systems::world::road_network::RoadNetwork;
+use crate::systems::world::unified_world::UnifiedWorldManager;
+use crate::t;
+use bevy::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Points sampled per road when looking for where two roads meet
+const JUNCTION_SAMPLES: usize = 16;
+/// Roads whose sampled points come this close count as joined
+const JUNCTION_GAP: f32 = 20.0;
+/// Spacing of the points a road contributes to the route polyline
+const ROUTE_STEP: f32 = 10.0;
+
+/// Distance from the route that triggers a new one
+const OFF_ROUTE_DISTANCE: f32 = 25.0;
+/// Minimum seconds between reroutes, so driving off-road doesn't replan every frame
+const REROUTE_COOLDOWN: f32 = 2.0;
+const ARRIVAL_RADIUS: f32 = 20.0;
+
+const ARROW_COUNT: usize = 8;
+const ARROW_SPACING: f32 = 12.0;
+const ARROW_HEIGHT: f32 = 2.5;
+const BEACON_HEIGHT: f32 = 200.0;
+
+#[derive(PartialEq)]
+struct Frontier {
+    estimate: f32,
+    node: usize,
+}
+
+impl Eq for Frontier {}
+
+impl Ord for Frontier {
+    // Reversed so the BinaryHeap pops the lowest estimate first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Points on roads (road id, spline parameter) and the ways between them:
+/// along a road to its next point, or across a junction to another road
+#[derive(Default)]
+struct RouteGraph {
+    nodes: Vec<(u64, f32)>,
+    positions: Vec<Vec3>,
+    edges: Vec<Vec<(usize, f32)>>,
+    on_road: HashMap<u64, Vec<usize>>,
+}
+
+impl RouteGraph {
+    fn add_node(&mut self, network: &RoadNetwork, road: u64, t: f32) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push((road, t));
+        self.positions.push(network.roads[&road].evaluate(t));
+        self.edges.push(Vec::new());
+        self.on_road.entry(road).or_default().push(index);
+        index
+    }
+
+    fn link(&mut self, a: usize, b: usize, cost: f32) {
+        self.edges[a].push((b, cost));
+        self.edges[b].push((a, cost));
+    }
+}
+
+/// Pairs of roads meeting at an intersection or joined end to end
+fn adjacent_roads(network: &RoadNetwork) -> HashSet<(u64, u64)> {
+    let mut pairs = HashSet::new();
+    let mut add = |a: u64, b: u64| {
+        if a != b && network.roads.contains_key(&a) && network.roads.contains_key(&b) {
+            pairs.insert((a.min(b), a.max(b)));
+        }
+    };
+    for road in network.roads.values() {
+        for &other in &road.connections {
+            add(road.id, other);
+        }
+    }
+    for intersection in network.intersections.values() {
+        for &a in &intersection.connected_roads {
+            for &b in &intersection.connected_roads {
+                add(a, b);
+            }
+        }
+    }
+    pairs
+}
+
+/// Route from `from` to `to` along the road network, as a polyline starting
+/// at `from` and ending at `to`. None when there are no roads or the two
+/// ends' roads aren't connected.
+pub fn find_route(network: &RoadNetwork, from: Vec3, to: Vec3) -> Option<Vec<Vec3>> {
+    let (start_road, start_t, _) = network.nearest_road(from)?;
+    let (goal_road, goal_t, _) = network.nearest_road(to)?;
+
+    let samples: HashMap<u64, Vec<Vec3>> = network
+        .roads
+        .values()
+        .map(|road| {
+            let points = (0..=JUNCTION_SAMPLES)
+                .map(|i| road.evaluate(i as f32 / JUNCTION_SAMPLES as f32))
+                .collect();
+            (road.id, points)
+        })
+        .collect();
+    let lengths: HashMap<u64, f32> = samples
+        .iter()
+        .map(|(id, points)| {
+            let length = points.windows(2).map(|p| p[0].distance(p[1])).sum();
+            (*id, length)
+        })
+        .collect();
+
+    let mut graph = RouteGraph::default();
+    for &id in network.roads.keys() {
+        graph.add_node(network, id, 0.0);
+        graph.add_node(network, id, 1.0);
+    }
+    for (a, b) in adjacent_roads(network) {
+        let (i, j, gap) = closest_samples(&samples[&a], &samples[&b]);
+        if gap <= JUNCTION_GAP {
+            let step = 1.0 / JUNCTION_SAMPLES as f32;
+            let node_a = graph.add_node(network, a, i as f32 * step);
+            let node_b = graph.add_node(network, b, j as f32 * step);
+            graph.link(node_a, node_b, gap);
+        }
+    }
+    let start = graph.add_node(network, start_road, start_t);
+    let goal = graph.add_node(network, goal_road, goal_t);
+
+    for (road, nodes) in &graph.on_road.clone() {
+        let mut nodes = nodes.clone();
+        nodes.sort_by(|&a, &b| graph.nodes[a].1.total_cmp(&graph.nodes[b].1));
+        for pair in nodes.windows(2) {
+            let span = graph.nodes[pair[1]].1 - graph.nodes[pair[0]].1;
+            graph.link(pair[0], pair[1], span * lengths[road]);
+        }
+    }
+
+    let path = shortest_path(&graph, start, goal)?;
+
+    let mut points = vec![from];
+    for pair in path.windows(2) {
+        let ((road_a, t_a), (road_b, t_b)) = (graph.nodes[pair[0]], graph.nodes[pair[1]]);
+        if road_a == road_b {
+            let road = &network.roads[&road_a];
+            let steps = ((t_b - t_a).abs() * lengths[&road_a] / ROUTE_STEP)
+                .ceil()
+                .max(1.0);
+            for step in 0..=steps as usize {
+                points.push(road.evaluate(t_a + (t_b - t_a) * step as f32 / steps));
+            }
+        } else {
+            points.push(graph.positions[pair[1]]);
+        }
+    }
+    points.push(to);
+    points.dedup_by(|a, b| a.distance(*b) < 0.5);
+    Some(points)
+}
+
+/// Closest pair of points between two sampled roads, as (index in `a`,
+/// index in `b`, distance on the ground plane)
+fn closest_samples(a: &[Vec3], b: &[Vec3]) -> (usize, usize, f32) {
+    let mut best = (0, 0, f32::INFINITY);
+    for (i, pa) in a.iter().enumerate() {
+        for (j, pb) in b.iter().enumerate() {
+            let distance = pa.xz().distance(pb.xz());
+            if distance < best.2 {
+                best = (i, j, distance);
+            }
+        }
+    }
+    best
+}
+
+/// A* over the graph; the straight-line distance never overestimates
+fn shortest_path(graph: &RouteGraph, start: usize, goal: usize) -> Option<Vec<usize>> {
+    let goal_position = graph.positions[goal];
+    let mut cost = vec![f32::INFINITY; graph.nodes.len()];
+    let mut came_from = vec![usize::MAX; graph.nodes.len()];
+    let mut open = BinaryHeap::new();
+    cost[start] = 0.0;
+    open.push(Frontier {
+        estimate: graph.positions[start].distance(goal_position),
+        node: start,
+    });
+
+    while let Some(Frontier { node, estimate }) = open.pop() {
+        if node == goal {
+            let mut path = vec![goal];
+            while let Some(&last) = path.last()
+                && last != start
+            {
+                path.push(came_from[last]);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if estimate > cost[node] + graph.positions[node].distance(goal_position) + 1e-3 {
+            continue;
+        }
+        for &(next, step) in &graph.edges[node] {
+            let through = cost[node] + step;
+            if through < cost[next] {
+                cost[next] = through;
+                came_from[next] = node;
+                open.push(Frontier {
+                    estimate: through + graph.positions[next].distance(goal_position),
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Road route from `from` to `to`, or a straight line off the network
+fn plan_route(network: &RoadNetwork, from: Vec3, to: Vec3) -> Vec<Vec3> {
+    find_route(network, from, to).unwrap_or_else(|| vec![from, to])
+}
+
+/// "350 m" / "1.2 km"
+pub fn format_distance(meters: f32) -> String {
+    if meters < 1000.0 {
+        format!("{} m", (meters / 10.0).round() as u32 * 10)
+    } else {
+        format!("{:.1} km", meters / 1000.0)
+    }
+}
+
+pub fn set_gps_destination(
+    mut requests: EventReader<SetGpsDestination>,
+    mut route: ResMut<GpsRoute>,
+    world: Res<UnifiedWorldManager>,
+    player: Query<&Transform, With<ActiveEntity>>,
+) {
+    for SetGpsDestination(destination) in requests.read() {
+        let Some(destination) = *destination else {
+            route.clear();
+            continue;
+        };
+        let from = player
+            .single()
+            .map_or(destination, |transform| transform.translation);
+        *route = GpsRoute {
+            destination: Some(destination),
+            points: plan_route(&world.road_network, from, destination),
+            progress: 0,
+        };
+        info!("🧭 GPS route set: {:.0} m", route.remaining_distance(from));
+    }
+}
+
+/// Tracks the player along the route, replanning when they stray from it and
+/// clearing it on arrival
+pub fn follow_gps_route(
+    time: Res<Time>,
+    mut route: ResMut<GpsRoute>,
+    world: Res<UnifiedWorldManager>,
+    player: Query<&Transform, With<ActiveEntity>>,
+    mut since_reroute: Local<f32>,
+) {
+    *since_reroute += time.delta_secs();
+    let (Some(destination), Ok(player)) = (route.destination, player.single()) else {
+        return;
+    };
+    let position = player.translation;
+    if position.xz().distance(destination.xz()) < ARRIVAL_RADIUS {
+        info!("🧭 GPS destination reached");
+        route.clear();
+        return;
+    }
+
+    match route.nearest_segment(position) {
+        Some((segment, distance)) if distance <= OFF_ROUTE_DISTANCE => route.progress = segment,
+        _ if *since_reroute >= REROUTE_COOLDOWN => {
+            *since_reroute = 0.0;
+            route.points = plan_route(&world.road_network, position, destination);
+            route.progress = 0;
+        }
+        _ => {}
+    }
+}
+
+pub fn spawn_gps_markers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let color = Color::srgba(0.8, 0.3, 1.0, 0.8);
+    let material = materials.add(StandardMaterial {
+        base_color: color,
+        emissive: LinearRgba::rgb(0.8, 0.3, 1.0),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    let arrow = meshes.add(Cone::new(0.8, 2.0));
+    for _ in 0..ARROW_COUNT {
+        commands.spawn((
+            Name::new("GPS arrow"),
+            Mesh3d(arrow.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::default(),
+            Visibility::Hidden,
+            GpsArrow,
+        ));
+    }
+
+    commands.spawn((
+        Name::new("GPS destination"),
+        Mesh3d(meshes.add(Cylinder::new(1.5, BEACON_HEIGHT))),
+        MeshMaterial3d(material),
+        Transform::default(),
+        Visibility::Hidden,
+        GpsDestinationBeacon,
+    ));
+
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(color.with_alpha(1.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(280.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        GpsDistanceText,
+    ));
+}
+
+/// Lays the arrows out along the road ahead of the player and stands the
+/// beam on the destination
+#[allow(clippy::type_complexity)]
+pub fn update_gps_markers(
+    time: Res<Time>,
+    route: Res<GpsRoute>,
+    player: Query<&Transform, With<ActiveEntity>>,
+    mut arrows: Query<
+        (&mut Transform, &mut Visibility),
+        (
+            With<GpsArrow>,
+            Without<ActiveEntity>,
+            Without<GpsDestinationBeacon>,
+        ),
+    >,
+    mut beacon: Query<
+        (&mut Transform, &mut Visibility),
+        (With<GpsDestinationBeacon>, Without<ActiveEntity>),
+    >,
+) {
+    let position = player.single().ok().map(|t| t.translation);
+    let ahead = match (route.destination, position) {
+        (Some(_), Some(position)) => route.points_ahead(position, ARROW_SPACING, ARROW_COUNT),
+        _ => Vec::new(),
+    };
+    // Gentle bob so the arrows read as markers rather than scenery
+    let bob = (time.elapsed_secs() * 3.0).sin() * 0.3;
+
+    let mut ahead = ahead.into_iter();
+    for (mut transform, mut visibility) in &mut arrows {
+        match ahead.next() {
+            Some((point, direction)) => {
+                transform.translation = point + Vec3::Y * (ARROW_HEIGHT + bob);
+                transform.rotation =
+                    Quat::from_rotation_arc(Vec3::Y, direction.normalize_or(Vec3::Y));
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    if let Ok((mut transform, mut visibility)) = beacon.single_mut() {
+        match route.destination {
+            Some(destination) => {
+                transform.translation = destination + Vec3::Y * BEACON_HEIGHT / 2.0;
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+pub fn update_gps_hud(
+    route: Res<GpsRoute>,
+    player: Query<&Transform, With<ActiveEntity>>,
+    mut texts: Query<&mut Text, With<GpsDistanceText>>,
+) {
+    let shown = match (route.destination, player.single()) {
+        (Some(_), Ok(player)) => t!(
+            "gps.distance",
+            distance = format_distance(route.remaining_distance(player.translation))
+        ),
+        _ => String::new(),
+    };
+    for mut text in &mut texts {
+        if text.0 != shown {
+            text.0 = shown.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::road_network::RoadType;
+
+    #[test]
+    fn test_route_follows_roads_around_the_block() {
+        // Two streets meeting at a corner, plus a third that only touches
+        // the first one's far end
+        let mut network = RoadNetwork::default();
+        let a = network.add_road(Vec3::ZERO, Vec3::new(200.0, 0.0, 0.0), RoadType::MainStreet);
+        let b = network.add_road(
+            Vec3::new(200.0, 0.0, 0.0),
+            Vec3::new(200.0, 0.0, 200.0),
+            RoadType::MainStreet,
+        );
+        network.connect_roads(a, b);
+        network.add_road(
+            Vec3::new(0.0, 0.0, 400.0),
+            Vec3::new(0.0, 0.0, 600.0),
+            RoadType::MainStreet,
+        );
+
+        let from = Vec3::new(10.0, 0.0, 5.0);
+        let to = Vec3::new(205.0, 0.0, 190.0);
+        let route = find_route(&network, from, to).unwrap();
+        assert_eq!(route.first(), Some(&from));
+        assert_eq!(route.last(), Some(&to));
+        assert!(
+            route
+                .iter()
+                .any(|p| p.distance(Vec3::new(200.0, 0.0, 0.0)) < 1.0)
+        );
+        let length: f32 = route.windows(2).map(|p| p[0].distance(p[1])).sum();
+        assert!((length - 385.0).abs() < 20.0, "route length {length}");
+
+        // Nothing joins the third road to the others
+        assert!(find_route(&network, from, Vec3::new(0.0, 0.0, 500.0)).is_none());
+        assert!(find_route(&RoadNetwork::default(), from, to).is_none());
+    }
+
+    #[test]
+    fn test_format_distance() {
+        assert_eq!(format_distance(347.0), "350 m");
+        assert_eq!(format_distance(1234.0), "1.2 km");
+    }
+}
@@ -1,4 +1,6 @@
+use super::input_map::InputMap;
 use crate::components::{ControlState, VehicleControlType};
+use crate::t;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -12,7 +14,7 @@ use std::collections::HashMap;
 /// - Single source of truth for all control mappings
 /// - Supports runtime control customization
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum AssetControlAction {
     // Movement actions
     Forward,
@@ -40,9 +42,24 @@ pub enum AssetControlAction {
     Turbo, // Speed boost for boats/etc
     Afterburner,
 
+    // Aircraft systems
+    ToggleLandingGear,
+
+    // Car transmission
+    ShiftUp,
+    ShiftDown,
+    ToggleTransmission,
+    ToggleHeadlights,
+    Horn,
+
+    // Bailing out
+    Eject,
+    DeployParachute,
+
     // Meta actions
     Run,
     Interact,
+    CycleCamera,
     DebugInfo,
     EmergencyReset,
 }
@@ -103,7 +120,11 @@ impl Default for VehicleControlsConfig {
                         description: "Turn right".to_string(),
                     },
                 ],
-                secondary_controls: vec![],
+                secondary_controls: vec![AssetControlBinding {
+                    action: ACA::DeployParachute,
+                    key: KC::Space,
+                    description: "Open parachute".to_string(),
+                }],
                 meta_controls: vec![
                     AssetControlBinding {
                         action: ACA::Run,
@@ -115,6 +136,11 @@ impl Default for VehicleControlsConfig {
                         key: KC::KeyF,
                         description: "Interact".to_string(),
                     },
+                    AssetControlBinding {
+                        action: ACA::CycleCamera,
+                        key: KC::KeyV,
+                        description: "Cycle camera".to_string(),
+                    },
                 ],
             },
         );
@@ -147,16 +173,55 @@ impl Default for VehicleControlsConfig {
                         description: "Steer right".to_string(),
                     },
                 ],
-                secondary_controls: vec![AssetControlBinding {
-                    action: ACA::Turbo,
-                    key: KC::Space,
-                    description: "Turbo boost".to_string(),
-                }],
-                meta_controls: vec![AssetControlBinding {
-                    action: ACA::Interact,
-                    key: KC::KeyF,
-                    description: "Exit vehicle".to_string(),
-                }],
+                secondary_controls: vec![
+                    AssetControlBinding {
+                        action: ACA::Turbo,
+                        key: KC::Space,
+                        description: "Turbo boost".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::Eject,
+                        key: KC::KeyE,
+                        description: "Bail out".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::ShiftUp,
+                        key: KC::KeyX,
+                        description: "Shift up (manual)".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::ShiftDown,
+                        key: KC::KeyZ,
+                        description: "Shift down (manual)".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::ToggleTransmission,
+                        key: KC::KeyT,
+                        description: "Automatic / manual gearbox".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::ToggleHeadlights,
+                        key: KC::KeyL,
+                        description: "Headlights (auto / on / off)".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::Horn,
+                        key: KC::KeyH,
+                        description: "Horn".to_string(),
+                    },
+                ],
+                meta_controls: vec![
+                    AssetControlBinding {
+                        action: ACA::Interact,
+                        key: KC::KeyF,
+                        description: "Exit vehicle".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::CycleCamera,
+                        key: KC::KeyV,
+                        description: "Cycle camera".to_string(),
+                    },
+                ],
             },
         );
 
@@ -198,12 +263,23 @@ impl Default for VehicleControlsConfig {
                         description: "Descend".to_string(),
                     },
                 ],
-                secondary_controls: vec![],
-                meta_controls: vec![AssetControlBinding {
-                    action: ACA::Interact,
-                    key: KC::KeyF,
-                    description: "Exit helicopter".to_string(),
+                secondary_controls: vec![AssetControlBinding {
+                    action: ACA::Eject,
+                    key: KC::KeyE,
+                    description: "Bail out".to_string(),
                 }],
+                meta_controls: vec![
+                    AssetControlBinding {
+                        action: ACA::Interact,
+                        key: KC::KeyF,
+                        description: "Exit helicopter".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::CycleCamera,
+                        key: KC::KeyV,
+                        description: "Cycle camera".to_string(),
+                    },
+                ],
             },
         );
 
@@ -255,16 +331,35 @@ impl Default for VehicleControlsConfig {
                         description: "Yaw right".to_string(),
                     },
                 ],
-                secondary_controls: vec![AssetControlBinding {
-                    action: ACA::Afterburner,
-                    key: KC::Space,
-                    description: "Afterburner".to_string(),
-                }],
-                meta_controls: vec![AssetControlBinding {
-                    action: ACA::Interact,
-                    key: KC::KeyF,
-                    description: "Exit F16".to_string(),
-                }],
+                secondary_controls: vec![
+                    AssetControlBinding {
+                        action: ACA::Afterburner,
+                        key: KC::Space,
+                        description: "Afterburner".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::ToggleLandingGear,
+                        key: KC::KeyG,
+                        description: "Landing gear up/down".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::Eject,
+                        key: KC::KeyE,
+                        description: "Eject".to_string(),
+                    },
+                ],
+                meta_controls: vec![
+                    AssetControlBinding {
+                        action: ACA::Interact,
+                        key: KC::KeyF,
+                        description: "Exit F16".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::CycleCamera,
+                        key: KC::KeyV,
+                        description: "Cycle camera".to_string(),
+                    },
+                ],
             },
         );
 
@@ -312,6 +407,11 @@ impl Default for VehicleControlsConfig {
                         key: KC::ShiftLeft,
                         description: "Exit to water".to_string(),
                     },
+                    AssetControlBinding {
+                        action: ACA::CycleCamera,
+                        key: KC::KeyV,
+                        description: "Cycle camera".to_string(),
+                    },
                 ],
             },
         );
@@ -355,11 +455,18 @@ impl Default for VehicleControlsConfig {
                     },
                 ],
                 secondary_controls: vec![],
-                meta_controls: vec![AssetControlBinding {
-                    action: ACA::Interact,
-                    key: KC::KeyF,
-                    description: "Board yacht".to_string(),
-                }],
+                meta_controls: vec![
+                    AssetControlBinding {
+                        action: ACA::Interact,
+                        key: KC::KeyF,
+                        description: "Board yacht".to_string(),
+                    },
+                    AssetControlBinding {
+                        action: ACA::CycleCamera,
+                        key: KC::KeyV,
+                        description: "Cycle camera".to_string(),
+                    },
+                ],
             },
         );
 
@@ -399,6 +506,21 @@ pub struct LoadedVehicleControls {
     pub loading: bool,
 }
 
+impl LoadedVehicleControls {
+    /// Key `vehicle_controls.ron` binds to `action` for `vehicle`
+    pub fn default_key(
+        &self,
+        vehicle: &VehicleControlType,
+        action: &AssetControlAction,
+    ) -> Option<KeyCode> {
+        self.config
+            .as_ref()?
+            .vehicle_types
+            .get(vehicle)?
+            .get_key_for_action(action)
+    }
+}
+
 #[derive(Resource)]
 pub struct VehicleControlsHandle(pub Handle<VehicleControlsConfig>);
 
@@ -506,9 +628,12 @@ pub fn process_loaded_controls_system(
 ///
 /// This system uses loaded control configurations instead of hardcoded mappings
 /// Only processes entities with ActiveEntity to prevent state conflicts
+/// Keys remapped in the `InputMap` replace the vehicle defaults; gamepads add analog input
 pub fn asset_based_input_mapping_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     loaded_controls: Res<LoadedVehicleControls>,
+    input_map: Option<Res<InputMap>>,
+    gamepads: Query<&Gamepad>,
     mut query: Query<
         (&mut ControlState, &VehicleControlType),
         With<crate::components::ActiveEntity>,
@@ -535,16 +660,37 @@ pub fn asset_based_input_mapping_system(
 
         // Map input based on loaded configuration
         for binding in vehicle_controls.get_all_bindings() {
-            if keyboard_input.pressed(binding.key) {
+            let default_key = [binding.key];
+            let keys = input_map
+                .as_ref()
+                .and_then(|map| map.keys_for(&binding.action))
+                .unwrap_or(&default_key);
+
+            if keyboard_input.any_pressed(keys.iter().copied()) {
                 apply_control_action(&binding.action, &mut control_state);
             }
 
             // Handle just_pressed actions
-            if keyboard_input.just_pressed(binding.key) {
+            if keyboard_input.any_just_pressed(keys.iter().copied()) {
                 apply_control_action_once(&binding.action, &mut control_state);
             }
         }
 
+        // Gamepad input only for actions this vehicle actually has
+        if let Some(input_map) = input_map.as_ref() {
+            for binding in vehicle_controls.get_all_bindings() {
+                for gamepad in &gamepads {
+                    let strength = input_map.gamepad_strength(&binding.action, gamepad);
+                    if strength > 0.0 {
+                        apply_control_action_scaled(&binding.action, &mut control_state, strength);
+                    }
+                    if input_map.gamepad_just_pressed(&binding.action, gamepad) {
+                        apply_control_action_once(&binding.action, &mut control_state);
+                    }
+                }
+            }
+        }
+
         // Always validate inputs for safety
         control_state.validate_and_clamp();
     }
@@ -555,8 +701,18 @@ const CONTINUOUS_INPUT_DEADZONE: f32 = 0.1;
 
 /// Apply a control action to the control state (for held keys)
 fn apply_control_action(action: &AssetControlAction, control_state: &mut ControlState) {
+    apply_control_action_scaled(action, control_state, 1.0);
+}
+
+/// Apply a control action at partial strength (0..1), e.g. from an analog stick
+fn apply_control_action_scaled(
+    action: &AssetControlAction,
+    control_state: &mut ControlState,
+    strength: f32,
+) {
     // Helper to apply deadzone to continuous inputs
     let apply_with_deadzone = |value: f32| {
+        let value = value * strength;
         if value.abs() < CONTINUOUS_INPUT_DEADZONE {
             0.0
         } else {
@@ -582,12 +738,13 @@ fn apply_control_action(action: &AssetControlAction, control_state: &mut Control
 
         AssetControlAction::ThrottleUp => control_state.throttle = apply_with_deadzone(1.0),
         AssetControlAction::ThrottleDown => control_state.throttle = apply_with_deadzone(-1.0),
-        AssetControlAction::Brake => control_state.brake = 1.0, // Regular braking
+        AssetControlAction::Brake => control_state.brake = strength, // Regular braking
         AssetControlAction::EmergencyBrake => control_state.emergency_brake = true,
-        AssetControlAction::Turbo => control_state.boost = 1.0, // Turbo boost for boats
-        AssetControlAction::Afterburner => control_state.boost = 1.0, // Afterburner for jets
+        AssetControlAction::Turbo => control_state.boost = strength, // Turbo boost for boats
+        AssetControlAction::Afterburner => control_state.boost = strength, // Afterburner for jets
 
         AssetControlAction::Run => control_state.run = true,
+        AssetControlAction::Horn => control_state.horn = true,
 
         // Meta actions are handled in apply_control_action_once
         _ => {}
@@ -598,7 +755,15 @@ fn apply_control_action(action: &AssetControlAction, control_state: &mut Control
 fn apply_control_action_once(action: &AssetControlAction, control_state: &mut ControlState) {
     match action {
         AssetControlAction::Interact => control_state.interact = true,
+        AssetControlAction::ToggleLandingGear => control_state.gear_toggle = true,
+        AssetControlAction::ShiftUp => control_state.shift_up = true,
+        AssetControlAction::ShiftDown => control_state.shift_down = true,
+        AssetControlAction::ToggleTransmission => control_state.toggle_transmission = true,
+        AssetControlAction::ToggleHeadlights => control_state.toggle_headlights = true,
+        AssetControlAction::Eject => control_state.eject = true,
+        AssetControlAction::DeployParachute => control_state.deploy_parachute = true,
         AssetControlAction::EmergencyReset => control_state.emergency_brake = true,
+        AssetControlAction::CycleCamera => control_state.cycle_camera = true,
         // Other actions are continuous, not one-shot
         _ => {}
     }
@@ -613,27 +778,31 @@ pub fn get_vehicle_control_help(
     let vehicle_controls = config.vehicle_types.get(vehicle_type)?;
 
     let mut help_text = Vec::new();
-    help_text.push(format!("{} CONTROLS", vehicle_controls.name.to_uppercase()));
-    help_text.push(format!("{}\n", vehicle_controls.description));
+    // Names and descriptions may be localization keys
+    help_text.push(t!(
+        "controls.title",
+        vehicle = t!(&vehicle_controls.name).to_uppercase()
+    ));
+    help_text.push(format!("{}\n", t!(&vehicle_controls.description)));
 
     if !vehicle_controls.primary_controls.is_empty() {
-        help_text.push("PRIMARY CONTROLS:".to_string());
+        help_text.push(t!("controls.primary"));
         for binding in &vehicle_controls.primary_controls {
-            help_text.push(format!("  {:?}: {}", binding.key, binding.description));
+            help_text.push(format!("  {:?}: {}", binding.key, t!(&binding.description)));
         }
         help_text.push("".to_string());
     }
 
     if !vehicle_controls.secondary_controls.is_empty() {
-        help_text.push("SECONDARY CONTROLS:".to_string());
+        help_text.push(t!("controls.secondary"));
         for binding in &vehicle_controls.secondary_controls {
-            help_text.push(format!("  {:?}: {}", binding.key, binding.description));
+            help_text.push(format!("  {:?}: {}", binding.key, t!(&binding.description)));
         }
         help_text.push("".to_string());
     }
 
     if !vehicle_controls.meta_controls.is_empty() {
-        help_text.push("META CONTROLS:".to_string());
+        help_text.push(t!("controls.meta"));
 
         // Special handling for Yacht: combine Run + Interact into Shift+F
         if matches!(vehicle_type, VehicleControlType::Yacht) {
@@ -648,10 +817,16 @@ pub fn get_vehicle_control_help(
 
             if let (Some(interact), Some(run)) = (interact_binding, run_binding) {
                 // Show combined Shift+F controls
-                help_text.push(format!("  {:?}: Exit to deck", interact.key));
                 help_text.push(format!(
-                    "  {:?}+{:?}: Jump to water (exit swimming)",
-                    run.key, interact.key
+                    "  {:?}: {}",
+                    interact.key,
+                    t!("controls.yacht_exit_deck")
+                ));
+                help_text.push(format!(
+                    "  {:?}+{:?}: {}",
+                    run.key,
+                    interact.key,
+                    t!("controls.yacht_jump")
                 ));
 
                 // Show other meta controls except Run (already shown in combo)
@@ -660,19 +835,23 @@ pub fn get_vehicle_control_help(
                         binding.action,
                         AssetControlAction::Interact | AssetControlAction::Run
                     ) {
-                        help_text.push(format!("  {:?}: {}", binding.key, binding.description));
+                        help_text.push(format!(
+                            "  {:?}: {}",
+                            binding.key,
+                            t!(&binding.description)
+                        ));
                     }
                 }
             } else {
                 // Fallback: show all meta controls normally
                 for binding in &vehicle_controls.meta_controls {
-                    help_text.push(format!("  {:?}: {}", binding.key, binding.description));
+                    help_text.push(format!("  {:?}: {}", binding.key, t!(&binding.description)));
                 }
             }
         } else {
             // For all other vehicles, show meta controls normally
             for binding in &vehicle_controls.meta_controls {
-                help_text.push(format!("  {:?}: {}", binding.key, binding.description));
+                help_text.push(format!("  {:?}: {}", binding.key, t!(&binding.description)));
             }
         }
     }
@@ -696,6 +875,9 @@ mod tests {
 
         apply_control_action_once(&AssetControlAction::Interact, &mut control_state);
         assert!(control_state.interact);
+
+        apply_control_action_once(&AssetControlAction::CycleCamera, &mut control_state);
+        assert!(control_state.cycle_camera);
     }
 
     #[test]
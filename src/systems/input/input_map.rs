@@ -0,0 +1,389 @@
+//! Remappable bindings layered over the per-vehicle controls
+//!
+//! Loaded from the active profile's `input_map.ron` once the player has
+//! rebound anything, else from `assets/config/input_map.ron`. Keyboard keys
+//! listed here replace the vehicle's default key for that action; gamepad
+//! buttons and axes are added on top. Which actions exist for a vehicle is still decided by
+//! `vehicle_controls.ron`.
+
+use super::asset_based_controls::{AssetControlAction, LoadedVehicleControls};
+use crate::components::VehicleControlType;
+use crate::systems::profiles::{ActiveProfile, ProfileChangedEvent};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Which half of an axis drives the action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisBinding {
+    pub axis: GamepadAxis,
+    pub direction: AxisDirection,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionBinding {
+    pub keys: Vec<KeyCode>,
+    /// Analog buttons (triggers) report partial values, digital ones 0 or 1
+    pub buttons: Vec<GamepadButton>,
+    pub axes: Vec<AxisBinding>,
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputMap {
+    /// Stick/trigger values below this are ignored; the rest is rescaled to 0..1
+    pub dead_zone: f32,
+    pub bindings: HashMap<AssetControlAction, ActionBinding>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use AssetControlAction as ACA;
+
+        let axis = |axis, direction| ActionBinding {
+            axes: vec![AxisBinding { axis, direction }],
+            ..default()
+        };
+        let buttons = |buttons: &[GamepadButton]| ActionBinding {
+            buttons: buttons.to_vec(),
+            ..default()
+        };
+
+        let bindings = HashMap::from([
+            (ACA::Forward, buttons(&[GamepadButton::RightTrigger2])),
+            (ACA::Backward, buttons(&[GamepadButton::LeftTrigger2])),
+            (
+                ACA::TurnLeft,
+                axis(GamepadAxis::LeftStickX, AxisDirection::Negative),
+            ),
+            (
+                ACA::TurnRight,
+                axis(GamepadAxis::LeftStickX, AxisDirection::Positive),
+            ),
+            (
+                ACA::PitchUp,
+                axis(GamepadAxis::LeftStickY, AxisDirection::Negative),
+            ),
+            (
+                ACA::PitchDown,
+                axis(GamepadAxis::LeftStickY, AxisDirection::Positive),
+            ),
+            (
+                ACA::RollLeft,
+                axis(GamepadAxis::LeftStickX, AxisDirection::Negative),
+            ),
+            (
+                ACA::RollRight,
+                axis(GamepadAxis::LeftStickX, AxisDirection::Positive),
+            ),
+            (
+                ACA::YawLeft,
+                axis(GamepadAxis::RightStickX, AxisDirection::Negative),
+            ),
+            (
+                ACA::YawRight,
+                axis(GamepadAxis::RightStickX, AxisDirection::Positive),
+            ),
+            (
+                ACA::VerticalUp,
+                axis(GamepadAxis::RightStickY, AxisDirection::Positive),
+            ),
+            (
+                ACA::VerticalDown,
+                axis(GamepadAxis::RightStickY, AxisDirection::Negative),
+            ),
+            (ACA::ThrottleUp, buttons(&[GamepadButton::RightTrigger2])),
+            (ACA::ThrottleDown, buttons(&[GamepadButton::LeftTrigger2])),
+            (ACA::Brake, buttons(&[GamepadButton::West])),
+            (ACA::EmergencyBrake, buttons(&[GamepadButton::East])),
+            (ACA::Turbo, buttons(&[GamepadButton::South])),
+            (ACA::Afterburner, buttons(&[GamepadButton::South])),
+            (ACA::ToggleLandingGear, buttons(&[GamepadButton::DPadDown])),
+            (ACA::ShiftUp, buttons(&[GamepadButton::RightTrigger])),
+            (ACA::ShiftDown, buttons(&[GamepadButton::LeftTrigger])),
+            (
+                ACA::ToggleTransmission,
+                buttons(&[GamepadButton::DPadRight]),
+            ),
+            (ACA::ToggleHeadlights, buttons(&[GamepadButton::DPadLeft])),
+            (ACA::Horn, buttons(&[GamepadButton::RightThumb])),
+            (ACA::Eject, buttons(&[GamepadButton::DPadUp])),
+            (ACA::DeployParachute, buttons(&[GamepadButton::South])),
+            (ACA::Run, buttons(&[GamepadButton::LeftThumb])),
+            (ACA::Interact, buttons(&[GamepadButton::North])),
+            (ACA::CycleCamera, buttons(&[GamepadButton::Select])),
+        ]);
+
+        Self {
+            dead_zone: 0.15,
+            bindings,
+        }
+    }
+}
+
+impl InputMap {
+    pub fn validate_and_clamp(&mut self) {
+        self.dead_zone = self.dead_zone.clamp(0.0, 0.9);
+    }
+
+    /// Keyboard keys overriding the vehicle default, if any are bound
+    pub fn keys_for(&self, action: &AssetControlAction) -> Option<&[KeyCode]> {
+        self.bindings
+            .get(action)
+            .map(|binding| binding.keys.as_slice())
+            .filter(|keys| !keys.is_empty())
+    }
+
+    /// Remapped keys of `action`, or `default_key` when it is not remapped
+    pub fn keys_or_default(
+        &self,
+        action: &AssetControlAction,
+        default_key: Option<KeyCode>,
+    ) -> Vec<KeyCode> {
+        self.keys_for(action)
+            .map(<[KeyCode]>::to_vec)
+            .unwrap_or_else(|| default_key.into_iter().collect())
+    }
+
+    /// Gamepad buttons bound to `action`
+    pub fn buttons_for(&self, action: &AssetControlAction) -> &[GamepadButton] {
+        self.bindings
+            .get(action)
+            .map_or(&[], |binding| binding.buttons.as_slice())
+    }
+
+    /// Binds `key` to `action`, replacing its previous keys. The key is taken
+    /// away from any other action; that action is returned so callers can warn.
+    pub fn rebind_key(
+        &mut self,
+        action: AssetControlAction,
+        key: KeyCode,
+    ) -> Option<AssetControlAction> {
+        let mut displaced = None;
+        for (other, binding) in self.bindings.iter_mut() {
+            if *other != action && binding.keys.contains(&key) {
+                binding.keys.retain(|k| *k != key);
+                displaced = Some(*other);
+            }
+        }
+        self.bindings.entry(action).or_default().keys = vec![key];
+        displaced
+    }
+
+    /// Binds `button` to `action`, replacing its previous buttons
+    pub fn rebind_button(
+        &mut self,
+        action: AssetControlAction,
+        button: GamepadButton,
+    ) -> Option<AssetControlAction> {
+        let mut displaced = None;
+        for (other, binding) in self.bindings.iter_mut() {
+            if *other != action && binding.buttons.contains(&button) {
+                binding.buttons.retain(|b| *b != button);
+                displaced = Some(*other);
+            }
+        }
+        self.bindings.entry(action).or_default().buttons = vec![button];
+        displaced
+    }
+
+    /// Drops the keyboard override so the vehicle's default key applies again
+    pub fn clear_keys(&mut self, action: AssetControlAction) {
+        if let Some(binding) = self.bindings.get_mut(&action) {
+            binding.keys.clear();
+        }
+    }
+
+    /// Analog strength (0..1) of `action` on `gamepad`
+    pub fn gamepad_strength(&self, action: &AssetControlAction, gamepad: &Gamepad) -> f32 {
+        let Some(binding) = self.bindings.get(action) else {
+            return 0.0;
+        };
+
+        let buttons = binding.buttons.iter().map(|button| {
+            let value =
+                gamepad
+                    .get(*button)
+                    .unwrap_or(if gamepad.pressed(*button) { 1.0 } else { 0.0 });
+            apply_dead_zone(value, self.dead_zone)
+        });
+        let axes = binding.axes.iter().map(|binding| {
+            let value = gamepad.get(binding.axis).unwrap_or(0.0);
+            let value = match binding.direction {
+                AxisDirection::Positive => value,
+                AxisDirection::Negative => -value,
+            };
+            apply_dead_zone(value, self.dead_zone)
+        });
+
+        buttons.chain(axes).fold(0.0, f32::max)
+    }
+
+    pub fn gamepad_just_pressed(&self, action: &AssetControlAction, gamepad: &Gamepad) -> bool {
+        self.bindings.get(action).is_some_and(|binding| {
+            binding
+                .buttons
+                .iter()
+                .any(|button| gamepad.just_pressed(*button))
+        })
+    }
+}
+
+/// Keys that trigger `action` for `vehicle`: the remapped ones, otherwise the
+/// default from `vehicle_controls.ron`
+pub fn bound_keys(
+    input_map: Option<&InputMap>,
+    controls: Option<&LoadedVehicleControls>,
+    vehicle: &VehicleControlType,
+    action: &AssetControlAction,
+) -> Vec<KeyCode> {
+    let default_key = controls.and_then(|controls| controls.default_key(vehicle, action));
+    match input_map {
+        Some(map) => map.keys_or_default(action, default_key),
+        None => default_key.into_iter().collect(),
+    }
+}
+
+/// Zero inside the dead zone, rescaled so the usable range still reaches 1.0
+pub fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value <= dead_zone {
+        0.0
+    } else {
+        ((value - dead_zone) / (1.0 - dead_zone)).min(1.0)
+    }
+}
+
+/// Where the rebound controls of the profile in `profile_dir` are kept
+pub fn bindings_path(profile_dir: &Path) -> PathBuf {
+    profile_dir.join("input_map.ron")
+}
+
+/// Saves the bindings after an edit from the controls screen
+pub fn save_input_map(profile_dir: &Path, input_map: &InputMap) {
+    let path = bindings_path(profile_dir);
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .map_err(|e| e.to_string())
+        .and_then(|()| {
+            ron::ser::to_string_pretty(input_map, ron::ser::PrettyConfig::default())
+                .map_err(|e| e.to_string())
+        })
+        .and_then(|contents| std::fs::write(&path, contents).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!("⚠️ Failed to save key bindings to {:?}: {}", path, e);
+    }
+}
+
+pub fn load_input_map(mut commands: Commands, profile: Res<ActiveProfile>) {
+    commands.insert_resource(read_input_map(&profile.data_directory()));
+}
+
+/// Swaps in the bindings of the profile picked in the main menu
+pub fn reload_profile_input_map(
+    mut changed: EventReader<ProfileChangedEvent>,
+    mut commands: Commands,
+    profile: Res<ActiveProfile>,
+) {
+    if changed.read().last().is_some() {
+        commands.insert_resource(read_input_map(&profile.data_directory()));
+    }
+}
+
+fn read_input_map(profile_dir: &Path) -> InputMap {
+    let saved = bindings_path(profile_dir);
+    let path = if saved.is_file() {
+        saved.display().to_string()
+    } else {
+        format!(
+            "{}/config/input_map.ron",
+            crate::util::asset_path::get_assets_base_path()
+        )
+    };
+    let mut input_map = match std::fs::read_to_string(&path) {
+        Ok(content) => match ron::from_str::<InputMap>(&content) {
+            Ok(input_map) => {
+                #[cfg(feature = "debug-ui")]
+                info!("✅ Loaded {} input bindings", input_map.bindings.len());
+                input_map
+            }
+            Err(e) => {
+                error!("Failed to parse input map at '{}': {}", path, e);
+                InputMap::default()
+            }
+        },
+        Err(e) => {
+            info!("ℹ️ No input map found, using default bindings: {}", e);
+            InputMap::default()
+        }
+    };
+    input_map.validate_and_clamp();
+    input_map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::input::VehicleControlsConfig;
+
+    #[test]
+    fn test_dead_zone_rescales_range() {
+        assert_eq!(apply_dead_zone(0.1, 0.2), 0.0);
+        assert_eq!(apply_dead_zone(-0.8, 0.2), 0.0);
+        assert!((apply_dead_zone(0.6, 0.2) - 0.5).abs() < 1e-6);
+        assert_eq!(apply_dead_zone(1.0, 0.2), 1.0);
+    }
+
+    #[test]
+    fn test_rebind_key_displaces_previous_owner() {
+        let mut map = InputMap::default();
+        assert_eq!(map.keys_for(&AssetControlAction::Forward), None);
+
+        map.rebind_key(AssetControlAction::Forward, KeyCode::KeyW);
+        let displaced = map.rebind_key(AssetControlAction::Brake, KeyCode::KeyW);
+
+        assert_eq!(displaced, Some(AssetControlAction::Forward));
+        assert_eq!(map.keys_for(&AssetControlAction::Forward), None);
+        assert_eq!(
+            map.keys_for(&AssetControlAction::Brake),
+            Some(&[KeyCode::KeyW][..])
+        );
+    }
+
+    #[test]
+    fn test_bound_keys_prefer_the_rebinding_over_the_default() {
+        let controls = LoadedVehicleControls {
+            config: Some(VehicleControlsConfig::default()),
+            loading: false,
+        };
+        let walking = VehicleControlType::Walking;
+        let interact = AssetControlAction::Interact;
+        let mut map = InputMap::default();
+        assert_eq!(
+            bound_keys(Some(&map), Some(&controls), &walking, &interact),
+            vec![KeyCode::KeyF]
+        );
+
+        map.rebind_key(interact, KeyCode::KeyG);
+        assert_eq!(
+            bound_keys(Some(&map), Some(&controls), &walking, &interact),
+            vec![KeyCode::KeyG]
+        );
+    }
+
+    #[test]
+    fn test_input_map_file_parses() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/config/input_map.ron");
+        let content = std::fs::read_to_string(path).unwrap();
+        let map: InputMap = ron::from_str(&content).unwrap();
+        assert!(map.bindings.contains_key(&AssetControlAction::TurnLeft));
+    }
+}
@@ -1,8 +1,13 @@
 // Legacy input modules moved to examples/legacy/
 pub mod asset_based_controls;
+pub mod input_map;
 
 pub use asset_based_controls::{
     LoadedVehicleControls, VehicleControlsConfig, asset_based_input_mapping_system,
     controls_loaded, get_vehicle_control_help, load_vehicle_controls_system,
     process_loaded_controls_system,
 };
+pub use input_map::{
+    ActionBinding, AxisBinding, AxisDirection, InputMap, apply_dead_zone, bound_keys,
+    load_input_map, reload_profile_input_map, save_input_map,
+};
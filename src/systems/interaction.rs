@@ -1,12 +1,15 @@
 #![allow(clippy::too_many_arguments, clippy::type_complexity)]
 use crate::components::water::Yacht;
 use crate::components::{
-    ActiveEntity, Car, ControlState, DockedOnYacht, F16, Helicopter, HumanAnimation, InCar,
-    PendingPhysicsEnable, Player, PlayerControlled, VehicleControlType,
+    ActiveEntity, Car, ControlState, DeckWalker, DockedOnYacht, F16, Helicopter, HumanAnimation,
+    InCar, Interactable, InteractionEvent, InteractionKind, Parachute, PendingPhysicsEnable,
+    Player, PlayerControlled, PlayerOwned, TrainPassenger, VehicleControlType,
 };
-use crate::game_state::GameState;
+use crate::game_state::{GameState, GameStateTransitions};
+use crate::resources::Localization;
 use crate::systems::safe_active_entity::queue_active_transfer;
 use crate::systems::swimming::{ProneRotation, Swimming};
+use crate::t;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
@@ -47,17 +50,107 @@ fn transfer_to_vehicle(
         vehicle_commands.insert(PlayerControlled);
     }
 
-    vehicle_commands.insert(vehicle_type);
+    vehicle_commands.insert((vehicle_type, PlayerOwned));
 
     // Store vehicle reference
     commands.entity(player_entity).insert(InCar(vehicle_entity));
 }
 
+/// Makes every vehicle `Interactable`, with reach growing with its size
+pub fn sync_vehicle_interactables(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    vehicles: Query<
+        (Entity, Has<Car>, Has<Helicopter>, Has<F16>),
+        Or<(With<Car>, With<Helicopter>, With<F16>, With<Yacht>)>,
+    >,
+    added: Query<(), Or<(Added<Car>, Added<Helicopter>, Added<F16>, Added<Yacht>)>>,
+) {
+    let refresh = localization.is_changed();
+    if !refresh && added.is_empty() {
+        return;
+    }
+    for (entity, car, helicopter, f16) in &vehicles {
+        if !refresh && !added.contains(entity) {
+            continue;
+        }
+        let (vehicle_type, radius, prompt) = if car {
+            (VehicleControlType::Car, 3.0, "interact.enter_vehicle")
+        } else if helicopter {
+            (VehicleControlType::Helicopter, 5.0, "interact.enter_vehicle")
+        } else if f16 {
+            (VehicleControlType::F16, 8.0, "interact.enter_vehicle")
+        } else {
+            (VehicleControlType::Yacht, 35.0, "interact.take_helm")
+        };
+        commands.entity(entity).insert(Interactable::new(
+            InteractionKind::EnterVehicle(vehicle_type),
+            radius,
+            t!(prompt),
+        ));
+    }
+}
+
+/// Puts the walking player into the vehicle they used
+pub fn vehicle_entry_system(
+    mut commands: Commands,
+    mut state: GameStateTransitions,
+    time: Res<Time>,
+    mut interactions: EventReader<InteractionEvent>,
+    players: Query<
+        (
+            Option<&ControlState>,
+            Option<&PlayerControlled>,
+            Option<&DeckWalker>,
+        ),
+        With<Player>,
+    >,
+    docked: Query<(), With<DockedOnYacht>>,
+) {
+    for interaction in interactions.read() {
+        let InteractionKind::EnterVehicle(vehicle_type) = interaction.kind else {
+            continue;
+        };
+        let Ok((control_state, player_controlled, deck)) = players.get(interaction.actor) else {
+            continue;
+        };
+        // Taking the helm of the yacht underfoot is yacht_board_from_deck_system's job
+        if deck.is_some_and(|deck| deck.yacht == interaction.target) {
+            continue;
+        }
+        if docked.contains(interaction.target) {
+            info!(
+                "ENTERING: Player entering DOCKED helicopter {:?} (from walking) - will stay docked until lift-off",
+                interaction.target
+            );
+            // DO NOT UNDOCK YET - Wait for input (Lift Off)
+        }
+
+        transfer_to_vehicle(
+            &mut commands,
+            interaction.actor,
+            interaction.target,
+            control_state,
+            player_controlled,
+            vehicle_type,
+            vehicle_type.name(),
+            &time,
+        );
+        let next = match vehicle_type {
+            VehicleControlType::Helicopter => GameState::Flying,
+            VehicleControlType::F16 => GameState::Jetting,
+            _ => GameState::Driving,
+        };
+        if let Err(error) = state.set(next) {
+            warn!("GameState change refused: {error}");
+        }
+    }
+}
+
 pub fn interaction_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
-    mut state: ResMut<NextState<GameState>>,
-    current_state: Res<State<GameState>>,
+    mut state: GameStateTransitions,
     time: Res<Time>,
     mut player_query: Query<
         (
@@ -75,25 +168,12 @@ pub fn interaction_system(
             Without<Helicopter>,
             Without<F16>,
             Without<Yacht>,
+            Without<Parachute>,
+            Without<TrainPassenger>,
         ),
     >,
     active_control_query: Query<&ControlState, With<ActiveEntity>>,
-    // Unified vehicle query: single pass to check all vehicle types with Optional markers
-    unified_vehicle_query: Query<
-        (
-            Entity,
-            &GlobalTransform,
-            Option<&Car>,
-            Option<&Helicopter>,
-            Option<&F16>,
-            Option<&Yacht>,
-            Option<&DockedOnYacht>,
-        ),
-        (
-            Or<(With<Car>, With<Helicopter>, With<F16>, With<Yacht>)>,
-            Without<Player>,
-        ),
-    >,
+    yacht_query: Query<(Entity, &GlobalTransform), (With<Yacht>, Without<Player>)>,
     // Legacy queries kept for exit logic (needed to fetch specific active vehicle transforms)
     car_query: Query<(Entity, &GlobalTransform, Option<&Velocity>), (With<Car>, Without<Player>)>,
     helicopter_query: Query<
@@ -117,121 +197,7 @@ pub fn interaction_system(
         return;
     }
 
-    match **current_state {
-        GameState::Walking => {
-            // Try to enter vehicle (car or helicopter)
-            let Ok((
-                player_entity,
-                player_transform,
-                _,
-                control_state,
-                player_controlled,
-                _vehicle_control_type,
-                _human_animation,
-            )) = player_query.single_mut()
-            else {
-                return;
-            };
-
-            // OPTIMIZED: Priority-based collection with distance_squared (3-4x faster)
-            // Collect best match for each vehicle type, then check priority order
-            // Using squared distances avoids expensive sqrt: 3.0² = 9.0, 5.0² = 25.0, 8.0² = 64.0, 35.0² = 1225.0
-            let mut best_car: Option<(Entity, f32)> = None;
-            let mut best_helicopter: Option<(Entity, f32, GlobalTransform, Option<DockedOnYacht>)> =
-                None;
-            let mut best_f16: Option<(Entity, f32)> = None;
-            let mut best_yacht: Option<(Entity, f32)> = None;
-
-            for (entity, gt, car, helicopter, f16, yacht, docked) in unified_vehicle_query.iter() {
-                let dist_sq = player_transform
-                    .translation
-                    .distance_squared(gt.translation());
-
-                // Collect best match for each vehicle type
-                if car.is_some() && dist_sq < 9.0 {
-                    if best_car.is_none_or(|(_, d)| dist_sq < d) {
-                        best_car = Some((entity, dist_sq));
-                    }
-                } else if helicopter.is_some() && dist_sq < 25.0 {
-                    let is_closer = match &best_helicopter {
-                        None => true,
-                        Some((_, best_dist, _, _)) => dist_sq < *best_dist,
-                    };
-                    if is_closer {
-                        best_helicopter = Some((entity, dist_sq, *gt, docked.cloned()));
-                    }
-                } else if f16.is_some() && dist_sq < 64.0 {
-                    if best_f16.is_none_or(|(_, d)| dist_sq < d) {
-                        best_f16 = Some((entity, dist_sq));
-                    }
-                } else if yacht.is_some()
-                    && dist_sq < 1225.0
-                    && best_yacht.is_none_or(|(_, d)| dist_sq < d)
-                {
-                    best_yacht = Some((entity, dist_sq));
-                }
-            }
-
-            // Check priority order: car > helicopter > f16 > yacht
-            if let Some((entity, _)) = best_car {
-                transfer_to_vehicle(
-                    &mut commands,
-                    player_entity,
-                    entity,
-                    control_state,
-                    player_controlled,
-                    VehicleControlType::Car,
-                    "Car",
-                    &time,
-                );
-                state.set(GameState::Driving);
-            } else if let Some((entity, _, _gt, docked_opt)) = best_helicopter {
-                if let Some(_docked) = docked_opt {
-                    info!(
-                        "ENTERING: Player entering DOCKED helicopter {:?} (from walking) - will stay docked until lift-off",
-                        entity
-                    );
-                    // DO NOT UNDOCK YET - Wait for input (Lift Off)
-                }
-
-                transfer_to_vehicle(
-                    &mut commands,
-                    player_entity,
-                    entity,
-                    control_state,
-                    player_controlled,
-                    VehicleControlType::Helicopter,
-                    "Helicopter",
-                    &time,
-                );
-                state.set(GameState::Flying);
-            } else if let Some((entity, _)) = best_f16 {
-                transfer_to_vehicle(
-                    &mut commands,
-                    player_entity,
-                    entity,
-                    control_state,
-                    player_controlled,
-                    VehicleControlType::F16,
-                    "F16",
-                    &time,
-                );
-                state.set(GameState::Jetting);
-            } else if let Some((yacht_entity, _)) = best_yacht {
-                transfer_to_vehicle(
-                    &mut commands,
-                    player_entity,
-                    yacht_entity,
-                    control_state,
-                    player_controlled,
-                    VehicleControlType::Yacht,
-                    "Yacht",
-                    &time,
-                );
-
-                state.set(GameState::Driving);
-            }
-        }
+    match state.current() {
         GameState::Swimming => {
             // Try to enter yacht from swimming
             let Ok((
@@ -247,12 +213,11 @@ pub fn interaction_system(
                 return;
             };
 
-            // OPTIMIZED: Use unified query with distance_squared for yachts
+            // OPTIMIZED: distance_squared for yachts
             // Find closest yacht first to prevent multiple transfer requests
-            let closest_yacht = unified_vehicle_query
+            let closest_yacht = yacht_query
                 .iter()
-                .filter(|(_, _, _, _, _, yacht, _)| yacht.is_some())
-                .filter_map(|(entity, gt, _, _, _, _, _)| {
+                .filter_map(|(entity, gt)| {
                     let dist_sq = player_transform
                         .translation
                         .distance_squared(gt.translation());
@@ -302,7 +267,9 @@ pub fn interaction_system(
                 yacht_commands.insert(VehicleControlType::Yacht);
                 commands.entity(player_entity).insert(InCar(entity));
 
-                state.set(GameState::Driving);
+                if let Err(error) = state.set(GameState::Driving) {
+                    warn!("GameState change refused: {error}");
+                }
             }
         }
         GameState::Driving => {
@@ -359,7 +326,9 @@ pub fn interaction_system(
                         // DO NOT remove RigidBodyDisabled here; will be done next frame
 
                         // ONLY set Walking state if we actually exited a car
-                        state.set(GameState::Walking);
+                        if let Err(error) = state.set(GameState::Walking) {
+                            warn!("GameState change refused: {error}");
+                        }
                     }
                 }
                 // If active_car exists but isn't a Car (e.g., it's a Yacht), do nothing here
@@ -433,7 +402,9 @@ pub fn interaction_system(
                     }
 
                     // Switch to walking state
-                    state.set(GameState::Walking);
+                    if let Err(error) = state.set(GameState::Walking) {
+                        warn!("GameState change refused: {error}");
+                    }
                 }
             }
         }
@@ -496,9 +467,13 @@ pub fn interaction_system(
                     }
 
                     // Switch to walking state
-                    state.set(GameState::Walking);
+                    if let Err(error) = state.set(GameState::Walking) {
+                        warn!("GameState change refused: {error}");
+                    }
                 }
             }
         }
+        // The pause menu has the keyboard
+        GameState::Walking | GameState::Paused => {}
     }
 }
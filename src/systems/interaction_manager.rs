@@ -0,0 +1,226 @@
+//! Decides what the interact key does.
+//!
+//! Vehicles, doors, shops, properties, garage pads, trains and NPCs to talk
+//! to carry an `Interactable` instead of each checking on their own whether
+//! the player is close. Every frame the
+//! walking player's focus becomes the candidate in reach and not behind them
+//! with the highest priority, the nearest one among equals, and the prompt
+//! shows only that; a train passenger can only use the car they ride in.
+//! Pressing interact sends one `InteractionEvent` for the focus and uses up
+//! the press, so nothing else nearby reacts to it as well.
+
+use crate::components::{
+    ActiveEntity, ControlState, DeckWalker, Interactable, InteractionEvent, InteractionFocus,
+    Player, TrainPassenger, VehicleControlType,
+};
+use crate::systems::input::asset_based_controls::AssetControlAction;
+use crate::systems::input::{InputMap, LoadedVehicleControls, bound_keys};
+use bevy::prelude::*;
+
+/// Closer than this, an interactable counts whichever way the player faces
+const CLOSE_RANGE: f32 = 2.0;
+/// Dot product of the player's facing and the direction to an interactable
+/// further out; anything not clearly behind them is in view
+const MIN_FACING: f32 = -0.25;
+
+/// The candidate the interact key should use. `aboard` is the entity the
+/// player stands on, which is always in reach.
+pub fn pick_focus<'a>(
+    position: Vec3,
+    facing: Vec3,
+    aboard: Option<Entity>,
+    candidates: impl IntoIterator<Item = (Entity, Vec3, &'a Interactable)>,
+) -> Option<Entity> {
+    let facing = facing.xz().normalize_or_zero();
+    candidates
+        .into_iter()
+        .filter_map(|(entity, at, interactable)| {
+            if aboard == Some(entity) {
+                return Some((entity, interactable.priority, 0.0));
+            }
+            let offset = at - position;
+            let distance = if interactable.planar {
+                offset.xz().length()
+            } else {
+                offset.length()
+            };
+            if distance > interactable.radius {
+                return None;
+            }
+            let in_view = distance <= CLOSE_RANGE
+                || offset.xz().normalize_or_zero().dot(facing) >= MIN_FACING;
+            in_view.then_some((entity, interactable.priority, distance))
+        })
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.2.total_cmp(&a.2)))
+        .map(|(entity, ..)| entity)
+}
+
+#[allow(clippy::type_complexity)]
+pub fn interaction_focus_system(
+    mut focus: ResMut<InteractionFocus>,
+    player: Query<
+        (
+            &GlobalTransform,
+            Option<&DeckWalker>,
+            Option<&TrainPassenger>,
+        ),
+        (With<Player>, With<ActiveEntity>),
+    >,
+    interactables: Query<(Entity, &GlobalTransform, &Interactable)>,
+) {
+    let target = player
+        .single()
+        .ok()
+        .and_then(|(transform, deck, passenger)| {
+            if let Some(passenger) = passenger {
+                return interactables
+                    .contains(passenger.car)
+                    .then_some(passenger.car);
+            }
+            pick_focus(
+                transform.translation(),
+                transform.forward().into(),
+                deck.map(|deck| deck.yacht),
+                interactables
+                    .iter()
+                    .map(|(entity, at, interactable)| (entity, at.translation(), interactable)),
+            )
+        });
+    focus.set_if_neq(InteractionFocus(target));
+}
+
+/// Sends the event for the focused interactable on the interact key
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn dispatch_interaction_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    input_map: Option<Res<InputMap>>,
+    controls: Option<Res<LoadedVehicleControls>>,
+    gamepads: Query<&Gamepad>,
+    focus: Res<InteractionFocus>,
+    interactables: Query<&Interactable>,
+    mut player: Query<(Entity, Option<&mut ControlState>), (With<Player>, With<ActiveEntity>)>,
+    mut events: EventWriter<InteractionEvent>,
+) {
+    let Some((target, interactable)) = focus
+        .0
+        .and_then(|target| Some((target, interactables.get(target).ok()?)))
+    else {
+        return;
+    };
+    let Ok((actor, control)) = player.single_mut() else {
+        return;
+    };
+    let interact = match &control {
+        Some(control) => control.interact,
+        // No mapped control state yet, so read the binding directly
+        None => {
+            let action = AssetControlAction::Interact;
+            let bound = bound_keys(
+                input_map.as_deref(),
+                controls.as_deref(),
+                &VehicleControlType::Walking,
+                &action,
+            );
+            keys.any_just_pressed(bound)
+                || input_map.as_deref().is_some_and(|map| {
+                    gamepads
+                        .iter()
+                        .any(|gamepad| map.gamepad_just_pressed(&action, gamepad))
+                })
+        }
+    };
+    if !interact {
+        return;
+    }
+    events.write(InteractionEvent {
+        actor,
+        target,
+        kind: interactable.kind,
+    });
+    if let Some(mut control) = control {
+        control.interact = false;
+    }
+}
+
+pub fn clear_interaction_focus(mut focus: ResMut<InteractionFocus>) {
+    focus.0 = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{InteractionKind, VehicleControlType};
+
+    #[test]
+    fn test_priority_beats_distance_and_distance_breaks_ties() {
+        let shop = Interactable::new(InteractionKind::Shop, 3.0, "shop");
+        let car = Interactable::new(
+            InteractionKind::EnterVehicle(VehicleControlType::Car),
+            3.0,
+            "car",
+        );
+        let [a, b, c] = [
+            Entity::from_raw(1),
+            Entity::from_raw(2),
+            Entity::from_raw(3),
+        ];
+        let forward = Vec3::NEG_Z;
+
+        // The shop counter further away still wins over the car at the feet
+        let candidates = [
+            (a, Vec3::new(0.0, 0.0, -1.0), &car),
+            (b, Vec3::new(0.0, 0.0, -2.5), &shop),
+        ];
+        assert_eq!(pick_focus(Vec3::ZERO, forward, None, candidates), Some(b));
+        // Between two cars the nearer one
+        let candidates = [
+            (a, Vec3::new(0.0, 0.0, -2.5), &car),
+            (c, Vec3::new(1.0, 0.0, -1.0), &car),
+        ];
+        assert_eq!(pick_focus(Vec3::ZERO, forward, None, candidates), Some(c));
+        // Out of reach
+        let candidates = [(a, Vec3::new(0.0, 0.0, -3.5), &car)];
+        assert_eq!(pick_focus(Vec3::ZERO, forward, None, candidates), None);
+    }
+
+    #[test]
+    fn test_things_behind_the_player_are_ignored_unless_close_or_aboard() {
+        let yacht = Interactable::new(
+            InteractionKind::EnterVehicle(VehicleControlType::Yacht),
+            35.0,
+            "helm",
+        );
+        let pad = Interactable::new(InteractionKind::Property, 4.0, "buy").planar();
+        let [a, b] = [Entity::from_raw(1), Entity::from_raw(2)];
+        let forward = Vec3::NEG_Z;
+
+        assert_eq!(
+            pick_focus(
+                Vec3::ZERO,
+                forward,
+                None,
+                [(a, Vec3::new(0.0, 0.0, 10.0), &yacht)]
+            ),
+            None
+        );
+        assert_eq!(
+            pick_focus(
+                Vec3::ZERO,
+                forward,
+                Some(a),
+                [(a, Vec3::new(0.0, 0.0, 10.0), &yacht)]
+            ),
+            Some(a)
+        );
+        // Standing on the pad, well above its origin and facing away
+        assert_eq!(
+            pick_focus(
+                Vec3::new(0.0, 5.0, 0.5),
+                forward,
+                None,
+                [(b, Vec3::ZERO, &pad)]
+            ),
+            Some(b)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Entering and leaving building interiors.
+//!
+//! A street door and the exit door of its interior form a portal pair: using
+//! one moves the player to the other side. The door is the only opening
+//! between the two spaces, so while the player is on the interior side the
+//! exterior can't be seen and its rendering is suspended until they step back
+//! out.
+
+use crate::components::unified_water::UnifiedWaterBody;
+use crate::components::{
+    ActiveInterior, CurrentInterior, DynamicContent, EnterableDoor, ExteriorCulled, HlodProxy,
+    Interactable, InteractionEvent, InteractionKind, InteriorExit, InteriorScene, Player,
+};
+use crate::config::GameConfig;
+use crate::factories::{INTERIOR_ALTITUDE, InteriorFactory};
+use crate::resources::Localization;
+use crate::systems::world::unified_world::UnifiedChunkEntity;
+use crate::t;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+/// Players step out this far in front of the street door
+const DOOR_EXIT_OFFSET: f32 = 1.5;
+
+type DoorUserQuery<'w, 's> =
+    Query<'w, 's, (&'static mut Transform, Option<&'static mut Velocity>), With<Player>>;
+
+type ExteriorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Visibility),
+    (
+        Or<(
+            With<DynamicContent>,
+            With<UnifiedChunkEntity>,
+            With<UnifiedWaterBody>,
+            With<HlodProxy>,
+        )>,
+        Without<ExteriorCulled>,
+        Without<Player>,
+        Without<InteriorScene>,
+    ),
+>;
+
+/// Makes street doors and interior exits `Interactable`, again when the
+/// language or the use radius changes
+pub fn sync_door_interactables(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    localization: Res<Localization>,
+    doors: Query<(Entity, Ref<EnterableDoor>)>,
+    exits: Query<(Entity, Ref<InteriorExit>)>,
+) {
+    let refresh = config.is_changed() || localization.is_changed();
+    let radius = config.world.door_use_radius;
+    for (entity, door) in &doors {
+        if refresh || door.is_added() {
+            commands.entity(entity).insert(Interactable::new(
+                InteractionKind::Door,
+                radius,
+                t!("interior.enter_prompt"),
+            ));
+        }
+    }
+    for (entity, exit) in &exits {
+        if refresh || exit.is_added() {
+            commands.entity(entity).insert(Interactable::new(
+                InteractionKind::ExitInterior,
+                radius,
+                t!("interior.exit_prompt"),
+            ));
+        }
+    }
+}
+
+/// Moves the player through the door they used
+pub fn interior_door_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut interactions: EventReader<InteractionEvent>,
+    mut current: ResMut<CurrentInterior>,
+    mut player: DoorUserQuery,
+    doors: Query<(&GlobalTransform, &EnterableDoor)>,
+) {
+    for interaction in interactions.read() {
+        let Ok((mut transform, velocity)) = player.get_mut(interaction.actor) else {
+            continue;
+        };
+        match interaction.kind {
+            InteractionKind::ExitInterior => {
+                let Some(active) = current.0.take() else {
+                    continue;
+                };
+                commands.entity(active.scene).despawn();
+                transform.translation = active.return_position;
+                transform.rotation = active.return_rotation;
+            }
+            InteractionKind::Door if current.0.is_none() => {
+                let Ok((door, entry)) = doors.get(interaction.target) else {
+                    continue;
+                };
+                let door_position = door.translation();
+                let outward = door.back();
+                let origin = Vec3::new(door_position.x, INTERIOR_ALTITUDE, door_position.z);
+                let layout = InteriorFactory::new(&mut materials).spawn_interior(
+                    &mut commands,
+                    &mut meshes,
+                    entry.kind,
+                    entry.floors,
+                    origin,
+                );
+
+                current.0 = Some(ActiveInterior {
+                    scene: layout.root,
+                    return_position: door_position + outward * DOOR_EXIT_OFFSET,
+                    return_rotation: Transform::default().looking_to(outward, Vec3::Y).rotation,
+                });
+                // Face into the room, away from the exit door
+                transform.translation = layout.entry;
+                transform.rotation = Quat::IDENTITY;
+            }
+            _ => continue,
+        }
+        if let Some(mut velocity) = velocity {
+            *velocity = Velocity::zero();
+        }
+    }
+}
+
+/// Hides the exterior while the player is inside and restores it when they leave
+pub fn exterior_culling_system(
+    mut commands: Commands,
+    current: Res<CurrentInterior>,
+    exterior: ExteriorQuery,
+    mut culled: Query<(Entity, &ExteriorCulled, &mut Visibility)>,
+) {
+    if current.0.is_some() {
+        // Also catches chunks streamed in while inside
+        for (entity, visibility) in &exterior {
+            commands
+                .entity(entity)
+                .insert((ExteriorCulled(*visibility), Visibility::Hidden));
+        }
+    } else {
+        for (entity, saved, mut visibility) in &mut culled {
+            *visibility = saved.0;
+            commands.entity(entity).remove::<ExteriorCulled>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::ContentType;
+
+    #[test]
+    fn test_exterior_hidden_inside_and_restored_outside() {
+        let mut app = App::new();
+        app.init_resource::<CurrentInterior>()
+            .add_systems(Update, exterior_culling_system);
+
+        let building = app
+            .world_mut()
+            .spawn((
+                DynamicContent {
+                    content_type: ContentType::Building,
+                },
+                Visibility::Visible,
+            ))
+            .id();
+        let scene = app.world_mut().spawn(Visibility::Inherited).id();
+
+        app.world_mut().resource_mut::<CurrentInterior>().0 = Some(ActiveInterior {
+            scene,
+            return_position: Vec3::ZERO,
+            return_rotation: Quat::IDENTITY,
+        });
+        app.update();
+        assert_eq!(
+            app.world().get::<Visibility>(building),
+            Some(&Visibility::Hidden)
+        );
+
+        app.world_mut().resource_mut::<CurrentInterior>().0 = None;
+        app.update();
+        assert_eq!(
+            app.world().get::<Visibility>(building),
+            Some(&Visibility::Visible)
+        );
+        assert!(app.world().get::<ExteriorCulled>(building).is_none());
+    }
+}
@@ -0,0 +1,329 @@
+//! Time of day, car lamps and street lamps.
+//!
+//! `WorldClock` runs through a day every `lighting.day_length_minutes` and the
+//! sun is placed for its hour. Lamps come on when the sun's illuminance drops below
+//! `lighting.night_illuminance`. Every car carries two headlight spotlights and
+//! two taillight point lights, hidden until needed; taillights also brighten
+//! with the brake input, day or night. Streetlights are instanced props with no
+//! entities of their own, so a fixed pool of `StreetLamp` point lights is moved
+//! onto the nearest lamp heads instead.
+//!
+//! Bevy's clustered forward renderer shades every visible light, so both kinds
+//! are kept to a budget: only the cars and lamp heads nearest the camera are
+//! lit, and everything else is hidden rather than set to zero intensity, which
+//! keeps it out of the clusters altogether.
+
+use crate::components::{
+    ActiveEntity, CarAlarm, ControlState, NightLights, StreetLamp, VehicleLamp, VehicleLights,
+};
+use crate::config::{GameConfig, LightingConfig};
+use crate::resources::WorldClock;
+use crate::systems::rendering::{InstancedBatcher, Streetlight, instance_kept};
+use crate::systems::world::chunk_streaming::StreamingFocus;
+use crate::systems::world::unified_world::{ChunkCoord, UnifiedWorldManager};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+/// Lamp head of a streetlight relative to the instance transform, just
+/// under the lamp mesh
+const LAMP_HEAD_OFFSET: Vec3 = Vec3::new(0.0, 5.6, -1.4);
+
+/// Seconds between re-picking the lit street lamps
+const STREET_LAMP_INTERVAL: f32 = 0.25;
+
+/// Sun illuminance at noon and with the sun down
+pub const NOON_LUX: f32 = 25_000.0;
+pub const NIGHT_LUX: f32 = 400.0;
+
+/// Sun orientation and illuminance for an hour of the day; rises at 6, sets at 18
+pub fn sun_for_hour(hour: f32) -> (Quat, f32) {
+    let day = ((hour - 6.0) / 12.0 * PI).sin();
+    // Keep a low angle at night so shadows stay sane under the dim moon-ish light
+    let elevation = day.max(0.15) * 70f32.to_radians();
+    let azimuth = (hour / 24.0) * 2.0 * PI;
+    let rotation = Quat::from_euler(EulerRot::YXZ, azimuth, -elevation, 0.0);
+    let illuminance = NIGHT_LUX + (NOON_LUX - NIGHT_LUX) * day.max(0.0);
+    (rotation, illuminance)
+}
+
+/// Keeps the `budget` items nearest `origin`, nearest first
+pub fn nearest_within_budget<T>(
+    items: impl IntoIterator<Item = (Vec3, T)>,
+    origin: Vec3,
+    budget: usize,
+) -> Vec<T> {
+    let mut items: Vec<(f32, T)> = items
+        .into_iter()
+        .map(|(position, item)| (position.distance_squared(origin), item))
+        .collect();
+    if budget == 0 {
+        return Vec::new();
+    }
+    if items.len() > budget {
+        items.select_nth_unstable_by(budget - 1, |a, b| a.0.total_cmp(&b.0));
+        items.truncate(budget);
+    }
+    items.sort_by(|a, b| a.0.total_cmp(&b.0));
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Taillight intensity for the headlights being on and `brake` in 0..=1
+pub fn taillight_intensity(settings: &LightingConfig, headlights: bool, brake: f32) -> f32 {
+    let base = if headlights {
+        settings.taillight_intensity
+    } else {
+        0.0
+    };
+    base + (settings.brake_light_intensity - base).max(0.0) * brake.clamp(0.0, 1.0)
+}
+
+pub fn start_world_clock(config: Res<GameConfig>, mut clock: ResMut<WorldClock>) {
+    *clock = WorldClock::at(config.lighting.start_hour);
+}
+
+pub fn advance_world_clock(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut clock: ResMut<WorldClock>,
+) {
+    if config.lighting.day_length_minutes > 0.0 {
+        clock.advance(time.delta_secs(), config.lighting.day_length_minutes);
+    }
+}
+
+/// Places the sun for the clock's hour whenever the clock moves
+pub fn sync_sun_to_clock(
+    clock: Res<WorldClock>,
+    mut suns: Query<(&mut DirectionalLight, &mut Transform)>,
+) {
+    if !clock.is_changed() {
+        return;
+    }
+    let (rotation, illuminance) = sun_for_hour(clock.hour);
+    for (mut light, mut transform) in &mut suns {
+        light.illuminance = illuminance;
+        transform.rotation = rotation;
+    }
+}
+
+pub fn update_night_lights(
+    config: Res<GameConfig>,
+    suns: Query<&DirectionalLight>,
+    mut lights: ResMut<NightLights>,
+) {
+    let Some(illuminance) = suns.iter().map(|sun| sun.illuminance).reduce(f32::max) else {
+        return;
+    };
+    let night = illuminance < config.lighting.night_illuminance;
+    if lights.night != night {
+        lights.night = night;
+        info!("💡 Lamps {}", if night { "on" } else { "off" });
+    }
+}
+
+pub fn toggle_headlights(mut cars: Query<(&ControlState, &mut VehicleLights), With<ActiveEntity>>) {
+    for (control_state, mut lights) in &mut cars {
+        if control_state.toggle_headlights {
+            lights.mode = lights.mode.next();
+            info!("💡 Headlights: {:?}", lights.mode);
+        }
+    }
+}
+
+/// Lights the lamps of the cars nearest the camera and hides the rest; a car
+/// alarm going off flashes them
+#[allow(clippy::type_complexity)]
+pub fn update_vehicle_lights(
+    config: Res<GameConfig>,
+    night: Res<NightLights>,
+    focus: Res<StreamingFocus>,
+    cars: Query<(
+        Entity,
+        &GlobalTransform,
+        &VehicleLights,
+        Option<&ControlState>,
+        Option<&CarAlarm>,
+        &Children,
+    )>,
+    mut lamps: Query<(
+        &VehicleLamp,
+        &mut Visibility,
+        Option<&mut SpotLight>,
+        Option<&mut PointLight>,
+    )>,
+) {
+    let settings = &config.lighting;
+    let (budget, _) = settings.budgets(config.graphics.quality.settings().light_budget);
+
+    let wanted = cars
+        .iter()
+        .filter_map(|(entity, transform, lights, control, alarm, _)| {
+            let headlights = match alarm.filter(|alarm| alarm.sounding()) {
+                Some(alarm) => alarm.flash_on(),
+                None => lights.mode.is_on(night.night),
+            };
+            let brake = control.map_or(0.0, |control| {
+                if control.emergency_brake {
+                    1.0
+                } else {
+                    control.brake
+                }
+            });
+            (headlights || brake > 0.0)
+                .then_some((transform.translation(), (entity, (headlights, brake))))
+        });
+    let lit: HashMap<Entity, (bool, f32)> = nearest_within_budget(wanted, focus.position, budget)
+        .into_iter()
+        .collect();
+
+    for (entity, _, _, _, _, children) in &cars {
+        let (headlights, brake) = lit.get(&entity).copied().unwrap_or_default();
+        let mut car_lamps = lamps.iter_many_mut(children);
+        while let Some((lamp, mut visibility, spot, point)) = car_lamps.fetch_next() {
+            let intensity = match lamp {
+                VehicleLamp::Head if headlights => settings.headlight_intensity,
+                VehicleLamp::Head => 0.0,
+                VehicleLamp::Tail => taillight_intensity(settings, headlights, brake),
+            };
+            visibility.set_if_neq(if intensity > 0.0 {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+            if let Some(mut spot) = spot
+                && (spot.intensity != intensity || spot.range != settings.headlight_range)
+            {
+                spot.intensity = intensity;
+                spot.range = settings.headlight_range;
+            }
+            if let Some(mut point) = point
+                && point.intensity != intensity
+            {
+                point.intensity = intensity;
+            }
+        }
+    }
+}
+
+/// Moves the `StreetLamp` pool onto the lamp heads nearest the camera, a few
+/// times a second; the pool grows and shrinks with the budget
+#[allow(clippy::too_many_arguments)]
+pub fn update_street_lamps(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut since_update: Local<f32>,
+    config: Res<GameConfig>,
+    night: Res<NightLights>,
+    focus: Res<StreamingFocus>,
+    world_manager: Res<UnifiedWorldManager>,
+    streetlights: Res<InstancedBatcher<Streetlight>>,
+    mut lamps: Query<(Entity, &mut Transform, &mut Visibility, &mut PointLight), With<StreetLamp>>,
+) {
+    *since_update += time.delta_secs();
+    if *since_update < STREET_LAMP_INTERVAL && !night.is_changed() && !config.is_changed() {
+        return;
+    }
+    *since_update = 0.0;
+
+    let settings = &config.lighting;
+    let (_, budget) = settings.budgets(config.graphics.quality.settings().light_budget);
+    let heads = if night.night {
+        let chunk_size = world_manager.chunk_size;
+        let center = ChunkCoord::from_world_pos(focus.position, chunk_size);
+        let reach = (settings.street_lamp_distance / chunk_size).ceil() as i32;
+        let max_distance_sq = settings.street_lamp_distance * settings.street_lamp_distance;
+        let density = streetlights.density();
+        let candidates = (-reach..=reach)
+            .flat_map(|dz| (-reach..=reach).map(move |dx| (dx, dz)))
+            .flat_map(|(dx, dz)| {
+                streetlights.instances(ChunkCoord::new(center.x + dx, center.z + dz))
+            })
+            .filter(|instance| instance_kept(instance.translation, density))
+            .map(|instance| instance.transform_point(LAMP_HEAD_OFFSET))
+            .filter(|head| head.distance_squared(focus.position) <= max_distance_sq)
+            .map(|head| (head, head));
+        nearest_within_budget(candidates, focus.position, budget)
+    } else {
+        Vec::new()
+    };
+
+    let mut heads = heads.into_iter();
+    let mut pooled = 0;
+    for (entity, mut transform, mut visibility, mut light) in &mut lamps {
+        pooled += 1;
+        if pooled > budget {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        match heads.next() {
+            Some(head) => {
+                transform.translation = head;
+                visibility.set_if_neq(Visibility::Inherited);
+                if light.intensity != settings.street_lamp_intensity
+                    || light.range != settings.street_lamp_range
+                {
+                    light.intensity = settings.street_lamp_intensity;
+                    light.range = settings.street_lamp_range;
+                }
+            }
+            None => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+    for head in heads {
+        commands.spawn((
+            StreetLamp,
+            PointLight {
+                color: Color::srgb(1.0, 0.85, 0.6),
+                intensity: settings.street_lamp_intensity,
+                range: settings.street_lamp_range,
+                radius: 0.2,
+                shadows_enabled: false,
+                ..default()
+            },
+            Transform::from_translation(head),
+            Name::new("Street Lamp"),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sun_is_higher_and_brighter_at_noon() {
+        let (noon, noon_lux) = sun_for_hour(12.0);
+        let (morning, morning_lux) = sun_for_hour(8.0);
+        let (_, night_lux) = sun_for_hour(0.0);
+        let down = |rotation: Quat| -(rotation * Vec3::NEG_Z).y;
+        assert!(down(noon) > down(morning));
+        assert!(noon_lux > morning_lux && morning_lux > night_lux);
+        assert_eq!(night_lux, NIGHT_LUX);
+    }
+
+    #[test]
+    fn test_budget_keeps_nearest_and_brakes_brighten_taillights() {
+        let points = [8.0, 1.0, 5.0, 3.0, 13.0].map(|x| (Vec3::new(x, 0.0, 0.0), x as i32));
+        assert_eq!(nearest_within_budget(points, Vec3::ZERO, 3), [1, 3, 5]);
+        assert_eq!(nearest_within_budget(points, Vec3::ZERO, 9).len(), 5);
+        assert!(nearest_within_budget(points, Vec3::ZERO, 0).is_empty());
+
+        let settings = LightingConfig::default();
+        assert_eq!(taillight_intensity(&settings, false, 0.0), 0.0);
+        assert_eq!(
+            taillight_intensity(&settings, true, 0.0),
+            settings.taillight_intensity
+        );
+        assert_eq!(
+            taillight_intensity(&settings, false, 1.0),
+            settings.brake_light_intensity
+        );
+        assert_eq!(
+            taillight_intensity(&settings, true, 1.0),
+            settings.brake_light_intensity
+        );
+    }
+}
@@ -1,8 +1,109 @@
+//! Everything loaded behind the splash screen.
+//!
+//! `AssetLoadingState` collects the handles of the files listed in
+//! `config/asset_manifest.ron`, the vehicle specs and the audio clips loaded
+//! by startup systems, and the splash screen shows how many have finished.
+//! Prefab meshes and materials are built into `PrefabAssetCache` up front.
+//! The game moves on to world generation once all of it is done.
+
 use crate::components::vehicles::{SimpleCarSpecs, SimpleF16Specs, SimpleHelicopterSpecs};
 use crate::config::AssetLoadingPolicy;
-use crate::resources::VehicleSpecsAssets;
-use crate::states::AppState;
+use crate::factories::{PrefabAssetCache, PrefabRegistry};
+use crate::resources::{DistrictMap, VehicleSpecsAssets};
+use crate::systems::audio::AudioClips;
+use crate::systems::music::MusicDirector;
+use crate::systems::ui::splash_screen::AssetLoadingState;
 use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Files to preload that nothing else tracks, relative to the assets folder
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssetManifest {
+    pub files: Vec<String>,
+}
+
+impl AssetManifest {
+    pub fn read(base: &str) -> Self {
+        let path = format!("{base}/config/asset_manifest.ron");
+        match std::fs::read_to_string(&path) {
+            Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+                error!("Failed to parse asset manifest at '{}': {}", path, e);
+                AssetManifest::default()
+            }),
+            Err(e) => {
+                info!(
+                    "ℹ️ No asset manifest found, nothing extra to preload: {}",
+                    e
+                );
+                AssetManifest::default()
+            }
+        }
+    }
+}
+
+/// Starts loading the manifest files; runs before `Startup`
+pub fn start_asset_preload(mut loading: ResMut<AssetLoadingState>, asset_server: Res<AssetServer>) {
+    let base = crate::util::asset_path::get_assets_base_path();
+    let manifest = AssetManifest::read(&base);
+    for file in &manifest.files {
+        if std::path::Path::new(&base).join(file).exists() {
+            loading
+                .handles
+                .push(asset_server.load_untyped(file.clone()).untyped());
+        } else {
+            warn!("⚠️ Asset manifest lists '{}' but it doesn't exist", file);
+        }
+    }
+}
+
+/// Adds the assets startup systems began loading and builds every prefab's
+/// meshes and materials, once, on the first loading frame
+#[allow(clippy::too_many_arguments)]
+pub fn track_startup_assets(
+    mut loading: ResMut<AssetLoadingState>,
+    specs: Option<Res<VehicleSpecsAssets>>,
+    audio: Option<Res<AudioClips>>,
+    music: Option<Res<MusicDirector>>,
+    districts: Option<Res<DistrictMap>>,
+    registry: Res<PrefabRegistry>,
+    mut prefab_assets: ResMut<PrefabAssetCache>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if loading.startup_assets_tracked {
+        return;
+    }
+    loading.startup_assets_tracked = true;
+
+    if let Some(specs) = specs {
+        loading.handles.extend([
+            specs.car.clone().untyped(),
+            specs.helicopter.clone().untyped(),
+            specs.f16.clone().untyped(),
+            specs.yacht.clone().untyped(),
+        ]);
+    }
+    let clips = audio.iter().flat_map(|audio| audio.handles());
+    let tracks = music.iter().flat_map(|music| music.track_handles());
+    let ambience = districts
+        .iter()
+        .flat_map(|districts| districts.ambient_clips.values());
+    let audio_handles: Vec<UntypedHandle> = clips
+        .chain(tracks)
+        .chain(ambience)
+        .map(|handle| handle.clone().untyped())
+        .collect();
+    loading.handles.extend(audio_handles);
+
+    for (_, definition) in registry.iter() {
+        prefab_assets.preload(definition, &mut meshes, &mut materials);
+    }
+    info!(
+        "📦 Preloading {} assets, {} prefab meshes and materials built",
+        loading.handles.len(),
+        prefab_assets.len()
+    );
+}
 
 pub fn start_loading_vehicle_specs(mut commands: Commands, asset_server: Res<AssetServer>) {
     #[cfg(feature = "debug-ui")]
@@ -11,15 +112,21 @@ pub fn start_loading_vehicle_specs(mut commands: Commands, asset_server: Res<Ass
     commands.insert_resource(specs);
 }
 
+/// Validates the vehicle specs once loaded, or falls back to defaults if
+/// any failed, then marks them ready for the splash screen
+#[allow(clippy::too_many_arguments)]
 pub fn check_vehicle_specs_loaded(
     specs: Res<VehicleSpecsAssets>,
     asset_server: Res<AssetServer>,
     policy: Res<AssetLoadingPolicy>,
-    mut next_state: ResMut<NextState<AppState>>,
+    mut loading: ResMut<AssetLoadingState>,
     mut car_specs_assets: ResMut<Assets<SimpleCarSpecs>>,
     mut heli_specs_assets: ResMut<Assets<SimpleHelicopterSpecs>>,
     mut f16_specs_assets: ResMut<Assets<SimpleF16Specs>>,
 ) {
+    if loading.vehicle_specs_ready {
+        return;
+    }
     if specs.all_loaded(&asset_server) {
         // Validate loaded specs
         if let Some(car_specs) = car_specs_assets.get_mut(&specs.car) {
@@ -34,7 +141,7 @@ pub fn check_vehicle_specs_loaded(
 
         #[cfg(feature = "debug-ui")]
         info!("✅ All vehicle specs loaded successfully");
-        next_state.set(AppState::WorldGeneration);
+        loading.vehicle_specs_ready = true;
     } else if specs.any_failed(&asset_server) {
         if matches!(
             asset_server.get_load_state(&specs.car),
@@ -83,12 +190,24 @@ pub fn check_vehicle_specs_loaded(
         }
 
         // Continue to world generation with defaults
-        next_state.set(AppState::WorldGeneration);
+        loading.vehicle_specs_ready = true;
     }
 }
 
-pub fn advance_to_ingame(mut next_state: ResMut<NextState<AppState>>) {
-    #[cfg(feature = "debug-ui")]
-    info!("🎮 Starting gameplay");
-    next_state.set(AppState::InGame);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_manifest_lists_existing_files() {
+        let base = concat!(env!("CARGO_MANIFEST_DIR"), "/assets");
+        let manifest = AssetManifest::read(base);
+        assert!(!manifest.files.is_empty());
+        for file in &manifest.files {
+            assert!(
+                std::path::Path::new(base).join(file).exists(),
+                "{file} is missing"
+            );
+        }
+    }
 }
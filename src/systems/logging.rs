@@ -0,0 +1,414 @@
+//! Logging subsystem layered into Bevy's `LogPlugin`.
+//!
+//! `logging_layer` is installed as the plugin's `custom_layer`. It puts a
+//! reloadable `EnvFilter` in front of everything else, so per-module levels can
+//! change while the game runs, and behind it a capture layer that keeps the
+//! most recent lines for the in-game log panel and appends them to a
+//! size-rotated `game.log` under the data directory.
+//!
+//! Levels come from `GameConfig::logging` (`logging.ron`) and can be
+//! overridden per module from the console:
+//! `log set gta_game::systems::traffic debug`. `RUST_LOG`, when set, still
+//! caps what reaches either filter.
+
+use crate::config::{ConfigFieldChanged, ConfigReloadedEvent, GameConfig, LoggingConfig};
+use crate::systems::persistence::data_directory;
+use crate::systems::ui::console::{ConsoleCommands, ConsoleResult};
+use crate::util::rolling_file::RollingFile;
+use bevy::log::BoxedLayer;
+use bevy::log::tracing::field::{Field, Visit};
+use bevy::log::tracing::{Event, Level, Subscriber};
+use bevy::log::tracing_subscriber::filter::LevelFilter;
+use bevy::log::tracing_subscriber::layer::Context;
+use bevy::log::tracing_subscriber::{EnvFilter, Layer, Registry, reload};
+use bevy::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::{self, Write as _};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+const LOG_FILE_NAME: &str = "game.log";
+
+/// Directory the rolling log files are written to
+pub fn log_directory() -> PathBuf {
+    data_directory().join("logs")
+}
+
+/// One captured log event
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// Seconds since logging started
+    pub elapsed: f32,
+    pub level: Level,
+    pub target: String,
+    /// The message followed by any structured fields as `name=value`
+    pub message: String,
+}
+
+impl fmt::Display for LogLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>9.3} {:<5} {}: {}",
+            self.elapsed, self.level, self.target, self.message
+        )
+    }
+}
+
+/// Where captured lines go: a bounded in-memory buffer and, optionally, a file
+#[derive(Debug)]
+pub struct LogSink {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+    /// Lines pushed since startup, so readers can tell when something new arrived
+    total: u64,
+    file: Option<RollingFile>,
+}
+
+impl LogSink {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity),
+            capacity,
+            total: 0,
+            file: None,
+        }
+    }
+
+    pub fn push(&mut self, line: LogLine) {
+        if let Some(file) = &mut self.file
+            && let Err(err) = file.write_line(&line.to_string())
+        {
+            // Logging from inside the logger would deadlock on the sink
+            eprintln!("log file disabled after write error: {err}");
+            self.file = None;
+        }
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.total += 1;
+    }
+
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &LogLine> {
+        self.lines.iter()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.lines.len() > self.capacity {
+            self.lines.pop_front();
+        }
+    }
+
+    pub fn set_file(&mut self, file: Option<RollingFile>) {
+        self.file = file;
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => {
+                let _ = write!(self.message, "{value:?}");
+            }
+            // Metadata `tracing-log` attaches to events bridged from the `log` crate
+            name if name.starts_with("log.") => {}
+            name => {
+                let _ = write!(self.fields, " {name}={value:?}");
+            }
+        }
+    }
+}
+
+struct CaptureLayer {
+    sink: Arc<Mutex<LogSink>>,
+    start: Instant,
+}
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let line = LogLine {
+            elapsed: self.start.elapsed().as_secs_f32(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message + visitor.fields.as_str(),
+        };
+        if let Ok(mut sink) = self.sink.lock() {
+            sink.push(line);
+        }
+    }
+}
+
+/// Joins the configured directives with the per-module overrides; later
+/// directives for the same target win
+pub fn compose_filter(base: &str, overrides: &BTreeMap<String, LevelFilter>) -> String {
+    let mut filter = base.trim().trim_end_matches(',').to_string();
+    for (target, level) in overrides {
+        if !filter.is_empty() {
+            filter.push(',');
+        }
+        let _ = write!(filter, "{target}={level}");
+    }
+    filter
+}
+
+/// Runtime handle on the log filter and the captured lines
+#[derive(Resource)]
+pub struct LogControl {
+    handle: reload::Handle<EnvFilter, Registry>,
+    sink: Arc<Mutex<LogSink>>,
+    base: String,
+    overrides: BTreeMap<String, LevelFilter>,
+}
+
+impl LogControl {
+    pub fn new(
+        handle: reload::Handle<EnvFilter, Registry>,
+        sink: Arc<Mutex<LogSink>>,
+        base: String,
+    ) -> Self {
+        Self {
+            handle,
+            sink,
+            base,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    pub fn sink(&self) -> &Arc<Mutex<LogSink>> {
+        &self.sink
+    }
+
+    /// The directives currently in effect
+    pub fn filter(&self) -> String {
+        compose_filter(&self.base, &self.overrides)
+    }
+
+    fn apply(&self, filter: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(filter).map_err(|err| err.to_string())?;
+        self.handle.reload(filter).map_err(|err| err.to_string())
+    }
+
+    /// Replaces the configured directives, keeping the overrides
+    pub fn set_base(&mut self, base: &str) -> Result<(), String> {
+        self.apply(&compose_filter(base, &self.overrides))?;
+        self.base = base.to_string();
+        Ok(())
+    }
+
+    pub fn set_level(&mut self, target: &str, level: &str) -> ConsoleResult {
+        if target.is_empty() || target.contains([',', '=', '[', ']', ' ']) {
+            return Err(format!("bad module path '{target}'"));
+        }
+        let level: LevelFilter = level
+            .parse()
+            .map_err(|_| format!("bad level '{level}' (off|error|warn|info|debug|trace)"))?;
+        let previous = self.overrides.insert(target.to_string(), level);
+        if let Err(err) = self.apply(&self.filter()) {
+            match previous {
+                Some(previous) => self.overrides.insert(target.to_string(), previous),
+                None => self.overrides.remove(target),
+            };
+            return Err(err);
+        }
+        Ok(format!("{target} = {level}"))
+    }
+
+    /// Drops one override, or all of them without a target
+    pub fn clear(&mut self, target: Option<&str>) -> ConsoleResult {
+        match target {
+            Some(target) if self.overrides.remove(target).is_none() => {
+                return Err(format!("no override for '{target}'"));
+            }
+            Some(_) => {}
+            None => self.overrides.clear(),
+        }
+        self.apply(&self.filter())?;
+        Ok(self.filter())
+    }
+}
+
+/// `custom_layer` for Bevy's `LogPlugin`; also inserts `LogControl`
+pub fn logging_layer(app: &mut App) -> Option<BoxedLayer> {
+    let defaults = LoggingConfig::default();
+    let (filter, handle) = reload::Layer::new(EnvFilter::builder().parse_lossy(&defaults.filter));
+    let sink = Arc::new(Mutex::new(LogSink::new(defaults.buffer_lines)));
+    app.insert_resource(LogControl::new(handle, sink.clone(), defaults.filter));
+    Some(Box::new(filter.and_then(CaptureLayer {
+        sink,
+        start: Instant::now(),
+    })))
+}
+
+/// Applies `GameConfig::logging` whenever the config is (re)loaded or a hot
+/// reload changes `logging.ron`. The file is only reopened when its settings
+/// change, since reopening rolls it over.
+pub fn apply_log_config(
+    mut reloaded: EventReader<ConfigReloadedEvent>,
+    mut changed: EventReader<ConfigFieldChanged>,
+    config: Res<GameConfig>,
+    control: Option<ResMut<LogControl>>,
+    mut applied: Local<Option<LoggingConfig>>,
+) {
+    let edited = changed
+        .read()
+        .filter(|change| change.file == "logging.ron")
+        .count()
+        > 0;
+    if reloaded.read().count() == 0 && !edited {
+        return;
+    }
+    let Some(mut control) = control else {
+        return;
+    };
+    let logging = &config.logging;
+    let previous = applied.replace(logging.clone());
+    if previous.as_ref() == Some(logging) {
+        return;
+    }
+
+    if previous.as_ref().is_none_or(|p| p.filter != logging.filter)
+        && let Err(err) = control.set_base(&logging.filter)
+    {
+        warn!("Invalid log filter '{}': {err}", logging.filter);
+    }
+
+    let file_changed = previous.as_ref().is_none_or(|p| {
+        (p.file_output, p.max_file_bytes, p.max_files)
+            != (
+                logging.file_output,
+                logging.max_file_bytes,
+                logging.max_files,
+            )
+    });
+    let file = if file_changed && logging.file_output {
+        match RollingFile::open(
+            &log_directory(),
+            LOG_FILE_NAME,
+            None,
+            logging.max_file_bytes,
+            logging.max_files,
+        ) {
+            Ok(file) => Some(file),
+            Err(err) => {
+                warn!("Could not open log file in {:?}: {err}", log_directory());
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let Ok(mut sink) = control.sink().lock() else {
+        return;
+    };
+    sink.set_capacity(logging.buffer_lines);
+    if file_changed {
+        sink.set_file(file);
+    }
+}
+
+/// `log [show] | log set <module> <level> | log clear [module]`
+pub fn log_command(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let mut control = world
+        .get_resource_mut::<LogControl>()
+        .ok_or_else(|| "logging layer not installed".to_string())?;
+    match args {
+        [] | ["show"] => Ok(control.filter()),
+        ["set", target, level] => control.set_level(target, level),
+        ["clear"] => control.clear(None),
+        ["clear", target] => control.clear(Some(target)),
+        _ => Err("usage: log [show] | log set <module> <level> | log clear [module]".to_string()),
+    }
+}
+
+pub fn register_log_command(mut registry: ResMut<ConsoleCommands>) {
+    registry
+        .register("log", log_command)
+        .usage("[show] | set <module> <level> | clear [module]");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn control() -> (reload::Layer<EnvFilter, Registry>, LogControl) {
+        let (layer, handle) = reload::Layer::new(EnvFilter::new("info"));
+        let sink = Arc::new(Mutex::new(LogSink::new(4)));
+        (
+            layer,
+            LogControl::new(handle, sink, "info,wgpu=error".into()),
+        )
+    }
+
+    #[test]
+    fn test_overrides_reload_the_filter() {
+        let (layer, mut control) = control();
+        control
+            .set_level("gta_game::systems::traffic", "debug")
+            .unwrap();
+        assert_eq!(
+            control.filter(),
+            "info,wgpu=error,gta_game::systems::traffic=debug"
+        );
+        assert!(control.set_level("gta_game", "loud").is_err());
+        assert!(control.set_level("a,b", "info").is_err());
+
+        control.set_base("warn").unwrap();
+        assert_eq!(control.filter(), "warn,gta_game::systems::traffic=debug");
+        assert!(control.clear(Some("nope")).is_err());
+        assert_eq!(control.clear(None).unwrap(), "warn");
+        drop(layer);
+        assert!(control.set_level("gta_game", "debug").is_err());
+    }
+
+    #[test]
+    fn test_sink_keeps_latest_lines_and_rolls_files() {
+        let dir = std::env::temp_dir().join(format!("gta_game_log_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut sink = LogSink::new(2);
+        sink.set_file(Some(
+            RollingFile::open(&dir, LOG_FILE_NAME, None, 64, 2).unwrap(),
+        ));
+        for index in 0..3 {
+            sink.push(LogLine {
+                elapsed: index as f32,
+                level: Level::INFO,
+                target: "test".into(),
+                message: format!("line {index} with enough text to roll the file"),
+            });
+        }
+
+        let kept: Vec<&str> = sink.lines().map(|line| line.message.as_str()).collect();
+        assert_eq!(kept.len(), 2);
+        assert!(kept[1].starts_with("line 2"));
+        assert_eq!(sink.total(), 3);
+        assert!(dir.join("game.log.1").exists());
+        assert!(!dir.join("game.log.2").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
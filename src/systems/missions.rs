@@ -0,0 +1,418 @@
+use crate::components::mission::{
+    ActiveMission, MissionCatalog, MissionCompleted, MissionDefinition, MissionFailReason,
+    MissionFailed, MissionHudText, MissionProgress, MissionStarted, MissionTrigger,
+    ObjectiveCompleted, ObjectiveKind, RunningMission, StartMissionRequest,
+};
+use crate::components::{ActiveEntity, PlayCutscene, VehicleControlType};
+use crate::t;
+use bevy::prelude::*;
+
+pub fn load_mission_catalog(mut commands: Commands) {
+    let path = format!(
+        "{}/config/missions.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    let catalog = match std::fs::read_to_string(&path) {
+        Ok(content) => match ron::from_str::<MissionCatalog>(&content) {
+            Ok(catalog) => {
+                #[cfg(feature = "debug-ui")]
+                info!("✅ Loaded {} missions", catalog.missions.len());
+                catalog
+            }
+            Err(e) => {
+                error!("Failed to parse mission config at '{}': {}", path, e);
+                MissionCatalog::default()
+            }
+        },
+        Err(e) => {
+            info!("ℹ️ No mission config found, missions disabled: {}", e);
+            MissionCatalog::default()
+        }
+    };
+
+    commands.insert_resource(catalog);
+}
+
+fn within_radius(position: Vec3, target: (f32, f32, f32), radius: f32) -> bool {
+    let target = Vec3::new(target.0, target.1, target.2);
+    position.xz().distance_squared(target.xz()) <= radius * radius
+}
+
+fn in_trigger_radius(mission: &MissionDefinition, position: Vec3) -> bool {
+    match &mission.trigger {
+        MissionTrigger::Location {
+            position: marker,
+            radius,
+        } => within_radius(position, *marker, *radius),
+        _ => false,
+    }
+}
+
+/// Starts the first eligible mission whose trigger is satisfied. A failed
+/// location mission waits in `left_behind` until the player has stepped out
+/// of its marker, so it doesn't restart on the spot
+#[allow(clippy::too_many_arguments)]
+pub fn mission_trigger_system(
+    catalog: Res<MissionCatalog>,
+    progress: Res<MissionProgress>,
+    mut active: ResMut<ActiveMission>,
+    mut failed: EventReader<MissionFailed>,
+    mut left_behind: Local<Vec<String>>,
+    active_query: Query<&Transform, With<ActiveEntity>>,
+    mut started: EventWriter<MissionStarted>,
+    mut cutscenes: EventWriter<PlayCutscene>,
+) {
+    left_behind.extend(failed.read().map(|failure| failure.mission_id.clone()));
+    if active.0.is_some() {
+        return;
+    }
+    let Ok(transform) = active_query.single() else {
+        return;
+    };
+    left_behind.retain(|id| {
+        catalog
+            .get(id)
+            .is_some_and(|mission| in_trigger_radius(mission, transform.translation))
+    });
+
+    let next = catalog.missions.iter().find(|mission| {
+        !progress.is_completed(&mission.id)
+            && match &mission.trigger {
+                MissionTrigger::Location { .. } => {
+                    !left_behind.contains(&mission.id)
+                        && in_trigger_radius(mission, transform.translation)
+                }
+                MissionTrigger::AfterMission(prev) => progress.is_completed(prev),
+                MissionTrigger::Dialogue => false,
+            }
+    });
+
+    if let Some(mission) = next {
+        start_mission(mission, &mut active, &mut started, &mut cutscenes);
+    }
+}
+
+fn start_mission(
+    mission: &MissionDefinition,
+    active: &mut ActiveMission,
+    started: &mut EventWriter<MissionStarted>,
+    cutscenes: &mut EventWriter<PlayCutscene>,
+) {
+    info!("🎯 Mission started: {}", mission.title);
+    active.0 = Some(RunningMission {
+        id: mission.id.clone(),
+        objective_index: 0,
+        elapsed: 0.0,
+        objective_elapsed: 0.0,
+    });
+    started.write(MissionStarted {
+        mission_id: mission.id.clone(),
+    });
+    if let Some(cutscene_id) = &mission.intro_cutscene {
+        cutscenes.write(PlayCutscene {
+            cutscene_id: cutscene_id.clone(),
+        });
+    }
+}
+
+/// Starts missions asked for by other systems, such as dialogue choices
+pub fn start_requested_missions(
+    catalog: Res<MissionCatalog>,
+    progress: Res<MissionProgress>,
+    mut active: ResMut<ActiveMission>,
+    mut requests: EventReader<StartMissionRequest>,
+    mut started: EventWriter<MissionStarted>,
+    mut cutscenes: EventWriter<PlayCutscene>,
+) {
+    for request in requests.read() {
+        let Some(mission) = catalog.get(&request.mission_id) else {
+            warn!("⚠️ Unknown mission '{}' requested", request.mission_id);
+            continue;
+        };
+        if active.0.is_some() || progress.is_completed(&mission.id) {
+            continue;
+        }
+        start_mission(mission, &mut active, &mut started, &mut cutscenes);
+    }
+}
+
+/// Advances the running mission's current objective and handles time limits
+#[allow(clippy::too_many_arguments)]
+pub fn mission_objective_system(
+    time: Res<Time>,
+    catalog: Res<MissionCatalog>,
+    mut progress: ResMut<MissionProgress>,
+    mut active: ResMut<ActiveMission>,
+    active_query: Query<(&Transform, Option<&VehicleControlType>), With<ActiveEntity>>,
+    mut objective_events: EventWriter<ObjectiveCompleted>,
+    mut completed_events: EventWriter<MissionCompleted>,
+    mut failed_events: EventWriter<MissionFailed>,
+) {
+    let Some(running) = active.0.as_mut() else {
+        return;
+    };
+    let Some(mission) = catalog.get(&running.id) else {
+        warn!("⚠️ Active mission '{}' missing from catalog", running.id);
+        active.0 = None;
+        return;
+    };
+    let Ok((transform, control_type)) = active_query.single() else {
+        return;
+    };
+
+    let dt = time.delta_secs();
+    running.elapsed += dt;
+    running.objective_elapsed += dt;
+
+    if let Some(limit) = mission.time_limit
+        && running.elapsed > limit
+    {
+        info!("❌ Mission failed: {}", mission.title);
+        failed_events.write(MissionFailed {
+            mission_id: mission.id.clone(),
+            reason: MissionFailReason::TimeExpired,
+        });
+        active.0 = None;
+        return;
+    }
+
+    let Some(objective) = mission.objectives.get(running.objective_index) else {
+        return;
+    };
+
+    let done = match &objective.kind {
+        ObjectiveKind::ReachLocation { position, radius } => {
+            within_radius(transform.translation, *position, *radius)
+        }
+        ObjectiveKind::EnterVehicle { vehicle } => control_type == Some(vehicle),
+        ObjectiveKind::Survive { seconds } => running.objective_elapsed >= *seconds,
+    };
+    if !done {
+        return;
+    }
+
+    objective_events.write(ObjectiveCompleted {
+        mission_id: mission.id.clone(),
+        objective_index: running.objective_index,
+    });
+    running.objective_index += 1;
+    running.objective_elapsed = 0.0;
+
+    if running.objective_index >= mission.objectives.len() {
+        info!("🏆 Mission complete: {}", mission.title);
+        progress.completed.push(mission.id.clone());
+        completed_events.write(MissionCompleted {
+            mission_id: mission.id.clone(),
+            reward: mission.reward.clone(),
+        });
+        active.0 = None;
+    }
+}
+
+pub fn setup_mission_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.85, 0.2)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            left: Val::Percent(35.0),
+            width: Val::Percent(30.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        MissionHudText,
+    ));
+}
+
+pub fn update_mission_hud(
+    catalog: Res<MissionCatalog>,
+    active: Res<ActiveMission>,
+    mut failed_events: EventReader<MissionFailed>,
+    mut completed_events: EventReader<MissionCompleted>,
+    mut hud_query: Query<&mut Text, With<MissionHudText>>,
+) {
+    let Ok(mut text) = hud_query.single_mut() else {
+        return;
+    };
+
+    // Show the most recent result until the next mission starts
+    if let Some(event) = completed_events.read().last() {
+        text.0 = t!("mission.passed", money = event.reward.money);
+    }
+    if failed_events.read().last().is_some() {
+        text.0 = t!("mission.failed");
+    }
+
+    let Some(running) = active.0.as_ref() else {
+        return;
+    };
+    let Some(mission) = catalog.get(&running.id) else {
+        return;
+    };
+    let Some(objective) = mission.objectives.get(running.objective_index) else {
+        return;
+    };
+
+    // Titles and descriptions may be localization keys
+    let title = t!(&mission.title);
+    let description = t!(&objective.description);
+    text.0 = match mission.time_limit {
+        Some(limit) => t!(
+            "mission.objective_timed",
+            title = title,
+            objective = description,
+            seconds = format!("{:.0}", (limit - running.elapsed).max(0.0))
+        ),
+        None => format!("{title}\n{description}"),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Wallet;
+    use crate::components::mission::{MissionObjective, MissionReward};
+    use crate::systems::economy::credit_mission_rewards;
+    use std::time::Duration;
+
+    fn mission(objectives: Vec<ObjectiveKind>, time_limit: Option<f32>) -> MissionDefinition {
+        MissionDefinition {
+            id: "job".to_string(),
+            title: "Job".to_string(),
+            trigger: MissionTrigger::Location {
+                position: (0.0, 0.0, 0.0),
+                radius: 5.0,
+            },
+            objectives: objectives
+                .into_iter()
+                .map(|kind| MissionObjective {
+                    description: String::new(),
+                    kind,
+                })
+                .collect(),
+            time_limit,
+            reward: MissionReward { money: 300 },
+            intro_cutscene: None,
+        }
+    }
+
+    fn mission_app(mission: MissionDefinition) -> (App, Entity) {
+        let mut app = App::new();
+        app.insert_resource(MissionCatalog {
+            missions: vec![mission],
+        })
+        .init_resource::<MissionProgress>()
+        .init_resource::<ActiveMission>()
+        .init_resource::<Time>()
+        .insert_resource(Wallet::new(0))
+        .add_event::<MissionStarted>()
+        .add_event::<PlayCutscene>()
+        .add_event::<ObjectiveCompleted>()
+        .add_event::<MissionCompleted>()
+        .add_event::<MissionFailed>()
+        .add_systems(
+            Update,
+            (
+                mission_trigger_system,
+                mission_objective_system,
+                credit_mission_rewards,
+            )
+                .chain(),
+        );
+        let player = app
+            .world_mut()
+            .spawn((Transform::default(), ActiveEntity))
+            .id();
+        (app, player)
+    }
+
+    /// Moves the player, then runs a frame `seconds` long
+    fn step(app: &mut App, player: Entity, position: Vec3, seconds: f32) {
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation = position;
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+    }
+
+    fn running(app: &App) -> Option<usize> {
+        let active = app.world().resource::<ActiveMission>();
+        active.0.as_ref().map(|running| running.objective_index)
+    }
+
+    #[test]
+    fn test_mission_triggers_completes_objectives_and_pays_out() {
+        let far_off = Vec3::new(50.0, 0.0, 0.0);
+        let (mut app, player) = mission_app(mission(
+            vec![
+                ObjectiveKind::ReachLocation {
+                    position: (50.0, 0.0, 0.0),
+                    radius: 5.0,
+                },
+                ObjectiveKind::Survive { seconds: 1.0 },
+            ],
+            None,
+        ));
+
+        step(&mut app, player, Vec3::new(100.0, 0.0, 0.0), 0.0);
+        assert_eq!(running(&app), None);
+        step(&mut app, player, Vec3::new(1.0, 0.0, 1.0), 0.0);
+        assert_eq!(running(&app), Some(0));
+        step(&mut app, player, far_off, 0.0);
+        assert_eq!(running(&app), Some(1));
+        step(&mut app, player, far_off, 2.0);
+        assert_eq!(running(&app), None);
+        assert!(
+            app.world()
+                .resource::<MissionProgress>()
+                .is_completed("job")
+        );
+        assert_eq!(app.world().resource::<Wallet>().balance(), 300);
+
+        // Done for good, even back at the marker
+        step(&mut app, player, Vec3::ZERO, 0.0);
+        assert_eq!(running(&app), None);
+    }
+
+    #[test]
+    fn test_timed_out_mission_waits_for_the_player_to_leave_the_marker() {
+        let (mut app, player) = mission_app(mission(
+            vec![ObjectiveKind::ReachLocation {
+                position: (500.0, 0.0, 0.0),
+                radius: 5.0,
+            }],
+            Some(5.0),
+        ));
+
+        step(&mut app, player, Vec3::ZERO, 0.0);
+        assert_eq!(running(&app), Some(0));
+        step(&mut app, player, Vec3::ZERO, 6.0);
+        assert_eq!(running(&app), None);
+        let failed = app.world().resource::<Events<MissionFailed>>();
+        assert_eq!(
+            failed
+                .iter_current_update_events()
+                .map(|failure| failure.reason)
+                .collect::<Vec<_>>(),
+            [MissionFailReason::TimeExpired]
+        );
+        assert_eq!(app.world().resource::<Wallet>().balance(), 0);
+
+        // Standing on the marker doesn't restart it...
+        step(&mut app, player, Vec3::ZERO, 0.0);
+        step(&mut app, player, Vec3::ZERO, 0.0);
+        assert_eq!(running(&app), None);
+        // ...walking out and back in does
+        step(&mut app, player, Vec3::new(100.0, 0.0, 0.0), 0.0);
+        step(&mut app, player, Vec3::ZERO, 0.0);
+        assert_eq!(running(&app), Some(0));
+    }
+}
@@ -16,31 +16,58 @@
 //! - `movement`: Player and entity movement mechanics
 //! - `physics_utils`: Physics simulation and collision handling  
 //! - `interaction`: Object interaction and pickup systems
+//! - `interaction_manager`: Interact-key focus over prioritized interactables
 //! - `vehicles`: Vehicle physics, spawning, and AI
+//! - `missions`: Mission triggers, objective tracking and HUD
+//! - `persistence`: Slot-based save/load of game progress
+//! - `garage`: Owned vehicle records, world garages and vehicle purchases
+//! - `economy`: Cash drops, pickups and mission payouts into the wallet
+//! - `shops`: Storefront buy menus, properties for sale and the player's outfit
+//! - `dialogue`: NPC conversations, choices and their mission or shop actions
+//! - `customization`: Applies vehicle paint, wheels and tuning
+//! - `traffic`: Ambient cars following the road network
+//! - `police`: Wanted level escalation and police pursuit
+//! - `racing`: Race markers, countdown, checkpoints, AI opponents and results
+//! - `stunts`: Stunt jump and time trial sessions, trick popups and saved best scores
+//! - `rail`: Trains between stations, boarding, level crossings and train strikes
+//! - `gps`: Road routes to a map destination, with route arrows and distance readout
+//! - `weather`: Weather presets, transitions, fog, rain and wind
+//! - `lighting`: Night headlights, brake lights and budgeted street lamp lights
+//! - `diving`: Breath, forced surfacing, oxygen meter and underwater camera grading
+//! - `accessibility`: Saved accessibility settings, UI scale and audio cue captions
+//! - `camera_rig`: Chase/close/hood/cinematic/orbit camera with a spring arm
+//! - `camera_shake`: Trauma-based camera shake from crashes and explosions
+//! - `cutscene`: Keyframed cutscene camera, letterbox bars and input lockout
+//! - `airfields`: Runway/helipad spawning and aircraft landing gear
+//! - `parachute`: Ejecting/bailing out, parachute descent and touchdown
+//! - `elevators`: Kinematic elevator cars and their riders in multi-storey interiors
+//! - `interiors`: Building doors, interior portals and exterior culling while inside
+//! - `tires`: Surface detection under cars and skid marks behind skidding wheels
 //!
 //! ### World Management
-//! - `world`: Terrain generation and world structure
+//! - `world`: Terrain generation and world structure (`terrain_height` answers ground height anywhere)
 //! - `water`: Water simulation and rendering
-//! - `vegetation_instancing_integration`: Plant and tree systems
 //! - `spawn_validation`: Entity spawning rules and limits
+//! - `spatial_index`: Morton-bucketed radius, box and nearest-N queries over live entities
 //!
 //! ### Services
-//! - `distance_cache`: Optimized distance calculations
+//! - `distance_cache`: Camera distances of moving entities, bucketed into LOD bands
 //! - `timing_service`: Frame timing and performance tracking
 //! - `performance_monitor`: System performance analysis
 //! - `unified_distance_calculator`: Centralized distance management
 //!
 //! ### Interface & Feedback
 //! - `ui`: User interface systems
-//! - `camera`: Camera control and positioning
+//! - `camera`: Turns the 3D camera off while loading
 //! - `input`: Input processing and mapping
 //! - `audio`: Sound effects and music
+//! - `music`: Mood-driven music crossfades
 //! - `effects`: Visual effects and particles
 //!
 //! ### Utility Systems
 //! - `debug`: Development and debugging tools
 //! - `transform_sync`: Transform synchronization
-//! - `rendering`: Render pipeline customization
+//! - `rendering`: Instanced batches of repeated props and far parked car impostors
 //!
 //! ## System Execution Order
 //!
@@ -49,24 +76,54 @@
 //!
 //! Use `.in_set()` to control when your system runs relative to others.
 
+pub mod accessibility;
+pub mod airfields;
 pub mod asset_validation;
 pub mod audio;
+pub mod config_reload;
+pub mod crash_report;
 pub mod camera;
-pub mod camera_car;
-pub mod camera_f16;
-pub mod camera_helicopter;
-pub mod camera_yacht;
+pub mod camera_rig;
+pub mod camera_shake;
+pub mod car_alarms;
+pub mod customization;
+pub mod cutscene;
+pub mod dialogue;
+pub mod distance_cache;
+pub mod diving;
+pub mod economy;
 pub mod effects;
+pub mod gameplay_events;
+pub mod garage;
+pub mod gps;
 
 pub mod interaction;
+pub mod interaction_manager;
+pub mod elevators;
+pub mod interiors;
+pub mod lighting;
 pub mod loading;
+pub mod logging;
+pub mod missions;
+pub mod music;
+pub mod parachute;
+pub mod persistence;
+pub mod police;
+pub mod profiles;
+pub mod racing;
+pub mod rail;
+pub mod traffic;
+pub mod weather;
 pub mod movement;
 pub mod world;
 
 pub mod physics;
+pub mod rendering;
 pub mod setup;
 
 pub mod debug;
+pub mod debug_gizmos;
+pub mod debug_window;
 pub mod ui;
 pub mod vehicles;
 pub mod visual;
@@ -74,9 +131,13 @@ pub mod water;
 // pub mod timing_service; // Moved to services/
 pub mod input;
 pub mod safety;
+pub mod shops;
+pub mod spatial_index;
 pub mod spawn_validation;
+pub mod stunts;
 pub mod swimming;
 pub mod terrain_water_manager;
+pub mod tires;
 pub mod validation;
 // pub mod realistic_physics_safeguards; // DISABLED - conflicts with Rapier
 pub mod transform_sync;
@@ -84,8 +145,11 @@ pub mod transform_sync;
 // pub mod batching_test; // Missing file
 
 pub mod player_collision_resolution;
+pub mod player_health;
 pub mod player_physics_enable;
 pub mod safe_active_entity;
+#[cfg(feature = "scripting")]
+pub mod scripting;
 // pub mod floating_origin; - REMOVED: Finite world doesn't need floating origin
 
 pub mod debug_docked_heli;
@@ -96,6 +160,7 @@ pub mod yacht_exit;
 // Only export items that are genuinely shared across multiple plugins and form stable APIs
 
 // Plugins that must be registered in main.rs or other top-level configs
+pub use distance_cache::DistanceCachePlugin;
 pub use performance::UnifiedPerformancePlugin;
 pub use spawn_validation::SpawnValidationPlugin;
 pub use transform_sync::TransformSyncPlugin;
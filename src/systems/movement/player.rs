@@ -56,6 +56,7 @@ pub fn velocity_apply_system(
             With<Player>,
             With<ActiveEntity>,
             Without<crate::systems::swimming::Swimming>,
+            Without<crate::components::Parachute>,
         ), // NEW FILTER
     >,
 ) {
@@ -13,13 +13,29 @@ use crate::systems::movement::simple_flight_common::SimpleFlightCommon;
 use crate::systems::physics::PhysicsUtilities;
 use crate::util::safe_math::safe_lerp;
 
+const GRAVITY: f32 = 9.81;
+
+/// Airspeed below which angle of attack is meaningless (taxiing or parked)
+const MIN_AOA_AIRSPEED: f32 = 5.0;
+
+/// A stalled wing recovers once the angle of attack is back under this share
+/// of the critical angle
+const STALL_RECOVERY_SHARE: f32 = 0.8;
+
 /// Simplified F16 flight system following AGENT.MD simplicity principles
 ///
 /// Replaces complex aerodynamic calculations with straightforward flight physics:
 /// - Pitch/roll → Direct angular velocity
 /// - Throttle → Forward thrust
 /// - Yaw → Rotation around Y-axis
-/// - Afterburner → Thrust multiplier
+/// - Afterburner → Thrust multiplier, burning extra fuel
+///
+/// On top of the arcade controls, a small flight envelope from `SimpleF16Specs`:
+/// - Angle-of-attack lift that scales with dynamic pressure
+/// - Stall past the critical AoA: lift collapses, the nose drops and the flight
+///   path stops following the nose until the wing unstalls
+/// - Pitch input limited to the airframe's G envelope; sustained high G builds
+///   pilot blackout
 ///
 /// Benefits:
 /// - Easy to understand: Direct control mapping
@@ -65,8 +81,20 @@ pub fn simple_f16_movement(
             dt,
         );
 
-        // Direct afterburner state
-        flight.afterburner_active = control_state.is_boosting();
+        burn_fuel(&mut flight, specs, control_state.is_boosting(), dt);
+
+        // === FLIGHT ENVELOPE ===
+
+        let airspeed = velocity.linvel.length();
+        let local_velocity = transform.rotation.inverse().mul_vec3(velocity.linvel);
+        flight.angle_of_attack = if airspeed > MIN_AOA_AIRSPEED {
+            // Forward is -Z; sinking relative to the nose is a positive angle
+            (-local_velocity.y).atan2(-local_velocity.z)
+        } else {
+            0.0
+        };
+
+        flight.stalled = update_stall(specs, flight.stalled, flight.angle_of_attack);
 
         // === MINIMAL FLIGHT PHYSICS ===
 
@@ -82,7 +110,6 @@ pub fn simple_f16_movement(
         // === GTA-STYLE ANGULAR CONTROL ===
 
         // Speed-based control effectiveness (once per frame)
-        let airspeed = velocity.linvel.length();
         let control_eff = (airspeed / specs.control_full_speed).clamp(0.0, 1.0);
         let control_eff = specs.min_control_factor + (1.0 - specs.min_control_factor) * control_eff;
 
@@ -99,6 +126,16 @@ pub fn simple_f16_movement(
             control_state.pitch.signum() * rate * control_eff
         };
 
+        let up_y = transform.up().y;
+        let pitch_cmd = g_limited_pitch_rate(specs, pitch_cmd, up_y, airspeed);
+
+        // A stalled wing drops the nose regardless of input
+        let stall_pitch = if flight.stalled {
+            -specs.stall_pitch_down_rate
+        } else {
+            0.0
+        };
+
         let roll_input_abs = control_state.roll.abs();
         let roll_cmd = if roll_input_abs < specs.input_deadzone {
             0.0
@@ -162,7 +199,7 @@ pub fn simple_f16_movement(
         // Combine control inputs (GTA-style: yaw is world-space, pitch/roll are local)
         // Pitch and roll in local space (aircraft body axes)
         let pr_local = Vec3::new(
-            pitch_cmd + pitch_auto,
+            pitch_cmd + pitch_auto + stall_pitch,
             0.0,
             roll_cmd + roll_auto + roll_bank,
         );
@@ -219,20 +256,16 @@ pub fn simple_f16_movement(
             // Safety: clamp final target speed to max velocity config
             let target_forward_speed = (max_forward_speed * flight.throttle * boost_multiplier)
                 .min(params.config.physics.max_velocity);
-            let target_forward_velocity = transform.forward() * target_forward_speed;
-
-            // Banked-lift feedback: reduce lift when wings are banked
-            let up_align = transform.up().dot(Vec3::Y).abs();
-            let lift_mult = 1.0 - specs.bank_lift_scale * (1.0 - up_align);
-            let lift_force = transform.up() * flight.throttle * specs.lift_per_throttle * lift_mult;
-
-            let target_linear_velocity = target_forward_velocity + lift_force;
-
-            velocity.linvel = safe_lerp(
-                velocity.linvel,
-                target_linear_velocity,
-                dt * linear_lerp_factor,
-            );
+            let target_linear_velocity = transform.forward() * target_forward_speed;
+
+            // Thrust acts along the nose at any speed, but the flight path only
+            // follows the nose as well as the wing can turn it. Lift comes from
+            // the angle of attack alone, below
+            let delta = target_linear_velocity - velocity.linvel;
+            let along = transform.forward() * delta.dot(*transform.forward());
+            let grip = nose_grip(specs, airspeed, flight.stalled);
+            velocity.linvel += along * (dt * linear_lerp_factor).min(1.0)
+                + (delta - along) * (dt * linear_lerp_factor * grip).min(1.0);
         } else {
             // Engine off: Apply frame-rate independent momentum decay (gliding like GTA V)
             // Clamp to prevent modded configs from breaking physics
@@ -247,10 +280,29 @@ pub fn simple_f16_movement(
             );
         }
 
+        // Angle-of-attack lift, perpendicular to the flight path
+        if airspeed > MIN_AOA_AIRSPEED {
+            let path = velocity.linvel / airspeed;
+            let lift_dir = transform
+                .up()
+                .reject_from_normalized(path)
+                .normalize_or_zero();
+            let lift_accel = (GRAVITY
+                * (airspeed / specs.stall_speed).powi(2)
+                * specs.lift_coefficient(flight.angle_of_attack))
+            .clamp(specs.min_g * GRAVITY, specs.max_g * GRAVITY);
+            velocity.linvel += lift_dir * lift_accel * dt;
+        }
+
         // === MINIMAL STATE TRACKING ===
 
         flight.airspeed = velocity.linvel.length();
 
+        // Load factor from the pitch rate actually flown
+        let pitch_rate = inv_rot.mul_vec3(velocity.angvel).x;
+        flight.g_load = up_y + flight.airspeed * pitch_rate / GRAVITY;
+        flight.blackout = step_blackout(specs, flight.blackout, flight.g_load, dt);
+
         // === SHARED PHYSICS SAFETY ===
 
         PhysicsUtilities::clamp_velocity(&mut velocity, &params.config);
@@ -259,6 +311,65 @@ pub fn simple_f16_movement(
     }
 }
 
+/// Burns this frame's fuel; a dry tank flames the engine out and takes the
+/// afterburner with it
+fn burn_fuel(flight: &mut AircraftFlight, specs: &SimpleF16Specs, boosting: bool, dt: f32) {
+    let has_fuel = flight.fuel_remaining(specs) > 0.0;
+    if !has_fuel {
+        flight.throttle = 0.0;
+    }
+    flight.afterburner_active = has_fuel && boosting;
+
+    let fuel_burn = specs.fuel_burn_rate * flight.throttle
+        + if flight.afterburner_active {
+            specs.afterburner_fuel_burn_rate
+        } else {
+            0.0
+        };
+    flight.fuel_used = (flight.fuel_used + fuel_burn * dt).min(specs.fuel_capacity);
+}
+
+/// Stalls past the critical angle of attack and only recovers well below it,
+/// so the stall doesn't flicker at the critical angle
+fn update_stall(specs: &SimpleF16Specs, stalled: bool, angle_of_attack: f32) -> bool {
+    let stall_aoa = specs.stall_aoa_deg.to_radians();
+    if stalled {
+        angle_of_attack.abs() > stall_aoa * STALL_RECOVERY_SHARE
+    } else {
+        angle_of_attack.abs() > stall_aoa
+    }
+}
+
+/// G limiter: the pitch rate needed to pull `n` G is (n - up.y) * g / airspeed
+fn g_limited_pitch_rate(specs: &SimpleF16Specs, pitch_rate: f32, up_y: f32, airspeed: f32) -> f32 {
+    let airspeed = airspeed.max(1.0);
+    pitch_rate.clamp(
+        (specs.min_g - up_y) * GRAVITY / airspeed,
+        (specs.max_g - up_y) * GRAVITY / airspeed,
+    )
+}
+
+/// Blackout builds with G above the onset and fades once below it
+fn step_blackout(specs: &SimpleF16Specs, blackout: f32, g_load: f32, dt: f32) -> f32 {
+    if g_load > specs.blackout_onset_g {
+        blackout + (g_load - specs.blackout_onset_g) * specs.blackout_rate * dt
+    } else {
+        blackout - specs.blackout_recovery_rate * dt
+    }
+    .clamp(0.0, 1.0)
+}
+
+/// How strongly the flight path is pulled toward the nose: falls off with
+/// dynamic pressure below stall speed and almost vanishes while stalled
+fn nose_grip(specs: &SimpleF16Specs, airspeed: f32, stalled: bool) -> f32 {
+    let pressure = (airspeed / specs.stall_speed).powi(2).min(1.0);
+    if stalled {
+        pressure * specs.post_stall_grip
+    } else {
+        pressure
+    }
+}
+
 /// Force-based helicopter physics following oracle's realistic flight model
 ///
 /// Replaces direct velocity manipulation with force/torque-based system:
@@ -621,3 +732,97 @@ pub fn spool_docked_helicopter_rpm(
         runtime.rpm = runtime.rpm.clamp(0.0, 2.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lift_peaks_at_the_critical_angle_then_collapses() {
+        let specs = SimpleF16Specs::default();
+        let critical = specs.stall_aoa_deg.to_radians();
+
+        assert_eq!(specs.lift_coefficient(0.0), 0.0);
+        assert!((specs.lift_coefficient(critical) - 1.0).abs() < 1e-5);
+        assert!((specs.lift_coefficient(-critical) + 1.0).abs() < 1e-5);
+        assert_eq!(
+            specs.lift_coefficient(critical * 1.2),
+            specs.post_stall_lift
+        );
+        assert_eq!(
+            specs.lift_coefficient(-critical * 1.2),
+            -specs.post_stall_lift
+        );
+    }
+
+    #[test]
+    fn test_stall_hysteresis_and_grip() {
+        let specs = SimpleF16Specs::default();
+        let critical = specs.stall_aoa_deg.to_radians();
+
+        // Stalls just past the critical angle, stays stalled until well below it
+        assert!(!update_stall(&specs, false, critical * 0.99));
+        assert!(update_stall(&specs, false, critical * 1.01));
+        assert!(update_stall(&specs, true, critical * 0.9));
+        assert!(!update_stall(&specs, true, critical * 0.7));
+        assert!(update_stall(&specs, false, -critical * 1.01));
+
+        // Grip grows with dynamic pressure up to stall speed and mostly goes
+        // once stalled
+        assert_eq!(nose_grip(&specs, specs.stall_speed * 2.0, false), 1.0);
+        assert!((nose_grip(&specs, specs.stall_speed * 0.5, false) - 0.25).abs() < 1e-5);
+        assert_eq!(
+            nose_grip(&specs, specs.stall_speed * 2.0, true),
+            specs.post_stall_grip
+        );
+    }
+
+    #[test]
+    fn test_g_limit_and_blackout() {
+        let specs = SimpleF16Specs::default();
+        let airspeed = 200.0;
+
+        // Level flight: a full pull is capped at max_g, a full push at min_g
+        let max_rate = (specs.max_g - 1.0) * GRAVITY / airspeed;
+        let min_rate = (specs.min_g - 1.0) * GRAVITY / airspeed;
+        assert_eq!(g_limited_pitch_rate(&specs, 10.0, 1.0, airspeed), max_rate);
+        assert_eq!(g_limited_pitch_rate(&specs, -10.0, 1.0, airspeed), min_rate);
+        assert_eq!(g_limited_pitch_rate(&specs, 0.01, 1.0, airspeed), 0.01);
+
+        // Sustained G past the onset blacks the pilot out, then it clears
+        let mut blackout = 0.0;
+        for _ in 0..600 {
+            blackout = step_blackout(&specs, blackout, specs.max_g, 0.1);
+        }
+        assert_eq!(blackout, 1.0);
+        blackout = step_blackout(&specs, blackout, 1.0, 0.1);
+        assert!(blackout < 1.0);
+        for _ in 0..600 {
+            blackout = step_blackout(&specs, blackout, 1.0, 0.1);
+        }
+        assert_eq!(blackout, 0.0);
+    }
+
+    #[test]
+    fn test_running_dry_cuts_thrust_and_afterburner() {
+        let specs = SimpleF16Specs::default();
+        let mut flight = AircraftFlight {
+            throttle: 1.0,
+            ..default()
+        };
+        burn_fuel(&mut flight, &specs, true, 1.0);
+        assert!(flight.afterburner_active);
+        assert_eq!(
+            flight.fuel_used,
+            specs.fuel_burn_rate + specs.afterburner_fuel_burn_rate
+        );
+
+        for _ in 0..1000 {
+            flight.throttle = 1.0;
+            burn_fuel(&mut flight, &specs, true, 1.0);
+        }
+        assert_eq!(flight.fuel_remaining(&specs), 0.0);
+        assert_eq!(flight.throttle, 0.0);
+        assert!(!flight.afterburner_active);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::components::control_state::ControlState;
 use crate::components::unified_water::UnifiedWaterBody;
-use crate::components::water::{Yacht, YachtSpecs};
+use crate::components::water::{Yacht, YachtSpecs, YachtState};
 use crate::components::{ActiveEntity, MissingSpecsWarned, PlayerControlled};
 use crate::config::GameConfig;
 use crate::systems::physics::PhysicsUtilities;
@@ -11,6 +11,20 @@ use bevy_rapier3d::prelude::*;
 #[derive(Component)]
 pub struct YachtSpecsHandle(pub Handle<YachtSpecs>);
 
+/// Share of full turning the rudder gets at `speed_share` of top speed.
+/// Rudders steer with the water flowing past them: propeller wash alone
+/// gives a little at rest, authority peaks at moderate speed and falls off
+/// again towards top speed, where the turning circle widens.
+pub fn rudder_authority(speed_share: f32, specs: &YachtSpecs) -> f32 {
+    let share = speed_share.abs().min(1.0);
+    let peak = specs.turn_peak_speed.clamp(0.05, 0.95);
+    if share < peak {
+        specs.rudder_idle_authority + (1.0 - specs.rudder_idle_authority) * share / peak
+    } else {
+        1.0 + (specs.top_speed_authority - 1.0) * (share - peak) / (1.0 - peak)
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub fn simple_yacht_movement(
     time: Res<Time>,
@@ -26,13 +40,15 @@ pub fn simple_yacht_movement(
             &Transform,
             &ControlState,
             &YachtSpecsHandle,
+            &mut YachtState,
         ),
         (With<Yacht>, With<ActiveEntity>, With<PlayerControlled>),
     >,
 ) {
     let dt = PhysicsUtilities::stable_dt(&time);
+    let now = time.elapsed_secs();
 
-    for (entity, mut velocity, transform, controls, specs_handle) in query.iter_mut() {
+    for (entity, mut velocity, transform, controls, specs_handle, mut state) in query.iter_mut() {
         let Some(specs) = yacht_specs.get(&specs_handle.0) else {
             if !warned_query.contains(entity) {
                 warn!(
@@ -54,28 +70,40 @@ pub fn simple_yacht_movement(
         let inv_rotation = transform.rotation.inverse();
         let mut v_local = inv_rotation * velocity.linvel;
 
-        // Check if yacht is in water (prop-in-water check to prevent land driving)
-        let in_water = water_regions
+        // Check if yacht is in water, and whether the propeller is under the
+        // wave surface (it lifts clear when the stern rides over a crest)
+        let region = water_regions
             .iter()
-            .any(|w| w.contains_point(transform.translation.x, transform.translation.z));
+            .find(|w| w.contains_point(transform.translation.x, transform.translation.z));
+        let propeller = transform.transform_point(specs.propeller_offset);
+        state.on_water = region.is_some();
+        state.thrust_in_water = region.is_some_and(|water| {
+            water.sample_surface_height(propeller.x, propeller.z, now) > propeller.y
+        });
 
         // Forward/backward speed control (arcade style)
         let mut input_throttle = controls.throttle - controls.brake;
 
         // Gate throttle if beached (GTA-style behavior)
-        if !in_water {
+        if !state.on_water {
             input_throttle = 0.0;
         }
-
-        let target_speed = max_speed * input_throttle.clamp(-0.5, 1.0);
-
-        // Smooth acceleration with frame-independent lerp
-        let accel_rate = if input_throttle.abs() > 0.05 {
-            throttle_ramp
-        } else {
-            throttle_ramp * 2.0 // Faster deceleration when no input
-        };
-        v_local.z = safe_lerp_f32(v_local.z, -target_speed, dt * accel_rate);
+        state.throttle = input_throttle;
+        state.rudder = controls.steering;
+
+        let reverse_share = specs.reverse_speed_share.clamp(0.1, 1.0);
+        let target_speed = max_speed * input_throttle.clamp(-reverse_share, 1.0);
+
+        // Smooth acceleration with frame-independent lerp; a propeller out
+        // of the water neither drives nor brakes the hull
+        if state.thrust_in_water {
+            let accel_rate = if input_throttle.abs() > 0.05 {
+                throttle_ramp
+            } else {
+                throttle_ramp * 2.0 // Faster deceleration when no input
+            };
+            v_local.z = safe_lerp_f32(v_local.z, -target_speed, dt * accel_rate);
+        }
 
         // Lateral grip: boats slide more than cars (tunable from specs)
         v_local.x = safe_lerp_f32(v_local.x, 0.0, dt * boat_grip);
@@ -86,10 +114,18 @@ pub fn simple_yacht_movement(
             v_local.z *= frame_drag;
         }
 
-        // Rudder control: yaw rate increases with speed (realistic boat behavior)
-        let speed_factor = (v_local.z.abs() / max_speed).min(1.0);
-        let rudder_effectiveness = 0.3 + 0.7 * speed_factor;
-        let target_yaw = controls.steering * rudder_effectiveness * 0.6;
+        // Rudder control: authority follows the flow past the rudder, and
+        // steering reverses going astern
+        let forward_speed = -v_local.z;
+        let authority = rudder_authority(forward_speed / max_speed, specs);
+        let flow_sign = if forward_speed < -0.5 { -1.0 } else { 1.0 };
+        let target_yaw =
+            controls.steering * specs.turn_rate.clamp(0.05, 3.0) * authority * flow_sign;
+        state.current_rudder = controls.steering * authority;
+
+        // Hard turns scrub off speed
+        let bleed = specs.turn_speed_bleed.clamp(0.0, 2.0) * controls.steering.abs() * authority;
+        v_local.z *= (1.0 - bleed * dt).max(0.0);
 
         let yaw_lerp_rate = 6.0;
         velocity.angvel = safe_lerp(
@@ -103,8 +139,35 @@ pub fn simple_yacht_movement(
         let world_v = transform.rotation * v_local;
         velocity.linvel.x = world_v.x;
         velocity.linvel.z = world_v.z;
+        state.current_thrust = if state.thrust_in_water {
+            input_throttle
+        } else {
+            0.0
+        };
 
         // Apply velocity clamping to prevent physics solver panics
         PhysicsUtilities::clamp_velocity(&mut velocity, &config);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rudder_authority_peaks_at_moderate_speed() {
+        let specs = YachtSpecs::default();
+        let at_rest = rudder_authority(0.0, &specs);
+        let peak = rudder_authority(specs.turn_peak_speed, &specs);
+        let flat_out = rudder_authority(1.0, &specs);
+        assert_eq!(at_rest, specs.rudder_idle_authority);
+        assert!((peak - 1.0).abs() < 1e-5);
+        assert!((flat_out - specs.top_speed_authority).abs() < 1e-5);
+        assert!(at_rest < flat_out && flat_out < peak);
+        // Going astern uses the same flow curve
+        assert_eq!(
+            rudder_authority(-0.2, &specs),
+            rudder_authority(0.2, &specs)
+        );
+    }
+}
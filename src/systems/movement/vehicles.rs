@@ -1,8 +1,10 @@
 #![allow(clippy::too_many_arguments, clippy::type_complexity)]
 use crate::components::ControlState;
 use crate::components::{
-    ActiveEntity, Car, Grounded, SimpleCarSpecs, SimpleCarSpecsHandle,
+    ActiveEntity, Car, Grounded, SimpleCarSpecs, SimpleCarSpecsHandle, TirePhysics, Transmission,
+    TransmissionMode, WeatherState,
 };
+use crate::components::tire::{axle_slip, slide_grip};
 use crate::systems::movement::vehicle_params::{validate_specs, VehicleParams};
 use crate::systems::physics::PhysicsUtilities;
 use crate::util::safe_math::{safe_lerp, safe_lerp_f32};
@@ -10,6 +12,9 @@ use crate::util::safe_specs::safe_clamp_f32;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
+/// Below this speed sliding tires are too slow to skid or screech (m/s)
+const SKID_MIN_SPEED: f32 = 3.0;
+
 /// Car movement system using asset-driven specs
 pub fn car_movement(
     mut params: VehicleParams<SimpleCarSpecs>,
@@ -21,15 +26,29 @@ pub fn car_movement(
             &ControlState,
             &SimpleCarSpecsHandle,
             &Grounded,
+            &mut Transmission,
+            &mut TirePhysics,
         ),
         (With<Car>, With<ActiveEntity>),
     >,
+    weather: Option<Res<WeatherState>>,
 ) {
     #[cfg(feature = "debug-movement")]
     let start_time = std::time::Instant::now();
 
-    for (entity, mut velocity, transform, control_state, specs_handle, grounded) in
-        car_query.iter_mut()
+    // Wet or foggy roads reduce tire grip
+    let road_friction = weather.map_or(1.0, |w| w.params.road_friction);
+
+    for (
+        entity,
+        mut velocity,
+        transform,
+        control_state,
+        specs_handle,
+        grounded,
+        mut transmission,
+        mut tires,
+    ) in car_query.iter_mut()
     {
         // Bug #38: Validate velocity is finite before physics operations
         if !velocity.linvel.is_finite() || !velocity.angvel.is_finite() {
@@ -61,6 +80,15 @@ pub fn car_movement(
         let forward_speed = -v_local.z;
         let current_speed = forward_speed.abs();
 
+        // Share of the engine's pull the gearbox passes to the wheels this step
+        let drive = transmission.update(
+            specs,
+            forward_speed,
+            control_state.throttle,
+            control_state.reverse,
+            dt,
+        );
+
         // PHASE 1: Auto-brake when throttle opposes velocity (GTA SA feature)
         // Detects when moving backward but trying to go forward (or vice versa)
         let auto_brake_gain =
@@ -119,18 +147,26 @@ pub fn car_movement(
             target_yaw = -target_yaw;
         }
 
+        // Yaw rate the driver is asking for, before tire limits
+        let commanded_yaw = target_yaw;
+
+        // Grass, sand and standing water hold the car less than dry asphalt
+        let surface_grip = specs.surface_grip.get(tires.surface);
+
         // Emergency brake handling: reduce grip and add yaw boost for drifting
         let base_grip = if control_state.emergency_brake {
             target_yaw += control_state.steering.signum() * specs.ebrake_yaw_boost;
             specs.drift_grip
         } else {
             specs.grip
-        };
+        } * road_friction
+            * surface_grip;
 
         // Forward/backward movement with proper brake/reverse separation
         // Bevy forward is -Z, so negate for correct direction
         // Gate acceleration/reverse to prevent fighting with auto-brake
-        if !throttle_opposes_velocity && control_state.is_accelerating() {
+        if !throttle_opposes_velocity && control_state.is_accelerating() && transmission.gear > 0
+        {
             // Accelerate forward
             let base_speed = safe_clamp_f32(specs.base_speed, 1.0, 100.0).unwrap_or_else(|| {
                 error!(
@@ -147,7 +183,9 @@ pub fn car_movement(
                 5.0
             });
             let target_speed = -base_speed * control_state.throttle;
-            v_local.z = safe_lerp_f32(v_local.z, target_speed, dt * accel_lerp);
+            let traction = surface_grip.min(1.0);
+            v_local.z =
+                safe_lerp_f32(v_local.z, target_speed, dt * accel_lerp * drive * traction);
         } else if control_state.brake > 0.0 {
             // Regular brake (Shift): slow down current velocity toward zero
             let brake_lerp = safe_clamp_f32(specs.brake_lerp, 1.0, 20.0).unwrap_or_else(|| {
@@ -158,7 +196,10 @@ pub fn car_movement(
                 8.0
             });
             v_local.z = safe_lerp_f32(v_local.z, 0.0, dt * brake_lerp * control_state.brake);
-        } else if !throttle_opposes_velocity && control_state.is_reversing() {
+        } else if !throttle_opposes_velocity
+            && control_state.is_reversing()
+            && transmission.gear < 0
+        {
             // Reverse (Arrow Down): move backward
             let base_speed = safe_clamp_f32(specs.base_speed, 1.0, 100.0).unwrap_or_else(|| {
                 error!(
@@ -175,7 +216,9 @@ pub fn car_movement(
                 5.0
             });
             let target_speed = base_speed * 0.5; // Half speed for reverse
-            v_local.z = safe_lerp_f32(v_local.z, target_speed, dt * accel_lerp);
+            let traction = surface_grip.min(1.0);
+            v_local.z =
+                safe_lerp_f32(v_local.z, target_speed, dt * accel_lerp * drive * traction);
         } else if !throttle_opposes_velocity {
             // No input: apply momentum decay (GTA-style coasting)
             let drag_factor = safe_clamp_f32(specs.drag_factor, 0.9, 1.0).unwrap_or_else(|| {
@@ -190,6 +233,21 @@ pub fn car_movement(
             v_local.z *= frame_drag;
         }
 
+        // Sideways slide at each axle, from the body's slide plus its rotation
+        let (front_slip, rear_slip) = axle_slip(
+            v_local,
+            velocity.angvel.y,
+            commanded_yaw,
+            specs.wheel_positions[0].2,
+            specs.wheel_positions[2].2,
+        );
+        tires.front_slip = front_slip;
+        tires.rear_slip = rear_slip;
+        tires.skidding = grounded.is_grounded
+            && current_speed > SKID_MIN_SPEED
+            && (front_slip.abs().max(rear_slip.abs()) > specs.skid_slip
+                || control_state.emergency_brake);
+
         // Phase 2: Only apply lateral grip when grounded
         if grounded.is_grounded {
             // PHASE 1: Slip/friction curve for lateral grip (replaces constant grip)
@@ -263,6 +321,20 @@ pub fn car_movement(
             let effective_grip =
                 downforce_grip * slip_factor * slip_stiffness * traction_circle_factor;
             v_local.x = safe_lerp_f32(v_local.x, 0.0, dt * effective_grip);
+
+            // Understeer: the front tires hold only so much cornering, past that the car runs wide
+            let front_limit = specs.cornering_limit * surface_grip * road_friction;
+            let demanded = (target_yaw * current_speed).abs();
+            if demanded > front_limit {
+                target_yaw *= front_limit / demanded;
+            }
+
+            // Oversteer: once the rear slides past its (surface-dependent) peak the tail keeps swinging
+            let rear_extremum = slip_extremum * specs.rear_grip_bias * surface_grip;
+            let rear_loss = 1.0 - slide_grip(rear_slip, rear_extremum, slip_asymptote);
+            if velocity.angvel.y.abs() > 0.1 {
+                target_yaw += velocity.angvel.y.signum() * rear_loss * specs.oversteer_yaw;
+            }
         }
         // No lateral grip when airborne - let physics handle it
 
@@ -333,3 +405,38 @@ pub fn car_movement(
         }
     }
 }
+
+/// Applies shift and transmission mode presses. Runs in Update, before the
+/// one-shot flags are cleared by the next frame's input pass.
+pub fn transmission_shift_input(
+    specs: Res<Assets<SimpleCarSpecs>>,
+    mut cars: Query<
+        (
+            &ControlState,
+            &SimpleCarSpecsHandle,
+            &Velocity,
+            &Transform,
+            &mut Transmission,
+        ),
+        (With<Car>, With<ActiveEntity>),
+    >,
+) {
+    for (control_state, specs_handle, velocity, transform, mut transmission) in &mut cars {
+        let Some(specs) = specs.get(&specs_handle.0) else {
+            continue;
+        };
+        if control_state.toggle_transmission {
+            transmission.toggle_mode();
+        }
+        if transmission.mode != TransmissionMode::Manual {
+            continue;
+        }
+        let forward_speed = -(transform.rotation.inverse() * velocity.linvel).z;
+        if control_state.shift_up {
+            transmission.shift(1, forward_speed, specs);
+        }
+        if control_state.shift_down {
+            transmission.shift(-1, forward_speed, specs);
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! Music director: picks a mood from gameplay and crossfades between tracks.
+//!
+//! Tracks per mood and crossfade rules come from `assets/config/music.ron`.
+//! Priority is underwater, then police chase, then flight, then cruising.
+
+use crate::components::{MainCamera, UnderwaterSettings, WantedLevel};
+use crate::game_state::{GameState, GameStateStack};
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum MusicMood {
+    Cruising,
+    Chase,
+    Flight,
+    Underwater,
+}
+
+impl MusicMood {
+    pub fn from_gameplay(state: &GameState, wanted_stars: u8, underwater: bool) -> Self {
+        if underwater {
+            MusicMood::Underwater
+        } else if wanted_stars > 0 {
+            MusicMood::Chase
+        } else if matches!(state, GameState::Flying | GameState::Jetting) {
+            MusicMood::Flight
+        } else {
+            MusicMood::Cruising
+        }
+    }
+}
+
+/// Crossfade length for a mood change; `from: None` matches any previous mood
+#[derive(Debug, Clone, Deserialize)]
+pub struct MusicTransition {
+    pub from: Option<MusicMood>,
+    pub to: MusicMood,
+    pub crossfade: f32,
+}
+
+#[derive(Resource, Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MusicPlaylist {
+    pub volume: f32,
+    pub default_crossfade: f32,
+    /// Seconds a mood must play before another change, so brief state flips don't thrash
+    pub min_mood_duration: f32,
+    pub moods: HashMap<MusicMood, Vec<String>>,
+    pub transitions: Vec<MusicTransition>,
+}
+
+impl Default for MusicPlaylist {
+    fn default() -> Self {
+        Self {
+            volume: 0.5,
+            default_crossfade: 3.0,
+            min_mood_duration: 8.0,
+            moods: HashMap::new(),
+            transitions: Vec::new(),
+        }
+    }
+}
+
+impl MusicPlaylist {
+    /// Exact rule first, then a wildcard rule for `to`, then the default
+    pub fn crossfade_for(&self, from: Option<MusicMood>, to: MusicMood) -> f32 {
+        let exact = self
+            .transitions
+            .iter()
+            .find(|rule| rule.to == to && rule.from.is_some() && rule.from == from);
+        let wildcard = || {
+            self.transitions
+                .iter()
+                .find(|rule| rule.to == to && rule.from.is_none())
+        };
+        exact
+            .or_else(wildcard)
+            .map_or(self.default_crossfade, |rule| rule.crossfade)
+            .max(0.0)
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct MusicDirector {
+    pub current: Option<MusicMood>,
+    pub time_in_mood: f32,
+    tracks: HashMap<MusicMood, Vec<Handle<AudioSource>>>,
+}
+
+impl MusicDirector {
+    /// Every track of every mood
+    pub fn track_handles(&self) -> impl Iterator<Item = &Handle<AudioSource>> {
+        self.tracks.values().flatten()
+    }
+}
+
+/// A playing music track fading from `from` to `to` volume
+#[derive(Component, Debug)]
+pub struct MusicTrack {
+    pub mood: MusicMood,
+    pub from: f32,
+    pub to: f32,
+    pub elapsed: f32,
+    pub duration: f32,
+}
+
+impl MusicTrack {
+    pub fn volume(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        self.from + (self.to - self.from) * t
+    }
+
+    fn fade_to(&mut self, to: f32, duration: f32) {
+        self.from = self.volume();
+        self.to = to;
+        self.elapsed = 0.0;
+        self.duration = duration;
+    }
+}
+
+pub fn load_music_playlist(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let base = crate::util::asset_path::get_assets_base_path();
+    let path = format!("{base}/config/music.ron");
+    let mut playlist = match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str::<MusicPlaylist>(&content).unwrap_or_else(|e| {
+            error!("Failed to parse music playlist at '{}': {}", path, e);
+            MusicPlaylist::default()
+        }),
+        Err(e) => {
+            info!("ℹ️ No music playlist found, music disabled: {}", e);
+            MusicPlaylist::default()
+        }
+    };
+    playlist.volume = playlist.volume.clamp(0.0, 2.0);
+
+    let mut director = MusicDirector::default();
+    for (mood, files) in &playlist.moods {
+        let handles = files
+            .iter()
+            .filter(|file| {
+                let exists = std::path::Path::new(&base).join(file).exists();
+                if !exists {
+                    info!("🔇 Music track '{}' not found, skipping", file);
+                }
+                exists
+            })
+            .map(|file| asset_server.load(file.clone()))
+            .collect();
+        director.tracks.insert(*mood, handles);
+    }
+
+    commands.insert_resource(director);
+    commands.insert_resource(playlist);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn music_director_system(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    playlist: Res<MusicPlaylist>,
+    mut director: ResMut<MusicDirector>,
+    game_state: Res<State<GameState>>,
+    stack: Res<GameStateStack>,
+    wanted: Option<Res<WantedLevel>>,
+    camera: Query<(&GlobalTransform, &UnderwaterSettings), With<MainCamera>>,
+    mut tracks: Query<&mut MusicTrack>,
+) {
+    director.time_in_mood += time.delta_secs();
+
+    let underwater = camera
+        .single()
+        .is_ok_and(|(transform, settings)| transform.translation().y < settings.sea_level);
+    let stars = wanted.map_or(0, |wanted| wanted.stars);
+    // Pausing keeps the current mood
+    let gameplay = stack.gameplay(*game_state.get());
+    let mood = MusicMood::from_gameplay(&gameplay, stars, underwater);
+
+    if director.current == Some(mood) {
+        return;
+    }
+    if director.current.is_some() && director.time_in_mood < playlist.min_mood_duration {
+        return;
+    }
+
+    let crossfade = playlist.crossfade_for(director.current, mood);
+    for mut track in &mut tracks {
+        if track.to > 0.0 {
+            track.fade_to(0.0, crossfade);
+        }
+    }
+
+    let candidates = director.tracks.get(&mood).map_or(&[][..], Vec::as_slice);
+    if !candidates.is_empty() {
+        let clip = candidates[rand::thread_rng().gen_range(0..candidates.len())].clone();
+        commands.spawn((
+            MusicTrack {
+                mood,
+                from: 0.0,
+                to: playlist.volume,
+                elapsed: 0.0,
+                duration: crossfade,
+            },
+            AudioPlayer(clip),
+            PlaybackSettings::LOOP.with_volume(Volume::Linear(0.0)),
+        ));
+    }
+
+    director.current = Some(mood);
+    director.time_in_mood = 0.0;
+}
+
+/// Runs on real time so fades finish while the game is paused
+pub fn music_crossfade_system(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut tracks: Query<(Entity, &mut MusicTrack, Option<&mut AudioSink>)>,
+) {
+    for (entity, mut track, sink) in &mut tracks {
+        track.elapsed += time.delta_secs();
+        let volume = track.volume();
+
+        if track.to <= 0.0 && track.elapsed >= track.duration {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if let Some(mut sink) = sink {
+            sink.set_volume(Volume::Linear(volume));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mood_priority() {
+        assert_eq!(
+            MusicMood::from_gameplay(&GameState::Jetting, 2, true),
+            MusicMood::Underwater
+        );
+        assert_eq!(
+            MusicMood::from_gameplay(&GameState::Flying, 1, false),
+            MusicMood::Chase
+        );
+        assert_eq!(
+            MusicMood::from_gameplay(&GameState::Jetting, 0, false),
+            MusicMood::Flight
+        );
+        assert_eq!(
+            MusicMood::from_gameplay(&GameState::Driving, 0, false),
+            MusicMood::Cruising
+        );
+    }
+
+    #[test]
+    fn test_crossfade_rules() {
+        let content = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/config/music.ron"
+        ))
+        .unwrap();
+        let playlist: MusicPlaylist = ron::from_str(&content).unwrap();
+
+        assert_eq!(
+            playlist.crossfade_for(Some(MusicMood::Cruising), MusicMood::Chase),
+            1.0
+        );
+        assert_eq!(
+            playlist.crossfade_for(Some(MusicMood::Flight), MusicMood::Underwater),
+            1.5
+        );
+        assert_eq!(
+            playlist.crossfade_for(Some(MusicMood::Flight), MusicMood::Cruising),
+            playlist.default_crossfade
+        );
+    }
+}
@@ -0,0 +1,465 @@
+//! Ejection, bailing out, skydiving and parachute descent.
+//!
+//! The eject action throws the player out of the active car, helicopter or
+//! F16 with a launch impulse and a ragdoll-style tumble. Stepping out of an
+//! aircraft (or off anything else) with enough air underneath starts the
+//! same freefall without the tumble. In freefall the walking controls turn
+//! the body, track forward head down and spread out to slow the fall. The
+//! player can open a parachute (if high enough), steer it with the walking
+//! controls and flare with the brake input. Touching down removes the
+//! `Parachute` and hands control back to normal walking; landing too fast
+//! without a canopy hurts like any other fall (see `player_health`).
+
+use crate::components::{
+    ActiveEntity, Car, ControlState, F16, Helicopter, InCar, Parachute, ParachuteCanopy,
+    ParachuteState, PendingPhysicsEnable, Player, PlayerControlled, VehicleControlType,
+};
+use crate::config::{GameConfig, ParachuteConfig};
+use crate::game_state::{GameState, GameStateTransitions};
+use crate::systems::safe_active_entity::queue_active_transfer;
+use crate::systems::swimming::Swimming;
+use crate::util::transform_utils::horizontal_forward;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::f32::consts::{PI, TAU};
+
+const GRAVITY: f32 = 9.81;
+
+/// Below this spin rate the tumble stops and the body rights itself
+const TUMBLE_SETTLE_RATE: f32 = 2.0;
+
+/// Canopy sits this far above the player's origin
+const CANOPY_HEIGHT: f32 = 4.5;
+
+/// Sink rate (m/s) a drop needs before it can turn into a freefall, so
+/// walking down slopes and steps never probes for one
+const FREEFALL_ENTRY_SINK: f32 = 3.0;
+
+type EjectableVehicleQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static ControlState,
+        &'static GlobalTransform,
+        Option<&'static Velocity>,
+        Has<F16>,
+        Has<Helicopter>,
+    ),
+    (
+        With<ActiveEntity>,
+        Or<(With<Car>, With<Helicopter>, With<F16>)>,
+        Without<Player>,
+    ),
+>;
+
+type ParachutistQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Transform,
+        &'static mut Velocity,
+        &'static mut Parachute,
+        &'static ControlState,
+        Has<Swimming>,
+    ),
+    (With<Player>, With<ActiveEntity>),
+>;
+
+type FallingPlayerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Transform, &'static Velocity),
+    (
+        With<Player>,
+        With<ActiveEntity>,
+        With<PlayerControlled>,
+        Without<Parachute>,
+        Without<Swimming>,
+        Without<InCar>,
+        Without<RigidBodyDisabled>,
+    ),
+>;
+
+type CanopyQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static ChildOf, &'static mut Transform),
+    (With<ParachuteCanopy>, Without<Player>),
+>;
+
+/// Advances the launch tumble: spins down, then eases the body back upright
+pub fn advance_tumble(parachute: &mut Parachute, dt: f32, damping: f32) {
+    parachute.tumble_rate *= (-damping * dt).exp();
+    if parachute.tumble_rate.abs() < TUMBLE_SETTLE_RATE || parachute.is_open() {
+        parachute.tumble_rate = 0.0;
+        parachute.tumble_angle *= (-damping * 2.0 * dt).exp();
+    } else {
+        parachute.tumble_angle =
+            (parachute.tumble_angle + parachute.tumble_rate * dt + PI).rem_euclid(TAU) - PI;
+    }
+}
+
+/// Velocity under an open canopy, blended from the current velocity by inflation
+pub fn canopy_velocity(
+    config: &ParachuteConfig,
+    parachute: &Parachute,
+    forward: Vec3,
+    control_state: &ControlState,
+    velocity: Vec3,
+    dt: f32,
+) -> Vec3 {
+    // Pulling the front risers speeds up and steepens the glide; the brakes flare
+    let dive = 1.0 + control_state.throttle * 0.5;
+    let flare = 1.0 - control_state.reverse * (1.0 - config.flare_descent_scale);
+    let target = forward * config.canopy_glide_speed * dive * flare
+        + Vec3::NEG_Y * config.canopy_descent_speed * dive * flare;
+
+    let blend = 1.0 - (-config.canopy_response * parachute.canopy * dt).exp();
+    velocity.lerp(target, blend)
+}
+
+/// Freefall velocity: quadratic drag on the sink rate that balances gravity
+/// at a terminal velocity set by body position, and horizontal drift eased
+/// toward tracking along `forward` under throttle
+pub fn freefall_velocity(
+    config: &ParachuteConfig,
+    forward: Vec3,
+    control_state: &ControlState,
+    velocity: Vec3,
+    dt: f32,
+) -> Vec3 {
+    let terminal = config.terminal_velocity
+        * (1.0 + control_state.throttle * (config.dive_terminal_scale - 1.0))
+        * (1.0 - control_state.reverse * (1.0 - config.spread_terminal_scale));
+    let drag = GRAVITY * (velocity.y / terminal).powi(2);
+    let sink = if velocity.y < 0.0 {
+        velocity.y + drag * dt
+    } else {
+        velocity.y
+    };
+
+    let track = forward * config.freefall_track_speed * control_state.throttle;
+    let blend = 1.0 - (-config.freefall_air_control * dt).exp();
+    velocity.with_y(0.0).lerp(track, blend).with_y(sink)
+}
+
+/// Starts a freefall when the player drops with a long way still to go,
+/// such as stepping out of a helicopter or jet in flight
+pub fn freefall_entry_system(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    rapier_context: ReadRapierContext,
+    players: FallingPlayerQuery,
+) {
+    let Ok((entity, transform, velocity)) = players.single() else {
+        return;
+    };
+    if velocity.linvel.y > -FREEFALL_ENTRY_SINK {
+        return;
+    }
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+    let settings = &config.parachute;
+    let ground = context.cast_ray(
+        transform.translation,
+        Vec3::NEG_Y,
+        settings.freefall_entry_height,
+        true,
+        QueryFilter::default()
+            .exclude_rigid_body(entity)
+            .exclude_sensors(),
+    );
+    if ground.is_none() {
+        commands.entity(entity).insert(Parachute::launched(0.0));
+        info!("Player in freefall at {:.0} m/s", -velocity.linvel.y);
+    }
+}
+
+/// Throws the player out of the active vehicle when eject is pressed
+pub fn ejection_system(
+    mut commands: Commands,
+    mut state: GameStateTransitions,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    vehicles: EjectableVehicleQuery,
+    player_query: Query<Entity, (With<Player>, With<InCar>)>,
+    just_controlled: Query<Entity, Added<PlayerControlled>>,
+) {
+    let Ok((vehicle, control_state, vehicle_gt, vehicle_vel, is_f16, is_helicopter)) =
+        vehicles.single()
+    else {
+        return;
+    };
+    if !control_state.eject || just_controlled.get(vehicle).is_ok() {
+        return;
+    }
+    let Ok(player) = player_query.single() else {
+        return;
+    };
+    let settings = &config.parachute;
+
+    let vehicle_velocity = vehicle_vel.map_or(Vec3::ZERO, |v| v.linvel);
+    let right_horizontal = vehicle_gt.right().with_y(0.0).normalize_or_zero();
+    let (position, launch_velocity) = if is_f16 {
+        // Seat fires along the canopy axis, whichever way the jet is pointing
+        let up = *vehicle_gt.up();
+        (
+            vehicle_gt.translation() + up * 3.0,
+            vehicle_velocity + up * settings.ejection_speed,
+        )
+    } else if is_helicopter {
+        (
+            vehicle_gt.translation() + right_horizontal * 4.0,
+            vehicle_velocity + right_horizontal * settings.bail_speed,
+        )
+    } else {
+        (
+            vehicle_gt.translation() + right_horizontal * 2.5 + Vec3::Y * 0.5,
+            vehicle_velocity * settings.bail_velocity_scale
+                + right_horizontal * settings.bail_speed
+                + Vec3::Y * settings.bail_speed * 0.5,
+        )
+    };
+
+    let (vehicle_yaw, _, _) = vehicle_gt
+        .to_scale_rotation_translation()
+        .1
+        .to_euler(EulerRot::YXZ);
+
+    // Queue atomic ActiveEntity transfer back to player
+    queue_active_transfer(&mut commands, vehicle, player, &time);
+
+    commands
+        .entity(vehicle)
+        .remove::<PlayerControlled>()
+        .remove::<ControlState>()
+        .remove::<VehicleControlType>();
+
+    // Same two-phase physics restore as a normal exit, plus the launch state
+    commands
+        .entity(player)
+        .remove::<InCar>()
+        .remove::<ChildOf>()
+        .insert(
+            Transform::from_translation(position).with_rotation(Quat::from_rotation_y(vehicle_yaw)),
+        )
+        .insert(Velocity::linear(launch_velocity))
+        .insert(PlayerControlled)
+        .insert(ControlState::default())
+        .insert(VehicleControlType::Walking)
+        .insert(Visibility::Visible)
+        .insert(PendingPhysicsEnable)
+        .insert(Parachute::launched(settings.launch_tumble_rate));
+
+    if let Err(error) = state.set(GameState::Walking) {
+        warn!("GameState change refused: {error}");
+    }
+    info!(
+        "Player ejected from {:?} at {:.0} m/s",
+        vehicle,
+        launch_velocity.length()
+    );
+}
+
+/// Freefall drag, canopy deployment and steering, tumble and touchdown
+#[allow(clippy::too_many_arguments)]
+pub fn parachute_flight_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    rapier_context: ReadRapierContext,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut parachutists: ParachutistQuery,
+    mut canopies: CanopyQuery,
+) {
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+    let settings = &config.parachute;
+    let foot_level = config.character_dimensions.player.foot_level;
+    let dt = time.delta_secs();
+
+    for (entity, mut transform, mut velocity, mut parachute, control_state, swimming) in
+        &mut parachutists
+    {
+        let probe = settings
+            .min_deploy_height
+            .max(settings.landing_probe_length)
+            - foot_level;
+        let height = context
+            .cast_ray(
+                transform.translation,
+                Vec3::NEG_Y,
+                probe,
+                true,
+                QueryFilter::default()
+                    .exclude_rigid_body(entity)
+                    .exclude_sensors(),
+            )
+            .map(|(_, toi)| toi + foot_level);
+
+        // Touchdown on ground or water ends the jump
+        let landed =
+            height.is_some_and(|h| h <= settings.landing_probe_length) && velocity.linvel.y <= 0.5;
+        if landed || swimming {
+            commands.entity(entity).remove::<Parachute>();
+            for (canopy, child_of, _) in &canopies {
+                if child_of.parent() == entity {
+                    commands.entity(canopy).despawn();
+                }
+            }
+            if !swimming {
+                let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+                transform.rotation = Quat::from_rotation_y(yaw);
+                velocity.angvel = Vec3::ZERO;
+            }
+            continue;
+        }
+
+        if control_state.deploy_parachute
+            && parachute.state == ParachuteState::Freefall
+            && height.is_none()
+        {
+            parachute.state = ParachuteState::Open;
+            commands.spawn((
+                Name::new("Parachute Canopy"),
+                ParachuteCanopy,
+                Mesh3d(meshes.add(Sphere::new(1.0).mesh().uv(16, 8))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.9, 0.35, 0.1),
+                    perceptual_roughness: 0.8,
+                    double_sided: true,
+                    cull_mode: None,
+                    ..default()
+                })),
+                Transform::from_xyz(0.0, CANOPY_HEIGHT, 0.0).with_scale(Vec3::splat(0.01)),
+                ChildOf(entity),
+            ));
+        }
+
+        advance_tumble(&mut parachute, dt, settings.tumble_damping);
+        let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, 0.0, parachute.tumble_angle);
+
+        match parachute.state {
+            ParachuteState::Freefall => {
+                // Still tumbling from a launch, the body can't be flown yet
+                let flying = parachute.tumble_rate == 0.0;
+                let controls = if flying {
+                    control_state.clone()
+                } else {
+                    ControlState::default()
+                };
+                velocity.linvel = freefall_velocity(
+                    settings,
+                    horizontal_forward(&transform),
+                    &controls,
+                    velocity.linvel,
+                    dt,
+                );
+                velocity.angvel = Vec3::Y * controls.steering * settings.freefall_turn_rate;
+            }
+            ParachuteState::Open => {
+                parachute.canopy = (parachute.canopy + dt / settings.deploy_time).min(1.0);
+                velocity.linvel = canopy_velocity(
+                    settings,
+                    &parachute,
+                    horizontal_forward(&transform),
+                    control_state,
+                    velocity.linvel,
+                    dt,
+                );
+                velocity.angvel =
+                    Vec3::Y * control_state.steering * settings.canopy_turn_rate * parachute.canopy;
+
+                // Canopy stays level while the body swings underneath
+                for (_, child_of, mut canopy_transform) in &mut canopies {
+                    if child_of.parent() == entity {
+                        canopy_transform.rotation = Quat::from_rotation_z(-parachute.tumble_angle);
+                        canopy_transform.scale =
+                            Vec3::new(3.5, 0.8, 2.0) * parachute.canopy.max(0.01);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tumble_settles_upright() {
+        let mut parachute = Parachute::launched(8.0);
+        for _ in 0..600 {
+            advance_tumble(&mut parachute, 1.0 / 60.0, 1.5);
+        }
+        assert_eq!(parachute.tumble_rate, 0.0);
+        assert!(parachute.tumble_angle.abs() < 0.05);
+    }
+
+    #[test]
+    fn test_freefall_body_position_sets_terminal_velocity() {
+        let config = ParachuteConfig::default();
+        let fall = |control_state: &ControlState| {
+            let mut velocity = Vec3::ZERO;
+            for _ in 0..1800 {
+                velocity += Vec3::NEG_Y * GRAVITY / 60.0;
+                velocity =
+                    freefall_velocity(&config, Vec3::NEG_Z, control_state, velocity, 1.0 / 60.0);
+            }
+            velocity
+        };
+
+        let belly = fall(&ControlState::default());
+        assert!((belly.y + config.terminal_velocity).abs() < 1.0);
+        let tracking = fall(&ControlState {
+            throttle: 1.0,
+            ..default()
+        });
+        assert!(tracking.y < belly.y);
+        assert!(tracking.z < -config.freefall_track_speed * 0.9);
+        let spread = fall(&ControlState {
+            reverse: 1.0,
+            ..default()
+        });
+        assert!(spread.y > belly.y);
+    }
+
+    #[test]
+    fn test_canopy_velocity_flare_slows_descent() {
+        let config = ParachuteConfig::default();
+        let mut parachute = Parachute::launched(0.0);
+        parachute.state = ParachuteState::Open;
+        parachute.canopy = 1.0;
+
+        let settle = |control_state: &ControlState| {
+            let mut velocity = Vec3::new(0.0, -40.0, 0.0);
+            for _ in 0..600 {
+                velocity = canopy_velocity(
+                    &config,
+                    &parachute,
+                    Vec3::NEG_Z,
+                    control_state,
+                    velocity,
+                    1.0 / 60.0,
+                );
+            }
+            velocity
+        };
+
+        let steady = settle(&ControlState::default());
+        assert!((steady.y + config.canopy_descent_speed).abs() < 0.1);
+
+        let flared = settle(&ControlState {
+            reverse: 1.0,
+            ..default()
+        });
+        assert!(-flared.y < -steady.y);
+    }
+}
@@ -1,7 +1,9 @@
 /// Compatibility stubs for the old performance system
 /// These allow code that depends on the old system to still compile
 /// while we transition to the new simplified system
+use super::schedule_timing::add_schedule_timing;
 use bevy::prelude::*;
+use std::collections::BTreeMap;
 
 /// Stub enum for performance categories - now unused
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -20,29 +22,48 @@ pub enum PerformanceCategory {
     System,
 }
 
-/// Stub resource to replace UnifiedPerformanceTracker
-/// This is a minimal implementation that does nothing
+/// Weight of the newest sample in the smoothed system timings
+const TIMING_SMOOTHING: f32 = 0.1;
+
+/// Smoothed per-schedule timings for the F3 performance overlay; the
+/// category API is kept as a stub for older callers
 #[derive(Resource, Default)]
 pub struct UnifiedPerformanceTracker {
     pub enabled: bool,
+    system_times: BTreeMap<String, f32>,
 }
 
 impl UnifiedPerformanceTracker {
     /// Stub method - does nothing
     pub fn record_category_time(&mut self, _category: PerformanceCategory, _time_ms: f32) {}
 
-    /// Stub method - does nothing
-    pub fn record_system_time(&mut self, _system_name: &str, _time_ms: f32) {}
+    /// Folds one sample into the exponential average kept for `system_name`
+    pub fn record_system_time(&mut self, system_name: &str, time_ms: f32) {
+        match self.system_times.get_mut(system_name) {
+            Some(average) => *average += (time_ms - *average) * TIMING_SMOOTHING,
+            None => {
+                self.system_times.insert(system_name.to_string(), time_ms);
+            }
+        }
+    }
+
+    /// Smoothed milliseconds per recorded system or schedule, sorted by name
+    pub fn system_times(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.system_times
+            .iter()
+            .map(|(name, time)| (name.as_str(), *time))
+    }
 
     /// Stub method - does nothing
     pub fn update_cache_stats(&mut self, _hits: usize, _misses: usize, _cache_type: &str) {}
 }
 
-/// Compatibility plugin that provides the old resource as a stub
+/// Provides the tracker and fills it with per-schedule frame timings
 pub struct UnifiedPerformancePlugin;
 
 impl Plugin for UnifiedPerformancePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UnifiedPerformanceTracker>();
+        add_schedule_timing(app);
     }
 }
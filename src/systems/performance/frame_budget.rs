@@ -0,0 +1,125 @@
+//! Frame budget governor.
+//!
+//! Watches the raw frame time against `PerformanceConfig::frame_time_threshold`
+//! and moves a single load scale between `budget_min_scale` and 1. The scale
+//! multiplies culling distances, palm density and the background chunk
+//! streaming budget. Separate over-budget and headroom streaks, plus a dead band
+//! between the two thresholds, keep it from oscillating around the budget.
+
+use crate::config::{GameConfig, PerformanceConfig};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+/// Current load scale and the streaks that move it
+#[derive(Resource, Debug)]
+pub struct FrameBudgetGovernor {
+    scale: f32,
+    over_budget: u32,
+    headroom: u32,
+}
+
+impl Default for FrameBudgetGovernor {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            over_budget: 0,
+            headroom: 0,
+        }
+    }
+}
+
+impl FrameBudgetGovernor {
+    /// 1 at full load, down to `budget_min_scale` while frames run long
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Feeds one frame time in milliseconds; returns whether the scale moved
+    pub fn observe(&mut self, frame_ms: f32, performance: &PerformanceConfig) -> bool {
+        let budget = performance.frame_time_threshold;
+        if frame_ms > budget {
+            self.over_budget += 1;
+            self.headroom = 0;
+        } else if frame_ms < budget * performance.budget_headroom {
+            self.headroom += 1;
+            self.over_budget = 0;
+        } else {
+            self.over_budget = 0;
+            self.headroom = 0;
+        }
+
+        let previous = self.scale;
+        if self.over_budget >= performance.budget_over_frames {
+            self.scale = (self.scale - performance.budget_step).max(performance.budget_min_scale);
+            self.over_budget = 0;
+        } else if self.headroom >= performance.budget_recover_frames {
+            self.scale = (self.scale + performance.budget_step).min(1.0);
+            self.headroom = 0;
+        }
+        self.scale != previous
+    }
+}
+
+/// Steps the load scale from the latest frame time. The resource is only
+/// marked changed when the scale moves, so consumers can key off that.
+pub fn frame_budget_governor_system(
+    diagnostics: Res<DiagnosticsStore>,
+    config: Res<GameConfig>,
+    mut governor: ResMut<FrameBudgetGovernor>,
+) {
+    let Some(frame_ms) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|diagnostic| diagnostic.value())
+    else {
+        return;
+    };
+
+    if governor
+        .bypass_change_detection()
+        .observe(frame_ms as f32, &config.performance)
+    {
+        governor.set_changed();
+        info!(
+            "⏱️ Frame budget: {:.1}ms against {:.1}ms, load scale now {:.2}",
+            frame_ms,
+            config.performance.frame_time_threshold,
+            governor.scale()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_governor_steps_down_and_recovers_with_hysteresis() {
+        let performance = PerformanceConfig::default();
+        let budget = performance.frame_time_threshold;
+        let mut governor = FrameBudgetGovernor::default();
+
+        // A short spike is tolerated
+        for _ in 0..performance.budget_over_frames - 1 {
+            assert!(!governor.observe(budget * 1.5, &performance));
+        }
+        assert!(!governor.observe(budget * 0.9, &performance));
+        assert_eq!(governor.scale(), 1.0);
+
+        // Sustained overload sheds load down to the floor
+        for _ in 0..performance.budget_over_frames * 20 {
+            governor.observe(budget * 1.5, &performance);
+        }
+        assert_eq!(governor.scale(), performance.budget_min_scale);
+
+        // Frames just under budget sit in the dead band and restore nothing
+        for _ in 0..performance.budget_recover_frames * 2 {
+            governor.observe(budget * 0.9, &performance);
+        }
+        assert_eq!(governor.scale(), performance.budget_min_scale);
+
+        for _ in 0..performance.budget_recover_frames {
+            governor.observe(budget * 0.5, &performance);
+        }
+        assert!(governor.scale() > performance.budget_min_scale);
+    }
+}
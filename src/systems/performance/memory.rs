@@ -0,0 +1,234 @@
+//! Approximate memory held by the large caches and registries.
+//!
+//! Tracked resources report their own `memory_bytes()`, except the mesh cache,
+//! which needs the mesh assets, and GPU assets, which are the resident total
+//! of the upload queue. The estimates count container capacity times
+//! element size plus mesh vertex and index data, not allocator overhead, so
+//! they are for spotting growth rather than exact accounting.
+//! `account_memory_usage` collects them once a second into `MemoryUsage` for
+//! the F3 overlay and warns when one crosses its `*_budget_kb` in
+//! `PerformanceConfig`.
+
+use crate::components::world::{MaterialCache, MeshCache};
+use crate::config::{GameConfig, PerformanceConfig};
+use crate::factories::PrefabRegistry;
+use crate::systems::distance_cache::DistanceCache;
+use crate::systems::rendering::upload_queue::GpuUploadQueue;
+use crate::systems::spatial_index::SpatialIndex;
+use bevy::prelude::*;
+use bevy::render::mesh::Indices;
+
+/// Resources whose memory is tracked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemorySubsystem {
+    MeshCache,
+    MaterialCache,
+    DistanceCache,
+    SpatialIndex,
+    PrefabRegistry,
+    GpuAssets,
+}
+
+impl MemorySubsystem {
+    pub const ALL: [MemorySubsystem; 6] = [
+        MemorySubsystem::MeshCache,
+        MemorySubsystem::MaterialCache,
+        MemorySubsystem::DistanceCache,
+        MemorySubsystem::SpatialIndex,
+        MemorySubsystem::PrefabRegistry,
+        MemorySubsystem::GpuAssets,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Budget in bytes; 0 means unlimited
+    pub fn budget_bytes(self, performance: &PerformanceConfig) -> usize {
+        let kb = match self {
+            MemorySubsystem::MeshCache => performance.mesh_cache_budget_kb,
+            MemorySubsystem::MaterialCache => performance.material_cache_budget_kb,
+            MemorySubsystem::DistanceCache => performance.distance_cache_budget_kb,
+            MemorySubsystem::SpatialIndex => performance.spatial_index_budget_kb,
+            MemorySubsystem::PrefabRegistry => performance.prefab_registry_budget_kb,
+            MemorySubsystem::GpuAssets => performance.gpu_residency_budget_kb,
+        };
+        kb * 1024
+    }
+}
+
+/// Bytes taken by a hash table with room for `capacity` `T`s, counting its
+/// one control byte per slot
+pub fn table_bytes<T>(capacity: usize) -> usize {
+    capacity * (std::mem::size_of::<T>() + 1)
+}
+
+/// Vertex and index data of a mesh still held in the main world
+pub fn mesh_bytes(mesh: &Mesh) -> usize {
+    let vertices: usize = mesh
+        .attributes()
+        .map(|(_, values)| values.get_bytes().len())
+        .sum();
+    let indices = match mesh.indices() {
+        Some(Indices::U16(indices)) => indices.len() * 2,
+        Some(Indices::U32(indices)) => indices.len() * 4,
+        None => 0,
+    };
+    vertices + indices
+}
+
+/// Cached meshes and their keys; meshes shared by several keys count once
+/// per key
+pub fn mesh_cache_bytes(cache: &MeshCache, meshes: &Assets<Mesh>) -> usize {
+    [
+        &cache.road_meshes,
+        &cache.npc_body_meshes,
+        &cache.intersection_meshes,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|(key, handle)| {
+        key.capacity()
+            + std::mem::size_of::<(String, Handle<Mesh>)>()
+            + meshes.get(handle).map_or(0, mesh_bytes)
+    })
+    .sum()
+}
+
+/// Latest estimate per subsystem
+#[derive(Resource, Debug, Default)]
+pub struct MemoryUsage {
+    bytes: [Option<usize>; MemorySubsystem::ALL.len()],
+    over_budget: [bool; MemorySubsystem::ALL.len()],
+}
+
+impl MemoryUsage {
+    /// `None` while the resource doesn't exist
+    pub fn bytes(&self, subsystem: MemorySubsystem) -> Option<usize> {
+        self.bytes[subsystem.index()]
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.bytes.iter().flatten().sum()
+    }
+
+    pub fn is_over_budget(&self, subsystem: MemorySubsystem) -> bool {
+        self.over_budget[subsystem.index()]
+    }
+
+    /// Stores an estimate; returns true when it has just gone over budget
+    pub fn record(
+        &mut self,
+        subsystem: MemorySubsystem,
+        bytes: Option<usize>,
+        budget: usize,
+    ) -> bool {
+        let index = subsystem.index();
+        let over = budget > 0 && bytes.is_some_and(|bytes| bytes > budget);
+        let crossed = over && !self.over_budget[index];
+        self.bytes[index] = bytes;
+        self.over_budget[index] = over;
+        crossed
+    }
+}
+
+/// `1.5 MiB`-style size for the overlay and warnings
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn account_memory_usage(
+    config: Res<GameConfig>,
+    meshes: Res<Assets<Mesh>>,
+    mesh_cache: Option<Res<MeshCache>>,
+    material_cache: Option<Res<MaterialCache>>,
+    distance_cache: Option<Res<DistanceCache>>,
+    spatial_index: Option<Res<SpatialIndex>>,
+    prefabs: Option<Res<PrefabRegistry>>,
+    uploads: Option<Res<GpuUploadQueue>>,
+    mut usage: ResMut<MemoryUsage>,
+) {
+    for subsystem in MemorySubsystem::ALL {
+        let bytes = match subsystem {
+            MemorySubsystem::MeshCache => mesh_cache
+                .as_ref()
+                .map(|cache| mesh_cache_bytes(cache, &meshes)),
+            MemorySubsystem::MaterialCache => {
+                material_cache.as_ref().map(|cache| cache.memory_bytes())
+            }
+            MemorySubsystem::DistanceCache => {
+                distance_cache.as_ref().map(|cache| cache.memory_bytes())
+            }
+            MemorySubsystem::SpatialIndex => {
+                spatial_index.as_ref().map(|index| index.memory_bytes())
+            }
+            MemorySubsystem::PrefabRegistry => {
+                prefabs.as_ref().map(|registry| registry.memory_bytes())
+            }
+            MemorySubsystem::GpuAssets => uploads.as_ref().map(|queue| queue.resident_bytes()),
+        };
+        let budget = subsystem.budget_bytes(&config.performance);
+        if usage.record(subsystem, bytes, budget) {
+            warn!(
+                "{subsystem:?} holds about {}, over its {} budget",
+                format_bytes(bytes.unwrap_or_default()),
+                format_bytes(budget)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::asset::RenderAssetUsages;
+    use bevy::render::mesh::PrimitiveTopology;
+
+    #[test]
+    fn test_budget_warning_fires_once_per_crossing() {
+        let mut usage = MemoryUsage::default();
+        let subsystem = MemorySubsystem::SpatialIndex;
+        assert!(!usage.record(subsystem, Some(512), 1024));
+        assert!(usage.record(subsystem, Some(2048), 1024));
+        assert!(!usage.record(subsystem, Some(4096), 1024));
+        assert!(usage.is_over_budget(subsystem));
+        assert!(!usage.record(subsystem, Some(100), 1024));
+        assert!(usage.record(subsystem, Some(2048), 1024));
+        // No budget means never over
+        assert!(!usage.record(subsystem, Some(1 << 40), 0));
+        assert!(!usage.is_over_budget(subsystem));
+
+        usage.record(subsystem, Some(100), 1024);
+        usage.record(MemorySubsystem::MeshCache, Some(1000), 0);
+        usage.record(MemorySubsystem::PrefabRegistry, None, 0);
+        assert_eq!(usage.total_bytes(), 1100);
+        assert_eq!(usage.bytes(MemorySubsystem::PrefabRegistry), None);
+    }
+
+    #[test]
+    fn test_mesh_bytes_and_formatting() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0f32; 3]; 4]);
+        mesh.insert_indices(Indices::U16(vec![0, 1, 2, 2, 3, 0]));
+        assert_eq!(mesh_bytes(&mesh), 4 * 12 + 6 * 2);
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(64 * 1024 * 1024), "64.0 MiB");
+    }
+}
@@ -4,7 +4,11 @@
 //! that leverages Bevy's FrameTimeDiagnosticsPlugin and LogDiagnosticsPlugin.
 
 pub mod compatibility;
+pub mod frame_budget;
+pub mod memory;
+pub mod schedule_timing;
 pub mod simple;
+pub mod telemetry;
 
 // Export the simple implementation
 pub use simple::{DebugUIPlugin, PerformancePlugin};
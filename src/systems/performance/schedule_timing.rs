@@ -0,0 +1,81 @@
+//! Wall-clock time spent in each main schedule per frame.
+//!
+//! Bevy does not time schedules on its own without tracing, so a tiny marker
+//! schedule is slotted into `MainScheduleOrder` after each schedule of
+//! interest. Each marker records the time since the previous one into
+//! `UnifiedPerformanceTracker` under the name of the schedule it closes.
+
+use super::compatibility::UnifiedPerformanceTracker;
+use bevy::app::{MainScheduleOrder, RunFixedMainLoop};
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use std::time::Instant;
+
+/// Marker schedule run right after the schedule it is named for
+#[derive(ScheduleLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TimingMark {
+    First,
+    PreUpdate,
+    FixedUpdate,
+    Update,
+    PostUpdate,
+}
+
+/// When the previous marker ran this frame
+#[derive(Resource, Default)]
+struct ScheduleClock(Option<Instant>);
+
+pub fn add_schedule_timing(app: &mut App) {
+    app.init_resource::<ScheduleClock>();
+
+    let marks: [(TimingMark, Option<&'static str>); 5] = [
+        (TimingMark::First, None),
+        (TimingMark::PreUpdate, Some("PreUpdate")),
+        (TimingMark::FixedUpdate, Some("FixedUpdate")),
+        (TimingMark::Update, Some("Update")),
+        (TimingMark::PostUpdate, Some("PostUpdate")),
+    ];
+    for (mark, span) in marks {
+        app.add_systems(
+            mark,
+            move |mut clock: ResMut<ScheduleClock>,
+                  mut tracker: ResMut<UnifiedPerformanceTracker>| {
+                let now = Instant::now();
+                if let (Some(span), Some(previous)) = (span, clock.0) {
+                    tracker.record_system_time(span, (now - previous).as_secs_f32() * 1000.0);
+                }
+                clock.0 = Some(now);
+            },
+        );
+    }
+
+    let mut order = app.world_mut().resource_mut::<MainScheduleOrder>();
+    order.insert_after(First, TimingMark::First);
+    order.insert_after(PreUpdate, TimingMark::PreUpdate);
+    order.insert_after(RunFixedMainLoop, TimingMark::FixedUpdate);
+    order.insert_after(Update, TimingMark::Update);
+    order.insert_after(PostUpdate, TimingMark::PostUpdate);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_main_schedule_gets_a_timing() {
+        let mut app = App::new();
+        app.init_resource::<UnifiedPerformanceTracker>();
+        add_schedule_timing(&mut app);
+        app.add_systems(Update, || {
+            std::thread::sleep(std::time::Duration::from_millis(5))
+        });
+        app.update();
+
+        let tracker = app.world().resource::<UnifiedPerformanceTracker>();
+        let times: Vec<(&str, f32)> = tracker.system_times().collect();
+        let names: Vec<&str> = times.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["FixedUpdate", "PostUpdate", "PreUpdate", "Update"]);
+        let update = times.iter().find(|(name, _)| *name == "Update").unwrap().1;
+        assert!(update >= 5.0, "Update took {update}ms");
+    }
+}
@@ -0,0 +1,458 @@
+//! Metrics export for soak tests and CI perf runs.
+//!
+//! Every `TelemetryConfig::sample_interval` seconds a `MetricsSample` is taken
+//! from the frame diagnostics, entity counts, process memory, the frame budget
+//! governor and `UnifiedPerformanceTracker`'s schedule timings. Samples are
+//! appended to a size-rotated `metrics.csv` under the data directory and/or
+//! served in Prometheus text format from a small HTTP endpoint at `/metrics`.
+//! Both outputs are off by default; `GTA_TELEMETRY_CSV=1` and
+//! `GTA_TELEMETRY_PORT=<port>` switch them on without touching the assets.
+
+use super::compatibility::UnifiedPerformanceTracker;
+use super::frame_budget::FrameBudgetGovernor;
+use crate::components::{NPCState, VehicleType};
+use crate::config::{ConfigFieldChanged, ConfigReloadedEvent, GameConfig, TelemetryConfig};
+use crate::systems::persistence::data_directory;
+use crate::systems::world::unified_world::{ChunkState, UnifiedWorldManager};
+use crate::util::rolling_file::RollingFile;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+use std::fmt::{Display, Write as _};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CSV_FILE_NAME: &str = "metrics.csv";
+/// Schedules timed by `schedule_timing`, in CSV column order
+const SCHEDULES: [&str; 4] = ["PreUpdate", "FixedUpdate", "Update", "PostUpdate"];
+/// How often the idle server checks whether it has been shut down
+const ACCEPT_POLL: Duration = Duration::from_millis(50);
+/// Longest request head read before answering; scrapers send far less
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Directory the rolling metrics CSV is written to
+pub fn metrics_directory() -> PathBuf {
+    data_directory().join("metrics")
+}
+
+/// One row of telemetry
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsSample {
+    pub unix_time: u64,
+    pub uptime: f64,
+    pub fps: f64,
+    pub frame_ms: f64,
+    pub entities: u32,
+    pub vehicles: usize,
+    pub npcs: usize,
+    pub loaded_chunks: usize,
+    /// Resident set size; only known on Linux
+    pub resident_bytes: Option<u64>,
+    pub load_scale: f32,
+    /// Smoothed milliseconds per entry of `SCHEDULES`
+    pub schedule_ms: [f32; 4],
+}
+
+pub fn csv_header() -> String {
+    let mut header = String::from(
+        "unix_time,uptime_s,fps,frame_ms,entities,vehicles,npcs,loaded_chunks,resident_bytes,load_scale",
+    );
+    for schedule in SCHEDULES {
+        let _ = write!(header, ",{}_ms", schedule.to_lowercase());
+    }
+    header
+}
+
+impl MetricsSample {
+    pub fn csv_row(&self) -> String {
+        let mut row = format!(
+            "{},{:.3},{:.2},{:.3},{},{},{},{},{},{:.2}",
+            self.unix_time,
+            self.uptime,
+            self.fps,
+            self.frame_ms,
+            self.entities,
+            self.vehicles,
+            self.npcs,
+            self.loaded_chunks,
+            self.resident_bytes
+                .map_or_else(String::new, |bytes| bytes.to_string()),
+            self.load_scale,
+        );
+        for ms in self.schedule_ms {
+            let _ = write!(row, ",{ms:.3}");
+        }
+        row
+    }
+
+    /// Prometheus text exposition format, one gauge per metric
+    pub fn prometheus(&self) -> String {
+        let mut text = String::new();
+        let mut gauge = |name: &str, help: &str, value: &dyn Display| {
+            let _ = writeln!(text, "# HELP gta_{name} {help}");
+            let _ = writeln!(text, "# TYPE gta_{name} gauge");
+            let _ = writeln!(text, "gta_{name} {value}");
+        };
+        gauge(
+            "uptime_seconds",
+            "Seconds since the game started",
+            &self.uptime,
+        );
+        gauge("fps", "Smoothed frames per second", &self.fps);
+        gauge(
+            "frame_time_ms",
+            "Smoothed frame time in milliseconds",
+            &self.frame_ms,
+        );
+        gauge("entities", "Live ECS entities", &self.entities);
+        gauge("vehicles", "Spawned vehicles", &self.vehicles);
+        gauge("npcs", "Spawned NPCs", &self.npcs);
+        gauge(
+            "loaded_chunks",
+            "World chunks in the loaded state",
+            &self.loaded_chunks,
+        );
+        gauge(
+            "load_scale",
+            "Frame budget governor load scale",
+            &self.load_scale,
+        );
+        if let Some(bytes) = self.resident_bytes {
+            gauge(
+                "resident_memory_bytes",
+                "Resident set size of the process",
+                &bytes,
+            );
+        }
+
+        let _ = writeln!(
+            text,
+            "# HELP gta_schedule_ms Smoothed time spent in each main schedule"
+        );
+        let _ = writeln!(text, "# TYPE gta_schedule_ms gauge");
+        for (schedule, ms) in SCHEDULES.iter().zip(self.schedule_ms) {
+            let _ = writeln!(text, "gta_schedule_ms{{schedule=\"{schedule}\"}} {ms}");
+        }
+        text
+    }
+}
+
+/// Resident set size from `/proc/self/status`
+fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// `GameConfig::telemetry` with the CI environment overrides applied
+fn effective_config(config: &TelemetryConfig) -> TelemetryConfig {
+    let mut telemetry = config.clone();
+    if let Ok(csv) = std::env::var("GTA_TELEMETRY_CSV") {
+        telemetry.csv_output = !matches!(csv.as_str(), "" | "0" | "false");
+    }
+    if let Ok(port) = std::env::var("GTA_TELEMETRY_PORT") {
+        match port.parse() {
+            Ok(port) => telemetry.http_port = Some(port),
+            Err(_) => warn!("Ignoring GTA_TELEMETRY_PORT={port}: not a port number"),
+        }
+    }
+    telemetry
+}
+
+/// HTTP endpoint serving the latest sample. The listener thread exits once
+/// the server is dropped.
+#[derive(Debug)]
+pub struct MetricsServer {
+    body: Arc<Mutex<String>>,
+    address: SocketAddr,
+}
+
+impl MetricsServer {
+    pub fn bind(host: &str, port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind((host, port))?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let body = Arc::new(Mutex::new(String::new()));
+        let weak = Arc::downgrade(&body);
+        std::thread::Builder::new()
+            .name("metrics-http".into())
+            .spawn(move || serve_metrics(listener, weak))?;
+        Ok(Self { body, address })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn publish(&self, text: String) {
+        if let Ok(mut body) = self.body.lock() {
+            *body = text;
+        }
+    }
+}
+
+fn serve_metrics(listener: TcpListener, body: Weak<Mutex<String>>) {
+    loop {
+        let Some(body) = body.upgrade() else {
+            return;
+        };
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(err) = respond(stream, &body) {
+                    debug!("Metrics request failed: {err}");
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                drop(body);
+                std::thread::sleep(ACCEPT_POLL);
+            }
+            Err(err) => {
+                warn!("Metrics endpoint stopped: {err}");
+                return;
+            }
+        }
+    }
+}
+
+fn respond(mut stream: TcpStream, body: &Mutex<String>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+    // Read the whole head even if it arrives in pieces; closing with unread
+    // bytes would reset the connection before the client sees the response
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&chunk[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let path = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) => path.split('?').next().unwrap_or(path),
+        _ => "",
+    };
+
+    let (status, content) = if path == "/metrics" {
+        (
+            "200 OK",
+            body.lock().map(|body| body.clone()).unwrap_or_default(),
+        )
+    } else {
+        (
+            "404 Not Found",
+            "Metrics are served at /metrics\n".to_string(),
+        )
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{content}",
+        content.len()
+    )
+}
+
+/// Open outputs and the sampling timer
+#[derive(Resource, Debug, Default)]
+pub struct TelemetryExporter {
+    applied: Option<TelemetryConfig>,
+    timer: Timer,
+    csv: Option<RollingFile>,
+    server: Option<MetricsServer>,
+}
+
+/// Applies `GameConfig::telemetry` on every `ConfigReloadedEvent` and when a
+/// hot reload changes `telemetry.ron`
+pub fn apply_telemetry_config(
+    mut reloaded: EventReader<ConfigReloadedEvent>,
+    mut changed: EventReader<ConfigFieldChanged>,
+    config: Res<GameConfig>,
+    mut exporter: ResMut<TelemetryExporter>,
+) {
+    let edited = changed
+        .read()
+        .filter(|change| change.file == "telemetry.ron")
+        .count()
+        > 0;
+    if reloaded.read().count() == 0 && !edited {
+        return;
+    }
+    let telemetry = effective_config(&config.telemetry);
+    let previous = exporter.applied.replace(telemetry.clone());
+    if previous.as_ref() == Some(&telemetry) {
+        return;
+    }
+
+    exporter.timer = Timer::from_seconds(telemetry.sample_interval, TimerMode::Repeating);
+
+    let csv_changed = previous.as_ref().is_none_or(|p| {
+        (p.csv_output, p.csv_max_bytes, p.csv_max_files)
+            != (
+                telemetry.csv_output,
+                telemetry.csv_max_bytes,
+                telemetry.csv_max_files,
+            )
+    });
+    if csv_changed {
+        exporter.csv = None;
+        if telemetry.csv_output {
+            match RollingFile::open(
+                &metrics_directory(),
+                CSV_FILE_NAME,
+                Some(&csv_header()),
+                telemetry.csv_max_bytes,
+                telemetry.csv_max_files,
+            ) {
+                Ok(file) => {
+                    info!("Writing metrics to {}", file.path().display());
+                    exporter.csv = Some(file);
+                }
+                Err(err) => warn!("Could not open metrics file: {err}"),
+            }
+        }
+    }
+
+    let server_changed = previous
+        .as_ref()
+        .is_none_or(|p| (p.http_port, &p.http_bind) != (telemetry.http_port, &telemetry.http_bind));
+    if server_changed {
+        // Release the old port before binding, in case it is being reused
+        exporter.server = None;
+        if let Some(port) = telemetry.http_port {
+            match MetricsServer::bind(&telemetry.http_bind, port) {
+                Ok(server) => {
+                    info!("Serving metrics at http://{}/metrics", server.address());
+                    exporter.server = Some(server);
+                }
+                Err(err) => warn!(
+                    "Could not serve metrics on {}:{port}: {err}",
+                    telemetry.http_bind
+                ),
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn sample_telemetry(
+    time: Res<Time>,
+    mut exporter: ResMut<TelemetryExporter>,
+    diagnostics: Res<DiagnosticsStore>,
+    entities: &Entities,
+    tracker: Option<Res<UnifiedPerformanceTracker>>,
+    governor: Option<Res<FrameBudgetGovernor>>,
+    world: Option<Res<UnifiedWorldManager>>,
+    vehicles: Query<(), With<VehicleType>>,
+    npcs: Query<(), With<NPCState>>,
+) {
+    if exporter.csv.is_none() && exporter.server.is_none() {
+        return;
+    }
+    if !exporter.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let smoothed = |path| {
+        diagnostics
+            .get(path)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or_default()
+    };
+    let mut schedule_ms = [0.0; SCHEDULES.len()];
+    if let Some(tracker) = &tracker {
+        for (name, ms) in tracker.system_times() {
+            if let Some(index) = SCHEDULES.iter().position(|schedule| *schedule == name) {
+                schedule_ms[index] = ms;
+            }
+        }
+    }
+    let sample = MetricsSample {
+        unix_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs()),
+        uptime: time.elapsed_secs_f64(),
+        fps: smoothed(&FrameTimeDiagnosticsPlugin::FPS),
+        frame_ms: smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME),
+        entities: entities.len(),
+        vehicles: vehicles.iter().count(),
+        npcs: npcs.iter().count(),
+        loaded_chunks: world.map_or(0, |world| {
+            world
+                .chunks
+                .iter()
+                .flatten()
+                .filter(|chunk| matches!(chunk.state, ChunkState::Loaded { .. }))
+                .count()
+        }),
+        resident_bytes: resident_memory_bytes(),
+        load_scale: governor.map_or(1.0, |governor| governor.scale()),
+        schedule_ms,
+    };
+
+    if let Some(csv) = &mut exporter.csv
+        && let Err(err) = csv.write_line(&sample.csv_row())
+    {
+        warn!("Could not write metrics, CSV output stopped: {err}");
+        exporter.csv = None;
+    }
+    if let Some(server) = &exporter.server {
+        server.publish(sample.prometheus());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MetricsSample {
+        MetricsSample {
+            unix_time: 1_700_000_000,
+            uptime: 12.5,
+            fps: 59.9,
+            frame_ms: 16.7,
+            entities: 4321,
+            vehicles: 20,
+            npcs: 35,
+            loaded_chunks: 49,
+            resident_bytes: None,
+            load_scale: 0.9,
+            schedule_ms: [0.5, 2.0, 6.25, 1.0],
+        }
+    }
+
+    #[test]
+    fn test_csv_row_matches_header() {
+        let header = csv_header();
+        let row = sample().csv_row();
+        assert_eq!(header.split(',').count(), row.split(',').count());
+        assert!(header.ends_with("update_ms,postupdate_ms"));
+        assert!(row.starts_with("1700000000,12.500,59.90,16.700,4321,20,35,49,,0.90,"));
+    }
+
+    #[test]
+    fn test_prometheus_text_over_http() {
+        let text = sample().prometheus();
+        assert!(text.contains("# TYPE gta_fps gauge\ngta_fps 59.9\n"));
+        assert!(text.contains("gta_schedule_ms{schedule=\"Update\"} 6.25\n"));
+        assert!(!text.contains("resident_memory_bytes"));
+
+        let server = MetricsServer::bind("127.0.0.1", 0).unwrap();
+        server.publish(text.clone());
+        let fetch = |path: &str| {
+            let mut stream = TcpStream::connect(server.address()).unwrap();
+            write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = fetch("/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(&text));
+        assert!(fetch("/").starts_with("HTTP/1.1 404"));
+    }
+}
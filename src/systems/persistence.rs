@@ -0,0 +1,370 @@
+//! Save/load of game progress to versioned RON files.
+//!
+//! Saves live under the active profile's data directory
+//! (`$XDG_DATA_HOME/gta_game/saves` on Linux for the default profile) with one
+//! file per numbered slot.
+
+use crate::components::mission::{ActiveMission, MissionProgress};
+use crate::components::{
+    ActiveEntity, Garage, GarageVehicle, OwnedProperties, Player, PlayerOutfit, PlayerOwned,
+    VehicleState, VehicleType, Wallet,
+};
+use crate::config::GameConfig;
+use crate::factories::VehicleFactory;
+use crate::game_state::{GameState, GameStateStack};
+use crate::resources::{WorldRng, WorldSeed};
+use crate::systems::profiles::ActiveProfile;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Bump when the save layout changes incompatibly
+pub const SAVE_VERSION: u32 = 1;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SaveGameRequest {
+    pub slot: u32,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LoadGameRequest {
+    pub slot: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedVehicle {
+    pub vehicle_type: VehicleType,
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub color: [f32; 4],
+    /// Links the restored vehicle back to its `Garage` record
+    #[serde(default)]
+    pub garage_id: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub player_translation: Vec3,
+    pub player_rotation: Quat,
+    pub owned_vehicles: Vec<SavedVehicle>,
+    pub completed_missions: Vec<String>,
+    pub world_seed: u64,
+    #[serde(default)]
+    pub garage: Garage,
+    /// `Wallet` balance
+    #[serde(default)]
+    pub money: u32,
+    /// Ids of bought properties
+    #[serde(default)]
+    pub owned_properties: Vec<String>,
+    #[serde(default)]
+    pub outfit: Option<[f32; 4]>,
+}
+
+/// Per-user data directory for the game, following the XDG base directory spec
+pub fn data_directory() -> PathBuf {
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    data_dir.join("gta_game")
+}
+
+/// Root directory for the save files of the profile kept in `profile_dir`
+pub fn save_directory(profile_dir: &Path) -> PathBuf {
+    profile_dir.join("saves")
+}
+
+pub fn slot_path(profile_dir: &Path, slot: u32) -> PathBuf {
+    save_directory(profile_dir).join(format!("slot_{slot}.ron"))
+}
+
+/// Slots that currently have a save file, sorted ascending
+pub fn list_save_slots(profile_dir: &Path) -> Vec<u32> {
+    let Ok(entries) = std::fs::read_dir(save_directory(profile_dir)) else {
+        return Vec::new();
+    };
+    let mut slots: Vec<u32> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix("slot_")?
+                .strip_suffix(".ron")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    slots.sort_unstable();
+    slots
+}
+
+/// Slot whose save file was written most recently
+pub fn latest_save_slot(profile_dir: &Path) -> Option<u32> {
+    list_save_slots(profile_dir)
+        .into_iter()
+        .filter_map(|slot| {
+            let modified = std::fs::metadata(slot_path(profile_dir, slot))
+                .ok()?
+                .modified()
+                .ok()?;
+            Some((modified, slot))
+        })
+        .max()
+        .map(|(_, slot)| slot)
+}
+
+/// Slot to restore once the world chosen from the main menu has generated
+#[derive(Resource, Debug, Default)]
+pub struct PendingLoad(pub Option<u32>);
+
+/// Loads the save picked with Continue as soon as gameplay starts
+pub fn request_pending_load(
+    mut pending: ResMut<PendingLoad>,
+    mut load_events: EventWriter<LoadGameRequest>,
+) {
+    if let Some(slot) = pending.0.take() {
+        load_events.write(LoadGameRequest { slot });
+    }
+}
+
+pub fn parse_save(contents: &str) -> Result<SaveGame, String> {
+    let save: SaveGame = ron::from_str(contents).map_err(|e| e.to_string())?;
+    if save.version > SAVE_VERSION {
+        return Err(format!(
+            "save version {} is newer than supported version {}",
+            save.version, SAVE_VERSION
+        ));
+    }
+    Ok(save)
+}
+
+pub fn write_save(profile_dir: &Path, slot: u32, save: &SaveGame) -> Result<PathBuf, String> {
+    let path = slot_path(profile_dir, slot);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let contents = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// F5 quick-saves and F9 quick-loads slot 0
+pub fn quick_save_input_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut save_events: EventWriter<SaveGameRequest>,
+    mut load_events: EventWriter<LoadGameRequest>,
+) {
+    if keys.just_pressed(KeyCode::F5) {
+        save_events.write(SaveGameRequest { slot: 0 });
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        load_events.write(LoadGameRequest { slot: 0 });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn save_game_system(
+    mut requests: EventReader<SaveGameRequest>,
+    active_query: Query<&GlobalTransform, With<ActiveEntity>>,
+    vehicle_query: Query<
+        (&VehicleState, &GlobalTransform, Option<&GarageVehicle>),
+        With<PlayerOwned>,
+    >,
+    progress: Res<MissionProgress>,
+    garage: Res<Garage>,
+    wallet: Res<Wallet>,
+    owned_properties: Res<OwnedProperties>,
+    outfit: Res<PlayerOutfit>,
+    world_seed: Res<WorldSeed>,
+    profile: Res<ActiveProfile>,
+) {
+    for request in requests.read() {
+        let Ok(active_transform) = active_query.single() else {
+            warn!("⚠️ Cannot save: no active entity");
+            continue;
+        };
+        let (_, player_rotation, player_translation) =
+            active_transform.to_scale_rotation_translation();
+
+        let owned_vehicles = vehicle_query
+            .iter()
+            .map(|(state, transform, garage_vehicle)| {
+                let (_, rotation, translation) = transform.to_scale_rotation_translation();
+                SavedVehicle {
+                    vehicle_type: state.vehicle_type,
+                    translation,
+                    rotation,
+                    color: state.color.to_srgba().to_f32_array(),
+                    garage_id: garage_vehicle.map(|vehicle| vehicle.0),
+                }
+            })
+            .collect();
+
+        let save = SaveGame {
+            version: SAVE_VERSION,
+            player_translation,
+            player_rotation,
+            owned_vehicles,
+            completed_missions: progress.completed.clone(),
+            world_seed: world_seed.0,
+            garage: garage.clone(),
+            money: wallet.balance(),
+            owned_properties: owned_properties.0.iter().cloned().collect(),
+            outfit: outfit.0,
+        };
+
+        match write_save(&profile.data_directory(), request.slot, &save) {
+            Ok(path) => info!("💾 Saved game to {}", path.display()),
+            Err(e) => error!("Failed to save slot {}: {}", request.slot, e),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn load_game_system(
+    mut requests: EventReader<LoadGameRequest>,
+    mut commands: Commands,
+    (current_state, state_stack): (Res<State<GameState>>, Res<GameStateStack>),
+    mut player_query: Query<(&mut Transform, Option<&mut Velocity>), With<Player>>,
+    owned_query: Query<Entity, With<PlayerOwned>>,
+    mut progress: ResMut<MissionProgress>,
+    mut active_mission: ResMut<ActiveMission>,
+    mut world_seed: ResMut<WorldSeed>,
+    mut world_rng: ResMut<WorldRng>,
+    mut garage: ResMut<Garage>,
+    mut wallet: ResMut<Wallet>,
+    (mut owned_properties, mut outfit): (ResMut<OwnedProperties>, ResMut<PlayerOutfit>),
+    (config, profile): (Res<GameConfig>, Res<ActiveProfile>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    // Only the most recent request matters
+    let Some(request) = requests.read().last().copied() else {
+        return;
+    };
+
+    if state_stack.gameplay(*current_state.get()) != GameState::Walking {
+        warn!("⚠️ Exit your vehicle before loading a save");
+        return;
+    }
+
+    let path = slot_path(&profile.data_directory(), request.slot);
+    let save = match std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| parse_save(&contents))
+    {
+        Ok(save) => save,
+        Err(e) => {
+            error!("Failed to load save '{}': {}", path.display(), e);
+            return;
+        }
+    };
+
+    if let Ok((mut transform, velocity)) = player_query.single_mut() {
+        // Offset sideways so the player doesn't spawn inside a vehicle they were driving
+        transform.translation = save.player_translation + Vec3::new(3.0, 1.0, 0.0);
+        // Keep only heading; a save taken mid-flight shouldn't leave the player tilted
+        let (yaw, _, _) = save.player_rotation.to_euler(EulerRot::YXZ);
+        transform.rotation = Quat::from_rotation_y(yaw);
+        if let Some(mut velocity) = velocity {
+            *velocity = Velocity::zero();
+        }
+    }
+
+    for entity in owned_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let factory = VehicleFactory::with_config(config.clone());
+    for vehicle in &save.owned_vehicles {
+        let [r, g, b, a] = vehicle.color;
+        match factory.spawn_vehicle_by_type(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &asset_server,
+            vehicle.vehicle_type,
+            vehicle.translation,
+            Some(Color::srgba(r, g, b, a)),
+        ) {
+            Ok(entity) => {
+                commands.entity(entity).insert((
+                    PlayerOwned,
+                    Transform::from_translation(vehicle.translation)
+                        .with_rotation(vehicle.rotation),
+                ));
+                if let Some(id) = vehicle.garage_id {
+                    commands.entity(entity).insert(GarageVehicle(id));
+                }
+            }
+            Err(e) => warn!("⚠️ Failed to restore {:?}: {:?}", vehicle.vehicle_type, e),
+        }
+    }
+
+    *garage = save.garage;
+    wallet.set(save.money);
+    owned_properties.0 = save.owned_properties.into_iter().collect();
+    outfit.0 = save.outfit;
+    progress.completed = save.completed_missions;
+    active_mission.0 = None;
+    // Continue generates the world from this seed; a quick-load only affects further generation
+    *world_seed = WorldSeed(save.world_seed);
+    *world_rng = WorldRng::new(save.world_seed);
+
+    info!("📂 Loaded save slot {}", request.slot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_save() -> SaveGame {
+        SaveGame {
+            version: SAVE_VERSION,
+            player_translation: Vec3::new(1.0, 2.0, 3.0),
+            player_rotation: Quat::IDENTITY,
+            owned_vehicles: vec![SavedVehicle {
+                vehicle_type: VehicleType::SuperCar,
+                translation: Vec3::new(10.0, 0.5, -4.0),
+                rotation: Quat::from_rotation_y(1.0),
+                color: [1.0, 0.0, 0.0, 1.0],
+                garage_id: Some(0),
+            }],
+            completed_missions: vec!["first_ride".to_string()],
+            world_seed: 42,
+            garage: Garage::default(),
+            money: 1250,
+            owned_properties: vec!["west_apartment".to_string()],
+            outfit: None,
+        }
+    }
+
+    #[test]
+    fn test_save_round_trip() {
+        let contents = ron::to_string(&sample_save()).unwrap();
+        let loaded = parse_save(&contents).unwrap();
+
+        assert_eq!(loaded.player_translation, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(loaded.owned_vehicles.len(), 1);
+        assert_eq!(loaded.owned_vehicles[0].vehicle_type, VehicleType::SuperCar);
+        assert_eq!(loaded.completed_missions, vec!["first_ride".to_string()]);
+        assert_eq!(loaded.world_seed, 42);
+        assert_eq!(loaded.money, 1250);
+        assert_eq!(loaded.owned_properties, vec!["west_apartment".to_string()]);
+    }
+
+    #[test]
+    fn test_rejects_newer_save_version() {
+        let mut save = sample_save();
+        save.version = SAVE_VERSION + 1;
+        let contents = ron::to_string(&save).unwrap();
+
+        assert!(parse_save(&contents).is_err());
+    }
+}
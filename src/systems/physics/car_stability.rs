@@ -1,7 +1,7 @@
 //! Phase 2: Car stability systems
 //!
 //! Implements GTA-style stability helpers:
-//! 1. Ground detection via raycast
+//! 1. Ground detection via raycast, falling back to the terrain height service
 //! 2. Downward force when airborne
 //! 3. Roll stabilizer (upright correction)
 
@@ -12,6 +12,7 @@ use crate::components::{
 };
 use crate::config::GameConfig;
 use crate::systems::physics::PhysicsUtilities;
+use crate::systems::world::terrain_height::TerrainHeightService;
 use crate::util::safe_specs::safe_clamp_f32;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
@@ -21,6 +22,7 @@ use bevy_rapier3d::prelude::*;
 pub fn ground_detection_system(
     rapier_context: ReadRapierContext,
     car_specs_assets: Res<Assets<SimpleCarSpecs>>,
+    terrain: Option<Res<TerrainHeightService>>,
     mut commands: Commands,
     warned_query: Query<(), With<MissingSpecsWarned>>,
     mut car_query: Query<
@@ -56,6 +58,14 @@ pub fn ground_detection_system(
         {
             grounded.is_grounded = true;
             grounded.ground_distance = hit_distance;
+        } else if let Some(height) = terrain
+            .as_ref()
+            .map(|terrain| ray_origin.y - terrain.height_at(ray_origin.x, ray_origin.z))
+            .filter(|height| (0.0..=ray_length).contains(height))
+        {
+            // Ray missed (e.g. between collider seams) but the terrain is within reach
+            grounded.is_grounded = true;
+            grounded.ground_distance = height;
         } else {
             grounded.is_grounded = false;
             grounded.ground_distance = ray_length;
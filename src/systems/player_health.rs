@@ -0,0 +1,424 @@
+//! Player health: damage sources, regeneration, death and respawn.
+//!
+//! Hard landings, being hit by vehicles while on foot and running out of
+//! breath underwater each send `PlayerDamaged`; `apply_player_damage` takes
+//! it off the player's `PlayerHealth`. Health regenerates after a spell
+//! without damage. At zero the player is `Wasted`: the screen fades to
+//! black, they wake up at the nearest hospital from `hospitals.ron` short
+//! the hospital fee, and the screen fades back in.
+
+use crate::bundles::PlayerPhysicsBundle;
+use crate::components::world::WorldBounds;
+use crate::components::{
+    ActiveEntity, Breath, Car, ControlState, DamageSource, F16, Helicopter, Hospital, Player,
+    PlayerDamaged, PlayerHealth, Wallet, Wasted,
+};
+use crate::config::{GameConfig, HealthConfig};
+use crate::constants::WorldEnvConfig;
+use crate::systems::shops::load_config;
+use crate::systems::swimming::{SwimState, Swimming};
+use crate::systems::ui::console::GodMode;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+
+/// A landing has to stop at least this much of the fall in one frame
+const LANDING_STOP_SHARE: f32 = 0.7;
+
+/// Seconds after a vehicle hit before another can hurt
+const VEHICLE_HIT_COOLDOWN: f32 = 1.0;
+
+/// Share of a hitting vehicle's closing speed passed on to the player
+const VEHICLE_KNOCKBACK: f32 = 0.5;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HospitalDefinition {
+    pub name: String,
+    /// Where the sign stands
+    pub position: Vec3,
+    /// Where the player wakes up
+    pub entrance: Vec3,
+}
+
+/// Hospitals loaded from `assets/config/hospitals.ron`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HospitalList {
+    #[serde(default)]
+    pub hospitals: Vec<HospitalDefinition>,
+}
+
+/// Advances the damage timer and regenerates once it has run long enough
+pub fn regenerate(health: &mut PlayerHealth, config: &HealthConfig, dt: f32) {
+    health.since_damage += dt;
+    if health.since_damage >= config.regen_delay && !health.is_dead() {
+        health.current = (health.current + health.max * config.regen_rate * dt).min(health.max);
+    }
+}
+
+/// Opacity of the death fade `elapsed` seconds after dying: black after
+/// `death_fade_time`, held while the player is moved, then faded back out
+pub fn death_fade_alpha(config: &HealthConfig, elapsed: f32) -> f32 {
+    let black_until = config.death_fade_time + config.wasted_hold_time;
+    if elapsed < config.death_fade_time {
+        elapsed / config.death_fade_time
+    } else if elapsed < black_until {
+        1.0
+    } else {
+        (1.0 - (elapsed - black_until) / config.respawn_fade_time).max(0.0)
+    }
+}
+
+pub fn spawn_hospitals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    existing: Query<(), With<Hospital>>,
+) {
+    // Hospitals persist across state re-entry
+    if !existing.is_empty() {
+        return;
+    }
+
+    let list = load_config::<HospitalList>("hospitals.ron");
+    let white = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.95, 0.95, 0.95),
+        ..default()
+    });
+    let red = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.85, 0.1, 0.1),
+        emissive: LinearRgba::rgb(0.6, 0.05, 0.05),
+        ..default()
+    });
+    let pole = meshes.add(Cylinder::new(0.12, 4.0));
+    let board = meshes.add(Cuboid::new(2.0, 2.0, 0.15));
+    let bar = meshes.add(Cuboid::new(1.4, 0.45, 0.2));
+
+    for definition in &list.hospitals {
+        let facing = (definition.entrance - definition.position).with_y(0.0);
+        commands
+            .spawn((
+                Name::new(definition.name.clone()),
+                Hospital {
+                    name: definition.name.clone(),
+                    entrance: definition.entrance,
+                },
+                Transform::from_translation(definition.position)
+                    .looking_to(facing.normalize_or(Vec3::Z), Vec3::Y),
+                Visibility::Visible,
+            ))
+            .with_children(|sign| {
+                sign.spawn((
+                    Mesh3d(pole.clone()),
+                    MeshMaterial3d(white.clone()),
+                    Transform::from_xyz(0.0, 2.0, 0.0),
+                ));
+                sign.spawn((
+                    Mesh3d(board.clone()),
+                    MeshMaterial3d(white.clone()),
+                    Transform::from_xyz(0.0, 4.8, 0.0),
+                ));
+                for rotation in [0.0, std::f32::consts::FRAC_PI_2] {
+                    sign.spawn((
+                        Mesh3d(bar.clone()),
+                        MeshMaterial3d(red.clone()),
+                        Transform::from_xyz(0.0, 4.8, 0.0)
+                            .with_rotation(Quat::from_rotation_z(rotation)),
+                    ));
+                }
+            });
+    }
+}
+
+/// Hard landings: a fall faster than the safe speed stopped short by
+/// anything but water
+#[allow(clippy::type_complexity)]
+pub fn fall_damage_system(
+    config: Res<GameConfig>,
+    mut last_sink: Local<f32>,
+    mut damage: EventWriter<PlayerDamaged>,
+    players: Query<
+        (&Velocity, &PlayerHealth, Has<Swimming>),
+        (
+            With<Player>,
+            With<ActiveEntity>,
+            Without<RigidBodyDisabled>,
+            Without<Wasted>,
+        ),
+    >,
+) {
+    let Ok((velocity, health, swimming)) = players.single() else {
+        *last_sink = 0.0;
+        return;
+    };
+    let sink = (-velocity.linvel.y).max(0.0);
+    let settings = &config.health;
+    if !swimming
+        && *last_sink > settings.fall_safe_speed
+        && sink < *last_sink * (1.0 - LANDING_STOP_SHARE)
+    {
+        let share = HealthConfig::damage_share(
+            *last_sink,
+            settings.fall_safe_speed,
+            settings.fall_lethal_speed,
+        );
+        damage.write(PlayerDamaged {
+            amount: share * health.max,
+            source: DamageSource::Fall,
+        });
+        info!("Player landed hard at {:.0} m/s", *last_sink);
+    }
+    *last_sink = if swimming { 0.0 } else { sink };
+}
+
+/// Vehicles driving into the player on foot hurt and knock them aside
+#[allow(clippy::type_complexity)]
+pub fn vehicle_impact_damage_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut cooldown: Local<f32>,
+    mut damage: EventWriter<PlayerDamaged>,
+    vehicles: Query<
+        (&GlobalTransform, &Velocity),
+        (
+            Or<(With<Car>, With<Helicopter>, With<F16>)>,
+            Without<RigidBodyDisabled>,
+            Without<Player>,
+        ),
+    >,
+    mut players: Query<
+        (&GlobalTransform, &mut Velocity, &PlayerHealth),
+        (
+            With<Player>,
+            With<ActiveEntity>,
+            Without<RigidBodyDisabled>,
+            Without<Wasted>,
+        ),
+    >,
+) {
+    *cooldown -= time.delta_secs();
+    if *cooldown > 0.0 {
+        return;
+    }
+    let Ok((player_gt, mut player_velocity, health)) = players.single_mut() else {
+        return;
+    };
+    let settings = &config.health;
+    let player_pos = player_gt.translation();
+
+    for (vehicle_gt, vehicle_velocity) in &vehicles {
+        let vehicle_pos = vehicle_gt.translation();
+        if vehicle_pos.distance_squared(player_pos) > config.npc.ragdoll_impact_radius.powi(2) {
+            continue;
+        }
+        let relative = vehicle_velocity.linvel - player_velocity.linvel;
+        // Only a vehicle closing on the player hits them
+        if relative.dot(player_pos - vehicle_pos) <= 0.0 {
+            continue;
+        }
+        let speed = relative.length();
+        let share = HealthConfig::damage_share(
+            speed,
+            settings.vehicle_safe_speed,
+            settings.vehicle_lethal_speed,
+        );
+        if share <= 0.0 {
+            continue;
+        }
+
+        player_velocity.linvel +=
+            relative.with_y(0.0) * VEHICLE_KNOCKBACK + Vec3::Y * speed * VEHICLE_KNOCKBACK * 0.5;
+        damage.write(PlayerDamaged {
+            amount: share * health.max,
+            source: DamageSource::Vehicle,
+        });
+        *cooldown = VEHICLE_HIT_COOLDOWN;
+        info!("Player hit by a vehicle at {:.0} m/s", speed);
+        return;
+    }
+}
+
+/// Diving on after the breath has run out drowns the player
+#[allow(clippy::type_complexity)]
+pub fn drowning_damage_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut damage: EventWriter<PlayerDamaged>,
+    players: Query<(&Breath, &Swimming), (With<Player>, Without<Wasted>)>,
+) {
+    for (breath, swimming) in &players {
+        if swimming.state == SwimState::Diving && breath.remaining <= 0.0 {
+            damage.write(PlayerDamaged {
+                amount: config.health.drowning_rate * time.delta_secs(),
+                source: DamageSource::Drowning,
+            });
+        }
+    }
+}
+
+/// Takes damage off the player's health and starts the death sequence when
+/// it runs out; god mode shrugs it all off
+#[allow(clippy::type_complexity)]
+pub fn apply_player_damage(
+    mut commands: Commands,
+    god_mode: Option<Res<GodMode>>,
+    mut events: EventReader<PlayerDamaged>,
+    mut players: Query<(Entity, &mut PlayerHealth), (With<Player>, Without<Wasted>)>,
+) {
+    let invulnerable = god_mode.is_some_and(|god_mode| god_mode.0);
+    let Some((entity, mut health)) = players.single_mut().ok().filter(|_| !invulnerable) else {
+        events.clear();
+        return;
+    };
+    for event in events.read() {
+        if health.damage(event.amount) {
+            commands.entity(entity).insert(Wasted::default());
+            info!("Wasted by {:?}", event.source);
+            break;
+        }
+    }
+    events.clear();
+}
+
+pub fn player_health_regen_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut players: Query<&mut PlayerHealth, Without<Wasted>>,
+) {
+    for mut health in &mut players {
+        regenerate(&mut health, &config.health, time.delta_secs());
+    }
+}
+
+/// Runs the death sequence: holds the player still while the screen goes
+/// black, wakes them at the nearest hospital with full health, charges the
+/// fee and hands control back once the screen has faded in again
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn wasted_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    env: Res<WorldEnvConfig>,
+    world_bounds: Option<Res<WorldBounds>>,
+    mut wallet: ResMut<Wallet>,
+    hospitals: Query<&Hospital>,
+    mut players: Query<
+        (
+            Entity,
+            &mut Wasted,
+            &mut PlayerHealth,
+            &mut Transform,
+            &mut ControlState,
+        ),
+        With<Player>,
+    >,
+) {
+    let Ok((entity, mut wasted, mut health, mut transform, mut control_state)) =
+        players.single_mut()
+    else {
+        return;
+    };
+    let settings = &config.health;
+    wasted.elapsed += time.delta_secs();
+    *control_state = ControlState::default();
+
+    if !wasted.respawned && wasted.elapsed >= settings.death_fade_time + settings.wasted_hold_time {
+        let position = transform.translation;
+        let respawn = hospitals
+            .iter()
+            .min_by(|a, b| {
+                a.entrance
+                    .distance_squared(position)
+                    .total_cmp(&b.entrance.distance_squared(position))
+            })
+            .map(|hospital| {
+                info!("Player wakes up at {}", hospital.name);
+                hospital.entrance + Vec3::Y
+            })
+            .or_else(|| {
+                world_bounds
+                    .as_ref()
+                    .map(|bounds| bounds.safe_respawn_position(env.land_elevation))
+            })
+            .unwrap_or(Vec3::new(env.islands.left_x, env.land_elevation + 1.0, 0.0));
+
+        *transform = Transform::from_translation(respawn);
+        *health = PlayerHealth::new(settings.max_health);
+        let fee = config.economy.hospital_fee.min(wallet.balance());
+        wallet.try_spend(fee);
+        // Also clears whatever velocity the fatal hit left behind
+        commands
+            .entity(entity)
+            .insert(PlayerPhysicsBundle::default());
+        wasted.respawned = true;
+    }
+
+    if wasted.respawned
+        && wasted.elapsed
+            >= settings.death_fade_time + settings.wasted_hold_time + settings.respawn_fade_time
+    {
+        commands.entity(entity).remove::<Wasted>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_regenerates_after_a_quiet_spell() {
+        let config = HealthConfig::default();
+        let mut health = PlayerHealth::new(100.0);
+        health.damage(60.0);
+
+        regenerate(&mut health, &config, config.regen_delay * 0.5);
+        assert_eq!(health.current, 40.0);
+
+        for _ in 0..600 {
+            regenerate(&mut health, &config, 0.1);
+        }
+        assert_eq!(health.current, health.max);
+    }
+
+    #[test]
+    fn test_god_mode_survives_lethal_damage() {
+        let mut app = App::new();
+        app.add_event::<PlayerDamaged>()
+            .insert_resource(GodMode(true))
+            .add_systems(Update, apply_player_damage);
+        let player = app
+            .world_mut()
+            .spawn((Player, PlayerHealth::new(100.0)))
+            .id();
+        app.world_mut().send_event(PlayerDamaged {
+            amount: 500.0,
+            source: DamageSource::Fall,
+        });
+        app.update();
+
+        let world = app.world();
+        assert!(!world.entity(player).contains::<Wasted>());
+        assert_eq!(world.get::<PlayerHealth>(player).unwrap().current, 100.0);
+
+        // Without it the same hit is fatal
+        app.insert_resource(GodMode(false));
+        app.world_mut().send_event(PlayerDamaged {
+            amount: 500.0,
+            source: DamageSource::Fall,
+        });
+        app.update();
+        assert!(app.world().entity(player).contains::<Wasted>());
+    }
+
+    #[test]
+    fn test_death_fade_goes_black_then_clears() {
+        let config = HealthConfig::default();
+        let black = config.death_fade_time + config.wasted_hold_time;
+        assert_eq!(death_fade_alpha(&config, 0.0), 0.0);
+        assert_eq!(death_fade_alpha(&config, black - 0.01), 1.0);
+        assert!(death_fade_alpha(&config, black + config.respawn_fade_time * 0.5) < 1.0);
+        assert_eq!(
+            death_fade_alpha(&config, black + config.respawn_fade_time),
+            0.0
+        );
+    }
+}
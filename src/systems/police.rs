@@ -0,0 +1,291 @@
+//! Wanted level and police response.
+//!
+//! Hitting pedestrians or ramming vehicles while driving raises heat, as do car
+//! alarms set off once the player is already well wanted; heat maps to wanted
+//! stars, each star brings pursuit cars, and heat decays after a quiet period.
+
+use crate::components::{
+    ActiveEntity, CrimeCommitted, CrimeKind, NPC, PlayerOwned, PoliceUnit, VehicleState,
+    VehicleType, WantedLevel, WantedLevelChanged, WantedStarsText,
+};
+use crate::config::{GameConfig, Indicator};
+use crate::factories::VehicleFactory;
+use crate::game_state::GameState;
+use crate::resources::WorldRng;
+use crate::systems::spatial_index::SpatialIndex;
+use crate::t;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use rand::Rng;
+
+/// Contact distance between the player's vehicle center and a pedestrian
+const PEDESTRIAN_HIT_DISTANCE: f32 = 2.5;
+/// Contact distance between two vehicle centers
+const VEHICLE_HIT_DISTANCE: f32 = 4.5;
+/// Pursuit cars slow down inside this distance to avoid overshooting
+const PURSUIT_CLOSE_DISTANCE: f32 = 12.0;
+/// How far an indexed position may trail its entity's live transform
+const SPATIAL_INDEX_SLACK: f32 = 5.0;
+
+const WANTED_YELLOW: Color = Color::srgb(1.0, 0.85, 0.1);
+
+/// Flags pedestrians and vehicles the player drives into at speed
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn detect_player_crimes(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    state: Res<State<GameState>>,
+    mut cooldown: Local<f32>,
+    active_query: Query<(Entity, &Transform, &Velocity), With<ActiveEntity>>,
+    npc_query: Query<&Transform, (With<NPC>, Without<ActiveEntity>)>,
+    vehicle_query: Query<(Entity, &Transform), (With<VehicleState>, Without<ActiveEntity>)>,
+    index: Res<SpatialIndex>,
+    mut crimes: EventWriter<CrimeCommitted>,
+) {
+    *cooldown -= time.delta_secs();
+    if *cooldown > 0.0 || *state.get() != GameState::Driving {
+        return;
+    }
+    let Ok((active_entity, active_transform, velocity)) = active_query.single() else {
+        return;
+    };
+    let police = &config.police;
+    if velocity.linvel.length() < police.hit_speed_threshold {
+        return;
+    }
+    let position = active_transform.translation;
+
+    // The index lags a frame, so candidates are confirmed against live transforms
+    let nearby = index.query_radius(position, VEHICLE_HIT_DISTANCE + SPATIAL_INDEX_SLACK);
+    let kind = if nearby.iter().any(|e| {
+        npc_query
+            .get(e.entity)
+            .is_ok_and(|t| t.translation.distance(position) < PEDESTRIAN_HIT_DISTANCE)
+    }) {
+        Some(CrimeKind::HitPedestrian)
+    } else if nearby.iter().any(|e| {
+        e.entity != active_entity
+            && vehicle_query
+                .get(e.entity)
+                .is_ok_and(|(_, t)| t.translation.distance(position) < VEHICLE_HIT_DISTANCE)
+    }) {
+        Some(CrimeKind::HitVehicle)
+    } else {
+        None
+    };
+
+    if let Some(kind) = kind {
+        *cooldown = police.crime_cooldown;
+        crimes.write(CrimeCommitted { kind, position });
+    }
+}
+
+/// Escalates heat on crimes and decays it after a quiet period
+pub fn update_wanted_level(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut wanted: ResMut<WantedLevel>,
+    mut crimes: EventReader<CrimeCommitted>,
+    mut changed: EventWriter<WantedLevelChanged>,
+) {
+    let police = &config.police;
+    let mut heat = wanted.heat;
+    let mut time_since_crime = wanted.time_since_crime + time.delta_secs();
+
+    for crime in crimes.read() {
+        heat += match crime.kind {
+            CrimeKind::HitPedestrian => police.pedestrian_hit_heat,
+            CrimeKind::HitVehicle => police.vehicle_hit_heat,
+            CrimeKind::CarAlarm => police.alarm_heat,
+        };
+        time_since_crime = 0.0;
+    }
+
+    if time_since_crime > police.decay_delay {
+        heat -= police.decay_rate * time.delta_secs();
+    }
+    let max_heat = police.max_stars as f32 * police.heat_per_star;
+    heat = heat.clamp(0.0, max_heat);
+
+    // Avoid triggering change detection every frame when idle
+    if heat != wanted.heat {
+        wanted.heat = heat;
+    }
+    wanted.time_since_crime = time_since_crime;
+
+    let stars = police.stars_for_heat(heat);
+    if stars != wanted.stars {
+        info!("🚨 Wanted level: {} → {} stars", wanted.stars, stars);
+        changed.write(WantedLevelChanged {
+            previous: wanted.stars,
+            stars,
+        });
+        wanted.stars = stars;
+    }
+}
+
+/// Keeps the number of pursuit cars in line with the wanted level
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn spawn_police_units(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    wanted: Res<WantedLevel>,
+    mut world_rng: ResMut<WorldRng>,
+    mut spawn_timer: Local<f32>,
+    active_query: Query<&Transform, With<ActiveEntity>>,
+    police_query: Query<(Entity, &Transform), (With<PoliceUnit>, Without<ActiveEntity>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    let police = &config.police;
+    let Ok(active_transform) = active_query.single() else {
+        return;
+    };
+    let player_pos = active_transform.translation;
+
+    // Called off: drop units that have fallen out of range
+    if wanted.stars == 0 {
+        for (entity, transform) in police_query.iter() {
+            if transform.translation.distance(player_pos) > police.despawn_distance {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    }
+
+    *spawn_timer -= time.delta_secs();
+    let wanted_units = wanted.stars as usize * police.units_per_star;
+    if *spawn_timer > 0.0 || police_query.iter().count() >= wanted_units {
+        return;
+    }
+    *spawn_timer = police.spawn_interval;
+
+    let angle = world_rng.global().gen_range(0.0..std::f32::consts::TAU);
+    let offset = Vec3::new(angle.cos(), 0.0, angle.sin()) * police.spawn_distance;
+    let position = Vec3::new(
+        player_pos.x + offset.x,
+        player_pos.y.max(0.0) + 1.0,
+        player_pos.z + offset.z,
+    );
+
+    let factory = VehicleFactory::with_config(config.clone());
+    match factory.spawn_vehicle_by_type(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        &asset_server,
+        VehicleType::SuperCar,
+        position,
+        Some(Color::srgb(0.05, 0.1, 0.35)),
+    ) {
+        Ok(entity) => {
+            commands.entity(entity).insert(PoliceUnit);
+            info!("🚓 Police unit dispatched ({} stars)", wanted.stars);
+        }
+        Err(e) => warn!("⚠️ Failed to spawn police unit: {:?}", e),
+    }
+}
+
+/// Drives police cars straight at the player while wanted
+#[allow(clippy::type_complexity)]
+pub fn police_pursuit_system(
+    config: Res<GameConfig>,
+    wanted: Res<WantedLevel>,
+    active_query: Query<&Transform, With<ActiveEntity>>,
+    mut police_query: Query<
+        (&Transform, &mut Velocity),
+        (
+            With<PoliceUnit>,
+            Without<ActiveEntity>,
+            Without<PlayerOwned>,
+        ),
+    >,
+) {
+    let Ok(active_transform) = active_query.single() else {
+        return;
+    };
+    let target = active_transform.translation;
+
+    for (transform, mut velocity) in police_query.iter_mut() {
+        if wanted.stars == 0 {
+            velocity.linvel.x *= 0.9;
+            velocity.linvel.z *= 0.9;
+            velocity.angvel = Vec3::ZERO;
+            continue;
+        }
+
+        let to_target = (target - transform.translation).with_y(0.0);
+        let distance = to_target.length();
+        let direction = to_target.normalize_or_zero();
+        let speed = config.police.pursuit_speed * (distance / PURSUIT_CLOSE_DISTANCE).min(1.0);
+
+        velocity.linvel.x = direction.x * speed;
+        velocity.linvel.z = direction.z * speed;
+
+        let heading = transform.forward().with_y(0.0).normalize_or_zero();
+        velocity.angvel = Vec3::new(0.0, heading.cross(direction).y.asin() * 2.0, 0.0);
+    }
+}
+
+pub fn setup_wanted_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 24.0,
+            ..default()
+        },
+        TextColor(WANTED_YELLOW),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            right: Val::Px(10.0),
+            ..default()
+        },
+        WantedStarsText,
+    ));
+}
+
+pub fn update_wanted_hud(
+    config: Res<GameConfig>,
+    wanted: Res<WantedLevel>,
+    mut hud_query: Query<(&mut Text, &mut TextColor), With<WantedStarsText>>,
+) {
+    if !wanted.is_changed() && !config.is_changed() {
+        return;
+    }
+    let Ok((mut text, mut color)) = hud_query.single_mut() else {
+        return;
+    };
+    color.set_if_neq(TextColor(
+        config
+            .accessibility
+            .indicator(Indicator::Warning, WANTED_YELLOW),
+    ));
+
+    text.0 = if wanted.stars == 0 {
+        String::new()
+    } else {
+        let empty = config.police.max_stars.saturating_sub(wanted.stars) as usize;
+        t!(
+            "hud.wanted",
+            stars = format!("{}{}", "*".repeat(wanted.stars as usize), "-".repeat(empty))
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::PoliceConfig;
+
+    #[test]
+    fn test_stars_for_heat() {
+        let config = PoliceConfig::default();
+
+        assert_eq!(config.stars_for_heat(0.0), 0);
+        assert_eq!(config.stars_for_heat(0.5), 1);
+        assert_eq!(config.stars_for_heat(config.heat_per_star * 2.0), 2);
+        assert_eq!(config.stars_for_heat(1000.0), config.max_stars);
+    }
+}
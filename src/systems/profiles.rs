@@ -0,0 +1,212 @@
+//! Player profiles.
+//!
+//! Each profile keeps its own saves, settings, key bindings and stunt scores
+//! under `<data dir>/gta_game/profiles/<name>`, and may add config overrides
+//! in `<config dir>/gta_game/profiles/<name>` that are layered over the user
+//! config. The `default` profile uses the directories themselves, so data from
+//! before profiles existed stays where it was.
+//!
+//! The `ActiveProfile` resource starts as `GTA_PROFILE` if set, else the one
+//! last picked from the main menu; systems that read or write profile files
+//! take their directories from it. Switching sends a `ProfileChangedEvent`;
+//! settings, bindings and the save list reload at once, while config
+//! overrides apply from the next launch because the world is sized from them
+//! at startup.
+
+use crate::config::user_config_directory;
+use crate::systems::persistence::data_directory;
+use crate::systems::ui::main_menu::LatestSave;
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+const MAX_NAME_LEN: usize = 32;
+
+/// Profile in use, by name
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ActiveProfile(pub String);
+
+impl Default for ActiveProfile {
+    fn default() -> Self {
+        Self(DEFAULT_PROFILE.to_string())
+    }
+}
+
+impl ActiveProfile {
+    /// `GTA_PROFILE` if set, else the profile last picked from the menu
+    pub fn from_environment() -> Self {
+        let name = std::env::var("GTA_PROFILE")
+            .ok()
+            .filter(|name| is_valid_profile_name(name))
+            .or_else(|| {
+                let contents = std::fs::read_to_string(last_profile_path()).ok()?;
+                ron::from_str::<String>(&contents)
+                    .ok()
+                    .filter(|name| is_valid_profile_name(name))
+            });
+        name.map(Self).unwrap_or_default()
+    }
+
+    /// Makes `name` the active profile and remembers it for the next launch
+    pub fn switch(&mut self, name: &str) -> Result<(), String> {
+        if !is_valid_profile_name(name) {
+            return Err(format!("invalid profile name '{name}'"));
+        }
+        std::fs::create_dir_all(profile_data_directory(name)).map_err(|e| e.to_string())?;
+        self.0 = name.to_string();
+        let contents = ron::to_string(name).map_err(|e| e.to_string())?;
+        std::fs::write(last_profile_path(), contents).map_err(|e| e.to_string())
+    }
+
+    /// Data directory of this profile: saves, settings and bindings
+    pub fn data_directory(&self) -> PathBuf {
+        profile_data_directory(&self.0)
+    }
+
+    /// Config overrides of this profile, layered after the user config
+    /// directory; `None` for the default profile, which has none of its own
+    pub fn config_directory(&self) -> Option<PathBuf> {
+        (self.0 != DEFAULT_PROFILE).then(|| profile_subdirectory(user_config_directory(), &self.0))
+    }
+}
+
+/// Sent after the main menu switches profile
+#[derive(Event, Debug, Clone)]
+pub struct ProfileChangedEvent {
+    pub profile: String,
+}
+
+/// Names double as directory names, so only plain ASCII words are allowed
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Where the last profile picked from the menu is remembered
+fn last_profile_path() -> PathBuf {
+    data_directory().join("profile.ron")
+}
+
+fn profile_subdirectory(root: PathBuf, name: &str) -> PathBuf {
+    if name == DEFAULT_PROFILE {
+        root
+    } else {
+        root.join("profiles").join(name)
+    }
+}
+
+pub fn profile_data_directory(name: &str) -> PathBuf {
+    profile_subdirectory(data_directory(), name)
+}
+
+fn profile_names_in(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir.join("profiles")) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_valid_profile_name(name))
+        .collect()
+}
+
+/// Every profile with data or config overrides, and `active`, default first
+pub fn list_profiles(active: &ActiveProfile) -> Vec<String> {
+    let mut names = profile_names_in(&data_directory());
+    names.extend(profile_names_in(&user_config_directory()));
+    names.push(active.0.clone());
+    names.retain(|name| name != DEFAULT_PROFILE);
+    names.sort();
+    names.dedup();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    names
+}
+
+/// Profile after `current` in `profiles`, wrapping around
+pub fn next_profile(current: &str, profiles: &[String]) -> String {
+    profiles
+        .iter()
+        .position(|name| name == current)
+        .and_then(|i| profiles.get((i + 1) % profiles.len()))
+        .or(profiles.first())
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// First `player<N>` name not in `profiles`
+pub fn unused_profile_name(profiles: &[String]) -> String {
+    (2..)
+        .map(|n| format!("player{n}"))
+        .find(|name| !profiles.contains(name))
+        .unwrap_or_default()
+}
+
+/// Points Continue at the new profile's latest save
+pub fn refresh_latest_save(
+    mut changed: EventReader<ProfileChangedEvent>,
+    profile: Res<ActiveProfile>,
+    latest_save: Option<ResMut<LatestSave>>,
+) {
+    if changed.read().last().is_none() {
+        return;
+    }
+    if let Some(mut latest_save) = latest_save {
+        latest_save.0 = crate::systems::persistence::latest_save_slot(&profile.data_directory());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names_are_plain_words() {
+        assert!(is_valid_profile_name("player2"));
+        assert!(is_valid_profile_name("ab_test-b"));
+        assert!(!is_valid_profile_name(""));
+        assert!(!is_valid_profile_name("../saves"));
+        assert!(!is_valid_profile_name("two words"));
+        assert!(!is_valid_profile_name(&"x".repeat(MAX_NAME_LEN + 1)));
+    }
+
+    #[test]
+    fn test_profile_cycling_and_new_names() {
+        let profiles: Vec<String> = ["default", "player2", "tester"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(next_profile("default", &profiles), "player2");
+        assert_eq!(next_profile("tester", &profiles), "default");
+        assert_eq!(next_profile("gone", &profiles), "default");
+        assert_eq!(unused_profile_name(&profiles), "player3");
+        assert_eq!(
+            profile_subdirectory(PathBuf::from("/data"), "tester"),
+            PathBuf::from("/data/profiles/tester")
+        );
+        assert_eq!(
+            profile_subdirectory(PathBuf::from("/data"), DEFAULT_PROFILE),
+            PathBuf::from("/data")
+        );
+    }
+
+    #[test]
+    fn test_each_profile_resolves_its_own_directories() {
+        let default = ActiveProfile::default();
+        let tester = ActiveProfile("tester".to_string());
+        assert_eq!(default.data_directory(), data_directory());
+        assert_eq!(
+            tester.data_directory(),
+            data_directory().join("profiles").join("tester")
+        );
+        assert_eq!(default.config_directory(), None);
+        assert_eq!(
+            tester.config_directory(),
+            Some(user_config_directory().join("profiles").join("tester"))
+        );
+    }
+}
@@ -0,0 +1,691 @@
+//! Races against AI opponents.
+//!
+//! Races are RON files in `assets/config/races`, one per race. Stopping a car
+//! in a race's start marker (or `StartRaceRequest`, e.g. from the `race`
+//! console command) lines the player up on the grid with the opponents, and
+//! a countdown holds everyone until the start. Racers pass the checkpoints in
+//! order for the given number of laps; `RaceManager` keeps lap times and
+//! ranks them by distance covered. Opponents follow the race's recorded
+//! racing line with rubber-banding against the player, who can record new
+//! lines with `record_line`. Once the player crosses the finish the results
+//! stay up for a few seconds and the race is cleared away.
+
+use crate::components::race::{
+    Checkpoint, RaceCatalog, RaceDefinition, RaceFinished, RaceManager, RaceOpponent, RacePhase,
+    RaceSession, RaceStartMarker, RaceUiText, RacerProgress, RacingLinePoint, StartRaceRequest,
+    rubber_band_factor,
+};
+use crate::components::{ActiveEntity, Car, ControlState, VehicleType};
+use crate::config::GameConfig;
+use crate::factories::VehicleFactory;
+use crate::systems::persistence::data_directory;
+use crate::systems::ui::console::{ConsoleCommands, ConsoleResult};
+use crate::t;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use std::path::Path;
+
+const COUNTDOWN_SECONDS: f32 = 3.0;
+const RESULTS_SECONDS: f32 = 8.0;
+
+/// "GO!" stays up this long after the start
+const GO_SECONDS: f32 = 1.0;
+
+const START_MARKER_RADIUS: f32 = 6.0;
+/// A car has to be slower than this in a start marker to enter the race
+const START_MAX_SPEED: f32 = 3.0;
+
+/// Opponents move on to the next racing line point this close to it
+const LINE_POINT_REACHED: f32 = 12.0;
+/// Opponent speed when a race has no racing line
+const CHECKPOINT_SPEED: f32 = 30.0;
+const OPPONENT_ACCELERATION: f32 = 12.0;
+/// Rubber-banding is at full strength this many meters ahead or behind
+const RUBBER_BAND_RANGE: f32 = 150.0;
+
+/// Meters between recorded racing line points
+const RECORD_SPACING: f32 = 15.0;
+
+/// Racing line being recorded from the player's driving
+#[derive(Resource, Debug, Default)]
+pub struct RacingLineRecorder {
+    pub recording: bool,
+    pub points: Vec<RacingLinePoint>,
+}
+
+fn vec3((x, y, z): (f32, f32, f32)) -> Vec3 {
+    Vec3::new(x, y, z)
+}
+
+/// Race clock as `m:ss.s`
+pub fn format_race_time(seconds: f32) -> String {
+    let tenths = (seconds.max(0.0) * 10.0).round() as u32;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+/// Parses every `.ron` file in `dir`, in file name order
+pub fn load_races(dir: &Path) -> Vec<RaceDefinition> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        info!("ℹ️ No race directory at {:?}, races disabled", dir);
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| error!("Failed to read race {:?}: {}", path, e))
+                .ok()?;
+            let race: RaceDefinition = ron::from_str(&content)
+                .map_err(|e| error!("Failed to parse race {:?}: {}", path, e))
+                .ok()?;
+            if race.checkpoints.is_empty() || race.laps == 0 {
+                warn!("⚠️ Race '{}' has no checkpoints or laps, skipped", race.id);
+                return None;
+            }
+            Some(race)
+        })
+        .collect()
+}
+
+pub fn load_race_catalog(mut commands: Commands) {
+    let dir = Path::new(&crate::util::asset_path::get_assets_base_path()).join("config/races");
+    let races = load_races(&dir);
+    #[cfg(feature = "debug-ui")]
+    info!("✅ Loaded {} races", races.len());
+    commands.insert_resource(RaceCatalog { races });
+}
+
+pub fn spawn_race_start_markers(
+    mut commands: Commands,
+    catalog: Res<RaceCatalog>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mesh = meshes.add(Cylinder::new(START_MARKER_RADIUS, 0.1));
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.9, 0.9, 0.9, 0.6),
+        emissive: LinearRgba::rgb(0.5, 0.5, 0.5),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    for race in &catalog.races {
+        commands.spawn((
+            Name::new(format!("Race start: {}", race.id)),
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(vec3(race.start)),
+            RaceStartMarker {
+                race_id: race.id.clone(),
+            },
+        ));
+    }
+}
+
+/// Enters the race whose marker the player's car comes to a stop in. A car
+/// that stays in the marker after a race has to leave it before it counts
+/// again.
+#[allow(clippy::type_complexity)]
+pub fn enter_races_at_markers(
+    manager: Res<RaceManager>,
+    car: Query<(&Transform, &Velocity), (With<ActiveEntity>, With<Car>)>,
+    markers: Query<(&Transform, &RaceStartMarker)>,
+    mut last_marker: Local<Option<String>>,
+    mut requests: EventWriter<StartRaceRequest>,
+) {
+    let Ok((car, velocity)) = car.single() else {
+        *last_marker = None;
+        return;
+    };
+    let inside = markers.iter().find(|(marker, _)| {
+        marker.translation.xz().distance(car.translation.xz()) <= START_MARKER_RADIUS
+    });
+    let Some((_, marker)) = inside else {
+        *last_marker = None;
+        return;
+    };
+    if manager.0.is_some() || last_marker.as_deref() == Some(marker.race_id.as_str()) {
+        return;
+    }
+    if velocity.linvel.length() < START_MAX_SPEED {
+        *last_marker = Some(marker.race_id.clone());
+        requests.write(StartRaceRequest {
+            race_id: marker.race_id.clone(),
+        });
+    }
+}
+
+/// Lines the player's car up on the grid, spawns the opponents and
+/// checkpoints and starts the countdown
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn start_requested_races(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    config: Res<GameConfig>,
+    catalog: Res<RaceCatalog>,
+    mut manager: ResMut<RaceManager>,
+    mut requests: EventReader<StartRaceRequest>,
+    mut car: Query<(Entity, &mut Transform, &mut Velocity), (With<ActiveEntity>, With<Car>)>,
+) {
+    for request in requests.read() {
+        let Some(race) = catalog.get(&request.race_id) else {
+            warn!("⚠️ Unknown race '{}' requested", request.race_id);
+            continue;
+        };
+        if manager.0.is_some() {
+            continue;
+        }
+        let Ok((player, mut transform, mut velocity)) = car.single_mut() else {
+            info!("ℹ️ Race '{}' needs the player in a car", race.id);
+            continue;
+        };
+
+        let ground = transform.translation.y;
+        let grid = race.grid_slot(0);
+        *transform = grid.with_translation(grid.translation.with_y(ground));
+        *velocity = Velocity::zero();
+
+        let mut racers = vec![RacerProgress {
+            entity: player,
+            name: t!("race.you"),
+            is_player: true,
+            passed: 0,
+            distance: 0.0,
+            lap_times: Vec::new(),
+            finish_time: None,
+        }];
+
+        let factory = VehicleFactory::with_config(config.clone());
+        for (slot, opponent) in race.opponents.iter().enumerate() {
+            let grid = race.grid_slot(slot + 1);
+            let position = grid.translation.with_y(ground + 0.5);
+            let (r, g, b) = opponent.color;
+            let entity = match factory.spawn_vehicle_by_type(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &asset_server,
+                VehicleType::SuperCar,
+                position,
+                Some(Color::srgb(r, g, b)),
+            ) {
+                Ok(entity) => entity,
+                Err(e) => {
+                    warn!("⚠️ Failed to spawn race opponent: {:?}", e);
+                    continue;
+                }
+            };
+            commands.entity(entity).insert((
+                Transform::from_translation(position).with_rotation(grid.rotation),
+                RaceOpponent {
+                    racer: racers.len(),
+                    skill: opponent.skill,
+                    line_index: 0,
+                },
+            ));
+            racers.push(RacerProgress {
+                entity,
+                name: opponent.name.clone(),
+                is_player: false,
+                passed: 0,
+                distance: 0.0,
+                lap_times: Vec::new(),
+                finish_time: None,
+            });
+        }
+
+        let mesh = meshes.add(Cylinder::new(race.checkpoint_radius, 8.0));
+        let material = materials.add(StandardMaterial {
+            base_color: Color::srgba(1.0, 0.85, 0.1, 0.25),
+            emissive: LinearRgba::rgb(0.8, 0.6, 0.05),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        });
+        for index in 0..race.checkpoints.len() {
+            commands.spawn((
+                Name::new(format!("Checkpoint {index}")),
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(race.checkpoint(index) + Vec3::Y * 4.0),
+                Visibility::Hidden,
+                Checkpoint { index },
+            ));
+        }
+
+        info!(
+            "🏁 Race started: {} ({} opponents)",
+            race.id,
+            racers.len() - 1
+        );
+        manager.0 = Some(RaceSession {
+            race_id: race.id.clone(),
+            phase: RacePhase::Countdown(COUNTDOWN_SECONDS),
+            clock: 0.0,
+            racers,
+            abandoned: false,
+        });
+    }
+}
+
+/// Keeps the player's car on the brakes until the start signal
+pub fn hold_player_at_start(
+    manager: Res<RaceManager>,
+    mut controls: Query<&mut ControlState, (With<ActiveEntity>, With<Car>)>,
+) {
+    let Some(session) = manager.0.as_ref() else {
+        return;
+    };
+    if matches!(session.phase, RacePhase::Countdown(_)) {
+        for mut control in &mut controls {
+            control.throttle = 0.0;
+            control.reverse = 0.0;
+            control.brake = 1.0;
+        }
+    }
+}
+
+/// Runs the countdown, counts checkpoints and laps, and ends the race when
+/// the player finishes or leaves the car
+pub fn race_progress_system(
+    time: Res<Time>,
+    catalog: Res<RaceCatalog>,
+    mut manager: ResMut<RaceManager>,
+    mut finished: EventWriter<RaceFinished>,
+    active: Query<Entity, With<ActiveEntity>>,
+    transforms: Query<&Transform>,
+) {
+    let Some(session) = manager.0.as_mut() else {
+        return;
+    };
+    let Some(race) = catalog.get(&session.race_id) else {
+        manager.0 = None;
+        return;
+    };
+    let dt = time.delta_secs();
+
+    match session.phase {
+        RacePhase::Countdown(remaining) => {
+            session.phase = if remaining > dt {
+                RacePhase::Countdown(remaining - dt)
+            } else {
+                RacePhase::Racing
+            };
+        }
+        RacePhase::Results(remaining) => {
+            if remaining > dt {
+                session.phase = RacePhase::Results(remaining - dt);
+            } else {
+                manager.0 = None;
+            }
+            return;
+        }
+        RacePhase::Racing => session.clock += dt,
+    }
+
+    let Some((player_index, player)) = session.player() else {
+        return;
+    };
+    let player_entity = player.entity;
+    if active.single().ok() != Some(player_entity) || transforms.get(player_entity).is_err() {
+        info!("🏁 Race abandoned: {}", race.id);
+        session.abandoned = true;
+        session.phase = RacePhase::Results(RESULTS_SECONDS);
+        finished.write(RaceFinished {
+            race_id: race.id.clone(),
+            position: None,
+            time: session.clock,
+            reward: 0,
+        });
+        return;
+    }
+    if session.phase != RacePhase::Racing {
+        return;
+    }
+
+    let clock = session.clock;
+    for racer in &mut session.racers {
+        if racer.finish_time.is_some() {
+            continue;
+        }
+        let Ok(transform) = transforms.get(racer.entity) else {
+            continue;
+        };
+        let position = transform.translation;
+        if position.xz().distance(race.checkpoint(racer.passed).xz()) <= race.checkpoint_radius {
+            racer.passed += 1;
+            if racer.passed % race.checkpoints.len() == 0 {
+                let previous: f32 = racer.lap_times.iter().sum();
+                racer.lap_times.push(clock - previous);
+            }
+            if racer.passed >= race.total_checkpoints() {
+                racer.finish_time = Some(clock);
+            }
+        }
+        racer.distance = race.course_distance(racer.passed, position);
+    }
+
+    if session.racers[player_index].finish_time.is_some() {
+        let position = session.position_of(player_index);
+        let reward = if position == 1 { race.reward } else { 0 };
+        info!(
+            "🏁 Race finished: {} in position {} ({})",
+            race.id,
+            position,
+            format_race_time(clock)
+        );
+        session.phase = RacePhase::Results(RESULTS_SECONDS);
+        finished.write(RaceFinished {
+            race_id: race.id.clone(),
+            position: Some(position),
+            time: clock,
+            reward,
+        });
+    }
+}
+
+/// Drives the opponents along the racing line, faster when behind the player
+/// and slower when ahead
+#[allow(clippy::type_complexity)]
+pub fn race_opponent_ai(
+    time: Res<Time>,
+    catalog: Res<RaceCatalog>,
+    manager: Res<RaceManager>,
+    mut opponents: Query<(&Transform, &mut Velocity, &mut RaceOpponent), Without<ActiveEntity>>,
+) {
+    let Some(session) = manager.0.as_ref() else {
+        return;
+    };
+    let Some(race) = catalog.get(&session.race_id) else {
+        return;
+    };
+    let dt = time.delta_secs();
+    let player_distance = session.player().map_or(0.0, |(_, p)| p.distance);
+
+    for (transform, mut velocity, mut opponent) in &mut opponents {
+        let Some(racer) = session.racers.get(opponent.racer) else {
+            continue;
+        };
+        let position = transform.translation;
+        let racing = session.phase == RacePhase::Racing && racer.finish_time.is_none();
+
+        let (target, line_speed) = match race.racing_line.get(opponent.line_index) {
+            Some(point) => {
+                if position.xz().distance(vec3(point.position).xz()) < LINE_POINT_REACHED {
+                    opponent.line_index = (opponent.line_index + 1) % race.racing_line.len();
+                }
+                let point = race.racing_line[opponent.line_index];
+                (vec3(point.position), point.speed)
+            }
+            None => (race.checkpoint(racer.passed), CHECKPOINT_SPEED),
+        };
+        let target_speed = if racing {
+            let gap = racer.distance - player_distance;
+            line_speed
+                * opponent.skill
+                * rubber_band_factor(gap, RUBBER_BAND_RANGE, race.rubber_band)
+        } else {
+            0.0
+        };
+
+        let current = velocity.linvel.xz().length();
+        let max_change = OPPONENT_ACCELERATION * dt;
+        let speed =
+            (current + (target_speed - current).clamp(-2.0 * max_change, max_change)).max(0.0);
+
+        let heading = transform.forward().with_y(0.0).normalize_or_zero();
+        let steer = (target - position).with_y(0.0).normalize_or(heading);
+        velocity.linvel.x = steer.x * speed;
+        velocity.linvel.z = steer.z * speed;
+        velocity.angvel = Vec3::new(0.0, heading.cross(steer).y.asin() * 2.0, 0.0);
+    }
+}
+
+/// Shows only the checkpoint the player has to reach next
+pub fn update_checkpoint_visibility(
+    manager: Res<RaceManager>,
+    catalog: Res<RaceCatalog>,
+    mut checkpoints: Query<(&Checkpoint, &mut Visibility)>,
+) {
+    let next = manager.0.as_ref().and_then(|session| {
+        let race = catalog.get(&session.race_id)?;
+        let (_, player) = session.player()?;
+        let racing =
+            !matches!(session.phase, RacePhase::Results(_)) && player.finish_time.is_none();
+        racing.then(|| player.passed % race.checkpoints.len())
+    });
+    for (checkpoint, mut visibility) in &mut checkpoints {
+        let shown = if next == Some(checkpoint.index) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        visibility.set_if_neq(shown);
+    }
+}
+
+/// Despawns the opponents and checkpoints once no race is running
+#[allow(clippy::type_complexity)]
+pub fn clear_race_entities(
+    mut commands: Commands,
+    manager: Res<RaceManager>,
+    leftovers: Query<Entity, Or<(With<RaceOpponent>, With<Checkpoint>)>>,
+) {
+    if manager.0.is_some() {
+        return;
+    }
+    for entity in &leftovers {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn setup_race_hud(mut commands: Commands) {
+    let layouts = [
+        (
+            RaceUiText::Status,
+            20.0,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(80.0),
+                right: Val::Px(20.0),
+                ..default()
+            },
+        ),
+        (
+            RaceUiText::Countdown,
+            96.0,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(30.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ),
+        (
+            RaceUiText::Results,
+            22.0,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(25.0),
+                left: Val::Percent(35.0),
+                width: Val::Percent(30.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ),
+    ];
+    for (kind, font_size, node) in layouts {
+        commands.spawn((
+            Text::new(""),
+            TextFont {
+                font_size,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            TextLayout::new_with_justify(JustifyText::Center),
+            node,
+            kind,
+        ));
+    }
+}
+
+fn results_text(race: &RaceDefinition, session: &RaceSession) -> String {
+    if session.abandoned {
+        return t!("race.abandoned");
+    }
+    let mut lines = vec![t!("race.results", name = t!(&race.name))];
+    for (place, index) in session.standings().into_iter().enumerate() {
+        let racer = &session.racers[index];
+        let time = racer
+            .finish_time
+            .map_or_else(|| t!("race.dnf"), format_race_time);
+        lines.push(format!("{}. {}  {}", place + 1, racer.name, time));
+    }
+    lines.join("\n")
+}
+
+pub fn update_race_hud(
+    catalog: Res<RaceCatalog>,
+    manager: Res<RaceManager>,
+    mut texts: Query<(&RaceUiText, &mut Text)>,
+) {
+    let session = manager.0.as_ref();
+    let race = session.and_then(|s| catalog.get(&s.race_id));
+
+    for (kind, mut text) in &mut texts {
+        let shown = match (kind, session, race) {
+            (RaceUiText::Status, Some(session), Some(race)) => {
+                match (session.phase, session.player()) {
+                    (RacePhase::Results(_), _) | (_, None) => String::new(),
+                    (_, Some((index, player))) => t!(
+                        "race.status",
+                        lap = (player.passed / race.checkpoints.len() + 1).min(race.laps as usize),
+                        laps = race.laps,
+                        position = session.position_of(index),
+                        racers = session.racers.len(),
+                        time = format_race_time(session.clock)
+                    ),
+                }
+            }
+            (RaceUiText::Countdown, Some(session), _) => match session.phase {
+                RacePhase::Countdown(remaining) => format!("{}", remaining.ceil() as u32),
+                RacePhase::Racing if session.clock < GO_SECONDS => t!("race.go"),
+                _ => String::new(),
+            },
+            (RaceUiText::Results, Some(session), Some(race)) => match session.phase {
+                RacePhase::Results(_) => results_text(race, session),
+                _ => String::new(),
+            },
+            _ => String::new(),
+        };
+        if text.0 != shown {
+            text.0 = shown;
+        }
+    }
+}
+
+/// Samples the player's path while `record_line` is on
+pub fn record_racing_line(
+    mut recorder: ResMut<RacingLineRecorder>,
+    active: Query<(&Transform, Option<&Velocity>), With<ActiveEntity>>,
+) {
+    if !recorder.recording {
+        return;
+    }
+    let Ok((transform, velocity)) = active.single() else {
+        return;
+    };
+    let position = transform.translation;
+    let far_enough = recorder
+        .points
+        .last()
+        .is_none_or(|last| vec3(last.position).distance(position) >= RECORD_SPACING);
+    if far_enough {
+        let speed = velocity.map_or(0.0, |v| v.linvel.length());
+        recorder.points.push(RacingLinePoint {
+            position: (position.x, position.y, position.z),
+            speed: (speed * 10.0).round() / 10.0,
+        });
+    }
+}
+
+fn race_command(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let catalog = world.resource::<RaceCatalog>();
+    let Some(id) = args.first() else {
+        let ids: Vec<&str> = catalog.races.iter().map(|r| r.id.as_str()).collect();
+        return Ok(format!("races: {}", ids.join(", ")));
+    };
+    if catalog.get(id).is_none() {
+        return Err(format!("unknown race `{id}`"));
+    }
+    world.send_event(StartRaceRequest {
+        race_id: id.to_string(),
+    });
+    Ok(format!("starting race {id}"))
+}
+
+/// `record_line start` samples the player's driving; `record_line stop`
+/// writes the line to the data directory, ready to paste into a race file
+fn record_line_command(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let mut recorder = world.resource_mut::<RacingLineRecorder>();
+    match args {
+        ["start"] => {
+            recorder.recording = true;
+            recorder.points.clear();
+            Ok("recording racing line".to_string())
+        }
+        ["stop"] => {
+            recorder.recording = false;
+            let path = data_directory().join("racing_line.ron");
+            let contents =
+                ron::ser::to_string_pretty(&recorder.points, ron::ser::PrettyConfig::default())
+                    .map_err(|e| e.to_string())?;
+            std::fs::create_dir_all(data_directory()).map_err(|e| e.to_string())?;
+            std::fs::write(&path, contents).map_err(|e| e.to_string())?;
+            Ok(format!(
+                "saved {} points to {}",
+                recorder.points.len(),
+                path.display()
+            ))
+        }
+        _ => Err("usage: record_line <start|stop>".to_string()),
+    }
+}
+
+pub fn register_race_commands(mut registry: ResMut<ConsoleCommands>) {
+    registry.register("race", race_command).usage("[race id]");
+    registry
+        .register("record_line", record_line_command)
+        .usage("<start|stop>");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_race_time() {
+        assert_eq!(format_race_time(0.0), "0:00.0");
+        assert_eq!(format_race_time(83.46), "1:23.5");
+        assert_eq!(format_race_time(599.99), "10:00.0");
+    }
+
+    #[test]
+    fn test_shipped_races_parse() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/config/races");
+        let races = load_races(&dir);
+        assert!(!races.is_empty());
+        for race in &races {
+            assert!(!race.opponents.is_empty(), "{} has no opponents", race.id);
+        }
+    }
+}
@@ -0,0 +1,556 @@
+#![allow(clippy::type_complexity)]
+//! Trains on fixed rail lines.
+//!
+//! Lines are laid out in `assets/config/rail.ron` and spawned through the
+//! `RailFactory`. Trains run between stations, stopping at each one; the
+//! player can board a stopped train and ride it to any later station, which
+//! runs express while they are aboard. Cars caught on the track are thrown
+//! aside and wrecked, and crossing lights flash as a train approaches.
+
+use crate::components::{
+    ActiveEntity, Car, Interactable, InteractionEvent, InteractionKind, PendingPhysicsEnable,
+    Player, RailCrossingLight, RailNetwork, Train, TrainCar, TrainPassenger, TrainState,
+    VehicleHealth,
+};
+use crate::config::GameConfig;
+use crate::factories::rail_factory::{CAR_HEIGHT, RailFactory, RailList};
+use crate::systems::camera_shake::CameraShake;
+use crate::systems::physics::physics_utils::CollisionGroupHelper;
+use crate::systems::world::unified_world::UnifiedWorldManager;
+use crate::t;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Slowest a train creeps while closing the last meters to a stop (m/s)
+const CREEP_SPEED: f32 = 0.5;
+
+/// Rails sit this high above the track centerline points
+const RAIL_HEIGHT: f32 = 0.25;
+
+/// Spacing of the points a road is broken into when checking it against
+/// the track (m)
+const CROSSING_SAMPLE_SPACING: f32 = 8.0;
+/// Crossings closer than this along the track are the same one
+const CROSSING_MERGE_DISTANCE: f32 = 25.0;
+
+/// Crossing lights flash while a train is within this distance along the track
+const CROSSING_WARNING_DISTANCE: f32 = 150.0;
+const CROSSING_FLASH_RATE: f32 = 1.5;
+
+/// Extra room around a train car that still counts as a hit (m)
+const HIT_MARGIN: f32 = 1.2;
+/// Seconds before the same car can be hit again
+const HIT_COOLDOWN: f32 = 1.0;
+
+/// New speed of a train moving at `speed` with `remaining` meters to its
+/// next stop: it accelerates towards `cruise` and brakes so it can stop in
+/// the distance left
+pub fn step_train_speed(
+    speed: f32,
+    remaining: f32,
+    cruise: f32,
+    acceleration: f32,
+    dt: f32,
+) -> f32 {
+    if remaining <= 0.0 {
+        return 0.0;
+    }
+    let braking = (2.0 * acceleration * remaining).sqrt();
+    let target = cruise.min(braking).max(CREEP_SPEED);
+    if speed < target {
+        (speed + acceleration * dt).min(target)
+    } else {
+        // Braking may need more than the nominal rate after a late stop change
+        target
+    }
+}
+
+pub fn spawn_rail_network(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut network: ResMut<RailNetwork>,
+    config: Res<GameConfig>,
+) {
+    // Lines and their trains persist across state re-entry
+    if !network.lines.is_empty() {
+        return;
+    }
+
+    let path = format!(
+        "{}/config/rail.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    let list = match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str::<RailList>(&content).unwrap_or_else(|e| {
+            error!("Failed to parse rail lines at '{}': {}", path, e);
+            RailList::default()
+        }),
+        Err(e) => {
+            info!("ℹ️ No rail config found: {}", e);
+            RailList::default()
+        }
+    };
+
+    *network = RailNetwork {
+        lines: Vec::new(),
+        car_length: list.car_length.max(4.0),
+        car_gap: list.car_gap.max(0.0),
+        hit_damage: list.hit_damage,
+        min_hit_speed: list.min_hit_speed,
+        boarding_radius: list.boarding_radius,
+    };
+
+    let factory = RailFactory::new(&mut materials);
+    let groups = CollisionGroupHelper::static_groups(&config);
+    for definition in &list.lines {
+        if definition.points.len() < 2 {
+            warn!("Rail line '{}' needs at least two points", definition.name);
+            continue;
+        }
+        let line = definition.build();
+        let line_index = network.lines.len();
+        let train_length = network.train_length(definition.cars);
+        factory.spawn_track(&mut commands, &mut meshes, &line);
+        for station in 0..line.stations.len() {
+            factory.spawn_station(&mut commands, &mut meshes, &line, station, train_length);
+        }
+
+        // Trains start spread over the stations, waiting with their middle
+        // at the platform
+        let stations = line.stations.len();
+        let trains = if stations == 0 {
+            0
+        } else if line.track.is_looped() {
+            definition.trains.min(stations)
+        } else {
+            definition.trains.min(1)
+        };
+        for train in 0..trains {
+            let station = train * stations / trains;
+            let distance = line
+                .track
+                .wrap(line.stations[station].distance + train_length * 0.5);
+            factory.spawn_train(
+                &mut commands,
+                &mut meshes,
+                groups,
+                line_index,
+                &line,
+                station,
+                distance,
+                definition.cars,
+                network.car_length,
+            );
+        }
+        info!(
+            "🚆 Rail line '{}': {:.0} m, {} stations, {} trains",
+            line.name,
+            line.track.length(),
+            stations,
+            trains
+        );
+        network.lines.push(line);
+    }
+    commands.insert_resource(factory);
+}
+
+/// Finds where newly streamed roads cross the track and puts up crossing
+/// lights there
+pub fn detect_level_crossings(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut network: ResMut<RailNetwork>,
+    factory: Option<Res<RailFactory>>,
+    world: Res<UnifiedWorldManager>,
+    mut checked: Local<HashSet<u64>>,
+) {
+    let Some(factory) = factory else {
+        return;
+    };
+    let roads = &world.road_network.roads;
+    if checked.len() == roads.len() && roads.keys().all(|id| checked.contains(id)) {
+        return;
+    }
+
+    for (id, road) in roads {
+        if !checked.insert(*id) {
+            continue;
+        }
+        let samples = (road.length() / CROSSING_SAMPLE_SPACING)
+            .ceil()
+            .clamp(1.0, 64.0) as usize;
+        let path: Vec<Vec3> = (0..=samples)
+            .map(|sample| road.evaluate(sample as f32 / samples as f32))
+            .collect();
+        let road_width = road.road_type.width();
+        for (line_index, line) in network.lines.iter_mut().enumerate() {
+            for along in line.track.crossings(&path) {
+                let known = line.crossings.iter().any(|&crossing| {
+                    line.track
+                        .distance_ahead(crossing, along, 1.0)
+                        .abs()
+                        .min(line.track.distance_ahead(along, crossing, 1.0).abs())
+                        < CROSSING_MERGE_DISTANCE
+                });
+                if !known {
+                    line.crossings.push(along);
+                    factory.spawn_crossing(
+                        &mut commands,
+                        &mut meshes,
+                        line_index,
+                        line,
+                        along,
+                        road_width,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Runs trains between stations and lays their cars out along the track
+pub fn train_movement_system(
+    time: Res<Time>,
+    network: Res<RailNetwork>,
+    passengers: Query<&TrainPassenger>,
+    mut trains: Query<(Entity, &mut Train)>,
+    mut cars: Query<&mut Transform, With<TrainCar>>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+    let half_car = network.car_length * 0.5;
+    for (entity, mut train) in &mut trains {
+        let Some(line) = network.lines.get(train.line) else {
+            continue;
+        };
+        let train_length = network.train_length(train.cars.len());
+        let express = if passengers.iter().any(|p| p.train == entity) {
+            line.express_multiplier
+        } else {
+            1.0
+        };
+
+        match train.state {
+            TrainState::Dwelling { station, remaining } => {
+                train.speed = 0.0;
+                if remaining > dt {
+                    train.state = TrainState::Dwelling {
+                        station,
+                        remaining: remaining - dt,
+                    };
+                } else {
+                    let (next, direction) = line.next_station(station, train.direction);
+                    if direction != train.direction {
+                        // Turning round: the old tail leads and the car order flips
+                        train.distance = line
+                            .track
+                            .wrap(train.distance - train.direction * train_length);
+                        train.direction = direction;
+                        train.cars.reverse();
+                    }
+                    train.state = TrainState::Running { station: next };
+                }
+            }
+            TrainState::Running { station } => {
+                let stop = line
+                    .track
+                    .wrap(line.stations[station].distance + train.direction * train_length * 0.5);
+                let remaining = line
+                    .track
+                    .distance_ahead(train.distance, stop, train.direction);
+                train.speed = step_train_speed(
+                    train.speed,
+                    remaining,
+                    line.cruise_speed * express,
+                    line.acceleration * express,
+                    dt,
+                );
+                let advance = (train.speed * dt).min(remaining.max(0.0));
+                train.distance = line.track.wrap(train.distance + train.direction * advance);
+                if remaining - advance <= 0.01 {
+                    train.distance = stop;
+                    train.speed = 0.0;
+                    train.state = TrainState::Dwelling {
+                        station,
+                        remaining: line.dwell_time,
+                    };
+                }
+            }
+        }
+
+        for (index, car) in train.cars.iter().enumerate() {
+            let Ok(mut transform) = cars.get_mut(*car) else {
+                continue;
+            };
+            let along = train.distance
+                - train.direction
+                    * (half_car + index as f32 * (network.car_length + network.car_gap));
+            let (position, tangent) = line.track.sample(along);
+            *transform =
+                Transform::from_translation(position + Vec3::Y * (RAIL_HEIGHT + CAR_HEIGHT * 0.5))
+                    .looking_to(tangent * train.direction, Vec3::Y);
+        }
+    }
+}
+
+/// Using a stopped train boards the player, or lets them off at the station
+/// when they are riding it
+pub fn train_boarding_system(
+    mut commands: Commands,
+    network: Res<RailNetwork>,
+    mut interactions: EventReader<InteractionEvent>,
+    player: Query<(&Transform, Option<&TrainPassenger>), (With<Player>, With<ActiveEntity>)>,
+    trains: Query<&Train>,
+    cars: Query<&TrainCar>,
+) {
+    for interaction in interactions.read() {
+        if interaction.kind != InteractionKind::Train {
+            continue;
+        }
+        let Ok((transform, riding)) = player.get(interaction.actor) else {
+            continue;
+        };
+        let Ok(car) = cars.get(interaction.target) else {
+            continue;
+        };
+        let Ok(train) = trains.get(car.train) else {
+            continue;
+        };
+        let TrainState::Dwelling { station, .. } = train.state else {
+            continue;
+        };
+
+        if riding.is_some() {
+            let Some(station) = network
+                .lines
+                .get(train.line)
+                .and_then(|line| line.stations.get(station))
+            else {
+                continue;
+            };
+            commands
+                .entity(interaction.actor)
+                .remove::<TrainPassenger>()
+                .insert(
+                    Transform::from_translation(station.platform).with_rotation(transform.rotation),
+                )
+                .insert(Velocity::zero())
+                .insert(Visibility::Visible)
+                .insert(PendingPhysicsEnable);
+            // RigidBodyDisabled comes off next frame, once the new pose is in
+        } else {
+            commands
+                .entity(interaction.actor)
+                .insert(TrainPassenger {
+                    train: car.train,
+                    car: interaction.target,
+                })
+                .insert(Velocity::zero())
+                .insert(Visibility::Hidden)
+                .insert(RigidBodyDisabled);
+        }
+    }
+}
+
+/// Keeps a riding player with their car, so the camera follows the train
+pub fn carry_train_passengers(
+    cars: Query<&Transform, (With<TrainCar>, Without<TrainPassenger>)>,
+    mut passengers: Query<(&mut Transform, &TrainPassenger)>,
+) {
+    for (mut transform, passenger) in &mut passengers {
+        if let Ok(car) = cars.get(passenger.car) {
+            transform.translation = car.translation + Vec3::Y * CAR_HEIGHT * 0.5;
+        }
+    }
+}
+
+/// Wrecks and throws aside cars a moving train runs into
+pub fn train_car_collisions(
+    time: Res<Time>,
+    network: Res<RailNetwork>,
+    shake: Option<ResMut<CameraShake>>,
+    trains: Query<&Train>,
+    train_cars: Query<(&Transform, &TrainCar)>,
+    mut vehicles: Query<
+        (
+            Entity,
+            &Transform,
+            &mut VehicleHealth,
+            &mut Velocity,
+            Has<ActiveEntity>,
+        ),
+        (With<Car>, Without<TrainCar>),
+    >,
+    mut last_hit: Local<HashMap<Entity, f32>>,
+) {
+    let now = time.elapsed_secs();
+    last_hit.retain(|_, at| now - *at < HIT_COOLDOWN);
+    let mut player_hit = false;
+
+    for (car_transform, train_car) in &train_cars {
+        let Ok(train) = trains.get(train_car.train) else {
+            continue;
+        };
+        if train.speed < network.min_hit_speed {
+            continue;
+        }
+        let forward = car_transform.forward().as_vec3();
+        let half = Vec3::new(1.5, CAR_HEIGHT * 0.5, network.car_length * 0.5) + HIT_MARGIN;
+        let inverse = car_transform.rotation.inverse();
+
+        for (vehicle, transform, mut health, mut velocity, active) in &mut vehicles {
+            let local = inverse * (transform.translation - car_transform.translation);
+            if local.x.abs() > half.x || local.y.abs() > half.y || local.z.abs() > half.z {
+                continue;
+            }
+            if last_hit.contains_key(&vehicle) {
+                continue;
+            }
+            last_hit.insert(vehicle, now);
+
+            health.current = (health.current - network.hit_damage * train.speed).max(0.0);
+            // Shoved ahead of the train and off to whichever side it sits on
+            let side = car_transform.right().as_vec3() * local.x.signum();
+            velocity.linvel += forward * train.speed * 1.1
+                + side * train.speed * 0.5
+                + Vec3::Y * train.speed * 0.25;
+            velocity.angvel += Vec3::new(0.0, local.x.signum() * 2.0, 1.5);
+            player_hit |= active;
+        }
+    }
+
+    if player_hit && let Some(mut shake) = shake {
+        shake.add_trauma(0.8);
+    }
+}
+
+/// Flashes crossing lights in turn while a train is close along the track
+pub fn update_crossing_lights(
+    time: Res<Time>,
+    network: Res<RailNetwork>,
+    factory: Option<Res<RailFactory>>,
+    trains: Query<&Train>,
+    mut lights: Query<(&RailCrossingLight, &mut MeshMaterial3d<StandardMaterial>)>,
+) {
+    let Some(factory) = factory else {
+        return;
+    };
+    let flash = (time.elapsed_secs() * CROSSING_FLASH_RATE * 2.0) as i32 % 2 == 0;
+    for (light, mut material) in &mut lights {
+        let Some(line) = network.lines.get(light.line) else {
+            continue;
+        };
+        let train_near = trains
+            .iter()
+            .filter(|train| train.line == light.line)
+            .any(|train| {
+                let train_length = network.train_length(train.cars.len());
+                let ahead =
+                    line.track
+                        .distance_ahead(train.distance, light.distance, train.direction);
+                // Approaching, or still on the crossing
+                (-train_length..CROSSING_WARNING_DISTANCE).contains(&ahead)
+                    || (line.track.is_looped() && line.track.length() - ahead < train_length)
+            });
+        let lit = train_near && flash != light.phase;
+        let wanted = if lit {
+            &factory.light_on
+        } else {
+            &factory.light_off
+        };
+        if material.0 != *wanted {
+            material.0 = wanted.clone();
+        }
+    }
+}
+
+/// Keeps train cars' `Interactable` in line with the train: every car of a
+/// stopped train can be boarded, and the car the player rides in says where
+/// it is heading or, once stopped, that they can get off
+pub fn sync_train_interactables(
+    mut commands: Commands,
+    network: Res<RailNetwork>,
+    passenger: Query<&TrainPassenger, (With<Player>, With<ActiveEntity>)>,
+    trains: Query<(Entity, &Train)>,
+    cars: Query<Option<&Interactable>, With<TrainCar>>,
+) {
+    let passenger = passenger.single().ok();
+    let reach = network.boarding_radius + network.car_length * 0.5;
+    for (entity, train) in &trains {
+        let Some(line) = network.lines.get(train.line) else {
+            continue;
+        };
+        let station_name = |station: usize| {
+            line.stations
+                .get(station)
+                .map(|station| station.name.clone())
+                .unwrap_or_default()
+        };
+        let riding = passenger.filter(|passenger| passenger.train == entity);
+
+        for &car in &train.cars {
+            let Ok(current) = cars.get(car) else {
+                continue;
+            };
+            let prompt = match (train.state, riding) {
+                (TrainState::Dwelling { station, .. }, Some(passenger)) => (passenger.car == car)
+                    .then(|| t!("train.alight", station = station_name(station))),
+                (TrainState::Dwelling { station, .. }, None) => {
+                    let (next, _) = line.next_station(station, train.direction);
+                    Some(t!(
+                        "train.board",
+                        line = &line.name,
+                        station = station_name(next)
+                    ))
+                }
+                (TrainState::Running { station }, Some(passenger)) if passenger.car == car => {
+                    Some(t!("train.riding", station = station_name(station)))
+                }
+                (TrainState::Running { .. }, _) => None,
+            };
+            match (prompt, current) {
+                (Some(prompt), Some(current)) if current.prompt == prompt => {}
+                (Some(prompt), _) => {
+                    commands.entity(car).insert(Interactable::new(
+                        InteractionKind::Train,
+                        reach,
+                        prompt,
+                    ));
+                }
+                (None, Some(_)) => {
+                    commands.entity(car).remove::<Interactable>();
+                }
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_train_speeds_up_then_brakes_into_the_station() {
+        let (cruise, acceleration, dt) = (25.0, 1.2, 1.0 / 60.0);
+        let (mut speed, mut remaining) = (0.0, 1000.0);
+        let mut peak: f32 = 0.0;
+        let mut steps = 0;
+        while remaining > 0.0 && steps < 100_000 {
+            speed = step_train_speed(speed, remaining, cruise, acceleration, dt);
+            remaining -= (speed * dt).min(remaining);
+            peak = peak.max(speed);
+            steps += 1;
+        }
+        assert_eq!(remaining, 0.0, "train never reached the stop");
+        assert!((peak - cruise).abs() < 1e-3);
+        // Stopping distance at cruise is ~260 m, so most of the run is at speed
+        let seconds = steps as f32 * dt;
+        assert!(seconds > 60.0 && seconds < 70.0, "took {seconds}s");
+        assert!(speed < 1.0);
+    }
+}
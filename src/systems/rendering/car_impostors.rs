@@ -0,0 +1,289 @@
+//! Far parked cars drawn as instanced impostors.
+//!
+//! Beyond `car_impostor_distance` a parked car's own meshes are hidden and a
+//! two-box stand-in is drawn through the shared `CarImpostor` batch instead,
+//! so a street full of distant cars costs a couple of instanced draws.
+//!
+//! Passes are incremental. Between passes the system only collects cars whose
+//! `Transform` changed, despawned cars and cars coming back from exterior
+//! culling. A pass re-evaluates those plus the cars the spatial index finds
+//! close enough to the impostor distance that the focus movement since the
+//! last pass could have moved them across it. Every parked car is scanned
+//! only on the first pass and when the impostor distance changes.
+
+use super::instance_kinds::CarImpostor;
+use super::instanced_batcher::InstancedBatcher;
+use crate::components::{ActiveEntity, Car, ExteriorCulled, TrafficAgent};
+use crate::config::GameConfig;
+use crate::systems::spatial_index::SpatialIndex;
+use crate::systems::world::unified_world::ChunkCoord;
+use bevy::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Seconds between impostor passes
+const IMPOSTOR_CHECK_INTERVAL: f32 = 0.5;
+/// Slack on the boundary band, covering float error in the distance bounds
+const BOUNDARY_MARGIN: f32 = 1.0;
+
+/// Parked car currently drawn as an impostor; holds its visibility to restore
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Impostored(pub Visibility);
+
+type ParkedCarQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static mut Visibility,
+        Option<&'static Impostored>,
+    ),
+    (
+        With<Car>,
+        Without<TrafficAgent>,
+        Without<ActiveEntity>,
+        Without<ExteriorCulled>,
+    ),
+>;
+
+/// What the previous passes decided, so the next one only revisits what may have changed
+#[derive(Default)]
+pub struct ImpostorPassState {
+    since_update: f32,
+    /// Focus position and impostor distance of the last pass
+    last_pass: Option<(Vec3, f32)>,
+    /// Cars to re-evaluate at the next pass
+    pending: HashSet<Entity>,
+    /// Chunk each impostor instance is drawn in
+    chunk_of: HashMap<Entity, ChunkCoord>,
+    chunks: HashMap<ChunkCoord, BTreeMap<Entity, Transform>>,
+    /// Chunks whose instances changed since they were last handed to the batcher
+    touched: HashSet<ChunkCoord>,
+}
+
+impl ImpostorPassState {
+    fn remove_instance(&mut self, entity: Entity) {
+        if let Some(coord) = self.chunk_of.remove(&entity) {
+            if let Some(chunk) = self.chunks.get_mut(&coord) {
+                chunk.remove(&entity);
+            }
+            self.touched.insert(coord);
+        }
+    }
+
+    fn set_instance(&mut self, entity: Entity, transform: Transform, coord: ChunkCoord) {
+        if self.chunk_of.get(&entity) != Some(&coord) {
+            self.remove_instance(entity);
+            self.chunk_of.insert(entity, coord);
+        }
+        self.chunks
+            .entry(coord)
+            .or_default()
+            .insert(entity, transform);
+        self.touched.insert(coord);
+    }
+}
+
+/// Swaps parked cars between their own meshes and impostor instances by distance
+#[allow(clippy::too_many_arguments)]
+pub fn parked_car_impostor_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut state: Local<ImpostorPassState>,
+    config: Res<GameConfig>,
+    index: Res<SpatialIndex>,
+    mut batcher: ResMut<InstancedBatcher<CarImpostor>>,
+    focus: Query<&Transform, With<ActiveEntity>>,
+    mut cars: ParkedCarQuery,
+    moved: Query<Entity, (With<Car>, Changed<Transform>)>,
+    culled: Query<Entity, (With<Car>, Added<ExteriorCulled>)>,
+    mut despawned: RemovedComponents<Car>,
+    mut unculled: RemovedComponents<ExteriorCulled>,
+) {
+    // Removal events only last a couple of frames, so collect every frame
+    for entity in despawned.read() {
+        state.pending.remove(&entity);
+        state.remove_instance(entity);
+    }
+    // The exterior is hidden wholesale while inside; the car keeps its impostor state
+    for entity in &culled {
+        state.remove_instance(entity);
+    }
+    state.pending.extend(unculled.read());
+    state.pending.extend(&moved);
+
+    state.since_update += time.delta_secs();
+    if state.since_update < IMPOSTOR_CHECK_INTERVAL {
+        return;
+    }
+    state.since_update = 0.0;
+
+    let Ok(focus) = focus.single() else {
+        return;
+    };
+    crate::profile_scope!("car_impostor_pass");
+    let focus = focus.translation;
+    let distance = config.world_streaming.car_impostor_distance;
+    let chunk_size = config.world_streaming.chunk_size;
+
+    let full_scan = !matches!(state.last_pass, Some((_, last)) if last == distance);
+    let mut candidates = std::mem::take(&mut state.pending);
+    if let Some((last_focus, _)) = state.last_pass
+        && !full_scan
+    {
+        // Only cars within `travel` of the boundary around the last focus can have crossed it
+        let travel = last_focus.distance(focus) + BOUNDARY_MARGIN;
+        let inner = (distance - travel).max(0.0);
+        candidates.extend(
+            index
+                .query_radius(last_focus, distance + travel)
+                .into_iter()
+                .filter(|entry| entry.position.distance_squared(last_focus) >= inner * inner)
+                .map(|entry| entry.entity),
+        );
+    }
+    state.last_pass = Some((focus, distance));
+
+    let far_sq = distance * distance;
+    let mut evaluate = |entity: Entity,
+                        transform: &Transform,
+                        visibility: &mut Visibility,
+                        impostor: Option<&Impostored>,
+                        state: &mut ImpostorPassState| {
+        let far = transform.translation.distance_squared(focus) > far_sq;
+        match (far, impostor) {
+            (true, None) => {
+                commands.entity(entity).insert(Impostored(*visibility));
+                *visibility = Visibility::Hidden;
+            }
+            (false, Some(saved)) => {
+                *visibility = saved.0;
+                commands.entity(entity).remove::<Impostored>();
+            }
+            _ => {}
+        }
+        if far {
+            let coord = ChunkCoord::from_world_pos(transform.translation, chunk_size);
+            state.set_instance(entity, *transform, coord);
+        } else {
+            state.remove_instance(entity);
+        }
+    };
+
+    if full_scan {
+        for (entity, transform, mut visibility, impostor) in &mut cars {
+            evaluate(entity, transform, &mut visibility, impostor, &mut state);
+        }
+    } else {
+        for entity in candidates {
+            match cars.get_mut(entity) {
+                Ok((_, transform, mut visibility, impostor)) => {
+                    evaluate(entity, transform, &mut visibility, impostor, &mut state)
+                }
+                // Traffic, driven or culled cars are not parked impostors
+                Err(_) => state.remove_instance(entity),
+            }
+        }
+    }
+
+    // Untouched chunks keep their upload, so a still scene doesn't re-upload anything
+    let touched: Vec<ChunkCoord> = state.touched.drain().collect();
+    for coord in touched {
+        let instances: Vec<Transform> = state
+            .chunks
+            .get(&coord)
+            .map(|chunk| chunk.values().copied().collect())
+            .unwrap_or_default();
+        if instances.is_empty() {
+            state.chunks.remove(&coord);
+        }
+        batcher.set_chunk(coord, instances);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ContentType, DynamicContent};
+    use crate::systems::spatial_index::update_spatial_index;
+    use std::time::Duration;
+
+    /// Runs one impostor pass and checks every car against a full distance scan
+    fn pass_matches_full_scan(app: &mut App, focus: Vec3) {
+        let mut focus_query = app
+            .world_mut()
+            .query_filtered::<&mut Transform, With<ActiveEntity>>();
+        focus_query.single_mut(app.world_mut()).unwrap().translation = focus;
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(IMPOSTOR_CHECK_INTERVAL));
+        app.update();
+
+        let far_sq = GameConfig::default()
+            .world_streaming
+            .car_impostor_distance
+            .powi(2);
+        let mut cars = app.world_mut().query_filtered::<(
+            &Transform,
+            &Visibility,
+            Option<&Impostored>,
+        ), (With<Car>, Without<ActiveEntity>)>();
+        let mut far_cars = 0;
+        for (transform, visibility, impostor) in cars.iter(app.world()) {
+            let far = transform.translation.distance_squared(focus) > far_sq;
+            far_cars += far as usize;
+            assert_eq!(impostor.is_some(), far, "car at {}", transform.translation);
+            assert_eq!(*visibility == Visibility::Hidden, far);
+        }
+        let batcher = app.world().resource::<InstancedBatcher<CarImpostor>>();
+        assert_eq!(batcher.instance_count(), far_cars);
+    }
+
+    #[test]
+    fn test_incremental_passes_match_full_scan() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<GameConfig>()
+            .init_resource::<SpatialIndex>()
+            .init_resource::<InstancedBatcher<CarImpostor>>()
+            .add_systems(Update, parked_car_impostor_system)
+            .add_systems(PostUpdate, update_spatial_index);
+        app.world_mut().spawn((ActiveEntity, Transform::default()));
+        let cars: Vec<Entity> = (0..400)
+            .map(|i| {
+                let position = Vec3::new(
+                    (i % 20) as f32 * 30.0 - 300.0,
+                    0.0,
+                    (i / 20) as f32 * 30.0 - 300.0,
+                );
+                app.world_mut()
+                    .spawn((
+                        Car,
+                        DynamicContent {
+                            content_type: ContentType::Vehicle,
+                        },
+                        Transform::from_translation(position),
+                        Visibility::Inherited,
+                    ))
+                    .id()
+            })
+            .collect();
+
+        pass_matches_full_scan(&mut app, Vec3::ZERO);
+        pass_matches_full_scan(&mut app, Vec3::new(30.0, 0.0, 0.0));
+        pass_matches_full_scan(&mut app, Vec3::new(200.0, 0.0, 0.0));
+
+        // Parked cars pushed around, and a couple towed away
+        for car in cars.iter().step_by(7) {
+            app.world_mut()
+                .get_mut::<Transform>(*car)
+                .unwrap()
+                .translation
+                .z += 40.0;
+        }
+        app.world_mut().despawn(cars[3]);
+        app.world_mut().despawn(cars[399]);
+        pass_matches_full_scan(&mut app, Vec3::new(200.0, 0.0, 150.0));
+        pass_matches_full_scan(&mut app, Vec3::new(-300.0, 0.0, -20.0));
+    }
+}
@@ -0,0 +1,595 @@
+//! Ground decals: skid marks, scorch marks, blood splats and road paint.
+//!
+//! Every decal is a quad laid on the surface it was projected onto, lifted a
+//! few centimetres along the surface normal, with UVs into one procedurally
+//! drawn atlas. All decals share one mesh and one material, so however many
+//! there are they cost a single draw; the mesh is rebuilt when decals are
+//! added or expire, and a few times a second while some are fading.
+//!
+//! Marks left by play (skids, scorches, blood) live in a ring buffer: once
+//! it is full the oldest mark is overwritten, and every mark fades out over
+//! the last `FADE_SECONDS` of its lifetime. Road paint has its own ring and
+//! never ages, so wear can't push it out.
+
+use crate::components::gameplay_events::{NpcKilled, VehicleDestroyed};
+use bevy::asset::RenderAssetUsages;
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::view::NoFrustumCulling;
+use bevy_rapier3d::prelude::*;
+
+/// Skid, scorch and blood marks kept at once
+pub const WEAR_BUDGET: usize = 1024;
+/// Road paint quads kept at once
+pub const PAINT_BUDGET: usize = 256;
+/// Marks fade to nothing over the end of their lifetime
+pub const FADE_SECONDS: f32 = 2.0;
+/// Height of decals above the surface, enough to avoid z-fighting
+const DECAL_LIFT: f32 = 0.03;
+/// Seconds between mesh rebuilds while only fading changes it
+const FADE_REBUILD_INTERVAL: f32 = 0.1;
+/// How far above and below a point impact decals look for a surface
+const PROJECTION_REACH: f32 = 3.0;
+
+const SCORCH_RADIUS: f32 = 3.5;
+const SCORCH_LIFETIME: f32 = 120.0;
+const BLOOD_RADIUS: f32 = 0.9;
+const BLOOD_LIFETIME: f32 = 60.0;
+
+/// Atlas cells per side, and pixels per cell
+const ATLAS_CELLS: u32 = 2;
+const CELL_SIZE: u32 = 128;
+
+/// Picture a decal shows, one atlas cell each
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecalKind {
+    SkidMark,
+    Scorch,
+    Blood,
+    Paint,
+}
+
+impl DecalKind {
+    fn cell(self) -> u32 {
+        match self {
+            DecalKind::SkidMark => 0,
+            DecalKind::Scorch => 1,
+            DecalKind::Blood => 2,
+            DecalKind::Paint => 3,
+        }
+    }
+
+    /// UV rectangle of the kind's cell, inset half a texel against bleeding
+    pub fn uv_rect(self) -> Rect {
+        let cell = self.cell();
+        let size = 1.0 / ATLAS_CELLS as f32;
+        let inset = 0.5 / (ATLAS_CELLS * CELL_SIZE) as f32;
+        let min = Vec2::new((cell % ATLAS_CELLS) as f32, (cell / ATLAS_CELLS) as f32) * size;
+        Rect::from_corners(min + inset, min + size - inset)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decal {
+    pub kind: DecalKind,
+    pub center: Vec3,
+    /// Normal of the surface the decal lies on
+    pub normal: Vec3,
+    /// Direction of the decal's length, projected into the surface
+    pub forward: Vec3,
+    /// Width across `forward`, then length along it
+    pub size: Vec2,
+    /// Multiplied into the atlas colour
+    pub tint: Color,
+    pub age: f32,
+    /// `f32::INFINITY` never expires
+    pub lifetime: f32,
+}
+
+impl Decal {
+    pub fn new(kind: DecalKind, center: Vec3, normal: Vec3, forward: Vec3, size: Vec2) -> Self {
+        Self {
+            kind,
+            center,
+            normal: normal.normalize_or(Vec3::Y),
+            forward,
+            size,
+            tint: Color::WHITE,
+            age: 0.0,
+            lifetime: f32::INFINITY,
+        }
+    }
+
+    pub fn with_lifetime(mut self, lifetime: f32) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Opacity from age: full until `FADE_SECONDS` before expiring
+    pub fn alpha(&self) -> f32 {
+        ((self.lifetime - self.age) / FADE_SECONDS).clamp(0.0, 1.0)
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.lifetime - self.age < FADE_SECONDS
+    }
+
+    /// Corners counter-clockwise seen from the normal side, starting at the
+    /// back left, in the order of `uv_rect`'s corners
+    pub fn corners(&self) -> [Vec3; 4] {
+        let normal = self.normal;
+        let along = self.forward - normal * self.forward.dot(normal);
+        let along = along
+            .try_normalize()
+            .unwrap_or_else(|| normal.any_orthonormal_vector());
+        let across = along.cross(normal);
+        let center = self.center + normal * DECAL_LIFT;
+        let (half_across, half_along) = (across * self.size.x * 0.5, along * self.size.y * 0.5);
+        [
+            center - half_across - half_along,
+            center + half_across - half_along,
+            center + half_across + half_along,
+            center - half_across + half_along,
+        ]
+    }
+}
+
+/// Fixed-size decal store; pushing into a full ring overwrites the oldest
+#[derive(Debug, Clone)]
+pub struct DecalRing {
+    decals: Vec<Decal>,
+    capacity: usize,
+    /// Slot the next push overwrites once the ring is full
+    oldest: usize,
+}
+
+impl DecalRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            decals: Vec::with_capacity(capacity),
+            capacity: capacity.max(1),
+            oldest: 0,
+        }
+    }
+
+    pub fn push(&mut self, decal: Decal) {
+        if self.decals.len() < self.capacity {
+            self.decals.push(decal);
+        } else {
+            self.decals[self.oldest] = decal;
+            self.oldest = (self.oldest + 1) % self.capacity;
+        }
+    }
+
+    /// Ages every decal by `delta` and drops the expired ones; returns
+    /// whether any were dropped
+    pub fn age(&mut self, delta: f32) -> bool {
+        let before = self.decals.len();
+        for decal in &mut self.decals {
+            decal.age += delta;
+        }
+        if self.decals.iter().all(|decal| decal.age < decal.lifetime) {
+            return false;
+        }
+        // Keep the survivors oldest first so overwriting stays in age order
+        self.decals.rotate_left(self.oldest);
+        self.oldest = 0;
+        self.decals.retain(|decal| decal.age < decal.lifetime);
+        self.decals.len() != before
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Decal> {
+        self.decals.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.decals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decals.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.decals.clear();
+        self.oldest = 0;
+    }
+}
+
+/// Every decal in the world and the batch they are drawn with
+#[derive(Resource)]
+pub struct Decals {
+    wear: DecalRing,
+    paint: DecalRing,
+    dirty: bool,
+    since_rebuild: f32,
+    mesh: Option<Handle<Mesh>>,
+}
+
+impl Default for Decals {
+    fn default() -> Self {
+        Self {
+            wear: DecalRing::new(WEAR_BUDGET),
+            paint: DecalRing::new(PAINT_BUDGET),
+            dirty: false,
+            since_rebuild: 0.0,
+            mesh: None,
+        }
+    }
+}
+
+impl Decals {
+    /// Adds a skid, scorch or blood mark, overwriting the oldest if full
+    pub fn add(&mut self, decal: Decal) {
+        self.wear.push(decal);
+        self.dirty = true;
+    }
+
+    /// Tyre rubber from `from` to `to`, lying on flat ground
+    pub fn skid_mark(&mut self, from: Vec3, to: Vec3, width: f32, lifetime: f32) {
+        let step = to - from;
+        self.add(
+            Decal::new(
+                DecalKind::SkidMark,
+                from + step * 0.5,
+                Vec3::Y,
+                step,
+                Vec2::new(width, step.length()),
+            )
+            .with_lifetime(lifetime),
+        );
+    }
+
+    /// Burnt patch where a vehicle blew up
+    pub fn scorch(&mut self, center: Vec3, normal: Vec3, radius: f32) {
+        self.add(
+            Decal::new(
+                DecalKind::Scorch,
+                center,
+                normal,
+                spin(center),
+                Vec2::splat(radius * 2.0),
+            )
+            .with_lifetime(SCORCH_LIFETIME),
+        );
+    }
+
+    pub fn blood_splat(&mut self, center: Vec3, normal: Vec3, radius: f32) {
+        self.add(
+            Decal::new(
+                DecalKind::Blood,
+                center,
+                normal,
+                spin(center),
+                Vec2::splat(radius * 2.0),
+            )
+            .with_lifetime(BLOOD_LIFETIME),
+        );
+    }
+
+    /// Permanent paint stripe `size` (width, length) along `direction`, for
+    /// start lines and other markings not baked into the road meshes
+    pub fn road_paint(&mut self, center: Vec3, direction: Vec3, size: Vec2, color: Color) {
+        self.paint
+            .push(Decal::new(DecalKind::Paint, center, Vec3::Y, direction, size).with_tint(color));
+        self.dirty = true;
+    }
+
+    pub fn len(&self) -> usize {
+        self.wear.len() + self.paint.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wear.is_empty() && self.paint.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.wear.clear();
+        self.paint.clear();
+        self.dirty = true;
+    }
+
+    /// One quad per decal, paint first so marks draw over it
+    pub fn build_mesh(&self) -> Mesh {
+        let count = self.len();
+        let mut positions = Vec::with_capacity(count * 4);
+        let mut normals = Vec::with_capacity(count * 4);
+        let mut uvs = Vec::with_capacity(count * 4);
+        let mut colors = Vec::with_capacity(count * 4);
+        let mut indices = Vec::with_capacity(count * 6);
+        for decal in self.paint.iter().chain(self.wear.iter()) {
+            let base = positions.len() as u32;
+            let rect = decal.kind.uv_rect();
+            let color = decal
+                .tint
+                .to_linear()
+                .with_alpha(decal.tint.alpha() * decal.alpha());
+            positions.extend(decal.corners().map(|corner| corner.to_array()));
+            normals.extend([decal.normal.to_array(); 4]);
+            uvs.extend([
+                [rect.min.x, rect.max.y],
+                [rect.max.x, rect.max.y],
+                [rect.max.x, rect.min.y],
+                [rect.min.x, rect.min.y],
+            ]);
+            colors.extend([color.to_f32_array(); 4]);
+            indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+/// Stable per-position rotation, so round decals don't all line up
+fn spin(position: Vec3) -> Vec3 {
+    let angle =
+        hash(position.x.to_bits() ^ position.z.to_bits().rotate_left(16)) * std::f32::consts::TAU;
+    Vec3::new(angle.cos(), 0.0, angle.sin())
+}
+
+/// Integer hash to 0..1
+fn hash(mut x: u32) -> f32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+    x as f32 / u32::MAX as f32
+}
+
+/// RGBA8 texel of `kind`'s cell at `uv` in 0..1 across the cell
+fn atlas_texel(kind: DecalKind, uv: Vec2) -> [u8; 4] {
+    let centered = uv * 2.0 - 1.0;
+    let radius = centered.length();
+    let angle = centered.y.atan2(centered.x);
+    let grain = hash((uv.x * 997.0) as u32 ^ ((uv.y * 991.0) as u32) << 12);
+    let (rgb, alpha) = match kind {
+        // Soft edges across the tread, faint streaks along it
+        DecalKind::SkidMark => {
+            let edge = 1.0 - centered.x.abs().powi(4);
+            let streak = 0.8 + 0.2 * hash((uv.x * 24.0) as u32);
+            ([22, 22, 24], 0.7 * edge * streak)
+        }
+        DecalKind::Scorch => {
+            let rim = 0.75 + 0.15 * (angle * 5.0).sin() + 0.1 * (angle * 11.0).cos();
+            let falloff = 1.0 - ((radius / rim - 0.35) / 0.65).clamp(0.0, 1.0);
+            ([16, 13, 11], falloff * falloff * (0.8 + 0.2 * grain))
+        }
+        DecalKind::Blood => {
+            let rim = 0.55 + 0.2 * (angle * 3.0 + 1.0).sin() * (angle * 7.0).cos();
+            let body = 1.0 - ((radius - rim) / 0.08).clamp(0.0, 1.0);
+            ([120, 6, 8], 0.85 * body)
+        }
+        DecalKind::Paint => {
+            let edge = (1.0 - centered.abs().max_element()) * CELL_SIZE as f32 / 4.0;
+            (
+                [235, 235, 230],
+                0.9 * edge.clamp(0.0, 1.0) * (0.9 + 0.1 * grain),
+            )
+        }
+    };
+    [
+        rgb[0],
+        rgb[1],
+        rgb[2],
+        (alpha.clamp(0.0, 1.0) * 255.0) as u8,
+    ]
+}
+
+/// Draws the decal atlas: one `CELL_SIZE` cell per `DecalKind`
+pub fn decal_atlas() -> Image {
+    let side = ATLAS_CELLS * CELL_SIZE;
+    let mut data = Vec::with_capacity((side * side * 4) as usize);
+    for y in 0..side {
+        for x in 0..side {
+            let cell = (y / CELL_SIZE) * ATLAS_CELLS + x / CELL_SIZE;
+            let kind = match cell {
+                0 => DecalKind::SkidMark,
+                1 => DecalKind::Scorch,
+                2 => DecalKind::Blood,
+                _ => DecalKind::Paint,
+            };
+            let uv = Vec2::new((x % CELL_SIZE) as f32 + 0.5, (y % CELL_SIZE) as f32 + 0.5)
+                / CELL_SIZE as f32;
+            data.extend(atlas_texel(kind, uv));
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: side,
+            height: side,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Spawns the entity every decal is drawn through
+pub fn setup_decal_batch(
+    mut commands: Commands,
+    mut decals: ResMut<Decals>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let mesh = meshes.add(decals.build_mesh());
+    let material = materials.add(StandardMaterial {
+        base_color_texture: Some(images.add(decal_atlas())),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        cull_mode: None,
+        ..default()
+    });
+    commands.spawn((
+        Mesh3d(mesh.clone()),
+        MeshMaterial3d(material),
+        Transform::IDENTITY,
+        // The mesh spans the whole world and changes, so its bounds would go stale
+        NoFrustumCulling,
+        NotShadowCaster,
+        NotShadowReceiver,
+        Name::new("Decal Batch"),
+    ));
+    decals.mesh = Some(mesh);
+}
+
+/// Ages the marks and rebuilds the batch mesh when something changed
+pub fn update_decal_batch(
+    time: Res<Time>,
+    mut decals: ResMut<Decals>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let delta = time.delta_secs();
+    let decals = decals.as_mut();
+    if decals.wear.age(delta) {
+        decals.dirty = true;
+    }
+    decals.since_rebuild += delta;
+    let fading =
+        decals.since_rebuild >= FADE_REBUILD_INTERVAL && decals.wear.iter().any(Decal::is_fading);
+    if !decals.dirty && !fading {
+        return;
+    }
+    let Some(mesh) = decals
+        .mesh
+        .as_ref()
+        .and_then(|handle| meshes.get_mut(handle))
+    else {
+        return;
+    };
+    *mesh = decals.build_mesh();
+    decals.dirty = false;
+    decals.since_rebuild = 0.0;
+}
+
+/// Point and normal of the surface under `position`
+fn project_down(context: &RapierContext, position: Vec3) -> Option<(Vec3, Vec3)> {
+    let origin = position + Vec3::Y * PROJECTION_REACH;
+    context
+        .cast_ray_and_get_normal(
+            origin,
+            Vec3::NEG_Y,
+            PROJECTION_REACH * 2.0,
+            true,
+            QueryFilter::only_fixed().exclude_sensors(),
+        )
+        .map(|(_, hit)| (hit.point, hit.normal))
+}
+
+/// Scorches the ground under destroyed vehicles and bloodies it under
+/// killed NPCs
+pub fn spawn_impact_decals(
+    mut destroyed: EventReader<VehicleDestroyed>,
+    mut killed: EventReader<NpcKilled>,
+    rapier_context: ReadRapierContext,
+    mut decals: ResMut<Decals>,
+) {
+    let Ok(context) = rapier_context.single() else {
+        destroyed.clear();
+        killed.clear();
+        return;
+    };
+    for event in destroyed.read() {
+        if let Some((point, normal)) = project_down(&context, event.position) {
+            decals.scorch(point, normal, SCORCH_RADIUS);
+        }
+    }
+    for event in killed.read() {
+        if let Some((point, normal)) = project_down(&context, event.position) {
+            decals.blood_splat(point, normal, BLOOD_RADIUS);
+        }
+    }
+}
+
+pub fn clear_decals(mut decals: ResMut<Decals>) {
+    decals.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mark(lifetime: f32) -> Decal {
+        Decal::new(
+            DecalKind::Scorch,
+            Vec3::new(lifetime, 0.0, 0.0),
+            Vec3::Y,
+            Vec3::Z,
+            Vec2::ONE,
+        )
+        .with_lifetime(lifetime)
+    }
+
+    #[test]
+    fn test_ring_overwrites_oldest_and_expires_marks() {
+        let mut ring = DecalRing::new(3);
+        for lifetime in [10.0, 20.0, 30.0, 40.0] {
+            ring.push(mark(lifetime));
+        }
+        let lifetimes: Vec<f32> = ring.iter().map(|decal| decal.lifetime).collect();
+        assert_eq!(lifetimes, [40.0, 20.0, 30.0]);
+
+        assert!(ring.age(29.0));
+        let lifetimes: Vec<f32> = ring.iter().map(|decal| decal.lifetime).collect();
+        assert_eq!(lifetimes, [30.0, 40.0]);
+        let fading = ring.iter().next().unwrap();
+        assert!(fading.is_fading() && fading.alpha() < 1.0);
+
+        // The oldest survivor is the next one overwritten
+        ring.push(mark(50.0));
+        ring.push(mark(60.0));
+        let lifetimes: Vec<f32> = ring.iter().map(|decal| decal.lifetime).collect();
+        assert_eq!(lifetimes, [60.0, 40.0, 50.0]);
+    }
+
+    #[test]
+    fn test_quads_lie_on_the_surface_with_their_cell_uvs() {
+        let normal = Vec3::new(0.0, 1.0, 1.0).normalize();
+        let decal = Decal::new(
+            DecalKind::Blood,
+            Vec3::ZERO,
+            normal,
+            Vec3::X,
+            Vec2::new(1.0, 2.0),
+        );
+        let corners = decal.corners();
+        for corner in corners {
+            assert!((corner.dot(normal) - DECAL_LIFT).abs() < 1e-5);
+        }
+        assert!((corners[0].distance(corners[1]) - 1.0).abs() < 1e-5);
+        assert!((corners[1].distance(corners[2]) - 2.0).abs() < 1e-5);
+        assert!(
+            (corners[1] - corners[0])
+                .cross(corners[2] - corners[1])
+                .dot(normal)
+                > 0.0
+        );
+
+        let rect = DecalKind::Blood.uv_rect();
+        assert!(rect.min.x < 0.5 && rect.min.y > 0.5 && rect.max.y < 1.0);
+
+        let mut decals = Decals::default();
+        decals.blood_splat(Vec3::ZERO, Vec3::Y, 1.0);
+        decals.road_paint(Vec3::X, Vec3::Z, Vec2::new(0.3, 4.0), Color::WHITE);
+        let mesh = decals.build_mesh();
+        assert_eq!(mesh.count_vertices(), 8);
+        assert_eq!(mesh.indices().map(|indices| indices.len()), Some(12));
+    }
+}
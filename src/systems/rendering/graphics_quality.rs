@@ -0,0 +1,135 @@
+//! Runtime side of the graphics quality presets.
+//!
+//! Shadows and the window are handled by `apply_graphics_settings`; these
+//! systems cover the settings that live on world entities: the LOD bias scales
+//! every `VisibilityRange`, and the vegetation density thins the palm batch.
+//! Both re-apply on `ConfigReloadedEvent`, so a preset change needs no restart,
+//! and whenever the frame budget governor moves its load scale, which
+//! multiplies into both.
+
+use super::instance_kinds::PalmTree;
+use super::instanced_batcher::{InstancedBatcher, instance_kept};
+use crate::config::{ConfigReloadedEvent, GameConfig};
+use crate::systems::performance::frame_budget::FrameBudgetGovernor;
+use crate::systems::world::unified_world::{ContentLayer, UnifiedChunkEntity};
+use bevy::prelude::*;
+use bevy::render::view::visibility::VisibilityRange;
+use bevy_rapier3d::prelude::{Collider, ColliderDisabled};
+
+type RangeQueries<'w, 's> = ParamSet<
+    'w,
+    's,
+    (
+        Query<'static, 'static, &'static mut VisibilityRange, Added<VisibilityRange>>,
+        Query<'static, 'static, &'static mut VisibilityRange>,
+    ),
+>;
+
+/// Keeps every `VisibilityRange` scaled by the preset's LOD bias. Spawners
+/// write unbiased ranges; new ones pick up the bias in use, and a preset
+/// change rescales everything by the ratio of new to old bias.
+pub fn apply_lod_bias(
+    mut reloaded: EventReader<ConfigReloadedEvent>,
+    config: Res<GameConfig>,
+    governor: Res<FrameBudgetGovernor>,
+    mut applied: Local<Option<f32>>,
+    mut ranges: RangeQueries,
+) {
+    let current = applied.unwrap_or(1.0);
+    if current != 1.0 {
+        for mut range in &mut ranges.p0() {
+            scale_range(&mut range, current);
+        }
+    }
+
+    let reloaded = reloaded.read().count() > 0;
+    if !reloaded && !governor.is_changed() {
+        return;
+    }
+    let bias = config.graphics.quality.settings().lod_bias * governor.scale();
+    if bias != current {
+        crate::profile_scope!("rescale_visibility_ranges", bias);
+        for mut range in &mut ranges.p1() {
+            scale_range(&mut range, bias / current);
+        }
+    }
+    *applied = Some(bias);
+}
+
+fn scale_range(range: &mut VisibilityRange, factor: f32) {
+    range.start_margin = range.start_margin.start * factor..range.start_margin.end * factor;
+    range.end_margin = range.end_margin.start * factor..range.end_margin.end * factor;
+}
+
+/// Thins the palm batch to the preset's vegetation density and disables the
+/// trunk colliders of palms that are no longer drawn
+#[allow(clippy::too_many_arguments)]
+pub fn apply_vegetation_density(
+    mut commands: Commands,
+    mut reloaded: EventReader<ConfigReloadedEvent>,
+    config: Res<GameConfig>,
+    governor: Res<FrameBudgetGovernor>,
+    mut palms: ResMut<InstancedBatcher<PalmTree>>,
+    colliders: Query<(Entity, &ChildOf, Has<ColliderDisabled>), With<Collider>>,
+    new_colliders: Query<Entity, Added<Collider>>,
+    trees: Query<(&Transform, &UnifiedChunkEntity)>,
+) {
+    let target = config.graphics.quality.settings().vegetation_density * governor.scale();
+    let changed =
+        (reloaded.read().count() > 0 || governor.is_changed()) && target != palms.density();
+    if changed {
+        palms.set_density(target);
+    } else if palms.density() >= 1.0 || new_colliders.is_empty() {
+        return;
+    }
+    let density = palms.density();
+
+    let mut update = |entity: Entity| {
+        let Ok((entity, parent, disabled)) = colliders.get(entity) else {
+            return;
+        };
+        let Ok((transform, chunk_entity)) = trees.get(parent.parent()) else {
+            return;
+        };
+        if chunk_entity.layer != ContentLayer::Vegetation {
+            return;
+        }
+        let kept = instance_kept(transform.translation, density);
+        if kept && disabled {
+            commands.entity(entity).remove::<ColliderDisabled>();
+        } else if !kept && !disabled {
+            commands.entity(entity).insert(ColliderDisabled);
+        }
+    };
+    if changed {
+        colliders.iter().for_each(|(entity, ..)| update(entity));
+    } else {
+        new_colliders.iter().for_each(&mut update);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lower_density_keeps_a_subset() {
+        let positions: Vec<Vec3> = (0..2000)
+            .map(|i| Vec3::new((i % 50) as f32 * 7.0, 0.0, (i / 50) as f32 * 11.0))
+            .collect();
+        let kept = |density| {
+            positions
+                .iter()
+                .filter(|p| instance_kept(**p, density))
+                .count()
+        };
+        assert_eq!(kept(1.0), positions.len());
+        let half = kept(0.5);
+        assert!((800..1200).contains(&half), "kept {half} of 2000");
+        assert!(
+            positions
+                .iter()
+                .all(|p| !instance_kept(*p, 0.3) || instance_kept(*p, 0.6))
+        );
+    }
+}
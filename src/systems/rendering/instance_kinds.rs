@@ -0,0 +1,203 @@
+//! Prop types drawn through `InstancedBatcher`.
+//!
+//! Instance transforms put props on the ground with local -Z (forward)
+//! facing the road or, for fences, running along local X.
+
+use super::instanced_batcher::{InstanceKind, InstancePart};
+use crate::config::GameConfig;
+use crate::systems::world::unified_world::ContentLayer;
+use bevy::prelude::*;
+
+/// Length of one fence panel; fence runs are built from panels end to end
+pub const FENCE_PANEL_LENGTH: f32 = 3.0;
+
+pub struct PalmTree;
+pub struct Streetlight;
+pub struct TrafficCone;
+pub struct Fence;
+/// Single-box stand-in for a parked car far from the player
+pub struct CarImpostor;
+
+fn part(
+    mesh: Handle<Mesh>,
+    material: &Handle<StandardMaterial>,
+    offset: Transform,
+) -> InstancePart {
+    InstancePart {
+        mesh,
+        material: material.clone(),
+        offset,
+    }
+}
+
+impl InstanceKind for PalmTree {
+    const LAYER: ContentLayer = ContentLayer::Vegetation;
+
+    fn parts(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Vec<InstancePart> {
+        let trunk = materials.add(Color::srgb(0.4, 0.25, 0.15));
+        let fronds = materials.add(Color::srgb(0.2, 0.6, 0.25));
+        let frond = meshes.add(Cuboid::new(2.5, 0.1, 0.8));
+
+        let mut parts = vec![part(
+            meshes.add(Cylinder::new(0.3, 8.0)),
+            &trunk,
+            Transform::from_xyz(0.0, 4.0, 0.0),
+        )];
+        // Four fronds in a cross with a slight droop
+        for i in 0..4 {
+            let angle = (i as f32) * std::f32::consts::PI / 2.0;
+            parts.push(part(
+                frond.clone(),
+                &fronds,
+                Transform::from_xyz(angle.cos() * 1.2, 7.5, angle.sin() * 1.2)
+                    .with_rotation(Quat::from_rotation_y(angle) * Quat::from_rotation_z(-0.2)),
+            ));
+        }
+        parts
+    }
+
+    fn cull_distance(config: &GameConfig) -> Option<f32> {
+        Some(config.world_streaming.vegetation_cull_distance)
+    }
+}
+
+impl InstanceKind for Streetlight {
+    const LAYER: ContentLayer = ContentLayer::Props;
+
+    fn parts(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Vec<InstancePart> {
+        let metal = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.25, 0.26, 0.28),
+            metallic: 0.6,
+            perceptual_roughness: 0.5,
+            ..default()
+        });
+        let lamp = materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.9, 0.7),
+            emissive: LinearRgba::rgb(4.0, 3.4, 2.4),
+            ..default()
+        });
+        vec![
+            part(
+                meshes.add(Cylinder::new(0.12, 6.0)),
+                &metal,
+                Transform::from_xyz(0.0, 3.0, 0.0),
+            ),
+            part(
+                meshes.add(Cuboid::new(0.1, 0.1, 1.6)),
+                &metal,
+                Transform::from_xyz(0.0, 5.95, -0.8),
+            ),
+            part(
+                meshes.add(Cuboid::new(0.35, 0.12, 0.6)),
+                &lamp,
+                Transform::from_xyz(0.0, 5.85, -1.4),
+            ),
+        ]
+    }
+
+    fn cull_distance(config: &GameConfig) -> Option<f32> {
+        Some(config.world_streaming.prop_cull_distance)
+    }
+}
+
+impl InstanceKind for TrafficCone {
+    const LAYER: ContentLayer = ContentLayer::Props;
+
+    fn parts(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Vec<InstancePart> {
+        let orange = materials.add(Color::srgb(1.0, 0.4, 0.05));
+        let base = materials.add(Color::srgb(0.08, 0.08, 0.08));
+        vec![
+            part(
+                meshes.add(Cone {
+                    radius: 0.2,
+                    height: 0.7,
+                }),
+                &orange,
+                Transform::from_xyz(0.0, 0.39, 0.0),
+            ),
+            part(
+                meshes.add(Cuboid::new(0.45, 0.04, 0.45)),
+                &base,
+                Transform::from_xyz(0.0, 0.02, 0.0),
+            ),
+        ]
+    }
+
+    fn cull_distance(config: &GameConfig) -> Option<f32> {
+        // Small enough to vanish well before other props
+        Some(config.world_streaming.prop_cull_distance * 0.5)
+    }
+}
+
+impl InstanceKind for Fence {
+    const LAYER: ContentLayer = ContentLayer::Props;
+
+    fn parts(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Vec<InstancePart> {
+        let steel = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.55, 0.56, 0.55),
+            metallic: 0.7,
+            perceptual_roughness: 0.6,
+            ..default()
+        });
+        let half = FENCE_PANEL_LENGTH * 0.5;
+        let post = meshes.add(Cuboid::new(0.08, 1.8, 0.08));
+        let rail = meshes.add(Cuboid::new(FENCE_PANEL_LENGTH, 0.06, 0.04));
+        let mut parts = vec![
+            part(post.clone(), &steel, Transform::from_xyz(-half, 0.9, 0.0)),
+            part(post, &steel, Transform::from_xyz(half, 0.9, 0.0)),
+        ];
+        for y in [0.3, 0.9, 1.6] {
+            parts.push(part(rail.clone(), &steel, Transform::from_xyz(0.0, y, 0.0)));
+        }
+        parts
+    }
+
+    fn cull_distance(config: &GameConfig) -> Option<f32> {
+        Some(config.world_streaming.prop_cull_distance)
+    }
+}
+
+impl InstanceKind for CarImpostor {
+    const LAYER: ContentLayer = ContentLayer::Vehicles;
+
+    fn parts(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Vec<InstancePart> {
+        let paint = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.35, 0.35, 0.38),
+            perceptual_roughness: 0.4,
+            ..default()
+        });
+        let glass = materials.add(Color::srgb(0.08, 0.1, 0.12));
+        // Matches the car's visual bounds around its body center
+        vec![
+            part(
+                meshes.add(Cuboid::new(1.8, 0.7, 4.2)),
+                &paint,
+                Transform::from_xyz(0.0, -0.25, 0.0),
+            ),
+            part(
+                meshes.add(Cuboid::new(1.5, 0.5, 2.0)),
+                &glass,
+                Transform::from_xyz(0.0, 0.35, 0.2),
+            ),
+        ]
+    }
+
+    fn cull_distance(config: &GameConfig) -> Option<f32> {
+        Some(config.world_streaming.streaming_radius)
+    }
+}
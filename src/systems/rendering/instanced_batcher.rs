@@ -0,0 +1,307 @@
+//! Shared instancing path for repeated world props.
+//!
+//! Each prop type implements `InstanceKind` and gets its own
+//! `InstancedBatcher<T>` resource: a per-chunk buffer of instance transforms
+//! plus the mesh/material handles every instance shares. Generators and
+//! systems only write transforms into the buffer; `upload_instances::<T>`
+//! turns dirty buffers into render entities. Because every entity of a kind
+//! uses the same handles, the renderer batches them into instanced draws.
+//! A batcher's density thins the drawn instances without touching the buffers.
+
+use crate::config::GameConfig;
+use crate::systems::world::unified_world::{ChunkCoord, ContentLayer, UnifiedChunkEntity};
+use bevy::prelude::*;
+use bevy::render::view::visibility::VisibilityRange;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+/// One mesh drawn for every instance, placed relative to the instance transform
+#[derive(Clone)]
+pub struct InstancePart {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+    pub offset: Transform,
+}
+
+/// A prop type drawn through an `InstancedBatcher`
+pub trait InstanceKind: Send + Sync + 'static {
+    /// Layer tag put on render entities, so chunk-wide systems treat them like other content
+    const LAYER: ContentLayer;
+
+    fn parts(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Vec<InstancePart>;
+
+    /// Distance past which instances stop drawing; `None` draws them at any range
+    fn cull_distance(config: &GameConfig) -> Option<f32>;
+}
+
+#[derive(Default)]
+struct InstanceBuffer {
+    instances: Vec<Transform>,
+    /// Render entities, `parts.len()` per instance in instance order
+    entities: Vec<Entity>,
+}
+
+/// Per-chunk instance buffers of one prop type
+#[derive(Resource)]
+pub struct InstancedBatcher<T: InstanceKind> {
+    parts: Vec<InstancePart>,
+    buffers: HashMap<ChunkCoord, InstanceBuffer>,
+    dirty: HashSet<ChunkCoord>,
+    /// Fraction of instances drawn, picked by `instance_kept`
+    density: f32,
+    _kind: PhantomData<T>,
+}
+
+impl<T: InstanceKind> Default for InstancedBatcher<T> {
+    fn default() -> Self {
+        Self {
+            parts: Vec::new(),
+            buffers: HashMap::new(),
+            dirty: HashSet::new(),
+            density: 1.0,
+            _kind: PhantomData,
+        }
+    }
+}
+
+impl<T: InstanceKind> InstancedBatcher<T> {
+    pub fn push(&mut self, coord: ChunkCoord, instance: Transform) {
+        self.buffers
+            .entry(coord)
+            .or_default()
+            .instances
+            .push(instance);
+        self.dirty.insert(coord);
+    }
+
+    /// Replaces a chunk's instances; unchanged contents don't mark it dirty
+    pub fn set_chunk(&mut self, coord: ChunkCoord, instances: Vec<Transform>) {
+        let buffer = self.buffers.entry(coord).or_default();
+        if buffer.instances != instances {
+            buffer.instances = instances;
+            self.dirty.insert(coord);
+        }
+    }
+
+    /// Replaces every chunk's instances, emptying chunks missing from `chunks`
+    pub fn set_all(&mut self, mut chunks: HashMap<ChunkCoord, Vec<Transform>>) {
+        let coords: Vec<ChunkCoord> = self.buffers.keys().copied().collect();
+        for coord in coords {
+            let instances = chunks.remove(&coord).unwrap_or_default();
+            self.set_chunk(coord, instances);
+        }
+        for (coord, instances) in chunks {
+            self.set_chunk(coord, instances);
+        }
+    }
+
+    /// Forgets every chunk without despawning its entities; for when they
+    /// were already despawned along with the rest of the chunk content
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+        self.dirty.clear();
+    }
+
+    pub fn instances(&self, coord: ChunkCoord) -> &[Transform] {
+        self.buffers
+            .get(&coord)
+            .map_or(&[], |buffer| &buffer.instances)
+    }
+
+    pub fn instance_count(&self) -> usize {
+        self.buffers
+            .values()
+            .map(|buffer| buffer.instances.len())
+            .sum()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    pub fn density(&self) -> f32 {
+        self.density
+    }
+
+    /// Changes the drawn fraction, re-uploading every chunk if it differs
+    pub fn set_density(&mut self, density: f32) {
+        let density = density.clamp(0.0, 1.0);
+        if density != self.density {
+            self.density = density;
+            self.dirty.extend(self.buffers.keys().copied());
+        }
+    }
+}
+
+/// Whether an instance at `translation` is drawn at `density`. The choice
+/// hashes the position, so it is stable across uploads and the instances kept
+/// at a lower density are a subset of those kept at a higher one.
+pub fn instance_kept(translation: Vec3, density: f32) -> bool {
+    if density >= 1.0 {
+        return true;
+    }
+    let cell = translation.round().as_ivec3();
+    let mut hash = (cell.x as u32).wrapping_mul(0x9E37_79B1)
+        ^ (cell.y as u32).wrapping_mul(0x85EB_CA77)
+        ^ (cell.z as u32).wrapping_mul(0xC2B2_AE3D);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2C1B_3C6D);
+    hash ^= hash >> 12;
+    (hash as f32 / u32::MAX as f32) < density
+}
+
+/// Brings the render entities of dirty chunks in line with their buffers,
+/// reusing existing entities where the instance count allows
+pub fn upload_instances<T: InstanceKind>(
+    mut commands: Commands,
+    mut batcher: ResMut<InstancedBatcher<T>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<GameConfig>,
+) {
+    if !batcher.is_dirty() {
+        return;
+    }
+    let batcher = &mut *batcher;
+    if batcher.parts.is_empty() {
+        batcher.parts = T::parts(&mut meshes, &mut materials);
+    }
+    let range = T::cull_distance(&config).map(|distance| VisibilityRange {
+        start_margin: 0.0..0.0,
+        end_margin: (distance * 0.9)..(distance * 1.1),
+        use_aabb: false,
+    });
+
+    for coord in batcher.dirty.drain() {
+        let Some(buffer) = batcher.buffers.get_mut(&coord) else {
+            continue;
+        };
+        crate::profile_scope!(
+            "upload_chunk",
+            kind = std::any::type_name::<T>(),
+            instances = buffer.instances.len()
+        );
+        let mut index = 0;
+        let drawn = buffer
+            .instances
+            .iter()
+            .filter(|instance| instance_kept(instance.translation, batcher.density));
+        for instance in drawn {
+            for part in &batcher.parts {
+                let transform = instance.mul_transform(part.offset);
+                if let Some(&entity) = buffer.entities.get(index) {
+                    commands.entity(entity).try_insert(transform);
+                } else {
+                    let mut entity = commands.spawn((
+                        Mesh3d(part.mesh.clone()),
+                        MeshMaterial3d(part.material.clone()),
+                        transform,
+                        Visibility::default(),
+                        UnifiedChunkEntity {
+                            coord,
+                            layer: T::LAYER,
+                        },
+                    ));
+                    if let Some(range) = &range {
+                        entity.insert(range.clone());
+                    }
+                    buffer.entities.push(entity.id());
+                }
+                index += 1;
+            }
+        }
+        for entity in buffer.entities.drain(index..) {
+            commands.entity(entity).try_despawn();
+        }
+    }
+    batcher
+        .buffers
+        .retain(|_, buffer| !buffer.instances.is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestProp;
+
+    impl InstanceKind for TestProp {
+        const LAYER: ContentLayer = ContentLayer::Props;
+
+        fn parts(
+            meshes: &mut Assets<Mesh>,
+            materials: &mut Assets<StandardMaterial>,
+        ) -> Vec<InstancePart> {
+            let material = materials.add(Color::WHITE);
+            [0.0, 1.0]
+                .into_iter()
+                .map(|y| InstancePart {
+                    mesh: meshes.add(Cuboid::default()),
+                    material: material.clone(),
+                    offset: Transform::from_xyz(0.0, y, 0.0),
+                })
+                .collect()
+        }
+
+        fn cull_distance(_config: &GameConfig) -> Option<f32> {
+            None
+        }
+    }
+
+    fn rendered(app: &mut App) -> Vec<Vec3> {
+        let mut query = app.world_mut().query_filtered::<&Transform, With<Mesh3d>>();
+        let mut positions: Vec<Vec3> = query
+            .iter(app.world())
+            .map(|transform| transform.translation)
+            .collect();
+        positions.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        positions
+    }
+
+    #[test]
+    fn test_upload_tracks_buffer_and_skips_clean_chunks() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<GameConfig>()
+            .init_resource::<InstancedBatcher<TestProp>>()
+            .add_systems(Update, upload_instances::<TestProp>);
+
+        let coord = ChunkCoord::new(0, 0);
+        let instances: Vec<Transform> = (0..3)
+            .map(|i| Transform::from_xyz(i as f32 * 10.0, 0.0, 0.0))
+            .collect();
+        app.world_mut()
+            .resource_mut::<InstancedBatcher<TestProp>>()
+            .set_chunk(coord, instances.clone());
+        app.update();
+        assert_eq!(rendered(&mut app).len(), 6);
+        assert_eq!(rendered(&mut app)[1], Vec3::new(0.0, 1.0, 0.0));
+
+        // Same contents stay clean; a shorter buffer drops surplus entities
+        let mut batcher = app.world_mut().resource_mut::<InstancedBatcher<TestProp>>();
+        batcher.set_chunk(coord, instances);
+        assert!(!batcher.is_dirty());
+        batcher.set_chunk(coord, vec![Transform::from_xyz(5.0, 0.0, 0.0)]);
+        app.update();
+        assert_eq!(
+            rendered(&mut app),
+            vec![Vec3::new(5.0, 0.0, 0.0), Vec3::new(5.0, 1.0, 0.0)]
+        );
+
+        app.world_mut()
+            .resource_mut::<InstancedBatcher<TestProp>>()
+            .set_all(HashMap::new());
+        app.update();
+        assert!(rendered(&mut app).is_empty());
+        assert_eq!(
+            app.world()
+                .resource::<InstancedBatcher<TestProp>>()
+                .instance_count(),
+            0
+        );
+    }
+}
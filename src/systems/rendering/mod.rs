@@ -0,0 +1,24 @@
+//! Instanced rendering of repeated props: palm trees, streetlights, traffic
+//! cones, fences and far parked car impostors, plus the graphics quality
+//! settings applied to world entities, the `PostPass` layer fullscreen
+//! post-processing passes are declared with, the single-draw decal batch,
+//! the sky probe and screen-space reflection hooks, and the budgeted GPU
+//! upload queue with the residency manager that frees far buildings' assets.
+
+pub mod car_impostors;
+pub mod decals;
+pub mod graphics_quality;
+pub mod instance_kinds;
+pub mod instanced_batcher;
+pub mod post_pass;
+pub mod post_processing;
+pub mod reflections;
+pub mod residency;
+pub mod upload_queue;
+
+pub use instance_kinds::{
+    CarImpostor, FENCE_PANEL_LENGTH, Fence, PalmTree, Streetlight, TrafficCone,
+};
+pub use instanced_batcher::{
+    InstanceKind, InstancePart, InstancedBatcher, instance_kept, upload_instances,
+};
@@ -0,0 +1,360 @@
+//! Declarative fullscreen post-processing passes on the `Core3d` render graph.
+//!
+//! A pass is a camera component implementing `PostPass`: its name, shader,
+//! the graph nodes it runs between and whether it reads the depth buffer.
+//! `PostPassPlugin` does the wiring every pass otherwise repeats by hand:
+//! extracting and uploading the component as a uniform, adding the graph node
+//! and its edges, making the depth texture bindable when it is read, building
+//! the pipeline, ping-ponging the view target, and labelling the pipeline,
+//! bind groups and render pass with the pass name for GPU debuggers.
+//!
+//! Passes run on the HDR target before tonemapping. The shader sees:
+//! - group 0: the `View` uniform
+//! - group 1: the source colour texture and a linear sampler
+//! - group 2: the depth texture, only when `READS_DEPTH` is set
+//! - the next group: the component itself as a uniform
+
+use bevy::core_pipeline::core_3d::graph::{Core3d, Node3d};
+use bevy::core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+use bevy::ecs::query::QueryItem;
+use bevy::prelude::*;
+use bevy::render::extract_component::{
+    ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+    UniformComponentPlugin,
+};
+use bevy::render::render_graph::{
+    InternedRenderLabel, NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode,
+    ViewNodeRunner,
+};
+use bevy::render::render_resource::encase::internal::WriteInto;
+use bevy::render::render_resource::{
+    binding_types::{sampler, texture_2d, texture_depth_2d, uniform_buffer},
+    *,
+};
+use bevy::render::renderer::{RenderContext, RenderDevice};
+use bevy::render::view::{
+    ViewDepthTexture, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms,
+};
+use bevy::render::{Render, RenderApp, RenderSet};
+use std::marker::PhantomData;
+
+/// Camera component describing a fullscreen pass; its fields are the
+/// pass's uniform
+pub trait PostPass:
+    Component + ExtractComponent + ShaderType + ShaderSize + WriteInto + Clone
+{
+    /// Graph label and prefix of every GPU debug label
+    const NAME: &'static str;
+    /// Asset path of the WGSL file with a `fragment` entry point
+    const SHADER: &'static str;
+    const READS_DEPTH: bool = false;
+
+    /// Nodes the pass runs after and before; another pass's `PostPassLabel`
+    /// works too, to order passes among themselves
+    fn between() -> (InternedRenderLabel, InternedRenderLabel) {
+        (
+            Node3d::MainTransparentPass.intern(),
+            Node3d::Tonemapping.intern(),
+        )
+    }
+}
+
+/// Graph label of a pass, keyed by `PostPass::NAME`
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PostPassLabel(pub &'static str);
+
+/// Bind group the pass's uniform goes in
+pub fn settings_group(reads_depth: bool) -> u32 {
+    if reads_depth { 3 } else { 2 }
+}
+
+pub struct PostPassPlugin<P: PostPass>(PhantomData<fn() -> P>);
+
+impl<P: PostPass> Default for PostPassPlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: PostPass> Plugin for PostPassPlugin<P> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractComponentPlugin::<P>::default())
+            .add_plugins(UniformComponentPlugin::<P>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        if P::READS_DEPTH {
+            render_app.add_systems(
+                Render,
+                make_depth_bindable::<P>.in_set(RenderSet::ManageViews),
+            );
+        }
+        let (after, before) = P::between();
+        render_app
+            .add_render_graph_node::<ViewNodeRunner<PostPassNode<P>>>(
+                Core3d,
+                PostPassLabel(P::NAME),
+            )
+            .add_render_graph_edges(Core3d, (after, PostPassLabel(P::NAME), before));
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<PostPassPipeline<P>>();
+    }
+}
+
+fn make_depth_bindable<P: PostPass>(mut cameras: Query<&mut Camera3d, With<P>>) {
+    for mut camera_3d in cameras.iter_mut() {
+        let usages = TextureUsages::from(camera_3d.depth_texture_usages);
+        if !usages.contains(TextureUsages::TEXTURE_BINDING) {
+            camera_3d.depth_texture_usages = (usages | TextureUsages::TEXTURE_BINDING).into();
+        }
+    }
+}
+
+struct PostPassNode<P>(PhantomData<fn() -> P>);
+
+impl<P> Default for PostPassNode<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: PostPass> ViewNode for PostPassNode<P> {
+    type ViewQuery = (
+        &'static ViewTarget,
+        Option<&'static ViewDepthTexture>,
+        &'static DynamicUniformIndex<P>,
+        &'static ViewUniformOffset,
+    );
+
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, depth, settings_index, view_uniform_offset): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pass = world.resource::<PostPassPipeline<P>>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let settings_uniforms = world.resource::<ComponentUniforms<P>>();
+        let view_uniforms = world.resource::<ViewUniforms>();
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pass.pipeline_id) else {
+            warn_once!("{} pipeline not ready yet", P::NAME);
+            return Ok(());
+        };
+        let (Some(settings_binding), Some(view_binding)) = (
+            settings_uniforms.uniforms().binding(),
+            view_uniforms.uniforms.binding(),
+        ) else {
+            return Ok(());
+        };
+        let depth_view = match (&pass.depth_layout, depth) {
+            (Some(_), Some(depth)) => Some(depth.view()),
+            (Some(_), None) => return Ok(()),
+            (None, _) => None,
+        };
+
+        let post_process = view_target.post_process_write();
+        let device = render_context.render_device();
+        let view_bind_group = device.create_bind_group(
+            pass.labels.view.as_str(),
+            &pass.view_layout,
+            &BindGroupEntries::sequential((view_binding,)),
+        );
+        let src_bind_group = device.create_bind_group(
+            pass.labels.source.as_str(),
+            &pass.src_layout,
+            &BindGroupEntries::sequential((post_process.source, &pass.sampler)),
+        );
+        let depth_bind_group = pass
+            .depth_layout
+            .as_ref()
+            .zip(depth_view)
+            .map(|(layout, view)| {
+                device.create_bind_group(
+                    pass.labels.depth.as_str(),
+                    layout,
+                    &BindGroupEntries::sequential((view,)),
+                )
+            });
+        let settings_bind_group = device.create_bind_group(
+            pass.labels.settings.as_str(),
+            &pass.settings_layout,
+            &BindGroupEntries::sequential((settings_binding,)),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some(pass.labels.pass.as_str()),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &view_bind_group, &[view_uniform_offset.offset]);
+        render_pass.set_bind_group(1, &src_bind_group, &[]);
+        if let Some(depth_bind_group) = &depth_bind_group {
+            render_pass.set_bind_group(2, depth_bind_group, &[]);
+        }
+        render_pass.set_bind_group(
+            settings_group(depth_bind_group.is_some()) as usize,
+            &settings_bind_group,
+            &[settings_index.index()],
+        );
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+/// GPU debug labels, built once from the pass name
+struct PassLabels {
+    pass: String,
+    view: String,
+    source: String,
+    depth: String,
+    settings: String,
+}
+
+impl PassLabels {
+    fn new(name: &str) -> Self {
+        Self {
+            pass: format!("{name}_pass"),
+            view: format!("{name}_view_bind_group"),
+            source: format!("{name}_src_bind_group"),
+            depth: format!("{name}_depth_bind_group"),
+            settings: format!("{name}_settings_bind_group"),
+        }
+    }
+}
+
+#[derive(Resource)]
+struct PostPassPipeline<P> {
+    view_layout: BindGroupLayout,
+    src_layout: BindGroupLayout,
+    depth_layout: Option<BindGroupLayout>,
+    settings_layout: BindGroupLayout,
+    sampler: Sampler,
+    pipeline_id: CachedRenderPipelineId,
+    labels: PassLabels,
+    marker: PhantomData<fn() -> P>,
+}
+
+impl<P: PostPass> FromWorld for PostPassPipeline<P> {
+    fn from_world(world: &mut World) -> Self {
+        let name = P::NAME;
+        let render_device = world.resource::<RenderDevice>();
+
+        let view_layout = render_device.create_bind_group_layout(
+            format!("{name}_view_layout").as_str(),
+            &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: Some(ViewUniform::min_size()),
+                },
+                count: None,
+            }],
+        );
+        let src_layout = render_device.create_bind_group_layout(
+            format!("{name}_src_layout").as_str(),
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+        let depth_layout = P::READS_DEPTH.then(|| {
+            render_device.create_bind_group_layout(
+                format!("{name}_depth_layout").as_str(),
+                &BindGroupLayoutEntries::sequential(ShaderStages::FRAGMENT, (texture_depth_2d(),)),
+            )
+        });
+        let settings_layout = render_device.create_bind_group_layout(
+            format!("{name}_settings_layout").as_str(),
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (uniform_buffer::<P>(true),),
+            ),
+        );
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some(format!("{name}_src_sampler").as_str()),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            ..default()
+        });
+
+        let mut layout = vec![view_layout.clone(), src_layout.clone()];
+        layout.extend(depth_layout.clone());
+        layout.push(settings_layout.clone());
+
+        let shader = world.load_asset(P::SHADER);
+        let pipeline_id =
+            world
+                .resource::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some(format!("{name}_pipeline").into()),
+                    layout,
+                    vertex: fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader,
+                        shader_defs: vec![],
+                        entry_point: "fragment".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: ViewTarget::TEXTURE_FORMAT_HDR,
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    push_constant_ranges: vec![],
+                    zero_initialize_workgroup_memory: false,
+                });
+
+        Self {
+            view_layout,
+            src_layout,
+            depth_layout,
+            settings_layout,
+            sampler,
+            pipeline_id,
+            labels: PassLabels::new(name),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pass_labels_and_groups_follow_name_and_inputs() {
+        let labels = PassLabels::new("underwater");
+        assert_eq!(labels.pass, "underwater_pass");
+        assert_eq!(labels.settings, "underwater_settings_bind_group");
+        assert_eq!(settings_group(true), 3);
+        assert_eq!(settings_group(false), 2);
+        assert_eq!(PostPassLabel("underwater"), PostPassLabel("underwater"));
+    }
+}
@@ -0,0 +1,182 @@
+//! Main camera post-processing: tonemapping, exposure, bloom, speed-scaled
+//! motion blur, screen-space reflections and the damage vignette.
+//!
+//! `apply_post_processing` sets the camera up from `post_processing.ron` and
+//! the quality preset on the first frame, when the preset changes and when the
+//! file is reloaded. Effects the preset turns off are removed rather than
+//! zeroed, so they cost nothing; motion blur takes its motion vector prepass
+//! with it and screen-space reflections their deferred prepass. The per-frame systems then only move the strengths: motion blur
+//! with the speed of the vehicle being driven, the vignette with the player's
+//! missing health plus a short flash after every hit.
+
+use crate::components::{ActiveEntity, DamageVignette, MainCamera, Player, PlayerHealth};
+use crate::config::{
+    ConfigFieldChanged, ConfigReloadedEvent, GameConfig, PostProcessingConfig, TonemappingMode,
+};
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::core_pipeline::motion_blur::MotionBlur;
+use bevy::core_pipeline::prepass::{DeferredPrepass, MotionVectorPrepass};
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::pbr::ScreenSpaceReflections;
+use bevy::prelude::*;
+use bevy::render::camera::Exposure;
+use bevy_rapier3d::prelude::Velocity;
+
+impl From<TonemappingMode> for Tonemapping {
+    fn from(mode: TonemappingMode) -> Self {
+        match mode {
+            TonemappingMode::None => Tonemapping::None,
+            TonemappingMode::Reinhard => Tonemapping::Reinhard,
+            TonemappingMode::ReinhardLuminance => Tonemapping::ReinhardLuminance,
+            TonemappingMode::AcesFitted => Tonemapping::AcesFitted,
+            TonemappingMode::AgX => Tonemapping::AgX,
+            TonemappingMode::TonyMcMapface => Tonemapping::TonyMcMapface,
+            TonemappingMode::BlenderFilmic => Tonemapping::BlenderFilmic,
+        }
+    }
+}
+
+/// Tight, high-pass bloom: the sun, headlights and flame cores glow, lit
+/// walls don't
+fn bloom(intensity: f32) -> Bloom {
+    Bloom {
+        intensity,
+        low_frequency_boost: 0.0,
+        low_frequency_boost_curvature: 0.0,
+        high_pass_frequency: 1.0,
+        ..default()
+    }
+}
+
+pub fn apply_post_processing(
+    mut commands: Commands,
+    mut reloaded: EventReader<ConfigReloadedEvent>,
+    mut changed: EventReader<ConfigFieldChanged>,
+    config: Res<GameConfig>,
+    cameras: Query<Entity, With<MainCamera>>,
+    new_cameras: Query<(), Added<MainCamera>>,
+) {
+    let reloaded = reloaded.read().count() > 0;
+    let file_changed = changed
+        .read()
+        .any(|change| change.file == "post_processing.ron");
+    if !reloaded && !file_changed && new_cameras.is_empty() {
+        return;
+    }
+    let settings = &config.post_processing;
+    let quality = config.graphics.quality.settings();
+
+    for camera in &cameras {
+        let mut camera = commands.entity(camera);
+        camera.insert((
+            Tonemapping::from(settings.tonemapping),
+            Exposure {
+                ev100: settings.exposure_ev100,
+            },
+        ));
+        if quality.bloom && settings.bloom_intensity > 0.0 {
+            camera.insert(bloom(settings.bloom_intensity));
+        } else {
+            camera.remove::<Bloom>();
+        }
+        if quality.motion_blur && settings.motion_blur_shutter_angle > 0.0 {
+            camera.insert(MotionBlur {
+                shutter_angle: 0.0,
+                samples: settings.motion_blur_samples,
+            });
+        } else {
+            camera.remove::<(MotionBlur, MotionVectorPrepass)>();
+        }
+        if quality.screen_space_reflections {
+            camera.insert(ScreenSpaceReflections {
+                perceptual_roughness_threshold: settings.ssr_max_roughness,
+                ..default()
+            });
+        } else {
+            camera.remove::<(ScreenSpaceReflections, DeferredPrepass)>();
+        }
+        if quality.damage_vignette && settings.damage_vignette > 0.0 {
+            camera.insert(DamageVignette::default());
+        } else {
+            camera.remove::<DamageVignette>();
+        }
+    }
+}
+
+/// Shutter angle for travelling at `speed`: none at rest, the configured
+/// angle from `motion_blur_full_speed` on
+pub fn motion_blur_shutter_angle(settings: &PostProcessingConfig, speed: f32) -> f32 {
+    settings.motion_blur_shutter_angle * (speed / settings.motion_blur_full_speed).clamp(0.0, 1.0)
+}
+
+/// Blurs with the speed of the vehicle being driven; on foot there is none
+#[allow(clippy::type_complexity)]
+pub fn update_motion_blur(
+    config: Res<GameConfig>,
+    vehicles: Query<&Velocity, (With<ActiveEntity>, Without<Player>)>,
+    mut cameras: Query<&mut MotionBlur, With<MainCamera>>,
+) {
+    let speed = vehicles
+        .single()
+        .map_or(0.0, |velocity| velocity.linvel.length());
+    let shutter_angle = motion_blur_shutter_angle(&config.post_processing, speed);
+    for mut motion_blur in &mut cameras {
+        if (motion_blur.shutter_angle - shutter_angle).abs() > 0.01 {
+            motion_blur.shutter_angle = shutter_angle;
+        }
+    }
+}
+
+/// Vignette strength for `health`: grows with missing health, plus a flash
+/// that fades over `damage_flash_seconds` after each hit
+pub fn damage_vignette_intensity(settings: &PostProcessingConfig, health: &PlayerHealth) -> f32 {
+    let missing = 1.0 - (health.current / health.max.max(1.0)).clamp(0.0, 1.0);
+    let flash = if settings.damage_flash_seconds > 0.0 {
+        0.5 * (1.0 - health.since_damage / settings.damage_flash_seconds).max(0.0)
+    } else {
+        0.0
+    };
+    (missing + flash).min(1.0) * settings.damage_vignette
+}
+
+pub fn update_damage_vignette(
+    config: Res<GameConfig>,
+    players: Query<&PlayerHealth, With<Player>>,
+    mut cameras: Query<&mut DamageVignette, With<MainCamera>>,
+) {
+    let intensity = players.single().map_or(0.0, |health| {
+        damage_vignette_intensity(&config.post_processing, health)
+    });
+    for mut vignette in &mut cameras {
+        if vignette.intensity != intensity {
+            vignette.intensity = intensity;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effect_strengths_follow_speed_and_health() {
+        let settings = PostProcessingConfig::default();
+        assert_eq!(motion_blur_shutter_angle(&settings, 0.0), 0.0);
+        assert_eq!(
+            motion_blur_shutter_angle(&settings, settings.motion_blur_full_speed * 0.5),
+            settings.motion_blur_shutter_angle * 0.5
+        );
+        assert_eq!(
+            motion_blur_shutter_angle(&settings, 1000.0),
+            settings.motion_blur_shutter_angle
+        );
+
+        let mut health = PlayerHealth::new(100.0);
+        assert_eq!(damage_vignette_intensity(&settings, &health), 0.0);
+        health.current = 50.0;
+        let hurt = damage_vignette_intensity(&settings, &health);
+        assert!((hurt - 0.5 * settings.damage_vignette).abs() < 1e-5);
+        health.since_damage = 0.0;
+        assert!(damage_vignette_intensity(&settings, &health) > hurt);
+    }
+}
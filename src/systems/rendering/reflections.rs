@@ -0,0 +1,332 @@
+//! Sky reflections on every surface and screen-space reflections on the
+//! materials that opted in.
+//!
+//! The sky probe is a small cubemap baked on the CPU from the same gradient
+//! the water shader reflects, and hung on the main camera as its
+//! `EnvironmentMapLight`, so it applies to the whole scene. It is re-baked
+//! whenever the sun has moved or dimmed noticeably, which follows the `time`
+//! console command and anything else that drives the sun.
+//!
+//! Screen-space reflections only see deferred materials, and a deferred
+//! material disappears from cameras without a deferred prepass. Materials
+//! opted in through `MaterialFactory::opt_into_reflections` are therefore only
+//! switched to deferred while the preset has SSR on (the camera side lives in
+//! `apply_post_processing`). Wet-road materials also lose roughness with the
+//! rain so that SSR and the probe pick them up.
+
+use crate::components::{MainCamera, VehicleState, VehicleType, WaterMaterial, WeatherState};
+use crate::config::GameConfig;
+use crate::factories::{MaterialFactory, ReflectionHook, ReflectiveMaterials};
+use crate::systems::lighting::{NIGHT_LUX, NOON_LUX};
+use bevy::asset::RenderAssetUsages;
+use bevy::pbr::OpaqueRendererMethod;
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+
+/// Face size of the specular cubemap; the sky is smooth, so this is plenty
+const SPECULAR_SIZE: u32 = 64;
+/// Face size of the diffuse cubemap
+const DIFFUSE_SIZE: u32 = 8;
+
+/// Smaller daylight changes keep the current bake
+const REBAKE_DAYLIGHT: f32 = 0.02;
+/// Sun directions closer than this (cosine) keep the current bake
+const REBAKE_SUN_COS: f32 = 0.9995;
+
+/// Sky probe brightness at night, relative to noon
+const NIGHT_PROBE_FLOOR: f32 = 0.05;
+
+/// Sky gradient for one sun height
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyColors {
+    pub zenith: Vec3,
+    pub horizon: Vec3,
+    pub ground: Vec3,
+    pub sun: Vec3,
+}
+
+impl SkyColors {
+    /// Linear colors for `daylight` in 0..=1, warming towards the horizon at
+    /// dawn and dusk
+    pub fn for_daylight(daylight: f32) -> Self {
+        let day = daylight.clamp(0.0, 1.0);
+        let dusk = 4.0 * day * (1.0 - day);
+        let zenith = Vec3::new(0.01, 0.015, 0.04).lerp(Vec3::new(0.25, 0.45, 0.85), day);
+        let horizon = Vec3::new(0.03, 0.035, 0.06).lerp(Vec3::new(0.7, 0.8, 0.9), day)
+            + Vec3::new(0.25, 0.1, 0.02) * dusk;
+        Self {
+            zenith,
+            horizon,
+            ground: horizon * 0.25,
+            sun: Vec3::new(1.0, 0.95, 0.85).lerp(Vec3::new(1.0, 0.6, 0.3), dusk) * day,
+        }
+    }
+
+    /// Sky gradient seen along `direction`, without the sun
+    pub fn gradient(&self, direction: Vec3) -> Vec3 {
+        if direction.y >= 0.0 {
+            self.horizon.lerp(self.zenith, direction.y.sqrt())
+        } else {
+            self.horizon
+                .lerp(self.ground, (-direction.y * 4.0).min(1.0))
+        }
+    }
+
+    /// Radiance seen along `direction` with the sun towards `sun_direction`
+    pub fn radiance(&self, direction: Vec3, sun_direction: Vec3) -> Vec3 {
+        let glare = direction.dot(sun_direction).max(0.0).powf(256.0);
+        (self.gradient(direction) + self.sun * glare).min(Vec3::ONE)
+    }
+}
+
+/// How far the sun is between night and noon, from its illuminance
+pub fn daylight(illuminance: f32) -> f32 {
+    ((illuminance - NIGHT_LUX) / (NOON_LUX - NIGHT_LUX)).clamp(0.0, 1.0)
+}
+
+/// Road roughness at `wetness` in 0..=1; never rougher than when dry
+pub fn wet_roughness(dry: f32, wet: f32, wetness: f32) -> f32 {
+    dry + (wet.min(dry) - dry) * wetness.clamp(0.0, 1.0)
+}
+
+/// World direction through a texel of a cubemap face, `u` and `v` in -1..=1.
+/// Faces follow the usual +X, -X, +Y, -Y, +Z, -Z order; Bevy samples
+/// cubemaps with Z negated, so the Z axis is flipped back here.
+fn cube_direction(face: u32, u: f32, v: f32) -> Vec3 {
+    let cube = match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    };
+    Vec3::new(cube.x, cube.y, -cube.z).normalize()
+}
+
+/// Bakes the sky into a cubemap; the diffuse bake leaves out the sun's glare
+pub fn sky_cubemap(size: u32, sky: &SkyColors, sun_direction: Option<Vec3>) -> Image {
+    let mut data = Vec::with_capacity((size * size * 6 * 4) as usize);
+    for face in 0..6 {
+        for y in 0..size {
+            for x in 0..size {
+                let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let direction = cube_direction(face, u, v);
+                let radiance = match sun_direction {
+                    Some(sun) => sky.radiance(direction, sun),
+                    None => sky.gradient(direction),
+                };
+                data.extend(
+                    Color::linear_rgb(radiance.x, radiance.y, radiance.z)
+                        .to_srgba()
+                        .to_u8_array(),
+                );
+            }
+        }
+    }
+    let mut image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..default()
+    });
+    image
+}
+
+/// Cubemaps of the camera's sky probe and what they were last baked for
+#[derive(Resource)]
+pub struct SkyProbe {
+    pub specular: Handle<Image>,
+    pub diffuse: Handle<Image>,
+    pub colors: SkyColors,
+    sun_direction: Vec3,
+    daylight: f32,
+}
+
+pub fn setup_sky_probe(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let colors = SkyColors::for_daylight(1.0);
+    commands.insert_resource(SkyProbe {
+        specular: images.add(sky_cubemap(SPECULAR_SIZE, &colors, Some(Vec3::Y))),
+        diffuse: images.add(sky_cubemap(DIFFUSE_SIZE, &colors, None)),
+        colors,
+        sun_direction: Vec3::Y,
+        // Forces a bake for the real sun on the first update
+        daylight: -1.0,
+    });
+}
+
+/// Re-bakes the probe when the sun moves, hands it to the main camera and
+/// keeps the water's reflected sky in step
+pub fn update_sky_probe(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut probe: ResMut<SkyProbe>,
+    mut images: ResMut<Assets<Image>>,
+    mut water: ResMut<Assets<WaterMaterial>>,
+    suns: Query<(&DirectionalLight, &GlobalTransform)>,
+    mut cameras: Query<(Entity, Option<&mut EnvironmentMapLight>), With<MainCamera>>,
+) {
+    let Some((sun, sun_transform)) = suns
+        .iter()
+        .max_by(|a, b| a.0.illuminance.total_cmp(&b.0.illuminance))
+    else {
+        return;
+    };
+    let day = daylight(sun.illuminance);
+    // Light travels along the sun's forward axis, so the sun sits behind it
+    let sun_direction = *sun_transform.back();
+
+    if (day - probe.daylight).abs() > REBAKE_DAYLIGHT
+        || sun_direction.dot(probe.sun_direction) < REBAKE_SUN_COS
+    {
+        let colors = SkyColors::for_daylight(day);
+        images.insert(
+            &probe.specular,
+            sky_cubemap(SPECULAR_SIZE, &colors, Some(sun_direction)),
+        );
+        images.insert(&probe.diffuse, sky_cubemap(DIFFUSE_SIZE, &colors, None));
+        for (_, material) in water.iter_mut() {
+            material.sky_zenith = colors.zenith;
+            material.sky_horizon = colors.horizon;
+        }
+        probe.colors = colors;
+        probe.sun_direction = sun_direction;
+        probe.daylight = day;
+    }
+
+    let intensity = config.post_processing.sky_reflection_intensity
+        * (NIGHT_PROBE_FLOOR + (1.0 - NIGHT_PROBE_FLOOR) * probe.daylight);
+    for (camera, environment) in &mut cameras {
+        match environment {
+            Some(mut environment) => {
+                if environment.intensity != intensity {
+                    environment.intensity = intensity;
+                }
+            }
+            None => {
+                commands.entity(camera).insert(EnvironmentMapLight {
+                    diffuse_map: probe.diffuse.clone(),
+                    specular_map: probe.specular.clone(),
+                    intensity,
+                    ..default()
+                });
+            }
+        }
+    }
+}
+
+/// Gives newly spawned cars a clear coat over their body paint. Body panels
+/// are the parts sharing the material made from the spawn color.
+pub fn opt_in_car_paint(
+    cars: Query<(Entity, &VehicleState), Added<VehicleState>>,
+    children: Query<&Children>,
+    parts: Query<&MeshMaterial3d<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut reflective: ResMut<ReflectiveMaterials>,
+) {
+    for (car, state) in &cars {
+        if state.vehicle_type != VehicleType::SuperCar {
+            continue;
+        }
+        let mut paint = None;
+        for part in children.iter_descendants(car) {
+            if let Ok(material) = parts.get(part)
+                && materials
+                    .get(&material.0)
+                    .is_some_and(|m| m.base_color == state.color)
+            {
+                paint = Some(material.0.clone());
+                break;
+            }
+        }
+        if let Some(paint) = paint {
+            MaterialFactory::opt_into_reflections(
+                &mut materials,
+                &mut reflective,
+                &paint,
+                ReflectionHook::ClearCoat,
+            );
+        }
+    }
+}
+
+/// Renders opted-in materials deferred while SSR is on and wets the roads
+/// with the rain
+pub fn update_reflective_materials(
+    config: Res<GameConfig>,
+    weather: Option<Res<WeatherState>>,
+    mut reflective: ResMut<ReflectiveMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut applied: Local<Option<(bool, f32)>>,
+) {
+    let ssr = config.graphics.quality.settings().screen_space_reflections;
+    // Quantized so a slowly changing downpour doesn't touch materials every frame
+    let wetness = weather.map_or(0.0, |weather| {
+        (weather.params.rain_intensity.clamp(0.0, 1.0) * 20.0).round() / 20.0
+    });
+    if *applied == Some((ssr, wetness)) && !reflective.is_changed() {
+        return;
+    }
+    *applied = Some((ssr, wetness));
+
+    let reflective = reflective.bypass_change_detection();
+    reflective.retain_existing(&materials);
+    let method = if ssr {
+        OpaqueRendererMethod::Deferred
+    } else {
+        OpaqueRendererMethod::Forward
+    };
+    for (id, hook) in reflective.iter() {
+        let Some(material) = materials.get_mut(id) else {
+            continue;
+        };
+        material.opaque_render_method = method;
+        if let ReflectionHook::Wet { dry_roughness } = hook {
+            material.perceptual_roughness = wet_roughness(
+                dry_roughness,
+                config.post_processing.wet_road_roughness,
+                wetness,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sky_brightens_with_daylight_and_cube_faces_point_right() {
+        let noon = SkyColors::for_daylight(1.0);
+        let night = SkyColors::for_daylight(daylight(NIGHT_LUX));
+        let up = noon.radiance(Vec3::Y, Vec3::X);
+        assert!(up.length() > night.radiance(Vec3::Y, Vec3::X).length());
+        assert!(up.length() > noon.radiance(Vec3::NEG_Y, Vec3::X).length());
+        assert_eq!(noon.radiance(Vec3::X, Vec3::X), Vec3::ONE);
+
+        assert_eq!(cube_direction(2, 0.0, 0.0), Vec3::Y);
+        assert_eq!(cube_direction(0, 0.0, 0.0), Vec3::X);
+        // +Z face is sampled for world -Z
+        assert_eq!(cube_direction(4, 0.0, 0.0), Vec3::NEG_Z);
+    }
+
+    #[test]
+    fn test_roads_get_glossier_in_rain_but_never_rougher() {
+        assert_eq!(wet_roughness(0.75, 0.125, 0.0), 0.75);
+        assert_eq!(wet_roughness(0.75, 0.125, 1.0), 0.125);
+        assert_eq!(wet_roughness(0.75, 0.25, 0.5), 0.5);
+        assert_eq!(wet_roughness(0.1, 0.15, 1.0), 0.1);
+    }
+}
@@ -0,0 +1,211 @@
+//! Keeps GPU memory for meshes and textures under a budget.
+//!
+//! Building clusters far enough out are drawn by their HLOD proxy, so their
+//! own meshes, materials and textures sit on the GPU unused. While the
+//! resident total in `GpuUploadQueue` is over `gpu_residency_budget_kb`, the
+//! farthest such clusters give theirs up: each asset only its building holds
+//! is taken out of its `Assets` collection, which frees the GPU copy, and
+//! parked on the building as `NonResident`. Once the cluster comes within
+//! `RESTORE_MARGIN` of swapping back, the assets return under their old
+//! handles and go through the upload budget like anything new.
+
+use crate::components::{Building, HlodClusters, MainCamera, NonResident};
+use crate::config::GameConfig;
+use crate::systems::performance::memory::mesh_bytes;
+use crate::systems::rendering::upload_queue::GpuUploadQueue;
+use crate::systems::world::chunk_streaming::StreamingFocus;
+use bevy::prelude::*;
+use std::sync::Arc;
+
+/// Clusters this far inside the proxy distance get their assets back, so the
+/// upload is done before they swap
+const RESTORE_MARGIN: f32 = 150.0;
+/// Only clusters this far past the proxy distance give their assets up
+const EVICT_MARGIN: f32 = 300.0;
+/// Eviction stops once resident memory is down to this share of the budget
+const EVICT_TARGET: f32 = 0.9;
+/// Buildings evicted or restored per frame at most
+const BUILDINGS_PER_FRAME: usize = 128;
+
+/// Whether nothing but this handle keeps the asset alive
+fn sole_owner<A: Asset>(handle: &Handle<A>) -> bool {
+    matches!(handle, Handle::Strong(strong) if Arc::strong_count(strong) == 1)
+}
+
+fn texture_slots(material: &StandardMaterial) -> impl Iterator<Item = &Handle<Image>> {
+    [
+        &material.base_color_texture,
+        &material.emissive_texture,
+        &material.metallic_roughness_texture,
+        &material.normal_map_texture,
+        &material.occlusion_texture,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Bytes over the budget to free; zero when under it or without a budget
+pub fn bytes_to_evict(resident: usize, budget: usize) -> usize {
+    if budget == 0 || resident <= budget {
+        return 0;
+    }
+    resident - (budget as f32 * EVICT_TARGET) as usize
+}
+
+/// Camera position and where it will be shortly, as the HLOD swap uses them
+fn viewpoints(camera: &GlobalTransform, focus: &StreamingFocus, config: &GameConfig) -> [Vec3; 2] {
+    let camera = camera.translation();
+    let ahead = camera + focus.velocity * config.world_streaming.lod_lookahead.max(0.0);
+    [camera, ahead]
+}
+
+fn proxy_distance(config: &GameConfig) -> f32 {
+    config.performance.hlod_distance * config.graphics.quality.settings().lod_bias
+}
+
+/// Takes the assets of far clusters' buildings off the GPU, farthest first
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn evict_far_clusters(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    queue: Res<GpuUploadQueue>,
+    clusters: Res<HlodClusters>,
+    focus: Res<StreamingFocus>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    buildings: Query<
+        (&Mesh3d, &MeshMaterial3d<StandardMaterial>),
+        (With<Building>, Without<NonResident>),
+    >,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let budget = config.performance.gpu_residency_budget_kb * 1024;
+    let mut to_free = bytes_to_evict(queue.resident_bytes(), budget);
+    if to_free == 0 {
+        return;
+    }
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let [camera, ahead] = viewpoints(camera, &focus, &config);
+    let evict_distance = proxy_distance(&config) + EVICT_MARGIN;
+
+    let mut far: Vec<(f32, &Vec<Entity>)> = clusters
+        .clusters
+        .values()
+        .filter(|cluster| cluster.using_proxy)
+        .map(|cluster| {
+            let distance = cluster.distance_to(camera).min(cluster.distance_to(ahead));
+            (distance, &cluster.members)
+        })
+        .filter(|(distance, _)| *distance > evict_distance)
+        .collect();
+    far.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut evicted = 0;
+    for member in far.into_iter().flat_map(|(_, members)| members) {
+        if to_free == 0 || evicted == BUILDINGS_PER_FRAME {
+            break;
+        }
+        let Ok((mesh, material)) = buildings.get(*member) else {
+            continue;
+        };
+        let mut parked = NonResident::default();
+        let mut freed = 0;
+        if sole_owner(&mesh.0) {
+            parked.mesh = meshes.remove(&mesh.0);
+            freed += parked.mesh.as_ref().map_or(0, mesh_bytes);
+        }
+        if sole_owner(&material.0)
+            && let Some(material) = materials.remove(&material.0)
+        {
+            for texture in texture_slots(&material) {
+                if sole_owner(texture)
+                    && let Some(image) = images.remove(texture)
+                {
+                    freed += image.data.as_ref().map_or(0, Vec::len);
+                    parked.textures.push((texture.id(), image));
+                }
+            }
+            parked.material = Some(material);
+        }
+        // Marked even when everything was shared, so it isn't tried again
+        commands.entity(*member).insert(parked);
+        to_free = to_free.saturating_sub(freed);
+        evicted += 1;
+    }
+}
+
+/// Gives buildings their assets back as their cluster comes close to
+/// swapping in
+#[allow(clippy::too_many_arguments)]
+pub fn restore_near_clusters(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    clusters: Res<HlodClusters>,
+    focus: Res<StreamingFocus>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut buildings: Query<(&Mesh3d, &MeshMaterial3d<StandardMaterial>, &mut NonResident)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if buildings.is_empty() {
+        return;
+    }
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let [camera, ahead] = viewpoints(camera, &focus, &config);
+    let restore_distance = proxy_distance(&config) + RESTORE_MARGIN;
+
+    let near = clusters.clusters.values().filter(|cluster| {
+        !cluster.using_proxy
+            || cluster.distance_to(camera).min(cluster.distance_to(ahead)) < restore_distance
+    });
+    let mut restored = 0;
+    for member in near.flat_map(|cluster| &cluster.members) {
+        if restored == BUILDINGS_PER_FRAME {
+            break;
+        }
+        let Ok((mesh, material, mut parked)) = buildings.get_mut(*member) else {
+            continue;
+        };
+        let parked = std::mem::take(&mut *parked);
+        if let Some(asset) = parked.mesh {
+            meshes.insert(&mesh.0, asset);
+        }
+        for (texture, image) in parked.textures {
+            images.insert(texture, image);
+        }
+        if let Some(asset) = parked.material {
+            materials.insert(&material.0, asset);
+        }
+        commands.entity(*member).remove::<NonResident>();
+        restored += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction_frees_down_below_the_budget() {
+        assert_eq!(bytes_to_evict(500, 1000), 0);
+        assert_eq!(bytes_to_evict(1000, 1000), 0);
+        assert_eq!(bytes_to_evict(1500, 1000), 600);
+        // No budget, no eviction
+        assert_eq!(bytes_to_evict(usize::MAX, 0), 0);
+
+        let mut materials = Assets::<StandardMaterial>::default();
+        let handle = materials.add(StandardMaterial::default());
+        assert!(sole_owner(&handle));
+        let shared = handle.clone();
+        assert!(!sole_owner(&handle));
+        drop(shared);
+        assert!(sole_owner(&handle));
+        assert!(!sole_owner(&Handle::<Mesh>::default()));
+    }
+}
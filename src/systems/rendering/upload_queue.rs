@@ -0,0 +1,186 @@
+//! Spreads mesh and texture uploads over frames.
+//!
+//! Streaming a block of buildings in adds hundreds of meshes in one frame, and
+//! the render world would copy every one of them to the GPU before drawing it.
+//! Bevy can hold uploads back to a byte budget per frame
+//! (`RenderAssetBytesPerFrame`); `configure_upload_budget` derives that budget
+//! from `upload_budget_ms` and the assumed throughput `upload_bytes_per_ms`,
+//! so the rest wait a frame or two instead of stalling this one.
+//!
+//! `GpuUploadQueue` mirrors that on the main world side: it sees every mesh
+//! and image as it is added or changed, queues it until the budget has let it
+//! through, and keeps the size of everything resident for the residency
+//! manager and the memory overlay.
+
+use crate::config::{GameConfig, PerformanceConfig};
+use crate::systems::performance::memory::mesh_bytes;
+use bevy::asset::UntypedAssetId;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetBytesPerFrame;
+use std::collections::{HashMap, VecDeque};
+
+/// Uploads waiting for the per-frame budget and what is already on the GPU
+#[derive(Resource, Debug, Default)]
+pub struct GpuUploadQueue {
+    pending: VecDeque<(UntypedAssetId, usize)>,
+    pending_bytes: usize,
+    resident: HashMap<UntypedAssetId, usize>,
+    resident_bytes: usize,
+    /// Uploads let through last frame
+    pub uploaded_last_frame: usize,
+}
+
+impl GpuUploadQueue {
+    /// Queues a new or changed asset of `bytes`
+    pub fn push(&mut self, id: impl Into<UntypedAssetId>, bytes: usize) {
+        let id = id.into();
+        if let Some(old) = self.resident.insert(id, bytes) {
+            self.resident_bytes -= old;
+        }
+        self.resident_bytes += bytes;
+        self.pending.push_back((id, bytes));
+        self.pending_bytes += bytes;
+    }
+
+    /// Forgets an asset that was removed; a pending upload of it is dropped
+    pub fn remove(&mut self, id: impl Into<UntypedAssetId>) {
+        let id = id.into();
+        if let Some(bytes) = self.resident.remove(&id) {
+            self.resident_bytes -= bytes;
+        }
+        let pending_bytes = &mut self.pending_bytes;
+        self.pending.retain(|&(pending, bytes)| {
+            let keep = pending != id;
+            if !keep {
+                *pending_bytes -= bytes;
+            }
+            keep
+        });
+    }
+
+    /// Lets a frame's worth of uploads through, the way the render world
+    /// does: whole assets in order, the first always fits and the last may
+    /// overshoot. `None` lets everything through.
+    pub fn drain(&mut self, budget_bytes: Option<usize>) -> usize {
+        let mut written = 0;
+        let mut count = 0;
+        while let Some(&(_, bytes)) = self.pending.front() {
+            if budget_bytes.is_some_and(|budget| written >= budget) {
+                break;
+            }
+            self.pending.pop_front();
+            self.pending_bytes -= bytes;
+            written += bytes;
+            count += 1;
+        }
+        self.uploaded_last_frame = count;
+        count
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn pending_bytes(&self) -> usize {
+        self.pending_bytes
+    }
+
+    /// Meshes and textures on the GPU or on their way there
+    pub fn resident_bytes(&self) -> usize {
+        self.resident_bytes
+    }
+}
+
+/// Bytes the render world may upload per frame; `None` when unlimited
+pub fn upload_budget_bytes(performance: &PerformanceConfig) -> Option<usize> {
+    (performance.upload_budget_ms > 0.0)
+        .then_some((performance.upload_budget_ms * performance.upload_bytes_per_ms as f32) as usize)
+}
+
+pub fn configure_upload_budget(
+    config: Res<GameConfig>,
+    mut bytes_per_frame: ResMut<RenderAssetBytesPerFrame>,
+) {
+    let max_bytes = upload_budget_bytes(&config.performance);
+    if bytes_per_frame.max_bytes != max_bytes {
+        bytes_per_frame.max_bytes = max_bytes;
+    }
+}
+
+/// Queues meshes and images as they are added and lets the budget through
+pub fn track_gpu_uploads(
+    config: Res<GameConfig>,
+    mut queue: ResMut<GpuUploadQueue>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    meshes: Res<Assets<Mesh>>,
+    images: Res<Assets<Image>>,
+) {
+    for event in mesh_events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                if let Some(mesh) = meshes.get(id) {
+                    queue.push(id, mesh_bytes(mesh));
+                }
+            }
+            AssetEvent::Removed { id } => queue.remove(id),
+            _ => {}
+        }
+    }
+    for event in image_events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                if let Some(image) = images.get(id) {
+                    queue.push(id, image.data.as_ref().map_or(0, Vec::len));
+                }
+            }
+            AssetEvent::Removed { id } => queue.remove(id),
+            _ => {}
+        }
+    }
+    queue.drain(upload_budget_bytes(&config.performance));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mesh_id(index: u32) -> AssetId<Mesh> {
+        AssetId::Uuid {
+            uuid: bevy::asset::uuid::Uuid::from_u128(index as u128),
+        }
+    }
+
+    #[test]
+    fn test_drain_lets_whole_assets_through_within_budget() {
+        let mut queue = GpuUploadQueue::default();
+        for index in 0..4 {
+            queue.push(mesh_id(index), 600);
+        }
+        assert_eq!(queue.pending_bytes(), 2400);
+
+        // 600 is under 1000, so a second one goes and overshoots
+        assert_eq!(queue.drain(Some(1000)), 2);
+        assert_eq!(queue.pending_len(), 2);
+        // One asset always gets through, however small the budget
+        queue.push(mesh_id(9), 5000);
+        assert_eq!(queue.drain(Some(100)), 1);
+        assert_eq!(queue.drain(None), 2);
+        assert_eq!(queue.pending_bytes(), 0);
+        assert_eq!(queue.resident_bytes(), 2400 + 5000);
+    }
+
+    #[test]
+    fn test_changed_and_removed_assets_keep_sizes_straight() {
+        let mut queue = GpuUploadQueue::default();
+        queue.push(mesh_id(0), 100);
+        queue.push(mesh_id(1), 200);
+        queue.push(mesh_id(0), 300);
+        assert_eq!(queue.resident_bytes(), 500);
+
+        queue.remove(mesh_id(0));
+        assert_eq!(queue.resident_bytes(), 200);
+        assert_eq!(queue.pending_len(), 1);
+        assert_eq!(queue.pending_bytes(), 200);
+    }
+}
@@ -0,0 +1,582 @@
+//! Rhai scripts for mission and event logic (`scripting` feature).
+//!
+//! Every `*.rhai` file in `assets/prefabs` is compiled at startup and its
+//! top-level statements run once. A script can then define any of these hooks:
+//!
+//! - `on_update(dt)`: every frame while in game
+//! - `on_mission_started(id)`, `on_mission_completed(id)`, `on_mission_failed(id)`
+//! - `on_objective_completed(id, index)`
+//! - `on_cutscene_finished(id, skipped)`
+//! - `on_gameplay_event(name, details)`: each entry recorded in
+//!   `GameplayEventHistory`, e.g. `"zone_entered"` with `details.district`
+//!
+//! Scripts only reach the game through the functions registered here:
+//! `spawn_prefab(name, x, y, z)`, `set_objective(text)`,
+//! `player_position()` (a map with `x`, `y` and `z`), `play_sound(path)`,
+//! `show_text(text, seconds)` and `play_cutscene(id)`. Calls that change the world are queued as
+//! `ScriptAction`s and applied by `apply_script_actions` after the hooks run.
+//! Operation and nesting limits keep a runaway script from hanging the frame;
+//! a script that errors is switched off until its file changes. Files are
+//! watched and recompiled on save.
+
+use crate::components::ActiveEntity;
+use crate::components::cutscene::{CutsceneFinished, PlayCutscene};
+use crate::components::gameplay_events::{GameplayEvent, GameplayEventHistory};
+use crate::components::mission::{
+    MissionCompleted, MissionFailed, MissionStarted, ObjectiveCompleted,
+};
+use crate::factories::{PrefabFactory, PrefabOverrides, PrefabRegistry};
+use bevy::prelude::*;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rhai::{AST, CallFnOptions, Dynamic, Engine, FLOAT, FuncArgs, Map, Scope};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+const SCRIPT_EXTENSION: &str = "rhai";
+/// Operations one hook call may run before it is aborted
+const MAX_OPERATIONS: u64 = 200_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING_SIZE: usize = 16 * 1024;
+const MAX_COLLECTION_SIZE: usize = 4096;
+
+/// Something a script asked the game to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptAction {
+    SpawnPrefab { name: String, position: Vec3 },
+    SetObjective(String),
+    ShowText { text: String, seconds: f32 },
+    PlaySound(String),
+    PlayCutscene(String),
+}
+
+/// Game state scripts can read, and the actions they have queued
+#[derive(Debug, Default)]
+struct ScriptBridge {
+    player_position: Vec3,
+    actions: Vec<ScriptAction>,
+}
+
+fn lock(bridge: &Mutex<ScriptBridge>) -> MutexGuard<'_, ScriptBridge> {
+    bridge
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Engine with the limits and the game API registered
+fn build_engine(bridge: &Arc<Mutex<ScriptBridge>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE)
+        .on_print(|text| info!("📜 {text}"))
+        .on_debug(|text, source, position| {
+            debug!("📜 {} {position}: {text}", source.unwrap_or("script"))
+        });
+
+    let shared = bridge.clone();
+    engine.register_fn(
+        "spawn_prefab",
+        move |name: &str, x: FLOAT, y: FLOAT, z: FLOAT| {
+            lock(&shared).actions.push(ScriptAction::SpawnPrefab {
+                name: name.to_string(),
+                position: Vec3::new(x as f32, y as f32, z as f32),
+            });
+        },
+    );
+    let shared = bridge.clone();
+    engine.register_fn("set_objective", move |text: &str| {
+        lock(&shared)
+            .actions
+            .push(ScriptAction::SetObjective(text.to_string()));
+    });
+    let shared = bridge.clone();
+    engine.register_fn("show_text", move |text: &str, seconds: FLOAT| {
+        lock(&shared).actions.push(ScriptAction::ShowText {
+            text: text.to_string(),
+            seconds: seconds as f32,
+        });
+    });
+    let shared = bridge.clone();
+    engine.register_fn("play_sound", move |path: &str| {
+        lock(&shared)
+            .actions
+            .push(ScriptAction::PlaySound(path.to_string()));
+    });
+    let shared = bridge.clone();
+    engine.register_fn("play_cutscene", move |id: &str| {
+        lock(&shared)
+            .actions
+            .push(ScriptAction::PlayCutscene(id.to_string()));
+    });
+    let shared = bridge.clone();
+    engine.register_fn("player_position", move || {
+        let position = lock(&shared).player_position;
+        let mut map = Map::new();
+        map.insert("x".into(), Dynamic::from_float(position.x as FLOAT));
+        map.insert("y".into(), Dynamic::from_float(position.y as FLOAT));
+        map.insert("z".into(), Dynamic::from_float(position.z as FLOAT));
+        map
+    });
+    engine
+}
+
+/// A compiled script and the variables its top level declared
+struct LoadedScript {
+    path: PathBuf,
+    ast: AST,
+    scope: Scope<'static>,
+    /// Set after an error; cleared when the file is reloaded
+    failed: bool,
+}
+
+impl LoadedScript {
+    fn has_hook(&self, name: &str, arity: usize) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|function| function.name == name && function.params.len() == arity)
+    }
+}
+
+/// Compiled scripts and the engine that runs them
+#[derive(Resource)]
+pub struct ScriptHost {
+    engine: Engine,
+    bridge: Arc<Mutex<ScriptBridge>>,
+    scripts: Vec<LoadedScript>,
+}
+
+impl Default for ScriptHost {
+    fn default() -> Self {
+        let bridge = Arc::new(Mutex::new(ScriptBridge::default()));
+        Self {
+            engine: build_engine(&bridge),
+            bridge,
+            scripts: Vec::new(),
+        }
+    }
+}
+
+impl ScriptHost {
+    /// Compiles `source` and runs its top level, replacing any script already
+    /// loaded from `path`
+    pub fn load(&mut self, path: &Path, source: &str) -> Result<(), String> {
+        self.scripts.retain(|script| script.path != path);
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut scope = Scope::new();
+        self.engine
+            .run_ast_with_scope(&mut scope, &ast)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        self.scripts.push(LoadedScript {
+            path: path.to_path_buf(),
+            ast,
+            scope,
+            failed: false,
+        });
+        Ok(())
+    }
+
+    /// Loads every script in `dir`, returning how many loaded
+    pub fn load_directory(&mut self, dir: &Path) -> usize {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return 0;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| is_script(path))
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        for path in paths {
+            let result = std::fs::read_to_string(&path)
+                .map_err(|e| format!("{}: {e}", path.display()))
+                .and_then(|source| self.load(&path, &source));
+            match result {
+                Ok(()) => loaded += 1,
+                Err(e) => warn!("⚠️ Script failed to load: {e}"),
+            }
+        }
+        loaded
+    }
+
+    pub fn len(&self) -> usize {
+        self.scripts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    pub fn set_player_position(&self, position: Vec3) {
+        lock(&self.bridge).player_position = position;
+    }
+
+    /// Calls `hook` on every script that defines it with as many parameters
+    /// as `args` has
+    pub fn call_hook(&mut self, hook: &str, args: impl FuncArgs + Clone) {
+        let arity = {
+            let mut values = Vec::new();
+            args.clone().parse(&mut values);
+            values.len()
+        };
+        for script in &mut self.scripts {
+            if script.failed || !script.has_hook(hook, arity) {
+                continue;
+            }
+            let options = CallFnOptions::new().eval_ast(false).rewind_scope(false);
+            if let Err(e) = self.engine.call_fn_with_options::<Dynamic>(
+                options,
+                &mut script.scope,
+                &script.ast,
+                hook,
+                args.clone(),
+            ) {
+                warn!("⚠️ Script {} stopped in {hook}: {e}", script.path.display());
+                script.failed = true;
+            }
+        }
+    }
+
+    /// Actions queued since the last call
+    pub fn take_actions(&self) -> Vec<ScriptAction> {
+        std::mem::take(&mut lock(&self.bridge).actions)
+    }
+}
+
+fn is_script(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == SCRIPT_EXTENSION)
+}
+
+fn script_directory() -> PathBuf {
+    PathBuf::from(crate::util::asset_path::get_assets_base_path()).join("prefabs")
+}
+
+/// File watcher on the script directory
+#[derive(Resource)]
+pub struct ScriptWatcher {
+    events: Mutex<Receiver<notify::Result<notify::Event>>>,
+    // Dropping the watcher stops the events
+    _watcher: RecommendedWatcher,
+}
+
+impl ScriptWatcher {
+    fn watch(dir: &Path) -> notify::Result<Self> {
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            events: Mutex::new(events),
+            _watcher: watcher,
+        })
+    }
+
+    /// Drains pending events, returning the scripts that changed
+    fn changed_scripts(&self) -> Vec<PathBuf> {
+        let Ok(events) = self.events.lock() else {
+            return Vec::new();
+        };
+        let mut changed: Vec<PathBuf> = Vec::new();
+        for event in events.try_iter() {
+            match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    changed.extend(event.paths.into_iter().filter(|path| is_script(path)));
+                }
+                Ok(_) => {}
+                Err(e) => warn!("⚠️ Script watcher error: {}", e),
+            }
+        }
+        changed.sort();
+        changed.dedup();
+        changed
+    }
+}
+
+/// Text shown by `set_objective` and `show_text`
+#[derive(Resource, Debug, Default)]
+pub struct ScriptHud {
+    pub objective: String,
+    pub message: Option<(String, Timer)>,
+}
+
+#[derive(Component)]
+pub struct ScriptHudText;
+
+pub fn load_scripts(mut commands: Commands) {
+    let dir = script_directory();
+    let mut host = ScriptHost::default();
+    let loaded = host.load_directory(&dir);
+    info!("📜 Loaded {} script(s) from {}", loaded, dir.display());
+    commands.insert_resource(host);
+
+    match ScriptWatcher::watch(&dir) {
+        Ok(watcher) => commands.insert_resource(watcher),
+        Err(e) => warn!("⚠️ Script hot reload disabled: {}", e),
+    }
+}
+
+pub fn reload_changed_scripts(watcher: Option<Res<ScriptWatcher>>, mut host: ResMut<ScriptHost>) {
+    let Some(watcher) = watcher else {
+        return;
+    };
+    for path in watcher.changed_scripts() {
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            // Deleted or renamed away
+            Err(_) => {
+                host.scripts.retain(|script| script.path != path);
+                continue;
+            }
+        };
+        match host.load(&path, &source) {
+            Ok(()) => info!("🔥 Reloaded script {}", path.display()),
+            Err(e) => warn!("⚠️ Script failed to reload, it stays off: {e}"),
+        }
+    }
+}
+
+/// Fields of a gameplay event as a script map
+fn gameplay_event_details(event: &GameplayEvent) -> Map {
+    let mut details = Map::new();
+    let mut position = |position: Vec3| {
+        details.insert("x".into(), Dynamic::from_float(position.x as FLOAT));
+        details.insert("y".into(), Dynamic::from_float(position.y as FLOAT));
+        details.insert("z".into(), Dynamic::from_float(position.z as FLOAT));
+    };
+    match event {
+        GameplayEvent::VehicleDestroyed(e) => position(e.position),
+        GameplayEvent::NpcKilled(e) => position(e.position),
+        _ => {}
+    }
+    match event {
+        GameplayEvent::VehicleEntered(e) => {
+            details.insert(
+                "vehicle_type".into(),
+                format!("{:?}", e.vehicle_type).into(),
+            );
+        }
+        GameplayEvent::VehicleDestroyed(e) => {
+            details.insert(
+                "vehicle_type".into(),
+                format!("{:?}", e.vehicle_type).into(),
+            );
+        }
+        GameplayEvent::NpcKilled(_) => {}
+        GameplayEvent::ZoneEntered(e) => {
+            details.insert("district".into(), format!("{:?}", e.district).into());
+            let previous = e
+                .previous
+                .map_or(Dynamic::UNIT, |previous| format!("{previous:?}").into());
+            details.insert("previous".into(), previous);
+        }
+        GameplayEvent::MoneyChanged(e) => {
+            details.insert("previous".into(), (e.previous as rhai::INT).into());
+            details.insert("current".into(), (e.current as rhai::INT).into());
+            details.insert("delta".into(), (e.delta() as rhai::INT).into());
+        }
+    }
+    details
+}
+
+/// Feeds the player position, mission events and recorded gameplay events to
+/// the scripts, then `on_update`
+#[allow(clippy::too_many_arguments)]
+pub fn run_script_hooks(
+    time: Res<Time>,
+    mut host: ResMut<ScriptHost>,
+    active: Query<&GlobalTransform, With<ActiveEntity>>,
+    mut started: EventReader<MissionStarted>,
+    mut objectives: EventReader<ObjectiveCompleted>,
+    mut completed: EventReader<MissionCompleted>,
+    mut failed: EventReader<MissionFailed>,
+    mut cutscenes: EventReader<CutsceneFinished>,
+    history: Option<Res<GameplayEventHistory>>,
+    mut seen: Local<Option<u64>>,
+) {
+    if host.is_empty() {
+        return;
+    }
+    if let Ok(transform) = active.single() {
+        host.set_player_position(transform.translation());
+    }
+
+    for event in started.read() {
+        host.call_hook("on_mission_started", (event.mission_id.clone(),));
+    }
+    for event in objectives.read() {
+        host.call_hook(
+            "on_objective_completed",
+            (event.mission_id.clone(), event.objective_index as rhai::INT),
+        );
+    }
+    for event in completed.read() {
+        host.call_hook("on_mission_completed", (event.mission_id.clone(),));
+    }
+    for event in failed.read() {
+        host.call_hook("on_mission_failed", (event.mission_id.clone(),));
+    }
+    for event in cutscenes.read() {
+        host.call_hook(
+            "on_cutscene_finished",
+            (event.cutscene_id.clone(), event.skipped),
+        );
+    }
+    if let Some(history) = history {
+        // Scripts only hear about events from after they started
+        let from = seen.unwrap_or(history.next_sequence());
+        for recorded in history.since(from) {
+            host.call_hook(
+                "on_gameplay_event",
+                (
+                    recorded.event.name().to_string(),
+                    gameplay_event_details(&recorded.event),
+                ),
+            );
+        }
+        *seen = Some(history.next_sequence());
+    }
+    host.call_hook("on_update", (time.delta_secs() as FLOAT,));
+}
+
+pub fn apply_script_actions(
+    mut commands: Commands,
+    host: Res<ScriptHost>,
+    registry: Res<PrefabRegistry>,
+    asset_server: Res<AssetServer>,
+    mut hud: ResMut<ScriptHud>,
+    mut cutscenes: EventWriter<PlayCutscene>,
+) {
+    for action in host.take_actions() {
+        match action {
+            ScriptAction::SpawnPrefab { name, position } => {
+                let overrides = PrefabOverrides::at(position);
+                if let Err(e) =
+                    PrefabFactory::spawn_by_name(&mut commands, &registry, &name, overrides)
+                {
+                    warn!("⚠️ Script could not spawn '{}': {}", name, e);
+                }
+            }
+            ScriptAction::SetObjective(text) => hud.objective = text,
+            ScriptAction::ShowText { text, seconds } => {
+                hud.message = Some((text, Timer::from_seconds(seconds.max(0.1), TimerMode::Once)));
+            }
+            ScriptAction::PlaySound(path) => {
+                commands.spawn((
+                    AudioPlayer::new(asset_server.load::<AudioSource>(path)),
+                    PlaybackSettings::DESPAWN,
+                ));
+            }
+            ScriptAction::PlayCutscene(id) => {
+                cutscenes.write(PlayCutscene { cutscene_id: id });
+            }
+        }
+    }
+}
+
+pub fn setup_script_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.95, 1.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(96.0),
+            left: Val::Percent(30.0),
+            width: Val::Percent(40.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        ScriptHudText,
+    ));
+}
+
+pub fn update_script_hud(
+    time: Res<Time>,
+    mut hud: ResMut<ScriptHud>,
+    mut texts: Query<&mut Text, With<ScriptHudText>>,
+) {
+    if let Some((_, timer)) = &mut hud.message
+        && timer.tick(time.delta()).finished()
+    {
+        hud.message = None;
+    }
+    let Ok(mut text) = texts.single_mut() else {
+        return;
+    };
+    let shown = match &hud.message {
+        Some((message, _)) if hud.objective.is_empty() => message.clone(),
+        Some((message, _)) => format!("{}\n{message}", hud.objective),
+        None => hud.objective.clone(),
+    };
+    if text.0 != shown {
+        text.0 = shown;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_queue_actions_and_keep_state() {
+        let mut host = ScriptHost::default();
+        host.load(
+            Path::new("test.rhai"),
+            r#"
+                let started = 0;
+                fn on_mission_started(id) {
+                    started += 1;
+                    set_objective("Mission " + id + " #" + started);
+                    let p = player_position();
+                    spawn_prefab("traffic_cone", p.x + 2.0, p.y, p.z);
+                }
+                fn on_mission_failed(id) { play_sound("audio/fail.ogg"); show_text("Wasted", 3.0); }
+            "#,
+        )
+        .unwrap();
+        host.set_player_position(Vec3::new(1.0, 2.0, 3.0));
+        host.call_hook("on_mission_started", ("intro".to_string(),));
+        host.call_hook("on_mission_started", ("intro".to_string(),));
+        // Not defined by the script
+        host.call_hook("on_update", (0.016 as FLOAT,));
+
+        let actions = host.take_actions();
+        assert_eq!(actions.len(), 4);
+        assert_eq!(
+            actions[2],
+            ScriptAction::SetObjective("Mission intro #2".into())
+        );
+        assert_eq!(
+            actions[3],
+            ScriptAction::SpawnPrefab {
+                name: "traffic_cone".into(),
+                position: Vec3::new(3.0, 2.0, 3.0),
+            }
+        );
+        host.call_hook("on_mission_failed", ("intro".to_string(),));
+        assert_eq!(host.take_actions().len(), 2);
+    }
+
+    #[test]
+    fn test_runaway_script_is_stopped() {
+        let mut host = ScriptHost::default();
+        host.load(Path::new("loop.rhai"), "fn on_update(dt) { loop { } }")
+            .unwrap();
+        host.call_hook("on_update", (0.016 as FLOAT,));
+        assert!(host.scripts[0].failed);
+        assert!(host.load(Path::new("bad.rhai"), "fn (").is_err());
+        assert_eq!(host.len(), 1);
+    }
+}
@@ -0,0 +1,473 @@
+//! Shops, properties for sale and what the player buys from them.
+//!
+//! Storefronts are added to commercial buildings by the building generator;
+//! properties are placed from `properties.ron`. Both are `Interactable`s, so
+//! standing at one shows its prompt and F uses it: a shop opens its buy menu,
+//! listing its catalog from `shops.ron`, and a property is bought with the
+//! `Wallet`. Owned safehouses save the game on F and owned garage properties
+//! work as garages. Menus can
+//! also be opened through `OpenShopRequest`, e.g. by dialogue choices.
+
+use crate::components::{
+    ActiveEntity, Garage, GarageLocation, GarageVehicle, Interactable, InteractionEvent,
+    InteractionKind, MAX_GARAGE_VEHICLES, OpenShopRequest, OwnedProperties, PAINT_COLORS, Player,
+    PlayerOutfit, PlayerOwned, PlayerTorso, Property, PropertyKind, Shop, ShopBuyButton,
+    ShopCatalogs, ShopGood, ShopMenu, Wallet,
+};
+use crate::config::GameConfig;
+use crate::factories::VehicleFactory;
+use crate::resources::Localization;
+use crate::systems::garage::{HOVERED_BUTTON, NORMAL_BUTTON, spawn_menu_button};
+use crate::systems::persistence::SaveGameRequest;
+use crate::t;
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+/// Torso color of the player model before any outfit is bought
+const DEFAULT_OUTFIT: [f32; 4] = [0.2, 0.4, 0.8, 1.0];
+
+/// Vehicles bought at a dealer are delivered this far in front of the counter
+const DELIVERY_DISTANCE: f32 = 6.0;
+
+/// Open menus close once the player is this many use radii from their anchor
+const MENU_CLOSE_RADII: f32 = 2.0;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PropertyDefinition {
+    pub id: String,
+    pub name: String,
+    pub position: Vec3,
+    pub price: u32,
+    pub radius: f32,
+    pub kind: PropertyKind,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PropertyList {
+    pub properties: Vec<PropertyDefinition>,
+}
+
+pub(crate) fn load_config<T: DeserializeOwned + Default>(filename: &str) -> T {
+    let path = format!(
+        "{}/config/{filename}",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+            error!("Failed to parse '{}': {}", path, e);
+            T::default()
+        }),
+        Err(e) => {
+            info!("ℹ️ No {} found: {}", filename, e);
+            T::default()
+        }
+    }
+}
+
+pub fn load_shop_catalogs(mut commands: Commands) {
+    commands.insert_resource(load_config::<ShopCatalogs>("shops.ron"));
+}
+
+pub fn spawn_properties(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let list = load_config::<PropertyList>("properties.ron");
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(1.0, 0.8, 0.2, 0.6),
+        emissive: LinearRgba::rgb(0.6, 0.4, 0.05),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    for property in list.properties {
+        commands.spawn((
+            Name::new(property.name.clone()),
+            Mesh3d(meshes.add(Cylinder::new(property.radius, 0.1))),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(property.position),
+            Property {
+                id: property.id,
+                name: property.name,
+                price: property.price,
+                radius: property.radius,
+                kind: property.kind,
+            },
+        ));
+    }
+}
+
+/// Takes the price from the wallet and records the property as owned
+pub fn try_buy_property(
+    property: &Property,
+    wallet: &mut Wallet,
+    owned: &mut OwnedProperties,
+) -> bool {
+    if owned.owns(&property.id) || !wallet.try_spend(property.price) {
+        return false;
+    }
+    owned.0.insert(property.id.clone());
+    true
+}
+
+/// Owned garage properties get a `GarageLocation`; losing them (by loading an
+/// older save) takes it away again
+pub fn sync_property_garages(
+    mut commands: Commands,
+    owned: Res<OwnedProperties>,
+    properties: Query<(Entity, &Property, Has<GarageLocation>)>,
+) {
+    if !owned.is_changed() {
+        return;
+    }
+    for (entity, property, is_garage) in &properties {
+        let PropertyKind::Garage { spawn_point } = property.kind else {
+            continue;
+        };
+        let owns = owned.owns(&property.id);
+        if owns && !is_garage {
+            commands.entity(entity).insert(GarageLocation {
+                name: property.name.clone(),
+                radius: property.radius,
+                spawn_point,
+            });
+        } else if !owns && is_garage {
+            commands.entity(entity).remove::<GarageLocation>();
+        }
+    }
+}
+
+/// Gives shop counters their `Interactable`, again when the language or the
+/// use radius changes
+pub fn sync_shop_interactables(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    localization: Res<Localization>,
+    shops: Query<(Entity, Ref<Shop>)>,
+) {
+    let refresh = config.is_changed() || localization.is_changed();
+    for (entity, shop) in &shops {
+        if refresh || shop.is_added() {
+            commands.entity(entity).insert(Interactable::new(
+                InteractionKind::Shop,
+                config.world.door_use_radius,
+                t!("shop.prompt", shop = shop.kind.label()),
+            ));
+        }
+    }
+}
+
+/// Keeps each property's `Interactable` in line with whether it is owned:
+/// buy it or save at it. An owned garage is left to the garage pad's own
+/// `Interactable`
+pub fn sync_property_interactables(
+    mut commands: Commands,
+    owned: Res<OwnedProperties>,
+    localization: Res<Localization>,
+    properties: Query<(Entity, Ref<Property>)>,
+) {
+    let refresh = owned.is_changed() || localization.is_changed();
+    for (entity, property) in &properties {
+        if !refresh && !property.is_added() {
+            continue;
+        }
+        let prompt = match (owned.owns(&property.id), property.kind) {
+            (false, _) => t!(
+                "property.buy_prompt",
+                name = t!(&property.name),
+                price = property.price
+            ),
+            (true, PropertyKind::Safehouse) => {
+                t!("property.save_prompt", name = t!(&property.name))
+            }
+            (true, PropertyKind::Garage { .. }) => continue,
+        };
+        commands
+            .entity(entity)
+            .insert(Interactable::new(InteractionKind::Property, property.radius, prompt).planar());
+    }
+}
+
+/// Closes an open shop menu once the player walks away from it
+pub fn close_distant_shop_menus(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    player: Query<&GlobalTransform, (With<Player>, With<ActiveEntity>)>,
+    menus: Query<(Entity, &ShopMenu)>,
+) {
+    let position = player.single().ok().map(GlobalTransform::translation);
+    for (entity, menu) in &menus {
+        let in_reach = position.is_some_and(|position| {
+            position.distance(menu.anchor) <= config.world.door_use_radius * MENU_CLOSE_RADII
+        });
+        if !in_reach {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Using a shop toggles its menu; using a property buys it or, for an owned
+/// safehouse, saves the game
+#[allow(clippy::too_many_arguments)]
+pub fn shop_interaction_system(
+    mut commands: Commands,
+    catalogs: Res<ShopCatalogs>,
+    mut interactions: EventReader<InteractionEvent>,
+    mut wallet: ResMut<Wallet>,
+    mut owned: ResMut<OwnedProperties>,
+    shops: Query<(&GlobalTransform, &Shop)>,
+    properties: Query<&Property>,
+    menus: Query<Entity, With<ShopMenu>>,
+    mut saves: EventWriter<SaveGameRequest>,
+) {
+    for interaction in interactions.read() {
+        match interaction.kind {
+            InteractionKind::Shop => {
+                let Ok((counter, shop)) = shops.get(interaction.target) else {
+                    continue;
+                };
+                if menus.is_empty() {
+                    let menu = ShopMenu {
+                        kind: shop.kind,
+                        anchor: counter.translation(),
+                        delivery: counter.translation() + counter.back() * DELIVERY_DISTANCE,
+                    };
+                    spawn_shop_menu(&mut commands, menu, &catalogs);
+                } else {
+                    for menu in &menus {
+                        commands.entity(menu).despawn();
+                    }
+                }
+            }
+            InteractionKind::Property => {
+                let Ok(property) = properties.get(interaction.target) else {
+                    continue;
+                };
+                if !owned.owns(&property.id) {
+                    if try_buy_property(property, &mut wallet, &mut owned) {
+                        info!("🏠 Bought {} for ${}", property.name, property.price);
+                    } else {
+                        info!(
+                            "💸 {} costs ${}, you have ${}",
+                            property.name,
+                            property.price,
+                            wallet.balance()
+                        );
+                    }
+                } else if property.kind == PropertyKind::Safehouse {
+                    saves.write(SaveGameRequest { slot: 0 });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Opens the menus asked for by `OpenShopRequest`, replacing any open one
+pub fn open_requested_shops(
+    mut commands: Commands,
+    catalogs: Res<ShopCatalogs>,
+    mut requests: EventReader<OpenShopRequest>,
+    menus: Query<Entity, With<ShopMenu>>,
+) {
+    let Some(request) = requests.read().last() else {
+        return;
+    };
+    for menu in &menus {
+        commands.entity(menu).despawn();
+    }
+    let menu = ShopMenu {
+        kind: request.kind,
+        anchor: request.anchor,
+        delivery: request.delivery,
+    };
+    spawn_shop_menu(&mut commands, menu, &catalogs);
+}
+
+fn spawn_shop_menu(commands: &mut Commands, menu: ShopMenu, catalogs: &ShopCatalogs) {
+    commands
+        .spawn((
+            menu,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(120.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(menu.kind.label().to_uppercase()),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            let items = catalogs.items(menu.kind);
+            if items.is_empty() {
+                parent.spawn((
+                    Text::new(t!("shop.sold_out")),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.7, 0.7, 0.75)),
+                ));
+            }
+            for (index, item) in items.iter().enumerate() {
+                spawn_menu_button(
+                    parent,
+                    ShopBuyButton(index),
+                    240.0,
+                    format!("{} ${}", t!(&item.name), item.price),
+                );
+            }
+        });
+}
+
+/// Buys the picked catalog entry: vehicles are delivered at the menu's
+/// delivery point and recorded in the garage, outfits are worn straight away
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn shop_menu_buttons(
+    mut commands: Commands,
+    mut buttons: Query<(&Interaction, &ShopBuyButton, &mut BackgroundColor), Changed<Interaction>>,
+    catalogs: Res<ShopCatalogs>,
+    menus: Query<&ShopMenu>,
+    mut wallet: ResMut<Wallet>,
+    mut garage: ResMut<Garage>,
+    mut outfit: ResMut<PlayerOutfit>,
+    config: Res<GameConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (interaction, button, mut color) in &mut buttons {
+        *color = match interaction {
+            Interaction::Hovered | Interaction::Pressed => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok(menu) = menus.single() else {
+            continue;
+        };
+        let Some(item) = catalogs.items(menu.kind).get(button.0) else {
+            continue;
+        };
+        if let ShopGood::Vehicle(_) = item.good
+            && garage.vehicles.len() >= MAX_GARAGE_VEHICLES
+        {
+            info!("🚗 Garage full");
+            continue;
+        }
+        if !wallet.try_spend(item.price) {
+            info!(
+                "💸 {} costs ${}, you have ${}",
+                item.name,
+                item.price,
+                wallet.balance()
+            );
+            continue;
+        }
+
+        match item.good {
+            ShopGood::Vehicle(vehicle_type) => {
+                let color = PAINT_COLORS[0];
+                let Some(id) = garage.add(vehicle_type, color) else {
+                    continue;
+                };
+                let [r, g, b, a] = color;
+                let factory = VehicleFactory::with_config(config.clone());
+                match factory.spawn_vehicle_by_type(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    &asset_server,
+                    vehicle_type,
+                    menu.delivery,
+                    Some(Color::srgba(r, g, b, a)),
+                ) {
+                    Ok(entity) => {
+                        commands
+                            .entity(entity)
+                            .insert((PlayerOwned, GarageVehicle(id)));
+                    }
+                    Err(e) => warn!("⚠️ Failed to spawn {:?}: {:?}", vehicle_type, e),
+                }
+            }
+            ShopGood::Outfit(color) => outfit.0 = Some(color),
+        }
+        info!("🛍️ Bought {} for ${}", item.name, item.price);
+    }
+}
+
+/// Recolors the player's torso to the worn outfit
+pub fn apply_player_outfit(
+    outfit: Res<PlayerOutfit>,
+    torso: Query<&MeshMaterial3d<StandardMaterial>, With<PlayerTorso>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !outfit.is_changed() {
+        return;
+    }
+    let [r, g, b, a] = outfit.0.unwrap_or(DEFAULT_OUTFIT);
+    for handle in &torso {
+        if let Some(material) = materials.get_mut(&handle.0) {
+            material.base_color = Color::srgba(r, g, b, a);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_bought_once_when_affordable() {
+        let property = Property {
+            id: "beach_house".to_string(),
+            name: "Beach House".to_string(),
+            price: 500,
+            radius: 3.0,
+            kind: PropertyKind::Safehouse,
+        };
+        let mut owned = OwnedProperties::default();
+        let mut wallet = Wallet::new(400);
+        assert!(!try_buy_property(&property, &mut wallet, &mut owned));
+
+        wallet.credit(600);
+        assert!(try_buy_property(&property, &mut wallet, &mut owned));
+        assert!(!try_buy_property(&property, &mut wallet, &mut owned));
+        assert_eq!(wallet.balance(), 500);
+        assert!(owned.owns("beach_house"));
+    }
+
+    #[test]
+    fn test_shop_and_property_configs_parse() {
+        let read = |file: &str| {
+            std::fs::read_to_string(format!(
+                "{}/assets/config/{file}",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .unwrap()
+        };
+        let catalogs: ShopCatalogs = ron::from_str(&read("shops.ron")).unwrap();
+        assert!(!catalogs.vehicle_dealer.is_empty());
+        assert!(!catalogs.clothes.is_empty());
+        let list: PropertyList = ron::from_str(&read("properties.ron")).unwrap();
+        assert!(
+            list.properties
+                .iter()
+                .any(|p| matches!(p.kind, PropertyKind::Garage { .. }))
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Shared spatial queries over live world entities.
+//!
+//! `SpatialIndex` buckets entities by ground-plane cell, keyed by the cell's
+//! Morton code, so radius, box and nearest-neighbour queries only touch the
+//! cells they overlap. The `SpatialIndex` resource tracks every entity with
+//! `DynamicContent` or `NPCState` and is refreshed in `PostUpdate`; queries
+//! during `Update` see positions from the end of the previous frame.
+
+use crate::components::{ContentType, DynamicContent, NPCState};
+use crate::systems::performance::memory::table_bytes;
+use crate::systems::spawn_validation::SpawnableType;
+use crate::util::morton::{ZOrderCells, morton_encode};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    buckets: HashMap<u64, Vec<SpatialEntry>>,
+    /// Bucket each entity currently sits in
+    cells: HashMap<Entity, u64>,
+    /// Largest radius ever inserted, so radius-aware queries can widen their search
+    max_radius: f32,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(25.0)
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            buckets: HashMap::new(),
+            cells: HashMap::new(),
+            max_radius: 0.0,
+        }
+    }
+
+    /// Approximate memory held by the buckets and the entity lookup
+    pub fn memory_bytes(&self) -> usize {
+        table_bytes::<(u64, Vec<SpatialEntry>)>(self.buckets.capacity())
+            + table_bytes::<(Entity, u64)>(self.cells.capacity())
+            + self
+                .buckets
+                .values()
+                .map(|entries| entries.capacity() * std::mem::size_of::<SpatialEntry>())
+                .sum::<usize>()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn max_radius(&self) -> f32 {
+        self.max_radius
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Every non-empty cell with the number of entries in it
+    pub fn occupied_cells(&self) -> impl Iterator<Item = (IVec2, usize)> + '_ {
+        self.buckets
+            .values()
+            .filter_map(|bucket| Some((self.cell_of(bucket.first()?.position), bucket.len())))
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.cells.clear();
+        self.max_radius = 0.0;
+    }
+
+    fn cell_of(&self, position: Vec3) -> IVec2 {
+        IVec2::new(
+            (position.x / self.cell_size).floor() as i32,
+            (position.z / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Adds `entity`, or moves it if it is already indexed
+    pub fn insert(&mut self, entity: Entity, position: Vec3, radius: f32) {
+        let code = morton_encode(self.cell_of(position));
+        let entry = SpatialEntry {
+            entity,
+            position,
+            radius,
+        };
+        self.max_radius = self.max_radius.max(radius);
+
+        if let Some(previous) = self.cells.insert(entity, code)
+            && let Some(bucket) = self.buckets.get_mut(&previous)
+        {
+            if previous == code
+                && let Some(existing) = bucket.iter_mut().find(|e| e.entity == entity)
+            {
+                *existing = entry;
+                return;
+            }
+            bucket.retain(|e| e.entity != entity);
+            if bucket.is_empty() {
+                self.buckets.remove(&previous);
+            }
+        }
+        self.buckets.entry(code).or_default().push(entry);
+    }
+
+    /// Returns whether `entity` was indexed
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let Some(code) = self.cells.remove(&entity) else {
+            return false;
+        };
+        if let Some(bucket) = self.buckets.get_mut(&code) {
+            bucket.retain(|e| e.entity != entity);
+            if bucket.is_empty() {
+                self.buckets.remove(&code);
+            }
+        }
+        true
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&SpatialEntry> {
+        let code = self.cells.get(&entity)?;
+        self.buckets.get(code)?.iter().find(|e| e.entity == entity)
+    }
+
+    fn entries_in_cells(&self, min: IVec2, max: IVec2) -> impl Iterator<Item = &SpatialEntry> {
+        ZOrderCells::new(min, max)
+            .filter_map(|cell| self.buckets.get(&morton_encode(cell)))
+            .flatten()
+    }
+
+    /// Entries whose position lies within `radius` of `center`
+    pub fn query_radius(&self, center: Vec3, radius: f32) -> Vec<SpatialEntry> {
+        let reach = Vec3::splat(radius);
+        let radius_squared = radius * radius;
+        self.entries_in_cells(self.cell_of(center - reach), self.cell_of(center + reach))
+            .filter(|e| e.position.distance_squared(center) <= radius_squared)
+            .copied()
+            .collect()
+    }
+
+    /// Entries whose position lies inside the box `min..=max`
+    pub fn query_aabb(&self, min: Vec3, max: Vec3) -> Vec<SpatialEntry> {
+        self.entries_in_cells(self.cell_of(min), self.cell_of(max))
+            .filter(|e| e.position.cmpge(min).all() && e.position.cmple(max).all())
+            .copied()
+            .collect()
+    }
+
+    /// Up to `n` entries closest to `point`, nearest first
+    pub fn nearest_n(&self, point: Vec3, n: usize) -> Vec<SpatialEntry> {
+        let n = n.min(self.len());
+        let mut found: Vec<SpatialEntry> = Vec::new();
+        if n == 0 {
+            return found;
+        }
+
+        let center = self.cell_of(point);
+        let by_distance = |a: &SpatialEntry, b: &SpatialEntry| {
+            a.position
+                .distance_squared(point)
+                .total_cmp(&b.position.distance_squared(point))
+        };
+        for ring in 0.. {
+            // Only the outline of the square; its inside was searched by earlier rings
+            let (min, max) = (center - IVec2::splat(ring), center + IVec2::splat(ring));
+            let outline: Vec<IVec2> = if ring == 0 {
+                vec![center]
+            } else {
+                (min.x..=max.x)
+                    .flat_map(|x| [IVec2::new(x, min.y), IVec2::new(x, max.y)])
+                    .chain(
+                        (min.y + 1..max.y)
+                            .flat_map(|z| [IVec2::new(min.x, z), IVec2::new(max.x, z)]),
+                    )
+                    .collect()
+            };
+            for cell in outline {
+                if let Some(bucket) = self.buckets.get(&morton_encode(cell)) {
+                    found.extend(bucket);
+                }
+            }
+
+            // Unsearched cells are at least `ring` cells away from `point`
+            let searched = ring as f32 * self.cell_size;
+            found.sort_by(by_distance);
+            if found.len() == self.len()
+                || (found.len() >= n && found[n - 1].position.distance(point) <= searched)
+            {
+                break;
+            }
+        }
+        found.truncate(n);
+        found
+    }
+}
+
+/// Keeps the `SpatialIndex` resource in step with moved, spawned and removed entities
+#[allow(clippy::type_complexity)]
+pub fn update_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    moved: Query<
+        (Entity, &Transform, Option<&DynamicContent>),
+        (
+            Changed<Transform>,
+            Or<(With<DynamicContent>, With<NPCState>)>,
+        ),
+    >,
+    indexed: Query<(), Or<(With<DynamicContent>, With<NPCState>)>>,
+    mut removed_content: RemovedComponents<DynamicContent>,
+    mut removed_npcs: RemovedComponents<NPCState>,
+) {
+    for entity in removed_content.read().chain(removed_npcs.read()) {
+        if !indexed.contains(entity) {
+            index.remove(entity);
+        }
+    }
+
+    for (entity, transform, content) in &moved {
+        let content_type = content.map_or(ContentType::NPC, |c| c.content_type);
+        let radius = SpawnableType::from_content_type(content_type).clearance_radius();
+        index.insert(entity, transform.translation, radius);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_with_line(count: u32) -> (SpatialIndex, Vec<Entity>) {
+        let mut index = SpatialIndex::new(10.0);
+        let entities: Vec<Entity> = (0..count).map(Entity::from_raw).collect();
+        for (i, entity) in entities.iter().enumerate() {
+            index.insert(*entity, Vec3::new(i as f32 * 7.0 - 30.0, 0.0, 3.0), 1.0);
+        }
+        (index, entities)
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let (index, entities) = index_with_line(20);
+        let center = Vec3::new(1.0, 0.0, 0.0);
+
+        let mut in_radius: Vec<Entity> = index
+            .query_radius(center, 15.0)
+            .iter()
+            .map(|e| e.entity)
+            .collect();
+        in_radius.sort();
+        let expected: Vec<Entity> = entities
+            .iter()
+            .copied()
+            .filter(|e| index.get(*e).unwrap().position.distance(center) <= 15.0)
+            .collect();
+        assert_eq!(in_radius, expected);
+
+        let in_box = index.query_aabb(Vec3::new(-31.0, -1.0, 0.0), Vec3::new(-16.0, 1.0, 5.0));
+        assert_eq!(in_box.len(), 3);
+
+        let nearest: Vec<Entity> = index
+            .nearest_n(Vec3::new(200.0, 0.0, 0.0), 2)
+            .iter()
+            .map(|e| e.entity)
+            .collect();
+        assert_eq!(nearest, vec![entities[19], entities[18]]);
+    }
+
+    #[test]
+    fn test_insert_moves_and_remove_forgets() {
+        let (mut index, entities) = index_with_line(3);
+        index.insert(entities[0], Vec3::new(500.0, 0.0, -500.0), 2.0);
+
+        assert_eq!(index.len(), 3);
+        assert!(
+            index
+                .query_radius(Vec3::new(-30.0, 0.0, 3.0), 1.0)
+                .is_empty()
+        );
+        assert_eq!(
+            index.nearest_n(Vec3::new(490.0, 0.0, -490.0), 1)[0].entity,
+            entities[0]
+        );
+        assert!(
+            index
+                .occupied_cells()
+                .any(|(cell, count)| cell == IVec2::new(50, -50) && count == 1)
+        );
+
+        assert!(index.remove(entities[0]));
+        assert!(!index.remove(entities[0]));
+        assert!(index.get(entities[0]).is_none());
+        assert_eq!(index.nearest_n(Vec3::ZERO, 5).len(), 2);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::components::ContentType;
+use crate::systems::spatial_index::{SpatialIndex, update_spatial_index};
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet};
 
@@ -89,69 +90,14 @@ pub struct SpawnedEntity {
 #[derive(Resource, Default)]
 pub struct SpawnRegistry {
     entities: HashMap<Entity, SpawnedEntity>,
-    spatial_grid: SpatialGrid,
-}
-
-/// Spatial grid for efficient proximity queries
-#[derive(Debug, Default)]
-struct SpatialGrid {
-    grid_size: f32,
-    cells: HashMap<(i32, i32), Vec<Entity>>,
-}
-
-impl SpatialGrid {
-    fn new(grid_size: f32) -> Self {
-        Self {
-            grid_size,
-            cells: HashMap::new(),
-        }
-    }
-
-    fn get_cell_coord(&self, position: Vec3) -> (i32, i32) {
-        (
-            (position.x / self.grid_size).floor() as i32,
-            (position.z / self.grid_size).floor() as i32,
-        )
-    }
-
-    fn add_entity(&mut self, entity: Entity, position: Vec3) {
-        let coord = self.get_cell_coord(position);
-        self.cells.entry(coord).or_default().push(entity);
-    }
-
-    fn remove_entity(&mut self, entity: Entity, position: Vec3) {
-        let coord = self.get_cell_coord(position);
-        if let Some(entities) = self.cells.get_mut(&coord) {
-            entities.retain(|&e| e != entity);
-        }
-    }
-
-    fn get_nearby_entities(&self, position: Vec3, radius: f32) -> Vec<Entity> {
-        // Pre-allocate with estimated capacity to reduce reallocations
-        let mut result = Vec::with_capacity(32);
-        let center_coord = self.get_cell_coord(position);
-        let cell_range = ((radius / self.grid_size).ceil() as i32).max(1);
-
-        // CRITICAL FIX: Always check neighbors, even when cell_range == 1
-        // Entities near cell boundaries can be in adjacent cells
-        for dx in -cell_range..=cell_range {
-            for dz in -cell_range..=cell_range {
-                let coord = (center_coord.0 + dx, center_coord.1 + dz);
-                if let Some(entities) = self.cells.get(&coord) {
-                    result.extend(entities);
-                }
-            }
-        }
-
-        result
-    }
+    spatial_index: SpatialIndex,
 }
 
 impl SpawnRegistry {
     pub fn new() -> Self {
         Self {
             entities: HashMap::new(),
-            spatial_grid: SpatialGrid::new(20.0), // 20 unit grid cells
+            spatial_index: SpatialIndex::new(20.0), // 20 unit grid cells
         }
     }
 
@@ -171,25 +117,23 @@ impl SpawnRegistry {
             entity_type, position, radius
         );
 
-        self.spatial_grid.add_entity(entity, position);
+        self.spatial_index.insert(entity, position, radius);
         self.entities.insert(entity, spawned_entity);
     }
 
     /// Remove an entity from the registry
     pub fn unregister_entity(&mut self, entity: Entity) {
-        if let Some(spawned_entity) = self.entities.remove(&entity) {
-            self.spatial_grid
-                .remove_entity(entity, spawned_entity.position);
+        if self.entities.remove(&entity).is_some() {
+            self.spatial_index.remove(entity);
         }
     }
 
     /// Update an entity's position in the registry
     pub fn update_entity_position(&mut self, entity: Entity, new_position: Vec3) {
         if let Some(spawned_entity) = self.entities.get_mut(&entity) {
-            self.spatial_grid
-                .remove_entity(entity, spawned_entity.position);
             spawned_entity.position = new_position;
-            self.spatial_grid.add_entity(entity, new_position);
+            self.spatial_index
+                .insert(entity, new_position, spawned_entity.radius);
         }
     }
 
@@ -198,9 +142,7 @@ impl SpawnRegistry {
         // CRITICAL FIX: Use worst-case search radius to catch all potential collisions
         // Building vs Building = 20 + 20 + 8 = 48m, so use 64m to be safe
         let search_radius = 64.0;
-        let nearby_entities = self
-            .spatial_grid
-            .get_nearby_entities(position, search_radius);
+        let nearby_entities = self.spatial_index.query_radius(position, search_radius);
 
         debug!(
             "SPAWN CHECK: Checking {:?} at {:?} against {} nearby entities",
@@ -209,8 +151,8 @@ impl SpawnRegistry {
             nearby_entities.len()
         );
 
-        for &nearby_entity in &nearby_entities {
-            if let Some(spawned_entity) = self.entities.get(&nearby_entity) {
+        for nearby_entity in &nearby_entities {
+            if let Some(spawned_entity) = self.entities.get(&nearby_entity.entity) {
                 let required_distance = entity_type.minimum_spacing(&spawned_entity.entity_type);
 
                 // CRITICAL FIX: Skip check if negative (overlap allowed)
@@ -281,11 +223,10 @@ impl SpawnRegistry {
 
     /// Get all entities within a radius of a position
     pub fn get_entities_in_radius(&self, position: Vec3, radius: f32) -> Vec<&SpawnedEntity> {
-        let nearby_entities = self.spatial_grid.get_nearby_entities(position, radius);
-        nearby_entities
+        self.spatial_index
+            .query_radius(position, radius)
             .iter()
-            .filter_map(|&entity| self.entities.get(&entity))
-            .filter(|entity| entity.position.distance(position) <= radius)
+            .filter_map(|nearby| self.entities.get(&nearby.entity))
             .collect()
     }
 }
@@ -307,8 +248,12 @@ pub struct SpawnValidationPlugin;
 impl Plugin for SpawnValidationPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(SpawnRegistry::new())
+            .init_resource::<SpatialIndex>()
             .add_systems(Update, cleanup_despawned_entities)
-            .add_systems(PostUpdate, auto_update_spawn_positions);
+            .add_systems(
+                PostUpdate,
+                (auto_update_spawn_positions, update_spatial_index),
+            );
     }
 }
 
@@ -344,24 +289,18 @@ fn cleanup_despawned_entities(
     let valid_entities: HashSet<Entity> = query.iter().collect();
 
     // Process only a batch of entities
-    let entities_to_remove: Vec<(Entity, Vec3)> = registry
+    let entities_to_remove: Vec<Entity> = registry
         .entities
-        .iter()
+        .keys()
         .skip(start_index)
         .take(BATCH_SIZE)
-        .filter_map(|(&entity, spawned_entity)| {
-            if !valid_entities.contains(&entity) {
-                Some((entity, spawned_entity.position))
-            } else {
-                None
-            }
-        })
+        .filter(|entity| !valid_entities.contains(entity))
+        .copied()
         .collect();
 
     // Remove them from both data structures
-    for (entity, position) in entities_to_remove {
-        registry.entities.remove(&entity);
-        registry.spatial_grid.remove_entity(entity, position);
+    for entity in entities_to_remove {
+        registry.unregister_entity(entity);
     }
 }
 
@@ -0,0 +1,670 @@
+//! Stunt jumps and time trials.
+//!
+//! Events are listed in `assets/config/stunts.ron`. Driving into an event's
+//! start zone starts a scoring session: jumps score airtime, flips and the
+//! distance covered, passing other cars at speed scores near misses, and a
+//! time trial adds points per checkpoint plus a bonus for the time left.
+//! Each trick pops up in the middle of the screen. A jump ends once the car
+//! has settled after landing, a time trial at its finish or when the clock
+//! runs out; the score then goes into the event's best-score table, which is
+//! kept in the active profile's data directory.
+
+use crate::components::race::RaceManager;
+use crate::components::stunt::{
+    StuntAirborne, StuntCatalog, StuntCheckpoint, StuntEventDefinition, StuntEventFinished,
+    StuntEventKind, StuntManager, StuntPhase, StuntPopup, StuntPopupColumn, StuntScores,
+    StuntSession, StuntTrick, StuntTrickScored, StuntUiText, StuntZone, count_flips,
+};
+use crate::components::{ActiveEntity, Car, Grounded};
+use crate::config::GameConfig;
+use crate::systems::physics::physics_utils::CollisionGroupHelper;
+use crate::systems::profiles::{ActiveProfile, ProfileChangedEvent};
+use crate::systems::racing::format_race_time;
+use crate::systems::shops::load_config;
+use crate::t;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const SUMMARY_SECONDS: f32 = 6.0;
+
+/// Jumps shorter than this are bumps in the road, not stunts
+const MIN_AIRTIME: f32 = 0.6;
+/// A jump event ends once the car has been back on its wheels this long
+const LANDING_SETTLE_SECONDS: f32 = 1.0;
+
+/// Cars whose centers pass within this many meters count as a close call...
+const NEAR_MISS_RADIUS: f32 = 4.0;
+/// ...unless they got this close, which is a scrape rather than a miss
+const NEAR_MISS_CONTACT: f32 = 2.4;
+/// Slowest the player can be going for a near miss to count (m/s)
+const NEAR_MISS_MIN_SPEED: f32 = 15.0;
+
+const RAMP_THICKNESS: f32 = 0.4;
+
+const POPUP_SECONDS: f32 = 2.0;
+const POPUP_FADE_SECONDS: f32 = 0.5;
+const POPUP_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// Where the best-score tables of the profile in `profile_dir` are kept
+pub fn stunt_scores_path(profile_dir: &Path) -> PathBuf {
+    profile_dir.join("stunt_scores.ron")
+}
+
+pub fn read_stunt_scores(path: &Path) -> Result<StuntScores, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    ron::from_str(&contents).map_err(|e| e.to_string())
+}
+
+pub fn write_stunt_scores(path: &Path, scores: &StuntScores) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let contents = ron::ser::to_string_pretty(scores, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| e.to_string())
+}
+
+pub fn load_stunt_catalog(mut commands: Commands, profile: Res<ActiveProfile>) {
+    commands.insert_resource(load_config::<StuntCatalog>("stunts.ron"));
+    commands.insert_resource(load_saved_stunt_scores(&profile.data_directory()));
+}
+
+fn load_saved_stunt_scores(profile_dir: &Path) -> StuntScores {
+    let path = stunt_scores_path(profile_dir);
+    if !path.is_file() {
+        return StuntScores::default();
+    }
+    read_stunt_scores(&path).unwrap_or_else(|e| {
+        warn!("⚠️ Failed to read stunt scores {:?}: {}", path, e);
+        StuntScores::default()
+    })
+}
+
+/// Swaps in the best scores of the profile picked in the main menu
+pub fn reload_profile_stunt_scores(
+    mut changed: EventReader<ProfileChangedEvent>,
+    profile: Res<ActiveProfile>,
+    mut scores: ResMut<StuntScores>,
+) {
+    if changed.read().last().is_some() {
+        *scores = load_saved_stunt_scores(&profile.data_directory());
+    }
+}
+
+/// Start zone discs, and the ramps of jump events
+pub fn spawn_stunt_zones(
+    mut commands: Commands,
+    catalog: Res<StuntCatalog>,
+    config: Res<GameConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let zone_material = materials.add(StandardMaterial {
+        base_color: Color::srgba(1.0, 0.5, 0.1, 0.5),
+        emissive: LinearRgba::rgb(0.6, 0.3, 0.05),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    let ramp_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.55, 0.55, 0.6),
+        perceptual_roughness: 0.7,
+        metallic: 0.4,
+        ..default()
+    });
+
+    for event in &catalog.events {
+        let start = event.start_position();
+        commands.spawn((
+            Name::new(format!("Stunt start: {}", event.id)),
+            Mesh3d(meshes.add(Cylinder::new(event.radius, 0.1))),
+            MeshMaterial3d(zone_material.clone()),
+            Transform::from_translation(start),
+            StuntZone {
+                event_id: event.id.clone(),
+            },
+        ));
+
+        let Some(ramp) = event.ramp else {
+            continue;
+        };
+        let direction = event.direction();
+        let foot = start + direction * event.radius;
+        let facing = Transform::default().looking_to(direction, Vec3::Y).rotation;
+
+        // Slope plank, tipped up so its top runs from the ground to the lip
+        let slope = ramp.height.atan2(ramp.length);
+        let rotation = facing * Quat::from_rotation_x(slope);
+        let half = Vec3::new(
+            ramp.width * 0.5,
+            RAMP_THICKNESS * 0.5,
+            ramp.length.hypot(ramp.height) * 0.5,
+        );
+        let top_middle = foot + direction * ramp.length * 0.5 + Vec3::Y * ramp.height * 0.5;
+        commands.spawn((
+            Name::new(format!("Stunt ramp: {}", event.id)),
+            Mesh3d(meshes.add(Cuboid::from_size(half * 2.0))),
+            MeshMaterial3d(ramp_material.clone()),
+            Transform::from_translation(top_middle - rotation * Vec3::Y * half.y)
+                .with_rotation(rotation),
+            RigidBody::Fixed,
+            Collider::cuboid(half.x, half.y, half.z),
+            CollisionGroupHelper::static_groups(&config),
+        ));
+
+        // Back wall holding up the lip
+        let wall = Vec3::new(ramp.width * 0.5, ramp.height * 0.5, RAMP_THICKNESS * 0.5);
+        commands.spawn((
+            Mesh3d(meshes.add(Cuboid::from_size(wall * 2.0))),
+            MeshMaterial3d(ramp_material.clone()),
+            Transform::from_translation(
+                foot + direction * (ramp.length - wall.z) + Vec3::Y * wall.y,
+            )
+            .with_rotation(facing),
+            RigidBody::Fixed,
+            Collider::cuboid(wall.x, wall.y, wall.z),
+            CollisionGroupHelper::static_groups(&config),
+        ));
+    }
+}
+
+/// Starts the event whose zone the player's car drives into. A car that is
+/// still in the zone when a session ends has to leave it before it counts
+/// again.
+#[allow(clippy::too_many_arguments)]
+pub fn enter_stunt_zones(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    catalog: Res<StuntCatalog>,
+    races: Res<RaceManager>,
+    mut manager: ResMut<StuntManager>,
+    car: Query<&Transform, (With<ActiveEntity>, With<Car>)>,
+    zones: Query<(&Transform, &StuntZone)>,
+    mut last_zone: Local<Option<String>>,
+) {
+    let Ok(car) = car.single() else {
+        *last_zone = None;
+        return;
+    };
+    let inside = catalog.events.iter().find(|event| {
+        zones.iter().any(|(zone, marker)| {
+            marker.event_id == event.id
+                && zone.translation.xz().distance(car.translation.xz()) <= event.radius
+        })
+    });
+    let Some(event) = inside else {
+        *last_zone = None;
+        return;
+    };
+    if manager.0.is_some() || races.0.is_some() || last_zone.as_deref() == Some(event.id.as_str()) {
+        return;
+    }
+    *last_zone = Some(event.id.clone());
+
+    if event.kind == StuntEventKind::TimeTrial && !event.checkpoints.is_empty() {
+        let mesh = meshes.add(Cylinder::new(event.checkpoint_radius, 8.0));
+        let material = materials.add(StandardMaterial {
+            base_color: Color::srgba(1.0, 0.5, 0.1, 0.25),
+            emissive: LinearRgba::rgb(0.8, 0.4, 0.05),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        });
+        for (index, &(x, y, z)) in event.checkpoints.iter().enumerate() {
+            commands.spawn((
+                Name::new(format!("Stunt checkpoint {index}")),
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_xyz(x, y + 4.0, z),
+                Visibility::Hidden,
+                StuntCheckpoint { index },
+            ));
+        }
+    }
+
+    info!("🤸 Stunt event started: {}", event.id);
+    manager.0 = Some(StuntSession::new(event.id.clone()));
+}
+
+/// Updates the cars within `NEAR_MISS_RADIUS` and returns how many have just
+/// slipped past without coming within `NEAR_MISS_CONTACT`
+pub fn update_close_calls(close_calls: &mut HashMap<Entity, f32>, nearby: &[(Entity, f32)]) -> u32 {
+    let mut misses = 0;
+    close_calls.retain(
+        |entity, closest| match nearby.iter().find(|(other, _)| other == entity) {
+            Some(&(_, distance)) => {
+                *closest = closest.min(distance);
+                true
+            }
+            None => {
+                if *closest > NEAR_MISS_CONTACT {
+                    misses += 1;
+                }
+                false
+            }
+        },
+    );
+    for &(entity, distance) in nearby {
+        close_calls.entry(entity).or_insert(distance);
+    }
+    misses
+}
+
+/// Scores jumps (airtime, flips, distance) and near misses while a session
+/// runs
+#[allow(clippy::type_complexity)]
+pub fn score_stunt_tricks(
+    time: Res<Time>,
+    mut manager: ResMut<StuntManager>,
+    mut scored: EventWriter<StuntTrickScored>,
+    car: Query<(Entity, &Transform, &Velocity, &Grounded), (With<ActiveEntity>, With<Car>)>,
+    others: Query<(Entity, &Transform), (With<Car>, Without<ActiveEntity>)>,
+) {
+    let Some(session) = manager.0.as_mut() else {
+        return;
+    };
+    if session.phase != StuntPhase::Running {
+        return;
+    }
+    let Ok((car, transform, velocity, grounded)) = car.single() else {
+        return;
+    };
+    let dt = time.delta_secs();
+    let mut tricks = Vec::new();
+
+    match (grounded.is_grounded, session.airborne.as_mut()) {
+        (false, Some(air)) => {
+            air.time += dt;
+            air.pitch += velocity.angvel.dot(*transform.right()) * dt;
+            air.roll += velocity.angvel.dot(*transform.forward()) * dt;
+        }
+        (false, None) => {
+            session.airborne = Some(StuntAirborne {
+                takeoff: transform.translation,
+                time: 0.0,
+                pitch: 0.0,
+                roll: 0.0,
+            });
+        }
+        (true, Some(air)) => {
+            let air = *air;
+            session.airborne = None;
+            if air.time >= MIN_AIRTIME {
+                session.launched = true;
+                session.landed_for = 0.0;
+                tricks.push(StuntTrick::Airtime(air.time));
+                let distance = air.takeoff.xz().distance(transform.translation.xz());
+                tricks.push(StuntTrick::JumpDistance(distance));
+                let flips = count_flips(air.pitch) + count_flips(air.roll);
+                if flips > 0 {
+                    tricks.push(StuntTrick::Flips(flips));
+                }
+            }
+        }
+        (true, None) => session.landed_for += dt,
+    }
+
+    let nearby: Vec<(Entity, f32)> = if velocity.linvel.length() >= NEAR_MISS_MIN_SPEED {
+        others
+            .iter()
+            .filter(|(other, _)| *other != car)
+            .map(|(other, other_transform)| {
+                (
+                    other,
+                    other_transform.translation.distance(transform.translation),
+                )
+            })
+            .filter(|(_, distance)| *distance <= NEAR_MISS_RADIUS)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    for _ in 0..update_close_calls(&mut session.close_calls, &nearby) {
+        tricks.push(StuntTrick::NearMiss);
+    }
+
+    for trick in tricks {
+        session.score += trick.points();
+        scored.write(StuntTrickScored { trick });
+    }
+}
+
+fn finish_session(
+    session: &mut StuntSession,
+    event: &StuntEventDefinition,
+    scores: &mut StuntScores,
+    profile: &ActiveProfile,
+    finished: &mut EventWriter<StuntEventFinished>,
+) {
+    session.rank = scores.record(&event.id, session.score);
+    if session.rank.is_some() {
+        let path = stunt_scores_path(&profile.data_directory());
+        if let Err(e) = write_stunt_scores(&path, scores) {
+            warn!("⚠️ Failed to save stunt scores to {:?}: {}", path, e);
+        }
+    }
+    info!(
+        "🤸 Stunt event finished: {} with {} points",
+        event.id, session.score
+    );
+    session.phase = StuntPhase::Summary(SUMMARY_SECONDS);
+    finished.write(StuntEventFinished {
+        event_id: event.id.clone(),
+        score: session.score,
+        rank: session.rank,
+    });
+}
+
+/// Runs the clock, counts time trial checkpoints and ends the session. Leaving
+/// the car throws the run away without a score.
+#[allow(clippy::too_many_arguments)]
+pub fn stunt_session_system(
+    time: Res<Time>,
+    catalog: Res<StuntCatalog>,
+    mut manager: ResMut<StuntManager>,
+    mut scores: ResMut<StuntScores>,
+    profile: Res<ActiveProfile>,
+    mut scored: EventWriter<StuntTrickScored>,
+    mut finished: EventWriter<StuntEventFinished>,
+    car: Query<&Transform, (With<ActiveEntity>, With<Car>)>,
+) {
+    let Some(session) = manager.0.as_mut() else {
+        return;
+    };
+    let Some(event) = catalog.get(&session.event_id) else {
+        manager.0 = None;
+        return;
+    };
+    let dt = time.delta_secs();
+
+    if let StuntPhase::Summary(remaining) = session.phase {
+        if remaining > dt {
+            session.phase = StuntPhase::Summary(remaining - dt);
+        } else {
+            manager.0 = None;
+        }
+        return;
+    }
+
+    let Ok(car) = car.single() else {
+        info!("🤸 Stunt event abandoned: {}", event.id);
+        session.score = 0;
+        session.phase = StuntPhase::Summary(SUMMARY_SECONDS);
+        return;
+    };
+    session.elapsed += dt;
+
+    let done = match event.kind {
+        StuntEventKind::Jump => session.launched && session.landed_for >= LANDING_SETTLE_SECONDS,
+        StuntEventKind::TimeTrial => {
+            let reached = event.checkpoint(session.passed).is_some_and(|checkpoint| {
+                checkpoint.xz().distance(car.translation.xz()) <= event.checkpoint_radius
+            });
+            if reached {
+                session.passed += 1;
+                let mut tricks = vec![StuntTrick::Checkpoint];
+                if session.passed >= event.checkpoints.len() {
+                    session.completed = true;
+                    let left = (event.time_limit - session.elapsed).max(0.0);
+                    tricks.push(StuntTrick::TimeBonus(left));
+                }
+                for trick in tricks {
+                    session.score += trick.points();
+                    scored.write(StuntTrickScored { trick });
+                }
+            }
+            session.completed
+        }
+    };
+    if done || session.elapsed >= event.time_limit {
+        finish_session(session, event, &mut scores, &profile, &mut finished);
+    }
+}
+
+/// Shows only the time trial checkpoint the player has to reach next
+pub fn update_stunt_checkpoints(
+    manager: Res<StuntManager>,
+    mut checkpoints: Query<(&StuntCheckpoint, &mut Visibility)>,
+) {
+    let next = manager
+        .0
+        .as_ref()
+        .filter(|session| session.phase == StuntPhase::Running)
+        .map(|session| session.passed);
+    for (checkpoint, mut visibility) in &mut checkpoints {
+        let shown = if next == Some(checkpoint.index) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        visibility.set_if_neq(shown);
+    }
+}
+
+/// Despawns the time trial checkpoints once no session is running
+pub fn clear_stunt_checkpoints(
+    mut commands: Commands,
+    manager: Res<StuntManager>,
+    checkpoints: Query<Entity, With<StuntCheckpoint>>,
+) {
+    if manager.0.is_some() {
+        return;
+    }
+    for entity in &checkpoints {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn setup_stunt_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Right),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(80.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        StuntUiText::Status,
+    ));
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 22.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(25.0),
+            left: Val::Percent(35.0),
+            width: Val::Percent(30.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        StuntUiText::Summary,
+    ));
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(32.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        StuntPopupColumn,
+    ));
+}
+
+fn trick_text(trick: StuntTrick) -> String {
+    let points = trick.points();
+    match trick {
+        StuntTrick::Airtime(seconds) => t!(
+            "stunt.airtime",
+            seconds = format!("{seconds:.1}"),
+            points = points
+        ),
+        StuntTrick::Flips(count) => t!("stunt.flips", count = count, points = points),
+        StuntTrick::JumpDistance(meters) => {
+            t!("stunt.jump", meters = meters.round(), points = points)
+        }
+        StuntTrick::NearMiss => t!("stunt.near_miss", points = points),
+        StuntTrick::Checkpoint => t!("stunt.checkpoint", points = points),
+        StuntTrick::TimeBonus(_) => t!("stunt.time_bonus", points = points),
+    }
+}
+
+fn summary_text(
+    event: &StuntEventDefinition,
+    session: &StuntSession,
+    scores: &StuntScores,
+) -> String {
+    if session.score == 0 && session.rank.is_none() && !session.completed {
+        return t!("stunt.no_score");
+    }
+    let mut lines = vec![t!(
+        "stunt.summary",
+        name = t!(&event.name),
+        score = session.score
+    )];
+    if event.kind == StuntEventKind::TimeTrial && !session.completed {
+        lines.push(t!("stunt.time_up"));
+    }
+    if session.rank == Some(0) {
+        lines.push(t!("stunt.new_best"));
+    }
+    lines.push(String::new());
+    lines.push(t!("stunt.best_scores"));
+    for (place, score) in scores.table(&event.id).iter().enumerate() {
+        let marker = if session.rank == Some(place) {
+            "  <"
+        } else {
+            ""
+        };
+        lines.push(format!("{}. {}{}", place + 1, score, marker));
+    }
+    lines.join("\n")
+}
+
+pub fn update_stunt_hud(
+    catalog: Res<StuntCatalog>,
+    manager: Res<StuntManager>,
+    scores: Res<StuntScores>,
+    mut texts: Query<(&StuntUiText, &mut Text)>,
+) {
+    let session = manager.0.as_ref();
+    let event = session.and_then(|s| catalog.get(&s.event_id));
+
+    for (kind, mut text) in &mut texts {
+        let shown = match (kind, session, event) {
+            (StuntUiText::Status, Some(session), Some(event))
+                if session.phase == StuntPhase::Running =>
+            {
+                t!(
+                    "stunt.status",
+                    name = t!(&event.name),
+                    score = session.score,
+                    time = format_race_time(event.time_limit - session.elapsed)
+                )
+            }
+            (StuntUiText::Summary, Some(session), Some(event)) => match session.phase {
+                StuntPhase::Summary(_) => summary_text(event, session, &scores),
+                StuntPhase::Running => String::new(),
+            },
+            _ => String::new(),
+        };
+        if text.0 != shown {
+            text.0 = shown;
+        }
+    }
+}
+
+/// Pops each scored trick up in the middle of the screen
+pub fn spawn_stunt_popups(
+    mut commands: Commands,
+    mut scored: EventReader<StuntTrickScored>,
+    column: Query<Entity, With<StuntPopupColumn>>,
+) {
+    let Ok(column) = column.single() else {
+        scored.clear();
+        return;
+    };
+    for StuntTrickScored { trick } in scored.read() {
+        commands.spawn((
+            Text::new(trick_text(*trick)),
+            TextFont {
+                font_size: 30.0,
+                ..default()
+            },
+            TextColor(POPUP_COLOR),
+            StuntPopup { age: 0.0 },
+            ChildOf(column),
+        ));
+    }
+}
+
+/// Fades popups out and removes them once they've been up long enough
+pub fn fade_stunt_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popups: Query<(Entity, &mut StuntPopup, &mut TextColor)>,
+) {
+    for (entity, mut popup, mut color) in &mut popups {
+        popup.age += time.delta_secs();
+        if popup.age >= POPUP_SECONDS {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let fade = ((POPUP_SECONDS - popup.age) / POPUP_FADE_SECONDS).min(1.0);
+        color.0 = POPUP_COLOR.with_alpha(fade);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_close_calls_score_only_clean_passes() {
+        let clean = Entity::from_raw(1);
+        let scrape = Entity::from_raw(2);
+        let mut close_calls = HashMap::new();
+
+        assert_eq!(
+            update_close_calls(&mut close_calls, &[(clean, 3.8), (scrape, 3.5)]),
+            0
+        );
+        assert_eq!(
+            update_close_calls(&mut close_calls, &[(clean, 2.9), (scrape, 1.9)]),
+            0
+        );
+        assert_eq!(update_close_calls(&mut close_calls, &[]), 1);
+        assert!(close_calls.is_empty());
+    }
+
+    #[test]
+    fn test_shipped_stunt_events_parse() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/config/stunts.ron");
+        let contents = std::fs::read_to_string(path).expect("stunts.ron should exist");
+        let catalog: StuntCatalog = ron::from_str(&contents).expect("stunts.ron should parse");
+        assert!(!catalog.events.is_empty());
+        for event in &catalog.events {
+            match event.kind {
+                StuntEventKind::Jump => assert!(event.ramp.is_some(), "{} has no ramp", event.id),
+                StuntEventKind::TimeTrial => {
+                    assert!(!event.checkpoints.is_empty(), "{} has no route", event.id)
+                }
+            }
+        }
+    }
+}
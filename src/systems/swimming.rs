@@ -1,15 +1,16 @@
 use crate::bundles::PlayerPhysicsBundle;
 use crate::components::unified_water::{CurrentWaterRegion, UnifiedWaterBody, WaterBodyId};
 use crate::components::{
-    ActiveEntity, ControlState, HumanAnimation, HumanMovement, Player, SwimmingEvent,
+    ActiveEntity, Breath, ControlState, HumanAnimation, HumanMovement, Player, SwimmingEvent,
     VehicleControlType,
 };
+use crate::config::GameConfig;
 use crate::util::transform_utils::horizontal_forward;
 use bevy::math::EulerRot;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use crate::game_state::GameState;
+use crate::game_state::{GameState, GameStateTransitions};
 
 type DetectSwimmingQuery<'w, 's> = Query<
     'w,
@@ -49,6 +50,7 @@ type SwimVelocityQuery<'w, 's> = Query<
         &'static mut HumanMovement,
         &'static ControlState,
         &'static HumanAnimation,
+        Option<&'static Breath>,
     ),
     (With<Player>, With<ActiveEntity>),
 >;
@@ -165,10 +167,11 @@ pub fn detect_swimming_conditions(
 pub fn apply_swimming_state(
     mut commands: Commands,
     mut events: EventReader<SwimmingEvent>,
-    mut state: ResMut<NextState<GameState>>,
+    mut state: GameStateTransitions,
     mut query: ApplySwimmingStateQuery,
     water_regions: Query<&UnifiedWaterBody>,
     time: Res<Time<Fixed>>,
+    config: Res<GameConfig>,
 ) {
     let now = time.elapsed_secs();
 
@@ -191,8 +194,12 @@ pub fn apply_swimming_state(
                         target_pitch: -std::f32::consts::FRAC_PI_2,
                         current_pitch: 0.0,
                         going_prone: true,
-                    });
-                state.set(GameState::Swimming);
+                    })
+                    // Kept after leaving the water so breath refills from where it was
+                    .insert_if_new(Breath::new(config.diving.breath_capacity));
+                if let Err(error) = state.set(GameState::Swimming) {
+                    warn!("GameState change refused: {error}");
+                }
                 debug!("Player entered swimming mode (depth: {:.1})", depth);
             }
             SwimmingEvent::ExitWater { entity } => {
@@ -214,7 +221,9 @@ pub fn apply_swimming_state(
                         .remove::<WaterBodyId>()
                         .insert(VehicleControlType::Walking)
                         .insert(PlayerPhysicsBundle::default());
-                    state.set(GameState::Walking);
+                    if let Err(error) = state.set(GameState::Walking) {
+                        warn!("GameState change refused: {error}");
+                    }
                     debug!("Player exited swimming mode");
                 }
             }
@@ -259,7 +268,7 @@ pub fn apply_swimming_state(
 /// Emergency reset with F2 key
 pub fn emergency_swim_exit_system(
     mut commands: Commands,
-    mut state: ResMut<NextState<GameState>>,
+    mut state: GameStateTransitions,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut query: EmergencySwimExitQuery,
 ) {
@@ -278,7 +287,9 @@ pub fn emergency_swim_exit_system(
                 .insert(VehicleControlType::Walking)
                 .insert(ExitingSwim)
                 .insert(PlayerPhysicsBundle::default());
-            state.set(GameState::Walking);
+            if let Err(error) = state.set(GameState::Walking) {
+                warn!("GameState change refused: {error}");
+            }
             debug!("Emergency exit from swimming mode with F2");
         }
     }
@@ -287,19 +298,24 @@ pub fn emergency_swim_exit_system(
 /// 3D swimming movement with biomechanical arm/leg contributions
 pub fn swim_velocity_apply_system(
     time: Res<Time>,
+    config: Res<GameConfig>,
     mut query: SwimVelocityQuery,
     water_regions: Query<&UnifiedWaterBody>,
 ) {
-    let Ok((transform, mut vel, swim, mut move_data, control_state, animation)) =
+    let Ok((transform, mut vel, swim, mut move_data, control_state, animation, breath)) =
         query.single_mut()
     else {
         return;
     };
 
-    let base_speed = match swim.state {
-        SwimState::Surface => 3.0, // Surface swimming speed
-        SwimState::Diving => 2.5,  // Underwater speed
+    let out_of_air = breath.is_some_and(Breath::is_exhausted);
+    let mut base_speed = match swim.state {
+        SwimState::Surface => config.diving.surface_swim_speed,
+        SwimState::Diving => config.diving.dive_swim_speed,
     };
+    if out_of_air {
+        base_speed *= config.diving.breathless_speed_scale;
+    }
 
     // Horizontal movement (from control state)
     let mut dir = Vec3::ZERO;
@@ -385,6 +401,11 @@ pub fn swim_velocity_apply_system(
                 vertical_velocity = stabilization; // Leg movements help maintain position
             }
         }
+
+        // Out of air: head for the surface regardless of input
+        if out_of_air && current_depth > 0.1 {
+            vertical_velocity = config.diving.surfacing_speed;
+        }
     }
 
     let target_velocity = Vec3::new(
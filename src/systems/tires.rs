@@ -0,0 +1,95 @@
+#![allow(clippy::type_complexity)]
+
+use crate::components::{
+    ActiveEntity, AirfieldRegistry, Car, SimpleCarSpecs, SimpleCarSpecsHandle, SurfaceType,
+    TirePhysics, WeatherState,
+};
+use crate::systems::rendering::decals::Decals;
+use crate::systems::world::road_network::RoadNetwork;
+use crate::systems::world::terrain_height::TerrainHeightService;
+use bevy::prelude::*;
+
+/// A wheel that moved further than this (m) since its last mark was
+/// teleported or respawned, so no segment joins the two points
+const SKID_MARK_MAX_GAP: f32 = 8.0;
+
+/// Works out the surface under the active car: roads, intersections and
+/// airfields are paved, the island plateaus grass and the beaches sand
+pub fn detect_tire_surface(
+    roads: Option<Res<RoadNetwork>>,
+    airfields: Option<Res<AirfieldRegistry>>,
+    terrain: Option<Res<TerrainHeightService>>,
+    weather: Option<Res<WeatherState>>,
+    mut cars: Query<(&Transform, &mut TirePhysics), (With<Car>, With<ActiveEntity>)>,
+) {
+    let rain = weather.map_or(0.0, |weather| weather.params.rain_intensity);
+    for (transform, mut tires) in &mut cars {
+        let position = transform.translation;
+        let on_road = roads.as_ref().is_some_and(|roads| {
+            roads.intersection_at(position).is_some()
+                || roads
+                    .nearest_road(position)
+                    .is_some_and(|(id, _, distance)| {
+                        roads
+                            .roads
+                            .get(&id)
+                            .is_some_and(|road| distance <= road.road_type.width() * 0.5)
+                    })
+        });
+        let paved = on_road
+            || airfields
+                .as_ref()
+                .is_some_and(|airfields| airfields.surface_at(position).is_some());
+        let on_plateau = terrain
+            .as_ref()
+            .is_none_or(|terrain| terrain.is_on_plateau(position.x, position.z));
+
+        let surface = SurfaceType::classify(paved, on_plateau, rain);
+        if tires.surface != surface {
+            tires.surface = surface;
+        }
+    }
+}
+
+/// Lays skid mark decals behind the rear wheels of skidding cars; the decal
+/// ring ages them out
+pub fn spawn_skid_marks(
+    mut decals: ResMut<Decals>,
+    car_specs: Res<Assets<SimpleCarSpecs>>,
+    mut cars: Query<(&GlobalTransform, &SimpleCarSpecsHandle, &mut TirePhysics), With<Car>>,
+) {
+    for (transform, specs_handle, mut tires) in &mut cars {
+        if !tires.skidding {
+            if tires.last_marks.iter().any(Option::is_some) {
+                tires.last_marks = [None; 2];
+            }
+            continue;
+        }
+        let Some(specs) = car_specs.get(&specs_handle.0) else {
+            continue;
+        };
+
+        for (slot, wheel) in specs.wheel_positions[2..].iter().enumerate() {
+            let (x, y, z) = *wheel;
+            let contact = transform.transform_point(Vec3::new(x, y - specs.wheel_radius, z));
+            let Some(last) = tires.last_marks[slot] else {
+                tires.last_marks[slot] = Some(contact);
+                continue;
+            };
+            let length = contact.distance(last);
+            if length < specs.skid_mark_spacing {
+                continue;
+            }
+            tires.last_marks[slot] = Some(contact);
+            if length > SKID_MARK_MAX_GAP {
+                continue;
+            }
+            decals.skid_mark(
+                last,
+                contact,
+                specs.skid_mark_width,
+                specs.skid_mark_lifetime,
+            );
+        }
+    }
+}
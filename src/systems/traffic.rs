@@ -0,0 +1,263 @@
+//! Ambient traffic: chunk-spawned cars drive along the road network.
+//!
+//! Cars keep to the right-hand lane, respect per-road-type speed limits, wait
+//! briefly at intersections before turning onto a connected road and brake
+//! when the player is directly ahead.
+
+use crate::components::{ActiveEntity, CarAlarm, PlayerOwned, TrafficAgent, TrafficCandidate};
+use crate::config::{GameConfig, TrafficConfig};
+use crate::resources::WorldRng;
+use crate::systems::world::road_network::{RoadNetwork, RoadSpline, RoadType};
+use crate::systems::world::unified_world::UnifiedWorldManager;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use rand::Rng;
+
+/// Lane center as a fraction of road width, measured from the centerline
+const LANE_OFFSET_FRACTION: f32 = 0.25;
+
+pub fn speed_limit(config: &TrafficConfig, road_type: RoadType) -> f32 {
+    match road_type {
+        RoadType::Highway => config.highway_speed,
+        RoadType::MainStreet => config.main_street_speed,
+        RoadType::SideStreet => config.side_street_speed,
+        RoadType::Alley => config.alley_speed,
+    }
+}
+
+/// Travel direction on the XZ plane at parameter `t`
+fn road_direction(road: &RoadSpline, t: f32, forward: bool) -> Vec3 {
+    let dir = road.direction(t);
+    if forward { dir } else { -dir }
+}
+
+/// Point in the right-hand lane at parameter `t`
+pub fn lane_point(road: &RoadSpline, t: f32, forward: bool) -> Vec3 {
+    let right = road_direction(road, t, forward).cross(Vec3::Y);
+    road.evaluate(t) + right * road.road_type.width() * LANE_OFFSET_FRACTION
+}
+
+fn road_end(road: &RoadSpline, forward: bool) -> Vec3 {
+    road.evaluate(if forward { 1.0 } else { 0.0 })
+}
+
+fn ends_in_intersection(network: &RoadNetwork, road: &RoadSpline, forward: bool) -> bool {
+    network.intersection_at(road_end(road, forward)).is_some()
+}
+
+fn make_agent(
+    network: &RoadNetwork,
+    road: &RoadSpline,
+    t: f32,
+    forward: bool,
+    config: &TrafficConfig,
+) -> TrafficAgent {
+    TrafficAgent {
+        road_id: road.id,
+        t,
+        forward,
+        road_length: road.length(),
+        speed_limit: speed_limit(config, road.road_type),
+        current_speed: 0.0,
+        stop_at_end: ends_in_intersection(network, road, forward),
+        stop_timer: 0.0,
+    }
+}
+
+/// Attaches newly spawned cars to the closest road; cars far from any road stay
+/// parked, and some of those get an armed alarm
+pub fn assign_traffic_agents(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    world: Res<UnifiedWorldManager>,
+    mut world_rng: ResMut<WorldRng>,
+    candidates: Query<(Entity, &Transform), With<TrafficCandidate>>,
+) {
+    let traffic = &config.traffic;
+    let network = &world.road_network;
+
+    for (entity, transform) in candidates.iter() {
+        commands.entity(entity).remove::<TrafficCandidate>();
+        let rng = world_rng.global();
+
+        let road = network
+            .nearest_road(transform.translation)
+            .filter(|_| traffic.enabled)
+            .map(|(road_id, t, distance)| (&network.roads[&road_id], t, distance))
+            .filter(|(road, _, distance)| {
+                *distance <= traffic.max_road_snap_distance + road.road_type.width() * 0.5
+            });
+        match road {
+            Some((road, t, _)) => {
+                let forward = rng.gen_bool(0.5);
+                commands
+                    .entity(entity)
+                    .insert(make_agent(network, road, t, forward, traffic));
+            }
+            None if rng.gen_bool(traffic.alarm_chance as f64) => {
+                commands.entity(entity).insert(CarAlarm::default());
+            }
+            None => {}
+        }
+    }
+}
+
+/// Picks a road connected at the end of `road`, entering it from its closer end
+fn next_road(
+    network: &RoadNetwork,
+    road: &RoadSpline,
+    forward: bool,
+    rng: &mut impl Rng,
+) -> Option<(u64, f32, bool)> {
+    let end = road_end(road, forward);
+    let mut options: Vec<u64> = road.connections.clone();
+    for intersection in network.intersections.values() {
+        if intersection.position.xz().distance(end.xz()) <= intersection.radius {
+            options.extend(intersection.connected_roads.iter().copied());
+        }
+    }
+    options.retain(|id| *id != road.id && network.roads.contains_key(id));
+    options.sort_unstable();
+    options.dedup();
+
+    if options.is_empty() {
+        return None;
+    }
+    let id = options[rng.gen_range(0..options.len())];
+    let next = &network.roads[&id];
+    let from_start = next.evaluate(0.0).distance(end) <= next.evaluate(1.0).distance(end);
+    Some(if from_start {
+        (id, 0.0, true)
+    } else {
+        (id, 1.0, false)
+    })
+}
+
+#[allow(clippy::type_complexity)]
+pub fn traffic_ai_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    world: Res<UnifiedWorldManager>,
+    mut world_rng: ResMut<WorldRng>,
+    mut commands: Commands,
+    player_query: Query<&Transform, With<ActiveEntity>>,
+    mut agents: Query<
+        (Entity, &Transform, &mut Velocity, &mut TrafficAgent),
+        (Without<ActiveEntity>, Without<PlayerOwned>),
+    >,
+) {
+    let traffic = &config.traffic;
+    if !traffic.enabled {
+        return;
+    }
+    let Ok(player) = player_query.single() else {
+        return;
+    };
+    let network = &world.road_network;
+    let dt = time.delta_secs();
+
+    for (entity, transform, mut velocity, mut agent) in agents.iter_mut() {
+        let position = transform.translation;
+        if position.xz().distance(player.translation.xz()) > traffic.active_radius {
+            continue;
+        }
+        let Some(road) = network.roads.get(&agent.road_id) else {
+            // Road streamed out underneath the car
+            commands.entity(entity).remove::<TrafficAgent>();
+            continue;
+        };
+
+        // End of road: wait at the stop line once, then turn onto a connected road
+        if agent.distance_to_end() < 1.0 {
+            if agent.stop_at_end {
+                agent.stop_at_end = false;
+                agent.stop_timer = traffic.intersection_stop_time;
+            } else if agent.stop_timer <= 0.0 {
+                match next_road(network, road, agent.forward, world_rng.global()) {
+                    Some((id, t, forward)) => {
+                        let speed = agent.current_speed;
+                        *agent = make_agent(network, &network.roads[&id], t, forward, traffic);
+                        agent.current_speed = speed;
+                    }
+                    // Dead end: turn around
+                    None => {
+                        agent.forward = !agent.forward;
+                        agent.stop_at_end = ends_in_intersection(network, road, agent.forward);
+                    }
+                }
+                continue;
+            }
+        }
+
+        let direction = road_direction(road, agent.t, agent.forward);
+        let mut target_speed = agent.speed_limit;
+        if agent.stop_timer > 0.0 {
+            agent.stop_timer -= dt;
+            target_speed = 0.0;
+        } else if agent.stop_at_end {
+            // Decelerate so the car comes to rest at the intersection
+            let braking = (2.0 * traffic.acceleration * agent.distance_to_end()).sqrt();
+            target_speed = target_speed.min(braking.max(1.0));
+        }
+
+        let to_player = (player.translation - position).with_y(0.0);
+        if to_player.length() < traffic.player_avoid_distance
+            && direction.dot(to_player.normalize_or_zero()) > 0.7
+        {
+            target_speed = 0.0;
+        }
+
+        let max_change = traffic.acceleration * dt;
+        agent.current_speed +=
+            (target_speed - agent.current_speed).clamp(-2.0 * max_change, max_change);
+        agent.current_speed = agent.current_speed.max(0.0);
+
+        let target = lane_point(
+            road,
+            agent.t_ahead(traffic.lookahead_distance),
+            agent.forward,
+        );
+        let steer = (target - position).with_y(0.0).normalize_or(direction);
+        velocity.linvel.x = steer.x * agent.current_speed;
+        velocity.linvel.z = steer.z * agent.current_speed;
+
+        let heading = transform.forward().with_y(0.0).normalize_or_zero();
+        let heading_error = heading.cross(steer).y.asin();
+        velocity.angvel = Vec3::new(0.0, heading_error * 2.0, 0.0);
+
+        let advance = agent.current_speed * dt;
+        agent.t = agent.t_ahead(advance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lane_point_keeps_right() {
+        // Road heading -Z: right-hand side is +X
+        let road = RoadSpline::new(1, Vec3::ZERO, Vec3::new(0.0, 0.0, -100.0), RoadType::Alley);
+        let lane = lane_point(&road, 0.5, true);
+        assert!(lane.x > 0.0);
+
+        let lane_back = lane_point(&road, 0.5, false);
+        assert!(lane_back.x < 0.0);
+    }
+
+    #[test]
+    fn test_agent_progress_clamped_to_road() {
+        let agent = TrafficAgent {
+            road_id: 1,
+            t: 0.9,
+            forward: true,
+            road_length: 100.0,
+            speed_limit: 10.0,
+            current_speed: 0.0,
+            stop_at_end: false,
+            stop_timer: 0.0,
+        };
+        assert_eq!(agent.t_ahead(50.0), 1.0);
+        assert!((agent.distance_to_end() - 10.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,509 @@
+//! Quake-style drop-down console for cheats and debugging.
+//!
+//! `~` opens the console over the gameplay HUD; while it is open it swallows
+//! keyboard input so typing doesn't drive the player. Commands live in the
+//! `ConsoleCommands` registry, so any plugin can add its own with
+//! `ConsoleCommands::register`. Handlers run with exclusive `World` access and
+//! return the text to print.
+//!
+//! Built-ins: `tp`, `spawn`, `spawn_vehicle`, `god`, `time` and `help`. Up/Down
+//! walk the command history and Tab completes command names.
+
+use crate::components::{
+    ActiveEntity, Breath, Player, PlayerHealth, VehicleHealth, VehicleType, WantedLevel, Wasted,
+};
+use crate::config::GameConfig;
+use crate::factories::{PrefabFactory, PrefabOverrides, PrefabRegistry, VehicleFactory};
+use crate::resources::WorldClock;
+use bevy::ecs::system::SystemState;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use std::collections::{BTreeMap, VecDeque};
+
+/// Output lines kept for display
+const LOG_LINES: usize = 14;
+const HISTORY_LEN: usize = 50;
+/// How far ahead of the active entity `spawn` places things
+const SPAWN_DISTANCE: f32 = 6.0;
+
+pub type ConsoleResult = Result<String, String>;
+type Handler = Box<dyn Fn(&mut World, &[&str]) -> ConsoleResult + Send + Sync>;
+
+pub struct ConsoleCommand {
+    usage: String,
+    handler: Handler,
+}
+
+impl ConsoleCommand {
+    /// Argument summary shown by `help`
+    pub fn usage(&mut self, usage: &str) -> &mut Self {
+        self.usage = usage.to_string();
+        self
+    }
+}
+
+/// Named console commands
+#[derive(Resource, Default)]
+pub struct ConsoleCommands {
+    commands: BTreeMap<String, ConsoleCommand>,
+}
+
+impl ConsoleCommands {
+    /// Adds or replaces a command; `handler` gets the words after the name
+    pub fn register(
+        &mut self,
+        name: &str,
+        handler: impl Fn(&mut World, &[&str]) -> ConsoleResult + Send + Sync + 'static,
+    ) -> &mut ConsoleCommand {
+        self.commands.insert(
+            name.to_string(),
+            ConsoleCommand {
+                usage: String::new(),
+                handler: Box::new(handler),
+            },
+        );
+        self.commands.get_mut(name).expect("just inserted")
+    }
+
+    /// Command names, `help` included, in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once("help").chain(self.commands.keys().map(String::as_str))
+    }
+
+    fn help(&self) -> String {
+        self.commands
+            .iter()
+            .map(|(name, command)| format!("{name} {}", command.usage))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Whether the console is open, what is being typed and what it printed
+#[derive(Resource, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    pub input: String,
+    pub log: VecDeque<String>,
+    history: Vec<String>,
+    /// Index into `history` while browsing with Up/Down
+    browsing: Option<usize>,
+    /// Lines entered this frame, run by `run_console_commands`
+    submitted: Vec<String>,
+}
+
+impl ConsoleState {
+    pub fn print(&mut self, text: impl Into<String>) {
+        for line in text.into().lines() {
+            if self.log.len() == LOG_LINES {
+                self.log.pop_front();
+            }
+            self.log.push_back(line.to_string());
+        }
+    }
+
+    fn submit(&mut self) {
+        let line = std::mem::take(&mut self.input).trim().to_string();
+        self.browsing = None;
+        if line.is_empty() {
+            return;
+        }
+        if self.history.last() != Some(&line) {
+            if self.history.len() == HISTORY_LEN {
+                self.history.remove(0);
+            }
+            self.history.push(line.clone());
+        }
+        self.submitted.push(line);
+    }
+
+    /// Steps through history; `older` moves back in time
+    fn browse(&mut self, older: bool) {
+        let Some(last) = self.history.len().checked_sub(1) else {
+            return;
+        };
+        self.browsing = match (self.browsing, older) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index < last => Some(index + 1),
+            (Some(_), false) => None,
+        };
+        self.input = self
+            .browsing
+            .map_or_else(String::new, |index| self.history[index].clone());
+    }
+
+    /// Completes the command name being typed, listing the options when ambiguous
+    fn complete<'a>(&mut self, names: impl Iterator<Item = &'a str>) {
+        if self.input.contains(' ') {
+            return;
+        }
+        let matches: Vec<&str> = names.filter(|name| name.starts_with(&self.input)).collect();
+        match matches.as_slice() {
+            [] => {}
+            [only] => self.input = format!("{only} "),
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.len(), |len, name| {
+                    first
+                        .bytes()
+                        .zip(name.bytes())
+                        .take(len)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                });
+                self.input = first[..common].to_string();
+                self.print(matches.join("  "));
+            }
+        }
+    }
+}
+
+/// The player takes no damage, and their health, active vehicle health,
+/// breath and wanted heat stay topped up while set
+#[derive(Resource, Debug, Default)]
+pub struct GodMode(pub bool);
+
+#[derive(Component)]
+pub struct ConsoleUI;
+
+#[derive(Component)]
+pub struct ConsoleLogText;
+
+#[derive(Component)]
+pub struct ConsoleInputText;
+
+pub fn setup_console(mut commands: Commands) {
+    commands
+        .spawn((
+            ConsoleUI,
+            Name::new("Console"),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(40.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::FlexEnd,
+                padding: UiRect::all(Val::Px(8.0)),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.02, 0.02, 0.04, 0.85)),
+            GlobalZIndex(200),
+        ))
+        .with_children(|console| {
+            console.spawn((
+                ConsoleLogText,
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.75, 0.8, 0.75)),
+            ));
+            console.spawn((
+                ConsoleInputText,
+                Text::new("> "),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Toggles the console and feeds it typed text. Runs after Bevy's input
+/// update and clears the key state while open so gameplay ignores the keys.
+pub fn console_input(
+    mut events: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut state: ResMut<ConsoleState>,
+    registry: Res<ConsoleCommands>,
+) {
+    let was_open = state.open;
+    for event in events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        if event.key_code == KeyCode::Backquote || (state.open && event.key_code == KeyCode::Escape)
+        {
+            state.open = !state.open && event.key_code == KeyCode::Backquote;
+            continue;
+        }
+        if !state.open {
+            continue;
+        }
+        match event.key_code {
+            KeyCode::Enter | KeyCode::NumpadEnter => state.submit(),
+            KeyCode::Backspace => {
+                state.input.pop();
+            }
+            KeyCode::ArrowUp => state.browse(true),
+            KeyCode::ArrowDown => state.browse(false),
+            KeyCode::Tab => state.complete(registry.names()),
+            _ => {
+                if let Some(text) = &event.text {
+                    state.input.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+    if state.open || was_open {
+        keys.reset_all();
+    }
+}
+
+/// Runs one console line against the registry
+pub fn execute(world: &mut World, line: &str) -> ConsoleResult {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((name, args)) = words.split_first() else {
+        return Ok(String::new());
+    };
+    world.resource_scope(|world, registry: Mut<ConsoleCommands>| {
+        if *name == "help" {
+            return Ok(registry.help());
+        }
+        let command = registry
+            .commands
+            .get(*name)
+            .ok_or_else(|| format!("unknown command `{name}`, try `help`"))?;
+        (command.handler)(world, args)
+    })
+}
+
+pub fn run_console_commands(world: &mut World) {
+    let lines = std::mem::take(&mut world.resource_mut::<ConsoleState>().submitted);
+    for line in lines {
+        let result = execute(world, &line);
+        let mut state = world.resource_mut::<ConsoleState>();
+        state.print(format!("> {line}"));
+        match result {
+            Ok(output) => state.print(output),
+            Err(error) => state.print(format!("error: {error}")),
+        }
+    }
+}
+
+pub fn update_console_ui(
+    state: Res<ConsoleState>,
+    mut panels: Query<&mut Node, With<ConsoleUI>>,
+    mut logs: Query<&mut Text, (With<ConsoleLogText>, Without<ConsoleInputText>)>,
+    mut inputs: Query<&mut Text, (With<ConsoleInputText>, Without<ConsoleLogText>)>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    for mut node in &mut panels {
+        node.display = if state.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    for mut text in &mut logs {
+        **text = state.log.iter().cloned().collect::<Vec<_>>().join("\n");
+    }
+    for mut text in &mut inputs {
+        **text = format!("> {}_", state.input);
+    }
+}
+
+pub fn apply_god_mode(
+    god_mode: Res<GodMode>,
+    mut players: Query<&mut PlayerHealth, (With<Player>, Without<Wasted>)>,
+    mut vehicles: Query<&mut VehicleHealth, With<ActiveEntity>>,
+    mut breath: Query<&mut Breath, With<Player>>,
+    wanted: Option<ResMut<WantedLevel>>,
+) {
+    if !god_mode.0 {
+        return;
+    }
+    for mut health in &mut players {
+        if health.current < health.max {
+            health.current = health.max;
+        }
+    }
+    for mut health in &mut vehicles {
+        if health.current < health.max {
+            health.current = health.max;
+        }
+    }
+    for mut breath in &mut breath {
+        if breath.remaining < breath.capacity {
+            breath.remaining = breath.capacity;
+        }
+    }
+    // The wanted system drops the stars once the heat is gone
+    if let Some(mut wanted) = wanted
+        && wanted.heat > 0.0
+    {
+        wanted.heat = 0.0;
+    }
+}
+
+fn parse<T: std::str::FromStr>(arg: Option<&&str>, what: &str) -> Result<T, String> {
+    let arg = arg.ok_or_else(|| format!("missing {what}"))?;
+    arg.parse().map_err(|_| format!("bad {what} `{arg}`"))
+}
+
+/// Point `SPAWN_DISTANCE` ahead of the active entity
+fn spawn_point(world: &mut World) -> Result<Vec3, String> {
+    let mut active = world.query_filtered::<&Transform, With<ActiveEntity>>();
+    let transform = active
+        .single(world)
+        .map_err(|_| "no active entity".to_string())?;
+    Ok(transform.translation + transform.forward() * SPAWN_DISTANCE)
+}
+
+fn teleport(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let target = Vec3::new(
+        parse(args.first(), "x")?,
+        parse(args.get(1), "y")?,
+        parse(args.get(2), "z")?,
+    );
+    let mut active =
+        world.query_filtered::<(&mut Transform, Option<&mut Velocity>), With<ActiveEntity>>();
+    let (mut transform, velocity) = active
+        .single_mut(world)
+        .map_err(|_| "no active entity".to_string())?;
+    transform.translation = target;
+    if let Some(mut velocity) = velocity {
+        *velocity = Velocity::zero();
+    }
+    Ok(format!("teleported to {target}"))
+}
+
+fn spawn_prefab(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let name = args.first().ok_or("missing prefab name")?;
+    let position = spawn_point(world)?;
+    world.resource_scope(|world, registry: Mut<PrefabRegistry>| {
+        let mut commands = world.commands();
+        PrefabFactory::spawn_by_name(
+            &mut commands,
+            &registry,
+            name,
+            PrefabOverrides::at(position),
+        )
+        .map_err(|e| e.to_string())
+    })?;
+    world.flush();
+    Ok(format!("spawned {name}"))
+}
+
+type VehicleSpawnParams<'w, 's> = (
+    Commands<'w, 's>,
+    ResMut<'w, Assets<Mesh>>,
+    ResMut<'w, Assets<StandardMaterial>>,
+    Res<'w, AssetServer>,
+    Res<'w, GameConfig>,
+);
+
+fn spawn_vehicle(world: &mut World, args: &[&str]) -> ConsoleResult {
+    const TYPES: [VehicleType; 4] = [
+        VehicleType::SuperCar,
+        VehicleType::Helicopter,
+        VehicleType::F16,
+        VehicleType::Yacht,
+    ];
+    let name = args.first().ok_or("missing vehicle type")?;
+    let vehicle_type = TYPES
+        .into_iter()
+        .find(|t| format!("{t:?}").eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("unknown vehicle `{name}`, expected one of {TYPES:?}"))?;
+    let position = spawn_point(world)?;
+
+    let mut state = SystemState::<VehicleSpawnParams>::new(world);
+    let (mut commands, mut meshes, mut materials, asset_server, config) = state.get_mut(world);
+    VehicleFactory::with_config(config.clone())
+        .spawn_vehicle_by_type(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &asset_server,
+            vehicle_type,
+            position,
+            None,
+        )
+        .map_err(|e| format!("{e:?}"))?;
+    state.apply(world);
+    Ok(format!("spawned {vehicle_type:?}"))
+}
+
+fn toggle_god_mode(world: &mut World, _: &[&str]) -> ConsoleResult {
+    let mut god_mode = world.resource_mut::<GodMode>();
+    god_mode.0 = !god_mode.0;
+    Ok(format!(
+        "god mode {}",
+        if god_mode.0 { "on" } else { "off" }
+    ))
+}
+
+fn set_time_of_day(world: &mut World, args: &[&str]) -> ConsoleResult {
+    let hour: f32 = parse(args.first(), "hour")?;
+    if !(0.0..24.0).contains(&hour) {
+        return Err("hour must be between 0 and 24".to_string());
+    }
+    // The sun follows on the next frame
+    *world.resource_mut::<WorldClock>() = WorldClock::at(hour);
+    Ok(format!("time set to {hour:.1}h"))
+}
+
+pub fn register_builtin_commands(mut registry: ResMut<ConsoleCommands>) {
+    registry.register("tp", teleport).usage("<x> <y> <z>");
+    registry
+        .register("spawn", spawn_prefab)
+        .usage("<prefab name>");
+    registry
+        .register("spawn_vehicle", spawn_vehicle)
+        .usage("<supercar|helicopter|f16|yacht>");
+    registry.register("god", toggle_god_mode);
+    registry
+        .register("time", set_time_of_day)
+        .usage("<hour 0-24>");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_commands_run_with_arguments() {
+        let mut world = World::new();
+        world.init_resource::<ConsoleCommands>();
+        world
+            .resource_mut::<ConsoleCommands>()
+            .register("echo", |_: &mut World, args: &[&str]| Ok(args.join(" ")));
+
+        assert_eq!(execute(&mut world, "echo a  b"), Ok("a b".to_string()));
+        assert!(execute(&mut world, "nope").is_err());
+        assert!(execute(&mut world, "help").unwrap().contains("echo"));
+    }
+
+    #[test]
+    fn test_history_and_completion() {
+        let mut state = ConsoleState::default();
+        for line in ["god", "time 8", "time 8"] {
+            state.input = line.to_string();
+            state.submit();
+        }
+        assert_eq!(state.history, ["god", "time 8"]);
+        state.browse(true);
+        state.browse(true);
+        assert_eq!(state.input, "god");
+        state.browse(false);
+        state.browse(false);
+        assert_eq!(state.input, "");
+
+        let names = ["spawn", "spawn_vehicle", "tp"];
+        state.input = "sp".to_string();
+        state.complete(names.into_iter());
+        assert_eq!(state.input, "spawn");
+        state.input = "t".to_string();
+        state.complete(names.into_iter());
+        assert_eq!(state.input, "tp ");
+    }
+}
@@ -1,17 +1,19 @@
 use crate::components::{ActiveEntity, ControlsText, VehicleControlType};
-use crate::game_state::GameState;
+use crate::game_state::{GameState, GameStateStack};
 use crate::systems::input::{LoadedVehicleControls, get_vehicle_control_help};
+use crate::t;
 use bevy::prelude::*;
 
 pub fn controls_ui_system(
     current_state: Res<State<GameState>>,
+    stack: Res<GameStateStack>,
     loaded_controls: Res<LoadedVehicleControls>,
     mut controls_query: Query<&mut Text, With<ControlsText>>,
     active_vehicle_query: Query<&VehicleControlType, With<ActiveEntity>>,
 ) {
     for mut text in controls_query.iter_mut() {
         let controls_text = generate_dynamic_controls_text(
-            current_state.get(),
+            &stack.gameplay(*current_state.get()),
             &loaded_controls,
             &active_vehicle_query,
         );
@@ -26,7 +28,8 @@ fn generate_dynamic_controls_text(
 ) -> String {
     // Map GameState to expected VehicleControlType
     let state_vehicle_type = match state {
-        GameState::Walking => VehicleControlType::Walking,
+        // Paused only shows up with nothing underneath it
+        GameState::Walking | GameState::Paused => VehicleControlType::Walking,
         GameState::Swimming => VehicleControlType::Swimming,
         GameState::Driving => VehicleControlType::Car, // Note: could also be Yacht
         GameState::Flying => VehicleControlType::Helicopter,
@@ -53,21 +56,22 @@ fn generate_dynamic_controls_text(
         help_text
     } else {
         // Fallback if controls haven't loaded yet
-        match vehicle_type {
-            VehicleControlType::Walking => "LOADING WALKING CONTROLS...".to_string(),
-            VehicleControlType::Swimming => "LOADING SWIMMING CONTROLS...".to_string(),
-            VehicleControlType::Car => "LOADING CAR CONTROLS...".to_string(),
-            VehicleControlType::Helicopter => "LOADING HELICOPTER CONTROLS...".to_string(),
-            VehicleControlType::F16 => "LOADING F16 CONTROLS...".to_string(),
-            VehicleControlType::Yacht => "LOADING YACHT CONTROLS...".to_string(),
-        }
+        let mode = match vehicle_type {
+            VehicleControlType::Walking => "controls.mode.walking",
+            VehicleControlType::Swimming => "controls.mode.swimming",
+            VehicleControlType::Car => "controls.mode.car",
+            VehicleControlType::Helicopter => "controls.mode.helicopter",
+            VehicleControlType::F16 => "controls.mode.f16",
+            VehicleControlType::Yacht => "controls.mode.yacht",
+        };
+        t!("controls.loading", mode = t!(mode))
     }
 }
 
 // Remove the old hardcoded UI generation - now using asset-based system
 
-#[allow(dead_code)]
-fn format_key_name(key: KeyCode) -> String {
+/// Short on-screen name of a key, e.g. "F" or "SPACE"
+pub fn format_key_name(key: KeyCode) -> String {
     match key {
         KeyCode::ArrowUp => "UP".to_string(),
         KeyCode::ArrowDown => "DOWN".to_string(),
@@ -0,0 +1,101 @@
+//! Main menu page offered after a crash: where the report went, a button to
+//! read it in place and one to dismiss it for good.
+
+use crate::states::MainMenuPage;
+use crate::systems::crash_report::{crash_directory, dismiss_pending_report, pending_report};
+use crate::systems::ui::pause_menu::{MenuButton, spawn_menu_panel};
+use crate::t;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Report lines shown on the page; the rest stays in the file
+const VISIBLE_REPORT_LINES: usize = 40;
+
+/// Report from the previous run that the player hasn't dismissed yet
+#[derive(Resource, Debug, Default)]
+pub struct PendingCrashReport(pub Option<PathBuf>);
+
+#[derive(Component)]
+pub struct CrashReportText;
+
+pub fn load_pending_crash_report(mut commands: Commands) {
+    commands.insert_resource(PendingCrashReport(pending_report(&crash_directory())));
+}
+
+/// Opens the menu on the crash page while a report is pending
+pub fn show_pending_crash_report(
+    pending: Res<PendingCrashReport>,
+    mut next_page: ResMut<NextState<MainMenuPage>>,
+) {
+    if pending.0.is_some() {
+        next_page.set(MainMenuPage::CrashReport);
+    }
+}
+
+pub fn spawn_crash_report_page(mut commands: Commands, pending: Res<PendingCrashReport>) {
+    let panel = spawn_menu_panel(
+        commands.reborrow(),
+        StateScoped(MainMenuPage::CrashReport),
+        "menu.title.crashed",
+        &[MenuButton::ViewCrashReport, MenuButton::DismissCrashReport],
+    );
+    let location = pending
+        .0
+        .as_ref()
+        .map_or_else(String::new, |path| path.display().to_string());
+    let body = commands
+        .spawn((
+            CrashReportText,
+            Text::new(t!("crash.saved_to", location = location)),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.85, 0.85, 0.9)),
+            Node {
+                max_width: Val::Px(900.0),
+                margin: UiRect::bottom(Val::Px(12.0)),
+                ..default()
+            },
+        ))
+        .id();
+    // Between the title and the buttons
+    commands.entity(panel).insert_children(1, &[body]);
+}
+
+pub fn crash_report_actions(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut pending: ResMut<PendingCrashReport>,
+    mut next_page: ResMut<NextState<MainMenuPage>>,
+    mut texts: Query<&mut Text, With<CrashReportText>>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            MenuButton::ViewCrashReport => {
+                let Some(path) = &pending.0 else {
+                    continue;
+                };
+                let contents = std::fs::read_to_string(path).unwrap_or_else(|err| {
+                    t!("crash.unreadable", path = path.display(), error = err)
+                });
+                let mut shown: Vec<&str> = contents.lines().take(VISIBLE_REPORT_LINES).collect();
+                let footer = t!("crash.full_report", path = path.display());
+                if contents.lines().count() > VISIBLE_REPORT_LINES {
+                    shown.push(&footer);
+                }
+                for mut text in &mut texts {
+                    **text = shown.join("\n");
+                }
+            }
+            MenuButton::DismissCrashReport => {
+                dismiss_pending_report(&crash_directory());
+                pending.0 = None;
+                next_page.set(MainMenuPage::Title);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,901 @@
+use crate::components::gameplay_events::MoneyChanged;
+use crate::components::vehicle_hud::{HudGear, simulated_gearbox};
+use crate::components::{
+    ActiveDialogue, ActiveEntity, AircraftFlight, AttitudeHorizon, ControlState, DeathFade,
+    DeathFadeText, DialogueBox, DialogueCatalog, DialogueChoiceButton, DialogueNode, Elevator,
+    ElevatorFloorButton, ElevatorPanel, ElevatorRider, F16, HealthBar, HealthBarFill, Interactable,
+    InteractionFocus, InteractionPrompt, MoneyCounter, MoneyDeltaText, Player, PlayerHealth,
+    RpmNeedle, SimpleCarSpecs, SimpleCarSpecsHandle, SubtitleText, Subtitles, ThrottleFill,
+    Transmission, TransmissionMode, VehicleControlType, VehicleHud, VehicleHudSection,
+    VehicleHudText, VehicleState, Wallet, Wasted,
+};
+use crate::config::{GameConfig, Indicator};
+use crate::resources::Localization;
+use crate::systems::garage::spawn_menu_button;
+use crate::systems::input::asset_based_controls::AssetControlAction;
+use crate::systems::input::{InputMap, LoadedVehicleControls, bound_keys};
+use crate::systems::player_health::death_fade_alpha;
+use crate::systems::ui::format_key_name;
+use crate::t;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+/// Full-screen overlay darkened by pilot blackout.
+/// Each ring is a thick black border; stacking them fades the edges in before the center.
+#[derive(Component)]
+pub struct BlackoutVignette {
+    /// Blackout level at which this ring becomes fully opaque
+    pub full_at: f32,
+}
+
+const FLOOR_BUTTON: Color = Color::srgb(0.15, 0.15, 0.18);
+const FLOOR_BUTTON_HOVERED: Color = Color::srgb(0.25, 0.25, 0.3);
+const FLOOR_BUTTON_SELECTED: Color = Color::srgb(0.75, 0.55, 0.15);
+
+/// Border width and full-opacity blackout of each ring, outermost first
+const VIGNETTE_RINGS: [(f32, f32); 3] = [(14.0, 0.5), (12.0, 0.8), (10.0, 1.0)];
+
+pub fn setup_blackout_vignette(mut commands: Commands) {
+    let mut parent = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            Pickable::IGNORE,
+        ))
+        .id();
+
+    for (width, full_at) in VIGNETTE_RINGS {
+        parent = commands
+            .spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    border: UiRect::all(Val::VMin(width)),
+                    ..default()
+                },
+                BorderColor(Color::NONE),
+                BackgroundColor(Color::NONE),
+                BlackoutVignette { full_at },
+                Pickable::IGNORE,
+                ChildOf(parent),
+            ))
+            .id();
+    }
+}
+
+/// Fades the vignette with the active F16's blackout; the innermost ring also
+/// dims the center so full blackout goes dark
+pub fn update_blackout_vignette(
+    f16_query: Query<&AircraftFlight, (With<F16>, With<ActiveEntity>)>,
+    mut rings: Query<(&BlackoutVignette, &mut BorderColor, &mut BackgroundColor)>,
+) {
+    let blackout = f16_query.single().map_or(0.0, |flight| flight.blackout);
+
+    for (ring, mut border, mut background) in &mut rings {
+        let alpha = (blackout / ring.full_at).clamp(0.0, 1.0);
+        border.0 = Color::srgba(0.0, 0.0, 0.0, alpha);
+        background.0 = if ring.full_at >= 1.0 {
+            Color::srgba(0.0, 0.0, 0.0, blackout.powi(3))
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+/// Shows the floor panel while the player rides an elevator and removes it once they step off
+pub fn elevator_panel_system(
+    mut commands: Commands,
+    player: Query<&ElevatorRider, With<Player>>,
+    cars: Query<&Elevator>,
+    panels: Query<Entity, With<ElevatorPanel>>,
+) {
+    let riding = player
+        .single()
+        .ok()
+        .and_then(|rider| Some((rider.elevator, cars.get(rider.elevator).ok()?)));
+    match riding {
+        Some((car, elevator)) if panels.is_empty() => {
+            spawn_elevator_panel(&mut commands, car, elevator)
+        }
+        None => {
+            for panel in &panels {
+                commands.entity(panel).despawn();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn spawn_elevator_panel(commands: &mut Commands, car: Entity, elevator: &Elevator) {
+    commands
+        .spawn((
+            ElevatorPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(120.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(t!("elevator.title")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            // Top floor first, like a real panel
+            for floor in (0..elevator.floors).rev() {
+                let label = if floor == 0 {
+                    t!("elevator.ground")
+                } else {
+                    floor.to_string()
+                };
+                parent
+                    .spawn((
+                        ElevatorFloorButton {
+                            elevator: car,
+                            floor,
+                        },
+                        Button,
+                        Node {
+                            width: Val::Px(48.0),
+                            height: Val::Px(32.0),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor(FLOOR_BUTTON),
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            Text::new(label),
+                            TextFont {
+                                font_size: 16.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+        });
+}
+
+/// Sends the car to the clicked floor and lights the selected button
+pub fn elevator_floor_buttons(
+    mut buttons: Query<(&Interaction, &ElevatorFloorButton, &mut BackgroundColor)>,
+    mut cars: Query<&mut Elevator>,
+) {
+    for (interaction, button, mut color) in &mut buttons {
+        let Ok(mut elevator) = cars.get_mut(button.elevator) else {
+            continue;
+        };
+        if *interaction == Interaction::Pressed {
+            elevator.target_floor = button.floor;
+        }
+        *color = if elevator.target_floor == button.floor {
+            FLOOR_BUTTON_SELECTED.into()
+        } else if *interaction == Interaction::Hovered {
+            FLOOR_BUTTON_HOVERED.into()
+        } else {
+            FLOOR_BUTTON.into()
+        };
+    }
+}
+
+/// Seconds the "+$N" line stays up
+const MONEY_DELTA_SECONDS: f32 = 2.5;
+
+/// The counter closes this share of the gap per second, but never slower
+/// than `MONEY_COUNT_MIN_RATE` dollars per second
+const MONEY_COUNT_CATCH_UP: f32 = 3.0;
+const MONEY_COUNT_MIN_RATE: f32 = 200.0;
+
+const MONEY_GREEN: Color = Color::srgb(0.45, 0.85, 0.35);
+const MONEY_RED: Color = Color::srgb(0.9, 0.3, 0.25);
+
+pub fn setup_money_counter(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(72.0),
+                right: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::End,
+                ..default()
+            },
+            Pickable::IGNORE,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("$0"),
+                TextFont {
+                    font_size: 26.0,
+                    ..default()
+                },
+                TextColor(MONEY_GREEN),
+                MoneyCounter::default(),
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(MONEY_GREEN),
+                MoneyDeltaText::default(),
+            ));
+        });
+}
+
+/// Counts the shown balance towards the wallet and flashes each change
+pub fn update_money_counter(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    wallet: Res<Wallet>,
+    mut changes: EventReader<MoneyChanged>,
+    mut counter: Query<(&mut Text, &mut MoneyCounter), Without<MoneyDeltaText>>,
+    mut delta: Query<(&mut Text, &mut TextColor, &mut MoneyDeltaText), Without<MoneyCounter>>,
+) {
+    let dt = time.delta_secs();
+    if let Ok((mut text, mut counter)) = counter.single_mut() {
+        let target = wallet.balance() as f32;
+        let gap = target - counter.shown;
+        let step = (gap.abs() * MONEY_COUNT_CATCH_UP).max(MONEY_COUNT_MIN_RATE) * dt;
+        counter.shown = if gap.abs() <= step {
+            target
+        } else {
+            counter.shown + step.copysign(gap)
+        };
+        let shown = format!("${}", counter.shown.round() as u64);
+        if text.0 != shown {
+            text.0 = shown;
+        }
+    }
+
+    let Ok((mut text, mut color, mut delta)) = delta.single_mut() else {
+        return;
+    };
+    let change: i64 = changes.read().map(MoneyChanged::delta).sum();
+    if change != 0 {
+        if delta.timer.finished() || delta.timer.duration().is_zero() {
+            delta.amount = 0;
+        }
+        delta.amount += change;
+        delta.timer = Timer::from_seconds(MONEY_DELTA_SECONDS, TimerMode::Once);
+        text.0 = if delta.amount >= 0 {
+            format!("+${}", delta.amount)
+        } else {
+            format!("-${}", delta.amount.unsigned_abs())
+        };
+    }
+    if delta.timer.duration().is_zero() {
+        return;
+    }
+    delta.timer.tick(time.delta());
+    let base = if delta.amount >= 0 {
+        config.accessibility.indicator(Indicator::Good, MONEY_GREEN)
+    } else {
+        config.accessibility.indicator(Indicator::Danger, MONEY_RED)
+    };
+    color.0 = base.with_alpha(delta.timer.fraction_remaining());
+    if delta.timer.just_finished() {
+        text.0.clear();
+    }
+}
+
+const HEALTH_GREEN: Color = Color::srgb(0.35, 0.8, 0.35);
+const HEALTH_RED: Color = Color::srgb(0.9, 0.2, 0.2);
+
+/// Below this fraction of health the bar turns red
+const LOW_HEALTH_FRACTION: f32 = 0.25;
+
+pub fn setup_health_bar(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(134.0),
+                right: Val::Px(10.0),
+                width: Val::Px(150.0),
+                height: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            BorderRadius::all(Val::Px(3.0)),
+            Pickable::IGNORE,
+            HealthBar,
+        ))
+        .with_children(|bar| {
+            bar.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(HEALTH_GREEN),
+                BorderRadius::all(Val::Px(2.0)),
+                HealthBarFill,
+            ));
+        });
+}
+
+pub fn update_health_bar(
+    config: Res<GameConfig>,
+    players: Query<&PlayerHealth, (With<Player>, Changed<PlayerHealth>)>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<HealthBarFill>>,
+) {
+    let Ok(health) = players.single() else {
+        return;
+    };
+    let Ok((mut node, mut color)) = fill_query.single_mut() else {
+        return;
+    };
+    let fraction = health.fraction();
+    node.width = Val::Percent(fraction * 100.0);
+    color.0 = if fraction < LOW_HEALTH_FRACTION {
+        config
+            .accessibility
+            .indicator(Indicator::Danger, HEALTH_RED)
+    } else {
+        config
+            .accessibility
+            .indicator(Indicator::Good, HEALTH_GREEN)
+    };
+}
+
+pub fn setup_death_fade(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::NONE),
+            GlobalZIndex(50),
+            Pickable::IGNORE,
+            DeathFade,
+        ))
+        .with_children(|fade| {
+            fade.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 64.0,
+                    ..default()
+                },
+                TextColor(Color::NONE),
+                DeathFadeText,
+            ));
+        });
+}
+
+/// Fades the screen out and back in around the player's death
+pub fn update_death_fade(
+    config: Res<GameConfig>,
+    players: Query<&Wasted, With<Player>>,
+    mut fade: Query<&mut BackgroundColor, With<DeathFade>>,
+    mut caption: Query<(&mut Text, &mut TextColor), With<DeathFadeText>>,
+) {
+    let alpha = players.single().map_or(0.0, |wasted| {
+        death_fade_alpha(&config.health, wasted.elapsed)
+    });
+    if let Ok(mut background) = fade.single_mut() {
+        if background.0.alpha() == alpha {
+            return;
+        }
+        background.0 = Color::srgba(0.0, 0.0, 0.0, alpha);
+    }
+    if let Ok((mut text, mut color)) = caption.single_mut() {
+        if alpha <= 0.0 {
+            text.0.clear();
+        } else if text.0.is_empty() {
+            text.0 = t!("health.wasted");
+        }
+        color.0 = HEALTH_RED.with_alpha(alpha);
+    }
+}
+
+pub fn setup_subtitle_text(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(90.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Pickable::IGNORE,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+                SubtitleText,
+            ));
+        });
+}
+
+/// One caption per line, on a dark backing while any are up
+pub fn update_subtitle_text(
+    subtitles: Res<Subtitles>,
+    mut text: Query<(&mut Text, &mut BackgroundColor), With<SubtitleText>>,
+) {
+    let Ok((mut text, mut background)) = text.single_mut() else {
+        return;
+    };
+    let caption = subtitles
+        .lines
+        .iter()
+        .map(|line| t!(&line.key))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.0 != caption {
+        background.0 = if caption.is_empty() {
+            Color::NONE
+        } else {
+            Color::srgba(0.0, 0.0, 0.0, 0.6)
+        };
+        text.0 = caption;
+    }
+}
+
+pub fn setup_interaction_prompt(mut commands: Commands) {
+    commands.spawn((
+        InteractionPrompt,
+        Text::new(""),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(110.0),
+            left: Val::Percent(40.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        Pickable::IGNORE,
+    ));
+}
+
+/// Name of the input that currently triggers Interact: the gamepad button
+/// while a pad is connected, otherwise the remapped or default key
+pub fn interact_key_label(
+    input_map: Option<&InputMap>,
+    controls: Option<&LoadedVehicleControls>,
+    gamepad_connected: bool,
+) -> String {
+    let action = AssetControlAction::Interact;
+    if gamepad_connected
+        && let Some(button) = input_map.and_then(|map| map.buttons_for(&action).first())
+    {
+        return format!("{button:?}");
+    }
+    bound_keys(input_map, controls, &VehicleControlType::Walking, &action)
+        .first()
+        .map_or_else(|| "?".to_string(), |key| format_key_name(*key))
+}
+
+/// Shows the prompt of whatever the interact key would use, if anything
+pub fn update_interaction_prompt(
+    focus: Res<InteractionFocus>,
+    interactables: Query<&Interactable>,
+    input_map: Option<Res<InputMap>>,
+    controls: Option<Res<LoadedVehicleControls>>,
+    gamepads: Query<(), With<Gamepad>>,
+    mut prompt: Query<(&mut Text, &mut Visibility), With<InteractionPrompt>>,
+) {
+    let Ok((mut text, mut visibility)) = prompt.single_mut() else {
+        return;
+    };
+    let Some(interactable) = focus.0.and_then(|target| interactables.get(target).ok()) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    let key = interact_key_label(
+        input_map.as_deref(),
+        controls.as_deref(),
+        !gamepads.is_empty(),
+    );
+    let shown = interactable.prompt.replace("{key}", &key);
+    if text.0 != shown {
+        text.0 = shown;
+    }
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+/// Rebuilds the dialogue box whenever the conversation moves to another node
+pub fn refresh_dialogue_box(
+    mut commands: Commands,
+    active: Res<ActiveDialogue>,
+    catalog: Res<DialogueCatalog>,
+    localization: Res<Localization>,
+    boxes: Query<Entity, With<DialogueBox>>,
+) {
+    if !active.is_changed() {
+        return;
+    }
+    for dialogue_box in &boxes {
+        commands.entity(dialogue_box).despawn();
+    }
+    let node = active.0.as_ref().and_then(|session| {
+        catalog
+            .tree(&session.tree)
+            .and_then(|tree| tree.node(&session.node))
+    });
+    if let Some(node) = node {
+        spawn_dialogue_box(&mut commands, node, &localization);
+    }
+}
+
+fn spawn_dialogue_box(commands: &mut Commands, node: &DialogueNode, localization: &Localization) {
+    commands
+        .spawn((
+            DialogueBox,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.0),
+                left: Val::Percent(25.0),
+                width: Val::Percent(50.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(localization.get(&node.speaker).to_string()),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.95, 0.8, 0.35)),
+            ));
+            parent.spawn((
+                Text::new(localization.get(&node.text).to_string()),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            for (index, choice) in node.choices.iter().enumerate() {
+                spawn_menu_button(
+                    parent,
+                    DialogueChoiceButton(index),
+                    420.0,
+                    format!("{}. {}", index + 1, localization.get(&choice.text)),
+                );
+            }
+        });
+}
+
+const HUD_DIAL_SIZE: f32 = 90.0;
+/// Needle sweep either side of straight up, degrees
+const RPM_SWEEP: f32 = 135.0;
+/// Attitude card pixels per degree of pitch, and the most it moves
+const HORIZON_PX_PER_DEGREE: f32 = 1.5;
+const HORIZON_MAX_SHIFT: f32 = 80.0;
+const REDLINE: f32 = 0.9;
+
+fn hud_text(kind: VehicleHudText, font_size: f32) -> impl Bundle {
+    (
+        Text::new(""),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        kind,
+    )
+}
+
+fn hud_section(section: VehicleHudSection) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(4.0),
+            display: Display::None,
+            ..default()
+        },
+        section,
+    )
+}
+
+/// Speedometer in the bottom-left corner, with a rev counter for cars, an
+/// attitude indicator for aircraft and a throttle bar for jets and boats
+pub fn setup_vehicle_hud(mut commands: Commands) {
+    let dial = Node {
+        width: Val::Px(HUD_DIAL_SIZE),
+        height: Val::Px(HUD_DIAL_SIZE),
+        border: UiRect::all(Val::Px(2.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        overflow: Overflow::clip(),
+        ..default()
+    };
+
+    commands
+        .spawn((
+            VehicleHud,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.0),
+                bottom: Val::Px(20.0),
+                align_items: AlignItems::End,
+                column_gap: Val::Px(14.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            BorderRadius::all(Val::Px(5.0)),
+            Visibility::Hidden,
+            Pickable::IGNORE,
+        ))
+        .with_children(|hud| {
+            hud.spawn(hud_text(VehicleHudText::Speed, 32.0));
+
+            hud.spawn(hud_section(VehicleHudSection::Drivetrain))
+                .with_children(|section| {
+                    section
+                        .spawn((
+                            dial.clone(),
+                            BorderColor(Color::WHITE),
+                            BorderRadius::MAX,
+                            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                        ))
+                        .with_children(|dial| {
+                            dial.spawn(hud_text(VehicleHudText::Gear, 28.0));
+                            // Full-size pivot so the needle turns about the dial's center
+                            dial.spawn((
+                                RpmNeedle,
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    width: Val::Percent(100.0),
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                            ))
+                            .with_child((
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Percent(50.0),
+                                    top: Val::Percent(6.0),
+                                    width: Val::Px(3.0),
+                                    height: Val::Percent(30.0),
+                                    margin: UiRect::left(Val::Px(-1.5)),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(0.95, 0.25, 0.2)),
+                            ));
+                        });
+                });
+
+            hud.spawn(hud_section(VehicleHudSection::Flight))
+                .with_children(|section| {
+                    section
+                        .spawn((dial.clone(), BorderColor(Color::WHITE)))
+                        .with_children(|dial| {
+                            dial.spawn((
+                                AttitudeHorizon,
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    width: Val::Px(HUD_DIAL_SIZE * 3.0),
+                                    height: Val::Px(HUD_DIAL_SIZE * 3.0),
+                                    left: Val::Px(-HUD_DIAL_SIZE),
+                                    top: Val::Px(-HUD_DIAL_SIZE),
+                                    flex_direction: FlexDirection::Column,
+                                    ..default()
+                                },
+                            ))
+                            .with_children(|card| {
+                                for color in
+                                    [Color::srgb(0.25, 0.5, 0.85), Color::srgb(0.5, 0.33, 0.18)]
+                                {
+                                    card.spawn((
+                                        Node {
+                                            width: Val::Percent(100.0),
+                                            height: Val::Percent(50.0),
+                                            ..default()
+                                        },
+                                        BackgroundColor(color),
+                                    ));
+                                }
+                            });
+                            // Fixed aircraft symbol
+                            dial.spawn((
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    width: Val::Percent(40.0),
+                                    height: Val::Px(3.0),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(1.0, 0.85, 0.1)),
+                            ));
+                        });
+                    section.spawn(hud_text(VehicleHudText::Altitude, 16.0));
+                    section.spawn(hud_text(VehicleHudText::Attitude, 14.0));
+                });
+
+            hud.spawn(hud_section(VehicleHudSection::Throttle))
+                .with_children(|section| {
+                    section.spawn(hud_text(VehicleHudText::Throttle, 16.0));
+                    section
+                        .spawn((
+                            Node {
+                                width: Val::Px(120.0),
+                                height: Val::Px(10.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.2)),
+                        ))
+                        .with_child((
+                            ThrottleFill,
+                            Node {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(1.0, 0.6, 0.15)),
+                        ));
+                });
+        });
+}
+
+/// Fills the vehicle HUD from whatever the player is in, switching sections
+/// when the vehicle type changes
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_vehicle_hud(
+    active: Query<
+        (
+            &VehicleState,
+            &Transform,
+            Option<&Velocity>,
+            Option<&ControlState>,
+            Option<&AircraftFlight>,
+            Option<(&Transmission, &SimpleCarSpecsHandle)>,
+        ),
+        With<ActiveEntity>,
+    >,
+    car_specs: Res<Assets<SimpleCarSpecs>>,
+    mut hud: Query<&mut Visibility, With<VehicleHud>>,
+    mut sections: Query<
+        (&VehicleHudSection, &mut Node),
+        (Without<AttitudeHorizon>, Without<ThrottleFill>),
+    >,
+    mut texts: Query<(&VehicleHudText, &mut Text)>,
+    mut needle: Query<
+        &mut Transform,
+        (
+            With<RpmNeedle>,
+            Without<ActiveEntity>,
+            Without<AttitudeHorizon>,
+        ),
+    >,
+    mut horizon: Query<
+        (&mut Node, &mut Transform),
+        (
+            With<AttitudeHorizon>,
+            Without<ActiveEntity>,
+            Without<VehicleHudSection>,
+            Without<ThrottleFill>,
+        ),
+    >,
+    mut throttle_fill: Query<
+        (&mut Node, &mut BackgroundColor),
+        (With<ThrottleFill>, Without<VehicleHudSection>),
+    >,
+) {
+    let Ok(mut visibility) = hud.single_mut() else {
+        return;
+    };
+    let Ok((state, transform, velocity, controls, flight, gearbox)) = active.single() else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    visibility.set_if_neq(Visibility::Inherited);
+
+    for (section, mut node) in &mut sections {
+        let display = if section.shown_for(state.vehicle_type) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+
+    let linvel = velocity.map_or(Vec3::ZERO, |v| v.linvel);
+    let gearbox = gearbox.and_then(|(gearbox, handle)| Some((gearbox, car_specs.get(&handle.0)?)));
+    let (gear, rpm) = match gearbox {
+        Some((gearbox, specs)) => (HudGear::from_gear(gearbox.gear), gearbox.rev_share(specs)),
+        None => simulated_gearbox(linvel.dot(*transform.forward()), state.max_speed),
+    };
+    let manual = gearbox.is_some_and(|(gearbox, _)| gearbox.mode == TransmissionMode::Manual);
+    let (_, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+    let throttle = flight
+        .map(|f| f.throttle)
+        .unwrap_or_else(|| controls.map_or(0.0, |c| (c.throttle - c.brake).clamp(-1.0, 1.0)));
+
+    for (kind, mut text) in &mut texts {
+        let shown = match kind {
+            VehicleHudText::Speed => {
+                t!("hud.speed", speed = (linvel.length() * 3.6).round() as i32)
+            }
+            // Manual boxes show an M before the gear
+            VehicleHudText::Gear if manual => format!("M{}", gear.label()),
+            VehicleHudText::Gear => gear.label(),
+            VehicleHudText::Altitude => {
+                t!(
+                    "hud.altitude",
+                    altitude = transform.translation.y.round() as i32
+                )
+            }
+            VehicleHudText::Attitude => t!(
+                "hud.attitude",
+                pitch = pitch.to_degrees().round() as i32,
+                bank = (-roll).to_degrees().round() as i32
+            ),
+            VehicleHudText::Throttle => {
+                t!("hud.throttle", throttle = (throttle * 100.0).round() as i32)
+            }
+        };
+        if text.0 != shown {
+            text.0 = shown;
+        }
+    }
+
+    if let Ok(mut needle) = needle.single_mut() {
+        let angle = -RPM_SWEEP + 2.0 * RPM_SWEEP * rpm;
+        needle.rotation = Quat::from_rotation_z(angle.to_radians());
+    }
+    if let Ok((mut node, mut card)) = horizon.single_mut() {
+        // Nose up drops the horizon; banking turns it the other way
+        let shift = (pitch.to_degrees() * HORIZON_PX_PER_DEGREE)
+            .clamp(-HORIZON_MAX_SHIFT, HORIZON_MAX_SHIFT);
+        node.top = Val::Px(-HUD_DIAL_SIZE + shift);
+        card.rotation = Quat::from_rotation_z(roll);
+    }
+    if let Ok((mut node, mut color)) = throttle_fill.single_mut() {
+        node.width = Val::Percent(throttle.abs() * 100.0);
+        color.0 = if throttle < 0.0 {
+            Color::srgb(0.6, 0.6, 0.65)
+        } else if throttle > REDLINE {
+            Color::srgb(0.95, 0.25, 0.2)
+        } else {
+            Color::srgb(1.0, 0.6, 0.15)
+        };
+    }
+}
@@ -0,0 +1,474 @@
+//! Runtime entity inspector overlay.
+//!
+//! F4 opens a panel listing the colliders under the cursor (or the crosshair
+//! when the cursor is off-screen) after a left click; `[` and `]` cycle
+//! through them. The selected entity's reflected components are listed with
+//! their field values, and numeric fields can be nudged live: PageUp/PageDown
+//! pick a field, `,` and `.` step it down or up (Shift for ten times the
+//! step). The selection is outlined with a gizmo box.
+//!
+//! Only components registered with the `AppTypeRegistry` show up.
+
+use crate::components::MainCamera;
+use bevy::prelude::*;
+use bevy::reflect::{GetPath, ReflectRef};
+use bevy::render::primitives::Aabb;
+use bevy::window::PrimaryWindow;
+use bevy_rapier3d::prelude::*;
+use std::any::TypeId;
+use std::fmt::Write;
+
+/// Farthest pick distance, in metres
+const PICK_DISTANCE: f32 = 500.0;
+/// Nested fields deeper than this are skipped
+const MAX_DEPTH: usize = 4;
+/// Field lines shown around the cursor
+const VISIBLE_FIELDS: usize = 28;
+const HIGHLIGHT: Color = Color::srgb(1.0, 0.85, 0.1);
+
+/// One reflected field of the selected entity
+#[derive(Debug, Clone)]
+pub struct InspectorField {
+    pub component: TypeId,
+    pub component_name: String,
+    /// Reflect path within the component, e.g. `.translation.x`
+    pub path: String,
+    pub value: String,
+    pub numeric: bool,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct InspectorState {
+    pub open: bool,
+    /// Entities along the last pick ray, nearest first
+    pub candidates: Vec<Entity>,
+    pub selected: Option<Entity>,
+    pub fields: Vec<InspectorField>,
+    /// Index into `fields`, always on a numeric field when there is one
+    pub cursor: usize,
+    /// Step applied to the field under the cursor on the next refresh
+    pending_step: Option<f64>,
+}
+
+impl InspectorState {
+    fn select(&mut self, entity: Option<Entity>) {
+        if self.selected != entity {
+            self.selected = entity;
+            self.cursor = 0;
+        }
+    }
+
+    /// Moves the cursor to the next numeric field in `direction`, wrapping around
+    fn move_cursor(&mut self, direction: isize) {
+        let len = self.fields.len() as isize;
+        for offset in 1..=len {
+            let index = (self.cursor as isize + direction * offset).rem_euclid(len) as usize;
+            if self.fields[index].numeric {
+                self.cursor = index;
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct InspectorUI;
+
+#[derive(Component)]
+pub struct InspectorText;
+
+pub fn setup_inspector(mut commands: Commands) {
+    commands
+        .spawn((
+            InspectorUI,
+            Name::new("Inspector"),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(10.0),
+                max_width: Val::Px(460.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            GlobalZIndex(150),
+        ))
+        .with_child((
+            InspectorText,
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        ));
+}
+
+pub fn inspector_keys(keys: Res<ButtonInput<KeyCode>>, mut state: ResMut<InspectorState>) {
+    if keys.just_pressed(KeyCode::F4) {
+        state.open = !state.open;
+    }
+    if !state.open {
+        return;
+    }
+
+    let cycle = match (
+        keys.just_pressed(KeyCode::BracketLeft),
+        keys.just_pressed(KeyCode::BracketRight),
+    ) {
+        (true, false) => -1,
+        (false, true) => 1,
+        _ => 0,
+    };
+    if cycle != 0 && !state.candidates.is_empty() {
+        let len = state.candidates.len() as isize;
+        let current = state
+            .selected
+            .and_then(|selected| state.candidates.iter().position(|c| *c == selected))
+            .unwrap_or(0) as isize;
+        let next = state.candidates[(current + cycle).rem_euclid(len) as usize];
+        state.select(Some(next));
+    }
+
+    if keys.just_pressed(KeyCode::PageUp) {
+        state.move_cursor(-1);
+    }
+    if keys.just_pressed(KeyCode::PageDown) {
+        state.move_cursor(1);
+    }
+
+    let scale = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        10.0
+    } else {
+        1.0
+    };
+    if keys.just_pressed(KeyCode::Comma) {
+        state.pending_step = Some(-scale);
+    }
+    if keys.just_pressed(KeyCode::Period) {
+        state.pending_step = Some(scale);
+    }
+}
+
+/// Left click casts a ray through the cursor, or the screen center, and
+/// lists every collider it passes through
+pub fn pick_inspected_entities(
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut state: ResMut<InspectorState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    rapier_context: ReadRapierContext,
+) {
+    if !state.open || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform)), Ok(context)) =
+        (windows.single(), cameras.single(), rapier_context.single())
+    else {
+        return;
+    };
+    let screen_point = window
+        .cursor_position()
+        .unwrap_or_else(|| window.size() * 0.5);
+    let Ok(ray) = camera.viewport_to_world(camera_transform, screen_point) else {
+        return;
+    };
+
+    let mut hits = Vec::new();
+    context.intersections_with_ray(
+        ray.origin,
+        *ray.direction,
+        PICK_DISTANCE,
+        true,
+        QueryFilter::default(),
+        |entity, intersection| {
+            hits.push((intersection.time_of_impact, entity));
+            true
+        },
+    );
+    hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+    state.candidates = hits.into_iter().map(|(_, entity)| entity).collect();
+    let nearest = state.candidates.first().copied();
+    state.select(nearest);
+}
+
+/// Appends the fields of `value` below `path`, recursing into structs and tuples
+fn collect_fields(
+    value: &dyn PartialReflect,
+    path: String,
+    depth: usize,
+    out: &mut Vec<(String, String, bool)>,
+) {
+    if depth < MAX_DEPTH {
+        match value.reflect_ref() {
+            ReflectRef::Struct(fields) => {
+                for index in 0..fields.field_len() {
+                    if let (Some(name), Some(field)) =
+                        (fields.name_at(index), fields.field_at(index))
+                    {
+                        collect_fields(field, format!("{path}.{name}"), depth + 1, out);
+                    }
+                }
+                return;
+            }
+            ReflectRef::TupleStruct(fields) => {
+                for (index, field) in fields.iter_fields().enumerate() {
+                    collect_fields(field, format!("{path}.{index}"), depth + 1, out);
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    let numeric = numeric_value(value).is_some();
+    out.push((path, format!("{value:?}"), numeric));
+}
+
+fn numeric_value(value: &dyn PartialReflect) -> Option<f64> {
+    macro_rules! try_as {
+        ($($ty:ty),*) => {
+            $(if let Some(v) = value.try_downcast_ref::<$ty>() {
+                return Some(*v as f64);
+            })*
+        };
+    }
+    try_as!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+    None
+}
+
+/// Adds `step` to a numeric field; floats move in tenths, integers in whole steps
+fn nudge(value: &mut dyn PartialReflect, step: f64) -> bool {
+    macro_rules! float {
+        ($($ty:ty),*) => {
+            $(if let Some(v) = value.try_downcast_mut::<$ty>() {
+                *v += (step * 0.1) as $ty;
+                return true;
+            })*
+        };
+    }
+    macro_rules! int {
+        ($($ty:ty),*) => {
+            $(if let Some(v) = value.try_downcast_mut::<$ty>() {
+                *v = (*v as f64 + step).clamp(<$ty>::MIN as f64, <$ty>::MAX as f64) as $ty;
+                return true;
+            })*
+        };
+    }
+    float!(f32, f64);
+    int!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+    false
+}
+
+/// Reflected fields of every registered component on `entity`
+pub fn inspect(world: &World, entity: Entity) -> Vec<InspectorField> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    let Ok(components) = world.inspect_entity(entity) else {
+        return Vec::new();
+    };
+    let mut fields = Vec::new();
+    for info in components {
+        let Some(type_id) = info.type_id() else {
+            continue;
+        };
+        let Some(registration) = registry.get(type_id) else {
+            continue;
+        };
+        let Some(reflect) = registration
+            .data::<ReflectComponent>()
+            .and_then(|component| component.reflect(entity_ref))
+        else {
+            continue;
+        };
+        let name = registration.type_info().type_path_table().short_path();
+        let mut out = Vec::new();
+        collect_fields(reflect.as_partial_reflect(), String::new(), 0, &mut out);
+        fields.extend(
+            out.into_iter()
+                .map(|(path, value, numeric)| InspectorField {
+                    component: type_id,
+                    component_name: name.to_string(),
+                    path,
+                    value,
+                    numeric,
+                }),
+        );
+    }
+    fields
+}
+
+/// Steps one numeric field of a component through reflection
+pub fn edit_field(world: &mut World, entity: Entity, field: &InspectorField, step: f64) -> bool {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let Some(component) = registry
+        .get(field.component)
+        .and_then(|registration| registration.data::<ReflectComponent>())
+    else {
+        return false;
+    };
+    let Ok(entity_mut) = world.get_entity_mut(entity) else {
+        return false;
+    };
+    let Some(mut reflect) = component.reflect_mut(entity_mut) else {
+        return false;
+    };
+    let value = if field.path.is_empty() {
+        Ok(reflect.as_partial_reflect_mut())
+    } else {
+        reflect.reflect_path_mut(field.path.as_str())
+    };
+    value.is_ok_and(|value| nudge(value, step))
+}
+
+/// Applies the pending edit and re-reads the selected entity's fields
+pub fn refresh_inspector(world: &mut World) {
+    let (open, selected, step, field) = {
+        let mut state = world.resource_mut::<InspectorState>();
+        let step = state.pending_step.take();
+        let field = state.fields.get(state.cursor).cloned();
+        (state.open, state.selected, step, field)
+    };
+    if !open {
+        return;
+    }
+    let Some(entity) = selected.filter(|entity| world.get_entity(*entity).is_ok()) else {
+        let mut state = world.resource_mut::<InspectorState>();
+        state.select(None);
+        state.fields.clear();
+        return;
+    };
+    if let (Some(step), Some(field)) = (step, field)
+        && field.numeric
+    {
+        edit_field(world, entity, &field, step);
+    }
+
+    let fields = inspect(world, entity);
+    let mut state = world.resource_mut::<InspectorState>();
+    state.fields = fields;
+    if state
+        .fields
+        .get(state.cursor)
+        .is_none_or(|field| !field.numeric)
+    {
+        state.move_cursor(1);
+    }
+}
+
+pub fn update_inspector_ui(
+    state: Res<InspectorState>,
+    names: Query<&Name>,
+    mut panels: Query<&mut Node, With<InspectorUI>>,
+    mut texts: Query<&mut Text, With<InspectorText>>,
+) {
+    for mut node in &mut panels {
+        let display = if state.open {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if node.display != display {
+            node.display = display;
+        }
+    }
+    if !state.open {
+        return;
+    }
+    let Ok(mut text) = texts.single_mut() else {
+        return;
+    };
+
+    let label = |entity: Entity| {
+        names
+            .get(entity)
+            .map_or_else(|_| format!("{entity}"), |name| format!("{name} ({entity})"))
+    };
+    let mut out = String::from("INSPECTOR  click: pick  [ ]: cycle  PgUp/PgDn: field  , .: edit\n");
+    if state.candidates.is_empty() {
+        out.push_str("Nothing picked\n");
+    }
+    for candidate in &state.candidates {
+        let marker = if Some(*candidate) == state.selected {
+            ">"
+        } else {
+            " "
+        };
+        let _ = writeln!(out, "{marker} {}", label(*candidate));
+    }
+
+    let start = state.cursor.saturating_sub(VISIBLE_FIELDS / 2);
+    let mut component = None;
+    for (index, field) in state
+        .fields
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(VISIBLE_FIELDS)
+    {
+        if component != Some(field.component) {
+            component = Some(field.component);
+            let _ = writeln!(out, "{}", field.component_name);
+        }
+        let marker = if index == state.cursor && field.numeric {
+            ">"
+        } else {
+            " "
+        };
+        let _ = writeln!(out, "{marker}  {} = {}", field.path, field.value);
+    }
+    if **text != out {
+        **text = out;
+    }
+}
+
+/// Outlines the selection with its mesh bounds, or a unit box without a mesh
+pub fn draw_inspector_selection(
+    state: Res<InspectorState>,
+    targets: Query<(&GlobalTransform, Option<&Aabb>)>,
+    mut gizmos: Gizmos,
+) {
+    if !state.open {
+        return;
+    }
+    let Some((transform, aabb)) = state.selected.and_then(|entity| targets.get(entity).ok()) else {
+        return;
+    };
+    let local = aabb.map_or(Transform::IDENTITY, |aabb| {
+        Transform::from_translation(aabb.center.into())
+            .with_scale(Vec3::from(aabb.half_extents) * 2.0)
+    });
+    gizmos.cuboid(transform.mul_transform(local), HIGHLIGHT);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_and_edits_reflected_fields() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Transform>();
+        let entity = world.spawn(Transform::from_xyz(1.0, 2.0, 3.0)).id();
+
+        let fields = inspect(&world, entity);
+        let x = fields
+            .iter()
+            .find(|field| field.path == ".translation.x")
+            .expect("translation.x listed");
+        assert!(x.numeric);
+        assert_eq!(x.component_name, "Transform");
+
+        assert!(edit_field(&mut world, entity, x, 10.0));
+        let translation = world.get::<Transform>(entity).unwrap().translation;
+        assert!((translation.x - 2.0).abs() < 1e-6);
+        assert_eq!(translation.y, 2.0);
+    }
+}
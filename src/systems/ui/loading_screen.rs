@@ -1,3 +1,7 @@
+use crate::plugins::static_world_generation_plugin::WorldGenProgress;
+use crate::states::AppState;
+use crate::systems::ui::localization::LocalizedText;
+use crate::t;
 use bevy::prelude::*;
 
 /// Marker component for loading screen UI
@@ -12,7 +16,7 @@ pub struct LoadingProgressText;
 #[derive(Component)]
 pub struct LoadingProgressBar;
 
-/// Create loading screen UI on entering Loading state
+/// Create loading screen UI on entering WorldGeneration state
 pub fn setup_loading_screen(mut commands: Commands) {
     commands
         .spawn((
@@ -30,7 +34,8 @@ pub fn setup_loading_screen(mut commands: Commands) {
         .with_children(|parent| {
             // Title
             parent.spawn((
-                Text::new("GTA-Style World Generation"),
+                Text::default(),
+                LocalizedText::new("loading.title"),
                 TextFont {
                     font_size: 48.0,
                     ..default()
@@ -45,7 +50,8 @@ pub fn setup_loading_screen(mut commands: Commands) {
             // Progress text
             parent.spawn((
                 LoadingProgressText,
-                Text::new("Initializing..."),
+                Text::default(),
+                LocalizedText::new("loading.initializing"),
                 TextFont {
                     font_size: 24.0,
                     ..default()
@@ -82,6 +88,16 @@ pub fn setup_loading_screen(mut commands: Commands) {
                         BackgroundColor(Color::srgb(0.2, 0.6, 0.9)),
                     ));
                 });
+
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new("loading.cancel_hint"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.5, 0.5, 0.6)),
+            ));
         });
 
     info!("Loading screen UI created");
@@ -91,40 +107,44 @@ pub fn setup_loading_screen(mut commands: Commands) {
 pub fn update_loading_progress(
     mut text_query: Query<&mut Text, With<LoadingProgressText>>,
     mut bar_query: Query<&mut Node, With<LoadingProgressBar>>,
-    queue: Option<Res<crate::plugins::static_world_generation_plugin::StaticGenerationQueue>>,
+    progress: Option<Res<WorldGenProgress>>,
 ) {
-    let Some(queue) = queue else {
+    let Some(progress) = progress else {
         return;
     };
-
-    let progress = (queue.completed_chunks as f32 / queue.total_chunks as f32) * 100.0;
-    let elapsed = queue.start_time.elapsed().as_secs_f32();
-    let rate = if elapsed > 0.0 {
-        queue.completed_chunks as f32 / elapsed
-    } else {
-        0.0
-    };
-    let eta = if rate > 0.0 {
-        (queue.total_chunks - queue.completed_chunks) as f32 / rate
-    } else {
-        0.0
-    };
+    let percent = progress.fraction() * 100.0;
+    let eta = progress
+        .eta_secs()
+        .map_or_else(|| "--".to_string(), |eta| format!("{eta:.0}s"));
 
     // Update text
     if let Ok(mut text) = text_query.single_mut() {
-        **text = format!(
-            "Generating World: {}/{} chunks ({:.1}%)\n{:.0} chunks/sec - ETA: {:.0}s",
-            queue.completed_chunks, queue.total_chunks, progress, rate, eta
+        **text = t!(
+            "loading.progress",
+            chunks = progress.completed_chunks,
+            percent = format!("{percent:.1}"),
+            rate = format!("{:.0}", progress.chunks_per_second()),
+            eta = eta,
         );
     }
 
     // Update progress bar
     if let Ok(mut bar_node) = bar_query.single_mut() {
-        bar_node.width = Val::Percent(progress);
+        bar_node.width = Val::Percent(percent);
+    }
+}
+
+/// Esc abandons generation and returns to the main menu
+pub fn cancel_world_generation(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
     }
 }
 
-/// Cleanup loading screen when entering InGame state
+/// Cleanup loading screen when leaving WorldGeneration state
 pub fn cleanup_loading_screen(
     mut commands: Commands,
     loading_ui_query: Query<Entity, With<LoadingScreenUI>>,
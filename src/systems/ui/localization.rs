@@ -0,0 +1,191 @@
+//! UI language and font.
+//!
+//! The `Localization` resource holds the strings of the current language and
+//! `t!` reads them. Static labels carry a `LocalizedText` key so switching the
+//! language from the settings menu rewrites them in place; labels rebuilt by
+//! their own systems just call `t!` again. The UI font follows the language,
+//! picked from `GameConfig::localization`'s fallback list. Hot reloading
+//! `localization.ron` switches the same way, unless the language was picked
+//! in the menu.
+
+use crate::config::{ConfigFieldChanged, GameConfig};
+use crate::resources::Localization;
+use bevy::prelude::*;
+use std::path::Path;
+
+/// Text whose content is the translation of this key
+#[derive(Component, Debug, Clone)]
+pub struct LocalizedText(pub String);
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+}
+
+/// Font used for all UI text; the default handle is Bevy's built-in font
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct UiFont(pub Handle<Font>);
+
+/// Language from the config files, before any pick in the settings menu
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfiguredLanguage(pub String);
+
+/// `GTA_LANG` if set, otherwise the configured language
+pub fn startup_language(config: &GameConfig) -> String {
+    std::env::var("GTA_LANG")
+        .ok()
+        .filter(|language| !language.is_empty())
+        .unwrap_or_else(|| config.localization.language.clone())
+}
+
+pub fn load_localization(mut commands: Commands, config: Res<GameConfig>) {
+    let localization = Localization::load(&startup_language(&config));
+    localization.make_current();
+    info!("🌐 UI language: {}", localization.language);
+    commands.insert_resource(localization);
+    commands.insert_resource(ConfiguredLanguage(config.localization.language.clone()));
+}
+
+/// Switches the UI to `language`; used by the settings menu
+pub fn switch_language(localization: &mut Localization, language: &str) {
+    *localization = Localization::load(language);
+    localization.make_current();
+    info!("🌐 UI language: {}", language);
+}
+
+/// Follows a hot reload of `localization.ron`: a new language is switched to
+/// as if picked in the menu, and edited font lists are looked up again
+pub fn apply_reloaded_localization(
+    mut changed: EventReader<ConfigFieldChanged>,
+    config: Res<GameConfig>,
+    mut localization: ResMut<Localization>,
+) {
+    let mut reloaded = false;
+    let mut language_changed = false;
+    for change in changed
+        .read()
+        .filter(|change| change.file == "localization.ron")
+    {
+        reloaded = true;
+        language_changed |= change.path == "language";
+    }
+    if language_changed && localization.language != config.localization.language {
+        switch_language(&mut localization, &config.localization.language);
+    } else if reloaded {
+        localization.set_changed();
+    }
+}
+
+pub fn update_localized_text(
+    localization: Res<Localization>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    for (key, mut text) in &mut texts {
+        if !localization.is_changed() && !key.is_changed() {
+            continue;
+        }
+        let translated = localization.get(&key.0);
+        if text.0 != translated {
+            text.0 = translated.to_string();
+        }
+    }
+}
+
+/// Picks the first configured font file that exists for the current language
+pub fn select_ui_font(
+    config: Res<GameConfig>,
+    localization: Res<Localization>,
+    asset_server: Res<AssetServer>,
+    mut font: ResMut<UiFont>,
+) {
+    if !localization.is_changed() {
+        return;
+    }
+    let assets = crate::util::asset_path::get_assets_base_path();
+    let handle = config
+        .localization
+        .font_candidates(&localization.language)
+        .find(|path| Path::new(&assets).join(path).is_file())
+        .map_or_else(Handle::default, |path| asset_server.load(path.clone()));
+    font.set_if_neq(UiFont(handle));
+}
+
+pub fn apply_ui_font(font: Res<UiFont>, mut texts: Query<&mut TextFont>) {
+    for mut text_font in &mut texts {
+        if (font.is_changed() || text_font.is_added()) && text_font.font != font.0 {
+            text_font.font = font.0.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::config_reload::{
+        ConfigReloadRequest, hot_reload_config, layer_config_files,
+    };
+    use crate::systems::profiles::ActiveProfile;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_localized_text_follows_language() {
+        let mut app = App::new();
+        app.insert_resource(Localization::new(
+            "en",
+            HashMap::from([("menu.back".to_string(), "Back".to_string())]),
+        ))
+        .add_systems(Update, update_localized_text);
+        let label = app
+            .world_mut()
+            .spawn((LocalizedText::new("menu.back"), Text::default()))
+            .id();
+        app.update();
+        assert_eq!(app.world().get::<Text>(label).unwrap().0, "Back");
+
+        app.insert_resource(Localization::new(
+            "es",
+            HashMap::from([("menu.back".to_string(), "Volver".to_string())]),
+        ));
+        app.update();
+        assert_eq!(app.world().get::<Text>(label).unwrap().0, "Volver");
+    }
+
+    #[test]
+    fn test_hot_reload_switches_language_unless_picked_in_menu() {
+        let mut config = GameConfig::default();
+        layer_config_files(&mut config, None);
+        config.validate_and_clamp();
+        let configured = config.localization.language.clone();
+
+        // Picked in the menu: a reload keeps it
+        let mut app = App::new();
+        app.insert_resource(ConfiguredLanguage(configured.clone()))
+            .insert_resource(Localization::new("es", HashMap::new()))
+            .init_resource::<ActiveProfile>()
+            .add_event::<ConfigReloadRequest>()
+            .add_event::<ConfigFieldChanged>()
+            .add_systems(
+                Update,
+                (hot_reload_config, apply_reloaded_localization).chain(),
+            );
+        config.localization.language = "es".to_string();
+        app.insert_resource(config);
+        app.world_mut().send_event(ConfigReloadRequest);
+        app.update();
+        let world = app.world();
+        assert_eq!(world.resource::<GameConfig>().localization.language, "es");
+        assert_eq!(world.resource::<Localization>().language, "es");
+
+        // Edited in the file: the UI follows
+        app.insert_resource(Localization::new(configured.clone(), HashMap::new()));
+        app.world_mut().send_event(ConfigFieldChanged {
+            file: "localization.ron",
+            path: "language".to_string(),
+            before: configured.into(),
+            after: "es".into(),
+        });
+        app.update();
+        assert_eq!(app.world().resource::<Localization>().language, "es");
+    }
+}
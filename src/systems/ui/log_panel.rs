@@ -0,0 +1,101 @@
+//! F8 panel showing the most recent log lines captured by the logging layer.
+
+use crate::systems::logging::LogControl;
+use bevy::log::Level;
+use bevy::prelude::*;
+
+/// Lines shown at once, newest at the bottom
+const PANEL_LINES: usize = 24;
+
+#[derive(Component)]
+pub struct LogPanel;
+
+#[derive(Component)]
+pub struct LogPanelText;
+
+pub fn setup_log_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            LogPanel,
+            Name::new("LogPanel"),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                max_height: Val::Percent(45.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::FlexEnd,
+                padding: UiRect::all(Val::Px(6.0)),
+                overflow: Overflow::clip(),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            GlobalZIndex(190),
+        ))
+        .with_child((
+            LogPanelText,
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 0.85, 0.8)),
+        ));
+}
+
+pub fn toggle_log_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut panels: Query<&mut Node, With<LogPanel>>,
+) {
+    if !keys.just_pressed(KeyCode::F8) {
+        return;
+    }
+    for mut node in &mut panels {
+        node.display = match node.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+/// Rebuilds the text when the panel is open and new lines have arrived; the
+/// text color follows the worst level on screen
+pub fn update_log_panel(
+    control: Option<Res<LogControl>>,
+    panels: Query<&Node, With<LogPanel>>,
+    mut texts: Query<(&mut Text, &mut TextColor), With<LogPanelText>>,
+    mut shown: Local<Option<u64>>,
+) {
+    let Some(control) = control else {
+        return;
+    };
+    if panels.iter().all(|node| node.display == Display::None) {
+        *shown = None;
+        return;
+    }
+    let Ok((mut text, mut color)) = texts.single_mut() else {
+        return;
+    };
+
+    let (lines, worst) = {
+        let Ok(sink) = control.sink().lock() else {
+            return;
+        };
+        if *shown == Some(sink.total()) {
+            return;
+        }
+        *shown = Some(sink.total());
+        let recent: Vec<_> = sink.lines().rev().take(PANEL_LINES).collect();
+        let worst = recent.iter().map(|line| line.level).min();
+        let lines: Vec<String> = recent.iter().rev().map(|line| line.to_string()).collect();
+        (lines, worst)
+    };
+
+    **text = lines.join("\n");
+    color.0 = match worst {
+        Some(Level::ERROR) => Color::srgb(1.0, 0.55, 0.5),
+        Some(Level::WARN) => Color::srgb(1.0, 0.9, 0.5),
+        _ => Color::srgb(0.8, 0.85, 0.8),
+    };
+}
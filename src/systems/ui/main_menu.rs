@@ -0,0 +1,234 @@
+//! Main menu shown after asset loading and when world generation is cancelled.
+//!
+//! New Game asks for a world seed (typed digits or a random one), Continue
+//! regenerates the world from the latest save's seed and restores the save
+//! once gameplay starts, and Settings reuses the pause menu's screen. Profile
+//! cycles through the player profiles and New Profile starts a fresh one.
+//! Pages are `MainMenuPage` sub-states built from the pause menu's panels.
+
+use crate::resources::{WorldRng, WorldSeed};
+use crate::states::{AppState, MainMenuPage};
+use crate::systems::persistence::{PendingLoad, latest_save_slot, parse_save, slot_path};
+use crate::systems::profiles::{
+    ActiveProfile, ProfileChangedEvent, list_profiles, next_profile, unused_profile_name,
+};
+use crate::systems::ui::pause_menu::{MenuButton, SETTINGS_BUTTONS, spawn_menu_panel};
+use bevy::prelude::*;
+
+const DIGIT_KEYS: [KeyCode; 10] = [
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Seed being entered on the New Game page
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SeedEntry(pub u64);
+
+impl SeedEntry {
+    /// Appends a decimal digit; ignored once the seed would overflow
+    pub fn push_digit(&mut self, digit: u64) {
+        if let Some(seed) = self.0.checked_mul(10).and_then(|s| s.checked_add(digit)) {
+            self.0 = seed;
+        }
+    }
+
+    pub fn pop_digit(&mut self) {
+        self.0 /= 10;
+    }
+}
+
+/// Most recently written save slot, looked up when the menu opens
+#[derive(Resource, Debug, Default)]
+pub struct LatestSave(pub Option<u32>);
+
+/// Camera for the menu UI, drawn over the world camera if one exists
+#[derive(Component)]
+pub struct MenuCamera;
+
+/// Looks up the save for Continue and starts seed entry from the current seed
+pub fn open_main_menu(mut commands: Commands, seed: Res<WorldSeed>, profile: Res<ActiveProfile>) {
+    commands.insert_resource(LatestSave(latest_save_slot(&profile.data_directory())));
+    commands.insert_resource(SeedEntry(seed.0));
+    commands.spawn((
+        MenuCamera,
+        Camera2d,
+        Camera {
+            order: 1,
+            ..default()
+        },
+    ));
+}
+
+pub fn close_main_menu(mut commands: Commands, cameras: Query<Entity, With<MenuCamera>>) {
+    for camera in &cameras {
+        commands.entity(camera).despawn();
+    }
+}
+
+pub fn spawn_title_page(commands: Commands) {
+    spawn_menu_panel(
+        commands,
+        StateScoped(MainMenuPage::Title),
+        "menu.title.game",
+        &[
+            MenuButton::NewGame,
+            MenuButton::Continue,
+            MenuButton::Settings,
+            MenuButton::Profile,
+            MenuButton::NewProfile,
+            MenuButton::Quit,
+        ],
+    );
+}
+
+pub fn spawn_new_game_page(commands: Commands) {
+    spawn_menu_panel(
+        commands,
+        StateScoped(MainMenuPage::NewGame),
+        "menu.title.new_game",
+        &[
+            MenuButton::RandomSeed,
+            MenuButton::StartGame,
+            MenuButton::Back,
+        ],
+    );
+}
+
+pub fn spawn_settings_page(commands: Commands) {
+    spawn_menu_panel(
+        commands,
+        StateScoped(MainMenuPage::Settings),
+        "menu.title.settings",
+        &SETTINGS_BUTTONS,
+    );
+}
+
+/// Digits and Backspace edit the seed on the New Game page; Esc steps back to the title
+pub fn main_menu_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    page: Res<State<MainMenuPage>>,
+    mut next_page: ResMut<NextState<MainMenuPage>>,
+    mut seed: ResMut<SeedEntry>,
+) {
+    if keys.just_pressed(KeyCode::Escape) && *page.get() != MainMenuPage::Title {
+        next_page.set(MainMenuPage::Title);
+    }
+    if *page.get() != MainMenuPage::NewGame {
+        return;
+    }
+    for (digit, key) in DIGIT_KEYS.iter().enumerate() {
+        if keys.just_pressed(*key) {
+            seed.push_digit(digit as u64);
+        }
+    }
+    if keys.just_pressed(KeyCode::Backspace) {
+        seed.pop_digit();
+    }
+}
+
+/// Buttons that start a game; navigation is handled by `menu_button_system`
+#[allow(clippy::too_many_arguments)]
+pub fn main_menu_actions(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut seed_entry: ResMut<SeedEntry>,
+    latest_save: Res<LatestSave>,
+    profile: Res<ActiveProfile>,
+    mut world_seed: ResMut<WorldSeed>,
+    mut world_rng: ResMut<WorldRng>,
+    mut pending_load: ResMut<PendingLoad>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let seed = match button {
+            MenuButton::RandomSeed => {
+                seed_entry.0 = rand::random::<u32>() as u64;
+                continue;
+            }
+            MenuButton::StartGame => {
+                pending_load.0 = None;
+                seed_entry.0
+            }
+            MenuButton::Continue => {
+                let Some(slot) = latest_save.0 else {
+                    continue;
+                };
+                let path = slot_path(&profile.data_directory(), slot);
+                match std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|contents| parse_save(&contents))
+                {
+                    Ok(save) => {
+                        pending_load.0 = Some(slot);
+                        save.world_seed
+                    }
+                    Err(e) => {
+                        error!("Failed to read save '{}': {}", path.display(), e);
+                        continue;
+                    }
+                }
+            }
+            _ => continue,
+        };
+        info!("🌱 World seed: {seed}");
+        *world_seed = WorldSeed(seed);
+        *world_rng = WorldRng::new(seed);
+        next_state.set(AppState::WorldGeneration);
+    }
+}
+
+/// Profile and New Profile switch the active profile
+pub fn switch_profile(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut active: ResMut<ActiveProfile>,
+    mut changed: EventWriter<ProfileChangedEvent>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let profiles = list_profiles(&active);
+        let profile = match button {
+            MenuButton::Profile => next_profile(&active.0, &profiles),
+            MenuButton::NewProfile => unused_profile_name(&profiles),
+            _ => continue,
+        };
+        match active.switch(&profile) {
+            Ok(()) => {
+                info!("👤 Profile: {profile}");
+                changed.write(ProfileChangedEvent { profile });
+            }
+            Err(e) => error!("Failed to switch to profile '{}': {}", profile, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_entry_digits() {
+        let mut seed = SeedEntry::default();
+        for digit in [4, 2, 7] {
+            seed.push_digit(digit);
+        }
+        assert_eq!(seed, SeedEntry(427));
+        seed.pop_digit();
+        assert_eq!(seed, SeedEntry(42));
+
+        let mut full = SeedEntry(u64::MAX / 10);
+        full.push_digit(9);
+        assert_eq!(full, SeedEntry(u64::MAX / 10));
+    }
+}
@@ -1,8 +1,19 @@
+pub mod console;
 pub mod controls_ui;
+pub mod crash_dialog;
 pub mod fps_display;
+pub mod gameplay_ui;
+pub mod inspector;
 pub mod loading_screen;
+pub mod localization;
+pub mod log_panel;
+pub mod main_menu;
+pub mod pause_menu;
+pub mod performance_hud;
 pub mod splash_screen;
 
 pub use controls_ui::*;
 pub use fps_display::*;
+pub use gameplay_ui::*;
+pub use localization::LocalizedText;
 pub use splash_screen::*;
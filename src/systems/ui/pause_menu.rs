@@ -0,0 +1,588 @@
+//! Pause menu: resume/quit, graphics, language and accessibility settings and
+//! control remapping.
+//!
+//! Each `MenuState` screen is a state-scoped panel of `MenuButton`s. The main
+//! menu builds its pages from the same panel and buttons, and shares the
+//! settings screen. Graphics
+//! settings are written to `GameConfig::graphics` and announced with a
+//! `ConfigReloadedEvent`, which `apply_graphics_settings` answers by updating
+//! the window and sun; accessibility settings are saved to the profile's data
+//! directory as they change. Rebinding edits the `InputMap` and saves it to the active
+//! profile. Labels are
+//! localization keys, translated every frame so a language switch shows at once.
+
+use crate::config::{ConfigReloadedEvent, GameConfig};
+use crate::game_state::{GameState, GameStateTransitions};
+use crate::resources::Localization;
+use crate::states::{MainMenuPage, MenuState};
+use crate::systems::accessibility::save_accessibility;
+use crate::systems::input::asset_based_controls::AssetControlAction;
+use crate::systems::input::{InputMap, save_input_map};
+use crate::systems::profiles::ActiveProfile;
+use crate::systems::ui::localization::{LocalizedText, switch_language};
+use crate::systems::ui::main_menu::{LatestSave, SeedEntry};
+use crate::t;
+use bevy::app::AppExit;
+use bevy::pbr::{CascadeShadowConfig, CascadeShadowConfigBuilder, DirectionalLightShadowMap};
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow, WindowResized};
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.18);
+const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.3);
+const PRESSED_BUTTON: Color = Color::srgb(0.2, 0.5, 0.8);
+
+/// Resolutions offered by the settings screen, cycled in order
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+
+/// UI scale factors offered by the settings screen, cycled in order
+pub const UI_SCALES: [f32; 5] = [0.75, 1.0, 1.25, 1.5, 2.0];
+
+/// Actions shown on the controls screen
+const REMAPPABLE_ACTIONS: [AssetControlAction; 14] = [
+    AssetControlAction::Forward,
+    AssetControlAction::Backward,
+    AssetControlAction::TurnLeft,
+    AssetControlAction::TurnRight,
+    AssetControlAction::Brake,
+    AssetControlAction::EmergencyBrake,
+    AssetControlAction::Turbo,
+    AssetControlAction::ThrottleUp,
+    AssetControlAction::ThrottleDown,
+    AssetControlAction::VerticalUp,
+    AssetControlAction::VerticalDown,
+    AssetControlAction::Run,
+    AssetControlAction::Interact,
+    AssetControlAction::CycleCamera,
+];
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum MenuButton {
+    Resume,
+    Settings,
+    Controls,
+    Quit,
+    Back,
+    Resolution,
+    VSync,
+    Quality,
+    Language,
+    UiScale,
+    ColorblindMode,
+    Subtitles,
+    CameraShake,
+    Rebind(AssetControlAction),
+    ResetBindings,
+    NewGame,
+    Continue,
+    StartGame,
+    RandomSeed,
+    Profile,
+    NewProfile,
+    ViewCrashReport,
+    DismissCrashReport,
+}
+
+/// Graphics, language and accessibility options, shown from both the pause
+/// menu and the main menu
+pub const SETTINGS_BUTTONS: [MenuButton; 9] = [
+    MenuButton::Resolution,
+    MenuButton::VSync,
+    MenuButton::Quality,
+    MenuButton::Language,
+    MenuButton::UiScale,
+    MenuButton::ColorblindMode,
+    MenuButton::Subtitles,
+    MenuButton::CameraShake,
+    MenuButton::Back,
+];
+
+/// Action waiting for its next key press on the controls screen
+#[derive(Resource, Debug, Default)]
+pub struct PendingRebind(pub Option<AssetControlAction>);
+
+/// Esc opens the menu, steps back from sub-screens and cancels a pending rebind
+pub fn toggle_pause_menu(
+    keys: Res<ButtonInput<KeyCode>>,
+    state: Res<State<MenuState>>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    mut pending: ResMut<PendingRebind>,
+) {
+    if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    if pending.0.take().is_some() {
+        return;
+    }
+    next_state.set(match state.get() {
+        MenuState::Closed => MenuState::Paused,
+        MenuState::Paused => MenuState::Closed,
+        MenuState::Settings | MenuState::Controls => MenuState::Paused,
+    });
+}
+
+pub fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+pub fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+/// Lays `GameState::Paused` over whatever the player was doing
+pub fn push_paused_state(mut transitions: GameStateTransitions) {
+    if let Err(error) = transitions.push(GameState::Paused) {
+        warn!("Couldn't pause: {error}");
+    }
+}
+
+/// Hands back the state the pause menu was opened over. Also runs when a
+/// game starts with the menu closed, where there is nothing to pop.
+pub fn pop_paused_state(mut transitions: GameStateTransitions) {
+    if transitions.current() == GameState::Paused
+        && let Err(error) = transitions.pop()
+    {
+        warn!("Couldn't unpause: {error}");
+    }
+}
+
+pub fn spawn_pause_menu(commands: Commands) {
+    spawn_menu_panel(
+        commands,
+        StateScoped(MenuState::Paused),
+        "menu.title.paused",
+        &[
+            MenuButton::Resume,
+            MenuButton::Settings,
+            MenuButton::Controls,
+            MenuButton::Quit,
+        ],
+    );
+}
+
+pub fn spawn_settings_menu(commands: Commands) {
+    spawn_menu_panel(
+        commands,
+        StateScoped(MenuState::Settings),
+        "menu.title.settings",
+        &SETTINGS_BUTTONS,
+    );
+}
+
+pub fn spawn_controls_menu(commands: Commands) {
+    let mut buttons: Vec<MenuButton> = REMAPPABLE_ACTIONS
+        .iter()
+        .map(|action| MenuButton::Rebind(*action))
+        .collect();
+    buttons.extend([MenuButton::ResetBindings, MenuButton::Back]);
+    spawn_menu_panel(
+        commands,
+        StateScoped(MenuState::Controls),
+        "menu.title.controls",
+        &buttons,
+    );
+}
+
+/// `scope` marks the panel so it can be torn down with its screen and `title`
+/// is a localization key; returns the panel, whose first child is the title
+pub(crate) fn spawn_menu_panel(
+    mut commands: Commands,
+    scope: impl Bundle,
+    title: &str,
+    buttons: &[MenuButton],
+) -> Entity {
+    commands
+        .spawn((
+            scope,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(100),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                LocalizedText::new(title),
+                TextFont {
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.95)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+
+            for button in buttons {
+                parent
+                    .spawn((
+                        *button,
+                        Button,
+                        Node {
+                            width: Val::Px(360.0),
+                            height: Val::Px(36.0),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor(NORMAL_BUTTON),
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            Text::new(""),
+                            TextFont {
+                                font_size: 18.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+        })
+        .id()
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn menu_button_system(
+    mut buttons: Query<(&Interaction, &MenuButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    main_menu: Option<Res<State<MainMenuPage>>>,
+    mut next_page: ResMut<NextState<MainMenuPage>>,
+    mut config: ResMut<GameConfig>,
+    mut localization: ResMut<Localization>,
+    profile: Res<ActiveProfile>,
+    mut input_map: Option<ResMut<InputMap>>,
+    mut pending: ResMut<PendingRebind>,
+    mut exit: EventWriter<AppExit>,
+    mut reloaded: EventWriter<ConfigReloadedEvent>,
+) {
+    let mut accessibility_changed = false;
+    for (interaction, button, mut color) in &mut buttons {
+        *color = match interaction {
+            Interaction::Pressed => PRESSED_BUTTON.into(),
+            Interaction::Hovered => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            MenuButton::Resume => next_state.set(MenuState::Closed),
+            MenuButton::Settings if main_menu.is_some() => next_page.set(MainMenuPage::Settings),
+            MenuButton::Settings => next_state.set(MenuState::Settings),
+            MenuButton::Controls => next_state.set(MenuState::Controls),
+            MenuButton::Back if main_menu.is_some() => next_page.set(MainMenuPage::Title),
+            MenuButton::Back => {
+                pending.0 = None;
+                next_state.set(MenuState::Paused);
+            }
+            MenuButton::NewGame => next_page.set(MainMenuPage::NewGame),
+            MenuButton::Quit => {
+                exit.write(AppExit::Success);
+            }
+            MenuButton::Resolution => {
+                config.graphics.resolution = next_resolution(config.graphics.resolution);
+                reloaded.write(ConfigReloadedEvent);
+            }
+            MenuButton::VSync => {
+                config.graphics.vsync = !config.graphics.vsync;
+                reloaded.write(ConfigReloadedEvent);
+            }
+            MenuButton::Quality => {
+                config.graphics.quality = config.graphics.quality.next();
+                reloaded.write(ConfigReloadedEvent);
+            }
+            MenuButton::Language => {
+                let language = config.localization.next_language(&localization.language);
+                switch_language(&mut localization, &language);
+                config.localization.language = language;
+            }
+            MenuButton::UiScale => {
+                config.accessibility.ui_scale = next_ui_scale(config.accessibility.ui_scale);
+                accessibility_changed = true;
+            }
+            MenuButton::ColorblindMode => {
+                config.accessibility.colorblind_mode = config.accessibility.colorblind_mode.next();
+                accessibility_changed = true;
+            }
+            MenuButton::Subtitles => {
+                config.accessibility.subtitles = !config.accessibility.subtitles;
+                accessibility_changed = true;
+            }
+            MenuButton::CameraShake => {
+                config.accessibility.reduced_camera_shake =
+                    !config.accessibility.reduced_camera_shake;
+                accessibility_changed = true;
+            }
+            MenuButton::Rebind(action) => pending.0 = Some(*action),
+            MenuButton::ResetBindings => {
+                if let Some(input_map) = input_map.as_mut() {
+                    for action in REMAPPABLE_ACTIONS {
+                        input_map.clear_keys(action);
+                    }
+                    save_input_map(&profile.data_directory(), input_map);
+                }
+                pending.0 = None;
+            }
+            // Starting a game touches the seed and save state; see `main_menu_actions`
+            MenuButton::Continue | MenuButton::StartGame | MenuButton::RandomSeed => {}
+            // Handled by `switch_profile`
+            MenuButton::Profile | MenuButton::NewProfile => {}
+            // Handled by `crash_report_actions`
+            MenuButton::ViewCrashReport | MenuButton::DismissCrashReport => {}
+        }
+    }
+
+    if accessibility_changed {
+        save_accessibility(&profile.data_directory(), &config.accessibility);
+        reloaded.write(ConfigReloadedEvent);
+    }
+}
+
+/// Next entry in `RESOLUTIONS`; unknown sizes restart the cycle
+pub fn next_resolution(current: (u32, u32)) -> (u32, u32) {
+    RESOLUTIONS
+        .iter()
+        .position(|r| *r == current)
+        .map_or(RESOLUTIONS[0], |i| RESOLUTIONS[(i + 1) % RESOLUTIONS.len()])
+}
+
+/// Next entry in `UI_SCALES` after `current`, wrapping to the smallest
+pub fn next_ui_scale(current: f32) -> f32 {
+    UI_SCALES
+        .iter()
+        .copied()
+        .find(|scale| *scale > current + 0.01)
+        .unwrap_or(UI_SCALES[0])
+}
+
+/// Binds the next key pressed to the pending action
+pub fn capture_rebind_key(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut pending: ResMut<PendingRebind>,
+    profile: Res<ActiveProfile>,
+    input_map: Option<ResMut<InputMap>>,
+) {
+    let Some(action) = pending.0 else {
+        return;
+    };
+    let Some(mut input_map) = input_map else {
+        pending.0 = None;
+        return;
+    };
+    let Some(key) = keys
+        .get_just_pressed()
+        .copied()
+        .find(|key| *key != KeyCode::Escape)
+    else {
+        return;
+    };
+
+    if let Some(displaced) = input_map.rebind_key(action, key) {
+        warn!(
+            "{:?} unbound from {:?}, now bound to {:?}",
+            key, displaced, action
+        );
+    }
+    save_input_map(&profile.data_directory(), &input_map);
+    pending.0 = None;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_menu_labels(
+    buttons: Query<(&MenuButton, &Children)>,
+    mut texts: Query<&mut Text>,
+    config: Res<GameConfig>,
+    profile: Res<ActiveProfile>,
+    input_map: Option<Res<InputMap>>,
+    pending: Res<PendingRebind>,
+    seed: Option<Res<SeedEntry>>,
+    latest_save: Option<Res<LatestSave>>,
+) {
+    for (button, children) in &buttons {
+        let label = match button {
+            MenuButton::Resume => t!("menu.resume"),
+            MenuButton::Settings => t!("menu.settings"),
+            MenuButton::Controls => t!("menu.controls"),
+            MenuButton::Quit => t!("menu.quit"),
+            MenuButton::Back => t!("menu.back"),
+            MenuButton::Resolution => {
+                let (width, height) = config.graphics.resolution;
+                t!("menu.resolution", width = width, height = height)
+            }
+            MenuButton::VSync => t!("menu.vsync", state = on_off(config.graphics.vsync)),
+            MenuButton::Quality => t!(
+                "menu.quality",
+                quality = format!("{:?}", config.graphics.quality)
+            ),
+            MenuButton::Language => t!("menu.language", language = t!("language.name")),
+            MenuButton::UiScale => t!(
+                "menu.ui_scale",
+                percent = (config.accessibility.ui_scale * 100.0).round()
+            ),
+            MenuButton::ColorblindMode => t!(
+                "menu.colorblind",
+                mode = t!(
+                    &format!("colorblind.{:?}", config.accessibility.colorblind_mode)
+                        .to_lowercase()
+                )
+            ),
+            MenuButton::Subtitles => t!(
+                "menu.subtitles",
+                state = on_off(config.accessibility.subtitles)
+            ),
+            MenuButton::CameraShake => t!(
+                "menu.camera_shake",
+                state = t!(if config.accessibility.reduced_camera_shake {
+                    "menu.reduced"
+                } else {
+                    "menu.full"
+                })
+            ),
+            MenuButton::Rebind(action) if pending.0 == Some(*action) => {
+                t!("menu.rebind_waiting", action = format!("{action:?}"))
+            }
+            MenuButton::Rebind(action) => {
+                let keys = input_map
+                    .as_ref()
+                    .and_then(|map| map.keys_for(action))
+                    .map(|keys| {
+                        keys.iter()
+                            .map(|key| format!("{key:?}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_else(|| t!("menu.vehicle_default"));
+                format!("{action:?}: {keys}")
+            }
+            MenuButton::ResetBindings => t!("menu.reset_bindings"),
+            MenuButton::NewGame => t!("menu.new_game"),
+            MenuButton::Continue => match latest_save.as_ref().and_then(|save| save.0) {
+                Some(slot) => t!("menu.continue_slot", slot = slot),
+                None => t!("menu.continue_none"),
+            },
+            MenuButton::StartGame => t!("menu.start"),
+            MenuButton::RandomSeed => {
+                t!("menu.seed", seed = seed.as_ref().map_or(0, |seed| seed.0))
+            }
+            MenuButton::Profile => t!("menu.profile", name = profile.0),
+            MenuButton::NewProfile => t!("menu.new_profile"),
+            MenuButton::ViewCrashReport => t!("menu.view_report"),
+            MenuButton::DismissCrashReport => t!("menu.dismiss"),
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child)
+                && **text != label
+            {
+                **text = label.clone();
+            }
+        }
+    }
+}
+
+fn on_off(enabled: bool) -> String {
+    t!(if enabled { "menu.on" } else { "menu.off" })
+}
+
+/// Keeps `GameConfig::graphics.resolution` at the size the window was dragged
+/// to, so the next settings change doesn't snap it back. A minimized window
+/// reports zero size, which is ignored.
+pub fn track_window_resize(
+    mut resized: EventReader<WindowResized>,
+    primary: Query<(), With<PrimaryWindow>>,
+    mut config: ResMut<GameConfig>,
+) {
+    for event in resized.read() {
+        if !primary.contains(event.window) || event.width < 1.0 || event.height < 1.0 {
+            continue;
+        }
+        let size = (event.width.round() as u32, event.height.round() as u32);
+        if config.graphics.resolution != size {
+            config.graphics.resolution = size;
+        }
+    }
+}
+
+/// Applies `GameConfig::graphics` to the window and sun on the first frame,
+/// on every `ConfigReloadedEvent` and whenever a new sun is spawned
+pub fn apply_graphics_settings(
+    mut reloaded: EventReader<ConfigReloadedEvent>,
+    config: Res<GameConfig>,
+    mut applied: Local<bool>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut lights: Query<(&mut DirectionalLight, &mut CascadeShadowConfig)>,
+    new_lights: Query<(), Added<DirectionalLight>>,
+    shadow_map: Option<ResMut<DirectionalLightShadowMap>>,
+) {
+    let reloaded = reloaded.read().count() > 0;
+    if *applied && !reloaded && new_lights.is_empty() {
+        return;
+    }
+    let graphics = &config.graphics;
+    let quality = graphics.quality.settings();
+
+    // Only touch what differs; every change to the window reconfigures its surface
+    if let Ok(mut window) = windows.single_mut() {
+        let (width, height) = graphics.resolution;
+        let size = Vec2::new(width as f32, height as f32);
+        if window.resolution.size() != size {
+            window.resolution.set(size.x, size.y);
+        }
+        let present_mode = if graphics.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        };
+        if window.present_mode != present_mode {
+            window.present_mode = present_mode;
+        }
+    }
+
+    if let Some(mut shadow_map) = shadow_map
+        && shadow_map.size != quality.shadow_map_size
+    {
+        shadow_map.size = quality.shadow_map_size;
+    }
+
+    for (mut light, mut cascades) in &mut lights {
+        light.shadows_enabled = quality.shadow_distance.is_some();
+        if let Some(maximum_distance) = quality.shadow_distance {
+            *cascades = CascadeShadowConfigBuilder {
+                num_cascades: quality.shadow_cascades,
+                maximum_distance,
+                ..default()
+            }
+            .build();
+        }
+    }
+
+    *applied = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolution_cycle_wraps() {
+        assert_eq!(next_resolution((1280, 720)), (1600, 900));
+        assert_eq!(next_resolution((2560, 1440)), (1280, 720));
+        assert_eq!(next_resolution((1000, 800)), (1280, 720));
+    }
+
+    #[test]
+    fn test_ui_scale_cycle_wraps() {
+        assert_eq!(next_ui_scale(1.0), 1.25);
+        assert_eq!(next_ui_scale(2.0), 0.75);
+        assert_eq!(next_ui_scale(0.9), 1.0);
+    }
+}
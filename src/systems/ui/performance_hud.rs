@@ -0,0 +1,286 @@
+//! F3 performance overlay.
+//!
+//! A frame time graph against the frame budget, the per-schedule timings from
+//! `UnifiedPerformanceTracker`, world entity counts per content layer and
+//! camera distance band, how many meshes the renderer culled last frame, the
+//! NPC asset cache hit rate and approximate memory per cache against its budget.
+
+use crate::config::GameConfig;
+use crate::resources::NPCAssetCache;
+use crate::systems::distance_cache::{DistanceBand, DistanceCache};
+use crate::systems::performance::UnifiedPerformanceTracker;
+use crate::systems::performance::frame_budget::FrameBudgetGovernor;
+use crate::systems::performance::memory::{MemorySubsystem, MemoryUsage, format_bytes};
+use crate::systems::world::unified_world::{ContentLayer, UnifiedChunkEntity};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+/// Frames shown in the graph, one bar each
+const GRAPH_SAMPLES: usize = 90;
+const GRAPH_HEIGHT: f32 = 60.0;
+const BAR_WIDTH: f32 = 2.0;
+/// Seconds between text refreshes; the graph updates every frame
+const TEXT_INTERVAL: f32 = 0.25;
+const UNDER_BUDGET: Color = Color::srgb(0.3, 0.85, 0.4);
+const OVER_BUDGET: Color = Color::srgb(0.95, 0.45, 0.15);
+
+const LAYERS: [ContentLayer; 6] = [
+    ContentLayer::Roads,
+    ContentLayer::Buildings,
+    ContentLayer::Vehicles,
+    ContentLayer::NPCs,
+    ContentLayer::Vegetation,
+    ContentLayer::Props,
+];
+
+/// Recent frame times in milliseconds, newest last; kept while the overlay is
+/// closed so the graph is full as soon as it opens
+#[derive(Resource, Default)]
+pub struct FrameTimeHistory(pub VecDeque<f32>);
+
+#[derive(Component)]
+pub struct PerformanceHud;
+
+#[derive(Component)]
+pub struct PerformanceHudText;
+
+/// Graph bar showing the frame `GRAPH_SAMPLES - 1 - index` frames ago
+#[derive(Component)]
+pub struct FrameGraphBar(usize);
+
+pub fn record_frame_time(
+    diagnostics: Res<DiagnosticsStore>,
+    mut history: ResMut<FrameTimeHistory>,
+) {
+    if let Some(frame_ms) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|diagnostic| diagnostic.value())
+    {
+        if history.0.len() == GRAPH_SAMPLES {
+            history.0.pop_front();
+        }
+        history.0.push_back(frame_ms as f32);
+    }
+}
+
+pub fn toggle_performance_hud(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    huds: Query<Entity, With<PerformanceHud>>,
+    config: Res<GameConfig>,
+) {
+    if !keys.just_pressed(KeyCode::F3) {
+        return;
+    }
+    if let Ok(hud) = huds.single() {
+        commands.entity(hud).despawn();
+    } else {
+        spawn_performance_hud(&mut commands, config.performance.frame_time_threshold);
+    }
+}
+
+fn spawn_performance_hud(commands: &mut Commands, budget_ms: f32) {
+    commands
+        .spawn((
+            PerformanceHud,
+            Name::new("PerformanceHud"),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                right: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|hud| {
+            hud.spawn((
+                Node {
+                    width: Val::Px(GRAPH_SAMPLES as f32 * BAR_WIDTH),
+                    height: Val::Px(GRAPH_HEIGHT),
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.05)),
+            ))
+            .with_children(|graph| {
+                for index in 0..GRAPH_SAMPLES {
+                    graph.spawn((
+                        FrameGraphBar(index),
+                        Node {
+                            width: Val::Px(BAR_WIDTH),
+                            height: Val::Px(0.0),
+                            ..default()
+                        },
+                        BackgroundColor(UNDER_BUDGET),
+                    ));
+                }
+                // Budget line, drawn where a frame exactly on budget would end
+                graph.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.0),
+                        right: Val::Px(0.0),
+                        bottom: Val::Px(bar_height(budget_ms, budget_ms)),
+                        height: Val::Px(1.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
+                ));
+            });
+            hud.spawn((
+                PerformanceHudText,
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Graph bar height for a frame; the graph tops out at twice the budget
+fn bar_height(frame_ms: f32, budget_ms: f32) -> f32 {
+    (frame_ms / (budget_ms * 2.0)).clamp(0.0, 1.0) * GRAPH_HEIGHT
+}
+
+pub fn update_frame_graph(
+    history: Res<FrameTimeHistory>,
+    config: Res<GameConfig>,
+    mut bars: Query<(&FrameGraphBar, &mut Node, &mut BackgroundColor)>,
+) {
+    let budget = config.performance.frame_time_threshold;
+    // Right-align the samples so the newest frame is always the last bar
+    let missing = GRAPH_SAMPLES - history.0.len();
+    for (bar, mut node, mut color) in &mut bars {
+        let frame_ms = bar
+            .0
+            .checked_sub(missing)
+            .and_then(|sample| history.0.get(sample))
+            .copied()
+            .unwrap_or(0.0);
+        node.height = Val::Px(bar_height(frame_ms, budget));
+        color.0 = if frame_ms > budget {
+            OVER_BUDGET
+        } else {
+            UNDER_BUDGET
+        };
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_performance_hud_text(
+    time: Res<Time>,
+    mut since_update: Local<f32>,
+    history: Res<FrameTimeHistory>,
+    config: Res<GameConfig>,
+    governor: Res<FrameBudgetGovernor>,
+    tracker: Res<UnifiedPerformanceTracker>,
+    npc_cache: Option<Res<NPCAssetCache>>,
+    distance_cache: Option<Res<DistanceCache>>,
+    memory: Option<Res<MemoryUsage>>,
+    chunk_entities: Query<&UnifiedChunkEntity>,
+    meshes: Query<&ViewVisibility, With<Mesh3d>>,
+    entities: Query<()>,
+    mut texts: Query<&mut Text, With<PerformanceHudText>>,
+) {
+    *since_update += time.delta_secs();
+    if *since_update < TEXT_INTERVAL {
+        return;
+    }
+    *since_update = 0.0;
+    let Ok(mut text) = texts.single_mut() else {
+        return;
+    };
+
+    let frame_ms = if history.0.is_empty() {
+        0.0
+    } else {
+        history.0.iter().sum::<f32>() / history.0.len() as f32
+    };
+    let worst_ms = history.0.iter().copied().fold(0.0, f32::max);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Frame {frame_ms:.1} ms (worst {worst_ms:.1}) / budget {:.1} ms",
+        config.performance.frame_time_threshold
+    );
+    let _ = writeln!(out, "Load scale {:.2}", governor.scale());
+
+    for (schedule, ms) in tracker.system_times() {
+        let _ = writeln!(out, "  {schedule:<12} {ms:>6.2} ms");
+    }
+
+    let mut per_layer = [0usize; LAYERS.len()];
+    for chunk_entity in &chunk_entities {
+        if let Some(index) = LAYERS.iter().position(|layer| *layer == chunk_entity.layer) {
+            per_layer[index] += 1;
+        }
+    }
+    let _ = writeln!(out, "Entities {}", entities.iter().count());
+    for (layer, count) in LAYERS.iter().zip(per_layer) {
+        let _ = writeln!(out, "  {:<12} {count:>6}", format!("{layer:?}"));
+    }
+
+    if let Some(cache) = distance_cache {
+        let _ = write!(out, "Distance bands");
+        for band in DistanceBand::ALL {
+            let _ = write!(out, " {band:?} {}", cache.in_band(band).len());
+        }
+        let _ = writeln!(out);
+    }
+
+    let total_meshes = meshes.iter().count();
+    let culled = meshes.iter().filter(|visible| !visible.get()).count();
+    let _ = writeln!(out, "Meshes culled {culled} / {total_meshes}");
+
+    if let Some(cache) = npc_cache {
+        let (hits, misses, hit_rate) = cache.stats();
+        let _ = writeln!(
+            out,
+            "NPC asset cache {hit_rate:.1}% ({hits} hit, {misses} miss)"
+        );
+    }
+
+    if let Some(memory) = memory {
+        let _ = writeln!(out, "Memory ~{}", format_bytes(memory.total_bytes()));
+        for subsystem in MemorySubsystem::ALL {
+            let Some(bytes) = memory.bytes(subsystem) else {
+                continue;
+            };
+            let budget = subsystem.budget_bytes(&config.performance);
+            let _ = write!(
+                out,
+                "  {:<14} {:>9}",
+                format!("{subsystem:?}"),
+                format_bytes(bytes)
+            );
+            if budget > 0 {
+                let _ = write!(out, " / {}", format_bytes(budget));
+            }
+            if memory.is_over_budget(subsystem) {
+                out.push_str(" OVER");
+            }
+            out.push('\n');
+        }
+    }
+
+    text.0 = out;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_bars_scale_to_twice_the_budget() {
+        assert_eq!(bar_height(0.0, 16.0), 0.0);
+        assert_eq!(bar_height(16.0, 16.0), GRAPH_HEIGHT * 0.5);
+        assert_eq!(bar_height(100.0, 16.0), GRAPH_HEIGHT);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::systems::ui::localization::LocalizedText;
+use crate::t;
 use bevy::asset::RecursiveDependencyLoadState;
 use bevy::prelude::*;
 
@@ -10,10 +12,18 @@ pub struct LoadingBar;
 #[derive(Component)]
 pub struct LoadingText;
 
+/// Width of the progress bar at 100%
+const LOADING_BAR_WIDTH: f32 = 300.0;
+
+/// What the splash screen waits for before world generation starts; filled
+/// by the systems in `systems::loading`
 #[derive(Resource)]
 pub struct AssetLoadingState {
     pub handles: Vec<UntypedHandle>,
-    pub total_assets: usize,
+    /// Set once the handles loaded by startup systems have been added
+    pub startup_assets_tracked: bool,
+    /// Set once the vehicle specs are validated or replaced by defaults
+    pub vehicle_specs_ready: bool,
     pub min_display_timer: Timer,
 }
 
@@ -21,12 +31,44 @@ impl Default for AssetLoadingState {
     fn default() -> Self {
         Self {
             handles: Vec::new(),
-            total_assets: 0,
+            startup_assets_tracked: false,
+            vehicle_specs_ready: false,
             min_display_timer: Timer::from_seconds(2.0, TimerMode::Once),
         }
     }
 }
 
+impl AssetLoadingState {
+    /// Handles done loading, with their dependencies; failed loads count as
+    /// done so a missing file can't hold up the game
+    pub fn finished(&self, asset_server: &AssetServer) -> usize {
+        self.handles
+            .iter()
+            .filter(|handle| {
+                matches!(
+                    asset_server.get_recursive_dependency_load_state(handle.id()),
+                    Some(RecursiveDependencyLoadState::Loaded)
+                        | Some(RecursiveDependencyLoadState::Failed(_))
+                )
+            })
+            .count()
+    }
+
+    /// Whether world generation can start with `finished` handles done
+    pub fn ready(&self, finished: usize) -> bool {
+        self.startup_assets_tracked && self.vehicle_specs_ready && finished >= self.handles.len()
+    }
+}
+
+/// Fraction of the bar to fill; stays short of full until everything is ready
+fn loading_progress(finished: usize, total: usize, ready: bool) -> f32 {
+    match (ready, total) {
+        (true, _) => 1.0,
+        (false, 0) => 0.0,
+        (false, total) => (finished as f32 / total as f32).min(0.95),
+    }
+}
+
 pub fn setup_splash_screen(mut commands: Commands) {
     commands
         .spawn((
@@ -48,7 +90,8 @@ pub fn setup_splash_screen(mut commands: Commands) {
         .with_children(|parent| {
             // Main title with modern styling
             parent.spawn((
-                Text::new("VICE CITY"),
+                Text::default(),
+                LocalizedText::new("menu.title.game"),
                 TextFont {
                     font_size: 72.0,
                     ..default()
@@ -64,7 +107,7 @@ pub fn setup_splash_screen(mut commands: Commands) {
             parent
                 .spawn((
                     Node {
-                        width: Val::Px(300.0),
+                        width: Val::Px(LOADING_BAR_WIDTH),
                         height: Val::Px(4.0),
                         border: UiRect::all(Val::Px(1.0)),
                         margin: UiRect::bottom(Val::Px(20.0)),
@@ -88,7 +131,7 @@ pub fn setup_splash_screen(mut commands: Commands) {
 
             parent.spawn((
                 LoadingText,
-                Text::new("Loading assets..."),
+                Text::new(t!("splash.loading")),
                 TextFont {
                     font_size: 18.0,
                     ..default()
@@ -98,17 +141,6 @@ pub fn setup_splash_screen(mut commands: Commands) {
         });
 }
 
-pub fn load_initial_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let handles = vec![asset_server.load::<Image>("ui/arrow.png").untyped()];
-
-    let total = handles.len();
-    commands.insert_resource(AssetLoadingState {
-        handles,
-        total_assets: total,
-        min_display_timer: Timer::from_seconds(2.0, TimerMode::Once),
-    });
-}
-
 pub fn update_asset_loading(
     time: Res<Time>,
     asset_server: Res<AssetServer>,
@@ -119,38 +151,30 @@ pub fn update_asset_loading(
 ) {
     loading_state.min_display_timer.tick(time.delta());
 
-    let mut loaded = 0;
-    for handle in &loading_state.handles {
-        match asset_server.get_recursive_dependency_load_state(handle.id()) {
-            Some(RecursiveDependencyLoadState::Loaded) => loaded += 1,
-            Some(RecursiveDependencyLoadState::Failed(_)) => loaded += 1,
-            _ => {}
-        }
-    }
-
-    let progress = if loading_state.total_assets > 0 {
-        loaded as f32 / loading_state.total_assets as f32
-    } else {
-        0.0
-    };
+    let finished = loading_state.finished(&asset_server);
+    let total = loading_state.handles.len();
+    let ready = loading_state.ready(finished);
+    let progress = loading_progress(finished, total, ready);
 
     for mut node in loading_bar_query.iter_mut() {
-        node.width = Val::Px(300.0 * progress);
+        node.width = Val::Px(LOADING_BAR_WIDTH * progress);
     }
 
     for mut text in loading_text_query.iter_mut() {
-        if loaded >= loading_state.total_assets {
-            **text = "Ready!".to_string();
+        if ready {
+            **text = t!("splash.ready");
         } else {
-            **text = format!(
-                "Loading assets... {}/{}",
-                loaded, loading_state.total_assets
+            **text = t!(
+                "splash.progress",
+                finished = finished,
+                total = total,
+                percent = format!("{:.0}", progress * 100.0)
             );
         }
     }
 
-    if loaded >= loading_state.total_assets && loading_state.min_display_timer.finished() {
-        next_state.set(crate::states::AppState::WorldGeneration);
+    if ready && loading_state.min_display_timer.finished() {
+        next_state.set(crate::states::AppState::MainMenu);
     }
 }
 
@@ -162,3 +186,17 @@ pub fn cleanup_splash_screen(
         commands.entity(entity).despawn();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_only_fills_once_everything_is_ready() {
+        assert_eq!(loading_progress(0, 0, false), 0.0);
+        assert_eq!(loading_progress(2, 4, false), 0.5);
+        // All files in but the specs still validating
+        assert!(loading_progress(4, 4, false) < 1.0);
+        assert_eq!(loading_progress(4, 4, true), 1.0);
+    }
+}
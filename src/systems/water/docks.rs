@@ -0,0 +1,171 @@
+//! Piers yachts can moor at.
+//!
+//! Docks are laid out in `assets/config/docks.ron`. Pressing interact at the
+//! helm while slowly drifting alongside one ties the yacht up and puts the
+//! player on the pier (see `yacht_exit_system`); the yacht is held at its
+//! berth until someone takes the helm again.
+
+use crate::components::water::Yacht;
+use crate::components::{Dock, MooredAtDock, PlayerControlled};
+use crate::config::GameConfig;
+use crate::systems::physics::PhysicsUtilities;
+use crate::systems::physics::physics_utils::CollisionGroupHelper;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::Deserialize;
+
+/// Yachts within this distance (m) of their berth can tie up
+pub const DOCKING_RANGE: f32 = 30.0;
+/// Fastest a yacht can be drifting (m/s) and still tie up
+pub const DOCKING_MAX_SPEED: f32 = 4.0;
+
+/// Deck plank thickness and the spacing of the piles under it (m)
+const DECK_THICKNESS: f32 = 0.5;
+const PILE_SPACING: f32 = 12.0;
+const PILE_DEPTH: f32 = 8.0;
+
+/// How hard mooring lines pull a hull back to its berth and bow heading
+const MOORING_PULL: f32 = 0.6;
+const MOORING_MAX_SPEED: f32 = 3.0;
+const MOORING_TURN: f32 = 1.0;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DockDefinition {
+    pub name: String,
+    /// Landward end of the deck centerline, at deck height
+    pub start: Vec3,
+    /// Seaward end of the deck centerline
+    pub end: Vec3,
+    pub width: f32,
+}
+
+/// Dock layout loaded from `assets/config/docks.ron`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DockList {
+    #[serde(default)]
+    pub docks: Vec<DockDefinition>,
+}
+
+pub fn spawn_docks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<GameConfig>,
+    existing: Query<(), With<Dock>>,
+) {
+    // Docks persist across state re-entry
+    if !existing.is_empty() {
+        return;
+    }
+
+    let path = format!(
+        "{}/config/docks.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    let list = match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str::<DockList>(&content).unwrap_or_else(|e| {
+            error!("Failed to parse docks at '{}': {}", path, e);
+            DockList::default()
+        }),
+        Err(e) => {
+            info!("ℹ️ No dock config found: {}", e);
+            DockList::default()
+        }
+    };
+
+    let planks = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.45, 0.33, 0.22),
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+    let piles = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.3, 0.22, 0.15),
+        perceptual_roughness: 0.95,
+        ..default()
+    });
+    let pile_mesh = meshes.add(Cylinder::new(0.35, PILE_DEPTH));
+
+    for definition in &list.docks {
+        let span = definition.end - definition.start;
+        let length = span.length();
+        if length < 1.0 {
+            warn!("Dock '{}' is too short", definition.name);
+            continue;
+        }
+        let center = definition.start + span * 0.5 - Vec3::Y * DECK_THICKNESS * 0.5;
+        let half = Vec3::new(definition.width, DECK_THICKNESS, length) * 0.5;
+        let dock = commands
+            .spawn((
+                Name::new(definition.name.clone()),
+                Dock {
+                    name: definition.name.clone(),
+                    start: definition.start,
+                    end: definition.end,
+                    width: definition.width,
+                },
+                Mesh3d(meshes.add(Cuboid::from_size(half * 2.0))),
+                MeshMaterial3d(planks.clone()),
+                Transform::from_translation(center).looking_to(span, Vec3::Y),
+                RigidBody::Fixed,
+                Collider::cuboid(half.x, half.y, half.z),
+                CollisionGroupHelper::static_groups(&config),
+            ))
+            .id();
+
+        // Piles down both edges; children sit in the deck's frame
+        let piles_per_side = (length / PILE_SPACING).floor() as i32 + 1;
+        for index in 0..piles_per_side {
+            let along = -half.z + index as f32 * PILE_SPACING;
+            for side in [-1.0, 1.0] {
+                commands.spawn((
+                    Mesh3d(pile_mesh.clone()),
+                    MeshMaterial3d(piles.clone()),
+                    Transform::from_xyz(
+                        side * (half.x - 0.3),
+                        -PILE_DEPTH * 0.5 + DECK_THICKNESS * 0.5,
+                        along,
+                    ),
+                    ChildOf(dock),
+                ));
+            }
+        }
+    }
+}
+
+/// Holds moored yachts at their berth, bow along the pier. Buoyancy still
+/// works the vertical, so they ride the swell against the lines.
+#[allow(clippy::type_complexity)]
+pub fn moor_yachts(
+    config: Res<GameConfig>,
+    mut yachts: Query<
+        (&Transform, &MooredAtDock, &mut Velocity),
+        (With<Yacht>, Without<PlayerControlled>),
+    >,
+) {
+    for (transform, mooring, mut velocity) in &mut yachts {
+        let offset = (mooring.berth - transform.translation).with_y(0.0);
+        let pull = (offset * MOORING_PULL).clamp_length_max(MOORING_MAX_SPEED);
+        velocity.linvel.x = pull.x;
+        velocity.linvel.z = pull.z;
+
+        let forward = transform
+            .forward()
+            .as_vec3()
+            .with_y(0.0)
+            .normalize_or(Vec3::NEG_Z);
+        let turn = forward.cross(mooring.heading).y.clamp(-1.0, 1.0);
+        velocity.angvel.y = turn * MOORING_TURN;
+
+        PhysicsUtilities::clamp_velocity(&mut velocity, &config);
+    }
+}
+
+/// Taking the helm of a moored yacht casts it off
+pub fn cast_off_moored_yachts(
+    mut commands: Commands,
+    yachts: Query<Entity, (With<MooredAtDock>, Added<PlayerControlled>)>,
+) {
+    for yacht in &yachts {
+        commands.entity(yacht).remove::<MooredAtDock>();
+    }
+}
@@ -0,0 +1,110 @@
+//! Hull-point buoyancy for boats.
+//!
+//! Samples the water surface (tide plus Gerstner swells) under every
+//! `BuoyancyHull` point and applies lift and drag through `ExternalForce`, so
+//! hulls heave, pitch and roll with the waves that are drawn instead of being
+//! pinned to a fixed height.
+
+use crate::components::unified_water::{BuoyancyHull, CurrentWaterRegion, UnifiedWaterBody};
+use crate::config::GameConfig;
+use crate::systems::physics::PhysicsUtilities;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+/// Net force and torque (about `center_of_mass`) from all submerged hull points.
+/// `surface_height` returns the water height above a world position.
+pub fn hull_forces(
+    hull: &BuoyancyHull,
+    transform: &Transform,
+    center_of_mass: Vec3,
+    velocity: &Velocity,
+    mass: f32,
+    gravity: f32,
+    surface_height: impl Fn(Vec3) -> f32,
+) -> (Vec3, Vec3) {
+    if hull.sample_points.is_empty() {
+        return (Vec3::ZERO, Vec3::ZERO);
+    }
+
+    let share = mass / hull.sample_points.len() as f32;
+    let mut force = Vec3::ZERO;
+    let mut torque = Vec3::ZERO;
+    for local in &hull.sample_points {
+        let point = transform.transform_point(*local);
+        let depth = surface_height(point) - point.y;
+        if depth <= 0.0 {
+            continue;
+        }
+
+        let lift = (depth / hull.rest_depth).min(hull.max_lift) * share * gravity;
+        let submerged = (depth / hull.rest_depth).min(1.0);
+        let arm = point - center_of_mass;
+        let point_velocity = velocity.linvel + velocity.angvel.cross(arm);
+        let drag = -Vec3::new(
+            point_velocity.x * hull.horizontal_drag,
+            point_velocity.y * hull.vertical_drag,
+            point_velocity.z * hull.horizontal_drag,
+        ) * share
+            * submerged;
+
+        let point_force = Vec3::Y * lift + drag;
+        force += point_force;
+        torque += arm.cross(point_force);
+    }
+    (force, torque)
+}
+
+/// Applies hull buoyancy to every `BuoyancyHull` body in a water region
+#[allow(clippy::type_complexity)]
+pub fn hull_buoyancy_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    water_regions: Query<&UnifiedWaterBody>,
+    mut hulls: Query<(
+        &GlobalTransform,
+        &BuoyancyHull,
+        &ReadMassProperties,
+        &CurrentWaterRegion,
+        &mut ExternalForce,
+        &mut Velocity,
+    )>,
+) {
+    let now = time.elapsed_secs();
+    let gravity = config.world_physics.water.gravity;
+
+    for (
+        global_transform,
+        hull,
+        mass_properties,
+        current_region,
+        mut external_force,
+        mut velocity,
+    ) in hulls.iter_mut()
+    {
+        let region = current_region
+            .region_entity
+            .and_then(|region_entity| water_regions.get(region_entity).ok());
+        let mass_properties = mass_properties.get();
+
+        // Assign rather than accumulate so forces vanish once the hull leaves the water
+        let (force, torque) = match region {
+            Some(region) if mass_properties.mass > 0.0 => {
+                let transform = global_transform.compute_transform();
+                hull_forces(
+                    hull,
+                    &transform,
+                    transform.transform_point(mass_properties.local_center_of_mass),
+                    &velocity,
+                    mass_properties.mass,
+                    gravity,
+                    |point| region.sample_surface_height(point.x, point.z, now),
+                )
+            }
+            _ => (Vec3::ZERO, Vec3::ZERO),
+        };
+        external_force.force = force;
+        external_force.torque = torque;
+
+        PhysicsUtilities::clamp_velocity(&mut velocity, &config);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::components::unified_water::{CurrentWaterRegion, UnifiedWaterBody, WaterBodyId};
+use crate::components::unified_water::{
+    BuoyancyHull, CurrentWaterRegion, UnifiedWaterBody, WaterBodyId,
+};
 use crate::components::water::Yacht;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
@@ -71,6 +73,7 @@ pub fn update_water_region_cache(
 /// Merged water physics system - applies both buoyancy and drag in single pass
 /// Uses CurrentWaterRegion cache for O(1) region lookup instead of O(n) scanning
 /// FALLBACK: If cache is invalid, performs one-time region scan and writes cache
+/// Bodies with a `BuoyancyHull` are handled by hull_buoyancy_system instead
 #[allow(clippy::type_complexity)]
 pub fn water_physics_system(
     time: Res<Time>,
@@ -83,7 +86,7 @@ pub fn water_physics_system(
             &Collider,
             &mut CurrentWaterRegion,
         ),
-        (With<WaterBodyId>, Without<Yacht>),
+        (With<WaterBodyId>, Without<Yacht>, Without<BuoyancyHull>),
     >,
     water_regions: Query<(Entity, &UnifiedWaterBody)>,
 ) {
@@ -1,5 +1,7 @@
 pub mod buoyancy;
+pub mod docks;
 pub mod drag;
+pub mod hull_buoyancy;
 pub mod merged_physics;
 pub mod simple_yacht_buoyancy;
 pub mod surface_render;
@@ -8,7 +10,9 @@ pub mod water_material_time;
 pub mod yacht_spawn;
 
 pub use buoyancy::*;
+pub use docks::*;
 pub use drag::*;
+pub use hull_buoyancy::*;
 pub use merged_physics::*;
 pub use simple_yacht_buoyancy::*;
 pub use surface_render::*;
@@ -1,3 +1,16 @@
+// ==================================================================================
+// DEPRECATED: This file is retained for reference but is no longer used in production.
+//
+// REPLACED BY: src/systems/water/hull_buoyancy.rs::hull_buoyancy_system()
+//
+// REASON FOR DEPRECATION:
+// This system pinned yachts to a fixed height with velocity correction, ignored
+// waves, and accumulated righting torque into ExternalForce without ever
+// clearing it. Hull buoyancy samples the wave surface under the keel and
+// applies lift and drag as forces, so yachts float, pitch and self-right
+// physically.
+// ==================================================================================
+
 use crate::components::unified_water::UnifiedWaterBody;
 use crate::components::water::{Yacht, YachtSpecs};
 use crate::config::GameConfig;
@@ -9,6 +22,7 @@ use bevy_rapier3d::prelude::*;
 /// General buoyancy system for ALL yachts (not just player-controlled)
 /// Keeps yachts floating at the correct water level using velocity correction
 /// Also applies self-righting torque to keep yacht upright
+/// DEPRECATED: Use hull_buoyancy_system in hull_buoyancy.rs instead
 pub fn simple_yacht_buoyancy(
     time: Res<Time>,
     config: Res<GameConfig>,
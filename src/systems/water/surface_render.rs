@@ -50,7 +50,7 @@ pub fn surface_render_system(
         // Apply wave parameters from config if available
         if let Some(wave_params) = &region.wave_params {
             // Scale default wave amplitudes by config amplitude ratio
-            let amplitude_scale = (wave_params.amplitude / 0.25).clamp(0.5, 2.0);
+            let amplitude_scale = wave_params.amplitude_scale();
             for i in 0..4 {
                 water_material.wave_data0[i].z *= amplitude_scale;
             }
@@ -0,0 +1,210 @@
+//! Data-driven weather: rain, fog and wind presets with smooth transitions.
+//!
+//! Presets come from `assets/config/weather.ron`. The blended `WeatherState`
+//! drives camera fog, the rain emitter, car grip and wind drift on aircraft.
+
+use crate::components::{
+    ActiveEntity, ChangeWeather, F16, Helicopter, MapCamera, RainEmitter, WeatherConfig,
+    WeatherKind, WeatherState,
+};
+use crate::resources::WorldRng;
+use crate::systems::effects::RAIN_EMITTER_HEIGHT;
+use bevy::prelude::*;
+use bevy_hanabi::prelude::EffectSpawner;
+use bevy_rapier3d::prelude::Velocity;
+use rand::Rng;
+
+/// Fraction of the wind velocity aircraft drift towards each second
+const WIND_DRIFT_RATE: f32 = 0.15;
+
+pub fn load_weather_config(mut commands: Commands) {
+    let path = format!(
+        "{}/config/weather.ron",
+        crate::util::asset_path::get_assets_base_path()
+    );
+    let config = match std::fs::read_to_string(&path) {
+        Ok(content) => match ron::from_str::<WeatherConfig>(&content) {
+            Ok(config) => {
+                #[cfg(feature = "debug-ui")]
+                info!("✅ Loaded {} weather presets", config.presets.len());
+                config
+            }
+            Err(e) => {
+                error!("Failed to parse weather config at '{}': {}", path, e);
+                WeatherConfig::default()
+            }
+        },
+        Err(e) => {
+            info!("ℹ️ No weather config found, using clear skies: {}", e);
+            WeatherConfig::default()
+        }
+    };
+
+    commands.insert_resource(WeatherState::new(config.initial, &config));
+    commands.insert_resource(config);
+}
+
+/// F7 cycles weather; otherwise weather changes on its own after a random duration
+pub fn weather_cycle_system(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<WeatherConfig>,
+    mut weather: ResMut<WeatherState>,
+    mut world_rng: ResMut<WorldRng>,
+    mut changes: EventWriter<ChangeWeather>,
+) {
+    if keys.just_pressed(KeyCode::F7) {
+        changes.write(ChangeWeather {
+            kind: weather.target.next(),
+        });
+        return;
+    }
+    if !config.auto_cycle {
+        return;
+    }
+
+    weather.time_until_change -= time.delta_secs();
+    if weather.time_until_change > 0.0 {
+        return;
+    }
+
+    let rng = world_rng.global();
+    weather.time_until_change =
+        rng.gen_range(config.min_duration..=config.max_duration.max(config.min_duration));
+    let options: Vec<WeatherKind> = WeatherKind::ALL
+        .into_iter()
+        .filter(|k| *k != weather.target && config.presets.contains_key(k))
+        .collect();
+    if !options.is_empty() {
+        changes.write(ChangeWeather {
+            kind: options[rng.gen_range(0..options.len())],
+        });
+    }
+}
+
+/// Starts requested transitions and blends parameters towards the target preset
+pub fn weather_transition_system(
+    time: Res<Time>,
+    config: Res<WeatherConfig>,
+    mut weather: ResMut<WeatherState>,
+    mut changes: EventReader<ChangeWeather>,
+) {
+    if let Some(change) = changes.read().last()
+        && change.kind != weather.target
+    {
+        info!(
+            "🌦️ Weather changing: {:?} → {:?}",
+            weather.target, change.kind
+        );
+        weather.from = weather.params;
+        weather.target = change.kind;
+        weather.transition = 0.0;
+    }
+
+    if weather.transition >= 1.0 {
+        return;
+    }
+
+    let step = time.delta_secs() / config.transition_secs.max(0.01);
+    weather.transition = (weather.transition + step).min(1.0);
+    // Smoothstep for gentle easing in and out
+    let t = weather.transition * weather.transition * (3.0 - 2.0 * weather.transition);
+    weather.params = weather.from.lerp(&config.params(weather.target), t);
+    if weather.transition >= 1.0 {
+        weather.current = weather.target;
+    }
+}
+
+pub fn apply_weather_fog(
+    mut commands: Commands,
+    weather: Res<WeatherState>,
+    cameras: Query<Entity, (With<Camera3d>, Without<MapCamera>)>,
+) {
+    if !weather.is_changed() {
+        return;
+    }
+    let (r, g, b) = weather.params.fog_color;
+    for camera in cameras.iter() {
+        commands.entity(camera).insert(DistanceFog {
+            color: Color::srgb(r, g, b),
+            falloff: FogFalloff::from_visibility(weather.params.fog_visibility),
+            ..default()
+        });
+    }
+}
+
+/// Keeps the rain emitter above the active entity and toggles it with rain intensity
+pub fn update_rain_emitter(
+    weather: Res<WeatherState>,
+    active_query: Query<&Transform, (With<ActiveEntity>, Without<RainEmitter>)>,
+    mut emitter_query: Query<(&mut Transform, &mut EffectSpawner), With<RainEmitter>>,
+) {
+    let Ok(active_transform) = active_query.single() else {
+        return;
+    };
+    let raining = weather.params.rain_intensity > 0.05;
+
+    for (mut transform, mut spawner) in emitter_query.iter_mut() {
+        spawner.active = raining;
+        if raining {
+            transform.translation = active_transform.translation + Vec3::Y * RAIN_EMITTER_HEIGHT;
+        }
+    }
+}
+
+/// Wind pushes airborne aircraft the player is flying
+#[allow(clippy::type_complexity)]
+pub fn weather_wind_system(
+    time: Res<Time>,
+    weather: Res<WeatherState>,
+    mut aircraft_query: Query<
+        &mut Velocity,
+        (With<ActiveEntity>, Or<(With<Helicopter>, With<F16>)>),
+    >,
+) {
+    let wind = weather.params.wind_vector();
+    if wind == Vec3::ZERO {
+        return;
+    }
+    let blend = (WIND_DRIFT_RATE * time.delta_secs()).min(1.0);
+    for mut velocity in aircraft_query.iter_mut() {
+        let drift = (wind - velocity.linvel.with_y(0.0)) * blend;
+        // Only push along the wind; never drag the aircraft back against it
+        if drift.dot(wind) > 0.0 {
+            velocity.linvel += drift.project_onto(wind);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::WeatherParams;
+
+    #[test]
+    fn test_weather_params_blend() {
+        let clear = WeatherParams::default();
+        let rain = WeatherParams {
+            rain_intensity: 1.0,
+            road_friction: 0.6,
+            ..default()
+        };
+
+        let half = clear.lerp(&rain, 0.5);
+        assert!((half.rain_intensity - 0.5).abs() < 1e-5);
+        assert!((half.road_friction - 0.8).abs() < 1e-5);
+        assert_eq!(clear.lerp(&rain, 1.0), rain);
+    }
+
+    #[test]
+    fn test_weather_ron_parses() {
+        let path = format!(
+            "{}/config/weather.ron",
+            crate::util::asset_path::get_assets_base_path()
+        );
+        let content = std::fs::read_to_string(path).unwrap();
+        let config: WeatherConfig = ron::from_str(&content).unwrap();
+
+        assert_eq!(config.presets.len(), WeatherKind::ALL.len());
+    }
+}
@@ -0,0 +1,167 @@
+//! Chunk-scoped teardown of dynamic content.
+//!
+//! Vehicles and street furniture carry `SpawnedByChunk` with the Morton code
+//! of the chunk that spawned them. Once a chunk lies a full chunk beyond the
+//! streaming radius, everything it owns is despawned with its children, or
+//! handed back to the `EntityPool` when its content type is pooled. Street
+//! furniture that was still standing is remembered on the chunk; when the
+//! chunk comes back in range that furniture is respawned and the chunk's
+//! vehicles are regenerated from the same chunk-keyed RNG stream.
+
+use crate::components::{ActiveEntity, DynamicContent, LooseProp, StreetProp};
+use crate::config::GameConfig;
+use crate::factories::{EntityPool, PrefabInstance};
+use crate::systems::world::chunk_streaming::{
+    ChunkGenerationContext, StreamingFocus, morton_encode,
+};
+use crate::systems::world::unified_world::{
+    ChunkCoord, ChunkState, SpawnedByChunk, UnifiedWorldManager,
+};
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// True once a chunk centered at `center` is far enough out to tear down.
+/// The extra chunk of slack keeps a chunk on the edge from flickering.
+fn streamed_out(center: Vec3, focus: Vec3, radius: f32, chunk_size: f32) -> bool {
+    center.xz().distance(focus.xz()) > radius + chunk_size
+}
+
+/// Despawns the dynamic content of chunks that streamed out. Content that
+/// wandered into range (a car driven off from where it was parked) moves to
+/// the chunk it is in now instead.
+#[allow(clippy::type_complexity)]
+pub fn evict_streamed_out_chunks(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    focus: Res<StreamingFocus>,
+    mut world_manager: ResMut<UnifiedWorldManager>,
+    mut pool: ResMut<EntityPool>,
+    owned: Query<
+        (
+            Entity,
+            &SpawnedByChunk,
+            &Transform,
+            Option<&DynamicContent>,
+            Option<&PrefabInstance>,
+            Has<StreetProp>,
+            Has<LooseProp>,
+        ),
+        Without<ActiveEntity>,
+    >,
+) {
+    let radius = config.world.streaming_radius;
+    let chunk_size = world_manager.chunk_size;
+    let leaving: Vec<ChunkCoord> = world_manager
+        .chunks
+        .iter()
+        .flatten()
+        .filter(|chunk| matches!(chunk.state, ChunkState::Loaded { .. }) && !chunk.evicted)
+        .filter(|chunk| {
+            let center = chunk.coord.to_world_pos_with_size(chunk_size);
+            streamed_out(center, focus.position, radius, chunk_size)
+        })
+        .map(|chunk| chunk.coord)
+        .collect();
+    if leaving.is_empty() {
+        return;
+    }
+
+    let mut codes = HashSet::new();
+    for &coord in &leaving {
+        if let Some(chunk) = world_manager.get_chunk_mut(coord) {
+            chunk.evicted = true;
+            chunk.vehicles_generated = false;
+            chunk.entities.clear();
+        }
+        // Regenerated vehicles would otherwise collide with their own ghosts
+        world_manager.clear_placement_grid_for_chunk(coord);
+        codes.insert(morton_encode(coord));
+    }
+
+    for (entity, owner, transform, content, prefab, street_prop, loose) in &owned {
+        if !codes.contains(&owner.0) {
+            continue;
+        }
+        let here = ChunkCoord::from_world_pos(transform.translation, chunk_size);
+        let here_center = here.to_world_pos_with_size(chunk_size);
+        if !streamed_out(here_center, focus.position, radius, chunk_size) {
+            commands
+                .entity(entity)
+                .insert(SpawnedByChunk(morton_encode(here)));
+            continue;
+        }
+
+        if street_prop
+            && !loose
+            && let Some(&PrefabInstance(id)) = prefab
+            && let Some(chunk) = world_manager.get_chunk_mut(owner.coord())
+        {
+            chunk.dormant_props.push((id, *transform));
+        }
+        match content {
+            Some(content) => {
+                pool.release(&mut commands, entity, content.content_type);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+    world_manager.chunks_unloaded_this_frame = leaving.len();
+
+    #[cfg(feature = "debug-ui")]
+    info!("Streamed out {} chunks", leaving.len());
+}
+
+/// Restores evicted chunks that came back inside the streaming radius,
+/// a few per run so a fast drive back doesn't spike one frame
+pub fn restore_streamed_in_chunks(focus: Res<StreamingFocus>, mut context: ChunkGenerationContext) {
+    let radius = context.config.world.streaming_radius;
+    let budget = context.config.world_streaming.chunks_per_frame.max(1);
+    let chunk_size = context.world_manager.chunk_size;
+    let returning: Vec<ChunkCoord> = context
+        .world_manager
+        .chunks
+        .iter()
+        .flatten()
+        .filter(|chunk| chunk.evicted)
+        .filter(|chunk| {
+            let center = chunk.coord.to_world_pos_with_size(chunk_size);
+            center.xz().distance(focus.position.xz()) <= radius
+        })
+        .map(|chunk| chunk.coord)
+        .take(budget)
+        .collect();
+
+    for coord in returning {
+        context.restore_dynamic_content(coord);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_on_the_edge_are_kept() {
+        let focus = Vec3::ZERO;
+        let (radius, chunk_size) = (800.0, 200.0);
+        assert!(!streamed_out(
+            Vec3::new(900.0, 0.0, 0.0),
+            focus,
+            radius,
+            chunk_size
+        ));
+        assert!(streamed_out(
+            Vec3::new(0.0, 0.0, -1001.0),
+            focus,
+            radius,
+            chunk_size
+        ));
+        // Height doesn't count, only ground distance
+        assert!(!streamed_out(
+            Vec3::new(0.0, 2000.0, 0.0),
+            focus,
+            radius,
+            chunk_size
+        ));
+    }
+}
@@ -0,0 +1,402 @@
+//! Budgeted chunk streaming with background prioritization.
+//!
+//! Pending chunks are keyed by Morton code. A task on the `AsyncComputeTaskPool`
+//! orders them by distance to where the camera will be shortly (position plus
+//! velocity lookahead), and the main thread generates at most a fixed number of
+//! chunks per frame from that order. Generation itself stays on the main thread
+//! because the generators spawn entities and create assets directly.
+
+use crate::components::MainCamera;
+use crate::components::unified_water::UnifiedWaterBody;
+use crate::config::GameConfig;
+use crate::constants::WorldEnvConfig;
+use crate::factories::{EntityPool, PrefabRegistry, ReflectiveMaterials};
+use crate::resources::{DistrictMap, MaterialRegistry, WorldSeed};
+use crate::systems::performance::frame_budget::FrameBudgetGovernor;
+use crate::systems::rendering::{InstancedBatcher, PalmTree};
+use crate::systems::world::generators::street_prop_generator::spawn_street_furniture;
+use crate::systems::world::generators::{
+    BuildingGenerator, RoadGenerator, StreetPropBatchers, StreetPropGenerator, VegetationGenerator,
+    VehicleGenerator,
+};
+use crate::systems::world::unified_world::{
+    ChunkCoord, ChunkState, ContentLayer, UnifiedWorldManager,
+};
+use crate::util::morton;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, poll_once};
+use std::collections::BTreeSet;
+
+/// Seconds between re-prioritization passes while chunks are pending
+const REPRIORITIZE_INTERVAL: f32 = 0.5;
+
+/// Z-order key of a chunk, ordered by x and z like `crate::util::morton` cells
+pub fn morton_encode(coord: ChunkCoord) -> u64 {
+    morton::morton_encode(IVec2::new(coord.x, coord.z))
+}
+
+pub fn morton_decode(code: u64) -> ChunkCoord {
+    let cell = morton::morton_decode(code);
+    ChunkCoord::new(cell.x, cell.y)
+}
+
+fn zigzag(v: i32) -> u32 {
+    ((v << 1) ^ (v >> 31)) as u32
+}
+
+/// Key of a chunk's `WorldSeed::chunk_rng` streams: the zigzag Morton code
+/// chunks were keyed by before streaming moved to order-preserving codes.
+/// Pinned, so a seed keeps generating the same world.
+pub fn chunk_content_key(coord: ChunkCoord) -> u64 {
+    morton::interleave(zigzag(coord.x), zigzag(coord.z))
+}
+
+/// Where streaming should focus: the camera and where it is heading
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct StreamingFocus {
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+/// Chunks waiting to be generated, ordered by a background prioritization task
+#[derive(Resource, Default)]
+pub struct ChunkStreamingProvider {
+    /// Morton keys, so the chunks in an area are one range of the set
+    pending: BTreeSet<u64>,
+    /// Morton keys ordered so the highest priority chunk is last
+    ordered: Vec<u64>,
+    task: Option<Task<Vec<u64>>>,
+    since_prioritized: f32,
+}
+
+impl ChunkStreamingProvider {
+    pub fn queue(&mut self, coord: ChunkCoord) {
+        self.pending.insert(morton_encode(coord));
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Pending chunks whose square around `position` could hold one within `radius`
+    fn pending_near(
+        &self,
+        position: Vec3,
+        radius: f32,
+        chunk_size: f32,
+    ) -> impl Iterator<Item = u64> + '_ {
+        let corner = |offset: f32| {
+            let coord = ChunkCoord::from_world_pos(position + Vec3::splat(offset), chunk_size);
+            IVec2::new(coord.x, coord.z)
+        };
+        let range = morton::morton_box_range(corner(-radius), corner(radius));
+        self.pending
+            .range(range.clone())
+            .copied()
+            .filter(move |code| morton::morton_in_box(*code, &range))
+    }
+
+    /// True while any pending chunk lies within `radius` of `position`
+    pub fn has_pending_within(&self, position: Vec3, radius: f32, chunk_size: f32) -> bool {
+        self.pending_near(position, radius, chunk_size)
+            .any(|code| within(code, position, radius, chunk_size))
+    }
+
+    /// Number of pending chunks within `radius` of `position`
+    pub fn count_pending_within(&self, position: Vec3, radius: f32, chunk_size: f32) -> usize {
+        self.pending_near(position, radius, chunk_size)
+            .filter(|&code| within(code, position, radius, chunk_size))
+            .count()
+    }
+
+    /// Highest priority pending chunk from the latest ordering
+    fn pop(&mut self) -> Option<ChunkCoord> {
+        while let Some(code) = self.ordered.pop() {
+            if self.pending.remove(&code) {
+                return Some(morton_decode(code));
+            }
+        }
+        None
+    }
+}
+
+fn within(code: u64, position: Vec3, radius: f32, chunk_size: f32) -> bool {
+    let center = morton_decode(code).to_world_pos_with_size(chunk_size);
+    center.xz().distance(position.xz()) <= radius
+}
+
+fn prioritize(mut codes: Vec<u64>, target: Vec3, chunk_size: f32) -> Vec<u64> {
+    let distance = |code: u64| {
+        morton_decode(code)
+            .to_world_pos_with_size(chunk_size)
+            .xz()
+            .distance_squared(target.xz())
+    };
+    // Farthest first so `pop()` yields the closest chunk
+    codes.sort_by(|a, b| distance(*b).total_cmp(&distance(*a)));
+    codes
+}
+
+/// Follows the main camera and smooths its velocity
+pub fn update_streaming_focus(
+    time: Res<Time>,
+    mut focus: ResMut<StreamingFocus>,
+    camera_query: Query<&GlobalTransform, With<MainCamera>>,
+) {
+    let Ok(camera) = camera_query.single() else {
+        return;
+    };
+    let position = camera.translation();
+    let dt = time.delta_secs();
+    if dt > 0.0 {
+        let instant = (position - focus.position) / dt;
+        focus.velocity = focus.velocity.lerp(instant, (dt * 4.0).min(1.0));
+    }
+    focus.position = position;
+}
+
+/// Collects finished prioritization tasks and starts new ones on the async pool
+pub fn prioritize_pending_chunks(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    focus: Res<StreamingFocus>,
+    world: Res<UnifiedWorldManager>,
+    mut provider: ResMut<ChunkStreamingProvider>,
+) {
+    provider.since_prioritized += time.delta_secs();
+
+    if let Some(task) = provider.task.as_mut() {
+        let Some(ordered) = block_on(poll_once(task)) else {
+            return;
+        };
+        provider.ordered = ordered;
+        provider.task = None;
+    }
+
+    if provider.pending.is_empty()
+        || (!provider.ordered.is_empty() && provider.since_prioritized < REPRIORITIZE_INTERVAL)
+    {
+        return;
+    }
+
+    let codes: Vec<u64> = provider.pending.iter().copied().collect();
+    let target = focus.position + focus.velocity * config.world_streaming.velocity_lookahead;
+    let chunk_size = world.chunk_size;
+    provider.since_prioritized = 0.0;
+    provider.task = Some(
+        AsyncComputeTaskPool::get().spawn(async move { prioritize(codes, target, chunk_size) }),
+    );
+}
+
+/// Everything the chunk generators need
+#[derive(SystemParam)]
+pub struct ChunkGenerationContext<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub world_manager: ResMut<'w, UnifiedWorldManager>,
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<StandardMaterial>>,
+    pub material_registry: ResMut<'w, MaterialRegistry>,
+    pub reflective_materials: ResMut<'w, ReflectiveMaterials>,
+    pub world_seed: Res<'w, WorldSeed>,
+    pub districts: Res<'w, DistrictMap>,
+    pub street_props: StreetPropBatchers<'w>,
+    pub prefabs: Res<'w, PrefabRegistry>,
+    pub palms: ResMut<'w, InstancedBatcher<PalmTree>>,
+    pub water_bodies: Query<'w, 's, &'static UnifiedWaterBody>,
+    pub asset_server: Res<'w, AssetServer>,
+    pub entity_pool: ResMut<'w, EntityPool>,
+    pub config: Res<'w, GameConfig>,
+    pub env: Res<'w, WorldEnvConfig>,
+}
+
+impl ChunkGenerationContext<'_, '_> {
+    /// Generates all content layers for one chunk and marks it loaded
+    pub fn generate_chunk(&mut self, coord: ChunkCoord) {
+        crate::profile_scope!("generate_chunk", x = coord.x, z = coord.z);
+        // Each layer gets its own chunk-keyed stream, so content doesn't depend on load order
+        let key = chunk_content_key(coord);
+        let seed = *self.world_seed;
+
+        let road_ids = RoadGenerator.generate_roads(
+            &mut self.commands,
+            &mut self.world_manager,
+            coord,
+            &mut self.meshes,
+            &mut self.materials,
+            &mut self.material_registry,
+            &mut self.reflective_materials,
+            &mut seed.chunk_rng(key, ContentLayer::Roads),
+            &self.water_bodies,
+            &self.config,
+            &self.env,
+        );
+
+        StreetPropGenerator.generate_props(
+            &mut self.commands,
+            &self.prefabs,
+            &self.world_manager,
+            coord,
+            &road_ids,
+            &mut seed.chunk_rng(key, ContentLayer::Props),
+            &self.districts,
+            &mut self.street_props,
+            &self.env,
+        );
+
+        BuildingGenerator.generate_buildings(
+            &mut self.commands,
+            &mut self.world_manager,
+            coord,
+            &mut self.meshes,
+            &mut self.materials,
+            &mut seed.chunk_rng(key, ContentLayer::Buildings),
+            &self.districts,
+            &self.water_bodies,
+            &self.config,
+            &self.env,
+        );
+
+        VehicleGenerator.generate_vehicles(
+            &mut self.commands,
+            &mut self.world_manager,
+            coord,
+            &mut self.meshes,
+            &mut self.materials,
+            &self.asset_server,
+            &mut self.entity_pool,
+            &mut seed.chunk_rng(key, ContentLayer::Vehicles),
+            &self.districts,
+            &self.config,
+        );
+
+        VegetationGenerator.generate_vegetation(
+            &mut self.commands,
+            &mut self.world_manager,
+            coord,
+            &mut seed.chunk_rng(key, ContentLayer::Vegetation),
+            &mut self.palms,
+            &self.water_bodies,
+            &self.config,
+            &self.env,
+        );
+
+        if let Some(chunk) = self.world_manager.get_chunk_mut(coord) {
+            chunk.state = ChunkState::Loaded { lod_level: 0 };
+        }
+    }
+
+    /// Brings back what an evicted chunk owned: the street furniture that was
+    /// still standing, and its vehicles regenerated from the chunk's RNG stream
+    pub fn restore_dynamic_content(&mut self, coord: ChunkCoord) {
+        let Some(chunk) = self.world_manager.get_chunk_mut(coord) else {
+            return;
+        };
+        chunk.evicted = false;
+        for (id, transform) in std::mem::take(&mut chunk.dormant_props) {
+            spawn_street_furniture(&mut self.commands, &self.prefabs, coord, id, transform);
+        }
+
+        let key = chunk_content_key(coord);
+        VehicleGenerator.generate_vehicles(
+            &mut self.commands,
+            &mut self.world_manager,
+            coord,
+            &mut self.meshes,
+            &mut self.materials,
+            &self.asset_server,
+            &mut self.entity_pool,
+            &mut self.world_seed.chunk_rng(key, ContentLayer::Vehicles),
+            &self.districts,
+            &self.config,
+        );
+    }
+
+    /// Generates up to `budget` of the highest priority pending chunks; returns how many ran
+    pub fn stream(&mut self, provider: &mut ChunkStreamingProvider, budget: usize) -> usize {
+        let mut generated = 0;
+        while generated < budget {
+            let Some(coord) = provider.pop() else {
+                break;
+            };
+            self.generate_chunk(coord);
+            generated += 1;
+        }
+        generated
+    }
+}
+
+/// Keeps generating the rest of the world in-game within the per-frame budget,
+/// shrunk by the frame budget governor while frames run long
+pub fn stream_remaining_chunks(
+    mut provider: ResMut<ChunkStreamingProvider>,
+    mut context: ChunkGenerationContext,
+    governor: Res<FrameBudgetGovernor>,
+) {
+    if provider.is_empty() {
+        return;
+    }
+    let budget = ((context.config.world_streaming.chunks_per_frame as f32 * governor.scale())
+        .round() as usize)
+        .max(1);
+    context.stream(&mut provider, budget);
+
+    if provider.is_empty() {
+        info!("🌍 Background world streaming complete");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_morton_round_trip() {
+        for (x, z) in [
+            (0, 0),
+            (1, -1),
+            (-30, 29),
+            (i16::MAX as i32, i16::MIN as i32),
+        ] {
+            let coord = ChunkCoord::new(x, z);
+            assert_eq!(morton_decode(morton_encode(coord)), coord);
+        }
+    }
+
+    #[test]
+    fn test_chunk_content_keys_stay_pinned() {
+        // Changing these changes what every existing seed generates
+        for ((x, z), key) in [
+            ((0, 0), 0),
+            ((1, 0), 4),
+            ((-1, 0), 1),
+            ((0, -1), 2),
+            ((3, -2), 30),
+            ((-30, 29), 4045),
+        ] {
+            assert_eq!(chunk_content_key(ChunkCoord::new(x, z)), key);
+        }
+    }
+
+    #[test]
+    fn test_prioritize_pops_closest_first() {
+        let mut provider = ChunkStreamingProvider::default();
+        for x in -3..=3 {
+            provider.queue(ChunkCoord::new(x, 0));
+        }
+        let codes = provider.pending.iter().copied().collect();
+        provider.ordered = prioritize(codes, Vec3::new(500.0, 0.0, 0.0), 200.0);
+
+        assert_eq!(
+            provider.count_pending_within(Vec3::new(500.0, 0.0, 100.0), 250.0, 200.0),
+            3
+        );
+
+        assert_eq!(provider.pop(), Some(ChunkCoord::new(2, 0)));
+        assert_eq!(provider.len(), 6);
+    }
+}
@@ -0,0 +1,52 @@
+//! District zoning for world generation.
+//!
+//! `assets/config/zones.ron` splits the world into downtown, suburbs,
+//! industrial, beach and park districts and gives each a content palette.
+//! Chunk generators read it for building archetypes and vehicle spawn tables,
+//! the NPC spawner for population density, NPC schedules for workplaces and
+//! parks, and the audio plugin for ambience.
+
+use crate::resources::{DistrictMap, ZoneList};
+use bevy::prelude::*;
+
+pub fn load_district_map(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let base = crate::util::asset_path::get_assets_base_path();
+    let path = format!("{base}/config/zones.ron");
+    let list = match std::fs::read_to_string(&path) {
+        Ok(content) => ron::from_str::<ZoneList>(&content).unwrap_or_else(|e| {
+            error!("Failed to parse zones at '{}': {}", path, e);
+            ZoneList::default()
+        }),
+        Err(e) => {
+            info!(
+                "ℹ️ No zone config found, whole world uses one district: {}",
+                e
+            );
+            ZoneList::default()
+        }
+    };
+
+    let mut map = DistrictMap::from_list(list);
+
+    // Only load ambience that exists so missing audio doesn't spam asset errors
+    let clips: Vec<_> = map
+        .profiles()
+        .filter_map(|profile| {
+            let clip = profile.ambient.as_ref()?;
+            if std::path::Path::new(&base).join(clip).exists() {
+                Some((profile.district, asset_server.load(clip.clone())))
+            } else {
+                info!("🔇 Ambient clip '{}' not found, skipping", clip);
+                None
+            }
+        })
+        .collect();
+    map.ambient_clips.extend(clips);
+
+    info!(
+        "🏙️ Loaded {} district zones ({} ambient loops)",
+        map.zones.len(),
+        map.ambient_clips.len()
+    );
+    commands.insert_resource(map);
+}
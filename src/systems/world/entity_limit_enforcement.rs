@@ -1,5 +1,6 @@
 use crate::components::world::EntityLimits;
-use crate::components::{Building, Car, F16, Helicopter, NPCState, Yacht};
+use crate::components::{Building, Car, DynamicContent, F16, Helicopter, NPCState, Yacht};
+use crate::factories::EntityPool;
 use bevy::log::info;
 #[cfg(feature = "debug-ui")]
 use bevy::log::warn;
@@ -9,11 +10,14 @@ type VehicleFilter = Or<(With<Car>, With<Helicopter>, With<F16>, With<Yacht>)>;
 
 /// System to enforce entity limits with FIFO cleanup
 /// Replaces deleted EntityLimitManager service
+#[allow(clippy::too_many_arguments)]
 pub fn enforce_entity_limits(
     mut commands: Commands,
     mut entity_limits: ResMut<EntityLimits>,
+    mut pool: ResMut<EntityPool>,
     time: Res<Time>,
     vehicle_query: Query<Entity, VehicleFilter>,
+    dynamic_content: Query<&DynamicContent>,
     building_query: Query<Entity, With<Building>>,
     npc_query: Query<Entity, With<NPCState>>,
     _tree_query: Query<Entity, With<crate::components::world::DynamicContent>>,
@@ -70,10 +74,15 @@ pub fn enforce_entity_limits(
             .vehicle_entities
             .sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
 
-        // Despawn oldest excess vehicles (automatically recursive in Bevy 0.16)
+        // Pool or despawn oldest excess vehicles (automatically recursive in Bevy 0.16)
         for i in 0..excess.min(entity_limits.vehicle_entities.len()) {
-            if let Some((entity, _)) = entity_limits.vehicle_entities.get(i) {
-                commands.entity(*entity).despawn();
+            if let Some(&(entity, _)) = entity_limits.vehicle_entities.get(i) {
+                match dynamic_content.get(entity) {
+                    Ok(content) => {
+                        pool.release(&mut commands, entity, content.content_type);
+                    }
+                    Err(_) => commands.entity(entity).despawn(),
+                }
             }
         }
 
@@ -105,28 +114,7 @@ pub fn enforce_entity_limits(
         entity_limits.building_entities.drain(0..drain_count);
     }
 
-    // Check and enforce NPC limits
-    let npc_count = npc_query.iter().count();
-    if npc_count > entity_limits.max_npcs {
-        let excess = npc_count - entity_limits.max_npcs;
-        info!(
-            "NPC limit exceeded: {npc_count}/{} (removing {excess} oldest)",
-            entity_limits.max_npcs
-        );
-
-        entity_limits
-            .npc_entities
-            .sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-
-        for i in 0..excess.min(entity_limits.npc_entities.len()) {
-            if let Some((entity, _)) = entity_limits.npc_entities.get(i) {
-                commands.entity(*entity).despawn();
-            }
-        }
-
-        let drain_count = excess.min(entity_limits.npc_entities.len());
-        entity_limits.npc_entities.drain(0..drain_count);
-    }
+    // NPCs are budgeted by distance to the player in `npc_spawn::enforce_npc_budget`
 
     // Periodic cleanup: Remove invalid entities from tracking lists
     if (current_time % 30.0) < 0.1 {
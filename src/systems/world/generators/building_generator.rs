@@ -1,14 +1,15 @@
-use crate::components::ContentType;
 use crate::components::unified_water::UnifiedWaterBody;
+use crate::components::{ContentType, ShopKind};
 use crate::config::GameConfig;
 use crate::constants::WorldEnvConfig;
-use crate::factories::{BuildingFactory, BuildingType};
-use crate::resources::WorldRng;
+use crate::factories::{BuildingFactory, BuildingType, InteriorFactory};
+use crate::resources::DistrictMap;
 use crate::systems::world::unified_world::{
     ChunkCoord, ContentLayer, UnifiedChunkEntity, UnifiedWorldManager,
 };
 use bevy::prelude::*;
 use rand::Rng;
+use rand::rngs::StdRng;
 
 pub struct BuildingGenerator;
 
@@ -153,11 +154,13 @@ impl BuildingGenerator {
         coord: ChunkCoord,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
-        world_rng: &mut WorldRng,
+        rng: &mut StdRng,
+        districts: &DistrictMap,
         water_bodies: &Query<&UnifiedWaterBody>,
         config: &GameConfig,
         env: &WorldEnvConfig,
     ) {
+        crate::profile_scope!("generate_buildings", x = coord.x, z = coord.z);
         let chunk_center = coord.to_world_pos_with_size(world.chunk_size);
         let half_size = world.chunk_size * 0.5;
 
@@ -192,18 +195,20 @@ impl BuildingGenerator {
         }
 
         // Generate building positions - reduced for simplicity
+        let profile = districts.profile_at(chunk_center);
         let building_attempts = if world.is_on_grid_island(chunk_center) {
             60 // Dense Manhattan packing
         } else {
-            (building_density * 8.0) as usize
+            (building_density * profile.building_density * 8.0) as usize
         };
 
         // Calculate grid center from environment config for Manhattan building selection
         let grid_center = Vec3::new(env.islands.grid_x, 0.0, env.islands.grid_z);
+        let interiors = InteriorFactory::new(materials);
 
         for _ in 0..building_attempts {
-            let local_x = world_rng.global().gen_range(-half_size..half_size);
-            let local_z = world_rng.global().gen_range(-half_size..half_size);
+            let local_x = rng.gen_range(-half_size..half_size);
+            let local_z = rng.gen_range(-half_size..half_size);
             let position = Vec3::new(
                 chunk_center.x + local_x,
                 env.land_elevation,
@@ -213,15 +218,24 @@ impl BuildingGenerator {
             // Check on_grid per position
             let on_grid = world.is_on_grid_island(position);
 
+            let mut building_type = BuildingType::Commercial;
             let (footprint_x, footprint_z, height, color, radius) = if on_grid {
-                let (fx, fz, h, c) =
-                    self.choose_manhattan_building(grid_center, position, world_rng.global());
+                let (fx, fz, h, c) = self.choose_manhattan_building(grid_center, position, rng);
                 let r = fx.max(fz) * 0.5;
-                (fx, fz, h, Some(c), r)
+                (fx, fz, h, c, r)
+            } else if let Some(template) = districts.profile_at(position).pick_building(rng) {
+                building_type = template.kind;
+                let (size, h, c) = template.roll(rng);
+                (size, size, h, c, size * 0.5)
             } else {
-                let size = world_rng.global().gen_range(8.0..15.0);
-                let h = world_rng.global().gen_range(8.0..30.0);
-                (size, size, h, None, size * 0.5)
+                let size = rng.gen_range(8.0..15.0);
+                let h = rng.gen_range(8.0..30.0);
+                let c = Color::srgb(
+                    rng.gen_range(0.5..0.9),
+                    rng.gen_range(0.5..0.9),
+                    rng.gen_range(0.5..0.9),
+                );
+                (size, size, h, c, size * 0.5)
             };
 
             // Check if position is valid (on island, not on road with radius, not overlapping, not in water)
@@ -246,11 +260,33 @@ impl BuildingGenerator {
                         coord,
                         position,
                         Vec3::new(footprint_x, height, footprint_z),
+                        building_type,
                         color,
                         meshes,
                         materials,
                         config,
                     ) {
+                        // Zero-lot-line grid blocks have no room for a street door
+                        // or storefront
+                        let size = Vec3::new(footprint_x, height, footprint_z);
+                        if !on_grid
+                            && matches!(building_type, BuildingType::Commercial)
+                            && rng.gen_bool(config.world.shop_chance as f64)
+                        {
+                            let kind = ShopKind::ALL[rng.gen_range(0..ShopKind::ALL.len())];
+                            BuildingFactory::with_config(config.clone()).spawn_storefront(
+                                commands,
+                                meshes,
+                                materials,
+                                building_entity,
+                                size,
+                                kind,
+                            );
+                        } else if !on_grid && rng.gen_bool(config.world.interior_chance as f64) {
+                            let kind = InteriorFactory::interior_kind_for(building_type, rng);
+                            interiors.spawn_door(commands, meshes, building_entity, size, kind);
+                        }
+
                         // Add to placement grid
                         world
                             .placement_grid
@@ -278,26 +314,23 @@ impl BuildingGenerator {
         chunk_coord: ChunkCoord,
         position: Vec3,
         size: Vec3,
-        color: Option<Color>,
+        building_type: BuildingType,
+        color: Color,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
         config: &GameConfig,
     ) -> Result<Entity, String> {
         let factory = BuildingFactory::with_config(config.clone());
 
-        let entity = if let Some(c) = color {
-            factory.spawn_building_with_size(
-                commands,
-                meshes,
-                materials,
-                position,
-                size,
-                BuildingType::Commercial,
-                c,
-            )
-        } else {
-            factory.spawn_building(commands, meshes, materials, position, None)
-        };
+        let entity = factory.spawn_building_with_size(
+            commands,
+            meshes,
+            materials,
+            position,
+            size,
+            building_type,
+            color,
+        );
 
         match entity {
             Ok(entity) => {
@@ -4,11 +4,13 @@
 pub mod building_generator;
 pub mod manhattan_grid;
 pub mod road_generator;
+pub mod street_prop_generator;
 pub mod vegetation_generator;
 pub mod vehicle_generator;
 
 pub use building_generator::BuildingGenerator;
 pub use manhattan_grid::ManhattanGridGenerator;
 pub use road_generator::RoadGenerator;
+pub use street_prop_generator::{StreetPropBatchers, StreetPropGenerator};
 pub use vegetation_generator::VegetationGenerator;
 pub use vehicle_generator::VehicleGenerator;
@@ -3,19 +3,22 @@ use crate::components::unified_water::UnifiedWaterBody;
 use crate::components::{ContentType, DynamicContent, IntersectionEntity, RoadEntity};
 use crate::config::GameConfig;
 use crate::constants::WorldEnvConfig;
-use crate::resources::{MaterialKey, MaterialRegistry, WorldRng};
+use crate::factories::{MaterialFactory, ReflectionHook, ReflectiveMaterials};
+use crate::resources::{MaterialKey, MaterialRegistry};
 use crate::systems::world::road_mesh::{
     generate_road_markings_mesh_local, generate_road_mesh_local,
 };
-use crate::systems::world::road_network::{IntersectionType, RoadSpline, RoadType};
+use crate::systems::world::road_network::{RoadSpline, RoadType};
 use crate::systems::world::unified_world::{
     ChunkCoord, ContentLayer, UnifiedChunkEntity, UnifiedWorldManager,
 };
 use bevy::prelude::*;
+use rand::rngs::StdRng;
 
 pub struct RoadGenerator;
 
 impl RoadGenerator {
+    /// Spawns this chunk's roads and intersections; returns the ids of the roads spawned
     #[allow(clippy::too_many_arguments)]
     pub fn generate_roads(
         &self,
@@ -25,15 +28,17 @@ impl RoadGenerator {
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
         material_registry: &mut MaterialRegistry,
-        world_rng: &mut WorldRng,
+        reflective: &mut ReflectiveMaterials,
+        rng: &mut StdRng,
         water_bodies: &Query<&UnifiedWaterBody>,
         config: &GameConfig,
         env: &WorldEnvConfig,
-    ) {
+    ) -> Vec<u64> {
+        crate::profile_scope!("generate_roads", x = coord.x, z = coord.z);
         // Skip if chunk is not on a terrain island
         let chunk_center = coord.to_world_pos_with_size(world.chunk_size);
         if !world.is_on_terrain_island(chunk_center) {
-            return;
+            return Vec::new();
         }
 
         // Use simple Manhattan grid generator for grid island, organic generator for other islands
@@ -46,12 +51,13 @@ impl RoadGenerator {
             world.road_network.generate_roads_for_cell(
                 IVec2::new(coord.x, coord.z),
                 config.world_streaming.road_cell_size,
-                world_rng.global(),
+                rng,
                 config,
             )
         };
 
         // Create road entities and add to placement grid
+        let mut spawned = Vec::new();
         for road_id in new_road_ids {
             if let Some(road) = world.road_network.roads.get(&road_id).cloned() {
                 // RoadNetwork already validates island boundaries during generation
@@ -67,6 +73,7 @@ impl RoadGenerator {
                     meshes,
                     materials,
                     material_registry,
+                    reflective,
                     config,
                     env,
                 );
@@ -87,6 +94,7 @@ impl RoadGenerator {
                 if let Some(chunk) = world.get_chunk_mut(coord) {
                     chunk.entities.push(road_entity);
                 }
+                spawned.push(road_id);
             }
         }
 
@@ -97,6 +105,7 @@ impl RoadGenerator {
         if let Some(chunk) = world.get_chunk_mut(coord) {
             chunk.roads_generated = true;
         }
+        spawned
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -109,6 +118,7 @@ impl RoadGenerator {
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
         material_registry: &mut MaterialRegistry,
+        reflective: &mut ReflectiveMaterials,
         _config: &GameConfig,
         env: &WorldEnvConfig,
     ) -> Entity {
@@ -118,7 +128,7 @@ impl RoadGenerator {
         center_pos.y = base_y;
 
         let road_material =
-            self.create_road_material(&road.road_type, materials, material_registry);
+            self.create_road_material(&road.road_type, materials, material_registry, reflective);
         let marking_material = self.create_marking_material(materials, material_registry);
 
         let road_entity = commands
@@ -198,7 +208,9 @@ impl RoadGenerator {
                         && intersection_point.z >= chunk_center.z - half_size
                         && intersection_point.z <= chunk_center.z + half_size
                     {
-                        let intersection_type = IntersectionType::Cross;
+                        let intersection_type = world
+                            .road_network
+                            .classify_intersection(intersection_point, &[*road1_id, *road2_id]);
 
                         detected_intersections.push((
                             intersection_point,
@@ -326,6 +338,7 @@ impl RoadGenerator {
         road_type: &RoadType,
         materials: &mut ResMut<Assets<StandardMaterial>>,
         material_registry: &mut MaterialRegistry,
+        reflective: &mut ReflectiveMaterials,
     ) -> Handle<StandardMaterial> {
         let (base_color, roughness) = match road_type {
             RoadType::Highway => (Color::srgb(0.4, 0.4, 0.45), 0.8),
@@ -336,7 +349,13 @@ impl RoadGenerator {
 
         // Use registry for performance (no depth_bias needed with physical offset)
         let key = MaterialKey::road(base_color).with_roughness(roughness);
-        material_registry.get_or_create(materials, key)
+        let handle = material_registry.get_or_create(materials, key);
+        // Asphalt turns glossy in the rain
+        let hook = ReflectionHook::Wet {
+            dry_roughness: roughness,
+        };
+        MaterialFactory::opt_into_reflections(materials, reflective, &handle, hook);
+        handle
     }
 
     fn create_marking_material(
@@ -0,0 +1,378 @@
+use crate::constants::WorldEnvConfig;
+use crate::factories::prefab_factory::PrefabComponent;
+use crate::factories::{PrefabFactory, PrefabId, PrefabOverrides, PrefabRegistry};
+use crate::resources::{District, DistrictMap};
+use crate::systems::rendering::{
+    FENCE_PANEL_LENGTH, Fence, InstancedBatcher, Streetlight, TrafficCone,
+};
+use crate::systems::world::chunk_streaming::morton_encode;
+use crate::systems::world::road_network::{RoadSpline, RoadType};
+use crate::systems::world::unified_world::{
+    ChunkCoord, ContentLayer, SpawnedByChunk, UnifiedChunkEntity, UnifiedWorldManager,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+use rand::rngs::StdRng;
+
+/// Distance between streetlights along one side of a road
+const STREETLIGHT_SPACING: f32 = 35.0;
+/// How far outside the road edge props stand
+const STREETLIGHT_SETBACK: f32 = 1.0;
+const FENCE_SETBACK: f32 = 2.5;
+/// Props keep at least this much clear of other roads' edges
+const CROSSING_CLEARANCE: f32 = 2.0;
+/// Chance that a road gets a row of roadwork cones
+const ROADWORKS_CHANCE: f64 = 0.1;
+const ROADWORKS_CONES: usize = 6;
+const CONE_SPACING: f32 = 2.5;
+/// Centerline sample spacing used for clearance checks
+const POLYLINE_STEP: f32 = 10.0;
+
+/// Trash cans and mailboxes stand between the streetlights, this far out
+const FURNITURE_SETBACK: f32 = 1.5;
+const FURNITURE_CHANCE: f64 = 0.5;
+/// Share of sidewalk furniture that is a mailbox rather than a trash can
+const MAILBOX_SHARE: f64 = 0.3;
+/// Alleys get short lamp posts instead of streetlights
+const LAMP_POST_SPACING: f32 = 25.0;
+const LAMP_POST_SETBACK: f32 = 0.6;
+/// Chance a suburban road gets a wooden fence along its left side
+const PICKET_FENCE_CHANCE: f64 = 0.4;
+const PICKET_PANEL_LENGTH: f32 = 2.5;
+
+/// Instance buffers street props are written into
+#[derive(SystemParam)]
+pub struct StreetPropBatchers<'w> {
+    pub streetlights: ResMut<'w, InstancedBatcher<Streetlight>>,
+    pub cones: ResMut<'w, InstancedBatcher<TrafficCone>>,
+    pub fences: ResMut<'w, InstancedBatcher<Fence>>,
+}
+
+/// Centerline of another road, for keeping props off it
+struct Crossing {
+    half_width: f32,
+    points: Vec<Vec2>,
+}
+
+impl Crossing {
+    fn blocks(&self, position: Vec2) -> bool {
+        self.points.windows(2).any(|s| {
+            distance_to_segment(position, s[0], s[1]) < self.half_width + CROSSING_CLEARANCE
+        })
+    }
+}
+
+/// A street furniture prefab and how high its origin sits above the ground
+#[derive(Clone, Copy)]
+struct PropPrefab {
+    id: PrefabId,
+    lift: f32,
+}
+
+impl PropPrefab {
+    fn find(registry: &PrefabRegistry, name: &str) -> Option<Self> {
+        let id = registry.id_of(name)?;
+        let lift = registry
+            .get(id)?
+            .components
+            .iter()
+            .find_map(|component| match component {
+                PrefabComponent::Transform { translation, .. } => Some(translation.1),
+                _ => None,
+            })
+            .unwrap_or(0.0);
+        Some(Self { id, lift })
+    }
+}
+
+/// Destructible street furniture, spawned as fixed bodies from prefabs
+struct StreetFurniture<'a> {
+    registry: &'a PrefabRegistry,
+    coord: ChunkCoord,
+    trash_can: Option<PropPrefab>,
+    mailbox: Option<PropPrefab>,
+    lamp_post: Option<PropPrefab>,
+    fence_panel: Option<PropPrefab>,
+}
+
+impl<'a> StreetFurniture<'a> {
+    fn new(registry: &'a PrefabRegistry, coord: ChunkCoord) -> Self {
+        Self {
+            registry,
+            coord,
+            trash_can: PropPrefab::find(registry, "trash_can"),
+            mailbox: PropPrefab::find(registry, "mailbox"),
+            lamp_post: PropPrefab::find(registry, "lamp_post"),
+            fence_panel: PropPrefab::find(registry, "fence_panel"),
+        }
+    }
+
+    fn spawn(&self, commands: &mut Commands, prop: Option<PropPrefab>, transform: Transform) {
+        let Some(prop) = prop else {
+            return;
+        };
+        let lifted = transform.with_translation(transform.translation + Vec3::Y * prop.lift);
+        spawn_street_furniture(commands, self.registry, self.coord, prop.id, lifted);
+    }
+}
+
+/// Spawns one piece of street furniture owned by `coord`, at its final transform
+pub fn spawn_street_furniture(
+    commands: &mut Commands,
+    registry: &PrefabRegistry,
+    coord: ChunkCoord,
+    id: PrefabId,
+    transform: Transform,
+) {
+    let overrides = PrefabOverrides::at(transform.translation).with_rotation(transform.rotation);
+    if let Ok(entity) = PrefabFactory::spawn(commands, registry, id, overrides) {
+        commands.entity(entity).insert((
+            UnifiedChunkEntity {
+                coord,
+                layer: ContentLayer::Props,
+            },
+            SpawnedByChunk(morton_encode(coord)),
+        ));
+    }
+}
+
+pub struct StreetPropGenerator;
+
+impl StreetPropGenerator {
+    /// Lines the roads spawned for `coord` with streetlights, fences in
+    /// industrial areas and the odd roadworks, plus destructible trash cans,
+    /// mailboxes, alley lamp posts and suburban fences
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_props(
+        &self,
+        commands: &mut Commands,
+        prefabs: &PrefabRegistry,
+        world: &UnifiedWorldManager,
+        coord: ChunkCoord,
+        road_ids: &[u64],
+        rng: &mut StdRng,
+        districts: &DistrictMap,
+        props: &mut StreetPropBatchers,
+        env: &WorldEnvConfig,
+    ) {
+        crate::profile_scope!("generate_props", x = coord.x, z = coord.z);
+        let furniture = StreetFurniture::new(prefabs, coord);
+        for road_id in road_ids {
+            let Some(road) = world.road_network.roads.get(road_id) else {
+                continue;
+            };
+            let crossings = crossings_near(world, road);
+            let clear = |position: Vec3| {
+                world.road_network.intersection_at(position).is_none()
+                    && !crossings.iter().any(|c| c.blocks(position.xz()))
+            };
+            let length = road.length();
+            if length <= 0.0 {
+                continue;
+            }
+            let half_width = road.road_type.width() * 0.5;
+            let district = districts.district_at(road.evaluate(0.5));
+            let industrial = district == District::Industrial;
+
+            // Alternate sides; industrial roads keep the left side for the fence
+            if road.road_type != RoadType::Alley {
+                let count = (length / STREETLIGHT_SPACING) as usize;
+                for i in 0..count {
+                    let t = (i as f32 + 0.5) * STREETLIGHT_SPACING / length;
+                    let side = if industrial || i % 2 == 0 { 1.0 } else { -1.0 };
+                    let (position, outward) =
+                        roadside(road, t, side, half_width + STREETLIGHT_SETBACK, env);
+                    if clear(position) {
+                        props.streetlights.push(
+                            coord,
+                            Transform::from_translation(position).looking_to(-outward, Vec3::Y),
+                        );
+                    }
+                }
+            }
+
+            if industrial {
+                let panels = (length / FENCE_PANEL_LENGTH) as usize;
+                for i in 0..panels {
+                    let t = (i as f32 + 0.5) * FENCE_PANEL_LENGTH / length;
+                    let (position, outward) =
+                        roadside(road, t, -1.0, half_width + FENCE_SETBACK, env);
+                    if clear(position) {
+                        props.fences.push(
+                            coord,
+                            Transform::from_translation(position).looking_to(outward, Vec3::Y),
+                        );
+                    }
+                }
+            }
+
+            if road.road_type != RoadType::Highway && rng.gen_bool(ROADWORKS_CHANCE) {
+                // Cones taper in from the right edge, closing off the kerb lane
+                let start = rng.gen_range(0.2..0.8);
+                for k in 0..ROADWORKS_CONES {
+                    let t = start + k as f32 * CONE_SPACING / length;
+                    if t > 1.0 {
+                        break;
+                    }
+                    let lateral = half_width - 0.5 - k as f32 * 0.4;
+                    let (position, _) = roadside(road, t, 1.0, lateral, env);
+                    if clear(position) {
+                        props
+                            .cones
+                            .push(coord, Transform::from_translation(position));
+                    }
+                }
+            }
+
+            // Trash cans and mailboxes between the streetlights
+            let sidewalks = matches!(district, District::Downtown | District::Suburbs)
+                && !matches!(road.road_type, RoadType::Highway | RoadType::Alley);
+            if sidewalks {
+                let count = (length / STREETLIGHT_SPACING) as usize;
+                for i in 1..count {
+                    if !rng.gen_bool(FURNITURE_CHANCE) {
+                        continue;
+                    }
+                    let prop = if rng.gen_bool(MAILBOX_SHARE) {
+                        furniture.mailbox
+                    } else {
+                        furniture.trash_can
+                    };
+                    let t = i as f32 * STREETLIGHT_SPACING / length;
+                    let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+                    let (position, outward) =
+                        roadside(road, t, side, half_width + FURNITURE_SETBACK, env);
+                    if clear(position) {
+                        furniture.spawn(
+                            commands,
+                            prop,
+                            Transform::from_translation(position).looking_to(-outward, Vec3::Y),
+                        );
+                    }
+                }
+            }
+
+            if road.road_type == RoadType::Alley {
+                let count = (length / LAMP_POST_SPACING) as usize;
+                for i in 0..count {
+                    let t = (i as f32 + 0.5) * LAMP_POST_SPACING / length;
+                    let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+                    let (position, _) =
+                        roadside(road, t, side, half_width + LAMP_POST_SETBACK, env);
+                    if clear(position) {
+                        furniture.spawn(
+                            commands,
+                            furniture.lamp_post,
+                            Transform::from_translation(position),
+                        );
+                    }
+                }
+            }
+
+            if district == District::Suburbs
+                && road.road_type != RoadType::Highway
+                && rng.gen_bool(PICKET_FENCE_CHANCE)
+            {
+                let panels = (length / PICKET_PANEL_LENGTH) as usize;
+                for i in 0..panels {
+                    let t = (i as f32 + 0.5) * PICKET_PANEL_LENGTH / length;
+                    let (position, outward) =
+                        roadside(road, t, -1.0, half_width + FENCE_SETBACK, env);
+                    if clear(position) {
+                        furniture.spawn(
+                            commands,
+                            furniture.fence_panel,
+                            Transform::from_translation(position).looking_to(outward, Vec3::Y),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Ground point `offset` to the right (`side` 1) or left (`side` -1) of the
+/// centerline at `t`, and the unit direction pointing away from the road
+fn roadside(
+    road: &RoadSpline,
+    t: f32,
+    side: f32,
+    offset: f32,
+    env: &WorldEnvConfig,
+) -> (Vec3, Vec3) {
+    let outward = road.direction(t).cross(Vec3::Y).normalize_or_zero() * side;
+    let mut position = road.evaluate(t) + outward * offset;
+    position.y = env.land_elevation;
+    (position, outward)
+}
+
+/// Other roads whose bounds come near `road`
+fn crossings_near(world: &UnifiedWorldManager, road: &RoadSpline) -> Vec<Crossing> {
+    let (min, max) = bounds(road);
+    let reach = Vec2::splat(FENCE_SETBACK + CROSSING_CLEARANCE + RoadType::MainStreet.width());
+    world
+        .road_network
+        .roads
+        .values()
+        .filter(|other| other.id != road.id)
+        .filter(|other| {
+            let (other_min, other_max) = bounds(other);
+            other_min.cmple(max + reach).all() && other_max.cmpge(min - reach).all()
+        })
+        .map(|other| {
+            let steps = ((other.length() / POLYLINE_STEP).ceil() as usize).clamp(1, 256);
+            Crossing {
+                half_width: other.road_type.width() * 0.5,
+                points: (0..=steps)
+                    .map(|i| other.evaluate(i as f32 / steps as f32).xz())
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn bounds(road: &RoadSpline) -> (Vec2, Vec2) {
+    road.control_points.iter().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), point| (min.min(point.xz()), max.max(point.xz())),
+    )
+}
+
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 0.0 {
+        ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    p.distance(a + ab * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_props_stay_off_crossing_road() {
+        let crossing = Crossing {
+            half_width: 10.0,
+            points: vec![Vec2::new(0.0, -50.0), Vec2::new(0.0, 50.0)],
+        };
+        assert!(crossing.blocks(Vec2::new(11.0, 20.0)));
+        assert!(!crossing.blocks(Vec2::new(12.5, 20.0)));
+        assert!(!crossing.blocks(Vec2::new(0.0, 70.0)));
+    }
+
+    #[test]
+    fn test_street_furniture_prefabs_ship() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/prefabs");
+        let mut registry = PrefabRegistry::default();
+        registry.load_directory(&dir).unwrap();
+
+        for name in ["trash_can", "mailbox", "lamp_post", "fence_panel"] {
+            let prop = PropPrefab::find(&registry, name).expect(name);
+            assert!(prop.lift > 0.0, "{name} should stand on the ground");
+        }
+    }
+}
@@ -1,16 +1,15 @@
-use crate::bundles::VisibleChildBundle;
 use crate::components::ContentType;
 use crate::components::unified_water::UnifiedWaterBody;
 use crate::config::GameConfig;
 use crate::constants::WorldEnvConfig;
-use crate::resources::WorldRng;
+use crate::systems::rendering::{InstancedBatcher, PalmTree};
 use crate::systems::world::unified_world::{
     ChunkCoord, ContentLayer, UnifiedChunkEntity, UnifiedWorldManager,
 };
 use bevy::prelude::*;
-use bevy::render::view::visibility::VisibilityRange;
 use bevy_rapier3d::prelude::*;
 use rand::Rng;
+use rand::rngs::StdRng;
 
 pub struct VegetationGenerator;
 
@@ -21,13 +20,13 @@ impl VegetationGenerator {
         commands: &mut Commands,
         world: &mut UnifiedWorldManager,
         coord: ChunkCoord,
-        meshes: &mut ResMut<Assets<Mesh>>,
-        materials: &mut ResMut<Assets<StandardMaterial>>,
-        world_rng: &mut WorldRng,
+        rng: &mut StdRng,
+        palms: &mut InstancedBatcher<PalmTree>,
         water_bodies: &Query<&UnifiedWaterBody>,
         config: &GameConfig,
         env: &WorldEnvConfig,
     ) {
+        crate::profile_scope!("generate_vegetation", x = coord.x, z = coord.z);
         let chunk_center = coord.to_world_pos_with_size(world.chunk_size);
         let half_size = world.chunk_size * 0.5;
 
@@ -49,8 +48,8 @@ impl VegetationGenerator {
         let mut trees_spawned = 0;
 
         for _ in 0..tree_attempts {
-            let local_x = world_rng.global().gen_range(-half_size..half_size);
-            let local_z = world_rng.global().gen_range(-half_size..half_size);
+            let local_x = rng.gen_range(-half_size..half_size);
+            let local_z = rng.gen_range(-half_size..half_size);
             let position = Vec3::new(
                 chunk_center.x + local_x,
                 env.land_elevation,
@@ -66,7 +65,7 @@ impl VegetationGenerator {
                     .can_place(position, ContentType::Tree, 3.0, 10.0)
             {
                 if let Ok(tree_entity) =
-                    self.spawn_palm_tree(commands, coord, position, meshes, materials, config)
+                    self.spawn_palm_tree(commands, coord, position, palms, config)
                 {
                     trees_spawned += 1;
 
@@ -96,65 +95,26 @@ impl VegetationGenerator {
         }
     }
 
+    /// Palm with a trunk collider; its meshes are drawn through the palm tree batch
     fn spawn_palm_tree(
         &self,
         commands: &mut Commands,
         chunk_coord: ChunkCoord,
         position: Vec3,
-        meshes: &mut ResMut<Assets<Mesh>>,
-        materials: &mut ResMut<Assets<StandardMaterial>>,
+        palms: &mut InstancedBatcher<PalmTree>,
         config: &GameConfig,
     ) -> Result<Entity, String> {
-        // Create palm tree parent entity
         let palm_entity = commands
             .spawn((
                 Transform::from_translation(position),
                 Visibility::Visible,
-                InheritedVisibility::VISIBLE,
-                ViewVisibility::default(),
-                // Removed parent VisibilityRange - let children control their own culling
                 UnifiedChunkEntity {
                     coord: chunk_coord,
                     layer: ContentLayer::Vegetation,
                 },
             ))
             .id();
-
-        // Simple trunk - needs own VisibilityRange (doesn't inherit in 0.16)
-        commands.spawn((
-            Mesh3d(meshes.add(Cylinder::new(0.3, 8.0))),
-            MeshMaterial3d(materials.add(Color::srgb(0.4, 0.25, 0.15))), // Brown trunk
-            Transform::from_xyz(0.0, 4.0, 0.0),
-            ChildOf(palm_entity),
-            VisibleChildBundle::default(),
-            VisibilityRange {
-                start_margin: 0.0..0.0,
-                end_margin: (config.world_streaming.vegetation_cull_distance * 0.9)
-                    ..(config.world_streaming.vegetation_cull_distance * 1.1),
-                use_aabb: false,
-            },
-        ));
-
-        // Simple fronds - 4 green rectangles arranged in a cross
-        for i in 0..4 {
-            let angle = (i as f32) * std::f32::consts::PI / 2.0;
-
-            commands.spawn((
-                Mesh3d(meshes.add(Cuboid::new(2.5, 0.1, 0.8))),
-                MeshMaterial3d(materials.add(Color::srgb(0.2, 0.6, 0.25))), // Green fronds
-                Transform::from_xyz(angle.cos() * 1.2, 7.5, angle.sin() * 1.2).with_rotation(
-                    Quat::from_rotation_y(angle) * Quat::from_rotation_z(-0.2), // Slight droop
-                ),
-                ChildOf(palm_entity),
-                VisibleChildBundle::default(),
-                VisibilityRange {
-                    start_margin: 0.0..0.0,
-                    end_margin: (config.world_streaming.vegetation_cull_distance * 0.9)
-                        ..(config.world_streaming.vegetation_cull_distance * 1.1),
-                    use_aabb: true, // Use AABB for accurate culling
-                },
-            ));
-        }
+        palms.push(chunk_coord, Transform::from_translation(position));
 
         // Tree trunk collider from config
         let tree_config = &config.world_objects.palm_tree;
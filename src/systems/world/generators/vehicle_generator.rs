@@ -1,12 +1,14 @@
-use crate::components::{ContentType, VehicleType};
+use crate::components::{ContentType, TrafficCandidate, VehicleType};
 use crate::config::GameConfig;
-use crate::factories::VehicleFactory;
-use crate::resources::WorldRng;
+use crate::factories::{EntityPool, VehicleFactory};
+use crate::resources::{DistrictMap, DistrictProfile};
+use crate::systems::world::chunk_streaming::morton_encode;
 use crate::systems::world::unified_world::{
-    ChunkCoord, ContentLayer, UnifiedChunkEntity, UnifiedWorldManager,
+    ChunkCoord, ContentLayer, SpawnedByChunk, UnifiedChunkEntity, UnifiedWorldManager,
 };
 use bevy::prelude::*;
 use rand::Rng;
+use rand::rngs::StdRng;
 
 pub struct VehicleGenerator;
 
@@ -20,9 +22,12 @@ impl VehicleGenerator {
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
         asset_server: &Res<AssetServer>,
-        world_rng: &mut WorldRng,
+        pool: &mut EntityPool,
+        rng: &mut StdRng,
+        districts: &DistrictMap,
         config: &GameConfig,
     ) {
+        crate::profile_scope!("generate_vehicles", x = coord.x, z = coord.z);
         let chunk_center = coord.to_world_pos_with_size(world.chunk_size);
         let half_size = world.chunk_size * 0.5;
 
@@ -47,10 +52,11 @@ impl VehicleGenerator {
         }
 
         // Generate road vehicles
-        let vehicle_attempts = 8;
+        let profile = districts.profile_at(chunk_center);
+        let vehicle_attempts = (8.0 * profile.vehicle_density).round() as usize;
         for _ in 0..vehicle_attempts {
-            let local_x = world_rng.global().gen_range(-half_size..half_size);
-            let local_z = world_rng.global().gen_range(-half_size..half_size);
+            let local_x = rng.gen_range(-half_size..half_size);
+            let local_z = rng.gen_range(-half_size..half_size);
             let position = Vec3::new(
                 chunk_center.x + local_x,
                 config.world_env.land_elevation,
@@ -71,7 +77,9 @@ impl VehicleGenerator {
                     meshes,
                     materials,
                     asset_server,
-                    world_rng,
+                    pool,
+                    rng,
+                    profile,
                     config,
                 ) {
                     world
@@ -86,9 +94,9 @@ impl VehicleGenerator {
         }
 
         // Generate aircraft in open areas (2% chance per chunk)
-        if world_rng.global().gen_bool(0.02) {
-            let local_x = world_rng.global().gen_range(-half_size..half_size);
-            let local_z = world_rng.global().gen_range(-half_size..half_size);
+        if rng.gen_bool(0.02) {
+            let local_x = rng.gen_range(-half_size..half_size);
+            let local_z = rng.gen_range(-half_size..half_size);
             let position = Vec3::new(
                 chunk_center.x + local_x,
                 config.world_env.land_elevation + config.world_env.spawn_drop_height,
@@ -111,7 +119,7 @@ impl VehicleGenerator {
                     meshes,
                     materials,
                     asset_server,
-                    world_rng,
+                    rng,
                     config,
                 ) {
                     world
@@ -140,27 +148,48 @@ impl VehicleGenerator {
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
         asset_server: &Res<AssetServer>,
-        world_rng: &mut WorldRng,
+        pool: &mut EntityPool,
+        rng: &mut StdRng,
+        profile: &DistrictProfile,
         config: &GameConfig,
     ) -> Result<Entity, String> {
         let factory = VehicleFactory::with_config(config.clone());
-        let vehicle_types = [VehicleType::SuperCar];
-        let vehicle_type = vehicle_types[world_rng.global().gen_range(0..vehicle_types.len())];
+        let vehicle_type = profile.pick_vehicle(rng).unwrap_or(VehicleType::SuperCar);
+        let color = profile
+            .pick_vehicle_color(rng)
+            .unwrap_or_else(|| VehicleFactory::car_color_from(rng));
 
-        match factory.spawn_vehicle_by_type(
-            commands,
-            meshes,
-            materials,
-            asset_server,
-            vehicle_type,
-            position,
-            None,
-        ) {
+        // Traffic cars churn with streaming, so they reuse pooled bodies
+        let spawned = match vehicle_type {
+            VehicleType::SuperCar => factory.spawn_pooled_super_car(
+                commands,
+                pool,
+                meshes,
+                materials,
+                asset_server,
+                position,
+                Some(color),
+            ),
+            _ => factory.spawn_vehicle_by_type(
+                commands,
+                meshes,
+                materials,
+                asset_server,
+                vehicle_type,
+                position,
+                Some(color),
+            ),
+        };
+        match spawned {
             Ok(entity) => {
-                commands.entity(entity).insert(UnifiedChunkEntity {
-                    coord: chunk_coord,
-                    layer: ContentLayer::Vehicles,
-                });
+                commands.entity(entity).insert((
+                    UnifiedChunkEntity {
+                        coord: chunk_coord,
+                        layer: ContentLayer::Vehicles,
+                    },
+                    SpawnedByChunk(morton_encode(chunk_coord)),
+                    TrafficCandidate,
+                ));
                 Ok(entity)
             }
             Err(e) => Err(format!("Failed to spawn ground vehicle: {e}")),
@@ -176,12 +205,12 @@ impl VehicleGenerator {
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
         asset_server: &Res<AssetServer>,
-        world_rng: &mut WorldRng,
+        rng: &mut StdRng,
         config: &GameConfig,
     ) -> Result<Entity, String> {
         let factory = VehicleFactory::with_config(config.clone());
         let aircraft_types = [VehicleType::Helicopter, VehicleType::F16];
-        let vehicle_type = aircraft_types[world_rng.global().gen_range(0..aircraft_types.len())];
+        let vehicle_type = aircraft_types[rng.gen_range(0..aircraft_types.len())];
 
         match factory.spawn_vehicle_by_type(
             commands,
@@ -193,10 +222,13 @@ impl VehicleGenerator {
             None,
         ) {
             Ok(entity) => {
-                commands.entity(entity).insert(UnifiedChunkEntity {
-                    coord: chunk_coord,
-                    layer: ContentLayer::Vehicles,
-                });
+                commands.entity(entity).insert((
+                    UnifiedChunkEntity {
+                        coord: chunk_coord,
+                        layer: ContentLayer::Vehicles,
+                    },
+                    SpawnedByChunk(morton_encode(chunk_coord)),
+                ));
                 Ok(entity)
             }
             Err(e) => Err(format!("Failed to spawn aircraft: {e}")),
@@ -0,0 +1,300 @@
+//! Hierarchical LOD for the building skyline.
+//!
+//! Buildings are clustered into square regions as they spawn. Each cluster
+//! gets a proxy: one mesh merging every member box, coloured per vertex so a
+//! single material serves all proxies. Once the camera is farther than
+//! `hlod_distance` from a cluster, the whole cluster swaps to its proxy, so a
+//! district of buildings costs one draw instead of one per building.
+//!
+//! Swaps are held back by `lod_hysteresis` on either side of the distance, and
+//! measured from the nearer of the camera and where it will be `lod_lookahead`
+//! seconds from now, so clusters ahead of a fast camera get their buildings
+//! back before it arrives.
+
+use crate::components::{
+    Building, CurrentInterior, ExteriorCulled, HlodClusters, HlodProxy, MainCamera, NonResident,
+};
+use crate::config::GameConfig;
+use crate::systems::performance::frame_budget::FrameBudgetGovernor;
+use crate::systems::world::chunk_streaming::StreamingFocus;
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+
+/// Proxies rebuilt per frame while the world is streaming in
+const HLOD_BAKES_PER_FRAME: usize = 2;
+/// Seconds between swap passes
+const HLOD_SWAP_INTERVAL: f32 = 0.25;
+
+/// Box of a merged proxy: center relative to the proxy origin, full size and linear colour
+pub type ProxyBox = (Vec3, Vec3, [f32; 4]);
+
+/// Adds newly spawned buildings to the cluster of their region
+pub fn register_hlod_members(
+    mut clusters: ResMut<HlodClusters>,
+    config: Res<GameConfig>,
+    added: Query<(Entity, &Transform, &Building), Added<Building>>,
+) {
+    let region_size = config.performance.hlod_region_size;
+    for (entity, transform, building) in &added {
+        let region = HlodClusters::region_of(transform.translation, region_size);
+        let cluster = clusters.clusters.entry(region).or_default();
+        let half = building.scale.xz() * 0.5;
+        cluster.members.push(entity);
+        cluster.min = cluster.min.min(transform.translation.xz() - half);
+        cluster.max = cluster.max.max(transform.translation.xz() + half);
+        cluster.dirty = true;
+    }
+}
+
+/// Rebuilds the proxy mesh of clusters whose members changed
+pub fn bake_hlod_proxies(
+    mut commands: Commands,
+    mut clusters: ResMut<HlodClusters>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut proxy_material: Local<Option<Handle<StandardMaterial>>>,
+    config: Res<GameConfig>,
+    buildings: Query<(
+        &Transform,
+        &Building,
+        &MeshMaterial3d<StandardMaterial>,
+        Option<&NonResident>,
+    )>,
+) {
+    let region_size = config.performance.hlod_region_size;
+    let dirty: Vec<IVec2> = clusters
+        .clusters
+        .iter()
+        .filter(|(_, cluster)| cluster.dirty)
+        .map(|(region, _)| *region)
+        .take(HLOD_BAKES_PER_FRAME)
+        .collect();
+
+    for region in dirty {
+        let Some(cluster) = clusters.clusters.get_mut(&region) else {
+            continue;
+        };
+        crate::profile_scope!("bake_hlod_proxy", members = cluster.members.len());
+        let origin = Vec3::new(
+            (region.x as f32 + 0.5) * region_size,
+            0.0,
+            (region.y as f32 + 0.5) * region_size,
+        );
+        cluster.members.retain(|member| buildings.contains(*member));
+        let boxes: Vec<ProxyBox> = cluster
+            .members
+            .iter()
+            .filter_map(|member| buildings.get(*member).ok())
+            .map(|(transform, building, material, parked)| {
+                // A far member's material may be parked off the GPU
+                let color = materials
+                    .get(&material.0)
+                    .or_else(|| parked.and_then(|parked| parked.material.as_ref()))
+                    .map_or(Color::srgb(0.7, 0.7, 0.7), |m| m.base_color);
+                (
+                    transform.translation - origin,
+                    building.scale,
+                    color.to_linear().to_f32_array(),
+                )
+            })
+            .collect();
+        let mesh = meshes.add(merge_boxes(&boxes));
+
+        if let Some(proxy) = cluster.proxy {
+            commands.entity(proxy).insert(Mesh3d(mesh));
+        } else {
+            let material = proxy_material
+                .get_or_insert_with(|| {
+                    materials.add(StandardMaterial {
+                        base_color: Color::WHITE,
+                        perceptual_roughness: 0.9,
+                        ..default()
+                    })
+                })
+                .clone();
+            cluster.proxy = Some(
+                commands
+                    .spawn((
+                        Name::new(format!("HlodProxy_{}_{}", region.x, region.y)),
+                        HlodProxy { region },
+                        Mesh3d(mesh),
+                        MeshMaterial3d(material),
+                        Transform::from_translation(origin),
+                        Visibility::Hidden,
+                    ))
+                    .id(),
+            );
+        }
+        cluster.dirty = false;
+        cluster.resync = true;
+    }
+}
+
+/// Swaps each cluster between its buildings and its proxy by camera distance
+#[allow(clippy::too_many_arguments)]
+pub fn hlod_swap_system(
+    time: Res<Time>,
+    mut since_update: Local<f32>,
+    config: Res<GameConfig>,
+    governor: Res<FrameBudgetGovernor>,
+    focus: Res<StreamingFocus>,
+    current: Res<CurrentInterior>,
+    mut clusters: ResMut<HlodClusters>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    mut visibilities: Query<&mut Visibility, Without<ExteriorCulled>>,
+) {
+    *since_update += time.delta_secs();
+    if *since_update < HLOD_SWAP_INTERVAL {
+        return;
+    }
+    *since_update = 0.0;
+
+    // The exterior is culled wholesale while inside
+    if current.0.is_some() {
+        return;
+    }
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    crate::profile_scope!("hlod_swap", clusters = clusters.clusters.len());
+    let camera = camera.translation();
+    let hlod_distance = config.performance.hlod_distance
+        * config.graphics.quality.settings().lod_bias
+        * governor.scale();
+    let band = config.world_streaming.lod_hysteresis.max(0.0);
+    let ahead = camera + focus.velocity * config.world_streaming.lod_lookahead.max(0.0);
+
+    for cluster in clusters.clusters.values_mut() {
+        let Some(proxy) = cluster.proxy else {
+            continue;
+        };
+        let distance = cluster.distance_to(camera).min(cluster.distance_to(ahead));
+        let far = past_threshold(distance, hlod_distance, band, cluster.using_proxy);
+        if far == cluster.using_proxy && !cluster.resync {
+            continue;
+        }
+        let (proxy_visibility, member_visibility) = if far {
+            (Visibility::Inherited, Visibility::Hidden)
+        } else {
+            (Visibility::Hidden, Visibility::Inherited)
+        };
+        if let Ok(mut visibility) = visibilities.get_mut(proxy) {
+            *visibility = proxy_visibility;
+        }
+        for member in &cluster.members {
+            if let Ok(mut visibility) = visibilities.get_mut(*member) {
+                *visibility = member_visibility;
+            }
+        }
+        cluster.using_proxy = far;
+        cluster.resync = false;
+    }
+}
+
+/// Whether `distance` is past `threshold`, given whether it was last time:
+/// crossing takes going `band` beyond the threshold in either direction
+pub fn past_threshold(distance: f32, threshold: f32, band: f32, was_past: bool) -> bool {
+    if was_past {
+        distance > threshold - band
+    } else {
+        distance > threshold + band
+    }
+}
+
+/// One mesh holding the top and four sides of every box; bottoms are never seen
+pub fn merge_boxes(boxes: &[ProxyBox]) -> Mesh {
+    // (normal, u, v) with u × v = normal so each quad winds counter-clockwise from outside
+    const FACES: [(Vec3, Vec3, Vec3); 5] = [
+        (Vec3::Y, Vec3::Z, Vec3::X),
+        (Vec3::X, Vec3::Y, Vec3::Z),
+        (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+        (Vec3::Z, Vec3::X, Vec3::Y),
+        (Vec3::NEG_Z, Vec3::Y, Vec3::X),
+    ];
+
+    let mut positions = Vec::with_capacity(boxes.len() * 20);
+    let mut normals = Vec::with_capacity(boxes.len() * 20);
+    let mut colors = Vec::with_capacity(boxes.len() * 20);
+    let mut indices = Vec::with_capacity(boxes.len() * 30);
+
+    for (center, size, color) in boxes {
+        let half = *size * 0.5;
+        let extent = |axis: Vec3| (half * axis.abs()).element_sum();
+        for (normal, u, v) in FACES {
+            let face = *center + normal * extent(normal);
+            let (du, dv) = (u * extent(u), v * extent(v));
+            let base = positions.len() as u32;
+            for corner in [
+                face - du - dv,
+                face + du - dv,
+                face + du + dv,
+                face - du + dv,
+            ] {
+                positions.push(corner.to_array());
+                normals.push(normal.to_array());
+                colors.push(*color);
+            }
+            indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::mesh::VertexAttributeValues;
+
+    #[test]
+    fn test_threshold_holds_inside_band() {
+        assert!(!past_threshold(720.0, 700.0, 50.0, false));
+        assert!(past_threshold(751.0, 700.0, 50.0, false));
+        assert!(past_threshold(680.0, 700.0, 50.0, true));
+        assert!(!past_threshold(649.0, 700.0, 50.0, true));
+        assert!(past_threshold(701.0, 700.0, 0.0, false));
+    }
+
+    #[test]
+    fn test_merged_proxy_faces_point_outward() {
+        let boxes = [
+            (
+                Vec3::new(0.0, 10.0, 0.0),
+                Vec3::new(10.0, 20.0, 10.0),
+                [1.0; 4],
+            ),
+            (
+                Vec3::new(30.0, 5.0, 0.0),
+                Vec3::new(8.0, 10.0, 8.0),
+                [0.5; 4],
+            ),
+        ];
+        let mesh = merge_boxes(&boxes);
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("missing indices");
+        };
+        assert_eq!(positions.len(), 40);
+        assert_eq!(indices.len(), 60);
+
+        // Every triangle faces away from the center of the box it belongs to
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(positions[triangle[i] as usize]));
+            let center = boxes[triangle[0] as usize / 20].0;
+            let normal = (b - a).cross(c - a);
+            assert!(normal.dot((a + b + c) / 3.0 - center) > 0.0);
+        }
+    }
+}
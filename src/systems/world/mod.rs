@@ -1,11 +1,21 @@
 // pub mod culling; // DELETED: Using Bevy's built-in VisibilityRange instead
+pub mod chunk_eviction;
+pub mod chunk_streaming;
 pub mod debug;
+pub mod districts;
+pub mod hlod;
 pub mod npc;
 pub mod npc_animation;
+pub mod npc_persistence;
+pub mod pedestrians;
+pub mod ragdoll;
 pub mod performance;
+pub mod schedules;
 pub mod road_generation;
 pub mod road_mesh;
 pub mod road_network;
+pub mod terrain_clipmap;
+pub mod terrain_height;
 
 // NEW UNIFIED WORLD SYSTEM
 pub mod generators; // NEW: Focused chunk generators following AGENT.md simplicity principles
@@ -1,4 +1,4 @@
-use crate::components::{ActiveEntity, HumanAnimation, HumanMovement, NPC};
+use crate::components::{ActiveEntity, HumanAnimation, HumanMovement, NPC, Pedestrian, Ragdoll};
 use crate::constants::WorldEnvConfig;
 use bevy::prelude::*;
 use bevy::render::view::visibility::VisibilityRange;
@@ -6,6 +6,7 @@ use bevy_rapier3d::prelude::*;
 use rand::Rng;
 
 /// Simple NPC movement that follows direct AI patterns
+/// Pedestrians on the sidewalk graph are driven by `pedestrian_behavior_system` instead
 #[allow(clippy::type_complexity)]
 pub fn simple_npc_movement(
     time: Res<Time>,
@@ -19,7 +20,12 @@ pub fn simple_npc_movement(
             &mut HumanMovement,
             &mut HumanAnimation,
         ),
-        (With<VisibilityRange>, Without<RigidBodyDisabled>),
+        (
+            With<VisibilityRange>,
+            Without<RigidBodyDisabled>,
+            Without<Pedestrian>,
+            Without<Ragdoll>,
+        ),
     >,
     active_query: Query<&Transform, (With<ActiveEntity>, Without<NPC>)>,
 ) {
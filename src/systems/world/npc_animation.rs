@@ -1,6 +1,6 @@
 use crate::components::{
     HumanAnimation, HumanMovement, NPC, NPCHead, NPCLeftArm, NPCLeftFoot, NPCLeftLeg, NPCRightArm,
-    NPCRightFoot, NPCRightLeg, NPCTorso,
+    NPCRightFoot, NPCRightLeg, NPCTorso, Ragdoll,
 };
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -64,7 +64,7 @@ impl AnimationValues {
 #[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn npc_animation_system(
     time: Res<Time>,
-    npc_data: Query<(Entity, &HumanAnimation, &HumanMovement), With<NPC>>,
+    npc_data: Query<(Entity, &HumanAnimation, &HumanMovement), (With<NPC>, Without<Ragdoll>)>,
     mut head_query: Query<
         (&ChildOf, &mut Transform),
         (
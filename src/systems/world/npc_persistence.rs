@@ -0,0 +1,345 @@
+//! Streaming-aware NPC persistence.
+//!
+//! NPCs that drift beyond the streaming radius are packed into a `DormantNpc`
+//! record held by `UnifiedWorldManager` and their entity goes back to the
+//! `EntityPool`. When the active entity comes back in range the record is
+//! rehydrated with the same position, appearance and behaviour state. The
+//! store is bounded by `dormant_npc_budget_kb`; past it the longest-dormant
+//! NPCs are forgotten and the spawner replaces them with fresh ones. Stored
+//! NPCs with a schedule keep following it, so they may wake up somewhere else.
+
+use crate::components::{
+    ActiveEntity, ContentType, NPC, NPCAppearance, NPCState, NpcSchedule, Pedestrian, Ragdoll,
+    Routines,
+};
+use crate::config::GameConfig;
+use crate::factories::{EntityPool, NPCFactory, NPCType, Pooled};
+use crate::resources::{NPCAssetCache, WorldClock, WorldRng};
+use crate::systems::world::npc_spawn::npc_bundle_with_state;
+use crate::systems::world::unified_world::UnifiedWorldManager;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Seconds between dormancy sweeps
+const SWEEP_INTERVAL: f32 = 1.0;
+
+/// NPCs rehydrate inside this fraction of the streaming radius, so they don't flicker at the edge
+const REHYDRATE_RADIUS_SCALE: f32 = 0.9;
+
+/// Most NPCs rehydrated per sweep, to spread the spawn cost
+const MAX_REHYDRATES_PER_SWEEP: usize = 16;
+
+/// Everything needed to bring an out-of-range NPC back as it was
+#[derive(Clone)]
+pub struct DormantNpc {
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub state: NPCState,
+    pub target_position: Vec3,
+    pub speed: f32,
+    pub pedestrian: Option<Pedestrian>,
+    pub schedule: Option<NpcSchedule>,
+    /// Factory type and colours for NPCs with articulated bodies
+    pub body: Option<(NPCType, NPCAppearance)>,
+}
+
+/// Dormant NPCs, oldest first, capped by a memory budget
+#[derive(Clone, Default)]
+pub struct DormantNpcStore {
+    npcs: VecDeque<DormantNpc>,
+    capacity: usize,
+}
+
+impl DormantNpcStore {
+    pub fn with_budget(budget_bytes: usize) -> Self {
+        Self {
+            npcs: VecDeque::new(),
+            capacity: budget_bytes / std::mem::size_of::<DormantNpc>(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.npcs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.npcs.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Vec3> + '_ {
+        self.npcs.iter().map(|npc| npc.position)
+    }
+
+    /// Approximate memory held by stored NPCs
+    pub fn memory_bytes(&self) -> usize {
+        self.npcs.len() * std::mem::size_of::<DormantNpc>()
+    }
+
+    /// Stores an NPC, dropping the longest-dormant ones to stay within budget.
+    /// Returns how many were dropped.
+    pub fn push(&mut self, npc: DormantNpc) -> usize {
+        if self.capacity == 0 {
+            return 1;
+        }
+        let mut dropped = 0;
+        while self.npcs.len() >= self.capacity {
+            self.npcs.pop_front();
+            dropped += 1;
+        }
+        self.npcs.push_back(npc);
+        dropped
+    }
+
+    /// Moves scheduled NPCs on by `seconds` of their routine at `hour`
+    pub fn advance_schedules(&mut self, routines: &Routines, hour: f32, seconds: f32) {
+        for npc in &mut self.npcs {
+            let Some(schedule) = npc.schedule.as_mut() else {
+                continue;
+            };
+            if let Some(routine) = routines.routines.get(schedule.routine) {
+                schedule.follow(routine, hour);
+            }
+            let position = schedule.step(npc.position, npc.speed * seconds);
+            if position != npc.position {
+                npc.position = position;
+                if let Some(pedestrian) = npc.pedestrian.as_mut() {
+                    pedestrian.target_node = None;
+                    pedestrian.previous_node = None;
+                }
+            }
+            npc.state.behavior = schedule.behavior();
+        }
+    }
+
+    /// Removes and returns up to `limit` NPCs within `radius` of `center` (ground plane)
+    pub fn take_within(&mut self, center: Vec3, radius: f32, limit: usize) -> Vec<DormantNpc> {
+        let radius_sq = radius * radius;
+        let mut taken = Vec::new();
+        let mut i = 0;
+        while i < self.npcs.len() && taken.len() < limit {
+            if self.npcs[i].position.xz().distance_squared(center.xz()) <= radius_sq {
+                taken.extend(self.npcs.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        taken
+    }
+}
+
+type PersistableNpcQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static NPCState,
+        &'static NPC,
+        Option<&'static Pedestrian>,
+        Option<&'static NpcSchedule>,
+        Option<&'static NPCType>,
+        Option<&'static NPCAppearance>,
+    ),
+    (Without<Pooled>, Without<Ragdoll>, Without<ActiveEntity>),
+>;
+
+/// Stores NPCs beyond the streaming radius and rehydrates stored ones back in range
+#[allow(clippy::too_many_arguments)]
+pub fn dormant_npc_system(
+    mut commands: Commands,
+    mut timer: Local<Timer>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut world: ResMut<UnifiedWorldManager>,
+    mut pool: ResMut<EntityPool>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut cache: ResMut<NPCAssetCache>,
+    mut world_rng: ResMut<WorldRng>,
+    clock: Res<WorldClock>,
+    routines: Res<Routines>,
+    npcs: PersistableNpcQuery,
+    active_query: Query<&GlobalTransform, With<ActiveEntity>>,
+) {
+    if timer.duration().as_secs_f32() == 0.0 {
+        *timer = Timer::from_seconds(SWEEP_INTERVAL, TimerMode::Repeating);
+    }
+    timer.tick(time.delta());
+    if !timer.just_finished() || world.dormant_npcs.capacity() == 0 {
+        return;
+    }
+    let Ok(active) = active_query.single() else {
+        return;
+    };
+    let active_pos = active.translation();
+    let radius = config.world.streaming_radius;
+    world
+        .dormant_npcs
+        .advance_schedules(&routines, clock.hour, SWEEP_INTERVAL);
+
+    // Put out-of-range NPCs to sleep
+    let mut dropped = 0;
+    for (entity, transform, state, npc, pedestrian, schedule, npc_type, appearance) in &npcs {
+        if transform.translation.xz().distance(active_pos.xz()) <= radius {
+            continue;
+        }
+        dropped += world.dormant_npcs.push(DormantNpc {
+            position: transform.translation,
+            rotation: transform.rotation,
+            state: state.clone(),
+            target_position: npc.target_position,
+            speed: npc.speed,
+            pedestrian: pedestrian.cloned(),
+            schedule: schedule.copied(),
+            body: npc_type.copied().zip(appearance.copied()),
+        });
+        pool.release(&mut commands, entity, ContentType::NPC);
+    }
+    if dropped > 0 {
+        debug!("Dormant NPC budget full, forgot {dropped} NPCs");
+    }
+
+    // Wake stored NPCs the player has come back to
+    let factory = NPCFactory::new();
+    for dormant in world.dormant_npcs.take_within(
+        active_pos,
+        radius * REHYDRATE_RADIUS_SCALE,
+        MAX_REHYDRATES_PER_SWEEP,
+    ) {
+        let npc = NPC {
+            target_position: dormant.target_position,
+            speed: dormant.speed,
+            last_update: 0.0,
+            update_interval: 0.05,
+        };
+
+        let entity = match dormant.body {
+            Some((npc_type, appearance)) => {
+                let Ok(entity) = factory.spawn_npc_with_appearance(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    &mut cache,
+                    dormant.position,
+                    npc_type,
+                    appearance,
+                    world_rng.global(),
+                ) else {
+                    continue;
+                };
+                // The factory lifts its spawn onto the capsule; the stored position already is
+                commands.entity(entity).insert((
+                    Transform::from_translation(dormant.position).with_rotation(dormant.rotation),
+                    dormant.state,
+                    npc,
+                ));
+                entity
+            }
+            None => pool.spawn(
+                &mut commands,
+                ContentType::NPC,
+                npc_bundle_with_state(
+                    dormant.position,
+                    dormant.rotation,
+                    dormant.state,
+                    npc,
+                    &config,
+                ),
+            ),
+        };
+        if let Some(pedestrian) = dormant.pedestrian {
+            commands.entity(entity).insert(pedestrian);
+        }
+        if let Some(schedule) = dormant.schedule {
+            commands.entity(entity).insert(schedule);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::world::NPCType as WorldNpcType;
+    use crate::components::{Activity, NPCBehaviorType};
+
+    fn dormant_at(x: f32) -> DormantNpc {
+        DormantNpc {
+            position: Vec3::new(x, 0.0, 0.0),
+            rotation: Quat::IDENTITY,
+            state: NPCState::new(WorldNpcType::Civilian),
+            target_position: Vec3::ZERO,
+            speed: 2.0,
+            pedestrian: None,
+            schedule: None,
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_store_evicts_oldest_past_budget() {
+        let mut store = DormantNpcStore::with_budget(std::mem::size_of::<DormantNpc>() * 2);
+        assert_eq!(store.capacity(), 2);
+
+        assert_eq!(store.push(dormant_at(1.0)), 0);
+        assert_eq!(store.push(dormant_at(2.0)), 0);
+        assert_eq!(store.push(dormant_at(3.0)), 1);
+        assert_eq!(store.len(), 2);
+        assert!(store.memory_bytes() <= std::mem::size_of::<DormantNpc>() * 2);
+
+        // The first NPC was forgotten
+        assert!(store.take_within(Vec3::ZERO, 1.5, 10).is_empty());
+    }
+
+    #[test]
+    fn test_take_within_radius_and_limit() {
+        let mut store = DormantNpcStore::with_budget(1 << 20);
+        for x in [10.0, 500.0, 20.0, 30.0] {
+            store.push(dormant_at(x));
+        }
+
+        let near = store.take_within(Vec3::ZERO, 100.0, 2);
+        assert_eq!(near.len(), 2);
+        assert_eq!(store.len(), 2);
+        let rest = store.take_within(Vec3::ZERO, 100.0, 10);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].position.x, 30.0);
+    }
+
+    #[test]
+    fn test_dormant_npcs_keep_to_their_schedule() {
+        let routines: Routines = ron::from_str(
+            "(routines: [(name: \"office\", weight: 1.0, entries: [(8.0, Work), (18.0, Home)])])",
+        )
+        .unwrap();
+        let mut store = DormantNpcStore::with_budget(1 << 20);
+        store.push(DormantNpc {
+            schedule: Some(NpcSchedule {
+                routine: 0,
+                home: Vec3::ZERO,
+                work: Vec3::new(1000.0, 0.0, 0.0),
+                park: Vec3::ZERO,
+                activity: Activity::Home,
+                arrived: true,
+            }),
+            ..dormant_at(0.0)
+        });
+
+        // Still night: it stays home
+        store.advance_schedules(&routines, 6.0, 10.0);
+        let npc = store.take_within(Vec3::ZERO, 1.0, 1).pop().unwrap();
+        assert_eq!(npc.state.behavior, NPCBehaviorType::AtHome);
+        store.push(npc);
+
+        // Off to work at walking speed
+        store.advance_schedules(&routines, 9.0, 10.0);
+        let npc = store
+            .take_within(Vec3::new(20.0, 0.0, 0.0), 1.0, 1)
+            .pop()
+            .unwrap();
+        assert_eq!(npc.state.behavior, NPCBehaviorType::Commuting);
+    }
+}
@@ -1,76 +1,204 @@
-use crate::components::{NPCState, NPCType};
+//! NPC population.
+//!
+//! Every district sets a target number of NPCs per hectare in `zones.ron`
+//! and `CrowdDensity` counts how many there are, dormant ones included. The
+//! spawner tries a handful of spots out of sight around the player and fills
+//! the ones furthest below target first. `world_streaming.npc_budget` caps
+//! the live NPCs; past it the ones farthest from the player are released.
+
+use crate::components::{ActiveEntity, ContentType, NPCState, NPCType, Pedestrian, Ragdoll};
 use crate::constants::WorldEnvConfig;
+use crate::factories::{CollisionDetector, EntityPool};
+use crate::systems::spatial_index::SpatialIndex;
 use crate::systems::world::unified_world::UnifiedWorldManager;
 use bevy::{prelude::*, render::view::visibility::VisibilityRange};
 use bevy_rapier3d::prelude::*;
+use std::f32::consts::TAU;
 
 use crate::config::GameConfig;
-use crate::resources::WorldRng;
+use crate::resources::{CrowdDensity, DistrictMap, WorldRng};
 
 use rand::prelude::*;
 
-/// Spawn NPCs using the new architecture while maintaining compatibility
-/// This system replaces the old spawn_dynamic_npc function
-/// CONSOLIDATED: Now uses spawn validation from UnifiedEntityFactory
+/// Spawn spots tried per spawn tick
+const SPAWN_CANDIDATES: usize = 8;
+/// Most NPCs spawned per spawn tick
+const MAX_SPAWNS_PER_TICK: usize = 4;
+/// Seconds between crowd recounts
+const RECOUNT_INTERVAL: f32 = 1.0;
+
+/// Recounts live and dormant NPCs per hectare
+pub fn update_crowd_density(
+    mut timer: Local<Timer>,
+    time: Res<Time>,
+    world: Res<UnifiedWorldManager>,
+    npcs: Query<&GlobalTransform, With<NPCState>>,
+    mut density: ResMut<CrowdDensity>,
+) {
+    if timer.duration().as_secs_f32() == 0.0 {
+        *timer = Timer::from_seconds(RECOUNT_INTERVAL, TimerMode::Repeating);
+    }
+    timer.tick(time.delta());
+    if !timer.just_finished() {
+        return;
+    }
+    density.rebuild(
+        npcs.iter()
+            .map(GlobalTransform::translation)
+            .chain(world.dormant_npcs.positions()),
+    );
+}
+
+/// Random ground point around `center` between `inner` and `outer` meters away
+fn spawn_candidate(center: Vec3, inner: f32, outer: f32, rng: &mut impl Rng) -> Vec3 {
+    let angle = rng.gen_range(0.0..TAU);
+    let distance = rng
+        .gen_range(inner * inner..=(outer * outer).max(inner * inner))
+        .sqrt();
+    center + Vec3::new(angle.cos(), 0.0, angle.sin()) * distance
+}
+
+/// Spawns NPCs where the crowd is thinnest against its district's target
 #[allow(clippy::too_many_arguments)]
 pub fn spawn_new_npc_system(
     mut commands: Commands,
     mut spawn_timer: Local<Timer>,
     time: Res<Time>,
-    npc_query: Query<Entity, With<NPCState>>,
+    npc_query: Query<&GlobalTransform, With<NPCState>>,
+    active_query: Query<&GlobalTransform, With<ActiveEntity>>,
     world: Res<UnifiedWorldManager>,
     mut world_rng: ResMut<WorldRng>,
     env: Res<WorldEnvConfig>,
     config: Res<GameConfig>,
+    mut pool: ResMut<EntityPool>,
+    index: Res<SpatialIndex>,
+    districts: Res<DistrictMap>,
+    mut density: ResMut<CrowdDensity>,
 ) {
     // Initialize timer on first run
     if spawn_timer.duration().as_secs_f32() == 0.0 {
-        *spawn_timer = Timer::from_seconds(10.0, TimerMode::Repeating);
+        *spawn_timer = Timer::from_seconds(config.npc.spawn_interval, TimerMode::Repeating);
     }
-
-    // Tick the timer
     spawn_timer.tick(time.delta());
-
-    // Limit NPC spawning to avoid performance issues (unified entity limits)
-    if npc_query.iter().count() >= 150 {
-        // GTA-style population density
+    if !spawn_timer.just_finished() {
         return;
     }
+    let Ok(active) = active_query.single() else {
+        return;
+    };
+    crate::profile_scope!("spawn_npcs");
+    let player = active.translation();
+    let streaming = &config.world_streaming;
+
+    let live = npc_query.iter().count();
+    let mut room = streaming.npc_budget.saturating_sub(live);
+    // With the budget full a spawn only pays off nearer than the farthest
+    // NPC, which budgeting then releases in its place
+    let farthest = npc_query
+        .iter()
+        .map(|transform| transform.translation().distance(player))
+        .reduce(f32::max)
+        .unwrap_or(0.0);
 
-    // Spawn new NPCs on both islands (randomly choose left or right)
-    if spawn_timer.just_finished() {
-        let island_x = if world_rng.global().gen_bool(0.5) {
-            env.islands.left_x
-        } else {
-            env.islands.right_x
-        };
-
-        let terrain_half_size = env.terrain.half_size;
-        let x = island_x
-            + world_rng
-                .global()
-                .gen_range(-terrain_half_size..terrain_half_size);
-        let z = world_rng
-            .global()
-            .gen_range(-terrain_half_size..terrain_half_size);
-
-        let test_position = Vec3::new(x, env.land_elevation, z);
-
-        // Validate spawn position is on terrain island
-        if !world.is_on_terrain_island(test_position) {
-            return; // Skip this spawn cycle, try again next time
+    // Out of sight, but inside the radius where NPCs stay live
+    let ground = player.with_y(env.land_elevation);
+    let mut candidates: Vec<(f32, Vec3)> = (0..SPAWN_CANDIDATES)
+        .map(|_| {
+            spawn_candidate(
+                ground,
+                streaming.npc_lod.cull,
+                streaming.streaming_radius,
+                world_rng.global(),
+            )
+        })
+        .filter(|position| world.is_on_terrain_island(*position))
+        .map(|position| {
+            let deficit = density.deficit(position, districts.npc_density_at(position));
+            (deficit, position)
+        })
+        .filter(|(deficit, _)| *deficit > 0.0)
+        .collect();
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut spawned = 0;
+    for (_, position) in candidates {
+        if spawned >= MAX_SPAWNS_PER_TICK {
+            break;
+        }
+        // An earlier spawn this tick may have filled the cell
+        if density.deficit(position, districts.npc_density_at(position)) <= 0.0 {
+            continue;
+        }
+        if room == 0 && position.distance(player) >= farthest {
+            continue;
+        }
+
+        // Don't drop NPCs onto vehicles or other pedestrians
+        if CollisionDetector::has_indexed_collision(position, ContentType::NPC, &index) {
+            continue;
         }
 
         // Spawn above terrain, let gravity drop NPCs
-        let spawn_position = Vec3::new(x, env.land_elevation + env.spawn_drop_height, z);
+        let spawn_position = position + Vec3::Y * env.spawn_drop_height;
 
-        // Use spawn_simple_npc which adds ALL required components
-        spawn_simple_npc(&mut commands, spawn_position, &mut world_rng, &config);
+        // Reuse a pooled NPC entity when one is free
+        let npc = simple_npc_bundle(spawn_position, &mut world_rng, &config);
+        pool.spawn(&mut commands, ContentType::NPC, npc);
+        density.record(position);
+        room = room.saturating_sub(1);
+        spawned += 1;
 
         debug!("Spawned NPC at {spawn_position:?}");
     }
 }
 
+/// NPCs to release to bring `npcs` (entity, distance to the player) down to
+/// `budget`, farthest first
+pub fn npcs_over_budget(mut npcs: Vec<(Entity, f32)>, budget: usize) -> Vec<Entity> {
+    if npcs.len() <= budget {
+        return Vec::new();
+    }
+    let excess = npcs.len() - budget;
+    npcs.select_nth_unstable_by(excess - 1, |a, b| b.1.total_cmp(&a.1));
+    npcs.truncate(excess);
+    npcs.into_iter().map(|(entity, _)| entity).collect()
+}
+
+/// Keeps live NPCs within `world_streaming.npc_budget`
+#[allow(clippy::type_complexity)]
+pub fn enforce_npc_budget(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut pool: ResMut<EntityPool>,
+    active_query: Query<&GlobalTransform, With<ActiveEntity>>,
+    npcs: Query<
+        (Entity, &GlobalTransform),
+        (With<NPCState>, Without<ActiveEntity>, Without<Ragdoll>),
+    >,
+) {
+    let budget = config.world_streaming.npc_budget;
+    if npcs.iter().len() <= budget {
+        return;
+    }
+    let Ok(active) = active_query.single() else {
+        return;
+    };
+    let player = active.translation();
+    let evicted = npcs_over_budget(
+        npcs.iter()
+            .map(|(entity, transform)| (entity, transform.translation().distance(player)))
+            .collect(),
+        budget,
+    );
+    debug!(
+        "NPC budget of {budget} exceeded, releasing {} farthest",
+        evicted.len()
+    );
+    for entity in evicted {
+        pool.release(&mut commands, entity, ContentType::NPC);
+    }
+}
+
 /// Legacy spawn a single NPC using the simplified system
 pub fn spawn_simple_npc(
     commands: &mut Commands,
@@ -78,6 +206,17 @@ pub fn spawn_simple_npc(
     world_rng: &mut WorldRng,
     config: &GameConfig,
 ) -> Entity {
+    commands
+        .spawn(simple_npc_bundle(position, world_rng, config))
+        .id()
+}
+
+/// Every component a simple NPC needs, for spawning or reusing a pooled entity
+pub fn simple_npc_bundle(
+    position: Vec3,
+    world_rng: &mut WorldRng,
+    config: &GameConfig,
+) -> impl Bundle {
     // Create NPC with new state-based architecture
     let npc_type = match world_rng.global().gen_range(0..4) {
         0 => NPCType::Civilian,
@@ -86,33 +225,48 @@ pub fn spawn_simple_npc(
         _ => NPCType::Emergency,
     };
 
-    let npc_state = NPCState::new(npc_type);
+    let npc = crate::components::NPC {
+        target_position: position,
+        speed: world_rng.global().gen_range(2.0..4.0),
+        last_update: 0.0,
+        update_interval: 0.05,
+    };
+    npc_bundle_with_state(
+        position,
+        Quat::IDENTITY,
+        NPCState::with_rng(npc_type, world_rng.global()),
+        npc,
+        config,
+    )
+}
+
+/// Simple NPC bundle around existing state, used when rehydrating dormant NPCs
+pub fn npc_bundle_with_state(
+    position: Vec3,
+    rotation: Quat,
+    npc_state: NPCState,
+    npc: crate::components::NPC,
+    config: &GameConfig,
+) -> impl Bundle {
     let height = npc_state.appearance.height;
 
-    // Use simplified entity creation
     // TODO: Migrate to NPCFactory for proper visuals
-    commands
-        .spawn((
-            npc_state,
-            crate::components::NPC {
-                target_position: position,
-                speed: world_rng.global().gen_range(2.0..4.0),
-                last_update: 0.0,
-                update_interval: 0.05,
-            },
-            RigidBody::Dynamic,
-            Collider::capsule(
-                Vec3::new(0.0, -height / 2.0, 0.0),
-                Vec3::new(0.0, height / 2.0, 0.0),
-                0.3,
-            ),
-            Velocity::zero(),
-            Transform::from_translation(position),
-            Visibility::Visible,
-            LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
-            VisibilityRange::abrupt(0.0, config.world_streaming.npc_lod.cull),
-        ))
-        .id()
+    (
+        npc_state,
+        npc,
+        Pedestrian::default(),
+        RigidBody::Dynamic,
+        Collider::capsule(
+            Vec3::new(0.0, -height / 2.0, 0.0),
+            Vec3::new(0.0, height / 2.0, 0.0),
+            0.3,
+        ),
+        Velocity::zero(),
+        Transform::from_translation(position).with_rotation(rotation),
+        Visibility::Visible,
+        LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
+        VisibilityRange::abrupt(0.0, config.world_streaming.npc_lod.cull),
+    )
 }
 
 /// NPC spawn using unified factory (replaces legacy functions)
@@ -130,7 +284,7 @@ pub fn spawn_npc_with_new_architecture(
         _ => NPCType::Emergency,
     };
 
-    let npc_state = NPCState::new(npc_type);
+    let npc_state = NPCState::with_rng(npc_type, world_rng.global());
     let height = npc_state.appearance.height;
 
     #[allow(deprecated)]
@@ -153,3 +307,21 @@ pub fn spawn_npc_with_new_architecture(
         ))
         .id()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_releases_the_farthest_npcs() {
+        let npcs: Vec<(Entity, f32)> = [40.0, 300.0, 10.0, 120.0, 500.0]
+            .iter()
+            .enumerate()
+            .map(|(i, distance)| (Entity::from_raw(i as u32), *distance))
+            .collect();
+        let mut evicted = npcs_over_budget(npcs.clone(), 3);
+        evicted.sort();
+        assert_eq!(evicted, vec![Entity::from_raw(1), Entity::from_raw(4)]);
+        assert!(npcs_over_budget(npcs, 5).is_empty());
+    }
+}
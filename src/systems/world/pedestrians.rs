@@ -0,0 +1,438 @@
+use crate::components::{
+    ActiveEntity, ControlState, HORN_BARKS, HornHonked, HumanAnimation, HumanMovement, NPC, NPCLOD,
+    NPCState, NpcSchedule, Pedestrian, PedestrianBark, PedestrianState, Ragdoll,
+};
+use crate::config::{GameConfig, LodConfig};
+use crate::game_state::GameState;
+use crate::resources::{SidewalkGraph, WorldRng};
+use crate::systems::spatial_index::SpatialIndex;
+use crate::systems::world::schedules::scheduled_waypoint;
+use crate::systems::world::unified_world::UnifiedWorldManager;
+use bevy::prelude::*;
+use bevy::render::view::visibility::VisibilityRange;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Distance at which a waypoint counts as reached
+const WAYPOINT_REACHED: f32 = 1.5;
+/// Distance within which two wandering pedestrians may start chatting
+const CONVERSE_RADIUS: f32 = 4.0;
+/// Half-width of the corridor in front of a vehicle that pedestrians step out of
+const DODGE_CORRIDOR: f32 = 3.0;
+/// Vehicles slower than this are ignored by pedestrians
+const THREAT_MIN_SPEED: f32 = 3.0;
+/// Half-width of the lane ahead of a honking vehicle that pedestrians jump out of
+const HONK_CORRIDOR: f32 = 4.0;
+/// Upward speed of the jump out of a honking vehicle's way
+const HONK_HOP_SPEED: f32 = 3.0;
+/// Seconds a pedestrian stands shouting at a honking driver
+const BARK_SECONDS: f32 = 2.5;
+/// Seconds between horn blasts while the horn is held
+const HORN_REPEAT: f32 = 1.0;
+
+fn lod_tier(distance: f32, lod: &LodConfig) -> NPCLOD {
+    if distance < lod.full {
+        NPCLOD::Full
+    } else if distance < lod.medium {
+        NPCLOD::Medium
+    } else if distance < lod.low {
+        NPCLOD::Low
+    } else {
+        NPCLOD::StateOnly
+    }
+}
+
+/// Seconds between sidewalk rebuilds while roads are still streaming in
+const SIDEWALK_REBUILD_INTERVAL: f32 = 5.0;
+
+/// Rebuilds the sidewalk graph whenever the road network has grown
+pub fn build_sidewalk_graph(
+    time: Res<Time>,
+    mut since_rebuild: Local<Option<f32>>,
+    world: Res<UnifiedWorldManager>,
+    config: Res<GameConfig>,
+    mut graph: ResMut<SidewalkGraph>,
+    mut pedestrians: Query<&mut Pedestrian>,
+) {
+    let road_count = world.road_network.roads.len();
+    if road_count == 0 || road_count == graph.source_road_count {
+        return;
+    }
+    // Build immediately the first time, then throttle while chunks keep streaming
+    if let Some(elapsed) = since_rebuild.as_mut() {
+        *elapsed += time.delta_secs();
+        if *elapsed < SIDEWALK_REBUILD_INTERVAL {
+            return;
+        }
+    }
+    *since_rebuild = Some(0.0);
+
+    *graph = SidewalkGraph::build(
+        &world.road_network,
+        config.npc.sidewalk_offset,
+        config.npc.waypoint_spacing,
+    );
+
+    // Waypoint indices changed; pedestrians re-snap on their next update
+    for mut pedestrian in pedestrians.iter_mut() {
+        pedestrian.target_node = None;
+        pedestrian.previous_node = None;
+    }
+
+    info!(
+        "🚶 Built sidewalk graph: {} waypoints from {} roads",
+        graph.nodes.len(),
+        road_count
+    );
+}
+
+/// Honks when the horn is pressed while driving, and again every
+/// `HORN_REPEAT` seconds while it is held
+pub fn vehicle_horn_input(
+    time: Res<Time>,
+    state: Res<State<GameState>>,
+    mut since_honk: Local<Option<f32>>,
+    active_query: Query<(&Transform, &ControlState), With<ActiveEntity>>,
+    mut horn_events: EventWriter<HornHonked>,
+) {
+    let Ok((transform, control)) = active_query.single() else {
+        return;
+    };
+    if *state.get() != GameState::Driving || !control.horn {
+        *since_honk = None;
+        return;
+    }
+    let since = since_honk.get_or_insert(HORN_REPEAT);
+    *since += time.delta_secs();
+    if *since < HORN_REPEAT {
+        return;
+    }
+    *since = 0.0;
+    horn_events.write(HornHonked {
+        position: transform.translation,
+        direction: transform.forward().with_y(0.0).normalize_or_zero(),
+    });
+}
+
+/// Sideways direction out of the path of a vehicle heading along `direction`,
+/// on the side of `lateral`, the offset from its path
+fn step_aside(direction: Vec3, lateral: Vec3) -> Vec3 {
+    let side = direction.cross(Vec3::Y);
+    if lateral.dot(side) >= 0.0 {
+        side
+    } else {
+        -side
+    }
+}
+
+/// How a pedestrian at `position` answers a horn: jumps aside when standing in
+/// the vehicle's way, otherwise turns to shout at the driver when `barks`, or
+/// runs off
+pub fn honk_reaction(position: Vec3, honk: &HornHonked, barks: bool) -> (PedestrianState, Vec3) {
+    let offset = (position - honk.position).with_y(0.0);
+    let ahead = offset.dot(honk.direction);
+    let lateral = offset - honk.direction * ahead;
+    if ahead > 0.0 && lateral.length() < HONK_CORRIDOR {
+        (PedestrianState::Dodge, step_aside(honk.direction, lateral))
+    } else if barks {
+        (PedestrianState::Converse, -offset.normalize_or_zero())
+    } else {
+        (PedestrianState::Flee, offset.normalize_or(Vec3::X))
+    }
+}
+
+/// Sidewalk navigation with wander/converse/flee/dodge behaviors.
+/// Update rate and reactions scale with the NPC LOD tier. A horn makes those
+/// in front of the car jump aside and the rest run off, bar the one who stops
+/// to shout back. Pedestrians with a
+/// schedule walk towards the place of their current activity instead of
+/// wandering, and wait there once they arrive.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn pedestrian_behavior_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    state: Res<State<GameState>>,
+    graph: Res<SidewalkGraph>,
+    mut world_rng: ResMut<WorldRng>,
+    mut horn_events: EventReader<HornHonked>,
+    mut bark_events: EventWriter<PedestrianBark>,
+    index: Res<SpatialIndex>,
+    active_query: Query<(&Transform, Option<&Velocity>), (With<ActiveEntity>, Without<NPC>)>,
+    mut npc_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &mut NPC,
+            &mut Pedestrian,
+            Option<&mut NPCState>,
+            Option<&mut HumanMovement>,
+            Option<&mut HumanAnimation>,
+            Option<&mut NpcSchedule>,
+        ),
+        (
+            With<VisibilityRange>,
+            Without<RigidBodyDisabled>,
+            Without<Ragdoll>,
+        ),
+    >,
+) {
+    let honks: Vec<HornHonked> = horn_events.read().copied().collect();
+    if graph.is_empty() && honks.is_empty() {
+        return;
+    }
+    let Ok((active_transform, active_velocity)) = active_query.single() else {
+        return;
+    };
+    let player_pos = active_transform.translation;
+    let npc_config = &config.npc;
+    let lod = &config.world_streaming.npc_lod;
+    let current_time = time.elapsed_secs();
+
+    // Only a moving vehicle is a threat; a walking player is ignored
+    let threat = active_velocity
+        .filter(|_| *state.get() == GameState::Driving)
+        .map(|v| v.linvel.with_y(0.0))
+        .filter(|v| v.length() > THREAT_MIN_SPEED);
+
+    // Wandering pedestrians close enough to start a conversation with
+    let chat_candidates: HashMap<Entity, Vec3> = npc_query
+        .iter()
+        .filter(|(_, transform, _, _, pedestrian, ..)| {
+            pedestrian.state == PedestrianState::Wander
+                && transform.translation.distance(player_pos) < lod.medium
+        })
+        .map(|(entity, transform, ..)| (entity, transform.translation))
+        .collect();
+    let mut conversations: Vec<(Entity, Vec3)> = Vec::new();
+    let mut barked = false;
+
+    for (
+        entity,
+        mut transform,
+        mut velocity,
+        mut npc,
+        mut pedestrian,
+        mut npc_state,
+        movement,
+        animation,
+        mut schedule,
+    ) in npc_query.iter_mut()
+    {
+        let position = transform.translation;
+        let distance = position.distance(player_pos);
+        let tier = lod_tier(distance, lod);
+        if let Some(npc_state) = npc_state.as_mut()
+            && npc_state.current_lod != tier
+        {
+            npc_state.current_lod = tier;
+            npc_state.last_lod_check = current_time;
+        }
+
+        // Horns are rare, so react immediately regardless of update interval
+        if let Some(honk) = honks
+            .iter()
+            .find(|h| h.position.distance(position) < npc_config.honk_radius)
+        {
+            let rng = world_rng.global();
+            let barks = !barked && rng.gen_bool(npc_config.bark_chance as f64);
+            match honk_reaction(position, honk, barks) {
+                (PedestrianState::Dodge, heading) => {
+                    pedestrian.enter(PedestrianState::Dodge, 1.0, heading);
+                    velocity.linvel.y = HONK_HOP_SPEED;
+                }
+                (PedestrianState::Converse, heading) => {
+                    pedestrian.enter(PedestrianState::Converse, BARK_SECONDS, heading);
+                    bark_events.write(PedestrianBark {
+                        position,
+                        line: HORN_BARKS[rng.gen_range(0..HORN_BARKS.len())],
+                    });
+                    barked = true;
+                }
+                (state, heading) => pedestrian.enter(state, npc_config.flee_duration, heading),
+            }
+        }
+
+        let interval = match tier {
+            NPCLOD::Full => npc_config.update_intervals.close_interval,
+            NPCLOD::Medium | NPCLOD::Low => npc_config.update_intervals.medium_interval,
+            NPCLOD::StateOnly => npc_config.update_intervals.far_interval,
+        };
+        let dt = current_time - npc.last_update;
+        if dt < interval {
+            continue;
+        }
+        npc.last_update = current_time;
+        npc.update_interval = interval;
+
+        // Only nearby pedestrians bother watching traffic
+        if let Some(vehicle_velocity) = threat
+            && matches!(tier, NPCLOD::Full | NPCLOD::Medium)
+            && pedestrian.state != PedestrianState::Flee
+        {
+            let direction = vehicle_velocity.normalize();
+            let offset = (position - player_pos).with_y(0.0);
+            let ahead = offset.dot(direction);
+            let lookahead = npc_config.avoidance_distance + vehicle_velocity.length();
+            let lateral = offset - direction * ahead;
+            if ahead > 0.0 && ahead < lookahead && lateral.length() < DODGE_CORRIDOR {
+                pedestrian.enter(PedestrianState::Dodge, 1.0, step_aside(direction, lateral));
+            }
+        }
+
+        let mut move_dir = Vec3::ZERO;
+        let mut speed = npc.speed;
+        match pedestrian.state {
+            // At home or at work: wait there for the routine's next entry
+            PedestrianState::Wander if schedule.as_deref().is_some_and(NpcSchedule::idle) => {}
+            PedestrianState::Wander => {
+                let target = match pedestrian.target_node {
+                    Some(node) if node < graph.nodes.len() => node,
+                    _ => match graph.nearest_node(position, npc_config.sidewalk_snap_distance) {
+                        Some(node) => {
+                            pedestrian.target_node = Some(node);
+                            node
+                        }
+                        None => {
+                            // Nowhere near a sidewalk: hand back to free roaming
+                            commands.entity(entity).remove::<Pedestrian>();
+                            continue;
+                        }
+                    },
+                };
+
+                let to_target = (graph.nodes[target] - position).with_y(0.0);
+                if to_target.length() > WAYPOINT_REACHED {
+                    move_dir = to_target.normalize();
+                } else {
+                    let rng = world_rng.global();
+                    let neighbors = &graph.edges[target];
+                    let onward: Vec<usize> = neighbors
+                        .iter()
+                        .copied()
+                        .filter(|n| Some(*n) != pedestrian.previous_node)
+                        .collect();
+                    let choices = if onward.is_empty() {
+                        neighbors
+                    } else {
+                        &onward
+                    };
+                    pedestrian.previous_node = Some(target);
+                    pedestrian.target_node = if choices.is_empty() {
+                        None
+                    } else if let Some(schedule) = schedule.as_deref_mut() {
+                        schedule.reach(position);
+                        let next = scheduled_waypoint(schedule, &graph, target, choices, rng);
+                        if let Some(npc_state) = npc_state.as_mut() {
+                            npc_state.behavior = schedule.behavior();
+                        }
+                        next
+                    } else {
+                        Some(choices[rng.gen_range(0..choices.len())])
+                    };
+
+                    if tier == NPCLOD::Full && rng.gen_bool(npc_config.converse_chance as f64) {
+                        let partner = index
+                            .query_radius(position, CONVERSE_RADIUS)
+                            .into_iter()
+                            .filter(|nearby| nearby.entity != entity)
+                            .find_map(|nearby| {
+                                let other_pos = *chat_candidates.get(&nearby.entity)?;
+                                (other_pos.distance(position) < CONVERSE_RADIUS)
+                                    .then_some((nearby.entity, other_pos))
+                            });
+                        if let Some((other, other_pos)) = partner {
+                            let facing = (other_pos - position).with_y(0.0).normalize_or_zero();
+                            pedestrian.enter(
+                                PedestrianState::Converse,
+                                npc_config.converse_duration,
+                                facing,
+                            );
+                            conversations.push((other, position));
+                        }
+                    }
+                }
+            }
+            PedestrianState::Converse | PedestrianState::Flee | PedestrianState::Dodge => {
+                pedestrian.state_timer -= dt;
+                if pedestrian.state_timer <= 0.0 {
+                    if pedestrian.state == PedestrianState::Flee {
+                        // Fled off the route; pick the closest sidewalk again
+                        pedestrian.target_node = None;
+                    }
+                    pedestrian.state = PedestrianState::Wander;
+                } else if pedestrian.state != PedestrianState::Converse {
+                    move_dir = pedestrian.heading;
+                    speed = npc_config.run_speed;
+                }
+            }
+        }
+
+        velocity.linvel.x = move_dir.x * speed;
+        velocity.linvel.z = move_dir.z * speed;
+        // Keep velocity.linvel.y unchanged (preserve gravity)
+
+        let facing = if move_dir != Vec3::ZERO {
+            move_dir
+        } else {
+            pedestrian.heading
+        };
+        if facing.length_squared() > 1e-4 {
+            transform.rotation = Quat::from_rotation_y(facing.x.atan2(facing.z));
+        }
+
+        if let (Some(mut movement), Some(mut animation)) = (movement, animation) {
+            movement.current_speed = Vec2::new(velocity.linvel.x, velocity.linvel.z).length();
+            movement.target_velocity = move_dir * speed;
+            animation.is_walking = movement.current_speed > 0.3;
+            animation.is_running = movement.current_speed > 5.0 && animation.is_walking;
+        }
+    }
+
+    // Partners stop and face whoever started the conversation
+    for (partner, speaker_pos) in conversations {
+        if let Ok((_, transform, mut velocity, _, mut pedestrian, .., schedule)) =
+            npc_query.get_mut(partner)
+            && pedestrian.state == PedestrianState::Wander
+            && !schedule.is_some_and(|schedule| schedule.idle())
+        {
+            let facing = (speaker_pos - transform.translation)
+                .with_y(0.0)
+                .normalize_or_zero();
+            pedestrian.enter(
+                PedestrianState::Converse,
+                npc_config.converse_duration,
+                facing,
+            );
+            velocity.linvel.x = 0.0;
+            velocity.linvel.z = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_honk_moves_pedestrians_out_of_the_way_or_shouting() {
+        let honk = HornHonked {
+            position: Vec3::ZERO,
+            direction: Vec3::NEG_Z,
+        };
+        // Slightly right of the car's path, so the jump goes further right
+        let (state, heading) = honk_reaction(Vec3::new(1.0, 0.0, -10.0), &honk, true);
+        assert_eq!(state, PedestrianState::Dodge);
+        assert!(heading.x > 0.9);
+
+        let beside = Vec3::new(8.0, 0.0, 0.0);
+        let (state, heading) = honk_reaction(beside, &honk, true);
+        assert_eq!(state, PedestrianState::Converse);
+        assert_eq!(heading, Vec3::NEG_X);
+        let (state, heading) = honk_reaction(beside, &honk, false);
+        assert_eq!(state, PedestrianState::Flee);
+        assert_eq!(heading, Vec3::X);
+    }
+}
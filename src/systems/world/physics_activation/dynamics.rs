@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use crate::components::{ActiveEntity, Car, F16, Helicopter, NPC, Yacht};
+use crate::components::{ActiveEntity, Car, F16, Helicopter, NPC, Ragdoll, Yacht};
 use crate::config::GameConfig;
 
 /// Disable physics for distant vehicles and NPCs (GTA-style optimization)
@@ -75,6 +75,8 @@ pub fn enable_nearby_dynamic_physics(
             )>,
             With<RigidBodyDisabled>,
             Without<ActiveEntity>,
+            // Full ragdolls disable their capsule themselves
+            Without<Ragdoll>,
         ),
     >,
 ) {
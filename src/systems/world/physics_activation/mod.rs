@@ -1,5 +1,7 @@
 pub mod buildings;
 pub mod dynamics;
+pub mod props;
 
 pub use buildings::{activate_nearby_building_physics, deactivate_distant_building_physics};
 pub use dynamics::{disable_distant_dynamic_physics, enable_nearby_dynamic_physics};
+pub use props::{clean_up_resting_props, knock_loose_struck_props};
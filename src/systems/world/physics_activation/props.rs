@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::components::{Car, LooseProp, StreetProp};
+use crate::config::GameConfig;
+
+/// How far ahead (seconds of travel) a vehicle looks for props it is about to hit
+const STRIKE_LOOKAHEAD: f32 = 0.1;
+/// Reach around a car's center that counts as hitting a prop
+const CAR_REACH: f32 = 2.6;
+/// Upward kick given to a knocked prop, per m/s of impact speed
+const KNOCK_LIFT: f32 = 0.15;
+/// Tumble given to a knocked prop, per m/s of impact speed
+const KNOCK_SPIN: f32 = 0.3;
+
+/// Velocity a prop at `offset` from the vehicle's center is sent off with
+pub fn knock_velocity(vehicle_velocity: Vec3, offset: Vec3, share: f32) -> Velocity {
+    let speed = vehicle_velocity.length();
+    let away = offset.with_y(0.0).normalize_or_zero();
+    let linvel = vehicle_velocity * share + away * speed * 0.2 + Vec3::Y * speed * KNOCK_LIFT;
+    let axis = vehicle_velocity.cross(Vec3::Y).normalize_or_zero();
+    Velocity {
+        linvel,
+        angvel: -axis * speed * KNOCK_SPIN,
+    }
+}
+
+/// Turns fixed street props that a vehicle is about to drive into into
+/// dynamic bodies before the physics step, so the car ploughs through them
+/// instead of stopping dead. Activations are capped per frame, and when too
+/// many props are already loose the oldest are cleared to make room.
+#[allow(clippy::type_complexity)]
+pub fn knock_loose_struck_props(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    rapier_context: ReadRapierContext,
+    vehicles: Query<(Entity, &Transform, &Velocity), (With<Car>, Without<RigidBodyDisabled>)>,
+    props: Query<(&Transform, &StreetProp), Without<LooseProp>>,
+    loose: Query<(Entity, &LooseProp)>,
+) {
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+    let budget = &config.world_physics.street_props;
+
+    let mut struck: Vec<(Entity, Vec3, Vec3)> = Vec::new();
+    for (vehicle, transform, velocity) in &vehicles {
+        let speed = velocity.linvel.length();
+        if speed < 1.0 {
+            continue;
+        }
+        let center = transform.translation + velocity.linvel * STRIKE_LOOKAHEAD;
+        let reach = Collider::ball(CAR_REACH + speed * STRIKE_LOOKAHEAD);
+        let filter = QueryFilter::only_fixed().exclude_rigid_body(vehicle);
+        context.intersections_with_shape(center, Quat::IDENTITY, &reach, filter, |entity| {
+            if let Ok((prop_transform, prop)) = props.get(entity) {
+                let offset = prop_transform.translation - transform.translation;
+                let ahead = offset.dot(velocity.linvel) > 0.0;
+                if ahead && speed >= prop.break_speed && !struck.iter().any(|(e, ..)| *e == entity)
+                {
+                    struck.push((entity, velocity.linvel, offset));
+                }
+            }
+            struck.len() < budget.activations_per_frame
+        });
+        if struck.len() >= budget.activations_per_frame {
+            break;
+        }
+    }
+    if struck.is_empty() {
+        return;
+    }
+
+    // Make room under the loose prop budget, oldest first
+    let loose_count = loose.iter().count();
+    let excess = (loose_count + struck.len()).saturating_sub(budget.max_loose);
+    if excess > 0 {
+        let mut oldest: Vec<(Entity, f32)> = loose.iter().map(|(e, p)| (e, p.age)).collect();
+        oldest.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (entity, _) in oldest.into_iter().take(excess) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (entity, vehicle_velocity, offset) in struck {
+        commands.entity(entity).insert((
+            RigidBody::Dynamic,
+            knock_velocity(vehicle_velocity, offset, budget.knock_share),
+            LooseProp::default(),
+        ));
+    }
+}
+
+/// Clears loose props away once they have lain still for a while
+pub fn clean_up_resting_props(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut loose: Query<(Entity, &Velocity, &mut LooseProp)>,
+) {
+    let dt = time.delta_secs();
+    let budget = &config.world_physics.street_props;
+    for (entity, velocity, mut prop) in &mut loose {
+        prop.age += dt;
+        let still = velocity.linvel.length() < budget.rest_speed
+            && velocity.angvel.length() < budget.rest_speed;
+        prop.resting = if still { prop.resting + dt } else { 0.0 };
+        if prop.resting >= budget.cleanup_seconds {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knocked_prop_flies_forward_and_up() {
+        let velocity = knock_velocity(Vec3::new(0.0, 0.0, -20.0), Vec3::new(1.0, 0.0, -2.0), 0.8);
+        assert!(velocity.linvel.z < -15.0);
+        assert!(velocity.linvel.x > 0.0, "pushed to the side it was hit on");
+        assert!(velocity.linvel.y > 0.0);
+        assert!(velocity.angvel.length() > 0.0);
+    }
+}
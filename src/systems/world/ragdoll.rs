@@ -0,0 +1,428 @@
+//! NPC ragdolls on vehicle impact.
+//!
+//! A moving vehicle that hits an NPC hard enough knocks it over. Near the
+//! camera the NPC's body parts are detached into rigid bodies held together by
+//! spherical joints; further away (or for NPCs without body parts) the single
+//! capsule is unlocked and tumbles instead. Full ragdolls collapse to the
+//! simplified form when the camera moves away. After a while the NPC gets back
+//! up and flees, or is despawned if the hit was fatal.
+
+use crate::components::{
+    ActiveEntity, Car, F16, Helicopter, NPCState, Pedestrian, PedestrianState, Ragdoll,
+    RagdollBone, RagdollPart,
+};
+use crate::config::{GameConfig, NPCConfig};
+use crate::systems::spatial_index::SpatialIndex;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use std::collections::HashMap;
+
+/// Full ragdolls beyond this multiple of `ragdoll_full_distance` collapse to simplified
+const COLLAPSE_DISTANCE_SCALE: f32 = 1.5;
+
+/// Upward share of the impact speed given to the launch
+const LAUNCH_LIFT: f32 = 0.3;
+
+/// Recovered NPCs are lifted this far so the upright capsule clears the ground
+const RECOVER_LIFT: f32 = 0.5;
+
+type VehicleQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static GlobalTransform, &'static Velocity),
+    (
+        Or<(With<Car>, With<Helicopter>, With<F16>)>,
+        Without<RigidBodyDisabled>,
+    ),
+>;
+
+type ImpactableNpcQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GlobalTransform,
+        &'static Velocity,
+        Option<&'static Children>,
+    ),
+    (With<NPCState>, Without<Ragdoll>, Without<RigidBodyDisabled>),
+>;
+
+type RagdollQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Ragdoll,
+        &'static mut Transform,
+        Option<&'static mut Pedestrian>,
+    ),
+    Without<RagdollPart>,
+>;
+
+type RagdollPartQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static RagdollPart,
+        &'static RagdollBone,
+        &'static Transform,
+        &'static Velocity,
+    ),
+    Without<Ragdoll>,
+>;
+
+/// Result of a vehicle hitting an NPC hard enough to knock it over
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Impact {
+    pub fatal: bool,
+    pub launch_velocity: Vec3,
+}
+
+/// Decides whether a vehicle hit knocks the NPC over, from the relative impact energy
+pub fn classify_impact(
+    config: &NPCConfig,
+    vehicle_position: Vec3,
+    vehicle_velocity: Vec3,
+    npc_position: Vec3,
+    npc_velocity: Vec3,
+) -> Option<Impact> {
+    let relative = vehicle_velocity - npc_velocity;
+    // Only a vehicle closing on the NPC hits it
+    if relative.dot(npc_position - vehicle_position) <= 0.0 {
+        return None;
+    }
+
+    let energy = 0.5 * config.ragdoll_mass * relative.length_squared();
+    if energy < config.ragdoll_impact_energy {
+        return None;
+    }
+
+    let speed = relative.length();
+    Some(Impact {
+        fatal: energy >= config.ragdoll_fatal_energy,
+        launch_velocity: relative.with_y(0.0) + Vec3::Y * speed * LAUNCH_LIFT,
+    })
+}
+
+fn bone_collider(bone: RagdollBone) -> Collider {
+    match bone {
+        RagdollBone::Torso => Collider::cuboid(0.3, 0.4, 0.15),
+        RagdollBone::Head => Collider::ball(0.2),
+        RagdollBone::LeftArm | RagdollBone::RightArm => Collider::capsule_y(0.25, 0.08),
+        RagdollBone::LeftLeg | RagdollBone::RightLeg => Collider::capsule_y(0.3, 0.12),
+        RagdollBone::LeftFoot | RagdollBone::RightFoot => Collider::cuboid(0.1, 0.05, 0.175),
+    }
+}
+
+/// Share of the body mass carried by each bone
+fn bone_mass_fraction(bone: RagdollBone) -> f32 {
+    match bone {
+        RagdollBone::Torso => 0.45,
+        RagdollBone::Head => 0.08,
+        RagdollBone::LeftArm | RagdollBone::RightArm => 0.05,
+        RagdollBone::LeftLeg | RagdollBone::RightLeg => 0.14,
+        RagdollBone::LeftFoot | RagdollBone::RightFoot => 0.045,
+    }
+}
+
+/// Joint anchors in the parent bone's and this bone's local frames
+pub fn joint_anchors(bone: RagdollBone, parent: RagdollBone) -> (Vec3, Vec3) {
+    (
+        bone.pivot() - parent.rest_position(),
+        bone.pivot() - bone.rest_position(),
+    )
+}
+
+/// Knocks over NPCs hit by moving vehicles
+pub fn ragdoll_activation_system(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    index: Res<SpatialIndex>,
+    vehicles: VehicleQuery,
+    npcs: ImpactableNpcQuery,
+    parts: Query<(&RagdollBone, &GlobalTransform)>,
+    active_query: Query<&GlobalTransform, With<ActiveEntity>>,
+) {
+    let npc_config = &config.npc;
+    let physics = &config.physics;
+    let camera_pos = active_query.single().map(|t| t.translation()).ok();
+    // Slower than this, even a stationary NPC can't be knocked over
+    let min_speed_sq = 2.0 * npc_config.ragdoll_impact_energy / npc_config.ragdoll_mass;
+
+    let mut knocked = Vec::new();
+    for (vehicle_transform, vehicle_velocity) in &vehicles {
+        if vehicle_velocity.linvel.length_squared() < min_speed_sq {
+            continue;
+        }
+        let vehicle_pos = vehicle_transform.translation();
+
+        for entry in index.query_radius(vehicle_pos, npc_config.ragdoll_impact_radius) {
+            if knocked.contains(&entry.entity) {
+                continue;
+            }
+            let Ok((npc_transform, npc_velocity, children)) = npcs.get(entry.entity) else {
+                continue;
+            };
+            let npc_pos = npc_transform.translation();
+            let Some(impact) = classify_impact(
+                npc_config,
+                vehicle_pos,
+                vehicle_velocity.linvel,
+                npc_pos,
+                npc_velocity.linvel,
+            ) else {
+                continue;
+            };
+            knocked.push(entry.entity);
+
+            let bones: Vec<(Entity, RagdollBone, Transform)> = children
+                .into_iter()
+                .flatten()
+                .filter_map(|&child| {
+                    parts
+                        .get(child)
+                        .ok()
+                        .map(|(bone, gt)| (child, *bone, gt.compute_transform()))
+                })
+                .collect();
+            let full = !bones.is_empty()
+                && camera_pos.is_some_and(|camera| {
+                    camera.distance(npc_pos) <= npc_config.ragdoll_full_distance
+                });
+
+            let timer = if impact.fatal {
+                npc_config.ragdoll_despawn_time
+            } else {
+                npc_config.ragdoll_recover_time
+            };
+            commands.entity(entry.entity).insert(Ragdoll {
+                timer,
+                fatal: impact.fatal,
+                full,
+                heading: impact.launch_velocity.with_y(0.0).normalize_or_zero(),
+            });
+
+            if !full {
+                // Let the capsule tumble, spinning about the axis across the hit
+                let spin = Vec3::Y.cross(impact.launch_velocity).normalize_or_zero()
+                    * impact.launch_velocity.length()
+                    * 0.5;
+                commands.entity(entry.entity).insert((
+                    LockedAxes::empty(),
+                    Velocity {
+                        linvel: impact.launch_velocity,
+                        angvel: spin,
+                    },
+                ));
+                continue;
+            }
+
+            let find = |wanted: RagdollBone| {
+                bones
+                    .iter()
+                    .find(|(_, bone, _)| *bone == wanted)
+                    .map(|(entity, _, _)| *entity)
+            };
+            for &(part, bone, world_transform) in &bones {
+                let mut part_commands = commands.entity(part);
+                part_commands.remove::<ChildOf>().insert((
+                    world_transform,
+                    RigidBody::Dynamic,
+                    bone_collider(bone),
+                    ColliderMassProperties::Mass(
+                        npc_config.ragdoll_mass * bone_mass_fraction(bone),
+                    ),
+                    Velocity::linear(impact.launch_velocity),
+                    // Parts skip other characters so limbs don't fight each other
+                    CollisionGroups::new(
+                        physics.character_group,
+                        physics.static_group | physics.vehicle_group,
+                    ),
+                    Damping {
+                        linear_damping: 0.1,
+                        angular_damping: 1.0,
+                    },
+                    RagdollPart {
+                        owner: entry.entity,
+                    },
+                ));
+
+                if let Some(parent_bone) = bone.parent()
+                    && let Some(parent) = find(parent_bone)
+                {
+                    let (anchor1, anchor2) = joint_anchors(bone, parent_bone);
+                    part_commands.insert(ImpulseJoint::new(
+                        parent,
+                        SphericalJointBuilder::new()
+                            .local_anchor1(anchor1)
+                            .local_anchor2(anchor2),
+                    ));
+                }
+            }
+            // The capsule steps aside while the parts carry the body
+            commands.entity(entry.entity).insert(RigidBodyDisabled);
+        }
+    }
+}
+
+/// Returns detached body parts to their NPC in the rest pose
+fn reattach_parts(commands: &mut Commands, owner: Entity, parts: &[(Entity, RagdollBone)]) {
+    for &(part, bone) in parts {
+        commands
+            .entity(part)
+            .remove::<(
+                RigidBody,
+                Collider,
+                ColliderMassProperties,
+                Velocity,
+                CollisionGroups,
+                Damping,
+                ImpulseJoint,
+                RagdollPart,
+            )>()
+            .insert((
+                Transform::from_translation(bone.rest_position()),
+                ChildOf(owner),
+            ));
+    }
+}
+
+/// Keeps ragdolled NPCs following their torso, simplifies them at distance and
+/// gets them back up (or removes them) when their timer runs out
+pub fn ragdoll_update_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut ragdolls: RagdollQuery,
+    parts: RagdollPartQuery,
+    active_query: Query<&GlobalTransform, With<ActiveEntity>>,
+) {
+    let npc_config = &config.npc;
+    let dt = time.delta_secs();
+    let camera_pos = active_query.single().map(|t| t.translation()).ok();
+
+    let mut by_owner: HashMap<Entity, Vec<(Entity, RagdollBone, Transform, Vec3)>> = HashMap::new();
+    for (part, ragdoll_part, bone, transform, velocity) in &parts {
+        if ragdolls.contains(ragdoll_part.owner) {
+            by_owner.entry(ragdoll_part.owner).or_default().push((
+                part,
+                *bone,
+                *transform,
+                velocity.linvel,
+            ));
+        } else {
+            // Owner was despawned (streaming, entity limits) while ragdolling
+            commands.entity(part).despawn();
+        }
+    }
+
+    for (entity, mut ragdoll, mut transform, pedestrian) in &mut ragdolls {
+        ragdoll.timer -= dt;
+        let body = by_owner.remove(&entity).unwrap_or_default();
+        let attached: Vec<(Entity, RagdollBone)> = body
+            .iter()
+            .map(|&(part, bone, _, _)| (part, bone))
+            .collect();
+
+        if ragdoll.full {
+            let torso = body
+                .iter()
+                .find(|(_, bone, _, _)| *bone == RagdollBone::Torso);
+            if let Some(&(_, _, torso_transform, torso_velocity)) = torso {
+                // Root tracks the body so streaming and the spatial index follow it
+                transform.translation =
+                    torso_transform.translation - RagdollBone::Torso.rest_position();
+
+                let too_far = camera_pos.is_some_and(|camera| {
+                    camera.distance(transform.translation)
+                        > npc_config.ragdoll_full_distance * COLLAPSE_DISTANCE_SCALE
+                });
+                if too_far && ragdoll.timer > 0.0 {
+                    reattach_parts(&mut commands, entity, &attached);
+                    commands
+                        .entity(entity)
+                        .remove::<RigidBodyDisabled>()
+                        .insert((LockedAxes::empty(), Velocity::linear(torso_velocity)));
+                    ragdoll.full = false;
+                }
+            }
+        }
+
+        if ragdoll.timer > 0.0 {
+            continue;
+        }
+
+        if ragdoll.fatal {
+            for &(part, _) in &attached {
+                commands.entity(part).despawn();
+            }
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if ragdoll.full {
+            reattach_parts(&mut commands, entity, &attached);
+            commands.entity(entity).remove::<RigidBodyDisabled>();
+        }
+        let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        transform.rotation = Quat::from_rotation_y(yaw);
+        transform.translation.y += RECOVER_LIFT;
+        commands.entity(entity).remove::<Ragdoll>().insert((
+            LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z,
+            Velocity::zero(),
+        ));
+
+        if let Some(mut pedestrian) = pedestrian {
+            pedestrian.enter(
+                PedestrianState::Flee,
+                npc_config.flee_duration,
+                ragdoll.heading,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_joint_anchors_meet_at_rest() {
+        for bone in [
+            RagdollBone::Head,
+            RagdollBone::LeftArm,
+            RagdollBone::RightArm,
+            RagdollBone::LeftLeg,
+            RagdollBone::RightLeg,
+            RagdollBone::LeftFoot,
+            RagdollBone::RightFoot,
+        ] {
+            let parent = bone.parent().unwrap();
+            let (anchor1, anchor2) = joint_anchors(bone, parent);
+            let from_parent = parent.rest_position() + anchor1;
+            let from_child = bone.rest_position() + anchor2;
+            assert!(from_parent.distance(from_child) < 1e-5, "{bone:?}");
+        }
+    }
+
+    #[test]
+    fn test_classify_impact_thresholds() {
+        let config = NPCConfig::default();
+        let at = |speed: f32| {
+            classify_impact(
+                &config,
+                Vec3::ZERO,
+                Vec3::new(0.0, 0.0, speed),
+                Vec3::new(0.0, 0.0, 2.0),
+                Vec3::ZERO,
+            )
+        };
+
+        assert!(at(3.0).is_none());
+        assert!(!at(10.0).unwrap().fatal);
+        assert!(at(25.0).unwrap().fatal);
+        // Reversing away from the NPC doesn't hit it
+        assert!(at(-25.0).is_none());
+    }
+}
@@ -126,7 +126,11 @@ pub fn generate_road_markings_mesh_local(road: &RoadSpline, center_offset: Vec3)
         RoadType::Highway => {
             // Multiple lanes with dashed lines
             markings.push(generate_center_line_mesh(road, true, center_offset)); // Dashed
-            markings.push(generate_lane_markings_mesh(road, 4, center_offset)); // 4 lanes
+            markings.push(generate_lane_markings_mesh(
+                road,
+                road.road_type.lanes(),
+                center_offset,
+            ));
         }
         RoadType::MainStreet => {
             // Center line + edge lines
@@ -390,6 +394,7 @@ pub fn generate_intersection_mesh(
         IntersectionType::TJunction => generate_t_intersection_mesh(intersection, connected_roads),
         IntersectionType::Curve => generate_curved_intersection_mesh(intersection, connected_roads),
         IntersectionType::HighwayOnramp => generate_onramp_mesh(intersection, connected_roads),
+        IntersectionType::Roundabout => generate_roundabout_mesh(intersection),
     }
 }
 
@@ -467,3 +472,40 @@ fn generate_onramp_mesh(intersection: &RoadIntersection, _connected_roads: &[&Ro
     // Highway onramp with proper merging geometry
     generate_cross_intersection_mesh(intersection, _connected_roads)
 }
+
+fn generate_roundabout_mesh(intersection: &RoadIntersection) -> Mesh {
+    // Ring around a central island, which is left to the scenery
+    let outer = intersection.radius;
+    let inner = intersection.radius * 0.4;
+    let segments = 32;
+    let center = intersection.position;
+
+    let mut vertices = Vec::with_capacity(segments * 2);
+    let mut normals = Vec::with_capacity(segments * 2);
+    let mut uvs = Vec::with_capacity(segments * 2);
+    let mut indices = Vec::with_capacity(segments * 6);
+
+    for i in 0..segments {
+        let angle = (i as f32 / segments as f32) * std::f32::consts::TAU;
+        let (sin, cos) = angle.sin_cos();
+        for (radius, v) in [(outer, 0.0), (inner, 1.0)] {
+            vertices.push([center.x + radius * cos, center.y, center.z + radius * sin]);
+            normals.push([0.0, 1.0, 0.0]);
+            uvs.push([i as f32 / segments as f32, v]);
+        }
+
+        let (o0, i0) = ((i * 2) as u32, (i * 2 + 1) as u32);
+        let next = (i + 1) % segments;
+        let (o1, i1) = ((next * 2) as u32, (next * 2 + 1) as u32);
+        // Same winding as the triangle fans above
+        indices.extend_from_slice(&[i0, o0, o1, i0, o1, i1]);
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+
+    mesh
+}
@@ -25,6 +25,12 @@ pub fn generate_unique_road_id(cell_coord: IVec2, local_index: u16) -> u64 {
     ((zx & 0xFFFFF) << 36) | ((zy & 0xFFFFF) << 16) | (local_index as u64)
 }
 
+/// Samples per road for nearest-point queries
+const ROAD_QUERY_SAMPLES: usize = 16;
+
+/// Largest sideways bend of a side-street connector, as a fraction of its length
+const CONNECTOR_BEND_FRACTION: f32 = 0.2;
+
 // NEW GTA-STYLE ROAD NETWORK SYSTEM
 //
 // ROAD ID ALLOCATION STRATEGY:
@@ -67,6 +73,37 @@ impl RoadType {
             RoadType::Alley => 1,
         }
     }
+
+    /// Total lanes across both directions
+    pub fn lanes(&self) -> u32 {
+        match self {
+            RoadType::Highway => 4,
+            RoadType::MainStreet => 4,
+            RoadType::SideStreet => 2,
+            RoadType::Alley => 1,
+        }
+    }
+
+    pub fn lane_width(&self) -> f32 {
+        self.width() / self.lanes() as f32
+    }
+}
+
+/// Which way traffic flows in a lane, relative to the spline's start → end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaneDirection {
+    Forward,
+    Backward,
+    /// Single shared lane (alleys)
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lane {
+    pub index: u32,
+    /// Signed distance from the centerline; positive is right of travel start → end
+    pub offset: f32,
+    pub direction: LaneDirection,
 }
 
 #[derive(Debug, Clone)]
@@ -104,6 +141,16 @@ impl RoadSpline {
         if self.control_points.len() == 2 {
             // Linear interpolation for simple roads
             safe_lerp(self.control_points[0], self.control_points[1], t)
+        } else if self.control_points.len() == 3 {
+            // Quadratic Bézier for single-bend roads
+            let [p0, p1, p2] = [
+                self.control_points[0],
+                self.control_points[1],
+                self.control_points[2],
+            ];
+            let t = t.clamp(0.0, 1.0);
+            let u = 1.0 - t;
+            u * u * p0 + 2.0 * u * t * p1 + t * t * p2
         } else {
             // Catmull-Rom spline for curved roads
             self.catmull_rom_spline(t)
@@ -151,14 +198,79 @@ impl RoadSpline {
 
         length
     }
+
+    /// Unit travel direction on the XZ plane at parameter `t`, start → end
+    pub fn direction(&self, t: f32) -> Vec3 {
+        let a = self.evaluate((t - 0.01).max(0.0));
+        let b = self.evaluate((t + 0.01).min(1.0));
+        Vec3::new(b.x - a.x, 0.0, b.z - a.z).normalize_or_zero()
+    }
+
+    /// Lanes ordered left to right when looking from start to end.
+    /// Right-hand traffic: lanes right of the centerline run forward.
+    pub fn lanes(&self) -> Vec<Lane> {
+        let count = self.road_type.lanes();
+        let lane_width = self.road_type.lane_width();
+        let half_width = self.road_type.width() * 0.5;
+        (0..count)
+            .map(|index| {
+                let offset = (index as f32 + 0.5) * lane_width - half_width;
+                let direction = if count == 1 {
+                    LaneDirection::Both
+                } else if offset > 0.0 {
+                    LaneDirection::Forward
+                } else {
+                    LaneDirection::Backward
+                };
+                Lane {
+                    index,
+                    offset,
+                    direction,
+                }
+            })
+            .collect()
+    }
+
+    /// Center of `lane` at parameter `t`
+    pub fn lane_point(&self, lane: &Lane, t: f32) -> Vec3 {
+        let right = self.direction(t).cross(Vec3::Y);
+        self.evaluate(t) + right * lane.offset
+    }
+
+    /// Closest of `samples + 1` evenly spaced points to `position` (ground plane), as (t, distance)
+    pub fn closest_point(&self, position: Vec3, samples: usize) -> (f32, f32) {
+        let samples = samples.max(1);
+        (0..=samples)
+            .map(|i| {
+                let t = i as f32 / samples as f32;
+                (t, self.evaluate(t).xz().distance(position.xz()))
+            })
+            .fold(
+                (0.0, f32::INFINITY),
+                |best, c| if c.1 < best.1 { c } else { best },
+            )
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IntersectionType {
     Cross,         // 4-way intersection
     TJunction,     // 3-way intersection
     Curve,         // 2-way curved connection
     HighwayOnramp, // Highway merge
+    Roundabout,    // Highway crossing, circular
+}
+
+impl IntersectionType {
+    /// Picks a junction type from the number of road arms meeting at it
+    pub fn from_arms(arms: usize, all_highways: bool) -> Self {
+        match arms {
+            0..=2 => IntersectionType::Curve,
+            3 => IntersectionType::TJunction,
+            _ if all_highways => IntersectionType::Roundabout,
+            _ => IntersectionType::Cross,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -472,6 +584,7 @@ impl RoadNetwork {
             IntersectionType::TJunction => 15.0,
             IntersectionType::Curve => 12.0,
             IntersectionType::HighwayOnramp => 30.0,
+            IntersectionType::Roundabout => 36.0,
         };
 
         let intersection = RoadIntersection {
@@ -485,6 +598,78 @@ impl RoadNetwork {
         id
     }
 
+    /// Number of road arms leaving `position`: one per road ending there, two per road passing through
+    pub fn count_arms(&self, position: Vec3, road_ids: &[u64]) -> usize {
+        road_ids
+            .iter()
+            .filter_map(|id| self.roads.get(id))
+            .map(|road| {
+                let reach = road.road_type.width();
+                let at_start = road.evaluate(0.0).xz().distance(position.xz()) <= reach;
+                let at_end = road.evaluate(1.0).xz().distance(position.xz()) <= reach;
+                if at_start || at_end { 1 } else { 2 }
+            })
+            .sum()
+    }
+
+    /// Intersection type for roads meeting at `position`
+    pub fn classify_intersection(&self, position: Vec3, road_ids: &[u64]) -> IntersectionType {
+        let all_highways = road_ids
+            .iter()
+            .filter_map(|id| self.roads.get(id))
+            .all(|road| road.road_type == RoadType::Highway);
+        IntersectionType::from_arms(self.count_arms(position, road_ids), all_highways)
+    }
+
+    /// Closest road to `position` as (road id, t, distance)
+    pub fn nearest_road(&self, position: Vec3) -> Option<(u64, f32, f32)> {
+        self.roads
+            .values()
+            .map(|road| {
+                let (t, distance) = road.closest_point(position, ROAD_QUERY_SAMPLES);
+                (road.id, t, distance)
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+    }
+
+    /// Roads with any sampled point within `radius` of `position`
+    pub fn roads_near(&self, position: Vec3, radius: f32) -> Vec<u64> {
+        self.roads
+            .values()
+            .filter(|road| {
+                let (_, distance) = road.closest_point(position, ROAD_QUERY_SAMPLES);
+                distance <= radius + road.road_type.width() * 0.5
+            })
+            .map(|road| road.id)
+            .collect()
+    }
+
+    /// Intersection whose footprint contains `position`
+    pub fn intersection_at(&self, position: Vec3) -> Option<(u32, &RoadIntersection)> {
+        self.intersections
+            .iter()
+            .find(|(_, i)| i.position.xz().distance(position.xz()) <= i.radius)
+            .map(|(id, i)| (*id, i))
+    }
+
+    /// Roads joined to `road_id`, both explicit connections and shared intersections
+    pub fn neighbors(&self, road_id: u64) -> Vec<u64> {
+        let mut neighbors: Vec<u64> = self
+            .roads
+            .get(&road_id)
+            .map(|road| road.connections.clone())
+            .unwrap_or_default();
+        for intersection in self.intersections.values() {
+            if intersection.connected_roads.contains(&road_id) {
+                neighbors.extend(intersection.connected_roads.iter().copied());
+            }
+        }
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors.retain(|id| *id != road_id);
+        neighbors
+    }
+
     /// Check if position is on a rectangular terrain island (left, right, or grid)
     fn is_position_on_island(&self, position: Vec3, config: &GameConfig) -> bool {
         let left_island_x = config.world_env.islands.left_x;
@@ -708,14 +893,14 @@ impl RoadNetwork {
             if self.segment_on_island(start, end_v, config) {
                 let road_id_v = generate_unique_road_id(cell_coord, local_index);
                 local_index += 1;
-                let connector_v = RoadSpline::new(road_id_v, start, end_v, road_type);
+                let connector_v = curved_connector(road_id_v, start, end_v, road_type, &mut rng);
                 self.roads.insert(road_id_v, connector_v);
                 new_roads.push(road_id_v);
             }
 
             if self.segment_on_island(start, end_h, config) {
                 let road_id_h = generate_unique_road_id(cell_coord, local_index);
-                let connector_h = RoadSpline::new(road_id_h, start, end_h, road_type);
+                let connector_h = curved_connector(road_id_h, start, end_h, road_type, &mut rng);
                 self.roads.insert(road_id_h, connector_h);
                 new_roads.push(road_id_h);
             }
@@ -898,4 +1083,92 @@ impl RoadNetwork {
     }
 }
 
+/// Side street from `start` to `end` with a seeded sideways bend in the middle
+fn curved_connector(
+    id: u64,
+    start: Vec3,
+    end: Vec3,
+    road_type: RoadType,
+    rng: &mut impl rand::Rng,
+) -> RoadSpline {
+    let mut road = RoadSpline::new(id, start, end, road_type);
+    let span = end - start;
+    let side = Vec3::new(-span.z, 0.0, span.x).normalize_or_zero();
+    let bend = rng.gen_range(-CONNECTOR_BEND_FRACTION..=CONNECTOR_BEND_FRACTION) * span.length();
+    if bend.abs() > road_type.width() {
+        road.add_curve((start + end) * 0.5 + side * bend);
+    }
+    road
+}
+
 // NOTE: RoadEntity and IntersectionEntity are defined in components/world.rs
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_bend_road_curves_through_endpoints() {
+        let mut road = RoadSpline::new(
+            1,
+            Vec3::ZERO,
+            Vec3::new(100.0, 0.0, 0.0),
+            RoadType::SideStreet,
+        );
+        road.add_curve(Vec3::new(50.0, 0.0, 40.0));
+
+        assert!(road.evaluate(0.0).distance(Vec3::ZERO) < 1e-4);
+        assert!(road.evaluate(1.0).distance(Vec3::new(100.0, 0.0, 0.0)) < 1e-4);
+        // Midpoint of a quadratic Bézier is halfway to the control point
+        assert!((road.evaluate(0.5).z - 20.0).abs() < 1e-3);
+        assert!(road.length() > 100.0);
+
+        let lanes = road.lanes();
+        assert_eq!(lanes.len(), 2);
+        assert_eq!(lanes[0].direction, LaneDirection::Backward);
+        assert_eq!(lanes[1].direction, LaneDirection::Forward);
+        // Forward lane sits right of travel (+X heading → +Z side)
+        assert!(road.lane_point(&lanes[1], 0.0).z > 0.0);
+    }
+
+    #[test]
+    fn test_intersection_classification_and_queries() {
+        let mut network = RoadNetwork::default();
+        let a = network.add_road(
+            Vec3::new(-100.0, 0.0, 0.0),
+            Vec3::new(100.0, 0.0, 0.0),
+            RoadType::MainStreet,
+        );
+        let b = network.add_road(Vec3::ZERO, Vec3::new(0.0, 0.0, 100.0), RoadType::SideStreet);
+        let c = network.add_road(
+            Vec3::new(0.0, 0.0, -100.0),
+            Vec3::ZERO,
+            RoadType::SideStreet,
+        );
+
+        assert_eq!(
+            network.classify_intersection(Vec3::ZERO, &[a, b]),
+            IntersectionType::TJunction
+        );
+        assert_eq!(
+            network.classify_intersection(Vec3::ZERO, &[a, b, c]),
+            IntersectionType::Cross
+        );
+        assert_eq!(
+            IntersectionType::from_arms(4, true),
+            IntersectionType::Roundabout
+        );
+
+        let id = network.add_intersection(Vec3::ZERO, vec![a, b, c], IntersectionType::Cross);
+        assert_eq!(
+            network.intersection_at(Vec3::new(5.0, 0.0, 5.0)).unwrap().0,
+            id
+        );
+        assert_eq!(network.neighbors(a), vec![b, c]);
+
+        let (road, t, distance) = network.nearest_road(Vec3::new(60.0, 0.0, 3.0)).unwrap();
+        assert_eq!(road, a);
+        assert!(t > 0.5 && distance < 10.0);
+        assert_eq!(network.roads_near(Vec3::new(0.0, 0.0, 60.0), 5.0), vec![b]);
+    }
+}
@@ -0,0 +1,206 @@
+//! NPC daily routines.
+//!
+//! Every NPC draws a routine from `routines.ron` when it spawns and keeps the
+//! spot it spawned at as home, a workplace in the routine's work district and
+//! the nearest park. Whenever `WorldClock` passes an entry of the routine the
+//! NPC sets off for the new place. Nearby pedestrians walk the sidewalk graph
+//! there in `pedestrian_behavior_system`; NPCs too far away to have physics are
+//! slid straight towards it at walking pace, and dormant ones are advanced the
+//! same way inside the dormant store.
+
+use crate::components::{NPC, NPCState, NpcSchedule, Pedestrian, Ragdoll, Routines};
+use crate::resources::district_map::pick_weighted;
+use crate::resources::{District, DistrictMap, SidewalkGraph, WorldClock, WorldRng};
+use crate::systems::shops::load_config;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::RigidBodyDisabled;
+use rand::Rng;
+
+/// Workplaces and parks farther than this from home are left out
+const MAX_COMMUTE: f32 = 1200.0;
+/// Scheduled NPCs in the park keep to this radius around their spot
+const PARK_RADIUS: f32 = 60.0;
+/// Seconds between checking routines against the clock, and between coarse steps
+const SCHEDULE_INTERVAL: f32 = 1.0;
+
+pub fn load_routines(mut commands: Commands) {
+    commands.insert_resource(load_config::<Routines>("routines.ron"));
+}
+
+/// Gives newly spawned NPCs a routine and the places it sends them to
+#[allow(clippy::type_complexity)]
+pub fn assign_npc_schedules(
+    mut commands: Commands,
+    routines: Res<Routines>,
+    districts: Res<DistrictMap>,
+    clock: Res<WorldClock>,
+    mut world_rng: ResMut<WorldRng>,
+    mut npcs: Query<(Entity, &Transform, &mut NPCState), (Added<NPCState>, Without<NpcSchedule>)>,
+) {
+    let weighted: Vec<_> = routines.routines.iter().enumerate().collect();
+    for (entity, transform, mut state) in &mut npcs {
+        let rng = world_rng.global();
+        let Some(&(index, routine)) = pick_weighted(&weighted, |(_, r)| r.weight, rng) else {
+            return;
+        };
+        let home = transform.translation;
+        let mut place = |district: Option<District>| {
+            district
+                .and_then(|district| districts.random_point_near(district, home, MAX_COMMUTE, rng))
+                .unwrap_or(home)
+        };
+        let work = place(routine.work);
+        let park = place(Some(District::Park));
+        let mut schedule = NpcSchedule {
+            routine: index,
+            home,
+            work,
+            park,
+            activity: routine.activity_at(clock.hour),
+            arrived: false,
+        };
+        schedule.reach(home);
+        state.behavior = schedule.behavior();
+        commands.entity(entity).insert(schedule);
+    }
+}
+
+/// Moves NPCs on to the next activity of their routine as the clock passes it
+pub fn update_npc_schedules(
+    mut timer: Local<Timer>,
+    time: Res<Time>,
+    clock: Res<WorldClock>,
+    routines: Res<Routines>,
+    mut npcs: Query<(&mut NpcSchedule, &mut NPCState)>,
+) {
+    if timer.duration().as_secs_f32() == 0.0 {
+        *timer = Timer::from_seconds(SCHEDULE_INTERVAL, TimerMode::Repeating);
+    }
+    timer.tick(time.delta());
+    if !timer.just_finished() {
+        return;
+    }
+    for (mut schedule, mut state) in &mut npcs {
+        let Some(routine) = routines.routines.get(schedule.routine) else {
+            continue;
+        };
+        schedule.follow(routine, clock.hour);
+        state.behavior = schedule.behavior();
+    }
+}
+
+/// Next sidewalk waypoint for a scheduled pedestrian standing on `current`:
+/// the choice nearest the destination while under way, or one that stays
+/// around its spot once in the park. A waypoint with no choice nearer the
+/// destination is as close as the sidewalks get, so the NPC counts as there.
+pub fn scheduled_waypoint(
+    schedule: &mut NpcSchedule,
+    graph: &SidewalkGraph,
+    current: usize,
+    choices: &[usize],
+    rng: &mut impl Rng,
+) -> Option<usize> {
+    let destination = schedule.destination().xz();
+    let distance = |node: usize| graph.nodes[node].xz().distance(destination);
+    let nearest = choices
+        .iter()
+        .copied()
+        .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))?;
+    if !schedule.arrived {
+        if distance(nearest) < distance(current) {
+            return Some(nearest);
+        }
+        schedule.arrived = true;
+    }
+    let around: Vec<usize> = choices
+        .iter()
+        .copied()
+        .filter(|node| distance(*node) <= PARK_RADIUS)
+        .collect();
+    if around.is_empty() {
+        Some(nearest)
+    } else {
+        Some(around[rng.gen_range(0..around.len())])
+    }
+}
+
+/// Coarse simulation for scheduled NPCs whose physics is off: straight steps
+/// towards the destination, with no sidewalks, collisions or animation
+#[allow(clippy::type_complexity)]
+pub fn coarse_schedule_system(
+    mut timer: Local<Timer>,
+    time: Res<Time>,
+    mut npcs: Query<
+        (
+            &mut Transform,
+            &NPC,
+            &mut NpcSchedule,
+            &mut NPCState,
+            Option<&mut Pedestrian>,
+        ),
+        (With<RigidBodyDisabled>, Without<Ragdoll>),
+    >,
+) {
+    if timer.duration().as_secs_f32() == 0.0 {
+        *timer = Timer::from_seconds(SCHEDULE_INTERVAL, TimerMode::Repeating);
+    }
+    timer.tick(time.delta());
+    if !timer.just_finished() {
+        return;
+    }
+    for (mut transform, npc, mut schedule, mut state, pedestrian) in &mut npcs {
+        if schedule.arrived {
+            continue;
+        }
+        let position = transform.translation;
+        transform.translation = schedule.step(position, npc.speed * SCHEDULE_INTERVAL);
+        let heading = (transform.translation - position).with_y(0.0);
+        if heading.length_squared() > 1e-4 {
+            transform.rotation = Quat::from_rotation_y(heading.x.atan2(heading.z));
+        }
+        state.behavior = schedule.behavior();
+        // Off the sidewalks now; snap back onto them once physics returns
+        if let Some(mut pedestrian) = pedestrian {
+            pedestrian.target_node = None;
+            pedestrian.previous_node = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Activity;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_waypoints_head_for_the_destination_and_stop_at_the_closest() {
+        // A straight sidewalk along x, with the workplace just off its end
+        let mut graph = SidewalkGraph::default();
+        graph.nodes = (0..5)
+            .map(|i| Vec3::new(i as f32 * 10.0, 0.0, 0.0))
+            .collect();
+        graph.edges = vec![vec![1], vec![0, 2], vec![1, 3], vec![2, 4], vec![3]];
+        let mut schedule = NpcSchedule {
+            routine: 0,
+            home: Vec3::ZERO,
+            work: Vec3::new(40.0, 0.0, 30.0),
+            park: Vec3::ZERO,
+            activity: Activity::Work,
+            arrived: false,
+        };
+        let mut rng = StdRng::seed_from_u64(3);
+
+        assert_eq!(
+            scheduled_waypoint(&mut schedule, &graph, 1, &[0, 2], &mut rng),
+            Some(2)
+        );
+        assert!(!schedule.arrived);
+        assert_eq!(
+            scheduled_waypoint(&mut schedule, &graph, 4, &[3], &mut rng),
+            Some(3)
+        );
+        assert!(schedule.arrived);
+    }
+}
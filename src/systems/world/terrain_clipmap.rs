@@ -0,0 +1,95 @@
+//! Camera-centered clipmap rendering of plateau relief.
+//!
+//! Only spawned when the terrain has relief. Each level is a fixed-size grid
+//! whose spacing doubles per level; a level is rebuilt when its snapped origin,
+//! or that of the finer level inside it, moves with the camera. Origins only
+//! move once the camera is clearly past a snap boundary, and each level is
+//! centered a little ahead in the direction of travel (farther for coarser
+//! levels), so relief the camera is heading into is built before it arrives.
+
+use crate::components::MainCamera;
+use crate::config::GameConfig;
+use crate::factories::clipmap_terrain::{
+    CLIPMAP_LEVELS, clipmap_max_lead, clipmap_origin_with_hysteresis, create_clipmap_level,
+};
+use crate::systems::world::chunk_streaming::StreamingFocus;
+use crate::systems::world::terrain_height::TerrainHeightService;
+use bevy::prelude::*;
+use bevy::render::view::NoFrustumCulling;
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TerrainClipmapLevel {
+    pub level: u32,
+    /// Origin the current mesh was built around
+    pub origin: Option<Vec2>,
+}
+
+pub fn terrain_has_relief(terrain: Option<Res<TerrainHeightService>>) -> bool {
+    terrain.is_some_and(|terrain| terrain.has_relief())
+}
+
+pub fn spawn_terrain_clipmap(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.85, 0.75, 0.6),
+        perceptual_roughness: 0.95,
+        ..default()
+    });
+    for level in 0..CLIPMAP_LEVELS {
+        commands.spawn((
+            TerrainClipmapLevel {
+                level,
+                origin: None,
+            },
+            Mesh3d(meshes.add(Mesh::from(Rectangle::default()))),
+            MeshMaterial3d(material.clone()),
+            // Vertices are in world space and the bounds change on every rebuild
+            Transform::default(),
+            NoFrustumCulling,
+            Name::new(format!("Terrain Clipmap L{level}")),
+        ));
+    }
+}
+
+pub fn update_terrain_clipmap(
+    terrain: Res<TerrainHeightService>,
+    config: Res<GameConfig>,
+    focus: Res<StreamingFocus>,
+    camera_query: Query<&GlobalTransform, With<MainCamera>>,
+    mut levels: Query<(&mut TerrainClipmapLevel, &Mesh3d)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Ok(camera) = camera_query.single() else {
+        return;
+    };
+    let camera = camera.translation().xz();
+    let lead = focus.velocity.xz() * config.world_streaming.lod_lookahead.max(0.0);
+
+    // Origins the meshes were last built around, indexed by level
+    let mut built = vec![None; CLIPMAP_LEVELS as usize];
+    for (clipmap, _) in &levels {
+        built[clipmap.level as usize] = clipmap.origin;
+    }
+    let origins: Vec<Vec2> = (0..CLIPMAP_LEVELS)
+        .map(|level| {
+            let focus = camera + lead.clamp_length_max(clipmap_max_lead(level));
+            clipmap_origin_with_hysteresis(level, focus, built[level as usize])
+        })
+        .collect();
+
+    for (mut clipmap, mesh) in &mut levels {
+        let level = clipmap.level as usize;
+        let finer_moved = level > 0 && built[level - 1] != Some(origins[level - 1]);
+        if built[level] == Some(origins[level]) && !finer_moved {
+            continue;
+        }
+        let hole = level.checked_sub(1).map(|finer| origins[finer]);
+        if let Some(mesh) = meshes.get_mut(&mesh.0) {
+            *mesh = create_clipmap_level(clipmap.level, origins[level], hole, &terrain);
+        }
+        clipmap.origin = Some(origins[level]);
+    }
+}
@@ -0,0 +1,207 @@
+//! Ground height anywhere in the world.
+//!
+//! `TerrainHeightService` reproduces the terrain the world is built from:
+//! island plateaus at `land_elevation` (plus optional seeded noise relief),
+//! smoothstep beach slopes down to the ocean floor, and the ocean floor
+//! beyond. Spawn validation, vehicle ground probes and the terrain clipmap all
+//! sample it, so they agree with each other and with the island colliders.
+
+use crate::constants::{TerrainReliefConfig, WorldEnvConfig};
+use bevy::prelude::*;
+
+/// Seeded fractal value noise, normalized to `0..=1`
+#[derive(Debug, Clone)]
+pub struct TerrainHeightmap {
+    seed: u64,
+    wavelength: f32,
+    octaves: u32,
+}
+
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+impl TerrainHeightmap {
+    pub fn new(seed: u64, wavelength: f32, octaves: u32) -> Self {
+        Self {
+            seed,
+            wavelength: wavelength.max(1.0),
+            octaves: octaves.clamp(1, 8),
+        }
+    }
+
+    /// Pseudo-random value in `0..1` for a lattice point
+    fn lattice(&self, x: i32, z: i32, octave: u32) -> f32 {
+        let mut h = self.seed
+            ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (z as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+            ^ (octave as u64).wrapping_mul(0x1656_67B1_9E37_79F9);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+        h ^= h >> 33;
+        (h >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn value_noise(&self, x: f32, z: f32, octave: u32) -> f32 {
+        let (x0, z0) = (x.floor(), z.floor());
+        let (tx, tz) = (smoothstep(x - x0), smoothstep(z - z0));
+        let (ix, iz) = (x0 as i32, z0 as i32);
+
+        let a = self.lattice(ix, iz, octave);
+        let b = self.lattice(ix + 1, iz, octave);
+        let c = self.lattice(ix, iz + 1, octave);
+        let d = self.lattice(ix + 1, iz + 1, octave);
+        let top = a + (b - a) * tx;
+        let bottom = c + (d - c) * tx;
+        top + (bottom - top) * tz
+    }
+
+    pub fn sample(&self, x: f32, z: f32) -> f32 {
+        let mut total = 0.0;
+        let mut weight = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0 / self.wavelength;
+        for octave in 0..self.octaves {
+            total += self.value_noise(x * frequency, z * frequency, octave) * amplitude;
+            weight += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        total / weight
+    }
+}
+
+/// Ground height queries shared by spawning, physics probes and terrain rendering
+#[derive(Resource, Debug, Clone)]
+pub struct TerrainHeightService {
+    land_elevation: f32,
+    ocean_floor: f32,
+    half_size: f32,
+    beach_width: f32,
+    /// Plateau centers on the XZ plane
+    islands: Vec<Vec2>,
+    relief: TerrainReliefConfig,
+    heightmap: TerrainHeightmap,
+}
+
+impl TerrainHeightService {
+    pub fn from_env(env: &WorldEnvConfig) -> Self {
+        let relief = env.terrain.relief.clone();
+        Self {
+            land_elevation: env.land_elevation,
+            ocean_floor: env.ocean_floor_depth,
+            half_size: env.terrain.half_size,
+            beach_width: env.terrain.beach_width.max(0.01),
+            islands: vec![
+                Vec2::new(env.islands.left_x, 0.0),
+                Vec2::new(env.islands.right_x, 0.0),
+                Vec2::new(env.islands.grid_x, env.islands.grid_z),
+            ],
+            heightmap: TerrainHeightmap::new(relief.seed, relief.wavelength, relief.octaves),
+            relief,
+        }
+    }
+
+    /// Whether the plateaus have noise hills (otherwise they are flat)
+    pub fn has_relief(&self) -> bool {
+        self.relief.amplitude > 0.0
+    }
+
+    /// Distance outside the nearest plateau edge; negative inside a plateau
+    fn edge_distance(&self, x: f32, z: f32) -> f32 {
+        self.islands
+            .iter()
+            .map(|center| {
+                let offset = (Vec2::new(x, z) - *center).abs() - Vec2::splat(self.half_size);
+                offset.max_element()
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    pub fn is_on_plateau(&self, x: f32, z: f32) -> bool {
+        self.edge_distance(x, z) <= 0.0
+    }
+
+    /// Ground (or sea bed) height at a world XZ position
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        let distance = self.edge_distance(x, z);
+        if distance <= 0.0 {
+            if !self.has_relief() {
+                return self.land_elevation;
+            }
+            let fade = smoothstep(-distance / self.relief.edge_blend.max(1.0));
+            self.land_elevation + self.heightmap.sample(x, z) * self.relief.amplitude * fade
+        } else if distance < self.beach_width {
+            // Same profile as the beach slope meshes
+            let t = smoothstep(distance / self.beach_width);
+            self.land_elevation + (self.ocean_floor - self.land_elevation) * t
+        } else {
+            self.ocean_floor
+        }
+    }
+
+    /// Surface normal from central differences over `step` meters
+    pub fn normal_at(&self, x: f32, z: f32, step: f32) -> Vec3 {
+        let dx = self.height_at(x + step, z) - self.height_at(x - step, z);
+        let dz = self.height_at(x, z + step) - self.height_at(x, z - step);
+        Vec3::new(-dx, 2.0 * step, -dz).normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_with_relief(amplitude: f32) -> WorldEnvConfig {
+        let mut env = WorldEnvConfig::default();
+        env.terrain.relief.amplitude = amplitude;
+        env
+    }
+
+    #[test]
+    fn test_flat_world_matches_island_layout() {
+        let env = env_with_relief(0.0);
+        let terrain = TerrainHeightService::from_env(&env);
+
+        assert_eq!(terrain.height_at(env.islands.left_x + 250.0, 100.0), 3.0);
+        assert_eq!(terrain.height_at(0.0, 0.0), env.ocean_floor_depth);
+        let beach_mid = terrain.height_at(env.islands.right_x + 650.0, 0.0);
+        assert!((beach_mid - (3.0 + -10.0) / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_relief_is_seeded_and_fades_at_the_coast() {
+        let env = env_with_relief(12.0);
+        let terrain = TerrainHeightService::from_env(&env);
+        let again = TerrainHeightService::from_env(&env);
+        let mut reseeded = env.clone();
+        reseeded.terrain.relief.seed += 1;
+        let reseeded = TerrainHeightService::from_env(&reseeded);
+
+        let samples: Vec<Vec2> = (0..32)
+            .map(|i| Vec2::new(env.islands.left_x - 300.0 + i as f32 * 19.0, i as f32 * 7.0))
+            .collect();
+        let heights: Vec<f32> = samples
+            .iter()
+            .map(|p| terrain.height_at(p.x, p.y))
+            .collect();
+
+        assert!(heights.iter().all(|h| (3.0..=15.0).contains(h)));
+        assert!(heights.iter().any(|h| *h > 4.0));
+        assert_eq!(
+            heights,
+            samples
+                .iter()
+                .map(|p| again.height_at(p.x, p.y))
+                .collect::<Vec<_>>()
+        );
+        assert!(
+            samples
+                .iter()
+                .any(|p| reseeded.height_at(p.x, p.y) != terrain.height_at(p.x, p.y))
+        );
+        // The plateau edge still meets the top of the beach
+        assert_eq!(terrain.height_at(env.islands.left_x + 600.0, 0.0), 3.0);
+    }
+}
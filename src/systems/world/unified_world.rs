@@ -5,7 +5,10 @@
 )]
 use crate::components::ContentType;
 use crate::config::GameConfig;
+use crate::factories::PrefabId;
+use crate::systems::world::chunk_streaming::morton_decode;
 use crate::systems::world::generators::ManhattanGridGenerator;
+use crate::systems::world::npc_persistence::DormantNpcStore;
 use crate::systems::world::road_network::RoadNetwork;
 use bevy::prelude::*;
 use std::collections::HashMap;
@@ -160,6 +163,11 @@ pub struct ChunkData {
     pub buildings_generated: bool,
     pub vehicles_generated: bool,
     pub vegetation_generated: bool,
+
+    /// Dynamic content was torn down when the chunk streamed out
+    pub evicted: bool,
+    /// Street furniture still standing at eviction, respawned on return
+    pub dormant_props: Vec<(PrefabId, Transform)>,
 }
 
 impl ChunkData {
@@ -175,6 +183,8 @@ impl ChunkData {
             buildings_generated: false,
             vehicles_generated: false,
             vegetation_generated: false,
+            evicted: false,
+            dormant_props: Vec::new(),
         }
     }
 }
@@ -306,6 +316,9 @@ pub struct UnifiedWorldManager {
     pub chunks_unloaded_this_frame: usize,
     pub max_chunks_per_frame: usize,
 
+    // NPCs parked beyond the streaming radius
+    pub dormant_npcs: DormantNpcStore,
+
     // LOD configuration
     pub lod_distances: [f32; 3],
 
@@ -338,6 +351,9 @@ impl UnifiedWorldManager {
             chunks_loaded_this_frame: 0,
             chunks_unloaded_this_frame: 0,
             max_chunks_per_frame: 4,
+            dormant_npcs: DormantNpcStore::with_budget(
+                config.performance.dormant_npc_budget_kb * 1024,
+            ),
             lod_distances: config.world.lod_distances,
             left_island_x: config.world_env.islands.left_x,
             right_island_x: config.world_env.islands.right_x,
@@ -423,6 +439,7 @@ impl Default for UnifiedWorldManager {
             chunks_loaded_this_frame: 0,
             chunks_unloaded_this_frame: 0,
             max_chunks_per_frame: 4,
+            dormant_npcs: DormantNpcStore::default(),
             lod_distances: [150.0, 300.0, 500.0],
             left_island_x: -1500.0,
             right_island_x: 1500.0,
@@ -551,6 +568,17 @@ pub struct UnifiedChunkEntity {
     pub layer: ContentLayer,
 }
 
+/// Dynamic content owned by the chunk with this Morton code, torn down
+/// with everything under it when the chunk streams out
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnedByChunk(pub u64);
+
+impl SpawnedByChunk {
+    pub fn coord(&self) -> ChunkCoord {
+        morton_decode(self.0)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ContentLayer {
     Roads,
@@ -558,6 +586,7 @@ pub enum ContentLayer {
     Vehicles,
     NPCs,
     Vegetation,
+    Props,
 }
 
 // NOTE: World streaming systems removed - static generation is the current design
@@ -1,19 +1,18 @@
 use bevy::prelude::*;
 use bevy_rapier3d::geometry::Collider;
 use bevy_rapier3d::prelude::{
-    Damping, ExternalForce, GravityScale, ReadMassProperties, RigidBody, RigidBodyDisabled,
-    Velocity,
+    Damping, ExternalForce, ReadMassProperties, RigidBody, RigidBodyDisabled, Velocity,
 };
 
-use crate::components::unified_water::WaterBodyId;
 use crate::components::{
-    ControlState, DeckWalkAnchor, DeckWalker, DockedOnYacht, DockingCooldown, Enterable, ExitPoint,
-    ExitPointKind, Helicopter, HelicopterRuntime, Helipad, InCar, LandedOnYacht,
-    PendingPhysicsEnable, Player, PlayerControlled, VehicleControlType, Yacht,
+    ControlState, DeckWalkAnchor, DeckWalker, Dock, DockedOnYacht, DockingCooldown, Enterable,
+    ExitPoint, ExitPointKind, Helicopter, HelicopterRuntime, Helipad, InCar, InteractionEvent,
+    LandedOnYacht, MooredAtDock, PendingPhysicsEnable, Player, PlayerControlled, SwimmingEvent,
+    VehicleControlType, Yacht,
 };
-use crate::game_state::GameState;
+use crate::game_state::{GameState, GameStateTransitions};
 use crate::systems::safe_active_entity::queue_active_transfer;
-use crate::systems::swimming::{ProneRotation, SwimState, Swimming};
+use crate::systems::water::{DOCKING_MAX_SPEED, DOCKING_RANGE};
 
 fn dock_helicopter(
     commands: &mut Commands,
@@ -26,13 +25,13 @@ fn dock_helicopter(
 ) {
     let yacht_affine = yacht_transform.affine();
     let yacht_inverse = yacht_affine.inverse();
-    
+
     let world_pos = heli_transform.translation();
     let current_local_pos = yacht_inverse.transform_point3(world_pos);
-    
+
     // Calculate helipad local position to get the correct deck height
     let helipad_local_pos = yacht_inverse.transform_point3(helipad_world_pos);
-    
+
     // Snap Y to helipad height + TARGET_DOCK_HEIGHT
     // Matches the detection window to ensure seamless transition
     let target_local_pos = Vec3::new(
@@ -44,7 +43,7 @@ fn dock_helicopter(
     let world_rotation = heli_transform.to_scale_rotation_translation().1;
     let yacht_rotation = yacht_transform.to_scale_rotation_translation().1;
     let local_rotation = yacht_rotation.inverse() * world_rotation;
-    
+
     // Flatten rotation relative to yacht (remove pitch/roll) so it sits flat on deck
     let (y_rot, _, _) = local_rotation.to_euler(EulerRot::YXZ);
     let flat_local_rotation = Quat::from_rotation_y(y_rot);
@@ -126,7 +125,11 @@ pub fn helicopter_undock_trigger_system(
             &GlobalTransform,
             &mut HelicopterRuntime,
         ),
-        (With<Helicopter>, With<PlayerControlled>, Without<DockingCooldown>),
+        (
+            With<Helicopter>,
+            With<PlayerControlled>,
+            Without<DockingCooldown>,
+        ),
     >,
     _yacht_query: Query<(&Velocity, &GlobalTransform), With<Yacht>>,
 ) {
@@ -144,7 +147,7 @@ pub fn helicopter_undock_trigger_system(
             if let Ok((yacht_vel, yacht_gt)) = yacht_query.get(docked.yacht) {
                 let r = heli_gt.translation() - yacht_gt.translation();
                 let tangential_vel = yacht_vel.angvel.cross(r);
-                
+
                 initial_velocity.linvel = yacht_vel.linvel + tangential_vel;
                 initial_velocity.angvel = yacht_vel.angvel;
             }
@@ -183,12 +186,21 @@ pub fn tick_docking_cooldown_system(
     }
 }
 
+/// Half the yacht's beam, for laying it alongside a pier
+const YACHT_HALF_BEAM: f32 = 10.0;
+
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn yacht_exit_system(
     mut commands: Commands,
     time: Res<Time>,
     yacht_query: Query<
-        (Entity, &ControlState, &Children, &GlobalTransform),
+        (
+            Entity,
+            &ControlState,
+            &Children,
+            &GlobalTransform,
+            &Velocity,
+        ),
         (With<Yacht>, With<PlayerControlled>),
     >,
     just_controlled: Query<Entity, (With<Yacht>, Added<PlayerControlled>)>,
@@ -207,13 +219,15 @@ pub fn yacht_exit_system(
     >,
     exit_point_query: Query<(&ExitPoint, &GlobalTransform)>,
     deck_anchor_query: Query<(Entity, &GlobalTransform), With<DeckWalkAnchor>>,
+    dock_query: Query<(Entity, &Dock)>,
     mut player_query: Query<
         (Entity, &mut Transform, &mut Visibility),
         (With<Player>, Without<PlayerControlled>),
     >,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut next_state: GameStateTransitions,
+    mut swimming_events: EventWriter<SwimmingEvent>,
 ) {
-    for (yacht_entity, control_state, children, _yacht_gt) in yacht_query.iter() {
+    for (yacht_entity, control_state, children, yacht_gt, yacht_velocity) in yacht_query.iter() {
         // Skip one frame after control transfer to prevent immediate exit when F is held
         if just_controlled.get(yacht_entity).is_ok() {
             continue;
@@ -262,12 +276,69 @@ pub fn yacht_exit_system(
                 }
 
                 queue_active_transfer(&mut commands, yacht_entity, heli_entity, &time);
-                next_state.set(GameState::Flying);
+                if let Err(error) = next_state.set(GameState::Flying) {
+                    warn!("GameState change refused: {error}");
+                }
 
                 continue;
             }
         }
 
+        // Plain F while drifting alongside a pier ties up and steps ashore
+        let position = yacht_gt.translation();
+        let forward = yacht_gt.forward().as_vec3();
+        let drifting = yacht_velocity.linvel.with_y(0.0).length() < DOCKING_MAX_SPEED;
+        let mooring = dock_query
+            .iter()
+            .map(|(dock_entity, dock)| {
+                let (berth, heading) = dock.berth(position, YACHT_HALF_BEAM, forward);
+                (dock_entity, dock, berth, heading)
+            })
+            .filter(|(_, _, berth, _)| {
+                berth.with_y(0.0).distance(position.with_y(0.0)) < DOCKING_RANGE
+            })
+            .min_by(|(_, _, a, _), (_, _, b, _)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+        if !control_state.run
+            && drifting
+            && let Some((dock_entity, dock, berth, heading)) = mooring
+            && let Ok((player_entity, mut player_transform, mut player_visibility)) =
+                player_query.single_mut()
+        {
+            commands
+                .entity(yacht_entity)
+                .remove::<PlayerControlled>()
+                .insert(ControlState::default())
+                .insert(MooredAtDock {
+                    dock: dock_entity,
+                    berth,
+                    heading,
+                });
+
+            // Step off onto the pier next to the helm
+            let (landing, _) = dock.closest(position);
+            *player_transform = Transform::from_translation(landing + Vec3::Y * 1.0);
+            *player_visibility = Visibility::Visible;
+            commands
+                .entity(player_entity)
+                .insert(PlayerControlled)
+                .insert(ControlState::default())
+                .insert(VehicleControlType::Walking)
+                .insert(PendingPhysicsEnable)
+                .remove::<InCar>()
+                .remove::<ChildOf>()
+                .remove::<DeckWalker>();
+
+            info!("Moored at {}", dock.name);
+            queue_active_transfer(&mut commands, yacht_entity, player_entity, &time);
+            if let Err(error) = next_state.set(GameState::Walking) {
+                warn!("GameState change refused: {error}");
+            }
+            continue;
+        }
+
         // Determine exit behavior: Shift+F = water, plain F = deck walk
         let exit_to_water = control_state.run;
 
@@ -355,34 +426,25 @@ pub fn yacht_exit_system(
                         // }
 
                         queue_active_transfer(&mut commands, yacht_entity, player_entity, &time);
-                        next_state.set(GameState::Walking);
+                        if let Err(error) = next_state.set(GameState::Walking) {
+                            warn!("GameState change refused: {error}");
+                        }
                     }
                     ExitPointKind::Water => {
                         // Enable physics for swimming (buoyancy, collisions)
                         commands.entity(player_entity).remove::<ChildOf>();
                         *player_transform = Transform::from_translation(exit_gt.translation());
 
-                        // CRITICAL: Insert swimming components and physics for proper water entry
+                        // Swimming components and GameState are applied by apply_swimming_state
                         commands
                             .entity(player_entity)
-                            .insert(VehicleControlType::Swimming)
                             .insert(ControlState::default())
-                            .insert(Swimming {
-                                state: SwimState::Surface,
-                            })
-                            .insert(GravityScale(0.1))
-                            .insert(Damping {
-                                linear_damping: 6.0,
-                                angular_damping: 3.0,
-                            })
-                            .insert(WaterBodyId)
-                            .insert(ProneRotation {
-                                target_pitch: -std::f32::consts::FRAC_PI_2,
-                                current_pitch: 0.0,
-                                going_prone: true,
-                            })
                             .insert(PendingPhysicsEnable)
                             .remove::<DeckWalker>();
+                        swimming_events.write(SwimmingEvent::EnterWater {
+                            entity: player_entity,
+                            depth: 0.0,
+                        });
 
                         // DO NOT UNDOCK helicopters here! They should stay docked until flown.
                         // for (heli_entity, heli_gt, docked) in docked_helicopter_query.iter() {
@@ -395,7 +457,6 @@ pub fn yacht_exit_system(
                         // }
 
                         queue_active_transfer(&mut commands, yacht_entity, player_entity, &time);
-                        next_state.set(GameState::Swimming);
                     }
                 }
             }
@@ -403,6 +464,12 @@ pub fn yacht_exit_system(
     }
 }
 
+/// How far a point riding on an anchor moved since the anchor was at `last`
+pub fn anchor_delta(last: &GlobalTransform, now: &GlobalTransform) -> Vec3 {
+    let delta = now.affine() * last.affine().inverse();
+    delta.transform_point3(Vec3::ZERO)
+}
+
 pub fn deck_walk_movement_system(
     time: Res<Time>,
     mut deck_walker_query: Query<(Entity, &mut DeckWalker, &ControlState), With<Player>>,
@@ -413,11 +480,7 @@ pub fn deck_walk_movement_system(
         if let Ok(mut player_transform) = player_transform_query.get_mut(player_entity) {
             if let Ok(anchor_gt) = anchor_query.get(deck_walker.deck_anchor) {
                 let a_now = anchor_gt.affine();
-                let a_last = deck_walker.last_anchor.affine();
-                let delta = a_now * a_last.inverse();
-
-                let delta_translation = delta.transform_point3(Vec3::ZERO);
-                player_transform.translation += delta_translation;
+                player_transform.translation += anchor_delta(&deck_walker.last_anchor, anchor_gt);
 
                 let walk_speed = if control_state.run { 8.0 } else { 4.0 };
 
@@ -456,19 +519,21 @@ pub fn deck_walk_movement_system(
     }
 }
 
-#[allow(clippy::type_complexity)]
+/// Takes the helm of the yacht the player is walking on when they use it
 pub fn yacht_board_from_deck_system(
     mut commands: Commands,
     time: Res<Time>,
-    player_query: Query<
-        (Entity, &ControlState, &DeckWalker),
-        (With<Player>, With<PlayerControlled>),
-    >,
+    mut interactions: EventReader<InteractionEvent>,
+    player_query: Query<&DeckWalker, (With<Player>, With<PlayerControlled>)>,
     yacht_query: Query<Entity, (With<Yacht>, Without<PlayerControlled>)>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut next_state: GameStateTransitions,
 ) {
-    for (player_entity, control_state, deck_walker) in player_query.iter() {
-        if !control_state.interact {
+    for interaction in interactions.read() {
+        let player_entity = interaction.actor;
+        let Ok(deck_walker) = player_query.get(player_entity) else {
+            continue;
+        };
+        if deck_walker.yacht != interaction.target {
             continue;
         }
 
@@ -488,7 +553,9 @@ pub fn yacht_board_from_deck_system(
                 .insert(ControlState::default());
 
             queue_active_transfer(&mut commands, player_entity, yacht_entity, &time);
-            next_state.set(GameState::Driving);
+            if let Err(error) = next_state.set(GameState::Driving) {
+                warn!("GameState change refused: {error}");
+            }
         }
     }
 }
@@ -522,9 +589,9 @@ pub fn heli_landing_detection_system(
 ) {
     // OPTIMIZATION 1: Altitude pre-filter - only check helicopters below landing altitude
     const MAX_LANDING_ALTITUDE: f32 = 20.0;
-    const LANDING_RADIUS: f32 = 8.0; 
+    const LANDING_RADIUS: f32 = 8.0;
     const LANDING_RADIUS_SQUARED: f32 = LANDING_RADIUS * LANDING_RADIUS;
-    
+
     // TARGET_DOCK_HEIGHT: -0.78m relative to sensor (Calculated from geometry).
     // - Sensor Y = 5.5m
     // - Visual Deck Y = 4.0m
@@ -533,14 +600,14 @@ pub fn heli_landing_detection_system(
     // - Offset from Sensor = 4.7 - 5.5 = -0.8m
     // - Added +0.02m epsilon to prevent z-fighting -> -0.78m
     const TARGET_DOCK_HEIGHT: f32 = -0.78;
-    
+
     // Detection window centered on target height.
     // Must include 0.0 (physical contact) and allow for suspension compression/float.
     // Range: [-0.35, 0.45]
-    const DOCK_TOLERANCE: f32 = 0.4; 
+    const DOCK_TOLERANCE: f32 = 0.4;
     const MIN_TOUCHDOWN_HEIGHT: f32 = TARGET_DOCK_HEIGHT - DOCK_TOLERANCE;
     const MAX_TOUCHDOWN_HEIGHT: f32 = TARGET_DOCK_HEIGHT + DOCK_TOLERANCE;
-    
+
     const MAX_LANDING_SPEED: f32 = 6.0; // Increased from 2.0 for forgiving landing
     const MAX_LANDING_ROTATION: f32 = 1.0; // Increased from 0.5 for stability
 
@@ -594,10 +661,10 @@ pub fn heli_landing_detection_system(
         for (yacht_entity, helipad_pos, yacht_gt) in &helipad_cache {
             let yacht_up = yacht_gt.up();
             let delta = heli_pos - *helipad_pos;
-            
+
             // Project delta onto yacht up vector for vertical distance
             let vertical_dist = delta.dot(*yacht_up);
-            
+
             // Project delta onto deck plane for horizontal distance
             let horizontal_vec = delta - (*yacht_up * vertical_dist);
             let horiz_dist_sq = horizontal_vec.length_squared();
@@ -610,14 +677,14 @@ pub fn heli_landing_detection_system(
             // 2. Vertical check (touching the deck)
             // Target landed height is ~1.5m. Allow 0.5m to 3.0m (1.5m tolerance)
             if !(MIN_TOUCHDOWN_HEIGHT..=MAX_TOUCHDOWN_HEIGHT).contains(&vertical_dist) {
-                 // Only log if close horizontally but wrong height
-                 if horiz_dist_sq < LANDING_RADIUS_SQUARED * 0.5 {
+                // Only log if close horizontally but wrong height
+                if horiz_dist_sq < LANDING_RADIUS_SQUARED * 0.5 {
                     trace!(
-                        "LANDING REJECTED: Height {:.2}m out of range [{:.1}, {:.1}]", 
+                        "LANDING REJECTED: Height {:.2}m out of range [{:.1}, {:.1}]",
                         vertical_dist, MIN_TOUCHDOWN_HEIGHT, MAX_TOUCHDOWN_HEIGHT
                     );
-                 }
-                 continue;
+                }
+                continue;
             }
 
             // info!(
@@ -137,6 +137,7 @@ fn test_world_env_config_resource_availability() {
             size: 1200.0,
             half_size: 600.0,
             beach_width: 100.0,
+            relief: Default::default(),
         },
         max_world_coordinate: 3000.0,
     };
@@ -559,6 +560,62 @@ fn test_ron_file_parsing_simple_yacht() {
     );
 }
 
+#[test]
+fn test_ron_file_parsing_docks() {
+    let assets_base =
+        if cfg!(target_os = "macos") && std::path::Path::new("../Resources/assets").exists() {
+            "../Resources/assets"
+        } else {
+            "assets"
+        };
+
+    let path = format!("{assets_base}/config/docks.ron");
+    let contents = fs::read_to_string(&path).expect("docks.ron should exist and be readable");
+
+    let list: crate::systems::water::DockList =
+        ron::from_str(&contents).expect("docks.ron should parse correctly");
+
+    assert!(
+        !list.docks.is_empty(),
+        "At least one dock should be defined"
+    );
+    for dock in &list.docks {
+        assert!(
+            dock.start.distance(dock.end) > dock.width,
+            "Dock '{}' should be longer than it is wide",
+            dock.name
+        );
+    }
+}
+
+#[test]
+fn test_ron_file_parsing_hospitals() {
+    let assets_base =
+        if cfg!(target_os = "macos") && std::path::Path::new("../Resources/assets").exists() {
+            "../Resources/assets"
+        } else {
+            "assets"
+        };
+
+    let path = format!("{assets_base}/config/hospitals.ron");
+    let contents = fs::read_to_string(&path).expect("hospitals.ron should exist and be readable");
+
+    let list: crate::systems::player_health::HospitalList =
+        ron::from_str(&contents).expect("hospitals.ron should parse correctly");
+
+    assert!(
+        !list.hospitals.is_empty(),
+        "At least one hospital should be defined"
+    );
+    for hospital in &list.hospitals {
+        assert!(
+            hospital.position.distance(hospital.entrance) < 20.0,
+            "Hospital '{}' should have its entrance next to the sign",
+            hospital.name
+        );
+    }
+}
+
 #[test]
 fn test_ron_file_parsing_vehicle_controls() {
     let assets_base =
@@ -646,4 +703,90 @@ fn test_ron_file_parsing_world_physics() {
         physics_config.building_activation.activation_radius > 0.0,
         "Building activation radius should be positive"
     );
+    assert!(
+        physics_config.street_props.max_loose >= physics_config.street_props.activations_per_frame,
+        "Loose prop budget should fit at least one frame of activations"
+    );
+}
+
+#[test]
+fn test_workspace_configs_layer_over_defaults() {
+    use crate::util::config_merge::merge_ron;
+
+    let assets_base =
+        if cfg!(target_os = "macos") && std::path::Path::new("../Resources/assets").exists() {
+            "../Resources/assets"
+        } else {
+            "assets"
+        };
+    let read = |name: &str| {
+        fs::read_to_string(format!("{assets_base}/config/{name}"))
+            .unwrap_or_else(|e| panic!("{name} should be readable: {e}"))
+    };
+    let defaults = GameConfig::default();
+
+    let streaming = merge_ron(&defaults.world_streaming, &read("world_streaming.ron"))
+        .expect("world_streaming.ron should layer over defaults");
+    let parsed: WorldStreamingConfig = ron::from_str(&read("world_streaming.ron")).unwrap();
+    assert_eq!(streaming.streaming_radius, parsed.streaming_radius);
+
+    let env = merge_ron(&defaults.world_env, &read("world_config.ron"))
+        .expect("world_config.ron should layer over defaults");
+    let parsed: WorldEnvConfig = ron::from_str(&read("world_config.ron")).unwrap();
+    assert_eq!(env.islands.grid_z, parsed.islands.grid_z);
+
+    merge_ron(&defaults.world_physics, &read("world_physics.ron"))
+        .expect("world_physics.ron should layer over defaults");
+    merge_ron(&defaults.world_bounds, &read("world_bounds.ron"))
+        .expect("world_bounds.ron should layer over defaults");
+    merge_ron(&defaults.camera, &read("camera.ron"))
+        .expect("camera.ron should layer over defaults");
+}
+
+#[test]
+fn test_env_overrides_set_nested_keys() {
+    use crate::config::{ColorblindMode, parse_env_override};
+
+    let (file, fields) =
+        parse_env_override("GTA_CONFIG__WORLD_STREAMING__LOD_DISTANCES__FULL").unwrap();
+    assert_eq!(file, "world_streaming.ron");
+    assert_eq!(fields, vec!["lod_distances", "full"]);
+    assert!(parse_env_override("GTA_CONFIG__CAMERA").is_none());
+    assert!(parse_env_override("GTA_CONFIG__NO_SUCH_FILE__FIELD").is_none());
+    assert!(parse_env_override("GTA_LANG").is_none());
+
+    let mut config = GameConfig::default();
+    let medium = config.world_streaming.lod_distances.medium;
+    config
+        .apply_override(&file, &fields, "200.0")
+        .expect("a float should set a nested float");
+    assert_eq!(config.world_streaming.lod_distances.full, 200.0);
+    assert_eq!(config.world_streaming.lod_distances.medium, medium);
+    assert_eq!(config.world.lod_distances[0], 200.0);
+
+    config
+        .apply_override("localization.ron", &["language".to_string()], "es")
+        .expect("a bare word should be taken as a string");
+    assert_eq!(config.localization.language, "es");
+    config
+        .apply_override(
+            "accessibility.ron",
+            &["colorblind_mode".to_string()],
+            "Deuteranopia",
+        )
+        .expect("a bare word should also name an enum variant");
+    assert_eq!(
+        config.accessibility.colorblind_mode,
+        ColorblindMode::Deuteranopia
+    );
+    assert!(
+        config
+            .apply_override("camera.ron", &["car".into(), "distance".into()], "far")
+            .is_err()
+    );
+    assert!(
+        config
+            .apply_override("camera.ron", &["distance".to_string()], "12.0")
+            .is_err()
+    );
 }
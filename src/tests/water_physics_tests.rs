@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use crate::components::unified_water::{TideConfig, UnifiedWaterBody};
+    use crate::components::unified_water::{
+        BuoyancyHull, TideConfig, UnifiedWaterBody, WaveParams,
+    };
+    use crate::systems::water::hull_forces;
     use bevy::prelude::*;
+    use bevy_rapier3d::prelude::Velocity;
 
     #[test]
     fn test_tide_offset_normal() {
@@ -225,4 +229,72 @@ mod tests {
 
         assert_eq!(water.get_bed_level(), 2.0); // 5.0 - 3.0
     }
+
+    #[test]
+    fn test_surface_height_follows_waves() {
+        let mut water = UnifiedWaterBody {
+            surface_level: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(water.sample_surface_height(30.0, -12.0, 4.0), 2.0);
+
+        water.wave_params = Some(WaveParams {
+            amplitude: 0.25,
+            frequency: 0.05,
+            speed: 0.0,
+        });
+        let heights: Vec<f32> = (0..20)
+            .map(|i| water.sample_surface_height(i as f32 * 9.0, 5.0, 1.5))
+            .collect();
+        // Swell amplitudes at scale 1.0 sum to 1.75
+        assert!(heights.iter().all(|h| (h - 2.0).abs() <= 1.75));
+        assert!(heights.iter().any(|h| (h - 2.0).abs() > 0.1));
+    }
+
+    #[test]
+    fn test_hull_floats_at_rest_depth_and_rights_itself() {
+        let hull = BuoyancyHull::box_keel(Vec3::new(2.0, 1.0, 5.0), 0.5);
+        let mass = 900.0;
+        let calm = |_: Vec3| 0.0;
+
+        // Keel exactly rest_depth below the surface: lift balances weight
+        let level = Transform::from_xyz(0.0, 0.5, 0.0);
+        let (force, torque) = hull_forces(
+            &hull,
+            &level,
+            level.translation,
+            &Velocity::zero(),
+            mass,
+            9.81,
+            calm,
+        );
+        assert!((force.y - mass * 9.81).abs() < 1e-2);
+        assert!(torque.length() < 1e-2);
+
+        // Rolled onto its right side: torque rolls it back
+        let rolled = level.with_rotation(Quat::from_rotation_z(0.2));
+        let (_, torque) = hull_forces(
+            &hull,
+            &rolled,
+            rolled.translation,
+            &Velocity::zero(),
+            mass,
+            9.81,
+            calm,
+        );
+        assert!(torque.z < 0.0);
+
+        // Clear of the water: no forces at all
+        let airborne = Transform::from_xyz(0.0, 10.0, 0.0);
+        let (force, _) = hull_forces(
+            &hull,
+            &airborne,
+            airborne.translation,
+            &Velocity::zero(),
+            mass,
+            9.81,
+            calm,
+        );
+        assert_eq!(force, Vec3::ZERO);
+    }
 }
@@ -0,0 +1,392 @@
+//! Bounding volumes, view frustums and intersection tests.
+//!
+//! `Frustum` is extracted from a view-projection matrix (Gribb/Hartmann) and
+//! tests spheres, axis-aligned and oriented boxes conservatively: a volume is
+//! only reported outside when it lies wholly behind one plane. Works with
+//! both standard `[0, 1]` depth and Bevy's infinite reverse-Z projections;
+//! the plane at infinity is degenerate and never culls.
+//!
+//! For culling many bounds at once, `SphereBatch` and `BoxBatch` keep their
+//! volumes in structure-of-arrays layout so the frustum tests four at a time
+//! in `Vec4` lanes.
+
+use bevy::math::{BVec4A, Mat3, Mat4, Quat, Vec3, Vec4};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingSphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    pub fn new(center: Vec3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+}
+
+/// Axis-aligned box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl BoundingBox {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
+
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        let closest = sphere.center.clamp(self.min, self.max);
+        closest.distance_squared(sphere.center) <= sphere.radius * sphere.radius
+    }
+
+    /// Distance along the ray to where it enters the box, or 0 when it
+    /// starts inside; `None` if it misses. `direction` needn't be normalized,
+    /// the distance is then in multiples of it.
+    pub fn intersect_ray(&self, origin: Vec3, direction: Vec3) -> Option<f32> {
+        let inverse = direction.recip();
+        let to_min = (self.min - origin) * inverse;
+        let to_max = (self.max - origin) * inverse;
+        // A zero direction component gives inf or NaN; min/max skip the NaNs
+        let near = to_min.min(to_max).max_element();
+        let far = to_min.max(to_max).min_element();
+        (near <= far && far >= 0.0).then(|| near.max(0.0))
+    }
+}
+
+/// Box with its own rotation, e.g. a vehicle's collider
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedBox {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+    pub rotation: Quat,
+}
+
+impl OrientedBox {
+    pub fn new(center: Vec3, half_extents: Vec3, rotation: Quat) -> Self {
+        Self {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// Smallest axis-aligned box around this one
+    pub fn bounding_box(&self) -> BoundingBox {
+        let axes = Mat3::from_quat(self.rotation);
+        let reach = axes.x_axis.abs() * self.half_extents.x
+            + axes.y_axis.abs() * self.half_extents.y
+            + axes.z_axis.abs() * self.half_extents.z;
+        BoundingBox::from_center_half_extents(self.center, reach)
+    }
+
+    /// Like `BoundingBox::intersect_ray`, tested in the box's own space
+    pub fn intersect_ray(&self, origin: Vec3, direction: Vec3) -> Option<f32> {
+        let inverse = self.rotation.inverse();
+        let local = BoundingBox::from_center_half_extents(Vec3::ZERO, self.half_extents);
+        local.intersect_ray(inverse * (origin - self.center), inverse * direction)
+    }
+}
+
+/// Six planes as `(normal, d)` with normals pointing inward, so a point is
+/// inside when `normal.dot(point) + d >= 0` for all of them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    pub planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Planes of `clip_from_world` (projection * view)
+    pub fn from_view_projection(clip_from_world: Mat4) -> Self {
+        let rows = [
+            clip_from_world.row(0),
+            clip_from_world.row(1),
+            clip_from_world.row(2),
+            clip_from_world.row(3),
+        ];
+        let planes = [
+            rows[3] + rows[0],
+            rows[3] - rows[0],
+            rows[3] + rows[1],
+            rows[3] - rows[1],
+            rows[2],
+            rows[3] - rows[2],
+        ]
+        .map(|plane| {
+            let length = plane.truncate().length();
+            if length > f32::EPSILON {
+                plane / length
+            } else {
+                // Plane at infinity; nothing is behind it
+                Vec4::W
+            }
+        });
+        Self { planes }
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(point) + plane.w >= 0.0)
+    }
+
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(sphere.center) + plane.w >= -sphere.radius)
+    }
+
+    pub fn intersects_box(&self, bounds: &BoundingBox) -> bool {
+        let (center, half) = (bounds.center(), bounds.half_extents());
+        self.planes.iter().all(|plane| {
+            let normal = plane.truncate();
+            normal.dot(center) + plane.w >= -normal.abs().dot(half)
+        })
+    }
+
+    pub fn intersects_oriented_box(&self, bounds: &OrientedBox) -> bool {
+        let axes = Mat3::from_quat(bounds.rotation);
+        let half = bounds.half_extents;
+        self.planes.iter().all(|plane| {
+            let normal = plane.truncate();
+            let reach = normal.dot(axes.x_axis).abs() * half.x
+                + normal.dot(axes.y_axis).abs() * half.y
+                + normal.dot(axes.z_axis).abs() * half.z;
+            normal.dot(bounds.center) + plane.w >= -reach
+        })
+    }
+
+    /// Writes one flag per sphere in `batch` to `visible`
+    pub fn visible_spheres(&self, batch: &SphereBatch, visible: &mut Vec<bool>) {
+        self.test_lanes(&batch.lanes, batch.len, visible, |plane, lanes| {
+            let [x, y, z, radius] = lanes;
+            let distance = x * plane.x + y * plane.y + z * plane.z + Vec4::splat(plane.w);
+            distance.cmpge(-*radius)
+        });
+    }
+
+    /// Writes one flag per box in `batch` to `visible`
+    pub fn visible_boxes(&self, batch: &BoxBatch, visible: &mut Vec<bool>) {
+        self.test_lanes(&batch.lanes, batch.len, visible, |plane, lanes| {
+            let [x, y, z, hx, hy, hz] = lanes;
+            let distance = x * plane.x + y * plane.y + z * plane.z + Vec4::splat(plane.w);
+            let reach = hx * plane.x.abs() + hy * plane.y.abs() + hz * plane.z.abs();
+            distance.cmpge(-reach)
+        });
+    }
+
+    fn test_lanes<const N: usize>(
+        &self,
+        lanes: &[[Vec4; N]],
+        len: usize,
+        visible: &mut Vec<bool>,
+        inside: impl Fn(Vec4, &[Vec4; N]) -> BVec4A,
+    ) {
+        visible.clear();
+        visible.reserve(lanes.len() * 4);
+        for group in lanes {
+            let mask = self
+                .planes
+                .iter()
+                .fold(BVec4A::splat(true), |mask, plane| {
+                    mask & inside(*plane, group)
+                })
+                .bitmask();
+            visible.extend((0..4).map(|lane| mask & (1 << lane) != 0));
+        }
+        visible.truncate(len);
+    }
+}
+
+/// Spheres stored four to a group, one `Vec4` per coordinate
+#[derive(Debug, Clone, Default)]
+pub struct SphereBatch {
+    lanes: Vec<[Vec4; 4]>,
+    len: usize,
+}
+
+impl SphereBatch {
+    pub fn push(&mut self, sphere: BoundingSphere) {
+        let lane = self.len % 4;
+        if lane == 0 {
+            self.lanes.push([Vec4::ZERO; 4]);
+        }
+        let group = self.lanes.last_mut().expect("group pushed above");
+        group[0][lane] = sphere.center.x;
+        group[1][lane] = sphere.center.y;
+        group[2][lane] = sphere.center.z;
+        group[3][lane] = sphere.radius;
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.lanes.clear();
+        self.len = 0;
+    }
+}
+
+/// Axis-aligned boxes stored four to a group as centers and half extents
+#[derive(Debug, Clone, Default)]
+pub struct BoxBatch {
+    lanes: Vec<[Vec4; 6]>,
+    len: usize,
+}
+
+impl BoxBatch {
+    pub fn push(&mut self, bounds: BoundingBox) {
+        let lane = self.len % 4;
+        if lane == 0 {
+            self.lanes.push([Vec4::ZERO; 6]);
+        }
+        let group = self.lanes.last_mut().expect("group pushed above");
+        let (center, half) = (bounds.center(), bounds.half_extents());
+        for axis in 0..3 {
+            group[axis][lane] = center[axis];
+            group[axis + 3][lane] = half[axis];
+        }
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.lanes.clear();
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    /// Camera at the origin looking down -Z
+    fn frustums() -> [Frustum; 2] {
+        let view = Mat4::look_to_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        [
+            Mat4::perspective_rh(FRAC_PI_2, 1.0, 0.1, 100.0),
+            Mat4::perspective_infinite_reverse_rh(FRAC_PI_2, 1.0, 0.1),
+        ]
+        .map(|projection| Frustum::from_view_projection(projection * view))
+    }
+
+    #[test]
+    fn test_frustum_culls_volumes_outside_any_plane() {
+        for (index, frustum) in frustums().into_iter().enumerate() {
+            assert!(frustum.contains_point(Vec3::new(0.0, 0.0, -10.0)));
+            assert!(!frustum.contains_point(Vec3::new(0.0, 0.0, 10.0)));
+            assert!(!frustum.contains_point(Vec3::new(20.0, 0.0, -10.0)));
+            // Only the finite projection has a far plane
+            assert_eq!(
+                frustum.contains_point(Vec3::new(0.0, 0.0, -500.0)),
+                index == 1
+            );
+
+            // Centered outside the right plane but reaching back in
+            let straddling = BoundingSphere::new(Vec3::new(11.0, 0.0, -10.0), 2.0);
+            assert!(frustum.intersects_sphere(&straddling));
+            let behind = BoundingBox::from_center_half_extents(Vec3::new(0.0, 0.0, 5.0), Vec3::ONE);
+            assert!(!frustum.intersects_box(&behind));
+
+            // A beam just outside the right plane and parallel to it is culled,
+            // though its axis-aligned bounds reach into view
+            let beam = Vec3::new(8.0, 0.5, 0.5);
+            let center = Vec3::new(12.0, 0.0, -10.0);
+            let parallel = OrientedBox::new(center, beam, Quat::from_rotation_y(FRAC_PI_4));
+            assert!(!frustum.intersects_oriented_box(&parallel));
+            assert!(frustum.intersects_box(&parallel.bounding_box()));
+            let across = OrientedBox::new(center, beam, Quat::IDENTITY);
+            assert!(frustum.intersects_oriented_box(&across));
+        }
+    }
+
+    #[test]
+    fn test_batches_match_single_tests() {
+        let [frustum, _] = frustums();
+        let mut spheres = SphereBatch::default();
+        let mut boxes = BoxBatch::default();
+        let mut expected_spheres = Vec::new();
+        let mut expected_boxes = Vec::new();
+        for i in 0..11 {
+            let center = Vec3::new(i as f32 * 4.0 - 20.0, 0.0, -10.0 + i as f32);
+            let sphere = BoundingSphere::new(center, 1.5);
+            let bounds = BoundingBox::from_center_half_extents(center, Vec3::new(1.0, 2.0, 0.5));
+            spheres.push(sphere);
+            boxes.push(bounds);
+            expected_spheres.push(frustum.intersects_sphere(&sphere));
+            expected_boxes.push(frustum.intersects_box(&bounds));
+        }
+
+        let mut visible = Vec::new();
+        frustum.visible_spheres(&spheres, &mut visible);
+        assert_eq!(visible, expected_spheres);
+        frustum.visible_boxes(&boxes, &mut visible);
+        assert_eq!(visible, expected_boxes);
+        assert!(visible.contains(&true) && visible.contains(&false));
+    }
+
+    #[test]
+    fn test_ray_slab_intersection() {
+        let bounds = BoundingBox::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+        let hit = bounds.intersect_ray(Vec3::new(-5.0, 0.5, 0.0), Vec3::X);
+        assert_eq!(hit, Some(4.0));
+        assert_eq!(bounds.intersect_ray(Vec3::ZERO, Vec3::Y), Some(0.0));
+        assert_eq!(
+            bounds.intersect_ray(Vec3::new(-5.0, 2.0, 0.0), Vec3::X),
+            None
+        );
+        assert_eq!(
+            bounds.intersect_ray(Vec3::new(-5.0, 0.0, 0.0), Vec3::NEG_X),
+            None
+        );
+
+        let turned = OrientedBox::new(
+            Vec3::ZERO,
+            Vec3::new(3.0, 1.0, 1.0),
+            Quat::from_rotation_z(FRAC_PI_2),
+        );
+        let hit = turned
+            .intersect_ray(Vec3::new(0.0, -10.0, 0.0), Vec3::Y)
+            .unwrap();
+        assert!((hit - 7.0).abs() < 1e-4);
+        assert!((turned.bounding_box().half_extents() - Vec3::new(1.0, 3.0, 1.0)).length() < 1e-4);
+    }
+}
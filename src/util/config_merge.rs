@@ -0,0 +1,269 @@
+//! Field-level merging of partial RON config files
+//!
+//! `merge_ron` parses a RON document on top of an existing value: every
+//! struct field the document leaves out, at any depth, keeps its value from
+//! the base. Anything that isn't a struct (numbers, strings, enums, lists,
+//! maps) is replaced whole when the document sets it.
+
+use serde::Serialize;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor,
+};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Parses `overrides` over `base`, keeping base values for missing struct fields
+pub fn merge_ron<T: Serialize + DeserializeOwned>(base: &T, overrides: &str) -> ron::Result<T> {
+    let base = serde_json::to_value(base).map_err(|e| ron::Error::Message(e.to_string()))?;
+    let mut deserializer = ron::Deserializer::from_str(overrides).map_err(|e| e.code)?;
+    let merged = T::deserialize(Layered {
+        over: &mut deserializer,
+        base,
+    })?;
+    deserializer.end()?;
+    Ok(merged)
+}
+
+/// Deserializer reading from `over` that fills in struct fields from `base`
+struct Layered<D> {
+    over: D,
+    base: Value,
+}
+
+macro_rules! forward_to_over {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                self.over.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Layered<D> {
+    type Error = D::Error;
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.base {
+            Value::Object(base) => self.over.deserialize_struct(
+                name,
+                fields,
+                LayeredVisitor {
+                    inner: visitor,
+                    base,
+                },
+            ),
+            _ => self.over.deserialize_struct(name, fields, visitor),
+        }
+    }
+
+    forward_to_over! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.over.is_human_readable()
+    }
+}
+
+/// Wraps a struct visitor so its map sees the base's fields after the overrides
+struct LayeredVisitor<V> {
+    inner: V,
+    base: Map<String, Value>,
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for LayeredVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, over: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_map(LayeredMap {
+            over,
+            over_done: false,
+            base: self.base,
+            field_base: None,
+            leftover: None,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_seq(seq)
+    }
+}
+
+/// Yields the fields set in the overrides, then whatever the base has left
+struct LayeredMap<A> {
+    over: A,
+    over_done: bool,
+    base: Map<String, Value>,
+    /// Base value of the field just read from the overrides
+    field_base: Option<Value>,
+    /// Value of the field just taken from the base
+    leftover: Option<Value>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for LayeredMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        if !self.over_done {
+            match self.over.next_key_seed(FieldName)? {
+                Some(key) => {
+                    self.field_base = self.base.remove(&key);
+                    return seed.deserialize(key.into_deserializer()).map(Some);
+                }
+                None => self.over_done = true,
+            }
+        }
+        let Some(key) = self.base.keys().next().cloned() else {
+            return Ok(None);
+        };
+        self.leftover = self.base.remove(&key);
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        if let Some(value) = self.leftover.take() {
+            return seed.deserialize(value).map_err(de::Error::custom);
+        }
+        self.over.next_value_seed(LayeredSeed {
+            seed,
+            base: self.field_base.take().unwrap_or(Value::Null),
+        })
+    }
+}
+
+/// Seed for one field's value, layered over that field's base value
+struct LayeredSeed<S> {
+    seed: S,
+    base: Value,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for LayeredSeed<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, over: D) -> Result<Self::Value, D::Error> {
+        self.seed.deserialize(Layered {
+            over,
+            base: self.base,
+        })
+    }
+}
+
+/// Reads a struct field name as a plain string
+struct FieldName;
+
+impl<'de> DeserializeSeed<'de> for FieldName {
+    type Value = String;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<String, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl Visitor<'_> for FieldName {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a field name")
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<String, E> {
+        Ok(name.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WorldPhysicsConfig;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Cuboid,
+        Ball(f32),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Part {
+        size: f32,
+        shape: Shape,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn test_missing_fields_keep_base_values() {
+        let base = WorldPhysicsConfig::default();
+        let merged = merge_ron(&base, "(street_props: (max_loose: 10))").unwrap();
+        assert_eq!(merged.street_props.max_loose, 10);
+        assert_eq!(
+            merged.street_props.activations_per_frame,
+            base.street_props.activations_per_frame
+        );
+        assert_eq!(
+            merged.building_activation.activation_radius,
+            base.building_activation.activation_radius
+        );
+    }
+
+    #[test]
+    fn test_set_values_replace_whole() {
+        let base = Part {
+            size: 1.0,
+            shape: Shape::Ball(2.0),
+            tags: vec!["a".into(), "b".into()],
+        };
+        let merged = merge_ron(&base, "Part(shape: Cuboid, tags: [\"c\"])").unwrap();
+        assert_eq!(merged.size, 1.0);
+        assert_eq!(merged.shape, Shape::Cuboid);
+        assert_eq!(merged.tags, vec!["c".to_string()]);
+        assert!(merge_ron(&base, "(size: \"big\")").is_err());
+    }
+}
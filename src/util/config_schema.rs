@@ -0,0 +1,184 @@
+//! Schema export and checks for the layered RON config files
+//!
+//! Schemas are inferred from the default `GameConfig` sections, so they list
+//! every field with its type and default but know nothing of enums beyond
+//! the shape of the default variant. Range checks run the same
+//! `validate_and_clamp` the game runs at startup and report what it changed;
+//! hot reload diffs the sections the same way.
+
+use crate::util::suggest::closest_match;
+use serde_json::{Map, Value, json};
+
+/// JSON Schema describing `value`, with its contents as the defaults.
+/// Empty maps are taken to be free-form (per-language font lists and the like).
+pub fn json_schema(value: &Value) -> Value {
+    let mut schema = match value {
+        Value::Object(fields) if fields.is_empty() => json!({ "type": "object" }),
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, field)| (name.clone(), json_schema(field)))
+                .collect();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "additionalProperties": false,
+            })
+        }
+        Value::Array(items) => match items.first() {
+            Some(first) => json!({ "type": "array", "items": json_schema(first) }),
+            None => json!({ "type": "array" }),
+        },
+        Value::Number(number) if number.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Null => json!({}),
+    };
+    schema["default"] = value.clone();
+    schema
+}
+
+/// A field set in a config file that its section doesn't have
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownField {
+    /// Dotted path from the section root
+    pub path: String,
+    /// Closest known field at the same level, when one is close enough
+    pub suggestion: Option<String>,
+}
+
+/// Fields in `user` that `known` (a default section as JSON) doesn't have
+pub fn unknown_fields(user: &ron::Value, known: &Value) -> Vec<UnknownField> {
+    let mut unknown = Vec::new();
+    collect_unknown(user, known, "", &mut unknown);
+    unknown
+}
+
+fn collect_unknown(user: &ron::Value, known: &Value, prefix: &str, out: &mut Vec<UnknownField>) {
+    let (ron::Value::Map(map), Value::Object(fields)) = (user, known) else {
+        return;
+    };
+    if fields.is_empty() {
+        return;
+    }
+    for (key, value) in map.iter() {
+        let ron::Value::String(name) = key else {
+            continue;
+        };
+        let path = format!("{prefix}{name}");
+        match fields.get(name) {
+            Some(field) => collect_unknown(value, field, &format!("{path}."), out),
+            None => out.push(UnknownField {
+                suggestion: closest_match(name, fields.keys().map(String::as_str))
+                    .map(str::to_string),
+                path,
+            }),
+        }
+    }
+}
+
+/// A leaf that differs between two versions of a section, such as a value
+/// `validate_and_clamp` moved back into range
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedValue {
+    /// Dotted path from the section root, with `[i]` for list entries
+    pub path: String,
+    /// `Null` for map keys only `after` has
+    pub before: Value,
+    /// `Null` for map keys only `before` has
+    pub after: Value,
+}
+
+/// Leaves that differ between `before` and `after`
+pub fn changed_values(before: &Value, after: &Value) -> Vec<ChangedValue> {
+    let mut changed = Vec::new();
+    collect_changed(before, after, "", &mut changed);
+    changed
+}
+
+fn collect_changed(before: &Value, after: &Value, path: &str, out: &mut Vec<ChangedValue>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            let added = after.keys().filter(|name| !before.contains_key(*name));
+            for name in before.keys().chain(added) {
+                let path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{path}.{name}")
+                };
+                collect_changed(
+                    before.get(name).unwrap_or(&Value::Null),
+                    after.get(name).unwrap_or(&Value::Null),
+                    &path,
+                    out,
+                );
+            }
+        }
+        (Value::Array(before), Value::Array(after)) if before.len() == after.len() => {
+            for (index, (value, other)) in before.iter().zip(after).enumerate() {
+                collect_changed(value, other, &format!("{path}[{index}]"), out);
+            }
+        }
+        _ if before != after => out.push(ChangedValue {
+            path: path.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_fields_suggest_the_closest_name() {
+        let known = json!({ "camera": { "distance": 8.0, "height": 2.0 }, "fonts": {} });
+        let user: ron::Value =
+            ron::from_str("(camera: (distnace: 9.0, zoom: 1.0), fonts: { \"es\": [] })").unwrap();
+        let unknown = unknown_fields(&user, &known);
+        assert_eq!(
+            unknown,
+            vec![
+                UnknownField {
+                    path: "camera.distnace".into(),
+                    suggestion: Some("distance".into()),
+                },
+                UnknownField {
+                    path: "camera.zoom".into(),
+                    suggestion: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_and_diff_follow_the_defaults() {
+        let defaults = json!({ "radius": 800.0, "budget": 48, "lods": [150.0, 300.0] });
+        let schema = json_schema(&defaults);
+        assert_eq!(schema["properties"]["radius"]["type"], "number");
+        assert_eq!(schema["properties"]["budget"]["type"], "integer");
+        assert_eq!(
+            schema["properties"]["lods"]["default"],
+            json!([150.0, 300.0])
+        );
+
+        let given = json!({ "radius": 9000.0, "budget": 48, "lods": [150.0, 20.0] });
+        let after = json!({ "radius": 3000.0, "budget": 48, "lods": [150.0, 50.0] });
+        let mut paths: Vec<String> = changed_values(&given, &after)
+            .into_iter()
+            .map(|changed| changed.path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["lods[1]", "radius"]);
+
+        let fonts = json!({ "fonts": { "es": ["a.ttf"] } });
+        let more_fonts = json!({ "fonts": { "es": ["a.ttf"], "ja": ["b.ttf"] } });
+        let changed = changed_values(&fonts, &more_fonts);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].path, "fonts.ja");
+        assert_eq!(changed[0].before, Value::Null);
+    }
+}
@@ -1,4 +1,11 @@
 pub mod asset_path;
+pub mod bounds;
+pub mod config_merge;
+pub mod config_schema;
+pub mod morton;
+pub mod profiling;
+pub mod rolling_file;
 pub mod safe_math;
 pub mod safe_specs;
+pub mod suggest;
 pub mod transform_utils;
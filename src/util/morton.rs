@@ -0,0 +1,212 @@
+//! Z-order (Morton) codes for ground-plane cells.
+//!
+//! Cell x and z are offset by 2^31 before their bits are interleaved, so the
+//! codes keep each axis' order: negative cells sort before positive ones and
+//! every cell in an axis-aligned box has a code between those of the box's
+//! min and max corners. A box is therefore one code interval, which
+//! `ZOrderCells` walks in sort order while jumping over the stretches that
+//! leave the box. Neighbours are stepped to directly on the code, without
+//! decoding it.
+
+use bevy::math::IVec2;
+use std::ops::RangeInclusive;
+
+/// Bits holding the cell's x
+const X_BITS: u64 = 0x5555_5555_5555_5555;
+/// Bits holding the cell's z (the y of the `IVec2`)
+const Z_BITS: u64 = 0xAAAA_AAAA_AAAA_AAAA;
+
+const SIGN_FLIP: u32 = 0x8000_0000;
+
+/// Spreads the bits of `value` so they occupy the even bits of the result
+fn spread_bits(value: u32) -> u64 {
+    let mut x = value as u64;
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & X_BITS
+}
+
+fn compact_bits(code: u64) -> u32 {
+    let mut x = code & X_BITS;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x >> 4)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((x | (x >> 16)) & 0x0000_0000_FFFF_FFFF) as u32
+}
+
+/// Interleaves `x` into the even bits and `z` into the odd bits
+pub fn interleave(x: u32, z: u32) -> u64 {
+    spread_bits(x) | (spread_bits(z) << 1)
+}
+
+pub fn morton_encode(cell: IVec2) -> u64 {
+    interleave(cell.x as u32 ^ SIGN_FLIP, cell.y as u32 ^ SIGN_FLIP)
+}
+
+pub fn morton_decode(code: u64) -> IVec2 {
+    IVec2::new(
+        (compact_bits(code) ^ SIGN_FLIP) as i32,
+        (compact_bits(code >> 1) ^ SIGN_FLIP) as i32,
+    )
+}
+
+/// Moves one cell along the axis in `axis` bits; the carry skips the other
+/// axis' bits because they are filled with ones (or zeros) first
+fn step(code: u64, axis: u64, delta: i32) -> u64 {
+    let lane = match delta.signum() {
+        1 => (code | !axis).wrapping_add(1) & axis,
+        -1 => (code & axis).wrapping_sub(1) & axis,
+        _ => code & axis,
+    };
+    lane | (code & !axis)
+}
+
+/// Code of the cell `offset` away from `code`, for offsets of at most one cell
+pub fn morton_step(code: u64, offset: IVec2) -> u64 {
+    step(step(code, X_BITS, offset.x), Z_BITS, offset.y)
+}
+
+/// Codes of the eight cells around `code`, row by row from (-1, -1).
+/// Chunks and the spatial index only grid the ground plane, so this is the
+/// 2D neighbourhood; there is no 26-cell 3D variant.
+pub fn morton_neighbors(code: u64) -> [u64; 8] {
+    let mut neighbors = [0; 8];
+    let offsets = (-1..=1)
+        .flat_map(|z| (-1..=1).map(move |x| IVec2::new(x, z)))
+        .filter(|offset| *offset != IVec2::ZERO);
+    for (neighbor, offset) in neighbors.iter_mut().zip(offsets) {
+        *neighbor = morton_step(code, offset);
+    }
+    neighbors
+}
+
+/// Interval holding the codes of every cell in `min..=max`; it also holds
+/// cells outside the box, which `morton_in_box` tells apart
+pub fn morton_box_range(min: IVec2, max: IVec2) -> RangeInclusive<u64> {
+    morton_encode(min.min(max))..=morton_encode(min.max(max))
+}
+
+/// Whether `code` lies in the box with corner codes `range`, compared
+/// axis by axis on the interleaved bits
+pub fn morton_in_box(code: u64, range: &RangeInclusive<u64>) -> bool {
+    [X_BITS, Z_BITS].into_iter().all(|axis| {
+        let value = code & axis;
+        range.start() & axis <= value && value <= range.end() & axis
+    })
+}
+
+/// Smallest code in the box `range` that is at least `code`; Tropf and
+/// Herzog's BIGMIN for codes outside the box
+pub fn morton_next_in_box(code: u64, range: &RangeInclusive<u64>) -> Option<u64> {
+    if code > *range.end() {
+        return None;
+    }
+    if morton_in_box(code, range) {
+        return Some(code);
+    }
+    let (mut min, mut max) = (*range.start(), *range.end());
+    let mut next = None;
+    for bit in (0..64).rev() {
+        let mask = 1u64 << bit;
+        // Lower bits of the same axis as `bit`
+        let below = (if bit % 2 == 0 { X_BITS } else { Z_BITS }) & (mask - 1);
+        match (code & mask != 0, min & mask != 0, max & mask != 0) {
+            (false, false, true) => {
+                next = Some((min | mask) & !below);
+                max = (max & !mask) | below;
+            }
+            (false, true, true) => return Some(min),
+            (true, false, false) => return next,
+            (true, false, true) => min = (min | mask) & !below,
+            _ => {}
+        }
+    }
+    next
+}
+
+/// Cells of a box in Morton order, so neighbouring cells are visited close
+/// together and lookups into code-keyed maps stay local
+#[derive(Debug, Clone)]
+pub struct ZOrderCells {
+    range: RangeInclusive<u64>,
+    next: Option<u64>,
+}
+
+impl ZOrderCells {
+    pub fn new(min: IVec2, max: IVec2) -> Self {
+        let range = morton_box_range(min, max);
+        Self {
+            next: Some(*range.start()),
+            range,
+        }
+    }
+}
+
+impl Iterator for ZOrderCells {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        let code = self.next?;
+        self.next = code
+            .checked_add(1)
+            .and_then(|after| morton_next_in_box(after, &self.range));
+        Some(morton_decode(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_round_trip_and_keep_axis_order() {
+        for cell in [
+            IVec2::ZERO,
+            IVec2::new(-1, 1),
+            IVec2::new(-30, 29),
+            IVec2::new(i32::MIN, i32::MAX),
+        ] {
+            assert_eq!(morton_decode(morton_encode(cell)), cell);
+        }
+        assert!(morton_encode(IVec2::new(-1, 0)) < morton_encode(IVec2::new(0, 0)));
+        assert!(morton_encode(IVec2::new(3, -2)) < morton_encode(IVec2::new(3, 1)));
+
+        let center = IVec2::new(-1, 0);
+        let neighbors = morton_neighbors(morton_encode(center));
+        let expected: Vec<u64> = [
+            (-2, -1),
+            (-1, -1),
+            (0, -1),
+            (-2, 0),
+            (0, 0),
+            (-2, 1),
+            (-1, 1),
+            (0, 1),
+        ]
+        .into_iter()
+        .map(|(x, z)| morton_encode(IVec2::new(x, z)))
+        .collect();
+        assert_eq!(neighbors.to_vec(), expected);
+    }
+
+    #[test]
+    fn test_z_order_walk_covers_box_once_in_code_order() {
+        let (min, max) = (IVec2::new(-3, -2), IVec2::new(4, 2));
+        let walked: Vec<IVec2> = ZOrderCells::new(min, max).collect();
+
+        let mut expected: Vec<IVec2> = (min.y..=max.y)
+            .flat_map(|z| (min.x..=max.x).map(move |x| IVec2::new(x, z)))
+            .collect();
+        expected.sort_by_key(|cell| morton_encode(*cell));
+        assert_eq!(walked, expected);
+
+        let range = morton_box_range(min, max);
+        let outside = morton_encode(IVec2::new(5, -2));
+        assert!(range.contains(&outside));
+        assert!(!morton_in_box(outside, &range));
+        assert_eq!(ZOrderCells::new(max, max).count(), 1);
+    }
+}
@@ -0,0 +1,20 @@
+//! Tracing spans for profiling builds.
+//!
+//! Built with `--features profiling`, Bevy records a span per system and
+//! writes them as chrome://tracing JSON (`profiling-tracy` also streams them to
+//! Tracy). `profile_scope!` adds finer spans inside the heavy systems: chunk
+//! generation per layer, instance uploads per chunk, HLOD bakes and culling
+//! passes. Without the feature it expands to nothing.
+//!
+//! `cargo xtask profile` builds with the feature, runs the game and keeps the
+//! capture under `target/profiles`.
+
+/// Opens an info-level span that lasts until the end of the enclosing block.
+/// Takes the same arguments as `info_span!`.
+#[macro_export]
+macro_rules! profile_scope {
+    ($($span:tt)+) => {
+        #[cfg(feature = "profiling")]
+        let _profile_scope = ::bevy::log::info_span!($($span)+).entered();
+    };
+}
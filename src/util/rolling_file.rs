@@ -0,0 +1,109 @@
+//! Append-only text file that rolls over by size.
+//!
+//! `name` is the live file; older generations are kept as `name.1`,
+//! `name.2`, ... up to `max_files` in total. Opening rolls the previous
+//! session's file over, so each run starts fresh. An optional header line
+//! starts every generation, which keeps rotated CSV files self-describing.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub struct RollingFile {
+    path: PathBuf,
+    header: Option<String>,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    written: u64,
+}
+
+fn generation(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_path_buf();
+    }
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{index}"));
+    path.with_file_name(name)
+}
+
+fn shift_generations(path: &Path, max_files: usize) {
+    for index in (1..max_files).rev() {
+        let from = generation(path, index - 1);
+        if from.exists() {
+            let _ = fs::rename(from, generation(path, index));
+        }
+    }
+}
+
+impl RollingFile {
+    pub fn open(
+        dir: &Path,
+        name: &str,
+        header: Option<&str>,
+        max_bytes: u64,
+        max_files: usize,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(name);
+        shift_generations(&path, max_files);
+        let mut file = Self {
+            file: File::create(&path)?,
+            path,
+            header: header.map(str::to_string),
+            max_bytes,
+            max_files,
+            written: 0,
+        };
+        file.write_header()?;
+        Ok(file)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if let Some(header) = &self.header {
+            writeln!(self.file, "{header}")?;
+            self.written = header.len() as u64 + 1;
+        }
+        Ok(())
+    }
+
+    /// Appends `line` and a newline, rolling over once the size limit is reached
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.file, "{line}")?;
+        self.written += line.len() as u64 + 1;
+        if self.written >= self.max_bytes {
+            shift_generations(&self.path, self.max_files);
+            self.file = File::create(&self.path)?;
+            self.written = 0;
+            self.write_header()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolls_over_and_repeats_header() {
+        let dir = std::env::temp_dir().join(format!("gta_game_rolling_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut file = RollingFile::open(&dir, "metrics.csv", Some("a,b"), 12, 3).unwrap();
+        for row in ["1,2", "3,4", "5,6", "7,8", "9,10"] {
+            file.write_line(row).unwrap();
+        }
+
+        let current = fs::read_to_string(dir.join("metrics.csv")).unwrap();
+        let oldest = fs::read_to_string(dir.join("metrics.csv.2")).unwrap();
+        assert!(current.starts_with("a,b\n"));
+        assert!(oldest.starts_with("a,b\n"));
+        assert!(!dir.join("metrics.csv.3").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,57 @@
+//! "Did you mean" suggestions for misspelled names.
+//!
+//! Shared by prefab validation and config schema checks, so a typo gets the
+//! same suggestion whichever file it was made in.
+
+/// Candidate within a third of `name`'s length in edits (at least 2), ignoring case
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let limit = (name.chars().count() / 3).max(2);
+    let name = name.to_lowercase();
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(&name, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance over chars
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_match_allows_a_third_of_the_name_in_edits() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(closest_match("Mas", ["Mass", "Velocity"]), Some("Mass"));
+        assert_eq!(closest_match("DISTANSE", ["distance"]), Some("distance"));
+        // 2 edits is the floor, 12 chars allow 4
+        assert_eq!(closest_match("ab", ["xyz"]), None);
+        assert_eq!(
+            closest_match("max_speed_ms", ["max_spd"]),
+            None,
+            "5 edits is over budget"
+        );
+        assert_eq!(
+            closest_match("max_speed_ms", ["max_speed"]),
+            Some("max_speed")
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Project automation, run through the `cargo xtask` alias.
+//!
+//! `cargo xtask profile [--tracy] [-- <game args>]` builds the game in release
+//! with the `profiling` feature, runs it, and leaves the chrome://tracing JSON
+//! in `target/profiles`. With `--tracy` it builds `profiling-tracy` instead
+//! and, if `tracy-capture` is on the PATH, records a `.tracy` capture next to
+//! the JSON while the game runs.
+//!
+//! `cargo xtask bench [--save <name>] [--baseline <name>] [--threshold <pct>]`
+//! runs the `frame_systems` criterion benchmarks. `--save` records the run as
+//! a named baseline; `--baseline` compares against one and fails if any
+//! benchmark's mean got more than `--threshold` percent slower (default 10).
+//!
+//! `cargo xtask config-schema [--template]` prints a JSON Schema for the RON
+//! files in `assets/config`, or with `--template` their defaults as RON.
+//! `cargo xtask config-validate <path>...` checks config files for syntax
+//! errors, unknown fields and values the game would clamp. Both run the
+//! game's `config_tool` binary, which knows the config types.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "usage: cargo xtask profile [--tracy] [-- <game args>]
+       cargo xtask bench [--save <name>] [--baseline <name>] [--threshold <pct>]
+       cargo xtask config-schema [--template]
+       cargo xtask config-validate <path>...";
+
+/// Slowdown, in percent, past which `bench --baseline` fails
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("profile") => profile(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("config-schema") => config_tool("schema", &args[1..]),
+        Some("config-validate") if args.len() > 1 => config_tool("validate", &args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn profile(args: &[String]) -> Result<(), String> {
+    let (ours, game_args) = match args.iter().position(|arg| arg == "--") {
+        Some(split) => (&args[..split], &args[split + 1..]),
+        None => (args, &[][..]),
+    };
+    let mut tracy = false;
+    for arg in ours {
+        match arg.as_str() {
+            "--tracy" => tracy = true,
+            other => return Err(format!("unknown option `{other}`\n{USAGE}")),
+        }
+    }
+
+    let root = workspace_root();
+    let out_dir = root.join("target").join("profiles");
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("can't create {}: {e}", out_dir.display()))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let trace = out_dir.join(format!("trace-{stamp}.json"));
+
+    // tracy-capture waits for the game to connect and exits when it closes
+    let mut capture = None;
+    if tracy {
+        let capture_path = out_dir.join(format!("capture-{stamp}.tracy"));
+        match Command::new("tracy-capture")
+            .arg("-o")
+            .arg(&capture_path)
+            .arg("-f")
+            .spawn()
+        {
+            Ok(child) => capture = Some((child, capture_path)),
+            Err(e) => eprintln!(
+                "tracy-capture not started ({e}); connect the Tracy profiler to the game manually"
+            ),
+        }
+    }
+
+    let features = if tracy {
+        "profiling-tracy"
+    } else {
+        "profiling"
+    };
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .current_dir(&root)
+        .args([
+            "run",
+            "--release",
+            "--package",
+            "gta_game",
+            "--features",
+            features,
+            "--",
+        ])
+        .args(game_args)
+        .env("TRACE_CHROME", &trace)
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    if let Some((mut child, capture_path)) = capture {
+        match child.wait() {
+            Ok(_) => report("Tracy capture", &capture_path),
+            Err(e) => eprintln!("tracy-capture failed: {e}"),
+        }
+    }
+    report(
+        "Chrome trace (open in chrome://tracing or ui.perfetto.dev)",
+        &trace,
+    );
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("game exited with {status}"))
+    }
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("`{arg}` needs a value\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "--save" => save = Some(value()?),
+            "--baseline" => baseline = Some(value()?),
+            "--threshold" => {
+                threshold = value()?
+                    .parse()
+                    .map_err(|e| format!("bad --threshold: {e}"))?
+            }
+            other => return Err(format!("unknown option `{other}`\n{USAGE}")),
+        }
+    }
+
+    let root = workspace_root();
+    let criterion_dir = root.join("target").join("criterion");
+    // Stale change estimates from an earlier comparison would be re-read below
+    if baseline.is_some() {
+        remove_change_estimates(&criterion_dir);
+    }
+
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command.current_dir(&root).args([
+        "bench",
+        "--package",
+        "gta_game",
+        "--bench",
+        "frame_systems",
+        "--",
+    ]);
+    if let Some(name) = &save {
+        command.args(["--save-baseline", name]);
+    }
+    if let Some(name) = &baseline {
+        command.args(["--baseline", name]);
+    }
+    let status = command
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+    if !status.success() {
+        return Err(format!("benchmarks exited with {status}"));
+    }
+
+    let Some(baseline) = baseline else {
+        return Ok(());
+    };
+    let mut changes = Vec::new();
+    collect_changes(&criterion_dir, &criterion_dir, &mut changes);
+    if changes.is_empty() {
+        return Err(format!(
+            "no comparisons against baseline `{baseline}`; record it with --save {baseline}"
+        ));
+    }
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    println!("\nChange in mean time against `{baseline}`:");
+    let mut regressions = 0;
+    for (name, percent) in &changes {
+        let flag = if *percent > threshold {
+            regressions += 1;
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        println!("  {name:<48} {percent:>+7.2}%{flag}");
+    }
+    if regressions == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{regressions} benchmark(s) more than {threshold}% slower than `{baseline}`"
+        ))
+    }
+}
+
+/// Runs the game's `config_tool` binary. Paths are made absolute first,
+/// since cargo runs it from the workspace root.
+fn config_tool(command: &str, args: &[String]) -> Result<(), String> {
+    let cwd = env::current_dir().map_err(|e| format!("no current directory: {e}"))?;
+    let args = args.iter().map(|arg| {
+        if arg.starts_with("--") {
+            arg.clone()
+        } else {
+            cwd.join(arg).to_string_lossy().into_owned()
+        }
+    });
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .current_dir(workspace_root())
+        .args([
+            "run",
+            "--quiet",
+            "--package",
+            "gta_game",
+            "--bin",
+            "config_tool",
+            "--",
+            command,
+        ])
+        .args(args)
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("config_tool {command} exited with {status}"))
+    }
+}
+
+fn remove_change_estimates(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let _ = std::fs::remove_file(path.join("change").join("estimates.json"));
+            remove_change_estimates(&path);
+        }
+    }
+}
+
+/// Finds every `<bench id>/change/estimates.json` criterion wrote under `dir`
+/// and records the bench id with its mean change in percent
+fn collect_changes(root: &Path, dir: &Path, changes: &mut Vec<(String, f64)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if let Ok(json) = std::fs::read_to_string(path.join("change").join("estimates.json"))
+            && let Some(mean) = mean_point_estimate(&json)
+        {
+            let name = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            changes.push((name, mean * 100.0));
+        } else {
+            collect_changes(root, &path, changes);
+        }
+    }
+}
+
+/// Pulls `mean.point_estimate` out of a criterion estimates file. The layout
+/// is fixed, so a string search avoids pulling in a JSON parser.
+fn mean_point_estimate(json: &str) -> Option<f64> {
+    let mean = &json[json.find("\"mean\"")?..];
+    let value = &mean[mean.find("\"point_estimate\"")? + "\"point_estimate\"".len()..];
+    let value = value.trim_start().strip_prefix(':')?.trim_start();
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+fn report(what: &str, path: &Path) {
+    if path.exists() {
+        println!("{what}: {}", path.display());
+    } else {
+        eprintln!("{what}: nothing written to {}", path.display());
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_mean_change_from_criterion_estimates() {
+        let json = r#"{"mean":{"confidence_interval":{"confidence_level":0.95,"lower_bound":0.01,"upper_bound":0.2},"point_estimate":0.1234,"standard_error":0.01},"median":{"point_estimate":-0.5}}"#;
+        assert_eq!(mean_point_estimate(json), Some(0.1234));
+        assert_eq!(
+            mean_point_estimate(r#"{"median":{"point_estimate":1.0}}"#),
+            None
+        );
+    }
+}